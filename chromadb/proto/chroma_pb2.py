@@ -13,7 +13,7 @@ _sym_db = _symbol_database.Default()
 
 
 
-DESCRIPTOR = _descriptor_pool.Default().AddSerializedFile(b'\n\x1b\x63hromadb/proto/chroma.proto\x12\x06\x63hroma\"U\n\x06Vector\x12\x11\n\tdimension\x18\x01 \x01(\x05\x12\x0e\n\x06vector\x18\x02 \x01(\x0c\x12(\n\x08\x65ncoding\x18\x03 \x01(\x0e\x32\x16.chroma.ScalarEncoding\"\x1a\n\tFilePaths\x12\r\n\x05paths\x18\x01 \x03(\t\"\x91\x02\n\x07Segment\x12\n\n\x02id\x18\x01 \x01(\t\x12\x0c\n\x04type\x18\x02 \x01(\t\x12#\n\x05scope\x18\x03 \x01(\x0e\x32\x14.chroma.SegmentScope\x12\x12\n\ncollection\x18\x05 \x01(\t\x12-\n\x08metadata\x18\x06 \x01(\x0b\x32\x16.chroma.UpdateMetadataH\x00\x88\x01\x01\x12\x32\n\nfile_paths\x18\x07 \x03(\x0b\x32\x1e.chroma.Segment.FilePathsEntry\x1a\x43\n\x0e\x46ilePathsEntry\x12\x0b\n\x03key\x18\x01 \x01(\t\x12 \n\x05value\x18\x02 \x01(\x0b\x32\x11.chroma.FilePaths:\x02\x38\x01\x42\x0b\n\t_metadata\"\xf1\x01\n\nCollection\x12\n\n\x02id\x18\x01 \x01(\t\x12\x0c\n\x04name\x18\x02 \x01(\t\x12\x1e\n\x16\x63onfiguration_json_str\x18\x03 \x01(\t\x12-\n\x08metadata\x18\x04 \x01(\x0b\x32\x16.chroma.UpdateMetadataH\x00\x88\x01\x01\x12\x16\n\tdimension\x18\x05 \x01(\x05H\x01\x88\x01\x01\x12\x0e\n\x06tenant\x18\x06 \x01(\t\x12\x10\n\x08\x64\x61tabase\x18\x07 \x01(\t\x12\x14\n\x0clog_position\x18\x08 \x01(\x03\x12\x0f\n\x07version\x18\t \x01(\x05\x42\x0b\n\t_metadataB\x0c\n\n_dimension\"4\n\x08\x44\x61tabase\x12\n\n\x02id\x18\x01 \x01(\t\x12\x0c\n\x04name\x18\x02 \x01(\t\x12\x0e\n\x06tenant\x18\x03 \x01(\t\"\x16\n\x06Tenant\x12\x0c\n\x04name\x18\x01 \x01(\t\"x\n\x13UpdateMetadataValue\x12\x16\n\x0cstring_value\x18\x01 \x01(\tH\x00\x12\x13\n\tint_value\x18\x02 \x01(\x03H\x00\x12\x15\n\x0b\x66loat_value\x18\x03 \x01(\x01H\x00\x12\x14\n\nbool_value\x18\x04 \x01(\x08H\x00\x42\x07\n\x05value\"\x96\x01\n\x0eUpdateMetadata\x12\x36\n\x08metadata\x18\x01 \x03(\x0b\x32$.chroma.UpdateMetadata.MetadataEntry\x1aL\n\rMetadataEntry\x12\x0b\n\x03key\x18\x01 \x01(\t\x12*\n\x05value\x18\x02 \x01(\x0b\x32\x1b.chroma.UpdateMetadataValue:\x02\x38\x01\"\xaf\x01\n\x0fOperationRecord\x12\n\n\x02id\x18\x01 \x01(\t\x12#\n\x06vector\x18\x02 \x01(\x0b\x32\x0e.chroma.VectorH\x00\x88\x01\x01\x12-\n\x08metadata\x18\x03 \x01(\x0b\x32\x16.chroma.UpdateMetadataH\x01\x88\x01\x01\x12$\n\toperation\x18\x04 \x01(\x0e\x32\x11.chroma.OperationB\t\n\x07_vectorB\x0b\n\t_metadata\"I\n\x15RequestVersionContext\x12\x1a\n\x12\x63ollection_version\x18\x01 \x01(\r\x12\x14\n\x0clog_position\x18\x02 \x01(\x04\"x\n\x13\x43ountRecordsRequest\x12\x12\n\nsegment_id\x18\x01 \x01(\t\x12\x15\n\rcollection_id\x18\x02 \x01(\t\x12\x36\n\x0fversion_context\x18\x03 \x01(\x0b\x32\x1d.chroma.RequestVersionContext\"%\n\x14\x43ountRecordsResponse\x12\r\n\x05\x63ount\x18\x01 \x01(\r\"\xc9\x02\n\x14QueryMetadataRequest\x12\x12\n\nsegment_id\x18\x01 \x01(\t\x12\x1c\n\x05where\x18\x02 \x01(\x0b\x32\r.chroma.Where\x12-\n\x0ewhere_document\x18\x03 \x01(\x0b\x32\x15.chroma.WhereDocument\x12!\n\x03ids\x18\x04 \x01(\x0b\x32\x0f.chroma.UserIdsH\x00\x88\x01\x01\x12\x12\n\x05limit\x18\x05 \x01(\rH\x01\x88\x01\x01\x12\x13\n\x06offset\x18\x06 \x01(\rH\x02\x88\x01\x01\x12\x15\n\rcollection_id\x18\x07 \x01(\t\x12\x18\n\x10include_metadata\x18\x08 \x01(\x08\x12\x36\n\x0fversion_context\x18\t \x01(\x0b\x32\x1d.chroma.RequestVersionContextB\x06\n\x04_idsB\x08\n\x06_limitB\t\n\x07_offset\"I\n\x15QueryMetadataResponse\x12\x30\n\x07records\x18\x01 \x03(\x0b\x32\x1f.chroma.MetadataEmbeddingRecord\"O\n\x17MetadataEmbeddingRecord\x12\n\n\x02id\x18\x01 \x01(\t\x12(\n\x08metadata\x18\x02 \x01(\x0b\x32\x16.chroma.UpdateMetadata\"\x16\n\x07UserIds\x12\x0b\n\x03ids\x18\x01 \x03(\t\"\x83\x01\n\rWhereDocument\x12-\n\x06\x64irect\x18\x01 \x01(\x0b\x32\x1b.chroma.DirectWhereDocumentH\x00\x12\x31\n\x08\x63hildren\x18\x02 \x01(\x0b\x32\x1d.chroma.WhereDocumentChildrenH\x00\x42\x10\n\x0ewhere_document\"X\n\x13\x44irectWhereDocument\x12\x10\n\x08\x64ocument\x18\x01 \x01(\t\x12/\n\x08operator\x18\x02 \x01(\x0e\x32\x1d.chroma.WhereDocumentOperator\"k\n\x15WhereDocumentChildren\x12\'\n\x08\x63hildren\x18\x01 \x03(\x0b\x32\x15.chroma.WhereDocument\x12)\n\x08operator\x18\x02 \x01(\x0e\x32\x17.chroma.BooleanOperator\"r\n\x05Where\x12\x35\n\x11\x64irect_comparison\x18\x01 \x01(\x0b\x32\x18.chroma.DirectComparisonH\x00\x12)\n\x08\x63hildren\x18\x02 \x01(\x0b\x32\x15.chroma.WhereChildrenH\x00\x42\x07\n\x05where\"\x91\x04\n\x10\x44irectComparison\x12\x0b\n\x03key\x18\x01 \x01(\t\x12?\n\x15single_string_operand\x18\x02 \x01(\x0b\x32\x1e.chroma.SingleStringComparisonH\x00\x12;\n\x13string_list_operand\x18\x03 \x01(\x0b\x32\x1c.chroma.StringListComparisonH\x00\x12\x39\n\x12single_int_operand\x18\x04 \x01(\x0b\x32\x1b.chroma.SingleIntComparisonH\x00\x12\x35\n\x10int_list_operand\x18\x05 \x01(\x0b\x32\x19.chroma.IntListComparisonH\x00\x12?\n\x15single_double_operand\x18\x06 \x01(\x0b\x32\x1e.chroma.SingleDoubleComparisonH\x00\x12;\n\x13\x64ouble_list_operand\x18\x07 \x01(\x0b\x32\x1c.chroma.DoubleListComparisonH\x00\x12\x37\n\x11\x62ool_list_operand\x18\x08 \x01(\x0b\x32\x1a.chroma.BoolListComparisonH\x00\x12;\n\x13single_bool_operand\x18\t \x01(\x0b\x32\x1c.chroma.SingleBoolComparisonH\x00\x42\x0c\n\ncomparison\"[\n\rWhereChildren\x12\x1f\n\x08\x63hildren\x18\x01 \x03(\x0b\x32\r.chroma.Where\x12)\n\x08operator\x18\x02 \x01(\x0e\x32\x17.chroma.BooleanOperator\"S\n\x14StringListComparison\x12\x0e\n\x06values\x18\x01 \x03(\t\x12+\n\rlist_operator\x18\x02 \x01(\x0e\x32\x14.chroma.ListOperator\"V\n\x16SingleStringComparison\x12\r\n\x05value\x18\x01 \x01(\t\x12-\n\ncomparator\x18\x02 \x01(\x0e\x32\x19.chroma.GenericComparator\"T\n\x14SingleBoolComparison\x12\r\n\x05value\x18\x01 \x01(\x08\x12-\n\ncomparator\x18\x02 \x01(\x0e\x32\x19.chroma.GenericComparator\"P\n\x11IntListComparison\x12\x0e\n\x06values\x18\x01 \x03(\x03\x12+\n\rlist_operator\x18\x02 \x01(\x0e\x32\x14.chroma.ListOperator\"\xa2\x01\n\x13SingleIntComparison\x12\r\n\x05value\x18\x01 \x01(\x03\x12\x37\n\x12generic_comparator\x18\x02 \x01(\x0e\x32\x19.chroma.GenericComparatorH\x00\x12\x35\n\x11number_comparator\x18\x03 \x01(\x0e\x32\x18.chroma.NumberComparatorH\x00\x42\x0c\n\ncomparator\"S\n\x14\x44oubleListComparison\x12\x0e\n\x06values\x18\x01 \x03(\x01\x12+\n\rlist_operator\x18\x02 \x01(\x0e\x32\x14.chroma.ListOperator\"Q\n\x12\x42oolListComparison\x12\x0e\n\x06values\x18\x01 \x03(\x08\x12+\n\rlist_operator\x18\x02 \x01(\x0e\x32\x14.chroma.ListOperator\"\xa5\x01\n\x16SingleDoubleComparison\x12\r\n\x05value\x18\x01 \x01(\x01\x12\x37\n\x12generic_comparator\x18\x02 \x01(\x0e\x32\x19.chroma.GenericComparatorH\x00\x12\x35\n\x11number_comparator\x18\x03 \x01(\x0e\x32\x18.chroma.NumberComparatorH\x00\x42\x0c\n\ncomparator\"\x83\x01\n\x11GetVectorsRequest\x12\x0b\n\x03ids\x18\x01 \x03(\t\x12\x12\n\nsegment_id\x18\x02 \x01(\t\x12\x15\n\rcollection_id\x18\x03 \x01(\t\x12\x36\n\x0fversion_context\x18\x04 \x01(\x0b\x32\x1d.chroma.RequestVersionContext\"D\n\x12GetVectorsResponse\x12.\n\x07records\x18\x01 \x03(\x0b\x32\x1d.chroma.VectorEmbeddingRecord\"C\n\x15VectorEmbeddingRecord\x12\n\n\x02id\x18\x01 \x01(\t\x12\x1e\n\x06vector\x18\x03 \x01(\x0b\x32\x0e.chroma.Vector\"\xd5\x01\n\x13QueryVectorsRequest\x12\x1f\n\x07vectors\x18\x01 \x03(\x0b\x32\x0e.chroma.Vector\x12\t\n\x01k\x18\x02 \x01(\x05\x12\x13\n\x0b\x61llowed_ids\x18\x03 \x03(\t\x12\x1a\n\x12include_embeddings\x18\x04 \x01(\x08\x12\x12\n\nsegment_id\x18\x05 \x01(\t\x12\x15\n\rcollection_id\x18\x06 \x01(\t\x12\x36\n\x0fversion_context\x18\x07 \x01(\x0b\x32\x1d.chroma.RequestVersionContext\"C\n\x14QueryVectorsResponse\x12+\n\x07results\x18\x01 \x03(\x0b\x32\x1a.chroma.VectorQueryResults\"@\n\x12VectorQueryResults\x12*\n\x07results\x18\x01 \x03(\x0b\x32\x19.chroma.VectorQueryResult\"a\n\x11VectorQueryResult\x12\n\n\x02id\x18\x01 \x01(\t\x12\x10\n\x08\x64istance\x18\x03 \x01(\x02\x12#\n\x06vector\x18\x04 \x01(\x0b\x32\x0e.chroma.VectorH\x00\x88\x01\x01\x42\t\n\x07_vector*8\n\tOperation\x12\x07\n\x03\x41\x44\x44\x10\x00\x12\n\n\x06UPDATE\x10\x01\x12\n\n\x06UPSERT\x10\x02\x12\n\n\x06\x44\x45LETE\x10\x03*(\n\x0eScalarEncoding\x12\x0b\n\x07\x46LOAT32\x10\x00\x12\t\n\x05INT32\x10\x01*@\n\x0cSegmentScope\x12\n\n\x06VECTOR\x10\x00\x12\x0c\n\x08METADATA\x10\x01\x12\n\n\x06RECORD\x10\x02\x12\n\n\x06SQLITE\x10\x03*7\n\x15WhereDocumentOperator\x12\x0c\n\x08\x43ONTAINS\x10\x00\x12\x10\n\x0cNOT_CONTAINS\x10\x01*\"\n\x0f\x42ooleanOperator\x12\x07\n\x03\x41ND\x10\x00\x12\x06\n\x02OR\x10\x01*\x1f\n\x0cListOperator\x12\x06\n\x02IN\x10\x00\x12\x07\n\x03NIN\x10\x01*#\n\x11GenericComparator\x12\x06\n\x02\x45Q\x10\x00\x12\x06\n\x02NE\x10\x01*4\n\x10NumberComparator\x12\x06\n\x02GT\x10\x00\x12\x07\n\x03GTE\x10\x01\x12\x06\n\x02LT\x10\x02\x12\x07\n\x03LTE\x10\x03\x32\xad\x01\n\x0eMetadataReader\x12N\n\rQueryMetadata\x12\x1c.chroma.QueryMetadataRequest\x1a\x1d.chroma.QueryMetadataResponse\"\x00\x12K\n\x0c\x43ountRecords\x12\x1b.chroma.CountRecordsRequest\x1a\x1c.chroma.CountRecordsResponse\"\x00\x32\xa2\x01\n\x0cVectorReader\x12\x45\n\nGetVectors\x12\x19.chroma.GetVectorsRequest\x1a\x1a.chroma.GetVectorsResponse\"\x00\x12K\n\x0cQueryVectors\x12\x1b.chroma.QueryVectorsRequest\x1a\x1c.chroma.QueryVectorsResponse\"\x00\x42:Z8github.com/chroma-core/chroma/go/pkg/proto/coordinatorpbb\x06proto3')
+DESCRIPTOR = _descriptor_pool.Default().AddSerializedFile(b'\n\x1b\x63hromadb/proto/chroma.proto\x12\x06\x63hroma\"U\n\x06Vector\x12\x11\n\tdimension\x18\x01 \x01(\x05\x12\x0e\n\x06vector\x18\x02 \x01(\x0c\x12(\n\x08\x65ncoding\x18\x03 \x01(\x0e\x32\x16.chroma.ScalarEncoding\"\x1a\n\tFilePaths\x12\r\n\x05paths\x18\x01 \x03(\t\"\x91\x02\n\x07Segment\x12\n\n\x02id\x18\x01 \x01(\t\x12\x0c\n\x04type\x18\x02 \x01(\t\x12#\n\x05scope\x18\x03 \x01(\x0e\x32\x14.chroma.SegmentScope\x12\x12\n\ncollection\x18\x05 \x01(\t\x12-\n\x08metadata\x18\x06 \x01(\x0b\x32\x16.chroma.UpdateMetadataH\x00\x88\x01\x01\x12\x32\n\nfile_paths\x18\x07 \x03(\x0b\x32\x1e.chroma.Segment.FilePathsEntry\x1a\x43\n\x0e\x46ilePathsEntry\x12\x0b\n\x03key\x18\x01 \x01(\t\x12 \n\x05value\x18\x02 \x01(\x0b\x32\x11.chroma.FilePaths:\x02\x38\x01\x42\x0b\n\t_metadata\"\xf1\x01\n\nCollection\x12\n\n\x02id\x18\x01 \x01(\t\x12\x0c\n\x04name\x18\x02 \x01(\t\x12\x1e\n\x16\x63onfiguration_json_str\x18\x03 \x01(\t\x12-\n\x08metadata\x18\x04 \x01(\x0b\x32\x16.chroma.UpdateMetadataH\x00\x88\x01\x01\x12\x16\n\tdimension\x18\x05 \x01(\x05H\x01\x88\x01\x01\x12\x0e\n\x06tenant\x18\x06 \x01(\t\x12\x10\n\x08\x64\x61tabase\x18\x07 \x01(\t\x12\x14\n\x0clog_position\x18\x08 \x01(\x03\x12\x0f\n\x07version\x18\t \x01(\x05\x42\x0b\n\t_metadataB\x0c\n\n_dimension\"4\n\x08\x44\x61tabase\x12\n\n\x02id\x18\x01 \x01(\t\x12\x0c\n\x04name\x18\x02 \x01(\t\x12\x0e\n\x06tenant\x18\x03 \x01(\t\">\n\x06Tenant\x12\x0c\n\x04name\x18\x01 \x01(\t\x12\x17\n\nkms_key_id\x18\x02 \x01(\tH\x00\x88\x01\x01\x42\r\n\x0b_kms_key_id\"\xef\x01\n\x13UpdateMetadataValue\x12\x16\n\x0cstring_value\x18\x01 \x01(\tH\x00\x12\x13\n\tint_value\x18\x02 \x01(\x03H\x00\x12\x15\n\x0b\x66loat_value\x18\x03 \x01(\x01H\x00\x12\x14\n\nbool_value\x18\x04 \x01(\x08H\x00\x12*\n\nlist_value\x18\x05 \x01(\x0b\x32\x14.chroma.MetadataListH\x00\x12\x19\n\x0ftimestamp_value\x18\x06 \x01(\x03H\x00\x12.\n\x0cobject_value\x18\x07 \x01(\x0b\x32\x16.chroma.MetadataObjectH\x00\x42\x07\n\x05value\";\n\x0cMetadataList\x12+\n\x06values\x18\x01 \x03(\x0b\x32\x1b.chroma.UpdateMetadataValue\"\x90\x01\n\x0eMetadataObject\x12\x32\n\x06values\x18\x01 \x03(\x0b\x32\".chroma.MetadataObject.ValuesEntry\x1aJ\n\x0bValuesEntry\x12\x0b\n\x03key\x18\x01 \x01(\t\x12*\n\x05value\x18\x02 \x01(\x0b\x32\x1b.chroma.UpdateMetadataValue:\x02\x38\x01\"\x96\x01\n\x0eUpdateMetadata\x12\x36\n\x08metadata\x18\x01 \x03(\x0b\x32$.chroma.UpdateMetadata.MetadataEntry\x1aL\n\rMetadataEntry\x12\x0b\n\x03key\x18\x01 \x01(\t\x12*\n\x05value\x18\x02 \x01(\x0b\x32\x1b.chroma.UpdateMetadataValue:\x02\x38\x01\">\n\x0fVectorReference\x12\x0b\n\x03uri\x18\x01 \x01(\t\x12\x0b\n\x03row\x18\x02 \x01(\r\x12\x11\n\tdimension\x18\x03 \x01(\x05\"\xfc\x01\n\x0fOperationRecord\x12\n\n\x02id\x18\x01 \x01(\t\x12#\n\x06vector\x18\x02 \x01(\x0b\x32\x0e.chroma.VectorH\x00\x88\x01\x01\x12-\n\x08metadata\x18\x03 \x01(\x0b\x32\x16.chroma.UpdateMetadataH\x01\x88\x01\x01\x12$\n\toperation\x18\x04 \x01(\x0e\x32\x11.chroma.Operation\x12\x36\n\x10vector_reference\x18\x05 \x01(\x0b\x32\x17.chroma.VectorReferenceH\x02\x88\x01\x01\x42\t\n\x07_vectorB\x0b\n\t_metadataB\x13\n\x11_vector_reference\"{\n\x15RequestVersionContext\x12\x1a\n\x12\x63ollection_version\x18\x01 \x01(\r\x12\x14\n\x0clog_position\x18\x02 \x01(\x04\x12\x1c\n\x0fmax_log_backlog\x18\x03 \x01(\rH\x00\x88\x01\x01\x42\x12\n\x10_max_log_backlog\"\x9f\x01\n\x0c\x45rrorDetails\x12\x11\n\tretryable\x18\x01 \x01(\x08\x12\x12\n\nuser_error\x18\x02 \x01(\x08\x12\x12\n\x05\x66ield\x18\x03 \x01(\tH\x00\x88\x01\x01\x12\x17\n\nsegment_id\x18\x04 \x01(\tH\x01\x88\x01\x01\x12\x15\n\x08\x62lock_id\x18\x05 \x01(\tH\x02\x88\x01\x01\x42\x08\n\x06_fieldB\r\n\x0b_segment_idB\x0b\n\t_block_id\"\xb4\x01\n\x14StreamChangesRequest\x12\x15\n\rcollection_id\x18\x01 \x01(\t\x12\x36\n\x0fversion_context\x18\x02 \x01(\x0b\x32\x1d.chroma.RequestVersionContext\x12\x18\n\x10start_log_offset\x18\x03 \x01(\x04\x12\x1a\n\x12include_embeddings\x18\x04 \x01(\x08\x12\x17\n\x0f\x64\x65\x66\x61ult_include\x18\x05 \x01(\x08\"e\n\x0cRecordChange\x12$\n\toperation\x18\x01 \x01(\x0e\x32\x11.chroma.Operation\x12/\n\x06record\x18\x02 \x01(\x0b\x32\x1f.chroma.MetadataEmbeddingRecord\"R\n\x15StreamChangesResponse\x12%\n\x07\x63hanges\x18\x01 \x03(\x0b\x32\x14.chroma.RecordChange\x12\x12\n\nlog_offset\x18\x02 \x01(\x04\"x\n\x13\x43ountRecordsRequest\x12\x12\n\nsegment_id\x18\x01 \x01(\t\x12\x15\n\rcollection_id\x18\x02 \x01(\t\x12\x36\n\x0fversion_context\x18\x03 \x01(\x0b\x32\x1d.chroma.RequestVersionContext\"%\n\x14\x43ountRecordsResponse\x12\r\n\x05\x63ount\x18\x01 \x01(\r\"\xf9\x03\n\x14QueryMetadataRequest\x12\x12\n\nsegment_id\x18\x01 \x01(\t\x12\x1c\n\x05where\x18\x02 \x01(\x0b\x32\r.chroma.Where\x12-\n\x0ewhere_document\x18\x03 \x01(\x0b\x32\x15.chroma.WhereDocument\x12!\n\x03ids\x18\x04 \x01(\x0b\x32\x0f.chroma.UserIdsH\x00\x88\x01\x01\x12\x12\n\x05limit\x18\x05 \x01(\rH\x01\x88\x01\x01\x12\x13\n\x06offset\x18\x06 \x01(\rH\x02\x88\x01\x01\x12\x15\n\rcollection_id\x18\x07 \x01(\t\x12\x18\n\x10include_metadata\x18\x08 \x01(\x08\x12\x36\n\x0fversion_context\x18\t \x01(\x0b\x32\x1d.chroma.RequestVersionContext\x12\x1a\n\x12include_embeddings\x18\n \x01(\x08\x12 \n\x13max_embedding_bytes\x18\x0b \x01(\x04H\x03\x88\x01\x01\x12\x0f\n\x07\x65xplain\x18\x0c \x01(\x08\x12#\n\x06lookup\x18\r \x01(\x0b\x32\x0e.chroma.LookupH\x04\x88\x01\x01\x12\x17\n\x0f\x64\x65\x66\x61ult_include\x18\x0e \x01(\x08\x42\x06\n\x04_idsB\x08\n\x06_limitB\t\n\x07_offsetB\x16\n\x14_max_embedding_bytesB\t\n\x07_lookup\"\xc6\x01\n\x06Lookup\x12\x15\n\rcollection_id\x18\x01 \x01(\t\x12\x36\n\x0fversion_context\x18\x02 \x01(\x0b\x32\x1d.chroma.RequestVersionContext\x12\x13\n\x06id_key\x18\x03 \x01(\tH\x00\x88\x01\x01\x12\x18\n\x10include_metadata\x18\x04 \x01(\x08\x12\x1a\n\x12include_embeddings\x18\x05 \x01(\x08\x12\x17\n\x0f\x64\x65\x66\x61ult_include\x18\x06 \x01(\x08\x42\t\n\x07_id_key\"\x99\x02\n\x15QueryMetadataResponse\x12\x30\n\x07records\x18\x01 \x03(\x0b\x32\x1f.chroma.MetadataEmbeddingRecord\x12$\n\x04plan\x18\x02 \x01(\x0b\x32\x11.chroma.QueryPlanH\x00\x88\x01\x01\x12H\n\x0elookup_records\x18\x03 \x03(\x0b\x32\x30.chroma.QueryMetadataResponse.LookupRecordsEntry\x1aU\n\x12LookupRecordsEntry\x12\x0b\n\x03key\x18\x01 \x01(\t\x12.\n\x05value\x18\x02 \x01(\x0b\x32\x1f.chroma.MetadataEmbeddingRecord:\x02\x38\x01\x42\x07\n\x05_plan\"\x95\x01\n\tQueryPlan\x12\x31\n\x08strategy\x18\x01 \x03(\x0b\x32\x1f.chroma.QueryPlan.StrategyEntry\x12$\n\x05nodes\x18\x02 \x03(\x0b\x32\x15.chroma.QueryPlanNode\x1a/\n\rStrategyEntry\x12\x0b\n\x03key\x18\x01 \x01(\t\x12\r\n\x05value\x18\x02 \x01(\t:\x02\x38\x01\"\xf9\x01\n\rQueryPlanNode\x12\x10\n\x08operator\x18\x01 \x01(\t\x12\x0e\n\x06inputs\x18\x02 \x03(\r\x12\x1b\n\x0e\x65stimated_rows\x18\x03 \x01(\x04H\x00\x88\x01\x01\x12\x18\n\x0boutput_rows\x18\x04 \x01(\x04H\x01\x88\x01\x01\x12\x1b\n\x0e\x65lapsed_micros\x18\x05 \x01(\x04H\x02\x88\x01\x01\x12\x12\n\ncache_hits\x18\x06 \x01(\x04\x12\x14\n\x0c\x63\x61\x63he_misses\x18\x07 \x01(\x04\x12\x12\n\nbytes_read\x18\x08 \x01(\x04\x42\x11\n\x0f_estimated_rowsB\x0e\n\x0c_output_rowsB\x11\n\x0f_elapsed_micros\"\xd2\x01\n\x17MetadataEmbeddingRecord\x12\n\n\x02id\x18\x01 \x01(\t\x12(\n\x08metadata\x18\x02 \x01(\x0b\x32\x16.chroma.UpdateMetadata\x12&\n\tembedding\x18\x03 \x01(\x0b\x32\x0e.chroma.VectorH\x00\x88\x01\x01\x12\x36\n\x10\x65mbedding_handle\x18\x04 \x01(\x0b\x32\x17.chroma.EmbeddingHandleH\x01\x88\x01\x01\x42\x0c\n\n_embeddingB\x13\n\x11_embedding_handle\"\x84\x01\n\x0f\x45mbeddingHandle\x12\x11\n\toffset_id\x18\x01 \x01(\r\x12\x36\n\x0fversion_context\x18\x02 \x01(\x0b\x32\x1d.chroma.RequestVersionContext\x12\x17\n\nlog_offset\x18\x03 \x01(\x04H\x00\x88\x01\x01\x42\r\n\x0b_log_offset\"\xb5\x01\n\x14GetEmbeddingsRequest\x12\x12\n\nsegment_id\x18\x01 \x01(\t\x12\x15\n\rcollection_id\x18\x02 \x01(\t\x12\x36\n\x0fversion_context\x18\x03 \x01(\x0b\x32\x1d.chroma.RequestVersionContext\x12\x12\n\noffset_ids\x18\x04 \x03(\r\x12\x17\n\nlog_offset\x18\x05 \x01(\x04H\x00\x88\x01\x01\x42\r\n\x0b_log_offset\"G\n\x15GetEmbeddingsResponse\x12.\n\x07records\x18\x01 \x03(\x0b\x32\x1d.chroma.VectorEmbeddingRecord\"\x16\n\x07UserIds\x12\x0b\n\x03ids\x18\x01 \x03(\t\"\x83\x01\n\rWhereDocument\x12-\n\x06\x64irect\x18\x01 \x01(\x0b\x32\x1b.chroma.DirectWhereDocumentH\x00\x12\x31\n\x08\x63hildren\x18\x02 \x01(\x0b\x32\x1d.chroma.WhereDocumentChildrenH\x00\x42\x10\n\x0ewhere_document\"|\n\x13\x44irectWhereDocument\x12\x10\n\x08\x64ocument\x18\x01 \x01(\t\x12/\n\x08operator\x18\x02 \x01(\x0e\x32\x1d.chroma.WhereDocumentOperator\x12\x15\n\x08\x64istance\x18\x03 \x01(\rH\x00\x88\x01\x01\x42\x0b\n\t_distance\"k\n\x15WhereDocumentChildren\x12\'\n\x08\x63hildren\x18\x01 \x03(\x0b\x32\x15.chroma.WhereDocument\x12)\n\x08operator\x18\x02 \x01(\x0e\x32\x17.chroma.BooleanOperator\"r\n\x05Where\x12\x35\n\x11\x64irect_comparison\x18\x01 \x01(\x0b\x32\x18.chroma.DirectComparisonH\x00\x12)\n\x08\x63hildren\x18\x02 \x01(\x0b\x32\x15.chroma.WhereChildrenH\x00\x42\x07\n\x05where\"\x92\x05\n\x10\x44irectComparison\x12\x0b\n\x03key\x18\x01 \x01(\t\x12?\n\x15single_string_operand\x18\x02 \x01(\x0b\x32\x1e.chroma.SingleStringComparisonH\x00\x12;\n\x13string_list_operand\x18\x03 \x01(\x0b\x32\x1c.chroma.StringListComparisonH\x00\x12\x39\n\x12single_int_operand\x18\x04 \x01(\x0b\x32\x1b.chroma.SingleIntComparisonH\x00\x12\x35\n\x10int_list_operand\x18\x05 \x01(\x0b\x32\x19.chroma.IntListComparisonH\x00\x12?\n\x15single_double_operand\x18\x06 \x01(\x0b\x32\x1e.chroma.SingleDoubleComparisonH\x00\x12;\n\x13\x64ouble_list_operand\x18\x07 \x01(\x0b\x32\x1c.chroma.DoubleListComparisonH\x00\x12\x37\n\x11\x62ool_list_operand\x18\x08 \x01(\x0b\x32\x1a.chroma.BoolListComparisonH\x00\x12;\n\x13single_bool_operand\x18\t \x01(\x0b\x32\x1c.chroma.SingleBoolComparisonH\x00\x12\x38\n\x11\x65xistence_operand\x18\n \x01(\x0b\x32\x1b.chroma.ExistenceComparisonH\x00\x12\x45\n\x18single_timestamp_operand\x18\x0b \x01(\x0b\x32!.chroma.SingleTimestampComparisonH\x00\x42\x0c\n\ncomparison\"[\n\rWhereChildren\x12\x1f\n\x08\x63hildren\x18\x01 \x03(\x0b\x32\r.chroma.Where\x12)\n\x08operator\x18\x02 \x01(\x0e\x32\x17.chroma.BooleanOperator\"S\n\x14StringListComparison\x12\x0e\n\x06values\x18\x01 \x03(\t\x12+\n\rlist_operator\x18\x02 \x01(\x0e\x32\x14.chroma.ListOperator\"B\n\x13\x45xistenceComparison\x12+\n\x08operator\x18\x01 \x01(\x0e\x32\x19.chroma.ExistenceOperator\"V\n\x16SingleStringComparison\x12\r\n\x05value\x18\x01 \x01(\t\x12-\n\ncomparator\x18\x02 \x01(\x0e\x32\x19.chroma.GenericComparator\"T\n\x14SingleBoolComparison\x12\r\n\x05value\x18\x01 \x01(\x08\x12-\n\ncomparator\x18\x02 \x01(\x0e\x32\x19.chroma.GenericComparator\"P\n\x11IntListComparison\x12\x0e\n\x06values\x18\x01 \x03(\x03\x12+\n\rlist_operator\x18\x02 \x01(\x0e\x32\x14.chroma.ListOperator\"\xa2\x01\n\x13SingleIntComparison\x12\r\n\x05value\x18\x01 \x01(\x03\x12\x37\n\x12generic_comparator\x18\x02 \x01(\x0e\x32\x19.chroma.GenericComparatorH\x00\x12\x35\n\x11number_comparator\x18\x03 \x01(\x0e\x32\x18.chroma.NumberComparatorH\x00\x42\x0c\n\ncomparator\"\xa8\x01\n\x19SingleTimestampComparison\x12\r\n\x05value\x18\x01 \x01(\x03\x12\x37\n\x12generic_comparator\x18\x02 \x01(\x0e\x32\x19.chroma.GenericComparatorH\x00\x12\x35\n\x11number_comparator\x18\x03 \x01(\x0e\x32\x18.chroma.NumberComparatorH\x00\x42\x0c\n\ncomparator\"S\n\x14\x44oubleListComparison\x12\x0e\n\x06values\x18\x01 \x03(\x01\x12+\n\rlist_operator\x18\x02 \x01(\x0e\x32\x14.chroma.ListOperator\"Q\n\x12\x42oolListComparison\x12\x0e\n\x06values\x18\x01 \x03(\x08\x12+\n\rlist_operator\x18\x02 \x01(\x0e\x32\x14.chroma.ListOperator\"\xa5\x01\n\x16SingleDoubleComparison\x12\r\n\x05value\x18\x01 \x01(\x01\x12\x37\n\x12generic_comparator\x18\x02 \x01(\x0e\x32\x19.chroma.GenericComparatorH\x00\x12\x35\n\x11number_comparator\x18\x03 \x01(\x0e\x32\x18.chroma.NumberComparatorH\x00\x42\x0c\n\ncomparator\"h\n\x17WarmUpCollectionRequest\x12\x15\n\rcollection_id\x18\x01 \x01(\t\x12\x36\n\x0fversion_context\x18\x02 \x01(\x0b\x32\x1d.chroma.RequestVersionContext\"c\n\x18WarmUpCollectionResponse\x12\x12\n\nnum_blocks\x18\x01 \x01(\r\x12\x1b\n\x13vector_index_loaded\x18\x02 \x01(\x08\x12\x16\n\x0enum_hot_blocks\x18\x03 \x01(\r\"\x8b\x01\n\x1e\x45xportCollectionIndexesRequest\x12\x15\n\rcollection_id\x18\x01 \x01(\t\x12\x36\n\x0fversion_context\x18\x02 \x01(\x0b\x32\x1d.chroma.RequestVersionContext\x12\x1a\n\x12\x64\x65stination_prefix\x18\x03 \x01(\t\"P\n\x1f\x45xportCollectionIndexesResponse\x12\x12\n\nnum_tokens\x18\x01 \x01(\r\x12\x19\n\x11num_posting_lists\x18\x02 \x01(\r\"\x80\x01\n\x1f\x43heckCollectionIntegrityRequest\x12\x15\n\rcollection_id\x18\x01 \x01(\t\x12\x36\n\x0fversion_context\x18\x02 \x01(\x0b\x32\x1d.chroma.RequestVersionContext\x12\x0e\n\x06repair\x18\x03 \x01(\x08\"L\n\x0eIntegrityIssue\x12%\n\x05\x63heck\x18\x01 \x01(\x0e\x32\x16.chroma.IntegrityCheck\x12\x13\n\x0b\x64\x65scription\x18\x02 \x01(\t\"\xc8\x01\n CheckCollectionIntegrityResponse\x12\x13\n\x0bnum_records\x18\x01 \x01(\r\x12\x12\n\nnum_issues\x18\x02 \x01(\r\x12&\n\x06issues\x18\x03 \x03(\x0b\x32\x16.chroma.IntegrityIssue\x12\x1b\n\x13rebuilt_segment_ids\x18\x04 \x03(\t\x12\x1f\n\x12\x63ollection_version\x18\x05 \x01(\x05H\x00\x88\x01\x01\x42\x15\n\x13_collection_version\"p\n\x1fVerifyCollectionChecksumRequest\x12\x15\n\rcollection_id\x18\x01 \x01(\t\x12\x36\n\x0fversion_context\x18\x02 \x01(\x0b\x32\x1d.chroma.RequestVersionContext\"\x95\x01\n VerifyCollectionChecksumResponse\x12\x1c\n\x0fstored_checksum\x18\x01 \x01(\tH\x00\x88\x01\x01\x12\x19\n\x11\x63omputed_checksum\x18\x02 \x01(\t\x12\x13\n\x0bnum_records\x18\x03 \x01(\x04\x12\x0f\n\x07matches\x18\x04 \x01(\x08\x42\x12\n\x10_stored_checksum\"F\n\x16ListSlowQueriesRequest\x12\x1a\n\rcollection_id\x18\x01 \x01(\tH\x00\x88\x01\x01\x42\x10\n\x0e_collection_id\"\xf3\x01\n\tSlowQuery\x12\x15\n\rcollection_id\x18\x01 \x01(\t\x12\x12\n\nquery_type\x18\x02 \x01(\t\x12\x18\n\x10\x66ilter_operators\x18\x03 \x03(\t\x12\x14\n\x07num_ids\x18\x04 \x01(\x04H\x00\x88\x01\x01\x12\x12\n\x05limit\x18\x05 \x01(\rH\x01\x88\x01\x01\x12\x0e\n\x06offset\x18\x06 \x01(\r\x12\x16\n\x0e\x65lapsed_micros\x18\x07 \x01(\x04\x12\x18\n\x10logged_at_micros\x18\x08 \x01(\x04\x12\x1f\n\x04plan\x18\t \x01(\x0b\x32\x11.chroma.QueryPlanB\n\n\x08_num_idsB\x08\n\x06_limit\"=\n\x17ListSlowQueriesResponse\x12\"\n\x07queries\x18\x01 \x03(\x0b\x32\x11.chroma.SlowQuery\"(\n\x17ListCachedBlocksRequest\x12\r\n\x05limit\x18\x01 \x01(\r\"a\n\x0b\x43\x61\x63hedBlock\x12\x10\n\x08\x62lock_id\x18\x01 \x01(\t\x12\x12\n\nsize_bytes\x18\x02 \x01(\x04\x12\x1a\n\rcollection_id\x18\x03 \x01(\tH\x00\x88\x01\x01\x42\x10\n\x0e_collection_id\"\x84\x01\n\x18ListCachedBlocksResponse\x12#\n\x06\x62locks\x18\x01 \x03(\x0b\x32\x13.chroma.CachedBlock\x12\x13\n\x0busage_bytes\x18\x02 \x01(\x04\x12\x1b\n\x0e\x63\x61pacity_bytes\x18\x03 \x01(\x04H\x00\x88\x01\x01\x42\x11\n\x0f_capacity_bytes\"/\n\x16PauseCompactionRequest\x12\x15\n\rcollection_id\x18\x01 \x01(\t\"1\n\x17PauseCompactionResponse\x12\x16\n\x0e\x61lready_paused\x18\x01 \x01(\x08\"0\n\x17ResumeCompactionRequest\x12\x15\n\rcollection_id\x18\x01 \x01(\t\".\n\x18ResumeCompactionResponse\x12\x12\n\nwas_paused\x18\x01 \x01(\x08\"\x83\x01\n\x11GetVectorsRequest\x12\x0b\n\x03ids\x18\x01 \x03(\t\x12\x12\n\nsegment_id\x18\x02 \x01(\t\x12\x15\n\rcollection_id\x18\x03 \x01(\t\x12\x36\n\x0fversion_context\x18\x04 \x01(\x0b\x32\x1d.chroma.RequestVersionContext\"D\n\x12GetVectorsResponse\x12.\n\x07records\x18\x01 \x03(\x0b\x32\x1d.chroma.VectorEmbeddingRecord\"C\n\x15VectorEmbeddingRecord\x12\n\n\x02id\x18\x01 \x01(\t\x12\x1e\n\x06vector\x18\x03 \x01(\x0b\x32\x0e.chroma.Vector\"\x9a\x03\n\x13QueryVectorsRequest\x12\x1f\n\x07vectors\x18\x01 \x03(\x0b\x32\x0e.chroma.Vector\x12\t\n\x01k\x18\x02 \x01(\x05\x12\x13\n\x0b\x61llowed_ids\x18\x03 \x03(\t\x12\x1a\n\x12include_embeddings\x18\x04 \x01(\x08\x12\x12\n\nsegment_id\x18\x05 \x01(\t\x12\x15\n\rcollection_id\x18\x06 \x01(\t\x12\x36\n\x0fversion_context\x18\x07 \x01(\x0b\x32\x1d.chroma.RequestVersionContext\x12\x0f\n\x07\x65xplain\x18\x08 \x01(\x08\x12\x32\n\x0c\x61\x63\x63umulation\x18\t \x01(\x0e\x32\x1c.chroma.DistanceAccumulation\x12\x19\n\x0cmax_distance\x18\n \x01(\x02H\x00\x88\x01\x01\x12\x16\n\tmin_score\x18\x0b \x01(\x02H\x01\x88\x01\x01\x12\x1a\n\rtime_limit_ms\x18\x0c \x01(\rH\x02\x88\x01\x01\x42\x0f\n\r_max_distanceB\x0c\n\n_min_scoreB\x10\n\x0e_time_limit_ms\"\x97\x01\n\x14QueryVectorsResponse\x12+\n\x07results\x18\x01 \x03(\x0b\x32\x1a.chroma.VectorQueryResults\x12\x10\n\x08\x64\x65graded\x18\x02 \x01(\x08\x12$\n\x04plan\x18\x03 \x01(\x0b\x32\x11.chroma.QueryPlanH\x00\x88\x01\x01\x12\x11\n\ttruncated\x18\x04 \x01(\x08\x42\x07\n\x05_plan\"@\n\x12VectorQueryResults\x12*\n\x07results\x18\x01 \x03(\x0b\x32\x19.chroma.VectorQueryResult\"a\n\x11VectorQueryResult\x12\n\n\x02id\x18\x01 \x01(\t\x12\x10\n\x08\x64istance\x18\x03 \x01(\x02\x12#\n\x06vector\x18\x04 \x01(\x0b\x32\x0e.chroma.VectorH\x00\x88\x01\x01\x42\t\n\x07_vector\"E\n\x13SignedRoaringBitmap\x12\x11\n\x07include\x18\x01 \x01(\x0cH\x00\x12\x11\n\x07\x65xclude\x18\x02 \x01(\x0cH\x00\x42\x08\n\x06\x62itmap\"|\n\x0c\x46ilterOutput\x12\x33\n\x0elog_offset_ids\x18\x01 \x01(\x0b\x32\x1b.chroma.SignedRoaringBitmap\x12\x37\n\x12\x63ompact_offset_ids\x18\x02 \x01(\x0b\x32\x1b.chroma.SignedRoaringBitmap\"!\n\x0bLimitOutput\x12\x12\n\noffset_ids\x18\x01 \x01(\x0c*8\n\tOperation\x12\x07\n\x03\x41\x44\x44\x10\x00\x12\n\n\x06UPDATE\x10\x01\x12\n\n\x06UPSERT\x10\x02\x12\n\n\x06\x44\x45LETE\x10\x03*(\n\x0eScalarEncoding\x12\x0b\n\x07\x46LOAT32\x10\x00\x12\t\n\x05INT32\x10\x01*@\n\x0cSegmentScope\x12\n\n\x06VECTOR\x10\x00\x12\x0c\n\x08METADATA\x10\x01\x12\n\n\x06RECORD\x10\x02\x12\n\n\x06SQLITE\x10\x03*[\n\x15WhereDocumentOperator\x12\x0c\n\x08\x43ONTAINS\x10\x00\x12\x10\n\x0cNOT_CONTAINS\x10\x01\x12\x08\n\x04NEAR\x10\x02\x12\t\n\x05REGEX\x10\x03\x12\r\n\tNOT_REGEX\x10\x04*\"\n\x0f\x42ooleanOperator\x12\x07\n\x03\x41ND\x10\x00\x12\x06\n\x02OR\x10\x01*,\n\x11\x45xistenceOperator\x12\n\n\x06\x45XISTS\x10\x00\x12\x0b\n\x07IS_NULL\x10\x01*\x1f\n\x0cListOperator\x12\x06\n\x02IN\x10\x00\x12\x07\n\x03NIN\x10\x01*i\n\x11GenericComparator\x12\x06\n\x02\x45Q\x10\x00\x12\x06\n\x02NE\x10\x01\x12\x11\n\rLIST_CONTAINS\x10\x02\x12\x15\n\x11LIST_NOT_CONTAINS\x10\x03\x12\t\n\x05\x45Q_CI\x10\x04\x12\x0f\n\x0bSTARTS_WITH\x10\x05*4\n\x10NumberComparator\x12\x06\n\x02GT\x10\x00\x12\x07\n\x03GTE\x10\x01\x12\x06\n\x02LT\x10\x02\x12\x07\n\x03LTE\x10\x03*]\n\x0eIntegrityCheck\x12\x18\n\x14OFFSET_ID_CONTINUITY\x10\x00\x12\x16\n\x12METADATA_AGREEMENT\x10\x01\x12\x19\n\x15VECTOR_INDEX_COVERAGE\x10\x02*>\n\x14\x44istanceAccumulation\x12\x12\n\x0e\x41\x43\x43UMULATE_F32\x10\x00\x12\x12\n\x0e\x41\x43\x43UMULATE_F64\x10\x01\x32\xa2\x03\n\x0eMetadataReader\x12N\n\rQueryMetadata\x12\x1c.chroma.QueryMetadataRequest\x1a\x1d.chroma.QueryMetadataResponse\"\x00\x12K\n\x0c\x43ountRecords\x12\x1b.chroma.CountRecordsRequest\x1a\x1c.chroma.CountRecordsResponse\"\x00\x12N\n\rGetEmbeddings\x12\x1c.chroma.GetEmbeddingsRequest\x1a\x1d.chroma.GetEmbeddingsResponse\"\x00\x12Q\n\x0eStreamMetadata\x12\x1c.chroma.QueryMetadataRequest\x1a\x1d.chroma.QueryMetadataResponse\"\x00\x30\x01\x12P\n\rStreamChanges\x12\x1c.chroma.StreamChangesRequest\x1a\x1d.chroma.StreamChangesResponse\"\x00\x30\x01\x32\xe4\x04\n\nQueryAdmin\x12W\n\x10WarmUpCollection\x12\x1f.chroma.WarmUpCollectionRequest\x1a .chroma.WarmUpCollectionResponse\"\x00\x12l\n\x17\x45xportCollectionIndexes\x12&.chroma.ExportCollectionIndexesRequest\x1a\'.chroma.ExportCollectionIndexesResponse\"\x00\x12o\n\x18\x43heckCollectionIntegrity\x12\'.chroma.CheckCollectionIntegrityRequest\x1a(.chroma.CheckCollectionIntegrityResponse\"\x00\x12o\n\x18VerifyCollectionChecksum\x12\'.chroma.VerifyCollectionChecksumRequest\x1a(.chroma.VerifyCollectionChecksumResponse\"\x00\x12T\n\x0fListSlowQueries\x12\x1e.chroma.ListSlowQueriesRequest\x1a\x1f.chroma.ListSlowQueriesResponse\"\x00\x12W\n\x10ListCachedBlocks\x12\x1f.chroma.ListCachedBlocksRequest\x1a .chroma.ListCachedBlocksResponse\"\x00\x32\xc0\x01\n\x0f\x43ompactionAdmin\x12T\n\x0fPauseCompaction\x12\x1e.chroma.PauseCompactionRequest\x1a\x1f.chroma.PauseCompactionResponse\"\x00\x12W\n\x10ResumeCompaction\x12\x1f.chroma.ResumeCompactionRequest\x1a .chroma.ResumeCompactionResponse\"\x00\x32\xa2\x01\n\x0cVectorReader\x12\x45\n\nGetVectors\x12\x19.chroma.GetVectorsRequest\x1a\x1a.chroma.GetVectorsResponse\"\x00\x12K\n\x0cQueryVectors\x12\x1b.chroma.QueryVectorsRequest\x1a\x1c.chroma.QueryVectorsResponse\"\x00\x42:Z8github.com/chroma-core/chroma/go/pkg/proto/coordinatorpbb\x06proto3')
 
 _globals = globals()
 _builder.BuildMessageAndEnumDescriptors(DESCRIPTOR, _globals)
@@ -24,24 +24,36 @@ if _descriptor._USE_C_DESCRIPTORS == False:
   DESCRIPTOR._serialized_options = b'Z8github.com/chroma-core/chroma/go/pkg/proto/coordinatorpb'
   _SEGMENT_FILEPATHSENTRY._options = None
   _SEGMENT_FILEPATHSENTRY._serialized_options = b'8\001'
+  _METADATAOBJECT_VALUESENTRY._options = None
+  _METADATAOBJECT_VALUESENTRY._serialized_options = b'8\001'
   _UPDATEMETADATA_METADATAENTRY._options = None
   _UPDATEMETADATA_METADATAENTRY._serialized_options = b'8\001'
-  _globals['_OPERATION']._serialized_start=4592
-  _globals['_OPERATION']._serialized_end=4648
-  _globals['_SCALARENCODING']._serialized_start=4650
-  _globals['_SCALARENCODING']._serialized_end=4690
-  _globals['_SEGMENTSCOPE']._serialized_start=4692
-  _globals['_SEGMENTSCOPE']._serialized_end=4756
-  _globals['_WHEREDOCUMENTOPERATOR']._serialized_start=4758
-  _globals['_WHEREDOCUMENTOPERATOR']._serialized_end=4813
-  _globals['_BOOLEANOPERATOR']._serialized_start=4815
-  _globals['_BOOLEANOPERATOR']._serialized_end=4849
-  _globals['_LISTOPERATOR']._serialized_start=4851
-  _globals['_LISTOPERATOR']._serialized_end=4882
-  _globals['_GENERICCOMPARATOR']._serialized_start=4884
-  _globals['_GENERICCOMPARATOR']._serialized_end=4919
-  _globals['_NUMBERCOMPARATOR']._serialized_start=4921
-  _globals['_NUMBERCOMPARATOR']._serialized_end=4973
+  _QUERYMETADATARESPONSE_LOOKUPRECORDSENTRY._options = None
+  _QUERYMETADATARESPONSE_LOOKUPRECORDSENTRY._serialized_options = b'8\001'
+  _QUERYPLAN_STRATEGYENTRY._options = None
+  _QUERYPLAN_STRATEGYENTRY._serialized_options = b'8\001'
+  _globals['_OPERATION']._serialized_start=10079
+  _globals['_OPERATION']._serialized_end=10135
+  _globals['_SCALARENCODING']._serialized_start=10137
+  _globals['_SCALARENCODING']._serialized_end=10177
+  _globals['_SEGMENTSCOPE']._serialized_start=10179
+  _globals['_SEGMENTSCOPE']._serialized_end=10243
+  _globals['_WHEREDOCUMENTOPERATOR']._serialized_start=10245
+  _globals['_WHEREDOCUMENTOPERATOR']._serialized_end=10336
+  _globals['_BOOLEANOPERATOR']._serialized_start=10338
+  _globals['_BOOLEANOPERATOR']._serialized_end=10372
+  _globals['_EXISTENCEOPERATOR']._serialized_start=10374
+  _globals['_EXISTENCEOPERATOR']._serialized_end=10418
+  _globals['_LISTOPERATOR']._serialized_start=10420
+  _globals['_LISTOPERATOR']._serialized_end=10451
+  _globals['_GENERICCOMPARATOR']._serialized_start=10453
+  _globals['_GENERICCOMPARATOR']._serialized_end=10558
+  _globals['_NUMBERCOMPARATOR']._serialized_start=10560
+  _globals['_NUMBERCOMPARATOR']._serialized_end=10612
+  _globals['_INTEGRITYCHECK']._serialized_start=10614
+  _globals['_INTEGRITYCHECK']._serialized_end=10707
+  _globals['_DISTANCEACCUMULATION']._serialized_start=10709
+  _globals['_DISTANCEACCUMULATION']._serialized_end=10771
   _globals['_VECTOR']._serialized_start=39
   _globals['_VECTOR']._serialized_end=124
   _globals['_FILEPATHS']._serialized_start=126
@@ -55,73 +67,157 @@ if _descriptor._USE_C_DESCRIPTORS == False:
   _globals['_DATABASE']._serialized_start=674
   _globals['_DATABASE']._serialized_end=726
   _globals['_TENANT']._serialized_start=728
-  _globals['_TENANT']._serialized_end=750
-  _globals['_UPDATEMETADATAVALUE']._serialized_start=752
-  _globals['_UPDATEMETADATAVALUE']._serialized_end=872
-  _globals['_UPDATEMETADATA']._serialized_start=875
-  _globals['_UPDATEMETADATA']._serialized_end=1025
-  _globals['_UPDATEMETADATA_METADATAENTRY']._serialized_start=949
-  _globals['_UPDATEMETADATA_METADATAENTRY']._serialized_end=1025
-  _globals['_OPERATIONRECORD']._serialized_start=1028
-  _globals['_OPERATIONRECORD']._serialized_end=1203
-  _globals['_REQUESTVERSIONCONTEXT']._serialized_start=1205
-  _globals['_REQUESTVERSIONCONTEXT']._serialized_end=1278
-  _globals['_COUNTRECORDSREQUEST']._serialized_start=1280
-  _globals['_COUNTRECORDSREQUEST']._serialized_end=1400
-  _globals['_COUNTRECORDSRESPONSE']._serialized_start=1402
-  _globals['_COUNTRECORDSRESPONSE']._serialized_end=1439
-  _globals['_QUERYMETADATAREQUEST']._serialized_start=1442
-  _globals['_QUERYMETADATAREQUEST']._serialized_end=1771
-  _globals['_QUERYMETADATARESPONSE']._serialized_start=1773
-  _globals['_QUERYMETADATARESPONSE']._serialized_end=1846
-  _globals['_METADATAEMBEDDINGRECORD']._serialized_start=1848
-  _globals['_METADATAEMBEDDINGRECORD']._serialized_end=1927
-  _globals['_USERIDS']._serialized_start=1929
-  _globals['_USERIDS']._serialized_end=1951
-  _globals['_WHEREDOCUMENT']._serialized_start=1954
-  _globals['_WHEREDOCUMENT']._serialized_end=2085
-  _globals['_DIRECTWHEREDOCUMENT']._serialized_start=2087
-  _globals['_DIRECTWHEREDOCUMENT']._serialized_end=2175
-  _globals['_WHEREDOCUMENTCHILDREN']._serialized_start=2177
-  _globals['_WHEREDOCUMENTCHILDREN']._serialized_end=2284
-  _globals['_WHERE']._serialized_start=2286
-  _globals['_WHERE']._serialized_end=2400
-  _globals['_DIRECTCOMPARISON']._serialized_start=2403
-  _globals['_DIRECTCOMPARISON']._serialized_end=2932
-  _globals['_WHERECHILDREN']._serialized_start=2934
-  _globals['_WHERECHILDREN']._serialized_end=3025
-  _globals['_STRINGLISTCOMPARISON']._serialized_start=3027
-  _globals['_STRINGLISTCOMPARISON']._serialized_end=3110
-  _globals['_SINGLESTRINGCOMPARISON']._serialized_start=3112
-  _globals['_SINGLESTRINGCOMPARISON']._serialized_end=3198
-  _globals['_SINGLEBOOLCOMPARISON']._serialized_start=3200
-  _globals['_SINGLEBOOLCOMPARISON']._serialized_end=3284
-  _globals['_INTLISTCOMPARISON']._serialized_start=3286
-  _globals['_INTLISTCOMPARISON']._serialized_end=3366
-  _globals['_SINGLEINTCOMPARISON']._serialized_start=3369
-  _globals['_SINGLEINTCOMPARISON']._serialized_end=3531
-  _globals['_DOUBLELISTCOMPARISON']._serialized_start=3533
-  _globals['_DOUBLELISTCOMPARISON']._serialized_end=3616
-  _globals['_BOOLLISTCOMPARISON']._serialized_start=3618
-  _globals['_BOOLLISTCOMPARISON']._serialized_end=3699
-  _globals['_SINGLEDOUBLECOMPARISON']._serialized_start=3702
-  _globals['_SINGLEDOUBLECOMPARISON']._serialized_end=3867
-  _globals['_GETVECTORSREQUEST']._serialized_start=3870
-  _globals['_GETVECTORSREQUEST']._serialized_end=4001
-  _globals['_GETVECTORSRESPONSE']._serialized_start=4003
-  _globals['_GETVECTORSRESPONSE']._serialized_end=4071
-  _globals['_VECTOREMBEDDINGRECORD']._serialized_start=4073
-  _globals['_VECTOREMBEDDINGRECORD']._serialized_end=4140
-  _globals['_QUERYVECTORSREQUEST']._serialized_start=4143
-  _globals['_QUERYVECTORSREQUEST']._serialized_end=4356
-  _globals['_QUERYVECTORSRESPONSE']._serialized_start=4358
-  _globals['_QUERYVECTORSRESPONSE']._serialized_end=4425
-  _globals['_VECTORQUERYRESULTS']._serialized_start=4427
-  _globals['_VECTORQUERYRESULTS']._serialized_end=4491
-  _globals['_VECTORQUERYRESULT']._serialized_start=4493
-  _globals['_VECTORQUERYRESULT']._serialized_end=4590
-  _globals['_METADATAREADER']._serialized_start=4976
-  _globals['_METADATAREADER']._serialized_end=5149
-  _globals['_VECTORREADER']._serialized_start=5152
-  _globals['_VECTORREADER']._serialized_end=5314
+  _globals['_TENANT']._serialized_end=790
+  _globals['_UPDATEMETADATAVALUE']._serialized_start=793
+  _globals['_UPDATEMETADATAVALUE']._serialized_end=1032
+  _globals['_METADATALIST']._serialized_start=1034
+  _globals['_METADATALIST']._serialized_end=1093
+  _globals['_METADATAOBJECT']._serialized_start=1096
+  _globals['_METADATAOBJECT']._serialized_end=1240
+  _globals['_METADATAOBJECT_VALUESENTRY']._serialized_start=1166
+  _globals['_METADATAOBJECT_VALUESENTRY']._serialized_end=1240
+  _globals['_UPDATEMETADATA']._serialized_start=1243
+  _globals['_UPDATEMETADATA']._serialized_end=1393
+  _globals['_UPDATEMETADATA_METADATAENTRY']._serialized_start=1317
+  _globals['_UPDATEMETADATA_METADATAENTRY']._serialized_end=1393
+  _globals['_VECTORREFERENCE']._serialized_start=1395
+  _globals['_VECTORREFERENCE']._serialized_end=1457
+  _globals['_OPERATIONRECORD']._serialized_start=1460
+  _globals['_OPERATIONRECORD']._serialized_end=1712
+  _globals['_REQUESTVERSIONCONTEXT']._serialized_start=1714
+  _globals['_REQUESTVERSIONCONTEXT']._serialized_end=1837
+  _globals['_ERRORDETAILS']._serialized_start=1840
+  _globals['_ERRORDETAILS']._serialized_end=1999
+  _globals['_STREAMCHANGESREQUEST']._serialized_start=2002
+  _globals['_STREAMCHANGESREQUEST']._serialized_end=2182
+  _globals['_RECORDCHANGE']._serialized_start=2184
+  _globals['_RECORDCHANGE']._serialized_end=2285
+  _globals['_STREAMCHANGESRESPONSE']._serialized_start=2287
+  _globals['_STREAMCHANGESRESPONSE']._serialized_end=2369
+  _globals['_COUNTRECORDSREQUEST']._serialized_start=2371
+  _globals['_COUNTRECORDSREQUEST']._serialized_end=2491
+  _globals['_COUNTRECORDSRESPONSE']._serialized_start=2493
+  _globals['_COUNTRECORDSRESPONSE']._serialized_end=2530
+  _globals['_QUERYMETADATAREQUEST']._serialized_start=2533
+  _globals['_QUERYMETADATAREQUEST']._serialized_end=3038
+  _globals['_LOOKUP']._serialized_start=3041
+  _globals['_LOOKUP']._serialized_end=3239
+  _globals['_QUERYMETADATARESPONSE']._serialized_start=3242
+  _globals['_QUERYMETADATARESPONSE']._serialized_end=3523
+  _globals['_QUERYMETADATARESPONSE_LOOKUPRECORDSENTRY']._serialized_start=3429
+  _globals['_QUERYMETADATARESPONSE_LOOKUPRECORDSENTRY']._serialized_end=3514
+  _globals['_QUERYPLAN']._serialized_start=3526
+  _globals['_QUERYPLAN']._serialized_end=3675
+  _globals['_QUERYPLAN_STRATEGYENTRY']._serialized_start=3628
+  _globals['_QUERYPLAN_STRATEGYENTRY']._serialized_end=3675
+  _globals['_QUERYPLANNODE']._serialized_start=3678
+  _globals['_QUERYPLANNODE']._serialized_end=3927
+  _globals['_METADATAEMBEDDINGRECORD']._serialized_start=3930
+  _globals['_METADATAEMBEDDINGRECORD']._serialized_end=4140
+  _globals['_EMBEDDINGHANDLE']._serialized_start=4143
+  _globals['_EMBEDDINGHANDLE']._serialized_end=4275
+  _globals['_GETEMBEDDINGSREQUEST']._serialized_start=4278
+  _globals['_GETEMBEDDINGSREQUEST']._serialized_end=4459
+  _globals['_GETEMBEDDINGSRESPONSE']._serialized_start=4461
+  _globals['_GETEMBEDDINGSRESPONSE']._serialized_end=4532
+  _globals['_USERIDS']._serialized_start=4534
+  _globals['_USERIDS']._serialized_end=4556
+  _globals['_WHEREDOCUMENT']._serialized_start=4559
+  _globals['_WHEREDOCUMENT']._serialized_end=4690
+  _globals['_DIRECTWHEREDOCUMENT']._serialized_start=4692
+  _globals['_DIRECTWHEREDOCUMENT']._serialized_end=4816
+  _globals['_WHEREDOCUMENTCHILDREN']._serialized_start=4818
+  _globals['_WHEREDOCUMENTCHILDREN']._serialized_end=4925
+  _globals['_WHERE']._serialized_start=4927
+  _globals['_WHERE']._serialized_end=5041
+  _globals['_DIRECTCOMPARISON']._serialized_start=5044
+  _globals['_DIRECTCOMPARISON']._serialized_end=5702
+  _globals['_WHERECHILDREN']._serialized_start=5704
+  _globals['_WHERECHILDREN']._serialized_end=5795
+  _globals['_STRINGLISTCOMPARISON']._serialized_start=5797
+  _globals['_STRINGLISTCOMPARISON']._serialized_end=5880
+  _globals['_EXISTENCECOMPARISON']._serialized_start=5882
+  _globals['_EXISTENCECOMPARISON']._serialized_end=5948
+  _globals['_SINGLESTRINGCOMPARISON']._serialized_start=5950
+  _globals['_SINGLESTRINGCOMPARISON']._serialized_end=6036
+  _globals['_SINGLEBOOLCOMPARISON']._serialized_start=6038
+  _globals['_SINGLEBOOLCOMPARISON']._serialized_end=6122
+  _globals['_INTLISTCOMPARISON']._serialized_start=6124
+  _globals['_INTLISTCOMPARISON']._serialized_end=6204
+  _globals['_SINGLEINTCOMPARISON']._serialized_start=6207
+  _globals['_SINGLEINTCOMPARISON']._serialized_end=6369
+  _globals['_SINGLETIMESTAMPCOMPARISON']._serialized_start=6372
+  _globals['_SINGLETIMESTAMPCOMPARISON']._serialized_end=6540
+  _globals['_DOUBLELISTCOMPARISON']._serialized_start=6542
+  _globals['_DOUBLELISTCOMPARISON']._serialized_end=6625
+  _globals['_BOOLLISTCOMPARISON']._serialized_start=6627
+  _globals['_BOOLLISTCOMPARISON']._serialized_end=6708
+  _globals['_SINGLEDOUBLECOMPARISON']._serialized_start=6711
+  _globals['_SINGLEDOUBLECOMPARISON']._serialized_end=6876
+  _globals['_WARMUPCOLLECTIONREQUEST']._serialized_start=6878
+  _globals['_WARMUPCOLLECTIONREQUEST']._serialized_end=6982
+  _globals['_WARMUPCOLLECTIONRESPONSE']._serialized_start=6984
+  _globals['_WARMUPCOLLECTIONRESPONSE']._serialized_end=7083
+  _globals['_EXPORTCOLLECTIONINDEXESREQUEST']._serialized_start=7086
+  _globals['_EXPORTCOLLECTIONINDEXESREQUEST']._serialized_end=7225
+  _globals['_EXPORTCOLLECTIONINDEXESRESPONSE']._serialized_start=7227
+  _globals['_EXPORTCOLLECTIONINDEXESRESPONSE']._serialized_end=7307
+  _globals['_CHECKCOLLECTIONINTEGRITYREQUEST']._serialized_start=7310
+  _globals['_CHECKCOLLECTIONINTEGRITYREQUEST']._serialized_end=7438
+  _globals['_INTEGRITYISSUE']._serialized_start=7440
+  _globals['_INTEGRITYISSUE']._serialized_end=7516
+  _globals['_CHECKCOLLECTIONINTEGRITYRESPONSE']._serialized_start=7519
+  _globals['_CHECKCOLLECTIONINTEGRITYRESPONSE']._serialized_end=7719
+  _globals['_VERIFYCOLLECTIONCHECKSUMREQUEST']._serialized_start=7721
+  _globals['_VERIFYCOLLECTIONCHECKSUMREQUEST']._serialized_end=7833
+  _globals['_VERIFYCOLLECTIONCHECKSUMRESPONSE']._serialized_start=7836
+  _globals['_VERIFYCOLLECTIONCHECKSUMRESPONSE']._serialized_end=7985
+  _globals['_LISTSLOWQUERIESREQUEST']._serialized_start=7987
+  _globals['_LISTSLOWQUERIESREQUEST']._serialized_end=8057
+  _globals['_SLOWQUERY']._serialized_start=8060
+  _globals['_SLOWQUERY']._serialized_end=8303
+  _globals['_LISTSLOWQUERIESRESPONSE']._serialized_start=8305
+  _globals['_LISTSLOWQUERIESRESPONSE']._serialized_end=8366
+  _globals['_LISTCACHEDBLOCKSREQUEST']._serialized_start=8368
+  _globals['_LISTCACHEDBLOCKSREQUEST']._serialized_end=8408
+  _globals['_CACHEDBLOCK']._serialized_start=8410
+  _globals['_CACHEDBLOCK']._serialized_end=8507
+  _globals['_LISTCACHEDBLOCKSRESPONSE']._serialized_start=8510
+  _globals['_LISTCACHEDBLOCKSRESPONSE']._serialized_end=8642
+  _globals['_PAUSECOMPACTIONREQUEST']._serialized_start=8644
+  _globals['_PAUSECOMPACTIONREQUEST']._serialized_end=8691
+  _globals['_PAUSECOMPACTIONRESPONSE']._serialized_start=8693
+  _globals['_PAUSECOMPACTIONRESPONSE']._serialized_end=8742
+  _globals['_RESUMECOMPACTIONREQUEST']._serialized_start=8744
+  _globals['_RESUMECOMPACTIONREQUEST']._serialized_end=8792
+  _globals['_RESUMECOMPACTIONRESPONSE']._serialized_start=8794
+  _globals['_RESUMECOMPACTIONRESPONSE']._serialized_end=8840
+  _globals['_GETVECTORSREQUEST']._serialized_start=8843
+  _globals['_GETVECTORSREQUEST']._serialized_end=8974
+  _globals['_GETVECTORSRESPONSE']._serialized_start=8976
+  _globals['_GETVECTORSRESPONSE']._serialized_end=9044
+  _globals['_VECTOREMBEDDINGRECORD']._serialized_start=9046
+  _globals['_VECTOREMBEDDINGRECORD']._serialized_end=9113
+  _globals['_QUERYVECTORSREQUEST']._serialized_start=9116
+  _globals['_QUERYVECTORSREQUEST']._serialized_end=9526
+  _globals['_QUERYVECTORSRESPONSE']._serialized_start=9529
+  _globals['_QUERYVECTORSRESPONSE']._serialized_end=9680
+  _globals['_VECTORQUERYRESULTS']._serialized_start=9682
+  _globals['_VECTORQUERYRESULTS']._serialized_end=9746
+  _globals['_VECTORQUERYRESULT']._serialized_start=9748
+  _globals['_VECTORQUERYRESULT']._serialized_end=9845
+  _globals['_SIGNEDROARINGBITMAP']._serialized_start=9847
+  _globals['_SIGNEDROARINGBITMAP']._serialized_end=9916
+  _globals['_FILTEROUTPUT']._serialized_start=9918
+  _globals['_FILTEROUTPUT']._serialized_end=10042
+  _globals['_LIMITOUTPUT']._serialized_start=10044
+  _globals['_LIMITOUTPUT']._serialized_end=10077
+  _globals['_METADATAREADER']._serialized_start=10774
+  _globals['_METADATAREADER']._serialized_end=11192
+  _globals['_QUERYADMIN']._serialized_start=11195
+  _globals['_QUERYADMIN']._serialized_end=11807
+  _globals['_COMPACTIONADMIN']._serialized_start=11810
+  _globals['_COMPACTIONADMIN']._serialized_end=12002
+  _globals['_VECTORREADER']._serialized_start=12005
+  _globals['_VECTORREADER']._serialized_end=12167
 # @@protoc_insertion_point(module_scope)
@@ -29,12 +29,20 @@ class WhereDocumentOperator(int, metaclass=_enum_type_wrapper.EnumTypeWrapper):
     __slots__ = []
     CONTAINS: _ClassVar[WhereDocumentOperator]
     NOT_CONTAINS: _ClassVar[WhereDocumentOperator]
+    NEAR: _ClassVar[WhereDocumentOperator]
+    REGEX: _ClassVar[WhereDocumentOperator]
+    NOT_REGEX: _ClassVar[WhereDocumentOperator]
 
 class BooleanOperator(int, metaclass=_enum_type_wrapper.EnumTypeWrapper):
     __slots__ = []
     AND: _ClassVar[BooleanOperator]
     OR: _ClassVar[BooleanOperator]
 
+class ExistenceOperator(int, metaclass=_enum_type_wrapper.EnumTypeWrapper):
+    __slots__ = []
+    EXISTS: _ClassVar[ExistenceOperator]
+    IS_NULL: _ClassVar[ExistenceOperator]
+
 class ListOperator(int, metaclass=_enum_type_wrapper.EnumTypeWrapper):
     __slots__ = []
     IN: _ClassVar[ListOperator]
@@ -44,6 +52,10 @@ class GenericComparator(int, metaclass=_enum_type_wrapper.EnumTypeWrapper):
     __slots__ = []
     EQ: _ClassVar[GenericComparator]
     NE: _ClassVar[GenericComparator]
+    LIST_CONTAINS: _ClassVar[GenericComparator]
+    LIST_NOT_CONTAINS: _ClassVar[GenericComparator]
+    EQ_CI: _ClassVar[GenericComparator]
+    STARTS_WITH: _ClassVar[GenericComparator]
 
 class NumberComparator(int, metaclass=_enum_type_wrapper.EnumTypeWrapper):
     __slots__ = []
@@ -51,6 +63,17 @@ class NumberComparator(int, metaclass=_enum_type_wrapper.EnumTypeWrapper):
     GTE: _ClassVar[NumberComparator]
     LT: _ClassVar[NumberComparator]
     LTE: _ClassVar[NumberComparator]
+
+class IntegrityCheck(int, metaclass=_enum_type_wrapper.EnumTypeWrapper):
+    __slots__ = []
+    OFFSET_ID_CONTINUITY: _ClassVar[IntegrityCheck]
+    METADATA_AGREEMENT: _ClassVar[IntegrityCheck]
+    VECTOR_INDEX_COVERAGE: _ClassVar[IntegrityCheck]
+
+class DistanceAccumulation(int, metaclass=_enum_type_wrapper.EnumTypeWrapper):
+    __slots__ = []
+    ACCUMULATE_F32: _ClassVar[DistanceAccumulation]
+    ACCUMULATE_F64: _ClassVar[DistanceAccumulation]
 ADD: Operation
 UPDATE: Operation
 UPSERT: Operation
@@ -63,16 +86,30 @@ RECORD: SegmentScope
 SQLITE: SegmentScope
 CONTAINS: WhereDocumentOperator
 NOT_CONTAINS: WhereDocumentOperator
+NEAR: WhereDocumentOperator
+REGEX: WhereDocumentOperator
+NOT_REGEX: WhereDocumentOperator
 AND: BooleanOperator
 OR: BooleanOperator
+EXISTS: ExistenceOperator
+IS_NULL: ExistenceOperator
 IN: ListOperator
 NIN: ListOperator
 EQ: GenericComparator
 NE: GenericComparator
+LIST_CONTAINS: GenericComparator
+LIST_NOT_CONTAINS: GenericComparator
+EQ_CI: GenericComparator
+STARTS_WITH: GenericComparator
 GT: NumberComparator
 GTE: NumberComparator
 LT: NumberComparator
 LTE: NumberComparator
+OFFSET_ID_CONTINUITY: IntegrityCheck
+METADATA_AGREEMENT: IntegrityCheck
+VECTOR_INDEX_COVERAGE: IntegrityCheck
+ACCUMULATE_F32: DistanceAccumulation
+ACCUMULATE_F64: DistanceAccumulation
 
 class Vector(_message.Message):
     __slots__ = ["dimension", "vector", "encoding"]
@@ -146,22 +183,49 @@ class Database(_message.Message):
     def __init__(self, id: _Optional[str] = ..., name: _Optional[str] = ..., tenant: _Optional[str] = ...) -> None: ...
 
 class Tenant(_message.Message):
-    __slots__ = ["name"]
+    __slots__ = ["name", "kms_key_id"]
     NAME_FIELD_NUMBER: _ClassVar[int]
+    KMS_KEY_ID_FIELD_NUMBER: _ClassVar[int]
     name: str
-    def __init__(self, name: _Optional[str] = ...) -> None: ...
+    kms_key_id: str
+    def __init__(self, name: _Optional[str] = ..., kms_key_id: _Optional[str] = ...) -> None: ...
 
 class UpdateMetadataValue(_message.Message):
-    __slots__ = ["string_value", "int_value", "float_value", "bool_value"]
+    __slots__ = ["string_value", "int_value", "float_value", "bool_value", "list_value", "timestamp_value", "object_value"]
     STRING_VALUE_FIELD_NUMBER: _ClassVar[int]
     INT_VALUE_FIELD_NUMBER: _ClassVar[int]
     FLOAT_VALUE_FIELD_NUMBER: _ClassVar[int]
     BOOL_VALUE_FIELD_NUMBER: _ClassVar[int]
+    LIST_VALUE_FIELD_NUMBER: _ClassVar[int]
+    TIMESTAMP_VALUE_FIELD_NUMBER: _ClassVar[int]
+    OBJECT_VALUE_FIELD_NUMBER: _ClassVar[int]
     string_value: str
     int_value: int
     float_value: float
     bool_value: bool
-    def __init__(self, string_value: _Optional[str] = ..., int_value: _Optional[int] = ..., float_value: _Optional[float] = ..., bool_value: bool = ...) -> None: ...
+    list_value: MetadataList
+    timestamp_value: int
+    object_value: MetadataObject
+    def __init__(self, string_value: _Optional[str] = ..., int_value: _Optional[int] = ..., float_value: _Optional[float] = ..., bool_value: bool = ..., list_value: _Optional[_Union[MetadataList, _Mapping]] = ..., timestamp_value: _Optional[int] = ..., object_value: _Optional[_Union[MetadataObject, _Mapping]] = ...) -> None: ...
+
+class MetadataList(_message.Message):
+    __slots__ = ["values"]
+    VALUES_FIELD_NUMBER: _ClassVar[int]
+    values: _containers.RepeatedCompositeFieldContainer[UpdateMetadataValue]
+    def __init__(self, values: _Optional[_Iterable[_Union[UpdateMetadataValue, _Mapping]]] = ...) -> None: ...
+
+class MetadataObject(_message.Message):
+    __slots__ = ["values"]
+    class ValuesEntry(_message.Message):
+        __slots__ = ["key", "value"]
+        KEY_FIELD_NUMBER: _ClassVar[int]
+        VALUE_FIELD_NUMBER: _ClassVar[int]
+        key: str
+        value: UpdateMetadataValue
+        def __init__(self, key: _Optional[str] = ..., value: _Optional[_Union[UpdateMetadataValue, _Mapping]] = ...) -> None: ...
+    VALUES_FIELD_NUMBER: _ClassVar[int]
+    values: _containers.MessageMap[str, UpdateMetadataValue]
+    def __init__(self, values: _Optional[_Mapping[str, UpdateMetadataValue]] = ...) -> None: ...
 
 class UpdateMetadata(_message.Message):
     __slots__ = ["metadata"]
@@ -176,25 +240,83 @@ class UpdateMetadata(_message.Message):
     metadata: _containers.MessageMap[str, UpdateMetadataValue]
     def __init__(self, metadata: _Optional[_Mapping[str, UpdateMetadataValue]] = ...) -> None: ...
 
+class VectorReference(_message.Message):
+    __slots__ = ["uri", "row", "dimension"]
+    URI_FIELD_NUMBER: _ClassVar[int]
+    ROW_FIELD_NUMBER: _ClassVar[int]
+    DIMENSION_FIELD_NUMBER: _ClassVar[int]
+    uri: str
+    row: int
+    dimension: int
+    def __init__(self, uri: _Optional[str] = ..., row: _Optional[int] = ..., dimension: _Optional[int] = ...) -> None: ...
+
 class OperationRecord(_message.Message):
-    __slots__ = ["id", "vector", "metadata", "operation"]
+    __slots__ = ["id", "vector", "metadata", "operation", "vector_reference"]
     ID_FIELD_NUMBER: _ClassVar[int]
     VECTOR_FIELD_NUMBER: _ClassVar[int]
     METADATA_FIELD_NUMBER: _ClassVar[int]
     OPERATION_FIELD_NUMBER: _ClassVar[int]
+    VECTOR_REFERENCE_FIELD_NUMBER: _ClassVar[int]
     id: str
     vector: Vector
     metadata: UpdateMetadata
     operation: Operation
-    def __init__(self, id: _Optional[str] = ..., vector: _Optional[_Union[Vector, _Mapping]] = ..., metadata: _Optional[_Union[UpdateMetadata, _Mapping]] = ..., operation: _Optional[_Union[Operation, str]] = ...) -> None: ...
+    vector_reference: VectorReference
+    def __init__(self, id: _Optional[str] = ..., vector: _Optional[_Union[Vector, _Mapping]] = ..., metadata: _Optional[_Union[UpdateMetadata, _Mapping]] = ..., operation: _Optional[_Union[Operation, str]] = ..., vector_reference: _Optional[_Union[VectorReference, _Mapping]] = ...) -> None: ...
 
 class RequestVersionContext(_message.Message):
-    __slots__ = ["collection_version", "log_position"]
+    __slots__ = ["collection_version", "log_position", "max_log_backlog"]
     COLLECTION_VERSION_FIELD_NUMBER: _ClassVar[int]
     LOG_POSITION_FIELD_NUMBER: _ClassVar[int]
+    MAX_LOG_BACKLOG_FIELD_NUMBER: _ClassVar[int]
     collection_version: int
     log_position: int
-    def __init__(self, collection_version: _Optional[int] = ..., log_position: _Optional[int] = ...) -> None: ...
+    max_log_backlog: int
+    def __init__(self, collection_version: _Optional[int] = ..., log_position: _Optional[int] = ..., max_log_backlog: _Optional[int] = ...) -> None: ...
+
+class ErrorDetails(_message.Message):
+    __slots__ = ["retryable", "user_error", "field", "segment_id", "block_id"]
+    RETRYABLE_FIELD_NUMBER: _ClassVar[int]
+    USER_ERROR_FIELD_NUMBER: _ClassVar[int]
+    FIELD_FIELD_NUMBER: _ClassVar[int]
+    SEGMENT_ID_FIELD_NUMBER: _ClassVar[int]
+    BLOCK_ID_FIELD_NUMBER: _ClassVar[int]
+    retryable: bool
+    user_error: bool
+    field: str
+    segment_id: str
+    block_id: str
+    def __init__(self, retryable: bool = ..., user_error: bool = ..., field: _Optional[str] = ..., segment_id: _Optional[str] = ..., block_id: _Optional[str] = ...) -> None: ...
+
+class StreamChangesRequest(_message.Message):
+    __slots__ = ["collection_id", "version_context", "start_log_offset", "include_embeddings", "default_include"]
+    COLLECTION_ID_FIELD_NUMBER: _ClassVar[int]
+    VERSION_CONTEXT_FIELD_NUMBER: _ClassVar[int]
+    START_LOG_OFFSET_FIELD_NUMBER: _ClassVar[int]
+    INCLUDE_EMBEDDINGS_FIELD_NUMBER: _ClassVar[int]
+    DEFAULT_INCLUDE_FIELD_NUMBER: _ClassVar[int]
+    collection_id: str
+    version_context: RequestVersionContext
+    start_log_offset: int
+    include_embeddings: bool
+    default_include: bool
+    def __init__(self, collection_id: _Optional[str] = ..., version_context: _Optional[_Union[RequestVersionContext, _Mapping]] = ..., start_log_offset: _Optional[int] = ..., include_embeddings: bool = ..., default_include: bool = ...) -> None: ...
+
+class RecordChange(_message.Message):
+    __slots__ = ["operation", "record"]
+    OPERATION_FIELD_NUMBER: _ClassVar[int]
+    RECORD_FIELD_NUMBER: _ClassVar[int]
+    operation: Operation
+    record: MetadataEmbeddingRecord
+    def __init__(self, operation: _Optional[_Union[Operation, str]] = ..., record: _Optional[_Union[MetadataEmbeddingRecord, _Mapping]] = ...) -> None: ...
+
+class StreamChangesResponse(_message.Message):
+    __slots__ = ["changes", "log_offset"]
+    CHANGES_FIELD_NUMBER: _ClassVar[int]
+    LOG_OFFSET_FIELD_NUMBER: _ClassVar[int]
+    changes: _containers.RepeatedCompositeFieldContainer[RecordChange]
+    log_offset: int
+    def __init__(self, changes: _Optional[_Iterable[_Union[RecordChange, _Mapping]]] = ..., log_offset: _Optional[int] = ...) -> None: ...
 
 class CountRecordsRequest(_message.Message):
     __slots__ = ["segment_id", "collection_id", "version_context"]
@@ -213,7 +335,7 @@ class CountRecordsResponse(_message.Message):
     def __init__(self, count: _Optional[int] = ...) -> None: ...
 
 class QueryMetadataRequest(_message.Message):
-    __slots__ = ["segment_id", "where", "where_document", "ids", "limit", "offset", "collection_id", "include_metadata", "version_context"]
+    __slots__ = ["segment_id", "where", "where_document", "ids", "limit", "offset", "collection_id", "include_metadata", "version_context", "include_embeddings", "max_embedding_bytes", "explain", "lookup", "default_include"]
     SEGMENT_ID_FIELD_NUMBER: _ClassVar[int]
     WHERE_FIELD_NUMBER: _ClassVar[int]
     WHERE_DOCUMENT_FIELD_NUMBER: _ClassVar[int]
@@ -223,6 +345,11 @@ class QueryMetadataRequest(_message.Message):
     COLLECTION_ID_FIELD_NUMBER: _ClassVar[int]
     INCLUDE_METADATA_FIELD_NUMBER: _ClassVar[int]
     VERSION_CONTEXT_FIELD_NUMBER: _ClassVar[int]
+    INCLUDE_EMBEDDINGS_FIELD_NUMBER: _ClassVar[int]
+    MAX_EMBEDDING_BYTES_FIELD_NUMBER: _ClassVar[int]
+    EXPLAIN_FIELD_NUMBER: _ClassVar[int]
+    LOOKUP_FIELD_NUMBER: _ClassVar[int]
+    DEFAULT_INCLUDE_FIELD_NUMBER: _ClassVar[int]
     segment_id: str
     where: Where
     where_document: WhereDocument
@@ -232,21 +359,122 @@ class QueryMetadataRequest(_message.Message):
     collection_id: str
     include_metadata: bool
     version_context: RequestVersionContext
-    def __init__(self, segment_id: _Optional[str] = ..., where: _Optional[_Union[Where, _Mapping]] = ..., where_document: _Optional[_Union[WhereDocument, _Mapping]] = ..., ids: _Optional[_Union[UserIds, _Mapping]] = ..., limit: _Optional[int] = ..., offset: _Optional[int] = ..., collection_id: _Optional[str] = ..., include_metadata: bool = ..., version_context: _Optional[_Union[RequestVersionContext, _Mapping]] = ...) -> None: ...
+    include_embeddings: bool
+    max_embedding_bytes: int
+    explain: bool
+    lookup: Lookup
+    default_include: bool
+    def __init__(self, segment_id: _Optional[str] = ..., where: _Optional[_Union[Where, _Mapping]] = ..., where_document: _Optional[_Union[WhereDocument, _Mapping]] = ..., ids: _Optional[_Union[UserIds, _Mapping]] = ..., limit: _Optional[int] = ..., offset: _Optional[int] = ..., collection_id: _Optional[str] = ..., include_metadata: bool = ..., version_context: _Optional[_Union[RequestVersionContext, _Mapping]] = ..., include_embeddings: bool = ..., max_embedding_bytes: _Optional[int] = ..., explain: bool = ..., lookup: _Optional[_Union[Lookup, _Mapping]] = ..., default_include: bool = ...) -> None: ...
+
+class Lookup(_message.Message):
+    __slots__ = ["collection_id", "version_context", "id_key", "include_metadata", "include_embeddings", "default_include"]
+    COLLECTION_ID_FIELD_NUMBER: _ClassVar[int]
+    VERSION_CONTEXT_FIELD_NUMBER: _ClassVar[int]
+    ID_KEY_FIELD_NUMBER: _ClassVar[int]
+    INCLUDE_METADATA_FIELD_NUMBER: _ClassVar[int]
+    INCLUDE_EMBEDDINGS_FIELD_NUMBER: _ClassVar[int]
+    DEFAULT_INCLUDE_FIELD_NUMBER: _ClassVar[int]
+    collection_id: str
+    version_context: RequestVersionContext
+    id_key: str
+    include_metadata: bool
+    include_embeddings: bool
+    default_include: bool
+    def __init__(self, collection_id: _Optional[str] = ..., version_context: _Optional[_Union[RequestVersionContext, _Mapping]] = ..., id_key: _Optional[str] = ..., include_metadata: bool = ..., include_embeddings: bool = ..., default_include: bool = ...) -> None: ...
 
 class QueryMetadataResponse(_message.Message):
-    __slots__ = ["records"]
+    __slots__ = ["records", "plan", "lookup_records"]
+    class LookupRecordsEntry(_message.Message):
+        __slots__ = ["key", "value"]
+        KEY_FIELD_NUMBER: _ClassVar[int]
+        VALUE_FIELD_NUMBER: _ClassVar[int]
+        key: str
+        value: MetadataEmbeddingRecord
+        def __init__(self, key: _Optional[str] = ..., value: _Optional[_Union[MetadataEmbeddingRecord, _Mapping]] = ...) -> None: ...
     RECORDS_FIELD_NUMBER: _ClassVar[int]
+    PLAN_FIELD_NUMBER: _ClassVar[int]
+    LOOKUP_RECORDS_FIELD_NUMBER: _ClassVar[int]
     records: _containers.RepeatedCompositeFieldContainer[MetadataEmbeddingRecord]
-    def __init__(self, records: _Optional[_Iterable[_Union[MetadataEmbeddingRecord, _Mapping]]] = ...) -> None: ...
+    plan: QueryPlan
+    lookup_records: _containers.MessageMap[str, MetadataEmbeddingRecord]
+    def __init__(self, records: _Optional[_Iterable[_Union[MetadataEmbeddingRecord, _Mapping]]] = ..., plan: _Optional[_Union[QueryPlan, _Mapping]] = ..., lookup_records: _Optional[_Mapping[str, MetadataEmbeddingRecord]] = ...) -> None: ...
+
+class QueryPlan(_message.Message):
+    __slots__ = ["strategy", "nodes"]
+    class StrategyEntry(_message.Message):
+        __slots__ = ["key", "value"]
+        KEY_FIELD_NUMBER: _ClassVar[int]
+        VALUE_FIELD_NUMBER: _ClassVar[int]
+        key: str
+        value: str
+        def __init__(self, key: _Optional[str] = ..., value: _Optional[str] = ...) -> None: ...
+    STRATEGY_FIELD_NUMBER: _ClassVar[int]
+    NODES_FIELD_NUMBER: _ClassVar[int]
+    strategy: _containers.ScalarMap[str, str]
+    nodes: _containers.RepeatedCompositeFieldContainer[QueryPlanNode]
+    def __init__(self, strategy: _Optional[_Mapping[str, str]] = ..., nodes: _Optional[_Iterable[_Union[QueryPlanNode, _Mapping]]] = ...) -> None: ...
+
+class QueryPlanNode(_message.Message):
+    __slots__ = ["operator", "inputs", "estimated_rows", "output_rows", "elapsed_micros", "cache_hits", "cache_misses", "bytes_read"]
+    OPERATOR_FIELD_NUMBER: _ClassVar[int]
+    INPUTS_FIELD_NUMBER: _ClassVar[int]
+    ESTIMATED_ROWS_FIELD_NUMBER: _ClassVar[int]
+    OUTPUT_ROWS_FIELD_NUMBER: _ClassVar[int]
+    ELAPSED_MICROS_FIELD_NUMBER: _ClassVar[int]
+    CACHE_HITS_FIELD_NUMBER: _ClassVar[int]
+    CACHE_MISSES_FIELD_NUMBER: _ClassVar[int]
+    BYTES_READ_FIELD_NUMBER: _ClassVar[int]
+    operator: str
+    inputs: _containers.RepeatedScalarFieldContainer[int]
+    estimated_rows: int
+    output_rows: int
+    elapsed_micros: int
+    cache_hits: int
+    cache_misses: int
+    bytes_read: int
+    def __init__(self, operator: _Optional[str] = ..., inputs: _Optional[_Iterable[int]] = ..., estimated_rows: _Optional[int] = ..., output_rows: _Optional[int] = ..., elapsed_micros: _Optional[int] = ..., cache_hits: _Optional[int] = ..., cache_misses: _Optional[int] = ..., bytes_read: _Optional[int] = ...) -> None: ...
 
 class MetadataEmbeddingRecord(_message.Message):
-    __slots__ = ["id", "metadata"]
+    __slots__ = ["id", "metadata", "embedding", "embedding_handle"]
     ID_FIELD_NUMBER: _ClassVar[int]
     METADATA_FIELD_NUMBER: _ClassVar[int]
+    EMBEDDING_FIELD_NUMBER: _ClassVar[int]
+    EMBEDDING_HANDLE_FIELD_NUMBER: _ClassVar[int]
     id: str
     metadata: UpdateMetadata
-    def __init__(self, id: _Optional[str] = ..., metadata: _Optional[_Union[UpdateMetadata, _Mapping]] = ...) -> None: ...
+    embedding: Vector
+    embedding_handle: EmbeddingHandle
+    def __init__(self, id: _Optional[str] = ..., metadata: _Optional[_Union[UpdateMetadata, _Mapping]] = ..., embedding: _Optional[_Union[Vector, _Mapping]] = ..., embedding_handle: _Optional[_Union[EmbeddingHandle, _Mapping]] = ...) -> None: ...
+
+class EmbeddingHandle(_message.Message):
+    __slots__ = ["offset_id", "version_context", "log_offset"]
+    OFFSET_ID_FIELD_NUMBER: _ClassVar[int]
+    VERSION_CONTEXT_FIELD_NUMBER: _ClassVar[int]
+    LOG_OFFSET_FIELD_NUMBER: _ClassVar[int]
+    offset_id: int
+    version_context: RequestVersionContext
+    log_offset: int
+    def __init__(self, offset_id: _Optional[int] = ..., version_context: _Optional[_Union[RequestVersionContext, _Mapping]] = ..., log_offset: _Optional[int] = ...) -> None: ...
+
+class GetEmbeddingsRequest(_message.Message):
+    __slots__ = ["segment_id", "collection_id", "version_context", "offset_ids", "log_offset"]
+    SEGMENT_ID_FIELD_NUMBER: _ClassVar[int]
+    COLLECTION_ID_FIELD_NUMBER: _ClassVar[int]
+    VERSION_CONTEXT_FIELD_NUMBER: _ClassVar[int]
+    OFFSET_IDS_FIELD_NUMBER: _ClassVar[int]
+    LOG_OFFSET_FIELD_NUMBER: _ClassVar[int]
+    segment_id: str
+    collection_id: str
+    version_context: RequestVersionContext
+    offset_ids: _containers.RepeatedScalarFieldContainer[int]
+    log_offset: int
+    def __init__(self, segment_id: _Optional[str] = ..., collection_id: _Optional[str] = ..., version_context: _Optional[_Union[RequestVersionContext, _Mapping]] = ..., offset_ids: _Optional[_Iterable[int]] = ..., log_offset: _Optional[int] = ...) -> None: ...
+
+class GetEmbeddingsResponse(_message.Message):
+    __slots__ = ["records"]
+    RECORDS_FIELD_NUMBER: _ClassVar[int]
+    records: _containers.RepeatedCompositeFieldContainer[VectorEmbeddingRecord]
+    def __init__(self, records: _Optional[_Iterable[_Union[VectorEmbeddingRecord, _Mapping]]] = ...) -> None: ...
 
 class UserIds(_message.Message):
     __slots__ = ["ids"]
@@ -263,12 +491,14 @@ class WhereDocument(_message.Message):
     def __init__(self, direct: _Optional[_Union[DirectWhereDocument, _Mapping]] = ..., children: _Optional[_Union[WhereDocumentChildren, _Mapping]] = ...) -> None: ...
 
 class DirectWhereDocument(_message.Message):
-    __slots__ = ["document", "operator"]
+    __slots__ = ["document", "operator", "distance"]
     DOCUMENT_FIELD_NUMBER: _ClassVar[int]
     OPERATOR_FIELD_NUMBER: _ClassVar[int]
+    DISTANCE_FIELD_NUMBER: _ClassVar[int]
     document: str
     operator: WhereDocumentOperator
-    def __init__(self, document: _Optional[str] = ..., operator: _Optional[_Union[WhereDocumentOperator, str]] = ...) -> None: ...
+    distance: int
+    def __init__(self, document: _Optional[str] = ..., operator: _Optional[_Union[WhereDocumentOperator, str]] = ..., distance: _Optional[int] = ...) -> None: ...
 
 class WhereDocumentChildren(_message.Message):
     __slots__ = ["children", "operator"]
@@ -287,7 +517,7 @@ class Where(_message.Message):
     def __init__(self, direct_comparison: _Optional[_Union[DirectComparison, _Mapping]] = ..., children: _Optional[_Union[WhereChildren, _Mapping]] = ...) -> None: ...
 
 class DirectComparison(_message.Message):
-    __slots__ = ["key", "single_string_operand", "string_list_operand", "single_int_operand", "int_list_operand", "single_double_operand", "double_list_operand", "bool_list_operand", "single_bool_operand"]
+    __slots__ = ["key", "single_string_operand", "string_list_operand", "single_int_operand", "int_list_operand", "single_double_operand", "double_list_operand", "bool_list_operand", "single_bool_operand", "existence_operand", "single_timestamp_operand"]
     KEY_FIELD_NUMBER: _ClassVar[int]
     SINGLE_STRING_OPERAND_FIELD_NUMBER: _ClassVar[int]
     STRING_LIST_OPERAND_FIELD_NUMBER: _ClassVar[int]
@@ -297,6 +527,8 @@ class DirectComparison(_message.Message):
     DOUBLE_LIST_OPERAND_FIELD_NUMBER: _ClassVar[int]
     BOOL_LIST_OPERAND_FIELD_NUMBER: _ClassVar[int]
     SINGLE_BOOL_OPERAND_FIELD_NUMBER: _ClassVar[int]
+    EXISTENCE_OPERAND_FIELD_NUMBER: _ClassVar[int]
+    SINGLE_TIMESTAMP_OPERAND_FIELD_NUMBER: _ClassVar[int]
     key: str
     single_string_operand: SingleStringComparison
     string_list_operand: StringListComparison
@@ -306,7 +538,9 @@ class DirectComparison(_message.Message):
     double_list_operand: DoubleListComparison
     bool_list_operand: BoolListComparison
     single_bool_operand: SingleBoolComparison
-    def __init__(self, key: _Optional[str] = ..., single_string_operand: _Optional[_Union[SingleStringComparison, _Mapping]] = ..., string_list_operand: _Optional[_Union[StringListComparison, _Mapping]] = ..., single_int_operand: _Optional[_Union[SingleIntComparison, _Mapping]] = ..., int_list_operand: _Optional[_Union[IntListComparison, _Mapping]] = ..., single_double_operand: _Optional[_Union[SingleDoubleComparison, _Mapping]] = ..., double_list_operand: _Optional[_Union[DoubleListComparison, _Mapping]] = ..., bool_list_operand: _Optional[_Union[BoolListComparison, _Mapping]] = ..., single_bool_operand: _Optional[_Union[SingleBoolComparison, _Mapping]] = ...) -> None: ...
+    existence_operand: ExistenceComparison
+    single_timestamp_operand: SingleTimestampComparison
+    def __init__(self, key: _Optional[str] = ..., single_string_operand: _Optional[_Union[SingleStringComparison, _Mapping]] = ..., string_list_operand: _Optional[_Union[StringListComparison, _Mapping]] = ..., single_int_operand: _Optional[_Union[SingleIntComparison, _Mapping]] = ..., int_list_operand: _Optional[_Union[IntListComparison, _Mapping]] = ..., single_double_operand: _Optional[_Union[SingleDoubleComparison, _Mapping]] = ..., double_list_operand: _Optional[_Union[DoubleListComparison, _Mapping]] = ..., bool_list_operand: _Optional[_Union[BoolListComparison, _Mapping]] = ..., single_bool_operand: _Optional[_Union[SingleBoolComparison, _Mapping]] = ..., existence_operand: _Optional[_Union[ExistenceComparison, _Mapping]] = ..., single_timestamp_operand: _Optional[_Union[SingleTimestampComparison, _Mapping]] = ...) -> None: ...
 
 class WhereChildren(_message.Message):
     __slots__ = ["children", "operator"]
@@ -324,6 +558,12 @@ class StringListComparison(_message.Message):
     list_operator: ListOperator
     def __init__(self, values: _Optional[_Iterable[str]] = ..., list_operator: _Optional[_Union[ListOperator, str]] = ...) -> None: ...
 
+class ExistenceComparison(_message.Message):
+    __slots__ = ["operator"]
+    OPERATOR_FIELD_NUMBER: _ClassVar[int]
+    operator: ExistenceOperator
+    def __init__(self, operator: _Optional[_Union[ExistenceOperator, str]] = ...) -> None: ...
+
 class SingleStringComparison(_message.Message):
     __slots__ = ["value", "comparator"]
     VALUE_FIELD_NUMBER: _ClassVar[int]
@@ -358,6 +598,16 @@ class SingleIntComparison(_message.Message):
     number_comparator: NumberComparator
     def __init__(self, value: _Optional[int] = ..., generic_comparator: _Optional[_Union[GenericComparator, str]] = ..., number_comparator: _Optional[_Union[NumberComparator, str]] = ...) -> None: ...
 
+class SingleTimestampComparison(_message.Message):
+    __slots__ = ["value", "generic_comparator", "number_comparator"]
+    VALUE_FIELD_NUMBER: _ClassVar[int]
+    GENERIC_COMPARATOR_FIELD_NUMBER: _ClassVar[int]
+    NUMBER_COMPARATOR_FIELD_NUMBER: _ClassVar[int]
+    value: int
+    generic_comparator: GenericComparator
+    number_comparator: NumberComparator
+    def __init__(self, value: _Optional[int] = ..., generic_comparator: _Optional[_Union[GenericComparator, str]] = ..., number_comparator: _Optional[_Union[NumberComparator, str]] = ...) -> None: ...
+
 class DoubleListComparison(_message.Message):
     __slots__ = ["values", "list_operator"]
     VALUES_FIELD_NUMBER: _ClassVar[int]
@@ -384,6 +634,178 @@ class SingleDoubleComparison(_message.Message):
     number_comparator: NumberComparator
     def __init__(self, value: _Optional[float] = ..., generic_comparator: _Optional[_Union[GenericComparator, str]] = ..., number_comparator: _Optional[_Union[NumberComparator, str]] = ...) -> None: ...
 
+class WarmUpCollectionRequest(_message.Message):
+    __slots__ = ["collection_id", "version_context"]
+    COLLECTION_ID_FIELD_NUMBER: _ClassVar[int]
+    VERSION_CONTEXT_FIELD_NUMBER: _ClassVar[int]
+    collection_id: str
+    version_context: RequestVersionContext
+    def __init__(self, collection_id: _Optional[str] = ..., version_context: _Optional[_Union[RequestVersionContext, _Mapping]] = ...) -> None: ...
+
+class WarmUpCollectionResponse(_message.Message):
+    __slots__ = ["num_blocks", "vector_index_loaded", "num_hot_blocks"]
+    NUM_BLOCKS_FIELD_NUMBER: _ClassVar[int]
+    VECTOR_INDEX_LOADED_FIELD_NUMBER: _ClassVar[int]
+    NUM_HOT_BLOCKS_FIELD_NUMBER: _ClassVar[int]
+    num_blocks: int
+    vector_index_loaded: bool
+    num_hot_blocks: int
+    def __init__(self, num_blocks: _Optional[int] = ..., vector_index_loaded: bool = ..., num_hot_blocks: _Optional[int] = ...) -> None: ...
+
+class ExportCollectionIndexesRequest(_message.Message):
+    __slots__ = ["collection_id", "version_context", "destination_prefix"]
+    COLLECTION_ID_FIELD_NUMBER: _ClassVar[int]
+    VERSION_CONTEXT_FIELD_NUMBER: _ClassVar[int]
+    DESTINATION_PREFIX_FIELD_NUMBER: _ClassVar[int]
+    collection_id: str
+    version_context: RequestVersionContext
+    destination_prefix: str
+    def __init__(self, collection_id: _Optional[str] = ..., version_context: _Optional[_Union[RequestVersionContext, _Mapping]] = ..., destination_prefix: _Optional[str] = ...) -> None: ...
+
+class ExportCollectionIndexesResponse(_message.Message):
+    __slots__ = ["num_tokens", "num_posting_lists"]
+    NUM_TOKENS_FIELD_NUMBER: _ClassVar[int]
+    NUM_POSTING_LISTS_FIELD_NUMBER: _ClassVar[int]
+    num_tokens: int
+    num_posting_lists: int
+    def __init__(self, num_tokens: _Optional[int] = ..., num_posting_lists: _Optional[int] = ...) -> None: ...
+
+class CheckCollectionIntegrityRequest(_message.Message):
+    __slots__ = ["collection_id", "version_context", "repair"]
+    COLLECTION_ID_FIELD_NUMBER: _ClassVar[int]
+    VERSION_CONTEXT_FIELD_NUMBER: _ClassVar[int]
+    REPAIR_FIELD_NUMBER: _ClassVar[int]
+    collection_id: str
+    version_context: RequestVersionContext
+    repair: bool
+    def __init__(self, collection_id: _Optional[str] = ..., version_context: _Optional[_Union[RequestVersionContext, _Mapping]] = ..., repair: bool = ...) -> None: ...
+
+class IntegrityIssue(_message.Message):
+    __slots__ = ["check", "description"]
+    CHECK_FIELD_NUMBER: _ClassVar[int]
+    DESCRIPTION_FIELD_NUMBER: _ClassVar[int]
+    check: IntegrityCheck
+    description: str
+    def __init__(self, check: _Optional[_Union[IntegrityCheck, str]] = ..., description: _Optional[str] = ...) -> None: ...
+
+class CheckCollectionIntegrityResponse(_message.Message):
+    __slots__ = ["num_records", "num_issues", "issues", "rebuilt_segment_ids", "collection_version"]
+    NUM_RECORDS_FIELD_NUMBER: _ClassVar[int]
+    NUM_ISSUES_FIELD_NUMBER: _ClassVar[int]
+    ISSUES_FIELD_NUMBER: _ClassVar[int]
+    REBUILT_SEGMENT_IDS_FIELD_NUMBER: _ClassVar[int]
+    COLLECTION_VERSION_FIELD_NUMBER: _ClassVar[int]
+    num_records: int
+    num_issues: int
+    issues: _containers.RepeatedCompositeFieldContainer[IntegrityIssue]
+    rebuilt_segment_ids: _containers.RepeatedScalarFieldContainer[str]
+    collection_version: int
+    def __init__(self, num_records: _Optional[int] = ..., num_issues: _Optional[int] = ..., issues: _Optional[_Iterable[_Union[IntegrityIssue, _Mapping]]] = ..., rebuilt_segment_ids: _Optional[_Iterable[str]] = ..., collection_version: _Optional[int] = ...) -> None: ...
+
+class VerifyCollectionChecksumRequest(_message.Message):
+    __slots__ = ["collection_id", "version_context"]
+    COLLECTION_ID_FIELD_NUMBER: _ClassVar[int]
+    VERSION_CONTEXT_FIELD_NUMBER: _ClassVar[int]
+    collection_id: str
+    version_context: RequestVersionContext
+    def __init__(self, collection_id: _Optional[str] = ..., version_context: _Optional[_Union[RequestVersionContext, _Mapping]] = ...) -> None: ...
+
+class VerifyCollectionChecksumResponse(_message.Message):
+    __slots__ = ["stored_checksum", "computed_checksum", "num_records", "matches"]
+    STORED_CHECKSUM_FIELD_NUMBER: _ClassVar[int]
+    COMPUTED_CHECKSUM_FIELD_NUMBER: _ClassVar[int]
+    NUM_RECORDS_FIELD_NUMBER: _ClassVar[int]
+    MATCHES_FIELD_NUMBER: _ClassVar[int]
+    stored_checksum: str
+    computed_checksum: str
+    num_records: int
+    matches: bool
+    def __init__(self, stored_checksum: _Optional[str] = ..., computed_checksum: _Optional[str] = ..., num_records: _Optional[int] = ..., matches: bool = ...) -> None: ...
+
+class ListSlowQueriesRequest(_message.Message):
+    __slots__ = ["collection_id"]
+    COLLECTION_ID_FIELD_NUMBER: _ClassVar[int]
+    collection_id: str
+    def __init__(self, collection_id: _Optional[str] = ...) -> None: ...
+
+class SlowQuery(_message.Message):
+    __slots__ = ["collection_id", "query_type", "filter_operators", "num_ids", "limit", "offset", "elapsed_micros", "logged_at_micros", "plan"]
+    COLLECTION_ID_FIELD_NUMBER: _ClassVar[int]
+    QUERY_TYPE_FIELD_NUMBER: _ClassVar[int]
+    FILTER_OPERATORS_FIELD_NUMBER: _ClassVar[int]
+    NUM_IDS_FIELD_NUMBER: _ClassVar[int]
+    LIMIT_FIELD_NUMBER: _ClassVar[int]
+    OFFSET_FIELD_NUMBER: _ClassVar[int]
+    ELAPSED_MICROS_FIELD_NUMBER: _ClassVar[int]
+    LOGGED_AT_MICROS_FIELD_NUMBER: _ClassVar[int]
+    PLAN_FIELD_NUMBER: _ClassVar[int]
+    collection_id: str
+    query_type: str
+    filter_operators: _containers.RepeatedScalarFieldContainer[str]
+    num_ids: int
+    limit: int
+    offset: int
+    elapsed_micros: int
+    logged_at_micros: int
+    plan: QueryPlan
+    def __init__(self, collection_id: _Optional[str] = ..., query_type: _Optional[str] = ..., filter_operators: _Optional[_Iterable[str]] = ..., num_ids: _Optional[int] = ..., limit: _Optional[int] = ..., offset: _Optional[int] = ..., elapsed_micros: _Optional[int] = ..., logged_at_micros: _Optional[int] = ..., plan: _Optional[_Union[QueryPlan, _Mapping]] = ...) -> None: ...
+
+class ListSlowQueriesResponse(_message.Message):
+    __slots__ = ["queries"]
+    QUERIES_FIELD_NUMBER: _ClassVar[int]
+    queries: _containers.RepeatedCompositeFieldContainer[SlowQuery]
+    def __init__(self, queries: _Optional[_Iterable[_Union[SlowQuery, _Mapping]]] = ...) -> None: ...
+
+class ListCachedBlocksRequest(_message.Message):
+    __slots__ = ["limit"]
+    LIMIT_FIELD_NUMBER: _ClassVar[int]
+    limit: int
+    def __init__(self, limit: _Optional[int] = ...) -> None: ...
+
+class CachedBlock(_message.Message):
+    __slots__ = ["block_id", "size_bytes", "collection_id"]
+    BLOCK_ID_FIELD_NUMBER: _ClassVar[int]
+    SIZE_BYTES_FIELD_NUMBER: _ClassVar[int]
+    COLLECTION_ID_FIELD_NUMBER: _ClassVar[int]
+    block_id: str
+    size_bytes: int
+    collection_id: str
+    def __init__(self, block_id: _Optional[str] = ..., size_bytes: _Optional[int] = ..., collection_id: _Optional[str] = ...) -> None: ...
+
+class ListCachedBlocksResponse(_message.Message):
+    __slots__ = ["blocks", "usage_bytes", "capacity_bytes"]
+    BLOCKS_FIELD_NUMBER: _ClassVar[int]
+    USAGE_BYTES_FIELD_NUMBER: _ClassVar[int]
+    CAPACITY_BYTES_FIELD_NUMBER: _ClassVar[int]
+    blocks: _containers.RepeatedCompositeFieldContainer[CachedBlock]
+    usage_bytes: int
+    capacity_bytes: int
+    def __init__(self, blocks: _Optional[_Iterable[_Union[CachedBlock, _Mapping]]] = ..., usage_bytes: _Optional[int] = ..., capacity_bytes: _Optional[int] = ...) -> None: ...
+
+class PauseCompactionRequest(_message.Message):
+    __slots__ = ["collection_id"]
+    COLLECTION_ID_FIELD_NUMBER: _ClassVar[int]
+    collection_id: str
+    def __init__(self, collection_id: _Optional[str] = ...) -> None: ...
+
+class PauseCompactionResponse(_message.Message):
+    __slots__ = ["already_paused"]
+    ALREADY_PAUSED_FIELD_NUMBER: _ClassVar[int]
+    already_paused: bool
+    def __init__(self, already_paused: bool = ...) -> None: ...
+
+class ResumeCompactionRequest(_message.Message):
+    __slots__ = ["collection_id"]
+    COLLECTION_ID_FIELD_NUMBER: _ClassVar[int]
+    collection_id: str
+    def __init__(self, collection_id: _Optional[str] = ...) -> None: ...
+
+class ResumeCompactionResponse(_message.Message):
+    __slots__ = ["was_paused"]
+    WAS_PAUSED_FIELD_NUMBER: _ClassVar[int]
+    was_paused: bool
+    def __init__(self, was_paused: bool = ...) -> None: ...
+
 class GetVectorsRequest(_message.Message):
     __slots__ = ["ids", "segment_id", "collection_id", "version_context"]
     IDS_FIELD_NUMBER: _ClassVar[int]
@@ -411,7 +833,7 @@ class VectorEmbeddingRecord(_message.Message):
     def __init__(self, id: _Optional[str] = ..., vector: _Optional[_Union[Vector, _Mapping]] = ...) -> None: ...
 
 class QueryVectorsRequest(_message.Message):
-    __slots__ = ["vectors", "k", "allowed_ids", "include_embeddings", "segment_id", "collection_id", "version_context"]
+    __slots__ = ["vectors", "k", "allowed_ids", "include_embeddings", "segment_id", "collection_id", "version_context", "explain", "accumulation", "max_distance", "min_score", "time_limit_ms"]
     VECTORS_FIELD_NUMBER: _ClassVar[int]
     K_FIELD_NUMBER: _ClassVar[int]
     ALLOWED_IDS_FIELD_NUMBER: _ClassVar[int]
@@ -419,6 +841,11 @@ class QueryVectorsRequest(_message.Message):
     SEGMENT_ID_FIELD_NUMBER: _ClassVar[int]
     COLLECTION_ID_FIELD_NUMBER: _ClassVar[int]
     VERSION_CONTEXT_FIELD_NUMBER: _ClassVar[int]
+    EXPLAIN_FIELD_NUMBER: _ClassVar[int]
+    ACCUMULATION_FIELD_NUMBER: _ClassVar[int]
+    MAX_DISTANCE_FIELD_NUMBER: _ClassVar[int]
+    MIN_SCORE_FIELD_NUMBER: _ClassVar[int]
+    TIME_LIMIT_MS_FIELD_NUMBER: _ClassVar[int]
     vectors: _containers.RepeatedCompositeFieldContainer[Vector]
     k: int
     allowed_ids: _containers.RepeatedScalarFieldContainer[str]
@@ -426,13 +853,24 @@ class QueryVectorsRequest(_message.Message):
     segment_id: str
     collection_id: str
     version_context: RequestVersionContext
-    def __init__(self, vectors: _Optional[_Iterable[_Union[Vector, _Mapping]]] = ..., k: _Optional[int] = ..., allowed_ids: _Optional[_Iterable[str]] = ..., include_embeddings: bool = ..., segment_id: _Optional[str] = ..., collection_id: _Optional[str] = ..., version_context: _Optional[_Union[RequestVersionContext, _Mapping]] = ...) -> None: ...
+    explain: bool
+    accumulation: DistanceAccumulation
+    max_distance: float
+    min_score: float
+    time_limit_ms: int
+    def __init__(self, vectors: _Optional[_Iterable[_Union[Vector, _Mapping]]] = ..., k: _Optional[int] = ..., allowed_ids: _Optional[_Iterable[str]] = ..., include_embeddings: bool = ..., segment_id: _Optional[str] = ..., collection_id: _Optional[str] = ..., version_context: _Optional[_Union[RequestVersionContext, _Mapping]] = ..., explain: bool = ..., accumulation: _Optional[_Union[DistanceAccumulation, str]] = ..., max_distance: _Optional[float] = ..., min_score: _Optional[float] = ..., time_limit_ms: _Optional[int] = ...) -> None: ...
 
 class QueryVectorsResponse(_message.Message):
-    __slots__ = ["results"]
+    __slots__ = ["results", "degraded", "plan", "truncated"]
     RESULTS_FIELD_NUMBER: _ClassVar[int]
+    DEGRADED_FIELD_NUMBER: _ClassVar[int]
+    PLAN_FIELD_NUMBER: _ClassVar[int]
+    TRUNCATED_FIELD_NUMBER: _ClassVar[int]
     results: _containers.RepeatedCompositeFieldContainer[VectorQueryResults]
-    def __init__(self, results: _Optional[_Iterable[_Union[VectorQueryResults, _Mapping]]] = ...) -> None: ...
+    degraded: bool
+    plan: QueryPlan
+    truncated: bool
+    def __init__(self, results: _Optional[_Iterable[_Union[VectorQueryResults, _Mapping]]] = ..., degraded: bool = ..., plan: _Optional[_Union[QueryPlan, _Mapping]] = ..., truncated: bool = ...) -> None: ...
 
 class VectorQueryResults(_message.Message):
     __slots__ = ["results"]
@@ -449,3 +887,25 @@ class VectorQueryResult(_message.Message):
     distance: float
     vector: Vector
     def __init__(self, id: _Optional[str] = ..., distance: _Optional[float] = ..., vector: _Optional[_Union[Vector, _Mapping]] = ...) -> None: ...
+
+class SignedRoaringBitmap(_message.Message):
+    __slots__ = ["include", "exclude"]
+    INCLUDE_FIELD_NUMBER: _ClassVar[int]
+    EXCLUDE_FIELD_NUMBER: _ClassVar[int]
+    include: bytes
+    exclude: bytes
+    def __init__(self, include: _Optional[bytes] = ..., exclude: _Optional[bytes] = ...) -> None: ...
+
+class FilterOutput(_message.Message):
+    __slots__ = ["log_offset_ids", "compact_offset_ids"]
+    LOG_OFFSET_IDS_FIELD_NUMBER: _ClassVar[int]
+    COMPACT_OFFSET_IDS_FIELD_NUMBER: _ClassVar[int]
+    log_offset_ids: SignedRoaringBitmap
+    compact_offset_ids: SignedRoaringBitmap
+    def __init__(self, log_offset_ids: _Optional[_Union[SignedRoaringBitmap, _Mapping]] = ..., compact_offset_ids: _Optional[_Union[SignedRoaringBitmap, _Mapping]] = ...) -> None: ...
+
+class LimitOutput(_message.Message):
+    __slots__ = ["offset_ids"]
+    OFFSET_IDS_FIELD_NUMBER: _ClassVar[int]
+    offset_ids: bytes
+    def __init__(self, offset_ids: _Optional[bytes] = ...) -> None: ...
@@ -26,6 +26,21 @@ class MetadataReaderStub(object):
                 request_serializer=chromadb_dot_proto_dot_chroma__pb2.CountRecordsRequest.SerializeToString,
                 response_deserializer=chromadb_dot_proto_dot_chroma__pb2.CountRecordsResponse.FromString,
                 )
+        self.GetEmbeddings = channel.unary_unary(
+                '/chroma.MetadataReader/GetEmbeddings',
+                request_serializer=chromadb_dot_proto_dot_chroma__pb2.GetEmbeddingsRequest.SerializeToString,
+                response_deserializer=chromadb_dot_proto_dot_chroma__pb2.GetEmbeddingsResponse.FromString,
+                )
+        self.StreamMetadata = channel.unary_stream(
+                '/chroma.MetadataReader/StreamMetadata',
+                request_serializer=chromadb_dot_proto_dot_chroma__pb2.QueryMetadataRequest.SerializeToString,
+                response_deserializer=chromadb_dot_proto_dot_chroma__pb2.QueryMetadataResponse.FromString,
+                )
+        self.StreamChanges = channel.unary_stream(
+                '/chroma.MetadataReader/StreamChanges',
+                request_serializer=chromadb_dot_proto_dot_chroma__pb2.StreamChangesRequest.SerializeToString,
+                response_deserializer=chromadb_dot_proto_dot_chroma__pb2.StreamChangesResponse.FromString,
+                )
 
 
 class MetadataReaderServicer(object):
@@ -45,6 +60,28 @@ class MetadataReaderServicer(object):
         context.set_details('Method not implemented!')
         raise NotImplementedError('Method not implemented!')
 
+    def GetEmbeddings(self, request, context):
+        """Missing associated documentation comment in .proto file."""
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
+    def StreamMetadata(self, request, context):
+        """Evaluates the same query as `QueryMetadata`, and streams its records in batches as they
+        are read instead of returning them all at once.
+        """
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
+    def StreamChanges(self, request, context):
+        """Streams the changes that the log of a collection makes to its records from a log offset
+        on, so that downstream systems can mirror the collection without polling `QueryMetadata`.
+        """
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
 
 def add_MetadataReaderServicer_to_server(servicer, server):
     rpc_method_handlers = {
@@ -58,6 +95,21 @@ def add_MetadataReaderServicer_to_server(servicer, server):
                     request_deserializer=chromadb_dot_proto_dot_chroma__pb2.CountRecordsRequest.FromString,
                     response_serializer=chromadb_dot_proto_dot_chroma__pb2.CountRecordsResponse.SerializeToString,
             ),
+            'GetEmbeddings': grpc.unary_unary_rpc_method_handler(
+                    servicer.GetEmbeddings,
+                    request_deserializer=chromadb_dot_proto_dot_chroma__pb2.GetEmbeddingsRequest.FromString,
+                    response_serializer=chromadb_dot_proto_dot_chroma__pb2.GetEmbeddingsResponse.SerializeToString,
+            ),
+            'StreamMetadata': grpc.unary_stream_rpc_method_handler(
+                    servicer.StreamMetadata,
+                    request_deserializer=chromadb_dot_proto_dot_chroma__pb2.QueryMetadataRequest.FromString,
+                    response_serializer=chromadb_dot_proto_dot_chroma__pb2.QueryMetadataResponse.SerializeToString,
+            ),
+            'StreamChanges': grpc.unary_stream_rpc_method_handler(
+                    servicer.StreamChanges,
+                    request_deserializer=chromadb_dot_proto_dot_chroma__pb2.StreamChangesRequest.FromString,
+                    response_serializer=chromadb_dot_proto_dot_chroma__pb2.StreamChangesResponse.SerializeToString,
+            ),
     }
     generic_handler = grpc.method_handlers_generic_handler(
             'chroma.MetadataReader', rpc_method_handlers)
@@ -104,6 +156,389 @@ class MetadataReader(object):
             options, channel_credentials,
             insecure, call_credentials, compression, wait_for_ready, timeout, metadata)
 
+    @staticmethod
+    def GetEmbeddings(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_unary(request, target, '/chroma.MetadataReader/GetEmbeddings',
+            chromadb_dot_proto_dot_chroma__pb2.GetEmbeddingsRequest.SerializeToString,
+            chromadb_dot_proto_dot_chroma__pb2.GetEmbeddingsResponse.FromString,
+            options, channel_credentials,
+            insecure, call_credentials, compression, wait_for_ready, timeout, metadata)
+
+    @staticmethod
+    def StreamMetadata(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_stream(request, target, '/chroma.MetadataReader/StreamMetadata',
+            chromadb_dot_proto_dot_chroma__pb2.QueryMetadataRequest.SerializeToString,
+            chromadb_dot_proto_dot_chroma__pb2.QueryMetadataResponse.FromString,
+            options, channel_credentials,
+            insecure, call_credentials, compression, wait_for_ready, timeout, metadata)
+
+    @staticmethod
+    def StreamChanges(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_stream(request, target, '/chroma.MetadataReader/StreamChanges',
+            chromadb_dot_proto_dot_chroma__pb2.StreamChangesRequest.SerializeToString,
+            chromadb_dot_proto_dot_chroma__pb2.StreamChangesResponse.FromString,
+            options, channel_credentials,
+            insecure, call_credentials, compression, wait_for_ready, timeout, metadata)
+
+
+class QueryAdminStub(object):
+    """Query Admin Interface 
+
+    """
+
+    def __init__(self, channel):
+        """Constructor.
+
+        Args:
+            channel: A grpc.Channel.
+        """
+        self.WarmUpCollection = channel.unary_unary(
+                '/chroma.QueryAdmin/WarmUpCollection',
+                request_serializer=chromadb_dot_proto_dot_chroma__pb2.WarmUpCollectionRequest.SerializeToString,
+                response_deserializer=chromadb_dot_proto_dot_chroma__pb2.WarmUpCollectionResponse.FromString,
+                )
+        self.ExportCollectionIndexes = channel.unary_unary(
+                '/chroma.QueryAdmin/ExportCollectionIndexes',
+                request_serializer=chromadb_dot_proto_dot_chroma__pb2.ExportCollectionIndexesRequest.SerializeToString,
+                response_deserializer=chromadb_dot_proto_dot_chroma__pb2.ExportCollectionIndexesResponse.FromString,
+                )
+        self.CheckCollectionIntegrity = channel.unary_unary(
+                '/chroma.QueryAdmin/CheckCollectionIntegrity',
+                request_serializer=chromadb_dot_proto_dot_chroma__pb2.CheckCollectionIntegrityRequest.SerializeToString,
+                response_deserializer=chromadb_dot_proto_dot_chroma__pb2.CheckCollectionIntegrityResponse.FromString,
+                )
+        self.VerifyCollectionChecksum = channel.unary_unary(
+                '/chroma.QueryAdmin/VerifyCollectionChecksum',
+                request_serializer=chromadb_dot_proto_dot_chroma__pb2.VerifyCollectionChecksumRequest.SerializeToString,
+                response_deserializer=chromadb_dot_proto_dot_chroma__pb2.VerifyCollectionChecksumResponse.FromString,
+                )
+        self.ListSlowQueries = channel.unary_unary(
+                '/chroma.QueryAdmin/ListSlowQueries',
+                request_serializer=chromadb_dot_proto_dot_chroma__pb2.ListSlowQueriesRequest.SerializeToString,
+                response_deserializer=chromadb_dot_proto_dot_chroma__pb2.ListSlowQueriesResponse.FromString,
+                )
+        self.ListCachedBlocks = channel.unary_unary(
+                '/chroma.QueryAdmin/ListCachedBlocks',
+                request_serializer=chromadb_dot_proto_dot_chroma__pb2.ListCachedBlocksRequest.SerializeToString,
+                response_deserializer=chromadb_dot_proto_dot_chroma__pb2.ListCachedBlocksResponse.FromString,
+                )
+
+
+class QueryAdminServicer(object):
+    """Query Admin Interface 
+
+    """
+
+    def WarmUpCollection(self, request, context):
+        """Missing associated documentation comment in .proto file."""
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
+    def ExportCollectionIndexes(self, request, context):
+        """Missing associated documentation comment in .proto file."""
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
+    def CheckCollectionIntegrity(self, request, context):
+        """Missing associated documentation comment in .proto file."""
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
+    def VerifyCollectionChecksum(self, request, context):
+        """Missing associated documentation comment in .proto file."""
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
+    def ListSlowQueries(self, request, context):
+        """Missing associated documentation comment in .proto file."""
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
+    def ListCachedBlocks(self, request, context):
+        """Missing associated documentation comment in .proto file."""
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
+
+def add_QueryAdminServicer_to_server(servicer, server):
+    rpc_method_handlers = {
+            'WarmUpCollection': grpc.unary_unary_rpc_method_handler(
+                    servicer.WarmUpCollection,
+                    request_deserializer=chromadb_dot_proto_dot_chroma__pb2.WarmUpCollectionRequest.FromString,
+                    response_serializer=chromadb_dot_proto_dot_chroma__pb2.WarmUpCollectionResponse.SerializeToString,
+            ),
+            'ExportCollectionIndexes': grpc.unary_unary_rpc_method_handler(
+                    servicer.ExportCollectionIndexes,
+                    request_deserializer=chromadb_dot_proto_dot_chroma__pb2.ExportCollectionIndexesRequest.FromString,
+                    response_serializer=chromadb_dot_proto_dot_chroma__pb2.ExportCollectionIndexesResponse.SerializeToString,
+            ),
+            'CheckCollectionIntegrity': grpc.unary_unary_rpc_method_handler(
+                    servicer.CheckCollectionIntegrity,
+                    request_deserializer=chromadb_dot_proto_dot_chroma__pb2.CheckCollectionIntegrityRequest.FromString,
+                    response_serializer=chromadb_dot_proto_dot_chroma__pb2.CheckCollectionIntegrityResponse.SerializeToString,
+            ),
+            'VerifyCollectionChecksum': grpc.unary_unary_rpc_method_handler(
+                    servicer.VerifyCollectionChecksum,
+                    request_deserializer=chromadb_dot_proto_dot_chroma__pb2.VerifyCollectionChecksumRequest.FromString,
+                    response_serializer=chromadb_dot_proto_dot_chroma__pb2.VerifyCollectionChecksumResponse.SerializeToString,
+            ),
+            'ListSlowQueries': grpc.unary_unary_rpc_method_handler(
+                    servicer.ListSlowQueries,
+                    request_deserializer=chromadb_dot_proto_dot_chroma__pb2.ListSlowQueriesRequest.FromString,
+                    response_serializer=chromadb_dot_proto_dot_chroma__pb2.ListSlowQueriesResponse.SerializeToString,
+            ),
+            'ListCachedBlocks': grpc.unary_unary_rpc_method_handler(
+                    servicer.ListCachedBlocks,
+                    request_deserializer=chromadb_dot_proto_dot_chroma__pb2.ListCachedBlocksRequest.FromString,
+                    response_serializer=chromadb_dot_proto_dot_chroma__pb2.ListCachedBlocksResponse.SerializeToString,
+            ),
+    }
+    generic_handler = grpc.method_handlers_generic_handler(
+            'chroma.QueryAdmin', rpc_method_handlers)
+    server.add_generic_rpc_handlers((generic_handler,))
+
+
+ # This class is part of an EXPERIMENTAL API.
+class QueryAdmin(object):
+    """Query Admin Interface 
+
+    """
+
+    @staticmethod
+    def WarmUpCollection(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_unary(request, target, '/chroma.QueryAdmin/WarmUpCollection',
+            chromadb_dot_proto_dot_chroma__pb2.WarmUpCollectionRequest.SerializeToString,
+            chromadb_dot_proto_dot_chroma__pb2.WarmUpCollectionResponse.FromString,
+            options, channel_credentials,
+            insecure, call_credentials, compression, wait_for_ready, timeout, metadata)
+
+    @staticmethod
+    def ExportCollectionIndexes(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_unary(request, target, '/chroma.QueryAdmin/ExportCollectionIndexes',
+            chromadb_dot_proto_dot_chroma__pb2.ExportCollectionIndexesRequest.SerializeToString,
+            chromadb_dot_proto_dot_chroma__pb2.ExportCollectionIndexesResponse.FromString,
+            options, channel_credentials,
+            insecure, call_credentials, compression, wait_for_ready, timeout, metadata)
+
+    @staticmethod
+    def CheckCollectionIntegrity(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_unary(request, target, '/chroma.QueryAdmin/CheckCollectionIntegrity',
+            chromadb_dot_proto_dot_chroma__pb2.CheckCollectionIntegrityRequest.SerializeToString,
+            chromadb_dot_proto_dot_chroma__pb2.CheckCollectionIntegrityResponse.FromString,
+            options, channel_credentials,
+            insecure, call_credentials, compression, wait_for_ready, timeout, metadata)
+
+    @staticmethod
+    def VerifyCollectionChecksum(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_unary(request, target, '/chroma.QueryAdmin/VerifyCollectionChecksum',
+            chromadb_dot_proto_dot_chroma__pb2.VerifyCollectionChecksumRequest.SerializeToString,
+            chromadb_dot_proto_dot_chroma__pb2.VerifyCollectionChecksumResponse.FromString,
+            options, channel_credentials,
+            insecure, call_credentials, compression, wait_for_ready, timeout, metadata)
+
+    @staticmethod
+    def ListSlowQueries(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_unary(request, target, '/chroma.QueryAdmin/ListSlowQueries',
+            chromadb_dot_proto_dot_chroma__pb2.ListSlowQueriesRequest.SerializeToString,
+            chromadb_dot_proto_dot_chroma__pb2.ListSlowQueriesResponse.FromString,
+            options, channel_credentials,
+            insecure, call_credentials, compression, wait_for_ready, timeout, metadata)
+
+    @staticmethod
+    def ListCachedBlocks(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_unary(request, target, '/chroma.QueryAdmin/ListCachedBlocks',
+            chromadb_dot_proto_dot_chroma__pb2.ListCachedBlocksRequest.SerializeToString,
+            chromadb_dot_proto_dot_chroma__pb2.ListCachedBlocksResponse.FromString,
+            options, channel_credentials,
+            insecure, call_credentials, compression, wait_for_ready, timeout, metadata)
+
+
+class CompactionAdminStub(object):
+    """Compaction Admin Interface 
+
+    """
+
+    def __init__(self, channel):
+        """Constructor.
+
+        Args:
+            channel: A grpc.Channel.
+        """
+        self.PauseCompaction = channel.unary_unary(
+                '/chroma.CompactionAdmin/PauseCompaction',
+                request_serializer=chromadb_dot_proto_dot_chroma__pb2.PauseCompactionRequest.SerializeToString,
+                response_deserializer=chromadb_dot_proto_dot_chroma__pb2.PauseCompactionResponse.FromString,
+                )
+        self.ResumeCompaction = channel.unary_unary(
+                '/chroma.CompactionAdmin/ResumeCompaction',
+                request_serializer=chromadb_dot_proto_dot_chroma__pb2.ResumeCompactionRequest.SerializeToString,
+                response_deserializer=chromadb_dot_proto_dot_chroma__pb2.ResumeCompactionResponse.FromString,
+                )
+
+
+class CompactionAdminServicer(object):
+    """Compaction Admin Interface 
+
+    """
+
+    def PauseCompaction(self, request, context):
+        """Missing associated documentation comment in .proto file."""
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
+    def ResumeCompaction(self, request, context):
+        """Missing associated documentation comment in .proto file."""
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
+
+def add_CompactionAdminServicer_to_server(servicer, server):
+    rpc_method_handlers = {
+            'PauseCompaction': grpc.unary_unary_rpc_method_handler(
+                    servicer.PauseCompaction,
+                    request_deserializer=chromadb_dot_proto_dot_chroma__pb2.PauseCompactionRequest.FromString,
+                    response_serializer=chromadb_dot_proto_dot_chroma__pb2.PauseCompactionResponse.SerializeToString,
+            ),
+            'ResumeCompaction': grpc.unary_unary_rpc_method_handler(
+                    servicer.ResumeCompaction,
+                    request_deserializer=chromadb_dot_proto_dot_chroma__pb2.ResumeCompactionRequest.FromString,
+                    response_serializer=chromadb_dot_proto_dot_chroma__pb2.ResumeCompactionResponse.SerializeToString,
+            ),
+    }
+    generic_handler = grpc.method_handlers_generic_handler(
+            'chroma.CompactionAdmin', rpc_method_handlers)
+    server.add_generic_rpc_handlers((generic_handler,))
+
+
+ # This class is part of an EXPERIMENTAL API.
+class CompactionAdmin(object):
+    """Compaction Admin Interface 
+
+    """
+
+    @staticmethod
+    def PauseCompaction(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_unary(request, target, '/chroma.CompactionAdmin/PauseCompaction',
+            chromadb_dot_proto_dot_chroma__pb2.PauseCompactionRequest.SerializeToString,
+            chromadb_dot_proto_dot_chroma__pb2.PauseCompactionResponse.FromString,
+            options, channel_credentials,
+            insecure, call_credentials, compression, wait_for_ready, timeout, metadata)
+
+    @staticmethod
+    def ResumeCompaction(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_unary(request, target, '/chroma.CompactionAdmin/ResumeCompaction',
+            chromadb_dot_proto_dot_chroma__pb2.ResumeCompactionRequest.SerializeToString,
+            chromadb_dot_proto_dot_chroma__pb2.ResumeCompactionResponse.FromString,
+            options, channel_credentials,
+            insecure, call_credentials, compression, wait_for_ready, timeout, metadata)
+
 
 class VectorReaderStub(object):
     """Vector Reader Interface 
@@ -36,10 +36,12 @@ class GetDatabaseResponse(_message.Message):
     def __init__(self, database: _Optional[_Union[_chroma_pb2.Database, _Mapping]] = ...) -> None: ...
 
 class CreateTenantRequest(_message.Message):
-    __slots__ = ["name"]
+    __slots__ = ["name", "kms_key_id"]
     NAME_FIELD_NUMBER: _ClassVar[int]
+    KMS_KEY_ID_FIELD_NUMBER: _ClassVar[int]
     name: str
-    def __init__(self, name: _Optional[str] = ...) -> None: ...
+    kms_key_id: str
+    def __init__(self, name: _Optional[str] = ..., kms_key_id: _Optional[str] = ...) -> None: ...
 
 class CreateTenantResponse(_message.Message):
     __slots__ = []
@@ -230,7 +232,7 @@ class SetLastCompactionTimeForTenantRequest(_message.Message):
     def __init__(self, tenant_last_compaction_time: _Optional[_Union[TenantLastCompactionTime, _Mapping]] = ...) -> None: ...
 
 class FlushSegmentCompactionInfo(_message.Message):
-    __slots__ = ["segment_id", "file_paths"]
+    __slots__ = ["segment_id", "file_paths", "metadata"]
     class FilePathsEntry(_message.Message):
         __slots__ = ["key", "value"]
         KEY_FIELD_NUMBER: _ClassVar[int]
@@ -240,9 +242,11 @@ class FlushSegmentCompactionInfo(_message.Message):
         def __init__(self, key: _Optional[str] = ..., value: _Optional[_Union[_chroma_pb2.FilePaths, _Mapping]] = ...) -> None: ...
     SEGMENT_ID_FIELD_NUMBER: _ClassVar[int]
     FILE_PATHS_FIELD_NUMBER: _ClassVar[int]
+    METADATA_FIELD_NUMBER: _ClassVar[int]
     segment_id: str
     file_paths: _containers.MessageMap[str, _chroma_pb2.FilePaths]
-    def __init__(self, segment_id: _Optional[str] = ..., file_paths: _Optional[_Mapping[str, _chroma_pb2.FilePaths]] = ...) -> None: ...
+    metadata: _chroma_pb2.UpdateMetadata
+    def __init__(self, segment_id: _Optional[str] = ..., file_paths: _Optional[_Mapping[str, _chroma_pb2.FilePaths]] = ..., metadata: _Optional[_Union[_chroma_pb2.UpdateMetadata, _Mapping]] = ...) -> None: ...
 
 class FlushCollectionCompactionRequest(_message.Message):
     __slots__ = ["tenant_id", "collection_id", "log_position", "collection_version", "segment_compaction_info"]
@@ -267,3 +271,35 @@ class FlushCollectionCompactionResponse(_message.Message):
     collection_version: int
     last_compaction_time: int
     def __init__(self, collection_id: _Optional[str] = ..., collection_version: _Optional[int] = ..., last_compaction_time: _Optional[int] = ...) -> None: ...
+
+class SetCollectionAliasRequest(_message.Message):
+    __slots__ = ["tenant", "database", "name", "collection_id"]
+    TENANT_FIELD_NUMBER: _ClassVar[int]
+    DATABASE_FIELD_NUMBER: _ClassVar[int]
+    NAME_FIELD_NUMBER: _ClassVar[int]
+    COLLECTION_ID_FIELD_NUMBER: _ClassVar[int]
+    tenant: str
+    database: str
+    name: str
+    collection_id: str
+    def __init__(self, tenant: _Optional[str] = ..., database: _Optional[str] = ..., name: _Optional[str] = ..., collection_id: _Optional[str] = ...) -> None: ...
+
+class SetCollectionAliasResponse(_message.Message):
+    __slots__ = []
+    def __init__(self) -> None: ...
+
+class GetCollectionAliasRequest(_message.Message):
+    __slots__ = ["tenant", "database", "name"]
+    TENANT_FIELD_NUMBER: _ClassVar[int]
+    DATABASE_FIELD_NUMBER: _ClassVar[int]
+    NAME_FIELD_NUMBER: _ClassVar[int]
+    tenant: str
+    database: str
+    name: str
+    def __init__(self, tenant: _Optional[str] = ..., database: _Optional[str] = ..., name: _Optional[str] = ...) -> None: ...
+
+class GetCollectionAliasResponse(_message.Message):
+    __slots__ = ["collection_id"]
+    COLLECTION_ID_FIELD_NUMBER: _ClassVar[int]
+    collection_id: str
+    def __init__(self, collection_id: _Optional[str] = ...) -> None: ...
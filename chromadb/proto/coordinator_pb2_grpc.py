@@ -95,6 +95,16 @@ class SysDBStub(object):
                 request_serializer=chromadb_dot_proto_dot_coordinator__pb2.FlushCollectionCompactionRequest.SerializeToString,
                 response_deserializer=chromadb_dot_proto_dot_coordinator__pb2.FlushCollectionCompactionResponse.FromString,
                 )
+        self.SetCollectionAlias = channel.unary_unary(
+                '/chroma.SysDB/SetCollectionAlias',
+                request_serializer=chromadb_dot_proto_dot_coordinator__pb2.SetCollectionAliasRequest.SerializeToString,
+                response_deserializer=chromadb_dot_proto_dot_coordinator__pb2.SetCollectionAliasResponse.FromString,
+                )
+        self.GetCollectionAlias = channel.unary_unary(
+                '/chroma.SysDB/GetCollectionAlias',
+                request_serializer=chromadb_dot_proto_dot_coordinator__pb2.GetCollectionAliasRequest.SerializeToString,
+                response_deserializer=chromadb_dot_proto_dot_coordinator__pb2.GetCollectionAliasResponse.FromString,
+                )
 
 
 class SysDBServicer(object):
@@ -196,6 +206,18 @@ class SysDBServicer(object):
         context.set_details('Method not implemented!')
         raise NotImplementedError('Method not implemented!')
 
+    def SetCollectionAlias(self, request, context):
+        """Missing associated documentation comment in .proto file."""
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
+    def GetCollectionAlias(self, request, context):
+        """Missing associated documentation comment in .proto file."""
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
 
 def add_SysDBServicer_to_server(servicer, server):
     rpc_method_handlers = {
@@ -279,6 +301,16 @@ def add_SysDBServicer_to_server(servicer, server):
                     request_deserializer=chromadb_dot_proto_dot_coordinator__pb2.FlushCollectionCompactionRequest.FromString,
                     response_serializer=chromadb_dot_proto_dot_coordinator__pb2.FlushCollectionCompactionResponse.SerializeToString,
             ),
+            'SetCollectionAlias': grpc.unary_unary_rpc_method_handler(
+                    servicer.SetCollectionAlias,
+                    request_deserializer=chromadb_dot_proto_dot_coordinator__pb2.SetCollectionAliasRequest.FromString,
+                    response_serializer=chromadb_dot_proto_dot_coordinator__pb2.SetCollectionAliasResponse.SerializeToString,
+            ),
+            'GetCollectionAlias': grpc.unary_unary_rpc_method_handler(
+                    servicer.GetCollectionAlias,
+                    request_deserializer=chromadb_dot_proto_dot_coordinator__pb2.GetCollectionAliasRequest.FromString,
+                    response_serializer=chromadb_dot_proto_dot_coordinator__pb2.GetCollectionAliasResponse.SerializeToString,
+            ),
     }
     generic_handler = grpc.method_handlers_generic_handler(
             'chroma.SysDB', rpc_method_handlers)
@@ -560,3 +592,37 @@ class SysDB(object):
             chromadb_dot_proto_dot_coordinator__pb2.FlushCollectionCompactionResponse.FromString,
             options, channel_credentials,
             insecure, call_credentials, compression, wait_for_ready, timeout, metadata)
+
+    @staticmethod
+    def SetCollectionAlias(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_unary(request, target, '/chroma.SysDB/SetCollectionAlias',
+            chromadb_dot_proto_dot_coordinator__pb2.SetCollectionAliasRequest.SerializeToString,
+            chromadb_dot_proto_dot_coordinator__pb2.SetCollectionAliasResponse.FromString,
+            options, channel_credentials,
+            insecure, call_credentials, compression, wait_for_ready, timeout, metadata)
+
+    @staticmethod
+    def GetCollectionAlias(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_unary(request, target, '/chroma.SysDB/GetCollectionAlias',
+            chromadb_dot_proto_dot_coordinator__pb2.GetCollectionAliasRequest.SerializeToString,
+            chromadb_dot_proto_dot_coordinator__pb2.GetCollectionAliasResponse.FromString,
+            options, channel_credentials,
+            insecure, call_credentials, compression, wait_for_ready, timeout, metadata)
@@ -47,14 +47,18 @@ class PullLogsResponse(_message.Message):
     def __init__(self, records: _Optional[_Iterable[_Union[LogRecord, _Mapping]]] = ...) -> None: ...
 
 class CollectionInfo(_message.Message):
-    __slots__ = ["collection_id", "first_log_offset", "first_log_ts"]
+    __slots__ = ["collection_id", "first_log_offset", "first_log_ts", "num_records", "num_bytes"]
     COLLECTION_ID_FIELD_NUMBER: _ClassVar[int]
     FIRST_LOG_OFFSET_FIELD_NUMBER: _ClassVar[int]
     FIRST_LOG_TS_FIELD_NUMBER: _ClassVar[int]
+    NUM_RECORDS_FIELD_NUMBER: _ClassVar[int]
+    NUM_BYTES_FIELD_NUMBER: _ClassVar[int]
     collection_id: str
     first_log_offset: int
     first_log_ts: int
-    def __init__(self, collection_id: _Optional[str] = ..., first_log_offset: _Optional[int] = ..., first_log_ts: _Optional[int] = ...) -> None: ...
+    num_records: int
+    num_bytes: int
+    def __init__(self, collection_id: _Optional[str] = ..., first_log_offset: _Optional[int] = ..., first_log_ts: _Optional[int] = ..., num_records: _Optional[int] = ..., num_bytes: _Optional[int] = ...) -> None: ...
 
 class GetAllCollectionInfoToCompactRequest(_message.Message):
     __slots__ = ["min_compaction_size"]
@@ -141,9 +141,9 @@ def _segment(type: SegmentType, scope: SegmentScope, collection: Collection) ->
     # For the segment types with python implementations, we can propagate metadata
     if type in SEGMENT_TYPE_IMPLS:
         cls = get_class(SEGMENT_TYPE_IMPLS[type], SegmentImplementation)
-        collection_metadata = collection.metadata
-        if collection_metadata:
-            metadata = cls.propagate_collection_metadata(collection_metadata)
+        # Segments can set defaults of their own, such as the number of metadata key buckets
+        collection_metadata = collection.metadata or {}
+        metadata = cls.propagate_collection_metadata(collection_metadata) or None
 
     return Segment(
         id=uuid4(),
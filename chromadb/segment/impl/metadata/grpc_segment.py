@@ -4,7 +4,7 @@ from chromadb.proto.utils import RetryOnRpcErrorClientInterceptor
 from chromadb.segment import MetadataReader
 from chromadb.config import System
 from chromadb.errors import InvalidArgumentError, VersionMismatchError
-from chromadb.types import Segment, RequestVersionContext
+from chromadb.types import Metadata, Segment, RequestVersionContext
 from overrides import override
 from chromadb.telemetry.opentelemetry import (
     OpenTelemetryGranularity,
@@ -21,6 +21,12 @@ import chromadb.proto.chroma_pb2 as pb
 import grpc
 
 
+# The number of buckets that the metadata keys of a new segment are spread over, unless
+# the collection sets it with `metadata:key_buckets`. Must match the bounds of the worker.
+DEFAULT_METADATA_KEY_BUCKETS = 8
+MAX_METADATA_KEY_BUCKETS = 64
+
+
 class GrpcMetadataSegment(MetadataReader):
     """Embedding Metadata segment interface"""
 
@@ -73,6 +79,20 @@ class GrpcMetadataSegment(MetadataReader):
     def delete(self, where: Optional[Where] = None) -> None:
         raise NotImplementedError()
 
+    @staticmethod
+    @override
+    def propagate_collection_metadata(metadata: Metadata) -> Optional[Metadata]:
+        key_buckets = metadata.get("metadata:key_buckets", DEFAULT_METADATA_KEY_BUCKETS)
+        if (
+            not isinstance(key_buckets, int)
+            or isinstance(key_buckets, bool)
+            or not 1 <= key_buckets <= MAX_METADATA_KEY_BUCKETS
+        ):
+            raise InvalidArgumentError(
+                f"metadata:key_buckets must be an integer between 1 and {MAX_METADATA_KEY_BUCKETS}, got {key_buckets}"
+            )
+        return {"metadata:key_buckets": key_buckets}
+
     @override
     def max_seqid(self) -> int:
         raise NotImplementedError()
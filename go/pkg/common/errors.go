@@ -24,6 +24,10 @@ var (
 	ErrCollectionVersionStale                = errors.New("collection version stale")
 	ErrCollectionVersionInvalid              = errors.New("collection version invalid")
 
+	// Collection alias errors
+	ErrCollectionAliasNotFound  = errors.New("collection alias not found")
+	ErrCollectionAliasNameEmpty = errors.New("collection alias name is empty")
+
 	// Collection metadata errors
 	ErrUnknownCollectionMetadataType = errors.New("collection metadata value type not supported")
 	ErrInvalidMetadataUpdate         = errors.New("invalid metadata update, reest metadata true and metadata value not empty")
@@ -430,6 +430,9 @@ func (suite *LogServerTestSuite) TestRecordLogDb_PushLogs() {
 					if actualCompactionSize < requestMinCompactionSize {
 						t.Fatalf("compaction size %d is less than request min compaction size %d", actualCompactionSize, requestMinCompactionSize)
 					}
+					if collection.NumRecords != actualCompactionSize {
+						t.Fatalf("collection has %d records to compact, expected %d", collection.NumRecords, actualCompactionSize)
+					}
 				}
 
 				// Verify that the length of the results is correct
@@ -86,6 +86,8 @@ func (s *logServer) GetAllCollectionInfoToCompact(ctx context.Context, req *logs
 			CollectionId:   collectionToCompact[index].CollectionID,
 			FirstLogOffset: collectionToCompact[index].Offset,
 			FirstLogTs:     int64(collectionToCompact[index].Timestamp),
+			NumRecords:     uint64(collectionToCompact[index].NumRecords),
+			NumBytes:       uint64(collectionToCompact[index].NumBytes),
 		}
 	}
 	return
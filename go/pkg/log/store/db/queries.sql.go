@@ -53,13 +53,15 @@ func (q *Queries) GetAllCollections(ctx context.Context) ([]string, error) {
 
 const getAllCollectionsToCompact = `-- name: GetAllCollectionsToCompact :many
 with summary as (
-    select r.collection_id, r.offset, r.timestamp, row_number() over(partition by r.collection_id order by r.offset) as rank
+    select r.collection_id, r.offset, r.timestamp, row_number() over(partition by r.collection_id order by r.offset) as rank,
+    count(*) over(partition by r.collection_id) as num_records,
+    CAST(sum(octet_length(r.record)) over(partition by r.collection_id) AS bigint) as num_bytes
     from record_log r, collection c
     where r.collection_id = c.id
     and (c.record_enumeration_offset_position - c.record_compaction_offset_position) >= $1
     and r.offset > c.record_compaction_offset_position
 )
-select collection_id, "offset", timestamp, rank from summary
+select collection_id, "offset", timestamp, rank, num_records, num_bytes from summary
 where rank=1
 order by timestamp
 `
@@ -69,6 +71,8 @@ type GetAllCollectionsToCompactRow struct {
 	Offset       int64
 	Timestamp    int64
 	Rank         int64
+	NumRecords   int64
+	NumBytes     int64
 }
 
 func (q *Queries) GetAllCollectionsToCompact(ctx context.Context, minCompactionSize int64) ([]GetAllCollectionsToCompactRow, error) {
@@ -85,6 +89,8 @@ func (q *Queries) GetAllCollectionsToCompact(ctx context.Context, minCompactionS
 			&i.Offset,
 			&i.Timestamp,
 			&i.Rank,
+			&i.NumRecords,
+			&i.NumBytes,
 		); err != nil {
 			return nil, err
 		}
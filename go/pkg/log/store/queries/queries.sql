@@ -12,7 +12,9 @@ SELECT * FROM record_log r WHERE r.collection_id = $1 AND r.offset >= $2 and r.t
 
 -- name: GetAllCollectionsToCompact :many
 with summary as (
-    select r.collection_id, r.offset, r.timestamp, row_number() over(partition by r.collection_id order by r.offset) as rank
+    select r.collection_id, r.offset, r.timestamp, row_number() over(partition by r.collection_id order by r.offset) as rank,
+    count(*) over(partition by r.collection_id) as num_records,
+    CAST(sum(octet_length(r.record)) over(partition by r.collection_id) AS bigint) as num_bytes
     from record_log r, collection c
     where r.collection_id = c.id
     and (c.record_enumeration_offset_position - c.record_compaction_offset_position) >= sqlc.arg(min_compaction_size)
@@ -172,13 +172,18 @@ func (SegmentScope) EnumDescriptor() ([]byte, []int) {
 }
 
 // Types of operators for `WhereDocument` clauses. A `WhereDocument` clause can
-// either require that a document contains a value or that it does not contain
-// a value.
+// either require that a document contains a value, that it does not contain
+// a value, that it contains the whitespace separated terms of the value
+// within `distance` words of each other, or that it matches (or does not match)
+// the value as a regular expression.
 type WhereDocumentOperator int32
 
 const (
 	WhereDocumentOperator_CONTAINS     WhereDocumentOperator = 0
 	WhereDocumentOperator_NOT_CONTAINS WhereDocumentOperator = 1
+	WhereDocumentOperator_NEAR         WhereDocumentOperator = 2
+	WhereDocumentOperator_REGEX        WhereDocumentOperator = 3
+	WhereDocumentOperator_NOT_REGEX    WhereDocumentOperator = 4
 )
 
 // Enum value maps for WhereDocumentOperator.
@@ -186,10 +191,16 @@ var (
 	WhereDocumentOperator_name = map[int32]string{
 		0: "CONTAINS",
 		1: "NOT_CONTAINS",
+		2: "NEAR",
+		3: "REGEX",
+		4: "NOT_REGEX",
 	}
 	WhereDocumentOperator_value = map[string]int32{
 		"CONTAINS":     0,
 		"NOT_CONTAINS": 1,
+		"NEAR":         2,
+		"REGEX":        3,
+		"NOT_REGEX":    4,
 	}
 )
 
@@ -268,6 +279,55 @@ func (BooleanOperator) EnumDescriptor() ([]byte, []int) {
 	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{4}
 }
 
+// A leaf-node `Where` clause may check whether a record has a value for the
+// key, regardless of the type of the value. This enum specifies whether the
+// value should be present (`$exists`) or absent (`$is_null`).
+type ExistenceOperator int32
+
+const (
+	ExistenceOperator_EXISTS  ExistenceOperator = 0
+	ExistenceOperator_IS_NULL ExistenceOperator = 1
+)
+
+// Enum value maps for ExistenceOperator.
+var (
+	ExistenceOperator_name = map[int32]string{
+		0: "EXISTS",
+		1: "IS_NULL",
+	}
+	ExistenceOperator_value = map[string]int32{
+		"EXISTS":  0,
+		"IS_NULL": 1,
+	}
+)
+
+func (x ExistenceOperator) Enum() *ExistenceOperator {
+	p := new(ExistenceOperator)
+	*p = x
+	return p
+}
+
+func (x ExistenceOperator) String() string {
+	return protoimpl.X.EnumStringOf(x.Descriptor(), protoreflect.EnumNumber(x))
+}
+
+func (ExistenceOperator) Descriptor() protoreflect.EnumDescriptor {
+	return file_chromadb_proto_chroma_proto_enumTypes[5].Descriptor()
+}
+
+func (ExistenceOperator) Type() protoreflect.EnumType {
+	return &file_chromadb_proto_chroma_proto_enumTypes[5]
+}
+
+func (x ExistenceOperator) Number() protoreflect.EnumNumber {
+	return protoreflect.EnumNumber(x)
+}
+
+// Deprecated: Use ExistenceOperator.Descriptor instead.
+func (ExistenceOperator) EnumDescriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{5}
+}
+
 // A `Where` clause may have a list of allowed or disallowed values. This enum
 // specifies which type of list it is.
 type ListOperator int32
@@ -300,11 +360,11 @@ func (x ListOperator) String() string {
 }
 
 func (ListOperator) Descriptor() protoreflect.EnumDescriptor {
-	return file_chromadb_proto_chroma_proto_enumTypes[5].Descriptor()
+	return file_chromadb_proto_chroma_proto_enumTypes[6].Descriptor()
 }
 
 func (ListOperator) Type() protoreflect.EnumType {
-	return &file_chromadb_proto_chroma_proto_enumTypes[5]
+	return &file_chromadb_proto_chroma_proto_enumTypes[6]
 }
 
 func (x ListOperator) Number() protoreflect.EnumNumber {
@@ -313,16 +373,26 @@ func (x ListOperator) Number() protoreflect.EnumNumber {
 
 // Deprecated: Use ListOperator.Descriptor instead.
 func (ListOperator) EnumDescriptor() ([]byte, []int) {
-	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{5}
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{6}
 }
 
 // A leaf-node `Where` clause may compare a string, int, or float to a single
-// value of the same type. These comparators apply to all three of those types.
+// value of the same type. These comparators apply to all three of those types,
+// except for `EQ_CI` and `STARTS_WITH`, which only apply to strings.
+// `LIST_CONTAINS` and `LIST_NOT_CONTAINS` check whether a list value has the
+// value as an element, and behave like `EQ` and `NE` for values that are not
+// lists.
+// `EQ_CI` compares strings case-insensitively, and `STARTS_WITH` checks whether
+// a string has the value as a prefix.
 type GenericComparator int32
 
 const (
-	GenericComparator_EQ GenericComparator = 0
-	GenericComparator_NE GenericComparator = 1
+	GenericComparator_EQ                GenericComparator = 0
+	GenericComparator_NE                GenericComparator = 1
+	GenericComparator_LIST_CONTAINS     GenericComparator = 2
+	GenericComparator_LIST_NOT_CONTAINS GenericComparator = 3
+	GenericComparator_EQ_CI             GenericComparator = 4
+	GenericComparator_STARTS_WITH       GenericComparator = 5
 )
 
 // Enum value maps for GenericComparator.
@@ -330,10 +400,18 @@ var (
 	GenericComparator_name = map[int32]string{
 		0: "EQ",
 		1: "NE",
+		2: "LIST_CONTAINS",
+		3: "LIST_NOT_CONTAINS",
+		4: "EQ_CI",
+		5: "STARTS_WITH",
 	}
 	GenericComparator_value = map[string]int32{
-		"EQ": 0,
-		"NE": 1,
+		"EQ":                0,
+		"NE":                1,
+		"LIST_CONTAINS":     2,
+		"LIST_NOT_CONTAINS": 3,
+		"EQ_CI":             4,
+		"STARTS_WITH":       5,
 	}
 )
 
@@ -348,11 +426,11 @@ func (x GenericComparator) String() string {
 }
 
 func (GenericComparator) Descriptor() protoreflect.EnumDescriptor {
-	return file_chromadb_proto_chroma_proto_enumTypes[6].Descriptor()
+	return file_chromadb_proto_chroma_proto_enumTypes[7].Descriptor()
 }
 
 func (GenericComparator) Type() protoreflect.EnumType {
-	return &file_chromadb_proto_chroma_proto_enumTypes[6]
+	return &file_chromadb_proto_chroma_proto_enumTypes[7]
 }
 
 func (x GenericComparator) Number() protoreflect.EnumNumber {
@@ -361,7 +439,7 @@ func (x GenericComparator) Number() protoreflect.EnumNumber {
 
 // Deprecated: Use GenericComparator.Descriptor instead.
 func (GenericComparator) EnumDescriptor() ([]byte, []int) {
-	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{6}
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{7}
 }
 
 // Used when a leaf-node `Where` clause compares an int or float to a single
@@ -402,11 +480,11 @@ func (x NumberComparator) String() string {
 }
 
 func (NumberComparator) Descriptor() protoreflect.EnumDescriptor {
-	return file_chromadb_proto_chroma_proto_enumTypes[7].Descriptor()
+	return file_chromadb_proto_chroma_proto_enumTypes[8].Descriptor()
 }
 
 func (NumberComparator) Type() protoreflect.EnumType {
-	return &file_chromadb_proto_chroma_proto_enumTypes[7]
+	return &file_chromadb_proto_chroma_proto_enumTypes[8]
 }
 
 func (x NumberComparator) Number() protoreflect.EnumNumber {
@@ -415,7 +493,107 @@ func (x NumberComparator) Number() protoreflect.EnumNumber {
 
 // Deprecated: Use NumberComparator.Descriptor instead.
 func (NumberComparator) EnumDescriptor() ([]byte, []int) {
-	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{7}
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{8}
+}
+
+type IntegrityCheck int32
+
+const (
+	// The offset ids of the record segment map to and from the user ids and to data records.
+	IntegrityCheck_OFFSET_ID_CONTINUITY IntegrityCheck = 0
+	// The metadata segment indexes the records of the record segment and nothing else.
+	IntegrityCheck_METADATA_AGREEMENT IntegrityCheck = 1
+	// The vector index holds an embedding for every record.
+	IntegrityCheck_VECTOR_INDEX_COVERAGE IntegrityCheck = 2
+)
+
+// Enum value maps for IntegrityCheck.
+var (
+	IntegrityCheck_name = map[int32]string{
+		0: "OFFSET_ID_CONTINUITY",
+		1: "METADATA_AGREEMENT",
+		2: "VECTOR_INDEX_COVERAGE",
+	}
+	IntegrityCheck_value = map[string]int32{
+		"OFFSET_ID_CONTINUITY":  0,
+		"METADATA_AGREEMENT":    1,
+		"VECTOR_INDEX_COVERAGE": 2,
+	}
+)
+
+func (x IntegrityCheck) Enum() *IntegrityCheck {
+	p := new(IntegrityCheck)
+	*p = x
+	return p
+}
+
+func (x IntegrityCheck) String() string {
+	return protoimpl.X.EnumStringOf(x.Descriptor(), protoreflect.EnumNumber(x))
+}
+
+func (IntegrityCheck) Descriptor() protoreflect.EnumDescriptor {
+	return file_chromadb_proto_chroma_proto_enumTypes[9].Descriptor()
+}
+
+func (IntegrityCheck) Type() protoreflect.EnumType {
+	return &file_chromadb_proto_chroma_proto_enumTypes[9]
+}
+
+func (x IntegrityCheck) Number() protoreflect.EnumNumber {
+	return protoreflect.EnumNumber(x)
+}
+
+// Deprecated: Use IntegrityCheck.Descriptor instead.
+func (IntegrityCheck) EnumDescriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{9}
+}
+
+// The precision in which the distances of a query are accumulated. Accumulating in double
+// precision ranks the records at almost the same distance stably, at some cost in speed.
+type DistanceAccumulation int32
+
+const (
+	DistanceAccumulation_ACCUMULATE_F32 DistanceAccumulation = 0
+	DistanceAccumulation_ACCUMULATE_F64 DistanceAccumulation = 1
+)
+
+// Enum value maps for DistanceAccumulation.
+var (
+	DistanceAccumulation_name = map[int32]string{
+		0: "ACCUMULATE_F32",
+		1: "ACCUMULATE_F64",
+	}
+	DistanceAccumulation_value = map[string]int32{
+		"ACCUMULATE_F32": 0,
+		"ACCUMULATE_F64": 1,
+	}
+)
+
+func (x DistanceAccumulation) Enum() *DistanceAccumulation {
+	p := new(DistanceAccumulation)
+	*p = x
+	return p
+}
+
+func (x DistanceAccumulation) String() string {
+	return protoimpl.X.EnumStringOf(x.Descriptor(), protoreflect.EnumNumber(x))
+}
+
+func (DistanceAccumulation) Descriptor() protoreflect.EnumDescriptor {
+	return file_chromadb_proto_chroma_proto_enumTypes[10].Descriptor()
+}
+
+func (DistanceAccumulation) Type() protoreflect.EnumType {
+	return &file_chromadb_proto_chroma_proto_enumTypes[10]
+}
+
+func (x DistanceAccumulation) Number() protoreflect.EnumNumber {
+	return protoreflect.EnumNumber(x)
+}
+
+// Deprecated: Use DistanceAccumulation.Descriptor instead.
+func (DistanceAccumulation) EnumDescriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{10}
 }
 
 type Vector struct {
@@ -794,7 +972,8 @@ type Tenant struct {
 	sizeCache     protoimpl.SizeCache
 	unknownFields protoimpl.UnknownFields
 
-	Name string `protobuf:"bytes,1,opt,name=name,proto3" json:"name,omitempty"`
+	Name     string  `protobuf:"bytes,1,opt,name=name,proto3" json:"name,omitempty"`
+	KmsKeyId *string `protobuf:"bytes,2,opt,name=kms_key_id,json=kmsKeyId,proto3,oneof" json:"kms_key_id,omitempty"`
 }
 
 func (x *Tenant) Reset() {
@@ -836,6 +1015,13 @@ func (x *Tenant) GetName() string {
 	return ""
 }
 
+func (x *Tenant) GetKmsKeyId() string {
+	if x != nil && x.KmsKeyId != nil {
+		return *x.KmsKeyId
+	}
+	return ""
+}
+
 type UpdateMetadataValue struct {
 	state         protoimpl.MessageState
 	sizeCache     protoimpl.SizeCache
@@ -850,6 +1036,9 @@ type UpdateMetadataValue struct {
 	//	*UpdateMetadataValue_IntValue
 	//	*UpdateMetadataValue_FloatValue
 	//	*UpdateMetadataValue_BoolValue
+	//	*UpdateMetadataValue_ListValue
+	//	*UpdateMetadataValue_TimestampValue
+	//	*UpdateMetadataValue_ObjectValue
 	Value isUpdateMetadataValue_Value `protobuf_oneof:"value"`
 }
 
@@ -920,6 +1109,27 @@ func (x *UpdateMetadataValue) GetBoolValue() bool {
 	return false
 }
 
+func (x *UpdateMetadataValue) GetListValue() *MetadataList {
+	if x, ok := x.GetValue().(*UpdateMetadataValue_ListValue); ok {
+		return x.ListValue
+	}
+	return nil
+}
+
+func (x *UpdateMetadataValue) GetTimestampValue() int64 {
+	if x, ok := x.GetValue().(*UpdateMetadataValue_TimestampValue); ok {
+		return x.TimestampValue
+	}
+	return 0
+}
+
+func (x *UpdateMetadataValue) GetObjectValue() *MetadataObject {
+	if x, ok := x.GetValue().(*UpdateMetadataValue_ObjectValue); ok {
+		return x.ObjectValue
+	}
+	return nil
+}
+
 type isUpdateMetadataValue_Value interface {
 	isUpdateMetadataValue_Value()
 }
@@ -940,6 +1150,19 @@ type UpdateMetadataValue_BoolValue struct {
 	BoolValue bool `protobuf:"varint,4,opt,name=bool_value,json=boolValue,proto3,oneof"`
 }
 
+type UpdateMetadataValue_ListValue struct {
+	ListValue *MetadataList `protobuf:"bytes,5,opt,name=list_value,json=listValue,proto3,oneof"`
+}
+
+type UpdateMetadataValue_TimestampValue struct {
+	// Microseconds since the Unix epoch.
+	TimestampValue int64 `protobuf:"varint,6,opt,name=timestamp_value,json=timestampValue,proto3,oneof"`
+}
+
+type UpdateMetadataValue_ObjectValue struct {
+	ObjectValue *MetadataObject `protobuf:"bytes,7,opt,name=object_value,json=objectValue,proto3,oneof"`
+}
+
 func (*UpdateMetadataValue_StringValue) isUpdateMetadataValue_Value() {}
 
 func (*UpdateMetadataValue_IntValue) isUpdateMetadataValue_Value() {}
@@ -948,16 +1171,24 @@ func (*UpdateMetadataValue_FloatValue) isUpdateMetadataValue_Value() {}
 
 func (*UpdateMetadataValue_BoolValue) isUpdateMetadataValue_Value() {}
 
-type UpdateMetadata struct {
+func (*UpdateMetadataValue_ListValue) isUpdateMetadataValue_Value() {}
+
+func (*UpdateMetadataValue_TimestampValue) isUpdateMetadataValue_Value() {}
+
+func (*UpdateMetadataValue_ObjectValue) isUpdateMetadataValue_Value() {}
+
+// A list of string, int, float or bool values. Lists cannot be nested. Each
+// element is indexed separately, so that `$contains` can match it.
+type MetadataList struct {
 	state         protoimpl.MessageState
 	sizeCache     protoimpl.SizeCache
 	unknownFields protoimpl.UnknownFields
 
-	Metadata map[string]*UpdateMetadataValue `protobuf:"bytes,1,rep,name=metadata,proto3" json:"metadata,omitempty" protobuf_key:"bytes,1,opt,name=key,proto3" protobuf_val:"bytes,2,opt,name=value,proto3"`
+	Values []*UpdateMetadataValue `protobuf:"bytes,1,rep,name=values,proto3" json:"values,omitempty"`
 }
 
-func (x *UpdateMetadata) Reset() {
-	*x = UpdateMetadata{}
+func (x *MetadataList) Reset() {
+	*x = MetadataList{}
 	if protoimpl.UnsafeEnabled {
 		mi := &file_chromadb_proto_chroma_proto_msgTypes[7]
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -965,13 +1196,13 @@ func (x *UpdateMetadata) Reset() {
 	}
 }
 
-func (x *UpdateMetadata) String() string {
+func (x *MetadataList) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*UpdateMetadata) ProtoMessage() {}
+func (*MetadataList) ProtoMessage() {}
 
-func (x *UpdateMetadata) ProtoReflect() protoreflect.Message {
+func (x *MetadataList) ProtoReflect() protoreflect.Message {
 	mi := &file_chromadb_proto_chroma_proto_msgTypes[7]
 	if protoimpl.UnsafeEnabled && x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -983,32 +1214,30 @@ func (x *UpdateMetadata) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use UpdateMetadata.ProtoReflect.Descriptor instead.
-func (*UpdateMetadata) Descriptor() ([]byte, []int) {
+// Deprecated: Use MetadataList.ProtoReflect.Descriptor instead.
+func (*MetadataList) Descriptor() ([]byte, []int) {
 	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{7}
 }
 
-func (x *UpdateMetadata) GetMetadata() map[string]*UpdateMetadataValue {
+func (x *MetadataList) GetValues() []*UpdateMetadataValue {
 	if x != nil {
-		return x.Metadata
+		return x.Values
 	}
 	return nil
 }
 
-// Represents an operation the user submits
-type OperationRecord struct {
+// A nested object of metadata values. Its values are indexed under their dotted paths, so that
+// `{"author": {"name": "x"}}` can be filtered with the key `author.name`.
+type MetadataObject struct {
 	state         protoimpl.MessageState
 	sizeCache     protoimpl.SizeCache
 	unknownFields protoimpl.UnknownFields
 
-	Id        string          `protobuf:"bytes,1,opt,name=id,proto3" json:"id,omitempty"`
-	Vector    *Vector         `protobuf:"bytes,2,opt,name=vector,proto3,oneof" json:"vector,omitempty"`
-	Metadata  *UpdateMetadata `protobuf:"bytes,3,opt,name=metadata,proto3,oneof" json:"metadata,omitempty"`
-	Operation Operation       `protobuf:"varint,4,opt,name=operation,proto3,enum=chroma.Operation" json:"operation,omitempty"`
+	Values map[string]*UpdateMetadataValue `protobuf:"bytes,1,rep,name=values,proto3" json:"values,omitempty" protobuf_key:"bytes,1,opt,name=key,proto3" protobuf_val:"bytes,2,opt,name=value,proto3"`
 }
 
-func (x *OperationRecord) Reset() {
-	*x = OperationRecord{}
+func (x *MetadataObject) Reset() {
+	*x = MetadataObject{}
 	if protoimpl.UnsafeEnabled {
 		mi := &file_chromadb_proto_chroma_proto_msgTypes[8]
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -1016,13 +1245,13 @@ func (x *OperationRecord) Reset() {
 	}
 }
 
-func (x *OperationRecord) String() string {
+func (x *MetadataObject) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*OperationRecord) ProtoMessage() {}
+func (*MetadataObject) ProtoMessage() {}
 
-func (x *OperationRecord) ProtoReflect() protoreflect.Message {
+func (x *MetadataObject) ProtoReflect() protoreflect.Message {
 	mi := &file_chromadb_proto_chroma_proto_msgTypes[8]
 	if protoimpl.UnsafeEnabled && x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -1034,50 +1263,28 @@ func (x *OperationRecord) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use OperationRecord.ProtoReflect.Descriptor instead.
-func (*OperationRecord) Descriptor() ([]byte, []int) {
+// Deprecated: Use MetadataObject.ProtoReflect.Descriptor instead.
+func (*MetadataObject) Descriptor() ([]byte, []int) {
 	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{8}
 }
 
-func (x *OperationRecord) GetId() string {
-	if x != nil {
-		return x.Id
-	}
-	return ""
-}
-
-func (x *OperationRecord) GetVector() *Vector {
-	if x != nil {
-		return x.Vector
-	}
-	return nil
-}
-
-func (x *OperationRecord) GetMetadata() *UpdateMetadata {
+func (x *MetadataObject) GetValues() map[string]*UpdateMetadataValue {
 	if x != nil {
-		return x.Metadata
+		return x.Values
 	}
 	return nil
 }
 
-func (x *OperationRecord) GetOperation() Operation {
-	if x != nil {
-		return x.Operation
-	}
-	return Operation_ADD
-}
-
-type RequestVersionContext struct {
+type UpdateMetadata struct {
 	state         protoimpl.MessageState
 	sizeCache     protoimpl.SizeCache
 	unknownFields protoimpl.UnknownFields
 
-	CollectionVersion uint32 `protobuf:"varint,1,opt,name=collection_version,json=collectionVersion,proto3" json:"collection_version,omitempty"`
-	LogPosition       uint64 `protobuf:"varint,2,opt,name=log_position,json=logPosition,proto3" json:"log_position,omitempty"`
+	Metadata map[string]*UpdateMetadataValue `protobuf:"bytes,1,rep,name=metadata,proto3" json:"metadata,omitempty" protobuf_key:"bytes,1,opt,name=key,proto3" protobuf_val:"bytes,2,opt,name=value,proto3"`
 }
 
-func (x *RequestVersionContext) Reset() {
-	*x = RequestVersionContext{}
+func (x *UpdateMetadata) Reset() {
+	*x = UpdateMetadata{}
 	if protoimpl.UnsafeEnabled {
 		mi := &file_chromadb_proto_chroma_proto_msgTypes[9]
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -1085,13 +1292,13 @@ func (x *RequestVersionContext) Reset() {
 	}
 }
 
-func (x *RequestVersionContext) String() string {
+func (x *UpdateMetadata) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*RequestVersionContext) ProtoMessage() {}
+func (*UpdateMetadata) ProtoMessage() {}
 
-func (x *RequestVersionContext) ProtoReflect() protoreflect.Message {
+func (x *UpdateMetadata) ProtoReflect() protoreflect.Message {
 	mi := &file_chromadb_proto_chroma_proto_msgTypes[9]
 	if protoimpl.UnsafeEnabled && x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -1103,37 +1310,39 @@ func (x *RequestVersionContext) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use RequestVersionContext.ProtoReflect.Descriptor instead.
-func (*RequestVersionContext) Descriptor() ([]byte, []int) {
+// Deprecated: Use UpdateMetadata.ProtoReflect.Descriptor instead.
+func (*UpdateMetadata) Descriptor() ([]byte, []int) {
 	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{9}
 }
 
-func (x *RequestVersionContext) GetCollectionVersion() uint32 {
-	if x != nil {
-		return x.CollectionVersion
-	}
-	return 0
-}
-
-func (x *RequestVersionContext) GetLogPosition() uint64 {
+func (x *UpdateMetadata) GetMetadata() map[string]*UpdateMetadataValue {
 	if x != nil {
-		return x.LogPosition
+		return x.Metadata
 	}
-	return 0
+	return nil
 }
 
-type CountRecordsRequest struct {
+// A reference to a vector that is stored in an object instead of in the record, so that
+// large batch writes do not push the bytes of their vectors through the log. The object is
+// read from the external bucket configured on the compactor, and it is either a
+// 2-dimensional float32 .npy array or an Arrow IPC file whose first column holds the vectors
+// as lists of float32.
+type VectorReference struct {
 	state         protoimpl.MessageState
 	sizeCache     protoimpl.SizeCache
 	unknownFields protoimpl.UnknownFields
 
-	SegmentId      string                 `protobuf:"bytes,1,opt,name=segment_id,json=segmentId,proto3" json:"segment_id,omitempty"`
-	CollectionId   string                 `protobuf:"bytes,2,opt,name=collection_id,json=collectionId,proto3" json:"collection_id,omitempty"`
-	VersionContext *RequestVersionContext `protobuf:"bytes,3,opt,name=version_context,json=versionContext,proto3" json:"version_context,omitempty"`
+	// The uri of the object, e.g. s3://bucket/batches/vectors.npy, which must name the
+	// external bucket.
+	Uri string `protobuf:"bytes,1,opt,name=uri,proto3" json:"uri,omitempty"`
+	// The row of the vector in the object.
+	Row uint32 `protobuf:"varint,2,opt,name=row,proto3" json:"row,omitempty"`
+	// The dimension of the vector, which the vector in the object must have.
+	Dimension int32 `protobuf:"varint,3,opt,name=dimension,proto3" json:"dimension,omitempty"`
 }
 
-func (x *CountRecordsRequest) Reset() {
-	*x = CountRecordsRequest{}
+func (x *VectorReference) Reset() {
+	*x = VectorReference{}
 	if protoimpl.UnsafeEnabled {
 		mi := &file_chromadb_proto_chroma_proto_msgTypes[10]
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -1141,13 +1350,13 @@ func (x *CountRecordsRequest) Reset() {
 	}
 }
 
-func (x *CountRecordsRequest) String() string {
+func (x *VectorReference) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*CountRecordsRequest) ProtoMessage() {}
+func (*VectorReference) ProtoMessage() {}
 
-func (x *CountRecordsRequest) ProtoReflect() protoreflect.Message {
+func (x *VectorReference) ProtoReflect() protoreflect.Message {
 	mi := &file_chromadb_proto_chroma_proto_msgTypes[10]
 	if protoimpl.UnsafeEnabled && x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -1159,43 +1368,50 @@ func (x *CountRecordsRequest) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use CountRecordsRequest.ProtoReflect.Descriptor instead.
-func (*CountRecordsRequest) Descriptor() ([]byte, []int) {
+// Deprecated: Use VectorReference.ProtoReflect.Descriptor instead.
+func (*VectorReference) Descriptor() ([]byte, []int) {
 	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{10}
 }
 
-func (x *CountRecordsRequest) GetSegmentId() string {
+func (x *VectorReference) GetUri() string {
 	if x != nil {
-		return x.SegmentId
+		return x.Uri
 	}
 	return ""
 }
 
-func (x *CountRecordsRequest) GetCollectionId() string {
+func (x *VectorReference) GetRow() uint32 {
 	if x != nil {
-		return x.CollectionId
+		return x.Row
 	}
-	return ""
+	return 0
 }
 
-func (x *CountRecordsRequest) GetVersionContext() *RequestVersionContext {
+func (x *VectorReference) GetDimension() int32 {
 	if x != nil {
-		return x.VersionContext
+		return x.Dimension
 	}
-	return nil
+	return 0
 }
 
-// TODO: Add error propagation in the response.
-type CountRecordsResponse struct {
+// Represents an operation the user submits
+type OperationRecord struct {
 	state         protoimpl.MessageState
 	sizeCache     protoimpl.SizeCache
 	unknownFields protoimpl.UnknownFields
 
-	Count uint32 `protobuf:"varint,1,opt,name=count,proto3" json:"count,omitempty"`
+	Id        string          `protobuf:"bytes,1,opt,name=id,proto3" json:"id,omitempty"`
+	Vector    *Vector         `protobuf:"bytes,2,opt,name=vector,proto3,oneof" json:"vector,omitempty"`
+	Metadata  *UpdateMetadata `protobuf:"bytes,3,opt,name=metadata,proto3,oneof" json:"metadata,omitempty"`
+	Operation Operation       `protobuf:"varint,4,opt,name=operation,proto3,enum=chroma.Operation" json:"operation,omitempty"`
+	// The vector of the record, if it is stored in an object instead of in `vector`. The
+	// reference is resolved when the record is compacted, so queries that need the vector
+	// of the record fail with FAILED_PRECONDITION until then.
+	VectorReference *VectorReference `protobuf:"bytes,5,opt,name=vector_reference,json=vectorReference,proto3,oneof" json:"vector_reference,omitempty"`
 }
 
-func (x *CountRecordsResponse) Reset() {
-	*x = CountRecordsResponse{}
+func (x *OperationRecord) Reset() {
+	*x = OperationRecord{}
 	if protoimpl.UnsafeEnabled {
 		mi := &file_chromadb_proto_chroma_proto_msgTypes[11]
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -1203,13 +1419,13 @@ func (x *CountRecordsResponse) Reset() {
 	}
 }
 
-func (x *CountRecordsResponse) String() string {
+func (x *OperationRecord) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*CountRecordsResponse) ProtoMessage() {}
+func (*OperationRecord) ProtoMessage() {}
 
-func (x *CountRecordsResponse) ProtoReflect() protoreflect.Message {
+func (x *OperationRecord) ProtoReflect() protoreflect.Message {
 	mi := &file_chromadb_proto_chroma_proto_msgTypes[11]
 	if protoimpl.UnsafeEnabled && x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -1221,36 +1437,61 @@ func (x *CountRecordsResponse) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use CountRecordsResponse.ProtoReflect.Descriptor instead.
-func (*CountRecordsResponse) Descriptor() ([]byte, []int) {
+// Deprecated: Use OperationRecord.ProtoReflect.Descriptor instead.
+func (*OperationRecord) Descriptor() ([]byte, []int) {
 	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{11}
 }
 
-func (x *CountRecordsResponse) GetCount() uint32 {
+func (x *OperationRecord) GetId() string {
 	if x != nil {
-		return x.Count
+		return x.Id
 	}
-	return 0
+	return ""
 }
 
-type QueryMetadataRequest struct {
-	state         protoimpl.MessageState
-	sizeCache     protoimpl.SizeCache
+func (x *OperationRecord) GetVector() *Vector {
+	if x != nil {
+		return x.Vector
+	}
+	return nil
+}
+
+func (x *OperationRecord) GetMetadata() *UpdateMetadata {
+	if x != nil {
+		return x.Metadata
+	}
+	return nil
+}
+
+func (x *OperationRecord) GetOperation() Operation {
+	if x != nil {
+		return x.Operation
+	}
+	return Operation_ADD
+}
+
+func (x *OperationRecord) GetVectorReference() *VectorReference {
+	if x != nil {
+		return x.VectorReference
+	}
+	return nil
+}
+
+type RequestVersionContext struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
 	unknownFields protoimpl.UnknownFields
 
-	SegmentId       string                 `protobuf:"bytes,1,opt,name=segment_id,json=segmentId,proto3" json:"segment_id,omitempty"`
-	Where           *Where                 `protobuf:"bytes,2,opt,name=where,proto3" json:"where,omitempty"`
-	WhereDocument   *WhereDocument         `protobuf:"bytes,3,opt,name=where_document,json=whereDocument,proto3" json:"where_document,omitempty"`
-	Ids             *UserIds               `protobuf:"bytes,4,opt,name=ids,proto3,oneof" json:"ids,omitempty"`
-	Limit           *uint32                `protobuf:"varint,5,opt,name=limit,proto3,oneof" json:"limit,omitempty"`
-	Offset          *uint32                `protobuf:"varint,6,opt,name=offset,proto3,oneof" json:"offset,omitempty"`
-	CollectionId    string                 `protobuf:"bytes,7,opt,name=collection_id,json=collectionId,proto3" json:"collection_id,omitempty"`
-	IncludeMetadata bool                   `protobuf:"varint,8,opt,name=include_metadata,json=includeMetadata,proto3" json:"include_metadata,omitempty"`
-	VersionContext  *RequestVersionContext `protobuf:"bytes,9,opt,name=version_context,json=versionContext,proto3" json:"version_context,omitempty"`
+	CollectionVersion uint32 `protobuf:"varint,1,opt,name=collection_version,json=collectionVersion,proto3" json:"collection_version,omitempty"`
+	LogPosition       uint64 `protobuf:"varint,2,opt,name=log_position,json=logPosition,proto3" json:"log_position,omitempty"`
+	// The maximum number of logs past the log position that the query materializes. A query
+	// on a collection with a larger backlog of uncompacted logs fails with FAILED_PRECONDITION
+	// instead. Defaults to the limit of the server, if any, and can only lower it.
+	MaxLogBacklog *uint32 `protobuf:"varint,3,opt,name=max_log_backlog,json=maxLogBacklog,proto3,oneof" json:"max_log_backlog,omitempty"`
 }
 
-func (x *QueryMetadataRequest) Reset() {
-	*x = QueryMetadataRequest{}
+func (x *RequestVersionContext) Reset() {
+	*x = RequestVersionContext{}
 	if protoimpl.UnsafeEnabled {
 		mi := &file_chromadb_proto_chroma_proto_msgTypes[12]
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -1258,13 +1499,13 @@ func (x *QueryMetadataRequest) Reset() {
 	}
 }
 
-func (x *QueryMetadataRequest) String() string {
+func (x *RequestVersionContext) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*QueryMetadataRequest) ProtoMessage() {}
+func (*RequestVersionContext) ProtoMessage() {}
 
-func (x *QueryMetadataRequest) ProtoReflect() protoreflect.Message {
+func (x *RequestVersionContext) ProtoReflect() protoreflect.Message {
 	mi := &file_chromadb_proto_chroma_proto_msgTypes[12]
 	if protoimpl.UnsafeEnabled && x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -1276,84 +1517,53 @@ func (x *QueryMetadataRequest) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use QueryMetadataRequest.ProtoReflect.Descriptor instead.
-func (*QueryMetadataRequest) Descriptor() ([]byte, []int) {
+// Deprecated: Use RequestVersionContext.ProtoReflect.Descriptor instead.
+func (*RequestVersionContext) Descriptor() ([]byte, []int) {
 	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{12}
 }
 
-func (x *QueryMetadataRequest) GetSegmentId() string {
-	if x != nil {
-		return x.SegmentId
-	}
-	return ""
-}
-
-func (x *QueryMetadataRequest) GetWhere() *Where {
-	if x != nil {
-		return x.Where
-	}
-	return nil
-}
-
-func (x *QueryMetadataRequest) GetWhereDocument() *WhereDocument {
+func (x *RequestVersionContext) GetCollectionVersion() uint32 {
 	if x != nil {
-		return x.WhereDocument
+		return x.CollectionVersion
 	}
-	return nil
+	return 0
 }
 
-func (x *QueryMetadataRequest) GetIds() *UserIds {
+func (x *RequestVersionContext) GetLogPosition() uint64 {
 	if x != nil {
-		return x.Ids
-	}
-	return nil
-}
-
-func (x *QueryMetadataRequest) GetLimit() uint32 {
-	if x != nil && x.Limit != nil {
-		return *x.Limit
+		return x.LogPosition
 	}
 	return 0
 }
 
-func (x *QueryMetadataRequest) GetOffset() uint32 {
-	if x != nil && x.Offset != nil {
-		return *x.Offset
+func (x *RequestVersionContext) GetMaxLogBacklog() uint32 {
+	if x != nil && x.MaxLogBacklog != nil {
+		return *x.MaxLogBacklog
 	}
 	return 0
 }
 
-func (x *QueryMetadataRequest) GetCollectionId() string {
-	if x != nil {
-		return x.CollectionId
-	}
-	return ""
-}
-
-func (x *QueryMetadataRequest) GetIncludeMetadata() bool {
-	if x != nil {
-		return x.IncludeMetadata
-	}
-	return false
-}
-
-func (x *QueryMetadataRequest) GetVersionContext() *RequestVersionContext {
-	if x != nil {
-		return x.VersionContext
-	}
-	return nil
-}
-
-type QueryMetadataResponse struct {
+// The details attached to the status of a failed request, which classify the error
+// so that clients can decide whether to retry the request and how to report the error.
+type ErrorDetails struct {
 	state         protoimpl.MessageState
 	sizeCache     protoimpl.SizeCache
 	unknownFields protoimpl.UnknownFields
 
-	Records []*MetadataEmbeddingRecord `protobuf:"bytes,1,rep,name=records,proto3" json:"records,omitempty"`
+	// Whether the same request may succeed if it is retried.
+	Retryable bool `protobuf:"varint,1,opt,name=retryable,proto3" json:"retryable,omitempty"`
+	// Whether the error is caused by the request, rather than by the system.
+	UserError bool `protobuf:"varint,2,opt,name=user_error,json=userError,proto3" json:"user_error,omitempty"`
+	// The field of the request that caused the error, if it is known.
+	Field *string `protobuf:"bytes,3,opt,name=field,proto3,oneof" json:"field,omitempty"`
+	// The segment that the error occurred in, if it is known.
+	SegmentId *string `protobuf:"bytes,4,opt,name=segment_id,json=segmentId,proto3,oneof" json:"segment_id,omitempty"`
+	// The block of the segment that the error occurred in, if it is known.
+	BlockId *string `protobuf:"bytes,5,opt,name=block_id,json=blockId,proto3,oneof" json:"block_id,omitempty"`
 }
 
-func (x *QueryMetadataResponse) Reset() {
-	*x = QueryMetadataResponse{}
+func (x *ErrorDetails) Reset() {
+	*x = ErrorDetails{}
 	if protoimpl.UnsafeEnabled {
 		mi := &file_chromadb_proto_chroma_proto_msgTypes[13]
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -1361,13 +1571,13 @@ func (x *QueryMetadataResponse) Reset() {
 	}
 }
 
-func (x *QueryMetadataResponse) String() string {
+func (x *ErrorDetails) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*QueryMetadataResponse) ProtoMessage() {}
+func (*ErrorDetails) ProtoMessage() {}
 
-func (x *QueryMetadataResponse) ProtoReflect() protoreflect.Message {
+func (x *ErrorDetails) ProtoReflect() protoreflect.Message {
 	mi := &file_chromadb_proto_chroma_proto_msgTypes[13]
 	if protoimpl.UnsafeEnabled && x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -1379,29 +1589,66 @@ func (x *QueryMetadataResponse) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use QueryMetadataResponse.ProtoReflect.Descriptor instead.
-func (*QueryMetadataResponse) Descriptor() ([]byte, []int) {
+// Deprecated: Use ErrorDetails.ProtoReflect.Descriptor instead.
+func (*ErrorDetails) Descriptor() ([]byte, []int) {
 	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{13}
 }
 
-func (x *QueryMetadataResponse) GetRecords() []*MetadataEmbeddingRecord {
+func (x *ErrorDetails) GetRetryable() bool {
 	if x != nil {
-		return x.Records
+		return x.Retryable
 	}
-	return nil
+	return false
 }
 
-type MetadataEmbeddingRecord struct {
+func (x *ErrorDetails) GetUserError() bool {
+	if x != nil {
+		return x.UserError
+	}
+	return false
+}
+
+func (x *ErrorDetails) GetField() string {
+	if x != nil && x.Field != nil {
+		return *x.Field
+	}
+	return ""
+}
+
+func (x *ErrorDetails) GetSegmentId() string {
+	if x != nil && x.SegmentId != nil {
+		return *x.SegmentId
+	}
+	return ""
+}
+
+func (x *ErrorDetails) GetBlockId() string {
+	if x != nil && x.BlockId != nil {
+		return *x.BlockId
+	}
+	return ""
+}
+
+type StreamChangesRequest struct {
 	state         protoimpl.MessageState
 	sizeCache     protoimpl.SizeCache
 	unknownFields protoimpl.UnknownFields
 
-	Id       string          `protobuf:"bytes,1,opt,name=id,proto3" json:"id,omitempty"`
-	Metadata *UpdateMetadata `protobuf:"bytes,2,opt,name=metadata,proto3" json:"metadata,omitempty"`
-}
-
-func (x *MetadataEmbeddingRecord) Reset() {
-	*x = MetadataEmbeddingRecord{}
+	CollectionId   string                 `protobuf:"bytes,1,opt,name=collection_id,json=collectionId,proto3" json:"collection_id,omitempty"`
+	VersionContext *RequestVersionContext `protobuf:"bytes,2,opt,name=version_context,json=versionContext,proto3" json:"version_context,omitempty"`
+	// The offset of the first log whose changes are streamed. It must be past the log position
+	// of the version context, since the logs up to it are compacted into the version, which is
+	// read with `QueryMetadata` instead. The request fails with FAILED_PRECONDITION otherwise.
+	StartLogOffset    uint64 `protobuf:"varint,3,opt,name=start_log_offset,json=startLogOffset,proto3" json:"start_log_offset,omitempty"`
+	IncludeEmbeddings bool   `protobuf:"varint,4,opt,name=include_embeddings,json=includeEmbeddings,proto3" json:"include_embeddings,omitempty"`
+	// Streams the fields of the records that the projection defaults of the collection include,
+	// instead of the ones that the request includes. Set when the caller leaves the include
+	// unspecified.
+	DefaultInclude bool `protobuf:"varint,5,opt,name=default_include,json=defaultInclude,proto3" json:"default_include,omitempty"`
+}
+
+func (x *StreamChangesRequest) Reset() {
+	*x = StreamChangesRequest{}
 	if protoimpl.UnsafeEnabled {
 		mi := &file_chromadb_proto_chroma_proto_msgTypes[14]
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -1409,13 +1656,13 @@ func (x *MetadataEmbeddingRecord) Reset() {
 	}
 }
 
-func (x *MetadataEmbeddingRecord) String() string {
+func (x *StreamChangesRequest) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*MetadataEmbeddingRecord) ProtoMessage() {}
+func (*StreamChangesRequest) ProtoMessage() {}
 
-func (x *MetadataEmbeddingRecord) ProtoReflect() protoreflect.Message {
+func (x *StreamChangesRequest) ProtoReflect() protoreflect.Message {
 	mi := &file_chromadb_proto_chroma_proto_msgTypes[14]
 	if protoimpl.UnsafeEnabled && x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -1427,36 +1674,59 @@ func (x *MetadataEmbeddingRecord) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use MetadataEmbeddingRecord.ProtoReflect.Descriptor instead.
-func (*MetadataEmbeddingRecord) Descriptor() ([]byte, []int) {
+// Deprecated: Use StreamChangesRequest.ProtoReflect.Descriptor instead.
+func (*StreamChangesRequest) Descriptor() ([]byte, []int) {
 	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{14}
 }
 
-func (x *MetadataEmbeddingRecord) GetId() string {
+func (x *StreamChangesRequest) GetCollectionId() string {
 	if x != nil {
-		return x.Id
+		return x.CollectionId
 	}
 	return ""
 }
 
-func (x *MetadataEmbeddingRecord) GetMetadata() *UpdateMetadata {
+func (x *StreamChangesRequest) GetVersionContext() *RequestVersionContext {
 	if x != nil {
-		return x.Metadata
+		return x.VersionContext
 	}
 	return nil
 }
 
-// A `UserIds` should contain the set of user provided ids allowed in the result.
-type UserIds struct {
+func (x *StreamChangesRequest) GetStartLogOffset() uint64 {
+	if x != nil {
+		return x.StartLogOffset
+	}
+	return 0
+}
+
+func (x *StreamChangesRequest) GetIncludeEmbeddings() bool {
+	if x != nil {
+		return x.IncludeEmbeddings
+	}
+	return false
+}
+
+func (x *StreamChangesRequest) GetDefaultInclude() bool {
+	if x != nil {
+		return x.DefaultInclude
+	}
+	return false
+}
+
+// The state of a record after a change. An UPSERT carries the record with its metadata merged
+// across the logs, and a DELETE carries only the id of the record.
+type RecordChange struct {
 	state         protoimpl.MessageState
 	sizeCache     protoimpl.SizeCache
 	unknownFields protoimpl.UnknownFields
 
-	Ids []string `protobuf:"bytes,1,rep,name=ids,proto3" json:"ids,omitempty"`
+	Operation Operation                `protobuf:"varint,1,opt,name=operation,proto3,enum=chroma.Operation" json:"operation,omitempty"`
+	Record    *MetadataEmbeddingRecord `protobuf:"bytes,2,opt,name=record,proto3" json:"record,omitempty"`
 }
 
-func (x *UserIds) Reset() {
-	*x = UserIds{}
+func (x *RecordChange) Reset() {
+	*x = RecordChange{}
 	if protoimpl.UnsafeEnabled {
 		mi := &file_chromadb_proto_chroma_proto_msgTypes[15]
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -1464,13 +1734,13 @@ func (x *UserIds) Reset() {
 	}
 }
 
-func (x *UserIds) String() string {
+func (x *RecordChange) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*UserIds) ProtoMessage() {}
+func (*RecordChange) ProtoMessage() {}
 
-func (x *UserIds) ProtoReflect() protoreflect.Message {
+func (x *RecordChange) ProtoReflect() protoreflect.Message {
 	mi := &file_chromadb_proto_chroma_proto_msgTypes[15]
 	if protoimpl.UnsafeEnabled && x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -1482,36 +1752,40 @@ func (x *UserIds) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use UserIds.ProtoReflect.Descriptor instead.
-func (*UserIds) Descriptor() ([]byte, []int) {
+// Deprecated: Use RecordChange.ProtoReflect.Descriptor instead.
+func (*RecordChange) Descriptor() ([]byte, []int) {
 	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{15}
 }
 
-func (x *UserIds) GetIds() []string {
+func (x *RecordChange) GetOperation() Operation {
 	if x != nil {
-		return x.Ids
+		return x.Operation
+	}
+	return Operation_ADD
+}
+
+func (x *RecordChange) GetRecord() *MetadataEmbeddingRecord {
+	if x != nil {
+		return x.Record
 	}
 	return nil
 }
 
-// A `WhereDocument` clause for filtering metadata. A `WhereDocument` clause is a tree of
-// `WhereDocument` clauses, where each node is exactly one of:
-// - A leaf node representing a `$contains` or `$not_contains` query directly.
-// - An branch node with a list of children and a way to combine them (AND or OR).
-type WhereDocument struct {
+type StreamChangesResponse struct {
 	state         protoimpl.MessageState
 	sizeCache     protoimpl.SizeCache
 	unknownFields protoimpl.UnknownFields
 
-	// Types that are assignable to WhereDocument:
-	//
-	//	*WhereDocument_Direct
-	//	*WhereDocument_Children
-	WhereDocument isWhereDocument_WhereDocument `protobuf_oneof:"where_document"`
+	// The changes in the order that the log first changes the records. A record that the log
+	// changes several times has a single change, with its state after the last of them.
+	Changes []*RecordChange `protobuf:"bytes,1,rep,name=changes,proto3" json:"changes,omitempty"`
+	// The offset of the last log whose changes are streamed, which the next request starts
+	// after. It is the same for every message of a stream.
+	LogOffset uint64 `protobuf:"varint,2,opt,name=log_offset,json=logOffset,proto3" json:"log_offset,omitempty"`
 }
 
-func (x *WhereDocument) Reset() {
-	*x = WhereDocument{}
+func (x *StreamChangesResponse) Reset() {
+	*x = StreamChangesResponse{}
 	if protoimpl.UnsafeEnabled {
 		mi := &file_chromadb_proto_chroma_proto_msgTypes[16]
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -1519,13 +1793,13 @@ func (x *WhereDocument) Reset() {
 	}
 }
 
-func (x *WhereDocument) String() string {
+func (x *StreamChangesResponse) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*WhereDocument) ProtoMessage() {}
+func (*StreamChangesResponse) ProtoMessage() {}
 
-func (x *WhereDocument) ProtoReflect() protoreflect.Message {
+func (x *StreamChangesResponse) ProtoReflect() protoreflect.Message {
 	mi := &file_chromadb_proto_chroma_proto_msgTypes[16]
 	if protoimpl.UnsafeEnabled && x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -1537,60 +1811,37 @@ func (x *WhereDocument) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use WhereDocument.ProtoReflect.Descriptor instead.
-func (*WhereDocument) Descriptor() ([]byte, []int) {
+// Deprecated: Use StreamChangesResponse.ProtoReflect.Descriptor instead.
+func (*StreamChangesResponse) Descriptor() ([]byte, []int) {
 	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{16}
 }
 
-func (m *WhereDocument) GetWhereDocument() isWhereDocument_WhereDocument {
-	if m != nil {
-		return m.WhereDocument
-	}
-	return nil
-}
-
-func (x *WhereDocument) GetDirect() *DirectWhereDocument {
-	if x, ok := x.GetWhereDocument().(*WhereDocument_Direct); ok {
-		return x.Direct
+func (x *StreamChangesResponse) GetChanges() []*RecordChange {
+	if x != nil {
+		return x.Changes
 	}
 	return nil
 }
 
-func (x *WhereDocument) GetChildren() *WhereDocumentChildren {
-	if x, ok := x.GetWhereDocument().(*WhereDocument_Children); ok {
-		return x.Children
+func (x *StreamChangesResponse) GetLogOffset() uint64 {
+	if x != nil {
+		return x.LogOffset
 	}
-	return nil
-}
-
-type isWhereDocument_WhereDocument interface {
-	isWhereDocument_WhereDocument()
-}
-
-type WhereDocument_Direct struct {
-	Direct *DirectWhereDocument `protobuf:"bytes,1,opt,name=direct,proto3,oneof"`
-}
-
-type WhereDocument_Children struct {
-	Children *WhereDocumentChildren `protobuf:"bytes,2,opt,name=children,proto3,oneof"`
+	return 0
 }
 
-func (*WhereDocument_Direct) isWhereDocument_WhereDocument() {}
-
-func (*WhereDocument_Children) isWhereDocument_WhereDocument() {}
-
-// A leaf-node `WhereDocument` clause may compare a document to a single value.
-type DirectWhereDocument struct {
+type CountRecordsRequest struct {
 	state         protoimpl.MessageState
 	sizeCache     protoimpl.SizeCache
 	unknownFields protoimpl.UnknownFields
 
-	Document string                `protobuf:"bytes,1,opt,name=document,proto3" json:"document,omitempty"`
-	Operator WhereDocumentOperator `protobuf:"varint,2,opt,name=operator,proto3,enum=chroma.WhereDocumentOperator" json:"operator,omitempty"`
+	SegmentId      string                 `protobuf:"bytes,1,opt,name=segment_id,json=segmentId,proto3" json:"segment_id,omitempty"`
+	CollectionId   string                 `protobuf:"bytes,2,opt,name=collection_id,json=collectionId,proto3" json:"collection_id,omitempty"`
+	VersionContext *RequestVersionContext `protobuf:"bytes,3,opt,name=version_context,json=versionContext,proto3" json:"version_context,omitempty"`
 }
 
-func (x *DirectWhereDocument) Reset() {
-	*x = DirectWhereDocument{}
+func (x *CountRecordsRequest) Reset() {
+	*x = CountRecordsRequest{}
 	if protoimpl.UnsafeEnabled {
 		mi := &file_chromadb_proto_chroma_proto_msgTypes[17]
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -1598,13 +1849,13 @@ func (x *DirectWhereDocument) Reset() {
 	}
 }
 
-func (x *DirectWhereDocument) String() string {
+func (x *CountRecordsRequest) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*DirectWhereDocument) ProtoMessage() {}
+func (*CountRecordsRequest) ProtoMessage() {}
 
-func (x *DirectWhereDocument) ProtoReflect() protoreflect.Message {
+func (x *CountRecordsRequest) ProtoReflect() protoreflect.Message {
 	mi := &file_chromadb_proto_chroma_proto_msgTypes[17]
 	if protoimpl.UnsafeEnabled && x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -1616,37 +1867,43 @@ func (x *DirectWhereDocument) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use DirectWhereDocument.ProtoReflect.Descriptor instead.
-func (*DirectWhereDocument) Descriptor() ([]byte, []int) {
+// Deprecated: Use CountRecordsRequest.ProtoReflect.Descriptor instead.
+func (*CountRecordsRequest) Descriptor() ([]byte, []int) {
 	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{17}
 }
 
-func (x *DirectWhereDocument) GetDocument() string {
+func (x *CountRecordsRequest) GetSegmentId() string {
 	if x != nil {
-		return x.Document
+		return x.SegmentId
 	}
 	return ""
 }
 
-func (x *DirectWhereDocument) GetOperator() WhereDocumentOperator {
+func (x *CountRecordsRequest) GetCollectionId() string {
 	if x != nil {
-		return x.Operator
+		return x.CollectionId
 	}
-	return WhereDocumentOperator_CONTAINS
+	return ""
 }
 
-// A branch-node `WhereDocument` node has a list of children.
-type WhereDocumentChildren struct {
+func (x *CountRecordsRequest) GetVersionContext() *RequestVersionContext {
+	if x != nil {
+		return x.VersionContext
+	}
+	return nil
+}
+
+// TODO: Add error propagation in the response.
+type CountRecordsResponse struct {
 	state         protoimpl.MessageState
 	sizeCache     protoimpl.SizeCache
 	unknownFields protoimpl.UnknownFields
 
-	Children []*WhereDocument `protobuf:"bytes,1,rep,name=children,proto3" json:"children,omitempty"`
-	Operator BooleanOperator  `protobuf:"varint,2,opt,name=operator,proto3,enum=chroma.BooleanOperator" json:"operator,omitempty"`
+	Count uint32 `protobuf:"varint,1,opt,name=count,proto3" json:"count,omitempty"`
 }
 
-func (x *WhereDocumentChildren) Reset() {
-	*x = WhereDocumentChildren{}
+func (x *CountRecordsResponse) Reset() {
+	*x = CountRecordsResponse{}
 	if protoimpl.UnsafeEnabled {
 		mi := &file_chromadb_proto_chroma_proto_msgTypes[18]
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -1654,13 +1911,13 @@ func (x *WhereDocumentChildren) Reset() {
 	}
 }
 
-func (x *WhereDocumentChildren) String() string {
+func (x *CountRecordsResponse) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*WhereDocumentChildren) ProtoMessage() {}
+func (*CountRecordsResponse) ProtoMessage() {}
 
-func (x *WhereDocumentChildren) ProtoReflect() protoreflect.Message {
+func (x *CountRecordsResponse) ProtoReflect() protoreflect.Message {
 	mi := &file_chromadb_proto_chroma_proto_msgTypes[18]
 	if protoimpl.UnsafeEnabled && x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -1672,44 +1929,48 @@ func (x *WhereDocumentChildren) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use WhereDocumentChildren.ProtoReflect.Descriptor instead.
-func (*WhereDocumentChildren) Descriptor() ([]byte, []int) {
+// Deprecated: Use CountRecordsResponse.ProtoReflect.Descriptor instead.
+func (*CountRecordsResponse) Descriptor() ([]byte, []int) {
 	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{18}
 }
 
-func (x *WhereDocumentChildren) GetChildren() []*WhereDocument {
-	if x != nil {
-		return x.Children
-	}
-	return nil
-}
-
-func (x *WhereDocumentChildren) GetOperator() BooleanOperator {
+func (x *CountRecordsResponse) GetCount() uint32 {
 	if x != nil {
-		return x.Operator
+		return x.Count
 	}
-	return BooleanOperator_AND
+	return 0
 }
 
-// A `Where` clause for filtering metadata. A `Where` clause is a tree of
-// `Where` clauses, where each node is exactly one of:
-//   - A leaf node representing a direct comparison between a metadata key and a
-//     value or list of values.
-//   - An branch node with a list of children and a way to combine them (AND or OR).
-type Where struct {
+type QueryMetadataRequest struct {
 	state         protoimpl.MessageState
 	sizeCache     protoimpl.SizeCache
 	unknownFields protoimpl.UnknownFields
 
-	// Types that are assignable to Where:
-	//
-	//	*Where_DirectComparison
-	//	*Where_Children
-	Where isWhere_Where `protobuf_oneof:"where"`
+	SegmentId         string                 `protobuf:"bytes,1,opt,name=segment_id,json=segmentId,proto3" json:"segment_id,omitempty"`
+	Where             *Where                 `protobuf:"bytes,2,opt,name=where,proto3" json:"where,omitempty"`
+	WhereDocument     *WhereDocument         `protobuf:"bytes,3,opt,name=where_document,json=whereDocument,proto3" json:"where_document,omitempty"`
+	Ids               *UserIds               `protobuf:"bytes,4,opt,name=ids,proto3,oneof" json:"ids,omitempty"`
+	Limit             *uint32                `protobuf:"varint,5,opt,name=limit,proto3,oneof" json:"limit,omitempty"`
+	Offset            *uint32                `protobuf:"varint,6,opt,name=offset,proto3,oneof" json:"offset,omitempty"`
+	CollectionId      string                 `protobuf:"bytes,7,opt,name=collection_id,json=collectionId,proto3" json:"collection_id,omitempty"`
+	IncludeMetadata   bool                   `protobuf:"varint,8,opt,name=include_metadata,json=includeMetadata,proto3" json:"include_metadata,omitempty"`
+	VersionContext    *RequestVersionContext `protobuf:"bytes,9,opt,name=version_context,json=versionContext,proto3" json:"version_context,omitempty"`
+	IncludeEmbeddings bool                   `protobuf:"varint,10,opt,name=include_embeddings,json=includeEmbeddings,proto3" json:"include_embeddings,omitempty"`
+	// The total size of the embeddings returned inline. The embeddings past it are
+	// returned as handles. Defaults to the limit of the server, if any.
+	MaxEmbeddingBytes *uint64 `protobuf:"varint,11,opt,name=max_embedding_bytes,json=maxEmbeddingBytes,proto3,oneof" json:"max_embedding_bytes,omitempty"`
+	// Returns the plan of the query together with its result.
+	Explain bool `protobuf:"varint,12,opt,name=explain,proto3" json:"explain,omitempty"`
+	// Returns the records of another collection that the records of the result reference.
+	Lookup *Lookup `protobuf:"bytes,13,opt,name=lookup,proto3,oneof" json:"lookup,omitempty"`
+	// Returns the fields of the records that the projection defaults of the collection include,
+	// instead of the ones that the request includes. Set when the caller leaves the include
+	// unspecified.
+	DefaultInclude bool `protobuf:"varint,14,opt,name=default_include,json=defaultInclude,proto3" json:"default_include,omitempty"`
 }
 
-func (x *Where) Reset() {
-	*x = Where{}
+func (x *QueryMetadataRequest) Reset() {
+	*x = QueryMetadataRequest{}
 	if protoimpl.UnsafeEnabled {
 		mi := &file_chromadb_proto_chroma_proto_msgTypes[19]
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -1717,13 +1978,13 @@ func (x *Where) Reset() {
 	}
 }
 
-func (x *Where) String() string {
+func (x *QueryMetadataRequest) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*Where) ProtoMessage() {}
+func (*QueryMetadataRequest) ProtoMessage() {}
 
-func (x *Where) ProtoReflect() protoreflect.Message {
+func (x *QueryMetadataRequest) ProtoReflect() protoreflect.Message {
 	mi := &file_chromadb_proto_chroma_proto_msgTypes[19]
 	if protoimpl.UnsafeEnabled && x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -1735,70 +1996,131 @@ func (x *Where) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use Where.ProtoReflect.Descriptor instead.
-func (*Where) Descriptor() ([]byte, []int) {
+// Deprecated: Use QueryMetadataRequest.ProtoReflect.Descriptor instead.
+func (*QueryMetadataRequest) Descriptor() ([]byte, []int) {
 	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{19}
 }
 
-func (m *Where) GetWhere() isWhere_Where {
-	if m != nil {
-		return m.Where
+func (x *QueryMetadataRequest) GetSegmentId() string {
+	if x != nil {
+		return x.SegmentId
+	}
+	return ""
+}
+
+func (x *QueryMetadataRequest) GetWhere() *Where {
+	if x != nil {
+		return x.Where
 	}
 	return nil
 }
 
-func (x *Where) GetDirectComparison() *DirectComparison {
-	if x, ok := x.GetWhere().(*Where_DirectComparison); ok {
-		return x.DirectComparison
+func (x *QueryMetadataRequest) GetWhereDocument() *WhereDocument {
+	if x != nil {
+		return x.WhereDocument
 	}
 	return nil
 }
 
-func (x *Where) GetChildren() *WhereChildren {
-	if x, ok := x.GetWhere().(*Where_Children); ok {
-		return x.Children
+func (x *QueryMetadataRequest) GetIds() *UserIds {
+	if x != nil {
+		return x.Ids
 	}
 	return nil
 }
 
-type isWhere_Where interface {
-	isWhere_Where()
+func (x *QueryMetadataRequest) GetLimit() uint32 {
+	if x != nil && x.Limit != nil {
+		return *x.Limit
+	}
+	return 0
 }
 
-type Where_DirectComparison struct {
-	DirectComparison *DirectComparison `protobuf:"bytes,1,opt,name=direct_comparison,json=directComparison,proto3,oneof"`
+func (x *QueryMetadataRequest) GetOffset() uint32 {
+	if x != nil && x.Offset != nil {
+		return *x.Offset
+	}
+	return 0
 }
 
-type Where_Children struct {
-	Children *WhereChildren `protobuf:"bytes,2,opt,name=children,proto3,oneof"`
+func (x *QueryMetadataRequest) GetCollectionId() string {
+	if x != nil {
+		return x.CollectionId
+	}
+	return ""
 }
 
-func (*Where_DirectComparison) isWhere_Where() {}
+func (x *QueryMetadataRequest) GetIncludeMetadata() bool {
+	if x != nil {
+		return x.IncludeMetadata
+	}
+	return false
+}
 
-func (*Where_Children) isWhere_Where() {}
+func (x *QueryMetadataRequest) GetVersionContext() *RequestVersionContext {
+	if x != nil {
+		return x.VersionContext
+	}
+	return nil
+}
 
-// A leaf-node `Where` clause.
-type DirectComparison struct {
+func (x *QueryMetadataRequest) GetIncludeEmbeddings() bool {
+	if x != nil {
+		return x.IncludeEmbeddings
+	}
+	return false
+}
+
+func (x *QueryMetadataRequest) GetMaxEmbeddingBytes() uint64 {
+	if x != nil && x.MaxEmbeddingBytes != nil {
+		return *x.MaxEmbeddingBytes
+	}
+	return 0
+}
+
+func (x *QueryMetadataRequest) GetExplain() bool {
+	if x != nil {
+		return x.Explain
+	}
+	return false
+}
+
+func (x *QueryMetadataRequest) GetLookup() *Lookup {
+	if x != nil {
+		return x.Lookup
+	}
+	return nil
+}
+
+func (x *QueryMetadataRequest) GetDefaultInclude() bool {
+	if x != nil {
+		return x.DefaultInclude
+	}
+	return false
+}
+
+// The records of another collection referenced by the records of a get, e.g. the parent
+// documents of the chunks that a query matches.
+type Lookup struct {
 	state         protoimpl.MessageState
 	sizeCache     protoimpl.SizeCache
 	unknownFields protoimpl.UnknownFields
 
-	Key string `protobuf:"bytes,1,opt,name=key,proto3" json:"key,omitempty"`
-	// Types that are assignable to Comparison:
-	//
-	//	*DirectComparison_SingleStringOperand
-	//	*DirectComparison_StringListOperand
-	//	*DirectComparison_SingleIntOperand
-	//	*DirectComparison_IntListOperand
-	//	*DirectComparison_SingleDoubleOperand
-	//	*DirectComparison_DoubleListOperand
-	//	*DirectComparison_BoolListOperand
-	//	*DirectComparison_SingleBoolOperand
-	Comparison isDirectComparison_Comparison `protobuf_oneof:"comparison"`
-}
-
-func (x *DirectComparison) Reset() {
-	*x = DirectComparison{}
+	CollectionId   string                 `protobuf:"bytes,1,opt,name=collection_id,json=collectionId,proto3" json:"collection_id,omitempty"`
+	VersionContext *RequestVersionContext `protobuf:"bytes,2,opt,name=version_context,json=versionContext,proto3" json:"version_context,omitempty"`
+	// The metadata key whose string value is the id of the referenced record. The record
+	// references the record with its own id if it is unset. Requires the request to
+	// include_metadata.
+	IdKey             *string `protobuf:"bytes,3,opt,name=id_key,json=idKey,proto3,oneof" json:"id_key,omitempty"`
+	IncludeMetadata   bool    `protobuf:"varint,4,opt,name=include_metadata,json=includeMetadata,proto3" json:"include_metadata,omitempty"`
+	IncludeEmbeddings bool    `protobuf:"varint,5,opt,name=include_embeddings,json=includeEmbeddings,proto3" json:"include_embeddings,omitempty"`
+	// Returns the fields that the projection defaults of the lookup collection include, instead
+	// of the ones that the lookup includes.
+	DefaultInclude bool `protobuf:"varint,6,opt,name=default_include,json=defaultInclude,proto3" json:"default_include,omitempty"`
+}
+
+func (x *Lookup) Reset() {
+	*x = Lookup{}
 	if protoimpl.UnsafeEnabled {
 		mi := &file_chromadb_proto_chroma_proto_msgTypes[20]
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -1806,13 +2128,13 @@ func (x *DirectComparison) Reset() {
 	}
 }
 
-func (x *DirectComparison) String() string {
+func (x *Lookup) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*DirectComparison) ProtoMessage() {}
+func (*Lookup) ProtoMessage() {}
 
-func (x *DirectComparison) ProtoReflect() protoreflect.Message {
+func (x *Lookup) ProtoReflect() protoreflect.Message {
 	mi := &file_chromadb_proto_chroma_proto_msgTypes[20]
 	if protoimpl.UnsafeEnabled && x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -1824,161 +2146,147 @@ func (x *DirectComparison) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use DirectComparison.ProtoReflect.Descriptor instead.
-func (*DirectComparison) Descriptor() ([]byte, []int) {
+// Deprecated: Use Lookup.ProtoReflect.Descriptor instead.
+func (*Lookup) Descriptor() ([]byte, []int) {
 	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{20}
 }
 
-func (x *DirectComparison) GetKey() string {
+func (x *Lookup) GetCollectionId() string {
 	if x != nil {
-		return x.Key
+		return x.CollectionId
 	}
 	return ""
 }
 
-func (m *DirectComparison) GetComparison() isDirectComparison_Comparison {
-	if m != nil {
-		return m.Comparison
+func (x *Lookup) GetVersionContext() *RequestVersionContext {
+	if x != nil {
+		return x.VersionContext
 	}
 	return nil
 }
 
-func (x *DirectComparison) GetSingleStringOperand() *SingleStringComparison {
-	if x, ok := x.GetComparison().(*DirectComparison_SingleStringOperand); ok {
-		return x.SingleStringOperand
+func (x *Lookup) GetIdKey() string {
+	if x != nil && x.IdKey != nil {
+		return *x.IdKey
 	}
-	return nil
+	return ""
 }
 
-func (x *DirectComparison) GetStringListOperand() *StringListComparison {
-	if x, ok := x.GetComparison().(*DirectComparison_StringListOperand); ok {
-		return x.StringListOperand
+func (x *Lookup) GetIncludeMetadata() bool {
+	if x != nil {
+		return x.IncludeMetadata
 	}
-	return nil
+	return false
 }
 
-func (x *DirectComparison) GetSingleIntOperand() *SingleIntComparison {
-	if x, ok := x.GetComparison().(*DirectComparison_SingleIntOperand); ok {
-		return x.SingleIntOperand
+func (x *Lookup) GetIncludeEmbeddings() bool {
+	if x != nil {
+		return x.IncludeEmbeddings
 	}
-	return nil
+	return false
 }
 
-func (x *DirectComparison) GetIntListOperand() *IntListComparison {
-	if x, ok := x.GetComparison().(*DirectComparison_IntListOperand); ok {
-		return x.IntListOperand
+func (x *Lookup) GetDefaultInclude() bool {
+	if x != nil {
+		return x.DefaultInclude
 	}
-	return nil
+	return false
 }
 
-func (x *DirectComparison) GetSingleDoubleOperand() *SingleDoubleComparison {
-	if x, ok := x.GetComparison().(*DirectComparison_SingleDoubleOperand); ok {
-		return x.SingleDoubleOperand
-	}
-	return nil
+type QueryMetadataResponse struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	Records []*MetadataEmbeddingRecord `protobuf:"bytes,1,rep,name=records,proto3" json:"records,omitempty"`
+	Plan    *QueryPlan                 `protobuf:"bytes,2,opt,name=plan,proto3,oneof" json:"plan,omitempty"`
+	// The records referenced by the lookup of the request by their ids. A record referenced
+	// by several records of the result is returned once, and missing records are left out.
+	LookupRecords map[string]*MetadataEmbeddingRecord `protobuf:"bytes,3,rep,name=lookup_records,json=lookupRecords,proto3" json:"lookup_records,omitempty" protobuf_key:"bytes,1,opt,name=key,proto3" protobuf_val:"bytes,2,opt,name=value,proto3"`
 }
 
-func (x *DirectComparison) GetDoubleListOperand() *DoubleListComparison {
-	if x, ok := x.GetComparison().(*DirectComparison_DoubleListOperand); ok {
-		return x.DoubleListOperand
+func (x *QueryMetadataResponse) Reset() {
+	*x = QueryMetadataResponse{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[21]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
 	}
-	return nil
 }
 
-func (x *DirectComparison) GetBoolListOperand() *BoolListComparison {
-	if x, ok := x.GetComparison().(*DirectComparison_BoolListOperand); ok {
-		return x.BoolListOperand
-	}
-	return nil
+func (x *QueryMetadataResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
 }
 
-func (x *DirectComparison) GetSingleBoolOperand() *SingleBoolComparison {
-	if x, ok := x.GetComparison().(*DirectComparison_SingleBoolOperand); ok {
-		return x.SingleBoolOperand
+func (*QueryMetadataResponse) ProtoMessage() {}
+
+func (x *QueryMetadataResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[21]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
 	}
-	return nil
+	return mi.MessageOf(x)
 }
 
-type isDirectComparison_Comparison interface {
-	isDirectComparison_Comparison()
-}
-
-type DirectComparison_SingleStringOperand struct {
-	SingleStringOperand *SingleStringComparison `protobuf:"bytes,2,opt,name=single_string_operand,json=singleStringOperand,proto3,oneof"`
-}
-
-type DirectComparison_StringListOperand struct {
-	StringListOperand *StringListComparison `protobuf:"bytes,3,opt,name=string_list_operand,json=stringListOperand,proto3,oneof"`
-}
-
-type DirectComparison_SingleIntOperand struct {
-	SingleIntOperand *SingleIntComparison `protobuf:"bytes,4,opt,name=single_int_operand,json=singleIntOperand,proto3,oneof"`
-}
-
-type DirectComparison_IntListOperand struct {
-	IntListOperand *IntListComparison `protobuf:"bytes,5,opt,name=int_list_operand,json=intListOperand,proto3,oneof"`
-}
-
-type DirectComparison_SingleDoubleOperand struct {
-	SingleDoubleOperand *SingleDoubleComparison `protobuf:"bytes,6,opt,name=single_double_operand,json=singleDoubleOperand,proto3,oneof"`
+// Deprecated: Use QueryMetadataResponse.ProtoReflect.Descriptor instead.
+func (*QueryMetadataResponse) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{21}
 }
 
-type DirectComparison_DoubleListOperand struct {
-	DoubleListOperand *DoubleListComparison `protobuf:"bytes,7,opt,name=double_list_operand,json=doubleListOperand,proto3,oneof"`
+func (x *QueryMetadataResponse) GetRecords() []*MetadataEmbeddingRecord {
+	if x != nil {
+		return x.Records
+	}
+	return nil
 }
 
-type DirectComparison_BoolListOperand struct {
-	BoolListOperand *BoolListComparison `protobuf:"bytes,8,opt,name=bool_list_operand,json=boolListOperand,proto3,oneof"`
+func (x *QueryMetadataResponse) GetPlan() *QueryPlan {
+	if x != nil {
+		return x.Plan
+	}
+	return nil
 }
 
-type DirectComparison_SingleBoolOperand struct {
-	SingleBoolOperand *SingleBoolComparison `protobuf:"bytes,9,opt,name=single_bool_operand,json=singleBoolOperand,proto3,oneof"`
+func (x *QueryMetadataResponse) GetLookupRecords() map[string]*MetadataEmbeddingRecord {
+	if x != nil {
+		return x.LookupRecords
+	}
+	return nil
 }
 
-func (*DirectComparison_SingleStringOperand) isDirectComparison_Comparison() {}
-
-func (*DirectComparison_StringListOperand) isDirectComparison_Comparison() {}
-
-func (*DirectComparison_SingleIntOperand) isDirectComparison_Comparison() {}
-
-func (*DirectComparison_IntListOperand) isDirectComparison_Comparison() {}
-
-func (*DirectComparison_SingleDoubleOperand) isDirectComparison_Comparison() {}
-
-func (*DirectComparison_DoubleListOperand) isDirectComparison_Comparison() {}
-
-func (*DirectComparison_BoolListOperand) isDirectComparison_Comparison() {}
-
-func (*DirectComparison_SingleBoolOperand) isDirectComparison_Comparison() {}
-
-// A branch-node `Where` clause has a list of children and a specification
-// for how to combine them.
-type WhereChildren struct {
+// The plan of a query as it was executed.
+type QueryPlan struct {
 	state         protoimpl.MessageState
 	sizeCache     protoimpl.SizeCache
 	unknownFields protoimpl.UnknownFields
 
-	Children []*Where        `protobuf:"bytes,1,rep,name=children,proto3" json:"children,omitempty"`
-	Operator BooleanOperator `protobuf:"varint,2,opt,name=operator,proto3,enum=chroma.BooleanOperator" json:"operator,omitempty"`
+	// The choices that the query made while it was evaluated, e.g. the index it searched.
+	Strategy map[string]string `protobuf:"bytes,1,rep,name=strategy,proto3" json:"strategy,omitempty" protobuf_key:"bytes,1,opt,name=key,proto3" protobuf_val:"bytes,2,opt,name=value,proto3"`
+	// The operators of the query in the order that they were scheduled.
+	Nodes []*QueryPlanNode `protobuf:"bytes,2,rep,name=nodes,proto3" json:"nodes,omitempty"`
 }
 
-func (x *WhereChildren) Reset() {
-	*x = WhereChildren{}
+func (x *QueryPlan) Reset() {
+	*x = QueryPlan{}
 	if protoimpl.UnsafeEnabled {
-		mi := &file_chromadb_proto_chroma_proto_msgTypes[21]
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[22]
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		ms.StoreMessageInfo(mi)
 	}
 }
 
-func (x *WhereChildren) String() string {
+func (x *QueryPlan) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*WhereChildren) ProtoMessage() {}
+func (*QueryPlan) ProtoMessage() {}
 
-func (x *WhereChildren) ProtoReflect() protoreflect.Message {
-	mi := &file_chromadb_proto_chroma_proto_msgTypes[21]
+func (x *QueryPlan) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[22]
 	if protoimpl.UnsafeEnabled && x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -1989,53 +2297,61 @@ func (x *WhereChildren) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use WhereChildren.ProtoReflect.Descriptor instead.
-func (*WhereChildren) Descriptor() ([]byte, []int) {
-	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{21}
+// Deprecated: Use QueryPlan.ProtoReflect.Descriptor instead.
+func (*QueryPlan) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{22}
 }
 
-func (x *WhereChildren) GetChildren() []*Where {
+func (x *QueryPlan) GetStrategy() map[string]string {
 	if x != nil {
-		return x.Children
+		return x.Strategy
 	}
 	return nil
 }
 
-func (x *WhereChildren) GetOperator() BooleanOperator {
+func (x *QueryPlan) GetNodes() []*QueryPlanNode {
 	if x != nil {
-		return x.Operator
+		return x.Nodes
 	}
-	return BooleanOperator_AND
+	return nil
 }
 
-// Used when a leaf-node `Where` clause compares a string to a list of strings.
-// `ListOperator` specifies whether values in the list are allowed or disallowed.
-type StringListComparison struct {
+type QueryPlanNode struct {
 	state         protoimpl.MessageState
 	sizeCache     protoimpl.SizeCache
 	unknownFields protoimpl.UnknownFields
 
-	Values       []string     `protobuf:"bytes,1,rep,name=values,proto3" json:"values,omitempty"`
-	ListOperator ListOperator `protobuf:"varint,2,opt,name=list_operator,json=listOperator,proto3,enum=chroma.ListOperator" json:"list_operator,omitempty"`
-}
-
-func (x *StringListComparison) Reset() {
-	*x = StringListComparison{}
+	Operator string `protobuf:"bytes,1,opt,name=operator,proto3" json:"operator,omitempty"`
+	// The indices of the nodes whose outputs the operator consumed.
+	Inputs        []uint32 `protobuf:"varint,2,rep,packed,name=inputs,proto3" json:"inputs,omitempty"`
+	EstimatedRows *uint64  `protobuf:"varint,3,opt,name=estimated_rows,json=estimatedRows,proto3,oneof" json:"estimated_rows,omitempty"`
+	OutputRows    *uint64  `protobuf:"varint,4,opt,name=output_rows,json=outputRows,proto3,oneof" json:"output_rows,omitempty"`
+	// The time from when the operator was scheduled until its output was received.
+	ElapsedMicros *uint64 `protobuf:"varint,5,opt,name=elapsed_micros,json=elapsedMicros,proto3,oneof" json:"elapsed_micros,omitempty"`
+	// The blocks that the operator read from the block cache and from storage.
+	CacheHits   uint64 `protobuf:"varint,6,opt,name=cache_hits,json=cacheHits,proto3" json:"cache_hits,omitempty"`
+	CacheMisses uint64 `protobuf:"varint,7,opt,name=cache_misses,json=cacheMisses,proto3" json:"cache_misses,omitempty"`
+	// The bytes of the blocks that the operator read from storage.
+	BytesRead uint64 `protobuf:"varint,8,opt,name=bytes_read,json=bytesRead,proto3" json:"bytes_read,omitempty"`
+}
+
+func (x *QueryPlanNode) Reset() {
+	*x = QueryPlanNode{}
 	if protoimpl.UnsafeEnabled {
-		mi := &file_chromadb_proto_chroma_proto_msgTypes[22]
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[23]
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		ms.StoreMessageInfo(mi)
 	}
 }
 
-func (x *StringListComparison) String() string {
+func (x *QueryPlanNode) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*StringListComparison) ProtoMessage() {}
+func (*QueryPlanNode) ProtoMessage() {}
 
-func (x *StringListComparison) ProtoReflect() protoreflect.Message {
-	mi := &file_chromadb_proto_chroma_proto_msgTypes[22]
+func (x *QueryPlanNode) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[23]
 	if protoimpl.UnsafeEnabled && x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -2046,92 +2362,81 @@ func (x *StringListComparison) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use StringListComparison.ProtoReflect.Descriptor instead.
-func (*StringListComparison) Descriptor() ([]byte, []int) {
-	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{22}
+// Deprecated: Use QueryPlanNode.ProtoReflect.Descriptor instead.
+func (*QueryPlanNode) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{23}
 }
 
-func (x *StringListComparison) GetValues() []string {
+func (x *QueryPlanNode) GetOperator() string {
 	if x != nil {
-		return x.Values
+		return x.Operator
 	}
-	return nil
+	return ""
 }
 
-func (x *StringListComparison) GetListOperator() ListOperator {
+func (x *QueryPlanNode) GetInputs() []uint32 {
 	if x != nil {
-		return x.ListOperator
+		return x.Inputs
 	}
-	return ListOperator_IN
-}
-
-// Used when a leaf-node `Where` clause compares a string to a single string.
-type SingleStringComparison struct {
-	state         protoimpl.MessageState
-	sizeCache     protoimpl.SizeCache
-	unknownFields protoimpl.UnknownFields
-
-	Value      string            `protobuf:"bytes,1,opt,name=value,proto3" json:"value,omitempty"`
-	Comparator GenericComparator `protobuf:"varint,2,opt,name=comparator,proto3,enum=chroma.GenericComparator" json:"comparator,omitempty"`
+	return nil
 }
 
-func (x *SingleStringComparison) Reset() {
-	*x = SingleStringComparison{}
-	if protoimpl.UnsafeEnabled {
-		mi := &file_chromadb_proto_chroma_proto_msgTypes[23]
-		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
-		ms.StoreMessageInfo(mi)
+func (x *QueryPlanNode) GetEstimatedRows() uint64 {
+	if x != nil && x.EstimatedRows != nil {
+		return *x.EstimatedRows
 	}
+	return 0
 }
 
-func (x *SingleStringComparison) String() string {
-	return protoimpl.X.MessageStringOf(x)
+func (x *QueryPlanNode) GetOutputRows() uint64 {
+	if x != nil && x.OutputRows != nil {
+		return *x.OutputRows
+	}
+	return 0
 }
 
-func (*SingleStringComparison) ProtoMessage() {}
-
-func (x *SingleStringComparison) ProtoReflect() protoreflect.Message {
-	mi := &file_chromadb_proto_chroma_proto_msgTypes[23]
-	if protoimpl.UnsafeEnabled && x != nil {
-		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
-		if ms.LoadMessageInfo() == nil {
-			ms.StoreMessageInfo(mi)
-		}
-		return ms
+func (x *QueryPlanNode) GetElapsedMicros() uint64 {
+	if x != nil && x.ElapsedMicros != nil {
+		return *x.ElapsedMicros
 	}
-	return mi.MessageOf(x)
+	return 0
 }
 
-// Deprecated: Use SingleStringComparison.ProtoReflect.Descriptor instead.
-func (*SingleStringComparison) Descriptor() ([]byte, []int) {
-	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{23}
+func (x *QueryPlanNode) GetCacheHits() uint64 {
+	if x != nil {
+		return x.CacheHits
+	}
+	return 0
 }
 
-func (x *SingleStringComparison) GetValue() string {
+func (x *QueryPlanNode) GetCacheMisses() uint64 {
 	if x != nil {
-		return x.Value
+		return x.CacheMisses
 	}
-	return ""
+	return 0
 }
 
-func (x *SingleStringComparison) GetComparator() GenericComparator {
+func (x *QueryPlanNode) GetBytesRead() uint64 {
 	if x != nil {
-		return x.Comparator
+		return x.BytesRead
 	}
-	return GenericComparator_EQ
+	return 0
 }
 
-type SingleBoolComparison struct {
+type MetadataEmbeddingRecord struct {
 	state         protoimpl.MessageState
 	sizeCache     protoimpl.SizeCache
 	unknownFields protoimpl.UnknownFields
 
-	Value      bool              `protobuf:"varint,1,opt,name=value,proto3" json:"value,omitempty"`
-	Comparator GenericComparator `protobuf:"varint,2,opt,name=comparator,proto3,enum=chroma.GenericComparator" json:"comparator,omitempty"`
+	Id        string          `protobuf:"bytes,1,opt,name=id,proto3" json:"id,omitempty"`
+	Metadata  *UpdateMetadata `protobuf:"bytes,2,opt,name=metadata,proto3" json:"metadata,omitempty"`
+	Embedding *Vector         `protobuf:"bytes,3,opt,name=embedding,proto3,oneof" json:"embedding,omitempty"`
+	// Set instead of the embedding if it does not fit in the inline budget of the response.
+	EmbeddingHandle *EmbeddingHandle `protobuf:"bytes,4,opt,name=embedding_handle,json=embeddingHandle,proto3,oneof" json:"embedding_handle,omitempty"`
 }
 
-func (x *SingleBoolComparison) Reset() {
-	*x = SingleBoolComparison{}
+func (x *MetadataEmbeddingRecord) Reset() {
+	*x = MetadataEmbeddingRecord{}
 	if protoimpl.UnsafeEnabled {
 		mi := &file_chromadb_proto_chroma_proto_msgTypes[24]
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -2139,13 +2444,13 @@ func (x *SingleBoolComparison) Reset() {
 	}
 }
 
-func (x *SingleBoolComparison) String() string {
+func (x *MetadataEmbeddingRecord) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*SingleBoolComparison) ProtoMessage() {}
+func (*MetadataEmbeddingRecord) ProtoMessage() {}
 
-func (x *SingleBoolComparison) ProtoReflect() protoreflect.Message {
+func (x *MetadataEmbeddingRecord) ProtoReflect() protoreflect.Message {
 	mi := &file_chromadb_proto_chroma_proto_msgTypes[24]
 	if protoimpl.UnsafeEnabled && x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -2157,38 +2462,54 @@ func (x *SingleBoolComparison) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use SingleBoolComparison.ProtoReflect.Descriptor instead.
-func (*SingleBoolComparison) Descriptor() ([]byte, []int) {
+// Deprecated: Use MetadataEmbeddingRecord.ProtoReflect.Descriptor instead.
+func (*MetadataEmbeddingRecord) Descriptor() ([]byte, []int) {
 	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{24}
 }
 
-func (x *SingleBoolComparison) GetValue() bool {
+func (x *MetadataEmbeddingRecord) GetId() string {
 	if x != nil {
-		return x.Value
+		return x.Id
 	}
-	return false
+	return ""
 }
 
-func (x *SingleBoolComparison) GetComparator() GenericComparator {
+func (x *MetadataEmbeddingRecord) GetMetadata() *UpdateMetadata {
 	if x != nil {
-		return x.Comparator
+		return x.Metadata
 	}
-	return GenericComparator_EQ
+	return nil
 }
 
-// Used when a leaf-node `Where` clause compares an int to a list of ints.
-// `ListOperator` specifies whether values in the list are allowed or disallowed.
-type IntListComparison struct {
+func (x *MetadataEmbeddingRecord) GetEmbedding() *Vector {
+	if x != nil {
+		return x.Embedding
+	}
+	return nil
+}
+
+func (x *MetadataEmbeddingRecord) GetEmbeddingHandle() *EmbeddingHandle {
+	if x != nil {
+		return x.EmbeddingHandle
+	}
+	return nil
+}
+
+// A handle to an embedding that is left out of a response, which is resolved with
+// `GetEmbeddings` at the same version of the collection that it was read at.
+type EmbeddingHandle struct {
 	state         protoimpl.MessageState
 	sizeCache     protoimpl.SizeCache
 	unknownFields protoimpl.UnknownFields
 
-	Values       []int64      `protobuf:"varint,1,rep,packed,name=values,proto3" json:"values,omitempty"`
-	ListOperator ListOperator `protobuf:"varint,2,opt,name=list_operator,json=listOperator,proto3,enum=chroma.ListOperator" json:"list_operator,omitempty"`
+	OffsetId       uint32                 `protobuf:"varint,1,opt,name=offset_id,json=offsetId,proto3" json:"offset_id,omitempty"`
+	VersionContext *RequestVersionContext `protobuf:"bytes,2,opt,name=version_context,json=versionContext,proto3" json:"version_context,omitempty"`
+	// The offset of the last log that the embedding was read at.
+	LogOffset *uint64 `protobuf:"varint,3,opt,name=log_offset,json=logOffset,proto3,oneof" json:"log_offset,omitempty"`
 }
 
-func (x *IntListComparison) Reset() {
-	*x = IntListComparison{}
+func (x *EmbeddingHandle) Reset() {
+	*x = EmbeddingHandle{}
 	if protoimpl.UnsafeEnabled {
 		mi := &file_chromadb_proto_chroma_proto_msgTypes[25]
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -2196,13 +2517,13 @@ func (x *IntListComparison) Reset() {
 	}
 }
 
-func (x *IntListComparison) String() string {
+func (x *EmbeddingHandle) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*IntListComparison) ProtoMessage() {}
+func (*EmbeddingHandle) ProtoMessage() {}
 
-func (x *IntListComparison) ProtoReflect() protoreflect.Message {
+func (x *EmbeddingHandle) ProtoReflect() protoreflect.Message {
 	mi := &file_chromadb_proto_chroma_proto_msgTypes[25]
 	if protoimpl.UnsafeEnabled && x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -2214,41 +2535,48 @@ func (x *IntListComparison) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use IntListComparison.ProtoReflect.Descriptor instead.
-func (*IntListComparison) Descriptor() ([]byte, []int) {
+// Deprecated: Use EmbeddingHandle.ProtoReflect.Descriptor instead.
+func (*EmbeddingHandle) Descriptor() ([]byte, []int) {
 	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{25}
 }
 
-func (x *IntListComparison) GetValues() []int64 {
+func (x *EmbeddingHandle) GetOffsetId() uint32 {
 	if x != nil {
-		return x.Values
+		return x.OffsetId
 	}
-	return nil
+	return 0
 }
 
-func (x *IntListComparison) GetListOperator() ListOperator {
+func (x *EmbeddingHandle) GetVersionContext() *RequestVersionContext {
 	if x != nil {
-		return x.ListOperator
+		return x.VersionContext
 	}
-	return ListOperator_IN
+	return nil
 }
 
-// Used when a leaf-node `Where` clause compares an int to a single int.
-type SingleIntComparison struct {
+func (x *EmbeddingHandle) GetLogOffset() uint64 {
+	if x != nil && x.LogOffset != nil {
+		return *x.LogOffset
+	}
+	return 0
+}
+
+type GetEmbeddingsRequest struct {
 	state         protoimpl.MessageState
 	sizeCache     protoimpl.SizeCache
 	unknownFields protoimpl.UnknownFields
 
-	Value int64 `protobuf:"varint,1,opt,name=value,proto3" json:"value,omitempty"`
-	// Types that are assignable to Comparator:
-	//
-	//	*SingleIntComparison_GenericComparator
-	//	*SingleIntComparison_NumberComparator
-	Comparator isSingleIntComparison_Comparator `protobuf_oneof:"comparator"`
+	SegmentId      string                 `protobuf:"bytes,1,opt,name=segment_id,json=segmentId,proto3" json:"segment_id,omitempty"`
+	CollectionId   string                 `protobuf:"bytes,2,opt,name=collection_id,json=collectionId,proto3" json:"collection_id,omitempty"`
+	VersionContext *RequestVersionContext `protobuf:"bytes,3,opt,name=version_context,json=versionContext,proto3" json:"version_context,omitempty"`
+	OffsetIds      []uint32               `protobuf:"varint,4,rep,packed,name=offset_ids,json=offsetIds,proto3" json:"offset_ids,omitempty"`
+	// The offset of the last log that the handles were read at, past which the logs are not
+	// read so that a later write does not change the embeddings.
+	LogOffset *uint64 `protobuf:"varint,5,opt,name=log_offset,json=logOffset,proto3,oneof" json:"log_offset,omitempty"`
 }
 
-func (x *SingleIntComparison) Reset() {
-	*x = SingleIntComparison{}
+func (x *GetEmbeddingsRequest) Reset() {
+	*x = GetEmbeddingsRequest{}
 	if protoimpl.UnsafeEnabled {
 		mi := &file_chromadb_proto_chroma_proto_msgTypes[26]
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -2256,13 +2584,13 @@ func (x *SingleIntComparison) Reset() {
 	}
 }
 
-func (x *SingleIntComparison) String() string {
+func (x *GetEmbeddingsRequest) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*SingleIntComparison) ProtoMessage() {}
+func (*GetEmbeddingsRequest) ProtoMessage() {}
 
-func (x *SingleIntComparison) ProtoReflect() protoreflect.Message {
+func (x *GetEmbeddingsRequest) ProtoReflect() protoreflect.Message {
 	mi := &file_chromadb_proto_chroma_proto_msgTypes[26]
 	if protoimpl.UnsafeEnabled && x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -2274,68 +2602,56 @@ func (x *SingleIntComparison) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use SingleIntComparison.ProtoReflect.Descriptor instead.
-func (*SingleIntComparison) Descriptor() ([]byte, []int) {
+// Deprecated: Use GetEmbeddingsRequest.ProtoReflect.Descriptor instead.
+func (*GetEmbeddingsRequest) Descriptor() ([]byte, []int) {
 	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{26}
 }
 
-func (x *SingleIntComparison) GetValue() int64 {
+func (x *GetEmbeddingsRequest) GetSegmentId() string {
 	if x != nil {
-		return x.Value
+		return x.SegmentId
 	}
-	return 0
+	return ""
 }
 
-func (m *SingleIntComparison) GetComparator() isSingleIntComparison_Comparator {
-	if m != nil {
-		return m.Comparator
+func (x *GetEmbeddingsRequest) GetCollectionId() string {
+	if x != nil {
+		return x.CollectionId
 	}
-	return nil
+	return ""
 }
 
-func (x *SingleIntComparison) GetGenericComparator() GenericComparator {
-	if x, ok := x.GetComparator().(*SingleIntComparison_GenericComparator); ok {
-		return x.GenericComparator
+func (x *GetEmbeddingsRequest) GetVersionContext() *RequestVersionContext {
+	if x != nil {
+		return x.VersionContext
 	}
-	return GenericComparator_EQ
+	return nil
 }
 
-func (x *SingleIntComparison) GetNumberComparator() NumberComparator {
-	if x, ok := x.GetComparator().(*SingleIntComparison_NumberComparator); ok {
-		return x.NumberComparator
+func (x *GetEmbeddingsRequest) GetOffsetIds() []uint32 {
+	if x != nil {
+		return x.OffsetIds
 	}
-	return NumberComparator_GT
-}
-
-type isSingleIntComparison_Comparator interface {
-	isSingleIntComparison_Comparator()
-}
-
-type SingleIntComparison_GenericComparator struct {
-	GenericComparator GenericComparator `protobuf:"varint,2,opt,name=generic_comparator,json=genericComparator,proto3,enum=chroma.GenericComparator,oneof"`
+	return nil
 }
 
-type SingleIntComparison_NumberComparator struct {
-	NumberComparator NumberComparator `protobuf:"varint,3,opt,name=number_comparator,json=numberComparator,proto3,enum=chroma.NumberComparator,oneof"`
+func (x *GetEmbeddingsRequest) GetLogOffset() uint64 {
+	if x != nil && x.LogOffset != nil {
+		return *x.LogOffset
+	}
+	return 0
 }
 
-func (*SingleIntComparison_GenericComparator) isSingleIntComparison_Comparator() {}
-
-func (*SingleIntComparison_NumberComparator) isSingleIntComparison_Comparator() {}
-
-// Used when a leaf-node `Where` clause compares a float to a list of floats.
-// `ListOperator` specifies whether values in the list are allowed or disallowed.
-type DoubleListComparison struct {
+type GetEmbeddingsResponse struct {
 	state         protoimpl.MessageState
 	sizeCache     protoimpl.SizeCache
 	unknownFields protoimpl.UnknownFields
 
-	Values       []float64    `protobuf:"fixed64,1,rep,packed,name=values,proto3" json:"values,omitempty"`
-	ListOperator ListOperator `protobuf:"varint,2,opt,name=list_operator,json=listOperator,proto3,enum=chroma.ListOperator" json:"list_operator,omitempty"`
+	Records []*VectorEmbeddingRecord `protobuf:"bytes,1,rep,name=records,proto3" json:"records,omitempty"`
 }
 
-func (x *DoubleListComparison) Reset() {
-	*x = DoubleListComparison{}
+func (x *GetEmbeddingsResponse) Reset() {
+	*x = GetEmbeddingsResponse{}
 	if protoimpl.UnsafeEnabled {
 		mi := &file_chromadb_proto_chroma_proto_msgTypes[27]
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -2343,13 +2659,13 @@ func (x *DoubleListComparison) Reset() {
 	}
 }
 
-func (x *DoubleListComparison) String() string {
+func (x *GetEmbeddingsResponse) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*DoubleListComparison) ProtoMessage() {}
+func (*GetEmbeddingsResponse) ProtoMessage() {}
 
-func (x *DoubleListComparison) ProtoReflect() protoreflect.Message {
+func (x *GetEmbeddingsResponse) ProtoReflect() protoreflect.Message {
 	mi := &file_chromadb_proto_chroma_proto_msgTypes[27]
 	if protoimpl.UnsafeEnabled && x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -2361,36 +2677,29 @@ func (x *DoubleListComparison) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use DoubleListComparison.ProtoReflect.Descriptor instead.
-func (*DoubleListComparison) Descriptor() ([]byte, []int) {
+// Deprecated: Use GetEmbeddingsResponse.ProtoReflect.Descriptor instead.
+func (*GetEmbeddingsResponse) Descriptor() ([]byte, []int) {
 	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{27}
 }
 
-func (x *DoubleListComparison) GetValues() []float64 {
+func (x *GetEmbeddingsResponse) GetRecords() []*VectorEmbeddingRecord {
 	if x != nil {
-		return x.Values
+		return x.Records
 	}
 	return nil
 }
 
-func (x *DoubleListComparison) GetListOperator() ListOperator {
-	if x != nil {
-		return x.ListOperator
-	}
-	return ListOperator_IN
-}
-
-type BoolListComparison struct {
+// A `UserIds` should contain the set of user provided ids allowed in the result.
+type UserIds struct {
 	state         protoimpl.MessageState
 	sizeCache     protoimpl.SizeCache
 	unknownFields protoimpl.UnknownFields
 
-	Values       []bool       `protobuf:"varint,1,rep,packed,name=values,proto3" json:"values,omitempty"`
-	ListOperator ListOperator `protobuf:"varint,2,opt,name=list_operator,json=listOperator,proto3,enum=chroma.ListOperator" json:"list_operator,omitempty"`
+	Ids []string `protobuf:"bytes,1,rep,name=ids,proto3" json:"ids,omitempty"`
 }
 
-func (x *BoolListComparison) Reset() {
-	*x = BoolListComparison{}
+func (x *UserIds) Reset() {
+	*x = UserIds{}
 	if protoimpl.UnsafeEnabled {
 		mi := &file_chromadb_proto_chroma_proto_msgTypes[28]
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -2398,13 +2707,13 @@ func (x *BoolListComparison) Reset() {
 	}
 }
 
-func (x *BoolListComparison) String() string {
+func (x *UserIds) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*BoolListComparison) ProtoMessage() {}
+func (*UserIds) ProtoMessage() {}
 
-func (x *BoolListComparison) ProtoReflect() protoreflect.Message {
+func (x *UserIds) ProtoReflect() protoreflect.Message {
 	mi := &file_chromadb_proto_chroma_proto_msgTypes[28]
 	if protoimpl.UnsafeEnabled && x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -2416,40 +2725,37 @@ func (x *BoolListComparison) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use BoolListComparison.ProtoReflect.Descriptor instead.
-func (*BoolListComparison) Descriptor() ([]byte, []int) {
+// Deprecated: Use UserIds.ProtoReflect.Descriptor instead.
+func (*UserIds) Descriptor() ([]byte, []int) {
 	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{28}
 }
 
-func (x *BoolListComparison) GetValues() []bool {
+func (x *UserIds) GetIds() []string {
 	if x != nil {
-		return x.Values
+		return x.Ids
 	}
 	return nil
 }
 
-func (x *BoolListComparison) GetListOperator() ListOperator {
-	if x != nil {
-		return x.ListOperator
-	}
-	return ListOperator_IN
-}
-
-type SingleDoubleComparison struct {
+// A `WhereDocument` clause for filtering metadata. A `WhereDocument` clause is a tree of
+// `WhereDocument` clauses, where each node is exactly one of:
+//   - A leaf node representing a `$contains`, `$not_contains`, `$near`, `$regex` or
+//     `$not_regex` query directly.
+//   - An branch node with a list of children and a way to combine them (AND or OR).
+type WhereDocument struct {
 	state         protoimpl.MessageState
 	sizeCache     protoimpl.SizeCache
 	unknownFields protoimpl.UnknownFields
 
-	Value float64 `protobuf:"fixed64,1,opt,name=value,proto3" json:"value,omitempty"`
-	// Types that are assignable to Comparator:
+	// Types that are assignable to WhereDocument:
 	//
-	//	*SingleDoubleComparison_GenericComparator
-	//	*SingleDoubleComparison_NumberComparator
-	Comparator isSingleDoubleComparison_Comparator `protobuf_oneof:"comparator"`
+	//	*WhereDocument_Direct
+	//	*WhereDocument_Children
+	WhereDocument isWhereDocument_WhereDocument `protobuf_oneof:"where_document"`
 }
 
-func (x *SingleDoubleComparison) Reset() {
-	*x = SingleDoubleComparison{}
+func (x *WhereDocument) Reset() {
+	*x = WhereDocument{}
 	if protoimpl.UnsafeEnabled {
 		mi := &file_chromadb_proto_chroma_proto_msgTypes[29]
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -2457,13 +2763,13 @@ func (x *SingleDoubleComparison) Reset() {
 	}
 }
 
-func (x *SingleDoubleComparison) String() string {
+func (x *WhereDocument) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*SingleDoubleComparison) ProtoMessage() {}
+func (*WhereDocument) ProtoMessage() {}
 
-func (x *SingleDoubleComparison) ProtoReflect() protoreflect.Message {
+func (x *WhereDocument) ProtoReflect() protoreflect.Message {
 	mi := &file_chromadb_proto_chroma_proto_msgTypes[29]
 	if protoimpl.UnsafeEnabled && x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -2475,68 +2781,62 @@ func (x *SingleDoubleComparison) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use SingleDoubleComparison.ProtoReflect.Descriptor instead.
-func (*SingleDoubleComparison) Descriptor() ([]byte, []int) {
+// Deprecated: Use WhereDocument.ProtoReflect.Descriptor instead.
+func (*WhereDocument) Descriptor() ([]byte, []int) {
 	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{29}
 }
 
-func (x *SingleDoubleComparison) GetValue() float64 {
-	if x != nil {
-		return x.Value
-	}
-	return 0
-}
-
-func (m *SingleDoubleComparison) GetComparator() isSingleDoubleComparison_Comparator {
+func (m *WhereDocument) GetWhereDocument() isWhereDocument_WhereDocument {
 	if m != nil {
-		return m.Comparator
+		return m.WhereDocument
 	}
 	return nil
 }
 
-func (x *SingleDoubleComparison) GetGenericComparator() GenericComparator {
-	if x, ok := x.GetComparator().(*SingleDoubleComparison_GenericComparator); ok {
-		return x.GenericComparator
+func (x *WhereDocument) GetDirect() *DirectWhereDocument {
+	if x, ok := x.GetWhereDocument().(*WhereDocument_Direct); ok {
+		return x.Direct
 	}
-	return GenericComparator_EQ
+	return nil
 }
 
-func (x *SingleDoubleComparison) GetNumberComparator() NumberComparator {
-	if x, ok := x.GetComparator().(*SingleDoubleComparison_NumberComparator); ok {
-		return x.NumberComparator
+func (x *WhereDocument) GetChildren() *WhereDocumentChildren {
+	if x, ok := x.GetWhereDocument().(*WhereDocument_Children); ok {
+		return x.Children
 	}
-	return NumberComparator_GT
+	return nil
 }
 
-type isSingleDoubleComparison_Comparator interface {
-	isSingleDoubleComparison_Comparator()
+type isWhereDocument_WhereDocument interface {
+	isWhereDocument_WhereDocument()
 }
 
-type SingleDoubleComparison_GenericComparator struct {
-	GenericComparator GenericComparator `protobuf:"varint,2,opt,name=generic_comparator,json=genericComparator,proto3,enum=chroma.GenericComparator,oneof"`
+type WhereDocument_Direct struct {
+	Direct *DirectWhereDocument `protobuf:"bytes,1,opt,name=direct,proto3,oneof"`
 }
 
-type SingleDoubleComparison_NumberComparator struct {
-	NumberComparator NumberComparator `protobuf:"varint,3,opt,name=number_comparator,json=numberComparator,proto3,enum=chroma.NumberComparator,oneof"`
+type WhereDocument_Children struct {
+	Children *WhereDocumentChildren `protobuf:"bytes,2,opt,name=children,proto3,oneof"`
 }
 
-func (*SingleDoubleComparison_GenericComparator) isSingleDoubleComparison_Comparator() {}
+func (*WhereDocument_Direct) isWhereDocument_WhereDocument() {}
 
-func (*SingleDoubleComparison_NumberComparator) isSingleDoubleComparison_Comparator() {}
+func (*WhereDocument_Children) isWhereDocument_WhereDocument() {}
 
-type GetVectorsRequest struct {
+// A leaf-node `WhereDocument` clause may compare a document to a single value.
+type DirectWhereDocument struct {
 	state         protoimpl.MessageState
 	sizeCache     protoimpl.SizeCache
 	unknownFields protoimpl.UnknownFields
 
-	Ids            []string               `protobuf:"bytes,1,rep,name=ids,proto3" json:"ids,omitempty"`
-	SegmentId      string                 `protobuf:"bytes,2,opt,name=segment_id,json=segmentId,proto3" json:"segment_id,omitempty"`
-	CollectionId   string                 `protobuf:"bytes,3,opt,name=collection_id,json=collectionId,proto3" json:"collection_id,omitempty"`
-	VersionContext *RequestVersionContext `protobuf:"bytes,4,opt,name=version_context,json=versionContext,proto3" json:"version_context,omitempty"`
+	Document string                `protobuf:"bytes,1,opt,name=document,proto3" json:"document,omitempty"`
+	Operator WhereDocumentOperator `protobuf:"varint,2,opt,name=operator,proto3,enum=chroma.WhereDocumentOperator" json:"operator,omitempty"`
+	// The maximum number of words between the terms of a `$near` query.
+	Distance *uint32 `protobuf:"varint,3,opt,name=distance,proto3,oneof" json:"distance,omitempty"`
 }
 
-func (x *GetVectorsRequest) Reset() {
-	*x = GetVectorsRequest{}
+func (x *DirectWhereDocument) Reset() {
+	*x = DirectWhereDocument{}
 	if protoimpl.UnsafeEnabled {
 		mi := &file_chromadb_proto_chroma_proto_msgTypes[30]
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -2544,13 +2844,13 @@ func (x *GetVectorsRequest) Reset() {
 	}
 }
 
-func (x *GetVectorsRequest) String() string {
+func (x *DirectWhereDocument) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*GetVectorsRequest) ProtoMessage() {}
+func (*DirectWhereDocument) ProtoMessage() {}
 
-func (x *GetVectorsRequest) ProtoReflect() protoreflect.Message {
+func (x *DirectWhereDocument) ProtoReflect() protoreflect.Message {
 	mi := &file_chromadb_proto_chroma_proto_msgTypes[30]
 	if protoimpl.UnsafeEnabled && x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -2562,49 +2862,44 @@ func (x *GetVectorsRequest) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use GetVectorsRequest.ProtoReflect.Descriptor instead.
-func (*GetVectorsRequest) Descriptor() ([]byte, []int) {
+// Deprecated: Use DirectWhereDocument.ProtoReflect.Descriptor instead.
+func (*DirectWhereDocument) Descriptor() ([]byte, []int) {
 	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{30}
 }
 
-func (x *GetVectorsRequest) GetIds() []string {
-	if x != nil {
-		return x.Ids
-	}
-	return nil
-}
-
-func (x *GetVectorsRequest) GetSegmentId() string {
+func (x *DirectWhereDocument) GetDocument() string {
 	if x != nil {
-		return x.SegmentId
+		return x.Document
 	}
 	return ""
 }
 
-func (x *GetVectorsRequest) GetCollectionId() string {
+func (x *DirectWhereDocument) GetOperator() WhereDocumentOperator {
 	if x != nil {
-		return x.CollectionId
+		return x.Operator
 	}
-	return ""
+	return WhereDocumentOperator_CONTAINS
 }
 
-func (x *GetVectorsRequest) GetVersionContext() *RequestVersionContext {
-	if x != nil {
-		return x.VersionContext
+func (x *DirectWhereDocument) GetDistance() uint32 {
+	if x != nil && x.Distance != nil {
+		return *x.Distance
 	}
-	return nil
+	return 0
 }
 
-type GetVectorsResponse struct {
+// A branch-node `WhereDocument` node has a list of children.
+type WhereDocumentChildren struct {
 	state         protoimpl.MessageState
 	sizeCache     protoimpl.SizeCache
 	unknownFields protoimpl.UnknownFields
 
-	Records []*VectorEmbeddingRecord `protobuf:"bytes,1,rep,name=records,proto3" json:"records,omitempty"`
+	Children []*WhereDocument `protobuf:"bytes,1,rep,name=children,proto3" json:"children,omitempty"`
+	Operator BooleanOperator  `protobuf:"varint,2,opt,name=operator,proto3,enum=chroma.BooleanOperator" json:"operator,omitempty"`
 }
 
-func (x *GetVectorsResponse) Reset() {
-	*x = GetVectorsResponse{}
+func (x *WhereDocumentChildren) Reset() {
+	*x = WhereDocumentChildren{}
 	if protoimpl.UnsafeEnabled {
 		mi := &file_chromadb_proto_chroma_proto_msgTypes[31]
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -2612,13 +2907,13 @@ func (x *GetVectorsResponse) Reset() {
 	}
 }
 
-func (x *GetVectorsResponse) String() string {
+func (x *WhereDocumentChildren) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*GetVectorsResponse) ProtoMessage() {}
+func (*WhereDocumentChildren) ProtoMessage() {}
 
-func (x *GetVectorsResponse) ProtoReflect() protoreflect.Message {
+func (x *WhereDocumentChildren) ProtoReflect() protoreflect.Message {
 	mi := &file_chromadb_proto_chroma_proto_msgTypes[31]
 	if protoimpl.UnsafeEnabled && x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -2630,29 +2925,44 @@ func (x *GetVectorsResponse) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use GetVectorsResponse.ProtoReflect.Descriptor instead.
-func (*GetVectorsResponse) Descriptor() ([]byte, []int) {
+// Deprecated: Use WhereDocumentChildren.ProtoReflect.Descriptor instead.
+func (*WhereDocumentChildren) Descriptor() ([]byte, []int) {
 	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{31}
 }
 
-func (x *GetVectorsResponse) GetRecords() []*VectorEmbeddingRecord {
+func (x *WhereDocumentChildren) GetChildren() []*WhereDocument {
 	if x != nil {
-		return x.Records
+		return x.Children
 	}
 	return nil
 }
 
-type VectorEmbeddingRecord struct {
+func (x *WhereDocumentChildren) GetOperator() BooleanOperator {
+	if x != nil {
+		return x.Operator
+	}
+	return BooleanOperator_AND
+}
+
+// A `Where` clause for filtering metadata. A `Where` clause is a tree of
+// `Where` clauses, where each node is exactly one of:
+//   - A leaf node representing a direct comparison between a metadata key and a
+//     value or list of values.
+//   - An branch node with a list of children and a way to combine them (AND or OR).
+type Where struct {
 	state         protoimpl.MessageState
 	sizeCache     protoimpl.SizeCache
 	unknownFields protoimpl.UnknownFields
 
-	Id     string  `protobuf:"bytes,1,opt,name=id,proto3" json:"id,omitempty"`
-	Vector *Vector `protobuf:"bytes,3,opt,name=vector,proto3" json:"vector,omitempty"` // TODO: we need to rethink source of truth for vector dimensionality and encoding
+	// Types that are assignable to Where:
+	//
+	//	*Where_DirectComparison
+	//	*Where_Children
+	Where isWhere_Where `protobuf_oneof:"where"`
 }
 
-func (x *VectorEmbeddingRecord) Reset() {
-	*x = VectorEmbeddingRecord{}
+func (x *Where) Reset() {
+	*x = Where{}
 	if protoimpl.UnsafeEnabled {
 		mi := &file_chromadb_proto_chroma_proto_msgTypes[32]
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -2660,13 +2970,13 @@ func (x *VectorEmbeddingRecord) Reset() {
 	}
 }
 
-func (x *VectorEmbeddingRecord) String() string {
+func (x *Where) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*VectorEmbeddingRecord) ProtoMessage() {}
+func (*Where) ProtoMessage() {}
 
-func (x *VectorEmbeddingRecord) ProtoReflect() protoreflect.Message {
+func (x *Where) ProtoReflect() protoreflect.Message {
 	mi := &file_chromadb_proto_chroma_proto_msgTypes[32]
 	if protoimpl.UnsafeEnabled && x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -2678,41 +2988,72 @@ func (x *VectorEmbeddingRecord) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use VectorEmbeddingRecord.ProtoReflect.Descriptor instead.
-func (*VectorEmbeddingRecord) Descriptor() ([]byte, []int) {
+// Deprecated: Use Where.ProtoReflect.Descriptor instead.
+func (*Where) Descriptor() ([]byte, []int) {
 	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{32}
 }
 
-func (x *VectorEmbeddingRecord) GetId() string {
-	if x != nil {
-		return x.Id
+func (m *Where) GetWhere() isWhere_Where {
+	if m != nil {
+		return m.Where
 	}
-	return ""
+	return nil
 }
 
-func (x *VectorEmbeddingRecord) GetVector() *Vector {
-	if x != nil {
-		return x.Vector
+func (x *Where) GetDirectComparison() *DirectComparison {
+	if x, ok := x.GetWhere().(*Where_DirectComparison); ok {
+		return x.DirectComparison
 	}
 	return nil
 }
 
-type QueryVectorsRequest struct {
+func (x *Where) GetChildren() *WhereChildren {
+	if x, ok := x.GetWhere().(*Where_Children); ok {
+		return x.Children
+	}
+	return nil
+}
+
+type isWhere_Where interface {
+	isWhere_Where()
+}
+
+type Where_DirectComparison struct {
+	DirectComparison *DirectComparison `protobuf:"bytes,1,opt,name=direct_comparison,json=directComparison,proto3,oneof"`
+}
+
+type Where_Children struct {
+	Children *WhereChildren `protobuf:"bytes,2,opt,name=children,proto3,oneof"`
+}
+
+func (*Where_DirectComparison) isWhere_Where() {}
+
+func (*Where_Children) isWhere_Where() {}
+
+// A leaf-node `Where` clause.
+type DirectComparison struct {
 	state         protoimpl.MessageState
 	sizeCache     protoimpl.SizeCache
 	unknownFields protoimpl.UnknownFields
 
-	Vectors           []*Vector              `protobuf:"bytes,1,rep,name=vectors,proto3" json:"vectors,omitempty"`
-	K                 int32                  `protobuf:"varint,2,opt,name=k,proto3" json:"k,omitempty"`
-	AllowedIds        []string               `protobuf:"bytes,3,rep,name=allowed_ids,json=allowedIds,proto3" json:"allowed_ids,omitempty"`
-	IncludeEmbeddings bool                   `protobuf:"varint,4,opt,name=include_embeddings,json=includeEmbeddings,proto3" json:"include_embeddings,omitempty"`
-	SegmentId         string                 `protobuf:"bytes,5,opt,name=segment_id,json=segmentId,proto3" json:"segment_id,omitempty"`
-	CollectionId      string                 `protobuf:"bytes,6,opt,name=collection_id,json=collectionId,proto3" json:"collection_id,omitempty"`
-	VersionContext    *RequestVersionContext `protobuf:"bytes,7,opt,name=version_context,json=versionContext,proto3" json:"version_context,omitempty"` // TODO: options as in types.py, its currently unused so can add later
+	Key string `protobuf:"bytes,1,opt,name=key,proto3" json:"key,omitempty"`
+	// Types that are assignable to Comparison:
+	//
+	//	*DirectComparison_SingleStringOperand
+	//	*DirectComparison_StringListOperand
+	//	*DirectComparison_SingleIntOperand
+	//	*DirectComparison_IntListOperand
+	//	*DirectComparison_SingleDoubleOperand
+	//	*DirectComparison_DoubleListOperand
+	//	*DirectComparison_BoolListOperand
+	//	*DirectComparison_SingleBoolOperand
+	//	*DirectComparison_ExistenceOperand
+	//	*DirectComparison_SingleTimestampOperand
+	Comparison isDirectComparison_Comparison `protobuf_oneof:"comparison"`
 }
 
-func (x *QueryVectorsRequest) Reset() {
-	*x = QueryVectorsRequest{}
+func (x *DirectComparison) Reset() {
+	*x = DirectComparison{}
 	if protoimpl.UnsafeEnabled {
 		mi := &file_chromadb_proto_chroma_proto_msgTypes[33]
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -2720,13 +3061,13 @@ func (x *QueryVectorsRequest) Reset() {
 	}
 }
 
-func (x *QueryVectorsRequest) String() string {
+func (x *DirectComparison) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*QueryVectorsRequest) ProtoMessage() {}
+func (*DirectComparison) ProtoMessage() {}
 
-func (x *QueryVectorsRequest) ProtoReflect() protoreflect.Message {
+func (x *DirectComparison) ProtoReflect() protoreflect.Message {
 	mi := &file_chromadb_proto_chroma_proto_msgTypes[33]
 	if protoimpl.UnsafeEnabled && x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -2738,70 +3079,172 @@ func (x *QueryVectorsRequest) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use QueryVectorsRequest.ProtoReflect.Descriptor instead.
-func (*QueryVectorsRequest) Descriptor() ([]byte, []int) {
+// Deprecated: Use DirectComparison.ProtoReflect.Descriptor instead.
+func (*DirectComparison) Descriptor() ([]byte, []int) {
 	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{33}
 }
 
-func (x *QueryVectorsRequest) GetVectors() []*Vector {
+func (x *DirectComparison) GetKey() string {
 	if x != nil {
-		return x.Vectors
+		return x.Key
+	}
+	return ""
+}
+
+func (m *DirectComparison) GetComparison() isDirectComparison_Comparison {
+	if m != nil {
+		return m.Comparison
 	}
 	return nil
 }
 
-func (x *QueryVectorsRequest) GetK() int32 {
-	if x != nil {
-		return x.K
+func (x *DirectComparison) GetSingleStringOperand() *SingleStringComparison {
+	if x, ok := x.GetComparison().(*DirectComparison_SingleStringOperand); ok {
+		return x.SingleStringOperand
 	}
-	return 0
+	return nil
 }
 
-func (x *QueryVectorsRequest) GetAllowedIds() []string {
-	if x != nil {
-		return x.AllowedIds
+func (x *DirectComparison) GetStringListOperand() *StringListComparison {
+	if x, ok := x.GetComparison().(*DirectComparison_StringListOperand); ok {
+		return x.StringListOperand
 	}
 	return nil
 }
 
-func (x *QueryVectorsRequest) GetIncludeEmbeddings() bool {
-	if x != nil {
-		return x.IncludeEmbeddings
+func (x *DirectComparison) GetSingleIntOperand() *SingleIntComparison {
+	if x, ok := x.GetComparison().(*DirectComparison_SingleIntOperand); ok {
+		return x.SingleIntOperand
 	}
-	return false
+	return nil
 }
 
-func (x *QueryVectorsRequest) GetSegmentId() string {
-	if x != nil {
-		return x.SegmentId
+func (x *DirectComparison) GetIntListOperand() *IntListComparison {
+	if x, ok := x.GetComparison().(*DirectComparison_IntListOperand); ok {
+		return x.IntListOperand
 	}
-	return ""
+	return nil
 }
 
-func (x *QueryVectorsRequest) GetCollectionId() string {
-	if x != nil {
-		return x.CollectionId
+func (x *DirectComparison) GetSingleDoubleOperand() *SingleDoubleComparison {
+	if x, ok := x.GetComparison().(*DirectComparison_SingleDoubleOperand); ok {
+		return x.SingleDoubleOperand
 	}
-	return ""
+	return nil
 }
 
-func (x *QueryVectorsRequest) GetVersionContext() *RequestVersionContext {
-	if x != nil {
-		return x.VersionContext
+func (x *DirectComparison) GetDoubleListOperand() *DoubleListComparison {
+	if x, ok := x.GetComparison().(*DirectComparison_DoubleListOperand); ok {
+		return x.DoubleListOperand
 	}
 	return nil
 }
 
-type QueryVectorsResponse struct {
+func (x *DirectComparison) GetBoolListOperand() *BoolListComparison {
+	if x, ok := x.GetComparison().(*DirectComparison_BoolListOperand); ok {
+		return x.BoolListOperand
+	}
+	return nil
+}
+
+func (x *DirectComparison) GetSingleBoolOperand() *SingleBoolComparison {
+	if x, ok := x.GetComparison().(*DirectComparison_SingleBoolOperand); ok {
+		return x.SingleBoolOperand
+	}
+	return nil
+}
+
+func (x *DirectComparison) GetExistenceOperand() *ExistenceComparison {
+	if x, ok := x.GetComparison().(*DirectComparison_ExistenceOperand); ok {
+		return x.ExistenceOperand
+	}
+	return nil
+}
+
+func (x *DirectComparison) GetSingleTimestampOperand() *SingleTimestampComparison {
+	if x, ok := x.GetComparison().(*DirectComparison_SingleTimestampOperand); ok {
+		return x.SingleTimestampOperand
+	}
+	return nil
+}
+
+type isDirectComparison_Comparison interface {
+	isDirectComparison_Comparison()
+}
+
+type DirectComparison_SingleStringOperand struct {
+	SingleStringOperand *SingleStringComparison `protobuf:"bytes,2,opt,name=single_string_operand,json=singleStringOperand,proto3,oneof"`
+}
+
+type DirectComparison_StringListOperand struct {
+	StringListOperand *StringListComparison `protobuf:"bytes,3,opt,name=string_list_operand,json=stringListOperand,proto3,oneof"`
+}
+
+type DirectComparison_SingleIntOperand struct {
+	SingleIntOperand *SingleIntComparison `protobuf:"bytes,4,opt,name=single_int_operand,json=singleIntOperand,proto3,oneof"`
+}
+
+type DirectComparison_IntListOperand struct {
+	IntListOperand *IntListComparison `protobuf:"bytes,5,opt,name=int_list_operand,json=intListOperand,proto3,oneof"`
+}
+
+type DirectComparison_SingleDoubleOperand struct {
+	SingleDoubleOperand *SingleDoubleComparison `protobuf:"bytes,6,opt,name=single_double_operand,json=singleDoubleOperand,proto3,oneof"`
+}
+
+type DirectComparison_DoubleListOperand struct {
+	DoubleListOperand *DoubleListComparison `protobuf:"bytes,7,opt,name=double_list_operand,json=doubleListOperand,proto3,oneof"`
+}
+
+type DirectComparison_BoolListOperand struct {
+	BoolListOperand *BoolListComparison `protobuf:"bytes,8,opt,name=bool_list_operand,json=boolListOperand,proto3,oneof"`
+}
+
+type DirectComparison_SingleBoolOperand struct {
+	SingleBoolOperand *SingleBoolComparison `protobuf:"bytes,9,opt,name=single_bool_operand,json=singleBoolOperand,proto3,oneof"`
+}
+
+type DirectComparison_ExistenceOperand struct {
+	ExistenceOperand *ExistenceComparison `protobuf:"bytes,10,opt,name=existence_operand,json=existenceOperand,proto3,oneof"`
+}
+
+type DirectComparison_SingleTimestampOperand struct {
+	SingleTimestampOperand *SingleTimestampComparison `protobuf:"bytes,11,opt,name=single_timestamp_operand,json=singleTimestampOperand,proto3,oneof"`
+}
+
+func (*DirectComparison_SingleStringOperand) isDirectComparison_Comparison() {}
+
+func (*DirectComparison_StringListOperand) isDirectComparison_Comparison() {}
+
+func (*DirectComparison_SingleIntOperand) isDirectComparison_Comparison() {}
+
+func (*DirectComparison_IntListOperand) isDirectComparison_Comparison() {}
+
+func (*DirectComparison_SingleDoubleOperand) isDirectComparison_Comparison() {}
+
+func (*DirectComparison_DoubleListOperand) isDirectComparison_Comparison() {}
+
+func (*DirectComparison_BoolListOperand) isDirectComparison_Comparison() {}
+
+func (*DirectComparison_SingleBoolOperand) isDirectComparison_Comparison() {}
+
+func (*DirectComparison_ExistenceOperand) isDirectComparison_Comparison() {}
+
+func (*DirectComparison_SingleTimestampOperand) isDirectComparison_Comparison() {}
+
+// A branch-node `Where` clause has a list of children and a specification
+// for how to combine them.
+type WhereChildren struct {
 	state         protoimpl.MessageState
 	sizeCache     protoimpl.SizeCache
 	unknownFields protoimpl.UnknownFields
 
-	Results []*VectorQueryResults `protobuf:"bytes,1,rep,name=results,proto3" json:"results,omitempty"`
+	Children []*Where        `protobuf:"bytes,1,rep,name=children,proto3" json:"children,omitempty"`
+	Operator BooleanOperator `protobuf:"varint,2,opt,name=operator,proto3,enum=chroma.BooleanOperator" json:"operator,omitempty"`
 }
 
-func (x *QueryVectorsResponse) Reset() {
-	*x = QueryVectorsResponse{}
+func (x *WhereChildren) Reset() {
+	*x = WhereChildren{}
 	if protoimpl.UnsafeEnabled {
 		mi := &file_chromadb_proto_chroma_proto_msgTypes[34]
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -2809,13 +3252,13 @@ func (x *QueryVectorsResponse) Reset() {
 	}
 }
 
-func (x *QueryVectorsResponse) String() string {
+func (x *WhereChildren) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*QueryVectorsResponse) ProtoMessage() {}
+func (*WhereChildren) ProtoMessage() {}
 
-func (x *QueryVectorsResponse) ProtoReflect() protoreflect.Message {
+func (x *WhereChildren) ProtoReflect() protoreflect.Message {
 	mi := &file_chromadb_proto_chroma_proto_msgTypes[34]
 	if protoimpl.UnsafeEnabled && x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -2827,28 +3270,38 @@ func (x *QueryVectorsResponse) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use QueryVectorsResponse.ProtoReflect.Descriptor instead.
-func (*QueryVectorsResponse) Descriptor() ([]byte, []int) {
+// Deprecated: Use WhereChildren.ProtoReflect.Descriptor instead.
+func (*WhereChildren) Descriptor() ([]byte, []int) {
 	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{34}
 }
 
-func (x *QueryVectorsResponse) GetResults() []*VectorQueryResults {
+func (x *WhereChildren) GetChildren() []*Where {
 	if x != nil {
-		return x.Results
+		return x.Children
 	}
 	return nil
 }
 
-type VectorQueryResults struct {
+func (x *WhereChildren) GetOperator() BooleanOperator {
+	if x != nil {
+		return x.Operator
+	}
+	return BooleanOperator_AND
+}
+
+// Used when a leaf-node `Where` clause compares a string to a list of strings.
+// `ListOperator` specifies whether values in the list are allowed or disallowed.
+type StringListComparison struct {
 	state         protoimpl.MessageState
 	sizeCache     protoimpl.SizeCache
 	unknownFields protoimpl.UnknownFields
 
-	Results []*VectorQueryResult `protobuf:"bytes,1,rep,name=results,proto3" json:"results,omitempty"`
+	Values       []string     `protobuf:"bytes,1,rep,name=values,proto3" json:"values,omitempty"`
+	ListOperator ListOperator `protobuf:"varint,2,opt,name=list_operator,json=listOperator,proto3,enum=chroma.ListOperator" json:"list_operator,omitempty"`
 }
 
-func (x *VectorQueryResults) Reset() {
-	*x = VectorQueryResults{}
+func (x *StringListComparison) Reset() {
+	*x = StringListComparison{}
 	if protoimpl.UnsafeEnabled {
 		mi := &file_chromadb_proto_chroma_proto_msgTypes[35]
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -2856,13 +3309,13 @@ func (x *VectorQueryResults) Reset() {
 	}
 }
 
-func (x *VectorQueryResults) String() string {
+func (x *StringListComparison) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*VectorQueryResults) ProtoMessage() {}
+func (*StringListComparison) ProtoMessage() {}
 
-func (x *VectorQueryResults) ProtoReflect() protoreflect.Message {
+func (x *StringListComparison) ProtoReflect() protoreflect.Message {
 	mi := &file_chromadb_proto_chroma_proto_msgTypes[35]
 	if protoimpl.UnsafeEnabled && x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -2874,30 +3327,37 @@ func (x *VectorQueryResults) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use VectorQueryResults.ProtoReflect.Descriptor instead.
-func (*VectorQueryResults) Descriptor() ([]byte, []int) {
+// Deprecated: Use StringListComparison.ProtoReflect.Descriptor instead.
+func (*StringListComparison) Descriptor() ([]byte, []int) {
 	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{35}
 }
 
-func (x *VectorQueryResults) GetResults() []*VectorQueryResult {
+func (x *StringListComparison) GetValues() []string {
 	if x != nil {
-		return x.Results
+		return x.Values
 	}
 	return nil
 }
 
-type VectorQueryResult struct {
+func (x *StringListComparison) GetListOperator() ListOperator {
+	if x != nil {
+		return x.ListOperator
+	}
+	return ListOperator_IN
+}
+
+// Used when a leaf-node `Where` clause checks whether a record has a value for
+// the key.
+type ExistenceComparison struct {
 	state         protoimpl.MessageState
 	sizeCache     protoimpl.SizeCache
 	unknownFields protoimpl.UnknownFields
 
-	Id       string  `protobuf:"bytes,1,opt,name=id,proto3" json:"id,omitempty"`
-	Distance float32 `protobuf:"fixed32,3,opt,name=distance,proto3" json:"distance,omitempty"`
-	Vector   *Vector `protobuf:"bytes,4,opt,name=vector,proto3,oneof" json:"vector,omitempty"`
+	Operator ExistenceOperator `protobuf:"varint,1,opt,name=operator,proto3,enum=chroma.ExistenceOperator" json:"operator,omitempty"`
 }
 
-func (x *VectorQueryResult) Reset() {
-	*x = VectorQueryResult{}
+func (x *ExistenceComparison) Reset() {
+	*x = ExistenceComparison{}
 	if protoimpl.UnsafeEnabled {
 		mi := &file_chromadb_proto_chroma_proto_msgTypes[36]
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
@@ -2905,606 +3365,4046 @@ func (x *VectorQueryResult) Reset() {
 	}
 }
 
-func (x *VectorQueryResult) String() string {
+func (x *ExistenceComparison) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*VectorQueryResult) ProtoMessage() {}
+func (*ExistenceComparison) ProtoMessage() {}
 
-func (x *VectorQueryResult) ProtoReflect() protoreflect.Message {
+func (x *ExistenceComparison) ProtoReflect() protoreflect.Message {
 	mi := &file_chromadb_proto_chroma_proto_msgTypes[36]
 	if protoimpl.UnsafeEnabled && x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
 			ms.StoreMessageInfo(mi)
 		}
-		return ms
-	}
-	return mi.MessageOf(x)
-}
-
-// Deprecated: Use VectorQueryResult.ProtoReflect.Descriptor instead.
-func (*VectorQueryResult) Descriptor() ([]byte, []int) {
-	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{36}
-}
-
-func (x *VectorQueryResult) GetId() string {
-	if x != nil {
-		return x.Id
-	}
-	return ""
-}
-
-func (x *VectorQueryResult) GetDistance() float32 {
-	if x != nil {
-		return x.Distance
-	}
-	return 0
-}
-
-func (x *VectorQueryResult) GetVector() *Vector {
-	if x != nil {
-		return x.Vector
-	}
-	return nil
-}
-
-var File_chromadb_proto_chroma_proto protoreflect.FileDescriptor
-
-var file_chromadb_proto_chroma_proto_rawDesc = []byte{
-	0x0a, 0x1b, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x64, 0x62, 0x2f, 0x70, 0x72, 0x6f, 0x74, 0x6f,
-	0x2f, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x70, 0x72, 0x6f, 0x74, 0x6f, 0x12, 0x06, 0x63,
-	0x68, 0x72, 0x6f, 0x6d, 0x61, 0x22, 0x72, 0x0a, 0x06, 0x56, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x12,
-	0x1c, 0x0a, 0x09, 0x64, 0x69, 0x6d, 0x65, 0x6e, 0x73, 0x69, 0x6f, 0x6e, 0x18, 0x01, 0x20, 0x01,
-	0x28, 0x05, 0x52, 0x09, 0x64, 0x69, 0x6d, 0x65, 0x6e, 0x73, 0x69, 0x6f, 0x6e, 0x12, 0x16, 0x0a,
-	0x06, 0x76, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0c, 0x52, 0x06, 0x76,
-	0x65, 0x63, 0x74, 0x6f, 0x72, 0x12, 0x32, 0x0a, 0x08, 0x65, 0x6e, 0x63, 0x6f, 0x64, 0x69, 0x6e,
-	0x67, 0x18, 0x03, 0x20, 0x01, 0x28, 0x0e, 0x32, 0x16, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61,
-	0x2e, 0x53, 0x63, 0x61, 0x6c, 0x61, 0x72, 0x45, 0x6e, 0x63, 0x6f, 0x64, 0x69, 0x6e, 0x67, 0x52,
-	0x08, 0x65, 0x6e, 0x63, 0x6f, 0x64, 0x69, 0x6e, 0x67, 0x22, 0x21, 0x0a, 0x09, 0x46, 0x69, 0x6c,
-	0x65, 0x50, 0x61, 0x74, 0x68, 0x73, 0x12, 0x14, 0x0a, 0x05, 0x70, 0x61, 0x74, 0x68, 0x73, 0x18,
-	0x01, 0x20, 0x03, 0x28, 0x09, 0x52, 0x05, 0x70, 0x61, 0x74, 0x68, 0x73, 0x22, 0xcf, 0x02, 0x0a,
-	0x07, 0x53, 0x65, 0x67, 0x6d, 0x65, 0x6e, 0x74, 0x12, 0x0e, 0x0a, 0x02, 0x69, 0x64, 0x18, 0x01,
-	0x20, 0x01, 0x28, 0x09, 0x52, 0x02, 0x69, 0x64, 0x12, 0x12, 0x0a, 0x04, 0x74, 0x79, 0x70, 0x65,
-	0x18, 0x02, 0x20, 0x01, 0x28, 0x09, 0x52, 0x04, 0x74, 0x79, 0x70, 0x65, 0x12, 0x2a, 0x0a, 0x05,
-	0x73, 0x63, 0x6f, 0x70, 0x65, 0x18, 0x03, 0x20, 0x01, 0x28, 0x0e, 0x32, 0x14, 0x2e, 0x63, 0x68,
-	0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x53, 0x65, 0x67, 0x6d, 0x65, 0x6e, 0x74, 0x53, 0x63, 0x6f, 0x70,
-	0x65, 0x52, 0x05, 0x73, 0x63, 0x6f, 0x70, 0x65, 0x12, 0x1e, 0x0a, 0x0a, 0x63, 0x6f, 0x6c, 0x6c,
-	0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x18, 0x05, 0x20, 0x01, 0x28, 0x09, 0x52, 0x0a, 0x63, 0x6f,
-	0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x12, 0x37, 0x0a, 0x08, 0x6d, 0x65, 0x74, 0x61,
-	0x64, 0x61, 0x74, 0x61, 0x18, 0x06, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x16, 0x2e, 0x63, 0x68, 0x72,
-	0x6f, 0x6d, 0x61, 0x2e, 0x55, 0x70, 0x64, 0x61, 0x74, 0x65, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61,
-	0x74, 0x61, 0x48, 0x00, 0x52, 0x08, 0x6d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x88, 0x01,
-	0x01, 0x12, 0x3d, 0x0a, 0x0a, 0x66, 0x69, 0x6c, 0x65, 0x5f, 0x70, 0x61, 0x74, 0x68, 0x73, 0x18,
-	0x07, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x1e, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x53,
-	0x65, 0x67, 0x6d, 0x65, 0x6e, 0x74, 0x2e, 0x46, 0x69, 0x6c, 0x65, 0x50, 0x61, 0x74, 0x68, 0x73,
-	0x45, 0x6e, 0x74, 0x72, 0x79, 0x52, 0x09, 0x66, 0x69, 0x6c, 0x65, 0x50, 0x61, 0x74, 0x68, 0x73,
-	0x1a, 0x4f, 0x0a, 0x0e, 0x46, 0x69, 0x6c, 0x65, 0x50, 0x61, 0x74, 0x68, 0x73, 0x45, 0x6e, 0x74,
-	0x72, 0x79, 0x12, 0x10, 0x0a, 0x03, 0x6b, 0x65, 0x79, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x52,
-	0x03, 0x6b, 0x65, 0x79, 0x12, 0x27, 0x0a, 0x05, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x18, 0x02, 0x20,
-	0x01, 0x28, 0x0b, 0x32, 0x11, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x46, 0x69, 0x6c,
-	0x65, 0x50, 0x61, 0x74, 0x68, 0x73, 0x52, 0x05, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x3a, 0x02, 0x38,
-	0x01, 0x42, 0x0b, 0x0a, 0x09, 0x5f, 0x6d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x22, 0xce,
-	0x02, 0x0a, 0x0a, 0x43, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x12, 0x0e, 0x0a,
-	0x02, 0x69, 0x64, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x52, 0x02, 0x69, 0x64, 0x12, 0x12, 0x0a,
-	0x04, 0x6e, 0x61, 0x6d, 0x65, 0x18, 0x02, 0x20, 0x01, 0x28, 0x09, 0x52, 0x04, 0x6e, 0x61, 0x6d,
-	0x65, 0x12, 0x34, 0x0a, 0x16, 0x63, 0x6f, 0x6e, 0x66, 0x69, 0x67, 0x75, 0x72, 0x61, 0x74, 0x69,
-	0x6f, 0x6e, 0x5f, 0x6a, 0x73, 0x6f, 0x6e, 0x5f, 0x73, 0x74, 0x72, 0x18, 0x03, 0x20, 0x01, 0x28,
-	0x09, 0x52, 0x14, 0x63, 0x6f, 0x6e, 0x66, 0x69, 0x67, 0x75, 0x72, 0x61, 0x74, 0x69, 0x6f, 0x6e,
-	0x4a, 0x73, 0x6f, 0x6e, 0x53, 0x74, 0x72, 0x12, 0x37, 0x0a, 0x08, 0x6d, 0x65, 0x74, 0x61, 0x64,
-	0x61, 0x74, 0x61, 0x18, 0x04, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x16, 0x2e, 0x63, 0x68, 0x72, 0x6f,
-	0x6d, 0x61, 0x2e, 0x55, 0x70, 0x64, 0x61, 0x74, 0x65, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74,
-	0x61, 0x48, 0x00, 0x52, 0x08, 0x6d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x88, 0x01, 0x01,
-	0x12, 0x21, 0x0a, 0x09, 0x64, 0x69, 0x6d, 0x65, 0x6e, 0x73, 0x69, 0x6f, 0x6e, 0x18, 0x05, 0x20,
-	0x01, 0x28, 0x05, 0x48, 0x01, 0x52, 0x09, 0x64, 0x69, 0x6d, 0x65, 0x6e, 0x73, 0x69, 0x6f, 0x6e,
-	0x88, 0x01, 0x01, 0x12, 0x16, 0x0a, 0x06, 0x74, 0x65, 0x6e, 0x61, 0x6e, 0x74, 0x18, 0x06, 0x20,
-	0x01, 0x28, 0x09, 0x52, 0x06, 0x74, 0x65, 0x6e, 0x61, 0x6e, 0x74, 0x12, 0x1a, 0x0a, 0x08, 0x64,
-	0x61, 0x74, 0x61, 0x62, 0x61, 0x73, 0x65, 0x18, 0x07, 0x20, 0x01, 0x28, 0x09, 0x52, 0x08, 0x64,
-	0x61, 0x74, 0x61, 0x62, 0x61, 0x73, 0x65, 0x12, 0x21, 0x0a, 0x0c, 0x6c, 0x6f, 0x67, 0x5f, 0x70,
-	0x6f, 0x73, 0x69, 0x74, 0x69, 0x6f, 0x6e, 0x18, 0x08, 0x20, 0x01, 0x28, 0x03, 0x52, 0x0b, 0x6c,
-	0x6f, 0x67, 0x50, 0x6f, 0x73, 0x69, 0x74, 0x69, 0x6f, 0x6e, 0x12, 0x18, 0x0a, 0x07, 0x76, 0x65,
-	0x72, 0x73, 0x69, 0x6f, 0x6e, 0x18, 0x09, 0x20, 0x01, 0x28, 0x05, 0x52, 0x07, 0x76, 0x65, 0x72,
-	0x73, 0x69, 0x6f, 0x6e, 0x42, 0x0b, 0x0a, 0x09, 0x5f, 0x6d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74,
-	0x61, 0x42, 0x0c, 0x0a, 0x0a, 0x5f, 0x64, 0x69, 0x6d, 0x65, 0x6e, 0x73, 0x69, 0x6f, 0x6e, 0x22,
-	0x46, 0x0a, 0x08, 0x44, 0x61, 0x74, 0x61, 0x62, 0x61, 0x73, 0x65, 0x12, 0x0e, 0x0a, 0x02, 0x69,
-	0x64, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x52, 0x02, 0x69, 0x64, 0x12, 0x12, 0x0a, 0x04, 0x6e,
-	0x61, 0x6d, 0x65, 0x18, 0x02, 0x20, 0x01, 0x28, 0x09, 0x52, 0x04, 0x6e, 0x61, 0x6d, 0x65, 0x12,
-	0x16, 0x0a, 0x06, 0x74, 0x65, 0x6e, 0x61, 0x6e, 0x74, 0x18, 0x03, 0x20, 0x01, 0x28, 0x09, 0x52,
-	0x06, 0x74, 0x65, 0x6e, 0x61, 0x6e, 0x74, 0x22, 0x1c, 0x0a, 0x06, 0x54, 0x65, 0x6e, 0x61, 0x6e,
-	0x74, 0x12, 0x12, 0x0a, 0x04, 0x6e, 0x61, 0x6d, 0x65, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x52,
-	0x04, 0x6e, 0x61, 0x6d, 0x65, 0x22, 0xa6, 0x01, 0x0a, 0x13, 0x55, 0x70, 0x64, 0x61, 0x74, 0x65,
-	0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x56, 0x61, 0x6c, 0x75, 0x65, 0x12, 0x23, 0x0a,
-	0x0c, 0x73, 0x74, 0x72, 0x69, 0x6e, 0x67, 0x5f, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x18, 0x01, 0x20,
-	0x01, 0x28, 0x09, 0x48, 0x00, 0x52, 0x0b, 0x73, 0x74, 0x72, 0x69, 0x6e, 0x67, 0x56, 0x61, 0x6c,
-	0x75, 0x65, 0x12, 0x1d, 0x0a, 0x09, 0x69, 0x6e, 0x74, 0x5f, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x18,
-	0x02, 0x20, 0x01, 0x28, 0x03, 0x48, 0x00, 0x52, 0x08, 0x69, 0x6e, 0x74, 0x56, 0x61, 0x6c, 0x75,
-	0x65, 0x12, 0x21, 0x0a, 0x0b, 0x66, 0x6c, 0x6f, 0x61, 0x74, 0x5f, 0x76, 0x61, 0x6c, 0x75, 0x65,
-	0x18, 0x03, 0x20, 0x01, 0x28, 0x01, 0x48, 0x00, 0x52, 0x0a, 0x66, 0x6c, 0x6f, 0x61, 0x74, 0x56,
-	0x61, 0x6c, 0x75, 0x65, 0x12, 0x1f, 0x0a, 0x0a, 0x62, 0x6f, 0x6f, 0x6c, 0x5f, 0x76, 0x61, 0x6c,
-	0x75, 0x65, 0x18, 0x04, 0x20, 0x01, 0x28, 0x08, 0x48, 0x00, 0x52, 0x09, 0x62, 0x6f, 0x6f, 0x6c,
-	0x56, 0x61, 0x6c, 0x75, 0x65, 0x42, 0x07, 0x0a, 0x05, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x22, 0xac,
-	0x01, 0x0a, 0x0e, 0x55, 0x70, 0x64, 0x61, 0x74, 0x65, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74,
-	0x61, 0x12, 0x40, 0x0a, 0x08, 0x6d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x18, 0x01, 0x20,
-	0x03, 0x28, 0x0b, 0x32, 0x24, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x55, 0x70, 0x64,
-	0x61, 0x74, 0x65, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x2e, 0x4d, 0x65, 0x74, 0x61,
-	0x64, 0x61, 0x74, 0x61, 0x45, 0x6e, 0x74, 0x72, 0x79, 0x52, 0x08, 0x6d, 0x65, 0x74, 0x61, 0x64,
-	0x61, 0x74, 0x61, 0x1a, 0x58, 0x0a, 0x0d, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x45,
-	0x6e, 0x74, 0x72, 0x79, 0x12, 0x10, 0x0a, 0x03, 0x6b, 0x65, 0x79, 0x18, 0x01, 0x20, 0x01, 0x28,
-	0x09, 0x52, 0x03, 0x6b, 0x65, 0x79, 0x12, 0x31, 0x0a, 0x05, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x18,
-	0x02, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x1b, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x55,
-	0x70, 0x64, 0x61, 0x74, 0x65, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x56, 0x61, 0x6c,
-	0x75, 0x65, 0x52, 0x05, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x3a, 0x02, 0x38, 0x01, 0x22, 0xd0, 0x01,
-	0x0a, 0x0f, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x69, 0x6f, 0x6e, 0x52, 0x65, 0x63, 0x6f, 0x72,
-	0x64, 0x12, 0x0e, 0x0a, 0x02, 0x69, 0x64, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x52, 0x02, 0x69,
-	0x64, 0x12, 0x2b, 0x0a, 0x06, 0x76, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x18, 0x02, 0x20, 0x01, 0x28,
-	0x0b, 0x32, 0x0e, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x56, 0x65, 0x63, 0x74, 0x6f,
-	0x72, 0x48, 0x00, 0x52, 0x06, 0x76, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x88, 0x01, 0x01, 0x12, 0x37,
-	0x0a, 0x08, 0x6d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x18, 0x03, 0x20, 0x01, 0x28, 0x0b,
-	0x32, 0x16, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x55, 0x70, 0x64, 0x61, 0x74, 0x65,
-	0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x48, 0x01, 0x52, 0x08, 0x6d, 0x65, 0x74, 0x61,
-	0x64, 0x61, 0x74, 0x61, 0x88, 0x01, 0x01, 0x12, 0x2f, 0x0a, 0x09, 0x6f, 0x70, 0x65, 0x72, 0x61,
-	0x74, 0x69, 0x6f, 0x6e, 0x18, 0x04, 0x20, 0x01, 0x28, 0x0e, 0x32, 0x11, 0x2e, 0x63, 0x68, 0x72,
-	0x6f, 0x6d, 0x61, 0x2e, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x69, 0x6f, 0x6e, 0x52, 0x09, 0x6f,
-	0x70, 0x65, 0x72, 0x61, 0x74, 0x69, 0x6f, 0x6e, 0x42, 0x09, 0x0a, 0x07, 0x5f, 0x76, 0x65, 0x63,
-	0x74, 0x6f, 0x72, 0x42, 0x0b, 0x0a, 0x09, 0x5f, 0x6d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61,
-	0x22, 0x69, 0x0a, 0x15, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x56, 0x65, 0x72, 0x73, 0x69,
-	0x6f, 0x6e, 0x43, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x12, 0x2d, 0x0a, 0x12, 0x63, 0x6f, 0x6c,
-	0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x5f, 0x76, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x18,
-	0x01, 0x20, 0x01, 0x28, 0x0d, 0x52, 0x11, 0x63, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f,
-	0x6e, 0x56, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x12, 0x21, 0x0a, 0x0c, 0x6c, 0x6f, 0x67, 0x5f,
-	0x70, 0x6f, 0x73, 0x69, 0x74, 0x69, 0x6f, 0x6e, 0x18, 0x02, 0x20, 0x01, 0x28, 0x04, 0x52, 0x0b,
-	0x6c, 0x6f, 0x67, 0x50, 0x6f, 0x73, 0x69, 0x74, 0x69, 0x6f, 0x6e, 0x22, 0xa1, 0x01, 0x0a, 0x13,
-	0x43, 0x6f, 0x75, 0x6e, 0x74, 0x52, 0x65, 0x63, 0x6f, 0x72, 0x64, 0x73, 0x52, 0x65, 0x71, 0x75,
-	0x65, 0x73, 0x74, 0x12, 0x1d, 0x0a, 0x0a, 0x73, 0x65, 0x67, 0x6d, 0x65, 0x6e, 0x74, 0x5f, 0x69,
-	0x64, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x52, 0x09, 0x73, 0x65, 0x67, 0x6d, 0x65, 0x6e, 0x74,
-	0x49, 0x64, 0x12, 0x23, 0x0a, 0x0d, 0x63, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e,
-	0x5f, 0x69, 0x64, 0x18, 0x02, 0x20, 0x01, 0x28, 0x09, 0x52, 0x0c, 0x63, 0x6f, 0x6c, 0x6c, 0x65,
-	0x63, 0x74, 0x69, 0x6f, 0x6e, 0x49, 0x64, 0x12, 0x46, 0x0a, 0x0f, 0x76, 0x65, 0x72, 0x73, 0x69,
-	0x6f, 0x6e, 0x5f, 0x63, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x18, 0x03, 0x20, 0x01, 0x28, 0x0b,
-	0x32, 0x1d, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73,
-	0x74, 0x56, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x43, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x52,
-	0x0e, 0x76, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x43, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x22,
-	0x2c, 0x0a, 0x14, 0x43, 0x6f, 0x75, 0x6e, 0x74, 0x52, 0x65, 0x63, 0x6f, 0x72, 0x64, 0x73, 0x52,
-	0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x12, 0x14, 0x0a, 0x05, 0x63, 0x6f, 0x75, 0x6e, 0x74,
-	0x18, 0x01, 0x20, 0x01, 0x28, 0x0d, 0x52, 0x05, 0x63, 0x6f, 0x75, 0x6e, 0x74, 0x22, 0xad, 0x03,
-	0x0a, 0x14, 0x51, 0x75, 0x65, 0x72, 0x79, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x52,
-	0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x12, 0x1d, 0x0a, 0x0a, 0x73, 0x65, 0x67, 0x6d, 0x65, 0x6e,
-	0x74, 0x5f, 0x69, 0x64, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x52, 0x09, 0x73, 0x65, 0x67, 0x6d,
-	0x65, 0x6e, 0x74, 0x49, 0x64, 0x12, 0x23, 0x0a, 0x05, 0x77, 0x68, 0x65, 0x72, 0x65, 0x18, 0x02,
-	0x20, 0x01, 0x28, 0x0b, 0x32, 0x0d, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x57, 0x68,
-	0x65, 0x72, 0x65, 0x52, 0x05, 0x77, 0x68, 0x65, 0x72, 0x65, 0x12, 0x3c, 0x0a, 0x0e, 0x77, 0x68,
-	0x65, 0x72, 0x65, 0x5f, 0x64, 0x6f, 0x63, 0x75, 0x6d, 0x65, 0x6e, 0x74, 0x18, 0x03, 0x20, 0x01,
-	0x28, 0x0b, 0x32, 0x15, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x57, 0x68, 0x65, 0x72,
-	0x65, 0x44, 0x6f, 0x63, 0x75, 0x6d, 0x65, 0x6e, 0x74, 0x52, 0x0d, 0x77, 0x68, 0x65, 0x72, 0x65,
-	0x44, 0x6f, 0x63, 0x75, 0x6d, 0x65, 0x6e, 0x74, 0x12, 0x26, 0x0a, 0x03, 0x69, 0x64, 0x73, 0x18,
-	0x04, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x0f, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x55,
-	0x73, 0x65, 0x72, 0x49, 0x64, 0x73, 0x48, 0x00, 0x52, 0x03, 0x69, 0x64, 0x73, 0x88, 0x01, 0x01,
-	0x12, 0x19, 0x0a, 0x05, 0x6c, 0x69, 0x6d, 0x69, 0x74, 0x18, 0x05, 0x20, 0x01, 0x28, 0x0d, 0x48,
-	0x01, 0x52, 0x05, 0x6c, 0x69, 0x6d, 0x69, 0x74, 0x88, 0x01, 0x01, 0x12, 0x1b, 0x0a, 0x06, 0x6f,
-	0x66, 0x66, 0x73, 0x65, 0x74, 0x18, 0x06, 0x20, 0x01, 0x28, 0x0d, 0x48, 0x02, 0x52, 0x06, 0x6f,
-	0x66, 0x66, 0x73, 0x65, 0x74, 0x88, 0x01, 0x01, 0x12, 0x23, 0x0a, 0x0d, 0x63, 0x6f, 0x6c, 0x6c,
-	0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x5f, 0x69, 0x64, 0x18, 0x07, 0x20, 0x01, 0x28, 0x09, 0x52,
-	0x0c, 0x63, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x49, 0x64, 0x12, 0x29, 0x0a,
-	0x10, 0x69, 0x6e, 0x63, 0x6c, 0x75, 0x64, 0x65, 0x5f, 0x6d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74,
-	0x61, 0x18, 0x08, 0x20, 0x01, 0x28, 0x08, 0x52, 0x0f, 0x69, 0x6e, 0x63, 0x6c, 0x75, 0x64, 0x65,
-	0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x12, 0x46, 0x0a, 0x0f, 0x76, 0x65, 0x72, 0x73,
-	0x69, 0x6f, 0x6e, 0x5f, 0x63, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x18, 0x09, 0x20, 0x01, 0x28,
-	0x0b, 0x32, 0x1d, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x52, 0x65, 0x71, 0x75, 0x65,
-	0x73, 0x74, 0x56, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x43, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74,
-	0x52, 0x0e, 0x76, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x43, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74,
-	0x42, 0x06, 0x0a, 0x04, 0x5f, 0x69, 0x64, 0x73, 0x42, 0x08, 0x0a, 0x06, 0x5f, 0x6c, 0x69, 0x6d,
-	0x69, 0x74, 0x42, 0x09, 0x0a, 0x07, 0x5f, 0x6f, 0x66, 0x66, 0x73, 0x65, 0x74, 0x22, 0x52, 0x0a,
-	0x15, 0x51, 0x75, 0x65, 0x72, 0x79, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x52, 0x65,
-	0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x12, 0x39, 0x0a, 0x07, 0x72, 0x65, 0x63, 0x6f, 0x72, 0x64,
-	0x73, 0x18, 0x01, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x1f, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61,
-	0x2e, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x45, 0x6d, 0x62, 0x65, 0x64, 0x64, 0x69,
-	0x6e, 0x67, 0x52, 0x65, 0x63, 0x6f, 0x72, 0x64, 0x52, 0x07, 0x72, 0x65, 0x63, 0x6f, 0x72, 0x64,
-	0x73, 0x22, 0x5d, 0x0a, 0x17, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x45, 0x6d, 0x62,
-	0x65, 0x64, 0x64, 0x69, 0x6e, 0x67, 0x52, 0x65, 0x63, 0x6f, 0x72, 0x64, 0x12, 0x0e, 0x0a, 0x02,
-	0x69, 0x64, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x52, 0x02, 0x69, 0x64, 0x12, 0x32, 0x0a, 0x08,
-	0x6d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x16,
-	0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x55, 0x70, 0x64, 0x61, 0x74, 0x65, 0x4d, 0x65,
-	0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x52, 0x08, 0x6d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61,
-	0x22, 0x1b, 0x0a, 0x07, 0x55, 0x73, 0x65, 0x72, 0x49, 0x64, 0x73, 0x12, 0x10, 0x0a, 0x03, 0x69,
-	0x64, 0x73, 0x18, 0x01, 0x20, 0x03, 0x28, 0x09, 0x52, 0x03, 0x69, 0x64, 0x73, 0x22, 0x95, 0x01,
-	0x0a, 0x0d, 0x57, 0x68, 0x65, 0x72, 0x65, 0x44, 0x6f, 0x63, 0x75, 0x6d, 0x65, 0x6e, 0x74, 0x12,
-	0x35, 0x0a, 0x06, 0x64, 0x69, 0x72, 0x65, 0x63, 0x74, 0x18, 0x01, 0x20, 0x01, 0x28, 0x0b, 0x32,
-	0x1b, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x44, 0x69, 0x72, 0x65, 0x63, 0x74, 0x57,
-	0x68, 0x65, 0x72, 0x65, 0x44, 0x6f, 0x63, 0x75, 0x6d, 0x65, 0x6e, 0x74, 0x48, 0x00, 0x52, 0x06,
-	0x64, 0x69, 0x72, 0x65, 0x63, 0x74, 0x12, 0x3b, 0x0a, 0x08, 0x63, 0x68, 0x69, 0x6c, 0x64, 0x72,
-	0x65, 0x6e, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x1d, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d,
-	0x61, 0x2e, 0x57, 0x68, 0x65, 0x72, 0x65, 0x44, 0x6f, 0x63, 0x75, 0x6d, 0x65, 0x6e, 0x74, 0x43,
-	0x68, 0x69, 0x6c, 0x64, 0x72, 0x65, 0x6e, 0x48, 0x00, 0x52, 0x08, 0x63, 0x68, 0x69, 0x6c, 0x64,
-	0x72, 0x65, 0x6e, 0x42, 0x10, 0x0a, 0x0e, 0x77, 0x68, 0x65, 0x72, 0x65, 0x5f, 0x64, 0x6f, 0x63,
-	0x75, 0x6d, 0x65, 0x6e, 0x74, 0x22, 0x6c, 0x0a, 0x13, 0x44, 0x69, 0x72, 0x65, 0x63, 0x74, 0x57,
-	0x68, 0x65, 0x72, 0x65, 0x44, 0x6f, 0x63, 0x75, 0x6d, 0x65, 0x6e, 0x74, 0x12, 0x1a, 0x0a, 0x08,
-	0x64, 0x6f, 0x63, 0x75, 0x6d, 0x65, 0x6e, 0x74, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x52, 0x08,
-	0x64, 0x6f, 0x63, 0x75, 0x6d, 0x65, 0x6e, 0x74, 0x12, 0x39, 0x0a, 0x08, 0x6f, 0x70, 0x65, 0x72,
-	0x61, 0x74, 0x6f, 0x72, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0e, 0x32, 0x1d, 0x2e, 0x63, 0x68, 0x72,
-	0x6f, 0x6d, 0x61, 0x2e, 0x57, 0x68, 0x65, 0x72, 0x65, 0x44, 0x6f, 0x63, 0x75, 0x6d, 0x65, 0x6e,
-	0x74, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x52, 0x08, 0x6f, 0x70, 0x65, 0x72, 0x61,
-	0x74, 0x6f, 0x72, 0x22, 0x7f, 0x0a, 0x15, 0x57, 0x68, 0x65, 0x72, 0x65, 0x44, 0x6f, 0x63, 0x75,
-	0x6d, 0x65, 0x6e, 0x74, 0x43, 0x68, 0x69, 0x6c, 0x64, 0x72, 0x65, 0x6e, 0x12, 0x31, 0x0a, 0x08,
-	0x63, 0x68, 0x69, 0x6c, 0x64, 0x72, 0x65, 0x6e, 0x18, 0x01, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x15,
-	0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x57, 0x68, 0x65, 0x72, 0x65, 0x44, 0x6f, 0x63,
-	0x75, 0x6d, 0x65, 0x6e, 0x74, 0x52, 0x08, 0x63, 0x68, 0x69, 0x6c, 0x64, 0x72, 0x65, 0x6e, 0x12,
-	0x33, 0x0a, 0x08, 0x6f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x18, 0x02, 0x20, 0x01, 0x28,
-	0x0e, 0x32, 0x17, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x42, 0x6f, 0x6f, 0x6c, 0x65,
-	0x61, 0x6e, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x52, 0x08, 0x6f, 0x70, 0x65, 0x72,
-	0x61, 0x74, 0x6f, 0x72, 0x22, 0x8e, 0x01, 0x0a, 0x05, 0x57, 0x68, 0x65, 0x72, 0x65, 0x12, 0x47,
-	0x0a, 0x11, 0x64, 0x69, 0x72, 0x65, 0x63, 0x74, 0x5f, 0x63, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x69,
-	0x73, 0x6f, 0x6e, 0x18, 0x01, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x18, 0x2e, 0x63, 0x68, 0x72, 0x6f,
-	0x6d, 0x61, 0x2e, 0x44, 0x69, 0x72, 0x65, 0x63, 0x74, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x69,
-	0x73, 0x6f, 0x6e, 0x48, 0x00, 0x52, 0x10, 0x64, 0x69, 0x72, 0x65, 0x63, 0x74, 0x43, 0x6f, 0x6d,
-	0x70, 0x61, 0x72, 0x69, 0x73, 0x6f, 0x6e, 0x12, 0x33, 0x0a, 0x08, 0x63, 0x68, 0x69, 0x6c, 0x64,
-	0x72, 0x65, 0x6e, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x15, 0x2e, 0x63, 0x68, 0x72, 0x6f,
-	0x6d, 0x61, 0x2e, 0x57, 0x68, 0x65, 0x72, 0x65, 0x43, 0x68, 0x69, 0x6c, 0x64, 0x72, 0x65, 0x6e,
-	0x48, 0x00, 0x52, 0x08, 0x63, 0x68, 0x69, 0x6c, 0x64, 0x72, 0x65, 0x6e, 0x42, 0x07, 0x0a, 0x05,
-	0x77, 0x68, 0x65, 0x72, 0x65, 0x22, 0xac, 0x05, 0x0a, 0x10, 0x44, 0x69, 0x72, 0x65, 0x63, 0x74,
-	0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x69, 0x73, 0x6f, 0x6e, 0x12, 0x10, 0x0a, 0x03, 0x6b, 0x65,
-	0x79, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x52, 0x03, 0x6b, 0x65, 0x79, 0x12, 0x54, 0x0a, 0x15,
-	0x73, 0x69, 0x6e, 0x67, 0x6c, 0x65, 0x5f, 0x73, 0x74, 0x72, 0x69, 0x6e, 0x67, 0x5f, 0x6f, 0x70,
-	0x65, 0x72, 0x61, 0x6e, 0x64, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x1e, 0x2e, 0x63, 0x68,
-	0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x53, 0x69, 0x6e, 0x67, 0x6c, 0x65, 0x53, 0x74, 0x72, 0x69, 0x6e,
-	0x67, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x69, 0x73, 0x6f, 0x6e, 0x48, 0x00, 0x52, 0x13, 0x73,
-	0x69, 0x6e, 0x67, 0x6c, 0x65, 0x53, 0x74, 0x72, 0x69, 0x6e, 0x67, 0x4f, 0x70, 0x65, 0x72, 0x61,
-	0x6e, 0x64, 0x12, 0x4e, 0x0a, 0x13, 0x73, 0x74, 0x72, 0x69, 0x6e, 0x67, 0x5f, 0x6c, 0x69, 0x73,
-	0x74, 0x5f, 0x6f, 0x70, 0x65, 0x72, 0x61, 0x6e, 0x64, 0x18, 0x03, 0x20, 0x01, 0x28, 0x0b, 0x32,
-	0x1c, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x53, 0x74, 0x72, 0x69, 0x6e, 0x67, 0x4c,
-	0x69, 0x73, 0x74, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x69, 0x73, 0x6f, 0x6e, 0x48, 0x00, 0x52,
-	0x11, 0x73, 0x74, 0x72, 0x69, 0x6e, 0x67, 0x4c, 0x69, 0x73, 0x74, 0x4f, 0x70, 0x65, 0x72, 0x61,
-	0x6e, 0x64, 0x12, 0x4b, 0x0a, 0x12, 0x73, 0x69, 0x6e, 0x67, 0x6c, 0x65, 0x5f, 0x69, 0x6e, 0x74,
-	0x5f, 0x6f, 0x70, 0x65, 0x72, 0x61, 0x6e, 0x64, 0x18, 0x04, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x1b,
-	0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x53, 0x69, 0x6e, 0x67, 0x6c, 0x65, 0x49, 0x6e,
-	0x74, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x69, 0x73, 0x6f, 0x6e, 0x48, 0x00, 0x52, 0x10, 0x73,
-	0x69, 0x6e, 0x67, 0x6c, 0x65, 0x49, 0x6e, 0x74, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x6e, 0x64, 0x12,
-	0x45, 0x0a, 0x10, 0x69, 0x6e, 0x74, 0x5f, 0x6c, 0x69, 0x73, 0x74, 0x5f, 0x6f, 0x70, 0x65, 0x72,
-	0x61, 0x6e, 0x64, 0x18, 0x05, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x19, 0x2e, 0x63, 0x68, 0x72, 0x6f,
-	0x6d, 0x61, 0x2e, 0x49, 0x6e, 0x74, 0x4c, 0x69, 0x73, 0x74, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72,
-	0x69, 0x73, 0x6f, 0x6e, 0x48, 0x00, 0x52, 0x0e, 0x69, 0x6e, 0x74, 0x4c, 0x69, 0x73, 0x74, 0x4f,
-	0x70, 0x65, 0x72, 0x61, 0x6e, 0x64, 0x12, 0x54, 0x0a, 0x15, 0x73, 0x69, 0x6e, 0x67, 0x6c, 0x65,
-	0x5f, 0x64, 0x6f, 0x75, 0x62, 0x6c, 0x65, 0x5f, 0x6f, 0x70, 0x65, 0x72, 0x61, 0x6e, 0x64, 0x18,
-	0x06, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x1e, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x53,
-	0x69, 0x6e, 0x67, 0x6c, 0x65, 0x44, 0x6f, 0x75, 0x62, 0x6c, 0x65, 0x43, 0x6f, 0x6d, 0x70, 0x61,
-	0x72, 0x69, 0x73, 0x6f, 0x6e, 0x48, 0x00, 0x52, 0x13, 0x73, 0x69, 0x6e, 0x67, 0x6c, 0x65, 0x44,
-	0x6f, 0x75, 0x62, 0x6c, 0x65, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x6e, 0x64, 0x12, 0x4e, 0x0a, 0x13,
-	0x64, 0x6f, 0x75, 0x62, 0x6c, 0x65, 0x5f, 0x6c, 0x69, 0x73, 0x74, 0x5f, 0x6f, 0x70, 0x65, 0x72,
-	0x61, 0x6e, 0x64, 0x18, 0x07, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x1c, 0x2e, 0x63, 0x68, 0x72, 0x6f,
-	0x6d, 0x61, 0x2e, 0x44, 0x6f, 0x75, 0x62, 0x6c, 0x65, 0x4c, 0x69, 0x73, 0x74, 0x43, 0x6f, 0x6d,
-	0x70, 0x61, 0x72, 0x69, 0x73, 0x6f, 0x6e, 0x48, 0x00, 0x52, 0x11, 0x64, 0x6f, 0x75, 0x62, 0x6c,
-	0x65, 0x4c, 0x69, 0x73, 0x74, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x6e, 0x64, 0x12, 0x48, 0x0a, 0x11,
-	0x62, 0x6f, 0x6f, 0x6c, 0x5f, 0x6c, 0x69, 0x73, 0x74, 0x5f, 0x6f, 0x70, 0x65, 0x72, 0x61, 0x6e,
-	0x64, 0x18, 0x08, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x1a, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61,
-	0x2e, 0x42, 0x6f, 0x6f, 0x6c, 0x4c, 0x69, 0x73, 0x74, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x69,
-	0x73, 0x6f, 0x6e, 0x48, 0x00, 0x52, 0x0f, 0x62, 0x6f, 0x6f, 0x6c, 0x4c, 0x69, 0x73, 0x74, 0x4f,
-	0x70, 0x65, 0x72, 0x61, 0x6e, 0x64, 0x12, 0x4e, 0x0a, 0x13, 0x73, 0x69, 0x6e, 0x67, 0x6c, 0x65,
-	0x5f, 0x62, 0x6f, 0x6f, 0x6c, 0x5f, 0x6f, 0x70, 0x65, 0x72, 0x61, 0x6e, 0x64, 0x18, 0x09, 0x20,
-	0x01, 0x28, 0x0b, 0x32, 0x1c, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x53, 0x69, 0x6e,
-	0x67, 0x6c, 0x65, 0x42, 0x6f, 0x6f, 0x6c, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x69, 0x73, 0x6f,
-	0x6e, 0x48, 0x00, 0x52, 0x11, 0x73, 0x69, 0x6e, 0x67, 0x6c, 0x65, 0x42, 0x6f, 0x6f, 0x6c, 0x4f,
-	0x70, 0x65, 0x72, 0x61, 0x6e, 0x64, 0x42, 0x0c, 0x0a, 0x0a, 0x63, 0x6f, 0x6d, 0x70, 0x61, 0x72,
-	0x69, 0x73, 0x6f, 0x6e, 0x22, 0x6f, 0x0a, 0x0d, 0x57, 0x68, 0x65, 0x72, 0x65, 0x43, 0x68, 0x69,
-	0x6c, 0x64, 0x72, 0x65, 0x6e, 0x12, 0x29, 0x0a, 0x08, 0x63, 0x68, 0x69, 0x6c, 0x64, 0x72, 0x65,
-	0x6e, 0x18, 0x01, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x0d, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61,
-	0x2e, 0x57, 0x68, 0x65, 0x72, 0x65, 0x52, 0x08, 0x63, 0x68, 0x69, 0x6c, 0x64, 0x72, 0x65, 0x6e,
-	0x12, 0x33, 0x0a, 0x08, 0x6f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x18, 0x02, 0x20, 0x01,
-	0x28, 0x0e, 0x32, 0x17, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x42, 0x6f, 0x6f, 0x6c,
-	0x65, 0x61, 0x6e, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x52, 0x08, 0x6f, 0x70, 0x65,
-	0x72, 0x61, 0x74, 0x6f, 0x72, 0x22, 0x69, 0x0a, 0x14, 0x53, 0x74, 0x72, 0x69, 0x6e, 0x67, 0x4c,
-	0x69, 0x73, 0x74, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x69, 0x73, 0x6f, 0x6e, 0x12, 0x16, 0x0a,
-	0x06, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x73, 0x18, 0x01, 0x20, 0x03, 0x28, 0x09, 0x52, 0x06, 0x76,
-	0x61, 0x6c, 0x75, 0x65, 0x73, 0x12, 0x39, 0x0a, 0x0d, 0x6c, 0x69, 0x73, 0x74, 0x5f, 0x6f, 0x70,
-	0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0e, 0x32, 0x14, 0x2e, 0x63,
-	0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x4c, 0x69, 0x73, 0x74, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x74,
-	0x6f, 0x72, 0x52, 0x0c, 0x6c, 0x69, 0x73, 0x74, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f, 0x72,
-	0x22, 0x69, 0x0a, 0x16, 0x53, 0x69, 0x6e, 0x67, 0x6c, 0x65, 0x53, 0x74, 0x72, 0x69, 0x6e, 0x67,
-	0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x69, 0x73, 0x6f, 0x6e, 0x12, 0x14, 0x0a, 0x05, 0x76, 0x61,
-	0x6c, 0x75, 0x65, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x52, 0x05, 0x76, 0x61, 0x6c, 0x75, 0x65,
-	0x12, 0x39, 0x0a, 0x0a, 0x63, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x18, 0x02,
-	0x20, 0x01, 0x28, 0x0e, 0x32, 0x19, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x47, 0x65,
-	0x6e, 0x65, 0x72, 0x69, 0x63, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x52,
-	0x0a, 0x63, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x22, 0x67, 0x0a, 0x14, 0x53,
-	0x69, 0x6e, 0x67, 0x6c, 0x65, 0x42, 0x6f, 0x6f, 0x6c, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x69,
-	0x73, 0x6f, 0x6e, 0x12, 0x14, 0x0a, 0x05, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x18, 0x01, 0x20, 0x01,
-	0x28, 0x08, 0x52, 0x05, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x12, 0x39, 0x0a, 0x0a, 0x63, 0x6f, 0x6d,
-	0x70, 0x61, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0e, 0x32, 0x19, 0x2e,
-	0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x47, 0x65, 0x6e, 0x65, 0x72, 0x69, 0x63, 0x43, 0x6f,
-	0x6d, 0x70, 0x61, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x52, 0x0a, 0x63, 0x6f, 0x6d, 0x70, 0x61, 0x72,
-	0x61, 0x74, 0x6f, 0x72, 0x22, 0x66, 0x0a, 0x11, 0x49, 0x6e, 0x74, 0x4c, 0x69, 0x73, 0x74, 0x43,
-	0x6f, 0x6d, 0x70, 0x61, 0x72, 0x69, 0x73, 0x6f, 0x6e, 0x12, 0x16, 0x0a, 0x06, 0x76, 0x61, 0x6c,
-	0x75, 0x65, 0x73, 0x18, 0x01, 0x20, 0x03, 0x28, 0x03, 0x52, 0x06, 0x76, 0x61, 0x6c, 0x75, 0x65,
-	0x73, 0x12, 0x39, 0x0a, 0x0d, 0x6c, 0x69, 0x73, 0x74, 0x5f, 0x6f, 0x70, 0x65, 0x72, 0x61, 0x74,
-	0x6f, 0x72, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0e, 0x32, 0x14, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d,
-	0x61, 0x2e, 0x4c, 0x69, 0x73, 0x74, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x52, 0x0c,
-	0x6c, 0x69, 0x73, 0x74, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x22, 0xce, 0x01, 0x0a,
-	0x13, 0x53, 0x69, 0x6e, 0x67, 0x6c, 0x65, 0x49, 0x6e, 0x74, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72,
-	0x69, 0x73, 0x6f, 0x6e, 0x12, 0x14, 0x0a, 0x05, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x18, 0x01, 0x20,
-	0x01, 0x28, 0x03, 0x52, 0x05, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x12, 0x4a, 0x0a, 0x12, 0x67, 0x65,
-	0x6e, 0x65, 0x72, 0x69, 0x63, 0x5f, 0x63, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x61, 0x74, 0x6f, 0x72,
-	0x18, 0x02, 0x20, 0x01, 0x28, 0x0e, 0x32, 0x19, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e,
-	0x47, 0x65, 0x6e, 0x65, 0x72, 0x69, 0x63, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x61, 0x74, 0x6f,
-	0x72, 0x48, 0x00, 0x52, 0x11, 0x67, 0x65, 0x6e, 0x65, 0x72, 0x69, 0x63, 0x43, 0x6f, 0x6d, 0x70,
-	0x61, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x12, 0x47, 0x0a, 0x11, 0x6e, 0x75, 0x6d, 0x62, 0x65, 0x72,
-	0x5f, 0x63, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x18, 0x03, 0x20, 0x01, 0x28,
-	0x0e, 0x32, 0x18, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x4e, 0x75, 0x6d, 0x62, 0x65,
-	0x72, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x48, 0x00, 0x52, 0x10, 0x6e,
-	0x75, 0x6d, 0x62, 0x65, 0x72, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x42,
-	0x0c, 0x0a, 0x0a, 0x63, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x22, 0x69, 0x0a,
-	0x14, 0x44, 0x6f, 0x75, 0x62, 0x6c, 0x65, 0x4c, 0x69, 0x73, 0x74, 0x43, 0x6f, 0x6d, 0x70, 0x61,
-	0x72, 0x69, 0x73, 0x6f, 0x6e, 0x12, 0x16, 0x0a, 0x06, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x73, 0x18,
-	0x01, 0x20, 0x03, 0x28, 0x01, 0x52, 0x06, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x73, 0x12, 0x39, 0x0a,
-	0x0d, 0x6c, 0x69, 0x73, 0x74, 0x5f, 0x6f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x18, 0x02,
-	0x20, 0x01, 0x28, 0x0e, 0x32, 0x14, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x4c, 0x69,
-	0x73, 0x74, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x52, 0x0c, 0x6c, 0x69, 0x73, 0x74,
-	0x4f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x22, 0x67, 0x0a, 0x12, 0x42, 0x6f, 0x6f, 0x6c,
-	0x4c, 0x69, 0x73, 0x74, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x69, 0x73, 0x6f, 0x6e, 0x12, 0x16,
-	0x0a, 0x06, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x73, 0x18, 0x01, 0x20, 0x03, 0x28, 0x08, 0x52, 0x06,
-	0x76, 0x61, 0x6c, 0x75, 0x65, 0x73, 0x12, 0x39, 0x0a, 0x0d, 0x6c, 0x69, 0x73, 0x74, 0x5f, 0x6f,
-	0x70, 0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0e, 0x32, 0x14, 0x2e,
-	0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x4c, 0x69, 0x73, 0x74, 0x4f, 0x70, 0x65, 0x72, 0x61,
-	0x74, 0x6f, 0x72, 0x52, 0x0c, 0x6c, 0x69, 0x73, 0x74, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f,
-	0x72, 0x22, 0xd1, 0x01, 0x0a, 0x16, 0x53, 0x69, 0x6e, 0x67, 0x6c, 0x65, 0x44, 0x6f, 0x75, 0x62,
-	0x6c, 0x65, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x69, 0x73, 0x6f, 0x6e, 0x12, 0x14, 0x0a, 0x05,
-	0x76, 0x61, 0x6c, 0x75, 0x65, 0x18, 0x01, 0x20, 0x01, 0x28, 0x01, 0x52, 0x05, 0x76, 0x61, 0x6c,
-	0x75, 0x65, 0x12, 0x4a, 0x0a, 0x12, 0x67, 0x65, 0x6e, 0x65, 0x72, 0x69, 0x63, 0x5f, 0x63, 0x6f,
-	0x6d, 0x70, 0x61, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0e, 0x32, 0x19,
-	0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x47, 0x65, 0x6e, 0x65, 0x72, 0x69, 0x63, 0x43,
-	0x6f, 0x6d, 0x70, 0x61, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x48, 0x00, 0x52, 0x11, 0x67, 0x65, 0x6e,
-	0x65, 0x72, 0x69, 0x63, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x12, 0x47,
-	0x0a, 0x11, 0x6e, 0x75, 0x6d, 0x62, 0x65, 0x72, 0x5f, 0x63, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x61,
-	0x74, 0x6f, 0x72, 0x18, 0x03, 0x20, 0x01, 0x28, 0x0e, 0x32, 0x18, 0x2e, 0x63, 0x68, 0x72, 0x6f,
-	0x6d, 0x61, 0x2e, 0x4e, 0x75, 0x6d, 0x62, 0x65, 0x72, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x61,
-	0x74, 0x6f, 0x72, 0x48, 0x00, 0x52, 0x10, 0x6e, 0x75, 0x6d, 0x62, 0x65, 0x72, 0x43, 0x6f, 0x6d,
-	0x70, 0x61, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x42, 0x0c, 0x0a, 0x0a, 0x63, 0x6f, 0x6d, 0x70, 0x61,
-	0x72, 0x61, 0x74, 0x6f, 0x72, 0x22, 0xb1, 0x01, 0x0a, 0x11, 0x47, 0x65, 0x74, 0x56, 0x65, 0x63,
-	0x74, 0x6f, 0x72, 0x73, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x12, 0x10, 0x0a, 0x03, 0x69,
-	0x64, 0x73, 0x18, 0x01, 0x20, 0x03, 0x28, 0x09, 0x52, 0x03, 0x69, 0x64, 0x73, 0x12, 0x1d, 0x0a,
-	0x0a, 0x73, 0x65, 0x67, 0x6d, 0x65, 0x6e, 0x74, 0x5f, 0x69, 0x64, 0x18, 0x02, 0x20, 0x01, 0x28,
-	0x09, 0x52, 0x09, 0x73, 0x65, 0x67, 0x6d, 0x65, 0x6e, 0x74, 0x49, 0x64, 0x12, 0x23, 0x0a, 0x0d,
-	0x63, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x5f, 0x69, 0x64, 0x18, 0x03, 0x20,
-	0x01, 0x28, 0x09, 0x52, 0x0c, 0x63, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x49,
-	0x64, 0x12, 0x46, 0x0a, 0x0f, 0x76, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x5f, 0x63, 0x6f, 0x6e,
-	0x74, 0x65, 0x78, 0x74, 0x18, 0x04, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x1d, 0x2e, 0x63, 0x68, 0x72,
-	0x6f, 0x6d, 0x61, 0x2e, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x56, 0x65, 0x72, 0x73, 0x69,
-	0x6f, 0x6e, 0x43, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x52, 0x0e, 0x76, 0x65, 0x72, 0x73, 0x69,
-	0x6f, 0x6e, 0x43, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x22, 0x4d, 0x0a, 0x12, 0x47, 0x65, 0x74,
-	0x56, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x73, 0x52, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x12,
-	0x37, 0x0a, 0x07, 0x72, 0x65, 0x63, 0x6f, 0x72, 0x64, 0x73, 0x18, 0x01, 0x20, 0x03, 0x28, 0x0b,
-	0x32, 0x1d, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x56, 0x65, 0x63, 0x74, 0x6f, 0x72,
-	0x45, 0x6d, 0x62, 0x65, 0x64, 0x64, 0x69, 0x6e, 0x67, 0x52, 0x65, 0x63, 0x6f, 0x72, 0x64, 0x52,
-	0x07, 0x72, 0x65, 0x63, 0x6f, 0x72, 0x64, 0x73, 0x22, 0x4f, 0x0a, 0x15, 0x56, 0x65, 0x63, 0x74,
-	0x6f, 0x72, 0x45, 0x6d, 0x62, 0x65, 0x64, 0x64, 0x69, 0x6e, 0x67, 0x52, 0x65, 0x63, 0x6f, 0x72,
-	0x64, 0x12, 0x0e, 0x0a, 0x02, 0x69, 0x64, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x52, 0x02, 0x69,
-	0x64, 0x12, 0x26, 0x0a, 0x06, 0x76, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x18, 0x03, 0x20, 0x01, 0x28,
-	0x0b, 0x32, 0x0e, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x56, 0x65, 0x63, 0x74, 0x6f,
-	0x72, 0x52, 0x06, 0x76, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x22, 0xa9, 0x02, 0x0a, 0x13, 0x51, 0x75,
-	0x65, 0x72, 0x79, 0x56, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x73, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73,
-	0x74, 0x12, 0x28, 0x0a, 0x07, 0x76, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x73, 0x18, 0x01, 0x20, 0x03,
-	0x28, 0x0b, 0x32, 0x0e, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x56, 0x65, 0x63, 0x74,
-	0x6f, 0x72, 0x52, 0x07, 0x76, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x73, 0x12, 0x0c, 0x0a, 0x01, 0x6b,
-	0x18, 0x02, 0x20, 0x01, 0x28, 0x05, 0x52, 0x01, 0x6b, 0x12, 0x1f, 0x0a, 0x0b, 0x61, 0x6c, 0x6c,
-	0x6f, 0x77, 0x65, 0x64, 0x5f, 0x69, 0x64, 0x73, 0x18, 0x03, 0x20, 0x03, 0x28, 0x09, 0x52, 0x0a,
-	0x61, 0x6c, 0x6c, 0x6f, 0x77, 0x65, 0x64, 0x49, 0x64, 0x73, 0x12, 0x2d, 0x0a, 0x12, 0x69, 0x6e,
-	0x63, 0x6c, 0x75, 0x64, 0x65, 0x5f, 0x65, 0x6d, 0x62, 0x65, 0x64, 0x64, 0x69, 0x6e, 0x67, 0x73,
-	0x18, 0x04, 0x20, 0x01, 0x28, 0x08, 0x52, 0x11, 0x69, 0x6e, 0x63, 0x6c, 0x75, 0x64, 0x65, 0x45,
-	0x6d, 0x62, 0x65, 0x64, 0x64, 0x69, 0x6e, 0x67, 0x73, 0x12, 0x1d, 0x0a, 0x0a, 0x73, 0x65, 0x67,
-	0x6d, 0x65, 0x6e, 0x74, 0x5f, 0x69, 0x64, 0x18, 0x05, 0x20, 0x01, 0x28, 0x09, 0x52, 0x09, 0x73,
-	0x65, 0x67, 0x6d, 0x65, 0x6e, 0x74, 0x49, 0x64, 0x12, 0x23, 0x0a, 0x0d, 0x63, 0x6f, 0x6c, 0x6c,
-	0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x5f, 0x69, 0x64, 0x18, 0x06, 0x20, 0x01, 0x28, 0x09, 0x52,
-	0x0c, 0x63, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x49, 0x64, 0x12, 0x46, 0x0a,
-	0x0f, 0x76, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x5f, 0x63, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74,
-	0x18, 0x07, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x1d, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e,
-	0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x56, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x43, 0x6f,
-	0x6e, 0x74, 0x65, 0x78, 0x74, 0x52, 0x0e, 0x76, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x43, 0x6f,
-	0x6e, 0x74, 0x65, 0x78, 0x74, 0x22, 0x4c, 0x0a, 0x14, 0x51, 0x75, 0x65, 0x72, 0x79, 0x56, 0x65,
-	0x63, 0x74, 0x6f, 0x72, 0x73, 0x52, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x12, 0x34, 0x0a,
-	0x07, 0x72, 0x65, 0x73, 0x75, 0x6c, 0x74, 0x73, 0x18, 0x01, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x1a,
-	0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x56, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x51, 0x75,
-	0x65, 0x72, 0x79, 0x52, 0x65, 0x73, 0x75, 0x6c, 0x74, 0x73, 0x52, 0x07, 0x72, 0x65, 0x73, 0x75,
-	0x6c, 0x74, 0x73, 0x22, 0x49, 0x0a, 0x12, 0x56, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x51, 0x75, 0x65,
-	0x72, 0x79, 0x52, 0x65, 0x73, 0x75, 0x6c, 0x74, 0x73, 0x12, 0x33, 0x0a, 0x07, 0x72, 0x65, 0x73,
-	0x75, 0x6c, 0x74, 0x73, 0x18, 0x01, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x19, 0x2e, 0x63, 0x68, 0x72,
-	0x6f, 0x6d, 0x61, 0x2e, 0x56, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x51, 0x75, 0x65, 0x72, 0x79, 0x52,
-	0x65, 0x73, 0x75, 0x6c, 0x74, 0x52, 0x07, 0x72, 0x65, 0x73, 0x75, 0x6c, 0x74, 0x73, 0x22, 0x77,
-	0x0a, 0x11, 0x56, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x51, 0x75, 0x65, 0x72, 0x79, 0x52, 0x65, 0x73,
-	0x75, 0x6c, 0x74, 0x12, 0x0e, 0x0a, 0x02, 0x69, 0x64, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x52,
-	0x02, 0x69, 0x64, 0x12, 0x1a, 0x0a, 0x08, 0x64, 0x69, 0x73, 0x74, 0x61, 0x6e, 0x63, 0x65, 0x18,
-	0x03, 0x20, 0x01, 0x28, 0x02, 0x52, 0x08, 0x64, 0x69, 0x73, 0x74, 0x61, 0x6e, 0x63, 0x65, 0x12,
-	0x2b, 0x0a, 0x06, 0x76, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x18, 0x04, 0x20, 0x01, 0x28, 0x0b, 0x32,
-	0x0e, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x56, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x48,
-	0x00, 0x52, 0x06, 0x76, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x88, 0x01, 0x01, 0x42, 0x09, 0x0a, 0x07,
-	0x5f, 0x76, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x2a, 0x38, 0x0a, 0x09, 0x4f, 0x70, 0x65, 0x72, 0x61,
-	0x74, 0x69, 0x6f, 0x6e, 0x12, 0x07, 0x0a, 0x03, 0x41, 0x44, 0x44, 0x10, 0x00, 0x12, 0x0a, 0x0a,
-	0x06, 0x55, 0x50, 0x44, 0x41, 0x54, 0x45, 0x10, 0x01, 0x12, 0x0a, 0x0a, 0x06, 0x55, 0x50, 0x53,
-	0x45, 0x52, 0x54, 0x10, 0x02, 0x12, 0x0a, 0x0a, 0x06, 0x44, 0x45, 0x4c, 0x45, 0x54, 0x45, 0x10,
-	0x03, 0x2a, 0x28, 0x0a, 0x0e, 0x53, 0x63, 0x61, 0x6c, 0x61, 0x72, 0x45, 0x6e, 0x63, 0x6f, 0x64,
-	0x69, 0x6e, 0x67, 0x12, 0x0b, 0x0a, 0x07, 0x46, 0x4c, 0x4f, 0x41, 0x54, 0x33, 0x32, 0x10, 0x00,
-	0x12, 0x09, 0x0a, 0x05, 0x49, 0x4e, 0x54, 0x33, 0x32, 0x10, 0x01, 0x2a, 0x40, 0x0a, 0x0c, 0x53,
-	0x65, 0x67, 0x6d, 0x65, 0x6e, 0x74, 0x53, 0x63, 0x6f, 0x70, 0x65, 0x12, 0x0a, 0x0a, 0x06, 0x56,
-	0x45, 0x43, 0x54, 0x4f, 0x52, 0x10, 0x00, 0x12, 0x0c, 0x0a, 0x08, 0x4d, 0x45, 0x54, 0x41, 0x44,
-	0x41, 0x54, 0x41, 0x10, 0x01, 0x12, 0x0a, 0x0a, 0x06, 0x52, 0x45, 0x43, 0x4f, 0x52, 0x44, 0x10,
-	0x02, 0x12, 0x0a, 0x0a, 0x06, 0x53, 0x51, 0x4c, 0x49, 0x54, 0x45, 0x10, 0x03, 0x2a, 0x37, 0x0a,
-	0x15, 0x57, 0x68, 0x65, 0x72, 0x65, 0x44, 0x6f, 0x63, 0x75, 0x6d, 0x65, 0x6e, 0x74, 0x4f, 0x70,
-	0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x12, 0x0c, 0x0a, 0x08, 0x43, 0x4f, 0x4e, 0x54, 0x41, 0x49,
-	0x4e, 0x53, 0x10, 0x00, 0x12, 0x10, 0x0a, 0x0c, 0x4e, 0x4f, 0x54, 0x5f, 0x43, 0x4f, 0x4e, 0x54,
-	0x41, 0x49, 0x4e, 0x53, 0x10, 0x01, 0x2a, 0x22, 0x0a, 0x0f, 0x42, 0x6f, 0x6f, 0x6c, 0x65, 0x61,
-	0x6e, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x12, 0x07, 0x0a, 0x03, 0x41, 0x4e, 0x44,
-	0x10, 0x00, 0x12, 0x06, 0x0a, 0x02, 0x4f, 0x52, 0x10, 0x01, 0x2a, 0x1f, 0x0a, 0x0c, 0x4c, 0x69,
-	0x73, 0x74, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x12, 0x06, 0x0a, 0x02, 0x49, 0x4e,
-	0x10, 0x00, 0x12, 0x07, 0x0a, 0x03, 0x4e, 0x49, 0x4e, 0x10, 0x01, 0x2a, 0x23, 0x0a, 0x11, 0x47,
-	0x65, 0x6e, 0x65, 0x72, 0x69, 0x63, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x61, 0x74, 0x6f, 0x72,
-	0x12, 0x06, 0x0a, 0x02, 0x45, 0x51, 0x10, 0x00, 0x12, 0x06, 0x0a, 0x02, 0x4e, 0x45, 0x10, 0x01,
-	0x2a, 0x34, 0x0a, 0x10, 0x4e, 0x75, 0x6d, 0x62, 0x65, 0x72, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72,
-	0x61, 0x74, 0x6f, 0x72, 0x12, 0x06, 0x0a, 0x02, 0x47, 0x54, 0x10, 0x00, 0x12, 0x07, 0x0a, 0x03,
-	0x47, 0x54, 0x45, 0x10, 0x01, 0x12, 0x06, 0x0a, 0x02, 0x4c, 0x54, 0x10, 0x02, 0x12, 0x07, 0x0a,
-	0x03, 0x4c, 0x54, 0x45, 0x10, 0x03, 0x32, 0xad, 0x01, 0x0a, 0x0e, 0x4d, 0x65, 0x74, 0x61, 0x64,
-	0x61, 0x74, 0x61, 0x52, 0x65, 0x61, 0x64, 0x65, 0x72, 0x12, 0x4e, 0x0a, 0x0d, 0x51, 0x75, 0x65,
-	0x72, 0x79, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x12, 0x1c, 0x2e, 0x63, 0x68, 0x72,
-	0x6f, 0x6d, 0x61, 0x2e, 0x51, 0x75, 0x65, 0x72, 0x79, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74,
-	0x61, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x1a, 0x1d, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d,
-	0x61, 0x2e, 0x51, 0x75, 0x65, 0x72, 0x79, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x52,
-	0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x22, 0x00, 0x12, 0x4b, 0x0a, 0x0c, 0x43, 0x6f, 0x75,
-	0x6e, 0x74, 0x52, 0x65, 0x63, 0x6f, 0x72, 0x64, 0x73, 0x12, 0x1b, 0x2e, 0x63, 0x68, 0x72, 0x6f,
-	0x6d, 0x61, 0x2e, 0x43, 0x6f, 0x75, 0x6e, 0x74, 0x52, 0x65, 0x63, 0x6f, 0x72, 0x64, 0x73, 0x52,
-	0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x1a, 0x1c, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e,
-	0x43, 0x6f, 0x75, 0x6e, 0x74, 0x52, 0x65, 0x63, 0x6f, 0x72, 0x64, 0x73, 0x52, 0x65, 0x73, 0x70,
-	0x6f, 0x6e, 0x73, 0x65, 0x22, 0x00, 0x32, 0xa2, 0x01, 0x0a, 0x0c, 0x56, 0x65, 0x63, 0x74, 0x6f,
-	0x72, 0x52, 0x65, 0x61, 0x64, 0x65, 0x72, 0x12, 0x45, 0x0a, 0x0a, 0x47, 0x65, 0x74, 0x56, 0x65,
-	0x63, 0x74, 0x6f, 0x72, 0x73, 0x12, 0x19, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x47,
-	0x65, 0x74, 0x56, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x73, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74,
-	0x1a, 0x1a, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x47, 0x65, 0x74, 0x56, 0x65, 0x63,
-	0x74, 0x6f, 0x72, 0x73, 0x52, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x22, 0x00, 0x12, 0x4b,
-	0x0a, 0x0c, 0x51, 0x75, 0x65, 0x72, 0x79, 0x56, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x73, 0x12, 0x1b,
-	0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x51, 0x75, 0x65, 0x72, 0x79, 0x56, 0x65, 0x63,
-	0x74, 0x6f, 0x72, 0x73, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x1a, 0x1c, 0x2e, 0x63, 0x68,
-	0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x51, 0x75, 0x65, 0x72, 0x79, 0x56, 0x65, 0x63, 0x74, 0x6f, 0x72,
-	0x73, 0x52, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x22, 0x00, 0x42, 0x3a, 0x5a, 0x38, 0x67,
-	0x69, 0x74, 0x68, 0x75, 0x62, 0x2e, 0x63, 0x6f, 0x6d, 0x2f, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61,
-	0x2d, 0x63, 0x6f, 0x72, 0x65, 0x2f, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2f, 0x67, 0x6f, 0x2f,
-	0x70, 0x6b, 0x67, 0x2f, 0x70, 0x72, 0x6f, 0x74, 0x6f, 0x2f, 0x63, 0x6f, 0x6f, 0x72, 0x64, 0x69,
-	0x6e, 0x61, 0x74, 0x6f, 0x72, 0x70, 0x62, 0x62, 0x06, 0x70, 0x72, 0x6f, 0x74, 0x6f, 0x33,
-}
-
-var (
-	file_chromadb_proto_chroma_proto_rawDescOnce sync.Once
-	file_chromadb_proto_chroma_proto_rawDescData = file_chromadb_proto_chroma_proto_rawDesc
-)
-
-func file_chromadb_proto_chroma_proto_rawDescGZIP() []byte {
-	file_chromadb_proto_chroma_proto_rawDescOnce.Do(func() {
-		file_chromadb_proto_chroma_proto_rawDescData = protoimpl.X.CompressGZIP(file_chromadb_proto_chroma_proto_rawDescData)
-	})
-	return file_chromadb_proto_chroma_proto_rawDescData
-}
-
-var file_chromadb_proto_chroma_proto_enumTypes = make([]protoimpl.EnumInfo, 8)
-var file_chromadb_proto_chroma_proto_msgTypes = make([]protoimpl.MessageInfo, 39)
-var file_chromadb_proto_chroma_proto_goTypes = []any{
-	(Operation)(0),                  // 0: chroma.Operation
-	(ScalarEncoding)(0),             // 1: chroma.ScalarEncoding
-	(SegmentScope)(0),               // 2: chroma.SegmentScope
-	(WhereDocumentOperator)(0),      // 3: chroma.WhereDocumentOperator
-	(BooleanOperator)(0),            // 4: chroma.BooleanOperator
-	(ListOperator)(0),               // 5: chroma.ListOperator
-	(GenericComparator)(0),          // 6: chroma.GenericComparator
-	(NumberComparator)(0),           // 7: chroma.NumberComparator
-	(*Vector)(nil),                  // 8: chroma.Vector
-	(*FilePaths)(nil),               // 9: chroma.FilePaths
-	(*Segment)(nil),                 // 10: chroma.Segment
-	(*Collection)(nil),              // 11: chroma.Collection
-	(*Database)(nil),                // 12: chroma.Database
-	(*Tenant)(nil),                  // 13: chroma.Tenant
-	(*UpdateMetadataValue)(nil),     // 14: chroma.UpdateMetadataValue
-	(*UpdateMetadata)(nil),          // 15: chroma.UpdateMetadata
-	(*OperationRecord)(nil),         // 16: chroma.OperationRecord
-	(*RequestVersionContext)(nil),   // 17: chroma.RequestVersionContext
-	(*CountRecordsRequest)(nil),     // 18: chroma.CountRecordsRequest
-	(*CountRecordsResponse)(nil),    // 19: chroma.CountRecordsResponse
-	(*QueryMetadataRequest)(nil),    // 20: chroma.QueryMetadataRequest
-	(*QueryMetadataResponse)(nil),   // 21: chroma.QueryMetadataResponse
-	(*MetadataEmbeddingRecord)(nil), // 22: chroma.MetadataEmbeddingRecord
-	(*UserIds)(nil),                 // 23: chroma.UserIds
-	(*WhereDocument)(nil),           // 24: chroma.WhereDocument
-	(*DirectWhereDocument)(nil),     // 25: chroma.DirectWhereDocument
-	(*WhereDocumentChildren)(nil),   // 26: chroma.WhereDocumentChildren
-	(*Where)(nil),                   // 27: chroma.Where
-	(*DirectComparison)(nil),        // 28: chroma.DirectComparison
-	(*WhereChildren)(nil),           // 29: chroma.WhereChildren
-	(*StringListComparison)(nil),    // 30: chroma.StringListComparison
-	(*SingleStringComparison)(nil),  // 31: chroma.SingleStringComparison
-	(*SingleBoolComparison)(nil),    // 32: chroma.SingleBoolComparison
-	(*IntListComparison)(nil),       // 33: chroma.IntListComparison
-	(*SingleIntComparison)(nil),     // 34: chroma.SingleIntComparison
-	(*DoubleListComparison)(nil),    // 35: chroma.DoubleListComparison
-	(*BoolListComparison)(nil),      // 36: chroma.BoolListComparison
-	(*SingleDoubleComparison)(nil),  // 37: chroma.SingleDoubleComparison
-	(*GetVectorsRequest)(nil),       // 38: chroma.GetVectorsRequest
-	(*GetVectorsResponse)(nil),      // 39: chroma.GetVectorsResponse
-	(*VectorEmbeddingRecord)(nil),   // 40: chroma.VectorEmbeddingRecord
-	(*QueryVectorsRequest)(nil),     // 41: chroma.QueryVectorsRequest
-	(*QueryVectorsResponse)(nil),    // 42: chroma.QueryVectorsResponse
-	(*VectorQueryResults)(nil),      // 43: chroma.VectorQueryResults
-	(*VectorQueryResult)(nil),       // 44: chroma.VectorQueryResult
-	nil,                             // 45: chroma.Segment.FilePathsEntry
-	nil,                             // 46: chroma.UpdateMetadata.MetadataEntry
-}
-var file_chromadb_proto_chroma_proto_depIdxs = []int32{
-	1,  // 0: chroma.Vector.encoding:type_name -> chroma.ScalarEncoding
-	2,  // 1: chroma.Segment.scope:type_name -> chroma.SegmentScope
-	15, // 2: chroma.Segment.metadata:type_name -> chroma.UpdateMetadata
-	45, // 3: chroma.Segment.file_paths:type_name -> chroma.Segment.FilePathsEntry
-	15, // 4: chroma.Collection.metadata:type_name -> chroma.UpdateMetadata
-	46, // 5: chroma.UpdateMetadata.metadata:type_name -> chroma.UpdateMetadata.MetadataEntry
-	8,  // 6: chroma.OperationRecord.vector:type_name -> chroma.Vector
-	15, // 7: chroma.OperationRecord.metadata:type_name -> chroma.UpdateMetadata
-	0,  // 8: chroma.OperationRecord.operation:type_name -> chroma.Operation
-	17, // 9: chroma.CountRecordsRequest.version_context:type_name -> chroma.RequestVersionContext
-	27, // 10: chroma.QueryMetadataRequest.where:type_name -> chroma.Where
-	24, // 11: chroma.QueryMetadataRequest.where_document:type_name -> chroma.WhereDocument
-	23, // 12: chroma.QueryMetadataRequest.ids:type_name -> chroma.UserIds
-	17, // 13: chroma.QueryMetadataRequest.version_context:type_name -> chroma.RequestVersionContext
-	22, // 14: chroma.QueryMetadataResponse.records:type_name -> chroma.MetadataEmbeddingRecord
-	15, // 15: chroma.MetadataEmbeddingRecord.metadata:type_name -> chroma.UpdateMetadata
-	25, // 16: chroma.WhereDocument.direct:type_name -> chroma.DirectWhereDocument
-	26, // 17: chroma.WhereDocument.children:type_name -> chroma.WhereDocumentChildren
-	3,  // 18: chroma.DirectWhereDocument.operator:type_name -> chroma.WhereDocumentOperator
-	24, // 19: chroma.WhereDocumentChildren.children:type_name -> chroma.WhereDocument
-	4,  // 20: chroma.WhereDocumentChildren.operator:type_name -> chroma.BooleanOperator
-	28, // 21: chroma.Where.direct_comparison:type_name -> chroma.DirectComparison
-	29, // 22: chroma.Where.children:type_name -> chroma.WhereChildren
-	31, // 23: chroma.DirectComparison.single_string_operand:type_name -> chroma.SingleStringComparison
-	30, // 24: chroma.DirectComparison.string_list_operand:type_name -> chroma.StringListComparison
-	34, // 25: chroma.DirectComparison.single_int_operand:type_name -> chroma.SingleIntComparison
-	33, // 26: chroma.DirectComparison.int_list_operand:type_name -> chroma.IntListComparison
-	37, // 27: chroma.DirectComparison.single_double_operand:type_name -> chroma.SingleDoubleComparison
-	35, // 28: chroma.DirectComparison.double_list_operand:type_name -> chroma.DoubleListComparison
-	36, // 29: chroma.DirectComparison.bool_list_operand:type_name -> chroma.BoolListComparison
-	32, // 30: chroma.DirectComparison.single_bool_operand:type_name -> chroma.SingleBoolComparison
-	27, // 31: chroma.WhereChildren.children:type_name -> chroma.Where
-	4,  // 32: chroma.WhereChildren.operator:type_name -> chroma.BooleanOperator
-	5,  // 33: chroma.StringListComparison.list_operator:type_name -> chroma.ListOperator
-	6,  // 34: chroma.SingleStringComparison.comparator:type_name -> chroma.GenericComparator
-	6,  // 35: chroma.SingleBoolComparison.comparator:type_name -> chroma.GenericComparator
-	5,  // 36: chroma.IntListComparison.list_operator:type_name -> chroma.ListOperator
-	6,  // 37: chroma.SingleIntComparison.generic_comparator:type_name -> chroma.GenericComparator
-	7,  // 38: chroma.SingleIntComparison.number_comparator:type_name -> chroma.NumberComparator
-	5,  // 39: chroma.DoubleListComparison.list_operator:type_name -> chroma.ListOperator
-	5,  // 40: chroma.BoolListComparison.list_operator:type_name -> chroma.ListOperator
-	6,  // 41: chroma.SingleDoubleComparison.generic_comparator:type_name -> chroma.GenericComparator
-	7,  // 42: chroma.SingleDoubleComparison.number_comparator:type_name -> chroma.NumberComparator
-	17, // 43: chroma.GetVectorsRequest.version_context:type_name -> chroma.RequestVersionContext
-	40, // 44: chroma.GetVectorsResponse.records:type_name -> chroma.VectorEmbeddingRecord
-	8,  // 45: chroma.VectorEmbeddingRecord.vector:type_name -> chroma.Vector
-	8,  // 46: chroma.QueryVectorsRequest.vectors:type_name -> chroma.Vector
-	17, // 47: chroma.QueryVectorsRequest.version_context:type_name -> chroma.RequestVersionContext
-	43, // 48: chroma.QueryVectorsResponse.results:type_name -> chroma.VectorQueryResults
-	44, // 49: chroma.VectorQueryResults.results:type_name -> chroma.VectorQueryResult
-	8,  // 50: chroma.VectorQueryResult.vector:type_name -> chroma.Vector
-	9,  // 51: chroma.Segment.FilePathsEntry.value:type_name -> chroma.FilePaths
-	14, // 52: chroma.UpdateMetadata.MetadataEntry.value:type_name -> chroma.UpdateMetadataValue
-	20, // 53: chroma.MetadataReader.QueryMetadata:input_type -> chroma.QueryMetadataRequest
-	18, // 54: chroma.MetadataReader.CountRecords:input_type -> chroma.CountRecordsRequest
-	38, // 55: chroma.VectorReader.GetVectors:input_type -> chroma.GetVectorsRequest
-	41, // 56: chroma.VectorReader.QueryVectors:input_type -> chroma.QueryVectorsRequest
-	21, // 57: chroma.MetadataReader.QueryMetadata:output_type -> chroma.QueryMetadataResponse
-	19, // 58: chroma.MetadataReader.CountRecords:output_type -> chroma.CountRecordsResponse
-	39, // 59: chroma.VectorReader.GetVectors:output_type -> chroma.GetVectorsResponse
-	42, // 60: chroma.VectorReader.QueryVectors:output_type -> chroma.QueryVectorsResponse
-	57, // [57:61] is the sub-list for method output_type
-	53, // [53:57] is the sub-list for method input_type
-	53, // [53:53] is the sub-list for extension type_name
-	53, // [53:53] is the sub-list for extension extendee
-	0,  // [0:53] is the sub-list for field type_name
-}
-
-func init() { file_chromadb_proto_chroma_proto_init() }
-func file_chromadb_proto_chroma_proto_init() {
-	if File_chromadb_proto_chroma_proto != nil {
-		return
-	}
-	if !protoimpl.UnsafeEnabled {
-		file_chromadb_proto_chroma_proto_msgTypes[0].Exporter = func(v any, i int) any {
-			switch v := v.(*Vector); i {
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ExistenceComparison.ProtoReflect.Descriptor instead.
+func (*ExistenceComparison) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{36}
+}
+
+func (x *ExistenceComparison) GetOperator() ExistenceOperator {
+	if x != nil {
+		return x.Operator
+	}
+	return ExistenceOperator_EXISTS
+}
+
+// Used when a leaf-node `Where` clause compares a string to a single string.
+type SingleStringComparison struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	Value      string            `protobuf:"bytes,1,opt,name=value,proto3" json:"value,omitempty"`
+	Comparator GenericComparator `protobuf:"varint,2,opt,name=comparator,proto3,enum=chroma.GenericComparator" json:"comparator,omitempty"`
+}
+
+func (x *SingleStringComparison) Reset() {
+	*x = SingleStringComparison{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[37]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *SingleStringComparison) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*SingleStringComparison) ProtoMessage() {}
+
+func (x *SingleStringComparison) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[37]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use SingleStringComparison.ProtoReflect.Descriptor instead.
+func (*SingleStringComparison) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{37}
+}
+
+func (x *SingleStringComparison) GetValue() string {
+	if x != nil {
+		return x.Value
+	}
+	return ""
+}
+
+func (x *SingleStringComparison) GetComparator() GenericComparator {
+	if x != nil {
+		return x.Comparator
+	}
+	return GenericComparator_EQ
+}
+
+type SingleBoolComparison struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	Value      bool              `protobuf:"varint,1,opt,name=value,proto3" json:"value,omitempty"`
+	Comparator GenericComparator `protobuf:"varint,2,opt,name=comparator,proto3,enum=chroma.GenericComparator" json:"comparator,omitempty"`
+}
+
+func (x *SingleBoolComparison) Reset() {
+	*x = SingleBoolComparison{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[38]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *SingleBoolComparison) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*SingleBoolComparison) ProtoMessage() {}
+
+func (x *SingleBoolComparison) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[38]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use SingleBoolComparison.ProtoReflect.Descriptor instead.
+func (*SingleBoolComparison) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{38}
+}
+
+func (x *SingleBoolComparison) GetValue() bool {
+	if x != nil {
+		return x.Value
+	}
+	return false
+}
+
+func (x *SingleBoolComparison) GetComparator() GenericComparator {
+	if x != nil {
+		return x.Comparator
+	}
+	return GenericComparator_EQ
+}
+
+// Used when a leaf-node `Where` clause compares an int to a list of ints.
+// `ListOperator` specifies whether values in the list are allowed or disallowed.
+type IntListComparison struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	Values       []int64      `protobuf:"varint,1,rep,packed,name=values,proto3" json:"values,omitempty"`
+	ListOperator ListOperator `protobuf:"varint,2,opt,name=list_operator,json=listOperator,proto3,enum=chroma.ListOperator" json:"list_operator,omitempty"`
+}
+
+func (x *IntListComparison) Reset() {
+	*x = IntListComparison{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[39]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *IntListComparison) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*IntListComparison) ProtoMessage() {}
+
+func (x *IntListComparison) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[39]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use IntListComparison.ProtoReflect.Descriptor instead.
+func (*IntListComparison) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{39}
+}
+
+func (x *IntListComparison) GetValues() []int64 {
+	if x != nil {
+		return x.Values
+	}
+	return nil
+}
+
+func (x *IntListComparison) GetListOperator() ListOperator {
+	if x != nil {
+		return x.ListOperator
+	}
+	return ListOperator_IN
+}
+
+// Used when a leaf-node `Where` clause compares an int to a single int.
+type SingleIntComparison struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	Value int64 `protobuf:"varint,1,opt,name=value,proto3" json:"value,omitempty"`
+	// Types that are assignable to Comparator:
+	//
+	//	*SingleIntComparison_GenericComparator
+	//	*SingleIntComparison_NumberComparator
+	Comparator isSingleIntComparison_Comparator `protobuf_oneof:"comparator"`
+}
+
+func (x *SingleIntComparison) Reset() {
+	*x = SingleIntComparison{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[40]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *SingleIntComparison) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*SingleIntComparison) ProtoMessage() {}
+
+func (x *SingleIntComparison) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[40]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use SingleIntComparison.ProtoReflect.Descriptor instead.
+func (*SingleIntComparison) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{40}
+}
+
+func (x *SingleIntComparison) GetValue() int64 {
+	if x != nil {
+		return x.Value
+	}
+	return 0
+}
+
+func (m *SingleIntComparison) GetComparator() isSingleIntComparison_Comparator {
+	if m != nil {
+		return m.Comparator
+	}
+	return nil
+}
+
+func (x *SingleIntComparison) GetGenericComparator() GenericComparator {
+	if x, ok := x.GetComparator().(*SingleIntComparison_GenericComparator); ok {
+		return x.GenericComparator
+	}
+	return GenericComparator_EQ
+}
+
+func (x *SingleIntComparison) GetNumberComparator() NumberComparator {
+	if x, ok := x.GetComparator().(*SingleIntComparison_NumberComparator); ok {
+		return x.NumberComparator
+	}
+	return NumberComparator_GT
+}
+
+type isSingleIntComparison_Comparator interface {
+	isSingleIntComparison_Comparator()
+}
+
+type SingleIntComparison_GenericComparator struct {
+	GenericComparator GenericComparator `protobuf:"varint,2,opt,name=generic_comparator,json=genericComparator,proto3,enum=chroma.GenericComparator,oneof"`
+}
+
+type SingleIntComparison_NumberComparator struct {
+	NumberComparator NumberComparator `protobuf:"varint,3,opt,name=number_comparator,json=numberComparator,proto3,enum=chroma.NumberComparator,oneof"`
+}
+
+func (*SingleIntComparison_GenericComparator) isSingleIntComparison_Comparator() {}
+
+func (*SingleIntComparison_NumberComparator) isSingleIntComparison_Comparator() {}
+
+// Used when a leaf-node `Where` clause compares a timestamp to a single
+// timestamp, given in microseconds since the Unix epoch.
+type SingleTimestampComparison struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	Value int64 `protobuf:"varint,1,opt,name=value,proto3" json:"value,omitempty"`
+	// Types that are assignable to Comparator:
+	//
+	//	*SingleTimestampComparison_GenericComparator
+	//	*SingleTimestampComparison_NumberComparator
+	Comparator isSingleTimestampComparison_Comparator `protobuf_oneof:"comparator"`
+}
+
+func (x *SingleTimestampComparison) Reset() {
+	*x = SingleTimestampComparison{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[41]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *SingleTimestampComparison) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*SingleTimestampComparison) ProtoMessage() {}
+
+func (x *SingleTimestampComparison) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[41]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use SingleTimestampComparison.ProtoReflect.Descriptor instead.
+func (*SingleTimestampComparison) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{41}
+}
+
+func (x *SingleTimestampComparison) GetValue() int64 {
+	if x != nil {
+		return x.Value
+	}
+	return 0
+}
+
+func (m *SingleTimestampComparison) GetComparator() isSingleTimestampComparison_Comparator {
+	if m != nil {
+		return m.Comparator
+	}
+	return nil
+}
+
+func (x *SingleTimestampComparison) GetGenericComparator() GenericComparator {
+	if x, ok := x.GetComparator().(*SingleTimestampComparison_GenericComparator); ok {
+		return x.GenericComparator
+	}
+	return GenericComparator_EQ
+}
+
+func (x *SingleTimestampComparison) GetNumberComparator() NumberComparator {
+	if x, ok := x.GetComparator().(*SingleTimestampComparison_NumberComparator); ok {
+		return x.NumberComparator
+	}
+	return NumberComparator_GT
+}
+
+type isSingleTimestampComparison_Comparator interface {
+	isSingleTimestampComparison_Comparator()
+}
+
+type SingleTimestampComparison_GenericComparator struct {
+	GenericComparator GenericComparator `protobuf:"varint,2,opt,name=generic_comparator,json=genericComparator,proto3,enum=chroma.GenericComparator,oneof"`
+}
+
+type SingleTimestampComparison_NumberComparator struct {
+	NumberComparator NumberComparator `protobuf:"varint,3,opt,name=number_comparator,json=numberComparator,proto3,enum=chroma.NumberComparator,oneof"`
+}
+
+func (*SingleTimestampComparison_GenericComparator) isSingleTimestampComparison_Comparator() {}
+
+func (*SingleTimestampComparison_NumberComparator) isSingleTimestampComparison_Comparator() {}
+
+// Used when a leaf-node `Where` clause compares a float to a list of floats.
+// `ListOperator` specifies whether values in the list are allowed or disallowed.
+type DoubleListComparison struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	Values       []float64    `protobuf:"fixed64,1,rep,packed,name=values,proto3" json:"values,omitempty"`
+	ListOperator ListOperator `protobuf:"varint,2,opt,name=list_operator,json=listOperator,proto3,enum=chroma.ListOperator" json:"list_operator,omitempty"`
+}
+
+func (x *DoubleListComparison) Reset() {
+	*x = DoubleListComparison{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[42]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *DoubleListComparison) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*DoubleListComparison) ProtoMessage() {}
+
+func (x *DoubleListComparison) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[42]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use DoubleListComparison.ProtoReflect.Descriptor instead.
+func (*DoubleListComparison) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{42}
+}
+
+func (x *DoubleListComparison) GetValues() []float64 {
+	if x != nil {
+		return x.Values
+	}
+	return nil
+}
+
+func (x *DoubleListComparison) GetListOperator() ListOperator {
+	if x != nil {
+		return x.ListOperator
+	}
+	return ListOperator_IN
+}
+
+type BoolListComparison struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	Values       []bool       `protobuf:"varint,1,rep,packed,name=values,proto3" json:"values,omitempty"`
+	ListOperator ListOperator `protobuf:"varint,2,opt,name=list_operator,json=listOperator,proto3,enum=chroma.ListOperator" json:"list_operator,omitempty"`
+}
+
+func (x *BoolListComparison) Reset() {
+	*x = BoolListComparison{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[43]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *BoolListComparison) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*BoolListComparison) ProtoMessage() {}
+
+func (x *BoolListComparison) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[43]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use BoolListComparison.ProtoReflect.Descriptor instead.
+func (*BoolListComparison) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{43}
+}
+
+func (x *BoolListComparison) GetValues() []bool {
+	if x != nil {
+		return x.Values
+	}
+	return nil
+}
+
+func (x *BoolListComparison) GetListOperator() ListOperator {
+	if x != nil {
+		return x.ListOperator
+	}
+	return ListOperator_IN
+}
+
+type SingleDoubleComparison struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	Value float64 `protobuf:"fixed64,1,opt,name=value,proto3" json:"value,omitempty"`
+	// Types that are assignable to Comparator:
+	//
+	//	*SingleDoubleComparison_GenericComparator
+	//	*SingleDoubleComparison_NumberComparator
+	Comparator isSingleDoubleComparison_Comparator `protobuf_oneof:"comparator"`
+}
+
+func (x *SingleDoubleComparison) Reset() {
+	*x = SingleDoubleComparison{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[44]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *SingleDoubleComparison) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*SingleDoubleComparison) ProtoMessage() {}
+
+func (x *SingleDoubleComparison) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[44]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use SingleDoubleComparison.ProtoReflect.Descriptor instead.
+func (*SingleDoubleComparison) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{44}
+}
+
+func (x *SingleDoubleComparison) GetValue() float64 {
+	if x != nil {
+		return x.Value
+	}
+	return 0
+}
+
+func (m *SingleDoubleComparison) GetComparator() isSingleDoubleComparison_Comparator {
+	if m != nil {
+		return m.Comparator
+	}
+	return nil
+}
+
+func (x *SingleDoubleComparison) GetGenericComparator() GenericComparator {
+	if x, ok := x.GetComparator().(*SingleDoubleComparison_GenericComparator); ok {
+		return x.GenericComparator
+	}
+	return GenericComparator_EQ
+}
+
+func (x *SingleDoubleComparison) GetNumberComparator() NumberComparator {
+	if x, ok := x.GetComparator().(*SingleDoubleComparison_NumberComparator); ok {
+		return x.NumberComparator
+	}
+	return NumberComparator_GT
+}
+
+type isSingleDoubleComparison_Comparator interface {
+	isSingleDoubleComparison_Comparator()
+}
+
+type SingleDoubleComparison_GenericComparator struct {
+	GenericComparator GenericComparator `protobuf:"varint,2,opt,name=generic_comparator,json=genericComparator,proto3,enum=chroma.GenericComparator,oneof"`
+}
+
+type SingleDoubleComparison_NumberComparator struct {
+	NumberComparator NumberComparator `protobuf:"varint,3,opt,name=number_comparator,json=numberComparator,proto3,enum=chroma.NumberComparator,oneof"`
+}
+
+func (*SingleDoubleComparison_GenericComparator) isSingleDoubleComparison_Comparator() {}
+
+func (*SingleDoubleComparison_NumberComparator) isSingleDoubleComparison_Comparator() {}
+
+// Loads the segments of a collection into the caches of the worker without reading any
+// record, so that the worker can serve the queries of the collection without cold reads.
+type WarmUpCollectionRequest struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	CollectionId   string                 `protobuf:"bytes,1,opt,name=collection_id,json=collectionId,proto3" json:"collection_id,omitempty"`
+	VersionContext *RequestVersionContext `protobuf:"bytes,2,opt,name=version_context,json=versionContext,proto3" json:"version_context,omitempty"`
+}
+
+func (x *WarmUpCollectionRequest) Reset() {
+	*x = WarmUpCollectionRequest{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[45]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *WarmUpCollectionRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*WarmUpCollectionRequest) ProtoMessage() {}
+
+func (x *WarmUpCollectionRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[45]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use WarmUpCollectionRequest.ProtoReflect.Descriptor instead.
+func (*WarmUpCollectionRequest) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{45}
+}
+
+func (x *WarmUpCollectionRequest) GetCollectionId() string {
+	if x != nil {
+		return x.CollectionId
+	}
+	return ""
+}
+
+func (x *WarmUpCollectionRequest) GetVersionContext() *RequestVersionContext {
+	if x != nil {
+		return x.VersionContext
+	}
+	return nil
+}
+
+type WarmUpCollectionResponse struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	// The number of blocks of the record segment that are loaded.
+	NumBlocks uint32 `protobuf:"varint,1,opt,name=num_blocks,json=numBlocks,proto3" json:"num_blocks,omitempty"`
+	// Whether the vector index of the collection is loaded.
+	VectorIndexLoaded bool `protobuf:"varint,2,opt,name=vector_index_loaded,json=vectorIndexLoaded,proto3" json:"vector_index_loaded,omitempty"`
+	// The number of the hottest blocks recorded by the previous owner of the collection that
+	// are loaded.
+	NumHotBlocks uint32 `protobuf:"varint,3,opt,name=num_hot_blocks,json=numHotBlocks,proto3" json:"num_hot_blocks,omitempty"`
+}
+
+func (x *WarmUpCollectionResponse) Reset() {
+	*x = WarmUpCollectionResponse{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[46]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *WarmUpCollectionResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*WarmUpCollectionResponse) ProtoMessage() {}
+
+func (x *WarmUpCollectionResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[46]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use WarmUpCollectionResponse.ProtoReflect.Descriptor instead.
+func (*WarmUpCollectionResponse) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{46}
+}
+
+func (x *WarmUpCollectionResponse) GetNumBlocks() uint32 {
+	if x != nil {
+		return x.NumBlocks
+	}
+	return 0
+}
+
+func (x *WarmUpCollectionResponse) GetVectorIndexLoaded() bool {
+	if x != nil {
+		return x.VectorIndexLoaded
+	}
+	return false
+}
+
+func (x *WarmUpCollectionResponse) GetNumHotBlocks() uint32 {
+	if x != nil {
+		return x.NumHotBlocks
+	}
+	return 0
+}
+
+// Writes the full text index and the metadata indexes of a collection to the storage of the
+// worker as JSON Lines files under the destination prefix: fulltext.jsonl holds one object per
+// token with its postings, and metadata.jsonl holds one object per metadata key and value with
+// its offset ids.
+type ExportCollectionIndexesRequest struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	CollectionId      string                 `protobuf:"bytes,1,opt,name=collection_id,json=collectionId,proto3" json:"collection_id,omitempty"`
+	VersionContext    *RequestVersionContext `protobuf:"bytes,2,opt,name=version_context,json=versionContext,proto3" json:"version_context,omitempty"`
+	DestinationPrefix string                 `protobuf:"bytes,3,opt,name=destination_prefix,json=destinationPrefix,proto3" json:"destination_prefix,omitempty"`
+}
+
+func (x *ExportCollectionIndexesRequest) Reset() {
+	*x = ExportCollectionIndexesRequest{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[47]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *ExportCollectionIndexesRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ExportCollectionIndexesRequest) ProtoMessage() {}
+
+func (x *ExportCollectionIndexesRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[47]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ExportCollectionIndexesRequest.ProtoReflect.Descriptor instead.
+func (*ExportCollectionIndexesRequest) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{47}
+}
+
+func (x *ExportCollectionIndexesRequest) GetCollectionId() string {
+	if x != nil {
+		return x.CollectionId
+	}
+	return ""
+}
+
+func (x *ExportCollectionIndexesRequest) GetVersionContext() *RequestVersionContext {
+	if x != nil {
+		return x.VersionContext
+	}
+	return nil
+}
+
+func (x *ExportCollectionIndexesRequest) GetDestinationPrefix() string {
+	if x != nil {
+		return x.DestinationPrefix
+	}
+	return ""
+}
+
+type ExportCollectionIndexesResponse struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	// The number of tokens of the full text index that are exported.
+	NumTokens uint32 `protobuf:"varint,1,opt,name=num_tokens,json=numTokens,proto3" json:"num_tokens,omitempty"`
+	// The number of metadata posting lists that are exported.
+	NumPostingLists uint32 `protobuf:"varint,2,opt,name=num_posting_lists,json=numPostingLists,proto3" json:"num_posting_lists,omitempty"`
+}
+
+func (x *ExportCollectionIndexesResponse) Reset() {
+	*x = ExportCollectionIndexesResponse{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[48]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *ExportCollectionIndexesResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ExportCollectionIndexesResponse) ProtoMessage() {}
+
+func (x *ExportCollectionIndexesResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[48]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ExportCollectionIndexesResponse.ProtoReflect.Descriptor instead.
+func (*ExportCollectionIndexesResponse) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{48}
+}
+
+func (x *ExportCollectionIndexesResponse) GetNumTokens() uint32 {
+	if x != nil {
+		return x.NumTokens
+	}
+	return 0
+}
+
+func (x *ExportCollectionIndexesResponse) GetNumPostingLists() uint32 {
+	if x != nil {
+		return x.NumPostingLists
+	}
+	return 0
+}
+
+// Runs the consistency checks of the segments of a collection against its record segment. With
+// repair, the metadata segment and the vector segment that fail their checks are rebuilt from
+// the record segment and registered as a new version of the collection. A failed offset id check
+// cannot be repaired, since the record segment is the source of truth.
+type CheckCollectionIntegrityRequest struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	CollectionId   string                 `protobuf:"bytes,1,opt,name=collection_id,json=collectionId,proto3" json:"collection_id,omitempty"`
+	VersionContext *RequestVersionContext `protobuf:"bytes,2,opt,name=version_context,json=versionContext,proto3" json:"version_context,omitempty"`
+	Repair         bool                   `protobuf:"varint,3,opt,name=repair,proto3" json:"repair,omitempty"`
+}
+
+func (x *CheckCollectionIntegrityRequest) Reset() {
+	*x = CheckCollectionIntegrityRequest{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[49]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *CheckCollectionIntegrityRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*CheckCollectionIntegrityRequest) ProtoMessage() {}
+
+func (x *CheckCollectionIntegrityRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[49]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use CheckCollectionIntegrityRequest.ProtoReflect.Descriptor instead.
+func (*CheckCollectionIntegrityRequest) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{49}
+}
+
+func (x *CheckCollectionIntegrityRequest) GetCollectionId() string {
+	if x != nil {
+		return x.CollectionId
+	}
+	return ""
+}
+
+func (x *CheckCollectionIntegrityRequest) GetVersionContext() *RequestVersionContext {
+	if x != nil {
+		return x.VersionContext
+	}
+	return nil
+}
+
+func (x *CheckCollectionIntegrityRequest) GetRepair() bool {
+	if x != nil {
+		return x.Repair
+	}
+	return false
+}
+
+type IntegrityIssue struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	Check       IntegrityCheck `protobuf:"varint,1,opt,name=check,proto3,enum=chroma.IntegrityCheck" json:"check,omitempty"`
+	Description string         `protobuf:"bytes,2,opt,name=description,proto3" json:"description,omitempty"`
+}
+
+func (x *IntegrityIssue) Reset() {
+	*x = IntegrityIssue{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[50]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *IntegrityIssue) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*IntegrityIssue) ProtoMessage() {}
+
+func (x *IntegrityIssue) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[50]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use IntegrityIssue.ProtoReflect.Descriptor instead.
+func (*IntegrityIssue) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{50}
+}
+
+func (x *IntegrityIssue) GetCheck() IntegrityCheck {
+	if x != nil {
+		return x.Check
+	}
+	return IntegrityCheck_OFFSET_ID_CONTINUITY
+}
+
+func (x *IntegrityIssue) GetDescription() string {
+	if x != nil {
+		return x.Description
+	}
+	return ""
+}
+
+type CheckCollectionIntegrityResponse struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	// The number of records in the record segment.
+	NumRecords uint32 `protobuf:"varint,1,opt,name=num_records,json=numRecords,proto3" json:"num_records,omitempty"`
+	// The number of issues found, of which at most the first 100 are described.
+	NumIssues uint32            `protobuf:"varint,2,opt,name=num_issues,json=numIssues,proto3" json:"num_issues,omitempty"`
+	Issues    []*IntegrityIssue `protobuf:"bytes,3,rep,name=issues,proto3" json:"issues,omitempty"`
+	// The ids of the segments that are rebuilt.
+	RebuiltSegmentIds []string `protobuf:"bytes,4,rep,name=rebuilt_segment_ids,json=rebuiltSegmentIds,proto3" json:"rebuilt_segment_ids,omitempty"`
+	// The version of the collection after the rebuilt segments are registered.
+	CollectionVersion *int32 `protobuf:"varint,5,opt,name=collection_version,json=collectionVersion,proto3,oneof" json:"collection_version,omitempty"`
+}
+
+func (x *CheckCollectionIntegrityResponse) Reset() {
+	*x = CheckCollectionIntegrityResponse{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[51]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *CheckCollectionIntegrityResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*CheckCollectionIntegrityResponse) ProtoMessage() {}
+
+func (x *CheckCollectionIntegrityResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[51]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use CheckCollectionIntegrityResponse.ProtoReflect.Descriptor instead.
+func (*CheckCollectionIntegrityResponse) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{51}
+}
+
+func (x *CheckCollectionIntegrityResponse) GetNumRecords() uint32 {
+	if x != nil {
+		return x.NumRecords
+	}
+	return 0
+}
+
+func (x *CheckCollectionIntegrityResponse) GetNumIssues() uint32 {
+	if x != nil {
+		return x.NumIssues
+	}
+	return 0
+}
+
+func (x *CheckCollectionIntegrityResponse) GetIssues() []*IntegrityIssue {
+	if x != nil {
+		return x.Issues
+	}
+	return nil
+}
+
+func (x *CheckCollectionIntegrityResponse) GetRebuiltSegmentIds() []string {
+	if x != nil {
+		return x.RebuiltSegmentIds
+	}
+	return nil
+}
+
+func (x *CheckCollectionIntegrityResponse) GetCollectionVersion() int32 {
+	if x != nil && x.CollectionVersion != nil {
+		return *x.CollectionVersion
+	}
+	return 0
+}
+
+// Recomputes the content checksum of a version of a collection from its records, and compares it
+// with the checksum stored when the version was flushed. The checksum only depends on the records,
+// so two replicas or a restored snapshot with the same records have the same checksum even if
+// their files differ.
+type VerifyCollectionChecksumRequest struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	CollectionId   string                 `protobuf:"bytes,1,opt,name=collection_id,json=collectionId,proto3" json:"collection_id,omitempty"`
+	VersionContext *RequestVersionContext `protobuf:"bytes,2,opt,name=version_context,json=versionContext,proto3" json:"version_context,omitempty"`
+}
+
+func (x *VerifyCollectionChecksumRequest) Reset() {
+	*x = VerifyCollectionChecksumRequest{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[52]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *VerifyCollectionChecksumRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*VerifyCollectionChecksumRequest) ProtoMessage() {}
+
+func (x *VerifyCollectionChecksumRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[52]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use VerifyCollectionChecksumRequest.ProtoReflect.Descriptor instead.
+func (*VerifyCollectionChecksumRequest) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{52}
+}
+
+func (x *VerifyCollectionChecksumRequest) GetCollectionId() string {
+	if x != nil {
+		return x.CollectionId
+	}
+	return ""
+}
+
+func (x *VerifyCollectionChecksumRequest) GetVersionContext() *RequestVersionContext {
+	if x != nil {
+		return x.VersionContext
+	}
+	return nil
+}
+
+type VerifyCollectionChecksumResponse struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	// The checksum stored with the record segment, which is absent for versions flushed before
+	// checksums were introduced.
+	StoredChecksum *string `protobuf:"bytes,1,opt,name=stored_checksum,json=storedChecksum,proto3,oneof" json:"stored_checksum,omitempty"`
+	// The checksum computed from the records of the record segment.
+	ComputedChecksum string `protobuf:"bytes,2,opt,name=computed_checksum,json=computedChecksum,proto3" json:"computed_checksum,omitempty"`
+	// The number of records in the record segment.
+	NumRecords uint64 `protobuf:"varint,3,opt,name=num_records,json=numRecords,proto3" json:"num_records,omitempty"`
+	// Whether the stored checksum is present and equal to the computed checksum.
+	Matches bool `protobuf:"varint,4,opt,name=matches,proto3" json:"matches,omitempty"`
+}
+
+func (x *VerifyCollectionChecksumResponse) Reset() {
+	*x = VerifyCollectionChecksumResponse{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[53]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *VerifyCollectionChecksumResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*VerifyCollectionChecksumResponse) ProtoMessage() {}
+
+func (x *VerifyCollectionChecksumResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[53]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use VerifyCollectionChecksumResponse.ProtoReflect.Descriptor instead.
+func (*VerifyCollectionChecksumResponse) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{53}
+}
+
+func (x *VerifyCollectionChecksumResponse) GetStoredChecksum() string {
+	if x != nil && x.StoredChecksum != nil {
+		return *x.StoredChecksum
+	}
+	return ""
+}
+
+func (x *VerifyCollectionChecksumResponse) GetComputedChecksum() string {
+	if x != nil {
+		return x.ComputedChecksum
+	}
+	return ""
+}
+
+func (x *VerifyCollectionChecksumResponse) GetNumRecords() uint64 {
+	if x != nil {
+		return x.NumRecords
+	}
+	return 0
+}
+
+func (x *VerifyCollectionChecksumResponse) GetMatches() bool {
+	if x != nil {
+		return x.Matches
+	}
+	return false
+}
+
+// Lists the most recent queries of the worker that took longer than its slow query threshold,
+// from the oldest to the newest. They are kept in memory, so they are lost when the worker
+// restarts.
+type ListSlowQueriesRequest struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	// Only lists the slow queries of the collection, if set.
+	CollectionId *string `protobuf:"bytes,1,opt,name=collection_id,json=collectionId,proto3,oneof" json:"collection_id,omitempty"`
+}
+
+func (x *ListSlowQueriesRequest) Reset() {
+	*x = ListSlowQueriesRequest{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[54]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *ListSlowQueriesRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ListSlowQueriesRequest) ProtoMessage() {}
+
+func (x *ListSlowQueriesRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[54]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ListSlowQueriesRequest.ProtoReflect.Descriptor instead.
+func (*ListSlowQueriesRequest) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{54}
+}
+
+func (x *ListSlowQueriesRequest) GetCollectionId() string {
+	if x != nil && x.CollectionId != nil {
+		return *x.CollectionId
+	}
+	return ""
+}
+
+type SlowQuery struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	CollectionId string `protobuf:"bytes,1,opt,name=collection_id,json=collectionId,proto3" json:"collection_id,omitempty"`
+	// Either "get" or "knn".
+	QueryType string `protobuf:"bytes,2,opt,name=query_type,json=queryType,proto3" json:"query_type,omitempty"`
+	// The operators of the filter in depth first order, without its keys and values.
+	FilterOperators []string `protobuf:"bytes,3,rep,name=filter_operators,json=filterOperators,proto3" json:"filter_operators,omitempty"`
+	// The number of ids that the query was restricted to, if any.
+	NumIds        *uint64 `protobuf:"varint,4,opt,name=num_ids,json=numIds,proto3,oneof" json:"num_ids,omitempty"`
+	Limit         *uint32 `protobuf:"varint,5,opt,name=limit,proto3,oneof" json:"limit,omitempty"`
+	Offset        uint32  `protobuf:"varint,6,opt,name=offset,proto3" json:"offset,omitempty"`
+	ElapsedMicros uint64  `protobuf:"varint,7,opt,name=elapsed_micros,json=elapsedMicros,proto3" json:"elapsed_micros,omitempty"`
+	// The time at which the query was logged, in microseconds since the Unix epoch.
+	LoggedAtMicros uint64     `protobuf:"varint,8,opt,name=logged_at_micros,json=loggedAtMicros,proto3" json:"logged_at_micros,omitempty"`
+	Plan           *QueryPlan `protobuf:"bytes,9,opt,name=plan,proto3" json:"plan,omitempty"`
+}
+
+func (x *SlowQuery) Reset() {
+	*x = SlowQuery{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[55]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *SlowQuery) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*SlowQuery) ProtoMessage() {}
+
+func (x *SlowQuery) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[55]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use SlowQuery.ProtoReflect.Descriptor instead.
+func (*SlowQuery) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{55}
+}
+
+func (x *SlowQuery) GetCollectionId() string {
+	if x != nil {
+		return x.CollectionId
+	}
+	return ""
+}
+
+func (x *SlowQuery) GetQueryType() string {
+	if x != nil {
+		return x.QueryType
+	}
+	return ""
+}
+
+func (x *SlowQuery) GetFilterOperators() []string {
+	if x != nil {
+		return x.FilterOperators
+	}
+	return nil
+}
+
+func (x *SlowQuery) GetNumIds() uint64 {
+	if x != nil && x.NumIds != nil {
+		return *x.NumIds
+	}
+	return 0
+}
+
+func (x *SlowQuery) GetLimit() uint32 {
+	if x != nil && x.Limit != nil {
+		return *x.Limit
+	}
+	return 0
+}
+
+func (x *SlowQuery) GetOffset() uint32 {
+	if x != nil {
+		return x.Offset
+	}
+	return 0
+}
+
+func (x *SlowQuery) GetElapsedMicros() uint64 {
+	if x != nil {
+		return x.ElapsedMicros
+	}
+	return 0
+}
+
+func (x *SlowQuery) GetLoggedAtMicros() uint64 {
+	if x != nil {
+		return x.LoggedAtMicros
+	}
+	return 0
+}
+
+func (x *SlowQuery) GetPlan() *QueryPlan {
+	if x != nil {
+		return x.Plan
+	}
+	return nil
+}
+
+type ListSlowQueriesResponse struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	Queries []*SlowQuery `protobuf:"bytes,1,rep,name=queries,proto3" json:"queries,omitempty"`
+}
+
+func (x *ListSlowQueriesResponse) Reset() {
+	*x = ListSlowQueriesResponse{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[56]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *ListSlowQueriesResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ListSlowQueriesResponse) ProtoMessage() {}
+
+func (x *ListSlowQueriesResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[56]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ListSlowQueriesResponse.ProtoReflect.Descriptor instead.
+func (*ListSlowQueriesResponse) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{56}
+}
+
+func (x *ListSlowQueriesResponse) GetQueries() []*SlowQuery {
+	if x != nil {
+		return x.Queries
+	}
+	return nil
+}
+
+// Lists the largest blocks that the block cache of the worker holds in memory, so that the
+// collections that thrash the cache can be told apart.
+type ListCachedBlocksRequest struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	// The number of blocks to list, the largest first.
+	Limit uint32 `protobuf:"varint,1,opt,name=limit,proto3" json:"limit,omitempty"`
+}
+
+func (x *ListCachedBlocksRequest) Reset() {
+	*x = ListCachedBlocksRequest{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[57]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *ListCachedBlocksRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ListCachedBlocksRequest) ProtoMessage() {}
+
+func (x *ListCachedBlocksRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[57]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ListCachedBlocksRequest.ProtoReflect.Descriptor instead.
+func (*ListCachedBlocksRequest) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{57}
+}
+
+func (x *ListCachedBlocksRequest) GetLimit() uint32 {
+	if x != nil {
+		return x.Limit
+	}
+	return 0
+}
+
+type CachedBlock struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	BlockId   string `protobuf:"bytes,1,opt,name=block_id,json=blockId,proto3" json:"block_id,omitempty"`
+	SizeBytes uint64 `protobuf:"varint,2,opt,name=size_bytes,json=sizeBytes,proto3" json:"size_bytes,omitempty"`
+	// The collection that the block is cached on behalf of, if any.
+	CollectionId *string `protobuf:"bytes,3,opt,name=collection_id,json=collectionId,proto3,oneof" json:"collection_id,omitempty"`
+}
+
+func (x *CachedBlock) Reset() {
+	*x = CachedBlock{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[58]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *CachedBlock) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*CachedBlock) ProtoMessage() {}
+
+func (x *CachedBlock) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[58]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use CachedBlock.ProtoReflect.Descriptor instead.
+func (*CachedBlock) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{58}
+}
+
+func (x *CachedBlock) GetBlockId() string {
+	if x != nil {
+		return x.BlockId
+	}
+	return ""
+}
+
+func (x *CachedBlock) GetSizeBytes() uint64 {
+	if x != nil {
+		return x.SizeBytes
+	}
+	return 0
+}
+
+func (x *CachedBlock) GetCollectionId() string {
+	if x != nil && x.CollectionId != nil {
+		return *x.CollectionId
+	}
+	return ""
+}
+
+type ListCachedBlocksResponse struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	Blocks []*CachedBlock `protobuf:"bytes,1,rep,name=blocks,proto3" json:"blocks,omitempty"`
+	// The bytes of all the blocks that the cache holds in memory.
+	UsageBytes    uint64  `protobuf:"varint,2,opt,name=usage_bytes,json=usageBytes,proto3" json:"usage_bytes,omitempty"`
+	CapacityBytes *uint64 `protobuf:"varint,3,opt,name=capacity_bytes,json=capacityBytes,proto3,oneof" json:"capacity_bytes,omitempty"`
+}
+
+func (x *ListCachedBlocksResponse) Reset() {
+	*x = ListCachedBlocksResponse{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[59]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *ListCachedBlocksResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ListCachedBlocksResponse) ProtoMessage() {}
+
+func (x *ListCachedBlocksResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[59]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ListCachedBlocksResponse.ProtoReflect.Descriptor instead.
+func (*ListCachedBlocksResponse) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{59}
+}
+
+func (x *ListCachedBlocksResponse) GetBlocks() []*CachedBlock {
+	if x != nil {
+		return x.Blocks
+	}
+	return nil
+}
+
+func (x *ListCachedBlocksResponse) GetUsageBytes() uint64 {
+	if x != nil {
+		return x.UsageBytes
+	}
+	return 0
+}
+
+func (x *ListCachedBlocksResponse) GetCapacityBytes() uint64 {
+	if x != nil && x.CapacityBytes != nil {
+		return *x.CapacityBytes
+	}
+	return 0
+}
+
+// Pauses the compaction of a collection on the compactor. A running compaction of the collection
+// stops at the next batch of records that it writes and waits until the compaction is resumed,
+// and new compactions of the collection are not started in the meantime. The pause is not
+// persisted, so it ends when the compactor restarts.
+type PauseCompactionRequest struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	CollectionId string `protobuf:"bytes,1,opt,name=collection_id,json=collectionId,proto3" json:"collection_id,omitempty"`
+}
+
+func (x *PauseCompactionRequest) Reset() {
+	*x = PauseCompactionRequest{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[60]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *PauseCompactionRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*PauseCompactionRequest) ProtoMessage() {}
+
+func (x *PauseCompactionRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[60]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use PauseCompactionRequest.ProtoReflect.Descriptor instead.
+func (*PauseCompactionRequest) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{60}
+}
+
+func (x *PauseCompactionRequest) GetCollectionId() string {
+	if x != nil {
+		return x.CollectionId
+	}
+	return ""
+}
+
+type PauseCompactionResponse struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	// Whether the compaction of the collection was already paused.
+	AlreadyPaused bool `protobuf:"varint,1,opt,name=already_paused,json=alreadyPaused,proto3" json:"already_paused,omitempty"`
+}
+
+func (x *PauseCompactionResponse) Reset() {
+	*x = PauseCompactionResponse{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[61]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *PauseCompactionResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*PauseCompactionResponse) ProtoMessage() {}
+
+func (x *PauseCompactionResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[61]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use PauseCompactionResponse.ProtoReflect.Descriptor instead.
+func (*PauseCompactionResponse) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{61}
+}
+
+func (x *PauseCompactionResponse) GetAlreadyPaused() bool {
+	if x != nil {
+		return x.AlreadyPaused
+	}
+	return false
+}
+
+type ResumeCompactionRequest struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	CollectionId string `protobuf:"bytes,1,opt,name=collection_id,json=collectionId,proto3" json:"collection_id,omitempty"`
+}
+
+func (x *ResumeCompactionRequest) Reset() {
+	*x = ResumeCompactionRequest{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[62]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *ResumeCompactionRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ResumeCompactionRequest) ProtoMessage() {}
+
+func (x *ResumeCompactionRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[62]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ResumeCompactionRequest.ProtoReflect.Descriptor instead.
+func (*ResumeCompactionRequest) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{62}
+}
+
+func (x *ResumeCompactionRequest) GetCollectionId() string {
+	if x != nil {
+		return x.CollectionId
+	}
+	return ""
+}
+
+type ResumeCompactionResponse struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	// Whether the compaction of the collection was paused.
+	WasPaused bool `protobuf:"varint,1,opt,name=was_paused,json=wasPaused,proto3" json:"was_paused,omitempty"`
+}
+
+func (x *ResumeCompactionResponse) Reset() {
+	*x = ResumeCompactionResponse{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[63]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *ResumeCompactionResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ResumeCompactionResponse) ProtoMessage() {}
+
+func (x *ResumeCompactionResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[63]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ResumeCompactionResponse.ProtoReflect.Descriptor instead.
+func (*ResumeCompactionResponse) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{63}
+}
+
+func (x *ResumeCompactionResponse) GetWasPaused() bool {
+	if x != nil {
+		return x.WasPaused
+	}
+	return false
+}
+
+type GetVectorsRequest struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	Ids            []string               `protobuf:"bytes,1,rep,name=ids,proto3" json:"ids,omitempty"`
+	SegmentId      string                 `protobuf:"bytes,2,opt,name=segment_id,json=segmentId,proto3" json:"segment_id,omitempty"`
+	CollectionId   string                 `protobuf:"bytes,3,opt,name=collection_id,json=collectionId,proto3" json:"collection_id,omitempty"`
+	VersionContext *RequestVersionContext `protobuf:"bytes,4,opt,name=version_context,json=versionContext,proto3" json:"version_context,omitempty"`
+}
+
+func (x *GetVectorsRequest) Reset() {
+	*x = GetVectorsRequest{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[64]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *GetVectorsRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*GetVectorsRequest) ProtoMessage() {}
+
+func (x *GetVectorsRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[64]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use GetVectorsRequest.ProtoReflect.Descriptor instead.
+func (*GetVectorsRequest) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{64}
+}
+
+func (x *GetVectorsRequest) GetIds() []string {
+	if x != nil {
+		return x.Ids
+	}
+	return nil
+}
+
+func (x *GetVectorsRequest) GetSegmentId() string {
+	if x != nil {
+		return x.SegmentId
+	}
+	return ""
+}
+
+func (x *GetVectorsRequest) GetCollectionId() string {
+	if x != nil {
+		return x.CollectionId
+	}
+	return ""
+}
+
+func (x *GetVectorsRequest) GetVersionContext() *RequestVersionContext {
+	if x != nil {
+		return x.VersionContext
+	}
+	return nil
+}
+
+type GetVectorsResponse struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	Records []*VectorEmbeddingRecord `protobuf:"bytes,1,rep,name=records,proto3" json:"records,omitempty"`
+}
+
+func (x *GetVectorsResponse) Reset() {
+	*x = GetVectorsResponse{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[65]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *GetVectorsResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*GetVectorsResponse) ProtoMessage() {}
+
+func (x *GetVectorsResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[65]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use GetVectorsResponse.ProtoReflect.Descriptor instead.
+func (*GetVectorsResponse) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{65}
+}
+
+func (x *GetVectorsResponse) GetRecords() []*VectorEmbeddingRecord {
+	if x != nil {
+		return x.Records
+	}
+	return nil
+}
+
+type VectorEmbeddingRecord struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	Id     string  `protobuf:"bytes,1,opt,name=id,proto3" json:"id,omitempty"`
+	Vector *Vector `protobuf:"bytes,3,opt,name=vector,proto3" json:"vector,omitempty"` // TODO: we need to rethink source of truth for vector dimensionality and encoding
+}
+
+func (x *VectorEmbeddingRecord) Reset() {
+	*x = VectorEmbeddingRecord{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[66]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *VectorEmbeddingRecord) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*VectorEmbeddingRecord) ProtoMessage() {}
+
+func (x *VectorEmbeddingRecord) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[66]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use VectorEmbeddingRecord.ProtoReflect.Descriptor instead.
+func (*VectorEmbeddingRecord) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{66}
+}
+
+func (x *VectorEmbeddingRecord) GetId() string {
+	if x != nil {
+		return x.Id
+	}
+	return ""
+}
+
+func (x *VectorEmbeddingRecord) GetVector() *Vector {
+	if x != nil {
+		return x.Vector
+	}
+	return nil
+}
+
+type QueryVectorsRequest struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	Vectors           []*Vector              `protobuf:"bytes,1,rep,name=vectors,proto3" json:"vectors,omitempty"`
+	K                 int32                  `protobuf:"varint,2,opt,name=k,proto3" json:"k,omitempty"`
+	AllowedIds        []string               `protobuf:"bytes,3,rep,name=allowed_ids,json=allowedIds,proto3" json:"allowed_ids,omitempty"`
+	IncludeEmbeddings bool                   `protobuf:"varint,4,opt,name=include_embeddings,json=includeEmbeddings,proto3" json:"include_embeddings,omitempty"`
+	SegmentId         string                 `protobuf:"bytes,5,opt,name=segment_id,json=segmentId,proto3" json:"segment_id,omitempty"`
+	CollectionId      string                 `protobuf:"bytes,6,opt,name=collection_id,json=collectionId,proto3" json:"collection_id,omitempty"`
+	VersionContext    *RequestVersionContext `protobuf:"bytes,7,opt,name=version_context,json=versionContext,proto3" json:"version_context,omitempty"`
+	// Returns the plan of the query together with its result.
+	Explain      bool                 `protobuf:"varint,8,opt,name=explain,proto3" json:"explain,omitempty"`
+	Accumulation DistanceAccumulation `protobuf:"varint,9,opt,name=accumulation,proto3,enum=chroma.DistanceAccumulation" json:"accumulation,omitempty"`
+	// Drops the records further than this distance from the query vector.
+	MaxDistance *float32 `protobuf:"fixed32,10,opt,name=max_distance,json=maxDistance,proto3,oneof" json:"max_distance,omitempty"`
+	// Drops the records whose similarity score, 1 - distance, is below this score. Only the
+	// cosine and inner product spaces support it, the query fails with INVALID_ARGUMENT
+	// otherwise.
+	MinScore *float32 `protobuf:"fixed32,11,opt,name=min_score,json=minScore,proto3,oneof" json:"min_score,omitempty"`
+	// Stops the brute-force searches of the logs and of the records after this many
+	// milliseconds, and returns the nearest records among those searched so far.
+	TimeLimitMs *uint32 `protobuf:"varint,12,opt,name=time_limit_ms,json=timeLimitMs,proto3,oneof" json:"time_limit_ms,omitempty"` // TODO: options as in types.py, its currently unused so can add later
+}
+
+func (x *QueryVectorsRequest) Reset() {
+	*x = QueryVectorsRequest{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[67]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *QueryVectorsRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*QueryVectorsRequest) ProtoMessage() {}
+
+func (x *QueryVectorsRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[67]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use QueryVectorsRequest.ProtoReflect.Descriptor instead.
+func (*QueryVectorsRequest) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{67}
+}
+
+func (x *QueryVectorsRequest) GetVectors() []*Vector {
+	if x != nil {
+		return x.Vectors
+	}
+	return nil
+}
+
+func (x *QueryVectorsRequest) GetK() int32 {
+	if x != nil {
+		return x.K
+	}
+	return 0
+}
+
+func (x *QueryVectorsRequest) GetAllowedIds() []string {
+	if x != nil {
+		return x.AllowedIds
+	}
+	return nil
+}
+
+func (x *QueryVectorsRequest) GetIncludeEmbeddings() bool {
+	if x != nil {
+		return x.IncludeEmbeddings
+	}
+	return false
+}
+
+func (x *QueryVectorsRequest) GetSegmentId() string {
+	if x != nil {
+		return x.SegmentId
+	}
+	return ""
+}
+
+func (x *QueryVectorsRequest) GetCollectionId() string {
+	if x != nil {
+		return x.CollectionId
+	}
+	return ""
+}
+
+func (x *QueryVectorsRequest) GetVersionContext() *RequestVersionContext {
+	if x != nil {
+		return x.VersionContext
+	}
+	return nil
+}
+
+func (x *QueryVectorsRequest) GetExplain() bool {
+	if x != nil {
+		return x.Explain
+	}
+	return false
+}
+
+func (x *QueryVectorsRequest) GetAccumulation() DistanceAccumulation {
+	if x != nil {
+		return x.Accumulation
+	}
+	return DistanceAccumulation_ACCUMULATE_F32
+}
+
+func (x *QueryVectorsRequest) GetMaxDistance() float32 {
+	if x != nil && x.MaxDistance != nil {
+		return *x.MaxDistance
+	}
+	return 0
+}
+
+func (x *QueryVectorsRequest) GetMinScore() float32 {
+	if x != nil && x.MinScore != nil {
+		return *x.MinScore
+	}
+	return 0
+}
+
+func (x *QueryVectorsRequest) GetTimeLimitMs() uint32 {
+	if x != nil && x.TimeLimitMs != nil {
+		return *x.TimeLimitMs
+	}
+	return 0
+}
+
+type QueryVectorsResponse struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	Results []*VectorQueryResults `protobuf:"bytes,1,rep,name=results,proto3" json:"results,omitempty"`
+	// Set if the vector index could not be loaded, so that the query fell back to a
+	// brute-force search over the records.
+	Degraded bool       `protobuf:"varint,2,opt,name=degraded,proto3" json:"degraded,omitempty"`
+	Plan     *QueryPlan `protobuf:"bytes,3,opt,name=plan,proto3,oneof" json:"plan,omitempty"`
+	// Set if the time limit was reached, so that the results are the nearest among the
+	// records searched before it.
+	Truncated bool `protobuf:"varint,4,opt,name=truncated,proto3" json:"truncated,omitempty"`
+}
+
+func (x *QueryVectorsResponse) Reset() {
+	*x = QueryVectorsResponse{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[68]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *QueryVectorsResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*QueryVectorsResponse) ProtoMessage() {}
+
+func (x *QueryVectorsResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[68]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use QueryVectorsResponse.ProtoReflect.Descriptor instead.
+func (*QueryVectorsResponse) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{68}
+}
+
+func (x *QueryVectorsResponse) GetResults() []*VectorQueryResults {
+	if x != nil {
+		return x.Results
+	}
+	return nil
+}
+
+func (x *QueryVectorsResponse) GetDegraded() bool {
+	if x != nil {
+		return x.Degraded
+	}
+	return false
+}
+
+func (x *QueryVectorsResponse) GetPlan() *QueryPlan {
+	if x != nil {
+		return x.Plan
+	}
+	return nil
+}
+
+func (x *QueryVectorsResponse) GetTruncated() bool {
+	if x != nil {
+		return x.Truncated
+	}
+	return false
+}
+
+type VectorQueryResults struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	Results []*VectorQueryResult `protobuf:"bytes,1,rep,name=results,proto3" json:"results,omitempty"`
+}
+
+func (x *VectorQueryResults) Reset() {
+	*x = VectorQueryResults{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[69]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *VectorQueryResults) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*VectorQueryResults) ProtoMessage() {}
+
+func (x *VectorQueryResults) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[69]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use VectorQueryResults.ProtoReflect.Descriptor instead.
+func (*VectorQueryResults) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{69}
+}
+
+func (x *VectorQueryResults) GetResults() []*VectorQueryResult {
+	if x != nil {
+		return x.Results
+	}
+	return nil
+}
+
+type VectorQueryResult struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	Id       string  `protobuf:"bytes,1,opt,name=id,proto3" json:"id,omitempty"`
+	Distance float32 `protobuf:"fixed32,3,opt,name=distance,proto3" json:"distance,omitempty"`
+	Vector   *Vector `protobuf:"bytes,4,opt,name=vector,proto3,oneof" json:"vector,omitempty"`
+}
+
+func (x *VectorQueryResult) Reset() {
+	*x = VectorQueryResult{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[70]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *VectorQueryResult) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*VectorQueryResult) ProtoMessage() {}
+
+func (x *VectorQueryResult) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[70]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use VectorQueryResult.ProtoReflect.Descriptor instead.
+func (*VectorQueryResult) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{70}
+}
+
+func (x *VectorQueryResult) GetId() string {
+	if x != nil {
+		return x.Id
+	}
+	return ""
+}
+
+func (x *VectorQueryResult) GetDistance() float32 {
+	if x != nil {
+		return x.Distance
+	}
+	return 0
+}
+
+func (x *VectorQueryResult) GetVector() *Vector {
+	if x != nil {
+		return x.Vector
+	}
+	return nil
+}
+
+// Offset ids of records in the portable format of roaring bitmaps, tagged with whether
+// the ids are included or excluded. See SignedRoaringBitmap in the rust types.
+type SignedRoaringBitmap struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	// Types that are assignable to Bitmap:
+	//
+	//	*SignedRoaringBitmap_Include
+	//	*SignedRoaringBitmap_Exclude
+	Bitmap isSignedRoaringBitmap_Bitmap `protobuf_oneof:"bitmap"`
+}
+
+func (x *SignedRoaringBitmap) Reset() {
+	*x = SignedRoaringBitmap{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[71]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *SignedRoaringBitmap) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*SignedRoaringBitmap) ProtoMessage() {}
+
+func (x *SignedRoaringBitmap) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[71]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use SignedRoaringBitmap.ProtoReflect.Descriptor instead.
+func (*SignedRoaringBitmap) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{71}
+}
+
+func (m *SignedRoaringBitmap) GetBitmap() isSignedRoaringBitmap_Bitmap {
+	if m != nil {
+		return m.Bitmap
+	}
+	return nil
+}
+
+func (x *SignedRoaringBitmap) GetInclude() []byte {
+	if x, ok := x.GetBitmap().(*SignedRoaringBitmap_Include); ok {
+		return x.Include
+	}
+	return nil
+}
+
+func (x *SignedRoaringBitmap) GetExclude() []byte {
+	if x, ok := x.GetBitmap().(*SignedRoaringBitmap_Exclude); ok {
+		return x.Exclude
+	}
+	return nil
+}
+
+type isSignedRoaringBitmap_Bitmap interface {
+	isSignedRoaringBitmap_Bitmap()
+}
+
+type SignedRoaringBitmap_Include struct {
+	Include []byte `protobuf:"bytes,1,opt,name=include,proto3,oneof"`
+}
+
+type SignedRoaringBitmap_Exclude struct {
+	Exclude []byte `protobuf:"bytes,2,opt,name=exclude,proto3,oneof"`
+}
+
+func (*SignedRoaringBitmap_Include) isSignedRoaringBitmap_Bitmap() {}
+
+func (*SignedRoaringBitmap_Exclude) isSignedRoaringBitmap_Bitmap() {}
+
+// The intermediate outputs of the operators of a get plan, so that the stages
+// of the plan can run on different workers.
+type FilterOutput struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	LogOffsetIds     *SignedRoaringBitmap `protobuf:"bytes,1,opt,name=log_offset_ids,json=logOffsetIds,proto3" json:"log_offset_ids,omitempty"`
+	CompactOffsetIds *SignedRoaringBitmap `protobuf:"bytes,2,opt,name=compact_offset_ids,json=compactOffsetIds,proto3" json:"compact_offset_ids,omitempty"`
+}
+
+func (x *FilterOutput) Reset() {
+	*x = FilterOutput{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[72]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *FilterOutput) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*FilterOutput) ProtoMessage() {}
+
+func (x *FilterOutput) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[72]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use FilterOutput.ProtoReflect.Descriptor instead.
+func (*FilterOutput) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{72}
+}
+
+func (x *FilterOutput) GetLogOffsetIds() *SignedRoaringBitmap {
+	if x != nil {
+		return x.LogOffsetIds
+	}
+	return nil
+}
+
+func (x *FilterOutput) GetCompactOffsetIds() *SignedRoaringBitmap {
+	if x != nil {
+		return x.CompactOffsetIds
+	}
+	return nil
+}
+
+type LimitOutput struct {
+	state         protoimpl.MessageState
+	sizeCache     protoimpl.SizeCache
+	unknownFields protoimpl.UnknownFields
+
+	OffsetIds []byte `protobuf:"bytes,1,opt,name=offset_ids,json=offsetIds,proto3" json:"offset_ids,omitempty"`
+}
+
+func (x *LimitOutput) Reset() {
+	*x = LimitOutput{}
+	if protoimpl.UnsafeEnabled {
+		mi := &file_chromadb_proto_chroma_proto_msgTypes[73]
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		ms.StoreMessageInfo(mi)
+	}
+}
+
+func (x *LimitOutput) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*LimitOutput) ProtoMessage() {}
+
+func (x *LimitOutput) ProtoReflect() protoreflect.Message {
+	mi := &file_chromadb_proto_chroma_proto_msgTypes[73]
+	if protoimpl.UnsafeEnabled && x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use LimitOutput.ProtoReflect.Descriptor instead.
+func (*LimitOutput) Descriptor() ([]byte, []int) {
+	return file_chromadb_proto_chroma_proto_rawDescGZIP(), []int{73}
+}
+
+func (x *LimitOutput) GetOffsetIds() []byte {
+	if x != nil {
+		return x.OffsetIds
+	}
+	return nil
+}
+
+var File_chromadb_proto_chroma_proto protoreflect.FileDescriptor
+
+var file_chromadb_proto_chroma_proto_rawDesc = []byte{
+	0x0a, 0x1b, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x64, 0x62, 0x2f, 0x70, 0x72, 0x6f, 0x74, 0x6f,
+	0x2f, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x70, 0x72, 0x6f, 0x74, 0x6f, 0x12, 0x06, 0x63,
+	0x68, 0x72, 0x6f, 0x6d, 0x61, 0x22, 0x72, 0x0a, 0x06, 0x56, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x12,
+	0x1c, 0x0a, 0x09, 0x64, 0x69, 0x6d, 0x65, 0x6e, 0x73, 0x69, 0x6f, 0x6e, 0x18, 0x01, 0x20, 0x01,
+	0x28, 0x05, 0x52, 0x09, 0x64, 0x69, 0x6d, 0x65, 0x6e, 0x73, 0x69, 0x6f, 0x6e, 0x12, 0x16, 0x0a,
+	0x06, 0x76, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0c, 0x52, 0x06, 0x76,
+	0x65, 0x63, 0x74, 0x6f, 0x72, 0x12, 0x32, 0x0a, 0x08, 0x65, 0x6e, 0x63, 0x6f, 0x64, 0x69, 0x6e,
+	0x67, 0x18, 0x03, 0x20, 0x01, 0x28, 0x0e, 0x32, 0x16, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61,
+	0x2e, 0x53, 0x63, 0x61, 0x6c, 0x61, 0x72, 0x45, 0x6e, 0x63, 0x6f, 0x64, 0x69, 0x6e, 0x67, 0x52,
+	0x08, 0x65, 0x6e, 0x63, 0x6f, 0x64, 0x69, 0x6e, 0x67, 0x22, 0x21, 0x0a, 0x09, 0x46, 0x69, 0x6c,
+	0x65, 0x50, 0x61, 0x74, 0x68, 0x73, 0x12, 0x14, 0x0a, 0x05, 0x70, 0x61, 0x74, 0x68, 0x73, 0x18,
+	0x01, 0x20, 0x03, 0x28, 0x09, 0x52, 0x05, 0x70, 0x61, 0x74, 0x68, 0x73, 0x22, 0xcf, 0x02, 0x0a,
+	0x07, 0x53, 0x65, 0x67, 0x6d, 0x65, 0x6e, 0x74, 0x12, 0x0e, 0x0a, 0x02, 0x69, 0x64, 0x18, 0x01,
+	0x20, 0x01, 0x28, 0x09, 0x52, 0x02, 0x69, 0x64, 0x12, 0x12, 0x0a, 0x04, 0x74, 0x79, 0x70, 0x65,
+	0x18, 0x02, 0x20, 0x01, 0x28, 0x09, 0x52, 0x04, 0x74, 0x79, 0x70, 0x65, 0x12, 0x2a, 0x0a, 0x05,
+	0x73, 0x63, 0x6f, 0x70, 0x65, 0x18, 0x03, 0x20, 0x01, 0x28, 0x0e, 0x32, 0x14, 0x2e, 0x63, 0x68,
+	0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x53, 0x65, 0x67, 0x6d, 0x65, 0x6e, 0x74, 0x53, 0x63, 0x6f, 0x70,
+	0x65, 0x52, 0x05, 0x73, 0x63, 0x6f, 0x70, 0x65, 0x12, 0x1e, 0x0a, 0x0a, 0x63, 0x6f, 0x6c, 0x6c,
+	0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x18, 0x05, 0x20, 0x01, 0x28, 0x09, 0x52, 0x0a, 0x63, 0x6f,
+	0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x12, 0x37, 0x0a, 0x08, 0x6d, 0x65, 0x74, 0x61,
+	0x64, 0x61, 0x74, 0x61, 0x18, 0x06, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x16, 0x2e, 0x63, 0x68, 0x72,
+	0x6f, 0x6d, 0x61, 0x2e, 0x55, 0x70, 0x64, 0x61, 0x74, 0x65, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61,
+	0x74, 0x61, 0x48, 0x00, 0x52, 0x08, 0x6d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x88, 0x01,
+	0x01, 0x12, 0x3d, 0x0a, 0x0a, 0x66, 0x69, 0x6c, 0x65, 0x5f, 0x70, 0x61, 0x74, 0x68, 0x73, 0x18,
+	0x07, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x1e, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x53,
+	0x65, 0x67, 0x6d, 0x65, 0x6e, 0x74, 0x2e, 0x46, 0x69, 0x6c, 0x65, 0x50, 0x61, 0x74, 0x68, 0x73,
+	0x45, 0x6e, 0x74, 0x72, 0x79, 0x52, 0x09, 0x66, 0x69, 0x6c, 0x65, 0x50, 0x61, 0x74, 0x68, 0x73,
+	0x1a, 0x4f, 0x0a, 0x0e, 0x46, 0x69, 0x6c, 0x65, 0x50, 0x61, 0x74, 0x68, 0x73, 0x45, 0x6e, 0x74,
+	0x72, 0x79, 0x12, 0x10, 0x0a, 0x03, 0x6b, 0x65, 0x79, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x52,
+	0x03, 0x6b, 0x65, 0x79, 0x12, 0x27, 0x0a, 0x05, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x18, 0x02, 0x20,
+	0x01, 0x28, 0x0b, 0x32, 0x11, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x46, 0x69, 0x6c,
+	0x65, 0x50, 0x61, 0x74, 0x68, 0x73, 0x52, 0x05, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x3a, 0x02, 0x38,
+	0x01, 0x42, 0x0b, 0x0a, 0x09, 0x5f, 0x6d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x22, 0xce,
+	0x02, 0x0a, 0x0a, 0x43, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x12, 0x0e, 0x0a,
+	0x02, 0x69, 0x64, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x52, 0x02, 0x69, 0x64, 0x12, 0x12, 0x0a,
+	0x04, 0x6e, 0x61, 0x6d, 0x65, 0x18, 0x02, 0x20, 0x01, 0x28, 0x09, 0x52, 0x04, 0x6e, 0x61, 0x6d,
+	0x65, 0x12, 0x34, 0x0a, 0x16, 0x63, 0x6f, 0x6e, 0x66, 0x69, 0x67, 0x75, 0x72, 0x61, 0x74, 0x69,
+	0x6f, 0x6e, 0x5f, 0x6a, 0x73, 0x6f, 0x6e, 0x5f, 0x73, 0x74, 0x72, 0x18, 0x03, 0x20, 0x01, 0x28,
+	0x09, 0x52, 0x14, 0x63, 0x6f, 0x6e, 0x66, 0x69, 0x67, 0x75, 0x72, 0x61, 0x74, 0x69, 0x6f, 0x6e,
+	0x4a, 0x73, 0x6f, 0x6e, 0x53, 0x74, 0x72, 0x12, 0x37, 0x0a, 0x08, 0x6d, 0x65, 0x74, 0x61, 0x64,
+	0x61, 0x74, 0x61, 0x18, 0x04, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x16, 0x2e, 0x63, 0x68, 0x72, 0x6f,
+	0x6d, 0x61, 0x2e, 0x55, 0x70, 0x64, 0x61, 0x74, 0x65, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74,
+	0x61, 0x48, 0x00, 0x52, 0x08, 0x6d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x88, 0x01, 0x01,
+	0x12, 0x21, 0x0a, 0x09, 0x64, 0x69, 0x6d, 0x65, 0x6e, 0x73, 0x69, 0x6f, 0x6e, 0x18, 0x05, 0x20,
+	0x01, 0x28, 0x05, 0x48, 0x01, 0x52, 0x09, 0x64, 0x69, 0x6d, 0x65, 0x6e, 0x73, 0x69, 0x6f, 0x6e,
+	0x88, 0x01, 0x01, 0x12, 0x16, 0x0a, 0x06, 0x74, 0x65, 0x6e, 0x61, 0x6e, 0x74, 0x18, 0x06, 0x20,
+	0x01, 0x28, 0x09, 0x52, 0x06, 0x74, 0x65, 0x6e, 0x61, 0x6e, 0x74, 0x12, 0x1a, 0x0a, 0x08, 0x64,
+	0x61, 0x74, 0x61, 0x62, 0x61, 0x73, 0x65, 0x18, 0x07, 0x20, 0x01, 0x28, 0x09, 0x52, 0x08, 0x64,
+	0x61, 0x74, 0x61, 0x62, 0x61, 0x73, 0x65, 0x12, 0x21, 0x0a, 0x0c, 0x6c, 0x6f, 0x67, 0x5f, 0x70,
+	0x6f, 0x73, 0x69, 0x74, 0x69, 0x6f, 0x6e, 0x18, 0x08, 0x20, 0x01, 0x28, 0x03, 0x52, 0x0b, 0x6c,
+	0x6f, 0x67, 0x50, 0x6f, 0x73, 0x69, 0x74, 0x69, 0x6f, 0x6e, 0x12, 0x18, 0x0a, 0x07, 0x76, 0x65,
+	0x72, 0x73, 0x69, 0x6f, 0x6e, 0x18, 0x09, 0x20, 0x01, 0x28, 0x05, 0x52, 0x07, 0x76, 0x65, 0x72,
+	0x73, 0x69, 0x6f, 0x6e, 0x42, 0x0b, 0x0a, 0x09, 0x5f, 0x6d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74,
+	0x61, 0x42, 0x0c, 0x0a, 0x0a, 0x5f, 0x64, 0x69, 0x6d, 0x65, 0x6e, 0x73, 0x69, 0x6f, 0x6e, 0x22,
+	0x46, 0x0a, 0x08, 0x44, 0x61, 0x74, 0x61, 0x62, 0x61, 0x73, 0x65, 0x12, 0x0e, 0x0a, 0x02, 0x69,
+	0x64, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x52, 0x02, 0x69, 0x64, 0x12, 0x12, 0x0a, 0x04, 0x6e,
+	0x61, 0x6d, 0x65, 0x18, 0x02, 0x20, 0x01, 0x28, 0x09, 0x52, 0x04, 0x6e, 0x61, 0x6d, 0x65, 0x12,
+	0x16, 0x0a, 0x06, 0x74, 0x65, 0x6e, 0x61, 0x6e, 0x74, 0x18, 0x03, 0x20, 0x01, 0x28, 0x09, 0x52,
+	0x06, 0x74, 0x65, 0x6e, 0x61, 0x6e, 0x74, 0x22, 0x4e, 0x0a, 0x06, 0x54, 0x65, 0x6e, 0x61, 0x6e,
+	0x74, 0x12, 0x12, 0x0a, 0x04, 0x6e, 0x61, 0x6d, 0x65, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x52,
+	0x04, 0x6e, 0x61, 0x6d, 0x65, 0x12, 0x21, 0x0a, 0x0a, 0x6b, 0x6d, 0x73, 0x5f, 0x6b, 0x65, 0x79,
+	0x5f, 0x69, 0x64, 0x18, 0x02, 0x20, 0x01, 0x28, 0x09, 0x48, 0x00, 0x52, 0x08, 0x6b, 0x6d, 0x73,
+	0x4b, 0x65, 0x79, 0x49, 0x64, 0x88, 0x01, 0x01, 0x42, 0x0d, 0x0a, 0x0b, 0x5f, 0x6b, 0x6d, 0x73,
+	0x5f, 0x6b, 0x65, 0x79, 0x5f, 0x69, 0x64, 0x22, 0xc5, 0x02, 0x0a, 0x13, 0x55, 0x70, 0x64, 0x61,
+	0x74, 0x65, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x56, 0x61, 0x6c, 0x75, 0x65, 0x12,
+	0x23, 0x0a, 0x0c, 0x73, 0x74, 0x72, 0x69, 0x6e, 0x67, 0x5f, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x18,
+	0x01, 0x20, 0x01, 0x28, 0x09, 0x48, 0x00, 0x52, 0x0b, 0x73, 0x74, 0x72, 0x69, 0x6e, 0x67, 0x56,
+	0x61, 0x6c, 0x75, 0x65, 0x12, 0x1d, 0x0a, 0x09, 0x69, 0x6e, 0x74, 0x5f, 0x76, 0x61, 0x6c, 0x75,
+	0x65, 0x18, 0x02, 0x20, 0x01, 0x28, 0x03, 0x48, 0x00, 0x52, 0x08, 0x69, 0x6e, 0x74, 0x56, 0x61,
+	0x6c, 0x75, 0x65, 0x12, 0x21, 0x0a, 0x0b, 0x66, 0x6c, 0x6f, 0x61, 0x74, 0x5f, 0x76, 0x61, 0x6c,
+	0x75, 0x65, 0x18, 0x03, 0x20, 0x01, 0x28, 0x01, 0x48, 0x00, 0x52, 0x0a, 0x66, 0x6c, 0x6f, 0x61,
+	0x74, 0x56, 0x61, 0x6c, 0x75, 0x65, 0x12, 0x1f, 0x0a, 0x0a, 0x62, 0x6f, 0x6f, 0x6c, 0x5f, 0x76,
+	0x61, 0x6c, 0x75, 0x65, 0x18, 0x04, 0x20, 0x01, 0x28, 0x08, 0x48, 0x00, 0x52, 0x09, 0x62, 0x6f,
+	0x6f, 0x6c, 0x56, 0x61, 0x6c, 0x75, 0x65, 0x12, 0x35, 0x0a, 0x0a, 0x6c, 0x69, 0x73, 0x74, 0x5f,
+	0x76, 0x61, 0x6c, 0x75, 0x65, 0x18, 0x05, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x14, 0x2e, 0x63, 0x68,
+	0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x4c, 0x69, 0x73,
+	0x74, 0x48, 0x00, 0x52, 0x09, 0x6c, 0x69, 0x73, 0x74, 0x56, 0x61, 0x6c, 0x75, 0x65, 0x12, 0x29,
+	0x0a, 0x0f, 0x74, 0x69, 0x6d, 0x65, 0x73, 0x74, 0x61, 0x6d, 0x70, 0x5f, 0x76, 0x61, 0x6c, 0x75,
+	0x65, 0x18, 0x06, 0x20, 0x01, 0x28, 0x03, 0x48, 0x00, 0x52, 0x0e, 0x74, 0x69, 0x6d, 0x65, 0x73,
+	0x74, 0x61, 0x6d, 0x70, 0x56, 0x61, 0x6c, 0x75, 0x65, 0x12, 0x3b, 0x0a, 0x0c, 0x6f, 0x62, 0x6a,
+	0x65, 0x63, 0x74, 0x5f, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x18, 0x07, 0x20, 0x01, 0x28, 0x0b, 0x32,
+	0x16, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74,
+	0x61, 0x4f, 0x62, 0x6a, 0x65, 0x63, 0x74, 0x48, 0x00, 0x52, 0x0b, 0x6f, 0x62, 0x6a, 0x65, 0x63,
+	0x74, 0x56, 0x61, 0x6c, 0x75, 0x65, 0x42, 0x07, 0x0a, 0x05, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x22,
+	0x43, 0x0a, 0x0c, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x4c, 0x69, 0x73, 0x74, 0x12,
+	0x33, 0x0a, 0x06, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x73, 0x18, 0x01, 0x20, 0x03, 0x28, 0x0b, 0x32,
+	0x1b, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x55, 0x70, 0x64, 0x61, 0x74, 0x65, 0x4d,
+	0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x56, 0x61, 0x6c, 0x75, 0x65, 0x52, 0x06, 0x76, 0x61,
+	0x6c, 0x75, 0x65, 0x73, 0x22, 0xa4, 0x01, 0x0a, 0x0e, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74,
+	0x61, 0x4f, 0x62, 0x6a, 0x65, 0x63, 0x74, 0x12, 0x3a, 0x0a, 0x06, 0x76, 0x61, 0x6c, 0x75, 0x65,
+	0x73, 0x18, 0x01, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x22, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61,
+	0x2e, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x4f, 0x62, 0x6a, 0x65, 0x63, 0x74, 0x2e,
+	0x56, 0x61, 0x6c, 0x75, 0x65, 0x73, 0x45, 0x6e, 0x74, 0x72, 0x79, 0x52, 0x06, 0x76, 0x61, 0x6c,
+	0x75, 0x65, 0x73, 0x1a, 0x56, 0x0a, 0x0b, 0x56, 0x61, 0x6c, 0x75, 0x65, 0x73, 0x45, 0x6e, 0x74,
+	0x72, 0x79, 0x12, 0x10, 0x0a, 0x03, 0x6b, 0x65, 0x79, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x52,
+	0x03, 0x6b, 0x65, 0x79, 0x12, 0x31, 0x0a, 0x05, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x18, 0x02, 0x20,
+	0x01, 0x28, 0x0b, 0x32, 0x1b, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x55, 0x70, 0x64,
+	0x61, 0x74, 0x65, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x56, 0x61, 0x6c, 0x75, 0x65,
+	0x52, 0x05, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x3a, 0x02, 0x38, 0x01, 0x22, 0xac, 0x01, 0x0a, 0x0e,
+	0x55, 0x70, 0x64, 0x61, 0x74, 0x65, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x12, 0x40,
+	0x0a, 0x08, 0x6d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x18, 0x01, 0x20, 0x03, 0x28, 0x0b,
+	0x32, 0x24, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x55, 0x70, 0x64, 0x61, 0x74, 0x65,
+	0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x2e, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74,
+	0x61, 0x45, 0x6e, 0x74, 0x72, 0x79, 0x52, 0x08, 0x6d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61,
+	0x1a, 0x58, 0x0a, 0x0d, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x45, 0x6e, 0x74, 0x72,
+	0x79, 0x12, 0x10, 0x0a, 0x03, 0x6b, 0x65, 0x79, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x52, 0x03,
+	0x6b, 0x65, 0x79, 0x12, 0x31, 0x0a, 0x05, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x18, 0x02, 0x20, 0x01,
+	0x28, 0x0b, 0x32, 0x1b, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x55, 0x70, 0x64, 0x61,
+	0x74, 0x65, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x56, 0x61, 0x6c, 0x75, 0x65, 0x52,
+	0x05, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x3a, 0x02, 0x38, 0x01, 0x22, 0x53, 0x0a, 0x0f, 0x56, 0x65,
+	0x63, 0x74, 0x6f, 0x72, 0x52, 0x65, 0x66, 0x65, 0x72, 0x65, 0x6e, 0x63, 0x65, 0x12, 0x10, 0x0a,
+	0x03, 0x75, 0x72, 0x69, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x52, 0x03, 0x75, 0x72, 0x69, 0x12,
+	0x10, 0x0a, 0x03, 0x72, 0x6f, 0x77, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0d, 0x52, 0x03, 0x72, 0x6f,
+	0x77, 0x12, 0x1c, 0x0a, 0x09, 0x64, 0x69, 0x6d, 0x65, 0x6e, 0x73, 0x69, 0x6f, 0x6e, 0x18, 0x03,
+	0x20, 0x01, 0x28, 0x05, 0x52, 0x09, 0x64, 0x69, 0x6d, 0x65, 0x6e, 0x73, 0x69, 0x6f, 0x6e, 0x22,
+	0xae, 0x02, 0x0a, 0x0f, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x69, 0x6f, 0x6e, 0x52, 0x65, 0x63,
+	0x6f, 0x72, 0x64, 0x12, 0x0e, 0x0a, 0x02, 0x69, 0x64, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x52,
+	0x02, 0x69, 0x64, 0x12, 0x2b, 0x0a, 0x06, 0x76, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x18, 0x02, 0x20,
+	0x01, 0x28, 0x0b, 0x32, 0x0e, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x56, 0x65, 0x63,
+	0x74, 0x6f, 0x72, 0x48, 0x00, 0x52, 0x06, 0x76, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x88, 0x01, 0x01,
+	0x12, 0x37, 0x0a, 0x08, 0x6d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x18, 0x03, 0x20, 0x01,
+	0x28, 0x0b, 0x32, 0x16, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x55, 0x70, 0x64, 0x61,
+	0x74, 0x65, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x48, 0x01, 0x52, 0x08, 0x6d, 0x65,
+	0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x88, 0x01, 0x01, 0x12, 0x2f, 0x0a, 0x09, 0x6f, 0x70, 0x65,
+	0x72, 0x61, 0x74, 0x69, 0x6f, 0x6e, 0x18, 0x04, 0x20, 0x01, 0x28, 0x0e, 0x32, 0x11, 0x2e, 0x63,
+	0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x69, 0x6f, 0x6e, 0x52,
+	0x09, 0x6f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x69, 0x6f, 0x6e, 0x12, 0x47, 0x0a, 0x10, 0x76, 0x65,
+	0x63, 0x74, 0x6f, 0x72, 0x5f, 0x72, 0x65, 0x66, 0x65, 0x72, 0x65, 0x6e, 0x63, 0x65, 0x18, 0x05,
+	0x20, 0x01, 0x28, 0x0b, 0x32, 0x17, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x56, 0x65,
+	0x63, 0x74, 0x6f, 0x72, 0x52, 0x65, 0x66, 0x65, 0x72, 0x65, 0x6e, 0x63, 0x65, 0x48, 0x02, 0x52,
+	0x0f, 0x76, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x52, 0x65, 0x66, 0x65, 0x72, 0x65, 0x6e, 0x63, 0x65,
+	0x88, 0x01, 0x01, 0x42, 0x09, 0x0a, 0x07, 0x5f, 0x76, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x42, 0x0b,
+	0x0a, 0x09, 0x5f, 0x6d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x42, 0x13, 0x0a, 0x11, 0x5f,
+	0x76, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x5f, 0x72, 0x65, 0x66, 0x65, 0x72, 0x65, 0x6e, 0x63, 0x65,
+	0x22, 0xaa, 0x01, 0x0a, 0x15, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x56, 0x65, 0x72, 0x73,
+	0x69, 0x6f, 0x6e, 0x43, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x12, 0x2d, 0x0a, 0x12, 0x63, 0x6f,
+	0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x5f, 0x76, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e,
+	0x18, 0x01, 0x20, 0x01, 0x28, 0x0d, 0x52, 0x11, 0x63, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69,
+	0x6f, 0x6e, 0x56, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x12, 0x21, 0x0a, 0x0c, 0x6c, 0x6f, 0x67,
+	0x5f, 0x70, 0x6f, 0x73, 0x69, 0x74, 0x69, 0x6f, 0x6e, 0x18, 0x02, 0x20, 0x01, 0x28, 0x04, 0x52,
+	0x0b, 0x6c, 0x6f, 0x67, 0x50, 0x6f, 0x73, 0x69, 0x74, 0x69, 0x6f, 0x6e, 0x12, 0x2b, 0x0a, 0x0f,
+	0x6d, 0x61, 0x78, 0x5f, 0x6c, 0x6f, 0x67, 0x5f, 0x62, 0x61, 0x63, 0x6b, 0x6c, 0x6f, 0x67, 0x18,
+	0x03, 0x20, 0x01, 0x28, 0x0d, 0x48, 0x00, 0x52, 0x0d, 0x6d, 0x61, 0x78, 0x4c, 0x6f, 0x67, 0x42,
+	0x61, 0x63, 0x6b, 0x6c, 0x6f, 0x67, 0x88, 0x01, 0x01, 0x42, 0x12, 0x0a, 0x10, 0x5f, 0x6d, 0x61,
+	0x78, 0x5f, 0x6c, 0x6f, 0x67, 0x5f, 0x62, 0x61, 0x63, 0x6b, 0x6c, 0x6f, 0x67, 0x22, 0xd0, 0x01,
+	0x0a, 0x0c, 0x45, 0x72, 0x72, 0x6f, 0x72, 0x44, 0x65, 0x74, 0x61, 0x69, 0x6c, 0x73, 0x12, 0x1c,
+	0x0a, 0x09, 0x72, 0x65, 0x74, 0x72, 0x79, 0x61, 0x62, 0x6c, 0x65, 0x18, 0x01, 0x20, 0x01, 0x28,
+	0x08, 0x52, 0x09, 0x72, 0x65, 0x74, 0x72, 0x79, 0x61, 0x62, 0x6c, 0x65, 0x12, 0x1d, 0x0a, 0x0a,
+	0x75, 0x73, 0x65, 0x72, 0x5f, 0x65, 0x72, 0x72, 0x6f, 0x72, 0x18, 0x02, 0x20, 0x01, 0x28, 0x08,
+	0x52, 0x09, 0x75, 0x73, 0x65, 0x72, 0x45, 0x72, 0x72, 0x6f, 0x72, 0x12, 0x19, 0x0a, 0x05, 0x66,
+	0x69, 0x65, 0x6c, 0x64, 0x18, 0x03, 0x20, 0x01, 0x28, 0x09, 0x48, 0x00, 0x52, 0x05, 0x66, 0x69,
+	0x65, 0x6c, 0x64, 0x88, 0x01, 0x01, 0x12, 0x22, 0x0a, 0x0a, 0x73, 0x65, 0x67, 0x6d, 0x65, 0x6e,
+	0x74, 0x5f, 0x69, 0x64, 0x18, 0x04, 0x20, 0x01, 0x28, 0x09, 0x48, 0x01, 0x52, 0x09, 0x73, 0x65,
+	0x67, 0x6d, 0x65, 0x6e, 0x74, 0x49, 0x64, 0x88, 0x01, 0x01, 0x12, 0x1e, 0x0a, 0x08, 0x62, 0x6c,
+	0x6f, 0x63, 0x6b, 0x5f, 0x69, 0x64, 0x18, 0x05, 0x20, 0x01, 0x28, 0x09, 0x48, 0x02, 0x52, 0x07,
+	0x62, 0x6c, 0x6f, 0x63, 0x6b, 0x49, 0x64, 0x88, 0x01, 0x01, 0x42, 0x08, 0x0a, 0x06, 0x5f, 0x66,
+	0x69, 0x65, 0x6c, 0x64, 0x42, 0x0d, 0x0a, 0x0b, 0x5f, 0x73, 0x65, 0x67, 0x6d, 0x65, 0x6e, 0x74,
+	0x5f, 0x69, 0x64, 0x42, 0x0b, 0x0a, 0x09, 0x5f, 0x62, 0x6c, 0x6f, 0x63, 0x6b, 0x5f, 0x69, 0x64,
+	0x22, 0x85, 0x02, 0x0a, 0x14, 0x53, 0x74, 0x72, 0x65, 0x61, 0x6d, 0x43, 0x68, 0x61, 0x6e, 0x67,
+	0x65, 0x73, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x12, 0x23, 0x0a, 0x0d, 0x63, 0x6f, 0x6c,
+	0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x5f, 0x69, 0x64, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09,
+	0x52, 0x0c, 0x63, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x49, 0x64, 0x12, 0x46,
+	0x0a, 0x0f, 0x76, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x5f, 0x63, 0x6f, 0x6e, 0x74, 0x65, 0x78,
+	0x74, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x1d, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61,
+	0x2e, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x56, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x43,
+	0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x52, 0x0e, 0x76, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x43,
+	0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x12, 0x28, 0x0a, 0x10, 0x73, 0x74, 0x61, 0x72, 0x74, 0x5f,
+	0x6c, 0x6f, 0x67, 0x5f, 0x6f, 0x66, 0x66, 0x73, 0x65, 0x74, 0x18, 0x03, 0x20, 0x01, 0x28, 0x04,
+	0x52, 0x0e, 0x73, 0x74, 0x61, 0x72, 0x74, 0x4c, 0x6f, 0x67, 0x4f, 0x66, 0x66, 0x73, 0x65, 0x74,
+	0x12, 0x2d, 0x0a, 0x12, 0x69, 0x6e, 0x63, 0x6c, 0x75, 0x64, 0x65, 0x5f, 0x65, 0x6d, 0x62, 0x65,
+	0x64, 0x64, 0x69, 0x6e, 0x67, 0x73, 0x18, 0x04, 0x20, 0x01, 0x28, 0x08, 0x52, 0x11, 0x69, 0x6e,
+	0x63, 0x6c, 0x75, 0x64, 0x65, 0x45, 0x6d, 0x62, 0x65, 0x64, 0x64, 0x69, 0x6e, 0x67, 0x73, 0x12,
+	0x27, 0x0a, 0x0f, 0x64, 0x65, 0x66, 0x61, 0x75, 0x6c, 0x74, 0x5f, 0x69, 0x6e, 0x63, 0x6c, 0x75,
+	0x64, 0x65, 0x18, 0x05, 0x20, 0x01, 0x28, 0x08, 0x52, 0x0e, 0x64, 0x65, 0x66, 0x61, 0x75, 0x6c,
+	0x74, 0x49, 0x6e, 0x63, 0x6c, 0x75, 0x64, 0x65, 0x22, 0x78, 0x0a, 0x0c, 0x52, 0x65, 0x63, 0x6f,
+	0x72, 0x64, 0x43, 0x68, 0x61, 0x6e, 0x67, 0x65, 0x12, 0x2f, 0x0a, 0x09, 0x6f, 0x70, 0x65, 0x72,
+	0x61, 0x74, 0x69, 0x6f, 0x6e, 0x18, 0x01, 0x20, 0x01, 0x28, 0x0e, 0x32, 0x11, 0x2e, 0x63, 0x68,
+	0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x69, 0x6f, 0x6e, 0x52, 0x09,
+	0x6f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x69, 0x6f, 0x6e, 0x12, 0x37, 0x0a, 0x06, 0x72, 0x65, 0x63,
+	0x6f, 0x72, 0x64, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x1f, 0x2e, 0x63, 0x68, 0x72, 0x6f,
+	0x6d, 0x61, 0x2e, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x45, 0x6d, 0x62, 0x65, 0x64,
+	0x64, 0x69, 0x6e, 0x67, 0x52, 0x65, 0x63, 0x6f, 0x72, 0x64, 0x52, 0x06, 0x72, 0x65, 0x63, 0x6f,
+	0x72, 0x64, 0x22, 0x66, 0x0a, 0x15, 0x53, 0x74, 0x72, 0x65, 0x61, 0x6d, 0x43, 0x68, 0x61, 0x6e,
+	0x67, 0x65, 0x73, 0x52, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x12, 0x2e, 0x0a, 0x07, 0x63,
+	0x68, 0x61, 0x6e, 0x67, 0x65, 0x73, 0x18, 0x01, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x14, 0x2e, 0x63,
+	0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x52, 0x65, 0x63, 0x6f, 0x72, 0x64, 0x43, 0x68, 0x61, 0x6e,
+	0x67, 0x65, 0x52, 0x07, 0x63, 0x68, 0x61, 0x6e, 0x67, 0x65, 0x73, 0x12, 0x1d, 0x0a, 0x0a, 0x6c,
+	0x6f, 0x67, 0x5f, 0x6f, 0x66, 0x66, 0x73, 0x65, 0x74, 0x18, 0x02, 0x20, 0x01, 0x28, 0x04, 0x52,
+	0x09, 0x6c, 0x6f, 0x67, 0x4f, 0x66, 0x66, 0x73, 0x65, 0x74, 0x22, 0xa1, 0x01, 0x0a, 0x13, 0x43,
+	0x6f, 0x75, 0x6e, 0x74, 0x52, 0x65, 0x63, 0x6f, 0x72, 0x64, 0x73, 0x52, 0x65, 0x71, 0x75, 0x65,
+	0x73, 0x74, 0x12, 0x1d, 0x0a, 0x0a, 0x73, 0x65, 0x67, 0x6d, 0x65, 0x6e, 0x74, 0x5f, 0x69, 0x64,
+	0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x52, 0x09, 0x73, 0x65, 0x67, 0x6d, 0x65, 0x6e, 0x74, 0x49,
+	0x64, 0x12, 0x23, 0x0a, 0x0d, 0x63, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x5f,
+	0x69, 0x64, 0x18, 0x02, 0x20, 0x01, 0x28, 0x09, 0x52, 0x0c, 0x63, 0x6f, 0x6c, 0x6c, 0x65, 0x63,
+	0x74, 0x69, 0x6f, 0x6e, 0x49, 0x64, 0x12, 0x46, 0x0a, 0x0f, 0x76, 0x65, 0x72, 0x73, 0x69, 0x6f,
+	0x6e, 0x5f, 0x63, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x18, 0x03, 0x20, 0x01, 0x28, 0x0b, 0x32,
+	0x1d, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74,
+	0x56, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x43, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x52, 0x0e,
+	0x76, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x43, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x22, 0x2c,
+	0x0a, 0x14, 0x43, 0x6f, 0x75, 0x6e, 0x74, 0x52, 0x65, 0x63, 0x6f, 0x72, 0x64, 0x73, 0x52, 0x65,
+	0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x12, 0x14, 0x0a, 0x05, 0x63, 0x6f, 0x75, 0x6e, 0x74, 0x18,
+	0x01, 0x20, 0x01, 0x28, 0x0d, 0x52, 0x05, 0x63, 0x6f, 0x75, 0x6e, 0x74, 0x22, 0xa4, 0x05, 0x0a,
+	0x14, 0x51, 0x75, 0x65, 0x72, 0x79, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x52, 0x65,
+	0x71, 0x75, 0x65, 0x73, 0x74, 0x12, 0x1d, 0x0a, 0x0a, 0x73, 0x65, 0x67, 0x6d, 0x65, 0x6e, 0x74,
+	0x5f, 0x69, 0x64, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x52, 0x09, 0x73, 0x65, 0x67, 0x6d, 0x65,
+	0x6e, 0x74, 0x49, 0x64, 0x12, 0x23, 0x0a, 0x05, 0x77, 0x68, 0x65, 0x72, 0x65, 0x18, 0x02, 0x20,
+	0x01, 0x28, 0x0b, 0x32, 0x0d, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x57, 0x68, 0x65,
+	0x72, 0x65, 0x52, 0x05, 0x77, 0x68, 0x65, 0x72, 0x65, 0x12, 0x3c, 0x0a, 0x0e, 0x77, 0x68, 0x65,
+	0x72, 0x65, 0x5f, 0x64, 0x6f, 0x63, 0x75, 0x6d, 0x65, 0x6e, 0x74, 0x18, 0x03, 0x20, 0x01, 0x28,
+	0x0b, 0x32, 0x15, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x57, 0x68, 0x65, 0x72, 0x65,
+	0x44, 0x6f, 0x63, 0x75, 0x6d, 0x65, 0x6e, 0x74, 0x52, 0x0d, 0x77, 0x68, 0x65, 0x72, 0x65, 0x44,
+	0x6f, 0x63, 0x75, 0x6d, 0x65, 0x6e, 0x74, 0x12, 0x26, 0x0a, 0x03, 0x69, 0x64, 0x73, 0x18, 0x04,
+	0x20, 0x01, 0x28, 0x0b, 0x32, 0x0f, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x55, 0x73,
+	0x65, 0x72, 0x49, 0x64, 0x73, 0x48, 0x00, 0x52, 0x03, 0x69, 0x64, 0x73, 0x88, 0x01, 0x01, 0x12,
+	0x19, 0x0a, 0x05, 0x6c, 0x69, 0x6d, 0x69, 0x74, 0x18, 0x05, 0x20, 0x01, 0x28, 0x0d, 0x48, 0x01,
+	0x52, 0x05, 0x6c, 0x69, 0x6d, 0x69, 0x74, 0x88, 0x01, 0x01, 0x12, 0x1b, 0x0a, 0x06, 0x6f, 0x66,
+	0x66, 0x73, 0x65, 0x74, 0x18, 0x06, 0x20, 0x01, 0x28, 0x0d, 0x48, 0x02, 0x52, 0x06, 0x6f, 0x66,
+	0x66, 0x73, 0x65, 0x74, 0x88, 0x01, 0x01, 0x12, 0x23, 0x0a, 0x0d, 0x63, 0x6f, 0x6c, 0x6c, 0x65,
+	0x63, 0x74, 0x69, 0x6f, 0x6e, 0x5f, 0x69, 0x64, 0x18, 0x07, 0x20, 0x01, 0x28, 0x09, 0x52, 0x0c,
+	0x63, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x49, 0x64, 0x12, 0x29, 0x0a, 0x10,
+	0x69, 0x6e, 0x63, 0x6c, 0x75, 0x64, 0x65, 0x5f, 0x6d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61,
+	0x18, 0x08, 0x20, 0x01, 0x28, 0x08, 0x52, 0x0f, 0x69, 0x6e, 0x63, 0x6c, 0x75, 0x64, 0x65, 0x4d,
+	0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x12, 0x46, 0x0a, 0x0f, 0x76, 0x65, 0x72, 0x73, 0x69,
+	0x6f, 0x6e, 0x5f, 0x63, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x18, 0x09, 0x20, 0x01, 0x28, 0x0b,
+	0x32, 0x1d, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73,
+	0x74, 0x56, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x43, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x52,
+	0x0e, 0x76, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x43, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x12,
+	0x2d, 0x0a, 0x12, 0x69, 0x6e, 0x63, 0x6c, 0x75, 0x64, 0x65, 0x5f, 0x65, 0x6d, 0x62, 0x65, 0x64,
+	0x64, 0x69, 0x6e, 0x67, 0x73, 0x18, 0x0a, 0x20, 0x01, 0x28, 0x08, 0x52, 0x11, 0x69, 0x6e, 0x63,
+	0x6c, 0x75, 0x64, 0x65, 0x45, 0x6d, 0x62, 0x65, 0x64, 0x64, 0x69, 0x6e, 0x67, 0x73, 0x12, 0x33,
+	0x0a, 0x13, 0x6d, 0x61, 0x78, 0x5f, 0x65, 0x6d, 0x62, 0x65, 0x64, 0x64, 0x69, 0x6e, 0x67, 0x5f,
+	0x62, 0x79, 0x74, 0x65, 0x73, 0x18, 0x0b, 0x20, 0x01, 0x28, 0x04, 0x48, 0x03, 0x52, 0x11, 0x6d,
+	0x61, 0x78, 0x45, 0x6d, 0x62, 0x65, 0x64, 0x64, 0x69, 0x6e, 0x67, 0x42, 0x79, 0x74, 0x65, 0x73,
+	0x88, 0x01, 0x01, 0x12, 0x18, 0x0a, 0x07, 0x65, 0x78, 0x70, 0x6c, 0x61, 0x69, 0x6e, 0x18, 0x0c,
+	0x20, 0x01, 0x28, 0x08, 0x52, 0x07, 0x65, 0x78, 0x70, 0x6c, 0x61, 0x69, 0x6e, 0x12, 0x2b, 0x0a,
+	0x06, 0x6c, 0x6f, 0x6f, 0x6b, 0x75, 0x70, 0x18, 0x0d, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x0e, 0x2e,
+	0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x4c, 0x6f, 0x6f, 0x6b, 0x75, 0x70, 0x48, 0x04, 0x52,
+	0x06, 0x6c, 0x6f, 0x6f, 0x6b, 0x75, 0x70, 0x88, 0x01, 0x01, 0x12, 0x27, 0x0a, 0x0f, 0x64, 0x65,
+	0x66, 0x61, 0x75, 0x6c, 0x74, 0x5f, 0x69, 0x6e, 0x63, 0x6c, 0x75, 0x64, 0x65, 0x18, 0x0e, 0x20,
+	0x01, 0x28, 0x08, 0x52, 0x0e, 0x64, 0x65, 0x66, 0x61, 0x75, 0x6c, 0x74, 0x49, 0x6e, 0x63, 0x6c,
+	0x75, 0x64, 0x65, 0x42, 0x06, 0x0a, 0x04, 0x5f, 0x69, 0x64, 0x73, 0x42, 0x08, 0x0a, 0x06, 0x5f,
+	0x6c, 0x69, 0x6d, 0x69, 0x74, 0x42, 0x09, 0x0a, 0x07, 0x5f, 0x6f, 0x66, 0x66, 0x73, 0x65, 0x74,
+	0x42, 0x16, 0x0a, 0x14, 0x5f, 0x6d, 0x61, 0x78, 0x5f, 0x65, 0x6d, 0x62, 0x65, 0x64, 0x64, 0x69,
+	0x6e, 0x67, 0x5f, 0x62, 0x79, 0x74, 0x65, 0x73, 0x42, 0x09, 0x0a, 0x07, 0x5f, 0x6c, 0x6f, 0x6f,
+	0x6b, 0x75, 0x70, 0x22, 0x9f, 0x02, 0x0a, 0x06, 0x4c, 0x6f, 0x6f, 0x6b, 0x75, 0x70, 0x12, 0x23,
+	0x0a, 0x0d, 0x63, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x5f, 0x69, 0x64, 0x18,
+	0x01, 0x20, 0x01, 0x28, 0x09, 0x52, 0x0c, 0x63, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f,
+	0x6e, 0x49, 0x64, 0x12, 0x46, 0x0a, 0x0f, 0x76, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x5f, 0x63,
+	0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x1d, 0x2e, 0x63,
+	0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x56, 0x65, 0x72,
+	0x73, 0x69, 0x6f, 0x6e, 0x43, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x52, 0x0e, 0x76, 0x65, 0x72,
+	0x73, 0x69, 0x6f, 0x6e, 0x43, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x12, 0x1a, 0x0a, 0x06, 0x69,
+	0x64, 0x5f, 0x6b, 0x65, 0x79, 0x18, 0x03, 0x20, 0x01, 0x28, 0x09, 0x48, 0x00, 0x52, 0x05, 0x69,
+	0x64, 0x4b, 0x65, 0x79, 0x88, 0x01, 0x01, 0x12, 0x29, 0x0a, 0x10, 0x69, 0x6e, 0x63, 0x6c, 0x75,
+	0x64, 0x65, 0x5f, 0x6d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x18, 0x04, 0x20, 0x01, 0x28,
+	0x08, 0x52, 0x0f, 0x69, 0x6e, 0x63, 0x6c, 0x75, 0x64, 0x65, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61,
+	0x74, 0x61, 0x12, 0x2d, 0x0a, 0x12, 0x69, 0x6e, 0x63, 0x6c, 0x75, 0x64, 0x65, 0x5f, 0x65, 0x6d,
+	0x62, 0x65, 0x64, 0x64, 0x69, 0x6e, 0x67, 0x73, 0x18, 0x05, 0x20, 0x01, 0x28, 0x08, 0x52, 0x11,
+	0x69, 0x6e, 0x63, 0x6c, 0x75, 0x64, 0x65, 0x45, 0x6d, 0x62, 0x65, 0x64, 0x64, 0x69, 0x6e, 0x67,
+	0x73, 0x12, 0x27, 0x0a, 0x0f, 0x64, 0x65, 0x66, 0x61, 0x75, 0x6c, 0x74, 0x5f, 0x69, 0x6e, 0x63,
+	0x6c, 0x75, 0x64, 0x65, 0x18, 0x06, 0x20, 0x01, 0x28, 0x08, 0x52, 0x0e, 0x64, 0x65, 0x66, 0x61,
+	0x75, 0x6c, 0x74, 0x49, 0x6e, 0x63, 0x6c, 0x75, 0x64, 0x65, 0x42, 0x09, 0x0a, 0x07, 0x5f, 0x69,
+	0x64, 0x5f, 0x6b, 0x65, 0x79, 0x22, 0xc3, 0x02, 0x0a, 0x15, 0x51, 0x75, 0x65, 0x72, 0x79, 0x4d,
+	0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x52, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x12,
+	0x39, 0x0a, 0x07, 0x72, 0x65, 0x63, 0x6f, 0x72, 0x64, 0x73, 0x18, 0x01, 0x20, 0x03, 0x28, 0x0b,
+	0x32, 0x1f, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61,
+	0x74, 0x61, 0x45, 0x6d, 0x62, 0x65, 0x64, 0x64, 0x69, 0x6e, 0x67, 0x52, 0x65, 0x63, 0x6f, 0x72,
+	0x64, 0x52, 0x07, 0x72, 0x65, 0x63, 0x6f, 0x72, 0x64, 0x73, 0x12, 0x2a, 0x0a, 0x04, 0x70, 0x6c,
+	0x61, 0x6e, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x11, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d,
+	0x61, 0x2e, 0x51, 0x75, 0x65, 0x72, 0x79, 0x50, 0x6c, 0x61, 0x6e, 0x48, 0x00, 0x52, 0x04, 0x70,
+	0x6c, 0x61, 0x6e, 0x88, 0x01, 0x01, 0x12, 0x57, 0x0a, 0x0e, 0x6c, 0x6f, 0x6f, 0x6b, 0x75, 0x70,
+	0x5f, 0x72, 0x65, 0x63, 0x6f, 0x72, 0x64, 0x73, 0x18, 0x03, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x30,
+	0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x51, 0x75, 0x65, 0x72, 0x79, 0x4d, 0x65, 0x74,
+	0x61, 0x64, 0x61, 0x74, 0x61, 0x52, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x2e, 0x4c, 0x6f,
+	0x6f, 0x6b, 0x75, 0x70, 0x52, 0x65, 0x63, 0x6f, 0x72, 0x64, 0x73, 0x45, 0x6e, 0x74, 0x72, 0x79,
+	0x52, 0x0d, 0x6c, 0x6f, 0x6f, 0x6b, 0x75, 0x70, 0x52, 0x65, 0x63, 0x6f, 0x72, 0x64, 0x73, 0x1a,
+	0x61, 0x0a, 0x12, 0x4c, 0x6f, 0x6f, 0x6b, 0x75, 0x70, 0x52, 0x65, 0x63, 0x6f, 0x72, 0x64, 0x73,
+	0x45, 0x6e, 0x74, 0x72, 0x79, 0x12, 0x10, 0x0a, 0x03, 0x6b, 0x65, 0x79, 0x18, 0x01, 0x20, 0x01,
+	0x28, 0x09, 0x52, 0x03, 0x6b, 0x65, 0x79, 0x12, 0x35, 0x0a, 0x05, 0x76, 0x61, 0x6c, 0x75, 0x65,
+	0x18, 0x02, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x1f, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e,
+	0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x45, 0x6d, 0x62, 0x65, 0x64, 0x64, 0x69, 0x6e,
+	0x67, 0x52, 0x65, 0x63, 0x6f, 0x72, 0x64, 0x52, 0x05, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x3a, 0x02,
+	0x38, 0x01, 0x42, 0x07, 0x0a, 0x05, 0x5f, 0x70, 0x6c, 0x61, 0x6e, 0x22, 0xb2, 0x01, 0x0a, 0x09,
+	0x51, 0x75, 0x65, 0x72, 0x79, 0x50, 0x6c, 0x61, 0x6e, 0x12, 0x3b, 0x0a, 0x08, 0x73, 0x74, 0x72,
+	0x61, 0x74, 0x65, 0x67, 0x79, 0x18, 0x01, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x1f, 0x2e, 0x63, 0x68,
+	0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x51, 0x75, 0x65, 0x72, 0x79, 0x50, 0x6c, 0x61, 0x6e, 0x2e, 0x53,
+	0x74, 0x72, 0x61, 0x74, 0x65, 0x67, 0x79, 0x45, 0x6e, 0x74, 0x72, 0x79, 0x52, 0x08, 0x73, 0x74,
+	0x72, 0x61, 0x74, 0x65, 0x67, 0x79, 0x12, 0x2b, 0x0a, 0x05, 0x6e, 0x6f, 0x64, 0x65, 0x73, 0x18,
+	0x02, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x15, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x51,
+	0x75, 0x65, 0x72, 0x79, 0x50, 0x6c, 0x61, 0x6e, 0x4e, 0x6f, 0x64, 0x65, 0x52, 0x05, 0x6e, 0x6f,
+	0x64, 0x65, 0x73, 0x1a, 0x3b, 0x0a, 0x0d, 0x53, 0x74, 0x72, 0x61, 0x74, 0x65, 0x67, 0x79, 0x45,
+	0x6e, 0x74, 0x72, 0x79, 0x12, 0x10, 0x0a, 0x03, 0x6b, 0x65, 0x79, 0x18, 0x01, 0x20, 0x01, 0x28,
+	0x09, 0x52, 0x03, 0x6b, 0x65, 0x79, 0x12, 0x14, 0x0a, 0x05, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x18,
+	0x02, 0x20, 0x01, 0x28, 0x09, 0x52, 0x05, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x3a, 0x02, 0x38, 0x01,
+	0x22, 0xd8, 0x02, 0x0a, 0x0d, 0x51, 0x75, 0x65, 0x72, 0x79, 0x50, 0x6c, 0x61, 0x6e, 0x4e, 0x6f,
+	0x64, 0x65, 0x12, 0x1a, 0x0a, 0x08, 0x6f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x18, 0x01,
+	0x20, 0x01, 0x28, 0x09, 0x52, 0x08, 0x6f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x12, 0x16,
+	0x0a, 0x06, 0x69, 0x6e, 0x70, 0x75, 0x74, 0x73, 0x18, 0x02, 0x20, 0x03, 0x28, 0x0d, 0x52, 0x06,
+	0x69, 0x6e, 0x70, 0x75, 0x74, 0x73, 0x12, 0x2a, 0x0a, 0x0e, 0x65, 0x73, 0x74, 0x69, 0x6d, 0x61,
+	0x74, 0x65, 0x64, 0x5f, 0x72, 0x6f, 0x77, 0x73, 0x18, 0x03, 0x20, 0x01, 0x28, 0x04, 0x48, 0x00,
+	0x52, 0x0d, 0x65, 0x73, 0x74, 0x69, 0x6d, 0x61, 0x74, 0x65, 0x64, 0x52, 0x6f, 0x77, 0x73, 0x88,
+	0x01, 0x01, 0x12, 0x24, 0x0a, 0x0b, 0x6f, 0x75, 0x74, 0x70, 0x75, 0x74, 0x5f, 0x72, 0x6f, 0x77,
+	0x73, 0x18, 0x04, 0x20, 0x01, 0x28, 0x04, 0x48, 0x01, 0x52, 0x0a, 0x6f, 0x75, 0x74, 0x70, 0x75,
+	0x74, 0x52, 0x6f, 0x77, 0x73, 0x88, 0x01, 0x01, 0x12, 0x2a, 0x0a, 0x0e, 0x65, 0x6c, 0x61, 0x70,
+	0x73, 0x65, 0x64, 0x5f, 0x6d, 0x69, 0x63, 0x72, 0x6f, 0x73, 0x18, 0x05, 0x20, 0x01, 0x28, 0x04,
+	0x48, 0x02, 0x52, 0x0d, 0x65, 0x6c, 0x61, 0x70, 0x73, 0x65, 0x64, 0x4d, 0x69, 0x63, 0x72, 0x6f,
+	0x73, 0x88, 0x01, 0x01, 0x12, 0x1d, 0x0a, 0x0a, 0x63, 0x61, 0x63, 0x68, 0x65, 0x5f, 0x68, 0x69,
+	0x74, 0x73, 0x18, 0x06, 0x20, 0x01, 0x28, 0x04, 0x52, 0x09, 0x63, 0x61, 0x63, 0x68, 0x65, 0x48,
+	0x69, 0x74, 0x73, 0x12, 0x21, 0x0a, 0x0c, 0x63, 0x61, 0x63, 0x68, 0x65, 0x5f, 0x6d, 0x69, 0x73,
+	0x73, 0x65, 0x73, 0x18, 0x07, 0x20, 0x01, 0x28, 0x04, 0x52, 0x0b, 0x63, 0x61, 0x63, 0x68, 0x65,
+	0x4d, 0x69, 0x73, 0x73, 0x65, 0x73, 0x12, 0x1d, 0x0a, 0x0a, 0x62, 0x79, 0x74, 0x65, 0x73, 0x5f,
+	0x72, 0x65, 0x61, 0x64, 0x18, 0x08, 0x20, 0x01, 0x28, 0x04, 0x52, 0x09, 0x62, 0x79, 0x74, 0x65,
+	0x73, 0x52, 0x65, 0x61, 0x64, 0x42, 0x11, 0x0a, 0x0f, 0x5f, 0x65, 0x73, 0x74, 0x69, 0x6d, 0x61,
+	0x74, 0x65, 0x64, 0x5f, 0x72, 0x6f, 0x77, 0x73, 0x42, 0x0e, 0x0a, 0x0c, 0x5f, 0x6f, 0x75, 0x74,
+	0x70, 0x75, 0x74, 0x5f, 0x72, 0x6f, 0x77, 0x73, 0x42, 0x11, 0x0a, 0x0f, 0x5f, 0x65, 0x6c, 0x61,
+	0x70, 0x73, 0x65, 0x64, 0x5f, 0x6d, 0x69, 0x63, 0x72, 0x6f, 0x73, 0x22, 0xfc, 0x01, 0x0a, 0x17,
+	0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x45, 0x6d, 0x62, 0x65, 0x64, 0x64, 0x69, 0x6e,
+	0x67, 0x52, 0x65, 0x63, 0x6f, 0x72, 0x64, 0x12, 0x0e, 0x0a, 0x02, 0x69, 0x64, 0x18, 0x01, 0x20,
+	0x01, 0x28, 0x09, 0x52, 0x02, 0x69, 0x64, 0x12, 0x32, 0x0a, 0x08, 0x6d, 0x65, 0x74, 0x61, 0x64,
+	0x61, 0x74, 0x61, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x16, 0x2e, 0x63, 0x68, 0x72, 0x6f,
+	0x6d, 0x61, 0x2e, 0x55, 0x70, 0x64, 0x61, 0x74, 0x65, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74,
+	0x61, 0x52, 0x08, 0x6d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x12, 0x31, 0x0a, 0x09, 0x65,
+	0x6d, 0x62, 0x65, 0x64, 0x64, 0x69, 0x6e, 0x67, 0x18, 0x03, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x0e,
+	0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x56, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x48, 0x00,
+	0x52, 0x09, 0x65, 0x6d, 0x62, 0x65, 0x64, 0x64, 0x69, 0x6e, 0x67, 0x88, 0x01, 0x01, 0x12, 0x47,
+	0x0a, 0x10, 0x65, 0x6d, 0x62, 0x65, 0x64, 0x64, 0x69, 0x6e, 0x67, 0x5f, 0x68, 0x61, 0x6e, 0x64,
+	0x6c, 0x65, 0x18, 0x04, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x17, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d,
+	0x61, 0x2e, 0x45, 0x6d, 0x62, 0x65, 0x64, 0x64, 0x69, 0x6e, 0x67, 0x48, 0x61, 0x6e, 0x64, 0x6c,
+	0x65, 0x48, 0x01, 0x52, 0x0f, 0x65, 0x6d, 0x62, 0x65, 0x64, 0x64, 0x69, 0x6e, 0x67, 0x48, 0x61,
+	0x6e, 0x64, 0x6c, 0x65, 0x88, 0x01, 0x01, 0x42, 0x0c, 0x0a, 0x0a, 0x5f, 0x65, 0x6d, 0x62, 0x65,
+	0x64, 0x64, 0x69, 0x6e, 0x67, 0x42, 0x13, 0x0a, 0x11, 0x5f, 0x65, 0x6d, 0x62, 0x65, 0x64, 0x64,
+	0x69, 0x6e, 0x67, 0x5f, 0x68, 0x61, 0x6e, 0x64, 0x6c, 0x65, 0x22, 0xa9, 0x01, 0x0a, 0x0f, 0x45,
+	0x6d, 0x62, 0x65, 0x64, 0x64, 0x69, 0x6e, 0x67, 0x48, 0x61, 0x6e, 0x64, 0x6c, 0x65, 0x12, 0x1b,
+	0x0a, 0x09, 0x6f, 0x66, 0x66, 0x73, 0x65, 0x74, 0x5f, 0x69, 0x64, 0x18, 0x01, 0x20, 0x01, 0x28,
+	0x0d, 0x52, 0x08, 0x6f, 0x66, 0x66, 0x73, 0x65, 0x74, 0x49, 0x64, 0x12, 0x46, 0x0a, 0x0f, 0x76,
+	0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x5f, 0x63, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x18, 0x02,
+	0x20, 0x01, 0x28, 0x0b, 0x32, 0x1d, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x52, 0x65,
+	0x71, 0x75, 0x65, 0x73, 0x74, 0x56, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x43, 0x6f, 0x6e, 0x74,
+	0x65, 0x78, 0x74, 0x52, 0x0e, 0x76, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x43, 0x6f, 0x6e, 0x74,
+	0x65, 0x78, 0x74, 0x12, 0x22, 0x0a, 0x0a, 0x6c, 0x6f, 0x67, 0x5f, 0x6f, 0x66, 0x66, 0x73, 0x65,
+	0x74, 0x18, 0x03, 0x20, 0x01, 0x28, 0x04, 0x48, 0x00, 0x52, 0x09, 0x6c, 0x6f, 0x67, 0x4f, 0x66,
+	0x66, 0x73, 0x65, 0x74, 0x88, 0x01, 0x01, 0x42, 0x0d, 0x0a, 0x0b, 0x5f, 0x6c, 0x6f, 0x67, 0x5f,
+	0x6f, 0x66, 0x66, 0x73, 0x65, 0x74, 0x22, 0xf4, 0x01, 0x0a, 0x14, 0x47, 0x65, 0x74, 0x45, 0x6d,
+	0x62, 0x65, 0x64, 0x64, 0x69, 0x6e, 0x67, 0x73, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x12,
+	0x1d, 0x0a, 0x0a, 0x73, 0x65, 0x67, 0x6d, 0x65, 0x6e, 0x74, 0x5f, 0x69, 0x64, 0x18, 0x01, 0x20,
+	0x01, 0x28, 0x09, 0x52, 0x09, 0x73, 0x65, 0x67, 0x6d, 0x65, 0x6e, 0x74, 0x49, 0x64, 0x12, 0x23,
+	0x0a, 0x0d, 0x63, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x5f, 0x69, 0x64, 0x18,
+	0x02, 0x20, 0x01, 0x28, 0x09, 0x52, 0x0c, 0x63, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f,
+	0x6e, 0x49, 0x64, 0x12, 0x46, 0x0a, 0x0f, 0x76, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x5f, 0x63,
+	0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x18, 0x03, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x1d, 0x2e, 0x63,
+	0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x56, 0x65, 0x72,
+	0x73, 0x69, 0x6f, 0x6e, 0x43, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x52, 0x0e, 0x76, 0x65, 0x72,
+	0x73, 0x69, 0x6f, 0x6e, 0x43, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x12, 0x1d, 0x0a, 0x0a, 0x6f,
+	0x66, 0x66, 0x73, 0x65, 0x74, 0x5f, 0x69, 0x64, 0x73, 0x18, 0x04, 0x20, 0x03, 0x28, 0x0d, 0x52,
+	0x09, 0x6f, 0x66, 0x66, 0x73, 0x65, 0x74, 0x49, 0x64, 0x73, 0x12, 0x22, 0x0a, 0x0a, 0x6c, 0x6f,
+	0x67, 0x5f, 0x6f, 0x66, 0x66, 0x73, 0x65, 0x74, 0x18, 0x05, 0x20, 0x01, 0x28, 0x04, 0x48, 0x00,
+	0x52, 0x09, 0x6c, 0x6f, 0x67, 0x4f, 0x66, 0x66, 0x73, 0x65, 0x74, 0x88, 0x01, 0x01, 0x42, 0x0d,
+	0x0a, 0x0b, 0x5f, 0x6c, 0x6f, 0x67, 0x5f, 0x6f, 0x66, 0x66, 0x73, 0x65, 0x74, 0x22, 0x50, 0x0a,
+	0x15, 0x47, 0x65, 0x74, 0x45, 0x6d, 0x62, 0x65, 0x64, 0x64, 0x69, 0x6e, 0x67, 0x73, 0x52, 0x65,
+	0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x12, 0x37, 0x0a, 0x07, 0x72, 0x65, 0x63, 0x6f, 0x72, 0x64,
+	0x73, 0x18, 0x01, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x1d, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61,
+	0x2e, 0x56, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x45, 0x6d, 0x62, 0x65, 0x64, 0x64, 0x69, 0x6e, 0x67,
+	0x52, 0x65, 0x63, 0x6f, 0x72, 0x64, 0x52, 0x07, 0x72, 0x65, 0x63, 0x6f, 0x72, 0x64, 0x73, 0x22,
+	0x1b, 0x0a, 0x07, 0x55, 0x73, 0x65, 0x72, 0x49, 0x64, 0x73, 0x12, 0x10, 0x0a, 0x03, 0x69, 0x64,
+	0x73, 0x18, 0x01, 0x20, 0x03, 0x28, 0x09, 0x52, 0x03, 0x69, 0x64, 0x73, 0x22, 0x95, 0x01, 0x0a,
+	0x0d, 0x57, 0x68, 0x65, 0x72, 0x65, 0x44, 0x6f, 0x63, 0x75, 0x6d, 0x65, 0x6e, 0x74, 0x12, 0x35,
+	0x0a, 0x06, 0x64, 0x69, 0x72, 0x65, 0x63, 0x74, 0x18, 0x01, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x1b,
+	0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x44, 0x69, 0x72, 0x65, 0x63, 0x74, 0x57, 0x68,
+	0x65, 0x72, 0x65, 0x44, 0x6f, 0x63, 0x75, 0x6d, 0x65, 0x6e, 0x74, 0x48, 0x00, 0x52, 0x06, 0x64,
+	0x69, 0x72, 0x65, 0x63, 0x74, 0x12, 0x3b, 0x0a, 0x08, 0x63, 0x68, 0x69, 0x6c, 0x64, 0x72, 0x65,
+	0x6e, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x1d, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61,
+	0x2e, 0x57, 0x68, 0x65, 0x72, 0x65, 0x44, 0x6f, 0x63, 0x75, 0x6d, 0x65, 0x6e, 0x74, 0x43, 0x68,
+	0x69, 0x6c, 0x64, 0x72, 0x65, 0x6e, 0x48, 0x00, 0x52, 0x08, 0x63, 0x68, 0x69, 0x6c, 0x64, 0x72,
+	0x65, 0x6e, 0x42, 0x10, 0x0a, 0x0e, 0x77, 0x68, 0x65, 0x72, 0x65, 0x5f, 0x64, 0x6f, 0x63, 0x75,
+	0x6d, 0x65, 0x6e, 0x74, 0x22, 0x9a, 0x01, 0x0a, 0x13, 0x44, 0x69, 0x72, 0x65, 0x63, 0x74, 0x57,
+	0x68, 0x65, 0x72, 0x65, 0x44, 0x6f, 0x63, 0x75, 0x6d, 0x65, 0x6e, 0x74, 0x12, 0x1a, 0x0a, 0x08,
+	0x64, 0x6f, 0x63, 0x75, 0x6d, 0x65, 0x6e, 0x74, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x52, 0x08,
+	0x64, 0x6f, 0x63, 0x75, 0x6d, 0x65, 0x6e, 0x74, 0x12, 0x39, 0x0a, 0x08, 0x6f, 0x70, 0x65, 0x72,
+	0x61, 0x74, 0x6f, 0x72, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0e, 0x32, 0x1d, 0x2e, 0x63, 0x68, 0x72,
+	0x6f, 0x6d, 0x61, 0x2e, 0x57, 0x68, 0x65, 0x72, 0x65, 0x44, 0x6f, 0x63, 0x75, 0x6d, 0x65, 0x6e,
+	0x74, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x52, 0x08, 0x6f, 0x70, 0x65, 0x72, 0x61,
+	0x74, 0x6f, 0x72, 0x12, 0x1f, 0x0a, 0x08, 0x64, 0x69, 0x73, 0x74, 0x61, 0x6e, 0x63, 0x65, 0x18,
+	0x03, 0x20, 0x01, 0x28, 0x0d, 0x48, 0x00, 0x52, 0x08, 0x64, 0x69, 0x73, 0x74, 0x61, 0x6e, 0x63,
+	0x65, 0x88, 0x01, 0x01, 0x42, 0x0b, 0x0a, 0x09, 0x5f, 0x64, 0x69, 0x73, 0x74, 0x61, 0x6e, 0x63,
+	0x65, 0x22, 0x7f, 0x0a, 0x15, 0x57, 0x68, 0x65, 0x72, 0x65, 0x44, 0x6f, 0x63, 0x75, 0x6d, 0x65,
+	0x6e, 0x74, 0x43, 0x68, 0x69, 0x6c, 0x64, 0x72, 0x65, 0x6e, 0x12, 0x31, 0x0a, 0x08, 0x63, 0x68,
+	0x69, 0x6c, 0x64, 0x72, 0x65, 0x6e, 0x18, 0x01, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x15, 0x2e, 0x63,
+	0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x57, 0x68, 0x65, 0x72, 0x65, 0x44, 0x6f, 0x63, 0x75, 0x6d,
+	0x65, 0x6e, 0x74, 0x52, 0x08, 0x63, 0x68, 0x69, 0x6c, 0x64, 0x72, 0x65, 0x6e, 0x12, 0x33, 0x0a,
+	0x08, 0x6f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0e, 0x32,
+	0x17, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x42, 0x6f, 0x6f, 0x6c, 0x65, 0x61, 0x6e,
+	0x4f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x52, 0x08, 0x6f, 0x70, 0x65, 0x72, 0x61, 0x74,
+	0x6f, 0x72, 0x22, 0x8e, 0x01, 0x0a, 0x05, 0x57, 0x68, 0x65, 0x72, 0x65, 0x12, 0x47, 0x0a, 0x11,
+	0x64, 0x69, 0x72, 0x65, 0x63, 0x74, 0x5f, 0x63, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x69, 0x73, 0x6f,
+	0x6e, 0x18, 0x01, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x18, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61,
+	0x2e, 0x44, 0x69, 0x72, 0x65, 0x63, 0x74, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x69, 0x73, 0x6f,
+	0x6e, 0x48, 0x00, 0x52, 0x10, 0x64, 0x69, 0x72, 0x65, 0x63, 0x74, 0x43, 0x6f, 0x6d, 0x70, 0x61,
+	0x72, 0x69, 0x73, 0x6f, 0x6e, 0x12, 0x33, 0x0a, 0x08, 0x63, 0x68, 0x69, 0x6c, 0x64, 0x72, 0x65,
+	0x6e, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x15, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61,
+	0x2e, 0x57, 0x68, 0x65, 0x72, 0x65, 0x43, 0x68, 0x69, 0x6c, 0x64, 0x72, 0x65, 0x6e, 0x48, 0x00,
+	0x52, 0x08, 0x63, 0x68, 0x69, 0x6c, 0x64, 0x72, 0x65, 0x6e, 0x42, 0x07, 0x0a, 0x05, 0x77, 0x68,
+	0x65, 0x72, 0x65, 0x22, 0xd7, 0x06, 0x0a, 0x10, 0x44, 0x69, 0x72, 0x65, 0x63, 0x74, 0x43, 0x6f,
+	0x6d, 0x70, 0x61, 0x72, 0x69, 0x73, 0x6f, 0x6e, 0x12, 0x10, 0x0a, 0x03, 0x6b, 0x65, 0x79, 0x18,
+	0x01, 0x20, 0x01, 0x28, 0x09, 0x52, 0x03, 0x6b, 0x65, 0x79, 0x12, 0x54, 0x0a, 0x15, 0x73, 0x69,
+	0x6e, 0x67, 0x6c, 0x65, 0x5f, 0x73, 0x74, 0x72, 0x69, 0x6e, 0x67, 0x5f, 0x6f, 0x70, 0x65, 0x72,
+	0x61, 0x6e, 0x64, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x1e, 0x2e, 0x63, 0x68, 0x72, 0x6f,
+	0x6d, 0x61, 0x2e, 0x53, 0x69, 0x6e, 0x67, 0x6c, 0x65, 0x53, 0x74, 0x72, 0x69, 0x6e, 0x67, 0x43,
+	0x6f, 0x6d, 0x70, 0x61, 0x72, 0x69, 0x73, 0x6f, 0x6e, 0x48, 0x00, 0x52, 0x13, 0x73, 0x69, 0x6e,
+	0x67, 0x6c, 0x65, 0x53, 0x74, 0x72, 0x69, 0x6e, 0x67, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x6e, 0x64,
+	0x12, 0x4e, 0x0a, 0x13, 0x73, 0x74, 0x72, 0x69, 0x6e, 0x67, 0x5f, 0x6c, 0x69, 0x73, 0x74, 0x5f,
+	0x6f, 0x70, 0x65, 0x72, 0x61, 0x6e, 0x64, 0x18, 0x03, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x1c, 0x2e,
+	0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x53, 0x74, 0x72, 0x69, 0x6e, 0x67, 0x4c, 0x69, 0x73,
+	0x74, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x69, 0x73, 0x6f, 0x6e, 0x48, 0x00, 0x52, 0x11, 0x73,
+	0x74, 0x72, 0x69, 0x6e, 0x67, 0x4c, 0x69, 0x73, 0x74, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x6e, 0x64,
+	0x12, 0x4b, 0x0a, 0x12, 0x73, 0x69, 0x6e, 0x67, 0x6c, 0x65, 0x5f, 0x69, 0x6e, 0x74, 0x5f, 0x6f,
+	0x70, 0x65, 0x72, 0x61, 0x6e, 0x64, 0x18, 0x04, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x1b, 0x2e, 0x63,
+	0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x53, 0x69, 0x6e, 0x67, 0x6c, 0x65, 0x49, 0x6e, 0x74, 0x43,
+	0x6f, 0x6d, 0x70, 0x61, 0x72, 0x69, 0x73, 0x6f, 0x6e, 0x48, 0x00, 0x52, 0x10, 0x73, 0x69, 0x6e,
+	0x67, 0x6c, 0x65, 0x49, 0x6e, 0x74, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x6e, 0x64, 0x12, 0x45, 0x0a,
+	0x10, 0x69, 0x6e, 0x74, 0x5f, 0x6c, 0x69, 0x73, 0x74, 0x5f, 0x6f, 0x70, 0x65, 0x72, 0x61, 0x6e,
+	0x64, 0x18, 0x05, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x19, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61,
+	0x2e, 0x49, 0x6e, 0x74, 0x4c, 0x69, 0x73, 0x74, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x69, 0x73,
+	0x6f, 0x6e, 0x48, 0x00, 0x52, 0x0e, 0x69, 0x6e, 0x74, 0x4c, 0x69, 0x73, 0x74, 0x4f, 0x70, 0x65,
+	0x72, 0x61, 0x6e, 0x64, 0x12, 0x54, 0x0a, 0x15, 0x73, 0x69, 0x6e, 0x67, 0x6c, 0x65, 0x5f, 0x64,
+	0x6f, 0x75, 0x62, 0x6c, 0x65, 0x5f, 0x6f, 0x70, 0x65, 0x72, 0x61, 0x6e, 0x64, 0x18, 0x06, 0x20,
+	0x01, 0x28, 0x0b, 0x32, 0x1e, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x53, 0x69, 0x6e,
+	0x67, 0x6c, 0x65, 0x44, 0x6f, 0x75, 0x62, 0x6c, 0x65, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x69,
+	0x73, 0x6f, 0x6e, 0x48, 0x00, 0x52, 0x13, 0x73, 0x69, 0x6e, 0x67, 0x6c, 0x65, 0x44, 0x6f, 0x75,
+	0x62, 0x6c, 0x65, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x6e, 0x64, 0x12, 0x4e, 0x0a, 0x13, 0x64, 0x6f,
+	0x75, 0x62, 0x6c, 0x65, 0x5f, 0x6c, 0x69, 0x73, 0x74, 0x5f, 0x6f, 0x70, 0x65, 0x72, 0x61, 0x6e,
+	0x64, 0x18, 0x07, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x1c, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61,
+	0x2e, 0x44, 0x6f, 0x75, 0x62, 0x6c, 0x65, 0x4c, 0x69, 0x73, 0x74, 0x43, 0x6f, 0x6d, 0x70, 0x61,
+	0x72, 0x69, 0x73, 0x6f, 0x6e, 0x48, 0x00, 0x52, 0x11, 0x64, 0x6f, 0x75, 0x62, 0x6c, 0x65, 0x4c,
+	0x69, 0x73, 0x74, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x6e, 0x64, 0x12, 0x48, 0x0a, 0x11, 0x62, 0x6f,
+	0x6f, 0x6c, 0x5f, 0x6c, 0x69, 0x73, 0x74, 0x5f, 0x6f, 0x70, 0x65, 0x72, 0x61, 0x6e, 0x64, 0x18,
+	0x08, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x1a, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x42,
+	0x6f, 0x6f, 0x6c, 0x4c, 0x69, 0x73, 0x74, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x69, 0x73, 0x6f,
+	0x6e, 0x48, 0x00, 0x52, 0x0f, 0x62, 0x6f, 0x6f, 0x6c, 0x4c, 0x69, 0x73, 0x74, 0x4f, 0x70, 0x65,
+	0x72, 0x61, 0x6e, 0x64, 0x12, 0x4e, 0x0a, 0x13, 0x73, 0x69, 0x6e, 0x67, 0x6c, 0x65, 0x5f, 0x62,
+	0x6f, 0x6f, 0x6c, 0x5f, 0x6f, 0x70, 0x65, 0x72, 0x61, 0x6e, 0x64, 0x18, 0x09, 0x20, 0x01, 0x28,
+	0x0b, 0x32, 0x1c, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x53, 0x69, 0x6e, 0x67, 0x6c,
+	0x65, 0x42, 0x6f, 0x6f, 0x6c, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x69, 0x73, 0x6f, 0x6e, 0x48,
+	0x00, 0x52, 0x11, 0x73, 0x69, 0x6e, 0x67, 0x6c, 0x65, 0x42, 0x6f, 0x6f, 0x6c, 0x4f, 0x70, 0x65,
+	0x72, 0x61, 0x6e, 0x64, 0x12, 0x4a, 0x0a, 0x11, 0x65, 0x78, 0x69, 0x73, 0x74, 0x65, 0x6e, 0x63,
+	0x65, 0x5f, 0x6f, 0x70, 0x65, 0x72, 0x61, 0x6e, 0x64, 0x18, 0x0a, 0x20, 0x01, 0x28, 0x0b, 0x32,
+	0x1b, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x45, 0x78, 0x69, 0x73, 0x74, 0x65, 0x6e,
+	0x63, 0x65, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x69, 0x73, 0x6f, 0x6e, 0x48, 0x00, 0x52, 0x10,
+	0x65, 0x78, 0x69, 0x73, 0x74, 0x65, 0x6e, 0x63, 0x65, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x6e, 0x64,
+	0x12, 0x5d, 0x0a, 0x18, 0x73, 0x69, 0x6e, 0x67, 0x6c, 0x65, 0x5f, 0x74, 0x69, 0x6d, 0x65, 0x73,
+	0x74, 0x61, 0x6d, 0x70, 0x5f, 0x6f, 0x70, 0x65, 0x72, 0x61, 0x6e, 0x64, 0x18, 0x0b, 0x20, 0x01,
+	0x28, 0x0b, 0x32, 0x21, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x53, 0x69, 0x6e, 0x67,
+	0x6c, 0x65, 0x54, 0x69, 0x6d, 0x65, 0x73, 0x74, 0x61, 0x6d, 0x70, 0x43, 0x6f, 0x6d, 0x70, 0x61,
+	0x72, 0x69, 0x73, 0x6f, 0x6e, 0x48, 0x00, 0x52, 0x16, 0x73, 0x69, 0x6e, 0x67, 0x6c, 0x65, 0x54,
+	0x69, 0x6d, 0x65, 0x73, 0x74, 0x61, 0x6d, 0x70, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x6e, 0x64, 0x42,
+	0x0c, 0x0a, 0x0a, 0x63, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x69, 0x73, 0x6f, 0x6e, 0x22, 0x6f, 0x0a,
+	0x0d, 0x57, 0x68, 0x65, 0x72, 0x65, 0x43, 0x68, 0x69, 0x6c, 0x64, 0x72, 0x65, 0x6e, 0x12, 0x29,
+	0x0a, 0x08, 0x63, 0x68, 0x69, 0x6c, 0x64, 0x72, 0x65, 0x6e, 0x18, 0x01, 0x20, 0x03, 0x28, 0x0b,
+	0x32, 0x0d, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x57, 0x68, 0x65, 0x72, 0x65, 0x52,
+	0x08, 0x63, 0x68, 0x69, 0x6c, 0x64, 0x72, 0x65, 0x6e, 0x12, 0x33, 0x0a, 0x08, 0x6f, 0x70, 0x65,
+	0x72, 0x61, 0x74, 0x6f, 0x72, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0e, 0x32, 0x17, 0x2e, 0x63, 0x68,
+	0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x42, 0x6f, 0x6f, 0x6c, 0x65, 0x61, 0x6e, 0x4f, 0x70, 0x65, 0x72,
+	0x61, 0x74, 0x6f, 0x72, 0x52, 0x08, 0x6f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x22, 0x69,
+	0x0a, 0x14, 0x53, 0x74, 0x72, 0x69, 0x6e, 0x67, 0x4c, 0x69, 0x73, 0x74, 0x43, 0x6f, 0x6d, 0x70,
+	0x61, 0x72, 0x69, 0x73, 0x6f, 0x6e, 0x12, 0x16, 0x0a, 0x06, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x73,
+	0x18, 0x01, 0x20, 0x03, 0x28, 0x09, 0x52, 0x06, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x73, 0x12, 0x39,
+	0x0a, 0x0d, 0x6c, 0x69, 0x73, 0x74, 0x5f, 0x6f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x18,
+	0x02, 0x20, 0x01, 0x28, 0x0e, 0x32, 0x14, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x4c,
+	0x69, 0x73, 0x74, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x52, 0x0c, 0x6c, 0x69, 0x73,
+	0x74, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x22, 0x4c, 0x0a, 0x13, 0x45, 0x78, 0x69,
+	0x73, 0x74, 0x65, 0x6e, 0x63, 0x65, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x69, 0x73, 0x6f, 0x6e,
+	0x12, 0x35, 0x0a, 0x08, 0x6f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x18, 0x01, 0x20, 0x01,
+	0x28, 0x0e, 0x32, 0x19, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x45, 0x78, 0x69, 0x73,
+	0x74, 0x65, 0x6e, 0x63, 0x65, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x52, 0x08, 0x6f,
+	0x70, 0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x22, 0x69, 0x0a, 0x16, 0x53, 0x69, 0x6e, 0x67, 0x6c,
+	0x65, 0x53, 0x74, 0x72, 0x69, 0x6e, 0x67, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x69, 0x73, 0x6f,
+	0x6e, 0x12, 0x14, 0x0a, 0x05, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09,
+	0x52, 0x05, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x12, 0x39, 0x0a, 0x0a, 0x63, 0x6f, 0x6d, 0x70, 0x61,
+	0x72, 0x61, 0x74, 0x6f, 0x72, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0e, 0x32, 0x19, 0x2e, 0x63, 0x68,
+	0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x47, 0x65, 0x6e, 0x65, 0x72, 0x69, 0x63, 0x43, 0x6f, 0x6d, 0x70,
+	0x61, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x52, 0x0a, 0x63, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x61, 0x74,
+	0x6f, 0x72, 0x22, 0x67, 0x0a, 0x14, 0x53, 0x69, 0x6e, 0x67, 0x6c, 0x65, 0x42, 0x6f, 0x6f, 0x6c,
+	0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x69, 0x73, 0x6f, 0x6e, 0x12, 0x14, 0x0a, 0x05, 0x76, 0x61,
+	0x6c, 0x75, 0x65, 0x18, 0x01, 0x20, 0x01, 0x28, 0x08, 0x52, 0x05, 0x76, 0x61, 0x6c, 0x75, 0x65,
+	0x12, 0x39, 0x0a, 0x0a, 0x63, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x18, 0x02,
+	0x20, 0x01, 0x28, 0x0e, 0x32, 0x19, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x47, 0x65,
+	0x6e, 0x65, 0x72, 0x69, 0x63, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x52,
+	0x0a, 0x63, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x22, 0x66, 0x0a, 0x11, 0x49,
+	0x6e, 0x74, 0x4c, 0x69, 0x73, 0x74, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x69, 0x73, 0x6f, 0x6e,
+	0x12, 0x16, 0x0a, 0x06, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x73, 0x18, 0x01, 0x20, 0x03, 0x28, 0x03,
+	0x52, 0x06, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x73, 0x12, 0x39, 0x0a, 0x0d, 0x6c, 0x69, 0x73, 0x74,
+	0x5f, 0x6f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0e, 0x32,
+	0x14, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x4c, 0x69, 0x73, 0x74, 0x4f, 0x70, 0x65,
+	0x72, 0x61, 0x74, 0x6f, 0x72, 0x52, 0x0c, 0x6c, 0x69, 0x73, 0x74, 0x4f, 0x70, 0x65, 0x72, 0x61,
+	0x74, 0x6f, 0x72, 0x22, 0xce, 0x01, 0x0a, 0x13, 0x53, 0x69, 0x6e, 0x67, 0x6c, 0x65, 0x49, 0x6e,
+	0x74, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x69, 0x73, 0x6f, 0x6e, 0x12, 0x14, 0x0a, 0x05, 0x76,
+	0x61, 0x6c, 0x75, 0x65, 0x18, 0x01, 0x20, 0x01, 0x28, 0x03, 0x52, 0x05, 0x76, 0x61, 0x6c, 0x75,
+	0x65, 0x12, 0x4a, 0x0a, 0x12, 0x67, 0x65, 0x6e, 0x65, 0x72, 0x69, 0x63, 0x5f, 0x63, 0x6f, 0x6d,
+	0x70, 0x61, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0e, 0x32, 0x19, 0x2e,
+	0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x47, 0x65, 0x6e, 0x65, 0x72, 0x69, 0x63, 0x43, 0x6f,
+	0x6d, 0x70, 0x61, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x48, 0x00, 0x52, 0x11, 0x67, 0x65, 0x6e, 0x65,
+	0x72, 0x69, 0x63, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x12, 0x47, 0x0a,
+	0x11, 0x6e, 0x75, 0x6d, 0x62, 0x65, 0x72, 0x5f, 0x63, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x61, 0x74,
+	0x6f, 0x72, 0x18, 0x03, 0x20, 0x01, 0x28, 0x0e, 0x32, 0x18, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d,
+	0x61, 0x2e, 0x4e, 0x75, 0x6d, 0x62, 0x65, 0x72, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x61, 0x74,
+	0x6f, 0x72, 0x48, 0x00, 0x52, 0x10, 0x6e, 0x75, 0x6d, 0x62, 0x65, 0x72, 0x43, 0x6f, 0x6d, 0x70,
+	0x61, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x42, 0x0c, 0x0a, 0x0a, 0x63, 0x6f, 0x6d, 0x70, 0x61, 0x72,
+	0x61, 0x74, 0x6f, 0x72, 0x22, 0xd4, 0x01, 0x0a, 0x19, 0x53, 0x69, 0x6e, 0x67, 0x6c, 0x65, 0x54,
+	0x69, 0x6d, 0x65, 0x73, 0x74, 0x61, 0x6d, 0x70, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x69, 0x73,
+	0x6f, 0x6e, 0x12, 0x14, 0x0a, 0x05, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x18, 0x01, 0x20, 0x01, 0x28,
+	0x03, 0x52, 0x05, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x12, 0x4a, 0x0a, 0x12, 0x67, 0x65, 0x6e, 0x65,
+	0x72, 0x69, 0x63, 0x5f, 0x63, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x18, 0x02,
+	0x20, 0x01, 0x28, 0x0e, 0x32, 0x19, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x47, 0x65,
+	0x6e, 0x65, 0x72, 0x69, 0x63, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x48,
+	0x00, 0x52, 0x11, 0x67, 0x65, 0x6e, 0x65, 0x72, 0x69, 0x63, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72,
+	0x61, 0x74, 0x6f, 0x72, 0x12, 0x47, 0x0a, 0x11, 0x6e, 0x75, 0x6d, 0x62, 0x65, 0x72, 0x5f, 0x63,
+	0x6f, 0x6d, 0x70, 0x61, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x18, 0x03, 0x20, 0x01, 0x28, 0x0e, 0x32,
+	0x18, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x4e, 0x75, 0x6d, 0x62, 0x65, 0x72, 0x43,
+	0x6f, 0x6d, 0x70, 0x61, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x48, 0x00, 0x52, 0x10, 0x6e, 0x75, 0x6d,
+	0x62, 0x65, 0x72, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x42, 0x0c, 0x0a,
+	0x0a, 0x63, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x22, 0x69, 0x0a, 0x14, 0x44,
+	0x6f, 0x75, 0x62, 0x6c, 0x65, 0x4c, 0x69, 0x73, 0x74, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x69,
+	0x73, 0x6f, 0x6e, 0x12, 0x16, 0x0a, 0x06, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x73, 0x18, 0x01, 0x20,
+	0x03, 0x28, 0x01, 0x52, 0x06, 0x76, 0x61, 0x6c, 0x75, 0x65, 0x73, 0x12, 0x39, 0x0a, 0x0d, 0x6c,
+	0x69, 0x73, 0x74, 0x5f, 0x6f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x18, 0x02, 0x20, 0x01,
+	0x28, 0x0e, 0x32, 0x14, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x4c, 0x69, 0x73, 0x74,
+	0x4f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x52, 0x0c, 0x6c, 0x69, 0x73, 0x74, 0x4f, 0x70,
+	0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x22, 0x67, 0x0a, 0x12, 0x42, 0x6f, 0x6f, 0x6c, 0x4c, 0x69,
+	0x73, 0x74, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x69, 0x73, 0x6f, 0x6e, 0x12, 0x16, 0x0a, 0x06,
+	0x76, 0x61, 0x6c, 0x75, 0x65, 0x73, 0x18, 0x01, 0x20, 0x03, 0x28, 0x08, 0x52, 0x06, 0x76, 0x61,
+	0x6c, 0x75, 0x65, 0x73, 0x12, 0x39, 0x0a, 0x0d, 0x6c, 0x69, 0x73, 0x74, 0x5f, 0x6f, 0x70, 0x65,
+	0x72, 0x61, 0x74, 0x6f, 0x72, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0e, 0x32, 0x14, 0x2e, 0x63, 0x68,
+	0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x4c, 0x69, 0x73, 0x74, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f,
+	0x72, 0x52, 0x0c, 0x6c, 0x69, 0x73, 0x74, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x22,
+	0xd1, 0x01, 0x0a, 0x16, 0x53, 0x69, 0x6e, 0x67, 0x6c, 0x65, 0x44, 0x6f, 0x75, 0x62, 0x6c, 0x65,
+	0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x69, 0x73, 0x6f, 0x6e, 0x12, 0x14, 0x0a, 0x05, 0x76, 0x61,
+	0x6c, 0x75, 0x65, 0x18, 0x01, 0x20, 0x01, 0x28, 0x01, 0x52, 0x05, 0x76, 0x61, 0x6c, 0x75, 0x65,
+	0x12, 0x4a, 0x0a, 0x12, 0x67, 0x65, 0x6e, 0x65, 0x72, 0x69, 0x63, 0x5f, 0x63, 0x6f, 0x6d, 0x70,
+	0x61, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0e, 0x32, 0x19, 0x2e, 0x63,
+	0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x47, 0x65, 0x6e, 0x65, 0x72, 0x69, 0x63, 0x43, 0x6f, 0x6d,
+	0x70, 0x61, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x48, 0x00, 0x52, 0x11, 0x67, 0x65, 0x6e, 0x65, 0x72,
+	0x69, 0x63, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x12, 0x47, 0x0a, 0x11,
+	0x6e, 0x75, 0x6d, 0x62, 0x65, 0x72, 0x5f, 0x63, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x61, 0x74, 0x6f,
+	0x72, 0x18, 0x03, 0x20, 0x01, 0x28, 0x0e, 0x32, 0x18, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61,
+	0x2e, 0x4e, 0x75, 0x6d, 0x62, 0x65, 0x72, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x61, 0x74, 0x6f,
+	0x72, 0x48, 0x00, 0x52, 0x10, 0x6e, 0x75, 0x6d, 0x62, 0x65, 0x72, 0x43, 0x6f, 0x6d, 0x70, 0x61,
+	0x72, 0x61, 0x74, 0x6f, 0x72, 0x42, 0x0c, 0x0a, 0x0a, 0x63, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x61,
+	0x74, 0x6f, 0x72, 0x22, 0x86, 0x01, 0x0a, 0x17, 0x57, 0x61, 0x72, 0x6d, 0x55, 0x70, 0x43, 0x6f,
+	0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x12,
+	0x23, 0x0a, 0x0d, 0x63, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x5f, 0x69, 0x64,
+	0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x52, 0x0c, 0x63, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69,
+	0x6f, 0x6e, 0x49, 0x64, 0x12, 0x46, 0x0a, 0x0f, 0x76, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x5f,
+	0x63, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x1d, 0x2e,
+	0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x56, 0x65,
+	0x72, 0x73, 0x69, 0x6f, 0x6e, 0x43, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x52, 0x0e, 0x76, 0x65,
+	0x72, 0x73, 0x69, 0x6f, 0x6e, 0x43, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x22, 0x8f, 0x01, 0x0a,
+	0x18, 0x57, 0x61, 0x72, 0x6d, 0x55, 0x70, 0x43, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f,
+	0x6e, 0x52, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x12, 0x1d, 0x0a, 0x0a, 0x6e, 0x75, 0x6d,
+	0x5f, 0x62, 0x6c, 0x6f, 0x63, 0x6b, 0x73, 0x18, 0x01, 0x20, 0x01, 0x28, 0x0d, 0x52, 0x09, 0x6e,
+	0x75, 0x6d, 0x42, 0x6c, 0x6f, 0x63, 0x6b, 0x73, 0x12, 0x2e, 0x0a, 0x13, 0x76, 0x65, 0x63, 0x74,
+	0x6f, 0x72, 0x5f, 0x69, 0x6e, 0x64, 0x65, 0x78, 0x5f, 0x6c, 0x6f, 0x61, 0x64, 0x65, 0x64, 0x18,
+	0x02, 0x20, 0x01, 0x28, 0x08, 0x52, 0x11, 0x76, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x49, 0x6e, 0x64,
+	0x65, 0x78, 0x4c, 0x6f, 0x61, 0x64, 0x65, 0x64, 0x12, 0x24, 0x0a, 0x0e, 0x6e, 0x75, 0x6d, 0x5f,
+	0x68, 0x6f, 0x74, 0x5f, 0x62, 0x6c, 0x6f, 0x63, 0x6b, 0x73, 0x18, 0x03, 0x20, 0x01, 0x28, 0x0d,
+	0x52, 0x0c, 0x6e, 0x75, 0x6d, 0x48, 0x6f, 0x74, 0x42, 0x6c, 0x6f, 0x63, 0x6b, 0x73, 0x22, 0xbc,
+	0x01, 0x0a, 0x1e, 0x45, 0x78, 0x70, 0x6f, 0x72, 0x74, 0x43, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74,
+	0x69, 0x6f, 0x6e, 0x49, 0x6e, 0x64, 0x65, 0x78, 0x65, 0x73, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73,
+	0x74, 0x12, 0x23, 0x0a, 0x0d, 0x63, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x5f,
+	0x69, 0x64, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x52, 0x0c, 0x63, 0x6f, 0x6c, 0x6c, 0x65, 0x63,
+	0x74, 0x69, 0x6f, 0x6e, 0x49, 0x64, 0x12, 0x46, 0x0a, 0x0f, 0x76, 0x65, 0x72, 0x73, 0x69, 0x6f,
+	0x6e, 0x5f, 0x63, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0b, 0x32,
+	0x1d, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74,
+	0x56, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x43, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x52, 0x0e,
+	0x76, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x43, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x12, 0x2d,
+	0x0a, 0x12, 0x64, 0x65, 0x73, 0x74, 0x69, 0x6e, 0x61, 0x74, 0x69, 0x6f, 0x6e, 0x5f, 0x70, 0x72,
+	0x65, 0x66, 0x69, 0x78, 0x18, 0x03, 0x20, 0x01, 0x28, 0x09, 0x52, 0x11, 0x64, 0x65, 0x73, 0x74,
+	0x69, 0x6e, 0x61, 0x74, 0x69, 0x6f, 0x6e, 0x50, 0x72, 0x65, 0x66, 0x69, 0x78, 0x22, 0x6c, 0x0a,
+	0x1f, 0x45, 0x78, 0x70, 0x6f, 0x72, 0x74, 0x43, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f,
+	0x6e, 0x49, 0x6e, 0x64, 0x65, 0x78, 0x65, 0x73, 0x52, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65,
+	0x12, 0x1d, 0x0a, 0x0a, 0x6e, 0x75, 0x6d, 0x5f, 0x74, 0x6f, 0x6b, 0x65, 0x6e, 0x73, 0x18, 0x01,
+	0x20, 0x01, 0x28, 0x0d, 0x52, 0x09, 0x6e, 0x75, 0x6d, 0x54, 0x6f, 0x6b, 0x65, 0x6e, 0x73, 0x12,
+	0x2a, 0x0a, 0x11, 0x6e, 0x75, 0x6d, 0x5f, 0x70, 0x6f, 0x73, 0x74, 0x69, 0x6e, 0x67, 0x5f, 0x6c,
+	0x69, 0x73, 0x74, 0x73, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0d, 0x52, 0x0f, 0x6e, 0x75, 0x6d, 0x50,
+	0x6f, 0x73, 0x74, 0x69, 0x6e, 0x67, 0x4c, 0x69, 0x73, 0x74, 0x73, 0x22, 0xa6, 0x01, 0x0a, 0x1f,
+	0x43, 0x68, 0x65, 0x63, 0x6b, 0x43, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x49,
+	0x6e, 0x74, 0x65, 0x67, 0x72, 0x69, 0x74, 0x79, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x12,
+	0x23, 0x0a, 0x0d, 0x63, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x5f, 0x69, 0x64,
+	0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x52, 0x0c, 0x63, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69,
+	0x6f, 0x6e, 0x49, 0x64, 0x12, 0x46, 0x0a, 0x0f, 0x76, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x5f,
+	0x63, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x1d, 0x2e,
+	0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x56, 0x65,
+	0x72, 0x73, 0x69, 0x6f, 0x6e, 0x43, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x52, 0x0e, 0x76, 0x65,
+	0x72, 0x73, 0x69, 0x6f, 0x6e, 0x43, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x12, 0x16, 0x0a, 0x06,
+	0x72, 0x65, 0x70, 0x61, 0x69, 0x72, 0x18, 0x03, 0x20, 0x01, 0x28, 0x08, 0x52, 0x06, 0x72, 0x65,
+	0x70, 0x61, 0x69, 0x72, 0x22, 0x60, 0x0a, 0x0e, 0x49, 0x6e, 0x74, 0x65, 0x67, 0x72, 0x69, 0x74,
+	0x79, 0x49, 0x73, 0x73, 0x75, 0x65, 0x12, 0x2c, 0x0a, 0x05, 0x63, 0x68, 0x65, 0x63, 0x6b, 0x18,
+	0x01, 0x20, 0x01, 0x28, 0x0e, 0x32, 0x16, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x49,
+	0x6e, 0x74, 0x65, 0x67, 0x72, 0x69, 0x74, 0x79, 0x43, 0x68, 0x65, 0x63, 0x6b, 0x52, 0x05, 0x63,
+	0x68, 0x65, 0x63, 0x6b, 0x12, 0x20, 0x0a, 0x0b, 0x64, 0x65, 0x73, 0x63, 0x72, 0x69, 0x70, 0x74,
+	0x69, 0x6f, 0x6e, 0x18, 0x02, 0x20, 0x01, 0x28, 0x09, 0x52, 0x0b, 0x64, 0x65, 0x73, 0x63, 0x72,
+	0x69, 0x70, 0x74, 0x69, 0x6f, 0x6e, 0x22, 0x8d, 0x02, 0x0a, 0x20, 0x43, 0x68, 0x65, 0x63, 0x6b,
+	0x43, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x49, 0x6e, 0x74, 0x65, 0x67, 0x72,
+	0x69, 0x74, 0x79, 0x52, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x12, 0x1f, 0x0a, 0x0b, 0x6e,
+	0x75, 0x6d, 0x5f, 0x72, 0x65, 0x63, 0x6f, 0x72, 0x64, 0x73, 0x18, 0x01, 0x20, 0x01, 0x28, 0x0d,
+	0x52, 0x0a, 0x6e, 0x75, 0x6d, 0x52, 0x65, 0x63, 0x6f, 0x72, 0x64, 0x73, 0x12, 0x1d, 0x0a, 0x0a,
+	0x6e, 0x75, 0x6d, 0x5f, 0x69, 0x73, 0x73, 0x75, 0x65, 0x73, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0d,
+	0x52, 0x09, 0x6e, 0x75, 0x6d, 0x49, 0x73, 0x73, 0x75, 0x65, 0x73, 0x12, 0x2e, 0x0a, 0x06, 0x69,
+	0x73, 0x73, 0x75, 0x65, 0x73, 0x18, 0x03, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x16, 0x2e, 0x63, 0x68,
+	0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x49, 0x6e, 0x74, 0x65, 0x67, 0x72, 0x69, 0x74, 0x79, 0x49, 0x73,
+	0x73, 0x75, 0x65, 0x52, 0x06, 0x69, 0x73, 0x73, 0x75, 0x65, 0x73, 0x12, 0x2e, 0x0a, 0x13, 0x72,
+	0x65, 0x62, 0x75, 0x69, 0x6c, 0x74, 0x5f, 0x73, 0x65, 0x67, 0x6d, 0x65, 0x6e, 0x74, 0x5f, 0x69,
+	0x64, 0x73, 0x18, 0x04, 0x20, 0x03, 0x28, 0x09, 0x52, 0x11, 0x72, 0x65, 0x62, 0x75, 0x69, 0x6c,
+	0x74, 0x53, 0x65, 0x67, 0x6d, 0x65, 0x6e, 0x74, 0x49, 0x64, 0x73, 0x12, 0x32, 0x0a, 0x12, 0x63,
+	0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x5f, 0x76, 0x65, 0x72, 0x73, 0x69, 0x6f,
+	0x6e, 0x18, 0x05, 0x20, 0x01, 0x28, 0x05, 0x48, 0x00, 0x52, 0x11, 0x63, 0x6f, 0x6c, 0x6c, 0x65,
+	0x63, 0x74, 0x69, 0x6f, 0x6e, 0x56, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x88, 0x01, 0x01, 0x42,
+	0x15, 0x0a, 0x13, 0x5f, 0x63, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x5f, 0x76,
+	0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x22, 0x8e, 0x01, 0x0a, 0x1f, 0x56, 0x65, 0x72, 0x69, 0x66,
+	0x79, 0x43, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x43, 0x68, 0x65, 0x63, 0x6b,
+	0x73, 0x75, 0x6d, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x12, 0x23, 0x0a, 0x0d, 0x63, 0x6f,
+	0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x5f, 0x69, 0x64, 0x18, 0x01, 0x20, 0x01, 0x28,
+	0x09, 0x52, 0x0c, 0x63, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x49, 0x64, 0x12,
+	0x46, 0x0a, 0x0f, 0x76, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x5f, 0x63, 0x6f, 0x6e, 0x74, 0x65,
+	0x78, 0x74, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x1d, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d,
+	0x61, 0x2e, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x56, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e,
+	0x43, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x52, 0x0e, 0x76, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e,
+	0x43, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x22, 0xcc, 0x01, 0x0a, 0x20, 0x56, 0x65, 0x72, 0x69,
+	0x66, 0x79, 0x43, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x43, 0x68, 0x65, 0x63,
+	0x6b, 0x73, 0x75, 0x6d, 0x52, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x12, 0x2c, 0x0a, 0x0f,
+	0x73, 0x74, 0x6f, 0x72, 0x65, 0x64, 0x5f, 0x63, 0x68, 0x65, 0x63, 0x6b, 0x73, 0x75, 0x6d, 0x18,
+	0x01, 0x20, 0x01, 0x28, 0x09, 0x48, 0x00, 0x52, 0x0e, 0x73, 0x74, 0x6f, 0x72, 0x65, 0x64, 0x43,
+	0x68, 0x65, 0x63, 0x6b, 0x73, 0x75, 0x6d, 0x88, 0x01, 0x01, 0x12, 0x2b, 0x0a, 0x11, 0x63, 0x6f,
+	0x6d, 0x70, 0x75, 0x74, 0x65, 0x64, 0x5f, 0x63, 0x68, 0x65, 0x63, 0x6b, 0x73, 0x75, 0x6d, 0x18,
+	0x02, 0x20, 0x01, 0x28, 0x09, 0x52, 0x10, 0x63, 0x6f, 0x6d, 0x70, 0x75, 0x74, 0x65, 0x64, 0x43,
+	0x68, 0x65, 0x63, 0x6b, 0x73, 0x75, 0x6d, 0x12, 0x1f, 0x0a, 0x0b, 0x6e, 0x75, 0x6d, 0x5f, 0x72,
+	0x65, 0x63, 0x6f, 0x72, 0x64, 0x73, 0x18, 0x03, 0x20, 0x01, 0x28, 0x04, 0x52, 0x0a, 0x6e, 0x75,
+	0x6d, 0x52, 0x65, 0x63, 0x6f, 0x72, 0x64, 0x73, 0x12, 0x18, 0x0a, 0x07, 0x6d, 0x61, 0x74, 0x63,
+	0x68, 0x65, 0x73, 0x18, 0x04, 0x20, 0x01, 0x28, 0x08, 0x52, 0x07, 0x6d, 0x61, 0x74, 0x63, 0x68,
+	0x65, 0x73, 0x42, 0x12, 0x0a, 0x10, 0x5f, 0x73, 0x74, 0x6f, 0x72, 0x65, 0x64, 0x5f, 0x63, 0x68,
+	0x65, 0x63, 0x6b, 0x73, 0x75, 0x6d, 0x22, 0x54, 0x0a, 0x16, 0x4c, 0x69, 0x73, 0x74, 0x53, 0x6c,
+	0x6f, 0x77, 0x51, 0x75, 0x65, 0x72, 0x69, 0x65, 0x73, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74,
+	0x12, 0x28, 0x0a, 0x0d, 0x63, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x5f, 0x69,
+	0x64, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x48, 0x00, 0x52, 0x0c, 0x63, 0x6f, 0x6c, 0x6c, 0x65,
+	0x63, 0x74, 0x69, 0x6f, 0x6e, 0x49, 0x64, 0x88, 0x01, 0x01, 0x42, 0x10, 0x0a, 0x0e, 0x5f, 0x63,
+	0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x5f, 0x69, 0x64, 0x22, 0xd9, 0x02, 0x0a,
+	0x09, 0x53, 0x6c, 0x6f, 0x77, 0x51, 0x75, 0x65, 0x72, 0x79, 0x12, 0x23, 0x0a, 0x0d, 0x63, 0x6f,
+	0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x5f, 0x69, 0x64, 0x18, 0x01, 0x20, 0x01, 0x28,
+	0x09, 0x52, 0x0c, 0x63, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x49, 0x64, 0x12,
+	0x1d, 0x0a, 0x0a, 0x71, 0x75, 0x65, 0x72, 0x79, 0x5f, 0x74, 0x79, 0x70, 0x65, 0x18, 0x02, 0x20,
+	0x01, 0x28, 0x09, 0x52, 0x09, 0x71, 0x75, 0x65, 0x72, 0x79, 0x54, 0x79, 0x70, 0x65, 0x12, 0x29,
+	0x0a, 0x10, 0x66, 0x69, 0x6c, 0x74, 0x65, 0x72, 0x5f, 0x6f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f,
+	0x72, 0x73, 0x18, 0x03, 0x20, 0x03, 0x28, 0x09, 0x52, 0x0f, 0x66, 0x69, 0x6c, 0x74, 0x65, 0x72,
+	0x4f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x73, 0x12, 0x1c, 0x0a, 0x07, 0x6e, 0x75, 0x6d,
+	0x5f, 0x69, 0x64, 0x73, 0x18, 0x04, 0x20, 0x01, 0x28, 0x04, 0x48, 0x00, 0x52, 0x06, 0x6e, 0x75,
+	0x6d, 0x49, 0x64, 0x73, 0x88, 0x01, 0x01, 0x12, 0x19, 0x0a, 0x05, 0x6c, 0x69, 0x6d, 0x69, 0x74,
+	0x18, 0x05, 0x20, 0x01, 0x28, 0x0d, 0x48, 0x01, 0x52, 0x05, 0x6c, 0x69, 0x6d, 0x69, 0x74, 0x88,
+	0x01, 0x01, 0x12, 0x16, 0x0a, 0x06, 0x6f, 0x66, 0x66, 0x73, 0x65, 0x74, 0x18, 0x06, 0x20, 0x01,
+	0x28, 0x0d, 0x52, 0x06, 0x6f, 0x66, 0x66, 0x73, 0x65, 0x74, 0x12, 0x25, 0x0a, 0x0e, 0x65, 0x6c,
+	0x61, 0x70, 0x73, 0x65, 0x64, 0x5f, 0x6d, 0x69, 0x63, 0x72, 0x6f, 0x73, 0x18, 0x07, 0x20, 0x01,
+	0x28, 0x04, 0x52, 0x0d, 0x65, 0x6c, 0x61, 0x70, 0x73, 0x65, 0x64, 0x4d, 0x69, 0x63, 0x72, 0x6f,
+	0x73, 0x12, 0x28, 0x0a, 0x10, 0x6c, 0x6f, 0x67, 0x67, 0x65, 0x64, 0x5f, 0x61, 0x74, 0x5f, 0x6d,
+	0x69, 0x63, 0x72, 0x6f, 0x73, 0x18, 0x08, 0x20, 0x01, 0x28, 0x04, 0x52, 0x0e, 0x6c, 0x6f, 0x67,
+	0x67, 0x65, 0x64, 0x41, 0x74, 0x4d, 0x69, 0x63, 0x72, 0x6f, 0x73, 0x12, 0x25, 0x0a, 0x04, 0x70,
+	0x6c, 0x61, 0x6e, 0x18, 0x09, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x11, 0x2e, 0x63, 0x68, 0x72, 0x6f,
+	0x6d, 0x61, 0x2e, 0x51, 0x75, 0x65, 0x72, 0x79, 0x50, 0x6c, 0x61, 0x6e, 0x52, 0x04, 0x70, 0x6c,
+	0x61, 0x6e, 0x42, 0x0a, 0x0a, 0x08, 0x5f, 0x6e, 0x75, 0x6d, 0x5f, 0x69, 0x64, 0x73, 0x42, 0x08,
+	0x0a, 0x06, 0x5f, 0x6c, 0x69, 0x6d, 0x69, 0x74, 0x22, 0x46, 0x0a, 0x17, 0x4c, 0x69, 0x73, 0x74,
+	0x53, 0x6c, 0x6f, 0x77, 0x51, 0x75, 0x65, 0x72, 0x69, 0x65, 0x73, 0x52, 0x65, 0x73, 0x70, 0x6f,
+	0x6e, 0x73, 0x65, 0x12, 0x2b, 0x0a, 0x07, 0x71, 0x75, 0x65, 0x72, 0x69, 0x65, 0x73, 0x18, 0x01,
+	0x20, 0x03, 0x28, 0x0b, 0x32, 0x11, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x53, 0x6c,
+	0x6f, 0x77, 0x51, 0x75, 0x65, 0x72, 0x79, 0x52, 0x07, 0x71, 0x75, 0x65, 0x72, 0x69, 0x65, 0x73,
+	0x22, 0x2f, 0x0a, 0x17, 0x4c, 0x69, 0x73, 0x74, 0x43, 0x61, 0x63, 0x68, 0x65, 0x64, 0x42, 0x6c,
+	0x6f, 0x63, 0x6b, 0x73, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x12, 0x14, 0x0a, 0x05, 0x6c,
+	0x69, 0x6d, 0x69, 0x74, 0x18, 0x01, 0x20, 0x01, 0x28, 0x0d, 0x52, 0x05, 0x6c, 0x69, 0x6d, 0x69,
+	0x74, 0x22, 0x83, 0x01, 0x0a, 0x0b, 0x43, 0x61, 0x63, 0x68, 0x65, 0x64, 0x42, 0x6c, 0x6f, 0x63,
+	0x6b, 0x12, 0x19, 0x0a, 0x08, 0x62, 0x6c, 0x6f, 0x63, 0x6b, 0x5f, 0x69, 0x64, 0x18, 0x01, 0x20,
+	0x01, 0x28, 0x09, 0x52, 0x07, 0x62, 0x6c, 0x6f, 0x63, 0x6b, 0x49, 0x64, 0x12, 0x1d, 0x0a, 0x0a,
+	0x73, 0x69, 0x7a, 0x65, 0x5f, 0x62, 0x79, 0x74, 0x65, 0x73, 0x18, 0x02, 0x20, 0x01, 0x28, 0x04,
+	0x52, 0x09, 0x73, 0x69, 0x7a, 0x65, 0x42, 0x79, 0x74, 0x65, 0x73, 0x12, 0x28, 0x0a, 0x0d, 0x63,
+	0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x5f, 0x69, 0x64, 0x18, 0x03, 0x20, 0x01,
+	0x28, 0x09, 0x48, 0x00, 0x52, 0x0c, 0x63, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e,
+	0x49, 0x64, 0x88, 0x01, 0x01, 0x42, 0x10, 0x0a, 0x0e, 0x5f, 0x63, 0x6f, 0x6c, 0x6c, 0x65, 0x63,
+	0x74, 0x69, 0x6f, 0x6e, 0x5f, 0x69, 0x64, 0x22, 0xa7, 0x01, 0x0a, 0x18, 0x4c, 0x69, 0x73, 0x74,
+	0x43, 0x61, 0x63, 0x68, 0x65, 0x64, 0x42, 0x6c, 0x6f, 0x63, 0x6b, 0x73, 0x52, 0x65, 0x73, 0x70,
+	0x6f, 0x6e, 0x73, 0x65, 0x12, 0x2b, 0x0a, 0x06, 0x62, 0x6c, 0x6f, 0x63, 0x6b, 0x73, 0x18, 0x01,
+	0x20, 0x03, 0x28, 0x0b, 0x32, 0x13, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x43, 0x61,
+	0x63, 0x68, 0x65, 0x64, 0x42, 0x6c, 0x6f, 0x63, 0x6b, 0x52, 0x06, 0x62, 0x6c, 0x6f, 0x63, 0x6b,
+	0x73, 0x12, 0x1f, 0x0a, 0x0b, 0x75, 0x73, 0x61, 0x67, 0x65, 0x5f, 0x62, 0x79, 0x74, 0x65, 0x73,
+	0x18, 0x02, 0x20, 0x01, 0x28, 0x04, 0x52, 0x0a, 0x75, 0x73, 0x61, 0x67, 0x65, 0x42, 0x79, 0x74,
+	0x65, 0x73, 0x12, 0x2a, 0x0a, 0x0e, 0x63, 0x61, 0x70, 0x61, 0x63, 0x69, 0x74, 0x79, 0x5f, 0x62,
+	0x79, 0x74, 0x65, 0x73, 0x18, 0x03, 0x20, 0x01, 0x28, 0x04, 0x48, 0x00, 0x52, 0x0d, 0x63, 0x61,
+	0x70, 0x61, 0x63, 0x69, 0x74, 0x79, 0x42, 0x79, 0x74, 0x65, 0x73, 0x88, 0x01, 0x01, 0x42, 0x11,
+	0x0a, 0x0f, 0x5f, 0x63, 0x61, 0x70, 0x61, 0x63, 0x69, 0x74, 0x79, 0x5f, 0x62, 0x79, 0x74, 0x65,
+	0x73, 0x22, 0x3d, 0x0a, 0x16, 0x50, 0x61, 0x75, 0x73, 0x65, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x63,
+	0x74, 0x69, 0x6f, 0x6e, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x12, 0x23, 0x0a, 0x0d, 0x63,
+	0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x5f, 0x69, 0x64, 0x18, 0x01, 0x20, 0x01,
+	0x28, 0x09, 0x52, 0x0c, 0x63, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x49, 0x64,
+	0x22, 0x40, 0x0a, 0x17, 0x50, 0x61, 0x75, 0x73, 0x65, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x63, 0x74,
+	0x69, 0x6f, 0x6e, 0x52, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x12, 0x25, 0x0a, 0x0e, 0x61,
+	0x6c, 0x72, 0x65, 0x61, 0x64, 0x79, 0x5f, 0x70, 0x61, 0x75, 0x73, 0x65, 0x64, 0x18, 0x01, 0x20,
+	0x01, 0x28, 0x08, 0x52, 0x0d, 0x61, 0x6c, 0x72, 0x65, 0x61, 0x64, 0x79, 0x50, 0x61, 0x75, 0x73,
+	0x65, 0x64, 0x22, 0x3e, 0x0a, 0x17, 0x52, 0x65, 0x73, 0x75, 0x6d, 0x65, 0x43, 0x6f, 0x6d, 0x70,
+	0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x12, 0x23, 0x0a,
+	0x0d, 0x63, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x5f, 0x69, 0x64, 0x18, 0x01,
+	0x20, 0x01, 0x28, 0x09, 0x52, 0x0c, 0x63, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e,
+	0x49, 0x64, 0x22, 0x39, 0x0a, 0x18, 0x52, 0x65, 0x73, 0x75, 0x6d, 0x65, 0x43, 0x6f, 0x6d, 0x70,
+	0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x52, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x12, 0x1d,
+	0x0a, 0x0a, 0x77, 0x61, 0x73, 0x5f, 0x70, 0x61, 0x75, 0x73, 0x65, 0x64, 0x18, 0x01, 0x20, 0x01,
+	0x28, 0x08, 0x52, 0x09, 0x77, 0x61, 0x73, 0x50, 0x61, 0x75, 0x73, 0x65, 0x64, 0x22, 0xb1, 0x01,
+	0x0a, 0x11, 0x47, 0x65, 0x74, 0x56, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x73, 0x52, 0x65, 0x71, 0x75,
+	0x65, 0x73, 0x74, 0x12, 0x10, 0x0a, 0x03, 0x69, 0x64, 0x73, 0x18, 0x01, 0x20, 0x03, 0x28, 0x09,
+	0x52, 0x03, 0x69, 0x64, 0x73, 0x12, 0x1d, 0x0a, 0x0a, 0x73, 0x65, 0x67, 0x6d, 0x65, 0x6e, 0x74,
+	0x5f, 0x69, 0x64, 0x18, 0x02, 0x20, 0x01, 0x28, 0x09, 0x52, 0x09, 0x73, 0x65, 0x67, 0x6d, 0x65,
+	0x6e, 0x74, 0x49, 0x64, 0x12, 0x23, 0x0a, 0x0d, 0x63, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69,
+	0x6f, 0x6e, 0x5f, 0x69, 0x64, 0x18, 0x03, 0x20, 0x01, 0x28, 0x09, 0x52, 0x0c, 0x63, 0x6f, 0x6c,
+	0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x49, 0x64, 0x12, 0x46, 0x0a, 0x0f, 0x76, 0x65, 0x72,
+	0x73, 0x69, 0x6f, 0x6e, 0x5f, 0x63, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x18, 0x04, 0x20, 0x01,
+	0x28, 0x0b, 0x32, 0x1d, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x52, 0x65, 0x71, 0x75,
+	0x65, 0x73, 0x74, 0x56, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x43, 0x6f, 0x6e, 0x74, 0x65, 0x78,
+	0x74, 0x52, 0x0e, 0x76, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x43, 0x6f, 0x6e, 0x74, 0x65, 0x78,
+	0x74, 0x22, 0x4d, 0x0a, 0x12, 0x47, 0x65, 0x74, 0x56, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x73, 0x52,
+	0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x12, 0x37, 0x0a, 0x07, 0x72, 0x65, 0x63, 0x6f, 0x72,
+	0x64, 0x73, 0x18, 0x01, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x1d, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d,
+	0x61, 0x2e, 0x56, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x45, 0x6d, 0x62, 0x65, 0x64, 0x64, 0x69, 0x6e,
+	0x67, 0x52, 0x65, 0x63, 0x6f, 0x72, 0x64, 0x52, 0x07, 0x72, 0x65, 0x63, 0x6f, 0x72, 0x64, 0x73,
+	0x22, 0x4f, 0x0a, 0x15, 0x56, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x45, 0x6d, 0x62, 0x65, 0x64, 0x64,
+	0x69, 0x6e, 0x67, 0x52, 0x65, 0x63, 0x6f, 0x72, 0x64, 0x12, 0x0e, 0x0a, 0x02, 0x69, 0x64, 0x18,
+	0x01, 0x20, 0x01, 0x28, 0x09, 0x52, 0x02, 0x69, 0x64, 0x12, 0x26, 0x0a, 0x06, 0x76, 0x65, 0x63,
+	0x74, 0x6f, 0x72, 0x18, 0x03, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x0e, 0x2e, 0x63, 0x68, 0x72, 0x6f,
+	0x6d, 0x61, 0x2e, 0x56, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x52, 0x06, 0x76, 0x65, 0x63, 0x74, 0x6f,
+	0x72, 0x22, 0xa9, 0x04, 0x0a, 0x13, 0x51, 0x75, 0x65, 0x72, 0x79, 0x56, 0x65, 0x63, 0x74, 0x6f,
+	0x72, 0x73, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x12, 0x28, 0x0a, 0x07, 0x76, 0x65, 0x63,
+	0x74, 0x6f, 0x72, 0x73, 0x18, 0x01, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x0e, 0x2e, 0x63, 0x68, 0x72,
+	0x6f, 0x6d, 0x61, 0x2e, 0x56, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x52, 0x07, 0x76, 0x65, 0x63, 0x74,
+	0x6f, 0x72, 0x73, 0x12, 0x0c, 0x0a, 0x01, 0x6b, 0x18, 0x02, 0x20, 0x01, 0x28, 0x05, 0x52, 0x01,
+	0x6b, 0x12, 0x1f, 0x0a, 0x0b, 0x61, 0x6c, 0x6c, 0x6f, 0x77, 0x65, 0x64, 0x5f, 0x69, 0x64, 0x73,
+	0x18, 0x03, 0x20, 0x03, 0x28, 0x09, 0x52, 0x0a, 0x61, 0x6c, 0x6c, 0x6f, 0x77, 0x65, 0x64, 0x49,
+	0x64, 0x73, 0x12, 0x2d, 0x0a, 0x12, 0x69, 0x6e, 0x63, 0x6c, 0x75, 0x64, 0x65, 0x5f, 0x65, 0x6d,
+	0x62, 0x65, 0x64, 0x64, 0x69, 0x6e, 0x67, 0x73, 0x18, 0x04, 0x20, 0x01, 0x28, 0x08, 0x52, 0x11,
+	0x69, 0x6e, 0x63, 0x6c, 0x75, 0x64, 0x65, 0x45, 0x6d, 0x62, 0x65, 0x64, 0x64, 0x69, 0x6e, 0x67,
+	0x73, 0x12, 0x1d, 0x0a, 0x0a, 0x73, 0x65, 0x67, 0x6d, 0x65, 0x6e, 0x74, 0x5f, 0x69, 0x64, 0x18,
+	0x05, 0x20, 0x01, 0x28, 0x09, 0x52, 0x09, 0x73, 0x65, 0x67, 0x6d, 0x65, 0x6e, 0x74, 0x49, 0x64,
+	0x12, 0x23, 0x0a, 0x0d, 0x63, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x5f, 0x69,
+	0x64, 0x18, 0x06, 0x20, 0x01, 0x28, 0x09, 0x52, 0x0c, 0x63, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74,
+	0x69, 0x6f, 0x6e, 0x49, 0x64, 0x12, 0x46, 0x0a, 0x0f, 0x76, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e,
+	0x5f, 0x63, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x18, 0x07, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x1d,
+	0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x56,
+	0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x43, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x52, 0x0e, 0x76,
+	0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x43, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x12, 0x18, 0x0a,
+	0x07, 0x65, 0x78, 0x70, 0x6c, 0x61, 0x69, 0x6e, 0x18, 0x08, 0x20, 0x01, 0x28, 0x08, 0x52, 0x07,
+	0x65, 0x78, 0x70, 0x6c, 0x61, 0x69, 0x6e, 0x12, 0x40, 0x0a, 0x0c, 0x61, 0x63, 0x63, 0x75, 0x6d,
+	0x75, 0x6c, 0x61, 0x74, 0x69, 0x6f, 0x6e, 0x18, 0x09, 0x20, 0x01, 0x28, 0x0e, 0x32, 0x1c, 0x2e,
+	0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x44, 0x69, 0x73, 0x74, 0x61, 0x6e, 0x63, 0x65, 0x41,
+	0x63, 0x63, 0x75, 0x6d, 0x75, 0x6c, 0x61, 0x74, 0x69, 0x6f, 0x6e, 0x52, 0x0c, 0x61, 0x63, 0x63,
+	0x75, 0x6d, 0x75, 0x6c, 0x61, 0x74, 0x69, 0x6f, 0x6e, 0x12, 0x26, 0x0a, 0x0c, 0x6d, 0x61, 0x78,
+	0x5f, 0x64, 0x69, 0x73, 0x74, 0x61, 0x6e, 0x63, 0x65, 0x18, 0x0a, 0x20, 0x01, 0x28, 0x02, 0x48,
+	0x00, 0x52, 0x0b, 0x6d, 0x61, 0x78, 0x44, 0x69, 0x73, 0x74, 0x61, 0x6e, 0x63, 0x65, 0x88, 0x01,
+	0x01, 0x12, 0x20, 0x0a, 0x09, 0x6d, 0x69, 0x6e, 0x5f, 0x73, 0x63, 0x6f, 0x72, 0x65, 0x18, 0x0b,
+	0x20, 0x01, 0x28, 0x02, 0x48, 0x01, 0x52, 0x08, 0x6d, 0x69, 0x6e, 0x53, 0x63, 0x6f, 0x72, 0x65,
+	0x88, 0x01, 0x01, 0x12, 0x27, 0x0a, 0x0d, 0x74, 0x69, 0x6d, 0x65, 0x5f, 0x6c, 0x69, 0x6d, 0x69,
+	0x74, 0x5f, 0x6d, 0x73, 0x18, 0x0c, 0x20, 0x01, 0x28, 0x0d, 0x48, 0x02, 0x52, 0x0b, 0x74, 0x69,
+	0x6d, 0x65, 0x4c, 0x69, 0x6d, 0x69, 0x74, 0x4d, 0x73, 0x88, 0x01, 0x01, 0x42, 0x0f, 0x0a, 0x0d,
+	0x5f, 0x6d, 0x61, 0x78, 0x5f, 0x64, 0x69, 0x73, 0x74, 0x61, 0x6e, 0x63, 0x65, 0x42, 0x0c, 0x0a,
+	0x0a, 0x5f, 0x6d, 0x69, 0x6e, 0x5f, 0x73, 0x63, 0x6f, 0x72, 0x65, 0x42, 0x10, 0x0a, 0x0e, 0x5f,
+	0x74, 0x69, 0x6d, 0x65, 0x5f, 0x6c, 0x69, 0x6d, 0x69, 0x74, 0x5f, 0x6d, 0x73, 0x22, 0xbb, 0x01,
+	0x0a, 0x14, 0x51, 0x75, 0x65, 0x72, 0x79, 0x56, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x73, 0x52, 0x65,
+	0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x12, 0x34, 0x0a, 0x07, 0x72, 0x65, 0x73, 0x75, 0x6c, 0x74,
+	0x73, 0x18, 0x01, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x1a, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61,
+	0x2e, 0x56, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x51, 0x75, 0x65, 0x72, 0x79, 0x52, 0x65, 0x73, 0x75,
+	0x6c, 0x74, 0x73, 0x52, 0x07, 0x72, 0x65, 0x73, 0x75, 0x6c, 0x74, 0x73, 0x12, 0x1a, 0x0a, 0x08,
+	0x64, 0x65, 0x67, 0x72, 0x61, 0x64, 0x65, 0x64, 0x18, 0x02, 0x20, 0x01, 0x28, 0x08, 0x52, 0x08,
+	0x64, 0x65, 0x67, 0x72, 0x61, 0x64, 0x65, 0x64, 0x12, 0x2a, 0x0a, 0x04, 0x70, 0x6c, 0x61, 0x6e,
+	0x18, 0x03, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x11, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e,
+	0x51, 0x75, 0x65, 0x72, 0x79, 0x50, 0x6c, 0x61, 0x6e, 0x48, 0x00, 0x52, 0x04, 0x70, 0x6c, 0x61,
+	0x6e, 0x88, 0x01, 0x01, 0x12, 0x1c, 0x0a, 0x09, 0x74, 0x72, 0x75, 0x6e, 0x63, 0x61, 0x74, 0x65,
+	0x64, 0x18, 0x04, 0x20, 0x01, 0x28, 0x08, 0x52, 0x09, 0x74, 0x72, 0x75, 0x6e, 0x63, 0x61, 0x74,
+	0x65, 0x64, 0x42, 0x07, 0x0a, 0x05, 0x5f, 0x70, 0x6c, 0x61, 0x6e, 0x22, 0x49, 0x0a, 0x12, 0x56,
+	0x65, 0x63, 0x74, 0x6f, 0x72, 0x51, 0x75, 0x65, 0x72, 0x79, 0x52, 0x65, 0x73, 0x75, 0x6c, 0x74,
+	0x73, 0x12, 0x33, 0x0a, 0x07, 0x72, 0x65, 0x73, 0x75, 0x6c, 0x74, 0x73, 0x18, 0x01, 0x20, 0x03,
+	0x28, 0x0b, 0x32, 0x19, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x56, 0x65, 0x63, 0x74,
+	0x6f, 0x72, 0x51, 0x75, 0x65, 0x72, 0x79, 0x52, 0x65, 0x73, 0x75, 0x6c, 0x74, 0x52, 0x07, 0x72,
+	0x65, 0x73, 0x75, 0x6c, 0x74, 0x73, 0x22, 0x77, 0x0a, 0x11, 0x56, 0x65, 0x63, 0x74, 0x6f, 0x72,
+	0x51, 0x75, 0x65, 0x72, 0x79, 0x52, 0x65, 0x73, 0x75, 0x6c, 0x74, 0x12, 0x0e, 0x0a, 0x02, 0x69,
+	0x64, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x52, 0x02, 0x69, 0x64, 0x12, 0x1a, 0x0a, 0x08, 0x64,
+	0x69, 0x73, 0x74, 0x61, 0x6e, 0x63, 0x65, 0x18, 0x03, 0x20, 0x01, 0x28, 0x02, 0x52, 0x08, 0x64,
+	0x69, 0x73, 0x74, 0x61, 0x6e, 0x63, 0x65, 0x12, 0x2b, 0x0a, 0x06, 0x76, 0x65, 0x63, 0x74, 0x6f,
+	0x72, 0x18, 0x04, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x0e, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61,
+	0x2e, 0x56, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x48, 0x00, 0x52, 0x06, 0x76, 0x65, 0x63, 0x74, 0x6f,
+	0x72, 0x88, 0x01, 0x01, 0x42, 0x09, 0x0a, 0x07, 0x5f, 0x76, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x22,
+	0x57, 0x0a, 0x13, 0x53, 0x69, 0x67, 0x6e, 0x65, 0x64, 0x52, 0x6f, 0x61, 0x72, 0x69, 0x6e, 0x67,
+	0x42, 0x69, 0x74, 0x6d, 0x61, 0x70, 0x12, 0x1a, 0x0a, 0x07, 0x69, 0x6e, 0x63, 0x6c, 0x75, 0x64,
+	0x65, 0x18, 0x01, 0x20, 0x01, 0x28, 0x0c, 0x48, 0x00, 0x52, 0x07, 0x69, 0x6e, 0x63, 0x6c, 0x75,
+	0x64, 0x65, 0x12, 0x1a, 0x0a, 0x07, 0x65, 0x78, 0x63, 0x6c, 0x75, 0x64, 0x65, 0x18, 0x02, 0x20,
+	0x01, 0x28, 0x0c, 0x48, 0x00, 0x52, 0x07, 0x65, 0x78, 0x63, 0x6c, 0x75, 0x64, 0x65, 0x42, 0x08,
+	0x0a, 0x06, 0x62, 0x69, 0x74, 0x6d, 0x61, 0x70, 0x22, 0x9c, 0x01, 0x0a, 0x0c, 0x46, 0x69, 0x6c,
+	0x74, 0x65, 0x72, 0x4f, 0x75, 0x74, 0x70, 0x75, 0x74, 0x12, 0x41, 0x0a, 0x0e, 0x6c, 0x6f, 0x67,
+	0x5f, 0x6f, 0x66, 0x66, 0x73, 0x65, 0x74, 0x5f, 0x69, 0x64, 0x73, 0x18, 0x01, 0x20, 0x01, 0x28,
+	0x0b, 0x32, 0x1b, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x53, 0x69, 0x67, 0x6e, 0x65,
+	0x64, 0x52, 0x6f, 0x61, 0x72, 0x69, 0x6e, 0x67, 0x42, 0x69, 0x74, 0x6d, 0x61, 0x70, 0x52, 0x0c,
+	0x6c, 0x6f, 0x67, 0x4f, 0x66, 0x66, 0x73, 0x65, 0x74, 0x49, 0x64, 0x73, 0x12, 0x49, 0x0a, 0x12,
+	0x63, 0x6f, 0x6d, 0x70, 0x61, 0x63, 0x74, 0x5f, 0x6f, 0x66, 0x66, 0x73, 0x65, 0x74, 0x5f, 0x69,
+	0x64, 0x73, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x1b, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d,
+	0x61, 0x2e, 0x53, 0x69, 0x67, 0x6e, 0x65, 0x64, 0x52, 0x6f, 0x61, 0x72, 0x69, 0x6e, 0x67, 0x42,
+	0x69, 0x74, 0x6d, 0x61, 0x70, 0x52, 0x10, 0x63, 0x6f, 0x6d, 0x70, 0x61, 0x63, 0x74, 0x4f, 0x66,
+	0x66, 0x73, 0x65, 0x74, 0x49, 0x64, 0x73, 0x22, 0x2c, 0x0a, 0x0b, 0x4c, 0x69, 0x6d, 0x69, 0x74,
+	0x4f, 0x75, 0x74, 0x70, 0x75, 0x74, 0x12, 0x1d, 0x0a, 0x0a, 0x6f, 0x66, 0x66, 0x73, 0x65, 0x74,
+	0x5f, 0x69, 0x64, 0x73, 0x18, 0x01, 0x20, 0x01, 0x28, 0x0c, 0x52, 0x09, 0x6f, 0x66, 0x66, 0x73,
+	0x65, 0x74, 0x49, 0x64, 0x73, 0x2a, 0x38, 0x0a, 0x09, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x69,
+	0x6f, 0x6e, 0x12, 0x07, 0x0a, 0x03, 0x41, 0x44, 0x44, 0x10, 0x00, 0x12, 0x0a, 0x0a, 0x06, 0x55,
+	0x50, 0x44, 0x41, 0x54, 0x45, 0x10, 0x01, 0x12, 0x0a, 0x0a, 0x06, 0x55, 0x50, 0x53, 0x45, 0x52,
+	0x54, 0x10, 0x02, 0x12, 0x0a, 0x0a, 0x06, 0x44, 0x45, 0x4c, 0x45, 0x54, 0x45, 0x10, 0x03, 0x2a,
+	0x28, 0x0a, 0x0e, 0x53, 0x63, 0x61, 0x6c, 0x61, 0x72, 0x45, 0x6e, 0x63, 0x6f, 0x64, 0x69, 0x6e,
+	0x67, 0x12, 0x0b, 0x0a, 0x07, 0x46, 0x4c, 0x4f, 0x41, 0x54, 0x33, 0x32, 0x10, 0x00, 0x12, 0x09,
+	0x0a, 0x05, 0x49, 0x4e, 0x54, 0x33, 0x32, 0x10, 0x01, 0x2a, 0x40, 0x0a, 0x0c, 0x53, 0x65, 0x67,
+	0x6d, 0x65, 0x6e, 0x74, 0x53, 0x63, 0x6f, 0x70, 0x65, 0x12, 0x0a, 0x0a, 0x06, 0x56, 0x45, 0x43,
+	0x54, 0x4f, 0x52, 0x10, 0x00, 0x12, 0x0c, 0x0a, 0x08, 0x4d, 0x45, 0x54, 0x41, 0x44, 0x41, 0x54,
+	0x41, 0x10, 0x01, 0x12, 0x0a, 0x0a, 0x06, 0x52, 0x45, 0x43, 0x4f, 0x52, 0x44, 0x10, 0x02, 0x12,
+	0x0a, 0x0a, 0x06, 0x53, 0x51, 0x4c, 0x49, 0x54, 0x45, 0x10, 0x03, 0x2a, 0x5b, 0x0a, 0x15, 0x57,
+	0x68, 0x65, 0x72, 0x65, 0x44, 0x6f, 0x63, 0x75, 0x6d, 0x65, 0x6e, 0x74, 0x4f, 0x70, 0x65, 0x72,
+	0x61, 0x74, 0x6f, 0x72, 0x12, 0x0c, 0x0a, 0x08, 0x43, 0x4f, 0x4e, 0x54, 0x41, 0x49, 0x4e, 0x53,
+	0x10, 0x00, 0x12, 0x10, 0x0a, 0x0c, 0x4e, 0x4f, 0x54, 0x5f, 0x43, 0x4f, 0x4e, 0x54, 0x41, 0x49,
+	0x4e, 0x53, 0x10, 0x01, 0x12, 0x08, 0x0a, 0x04, 0x4e, 0x45, 0x41, 0x52, 0x10, 0x02, 0x12, 0x09,
+	0x0a, 0x05, 0x52, 0x45, 0x47, 0x45, 0x58, 0x10, 0x03, 0x12, 0x0d, 0x0a, 0x09, 0x4e, 0x4f, 0x54,
+	0x5f, 0x52, 0x45, 0x47, 0x45, 0x58, 0x10, 0x04, 0x2a, 0x22, 0x0a, 0x0f, 0x42, 0x6f, 0x6f, 0x6c,
+	0x65, 0x61, 0x6e, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x12, 0x07, 0x0a, 0x03, 0x41,
+	0x4e, 0x44, 0x10, 0x00, 0x12, 0x06, 0x0a, 0x02, 0x4f, 0x52, 0x10, 0x01, 0x2a, 0x2c, 0x0a, 0x11,
+	0x45, 0x78, 0x69, 0x73, 0x74, 0x65, 0x6e, 0x63, 0x65, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f,
+	0x72, 0x12, 0x0a, 0x0a, 0x06, 0x45, 0x58, 0x49, 0x53, 0x54, 0x53, 0x10, 0x00, 0x12, 0x0b, 0x0a,
+	0x07, 0x49, 0x53, 0x5f, 0x4e, 0x55, 0x4c, 0x4c, 0x10, 0x01, 0x2a, 0x1f, 0x0a, 0x0c, 0x4c, 0x69,
+	0x73, 0x74, 0x4f, 0x70, 0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x12, 0x06, 0x0a, 0x02, 0x49, 0x4e,
+	0x10, 0x00, 0x12, 0x07, 0x0a, 0x03, 0x4e, 0x49, 0x4e, 0x10, 0x01, 0x2a, 0x69, 0x0a, 0x11, 0x47,
+	0x65, 0x6e, 0x65, 0x72, 0x69, 0x63, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x61, 0x74, 0x6f, 0x72,
+	0x12, 0x06, 0x0a, 0x02, 0x45, 0x51, 0x10, 0x00, 0x12, 0x06, 0x0a, 0x02, 0x4e, 0x45, 0x10, 0x01,
+	0x12, 0x11, 0x0a, 0x0d, 0x4c, 0x49, 0x53, 0x54, 0x5f, 0x43, 0x4f, 0x4e, 0x54, 0x41, 0x49, 0x4e,
+	0x53, 0x10, 0x02, 0x12, 0x15, 0x0a, 0x11, 0x4c, 0x49, 0x53, 0x54, 0x5f, 0x4e, 0x4f, 0x54, 0x5f,
+	0x43, 0x4f, 0x4e, 0x54, 0x41, 0x49, 0x4e, 0x53, 0x10, 0x03, 0x12, 0x09, 0x0a, 0x05, 0x45, 0x51,
+	0x5f, 0x43, 0x49, 0x10, 0x04, 0x12, 0x0f, 0x0a, 0x0b, 0x53, 0x54, 0x41, 0x52, 0x54, 0x53, 0x5f,
+	0x57, 0x49, 0x54, 0x48, 0x10, 0x05, 0x2a, 0x34, 0x0a, 0x10, 0x4e, 0x75, 0x6d, 0x62, 0x65, 0x72,
+	0x43, 0x6f, 0x6d, 0x70, 0x61, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x12, 0x06, 0x0a, 0x02, 0x47, 0x54,
+	0x10, 0x00, 0x12, 0x07, 0x0a, 0x03, 0x47, 0x54, 0x45, 0x10, 0x01, 0x12, 0x06, 0x0a, 0x02, 0x4c,
+	0x54, 0x10, 0x02, 0x12, 0x07, 0x0a, 0x03, 0x4c, 0x54, 0x45, 0x10, 0x03, 0x2a, 0x5d, 0x0a, 0x0e,
+	0x49, 0x6e, 0x74, 0x65, 0x67, 0x72, 0x69, 0x74, 0x79, 0x43, 0x68, 0x65, 0x63, 0x6b, 0x12, 0x18,
+	0x0a, 0x14, 0x4f, 0x46, 0x46, 0x53, 0x45, 0x54, 0x5f, 0x49, 0x44, 0x5f, 0x43, 0x4f, 0x4e, 0x54,
+	0x49, 0x4e, 0x55, 0x49, 0x54, 0x59, 0x10, 0x00, 0x12, 0x16, 0x0a, 0x12, 0x4d, 0x45, 0x54, 0x41,
+	0x44, 0x41, 0x54, 0x41, 0x5f, 0x41, 0x47, 0x52, 0x45, 0x45, 0x4d, 0x45, 0x4e, 0x54, 0x10, 0x01,
+	0x12, 0x19, 0x0a, 0x15, 0x56, 0x45, 0x43, 0x54, 0x4f, 0x52, 0x5f, 0x49, 0x4e, 0x44, 0x45, 0x58,
+	0x5f, 0x43, 0x4f, 0x56, 0x45, 0x52, 0x41, 0x47, 0x45, 0x10, 0x02, 0x2a, 0x3e, 0x0a, 0x14, 0x44,
+	0x69, 0x73, 0x74, 0x61, 0x6e, 0x63, 0x65, 0x41, 0x63, 0x63, 0x75, 0x6d, 0x75, 0x6c, 0x61, 0x74,
+	0x69, 0x6f, 0x6e, 0x12, 0x12, 0x0a, 0x0e, 0x41, 0x43, 0x43, 0x55, 0x4d, 0x55, 0x4c, 0x41, 0x54,
+	0x45, 0x5f, 0x46, 0x33, 0x32, 0x10, 0x00, 0x12, 0x12, 0x0a, 0x0e, 0x41, 0x43, 0x43, 0x55, 0x4d,
+	0x55, 0x4c, 0x41, 0x54, 0x45, 0x5f, 0x46, 0x36, 0x34, 0x10, 0x01, 0x32, 0xa2, 0x03, 0x0a, 0x0e,
+	0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x52, 0x65, 0x61, 0x64, 0x65, 0x72, 0x12, 0x4e,
+	0x0a, 0x0d, 0x51, 0x75, 0x65, 0x72, 0x79, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x12,
+	0x1c, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x51, 0x75, 0x65, 0x72, 0x79, 0x4d, 0x65,
+	0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x1a, 0x1d, 0x2e,
+	0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x51, 0x75, 0x65, 0x72, 0x79, 0x4d, 0x65, 0x74, 0x61,
+	0x64, 0x61, 0x74, 0x61, 0x52, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x22, 0x00, 0x12, 0x4b,
+	0x0a, 0x0c, 0x43, 0x6f, 0x75, 0x6e, 0x74, 0x52, 0x65, 0x63, 0x6f, 0x72, 0x64, 0x73, 0x12, 0x1b,
+	0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x43, 0x6f, 0x75, 0x6e, 0x74, 0x52, 0x65, 0x63,
+	0x6f, 0x72, 0x64, 0x73, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x1a, 0x1c, 0x2e, 0x63, 0x68,
+	0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x43, 0x6f, 0x75, 0x6e, 0x74, 0x52, 0x65, 0x63, 0x6f, 0x72, 0x64,
+	0x73, 0x52, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x22, 0x00, 0x12, 0x4e, 0x0a, 0x0d, 0x47,
+	0x65, 0x74, 0x45, 0x6d, 0x62, 0x65, 0x64, 0x64, 0x69, 0x6e, 0x67, 0x73, 0x12, 0x1c, 0x2e, 0x63,
+	0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x47, 0x65, 0x74, 0x45, 0x6d, 0x62, 0x65, 0x64, 0x64, 0x69,
+	0x6e, 0x67, 0x73, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x1a, 0x1d, 0x2e, 0x63, 0x68, 0x72,
+	0x6f, 0x6d, 0x61, 0x2e, 0x47, 0x65, 0x74, 0x45, 0x6d, 0x62, 0x65, 0x64, 0x64, 0x69, 0x6e, 0x67,
+	0x73, 0x52, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x22, 0x00, 0x12, 0x51, 0x0a, 0x0e, 0x53,
+	0x74, 0x72, 0x65, 0x61, 0x6d, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x12, 0x1c, 0x2e,
+	0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x51, 0x75, 0x65, 0x72, 0x79, 0x4d, 0x65, 0x74, 0x61,
+	0x64, 0x61, 0x74, 0x61, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x1a, 0x1d, 0x2e, 0x63, 0x68,
+	0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x51, 0x75, 0x65, 0x72, 0x79, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61,
+	0x74, 0x61, 0x52, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x22, 0x00, 0x30, 0x01, 0x12, 0x50,
+	0x0a, 0x0d, 0x53, 0x74, 0x72, 0x65, 0x61, 0x6d, 0x43, 0x68, 0x61, 0x6e, 0x67, 0x65, 0x73, 0x12,
+	0x1c, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x53, 0x74, 0x72, 0x65, 0x61, 0x6d, 0x43,
+	0x68, 0x61, 0x6e, 0x67, 0x65, 0x73, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x1a, 0x1d, 0x2e,
+	0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x53, 0x74, 0x72, 0x65, 0x61, 0x6d, 0x43, 0x68, 0x61,
+	0x6e, 0x67, 0x65, 0x73, 0x52, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x22, 0x00, 0x30, 0x01,
+	0x32, 0xe4, 0x04, 0x0a, 0x0a, 0x51, 0x75, 0x65, 0x72, 0x79, 0x41, 0x64, 0x6d, 0x69, 0x6e, 0x12,
+	0x57, 0x0a, 0x10, 0x57, 0x61, 0x72, 0x6d, 0x55, 0x70, 0x43, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74,
+	0x69, 0x6f, 0x6e, 0x12, 0x1f, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x57, 0x61, 0x72,
+	0x6d, 0x55, 0x70, 0x43, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x52, 0x65, 0x71,
+	0x75, 0x65, 0x73, 0x74, 0x1a, 0x20, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x57, 0x61,
+	0x72, 0x6d, 0x55, 0x70, 0x43, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x52, 0x65,
+	0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x22, 0x00, 0x12, 0x6c, 0x0a, 0x17, 0x45, 0x78, 0x70, 0x6f,
+	0x72, 0x74, 0x43, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x49, 0x6e, 0x64, 0x65,
+	0x78, 0x65, 0x73, 0x12, 0x26, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x45, 0x78, 0x70,
+	0x6f, 0x72, 0x74, 0x43, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x49, 0x6e, 0x64,
+	0x65, 0x78, 0x65, 0x73, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x1a, 0x27, 0x2e, 0x63, 0x68,
+	0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x45, 0x78, 0x70, 0x6f, 0x72, 0x74, 0x43, 0x6f, 0x6c, 0x6c, 0x65,
+	0x63, 0x74, 0x69, 0x6f, 0x6e, 0x49, 0x6e, 0x64, 0x65, 0x78, 0x65, 0x73, 0x52, 0x65, 0x73, 0x70,
+	0x6f, 0x6e, 0x73, 0x65, 0x22, 0x00, 0x12, 0x6f, 0x0a, 0x18, 0x43, 0x68, 0x65, 0x63, 0x6b, 0x43,
+	0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x49, 0x6e, 0x74, 0x65, 0x67, 0x72, 0x69,
+	0x74, 0x79, 0x12, 0x27, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x43, 0x68, 0x65, 0x63,
+	0x6b, 0x43, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x49, 0x6e, 0x74, 0x65, 0x67,
+	0x72, 0x69, 0x74, 0x79, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x1a, 0x28, 0x2e, 0x63, 0x68,
+	0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x43, 0x68, 0x65, 0x63, 0x6b, 0x43, 0x6f, 0x6c, 0x6c, 0x65, 0x63,
+	0x74, 0x69, 0x6f, 0x6e, 0x49, 0x6e, 0x74, 0x65, 0x67, 0x72, 0x69, 0x74, 0x79, 0x52, 0x65, 0x73,
+	0x70, 0x6f, 0x6e, 0x73, 0x65, 0x22, 0x00, 0x12, 0x6f, 0x0a, 0x18, 0x56, 0x65, 0x72, 0x69, 0x66,
+	0x79, 0x43, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x43, 0x68, 0x65, 0x63, 0x6b,
+	0x73, 0x75, 0x6d, 0x12, 0x27, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x56, 0x65, 0x72,
+	0x69, 0x66, 0x79, 0x43, 0x6f, 0x6c, 0x6c, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x43, 0x68, 0x65,
+	0x63, 0x6b, 0x73, 0x75, 0x6d, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x1a, 0x28, 0x2e, 0x63,
+	0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x56, 0x65, 0x72, 0x69, 0x66, 0x79, 0x43, 0x6f, 0x6c, 0x6c,
+	0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x43, 0x68, 0x65, 0x63, 0x6b, 0x73, 0x75, 0x6d, 0x52, 0x65,
+	0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x22, 0x00, 0x12, 0x54, 0x0a, 0x0f, 0x4c, 0x69, 0x73, 0x74,
+	0x53, 0x6c, 0x6f, 0x77, 0x51, 0x75, 0x65, 0x72, 0x69, 0x65, 0x73, 0x12, 0x1e, 0x2e, 0x63, 0x68,
+	0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x4c, 0x69, 0x73, 0x74, 0x53, 0x6c, 0x6f, 0x77, 0x51, 0x75, 0x65,
+	0x72, 0x69, 0x65, 0x73, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x1a, 0x1f, 0x2e, 0x63, 0x68,
+	0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x4c, 0x69, 0x73, 0x74, 0x53, 0x6c, 0x6f, 0x77, 0x51, 0x75, 0x65,
+	0x72, 0x69, 0x65, 0x73, 0x52, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x22, 0x00, 0x12, 0x57,
+	0x0a, 0x10, 0x4c, 0x69, 0x73, 0x74, 0x43, 0x61, 0x63, 0x68, 0x65, 0x64, 0x42, 0x6c, 0x6f, 0x63,
+	0x6b, 0x73, 0x12, 0x1f, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x4c, 0x69, 0x73, 0x74,
+	0x43, 0x61, 0x63, 0x68, 0x65, 0x64, 0x42, 0x6c, 0x6f, 0x63, 0x6b, 0x73, 0x52, 0x65, 0x71, 0x75,
+	0x65, 0x73, 0x74, 0x1a, 0x20, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x4c, 0x69, 0x73,
+	0x74, 0x43, 0x61, 0x63, 0x68, 0x65, 0x64, 0x42, 0x6c, 0x6f, 0x63, 0x6b, 0x73, 0x52, 0x65, 0x73,
+	0x70, 0x6f, 0x6e, 0x73, 0x65, 0x22, 0x00, 0x32, 0xc0, 0x01, 0x0a, 0x0f, 0x43, 0x6f, 0x6d, 0x70,
+	0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x41, 0x64, 0x6d, 0x69, 0x6e, 0x12, 0x54, 0x0a, 0x0f, 0x50,
+	0x61, 0x75, 0x73, 0x65, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x12, 0x1e,
+	0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x50, 0x61, 0x75, 0x73, 0x65, 0x43, 0x6f, 0x6d,
+	0x70, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x1a, 0x1f,
+	0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x50, 0x61, 0x75, 0x73, 0x65, 0x43, 0x6f, 0x6d,
+	0x70, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x52, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x22,
+	0x00, 0x12, 0x57, 0x0a, 0x10, 0x52, 0x65, 0x73, 0x75, 0x6d, 0x65, 0x43, 0x6f, 0x6d, 0x70, 0x61,
+	0x63, 0x74, 0x69, 0x6f, 0x6e, 0x12, 0x1f, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x52,
+	0x65, 0x73, 0x75, 0x6d, 0x65, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x52,
+	0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x1a, 0x20, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e,
+	0x52, 0x65, 0x73, 0x75, 0x6d, 0x65, 0x43, 0x6f, 0x6d, 0x70, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e,
+	0x52, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x22, 0x00, 0x32, 0xa2, 0x01, 0x0a, 0x0c, 0x56,
+	0x65, 0x63, 0x74, 0x6f, 0x72, 0x52, 0x65, 0x61, 0x64, 0x65, 0x72, 0x12, 0x45, 0x0a, 0x0a, 0x47,
+	0x65, 0x74, 0x56, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x73, 0x12, 0x19, 0x2e, 0x63, 0x68, 0x72, 0x6f,
+	0x6d, 0x61, 0x2e, 0x47, 0x65, 0x74, 0x56, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x73, 0x52, 0x65, 0x71,
+	0x75, 0x65, 0x73, 0x74, 0x1a, 0x1a, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x47, 0x65,
+	0x74, 0x56, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x73, 0x52, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65,
+	0x22, 0x00, 0x12, 0x4b, 0x0a, 0x0c, 0x51, 0x75, 0x65, 0x72, 0x79, 0x56, 0x65, 0x63, 0x74, 0x6f,
+	0x72, 0x73, 0x12, 0x1b, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x51, 0x75, 0x65, 0x72,
+	0x79, 0x56, 0x65, 0x63, 0x74, 0x6f, 0x72, 0x73, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x1a,
+	0x1c, 0x2e, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61, 0x2e, 0x51, 0x75, 0x65, 0x72, 0x79, 0x56, 0x65,
+	0x63, 0x74, 0x6f, 0x72, 0x73, 0x52, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x22, 0x00, 0x42,
+	0x3a, 0x5a, 0x38, 0x67, 0x69, 0x74, 0x68, 0x75, 0x62, 0x2e, 0x63, 0x6f, 0x6d, 0x2f, 0x63, 0x68,
+	0x72, 0x6f, 0x6d, 0x61, 0x2d, 0x63, 0x6f, 0x72, 0x65, 0x2f, 0x63, 0x68, 0x72, 0x6f, 0x6d, 0x61,
+	0x2f, 0x67, 0x6f, 0x2f, 0x70, 0x6b, 0x67, 0x2f, 0x70, 0x72, 0x6f, 0x74, 0x6f, 0x2f, 0x63, 0x6f,
+	0x6f, 0x72, 0x64, 0x69, 0x6e, 0x61, 0x74, 0x6f, 0x72, 0x70, 0x62, 0x62, 0x06, 0x70, 0x72, 0x6f,
+	0x74, 0x6f, 0x33,
+}
+
+var (
+	file_chromadb_proto_chroma_proto_rawDescOnce sync.Once
+	file_chromadb_proto_chroma_proto_rawDescData = file_chromadb_proto_chroma_proto_rawDesc
+)
+
+func file_chromadb_proto_chroma_proto_rawDescGZIP() []byte {
+	file_chromadb_proto_chroma_proto_rawDescOnce.Do(func() {
+		file_chromadb_proto_chroma_proto_rawDescData = protoimpl.X.CompressGZIP(file_chromadb_proto_chroma_proto_rawDescData)
+	})
+	return file_chromadb_proto_chroma_proto_rawDescData
+}
+
+var file_chromadb_proto_chroma_proto_enumTypes = make([]protoimpl.EnumInfo, 11)
+var file_chromadb_proto_chroma_proto_msgTypes = make([]protoimpl.MessageInfo, 79)
+var file_chromadb_proto_chroma_proto_goTypes = []any{
+	(Operation)(0),                           // 0: chroma.Operation
+	(ScalarEncoding)(0),                      // 1: chroma.ScalarEncoding
+	(SegmentScope)(0),                        // 2: chroma.SegmentScope
+	(WhereDocumentOperator)(0),               // 3: chroma.WhereDocumentOperator
+	(BooleanOperator)(0),                     // 4: chroma.BooleanOperator
+	(ExistenceOperator)(0),                   // 5: chroma.ExistenceOperator
+	(ListOperator)(0),                        // 6: chroma.ListOperator
+	(GenericComparator)(0),                   // 7: chroma.GenericComparator
+	(NumberComparator)(0),                    // 8: chroma.NumberComparator
+	(IntegrityCheck)(0),                      // 9: chroma.IntegrityCheck
+	(DistanceAccumulation)(0),                // 10: chroma.DistanceAccumulation
+	(*Vector)(nil),                           // 11: chroma.Vector
+	(*FilePaths)(nil),                        // 12: chroma.FilePaths
+	(*Segment)(nil),                          // 13: chroma.Segment
+	(*Collection)(nil),                       // 14: chroma.Collection
+	(*Database)(nil),                         // 15: chroma.Database
+	(*Tenant)(nil),                           // 16: chroma.Tenant
+	(*UpdateMetadataValue)(nil),              // 17: chroma.UpdateMetadataValue
+	(*MetadataList)(nil),                     // 18: chroma.MetadataList
+	(*MetadataObject)(nil),                   // 19: chroma.MetadataObject
+	(*UpdateMetadata)(nil),                   // 20: chroma.UpdateMetadata
+	(*VectorReference)(nil),                  // 21: chroma.VectorReference
+	(*OperationRecord)(nil),                  // 22: chroma.OperationRecord
+	(*RequestVersionContext)(nil),            // 23: chroma.RequestVersionContext
+	(*ErrorDetails)(nil),                     // 24: chroma.ErrorDetails
+	(*StreamChangesRequest)(nil),             // 25: chroma.StreamChangesRequest
+	(*RecordChange)(nil),                     // 26: chroma.RecordChange
+	(*StreamChangesResponse)(nil),            // 27: chroma.StreamChangesResponse
+	(*CountRecordsRequest)(nil),              // 28: chroma.CountRecordsRequest
+	(*CountRecordsResponse)(nil),             // 29: chroma.CountRecordsResponse
+	(*QueryMetadataRequest)(nil),             // 30: chroma.QueryMetadataRequest
+	(*Lookup)(nil),                           // 31: chroma.Lookup
+	(*QueryMetadataResponse)(nil),            // 32: chroma.QueryMetadataResponse
+	(*QueryPlan)(nil),                        // 33: chroma.QueryPlan
+	(*QueryPlanNode)(nil),                    // 34: chroma.QueryPlanNode
+	(*MetadataEmbeddingRecord)(nil),          // 35: chroma.MetadataEmbeddingRecord
+	(*EmbeddingHandle)(nil),                  // 36: chroma.EmbeddingHandle
+	(*GetEmbeddingsRequest)(nil),             // 37: chroma.GetEmbeddingsRequest
+	(*GetEmbeddingsResponse)(nil),            // 38: chroma.GetEmbeddingsResponse
+	(*UserIds)(nil),                          // 39: chroma.UserIds
+	(*WhereDocument)(nil),                    // 40: chroma.WhereDocument
+	(*DirectWhereDocument)(nil),              // 41: chroma.DirectWhereDocument
+	(*WhereDocumentChildren)(nil),            // 42: chroma.WhereDocumentChildren
+	(*Where)(nil),                            // 43: chroma.Where
+	(*DirectComparison)(nil),                 // 44: chroma.DirectComparison
+	(*WhereChildren)(nil),                    // 45: chroma.WhereChildren
+	(*StringListComparison)(nil),             // 46: chroma.StringListComparison
+	(*ExistenceComparison)(nil),              // 47: chroma.ExistenceComparison
+	(*SingleStringComparison)(nil),           // 48: chroma.SingleStringComparison
+	(*SingleBoolComparison)(nil),             // 49: chroma.SingleBoolComparison
+	(*IntListComparison)(nil),                // 50: chroma.IntListComparison
+	(*SingleIntComparison)(nil),              // 51: chroma.SingleIntComparison
+	(*SingleTimestampComparison)(nil),        // 52: chroma.SingleTimestampComparison
+	(*DoubleListComparison)(nil),             // 53: chroma.DoubleListComparison
+	(*BoolListComparison)(nil),               // 54: chroma.BoolListComparison
+	(*SingleDoubleComparison)(nil),           // 55: chroma.SingleDoubleComparison
+	(*WarmUpCollectionRequest)(nil),          // 56: chroma.WarmUpCollectionRequest
+	(*WarmUpCollectionResponse)(nil),         // 57: chroma.WarmUpCollectionResponse
+	(*ExportCollectionIndexesRequest)(nil),   // 58: chroma.ExportCollectionIndexesRequest
+	(*ExportCollectionIndexesResponse)(nil),  // 59: chroma.ExportCollectionIndexesResponse
+	(*CheckCollectionIntegrityRequest)(nil),  // 60: chroma.CheckCollectionIntegrityRequest
+	(*IntegrityIssue)(nil),                   // 61: chroma.IntegrityIssue
+	(*CheckCollectionIntegrityResponse)(nil), // 62: chroma.CheckCollectionIntegrityResponse
+	(*VerifyCollectionChecksumRequest)(nil),  // 63: chroma.VerifyCollectionChecksumRequest
+	(*VerifyCollectionChecksumResponse)(nil), // 64: chroma.VerifyCollectionChecksumResponse
+	(*ListSlowQueriesRequest)(nil),           // 65: chroma.ListSlowQueriesRequest
+	(*SlowQuery)(nil),                        // 66: chroma.SlowQuery
+	(*ListSlowQueriesResponse)(nil),          // 67: chroma.ListSlowQueriesResponse
+	(*ListCachedBlocksRequest)(nil),          // 68: chroma.ListCachedBlocksRequest
+	(*CachedBlock)(nil),                      // 69: chroma.CachedBlock
+	(*ListCachedBlocksResponse)(nil),         // 70: chroma.ListCachedBlocksResponse
+	(*PauseCompactionRequest)(nil),           // 71: chroma.PauseCompactionRequest
+	(*PauseCompactionResponse)(nil),          // 72: chroma.PauseCompactionResponse
+	(*ResumeCompactionRequest)(nil),          // 73: chroma.ResumeCompactionRequest
+	(*ResumeCompactionResponse)(nil),         // 74: chroma.ResumeCompactionResponse
+	(*GetVectorsRequest)(nil),                // 75: chroma.GetVectorsRequest
+	(*GetVectorsResponse)(nil),               // 76: chroma.GetVectorsResponse
+	(*VectorEmbeddingRecord)(nil),            // 77: chroma.VectorEmbeddingRecord
+	(*QueryVectorsRequest)(nil),              // 78: chroma.QueryVectorsRequest
+	(*QueryVectorsResponse)(nil),             // 79: chroma.QueryVectorsResponse
+	(*VectorQueryResults)(nil),               // 80: chroma.VectorQueryResults
+	(*VectorQueryResult)(nil),                // 81: chroma.VectorQueryResult
+	(*SignedRoaringBitmap)(nil),              // 82: chroma.SignedRoaringBitmap
+	(*FilterOutput)(nil),                     // 83: chroma.FilterOutput
+	(*LimitOutput)(nil),                      // 84: chroma.LimitOutput
+	nil,                                      // 85: chroma.Segment.FilePathsEntry
+	nil,                                      // 86: chroma.MetadataObject.ValuesEntry
+	nil,                                      // 87: chroma.UpdateMetadata.MetadataEntry
+	nil,                                      // 88: chroma.QueryMetadataResponse.LookupRecordsEntry
+	nil,                                      // 89: chroma.QueryPlan.StrategyEntry
+}
+var file_chromadb_proto_chroma_proto_depIdxs = []int32{
+	1,   // 0: chroma.Vector.encoding:type_name -> chroma.ScalarEncoding
+	2,   // 1: chroma.Segment.scope:type_name -> chroma.SegmentScope
+	20,  // 2: chroma.Segment.metadata:type_name -> chroma.UpdateMetadata
+	85,  // 3: chroma.Segment.file_paths:type_name -> chroma.Segment.FilePathsEntry
+	20,  // 4: chroma.Collection.metadata:type_name -> chroma.UpdateMetadata
+	18,  // 5: chroma.UpdateMetadataValue.list_value:type_name -> chroma.MetadataList
+	19,  // 6: chroma.UpdateMetadataValue.object_value:type_name -> chroma.MetadataObject
+	17,  // 7: chroma.MetadataList.values:type_name -> chroma.UpdateMetadataValue
+	86,  // 8: chroma.MetadataObject.values:type_name -> chroma.MetadataObject.ValuesEntry
+	87,  // 9: chroma.UpdateMetadata.metadata:type_name -> chroma.UpdateMetadata.MetadataEntry
+	11,  // 10: chroma.OperationRecord.vector:type_name -> chroma.Vector
+	20,  // 11: chroma.OperationRecord.metadata:type_name -> chroma.UpdateMetadata
+	0,   // 12: chroma.OperationRecord.operation:type_name -> chroma.Operation
+	21,  // 13: chroma.OperationRecord.vector_reference:type_name -> chroma.VectorReference
+	23,  // 14: chroma.StreamChangesRequest.version_context:type_name -> chroma.RequestVersionContext
+	0,   // 15: chroma.RecordChange.operation:type_name -> chroma.Operation
+	35,  // 16: chroma.RecordChange.record:type_name -> chroma.MetadataEmbeddingRecord
+	26,  // 17: chroma.StreamChangesResponse.changes:type_name -> chroma.RecordChange
+	23,  // 18: chroma.CountRecordsRequest.version_context:type_name -> chroma.RequestVersionContext
+	43,  // 19: chroma.QueryMetadataRequest.where:type_name -> chroma.Where
+	40,  // 20: chroma.QueryMetadataRequest.where_document:type_name -> chroma.WhereDocument
+	39,  // 21: chroma.QueryMetadataRequest.ids:type_name -> chroma.UserIds
+	23,  // 22: chroma.QueryMetadataRequest.version_context:type_name -> chroma.RequestVersionContext
+	31,  // 23: chroma.QueryMetadataRequest.lookup:type_name -> chroma.Lookup
+	23,  // 24: chroma.Lookup.version_context:type_name -> chroma.RequestVersionContext
+	35,  // 25: chroma.QueryMetadataResponse.records:type_name -> chroma.MetadataEmbeddingRecord
+	33,  // 26: chroma.QueryMetadataResponse.plan:type_name -> chroma.QueryPlan
+	88,  // 27: chroma.QueryMetadataResponse.lookup_records:type_name -> chroma.QueryMetadataResponse.LookupRecordsEntry
+	89,  // 28: chroma.QueryPlan.strategy:type_name -> chroma.QueryPlan.StrategyEntry
+	34,  // 29: chroma.QueryPlan.nodes:type_name -> chroma.QueryPlanNode
+	20,  // 30: chroma.MetadataEmbeddingRecord.metadata:type_name -> chroma.UpdateMetadata
+	11,  // 31: chroma.MetadataEmbeddingRecord.embedding:type_name -> chroma.Vector
+	36,  // 32: chroma.MetadataEmbeddingRecord.embedding_handle:type_name -> chroma.EmbeddingHandle
+	23,  // 33: chroma.EmbeddingHandle.version_context:type_name -> chroma.RequestVersionContext
+	23,  // 34: chroma.GetEmbeddingsRequest.version_context:type_name -> chroma.RequestVersionContext
+	77,  // 35: chroma.GetEmbeddingsResponse.records:type_name -> chroma.VectorEmbeddingRecord
+	41,  // 36: chroma.WhereDocument.direct:type_name -> chroma.DirectWhereDocument
+	42,  // 37: chroma.WhereDocument.children:type_name -> chroma.WhereDocumentChildren
+	3,   // 38: chroma.DirectWhereDocument.operator:type_name -> chroma.WhereDocumentOperator
+	40,  // 39: chroma.WhereDocumentChildren.children:type_name -> chroma.WhereDocument
+	4,   // 40: chroma.WhereDocumentChildren.operator:type_name -> chroma.BooleanOperator
+	44,  // 41: chroma.Where.direct_comparison:type_name -> chroma.DirectComparison
+	45,  // 42: chroma.Where.children:type_name -> chroma.WhereChildren
+	48,  // 43: chroma.DirectComparison.single_string_operand:type_name -> chroma.SingleStringComparison
+	46,  // 44: chroma.DirectComparison.string_list_operand:type_name -> chroma.StringListComparison
+	51,  // 45: chroma.DirectComparison.single_int_operand:type_name -> chroma.SingleIntComparison
+	50,  // 46: chroma.DirectComparison.int_list_operand:type_name -> chroma.IntListComparison
+	55,  // 47: chroma.DirectComparison.single_double_operand:type_name -> chroma.SingleDoubleComparison
+	53,  // 48: chroma.DirectComparison.double_list_operand:type_name -> chroma.DoubleListComparison
+	54,  // 49: chroma.DirectComparison.bool_list_operand:type_name -> chroma.BoolListComparison
+	49,  // 50: chroma.DirectComparison.single_bool_operand:type_name -> chroma.SingleBoolComparison
+	47,  // 51: chroma.DirectComparison.existence_operand:type_name -> chroma.ExistenceComparison
+	52,  // 52: chroma.DirectComparison.single_timestamp_operand:type_name -> chroma.SingleTimestampComparison
+	43,  // 53: chroma.WhereChildren.children:type_name -> chroma.Where
+	4,   // 54: chroma.WhereChildren.operator:type_name -> chroma.BooleanOperator
+	6,   // 55: chroma.StringListComparison.list_operator:type_name -> chroma.ListOperator
+	5,   // 56: chroma.ExistenceComparison.operator:type_name -> chroma.ExistenceOperator
+	7,   // 57: chroma.SingleStringComparison.comparator:type_name -> chroma.GenericComparator
+	7,   // 58: chroma.SingleBoolComparison.comparator:type_name -> chroma.GenericComparator
+	6,   // 59: chroma.IntListComparison.list_operator:type_name -> chroma.ListOperator
+	7,   // 60: chroma.SingleIntComparison.generic_comparator:type_name -> chroma.GenericComparator
+	8,   // 61: chroma.SingleIntComparison.number_comparator:type_name -> chroma.NumberComparator
+	7,   // 62: chroma.SingleTimestampComparison.generic_comparator:type_name -> chroma.GenericComparator
+	8,   // 63: chroma.SingleTimestampComparison.number_comparator:type_name -> chroma.NumberComparator
+	6,   // 64: chroma.DoubleListComparison.list_operator:type_name -> chroma.ListOperator
+	6,   // 65: chroma.BoolListComparison.list_operator:type_name -> chroma.ListOperator
+	7,   // 66: chroma.SingleDoubleComparison.generic_comparator:type_name -> chroma.GenericComparator
+	8,   // 67: chroma.SingleDoubleComparison.number_comparator:type_name -> chroma.NumberComparator
+	23,  // 68: chroma.WarmUpCollectionRequest.version_context:type_name -> chroma.RequestVersionContext
+	23,  // 69: chroma.ExportCollectionIndexesRequest.version_context:type_name -> chroma.RequestVersionContext
+	23,  // 70: chroma.CheckCollectionIntegrityRequest.version_context:type_name -> chroma.RequestVersionContext
+	9,   // 71: chroma.IntegrityIssue.check:type_name -> chroma.IntegrityCheck
+	61,  // 72: chroma.CheckCollectionIntegrityResponse.issues:type_name -> chroma.IntegrityIssue
+	23,  // 73: chroma.VerifyCollectionChecksumRequest.version_context:type_name -> chroma.RequestVersionContext
+	33,  // 74: chroma.SlowQuery.plan:type_name -> chroma.QueryPlan
+	66,  // 75: chroma.ListSlowQueriesResponse.queries:type_name -> chroma.SlowQuery
+	69,  // 76: chroma.ListCachedBlocksResponse.blocks:type_name -> chroma.CachedBlock
+	23,  // 77: chroma.GetVectorsRequest.version_context:type_name -> chroma.RequestVersionContext
+	77,  // 78: chroma.GetVectorsResponse.records:type_name -> chroma.VectorEmbeddingRecord
+	11,  // 79: chroma.VectorEmbeddingRecord.vector:type_name -> chroma.Vector
+	11,  // 80: chroma.QueryVectorsRequest.vectors:type_name -> chroma.Vector
+	23,  // 81: chroma.QueryVectorsRequest.version_context:type_name -> chroma.RequestVersionContext
+	10,  // 82: chroma.QueryVectorsRequest.accumulation:type_name -> chroma.DistanceAccumulation
+	80,  // 83: chroma.QueryVectorsResponse.results:type_name -> chroma.VectorQueryResults
+	33,  // 84: chroma.QueryVectorsResponse.plan:type_name -> chroma.QueryPlan
+	81,  // 85: chroma.VectorQueryResults.results:type_name -> chroma.VectorQueryResult
+	11,  // 86: chroma.VectorQueryResult.vector:type_name -> chroma.Vector
+	82,  // 87: chroma.FilterOutput.log_offset_ids:type_name -> chroma.SignedRoaringBitmap
+	82,  // 88: chroma.FilterOutput.compact_offset_ids:type_name -> chroma.SignedRoaringBitmap
+	12,  // 89: chroma.Segment.FilePathsEntry.value:type_name -> chroma.FilePaths
+	17,  // 90: chroma.MetadataObject.ValuesEntry.value:type_name -> chroma.UpdateMetadataValue
+	17,  // 91: chroma.UpdateMetadata.MetadataEntry.value:type_name -> chroma.UpdateMetadataValue
+	35,  // 92: chroma.QueryMetadataResponse.LookupRecordsEntry.value:type_name -> chroma.MetadataEmbeddingRecord
+	30,  // 93: chroma.MetadataReader.QueryMetadata:input_type -> chroma.QueryMetadataRequest
+	28,  // 94: chroma.MetadataReader.CountRecords:input_type -> chroma.CountRecordsRequest
+	37,  // 95: chroma.MetadataReader.GetEmbeddings:input_type -> chroma.GetEmbeddingsRequest
+	30,  // 96: chroma.MetadataReader.StreamMetadata:input_type -> chroma.QueryMetadataRequest
+	25,  // 97: chroma.MetadataReader.StreamChanges:input_type -> chroma.StreamChangesRequest
+	56,  // 98: chroma.QueryAdmin.WarmUpCollection:input_type -> chroma.WarmUpCollectionRequest
+	58,  // 99: chroma.QueryAdmin.ExportCollectionIndexes:input_type -> chroma.ExportCollectionIndexesRequest
+	60,  // 100: chroma.QueryAdmin.CheckCollectionIntegrity:input_type -> chroma.CheckCollectionIntegrityRequest
+	63,  // 101: chroma.QueryAdmin.VerifyCollectionChecksum:input_type -> chroma.VerifyCollectionChecksumRequest
+	65,  // 102: chroma.QueryAdmin.ListSlowQueries:input_type -> chroma.ListSlowQueriesRequest
+	68,  // 103: chroma.QueryAdmin.ListCachedBlocks:input_type -> chroma.ListCachedBlocksRequest
+	71,  // 104: chroma.CompactionAdmin.PauseCompaction:input_type -> chroma.PauseCompactionRequest
+	73,  // 105: chroma.CompactionAdmin.ResumeCompaction:input_type -> chroma.ResumeCompactionRequest
+	75,  // 106: chroma.VectorReader.GetVectors:input_type -> chroma.GetVectorsRequest
+	78,  // 107: chroma.VectorReader.QueryVectors:input_type -> chroma.QueryVectorsRequest
+	32,  // 108: chroma.MetadataReader.QueryMetadata:output_type -> chroma.QueryMetadataResponse
+	29,  // 109: chroma.MetadataReader.CountRecords:output_type -> chroma.CountRecordsResponse
+	38,  // 110: chroma.MetadataReader.GetEmbeddings:output_type -> chroma.GetEmbeddingsResponse
+	32,  // 111: chroma.MetadataReader.StreamMetadata:output_type -> chroma.QueryMetadataResponse
+	27,  // 112: chroma.MetadataReader.StreamChanges:output_type -> chroma.StreamChangesResponse
+	57,  // 113: chroma.QueryAdmin.WarmUpCollection:output_type -> chroma.WarmUpCollectionResponse
+	59,  // 114: chroma.QueryAdmin.ExportCollectionIndexes:output_type -> chroma.ExportCollectionIndexesResponse
+	62,  // 115: chroma.QueryAdmin.CheckCollectionIntegrity:output_type -> chroma.CheckCollectionIntegrityResponse
+	64,  // 116: chroma.QueryAdmin.VerifyCollectionChecksum:output_type -> chroma.VerifyCollectionChecksumResponse
+	67,  // 117: chroma.QueryAdmin.ListSlowQueries:output_type -> chroma.ListSlowQueriesResponse
+	70,  // 118: chroma.QueryAdmin.ListCachedBlocks:output_type -> chroma.ListCachedBlocksResponse
+	72,  // 119: chroma.CompactionAdmin.PauseCompaction:output_type -> chroma.PauseCompactionResponse
+	74,  // 120: chroma.CompactionAdmin.ResumeCompaction:output_type -> chroma.ResumeCompactionResponse
+	76,  // 121: chroma.VectorReader.GetVectors:output_type -> chroma.GetVectorsResponse
+	79,  // 122: chroma.VectorReader.QueryVectors:output_type -> chroma.QueryVectorsResponse
+	108, // [108:123] is the sub-list for method output_type
+	93,  // [93:108] is the sub-list for method input_type
+	93,  // [93:93] is the sub-list for extension type_name
+	93,  // [93:93] is the sub-list for extension extendee
+	0,   // [0:93] is the sub-list for field type_name
+}
+
+func init() { file_chromadb_proto_chroma_proto_init() }
+func file_chromadb_proto_chroma_proto_init() {
+	if File_chromadb_proto_chroma_proto != nil {
+		return
+	}
+	if !protoimpl.UnsafeEnabled {
+		file_chromadb_proto_chroma_proto_msgTypes[0].Exporter = func(v any, i int) any {
+			switch v := v.(*Vector); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[1].Exporter = func(v any, i int) any {
+			switch v := v.(*FilePaths); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[2].Exporter = func(v any, i int) any {
+			switch v := v.(*Segment); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[3].Exporter = func(v any, i int) any {
+			switch v := v.(*Collection); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[4].Exporter = func(v any, i int) any {
+			switch v := v.(*Database); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[5].Exporter = func(v any, i int) any {
+			switch v := v.(*Tenant); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[6].Exporter = func(v any, i int) any {
+			switch v := v.(*UpdateMetadataValue); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[7].Exporter = func(v any, i int) any {
+			switch v := v.(*MetadataList); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[8].Exporter = func(v any, i int) any {
+			switch v := v.(*MetadataObject); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[9].Exporter = func(v any, i int) any {
+			switch v := v.(*UpdateMetadata); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[10].Exporter = func(v any, i int) any {
+			switch v := v.(*VectorReference); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[11].Exporter = func(v any, i int) any {
+			switch v := v.(*OperationRecord); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[12].Exporter = func(v any, i int) any {
+			switch v := v.(*RequestVersionContext); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[13].Exporter = func(v any, i int) any {
+			switch v := v.(*ErrorDetails); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[14].Exporter = func(v any, i int) any {
+			switch v := v.(*StreamChangesRequest); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[15].Exporter = func(v any, i int) any {
+			switch v := v.(*RecordChange); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[16].Exporter = func(v any, i int) any {
+			switch v := v.(*StreamChangesResponse); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[17].Exporter = func(v any, i int) any {
+			switch v := v.(*CountRecordsRequest); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[18].Exporter = func(v any, i int) any {
+			switch v := v.(*CountRecordsResponse); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[19].Exporter = func(v any, i int) any {
+			switch v := v.(*QueryMetadataRequest); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[20].Exporter = func(v any, i int) any {
+			switch v := v.(*Lookup); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[21].Exporter = func(v any, i int) any {
+			switch v := v.(*QueryMetadataResponse); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[22].Exporter = func(v any, i int) any {
+			switch v := v.(*QueryPlan); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[23].Exporter = func(v any, i int) any {
+			switch v := v.(*QueryPlanNode); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[24].Exporter = func(v any, i int) any {
+			switch v := v.(*MetadataEmbeddingRecord); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[25].Exporter = func(v any, i int) any {
+			switch v := v.(*EmbeddingHandle); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[26].Exporter = func(v any, i int) any {
+			switch v := v.(*GetEmbeddingsRequest); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[27].Exporter = func(v any, i int) any {
+			switch v := v.(*GetEmbeddingsResponse); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[28].Exporter = func(v any, i int) any {
+			switch v := v.(*UserIds); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[29].Exporter = func(v any, i int) any {
+			switch v := v.(*WhereDocument); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[30].Exporter = func(v any, i int) any {
+			switch v := v.(*DirectWhereDocument); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[31].Exporter = func(v any, i int) any {
+			switch v := v.(*WhereDocumentChildren); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[32].Exporter = func(v any, i int) any {
+			switch v := v.(*Where); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[33].Exporter = func(v any, i int) any {
+			switch v := v.(*DirectComparison); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[34].Exporter = func(v any, i int) any {
+			switch v := v.(*WhereChildren); i {
 			case 0:
 				return &v.state
 			case 1:
@@ -3515,8 +7415,8 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[1].Exporter = func(v any, i int) any {
-			switch v := v.(*FilePaths); i {
+		file_chromadb_proto_chroma_proto_msgTypes[35].Exporter = func(v any, i int) any {
+			switch v := v.(*StringListComparison); i {
 			case 0:
 				return &v.state
 			case 1:
@@ -3527,8 +7427,8 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[2].Exporter = func(v any, i int) any {
-			switch v := v.(*Segment); i {
+		file_chromadb_proto_chroma_proto_msgTypes[36].Exporter = func(v any, i int) any {
+			switch v := v.(*ExistenceComparison); i {
 			case 0:
 				return &v.state
 			case 1:
@@ -3539,8 +7439,8 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[3].Exporter = func(v any, i int) any {
-			switch v := v.(*Collection); i {
+		file_chromadb_proto_chroma_proto_msgTypes[37].Exporter = func(v any, i int) any {
+			switch v := v.(*SingleStringComparison); i {
 			case 0:
 				return &v.state
 			case 1:
@@ -3551,8 +7451,8 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[4].Exporter = func(v any, i int) any {
-			switch v := v.(*Database); i {
+		file_chromadb_proto_chroma_proto_msgTypes[38].Exporter = func(v any, i int) any {
+			switch v := v.(*SingleBoolComparison); i {
 			case 0:
 				return &v.state
 			case 1:
@@ -3563,8 +7463,8 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[5].Exporter = func(v any, i int) any {
-			switch v := v.(*Tenant); i {
+		file_chromadb_proto_chroma_proto_msgTypes[39].Exporter = func(v any, i int) any {
+			switch v := v.(*IntListComparison); i {
 			case 0:
 				return &v.state
 			case 1:
@@ -3575,8 +7475,8 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[6].Exporter = func(v any, i int) any {
-			switch v := v.(*UpdateMetadataValue); i {
+		file_chromadb_proto_chroma_proto_msgTypes[40].Exporter = func(v any, i int) any {
+			switch v := v.(*SingleIntComparison); i {
 			case 0:
 				return &v.state
 			case 1:
@@ -3587,8 +7487,8 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[7].Exporter = func(v any, i int) any {
-			switch v := v.(*UpdateMetadata); i {
+		file_chromadb_proto_chroma_proto_msgTypes[41].Exporter = func(v any, i int) any {
+			switch v := v.(*SingleTimestampComparison); i {
 			case 0:
 				return &v.state
 			case 1:
@@ -3599,8 +7499,8 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[8].Exporter = func(v any, i int) any {
-			switch v := v.(*OperationRecord); i {
+		file_chromadb_proto_chroma_proto_msgTypes[42].Exporter = func(v any, i int) any {
+			switch v := v.(*DoubleListComparison); i {
 			case 0:
 				return &v.state
 			case 1:
@@ -3611,8 +7511,8 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[9].Exporter = func(v any, i int) any {
-			switch v := v.(*RequestVersionContext); i {
+		file_chromadb_proto_chroma_proto_msgTypes[43].Exporter = func(v any, i int) any {
+			switch v := v.(*BoolListComparison); i {
 			case 0:
 				return &v.state
 			case 1:
@@ -3623,8 +7523,8 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[10].Exporter = func(v any, i int) any {
-			switch v := v.(*CountRecordsRequest); i {
+		file_chromadb_proto_chroma_proto_msgTypes[44].Exporter = func(v any, i int) any {
+			switch v := v.(*SingleDoubleComparison); i {
 			case 0:
 				return &v.state
 			case 1:
@@ -3635,8 +7535,8 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[11].Exporter = func(v any, i int) any {
-			switch v := v.(*CountRecordsResponse); i {
+		file_chromadb_proto_chroma_proto_msgTypes[45].Exporter = func(v any, i int) any {
+			switch v := v.(*WarmUpCollectionRequest); i {
 			case 0:
 				return &v.state
 			case 1:
@@ -3647,8 +7547,8 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[12].Exporter = func(v any, i int) any {
-			switch v := v.(*QueryMetadataRequest); i {
+		file_chromadb_proto_chroma_proto_msgTypes[46].Exporter = func(v any, i int) any {
+			switch v := v.(*WarmUpCollectionResponse); i {
 			case 0:
 				return &v.state
 			case 1:
@@ -3659,8 +7559,8 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[13].Exporter = func(v any, i int) any {
-			switch v := v.(*QueryMetadataResponse); i {
+		file_chromadb_proto_chroma_proto_msgTypes[47].Exporter = func(v any, i int) any {
+			switch v := v.(*ExportCollectionIndexesRequest); i {
 			case 0:
 				return &v.state
 			case 1:
@@ -3671,8 +7571,8 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[14].Exporter = func(v any, i int) any {
-			switch v := v.(*MetadataEmbeddingRecord); i {
+		file_chromadb_proto_chroma_proto_msgTypes[48].Exporter = func(v any, i int) any {
+			switch v := v.(*ExportCollectionIndexesResponse); i {
 			case 0:
 				return &v.state
 			case 1:
@@ -3683,8 +7583,8 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[15].Exporter = func(v any, i int) any {
-			switch v := v.(*UserIds); i {
+		file_chromadb_proto_chroma_proto_msgTypes[49].Exporter = func(v any, i int) any {
+			switch v := v.(*CheckCollectionIntegrityRequest); i {
 			case 0:
 				return &v.state
 			case 1:
@@ -3695,8 +7595,8 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[16].Exporter = func(v any, i int) any {
-			switch v := v.(*WhereDocument); i {
+		file_chromadb_proto_chroma_proto_msgTypes[50].Exporter = func(v any, i int) any {
+			switch v := v.(*IntegrityIssue); i {
 			case 0:
 				return &v.state
 			case 1:
@@ -3707,8 +7607,8 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[17].Exporter = func(v any, i int) any {
-			switch v := v.(*DirectWhereDocument); i {
+		file_chromadb_proto_chroma_proto_msgTypes[51].Exporter = func(v any, i int) any {
+			switch v := v.(*CheckCollectionIntegrityResponse); i {
 			case 0:
 				return &v.state
 			case 1:
@@ -3719,8 +7619,8 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[18].Exporter = func(v any, i int) any {
-			switch v := v.(*WhereDocumentChildren); i {
+		file_chromadb_proto_chroma_proto_msgTypes[52].Exporter = func(v any, i int) any {
+			switch v := v.(*VerifyCollectionChecksumRequest); i {
 			case 0:
 				return &v.state
 			case 1:
@@ -3731,8 +7631,8 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[19].Exporter = func(v any, i int) any {
-			switch v := v.(*Where); i {
+		file_chromadb_proto_chroma_proto_msgTypes[53].Exporter = func(v any, i int) any {
+			switch v := v.(*VerifyCollectionChecksumResponse); i {
 			case 0:
 				return &v.state
 			case 1:
@@ -3743,8 +7643,8 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[20].Exporter = func(v any, i int) any {
-			switch v := v.(*DirectComparison); i {
+		file_chromadb_proto_chroma_proto_msgTypes[54].Exporter = func(v any, i int) any {
+			switch v := v.(*ListSlowQueriesRequest); i {
 			case 0:
 				return &v.state
 			case 1:
@@ -3755,8 +7655,8 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[21].Exporter = func(v any, i int) any {
-			switch v := v.(*WhereChildren); i {
+		file_chromadb_proto_chroma_proto_msgTypes[55].Exporter = func(v any, i int) any {
+			switch v := v.(*SlowQuery); i {
 			case 0:
 				return &v.state
 			case 1:
@@ -3767,8 +7667,8 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[22].Exporter = func(v any, i int) any {
-			switch v := v.(*StringListComparison); i {
+		file_chromadb_proto_chroma_proto_msgTypes[56].Exporter = func(v any, i int) any {
+			switch v := v.(*ListSlowQueriesResponse); i {
 			case 0:
 				return &v.state
 			case 1:
@@ -3779,8 +7679,8 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[23].Exporter = func(v any, i int) any {
-			switch v := v.(*SingleStringComparison); i {
+		file_chromadb_proto_chroma_proto_msgTypes[57].Exporter = func(v any, i int) any {
+			switch v := v.(*ListCachedBlocksRequest); i {
 			case 0:
 				return &v.state
 			case 1:
@@ -3791,8 +7691,8 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[24].Exporter = func(v any, i int) any {
-			switch v := v.(*SingleBoolComparison); i {
+		file_chromadb_proto_chroma_proto_msgTypes[58].Exporter = func(v any, i int) any {
+			switch v := v.(*CachedBlock); i {
 			case 0:
 				return &v.state
 			case 1:
@@ -3803,8 +7703,8 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[25].Exporter = func(v any, i int) any {
-			switch v := v.(*IntListComparison); i {
+		file_chromadb_proto_chroma_proto_msgTypes[59].Exporter = func(v any, i int) any {
+			switch v := v.(*ListCachedBlocksResponse); i {
 			case 0:
 				return &v.state
 			case 1:
@@ -3815,8 +7715,8 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[26].Exporter = func(v any, i int) any {
-			switch v := v.(*SingleIntComparison); i {
+		file_chromadb_proto_chroma_proto_msgTypes[60].Exporter = func(v any, i int) any {
+			switch v := v.(*PauseCompactionRequest); i {
 			case 0:
 				return &v.state
 			case 1:
@@ -3827,8 +7727,8 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[27].Exporter = func(v any, i int) any {
-			switch v := v.(*DoubleListComparison); i {
+		file_chromadb_proto_chroma_proto_msgTypes[61].Exporter = func(v any, i int) any {
+			switch v := v.(*PauseCompactionResponse); i {
 			case 0:
 				return &v.state
 			case 1:
@@ -3839,8 +7739,8 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[28].Exporter = func(v any, i int) any {
-			switch v := v.(*BoolListComparison); i {
+		file_chromadb_proto_chroma_proto_msgTypes[62].Exporter = func(v any, i int) any {
+			switch v := v.(*ResumeCompactionRequest); i {
 			case 0:
 				return &v.state
 			case 1:
@@ -3851,8 +7751,8 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[29].Exporter = func(v any, i int) any {
-			switch v := v.(*SingleDoubleComparison); i {
+		file_chromadb_proto_chroma_proto_msgTypes[63].Exporter = func(v any, i int) any {
+			switch v := v.(*ResumeCompactionResponse); i {
 			case 0:
 				return &v.state
 			case 1:
@@ -3863,7 +7763,7 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[30].Exporter = func(v any, i int) any {
+		file_chromadb_proto_chroma_proto_msgTypes[64].Exporter = func(v any, i int) any {
 			switch v := v.(*GetVectorsRequest); i {
 			case 0:
 				return &v.state
@@ -3875,7 +7775,7 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[31].Exporter = func(v any, i int) any {
+		file_chromadb_proto_chroma_proto_msgTypes[65].Exporter = func(v any, i int) any {
 			switch v := v.(*GetVectorsResponse); i {
 			case 0:
 				return &v.state
@@ -3887,7 +7787,7 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[32].Exporter = func(v any, i int) any {
+		file_chromadb_proto_chroma_proto_msgTypes[66].Exporter = func(v any, i int) any {
 			switch v := v.(*VectorEmbeddingRecord); i {
 			case 0:
 				return &v.state
@@ -3899,7 +7799,7 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[33].Exporter = func(v any, i int) any {
+		file_chromadb_proto_chroma_proto_msgTypes[67].Exporter = func(v any, i int) any {
 			switch v := v.(*QueryVectorsRequest); i {
 			case 0:
 				return &v.state
@@ -3911,7 +7811,7 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[34].Exporter = func(v any, i int) any {
+		file_chromadb_proto_chroma_proto_msgTypes[68].Exporter = func(v any, i int) any {
 			switch v := v.(*QueryVectorsResponse); i {
 			case 0:
 				return &v.state
@@ -3923,7 +7823,7 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[35].Exporter = func(v any, i int) any {
+		file_chromadb_proto_chroma_proto_msgTypes[69].Exporter = func(v any, i int) any {
 			switch v := v.(*VectorQueryResults); i {
 			case 0:
 				return &v.state
@@ -3935,7 +7835,7 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
-		file_chromadb_proto_chroma_proto_msgTypes[36].Exporter = func(v any, i int) any {
+		file_chromadb_proto_chroma_proto_msgTypes[70].Exporter = func(v any, i int) any {
 			switch v := v.(*VectorQueryResult); i {
 			case 0:
 				return &v.state
@@ -3947,26 +7847,75 @@ func file_chromadb_proto_chroma_proto_init() {
 				return nil
 			}
 		}
+		file_chromadb_proto_chroma_proto_msgTypes[71].Exporter = func(v any, i int) any {
+			switch v := v.(*SignedRoaringBitmap); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[72].Exporter = func(v any, i int) any {
+			switch v := v.(*FilterOutput); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
+		file_chromadb_proto_chroma_proto_msgTypes[73].Exporter = func(v any, i int) any {
+			switch v := v.(*LimitOutput); i {
+			case 0:
+				return &v.state
+			case 1:
+				return &v.sizeCache
+			case 2:
+				return &v.unknownFields
+			default:
+				return nil
+			}
+		}
 	}
 	file_chromadb_proto_chroma_proto_msgTypes[2].OneofWrappers = []any{}
 	file_chromadb_proto_chroma_proto_msgTypes[3].OneofWrappers = []any{}
+	file_chromadb_proto_chroma_proto_msgTypes[5].OneofWrappers = []any{}
 	file_chromadb_proto_chroma_proto_msgTypes[6].OneofWrappers = []any{
 		(*UpdateMetadataValue_StringValue)(nil),
 		(*UpdateMetadataValue_IntValue)(nil),
 		(*UpdateMetadataValue_FloatValue)(nil),
 		(*UpdateMetadataValue_BoolValue)(nil),
+		(*UpdateMetadataValue_ListValue)(nil),
+		(*UpdateMetadataValue_TimestampValue)(nil),
+		(*UpdateMetadataValue_ObjectValue)(nil),
 	}
-	file_chromadb_proto_chroma_proto_msgTypes[8].OneofWrappers = []any{}
+	file_chromadb_proto_chroma_proto_msgTypes[11].OneofWrappers = []any{}
 	file_chromadb_proto_chroma_proto_msgTypes[12].OneofWrappers = []any{}
-	file_chromadb_proto_chroma_proto_msgTypes[16].OneofWrappers = []any{
+	file_chromadb_proto_chroma_proto_msgTypes[13].OneofWrappers = []any{}
+	file_chromadb_proto_chroma_proto_msgTypes[19].OneofWrappers = []any{}
+	file_chromadb_proto_chroma_proto_msgTypes[20].OneofWrappers = []any{}
+	file_chromadb_proto_chroma_proto_msgTypes[21].OneofWrappers = []any{}
+	file_chromadb_proto_chroma_proto_msgTypes[23].OneofWrappers = []any{}
+	file_chromadb_proto_chroma_proto_msgTypes[24].OneofWrappers = []any{}
+	file_chromadb_proto_chroma_proto_msgTypes[25].OneofWrappers = []any{}
+	file_chromadb_proto_chroma_proto_msgTypes[26].OneofWrappers = []any{}
+	file_chromadb_proto_chroma_proto_msgTypes[29].OneofWrappers = []any{
 		(*WhereDocument_Direct)(nil),
 		(*WhereDocument_Children)(nil),
 	}
-	file_chromadb_proto_chroma_proto_msgTypes[19].OneofWrappers = []any{
+	file_chromadb_proto_chroma_proto_msgTypes[30].OneofWrappers = []any{}
+	file_chromadb_proto_chroma_proto_msgTypes[32].OneofWrappers = []any{
 		(*Where_DirectComparison)(nil),
 		(*Where_Children)(nil),
 	}
-	file_chromadb_proto_chroma_proto_msgTypes[20].OneofWrappers = []any{
+	file_chromadb_proto_chroma_proto_msgTypes[33].OneofWrappers = []any{
 		(*DirectComparison_SingleStringOperand)(nil),
 		(*DirectComparison_StringListOperand)(nil),
 		(*DirectComparison_SingleIntOperand)(nil),
@@ -3975,25 +7924,43 @@ func file_chromadb_proto_chroma_proto_init() {
 		(*DirectComparison_DoubleListOperand)(nil),
 		(*DirectComparison_BoolListOperand)(nil),
 		(*DirectComparison_SingleBoolOperand)(nil),
+		(*DirectComparison_ExistenceOperand)(nil),
+		(*DirectComparison_SingleTimestampOperand)(nil),
 	}
-	file_chromadb_proto_chroma_proto_msgTypes[26].OneofWrappers = []any{
+	file_chromadb_proto_chroma_proto_msgTypes[40].OneofWrappers = []any{
 		(*SingleIntComparison_GenericComparator)(nil),
 		(*SingleIntComparison_NumberComparator)(nil),
 	}
-	file_chromadb_proto_chroma_proto_msgTypes[29].OneofWrappers = []any{
+	file_chromadb_proto_chroma_proto_msgTypes[41].OneofWrappers = []any{
+		(*SingleTimestampComparison_GenericComparator)(nil),
+		(*SingleTimestampComparison_NumberComparator)(nil),
+	}
+	file_chromadb_proto_chroma_proto_msgTypes[44].OneofWrappers = []any{
 		(*SingleDoubleComparison_GenericComparator)(nil),
 		(*SingleDoubleComparison_NumberComparator)(nil),
 	}
-	file_chromadb_proto_chroma_proto_msgTypes[36].OneofWrappers = []any{}
+	file_chromadb_proto_chroma_proto_msgTypes[51].OneofWrappers = []any{}
+	file_chromadb_proto_chroma_proto_msgTypes[53].OneofWrappers = []any{}
+	file_chromadb_proto_chroma_proto_msgTypes[54].OneofWrappers = []any{}
+	file_chromadb_proto_chroma_proto_msgTypes[55].OneofWrappers = []any{}
+	file_chromadb_proto_chroma_proto_msgTypes[58].OneofWrappers = []any{}
+	file_chromadb_proto_chroma_proto_msgTypes[59].OneofWrappers = []any{}
+	file_chromadb_proto_chroma_proto_msgTypes[67].OneofWrappers = []any{}
+	file_chromadb_proto_chroma_proto_msgTypes[68].OneofWrappers = []any{}
+	file_chromadb_proto_chroma_proto_msgTypes[70].OneofWrappers = []any{}
+	file_chromadb_proto_chroma_proto_msgTypes[71].OneofWrappers = []any{
+		(*SignedRoaringBitmap_Include)(nil),
+		(*SignedRoaringBitmap_Exclude)(nil),
+	}
 	type x struct{}
 	out := protoimpl.TypeBuilder{
 		File: protoimpl.DescBuilder{
 			GoPackagePath: reflect.TypeOf(x{}).PkgPath(),
 			RawDescriptor: file_chromadb_proto_chroma_proto_rawDesc,
-			NumEnums:      8,
-			NumMessages:   39,
+			NumEnums:      11,
+			NumMessages:   79,
 			NumExtensions: 0,
-			NumServices:   2,
+			NumServices:   4,
 		},
 		GoTypes:           file_chromadb_proto_chroma_proto_goTypes,
 		DependencyIndexes: file_chromadb_proto_chroma_proto_depIdxs,
@@ -19,8 +19,11 @@ import (
 const _ = grpc.SupportPackageIsVersion9
 
 const (
-	MetadataReader_QueryMetadata_FullMethodName = "/chroma.MetadataReader/QueryMetadata"
-	MetadataReader_CountRecords_FullMethodName  = "/chroma.MetadataReader/CountRecords"
+	MetadataReader_QueryMetadata_FullMethodName  = "/chroma.MetadataReader/QueryMetadata"
+	MetadataReader_CountRecords_FullMethodName   = "/chroma.MetadataReader/CountRecords"
+	MetadataReader_GetEmbeddings_FullMethodName  = "/chroma.MetadataReader/GetEmbeddings"
+	MetadataReader_StreamMetadata_FullMethodName = "/chroma.MetadataReader/StreamMetadata"
+	MetadataReader_StreamChanges_FullMethodName  = "/chroma.MetadataReader/StreamChanges"
 )
 
 // MetadataReaderClient is the client API for MetadataReader service.
@@ -29,6 +32,13 @@ const (
 type MetadataReaderClient interface {
 	QueryMetadata(ctx context.Context, in *QueryMetadataRequest, opts ...grpc.CallOption) (*QueryMetadataResponse, error)
 	CountRecords(ctx context.Context, in *CountRecordsRequest, opts ...grpc.CallOption) (*CountRecordsResponse, error)
+	GetEmbeddings(ctx context.Context, in *GetEmbeddingsRequest, opts ...grpc.CallOption) (*GetEmbeddingsResponse, error)
+	// Evaluates the same query as `QueryMetadata`, and streams its records in batches as they
+	// are read instead of returning them all at once.
+	StreamMetadata(ctx context.Context, in *QueryMetadataRequest, opts ...grpc.CallOption) (grpc.ServerStreamingClient[QueryMetadataResponse], error)
+	// Streams the changes that the log of a collection makes to its records from a log offset
+	// on, so that downstream systems can mirror the collection without polling `QueryMetadata`.
+	StreamChanges(ctx context.Context, in *StreamChangesRequest, opts ...grpc.CallOption) (grpc.ServerStreamingClient[StreamChangesResponse], error)
 }
 
 type metadataReaderClient struct {
@@ -59,12 +69,67 @@ func (c *metadataReaderClient) CountRecords(ctx context.Context, in *CountRecord
 	return out, nil
 }
 
+func (c *metadataReaderClient) GetEmbeddings(ctx context.Context, in *GetEmbeddingsRequest, opts ...grpc.CallOption) (*GetEmbeddingsResponse, error) {
+	cOpts := append([]grpc.CallOption{grpc.StaticMethod()}, opts...)
+	out := new(GetEmbeddingsResponse)
+	err := c.cc.Invoke(ctx, MetadataReader_GetEmbeddings_FullMethodName, in, out, cOpts...)
+	if err != nil {
+		return nil, err
+	}
+	return out, nil
+}
+
+func (c *metadataReaderClient) StreamMetadata(ctx context.Context, in *QueryMetadataRequest, opts ...grpc.CallOption) (grpc.ServerStreamingClient[QueryMetadataResponse], error) {
+	cOpts := append([]grpc.CallOption{grpc.StaticMethod()}, opts...)
+	stream, err := c.cc.NewStream(ctx, &MetadataReader_ServiceDesc.Streams[0], MetadataReader_StreamMetadata_FullMethodName, cOpts...)
+	if err != nil {
+		return nil, err
+	}
+	x := &grpc.GenericClientStream[QueryMetadataRequest, QueryMetadataResponse]{ClientStream: stream}
+	if err := x.ClientStream.SendMsg(in); err != nil {
+		return nil, err
+	}
+	if err := x.ClientStream.CloseSend(); err != nil {
+		return nil, err
+	}
+	return x, nil
+}
+
+// This type alias is provided for backwards compatibility with existing code that references the prior non-generic stream type by name.
+type MetadataReader_StreamMetadataClient = grpc.ServerStreamingClient[QueryMetadataResponse]
+
+func (c *metadataReaderClient) StreamChanges(ctx context.Context, in *StreamChangesRequest, opts ...grpc.CallOption) (grpc.ServerStreamingClient[StreamChangesResponse], error) {
+	cOpts := append([]grpc.CallOption{grpc.StaticMethod()}, opts...)
+	stream, err := c.cc.NewStream(ctx, &MetadataReader_ServiceDesc.Streams[1], MetadataReader_StreamChanges_FullMethodName, cOpts...)
+	if err != nil {
+		return nil, err
+	}
+	x := &grpc.GenericClientStream[StreamChangesRequest, StreamChangesResponse]{ClientStream: stream}
+	if err := x.ClientStream.SendMsg(in); err != nil {
+		return nil, err
+	}
+	if err := x.ClientStream.CloseSend(); err != nil {
+		return nil, err
+	}
+	return x, nil
+}
+
+// This type alias is provided for backwards compatibility with existing code that references the prior non-generic stream type by name.
+type MetadataReader_StreamChangesClient = grpc.ServerStreamingClient[StreamChangesResponse]
+
 // MetadataReaderServer is the server API for MetadataReader service.
 // All implementations must embed UnimplementedMetadataReaderServer
 // for forward compatibility.
 type MetadataReaderServer interface {
 	QueryMetadata(context.Context, *QueryMetadataRequest) (*QueryMetadataResponse, error)
 	CountRecords(context.Context, *CountRecordsRequest) (*CountRecordsResponse, error)
+	GetEmbeddings(context.Context, *GetEmbeddingsRequest) (*GetEmbeddingsResponse, error)
+	// Evaluates the same query as `QueryMetadata`, and streams its records in batches as they
+	// are read instead of returning them all at once.
+	StreamMetadata(*QueryMetadataRequest, grpc.ServerStreamingServer[QueryMetadataResponse]) error
+	// Streams the changes that the log of a collection makes to its records from a log offset
+	// on, so that downstream systems can mirror the collection without polling `QueryMetadata`.
+	StreamChanges(*StreamChangesRequest, grpc.ServerStreamingServer[StreamChangesResponse]) error
 	mustEmbedUnimplementedMetadataReaderServer()
 }
 
@@ -81,6 +146,15 @@ func (UnimplementedMetadataReaderServer) QueryMetadata(context.Context, *QueryMe
 func (UnimplementedMetadataReaderServer) CountRecords(context.Context, *CountRecordsRequest) (*CountRecordsResponse, error) {
 	return nil, status.Errorf(codes.Unimplemented, "method CountRecords not implemented")
 }
+func (UnimplementedMetadataReaderServer) GetEmbeddings(context.Context, *GetEmbeddingsRequest) (*GetEmbeddingsResponse, error) {
+	return nil, status.Errorf(codes.Unimplemented, "method GetEmbeddings not implemented")
+}
+func (UnimplementedMetadataReaderServer) StreamMetadata(*QueryMetadataRequest, grpc.ServerStreamingServer[QueryMetadataResponse]) error {
+	return status.Errorf(codes.Unimplemented, "method StreamMetadata not implemented")
+}
+func (UnimplementedMetadataReaderServer) StreamChanges(*StreamChangesRequest, grpc.ServerStreamingServer[StreamChangesResponse]) error {
+	return status.Errorf(codes.Unimplemented, "method StreamChanges not implemented")
+}
 func (UnimplementedMetadataReaderServer) mustEmbedUnimplementedMetadataReaderServer() {}
 func (UnimplementedMetadataReaderServer) testEmbeddedByValue()                        {}
 
@@ -138,6 +212,46 @@ func _MetadataReader_CountRecords_Handler(srv interface{}, ctx context.Context,
 	return interceptor(ctx, in, info, handler)
 }
 
+func _MetadataReader_GetEmbeddings_Handler(srv interface{}, ctx context.Context, dec func(interface{}) error, interceptor grpc.UnaryServerInterceptor) (interface{}, error) {
+	in := new(GetEmbeddingsRequest)
+	if err := dec(in); err != nil {
+		return nil, err
+	}
+	if interceptor == nil {
+		return srv.(MetadataReaderServer).GetEmbeddings(ctx, in)
+	}
+	info := &grpc.UnaryServerInfo{
+		Server:     srv,
+		FullMethod: MetadataReader_GetEmbeddings_FullMethodName,
+	}
+	handler := func(ctx context.Context, req interface{}) (interface{}, error) {
+		return srv.(MetadataReaderServer).GetEmbeddings(ctx, req.(*GetEmbeddingsRequest))
+	}
+	return interceptor(ctx, in, info, handler)
+}
+
+func _MetadataReader_StreamMetadata_Handler(srv interface{}, stream grpc.ServerStream) error {
+	m := new(QueryMetadataRequest)
+	if err := stream.RecvMsg(m); err != nil {
+		return err
+	}
+	return srv.(MetadataReaderServer).StreamMetadata(m, &grpc.GenericServerStream[QueryMetadataRequest, QueryMetadataResponse]{ServerStream: stream})
+}
+
+// This type alias is provided for backwards compatibility with existing code that references the prior non-generic stream type by name.
+type MetadataReader_StreamMetadataServer = grpc.ServerStreamingServer[QueryMetadataResponse]
+
+func _MetadataReader_StreamChanges_Handler(srv interface{}, stream grpc.ServerStream) error {
+	m := new(StreamChangesRequest)
+	if err := stream.RecvMsg(m); err != nil {
+		return err
+	}
+	return srv.(MetadataReaderServer).StreamChanges(m, &grpc.GenericServerStream[StreamChangesRequest, StreamChangesResponse]{ServerStream: stream})
+}
+
+// This type alias is provided for backwards compatibility with existing code that references the prior non-generic stream type by name.
+type MetadataReader_StreamChangesServer = grpc.ServerStreamingServer[StreamChangesResponse]
+
 // MetadataReader_ServiceDesc is the grpc.ServiceDesc for MetadataReader service.
 // It's only intended for direct use with grpc.RegisterService,
 // and not to be introspected or modified (even as a copy)
@@ -153,6 +267,453 @@ var MetadataReader_ServiceDesc = grpc.ServiceDesc{
 			MethodName: "CountRecords",
 			Handler:    _MetadataReader_CountRecords_Handler,
 		},
+		{
+			MethodName: "GetEmbeddings",
+			Handler:    _MetadataReader_GetEmbeddings_Handler,
+		},
+	},
+	Streams: []grpc.StreamDesc{
+		{
+			StreamName:    "StreamMetadata",
+			Handler:       _MetadataReader_StreamMetadata_Handler,
+			ServerStreams: true,
+		},
+		{
+			StreamName:    "StreamChanges",
+			Handler:       _MetadataReader_StreamChanges_Handler,
+			ServerStreams: true,
+		},
+	},
+	Metadata: "chromadb/proto/chroma.proto",
+}
+
+const (
+	QueryAdmin_WarmUpCollection_FullMethodName         = "/chroma.QueryAdmin/WarmUpCollection"
+	QueryAdmin_ExportCollectionIndexes_FullMethodName  = "/chroma.QueryAdmin/ExportCollectionIndexes"
+	QueryAdmin_CheckCollectionIntegrity_FullMethodName = "/chroma.QueryAdmin/CheckCollectionIntegrity"
+	QueryAdmin_VerifyCollectionChecksum_FullMethodName = "/chroma.QueryAdmin/VerifyCollectionChecksum"
+	QueryAdmin_ListSlowQueries_FullMethodName          = "/chroma.QueryAdmin/ListSlowQueries"
+	QueryAdmin_ListCachedBlocks_FullMethodName         = "/chroma.QueryAdmin/ListCachedBlocks"
+)
+
+// QueryAdminClient is the client API for QueryAdmin service.
+//
+// For semantics around ctx use and closing/ending streaming RPCs, please refer to https://pkg.go.dev/google.golang.org/grpc/?tab=doc#ClientConn.NewStream.
+type QueryAdminClient interface {
+	WarmUpCollection(ctx context.Context, in *WarmUpCollectionRequest, opts ...grpc.CallOption) (*WarmUpCollectionResponse, error)
+	ExportCollectionIndexes(ctx context.Context, in *ExportCollectionIndexesRequest, opts ...grpc.CallOption) (*ExportCollectionIndexesResponse, error)
+	CheckCollectionIntegrity(ctx context.Context, in *CheckCollectionIntegrityRequest, opts ...grpc.CallOption) (*CheckCollectionIntegrityResponse, error)
+	VerifyCollectionChecksum(ctx context.Context, in *VerifyCollectionChecksumRequest, opts ...grpc.CallOption) (*VerifyCollectionChecksumResponse, error)
+	ListSlowQueries(ctx context.Context, in *ListSlowQueriesRequest, opts ...grpc.CallOption) (*ListSlowQueriesResponse, error)
+	ListCachedBlocks(ctx context.Context, in *ListCachedBlocksRequest, opts ...grpc.CallOption) (*ListCachedBlocksResponse, error)
+}
+
+type queryAdminClient struct {
+	cc grpc.ClientConnInterface
+}
+
+func NewQueryAdminClient(cc grpc.ClientConnInterface) QueryAdminClient {
+	return &queryAdminClient{cc}
+}
+
+func (c *queryAdminClient) WarmUpCollection(ctx context.Context, in *WarmUpCollectionRequest, opts ...grpc.CallOption) (*WarmUpCollectionResponse, error) {
+	cOpts := append([]grpc.CallOption{grpc.StaticMethod()}, opts...)
+	out := new(WarmUpCollectionResponse)
+	err := c.cc.Invoke(ctx, QueryAdmin_WarmUpCollection_FullMethodName, in, out, cOpts...)
+	if err != nil {
+		return nil, err
+	}
+	return out, nil
+}
+
+func (c *queryAdminClient) ExportCollectionIndexes(ctx context.Context, in *ExportCollectionIndexesRequest, opts ...grpc.CallOption) (*ExportCollectionIndexesResponse, error) {
+	cOpts := append([]grpc.CallOption{grpc.StaticMethod()}, opts...)
+	out := new(ExportCollectionIndexesResponse)
+	err := c.cc.Invoke(ctx, QueryAdmin_ExportCollectionIndexes_FullMethodName, in, out, cOpts...)
+	if err != nil {
+		return nil, err
+	}
+	return out, nil
+}
+
+func (c *queryAdminClient) CheckCollectionIntegrity(ctx context.Context, in *CheckCollectionIntegrityRequest, opts ...grpc.CallOption) (*CheckCollectionIntegrityResponse, error) {
+	cOpts := append([]grpc.CallOption{grpc.StaticMethod()}, opts...)
+	out := new(CheckCollectionIntegrityResponse)
+	err := c.cc.Invoke(ctx, QueryAdmin_CheckCollectionIntegrity_FullMethodName, in, out, cOpts...)
+	if err != nil {
+		return nil, err
+	}
+	return out, nil
+}
+
+func (c *queryAdminClient) VerifyCollectionChecksum(ctx context.Context, in *VerifyCollectionChecksumRequest, opts ...grpc.CallOption) (*VerifyCollectionChecksumResponse, error) {
+	cOpts := append([]grpc.CallOption{grpc.StaticMethod()}, opts...)
+	out := new(VerifyCollectionChecksumResponse)
+	err := c.cc.Invoke(ctx, QueryAdmin_VerifyCollectionChecksum_FullMethodName, in, out, cOpts...)
+	if err != nil {
+		return nil, err
+	}
+	return out, nil
+}
+
+func (c *queryAdminClient) ListSlowQueries(ctx context.Context, in *ListSlowQueriesRequest, opts ...grpc.CallOption) (*ListSlowQueriesResponse, error) {
+	cOpts := append([]grpc.CallOption{grpc.StaticMethod()}, opts...)
+	out := new(ListSlowQueriesResponse)
+	err := c.cc.Invoke(ctx, QueryAdmin_ListSlowQueries_FullMethodName, in, out, cOpts...)
+	if err != nil {
+		return nil, err
+	}
+	return out, nil
+}
+
+func (c *queryAdminClient) ListCachedBlocks(ctx context.Context, in *ListCachedBlocksRequest, opts ...grpc.CallOption) (*ListCachedBlocksResponse, error) {
+	cOpts := append([]grpc.CallOption{grpc.StaticMethod()}, opts...)
+	out := new(ListCachedBlocksResponse)
+	err := c.cc.Invoke(ctx, QueryAdmin_ListCachedBlocks_FullMethodName, in, out, cOpts...)
+	if err != nil {
+		return nil, err
+	}
+	return out, nil
+}
+
+// QueryAdminServer is the server API for QueryAdmin service.
+// All implementations must embed UnimplementedQueryAdminServer
+// for forward compatibility.
+type QueryAdminServer interface {
+	WarmUpCollection(context.Context, *WarmUpCollectionRequest) (*WarmUpCollectionResponse, error)
+	ExportCollectionIndexes(context.Context, *ExportCollectionIndexesRequest) (*ExportCollectionIndexesResponse, error)
+	CheckCollectionIntegrity(context.Context, *CheckCollectionIntegrityRequest) (*CheckCollectionIntegrityResponse, error)
+	VerifyCollectionChecksum(context.Context, *VerifyCollectionChecksumRequest) (*VerifyCollectionChecksumResponse, error)
+	ListSlowQueries(context.Context, *ListSlowQueriesRequest) (*ListSlowQueriesResponse, error)
+	ListCachedBlocks(context.Context, *ListCachedBlocksRequest) (*ListCachedBlocksResponse, error)
+	mustEmbedUnimplementedQueryAdminServer()
+}
+
+// UnimplementedQueryAdminServer must be embedded to have
+// forward compatible implementations.
+//
+// NOTE: this should be embedded by value instead of pointer to avoid a nil
+// pointer dereference when methods are called.
+type UnimplementedQueryAdminServer struct{}
+
+func (UnimplementedQueryAdminServer) WarmUpCollection(context.Context, *WarmUpCollectionRequest) (*WarmUpCollectionResponse, error) {
+	return nil, status.Errorf(codes.Unimplemented, "method WarmUpCollection not implemented")
+}
+func (UnimplementedQueryAdminServer) ExportCollectionIndexes(context.Context, *ExportCollectionIndexesRequest) (*ExportCollectionIndexesResponse, error) {
+	return nil, status.Errorf(codes.Unimplemented, "method ExportCollectionIndexes not implemented")
+}
+func (UnimplementedQueryAdminServer) CheckCollectionIntegrity(context.Context, *CheckCollectionIntegrityRequest) (*CheckCollectionIntegrityResponse, error) {
+	return nil, status.Errorf(codes.Unimplemented, "method CheckCollectionIntegrity not implemented")
+}
+func (UnimplementedQueryAdminServer) VerifyCollectionChecksum(context.Context, *VerifyCollectionChecksumRequest) (*VerifyCollectionChecksumResponse, error) {
+	return nil, status.Errorf(codes.Unimplemented, "method VerifyCollectionChecksum not implemented")
+}
+func (UnimplementedQueryAdminServer) ListSlowQueries(context.Context, *ListSlowQueriesRequest) (*ListSlowQueriesResponse, error) {
+	return nil, status.Errorf(codes.Unimplemented, "method ListSlowQueries not implemented")
+}
+func (UnimplementedQueryAdminServer) ListCachedBlocks(context.Context, *ListCachedBlocksRequest) (*ListCachedBlocksResponse, error) {
+	return nil, status.Errorf(codes.Unimplemented, "method ListCachedBlocks not implemented")
+}
+func (UnimplementedQueryAdminServer) mustEmbedUnimplementedQueryAdminServer() {}
+func (UnimplementedQueryAdminServer) testEmbeddedByValue()                    {}
+
+// UnsafeQueryAdminServer may be embedded to opt out of forward compatibility for this service.
+// Use of this interface is not recommended, as added methods to QueryAdminServer will
+// result in compilation errors.
+type UnsafeQueryAdminServer interface {
+	mustEmbedUnimplementedQueryAdminServer()
+}
+
+func RegisterQueryAdminServer(s grpc.ServiceRegistrar, srv QueryAdminServer) {
+	// If the following call pancis, it indicates UnimplementedQueryAdminServer was
+	// embedded by pointer and is nil.  This will cause panics if an
+	// unimplemented method is ever invoked, so we test this at initialization
+	// time to prevent it from happening at runtime later due to I/O.
+	if t, ok := srv.(interface{ testEmbeddedByValue() }); ok {
+		t.testEmbeddedByValue()
+	}
+	s.RegisterService(&QueryAdmin_ServiceDesc, srv)
+}
+
+func _QueryAdmin_WarmUpCollection_Handler(srv interface{}, ctx context.Context, dec func(interface{}) error, interceptor grpc.UnaryServerInterceptor) (interface{}, error) {
+	in := new(WarmUpCollectionRequest)
+	if err := dec(in); err != nil {
+		return nil, err
+	}
+	if interceptor == nil {
+		return srv.(QueryAdminServer).WarmUpCollection(ctx, in)
+	}
+	info := &grpc.UnaryServerInfo{
+		Server:     srv,
+		FullMethod: QueryAdmin_WarmUpCollection_FullMethodName,
+	}
+	handler := func(ctx context.Context, req interface{}) (interface{}, error) {
+		return srv.(QueryAdminServer).WarmUpCollection(ctx, req.(*WarmUpCollectionRequest))
+	}
+	return interceptor(ctx, in, info, handler)
+}
+
+func _QueryAdmin_ExportCollectionIndexes_Handler(srv interface{}, ctx context.Context, dec func(interface{}) error, interceptor grpc.UnaryServerInterceptor) (interface{}, error) {
+	in := new(ExportCollectionIndexesRequest)
+	if err := dec(in); err != nil {
+		return nil, err
+	}
+	if interceptor == nil {
+		return srv.(QueryAdminServer).ExportCollectionIndexes(ctx, in)
+	}
+	info := &grpc.UnaryServerInfo{
+		Server:     srv,
+		FullMethod: QueryAdmin_ExportCollectionIndexes_FullMethodName,
+	}
+	handler := func(ctx context.Context, req interface{}) (interface{}, error) {
+		return srv.(QueryAdminServer).ExportCollectionIndexes(ctx, req.(*ExportCollectionIndexesRequest))
+	}
+	return interceptor(ctx, in, info, handler)
+}
+
+func _QueryAdmin_CheckCollectionIntegrity_Handler(srv interface{}, ctx context.Context, dec func(interface{}) error, interceptor grpc.UnaryServerInterceptor) (interface{}, error) {
+	in := new(CheckCollectionIntegrityRequest)
+	if err := dec(in); err != nil {
+		return nil, err
+	}
+	if interceptor == nil {
+		return srv.(QueryAdminServer).CheckCollectionIntegrity(ctx, in)
+	}
+	info := &grpc.UnaryServerInfo{
+		Server:     srv,
+		FullMethod: QueryAdmin_CheckCollectionIntegrity_FullMethodName,
+	}
+	handler := func(ctx context.Context, req interface{}) (interface{}, error) {
+		return srv.(QueryAdminServer).CheckCollectionIntegrity(ctx, req.(*CheckCollectionIntegrityRequest))
+	}
+	return interceptor(ctx, in, info, handler)
+}
+
+func _QueryAdmin_VerifyCollectionChecksum_Handler(srv interface{}, ctx context.Context, dec func(interface{}) error, interceptor grpc.UnaryServerInterceptor) (interface{}, error) {
+	in := new(VerifyCollectionChecksumRequest)
+	if err := dec(in); err != nil {
+		return nil, err
+	}
+	if interceptor == nil {
+		return srv.(QueryAdminServer).VerifyCollectionChecksum(ctx, in)
+	}
+	info := &grpc.UnaryServerInfo{
+		Server:     srv,
+		FullMethod: QueryAdmin_VerifyCollectionChecksum_FullMethodName,
+	}
+	handler := func(ctx context.Context, req interface{}) (interface{}, error) {
+		return srv.(QueryAdminServer).VerifyCollectionChecksum(ctx, req.(*VerifyCollectionChecksumRequest))
+	}
+	return interceptor(ctx, in, info, handler)
+}
+
+func _QueryAdmin_ListSlowQueries_Handler(srv interface{}, ctx context.Context, dec func(interface{}) error, interceptor grpc.UnaryServerInterceptor) (interface{}, error) {
+	in := new(ListSlowQueriesRequest)
+	if err := dec(in); err != nil {
+		return nil, err
+	}
+	if interceptor == nil {
+		return srv.(QueryAdminServer).ListSlowQueries(ctx, in)
+	}
+	info := &grpc.UnaryServerInfo{
+		Server:     srv,
+		FullMethod: QueryAdmin_ListSlowQueries_FullMethodName,
+	}
+	handler := func(ctx context.Context, req interface{}) (interface{}, error) {
+		return srv.(QueryAdminServer).ListSlowQueries(ctx, req.(*ListSlowQueriesRequest))
+	}
+	return interceptor(ctx, in, info, handler)
+}
+
+func _QueryAdmin_ListCachedBlocks_Handler(srv interface{}, ctx context.Context, dec func(interface{}) error, interceptor grpc.UnaryServerInterceptor) (interface{}, error) {
+	in := new(ListCachedBlocksRequest)
+	if err := dec(in); err != nil {
+		return nil, err
+	}
+	if interceptor == nil {
+		return srv.(QueryAdminServer).ListCachedBlocks(ctx, in)
+	}
+	info := &grpc.UnaryServerInfo{
+		Server:     srv,
+		FullMethod: QueryAdmin_ListCachedBlocks_FullMethodName,
+	}
+	handler := func(ctx context.Context, req interface{}) (interface{}, error) {
+		return srv.(QueryAdminServer).ListCachedBlocks(ctx, req.(*ListCachedBlocksRequest))
+	}
+	return interceptor(ctx, in, info, handler)
+}
+
+// QueryAdmin_ServiceDesc is the grpc.ServiceDesc for QueryAdmin service.
+// It's only intended for direct use with grpc.RegisterService,
+// and not to be introspected or modified (even as a copy)
+var QueryAdmin_ServiceDesc = grpc.ServiceDesc{
+	ServiceName: "chroma.QueryAdmin",
+	HandlerType: (*QueryAdminServer)(nil),
+	Methods: []grpc.MethodDesc{
+		{
+			MethodName: "WarmUpCollection",
+			Handler:    _QueryAdmin_WarmUpCollection_Handler,
+		},
+		{
+			MethodName: "ExportCollectionIndexes",
+			Handler:    _QueryAdmin_ExportCollectionIndexes_Handler,
+		},
+		{
+			MethodName: "CheckCollectionIntegrity",
+			Handler:    _QueryAdmin_CheckCollectionIntegrity_Handler,
+		},
+		{
+			MethodName: "VerifyCollectionChecksum",
+			Handler:    _QueryAdmin_VerifyCollectionChecksum_Handler,
+		},
+		{
+			MethodName: "ListSlowQueries",
+			Handler:    _QueryAdmin_ListSlowQueries_Handler,
+		},
+		{
+			MethodName: "ListCachedBlocks",
+			Handler:    _QueryAdmin_ListCachedBlocks_Handler,
+		},
+	},
+	Streams:  []grpc.StreamDesc{},
+	Metadata: "chromadb/proto/chroma.proto",
+}
+
+const (
+	CompactionAdmin_PauseCompaction_FullMethodName  = "/chroma.CompactionAdmin/PauseCompaction"
+	CompactionAdmin_ResumeCompaction_FullMethodName = "/chroma.CompactionAdmin/ResumeCompaction"
+)
+
+// CompactionAdminClient is the client API for CompactionAdmin service.
+//
+// For semantics around ctx use and closing/ending streaming RPCs, please refer to https://pkg.go.dev/google.golang.org/grpc/?tab=doc#ClientConn.NewStream.
+type CompactionAdminClient interface {
+	PauseCompaction(ctx context.Context, in *PauseCompactionRequest, opts ...grpc.CallOption) (*PauseCompactionResponse, error)
+	ResumeCompaction(ctx context.Context, in *ResumeCompactionRequest, opts ...grpc.CallOption) (*ResumeCompactionResponse, error)
+}
+
+type compactionAdminClient struct {
+	cc grpc.ClientConnInterface
+}
+
+func NewCompactionAdminClient(cc grpc.ClientConnInterface) CompactionAdminClient {
+	return &compactionAdminClient{cc}
+}
+
+func (c *compactionAdminClient) PauseCompaction(ctx context.Context, in *PauseCompactionRequest, opts ...grpc.CallOption) (*PauseCompactionResponse, error) {
+	cOpts := append([]grpc.CallOption{grpc.StaticMethod()}, opts...)
+	out := new(PauseCompactionResponse)
+	err := c.cc.Invoke(ctx, CompactionAdmin_PauseCompaction_FullMethodName, in, out, cOpts...)
+	if err != nil {
+		return nil, err
+	}
+	return out, nil
+}
+
+func (c *compactionAdminClient) ResumeCompaction(ctx context.Context, in *ResumeCompactionRequest, opts ...grpc.CallOption) (*ResumeCompactionResponse, error) {
+	cOpts := append([]grpc.CallOption{grpc.StaticMethod()}, opts...)
+	out := new(ResumeCompactionResponse)
+	err := c.cc.Invoke(ctx, CompactionAdmin_ResumeCompaction_FullMethodName, in, out, cOpts...)
+	if err != nil {
+		return nil, err
+	}
+	return out, nil
+}
+
+// CompactionAdminServer is the server API for CompactionAdmin service.
+// All implementations must embed UnimplementedCompactionAdminServer
+// for forward compatibility.
+type CompactionAdminServer interface {
+	PauseCompaction(context.Context, *PauseCompactionRequest) (*PauseCompactionResponse, error)
+	ResumeCompaction(context.Context, *ResumeCompactionRequest) (*ResumeCompactionResponse, error)
+	mustEmbedUnimplementedCompactionAdminServer()
+}
+
+// UnimplementedCompactionAdminServer must be embedded to have
+// forward compatible implementations.
+//
+// NOTE: this should be embedded by value instead of pointer to avoid a nil
+// pointer dereference when methods are called.
+type UnimplementedCompactionAdminServer struct{}
+
+func (UnimplementedCompactionAdminServer) PauseCompaction(context.Context, *PauseCompactionRequest) (*PauseCompactionResponse, error) {
+	return nil, status.Errorf(codes.Unimplemented, "method PauseCompaction not implemented")
+}
+func (UnimplementedCompactionAdminServer) ResumeCompaction(context.Context, *ResumeCompactionRequest) (*ResumeCompactionResponse, error) {
+	return nil, status.Errorf(codes.Unimplemented, "method ResumeCompaction not implemented")
+}
+func (UnimplementedCompactionAdminServer) mustEmbedUnimplementedCompactionAdminServer() {}
+func (UnimplementedCompactionAdminServer) testEmbeddedByValue()                         {}
+
+// UnsafeCompactionAdminServer may be embedded to opt out of forward compatibility for this service.
+// Use of this interface is not recommended, as added methods to CompactionAdminServer will
+// result in compilation errors.
+type UnsafeCompactionAdminServer interface {
+	mustEmbedUnimplementedCompactionAdminServer()
+}
+
+func RegisterCompactionAdminServer(s grpc.ServiceRegistrar, srv CompactionAdminServer) {
+	// If the following call pancis, it indicates UnimplementedCompactionAdminServer was
+	// embedded by pointer and is nil.  This will cause panics if an
+	// unimplemented method is ever invoked, so we test this at initialization
+	// time to prevent it from happening at runtime later due to I/O.
+	if t, ok := srv.(interface{ testEmbeddedByValue() }); ok {
+		t.testEmbeddedByValue()
+	}
+	s.RegisterService(&CompactionAdmin_ServiceDesc, srv)
+}
+
+func _CompactionAdmin_PauseCompaction_Handler(srv interface{}, ctx context.Context, dec func(interface{}) error, interceptor grpc.UnaryServerInterceptor) (interface{}, error) {
+	in := new(PauseCompactionRequest)
+	if err := dec(in); err != nil {
+		return nil, err
+	}
+	if interceptor == nil {
+		return srv.(CompactionAdminServer).PauseCompaction(ctx, in)
+	}
+	info := &grpc.UnaryServerInfo{
+		Server:     srv,
+		FullMethod: CompactionAdmin_PauseCompaction_FullMethodName,
+	}
+	handler := func(ctx context.Context, req interface{}) (interface{}, error) {
+		return srv.(CompactionAdminServer).PauseCompaction(ctx, req.(*PauseCompactionRequest))
+	}
+	return interceptor(ctx, in, info, handler)
+}
+
+func _CompactionAdmin_ResumeCompaction_Handler(srv interface{}, ctx context.Context, dec func(interface{}) error, interceptor grpc.UnaryServerInterceptor) (interface{}, error) {
+	in := new(ResumeCompactionRequest)
+	if err := dec(in); err != nil {
+		return nil, err
+	}
+	if interceptor == nil {
+		return srv.(CompactionAdminServer).ResumeCompaction(ctx, in)
+	}
+	info := &grpc.UnaryServerInfo{
+		Server:     srv,
+		FullMethod: CompactionAdmin_ResumeCompaction_FullMethodName,
+	}
+	handler := func(ctx context.Context, req interface{}) (interface{}, error) {
+		return srv.(CompactionAdminServer).ResumeCompaction(ctx, req.(*ResumeCompactionRequest))
+	}
+	return interceptor(ctx, in, info, handler)
+}
+
+// CompactionAdmin_ServiceDesc is the grpc.ServiceDesc for CompactionAdmin service.
+// It's only intended for direct use with grpc.RegisterService,
+// and not to be introspected or modified (even as a copy)
+var CompactionAdmin_ServiceDesc = grpc.ServiceDesc{
+	ServiceName: "chroma.CompactionAdmin",
+	HandlerType: (*CompactionAdminServer)(nil),
+	Methods: []grpc.MethodDesc{
+		{
+			MethodName: "PauseCompaction",
+			Handler:    _CompactionAdmin_PauseCompaction_Handler,
+		},
+		{
+			MethodName: "ResumeCompaction",
+			Handler:    _CompactionAdmin_ResumeCompaction_Handler,
+		},
 	},
 	Streams:  []grpc.StreamDesc{},
 	Metadata: "chromadb/proto/chroma.proto",
@@ -36,6 +36,8 @@ const (
 	SysDB_GetLastCompactionTimeForTenant_FullMethodName = "/chroma.SysDB/GetLastCompactionTimeForTenant"
 	SysDB_SetLastCompactionTimeForTenant_FullMethodName = "/chroma.SysDB/SetLastCompactionTimeForTenant"
 	SysDB_FlushCollectionCompaction_FullMethodName      = "/chroma.SysDB/FlushCollectionCompaction"
+	SysDB_SetCollectionAlias_FullMethodName             = "/chroma.SysDB/SetCollectionAlias"
+	SysDB_GetCollectionAlias_FullMethodName             = "/chroma.SysDB/GetCollectionAlias"
 )
 
 // SysDBClient is the client API for SysDB service.
@@ -58,6 +60,8 @@ type SysDBClient interface {
 	GetLastCompactionTimeForTenant(ctx context.Context, in *GetLastCompactionTimeForTenantRequest, opts ...grpc.CallOption) (*GetLastCompactionTimeForTenantResponse, error)
 	SetLastCompactionTimeForTenant(ctx context.Context, in *SetLastCompactionTimeForTenantRequest, opts ...grpc.CallOption) (*emptypb.Empty, error)
 	FlushCollectionCompaction(ctx context.Context, in *FlushCollectionCompactionRequest, opts ...grpc.CallOption) (*FlushCollectionCompactionResponse, error)
+	SetCollectionAlias(ctx context.Context, in *SetCollectionAliasRequest, opts ...grpc.CallOption) (*SetCollectionAliasResponse, error)
+	GetCollectionAlias(ctx context.Context, in *GetCollectionAliasRequest, opts ...grpc.CallOption) (*GetCollectionAliasResponse, error)
 }
 
 type sysDBClient struct {
@@ -228,6 +232,26 @@ func (c *sysDBClient) FlushCollectionCompaction(ctx context.Context, in *FlushCo
 	return out, nil
 }
 
+func (c *sysDBClient) SetCollectionAlias(ctx context.Context, in *SetCollectionAliasRequest, opts ...grpc.CallOption) (*SetCollectionAliasResponse, error) {
+	cOpts := append([]grpc.CallOption{grpc.StaticMethod()}, opts...)
+	out := new(SetCollectionAliasResponse)
+	err := c.cc.Invoke(ctx, SysDB_SetCollectionAlias_FullMethodName, in, out, cOpts...)
+	if err != nil {
+		return nil, err
+	}
+	return out, nil
+}
+
+func (c *sysDBClient) GetCollectionAlias(ctx context.Context, in *GetCollectionAliasRequest, opts ...grpc.CallOption) (*GetCollectionAliasResponse, error) {
+	cOpts := append([]grpc.CallOption{grpc.StaticMethod()}, opts...)
+	out := new(GetCollectionAliasResponse)
+	err := c.cc.Invoke(ctx, SysDB_GetCollectionAlias_FullMethodName, in, out, cOpts...)
+	if err != nil {
+		return nil, err
+	}
+	return out, nil
+}
+
 // SysDBServer is the server API for SysDB service.
 // All implementations must embed UnimplementedSysDBServer
 // for forward compatibility.
@@ -248,6 +272,8 @@ type SysDBServer interface {
 	GetLastCompactionTimeForTenant(context.Context, *GetLastCompactionTimeForTenantRequest) (*GetLastCompactionTimeForTenantResponse, error)
 	SetLastCompactionTimeForTenant(context.Context, *SetLastCompactionTimeForTenantRequest) (*emptypb.Empty, error)
 	FlushCollectionCompaction(context.Context, *FlushCollectionCompactionRequest) (*FlushCollectionCompactionResponse, error)
+	SetCollectionAlias(context.Context, *SetCollectionAliasRequest) (*SetCollectionAliasResponse, error)
+	GetCollectionAlias(context.Context, *GetCollectionAliasRequest) (*GetCollectionAliasResponse, error)
 	mustEmbedUnimplementedSysDBServer()
 }
 
@@ -306,6 +332,12 @@ func (UnimplementedSysDBServer) SetLastCompactionTimeForTenant(context.Context,
 func (UnimplementedSysDBServer) FlushCollectionCompaction(context.Context, *FlushCollectionCompactionRequest) (*FlushCollectionCompactionResponse, error) {
 	return nil, status.Errorf(codes.Unimplemented, "method FlushCollectionCompaction not implemented")
 }
+func (UnimplementedSysDBServer) SetCollectionAlias(context.Context, *SetCollectionAliasRequest) (*SetCollectionAliasResponse, error) {
+	return nil, status.Errorf(codes.Unimplemented, "method SetCollectionAlias not implemented")
+}
+func (UnimplementedSysDBServer) GetCollectionAlias(context.Context, *GetCollectionAliasRequest) (*GetCollectionAliasResponse, error) {
+	return nil, status.Errorf(codes.Unimplemented, "method GetCollectionAlias not implemented")
+}
 func (UnimplementedSysDBServer) mustEmbedUnimplementedSysDBServer() {}
 func (UnimplementedSysDBServer) testEmbeddedByValue()               {}
 
@@ -615,6 +647,42 @@ func _SysDB_FlushCollectionCompaction_Handler(srv interface{}, ctx context.Conte
 	return interceptor(ctx, in, info, handler)
 }
 
+func _SysDB_SetCollectionAlias_Handler(srv interface{}, ctx context.Context, dec func(interface{}) error, interceptor grpc.UnaryServerInterceptor) (interface{}, error) {
+	in := new(SetCollectionAliasRequest)
+	if err := dec(in); err != nil {
+		return nil, err
+	}
+	if interceptor == nil {
+		return srv.(SysDBServer).SetCollectionAlias(ctx, in)
+	}
+	info := &grpc.UnaryServerInfo{
+		Server:     srv,
+		FullMethod: SysDB_SetCollectionAlias_FullMethodName,
+	}
+	handler := func(ctx context.Context, req interface{}) (interface{}, error) {
+		return srv.(SysDBServer).SetCollectionAlias(ctx, req.(*SetCollectionAliasRequest))
+	}
+	return interceptor(ctx, in, info, handler)
+}
+
+func _SysDB_GetCollectionAlias_Handler(srv interface{}, ctx context.Context, dec func(interface{}) error, interceptor grpc.UnaryServerInterceptor) (interface{}, error) {
+	in := new(GetCollectionAliasRequest)
+	if err := dec(in); err != nil {
+		return nil, err
+	}
+	if interceptor == nil {
+		return srv.(SysDBServer).GetCollectionAlias(ctx, in)
+	}
+	info := &grpc.UnaryServerInfo{
+		Server:     srv,
+		FullMethod: SysDB_GetCollectionAlias_FullMethodName,
+	}
+	handler := func(ctx context.Context, req interface{}) (interface{}, error) {
+		return srv.(SysDBServer).GetCollectionAlias(ctx, req.(*GetCollectionAliasRequest))
+	}
+	return interceptor(ctx, in, info, handler)
+}
+
 // SysDB_ServiceDesc is the grpc.ServiceDesc for SysDB service.
 // It's only intended for direct use with grpc.RegisterService,
 // and not to be introspected or modified (even as a copy)
@@ -686,6 +754,14 @@ var SysDB_ServiceDesc = grpc.ServiceDesc{
 			MethodName: "FlushCollectionCompaction",
 			Handler:    _SysDB_FlushCollectionCompaction_Handler,
 		},
+		{
+			MethodName: "SetCollectionAlias",
+			Handler:    _SysDB_SetCollectionAlias_Handler,
+		},
+		{
+			MethodName: "GetCollectionAlias",
+			Handler:    _SysDB_GetCollectionAlias_Handler,
+		},
 	},
 	Streams:  []grpc.StreamDesc{},
 	Metadata: "chromadb/proto/coordinator.proto",
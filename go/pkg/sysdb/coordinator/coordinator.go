@@ -196,6 +196,14 @@ func (s *Coordinator) GetTenantsLastCompactionTime(ctx context.Context, tenantID
 	return s.catalog.GetTenantsLastCompactionTime(ctx, tenantIDs)
 }
 
+func (s *Coordinator) SetCollectionAlias(ctx context.Context, tenantID string, databaseName string, name string, collectionID types.UniqueID) error {
+	return s.catalog.SetCollectionAlias(ctx, tenantID, databaseName, name, collectionID)
+}
+
+func (s *Coordinator) GetCollectionAlias(ctx context.Context, tenantID string, databaseName string, name string) (types.UniqueID, error) {
+	return s.catalog.GetCollectionAlias(ctx, tenantID, databaseName, name)
+}
+
 func (s *Coordinator) FlushCollectionCompaction(ctx context.Context, flushCollectionCompaction *model.FlushCollectionCompaction) (*model.FlushCollectionInfo, error) {
 	return s.catalog.FlushCollectionCompaction(ctx, flushCollectionCompaction)
 }
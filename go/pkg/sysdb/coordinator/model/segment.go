@@ -42,6 +42,7 @@ type GetSegments struct {
 type FlushSegmentCompaction struct {
 	ID        types.UniqueID
 	FilePaths map[string][]string
+	Metadata  *SegmentMetadata[SegmentMetadataValueType]
 }
 
 func FilterSegments(segment *Segment, segmentID types.UniqueID, segmentType *string, scope *string, topic *string, collectionID types.UniqueID) bool {
@@ -3,12 +3,14 @@ package model
 import "github.com/chroma-core/chroma/go/pkg/types"
 
 type Tenant struct {
-	Name string
+	Name     string
+	KmsKeyID *string
 }
 
 type CreateTenant struct {
-	Name string
-	Ts   types.Timestamp
+	Name     string
+	Ts       types.Timestamp
+	KmsKeyID *string
 }
 
 type GetTenant struct {
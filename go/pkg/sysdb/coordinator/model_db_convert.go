@@ -185,6 +185,7 @@ func convertDatabaseToModel(dbDatabase *dbmodel.Database) *model.Database {
 
 func convertTenantToModel(dbTenant *dbmodel.Tenant) *model.Tenant {
 	return &model.Tenant{
-		Name: dbTenant.ID,
+		Name:     dbTenant.ID,
+		KmsKeyID: dbTenant.KmsKeyID,
 	}
 }
@@ -39,6 +39,11 @@ func (tc *Catalog) ResetState(ctx context.Context) error {
 			log.Error("error reset collection db", zap.Error(err))
 			return err
 		}
+		err = tc.metaDomain.CollectionAliasDb(txCtx).DeleteAll()
+		if err != nil {
+			log.Error("error reset collection alias db", zap.Error(err))
+			return err
+		}
 		err = tc.metaDomain.SegmentMetadataDb(txCtx).DeleteAll()
 		if err != nil {
 			log.Error("error reset segment metadata db", zap.Error(err))
@@ -169,6 +174,7 @@ func (tc *Catalog) CreateTenant(ctx context.Context, createTenant *model.CreateT
 			ID:                 createTenant.Name,
 			Ts:                 ts,
 			LastCompactionTime: time.Now().Unix(),
+			KmsKeyID:           createTenant.KmsKeyID,
 		}
 		err := tc.metaDomain.TenantDb(txCtx).Insert(dbTenant)
 		if err != nil {
@@ -757,6 +763,48 @@ func (tc *Catalog) GetTenantsLastCompactionTime(ctx context.Context, tenantIDs [
 	return tenants, err
 }
 
+// SetCollectionAlias points the alias to the collection, which must be in the database of the
+// tenant. An alias that already exists is re-pointed in place, so that the queries resolving it
+// switch from the old collection to the new one at once.
+func (tc *Catalog) SetCollectionAlias(ctx context.Context, tenantID string, databaseName string, name string, collectionID types.UniqueID) error {
+	if name == "" {
+		return common.ErrCollectionAliasNameEmpty
+	}
+	return tc.txImpl.Transaction(ctx, func(txCtx context.Context) error {
+		databases, err := tc.metaDomain.DatabaseDb(txCtx).GetDatabases(tenantID, databaseName)
+		if err != nil {
+			return err
+		}
+		if len(databases) == 0 {
+			return common.ErrDatabaseNotFound
+		}
+		collections, err := tc.metaDomain.CollectionDb(txCtx).GetCollections(types.FromUniqueID(collectionID), nil, tenantID, databaseName, nil, nil)
+		if err != nil {
+			return err
+		}
+		if len(collections) == 0 {
+			return common.ErrCollectionNotFound
+		}
+		return tc.metaDomain.CollectionAliasDb(txCtx).Upsert(&dbmodel.CollectionAlias{
+			DatabaseID:   databases[0].ID,
+			Name:         name,
+			CollectionID: collectionID.String(),
+		})
+	})
+}
+
+// GetCollectionAlias returns the collection that the alias in the database of the tenant points to.
+func (tc *Catalog) GetCollectionAlias(ctx context.Context, tenantID string, databaseName string, name string) (types.UniqueID, error) {
+	alias, err := tc.metaDomain.CollectionAliasDb(ctx).GetCollectionAlias(tenantID, databaseName, name)
+	if err != nil {
+		return types.NilUniqueID(), err
+	}
+	if alias == nil {
+		return types.NilUniqueID(), common.ErrCollectionAliasNotFound
+	}
+	return types.Parse(alias.CollectionID)
+}
+
 func (tc *Catalog) FlushCollectionCompaction(ctx context.Context, flushCollectionCompaction *model.FlushCollectionCompaction) (*model.FlushCollectionInfo, error) {
 	flushCollectionInfo := &model.FlushCollectionInfo{
 		ID: flushCollectionCompaction.ID.String(),
@@ -769,6 +817,25 @@ func (tc *Catalog) FlushCollectionCompaction(ctx context.Context, flushCollectio
 			return err
 		}
 
+		// update the segment metadata that the files are written with, so that it changes together with the files
+		for _, flushSegmentCompaction := range flushCollectionCompaction.FlushSegmentCompactions {
+			metadata := flushSegmentCompaction.Metadata
+			if metadata == nil {
+				continue
+			}
+			err = tc.metaDomain.SegmentMetadataDb(txCtx).DeleteBySegmentIDAndKeys(flushSegmentCompaction.ID.String(), metadata.Keys())
+			if err != nil {
+				return err
+			}
+			dbSegmentMetadataList := convertSegmentMetadataToDB(flushSegmentCompaction.ID.String(), metadata)
+			if len(dbSegmentMetadataList) != 0 {
+				err = tc.metaDomain.SegmentMetadataDb(txCtx).Insert(dbSegmentMetadataList)
+				if err != nil {
+					return err
+				}
+			}
+		}
+
 		// update collection log position and version
 		collectionVersion, err := tc.metaDomain.CollectionDb(txCtx).UpdateLogPositionAndVersion(flushCollectionCompaction.ID.String(), flushCollectionCompaction.LogPosition, flushCollectionCompaction.CurrentCollectionVersion)
 		if err != nil {
@@ -254,9 +254,15 @@ func (s *Server) FlushCollectionCompaction(ctx context.Context, req *coordinator
 		for key, filePath := range flushSegmentCompaction.FilePaths {
 			filePaths[key] = filePath.Paths
 		}
+		metadata, err := convertSegmentMetadataToModel(flushSegmentCompaction.Metadata)
+		if err != nil {
+			log.Error("FlushCollectionCompaction failed. error parsing segment metadata", zap.Error(err), zap.String("collection_id", req.CollectionId), zap.Int32("collection_version", req.CollectionVersion), zap.Int64("log_position", req.LogPosition))
+			return nil, grpcutils.BuildInternalGrpcError(err.Error())
+		}
 		segmentCompactionInfo = append(segmentCompactionInfo, &model.FlushSegmentCompaction{
 			ID:        segmentID,
 			FilePaths: filePaths,
+			Metadata:  metadata,
 		})
 	}
 	FlushCollectionCompaction := &model.FlushCollectionCompaction{
@@ -279,3 +285,45 @@ func (s *Server) FlushCollectionCompaction(ctx context.Context, req *coordinator
 	log.Info("FlushCollectionCompaction succeeded", zap.String("collection_id", req.CollectionId), zap.Int32("collection_version", req.CollectionVersion), zap.Int64("log_position", req.LogPosition))
 	return res, nil
 }
+
+func (s *Server) SetCollectionAlias(ctx context.Context, req *coordinatorpb.SetCollectionAliasRequest) (*coordinatorpb.SetCollectionAliasResponse, error) {
+	res := &coordinatorpb.SetCollectionAliasResponse{}
+	collectionID, err := types.ToUniqueID(&req.CollectionId)
+	err = grpcutils.BuildErrorForUUID(collectionID, "collection", err)
+	if err != nil {
+		log.Error("SetCollectionAlias failed. error parsing collection id", zap.Error(err), zap.String("request", req.String()))
+		return res, err
+	}
+	err = s.coordinator.SetCollectionAlias(ctx, req.GetTenant(), req.GetDatabase(), req.GetName(), collectionID)
+	if err != nil {
+		log.Error("SetCollectionAlias failed", zap.Error(err), zap.String("request", req.String()))
+		if err == common.ErrCollectionNotFound || err == common.ErrDatabaseNotFound {
+			return res, grpcutils.BuildNotFoundGrpcError(err.Error())
+		}
+		if err == common.ErrCollectionAliasNameEmpty {
+			grpcError, err := grpcutils.BuildInvalidArgumentGrpcError("name", err.Error())
+			if err != nil {
+				return res, err
+			}
+			return res, grpcError
+		}
+		return res, grpcutils.BuildInternalGrpcError(err.Error())
+	}
+	log.Info("SetCollectionAlias succeeded", zap.String("request", req.String()))
+	return res, nil
+}
+
+func (s *Server) GetCollectionAlias(ctx context.Context, req *coordinatorpb.GetCollectionAliasRequest) (*coordinatorpb.GetCollectionAliasResponse, error) {
+	res := &coordinatorpb.GetCollectionAliasResponse{}
+	collectionID, err := s.coordinator.GetCollectionAlias(ctx, req.GetTenant(), req.GetDatabase(), req.GetName())
+	if err != nil {
+		log.Error("GetCollectionAlias failed", zap.Error(err), zap.String("request", req.String()))
+		if err == common.ErrCollectionAliasNotFound {
+			return res, grpcutils.BuildNotFoundGrpcError(err.Error())
+		}
+		return res, grpcutils.BuildInternalGrpcError(err.Error())
+	}
+	res.CollectionId = collectionID.String()
+	log.Info("GetCollectionAlias succeeded", zap.String("request", req.String()))
+	return res, nil
+}
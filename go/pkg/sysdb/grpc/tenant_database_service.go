@@ -59,7 +59,8 @@ func (s *Server) GetDatabase(ctx context.Context, req *coordinatorpb.GetDatabase
 func (s *Server) CreateTenant(ctx context.Context, req *coordinatorpb.CreateTenantRequest) (*coordinatorpb.CreateTenantResponse, error) {
 	res := &coordinatorpb.CreateTenantResponse{}
 	createTenant := &model.CreateTenant{
-		Name: req.GetName(),
+		Name:     req.GetName(),
+		KmsKeyID: req.KmsKeyId,
 	}
 	_, err := s.coordinator.CreateTenant(ctx, createTenant)
 	if err != nil {
@@ -87,7 +88,8 @@ func (s *Server) GetTenant(ctx context.Context, req *coordinatorpb.GetTenantRequ
 		return res, grpcutils.BuildInternalGrpcError(err.Error())
 	}
 	res.Tenant = &coordinatorpb.Tenant{
-		Name: tenant.Name,
+		Name:     tenant.Name,
+		KmsKeyId: tenant.KmsKeyID,
 	}
 	log.Info("GetTenant success", zap.String("request", req.String()))
 	return res, nil
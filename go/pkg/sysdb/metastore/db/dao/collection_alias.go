@@ -0,0 +1,58 @@
+package dao
+
+import (
+	"errors"
+
+	"github.com/chroma-core/chroma/go/pkg/sysdb/metastore/db/dbmodel"
+	"github.com/pingcap/log"
+	"go.uber.org/zap"
+	"gorm.io/gorm"
+	"gorm.io/gorm/clause"
+)
+
+type collectionAliasDb struct {
+	db *gorm.DB
+}
+
+var _ dbmodel.ICollectionAliasDb = &collectionAliasDb{}
+
+func (s *collectionAliasDb) DeleteAll() error {
+	return s.db.Where("1 = 1").Delete(&dbmodel.CollectionAlias{}).Error
+}
+
+// GetCollectionAlias returns the alias with the given name in the database of the tenant, or nil
+// if there is no such alias or the collection that it points to is deleted.
+func (s *collectionAliasDb) GetCollectionAlias(tenantID string, databaseName string, name string) (*dbmodel.CollectionAlias, error) {
+	var alias dbmodel.CollectionAlias
+	err := s.db.Table("collection_aliases").
+		Select("collection_aliases.database_id, collection_aliases.name, collection_aliases.collection_id, collection_aliases.created_at, collection_aliases.updated_at").
+		Joins("INNER JOIN databases ON collection_aliases.database_id = databases.id").
+		Joins("INNER JOIN collections ON collection_aliases.collection_id = collections.id").
+		Where("databases.tenant_id = ?", tenantID).
+		Where("databases.name = ?", databaseName).
+		Where("collection_aliases.name = ?", name).
+		Where("collections.is_deleted = ?", false).
+		First(&alias).Error
+	if err != nil {
+		if errors.Is(err, gorm.ErrRecordNotFound) {
+			return nil, nil
+		}
+		log.Error("GetCollectionAlias", zap.Error(err))
+		return nil, err
+	}
+	return &alias, nil
+}
+
+// Upsert points the alias to its collection, replacing the collection that it pointed to before in
+// a single statement, so that readers see either the old or the new collection.
+func (s *collectionAliasDb) Upsert(in *dbmodel.CollectionAlias) error {
+	err := s.db.Clauses(clause.OnConflict{
+		Columns:   []clause.Column{{Name: "database_id"}, {Name: "name"}},
+		DoUpdates: clause.Assignments(map[string]interface{}{"collection_id": in.CollectionID, "updated_at": gorm.Expr("CURRENT_TIMESTAMP")}),
+	}).Create(in).Error
+	if err != nil {
+		log.Error("upsert collection alias failed", zap.Error(err))
+		return err
+	}
+	return nil
+}
@@ -206,6 +206,59 @@ func (suite *CollectionDbTestSuite) TestCollectionDb_SoftDelete() {
 	suite.NoError(err)
 }
 
+func (suite *CollectionDbTestSuite) TestCollectionDb_Alias() {
+	collectionAliasDb := &collectionAliasDb{
+		db: suite.db,
+	}
+	blueID, err := CreateTestCollection(suite.db, "test_collection_alias_blue", 128, suite.databaseId)
+	suite.NoError(err)
+	greenID, err := CreateTestCollection(suite.db, "test_collection_alias_green", 128, suite.databaseId)
+	suite.NoError(err)
+
+	// An alias that does not exist resolves to nothing
+	alias, err := collectionAliasDb.GetCollectionAlias(suite.tenantName, suite.databaseName, "live")
+	suite.NoError(err)
+	suite.Nil(alias)
+
+	err = collectionAliasDb.Upsert(&dbmodel.CollectionAlias{DatabaseID: suite.databaseId, Name: "live", CollectionID: blueID})
+	suite.NoError(err)
+	alias, err = collectionAliasDb.GetCollectionAlias(suite.tenantName, suite.databaseName, "live")
+	suite.NoError(err)
+	suite.Equal(blueID, alias.CollectionID)
+
+	// Re-point the alias to the other collection
+	err = collectionAliasDb.Upsert(&dbmodel.CollectionAlias{DatabaseID: suite.databaseId, Name: "live", CollectionID: greenID})
+	suite.NoError(err)
+	alias, err = collectionAliasDb.GetCollectionAlias(suite.tenantName, suite.databaseName, "live")
+	suite.NoError(err)
+	suite.Equal(greenID, alias.CollectionID)
+
+	// The alias is scoped to its database
+	alias, err = collectionAliasDb.GetCollectionAlias(suite.tenantName, "other_database", "live")
+	suite.NoError(err)
+	suite.Nil(alias)
+
+	// An alias to a deleted collection resolves to nothing
+	err = suite.collectionDb.Update(&dbmodel.Collection{
+		ID:         greenID,
+		DatabaseID: suite.databaseId,
+		IsDeleted:  true,
+		UpdatedAt:  time.Now(),
+	})
+	suite.NoError(err)
+	alias, err = collectionAliasDb.GetCollectionAlias(suite.tenantName, suite.databaseName, "live")
+	suite.NoError(err)
+	suite.Nil(alias)
+
+	// Clean up
+	err = collectionAliasDb.DeleteAll()
+	suite.NoError(err)
+	err = CleanUpTestCollection(suite.db, blueID)
+	suite.NoError(err)
+	err = CleanUpTestCollection(suite.db, greenID)
+	suite.NoError(err)
+}
+
 func TestCollectionDbTestSuiteSuite(t *testing.T) {
 	testSuite := new(CollectionDbTestSuite)
 	suite.Run(t, testSuite)
@@ -25,6 +25,10 @@ func (*MetaDomain) CollectionDb(ctx context.Context) dbmodel.ICollectionDb {
 	return &collectionDb{dbcore.GetDB(ctx)}
 }
 
+func (*MetaDomain) CollectionAliasDb(ctx context.Context) dbmodel.ICollectionAliasDb {
+	return &collectionAliasDb{dbcore.GetDB(ctx)}
+}
+
 func (*MetaDomain) CollectionMetadataDb(ctx context.Context) dbmodel.ICollectionMetadataDb {
 	return &collectionMetadataDb{dbcore.GetDB(ctx)}
 }
@@ -96,6 +96,24 @@ func (suite *TenantDbTestSuite) TestTenantDb_GetTenantsLastCompactionTime() {
 	}
 }
 
+func (suite *TenantDbTestSuite) TestTenantDb_GetTenantsKmsKeyID() {
+	tenantId := "testGetTenantsKmsKeyID"
+	kmsKeyID := "testKmsKeyID"
+	err := suite.Db.Insert(&dbmodel.Tenant{
+		ID:       tenantId,
+		KmsKeyID: &kmsKeyID,
+	})
+	suite.Require().NoError(err)
+
+	tenants, err := suite.Db.GetTenants(tenantId)
+	suite.Require().NoError(err)
+	suite.Require().Len(tenants, 1)
+	suite.Require().NotNil(tenants[0].KmsKeyID)
+	suite.Require().Equal(kmsKeyID, *tenants[0].KmsKeyID)
+
+	suite.db.Delete(&dbmodel.Tenant{}, "id = ?", tenantId)
+}
+
 func TestTenantDbTestSuite(t *testing.T) {
 	testSuite := new(TenantDbTestSuite)
 	testSuite.t = t
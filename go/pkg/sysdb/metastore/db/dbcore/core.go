@@ -179,6 +179,10 @@ func CreateTestTables(db *gorm.DB) {
 	if !tableExist {
 		db.Migrator().CreateTable(&dbmodel.Collection{})
 	}
+	tableExist = db.Migrator().HasTable(&dbmodel.CollectionAlias{})
+	if !tableExist {
+		db.Migrator().CreateTable(&dbmodel.CollectionAlias{})
+	}
 	tableExist = db.Migrator().HasTable(&dbmodel.SegmentMetadata{})
 	if !tableExist {
 		db.Migrator().CreateTable(&dbmodel.SegmentMetadata{})
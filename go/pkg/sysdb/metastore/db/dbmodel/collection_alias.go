@@ -0,0 +1,24 @@
+package dbmodel
+
+import (
+	"time"
+)
+
+type CollectionAlias struct {
+	DatabaseID   string    `gorm:"database_id;primaryKey"`
+	Name         string    `gorm:"name;primaryKey"`
+	CollectionID string    `gorm:"collection_id;not null"`
+	CreatedAt    time.Time `gorm:"created_at;type:timestamp;not null;default:current_timestamp"`
+	UpdatedAt    time.Time `gorm:"updated_at;type:timestamp;not null;default:current_timestamp"`
+}
+
+func (v CollectionAlias) TableName() string {
+	return "collection_aliases"
+}
+
+//go:generate mockery --name=ICollectionAliasDb
+type ICollectionAliasDb interface {
+	GetCollectionAlias(tenantID string, databaseName string, name string) (*CollectionAlias, error)
+	Upsert(in *CollectionAlias) error
+	DeleteAll() error
+}
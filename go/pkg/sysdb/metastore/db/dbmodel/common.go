@@ -11,6 +11,7 @@ type IMetaDomain interface {
 	DatabaseDb(ctx context.Context) IDatabaseDb
 	TenantDb(ctx context.Context) ITenantDb
 	CollectionDb(ctx context.Context) ICollectionDb
+	CollectionAliasDb(ctx context.Context) ICollectionAliasDb
 	CollectionMetadataDb(ctx context.Context) ICollectionMetadataDb
 	SegmentDb(ctx context.Context) ISegmentDb
 	SegmentMetadataDb(ctx context.Context) ISegmentMetadataDb
@@ -0,0 +1,93 @@
+// Code generated by mockery v2.46.2. DO NOT EDIT.
+
+package mocks
+
+import (
+	dbmodel "github.com/chroma-core/chroma/go/pkg/sysdb/metastore/db/dbmodel"
+	mock "github.com/stretchr/testify/mock"
+)
+
+// ICollectionAliasDb is an autogenerated mock type for the ICollectionAliasDb type
+type ICollectionAliasDb struct {
+	mock.Mock
+}
+
+// DeleteAll provides a mock function with given fields:
+func (_m *ICollectionAliasDb) DeleteAll() error {
+	ret := _m.Called()
+
+	if len(ret) == 0 {
+		panic("no return value specified for DeleteAll")
+	}
+
+	var r0 error
+	if rf, ok := ret.Get(0).(func() error); ok {
+		r0 = rf()
+	} else {
+		r0 = ret.Error(0)
+	}
+
+	return r0
+}
+
+// GetCollectionAlias provides a mock function with given fields: tenantID, databaseName, name
+func (_m *ICollectionAliasDb) GetCollectionAlias(tenantID string, databaseName string, name string) (*dbmodel.CollectionAlias, error) {
+	ret := _m.Called(tenantID, databaseName, name)
+
+	if len(ret) == 0 {
+		panic("no return value specified for GetCollectionAlias")
+	}
+
+	var r0 *dbmodel.CollectionAlias
+	var r1 error
+	if rf, ok := ret.Get(0).(func(string, string, string) (*dbmodel.CollectionAlias, error)); ok {
+		return rf(tenantID, databaseName, name)
+	}
+	if rf, ok := ret.Get(0).(func(string, string, string) *dbmodel.CollectionAlias); ok {
+		r0 = rf(tenantID, databaseName, name)
+	} else {
+		if ret.Get(0) != nil {
+			r0 = ret.Get(0).(*dbmodel.CollectionAlias)
+		}
+	}
+
+	if rf, ok := ret.Get(1).(func(string, string, string) error); ok {
+		r1 = rf(tenantID, databaseName, name)
+	} else {
+		r1 = ret.Error(1)
+	}
+
+	return r0, r1
+}
+
+// Upsert provides a mock function with given fields: in
+func (_m *ICollectionAliasDb) Upsert(in *dbmodel.CollectionAlias) error {
+	ret := _m.Called(in)
+
+	if len(ret) == 0 {
+		panic("no return value specified for Upsert")
+	}
+
+	var r0 error
+	if rf, ok := ret.Get(0).(func(*dbmodel.CollectionAlias) error); ok {
+		r0 = rf(in)
+	} else {
+		r0 = ret.Error(0)
+	}
+
+	return r0
+}
+
+// NewICollectionAliasDb creates a new instance of ICollectionAliasDb. It also registers a testing interface on the mock and a cleanup function to assert the mocks expectations.
+// The first argument is typically a *testing.T value.
+func NewICollectionAliasDb(t interface {
+	mock.TestingT
+	Cleanup(func())
+}) *ICollectionAliasDb {
+	mock := &ICollectionAliasDb{}
+	mock.Mock.Test(t)
+
+	t.Cleanup(func() { mock.AssertExpectations(t) })
+
+	return mock
+}
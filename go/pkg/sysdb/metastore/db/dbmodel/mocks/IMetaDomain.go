@@ -14,6 +14,26 @@ type IMetaDomain struct {
 	mock.Mock
 }
 
+// CollectionAliasDb provides a mock function with given fields: ctx
+func (_m *IMetaDomain) CollectionAliasDb(ctx context.Context) dbmodel.ICollectionAliasDb {
+	ret := _m.Called(ctx)
+
+	if len(ret) == 0 {
+		panic("no return value specified for CollectionAliasDb")
+	}
+
+	var r0 dbmodel.ICollectionAliasDb
+	if rf, ok := ret.Get(0).(func(context.Context) dbmodel.ICollectionAliasDb); ok {
+		r0 = rf(ctx)
+	} else {
+		if ret.Get(0) != nil {
+			r0 = ret.Get(0).(dbmodel.ICollectionAliasDb)
+		}
+	}
+
+	return r0
+}
+
 // CollectionDb provides a mock function with given fields: ctx
 func (_m *IMetaDomain) CollectionDb(ctx context.Context) dbmodel.ICollectionDb {
 	ret := _m.Called(ctx)
@@ -13,6 +13,7 @@ type Tenant struct {
 	CreatedAt          time.Time       `gorm:"created_at;type:timestamp;not null;default:current_timestamp"`
 	UpdatedAt          time.Time       `gorm:"updated_at;type:timestamp;not null;default:current_timestamp"`
 	LastCompactionTime int64           `gorm:"last_compaction_time;not null"`
+	KmsKeyID           *string         `gorm:"kms_key_id;type:text;default:null"`
 }
 
 func (v Tenant) TableName() string {
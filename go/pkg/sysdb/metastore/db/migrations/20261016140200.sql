@@ -0,0 +1,2 @@
+-- Modify "tenants" table
+ALTER TABLE "public"."tenants" ADD COLUMN "kms_key_id" text NULL;
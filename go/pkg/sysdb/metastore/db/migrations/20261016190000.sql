@@ -0,0 +1,9 @@
+-- Create "collection_aliases" table
+CREATE TABLE "public"."collection_aliases" (
+  "database_id" text NOT NULL,
+  "name" text NOT NULL,
+  "collection_id" text NOT NULL,
+  "created_at" timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  "updated_at" timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY ("database_id", "name")
+);
@@ -129,6 +129,7 @@ impl DataRecordStorage {
             bit_util::round_upto_multiple_of_64(inner.size_tracker.get_metadata_size());
         let document_size =
             bit_util::round_upto_multiple_of_64(inner.size_tracker.get_document_size());
+        let uri_size = bit_util::round_upto_multiple_of_64(inner.size_tracker.get_uri_size());
 
         // offset sizing
         // https://docs.rs/arrow-buffer/52.2.0/arrow_buffer/buffer/struct.OffsetBuffer.html
@@ -138,10 +139,11 @@ impl DataRecordStorage {
         let id_offset = bit_util::round_upto_multiple_of_64((self.len() + 1) * 4);
         let metdata_offset = bit_util::round_upto_multiple_of_64((self.len() + 1) * 4);
         let document_offset = bit_util::round_upto_multiple_of_64((self.len() + 1) * 4);
+        let uri_offset = bit_util::round_upto_multiple_of_64((self.len() + 1) * 4);
 
-        // validity sizing both document and metadata can be null
+        // validity sizing document, metadata and uri can be null
         // https://docs.rs/arrow-buffer/52.2.0/src/arrow_buffer/buffer/null.rs.html#153-155
-        let validity_bytes = bit_util::round_upto_multiple_of_64(bit_util::ceil(self.len(), 8)) * 2;
+        let validity_bytes = bit_util::round_upto_multiple_of_64(bit_util::ceil(self.len(), 8)) * 3;
 
         prefix_size
             + key_size
@@ -149,11 +151,13 @@ impl DataRecordStorage {
             + embedding_size
             + metadata_size
             + document_size
+            + uri_size
             + prefix_offset_bytes
             + key_offset_bytes
             + id_offset
             + metdata_offset
             + document_offset
+            + uri_offset
             + validity_bytes
     }
 
@@ -179,10 +183,11 @@ impl DataRecordStorage {
             let id_offset = bit_util::round_upto_multiple_of_64((item_count + 1) * 4);
             let metdata_offset = bit_util::round_upto_multiple_of_64((item_count + 1) * 4);
             let document_offset = bit_util::round_upto_multiple_of_64((item_count + 1) * 4);
+            let uri_offset = bit_util::round_upto_multiple_of_64((item_count + 1) * 4);
 
-            // validity sizing both document and metadata can be null
+            // validity sizing document, metadata and uri can be null
             let validity_bytes =
-                bit_util::round_upto_multiple_of_64(bit_util::ceil(item_count, 8)) * 2;
+                bit_util::round_upto_multiple_of_64(bit_util::ceil(item_count, 8)) * 3;
 
             // round all running sizes to 64 and add them together
             let total_size =
@@ -194,11 +199,13 @@ impl DataRecordStorage {
                     )
                     + bit_util::round_upto_multiple_of_64(size_up_to_split_key.get_metadata_size())
                     + bit_util::round_upto_multiple_of_64(size_up_to_split_key.get_document_size())
+                    + bit_util::round_upto_multiple_of_64(size_up_to_split_key.get_uri_size())
                     + prefix_offset_bytes
                     + key_offset_bytes
                     + id_offset
                     + metdata_offset
                     + document_offset
+                    + uri_offset
                     + validity_bytes;
 
             if total_size > split_size {
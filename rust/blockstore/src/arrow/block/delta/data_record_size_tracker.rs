@@ -11,6 +11,7 @@ pub struct DataRecordSizeTracker {
     embedding_size: usize,
     metadata_size: usize,
     document_size: usize,
+    uri_size: usize,
     embedding_dimension: Option<usize>,
 }
 
@@ -26,6 +27,7 @@ impl Sub for DataRecordSizeTracker {
             embedding_size: self.embedding_size - rhs.embedding_size,
             metadata_size: self.metadata_size - rhs.metadata_size,
             document_size: self.document_size - rhs.document_size,
+            uri_size: self.uri_size - rhs.uri_size,
             embedding_dimension: self.embedding_dimension,
         }
     }
@@ -64,6 +66,10 @@ impl DataRecordSizeTracker {
         self.document_size
     }
 
+    pub fn get_uri_size(&self) -> usize {
+        self.uri_size
+    }
+
     pub fn get_embedding_dimension(&self) -> Option<usize> {
         self.embedding_dimension
     }
@@ -88,11 +94,12 @@ impl DataRecordSizeTracker {
         &mut self,
         value: &<&chroma_types::DataRecord<'_> as ArrowWriteableValue>::PreparedValue,
     ) {
-        let (id, embedding, metadata, document) = value;
+        let (id, embedding, metadata, document, uri) = value;
         self.id_size += id.len();
         self.embedding_size += embedding.len() * 4;
         self.metadata_size += metadata.as_ref().map(|m| m.len()).unwrap_or(0);
         self.document_size += document.as_ref().map(|d| d.len()).unwrap_or(0);
+        self.uri_size += uri.as_ref().map(|u| u.len()).unwrap_or(0);
         self.embedding_dimension = Some(embedding.len()); // todo: return error if embedding size has changed
     }
 
@@ -100,11 +107,12 @@ impl DataRecordSizeTracker {
         &mut self,
         value: &<&chroma_types::DataRecord<'_> as ArrowWriteableValue>::PreparedValue,
     ) {
-        let (id, embedding, metadata, document) = value;
+        let (id, embedding, metadata, document, uri) = value;
         self.id_size -= id.len();
         self.embedding_size -= embedding.len() * 4;
         self.metadata_size -= metadata.as_ref().map(|m| m.len()).unwrap_or(0);
         self.document_size -= document.as_ref().map(|d| d.len()).unwrap_or(0);
+        self.uri_size -= uri.as_ref().map(|u| u.len()).unwrap_or(0);
     }
 
     pub fn increment_item_count(&mut self) {
@@ -8,7 +8,8 @@ use crate::{
 };
 use arrow::{
     array::{
-        Array, ArrayRef, BooleanBuilder, Float32Builder, RecordBatch, StringBuilder, UInt32Builder,
+        Array, ArrayRef, BooleanBuilder, FixedSizeBinaryBuilder, Float32Builder, RecordBatch,
+        StringBuilder, UInt32Builder, UInt64Builder,
     },
     datatypes::Field,
 };
@@ -48,6 +49,8 @@ pub enum BlockKeyArrowBuilder {
     String((StringBuilder, StringBuilder)),
     Float32((StringBuilder, Float32Builder)),
     UInt32((StringBuilder, UInt32Builder)),
+    UInt64((StringBuilder, UInt64Builder)),
+    Uuid((StringBuilder, FixedSizeBinaryBuilder)),
 }
 
 impl BlockKeyArrowBuilder {
@@ -93,6 +96,29 @@ impl BlockKeyArrowBuilder {
                 builder.0.append_value(key.prefix);
                 builder.1.append_value(value);
             }
+            KeyWrapper::Uint64(value) => {
+                let builder = match self {
+                    BlockKeyArrowBuilder::UInt64(builder) => builder,
+                    _ => {
+                        unreachable!("Invariant violation. BlockKeyArrowBuilder should be UInt64.")
+                    }
+                };
+                builder.0.append_value(key.prefix);
+                builder.1.append_value(value);
+            }
+            KeyWrapper::Uuid(value) => {
+                let builder = match self {
+                    BlockKeyArrowBuilder::Uuid(builder) => builder,
+                    _ => {
+                        unreachable!("Invariant violation. BlockKeyArrowBuilder should be Uuid.")
+                    }
+                };
+                builder.0.append_value(key.prefix);
+                builder
+                    .1
+                    .append_value(value.as_bytes())
+                    .expect("Invariant violation. A UUID is 16 bytes.");
+            }
         }
     }
 
@@ -146,6 +172,34 @@ impl BlockKeyArrowBuilder {
                     (&key_arr as &dyn Array).slice(0, key_arr.len()),
                 )
             }
+            BlockKeyArrowBuilder::UInt64((ref mut prefix_builder, ref mut key_builder)) => {
+                let prefix_field = Field::new("prefix", arrow::datatypes::DataType::Utf8, false);
+                let key_field = Field::new("key", arrow::datatypes::DataType::UInt64, false);
+                let prefix_arr = prefix_builder.finish();
+                let key_arr = key_builder.finish();
+                (
+                    prefix_field,
+                    (&prefix_arr as &dyn Array).slice(0, prefix_arr.len()),
+                    key_field,
+                    (&key_arr as &dyn Array).slice(0, key_arr.len()),
+                )
+            }
+            BlockKeyArrowBuilder::Uuid((ref mut prefix_builder, ref mut key_builder)) => {
+                let prefix_field = Field::new("prefix", arrow::datatypes::DataType::Utf8, false);
+                let key_field = Field::new(
+                    "key",
+                    arrow::datatypes::DataType::FixedSizeBinary(16),
+                    false,
+                );
+                let prefix_arr = prefix_builder.finish();
+                let key_arr = key_builder.finish();
+                (
+                    prefix_field,
+                    (&prefix_arr as &dyn Array).slice(0, prefix_arr.len()),
+                    key_field,
+                    (&key_arr as &dyn Array).slice(0, key_arr.len()),
+                )
+            }
         }
     }
 }
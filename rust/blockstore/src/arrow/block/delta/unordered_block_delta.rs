@@ -137,6 +137,7 @@ impl UnorderedBlockDelta {
 mod test {
     use crate::arrow::{
         block::{delta::UnorderedBlockDelta, Block},
+        compression::BlockCodec,
         config::TEST_MAX_BLOCK_SIZE_BYTES,
         provider::BlockManager,
     };
@@ -194,7 +195,11 @@ mod test {
             values_before_flush.push(read.to_vec());
         }
         block_manager.flush(&block).await.unwrap();
-        let block = block_manager.get(&block.clone().id).await.unwrap().unwrap();
+        let block = block_manager
+            .get(&block.clone().id, BlockCodec::None)
+            .await
+            .unwrap()
+            .unwrap();
         #[allow(clippy::needless_range_loop)]
         for i in 0..n {
             let key = format!("key{}", i);
@@ -234,7 +239,11 @@ mod test {
         }
         block_manager.flush(&block).await.unwrap();
 
-        let block = block_manager.get(&delta_id).await.unwrap().unwrap();
+        let block = block_manager
+            .get(&delta_id, BlockCodec::None)
+            .await
+            .unwrap()
+            .unwrap();
 
         assert_eq!(size, block.get_size());
         #[allow(clippy::needless_range_loop)]
@@ -255,13 +264,17 @@ mod test {
 
         // test fork
         let forked_block = block_manager
-            .fork::<&str, String, UnorderedBlockDelta>(&delta_id)
+            .fork::<&str, String, UnorderedBlockDelta>(&delta_id, BlockCodec::None)
             .await
             .unwrap();
         let new_id = forked_block.id;
         let block = block_manager.commit::<&str, String>(forked_block).await;
         block_manager.flush(&block).await.unwrap();
-        let forked_block = block_manager.get(&new_id).await.unwrap().unwrap();
+        let forked_block = block_manager
+            .get(&new_id, BlockCodec::None)
+            .await
+            .unwrap()
+            .unwrap();
         for i in 0..n {
             let key = format!("key{}", i);
             let read = forked_block.get::<&str, &str>("prefix", &key);
@@ -296,7 +309,11 @@ mod test {
             values_before_flush.push(read);
         }
         block_manager.flush(&block).await.unwrap();
-        let block = block_manager.get(&delta_id).await.unwrap().unwrap();
+        let block = block_manager
+            .get(&delta_id, BlockCodec::None)
+            .await
+            .unwrap()
+            .unwrap();
         assert_eq!(size, block.get_size());
         #[allow(clippy::needless_range_loop)]
         for i in 0..n {
@@ -329,7 +346,11 @@ mod test {
         let delta_id = delta.id;
         let block = block_manager.commit::<&str, RoaringBitmap>(delta).await;
         block_manager.flush(&block).await.unwrap();
-        let block = block_manager.get(&delta_id).await.unwrap().unwrap();
+        let block = block_manager
+            .get(&delta_id, BlockCodec::None)
+            .await
+            .unwrap()
+            .unwrap();
 
         assert_eq!(size, block.get_size());
 
@@ -362,6 +383,7 @@ mod test {
         let metadata = Some(metadata);
         let metadatas = [None, metadata.clone(), None];
         let documents = [None, Some("test document"), None];
+        let uris = [Some("s3://bucket/key"), None, None];
         let delta = block_manager.create::<&str, &DataRecord, UnorderedBlockDelta>();
 
         //TODO: Option<&T> as opposed to &Option<T>
@@ -371,18 +393,21 @@ mod test {
                 embedding: &embeddings[0],
                 metadata: metadatas[0].clone(),
                 document: documents[0],
+                uri: uris[0],
             },
             DataRecord {
                 id: ids[1],
                 embedding: &embeddings[1],
                 metadata: metadatas[1].clone(),
                 document: documents[1],
+                uri: uris[1],
             },
             DataRecord {
                 id: ids[2],
                 embedding: &embeddings[2],
                 metadata: metadatas[2].clone(),
                 document: documents[2],
+                uri: uris[2],
             },
         ];
 
@@ -394,13 +419,18 @@ mod test {
         let delta_id = delta.id;
         let block = block_manager.commit::<&str, &DataRecord>(delta).await;
         block_manager.flush(&block).await.unwrap();
-        let block = block_manager.get(&delta_id).await.unwrap().unwrap();
+        let block = block_manager
+            .get(&delta_id, BlockCodec::None)
+            .await
+            .unwrap()
+            .unwrap();
         for i in 0..3 {
             let read = block.get::<&str, DataRecord>("", ids[i]).unwrap();
             assert_eq!(read.id, ids[i]);
             assert_eq!(read.embedding, &embeddings[i]);
             assert_eq!(read.metadata, metadatas[i]);
             assert_eq!(read.document, documents[i]);
+            assert_eq!(read.uri, uris[i]);
         }
         assert_eq!(size, block.get_size());
 
@@ -429,7 +459,11 @@ mod test {
         let delta_id = delta.id;
         let block = block_manager.commit::<u32, String>(delta).await;
         block_manager.flush(&block).await.unwrap();
-        let block = block_manager.get(&delta_id).await.unwrap().unwrap();
+        let block = block_manager
+            .get(&delta_id, BlockCodec::None)
+            .await
+            .unwrap()
+            .unwrap();
         assert_eq!(size, block.get_size());
 
         // test save/load
@@ -465,7 +499,11 @@ mod test {
         }
         block_manager.flush(&block).await.unwrap();
 
-        let block = block_manager.get(&delta_id).await.unwrap().unwrap();
+        let block = block_manager
+            .get(&delta_id, BlockCodec::None)
+            .await
+            .unwrap()
+            .unwrap();
 
         assert_eq!(size, block.get_size());
         #[allow(clippy::needless_range_loop)]
@@ -486,13 +524,17 @@ mod test {
 
         // test fork
         let forked_block = block_manager
-            .fork::<u32, u32, UnorderedBlockDelta>(&delta_id)
+            .fork::<u32, u32, UnorderedBlockDelta>(&delta_id, BlockCodec::None)
             .await
             .unwrap();
         let new_id = forked_block.id;
         let block = block_manager.commit::<u32, u32>(forked_block).await;
         block_manager.flush(&block).await.unwrap();
-        let forked_block = block_manager.get(&new_id).await.unwrap().unwrap();
+        let forked_block = block_manager
+            .get(&new_id, BlockCodec::None)
+            .await
+            .unwrap()
+            .unwrap();
         #[allow(clippy::needless_range_loop)]
         for i in 0..n {
             let key = i as u32;
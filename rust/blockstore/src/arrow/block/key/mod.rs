@@ -2,3 +2,5 @@ pub(super) mod bool_key;
 pub(super) mod f32_key;
 pub(super) mod str_key;
 pub(super) mod u32_key;
+pub(super) mod u64_key;
+pub(super) mod uuid_key;
@@ -0,0 +1,47 @@
+use crate::arrow::{
+    block::delta::{BlockKeyArrowBuilder, BlockStorage},
+    types::{ArrowReadableKey, ArrowReadableValue, ArrowWriteableKey},
+};
+use arrow::array::{Array, FixedSizeBinaryArray, FixedSizeBinaryBuilder, StringBuilder};
+use std::sync::Arc;
+use uuid::Uuid;
+
+// UUIDs are stored as their 16 bytes, whose order is the order of the UUIDs
+const UUID_SIZE: i32 = 16;
+
+impl ArrowWriteableKey for Uuid {
+    type ReadableKey<'referred_data> = Uuid;
+
+    fn offset_size(_: usize) -> usize {
+        0
+    }
+    fn get_arrow_builder(
+        item_count: usize,
+        prefix_capacity: usize,
+        _: usize,
+    ) -> BlockKeyArrowBuilder {
+        let prefix_builder = StringBuilder::with_capacity(item_count, prefix_capacity);
+        let key_builder = FixedSizeBinaryBuilder::with_capacity(item_count, UUID_SIZE);
+        BlockKeyArrowBuilder::Uuid((prefix_builder, key_builder))
+    }
+}
+
+impl ArrowReadableKey<'_> for Uuid {
+    fn get(array: &Arc<dyn Array>, index: usize) -> Self {
+        let bytes = array
+            .as_any()
+            .downcast_ref::<FixedSizeBinaryArray>()
+            .unwrap()
+            .value(index);
+        Uuid::from_slice(bytes).expect("Invariant violation. A UUID key is 16 bytes.")
+    }
+
+    fn add_to_delta<'external, V: ArrowReadableValue<'external>>(
+        prefix: &str,
+        key: Self,
+        value: V,
+        storage: &mut BlockStorage,
+    ) {
+        V::add_to_delta(prefix, key, value, storage);
+    }
+}
@@ -3,7 +3,10 @@ use std::collections::HashMap;
 use std::io::SeekFrom;
 use std::ops::{Bound, RangeBounds};
 
+use crate::arrow::bloom_filter::BloomFilter;
+use crate::arrow::sparse_index::ZoneMap;
 use crate::arrow::types::{ArrowReadableKey, ArrowReadableValue};
+use crate::key::CompositeKey;
 use arrow::array::ArrayData;
 use arrow::buffer::Buffer;
 use arrow::ipc::reader::read_footer_length;
@@ -443,6 +446,56 @@ impl Block {
         Some((prefix, key, value))
     }
 
+    /// Returns the smallest and largest keys of the block, or None if the block is empty
+    /// ### Panics
+    /// - If the underlying key type is not the same as the type specified in the function signature
+    pub(in crate::arrow) fn zone_map<'me, K: ArrowReadableKey<'me>>(&'me self) -> Option<ZoneMap> {
+        let last_index = self.len().checked_sub(1)?;
+        let prefix_arr = self
+            .data
+            .column(0)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        let key_at_index = |index| {
+            CompositeKey::new(
+                prefix_arr.value(index).to_string(),
+                K::get(self.data.column(1), index),
+            )
+        };
+        Some(ZoneMap {
+            min_key: key_at_index(0),
+            max_key: key_at_index(last_index),
+        })
+    }
+
+    /// Returns the bloom filter of the keys of the block, or None if the block is empty
+    /// ### Panics
+    /// - If the underlying key type is not the same as the type specified in the function signature
+    pub(in crate::arrow) fn bloom_filter<'me, K: ArrowReadableKey<'me>>(
+        &'me self,
+        bits_per_key: u32,
+    ) -> Option<BloomFilter> {
+        if self.len() == 0 {
+            return None;
+        }
+        let prefix_arr = self
+            .data
+            .column(0)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        let keys = (0..self.len())
+            .map(|index| {
+                CompositeKey::new(
+                    prefix_arr.value(index).to_string(),
+                    K::get(self.data.column(1), index),
+                )
+            })
+            .collect::<Vec<_>>();
+        Some(BloomFilter::new(&keys, bits_per_key))
+    }
+
     /*
         ===== Block Metadata =====
     */
@@ -462,6 +515,41 @@ impl Block {
         self.data.num_rows()
     }
 
+    /// Returns the number of items of a block from the start of its Arrow IPC bytes. The
+    /// schema and the header of the record batch precede the data of the block, so the items
+    /// are counted without reading the data. Returns None if the bytes end before the header
+    pub(in crate::arrow) fn len_from_bytes_prefix(
+        bytes: &[u8],
+    ) -> Result<Option<usize>, BlockLoadError> {
+        // The file starts with the magic, which the writer pads to its alignment, followed by
+        // the messages of the IPC stream format. The padding is zeroed, so the first
+        // continuation marker at an aligned offset starts the schema message
+        let Some(schema_start) = (8..bytes.len())
+            .step_by(8)
+            .find(|&at| bytes.get(at..at + 4) == Some(&CONTINUATION_MARKER[..]))
+        else {
+            return Ok(None);
+        };
+        let Some((schema, schema_end)) = read_message_metadata(bytes, schema_start) else {
+            return Ok(None);
+        };
+        let schema =
+            root_as_message(schema).map_err(ArrowLayoutVerificationError::InvalidFlatbuffer)?;
+        if schema.header_type() != MessageHeader::Schema {
+            return Err(ArrowLayoutVerificationError::InvalidMessageType.into());
+        }
+        let Some((record_batch, _)) =
+            read_message_metadata(bytes, schema_end + schema.bodyLength() as usize)
+        else {
+            return Ok(None);
+        };
+        let record_batch = root_as_message(record_batch)
+            .map_err(ArrowLayoutVerificationError::InvalidFlatbuffer)?
+            .header_as_record_batch()
+            .ok_or(ArrowLayoutVerificationError::RecordBatchDecodeError)?;
+        Ok(Some(record_batch.length() as usize))
+    }
+
     /// Returns a reference to metadata of the block if any is present
     /// ### Notes
     /// - The metadata is stored in the Arrow RB schema as custom metadata
@@ -632,6 +720,10 @@ impl chroma_cache::Weighted for Block {
     fn weight(&self) -> usize {
         8 // A block is at most 8 MB
     }
+
+    fn size_bytes(&self) -> usize {
+        self.get_size()
+    }
 }
 
 fn get_size_of_array_data(array_data: &ArrayData) -> usize {
@@ -723,6 +815,10 @@ pub enum BlockLoadError {
     BlockToBytesError(#[from] crate::arrow::block::types::BlockToBytesError),
     #[error(transparent)]
     CacheError(#[from] chroma_cache::CacheError),
+    #[error("Block decode task failed: {0}")]
+    DecodeTaskError(Box<dyn ChromaError>),
+    #[error("Block decode task was dropped before it ran")]
+    DecodeTaskDropped,
 }
 
 impl ChromaError for BlockLoadError {
@@ -734,10 +830,32 @@ impl ChromaError for BlockLoadError {
             BlockLoadError::NoRecordBatches => ErrorCodes::Internal,
             BlockLoadError::BlockToBytesError(_) => ErrorCodes::Internal,
             BlockLoadError::CacheError(_) => ErrorCodes::Internal,
+            BlockLoadError::DecodeTaskError(e) => e.code(),
+            BlockLoadError::DecodeTaskDropped => ErrorCodes::Internal,
         }
     }
 }
 
+// Precedes the length of each message of the Arrow IPC format
+const CONTINUATION_MARKER: [u8; 4] = [0xff; 4];
+
+// Returns the metadata of the encapsulated IPC message at the offset, and the offset that
+// follows the metadata, or None if the bytes end before the metadata does
+// https://arrow.apache.org/docs/format/Columnar.html#encapsulated-message-format
+fn read_message_metadata(bytes: &[u8], offset: usize) -> Option<(&[u8], usize)> {
+    let read_i32 = |at: usize| {
+        let word = bytes.get(at..at + 4)?;
+        Some(i32::from_le_bytes(word.try_into().ok()?))
+    };
+    // The length follows the continuation marker, which older writers omit
+    let (len, start) = match read_i32(offset)? {
+        -1 => (read_i32(offset + 4)?, offset + 8),
+        len => (len, offset + 4),
+    };
+    let end = start + usize::try_from(len).ok()?;
+    Some((bytes.get(start..end)?, end))
+}
+
 /*
 ===== Layout Verification =====
 */
@@ -20,7 +20,7 @@ use arrow::{
     array::{ArrayRef, BinaryArray},
     util::bit_util,
 };
-use chroma_types::{chroma_proto::UpdateMetadata, DataRecord};
+use chroma_types::{chroma_proto::UpdateMetadata, DataRecord, DataRecordMetadata, Metadata};
 use prost::Message;
 use std::sync::Arc;
 
@@ -29,9 +29,16 @@ pub struct ValueBuilderWrapper {
     embedding_builder: FixedSizeListBuilder<Float32Builder>,
     metadata_builder: BinaryBuilder,
     document_builder: StringBuilder,
+    uri_builder: StringBuilder,
 }
 
-pub type DataRecordStorageEntry = (String, Vec<f32>, Option<Vec<u8>>, Option<String>);
+pub type DataRecordStorageEntry = (
+    String,
+    Vec<f32>,
+    Option<Vec<u8>>,
+    Option<String>,
+    Option<String>,
+);
 
 impl ArrowWriteableValue for &DataRecord<'_> {
     type ReadableValue<'referred_data> = DataRecord<'referred_data>;
@@ -43,14 +50,15 @@ impl ArrowWriteableValue for &DataRecord<'_> {
         let id_offset = bit_util::round_upto_multiple_of_64((item_count + 1) * 4);
         let metdata_offset = bit_util::round_upto_multiple_of_64((item_count + 1) * 4);
         let document_offset = bit_util::round_upto_multiple_of_64((item_count + 1) * 4);
+        let uri_offset = bit_util::round_upto_multiple_of_64((item_count + 1) * 4);
 
-        id_offset + metdata_offset + document_offset
+        id_offset + metdata_offset + document_offset + uri_offset
     }
 
     fn validity_size(item_count: usize) -> usize {
         let validity_bytes = bit_util::round_upto_multiple_of_64(bit_util::ceil(item_count, 8));
-        // Both document and metadata can be null
-        validity_bytes * 2
+        // Document, metadata and uri can be null
+        validity_bytes * 3
     }
 
     fn add(prefix: &str, key: KeyWrapper, value: Self, delta: &BlockStorage) {
@@ -93,6 +101,10 @@ impl ArrowWriteableValue for &DataRecord<'_> {
                 size_tracker.get_num_items(),
                 size_tracker.get_document_size(),
             ),
+            uri_builder: StringBuilder::with_capacity(
+                size_tracker.get_num_items(),
+                size_tracker.get_uri_size(),
+            ),
         }
     }
 
@@ -109,12 +121,13 @@ impl ArrowWriteableValue for &DataRecord<'_> {
             None => None,
         };
         let document = value.document.as_ref().map(|s| s.to_string());
+        let uri = value.uri.as_ref().map(|s| s.to_string());
 
-        (id, embedding, metadata, document)
+        (id, embedding, metadata, document, uri)
     }
 
     fn append(value: Self::PreparedValue, builder: &mut Self::ArrowBuilder) {
-        let (id, embedding, metadata, document) = value;
+        let (id, embedding, metadata, document, uri) = value;
 
         builder.id_builder.append_value(id);
 
@@ -126,6 +139,7 @@ impl ArrowWriteableValue for &DataRecord<'_> {
 
         builder.metadata_builder.append_option(metadata);
         builder.document_builder.append_option(document);
+        builder.uri_builder.append_option(uri);
     }
 
     fn finish(mut builder: Self::ArrowBuilder, _: &Self::SizeTracker) -> (Field, Arc<dyn Array>) {
@@ -144,11 +158,13 @@ impl ArrowWriteableValue for &DataRecord<'_> {
         );
         let metadata_field = Field::new("metadata", arrow::datatypes::DataType::Binary, true);
         let document_field = Field::new("document", arrow::datatypes::DataType::Utf8, true);
+        let uri_field = Field::new("uri", arrow::datatypes::DataType::Utf8, true);
 
         let id_arr = builder.id_builder.finish();
         let embedding_arr = builder.embedding_builder.finish();
         let metadata_arr = builder.metadata_builder.finish();
         let document_arr = builder.document_builder.finish();
+        let uri_arr = builder.uri_builder.finish();
 
         let struct_arr = StructArray::from(vec![
             (Arc::new(id_field.clone()), Arc::new(id_arr) as ArrayRef),
@@ -164,12 +180,14 @@ impl ArrowWriteableValue for &DataRecord<'_> {
                 Arc::new(document_field.clone()),
                 Arc::new(document_arr) as ArrayRef,
             ),
+            (Arc::new(uri_field.clone()), Arc::new(uri_arr) as ArrayRef),
         ]);
         let struct_fields = Fields::from(vec![
             id_field,
             embedding_field,
             metadata_field,
             document_field,
+            uri_field,
         ]);
         let struct_field = Field::new(
             "value",
@@ -193,6 +211,23 @@ impl ArrowWriteableValue for &DataRecord<'_> {
     }
 }
 
+fn read_metadata(as_struct_array: &StructArray, index: usize) -> Option<Metadata> {
+    let metadata_arr = as_struct_array
+        .column(2)
+        .as_any()
+        .downcast_ref::<BinaryArray>()
+        .unwrap();
+    let metadata_bytes = metadata_arr.value(index);
+    match metadata_bytes.len() {
+        0 => None,
+        _ => {
+            let metadata_proto = UpdateMetadata::decode(metadata_bytes).unwrap();
+            // TODO: unwrap error handling
+            Some(metadata_proto.try_into().unwrap())
+        }
+    }
+}
+
 impl<'referred_data> ArrowReadableValue<'referred_data> for DataRecord<'referred_data> {
     fn get(array: &'referred_data Arc<dyn Array>, index: usize) -> Self {
         let as_struct_array = array.as_any().downcast_ref::<StructArray>().unwrap();
@@ -222,20 +257,7 @@ impl<'referred_data> ArrowReadableValue<'referred_data> for DataRecord<'referred
             &embedding_values[(index * embedding_len)..(index * embedding_len) + embedding_len];
 
         // Read out metadata
-        let metadata_arr = as_struct_array
-            .column(2)
-            .as_any()
-            .downcast_ref::<BinaryArray>()
-            .unwrap();
-        let metadata_bytes = metadata_arr.value(index);
-        let metadata = match metadata_bytes.len() {
-            0 => None,
-            _ => {
-                let metadata_proto = UpdateMetadata::decode(metadata_bytes).unwrap();
-                // TODO: unwrap error handling
-                Some(metadata_proto.try_into().unwrap())
-            }
-        };
+        let metadata = read_metadata(as_struct_array, index);
 
         // Read out document
         let document_arr = as_struct_array
@@ -248,11 +270,24 @@ impl<'referred_data> ArrowReadableValue<'referred_data> for DataRecord<'referred
             false => Some(document_arr.value(index)),
         };
 
+        // Read out uri. Blocks written before the uri column was introduced do not have it
+        let uri = match as_struct_array.column_by_name("uri") {
+            Some(uri_arr) => {
+                let uri_arr = uri_arr.as_any().downcast_ref::<StringArray>().unwrap();
+                match uri_arr.is_null(index) {
+                    true => None,
+                    false => Some(uri_arr.value(index)),
+                }
+            }
+            None => None,
+        };
+
         DataRecord {
             id: id_arr.value(index),
             embedding,
             metadata,
             document,
+            uri,
         }
     }
 
@@ -265,3 +300,16 @@ impl<'referred_data> ArrowReadableValue<'referred_data> for DataRecord<'referred
         <&DataRecord>::add(prefix, key.into(), &value, storage);
     }
 }
+
+// Only the metadata column is decoded, so that the records can be filtered on their metadata
+// before the rest of them is read
+impl<'referred_data> ArrowReadableValue<'referred_data> for DataRecordMetadata {
+    fn get(array: &'referred_data Arc<dyn Array>, index: usize) -> Self {
+        let as_struct_array = array.as_any().downcast_ref::<StructArray>().unwrap();
+        DataRecordMetadata(read_metadata(as_struct_array, index))
+    }
+
+    fn add_to_delta<K: ArrowWriteableKey>(_: &str, _: K, _: Self, _: &mut BlockStorage) {
+        panic!("The metadata of a data record cannot be written without the record");
+    }
+}
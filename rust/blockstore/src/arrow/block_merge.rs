@@ -0,0 +1,175 @@
+use super::{
+    block::{delta::UnorderedBlockDelta, Block},
+    provider::{BlockManager, GetError},
+    root::RootWriter,
+    sparse_index::SetCountError,
+    types::{ArrowWriteableKey, ArrowWriteableValue},
+};
+use chroma_error::{ChromaError, ErrorCodes};
+use futures::{StreamExt, TryStreamExt};
+use std::collections::HashMap;
+use thiserror::Error;
+use uuid::Uuid;
+
+#[derive(Error, Debug)]
+pub enum MergeError {
+    #[error("Error fetching a small block to merge: {0}")]
+    Get(#[from] GetError),
+    #[error(transparent)]
+    SetCount(#[from] SetCountError),
+}
+
+impl ChromaError for MergeError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            MergeError::Get(e) => e.code(),
+            MergeError::SetCount(e) => e.code(),
+        }
+    }
+}
+
+// The blocks that earlier commits left in the blockfile, and that are likely under the minimum
+// size. Their size is estimated from their count in the sparse index and the average size of a
+// key in the blocks of the commit, so that the commit only fetches the blocks it may merge
+fn small_existing_block_ids(
+    root: &RootWriter,
+    blocks: &HashMap<Uuid, Block>,
+    min_block_size_bytes: usize,
+) -> Vec<Uuid> {
+    let (size, len) = blocks.values().fold((0, 0), |(size, len), block| {
+        (size + block.get_size(), len + block.len())
+    });
+    if len == 0 {
+        return Vec::new();
+    }
+    let data = root.sparse_index.data.lock();
+    data.forward
+        .iter()
+        .filter(|(_, block_id)| !blocks.contains_key(block_id))
+        .filter(|(start_key, _)| {
+            let count = data.counts.get(*start_key).copied().unwrap_or_default() as usize;
+            count * size / len < min_block_size_bytes
+        })
+        .map(|(_, block_id)| *block_id)
+        .collect()
+}
+
+// The runs of adjacent blocks in key order that are under the minimum size, and that fit in one
+// block together
+fn small_block_runs(
+    root: &RootWriter,
+    sizes: &HashMap<Uuid, usize>,
+    min_block_size_bytes: usize,
+    max_block_size_bytes: usize,
+) -> Vec<Vec<Uuid>> {
+    let data = root.sparse_index.data.lock();
+    let mut runs = Vec::new();
+    let mut run = Vec::new();
+    let mut run_size = 0;
+    for block_id in data.forward.values() {
+        match sizes.get(block_id).copied() {
+            Some(size) if size < min_block_size_bytes => {
+                if run_size + size > max_block_size_bytes {
+                    runs.push(std::mem::take(&mut run));
+                    run_size = 0;
+                }
+                run.push(*block_id);
+                run_size += size;
+            }
+            _ => {
+                runs.push(std::mem::take(&mut run));
+                run_size = 0;
+            }
+        }
+    }
+    runs.push(run);
+    runs.retain(|run| run.len() > 1);
+    runs
+}
+
+/// Merges the adjacent small blocks of a blockfile into full-size blocks on commit, and returns
+/// the blocks to flush. Repeated small writes would otherwise leave many tiny blocks behind,
+/// which a reader has to fetch one at a time. Both the blocks written by the commit and the
+/// small blocks that earlier commits left are merged, and the merged blocks replace them in the
+/// sparse index of the root before it is flushed. The blocks of earlier commits stay in storage
+/// for the versions of the blockfile that still refer to them
+pub(super) async fn merge_small_blocks<K: ArrowWriteableKey, V: ArrowWriteableValue>(
+    root: &RootWriter,
+    block_manager: &BlockManager,
+    blocks: Vec<Block>,
+) -> Result<Vec<Block>, MergeError> {
+    let Some(min_block_size_bytes) = block_manager
+        .min_block_size_bytes()
+        .filter(|size| *size > 0)
+    else {
+        return Ok(blocks);
+    };
+    let mut blocks = blocks
+        .into_iter()
+        .map(|block| (block.id, block))
+        .collect::<HashMap<_, _>>();
+    let mut existing = futures::stream::iter(small_existing_block_ids(
+        root,
+        &blocks,
+        min_block_size_bytes,
+    ))
+    .map(|block_id| {
+        let codec = root.sparse_index.codec(&block_id);
+        async move { block_manager.get(&block_id, codec).await }
+    })
+    .buffer_unordered(block_manager.block_fetch_concurrency())
+    .try_filter_map(|block| async move { Ok(block.map(|block| (block.id, block))) })
+    .try_collect::<HashMap<_, _>>()
+    .await?;
+    let sizes = blocks
+        .values()
+        .chain(existing.values())
+        .map(|block| (block.id, block.get_size()))
+        .collect::<HashMap<_, _>>();
+    let runs = small_block_runs(
+        root,
+        &sizes,
+        min_block_size_bytes,
+        block_manager.max_block_size_bytes(),
+    );
+
+    for run in runs {
+        let mut delta = block_manager.create::<K, V, UnorderedBlockDelta>();
+        for block_id in &run {
+            let block = match blocks.remove(block_id) {
+                Some(block) => {
+                    // The merged blocks of the commit are never flushed, so they should not take
+                    // up the cache
+                    block_manager.evict(block_id).await;
+                    block
+                }
+                None => existing
+                    .remove(block_id)
+                    .expect("Invariant violation: the runs only contain fetched blocks"),
+            };
+            delta = block.to_block_delta::<K::ReadableKey<'_>, V::ReadableValue<'_>>(delta);
+        }
+        let merged = block_manager.commit::<K, V>(delta).await;
+
+        // The merged block takes over the start key of the first block of the run, and its
+        // range extends over the rest of the run once they are removed
+        root.sparse_index.replace_block(run[0], merged.id);
+        for block_id in &run[1..] {
+            root.sparse_index.remove_block(block_id);
+        }
+        root.sparse_index
+            .set_count(merged.id, merged.len() as u32)?;
+        root.sparse_index
+            .set_zone_map(merged.id, merged.zone_map::<K::ReadableKey<'_>>())?;
+        root.sparse_index
+            .set_codec(merged.id, block_manager.codec(&merged))?;
+        root.sparse_index.set_bloom_filter(
+            merged.id,
+            block_manager.bloom_filter::<K::ReadableKey<'_>>(&merged),
+        )?;
+        tracing::debug!("Merged {} small blocks into block {}", run.len(), merged.id);
+        blocks.insert(merged.id, merged);
+    }
+
+    Ok(blocks.into_values().collect())
+}
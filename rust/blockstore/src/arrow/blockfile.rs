@@ -1,3 +1,4 @@
+use super::block_merge::merge_small_blocks;
 use super::migrations::{apply_migrations_to_blockfile, MigrationError};
 use super::provider::{GetError, RootManager};
 use super::root::{RootReader, RootWriter, Version};
@@ -14,14 +15,16 @@ use crate::key::KeyWrapper;
 use crate::BlockfileError;
 use chroma_error::ChromaError;
 use chroma_error::ErrorCodes;
-use futures::future::join_all;
-use futures::{Stream, StreamExt, TryStreamExt};
+use chroma_error::ErrorLocation;
+use futures::future::{BoxFuture, Shared};
+use futures::{FutureExt, Stream, StreamExt, TryStreamExt};
 use parking_lot::Mutex;
 use std::collections::HashSet;
 use std::mem::transmute;
 use std::ops::RangeBounds;
 use std::{collections::HashMap, sync::Arc};
 use thiserror::Error;
+use tracing::{Instrument, Span};
 use uuid::Uuid;
 
 #[derive(Clone)]
@@ -49,10 +52,17 @@ impl ChromaError for ArrowBlockfileError {
     fn code(&self) -> ErrorCodes {
         match self {
             ArrowBlockfileError::BlockNotFound => ErrorCodes::Internal,
-            ArrowBlockfileError::BlockFetchError(_) => ErrorCodes::Internal,
+            ArrowBlockfileError::BlockFetchError(e) => e.code(),
             ArrowBlockfileError::MigrationError(e) => e.code(),
         }
     }
+
+    fn location(&self) -> ErrorLocation {
+        match self {
+            ArrowBlockfileError::BlockFetchError(e) => e.location(),
+            _ => ErrorLocation::default(),
+        }
+    }
 }
 
 impl ArrowUnorderedBlockfileWriter {
@@ -125,15 +135,35 @@ impl ArrowUnorderedBlockfileWriter {
 
         for delta in deltas_to_commit {
             let block = self.block_manager.commit::<K, V>(delta).await;
+            self.root
+                .sparse_index
+                .set_zone_map(block.id, block.zone_map::<K::ReadableKey<'_>>())
+                .map_err(|e| Box::new(e) as Box<dyn ChromaError>)?;
+            self.root
+                .sparse_index
+                .set_codec(block.id, self.block_manager.codec(&block))
+                .map_err(|e| Box::new(e) as Box<dyn ChromaError>)?;
+            self.root
+                .sparse_index
+                .set_bloom_filter(
+                    block.id,
+                    self.block_manager
+                        .bloom_filter::<K::ReadableKey<'_>>(&block),
+                )
+                .map_err(|e| Box::new(e) as Box<dyn ChromaError>)?;
             blocks.push(block);
         }
 
-        apply_migrations_to_blockfile(&mut self.root, &self.block_manager, &new_block_ids)
+        apply_migrations_to_blockfile::<K>(&mut self.root, &self.block_manager, &new_block_ids)
             .await
             .map_err(|e| {
                 Box::new(ArrowBlockfileError::MigrationError(e)) as Box<dyn ChromaError>
             })?;
 
+        let blocks = merge_small_blocks::<K, V>(&self.root, &self.block_manager, blocks)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn ChromaError>)?;
+
         let flusher = ArrowBlockfileFlusher::new(
             self.block_manager,
             self.root_manager,
@@ -171,7 +201,8 @@ impl ArrowUnorderedBlockfileWriter {
 
         let delta = match delta {
             None => {
-                let block = match self.block_manager.get(&target_block_id).await {
+                let codec = self.root.sparse_index.codec(&target_block_id);
+                let block = match self.block_manager.get(&target_block_id, codec).await {
                     Ok(Some(block)) => block,
                     Ok(None) => {
                         return Err(Box::new(ArrowBlockfileError::BlockNotFound));
@@ -182,7 +213,7 @@ impl ArrowUnorderedBlockfileWriter {
                 };
                 let new_delta = match self
                     .block_manager
-                    .fork::<K, V, UnorderedBlockDelta>(&block.id)
+                    .fork::<K, V, UnorderedBlockDelta>(&block.id, codec)
                     .await
                 {
                     Ok(delta) => delta,
@@ -250,7 +281,8 @@ impl ArrowUnorderedBlockfileWriter {
 
         let delta = match delta {
             None => {
-                let block = match self.block_manager.get(&target_block_id).await {
+                let codec = self.root.sparse_index.codec(&target_block_id);
+                let block = match self.block_manager.get(&target_block_id, codec).await {
                     Ok(Some(block)) => block,
                     Ok(None) => {
                         return Err(Box::new(ArrowBlockfileError::BlockNotFound));
@@ -261,7 +293,7 @@ impl ArrowUnorderedBlockfileWriter {
                 };
                 let new_delta = match self
                     .block_manager
-                    .fork::<K, V, UnorderedBlockDelta>(&block.id)
+                    .fork::<K, V, UnorderedBlockDelta>(&block.id, codec)
                     .await
                 {
                     Ok(delta) => delta,
@@ -304,7 +336,8 @@ impl ArrowUnorderedBlockfileWriter {
 
         let delta = match delta {
             None => {
-                let block = match self.block_manager.get(&target_block_id).await {
+                let codec = self.root.sparse_index.codec(&target_block_id);
+                let block = match self.block_manager.get(&target_block_id, codec).await {
                     Ok(Some(block)) => block,
                     Ok(None) => {
                         return Err(Box::new(ArrowBlockfileError::BlockNotFound));
@@ -315,7 +348,7 @@ impl ArrowUnorderedBlockfileWriter {
                 };
                 let new_delta = match self
                     .block_manager
-                    .fork::<K, V, UnorderedBlockDelta>(&block.id)
+                    .fork::<K, V, UnorderedBlockDelta>(&block.id, codec)
                     .await
                 {
                     Ok(delta) => delta,
@@ -353,6 +386,9 @@ pub struct ArrowBlockfileReader<
     block_manager: BlockManager,
     pub(super) root: RootReader,
     loaded_blocks: Arc<Mutex<HashMap<Uuid, Box<Block>>>>,
+    // The fetches of the blocks that are read ahead in the background, which complete once the
+    // blocks are in the cache of the block manager
+    readahead: Arc<Mutex<HashMap<Uuid, Shared<BoxFuture<'static, ()>>>>>,
     marker: std::marker::PhantomData<(K, V, &'me ())>,
 }
 
@@ -364,6 +400,7 @@ impl<'me, K: ArrowReadableKey<'me> + Into<KeyWrapper>, V: ArrowReadableValue<'me
             block_manager,
             root,
             loaded_blocks: Arc::new(Mutex::new(HashMap::new())),
+            readahead: Arc::default(),
             marker: std::marker::PhantomData,
         }
     }
@@ -373,7 +410,13 @@ impl<'me, K: ArrowReadableKey<'me> + Into<KeyWrapper>, V: ArrowReadableValue<'me
         // the loaded_blocks map across a call to the block manager.
         #[allow(clippy::map_entry)]
         if !self.loaded_blocks.lock().contains_key(&block_id) {
-            let block = match self.block_manager.get(&block_id).await {
+            // A block that is read ahead is fetched once, and then found in the cache
+            let readahead = self.readahead.lock().get(&block_id).cloned();
+            if let Some(readahead) = readahead {
+                readahead.await;
+            }
+            let codec = self.root.sparse_index.codec(&block_id);
+            let block = match self.block_manager.get(&block_id, codec).await {
                 Ok(Some(block)) => block,
                 Ok(None) => {
                     return Ok(None);
@@ -382,7 +425,12 @@ impl<'me, K: ArrowReadableKey<'me> + Into<KeyWrapper>, V: ArrowReadableValue<'me
                     return Err(e);
                 }
             };
-            self.loaded_blocks.lock().insert(block_id, Box::new(block));
+            // A concurrent fetch of the same block may have loaded it already, and the block
+            // that it loaded may be referenced, so it is not replaced
+            self.loaded_blocks
+                .lock()
+                .entry(block_id)
+                .or_insert_with(|| Box::new(block));
         }
 
         if let Some(block) = self.loaded_blocks.lock().get(&block_id) {
@@ -422,7 +470,102 @@ impl<'me, K: ArrowReadableKey<'me> + Into<KeyWrapper>, V: ArrowReadableValue<'me
                 futures.push(self.get_block(*block_id));
             }
         }
-        join_all(futures).await;
+        futures::stream::iter(futures)
+            .buffer_unordered(self.block_manager.block_fetch_concurrency())
+            .collect::<Vec<_>>()
+            .await;
+    }
+
+    /// Fetches the blocks, up to the fetch concurrency of the block manager at once, and
+    /// yields them in the order of the ids. The blocks that are cached are not fetched again,
+    /// and the blocks after each yielded block are read ahead while it is processed
+    fn fetch_blocks(
+        &'me self,
+        block_ids: Vec<Uuid>,
+    ) -> impl Stream<Item = Result<&'me Block, Box<ArrowBlockfileError>>> + 'me {
+        let concurrency = self.block_manager.block_fetch_concurrency();
+        let block_ids: Arc<[Uuid]> = block_ids.into();
+        futures::stream::iter(0..block_ids.len())
+            .map(move |index| {
+                let block_ids = block_ids.clone();
+                async move {
+                    self.readahead_blocks(block_ids.iter().skip(index + 1).take(concurrency));
+                    match self.get_block(block_ids[index]).await {
+                        Ok(Some(block)) => Ok(block),
+                        Ok(None) => Err(Box::new(ArrowBlockfileError::BlockNotFound)),
+                        Err(e) => Err(Box::new(ArrowBlockfileError::BlockFetchError(e))),
+                    }
+                }
+            })
+            .buffered(concurrency)
+    }
+
+    // Fetches the blocks into the cache of the block manager in the background. The blocks
+    // that the reader loaded or reads ahead already are skipped, and nothing is read ahead
+    // outside of a tokio runtime
+    fn readahead_blocks<'block>(&self, block_ids: impl IntoIterator<Item = &'block Uuid>) {
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let block_ids = {
+            let loaded_blocks = self.loaded_blocks.lock();
+            block_ids
+                .into_iter()
+                .filter(|block_id| !loaded_blocks.contains_key(block_id))
+                .copied()
+                .collect::<Vec<_>>()
+        };
+        let mut readahead = self.readahead.lock();
+        for block_id in block_ids {
+            if readahead.contains_key(&block_id) {
+                continue;
+            }
+            let block_manager = self.block_manager.clone();
+            let codec = self.root.sparse_index.codec(&block_id);
+            let fetch = runtime.spawn(
+                async move {
+                    if let Err(e) = block_manager.get(&block_id, codec).await {
+                        tracing::warn!("Error reading ahead block {}: {:?}", block_id, e);
+                    }
+                }
+                .instrument(Span::current()),
+            );
+            readahead.insert(
+                block_id,
+                async move {
+                    let _ = fetch.await;
+                }
+                .boxed()
+                .shared(),
+            );
+        }
+    }
+
+    /// Fetches up to `blocks` of the blocks after the block of the key in the background, so
+    /// that a sequential scan from the key finds them cached by the time it reaches them
+    /// instead of waiting on the storage for each of them
+    pub(crate) fn readahead(&self, prefix: &str, key: K, blocks: usize) {
+        let search_key = CompositeKey::new(prefix.to_string(), key);
+        self.readahead_blocks(
+            &self
+                .root
+                .sparse_index
+                .get_next_block_ids(&search_key, blocks),
+        );
+    }
+
+    /// Loads all of the blocks of the blockfile into the block manager
+    /// # Returns
+    /// - `usize`: The number of blocks of the blockfile
+    pub(crate) async fn load_all_blocks(&self) -> usize {
+        let block_ids = self
+            .root
+            .sparse_index
+            .get_all_block_ids()
+            .into_iter()
+            .collect::<Vec<_>>();
+        self.load_blocks(&block_ids).await;
+        block_ids.len()
     }
 
     pub(crate) async fn load_blocks_for_keys(&self, prefixes: &[&str], keys: &[K]) {
@@ -435,6 +578,8 @@ impl<'me, K: ArrowReadableKey<'me> + Into<KeyWrapper>, V: ArrowReadableValue<'me
                 composite_keys.push(composite_key);
             }
         }
+        // The blocks whose bloom filters rule out all of their keys are not loaded
+        composite_keys.retain(|key| self.root.sparse_index.may_contain(key));
         let target_block_ids = self
             .root
             .sparse_index
@@ -448,6 +593,10 @@ impl<'me, K: ArrowReadableKey<'me> + Into<KeyWrapper>, V: ArrowReadableValue<'me
         key: K,
     ) -> Result<Option<V>, Box<dyn ChromaError>> {
         let search_key = CompositeKey::new(prefix.to_string(), key.clone());
+        // The block is not fetched if its bloom filter rules the key out
+        if !self.root.sparse_index.may_contain(&search_key) {
+            return Ok(None);
+        }
         let target_block_id = self.root.sparse_index.get_target_block_id(&search_key);
         let block = self.get_block(target_block_id).await;
         match block {
@@ -460,6 +609,48 @@ impl<'me, K: ArrowReadableKey<'me> + Into<KeyWrapper>, V: ArrowReadableValue<'me
         }
     }
 
+    /// Returns the values of the keys under the prefix, in the same order as the keys. The
+    /// blocks of the keys are fetched concurrently, and each block is fetched once
+    pub(crate) async fn get_many(
+        &'me self,
+        prefix: &str,
+        keys: Vec<K>,
+    ) -> Result<Vec<Option<V>>, Box<dyn ChromaError>> {
+        let target_block_ids = keys
+            .iter()
+            .map(|key| {
+                let search_key = CompositeKey::new(prefix.to_string(), key.clone());
+                // The block is not fetched if its bloom filter rules the key out
+                self.root
+                    .sparse_index
+                    .may_contain(&search_key)
+                    .then(|| self.root.sparse_index.get_target_block_id(&search_key))
+            })
+            .collect::<Vec<_>>();
+        let mut seen = HashSet::new();
+        let block_ids = target_block_ids
+            .iter()
+            .flatten()
+            .copied()
+            .filter(|block_id| seen.insert(*block_id))
+            .collect::<Vec<_>>();
+        let blocks = self
+            .fetch_blocks(block_ids.clone())
+            .try_collect::<Vec<_>>()
+            .await
+            .map_err(|e| e as Box<dyn ChromaError>)?;
+        let blocks = block_ids.into_iter().zip(blocks).collect::<HashMap<_, _>>();
+        Ok(keys
+            .into_iter()
+            .zip(target_block_ids)
+            .map(|(key, block_id)| {
+                block_id
+                    .and_then(|block_id| blocks.get(&block_id).copied())
+                    .and_then(|block| block.get(prefix, key))
+            })
+            .collect())
+    }
+
     pub(crate) async fn get_at_index(
         &'me self,
         index: usize,
@@ -529,20 +720,11 @@ impl<'me, K: ArrowReadableKey<'me> + Into<KeyWrapper>, V: ArrowReadableValue<'me
         K: Sync,
         V: Sync,
     {
-        futures::stream::iter(
+        self.fetch_blocks(
             self.root
                 .sparse_index
-                .get_block_ids_range(prefix_range.clone(), key_range.clone())
-                .into_iter()
-                .map(Ok),
+                .get_block_ids_range(prefix_range.clone(), key_range.clone()),
         )
-        .try_filter_map(move |block_id| async move {
-            match self.get_block(block_id).await {
-                Ok(Some(block)) => Ok(Some(block)),
-                Ok(None) => Err(Box::new(ArrowBlockfileError::BlockNotFound)),
-                Err(e) => Err(Box::new(ArrowBlockfileError::BlockFetchError(e))),
-            }
-        })
         .map(move |block| match block {
             Ok(block) => futures::stream::iter(
                 block
@@ -555,6 +737,35 @@ impl<'me, K: ArrowReadableKey<'me> + Into<KeyWrapper>, V: ArrowReadableValue<'me
         .flatten()
     }
 
+    // Returns all Arrow records of the blockfile together with their prefixes, in key order
+    pub(crate) fn get_all_stream(
+        &'me self,
+    ) -> impl Stream<Item = Result<(&'me str, K, V), Box<dyn ChromaError>>> + Send + 'me
+    where
+        K: Sync,
+        V: Sync,
+    {
+        let block_ids = self
+            .root
+            .sparse_index
+            .data
+            .forward
+            .values()
+            .map(|sparse_index_value| sparse_index_value.id)
+            .collect::<Vec<_>>();
+        self.fetch_blocks(block_ids)
+            .map(move |block| match block {
+                Ok(block) => futures::stream::iter(
+                    (0..block.len())
+                        .filter_map(move |index| block.get_at_index::<K, V>(index))
+                        .map(Ok),
+                )
+                .boxed(),
+                Err(e) => futures::stream::once(async { Err(e as Box<dyn ChromaError>) }).boxed(),
+            })
+            .flatten()
+    }
+
     pub async fn get_range<'prefix, PrefixRange, KeyRange>(
         &'me self,
         prefix_range: PrefixRange,
@@ -570,23 +781,9 @@ impl<'me, K: ArrowReadableKey<'me> + Into<KeyWrapper>, V: ArrowReadableValue<'me
             .get_block_ids_range(prefix_range.clone(), key_range.clone());
 
         let mut result: Vec<(K, V)> = vec![];
-        for block_id in block_ids {
-            let block_opt = match self.get_block(block_id).await {
-                Ok(Some(block)) => Some(block),
-                Ok(None) => {
-                    return Err(Box::new(ArrowBlockfileError::BlockNotFound));
-                }
-                Err(e) => {
-                    return Err(Box::new(e));
-                }
-            };
-
-            let block = match block_opt {
-                Some(b) => b,
-                None => {
-                    return Err(Box::new(ArrowBlockfileError::BlockNotFound));
-                }
-            };
+        let mut blocks = self.fetch_blocks(block_ids);
+        while let Some(block) = blocks.next().await {
+            let block = block.map_err(|e| e as Box<dyn ChromaError>)?;
             result.extend(block.get_range(prefix_range.clone(), key_range.clone()));
         }
 
@@ -599,6 +796,9 @@ impl<'me, K: ArrowReadableKey<'me> + Into<KeyWrapper>, V: ArrowReadableValue<'me
         key: K,
     ) -> Result<bool, Box<dyn ChromaError>> {
         let search_key = CompositeKey::new(prefix.to_string(), key.clone());
+        if !self.root.sparse_index.may_contain(&search_key) {
+            return Ok(false);
+        }
         let target_block_id = self.root.sparse_index.get_target_block_id(&search_key);
         let block = match self.get_block(target_block_id).await {
             Ok(Some(block)) => block,
@@ -690,17 +890,23 @@ mod tests {
     use crate::arrow::block::delta::UnorderedBlockDelta;
     use crate::arrow::block::Block;
     use crate::arrow::blockfile::ArrowUnorderedBlockfileWriter;
+    use crate::arrow::compression::{BlockCodec, BlockCompressionConfig, BlockValueType};
+    use crate::arrow::config::DiskTierConfig;
+    use crate::arrow::decode_executor::DecodeExecutor;
+    use crate::arrow::disk_tier::DiskTier;
     use crate::arrow::provider::{BlockManager, RootManager};
     use crate::arrow::root::{RootWriter, Version};
     use crate::arrow::sparse_index::SparseIndexWriter;
     use crate::key::CompositeKey;
+    use crate::read_stats::count_reads;
     use crate::{
         arrow::config::TEST_MAX_BLOCK_SIZE_BYTES, arrow::provider::ArrowBlockfileProvider,
     };
     use crate::{BlockfileReader, BlockfileWriter, BlockfileWriterOptions};
     use chroma_cache::new_cache_for_test;
+    use chroma_error::ChromaError;
     use chroma_storage::{local::LocalStorage, Storage};
-    use chroma_types::{DataRecord, MetadataValue};
+    use chroma_types::{DataRecord, DataRecordMetadata, MetadataValue};
     use futures::{StreamExt, TryStreamExt};
     use parking_lot::Mutex;
     use proptest::prelude::*;
@@ -708,6 +914,7 @@ mod tests {
     use rand::seq::IteratorRandom;
     use std::collections::HashMap;
     use std::ops::Bound;
+    use std::sync::atomic::{AtomicUsize, Ordering};
     use std::sync::Arc;
     use tokio::runtime::Runtime;
     use uuid::Uuid;
@@ -1255,6 +1462,229 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_u64_key() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let storage = Storage::Local(LocalStorage::new(tmp_dir.path().to_str().unwrap()));
+        let provider = ArrowBlockfileProvider::new(
+            storage,
+            TEST_MAX_BLOCK_SIZE_BYTES,
+            new_cache_for_test(),
+            new_cache_for_test(),
+        );
+
+        let writer = provider
+            .write::<u64, String>(BlockfileWriterOptions::default())
+            .await
+            .unwrap();
+        let id = writer.id();
+
+        // The versions of each record are keyed under the prefix of the record, with versions
+        // beyond the range of u32
+        let records = 200;
+        let versions = 10u64;
+        for record in 0..records {
+            let prefix = format!("record-{:04}", record);
+            for version in (0..versions).rev() {
+                let value = format!("{}-{}", record, version);
+                writer
+                    .set(prefix.as_str(), version << 40, value)
+                    .await
+                    .unwrap();
+            }
+        }
+
+        let flusher = writer.commit::<u64, String>().await.unwrap();
+        flusher.flush::<u64, String>().await.unwrap();
+
+        let reader = provider.read::<u64, &str>(&id).await.unwrap();
+        for record in 0..records {
+            let prefix = format!("record-{:04}", record);
+            for version in 0..versions {
+                let value = reader.get(&prefix, version << 40).await.unwrap().unwrap();
+                assert_eq!(value, format!("{}-{}", record, version));
+            }
+        }
+
+        let prefix = "record-0100";
+        let range = reader
+            .get_range(prefix..=prefix, (3u64 << 40)..)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(version, _)| version >> 40)
+            .collect::<Vec<_>>();
+        assert_eq!(range, (3..versions).collect::<Vec<_>>());
+        assert_eq!(reader.count().await.unwrap(), records * versions as usize);
+    }
+
+    #[tokio::test]
+    async fn test_uuid_key() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let storage = Storage::Local(LocalStorage::new(tmp_dir.path().to_str().unwrap()));
+        let provider = ArrowBlockfileProvider::new(
+            storage,
+            TEST_MAX_BLOCK_SIZE_BYTES,
+            new_cache_for_test(),
+            new_cache_for_test(),
+        );
+
+        let writer = provider
+            .write::<Uuid, u32>(BlockfileWriterOptions::default())
+            .await
+            .unwrap();
+        let id = writer.id();
+
+        let n = 2000;
+        let mut keys = (0..n).map(|_| Uuid::new_v4()).collect::<Vec<_>>();
+        for (i, key) in keys.iter().enumerate() {
+            writer.set("key", *key, i as u32).await.unwrap();
+        }
+
+        let flusher = writer.commit::<Uuid, u32>().await.unwrap();
+        flusher.flush::<Uuid, u32>().await.unwrap();
+
+        let reader = provider.read::<Uuid, u32>(&id).await.unwrap();
+        for (i, key) in keys.iter().enumerate() {
+            let value = reader.get("key", *key).await.unwrap().unwrap();
+            assert_eq!(value, i as u32);
+        }
+
+        // The keys are ordered by their bytes
+        keys.sort();
+        let range = reader
+            .get_range_stream("key"..="key", ..)
+            .map_ok(|(key, _)| key)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(range, keys);
+    }
+
+    #[tokio::test]
+    async fn test_load_hottest_blocks() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let storage = Storage::Local(LocalStorage::new(tmp_dir.path().to_str().unwrap()));
+        let provider = ArrowBlockfileProvider::new(
+            storage.clone(),
+            TEST_MAX_BLOCK_SIZE_BYTES,
+            new_cache_for_test(),
+            new_cache_for_test(),
+        );
+        let writer = provider
+            .write::<u32, String>(BlockfileWriterOptions::default())
+            .await
+            .unwrap();
+        let id = writer.id();
+        for key in 0..1000u32 {
+            writer
+                .set("", key, format!("value-{:0100}", key))
+                .await
+                .unwrap();
+        }
+        let flusher = writer.commit::<u32, String>().await.unwrap();
+        flusher.flush::<u32, String>().await.unwrap();
+
+        // The block of the first key is read by more readers than the block of the last key
+        for _ in 0..3 {
+            let reader = provider.read::<u32, &str>(&id).await.unwrap();
+            reader.get("", 0).await.unwrap().unwrap();
+        }
+        let reader = provider.read::<u32, &str>(&id).await.unwrap();
+        reader.get("", 999).await.unwrap().unwrap();
+        let hottest = provider.hottest_block_ids(&[id], 2).await.unwrap();
+        assert_eq!(hottest.len(), 2);
+        let hottest_block = match &reader {
+            BlockfileReader::ArrowBlockfileReader(reader) => {
+                assert!(reader.root.sparse_index.len() > 2);
+                reader
+                    .root
+                    .sparse_index
+                    .get_target_block_id(&CompositeKey::new("".to_string(), 0u32))
+            }
+            _ => panic!("Unexpected reader type"),
+        };
+        assert_eq!(hottest[0], hottest_block);
+
+        // Another provider loads the hottest blocks once it has the root
+        let provider = ArrowBlockfileProvider::new(
+            storage,
+            TEST_MAX_BLOCK_SIZE_BYTES,
+            new_cache_for_test(),
+            new_cache_for_test(),
+        );
+        let block_ids = hottest.iter().copied().collect();
+        assert_eq!(provider.load_blocks(&[id], &block_ids).await, 0);
+        provider.read::<u32, &str>(&id).await.unwrap();
+        assert_eq!(provider.load_blocks(&[id], &block_ids).await, 2);
+        let unreferenced = [Uuid::new_v4()].into_iter().collect();
+        assert_eq!(provider.load_blocks(&[id], &unreferenced).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_evict_superseded() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let storage = Storage::Local(LocalStorage::new(tmp_dir.path().to_str().unwrap()));
+        let provider = ArrowBlockfileProvider::new(
+            storage.clone(),
+            TEST_MAX_BLOCK_SIZE_BYTES,
+            new_cache_for_test(),
+            new_cache_for_test(),
+        );
+        let writer = provider
+            .write::<u32, String>(BlockfileWriterOptions::default())
+            .await
+            .unwrap();
+        let stale_id = writer.id();
+        for key in 0..1000u32 {
+            writer
+                .set("", key, format!("value-{:0100}", key))
+                .await
+                .unwrap();
+        }
+        let flusher = writer.commit::<u32, String>().await.unwrap();
+        flusher.flush::<u32, String>().await.unwrap();
+
+        // Only the block of the first key is replaced by the fork
+        let writer = provider
+            .write::<u32, String>(BlockfileWriterOptions::new().fork(stale_id))
+            .await
+            .unwrap();
+        let current_id = writer.id();
+        writer.set("", 0u32, "updated".to_string()).await.unwrap();
+        let flusher = writer.commit::<u32, String>().await.unwrap();
+        flusher.flush::<u32, String>().await.unwrap();
+
+        // A fresh provider caches the roots and blocks that are read
+        let provider = ArrowBlockfileProvider::new(
+            storage,
+            TEST_MAX_BLOCK_SIZE_BYTES,
+            new_cache_for_test(),
+            new_cache_for_test(),
+        );
+        let stale_reader = provider.read::<u32, &str>(&stale_id).await.unwrap();
+        stale_reader.get("", 0).await.unwrap().unwrap();
+        stale_reader.get("", 999).await.unwrap().unwrap();
+        let unused_stale_reader = provider.read::<u32, &str>(&stale_id).await.unwrap();
+        let current_reader = provider.read::<u32, &str>(&current_id).await.unwrap();
+        assert_eq!(current_reader.get("", 0).await.unwrap(), Some("updated"));
+        current_reader.get("", 999).await.unwrap().unwrap();
+
+        provider.evict_superseded(&stale_id, &current_id).await;
+
+        // Without the stored blocks and stale root, only what is still cached can be read
+        std::fs::remove_dir_all(tmp_dir.path().join("block")).unwrap();
+        std::fs::remove_file(tmp_dir.path().join(format!("sparse_index/{}", stale_id))).unwrap();
+        let current_reader = provider.read::<u32, &str>(&current_id).await.unwrap();
+        assert_eq!(current_reader.get("", 0).await.unwrap(), Some("updated"));
+        assert_eq!(
+            current_reader.get("", 999).await.unwrap(),
+            Some(format!("value-{:0100}", 999).as_str())
+        );
+        assert!(unused_stale_reader.get("", 0).await.is_err());
+        assert!(provider.read::<u32, &str>(&stale_id).await.is_err());
+    }
+
     #[tokio::test]
     async fn test_roaring_bitmap_value() {
         let tmp_dir = tempfile::tempdir().unwrap();
@@ -1306,74 +1736,413 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_uint_key_val() {
+    async fn test_compressed_roaring_bitmap_value() {
         let tmp_dir = tempfile::tempdir().unwrap();
         let storage = Storage::Local(LocalStorage::new(tmp_dir.path().to_str().unwrap()));
-        let block_cache = new_cache_for_test();
-        let sparse_index_cache = new_cache_for_test();
+        let compression = BlockCompressionConfig {
+            default_codec: BlockCodec::None,
+            codecs: HashMap::from([(BlockValueType::RoaringBitmap, BlockCodec::Zstd)]),
+            zstd_level: 0,
+        };
         let blockfile_provider = ArrowBlockfileProvider::new(
-            storage,
+            storage.clone(),
             TEST_MAX_BLOCK_SIZE_BYTES,
-            block_cache,
-            sparse_index_cache,
-        );
+            new_cache_for_test(),
+            new_cache_for_test(),
+        )
+        .with_compression(compression.clone());
 
         let writer = blockfile_provider
-            .write::<u32, u32>(BlockfileWriterOptions::default())
+            .write::<&str, roaring::RoaringBitmap>(BlockfileWriterOptions::default())
             .await
             .unwrap();
         let id = writer.id();
 
         let n = 2000;
         for i in 0..n {
-            let key = i as u32;
-            let value = i as u32;
-            writer.set("key", key, value).await.unwrap();
+            let key = format!("{:04}", i);
+            let value = roaring::RoaringBitmap::from_iter(0..i);
+            writer.set("key", key.as_str(), value).await.unwrap();
         }
+        let flusher = writer
+            .commit::<&str, roaring::RoaringBitmap>()
+            .await
+            .unwrap();
+        flusher
+            .flush::<&str, roaring::RoaringBitmap>()
+            .await
+            .unwrap();
 
-        let flusher = writer.commit::<u32, u32>().await.unwrap();
-        flusher.flush::<u32, u32>().await.unwrap();
-
-        let reader = blockfile_provider.read::<u32, u32>(&id).await.unwrap();
+        // A provider with empty caches loads the blocks from storage
+        let blockfile_provider = ArrowBlockfileProvider::new(
+            storage,
+            TEST_MAX_BLOCK_SIZE_BYTES,
+            new_cache_for_test(),
+            new_cache_for_test(),
+        )
+        .with_compression(compression);
+        let reader = blockfile_provider
+            .read::<&str, roaring::RoaringBitmap>(&id)
+            .await
+            .unwrap();
+        let sparse_index = match &reader {
+            BlockfileReader::ArrowBlockfileReader(reader) => &reader.root.sparse_index,
+            _ => panic!("Unexpected reader type"),
+        };
+        assert!(sparse_index.len() > 1);
+        for block_id in sparse_index.get_all_block_ids() {
+            assert_eq!(sparse_index.codec(&block_id), BlockCodec::Zstd);
+        }
         for i in 0..n {
-            let key = i as u32;
-            let value = reader.get("key", key).await.unwrap().unwrap();
-            assert_eq!(value, i as u32);
+            let key = format!("{:04}", i);
+            let value = reader.get("key", &key).await.unwrap().unwrap();
+            assert_eq!(
+                value.iter().collect::<Vec<u32>>(),
+                (0..i).collect::<Vec<u32>>()
+            );
         }
     }
 
     #[tokio::test]
-    async fn test_data_record_val() {
+    async fn test_bloom_filter_skips_blocks() {
         let tmp_dir = tempfile::tempdir().unwrap();
         let storage = Storage::Local(LocalStorage::new(tmp_dir.path().to_str().unwrap()));
-        let block_cache = new_cache_for_test();
-        let sparse_index_cache = new_cache_for_test();
         let blockfile_provider = ArrowBlockfileProvider::new(
-            storage,
+            storage.clone(),
             TEST_MAX_BLOCK_SIZE_BYTES,
-            block_cache,
-            sparse_index_cache,
-        );
+            new_cache_for_test(),
+            new_cache_for_test(),
+        )
+        .with_bloom_filter_bits_per_key(Some(10));
 
         let writer = blockfile_provider
-            .write::<&str, &DataRecord>(BlockfileWriterOptions::default())
+            .write::<&str, String>(BlockfileWriterOptions::default())
             .await
             .unwrap();
         let id = writer.id();
 
+        // Only the even keys are written
         let n = 2000;
-        for i in 0..n {
+        for i in (0..n).step_by(2) {
             let key = format!("{:04}", i);
-            let mut metdata = HashMap::new();
-            metdata.insert("key".to_string(), MetadataValue::Str("value".to_string()));
-            let value = DataRecord {
-                id: &key,
-                embedding: &[i as f32],
-                document: None,
-                metadata: Some(metdata),
-            };
-            writer.set("key", key.as_str(), &value).await.unwrap();
-        }
+            writer
+                .set("key", key.as_str(), format!("value{}", i))
+                .await
+                .unwrap();
+        }
+        let flusher = writer.commit::<&str, String>().await.unwrap();
+        flusher.flush::<&str, String>().await.unwrap();
+
+        // A provider with empty caches loads the blocks from storage
+        let blockfile_provider = ArrowBlockfileProvider::new(
+            storage,
+            TEST_MAX_BLOCK_SIZE_BYTES,
+            new_cache_for_test(),
+            new_cache_for_test(),
+        );
+        let reader = match blockfile_provider.read::<&str, &str>(&id).await.unwrap() {
+            BlockfileReader::ArrowBlockfileReader(reader) => reader,
+            _ => panic!("Unexpected reader type"),
+        };
+        assert!(reader.root.sparse_index.len() > 1);
+        assert!(reader
+            .root
+            .sparse_index
+            .data
+            .forward
+            .values()
+            .all(|value| value.bloom_filter.is_some()));
+
+        let mut skipped = 0;
+        for i in (1..n).step_by(2) {
+            let key = format!("{:04}", i);
+            let search_key = CompositeKey::new("key".to_string(), key.as_str());
+            let target_block_id = reader.root.sparse_index.get_target_block_id(&search_key);
+            let was_loaded = reader.loaded_blocks.lock().contains_key(&target_block_id);
+            assert_eq!(reader.get("key", key.as_str()).await.unwrap(), None);
+            // A lookup that the bloom filter rules out does not load the block
+            if !reader.root.sparse_index.may_contain(&search_key) {
+                skipped += 1;
+                assert_eq!(
+                    reader.loaded_blocks.lock().contains_key(&target_block_id),
+                    was_loaded
+                );
+            }
+        }
+        // Most of the lookups of the missing keys are skipped
+        assert!(skipped > n / 4);
+
+        for i in (0..n).step_by(2) {
+            let key = format!("{:04}", i);
+            let value = reader.get("key", key.as_str()).await.unwrap();
+            assert_eq!(value, Some(format!("value{}", i).as_str()));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_many() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let storage = Storage::Local(LocalStorage::new(tmp_dir.path().to_str().unwrap()));
+        let blockfile_provider = ArrowBlockfileProvider::new(
+            storage.clone(),
+            TEST_MAX_BLOCK_SIZE_BYTES,
+            new_cache_for_test(),
+            new_cache_for_test(),
+        );
+
+        let writer = blockfile_provider
+            .write::<u32, String>(BlockfileWriterOptions::default())
+            .await
+            .unwrap();
+        let id = writer.id();
+        let n = 2000;
+        for i in (0..n).step_by(2) {
+            writer
+                .set("", i, format!("value-{:0100}", i))
+                .await
+                .unwrap();
+        }
+        let flusher = writer.commit::<u32, String>().await.unwrap();
+        flusher.flush::<u32, String>().await.unwrap();
+
+        // A provider with empty caches loads the blocks from storage
+        let blockfile_provider = ArrowBlockfileProvider::new(
+            storage,
+            TEST_MAX_BLOCK_SIZE_BYTES,
+            new_cache_for_test(),
+            new_cache_for_test(),
+        )
+        .with_block_fetch_concurrency(2);
+        let reader = match blockfile_provider.read::<u32, &str>(&id).await.unwrap() {
+            BlockfileReader::ArrowBlockfileReader(reader) => reader,
+            _ => panic!("Unexpected reader type"),
+        };
+        assert!(reader.root.sparse_index.len() > 2);
+
+        // The values are in the order of the keys, which span all the blocks
+        let keys = (0..n).rev().collect::<Vec<_>>();
+        let values = reader.get_many("", keys.clone()).await.unwrap();
+        assert_eq!(values.len(), keys.len());
+        for (key, value) in keys.into_iter().zip(values) {
+            let expected = (key % 2 == 0).then(|| format!("value-{:0100}", key));
+            assert_eq!(value, expected.as_deref());
+        }
+        assert_eq!(
+            reader.loaded_blocks.lock().len(),
+            reader.root.sparse_index.len()
+        );
+
+        // The range reads yield the values in key order
+        let values = reader.get_range(""..="", 100..1500).await.unwrap();
+        assert_eq!(
+            values.iter().map(|(key, _)| *key).collect::<Vec<_>>(),
+            (100..1500).step_by(2).collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_readahead() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let storage = Storage::Local(LocalStorage::new(tmp_dir.path().to_str().unwrap()));
+        let blockfile_provider = ArrowBlockfileProvider::new(
+            storage.clone(),
+            TEST_MAX_BLOCK_SIZE_BYTES,
+            new_cache_for_test(),
+            new_cache_for_test(),
+        );
+        let writer = blockfile_provider
+            .write::<u32, String>(BlockfileWriterOptions::default())
+            .await
+            .unwrap();
+        let id = writer.id();
+        for i in 0u32..2000 {
+            writer.set("", i, format!("value{}", i)).await.unwrap();
+        }
+        let flusher = writer.commit::<u32, String>().await.unwrap();
+        flusher.flush::<u32, String>().await.unwrap();
+
+        // A provider with empty caches loads the blocks from storage
+        let blockfile_provider = ArrowBlockfileProvider::new(
+            storage,
+            TEST_MAX_BLOCK_SIZE_BYTES,
+            new_cache_for_test(),
+            new_cache_for_test(),
+        );
+        let reader = match blockfile_provider.read::<u32, &str>(&id).await.unwrap() {
+            BlockfileReader::ArrowBlockfileReader(reader) => reader,
+            _ => panic!("Unexpected reader type"),
+        };
+        let block_ids = reader
+            .root
+            .sparse_index
+            .data
+            .forward
+            .values()
+            .map(|sparse_index_value| sparse_index_value.id)
+            .collect::<Vec<_>>();
+        assert!(block_ids.len() > 3);
+
+        // The blocks after the block of the key are read ahead, and the block of the key is not
+        reader.readahead("", 0, 2);
+        let readahead = reader
+            .readahead
+            .lock()
+            .iter()
+            .map(|(block_id, fetch)| (*block_id, fetch.clone()))
+            .collect::<HashMap<_, _>>();
+        assert_eq!(readahead.len(), 2);
+        assert!(readahead.contains_key(&block_ids[1]));
+        assert!(readahead.contains_key(&block_ids[2]));
+        futures::future::join_all(readahead.into_values()).await;
+
+        // The blocks that are read ahead are served from the cache
+        for block_id in &block_ids[1..3] {
+            let (block, read_stats) = count_reads(reader.get_block(*block_id)).await;
+            assert!(block.unwrap().is_some());
+            assert_eq!(read_stats.cache_hits, 1);
+            assert_eq!(read_stats.cache_misses, 0);
+        }
+        let (_, read_stats) = count_reads(reader.get_block(block_ids[3])).await;
+        assert_eq!(read_stats.cache_misses, 1);
+    }
+
+    #[derive(Debug, Default)]
+    struct CountingDecodeExecutor {
+        runs: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl DecodeExecutor for CountingDecodeExecutor {
+        async fn run(&self, decode: Box<dyn FnOnce() + Send>) -> Result<(), Box<dyn ChromaError>> {
+            self.runs.fetch_add(1, Ordering::SeqCst);
+            decode();
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_decode_executor() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let storage = Storage::Local(LocalStorage::new(tmp_dir.path().to_str().unwrap()));
+        let blockfile_provider = ArrowBlockfileProvider::new(
+            storage.clone(),
+            TEST_MAX_BLOCK_SIZE_BYTES,
+            new_cache_for_test(),
+            new_cache_for_test(),
+        );
+        let writer = blockfile_provider
+            .write::<u32, String>(BlockfileWriterOptions::default())
+            .await
+            .unwrap();
+        let id = writer.id();
+        for i in 0u32..2000 {
+            writer.set("", i, format!("value{}", i)).await.unwrap();
+        }
+        let flusher = writer.commit::<u32, String>().await.unwrap();
+        flusher.flush::<u32, String>().await.unwrap();
+
+        // The blocks under the threshold are decoded inline
+        let executor = Arc::new(CountingDecodeExecutor::default());
+        let blockfile_provider = ArrowBlockfileProvider::new(
+            storage.clone(),
+            TEST_MAX_BLOCK_SIZE_BYTES,
+            new_cache_for_test(),
+            new_cache_for_test(),
+        )
+        .with_decode_offload_threshold_bytes(usize::MAX);
+        blockfile_provider.set_decode_executor(executor.clone());
+        let reader = blockfile_provider.read::<u32, &str>(&id).await.unwrap();
+        assert_eq!(reader.get("", 1500).await.unwrap(), Some("value1500"));
+        assert_eq!(executor.runs.load(Ordering::SeqCst), 0);
+
+        // The blocks over the threshold are decoded by the executor, which the clones of the
+        // provider share
+        let executor = Arc::new(CountingDecodeExecutor::default());
+        let blockfile_provider = ArrowBlockfileProvider::new(
+            storage,
+            TEST_MAX_BLOCK_SIZE_BYTES,
+            new_cache_for_test(),
+            new_cache_for_test(),
+        )
+        .with_decode_offload_threshold_bytes(0);
+        blockfile_provider
+            .clone()
+            .set_decode_executor(executor.clone());
+        let reader = blockfile_provider.read::<u32, &str>(&id).await.unwrap();
+        assert_eq!(reader.get("", 1500).await.unwrap(), Some("value1500"));
+        assert!(executor.runs.load(Ordering::SeqCst) > 0);
+    }
+
+    #[tokio::test]
+    async fn test_uint_key_val() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let storage = Storage::Local(LocalStorage::new(tmp_dir.path().to_str().unwrap()));
+        let block_cache = new_cache_for_test();
+        let sparse_index_cache = new_cache_for_test();
+        let blockfile_provider = ArrowBlockfileProvider::new(
+            storage,
+            TEST_MAX_BLOCK_SIZE_BYTES,
+            block_cache,
+            sparse_index_cache,
+        );
+
+        let writer = blockfile_provider
+            .write::<u32, u32>(BlockfileWriterOptions::default())
+            .await
+            .unwrap();
+        let id = writer.id();
+
+        let n = 2000;
+        for i in 0..n {
+            let key = i as u32;
+            let value = i as u32;
+            writer.set("key", key, value).await.unwrap();
+        }
+
+        let flusher = writer.commit::<u32, u32>().await.unwrap();
+        flusher.flush::<u32, u32>().await.unwrap();
+
+        let reader = blockfile_provider.read::<u32, u32>(&id).await.unwrap();
+        for i in 0..n {
+            let key = i as u32;
+            let value = reader.get("key", key).await.unwrap().unwrap();
+            assert_eq!(value, i as u32);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_data_record_val() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let storage = Storage::Local(LocalStorage::new(tmp_dir.path().to_str().unwrap()));
+        let block_cache = new_cache_for_test();
+        let sparse_index_cache = new_cache_for_test();
+        let blockfile_provider = ArrowBlockfileProvider::new(
+            storage,
+            TEST_MAX_BLOCK_SIZE_BYTES,
+            block_cache,
+            sparse_index_cache,
+        );
+
+        let writer = blockfile_provider
+            .write::<&str, &DataRecord>(BlockfileWriterOptions::default())
+            .await
+            .unwrap();
+        let id = writer.id();
+
+        let n = 2000;
+        for i in 0..n {
+            let key = format!("{:04}", i);
+            let mut metdata = HashMap::new();
+            metdata.insert("key".to_string(), MetadataValue::Str("value".to_string()));
+            let value = DataRecord {
+                id: &key,
+                embedding: &[i as f32],
+                document: None,
+                uri: None,
+                metadata: Some(metdata),
+            };
+            writer.set("key", key.as_str(), &value).await.unwrap();
+        }
 
         let flusher = writer.commit::<&str, &DataRecord>().await.unwrap();
         flusher.flush::<&str, &DataRecord>().await.unwrap();
@@ -1394,6 +2163,25 @@ mod tests {
                 &MetadataValue::Str("value".to_string())
             );
         }
+
+        // The metadata of the records can be read on its own
+        let reader = blockfile_provider
+            .read::<&str, DataRecordMetadata>(&id)
+            .await
+            .unwrap();
+        let metadata = reader
+            .get_range_stream("key"..="key", ..)
+            .map_ok(|(_, DataRecordMetadata(metadata))| metadata.unwrap())
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(metadata.len(), n);
+        for metadata in metadata {
+            assert_eq!(
+                metadata.get("key").unwrap(),
+                &MetadataValue::Str("value".to_string())
+            );
+        }
     }
 
     #[tokio::test]
@@ -1508,6 +2296,193 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_merge_small_blocks() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let storage = Storage::Local(LocalStorage::new(tmp_dir.path().to_str().unwrap()));
+        let blockfile_provider = ArrowBlockfileProvider::new(
+            storage.clone(),
+            TEST_MAX_BLOCK_SIZE_BYTES,
+            new_cache_for_test(),
+            new_cache_for_test(),
+        );
+        let writer = blockfile_provider
+            .write::<u32, String>(BlockfileWriterOptions::default())
+            .await
+            .unwrap();
+        let id = writer.id();
+        let n = 2000;
+        for i in 0..n {
+            writer.set("", i, format!("value{}", i)).await.unwrap();
+        }
+        let flusher = writer.commit::<u32, String>().await.unwrap();
+        flusher.flush::<u32, String>().await.unwrap();
+        let num_blocks = match blockfile_provider.read::<u32, &str>(&id).await.unwrap() {
+            BlockfileReader::ArrowBlockfileReader(reader) => reader.root.sparse_index.len(),
+            _ => panic!("Unexpected reader type"),
+        };
+        assert!(num_blocks > 2);
+
+        // Deleting most of the keys leaves every block small, so they are merged on commit
+        let blockfile_provider = ArrowBlockfileProvider::new(
+            storage,
+            TEST_MAX_BLOCK_SIZE_BYTES,
+            new_cache_for_test(),
+            new_cache_for_test(),
+        )
+        .with_min_block_size_bytes(Some(TEST_MAX_BLOCK_SIZE_BYTES / 2));
+        let writer = blockfile_provider
+            .write::<u32, String>(BlockfileWriterOptions::new().fork(id))
+            .await
+            .unwrap();
+        let id = writer.id();
+        for i in (0..n).filter(|i| i % 10 != 0) {
+            writer.delete::<u32, String>("", i).await.unwrap();
+        }
+        let flusher = writer.commit::<u32, String>().await.unwrap();
+        assert!(flusher.num_blocks() < num_blocks);
+        flusher.flush::<u32, String>().await.unwrap();
+
+        let reader = match blockfile_provider.read::<u32, &str>(&id).await.unwrap() {
+            BlockfileReader::ArrowBlockfileReader(reader) => reader,
+            _ => panic!("Unexpected reader type"),
+        };
+        assert!(reader.root.sparse_index.len() < num_blocks);
+        assert!(reader.is_valid().await);
+        assert_eq!(reader.count().await.unwrap(), n as usize / 10);
+        for i in 0..n {
+            let value = reader.get("", i).await.unwrap();
+            if i % 10 == 0 {
+                assert_eq!(value, Some(format!("value{}", i).as_str()));
+            } else {
+                assert_eq!(value, None);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_merge_small_blocks_of_earlier_commits() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let storage = Storage::Local(LocalStorage::new(tmp_dir.path().to_str().unwrap()));
+        let blockfile_provider = ArrowBlockfileProvider::new(
+            storage.clone(),
+            TEST_MAX_BLOCK_SIZE_BYTES,
+            new_cache_for_test(),
+            new_cache_for_test(),
+        );
+        let writer = blockfile_provider
+            .write::<u32, String>(BlockfileWriterOptions::default())
+            .await
+            .unwrap();
+        let id = writer.id();
+        let n = 2000;
+        for i in 0..n {
+            writer.set("", i, format!("value{}", i)).await.unwrap();
+        }
+        let flusher = writer.commit::<u32, String>().await.unwrap();
+        flusher.flush::<u32, String>().await.unwrap();
+
+        // The blocks are left small, since the provider does not merge them
+        let writer = blockfile_provider
+            .write::<u32, String>(BlockfileWriterOptions::new().fork(id))
+            .await
+            .unwrap();
+        let id = writer.id();
+        for i in (0..n).filter(|i| i % 10 != 0) {
+            writer.delete::<u32, String>("", i).await.unwrap();
+        }
+        let flusher = writer.commit::<u32, String>().await.unwrap();
+        flusher.flush::<u32, String>().await.unwrap();
+        let num_blocks = match blockfile_provider.read::<u32, &str>(&id).await.unwrap() {
+            BlockfileReader::ArrowBlockfileReader(reader) => reader.root.sparse_index.len(),
+            _ => panic!("Unexpected reader type"),
+        };
+        assert!(num_blocks > 2);
+
+        // A commit that only writes to the first block merges the small blocks after it
+        let blockfile_provider = ArrowBlockfileProvider::new(
+            storage,
+            TEST_MAX_BLOCK_SIZE_BYTES,
+            new_cache_for_test(),
+            new_cache_for_test(),
+        )
+        .with_min_block_size_bytes(Some(TEST_MAX_BLOCK_SIZE_BYTES / 2));
+        let writer = blockfile_provider
+            .write::<u32, String>(BlockfileWriterOptions::new().fork(id))
+            .await
+            .unwrap();
+        let id = writer.id();
+        writer.set("", 1u32, "value1".to_string()).await.unwrap();
+        let flusher = writer.commit::<u32, String>().await.unwrap();
+        flusher.flush::<u32, String>().await.unwrap();
+
+        let reader = match blockfile_provider.read::<u32, &str>(&id).await.unwrap() {
+            BlockfileReader::ArrowBlockfileReader(reader) => reader,
+            _ => panic!("Unexpected reader type"),
+        };
+        assert!(reader.root.sparse_index.len() < num_blocks);
+        assert!(reader.is_valid().await);
+        assert_eq!(reader.count().await.unwrap(), n as usize / 10 + 1);
+        for i in 0..n {
+            let value = reader.get("", i).await.unwrap();
+            if i % 10 == 0 || i == 1 {
+                assert_eq!(value, Some(format!("value{}", i).as_str()));
+            } else {
+                assert_eq!(value, None);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_disk_tier_fallback() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let storage = Storage::Local(LocalStorage::new(tmp_dir.path().to_str().unwrap()));
+        let disk_tier_dir = tempfile::tempdir().unwrap();
+        let disk_tier_config = DiskTierConfig {
+            dir: disk_tier_dir.path().to_str().unwrap().to_string(),
+            capacity_bytes: 1024 * 1024,
+        };
+        let new_provider = |disk_tier| {
+            ArrowBlockfileProvider::new(
+                storage.clone(),
+                TEST_MAX_BLOCK_SIZE_BYTES,
+                new_cache_for_test(),
+                new_cache_for_test(),
+            )
+            .with_disk_tier(Some(disk_tier))
+        };
+        let provider = new_provider(DiskTier::open(&disk_tier_config).await.unwrap());
+        let writer = provider
+            .write::<u32, String>(BlockfileWriterOptions::default())
+            .await
+            .unwrap();
+        let id = writer.id();
+        writer.set("", 1u32, "value".to_string()).await.unwrap();
+        let flusher = writer.commit::<u32, String>().await.unwrap();
+        flusher.flush::<u32, String>().await.unwrap();
+
+        // The block is kept in the disk tier once it is fetched from storage
+        let provider = new_provider(DiskTier::open(&disk_tier_config).await.unwrap());
+        let reader = provider.read::<u32, &str>(&id).await.unwrap();
+        assert_eq!(reader.get("", 1).await.unwrap(), Some("value"));
+        let block_files = std::fs::read_dir(disk_tier_dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect::<Vec<_>>();
+        assert!(!block_files.is_empty());
+
+        // A truncated block in the disk tier is replaced by the block in storage
+        for path in &block_files {
+            std::fs::write(path, [0; 8]).unwrap();
+        }
+        let provider = new_provider(DiskTier::open(&disk_tier_config).await.unwrap());
+        let reader = provider.read::<u32, &str>(&id).await.unwrap();
+        assert_eq!(reader.get("", 1).await.unwrap(), Some("value"));
+        for path in &block_files {
+            assert!(std::fs::metadata(path).unwrap().len() > 8);
+        }
+    }
+
     #[tokio::test]
     async fn test_get_at_index() {
         let tmp_dir = tempfile::tempdir().unwrap();
@@ -1679,6 +2654,39 @@ mod tests {
         assert_eq!(value, n - 1);
     }
 
+    #[tokio::test]
+    async fn test_count_block_from_header() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let storage = Storage::Local(LocalStorage::new(tmp_dir.path().to_str().unwrap()));
+        let block_manager = BlockManager::new(
+            storage.clone(),
+            TEST_MAX_BLOCK_SIZE_BYTES,
+            new_cache_for_test(),
+        );
+        let delta = block_manager.create::<&str, String, UnorderedBlockDelta>();
+        for i in 0..100 {
+            delta.add("prefix", format!("{:04}", i).as_str(), "value".to_string());
+        }
+        let block = Block::from_record_batch(delta.id, delta.finish::<&str, String>(None));
+        block_manager.flush(&block).await.unwrap();
+
+        // The block is not cached, so it is counted from the start of its object
+        let block_manager =
+            BlockManager::new(storage, TEST_MAX_BLOCK_SIZE_BYTES, new_cache_for_test());
+        assert_eq!(
+            block_manager
+                .count(&block.id, BlockCodec::None)
+                .await
+                .unwrap(),
+            Some(100)
+        );
+
+        // The header is not complete in a prefix that is too short
+        let bytes = block.to_bytes().unwrap();
+        assert_eq!(Block::len_from_bytes_prefix(&bytes).unwrap(), Some(100));
+        assert_eq!(Block::len_from_bytes_prefix(&bytes[..16]).unwrap(), None);
+    }
+
     #[tokio::test]
     async fn test_v1_to_v1_1_migration_all_new() {
         let tmp_dir = tempfile::tempdir().unwrap();
@@ -1864,9 +2872,18 @@ mod tests {
             _ => panic!("Unexpected reader type"),
         };
 
-        assert_eq!(reader.root.version, Version::V1_1);
+        assert_eq!(reader.root.version, Version::V1_4);
         assert_eq!(reader.root.sparse_index.len(), 2);
 
+        // The zone maps are set for both the migrated and the new blocks
+        assert!(reader
+            .root
+            .sparse_index
+            .data
+            .forward
+            .values()
+            .all(|value| value.zone_map.is_some()));
+
         // Manually verify sparse index counts
         let count_in_index: u32 = reader
             .root
@@ -1879,4 +2896,78 @@ mod tests {
         assert_eq!(count_in_index, 3);
         assert_eq!(reader.count().await.unwrap(), 3);
     }
+
+    #[tokio::test]
+    async fn test_v1_to_v1_1_migration_uncached_blocks() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let storage = Storage::Local(LocalStorage::new(tmp_dir.path().to_str().unwrap()));
+        let root_manager = RootManager::new(storage.clone(), new_cache_for_test());
+        let block_manager = BlockManager::new(
+            storage.clone(),
+            TEST_MAX_BLOCK_SIZE_BYTES,
+            new_cache_for_test(),
+        );
+
+        // A v1 blockfile with two stored blocks and no counts in the root
+        let block_delta_1 = block_manager.create::<&str, String, UnorderedBlockDelta>();
+        block_delta_1.add("prefix", "a", "value_a".to_string());
+        let block_delta_2 = block_manager.create::<&str, String, UnorderedBlockDelta>();
+        block_delta_2.add("prefix", "f", "value_f".to_string());
+        block_delta_2.add("prefix", "g", "value_g".to_string());
+        let sparse_index = SparseIndexWriter::new(block_delta_1.id);
+        sparse_index
+            .add_block(
+                CompositeKey::new("prefix".to_string(), "f"),
+                block_delta_2.id,
+            )
+            .unwrap();
+        let first_write_id = Uuid::new_v4();
+        let root_writer = RootWriter::new(Version::V1, first_write_id, sparse_index);
+        let block_1 =
+            Block::from_record_batch(block_delta_1.id, block_delta_1.finish::<&str, String>(None));
+        let block_2 =
+            Block::from_record_batch(block_delta_2.id, block_delta_2.finish::<&str, String>(None));
+        block_manager.flush(&block_1).await.unwrap();
+        block_manager.flush(&block_2).await.unwrap();
+        root_manager.flush::<&str>(&root_writer).await.unwrap();
+
+        // No block is cached by the provider, so the untouched block is counted from the
+        // header of its stored object
+        let blockfile_provider = ArrowBlockfileProvider::new(
+            storage,
+            TEST_MAX_BLOCK_SIZE_BYTES,
+            new_cache_for_test(),
+            new_cache_for_test(),
+        );
+        let writer = blockfile_provider
+            .write::<&str, String>(BlockfileWriterOptions::new().fork(first_write_id))
+            .await
+            .unwrap();
+        let second_write_id = writer.id();
+        writer
+            .set("prefix", "b", "value_b".to_string())
+            .await
+            .unwrap();
+        let flusher = writer.commit::<&str, String>().await.unwrap();
+        flusher.flush::<&str, String>().await.unwrap();
+
+        let reader = match blockfile_provider
+            .read::<&str, &str>(&second_write_id)
+            .await
+            .unwrap()
+        {
+            BlockfileReader::ArrowBlockfileReader(reader) => reader,
+            _ => panic!("Unexpected reader type"),
+        };
+        let count_in_index: u32 = reader
+            .root
+            .sparse_index
+            .data
+            .forward
+            .values()
+            .map(|value| value.count)
+            .sum();
+        assert_eq!(count_in_index, 4);
+        assert_eq!(reader.count().await.unwrap(), 4);
+    }
 }
@@ -0,0 +1,164 @@
+use crate::key::{CompositeKey, KeyWrapper};
+use serde::{Deserialize, Serialize};
+
+// The number of hashes is capped, since the false positive rate barely improves past it
+const MAX_NUM_HASHES: u32 = 16;
+
+/// A bloom filter of the keys of a block. The sparse index only tells which block a key would
+/// be in, so the filter lets a point lookup skip fetching a block that does not contain the
+/// key. The filters are persisted in the root of the blockfile, so the hash of the keys must
+/// not change
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(super) struct BloomFilter {
+    num_hashes: u32,
+    bits: Vec<u64>,
+}
+
+impl BloomFilter {
+    /// Builds the filter of the given keys with about `bits_per_key` bits for each key, where
+    /// 10 bits per key have a false positive rate of about 1%
+    pub(super) fn new(keys: &[CompositeKey], bits_per_key: u32) -> Self {
+        let bits_per_key = bits_per_key.max(1);
+        // The optimal number of hashes is ln(2) times the number of bits per key
+        let num_hashes = ((bits_per_key as f64 * std::f64::consts::LN_2).round() as u32)
+            .clamp(1, MAX_NUM_HASHES);
+        let num_words = (keys.len() * bits_per_key as usize).div_ceil(64).max(1);
+        let mut filter = BloomFilter {
+            num_hashes,
+            bits: vec![0; num_words],
+        };
+        for key in keys {
+            for bit in filter.bit_indexes(key) {
+                filter.bits[bit / 64] |= 1u64 << (bit % 64);
+            }
+        }
+        filter
+    }
+
+    /// The bytes of the bits of the filter
+    pub(super) fn size_bytes(&self) -> usize {
+        self.bits.len() * std::mem::size_of::<u64>()
+    }
+
+    /// Returns whether the block may contain the key. A key that the block contains is never
+    /// ruled out
+    pub(super) fn may_contain(&self, key: &CompositeKey) -> bool {
+        self.bit_indexes(key)
+            .all(|bit| self.bits[bit / 64] & (1u64 << (bit % 64)) != 0)
+    }
+
+    pub(super) fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(4 + self.bits.len() * 8);
+        bytes.extend_from_slice(&self.num_hashes.to_le_bytes());
+        for word in &self.bits {
+            bytes.extend_from_slice(&word.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Parses the filter from the bytes written by `to_bytes`, or returns None if they are not
+    /// a valid filter
+    pub(super) fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let num_hashes = u32::from_le_bytes(bytes.get(..4)?.try_into().ok()?);
+        let bits = &bytes[4..];
+        if num_hashes == 0 || bits.is_empty() || !bits.len().is_multiple_of(8) {
+            return None;
+        }
+        let bits = bits
+            .chunks_exact(8)
+            .map(|word| u64::from_le_bytes(word.try_into().expect("Chunks are of 8 bytes")))
+            .collect();
+        Some(BloomFilter { num_hashes, bits })
+    }
+
+    // The bits of the key, by double hashing as in "Less Hashing, Same Performance: Building a
+    // Better Bloom Filter" by Kirsch and Mitzenmacher
+    fn bit_indexes(&self, key: &CompositeKey) -> impl Iterator<Item = usize> {
+        let num_bits = self.bits.len() as u64 * 64;
+        let first = hash_key(key);
+        // The second hash is odd so that it is coprime with the number of bits
+        let second = mix(first) | 1;
+        (0..self.num_hashes as u64)
+            .map(move |i| (first.wrapping_add(i.wrapping_mul(second)) % num_bits) as usize)
+    }
+}
+
+// The 64-bit FNV-1a hash of the prefix and the key, which is stable across processes and
+// releases unlike the hashers of the standard library
+fn hash_key(key: &CompositeKey) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+    let fnv = |hash: u64, bytes: &[u8]| {
+        bytes
+            .iter()
+            .fold(hash, |hash, byte| (hash ^ *byte as u64).wrapping_mul(PRIME))
+    };
+    let hash = fnv(OFFSET_BASIS, key.prefix.as_bytes());
+    // The prefix and the key are separated by a byte that is not valid in UTF-8, and the key
+    // is tagged with its type
+    let hash = fnv(hash, &[0xff]);
+    match &key.key {
+        KeyWrapper::String(s) => fnv(fnv(hash, &[0]), s.as_bytes()),
+        // -0.0 and 0.0 are equal keys, so they must hash the same
+        KeyWrapper::Float32(f) => {
+            let f = if *f == 0.0 { 0.0f32 } else { *f };
+            fnv(fnv(hash, &[1]), &f.to_le_bytes())
+        }
+        KeyWrapper::Bool(b) => fnv(fnv(hash, &[2]), &[*b as u8]),
+        KeyWrapper::Uint32(u) => fnv(fnv(hash, &[3]), &u.to_le_bytes()),
+        KeyWrapper::Uint64(u) => fnv(fnv(hash, &[4]), &u.to_le_bytes()),
+        KeyWrapper::Uuid(u) => fnv(fnv(hash, &[5]), u.as_bytes()),
+    }
+}
+
+// The finalizer of SplitMix64, which spreads the bits of the FNV hash for the second hash
+fn mix(mut hash: u64) -> u64 {
+    hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d049bb133111eb);
+    hash ^ (hash >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BloomFilter;
+    use crate::key::CompositeKey;
+
+    #[test]
+    fn test_bloom_filter() {
+        let keys = (0..1000)
+            .map(|i| CompositeKey::new("prefix".to_string(), format!("key{}", i).as_str()))
+            .collect::<Vec<_>>();
+        let filter = BloomFilter::new(&keys, 10);
+
+        // There are no false negatives
+        assert!(keys.iter().all(|key| filter.may_contain(key)));
+
+        // The false positive rate is about 1%
+        let false_positives = (1000..11000)
+            .filter(|i| {
+                filter.may_contain(&CompositeKey::new(
+                    "prefix".to_string(),
+                    format!("key{}", i).as_str(),
+                ))
+            })
+            .count();
+        assert!(false_positives < 300, "{} false positives", false_positives);
+
+        // The keys of other types are distinguished from the keys of the filter
+        let false_positives = (0..1000u32)
+            .filter(|i| filter.may_contain(&CompositeKey::new("prefix".to_string(), *i)))
+            .count();
+        assert!(false_positives < 30, "{} false positives", false_positives);
+
+        let bytes = filter.to_bytes();
+        assert_eq!(BloomFilter::from_bytes(&bytes), Some(filter));
+        assert_eq!(BloomFilter::from_bytes(&bytes[..bytes.len() - 1]), None);
+        assert_eq!(BloomFilter::from_bytes(&[]), None);
+
+        // Equal floats are not ruled out, whatever the sign of zero
+        let zero_filter = BloomFilter::new(&[CompositeKey::new("prefix".to_string(), -0.0f32)], 10);
+        assert!(zero_filter.may_contain(&CompositeKey::new("prefix".to_string(), 0.0f32)));
+        let zero_filter = BloomFilter::new(&[CompositeKey::new("prefix".to_string(), 0.0f32)], 10);
+        assert!(zero_filter.may_contain(&CompositeKey::new("prefix".to_string(), -0.0f32)));
+    }
+}
@@ -0,0 +1,149 @@
+use super::block::Block;
+use arrow::datatypes::DataType;
+use serde::{Deserialize, Serialize};
+use std::{borrow::Cow, collections::HashMap};
+
+/// The codec that a block is compressed with in storage. The codec of each block is recorded
+/// in the sparse index of the blockfile, and the blocks of the roots written before codecs
+/// were recorded are uncompressed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockCodec {
+    #[default]
+    None,
+    Zstd,
+}
+
+impl BlockCodec {
+    pub(super) fn as_u8(self) -> u8 {
+        match self {
+            BlockCodec::None => 0,
+            BlockCodec::Zstd => 1,
+        }
+    }
+
+    pub(super) fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(BlockCodec::None),
+            1 => Some(BlockCodec::Zstd),
+            _ => None,
+        }
+    }
+
+    /// Compresses the Arrow IPC bytes of a block. The level only applies to zstd, where 0
+    /// is the default level of zstd
+    pub(super) fn compress(self, bytes: Vec<u8>, level: i32) -> std::io::Result<Vec<u8>> {
+        match self {
+            BlockCodec::None => Ok(bytes),
+            BlockCodec::Zstd => zstd::encode_all(bytes.as_slice(), level),
+        }
+    }
+
+    /// Decompresses the stored bytes of a block into its Arrow IPC bytes
+    pub(super) fn decompress(self, bytes: &[u8]) -> std::io::Result<Cow<'_, [u8]>> {
+        match self {
+            BlockCodec::None => Ok(Cow::Borrowed(bytes)),
+            BlockCodec::Zstd => zstd::decode_all(bytes).map(Cow::Owned),
+        }
+    }
+}
+
+/// The type of the values of a block. The values of some types compress far better than
+/// others, e.g. the offset ids of posting lists compared to the floats of embeddings
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockValueType {
+    Str,
+    U32,
+    RoaringBitmap,
+    U32Array,
+    DataRecord,
+    SpannPostingList,
+}
+
+impl BlockValueType {
+    /// The type of the values of the block, from the type of its value column
+    pub(super) fn of(block: &Block) -> Option<Self> {
+        let schema = block.data.schema_ref();
+        let value_field = schema.field_with_name("value").ok()?;
+        match value_field.data_type() {
+            DataType::Utf8 => Some(BlockValueType::Str),
+            DataType::UInt32 => Some(BlockValueType::U32),
+            DataType::Binary => Some(BlockValueType::RoaringBitmap),
+            DataType::List(_) => Some(BlockValueType::U32Array),
+            DataType::Struct(fields) if fields.find("embedding").is_some() => {
+                Some(BlockValueType::DataRecord)
+            }
+            DataType::Struct(_) => Some(BlockValueType::SpannPostingList),
+            _ => None,
+        }
+    }
+}
+
+/// The compression of the blocks flushed to storage. The blocks are uncompressed by default
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct BlockCompressionConfig {
+    /// The codec of the blocks whose value type has no codec of its own
+    #[serde(default)]
+    pub default_codec: BlockCodec,
+    /// The codecs by the value type of the blocks, e.g. `roaring_bitmap: zstd`
+    #[serde(default)]
+    pub codecs: HashMap<BlockValueType, BlockCodec>,
+    /// The zstd compression level, where 0 is the default level of zstd
+    #[serde(default)]
+    pub zstd_level: i32,
+}
+
+impl BlockCompressionConfig {
+    /// The codec that the block is compressed with when it is flushed
+    pub(super) fn codec(&self, block: &Block) -> BlockCodec {
+        BlockValueType::of(block)
+            .and_then(|value_type| self.codecs.get(&value_type).copied())
+            .unwrap_or(self.default_codec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BlockCodec, BlockCompressionConfig, BlockValueType};
+    use crate::arrow::block::{delta::types::Delta, delta::UnorderedBlockDelta, Block};
+    use roaring::RoaringBitmap;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_block_codec() {
+        let delta = UnorderedBlockDelta::new::<&str, RoaringBitmap>(uuid::Uuid::new_v4());
+        for i in 0..100 {
+            delta.add(
+                "prefix",
+                format!("key{}", i).as_str(),
+                RoaringBitmap::from_iter(0..i),
+            );
+        }
+        let block = Block::from_record_batch(delta.id, delta.finish::<&str, RoaringBitmap>(None));
+        assert_eq!(
+            BlockValueType::of(&block),
+            Some(BlockValueType::RoaringBitmap)
+        );
+
+        let config = BlockCompressionConfig {
+            default_codec: BlockCodec::None,
+            codecs: HashMap::from([(BlockValueType::RoaringBitmap, BlockCodec::Zstd)]),
+            zstd_level: 0,
+        };
+        assert_eq!(config.codec(&block), BlockCodec::Zstd);
+        assert_eq!(
+            BlockCompressionConfig::default().codec(&block),
+            BlockCodec::None
+        );
+
+        let bytes = block.to_bytes().unwrap();
+        for codec in [BlockCodec::None, BlockCodec::Zstd] {
+            assert_eq!(BlockCodec::from_u8(codec.as_u8()), Some(codec));
+            let compressed = codec.compress(bytes.clone(), 0).unwrap();
+            let decompressed = codec.decompress(&compressed).unwrap();
+            let loaded = Block::from_bytes(&decompressed, block.id).unwrap();
+            assert_eq!(loaded.len(), block.len());
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use super::compression::BlockCompressionConfig;
 use chroma_cache::CacheConfig;
 use serde::Deserialize;
 
@@ -11,10 +12,53 @@ pub struct ArrowBlockfileProviderConfig {
     pub root_manager_config: RootManagerConfig,
 }
 
+// Blocks of at least this size are decoded by the decode executor of the provider instead of inline
+pub const DEFAULT_DECODE_OFFLOAD_THRESHOLD_BYTES: usize = 1024 * 1024;
+
+const fn default_decode_offload_threshold_bytes() -> usize {
+    DEFAULT_DECODE_OFFLOAD_THRESHOLD_BYTES
+}
+
+// The number of blocks that a reader fetches at once for a multi-get or a range read
+pub const DEFAULT_BLOCK_FETCH_CONCURRENCY: usize = 8;
+
+const fn default_block_fetch_concurrency() -> usize {
+    DEFAULT_BLOCK_FETCH_CONCURRENCY
+}
+
+// The blocks under the maximum block size divided by this are merged, unless the config sets the
+// minimum block size
+pub const DEFAULT_MIN_BLOCK_SIZE_DIVISOR: usize = 4;
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct BlockManagerConfig {
     pub max_block_size_bytes: usize,
     pub block_cache_config: CacheConfig,
+    #[serde(default = "default_decode_offload_threshold_bytes")]
+    pub decode_offload_threshold_bytes: usize,
+    #[serde(default)]
+    pub compression: BlockCompressionConfig,
+    // The blocks get a bloom filter of their keys with this many bits per key, if it is set
+    #[serde(default)]
+    pub bloom_filter_bits_per_key: Option<u32>,
+    #[serde(default = "default_block_fetch_concurrency")]
+    pub block_fetch_concurrency: usize,
+    // The adjacent blocks under this size are merged on commit, and zero turns the merging off.
+    // It is a quarter of the maximum block size if it is not set
+    #[serde(default)]
+    pub min_block_size_bytes: Option<usize>,
+    // The blocks that are fetched from storage are also kept on local disk, if it is set
+    #[serde(default)]
+    pub disk_tier: Option<DiskTierConfig>,
+}
+
+/// The local directory below the block cache in memory
+/// - `dir`: The directory of the blocks, which is kept across restarts
+/// - `capacity_bytes`: The bytes of the blocks that the directory may hold
+#[derive(Deserialize, Debug, Clone)]
+pub struct DiskTierConfig {
+    pub dir: String,
+    pub capacity_bytes: usize,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -0,0 +1,12 @@
+use async_trait::async_trait;
+use chroma_error::ChromaError;
+use std::fmt::Debug;
+
+/// Runs the decoding of the blocks that are at least the offload threshold in size away from
+/// the task that loads them, so that decoding a large block does not stall the other tasks of
+/// its runtime. The blocks are decoded inline if the provider has no executor
+#[async_trait]
+pub trait DecodeExecutor: Debug + Send + Sync {
+    /// Runs the decode to completion
+    async fn run(&self, decode: Box<dyn FnOnce() + Send>) -> Result<(), Box<dyn ChromaError>>;
+}
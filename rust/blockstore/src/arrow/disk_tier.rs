@@ -0,0 +1,263 @@
+use super::config::DiskTierConfig;
+use chroma_error::{ChromaError, ErrorCodes};
+use parking_lot::Mutex;
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+    sync::Arc,
+    time::SystemTime,
+};
+use thiserror::Error;
+use tokio::io::AsyncWriteExt;
+use uuid::Uuid;
+
+// The blocks are written to a temporary file first, synced, and renamed into place, so that a
+// crash never leaves a partial block behind under the id of the block
+const TEMPORARY_EXTENSION: &str = "tmp";
+
+#[derive(Error, Debug)]
+pub enum DiskTierError {
+    #[error("Error opening the disk tier at {0}: {1}")]
+    Open(String, #[source] std::io::Error),
+}
+
+impl ChromaError for DiskTierError {
+    fn code(&self) -> ErrorCodes {
+        ErrorCodes::Internal
+    }
+}
+
+struct DiskTierEntry {
+    size_bytes: usize,
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct DiskTierIndex {
+    entries: HashMap<Uuid, DiskTierEntry>,
+    // The blocks by when they were last used, the least recently used first
+    recency: BTreeMap<u64, Uuid>,
+    clock: u64,
+    usage_bytes: usize,
+}
+
+impl DiskTierIndex {
+    fn touch(&mut self, id: &Uuid) -> bool {
+        self.clock += 1;
+        let Some(entry) = self.entries.get_mut(id) else {
+            return false;
+        };
+        self.recency.remove(&entry.last_used);
+        entry.last_used = self.clock;
+        self.recency.insert(self.clock, *id);
+        true
+    }
+
+    fn insert(&mut self, id: Uuid, size_bytes: usize) {
+        self.remove(&id);
+        self.clock += 1;
+        self.recency.insert(self.clock, id);
+        self.entries.insert(
+            id,
+            DiskTierEntry {
+                size_bytes,
+                last_used: self.clock,
+            },
+        );
+        self.usage_bytes += size_bytes;
+    }
+
+    fn remove(&mut self, id: &Uuid) -> bool {
+        let Some(entry) = self.entries.remove(id) else {
+            return false;
+        };
+        self.recency.remove(&entry.last_used);
+        self.usage_bytes -= entry.size_bytes;
+        true
+    }
+
+    // Removes the least recently used blocks until the index is within the capacity, and
+    // returns them
+    fn evict(&mut self, capacity_bytes: usize) -> Vec<Uuid> {
+        let mut evicted = Vec::new();
+        while self.usage_bytes > capacity_bytes {
+            let Some((_, id)) = self.recency.pop_first() else {
+                break;
+            };
+            if let Some(entry) = self.entries.remove(&id) {
+                self.usage_bytes -= entry.size_bytes;
+            }
+            evicted.push(id);
+        }
+        evicted
+    }
+}
+
+/// A bounded local directory of the blocks that were fetched from storage, below the block
+/// cache in memory. The blocks that are evicted from memory are read from the directory
+/// rather than from storage, and so are the blocks of warm collections after a restart, since
+/// the directory is indexed again when it is opened. The blocks are kept as they are stored,
+/// compressed and encrypted if the storage encrypts its objects, and the least recently used
+/// blocks are removed beyond the capacity
+#[derive(Clone)]
+pub struct DiskTier {
+    dir: PathBuf,
+    capacity_bytes: usize,
+    index: Arc<Mutex<DiskTierIndex>>,
+}
+
+impl DiskTier {
+    /// Opens the directory, creating it if needed, and indexes the blocks that it holds from
+    /// before, the most recently written as the most recently used
+    pub async fn open(config: &DiskTierConfig) -> Result<Self, DiskTierError> {
+        let open_error = |e| DiskTierError::Open(config.dir.clone(), e);
+        let dir = PathBuf::from(&config.dir);
+        tokio::fs::create_dir_all(&dir).await.map_err(open_error)?;
+
+        let mut blocks = Vec::new();
+        let mut entries = tokio::fs::read_dir(&dir).await.map_err(open_error)?;
+        while let Some(entry) = entries.next_entry().await.map_err(open_error)? {
+            let path = entry.path();
+            let id = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| Uuid::parse_str(name).ok());
+            match id {
+                Some(id) => {
+                    let metadata = entry.metadata().await.map_err(open_error)?;
+                    let written = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                    blocks.push((written, id, metadata.len() as usize));
+                }
+                // The leftovers of the writes that did not complete
+                None => {
+                    if path
+                        .extension()
+                        .is_some_and(|ext| ext == TEMPORARY_EXTENSION)
+                    {
+                        let _ = tokio::fs::remove_file(&path).await;
+                    }
+                }
+            }
+        }
+        blocks.sort();
+
+        let disk_tier = Self {
+            dir,
+            capacity_bytes: config.capacity_bytes,
+            index: Arc::new(Mutex::new(DiskTierIndex::default())),
+        };
+        let evicted = {
+            let mut index = disk_tier.index.lock();
+            for (_, id, size_bytes) in blocks {
+                index.insert(id, size_bytes);
+            }
+            index.evict(disk_tier.capacity_bytes)
+        };
+        disk_tier.remove_files(evicted).await;
+        Ok(disk_tier)
+    }
+
+    fn path(&self, id: &Uuid) -> PathBuf {
+        self.dir.join(id.to_string())
+    }
+
+    async fn remove_files(&self, ids: Vec<Uuid>) {
+        for id in ids {
+            if let Err(e) = tokio::fs::remove_file(self.path(&id)).await {
+                tracing::warn!("Error removing block {} from the disk tier: {}", id, e);
+            }
+        }
+    }
+
+    /// Returns the bytes of the block if the directory holds it. A block that cannot be read
+    /// is dropped from the directory, so that it is fetched from storage instead
+    pub(super) async fn get(&self, id: &Uuid) -> Option<Arc<Vec<u8>>> {
+        if !self.index.lock().touch(id) {
+            return None;
+        }
+        match tokio::fs::read(self.path(id)).await {
+            Ok(bytes) => Some(Arc::new(bytes)),
+            Err(e) => {
+                tracing::warn!("Error reading block {} from the disk tier: {}", id, e);
+                self.remove(id).await;
+                None
+            }
+        }
+    }
+
+    /// Writes the bytes of the block to the directory, and removes the least recently used
+    /// blocks beyond the capacity. A failed write only leaves the block out of the directory
+    pub(super) async fn put(&self, id: &Uuid, bytes: &[u8]) {
+        if bytes.len() > self.capacity_bytes {
+            return;
+        }
+        let path = self.path(id);
+        let temporary_path = path.with_extension(TEMPORARY_EXTENSION);
+        let written = async {
+            let mut file = tokio::fs::File::create(&temporary_path).await?;
+            file.write_all(bytes).await?;
+            file.sync_all().await?;
+            tokio::fs::rename(&temporary_path, &path).await
+        }
+        .await;
+        if let Err(e) = written {
+            tracing::warn!("Error writing block {} to the disk tier: {}", id, e);
+            let _ = tokio::fs::remove_file(&temporary_path).await;
+            return;
+        }
+        let evicted = {
+            let mut index = self.index.lock();
+            index.insert(*id, bytes.len());
+            index.evict(self.capacity_bytes)
+        };
+        self.remove_files(evicted).await;
+    }
+
+    /// Removes the block from the directory, if it holds it
+    pub(super) async fn remove(&self, id: &Uuid) {
+        if self.index.lock().remove(id) {
+            self.remove_files(vec![*id]).await;
+        }
+    }
+
+    /// The bytes of the blocks that the directory holds
+    pub fn usage_bytes(&self) -> usize {
+        self.index.lock().usage_bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DiskTier;
+    use crate::arrow::config::DiskTierConfig;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_disk_tier() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let config = DiskTierConfig {
+            dir: tmp_dir.path().to_str().unwrap().to_string(),
+            capacity_bytes: 100,
+        };
+        let disk_tier = DiskTier::open(&config).await.unwrap();
+        let ids = (0..3).map(|_| Uuid::new_v4()).collect::<Vec<_>>();
+        disk_tier.put(&ids[0], &[0; 40]).await;
+        disk_tier.put(&ids[1], &[1; 40]).await;
+        // The first block is used more recently than the second
+        assert!(disk_tier.get(&ids[0]).await.is_some());
+        disk_tier.put(&ids[2], &[2; 40]).await;
+        assert_eq!(disk_tier.usage_bytes(), 80);
+        assert!(disk_tier.get(&ids[1]).await.is_none());
+        assert!(!tmp_dir.path().join(ids[1].to_string()).exists());
+
+        // The blocks are served again once the directory is opened again
+        let disk_tier = DiskTier::open(&config).await.unwrap();
+        assert_eq!(disk_tier.usage_bytes(), 80);
+        assert_eq!(*disk_tier.get(&ids[0]).await.unwrap(), vec![0; 40]);
+        assert_eq!(*disk_tier.get(&ids[2]).await.unwrap(), vec![2; 40]);
+
+        disk_tier.remove(&ids[0]).await;
+        assert!(disk_tier.get(&ids[0]).await.is_none());
+        assert_eq!(disk_tier.usage_bytes(), 40);
+    }
+}
@@ -67,4 +67,12 @@ impl ArrowBlockfileFlusher {
     pub(crate) fn id(&self) -> Uuid {
         self.id
     }
+
+    pub(crate) fn num_blocks(&self) -> usize {
+        self.blocks.len()
+    }
+
+    pub(crate) fn num_bytes(&self) -> usize {
+        self.blocks.iter().map(|block| block.get_size()).sum()
+    }
 }
@@ -4,6 +4,7 @@ use super::{
     provider::BlockManager,
     root::{RootWriter, Version},
     sparse_index::SetCountError,
+    types::ArrowWriteableKey,
 };
 use chroma_error::{ChromaError, ErrorCodes};
 use thiserror::Error;
@@ -50,16 +51,16 @@ async fn migrate_v1_to_v1_1(
                 .collect::<Vec<Uuid>>();
         }
         for block_id in block_ids.iter() {
-            let block = block_manager.get(block_id).await;
-            match block {
-                Ok(Some(block)) => {
-                    match root.sparse_index.set_count(*block_id, block.len() as u32) {
-                        Ok(_) => {}
-                        Err(e) => {
-                            return Err(MigrationError::SetCountError(e));
-                        }
+            let codec = root.sparse_index.codec(block_id);
+            // Only the header of the block is read to count it
+            let count = block_manager.count(block_id, codec).await;
+            match count {
+                Ok(Some(count)) => match root.sparse_index.set_count(*block_id, count as u32) {
+                    Ok(_) => {}
+                    Err(e) => {
+                        return Err(MigrationError::SetCountError(e));
                     }
-                }
+                },
                 Ok(None) => {
                     return Err(MigrationError::BlockNotFound);
                 }
@@ -73,10 +74,70 @@ async fn migrate_v1_to_v1_1(
     Ok(())
 }
 
-pub async fn apply_migrations_to_blockfile(
+async fn migrate_v1_1_to_v1_2<K: ArrowWriteableKey>(
     root: &mut RootWriter,
     block_manager: &BlockManager,
     new_block_ids: &HashSet<Uuid>,
 ) -> Result<(), MigrationError> {
-    migrate_v1_to_v1_1(root, block_manager, new_block_ids).await
+    // MIGRATION(10/16/2026) Get all the blocks and set their zone maps in the sparse index
+    if root.version == Version::V1_1 {
+        root.version = Version::V1_2;
+        let block_ids;
+        // Guard the sparse index data access with a lock
+        // otherwise we have to hold the lock across an await
+        {
+            let sparse_index_data = root.sparse_index.data.lock();
+            block_ids = sparse_index_data
+                .forward
+                .values()
+                .filter(|block_id| !new_block_ids.contains(block_id))
+                .copied()
+                .collect::<Vec<Uuid>>();
+        }
+        for block_id in block_ids.iter() {
+            let codec = root.sparse_index.codec(block_id);
+            let block = match block_manager.get(block_id, codec).await {
+                Ok(Some(block)) => block,
+                Ok(None) => {
+                    return Err(MigrationError::BlockNotFound);
+                }
+                Err(_) => {
+                    return Err(MigrationError::BlockFetchError);
+                }
+            };
+            root.sparse_index
+                .set_zone_map(*block_id, block.zone_map::<K::ReadableKey<'_>>())?;
+        }
+    }
+
+    Ok(())
+}
+
+fn migrate_v1_2_to_v1_3(root: &mut RootWriter) {
+    // MIGRATION(10/16/2026) The blocks of the earlier versions are uncompressed, which is the
+    // codec of a block without one in the sparse index, so only the version changes
+    if root.version == Version::V1_2 {
+        root.version = Version::V1_3;
+    }
+}
+
+fn migrate_v1_3_to_v1_4(root: &mut RootWriter) {
+    // MIGRATION(10/16/2026) The blocks of the earlier versions have no bloom filters, which only
+    // means that their lookups are not skipped, so only the version changes. The blocks get
+    // their bloom filters as they are rewritten
+    if root.version == Version::V1_3 {
+        root.version = Version::V1_4;
+    }
+}
+
+pub async fn apply_migrations_to_blockfile<K: ArrowWriteableKey>(
+    root: &mut RootWriter,
+    block_manager: &BlockManager,
+    new_block_ids: &HashSet<Uuid>,
+) -> Result<(), MigrationError> {
+    migrate_v1_to_v1_1(root, block_manager, new_block_ids).await?;
+    migrate_v1_1_to_v1_2::<K>(root, block_manager, new_block_ids).await?;
+    migrate_v1_2_to_v1_3(root);
+    migrate_v1_3_to_v1_4(root);
+    Ok(())
 }
@@ -1,8 +1,13 @@
 pub(crate) mod block;
+mod block_merge;
 pub(crate) mod blockfile;
+mod bloom_filter;
+pub mod compression;
 #[cfg(test)]
 mod concurrency_test;
 pub mod config;
+pub mod decode_executor;
+pub mod disk_tier;
 pub(crate) mod flusher;
 mod migrations;
 pub(crate) mod ordered_blockfile_writer;
@@ -1,5 +1,7 @@
 use super::block::delta::types::Delta;
 use super::block::delta::OrderedBlockDelta;
+use super::block::Block;
+use super::block_merge::merge_small_blocks;
 use super::migrations::apply_migrations_to_blockfile;
 use super::migrations::MigrationError;
 use super::provider::BlockManager;
@@ -22,12 +24,17 @@ use std::sync::Arc;
 use thiserror::Error;
 use tokio::sync::Mutex;
 use tokio::sync::MutexGuard;
+use tokio::task::JoinHandle;
 use uuid::Uuid;
 
 // The end key is exclusive, if the end key is None, then the block/delta is open-ended
 type BlockIdAndEndKey = (Uuid, Option<CompositeKey>);
 type CurrentDeltaAndEndKey = (OrderedBlockDelta, Option<CompositeKey>);
 
+// The number of streamed blocks that may be uploading at once, beyond which a write waits for
+// the oldest upload so that the blocks awaiting upload stay bounded
+const MAX_STREAMED_UPLOADS: usize = 16;
+
 #[derive(Default)]
 struct Inner {
     /// On construction, this contains all existing block IDs and the end of their key range ordered by end key (asc).
@@ -37,6 +44,10 @@ struct Inner {
     current_block_delta: Option<CurrentDeltaAndEndKey>,
     /// Deltas in this vec can no longer receive writes and are ready to be committed.
     completed_block_deltas: Vec<OrderedBlockDelta>,
+    /// When streaming, the IDs of the blocks that were already sealed and uploaded.
+    sealed_block_ids: HashSet<Uuid>,
+    /// When streaming, the uploads of the sealed blocks that have not been awaited yet.
+    uploads: VecDeque<JoinHandle<Result<(), Box<dyn ChromaError>>>>,
 }
 
 #[derive(Clone)]
@@ -46,18 +57,22 @@ pub struct ArrowOrderedBlockfileWriter {
     root: RootWriter,
     inner: Arc<Mutex<Inner>>,
     id: Uuid,
+    stream_blocks: bool,
 }
 
 #[derive(Error, Debug)]
 pub enum ArrowBlockfileError {
     #[error("Could not migrate blockfile to new version")]
     MigrationError(#[from] MigrationError),
+    #[error("Block upload task failed")]
+    UploadTaskError(#[from] tokio::task::JoinError),
 }
 
 impl ChromaError for ArrowBlockfileError {
     fn code(&self) -> ErrorCodes {
         match self {
             ArrowBlockfileError::MigrationError(e) => e.code(),
+            ArrowBlockfileError::UploadTaskError(_) => ErrorCodes::Internal,
         }
     }
 }
@@ -79,9 +94,10 @@ impl ArrowOrderedBlockfileWriter {
             id,
             inner: Arc::new(Mutex::new(Inner {
                 current_block_delta: Some((initial_block, None)),
-                completed_block_deltas: Vec::new(),
                 remaining_block_stack: VecDeque::new(),
+                ..Default::default()
             })),
+            stream_blocks: false,
         }
     }
 
@@ -120,12 +136,20 @@ impl ArrowOrderedBlockfileWriter {
             id,
             inner: Arc::new(Mutex::new(Inner {
                 current_block_delta: None,
-                completed_block_deltas: Vec::new(),
                 remaining_block_stack,
+                ..Default::default()
             })),
+            stream_blocks: false,
         }
     }
 
+    /// Seal and upload the deltas that can no longer receive writes as the writer goes, see
+    /// `BlockfileWriterOptions::stream_blocks()`
+    pub(super) fn with_stream_blocks(mut self, stream_blocks: bool) -> Self {
+        self.stream_blocks = stream_blocks;
+        self
+    }
+
     pub(crate) async fn commit<K: ArrowWriteableKey, V: ArrowWriteableValue>(
         mut self,
     ) -> Result<ArrowBlockfileFlusher, Box<dyn ChromaError>> {
@@ -133,44 +157,81 @@ impl ArrowOrderedBlockfileWriter {
 
         Self::complete_current_delta::<K, V>(&mut inner);
 
+        // The streamed blocks are already sealed, and are only awaited here so that the writer
+        // does not stall on their uploads
+        let mut new_block_ids = std::mem::take(&mut inner.sealed_block_ids);
+        let mut blocks = Vec::new();
+        for delta in std::mem::take(&mut inner.completed_block_deltas) {
+            blocks.extend(self.seal_delta::<K, V>(delta, &mut new_block_ids).await?);
+        }
+        while let Some(upload) = inner.uploads.pop_front() {
+            Self::await_upload(upload).await?;
+        }
+
+        apply_migrations_to_blockfile::<K>(&mut self.root, &self.block_manager, &new_block_ids)
+            .await
+            .map_err(|e| {
+                Box::new(ArrowBlockfileError::MigrationError(e)) as Box<dyn ChromaError>
+            })?;
+
+        let blocks = merge_small_blocks::<K, V>(&self.root, &self.block_manager, blocks)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn ChromaError>)?;
+
+        let flusher = ArrowBlockfileFlusher::new(
+            self.block_manager,
+            self.root_manager,
+            blocks,
+            self.root,
+            self.id,
+        );
+
+        Ok(flusher)
+    }
+
+    /// Commits the delta to one or more blocks, splitting it if it is over the maximum size,
+    /// and records them in the sparse index. An empty delta is removed from the sparse index
+    /// instead
+    async fn seal_delta<K: ArrowWriteableKey, V: ArrowWriteableValue>(
+        &self,
+        delta: OrderedBlockDelta,
+        new_block_ids: &mut HashSet<Uuid>,
+    ) -> Result<Vec<Block>, Box<dyn ChromaError>> {
         let mut split_block_deltas = Vec::new();
-        for delta in inner.completed_block_deltas.drain(..) {
-            // Don't we split on-mutation (.set() calls)?
-            // Yes, but that is only a performance optimization. For correctness, we must also split on commit. Why?
-            //
-            // We need to defer copying old forked data until:
-            // - we receive a set()/delete() for a later key
-            // - we are committing the delta (it will receive no further writes)
-            //
-            // Because of this constraint, we cannot always effectively split on-mutation if the writer is over a forked blockfile. Imagine this scenario:
-            // 1. There is 1 existing block whose size == limit.
-            // 2. We receive a .set() for a key before the existing block's start key.
-            // 3. We turn the existing block into a delta and add the new KV pair.
-            // 4. At this point, the total size of the delta (materialized + pending forked data) is above the limit.
-            // 5. We would like to split our delta into two immediately after the newly-added key. However, this means that the right half of the split is empty (there is no materialized data), which violates a fundamental assumption made by our blockstore code. And we cannot materialize only the first key in the right half from the pending forked data because that would violate the above constraint.
-            //
-            // Thus, we handle splitting in two places:
-            //
-            // 1. Split deltas in half on-mutation if the materialized size is over the limit (just a performance optimization).
-            // 2. During the commit phase, after all deltas have been fully materialized, split if necessary.
-            //
-            // An alternative would be to create a fresh delta that does not fork from an existing block if we receive a .set() for a key that is not contained in any existing block key range, however this complicates writing logic and potentially increases fragmentation.
-            if delta.get_size::<K, V>() > self.block_manager.max_block_size_bytes() {
-                let split_blocks = delta.split::<K, V>(self.block_manager.max_block_size_bytes());
-                for (split_key, split_delta) in split_blocks {
-                    self.root
-                        .sparse_index
-                        .add_block(split_key, split_delta.id)
-                        .map_err(|e| Box::new(e) as Box<dyn ChromaError>)?;
-                    split_block_deltas.push(split_delta);
-                }
+        // Don't we split on-mutation (.set() calls)?
+        // Yes, but that is only a performance optimization. For correctness, we must also split on commit. Why?
+        //
+        // We need to defer copying old forked data until:
+        // - we receive a set()/delete() for a later key
+        // - we are committing the delta (it will receive no further writes)
+        //
+        // Because of this constraint, we cannot always effectively split on-mutation if the writer is over a forked blockfile. Imagine this scenario:
+        // 1. There is 1 existing block whose size == limit.
+        // 2. We receive a .set() for a key before the existing block's start key.
+        // 3. We turn the existing block into a delta and add the new KV pair.
+        // 4. At this point, the total size of the delta (materialized + pending forked data) is above the limit.
+        // 5. We would like to split our delta into two immediately after the newly-added key. However, this means that the right half of the split is empty (there is no materialized data), which violates a fundamental assumption made by our blockstore code. And we cannot materialize only the first key in the right half from the pending forked data because that would violate the above constraint.
+        //
+        // Thus, we handle splitting in two places:
+        //
+        // 1. Split deltas in half on-mutation if the materialized size is over the limit (just a performance optimization).
+        // 2. During the commit phase, after all deltas have been fully materialized, split if necessary.
+        //
+        // An alternative would be to create a fresh delta that does not fork from an existing block if we receive a .set() for a key that is not contained in any existing block key range, however this complicates writing logic and potentially increases fragmentation.
+        if delta.get_size::<K, V>() > self.block_manager.max_block_size_bytes() {
+            let split_blocks = delta.split::<K, V>(self.block_manager.max_block_size_bytes());
+            for (split_key, split_delta) in split_blocks {
+                self.root
+                    .sparse_index
+                    .add_block(split_key, split_delta.id)
+                    .map_err(|e| Box::new(e) as Box<dyn ChromaError>)?;
+                split_block_deltas.push(split_delta);
             }
-            split_block_deltas.push(delta);
         }
+        split_block_deltas.push(delta);
 
         let mut blocks = Vec::new();
-        let mut new_block_ids = HashSet::new();
-        for delta in split_block_deltas.drain(..) {
+        for delta in split_block_deltas {
             new_block_ids.insert(delta.id());
             let mut removed = false;
             // Skip empty blocks. Also, remove from sparse index.
@@ -184,25 +245,59 @@ impl ArrowOrderedBlockfileWriter {
                     .set_count(delta.id(), delta.len() as u32)
                     .map_err(|e| Box::new(e) as Box<dyn ChromaError>)?;
                 let block = self.block_manager.commit::<K, V>(delta).await;
+                self.root
+                    .sparse_index
+                    .set_zone_map(block.id, block.zone_map::<K::ReadableKey<'_>>())
+                    .map_err(|e| Box::new(e) as Box<dyn ChromaError>)?;
+                self.root
+                    .sparse_index
+                    .set_codec(block.id, self.block_manager.codec(&block))
+                    .map_err(|e| Box::new(e) as Box<dyn ChromaError>)?;
+                self.root
+                    .sparse_index
+                    .set_bloom_filter(
+                        block.id,
+                        self.block_manager
+                            .bloom_filter::<K::ReadableKey<'_>>(&block),
+                    )
+                    .map_err(|e| Box::new(e) as Box<dyn ChromaError>)?;
                 blocks.push(block);
             }
         }
+        Ok(blocks)
+    }
 
-        apply_migrations_to_blockfile(&mut self.root, &self.block_manager, &new_block_ids)
-            .await
-            .map_err(|e| {
-                Box::new(ArrowBlockfileError::MigrationError(e)) as Box<dyn ChromaError>
-            })?;
-
-        let flusher = ArrowBlockfileFlusher::new(
-            self.block_manager,
-            self.root_manager,
-            blocks,
-            self.root,
-            self.id,
-        );
+    /// Seals the deltas that can no longer receive writes and uploads their blocks in the
+    /// background, so that they do not stay in memory until the writer is flushed
+    async fn stream_completed_deltas<K: ArrowWriteableKey, V: ArrowWriteableValue>(
+        &self,
+        inner: &mut Inner,
+    ) -> Result<(), Box<dyn ChromaError>> {
+        for delta in std::mem::take(&mut inner.completed_block_deltas) {
+            let blocks = self
+                .seal_delta::<K, V>(delta, &mut inner.sealed_block_ids)
+                .await?;
+            for block in blocks {
+                if inner.uploads.len() >= MAX_STREAMED_UPLOADS {
+                    if let Some(upload) = inner.uploads.pop_front() {
+                        Self::await_upload(upload).await?;
+                    }
+                }
+                let block_manager = self.block_manager.clone();
+                inner.uploads.push_back(tokio::spawn(
+                    async move { block_manager.flush(&block).await },
+                ));
+            }
+        }
+        Ok(())
+    }
 
-        Ok(flusher)
+    async fn await_upload(
+        upload: JoinHandle<Result<(), Box<dyn ChromaError>>>,
+    ) -> Result<(), Box<dyn ChromaError>> {
+        upload.await.map_err(|e| {
+            Box::new(ArrowBlockfileError::UploadTaskError(e)) as Box<dyn ChromaError>
+        })?
     }
 
     fn complete_current_delta<K: ArrowWriteableKey, V: ArrowWriteableValue>(inner: &mut Inner) {
@@ -220,9 +315,10 @@ impl ArrowOrderedBlockfileWriter {
     ) -> Result<(), Box<dyn ChromaError>> {
         Self::complete_current_delta::<K, V>(inner);
 
+        let codec = self.root.sparse_index.codec(new_delta_block_id);
         let new_delta = self
             .block_manager
-            .fork::<K, V, OrderedBlockDelta>(new_delta_block_id)
+            .fork::<K, V, OrderedBlockDelta>(new_delta_block_id, codec)
             .await
             .map_err(|e| Box::new(e) as Box<dyn ChromaError>)?;
 
@@ -321,6 +417,10 @@ impl ArrowOrderedBlockfileWriter {
             inner.current_block_delta = Some((new_delta, current_end_key));
         }
 
+        if self.stream_blocks {
+            self.stream_completed_deltas::<K, V>(inner).await?;
+        }
+
         Ok(())
     }
 
@@ -334,6 +434,11 @@ impl ArrowOrderedBlockfileWriter {
             .await?;
         let delta = &mut inner.current_block_delta.as_mut().expect("Invariant violation: advance_current_delta_and_get_inner() did not populate current delta").0;
         delta.skip::<K, V>(prefix, key);
+
+        if self.stream_blocks {
+            self.stream_completed_deltas::<K, V>(inner).await?;
+        }
+
         Ok(())
     }
 
@@ -786,8 +891,9 @@ mod tests {
             inner: Arc::new(Mutex::new(Inner {
                 remaining_block_stack: VecDeque::new(),
                 current_block_delta: Some((initial_block, None)),
-                completed_block_deltas: Vec::new(),
+                ..Default::default()
             })),
+            stream_blocks: false,
         };
 
         let n = 2000;
@@ -949,9 +1055,18 @@ mod tests {
             _ => panic!("Unexpected reader type"),
         };
 
-        assert_eq!(reader.root.version, Version::V1_1);
+        assert_eq!(reader.root.version, Version::V1_4);
         assert_eq!(reader.root.sparse_index.len(), 2);
 
+        // The zone maps are set for both the migrated and the new blocks
+        assert!(reader
+            .root
+            .sparse_index
+            .data
+            .forward
+            .values()
+            .all(|value| value.zone_map.is_some()));
+
         // Manually verify sparse index counts
         let count_in_index: u32 = reader
             .root
@@ -964,4 +1079,58 @@ mod tests {
         assert_eq!(count_in_index, 3);
         assert_eq!(reader.count().await.unwrap(), 3);
     }
+
+    #[tokio::test]
+    async fn test_stream_blocks() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let storage = Storage::Local(LocalStorage::new(tmp_dir.path().to_str().unwrap()));
+        let blockfile_provider = ArrowBlockfileProvider::new(
+            storage.clone(),
+            TEST_MAX_BLOCK_SIZE_BYTES,
+            new_cache_for_test(),
+            new_cache_for_test(),
+        );
+        let writer = blockfile_provider
+            .write::<&str, Vec<u32>>(
+                BlockfileWriterOptions::new()
+                    .ordered_mutations()
+                    .stream_blocks(),
+            )
+            .await
+            .unwrap();
+        let id = writer.id();
+
+        let n = 2000;
+        for i in 0..n {
+            let key = format!("{:04}", i);
+            writer.set("key", key.as_str(), vec![i]).await.unwrap();
+        }
+
+        // The completed blocks are uploaded by the time the writer is committed, before it is flushed
+        let flusher = writer.commit::<&str, Vec<u32>>().await.unwrap();
+        let streamed_blocks = storage.list("block/").await.unwrap().len();
+        assert!(streamed_blocks > 0);
+        flusher.flush::<&str, Vec<u32>>().await.unwrap();
+
+        let reader = blockfile_provider.read::<&str, &[u32]>(&id).await.unwrap();
+        for i in 0..n {
+            let key = format!("{:04}", i);
+            let value = reader.get("key", &key).await.unwrap().unwrap();
+            assert_eq!(value, [i]);
+        }
+        assert_eq!(reader.count().await.unwrap(), n as usize);
+
+        // The last block is only uploaded on flush, along with the root
+        match &reader {
+            BlockfileReader::ArrowBlockfileReader(reader) => {
+                assert!(reader.root.sparse_index.is_valid());
+                assert_eq!(reader.root.sparse_index.len(), streamed_blocks + 1);
+                assert_eq!(
+                    storage.list("block/").await.unwrap().len(),
+                    streamed_blocks + 1
+                );
+            }
+            _ => panic!("Unexpected reader type"),
+        }
+    }
 }
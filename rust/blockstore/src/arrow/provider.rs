@@ -1,7 +1,14 @@
 use super::{
     block::{delta::types::Delta, Block, BlockLoadError},
     blockfile::{ArrowBlockfileReader, ArrowUnorderedBlockfileWriter},
-    config::ArrowBlockfileProviderConfig,
+    bloom_filter::BloomFilter,
+    compression::{BlockCodec, BlockCompressionConfig},
+    config::{
+        ArrowBlockfileProviderConfig, DEFAULT_BLOCK_FETCH_CONCURRENCY,
+        DEFAULT_DECODE_OFFLOAD_THRESHOLD_BYTES, DEFAULT_MIN_BLOCK_SIZE_DIVISOR,
+    },
+    decode_executor::DecodeExecutor,
+    disk_tier::DiskTier,
     ordered_blockfile_writer::ArrowOrderedBlockfileWriter,
     root::{FromBytesError, RootReader, RootWriter},
     types::{ArrowReadableKey, ArrowReadableValue, ArrowWriteableKey, ArrowWriteableValue},
@@ -9,20 +16,58 @@ use super::{
 use crate::{
     key::KeyWrapper,
     memory::storage::Readable,
-    provider::{CreateError, OpenError},
+    provider::{BlockfileObject, CreateError, OpenError},
+    read_stats::{record_bytes_read, record_cache_hit},
     BlockfileReader, BlockfileWriter, BlockfileWriterMutationOrdering, BlockfileWriterOptions, Key,
     Value,
 };
 use async_trait::async_trait;
-use chroma_cache::{CacheError, PersistentCache};
+use chroma_cache::{CacheError, CacheMetrics, CachedEntry, PersistentCache};
 use chroma_config::Configurable;
-use chroma_error::{ChromaError, ErrorCodes};
-use chroma_storage::Storage;
-use std::sync::Arc;
+use chroma_error::{ChromaError, ErrorCodes, ErrorLocation};
+use chroma_storage::{DeleteError, ListError, ObjectMetadata, PutOptions, Storage};
+use futures::StreamExt;
+use parking_lot::Mutex;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, OnceLock};
+use std::time::SystemTime;
 use thiserror::Error;
 use tracing::{Instrument, Span};
 use uuid::Uuid;
 
+// The weight of the blocks in the cache is in MiB, unless the cache weighs their bytes
+const BLOCK_WEIGHT_BYTES: usize = 1024 * 1024;
+
+// The number of blocks whose reads are counted, beyond which the counts are halved and the
+// blocks that are rarely read are forgotten, so that the counts favor the recent reads
+const MAX_COUNTED_BLOCKS: usize = 1 << 20;
+
+// The prefixes of the keys of the blocks and the roots in storage
+const BLOCK_KEY_PREFIX: &str = "block/";
+const ROOT_KEY_PREFIX: &str = "sparse_index/";
+
+// The bytes at the start of a block that are read to count its items, which hold the schema
+// and the header of the record batch of any block
+const BLOCK_HEADER_PREFIX_BYTES: usize = 16 * 1024;
+
+// The ids of the objects that are listed under the prefix, with when they were written. The
+// objects whose keys are not an id are skipped
+fn parse_object_ids(prefix: &str, objects: Vec<ObjectMetadata>) -> Vec<(Uuid, SystemTime)> {
+    objects
+        .into_iter()
+        .filter_map(|object| {
+            let id = object.key.strip_prefix(prefix)?;
+            match Uuid::parse_str(id) {
+                Ok(id) => Some((id, object.last_modified)),
+                Err(_) => {
+                    tracing::warn!("Skipping object with unexpected key {}", object.key);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
 /// A BlockFileProvider that creates ArrowBlockfiles (Arrow-backed blockfiles used for production).
 /// For now, it keeps a simple local cache of blockfiles.
 #[derive(Clone)]
@@ -44,6 +89,72 @@ impl ArrowBlockfileProvider {
         }
     }
 
+    /// Sets the size from which blocks are decoded by the decode executor
+    pub fn with_decode_offload_threshold_bytes(mut self, threshold_bytes: usize) -> Self {
+        self.block_manager.decode_offload_threshold_bytes = threshold_bytes;
+        self
+    }
+
+    /// Sets the executor that decodes the large blocks, for this provider and all of its
+    /// clones. The executor can only be set once, since it is usually created after the
+    /// provider, e.g. from a pool of threads that the readers of the provider run on
+    pub fn set_decode_executor(&self, executor: Arc<dyn DecodeExecutor>) {
+        if self.block_manager.decode_executor.set(executor).is_err() {
+            tracing::warn!("The decode executor of the blockfile provider is already set");
+        }
+    }
+
+    /// Sets the compression of the blocks flushed by this provider. The blocks are read with
+    /// the codec recorded in the sparse index regardless of it
+    pub fn with_compression(mut self, compression: BlockCompressionConfig) -> Self {
+        self.block_manager.compression = compression;
+        self
+    }
+
+    /// Sets the number of bits per key of the bloom filters of the blocks committed by this
+    /// provider, which let point lookups skip the blocks that do not contain their keys. The
+    /// blocks get no bloom filter if it is None
+    pub fn with_bloom_filter_bits_per_key(mut self, bits_per_key: Option<u32>) -> Self {
+        self.block_manager.bloom_filter_bits_per_key = bits_per_key;
+        self
+    }
+
+    /// Sets the number of blocks that the readers of this provider fetch at once for a
+    /// multi-get or a range read
+    pub fn with_block_fetch_concurrency(mut self, concurrency: usize) -> Self {
+        self.block_manager.block_fetch_concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Sets the size under which the adjacent blocks of a blockfile are merged into full-size
+    /// blocks on commit, so that repeated small writes do not leave many tiny blocks behind.
+    /// The blocks are not merged if it is None or zero
+    pub fn with_min_block_size_bytes(mut self, min_block_size_bytes: Option<usize>) -> Self {
+        self.block_manager.min_block_size_bytes = min_block_size_bytes;
+        self
+    }
+
+    /// Sets the local directory that the blocks fetched from storage are also kept in, and
+    /// that the blocks missing from the cache are read from before storage
+    pub fn with_disk_tier(mut self, disk_tier: Option<DiskTier>) -> Self {
+        self.block_manager.disk_tier = disk_tier;
+        self
+    }
+
+    /// Sets the partition of the block cache that the blocks read and written by this provider
+    /// are accounted to, so that the cache bounds them by the quota of the partition
+    pub fn with_cache_partition(mut self, partition: &str) -> Self {
+        self.block_manager.cache_partition = Some(Arc::from(partition));
+        self
+    }
+
+    /// Sets the options of the writes of the blocks and roots flushed by this provider
+    pub fn with_put_options(mut self, put_options: PutOptions) -> Self {
+        self.block_manager.put_options = put_options.clone();
+        self.root_manager.put_options = put_options;
+        self
+    }
+
     pub async fn read<
         'new,
         K: Key + Into<KeyWrapper> + ArrowReadableKey<'new> + 'new,
@@ -54,9 +165,9 @@ impl ArrowBlockfileProvider {
     ) -> Result<BlockfileReader<'new, K, V>, Box<OpenError>> {
         let root = self.root_manager.get::<K>(id).await;
         match root {
-            Ok(Some(root)) => Ok(BlockfileReader::ArrowBlockfileReader(
+            Ok(Some(root)) => Ok(BlockfileReader::ArrowBlockfileReader(Box::new(
                 ArrowBlockfileReader::new(self.block_manager.clone(), root),
-            )),
+            ))),
             Ok(None) => Err(Box::new(OpenError::NotFound)),
             Err(e) => Err(Box::new(OpenError::Other(Box::new(e)))),
         }
@@ -89,7 +200,8 @@ impl ArrowBlockfileProvider {
                         self.block_manager.clone(),
                         self.root_manager.clone(),
                         new_root,
-                    );
+                    )
+                    .with_stream_blocks(options.stream_blocks);
 
                     Ok(BlockfileWriter::ArrowOrderedBlockfileWriter(file))
                 }
@@ -112,7 +224,8 @@ impl ArrowBlockfileProvider {
                         new_id,
                         self.block_manager.clone(),
                         self.root_manager.clone(),
-                    );
+                    )
+                    .with_stream_blocks(options.stream_blocks);
 
                     Ok(BlockfileWriter::ArrowOrderedBlockfileWriter(file))
                 }
@@ -133,6 +246,165 @@ impl ArrowBlockfileProvider {
         self.root_manager.cache.clear().await?;
         Ok(())
     }
+
+    /// Evicts the cached root and blocks of a blockfile that is superseded by another blockfile.
+    /// Blocks shared with the superseding blockfile (e.g. because it is forked from the stale one)
+    /// are kept in the cache.
+    pub async fn evict_superseded(&self, stale_id: &Uuid, current_id: &Uuid) {
+        let Some(stale_root) = self.root_manager.cached(stale_id).await else {
+            return;
+        };
+        // Without the current root we cannot tell which blocks are still shared,
+        // so we only evict the stale root and leave the blocks to the LRU
+        if let Some(current_root) = self.root_manager.cached(current_id).await {
+            let current_block_ids = current_root.sparse_index.get_all_block_ids();
+            for block_id in stale_root
+                .sparse_index
+                .get_all_block_ids()
+                .difference(&current_block_ids)
+            {
+                self.block_manager.evict(block_id).await;
+            }
+        }
+        self.root_manager.evict(stale_id).await;
+    }
+
+    /// Returns up to `limit` of the blocks that the roots reference, the most read first. The
+    /// blocks that were not read by this provider are left out
+    pub async fn hottest_block_ids(
+        &self,
+        root_ids: &[Uuid],
+        limit: usize,
+    ) -> Result<Vec<Uuid>, Box<dyn ChromaError>> {
+        let mut block_ids = HashSet::new();
+        for root_id in root_ids {
+            if let Some(ids) = self.referenced_block_ids(root_id).await? {
+                block_ids.extend(ids);
+            }
+        }
+        Ok(self.block_manager.hottest(block_ids, limit))
+    }
+
+    /// Loads the given blocks of the roots into the cache, and returns the number of blocks
+    /// that are loaded. The blocks that the roots do not reference are skipped, and so are the
+    /// roots that are not cached, since the codecs of their blocks are unknown without them
+    pub async fn load_blocks(&self, root_ids: &[Uuid], block_ids: &HashSet<Uuid>) -> usize {
+        let mut blocks = HashMap::new();
+        for root_id in root_ids {
+            if let Some(root) = self.root_manager.cached(root_id).await {
+                for block_id in root.sparse_index.get_all_block_ids() {
+                    if block_ids.contains(&block_id) {
+                        blocks.insert(block_id, root.sparse_index.codec(&block_id));
+                    }
+                }
+            }
+        }
+        futures::stream::iter(blocks)
+            .map(|(block_id, codec)| async move {
+                match self.block_manager.get(&block_id, codec).await {
+                    Ok(block) => block.is_some(),
+                    Err(e) => {
+                        tracing::warn!("Error loading block {}: {}", block_id, e);
+                        false
+                    }
+                }
+            })
+            .buffer_unordered(self.block_manager.block_fetch_concurrency())
+            .filter(|loaded| futures::future::ready(*loaded))
+            .count()
+            .await
+    }
+
+    // The bytes that a unit of the usage and the capacity of the block cache stands for
+    fn block_cache_unit_bytes(&self) -> usize {
+        if self.block_manager.block_cache.weighs_bytes() {
+            1
+        } else {
+            BLOCK_WEIGHT_BYTES
+        }
+    }
+
+    /// The bytes of the blocks that the provider holds in its cache in memory
+    pub fn cache_usage_bytes(&self) -> usize {
+        self.block_manager.block_cache.usage() * self.block_cache_unit_bytes()
+    }
+
+    /// The bytes of the blocks that the cache may hold in memory, if it is bounded
+    pub fn cache_capacity_bytes(&self) -> Option<usize> {
+        self.block_manager
+            .block_cache
+            .capacity()
+            .map(|capacity| capacity.saturating_mul(self.block_cache_unit_bytes()))
+    }
+
+    /// The largest blocks that the cache holds in memory, up to the limit and the largest
+    /// first, with the partitions that they are cached on behalf of
+    pub fn largest_cached_blocks(&self, limit: usize) -> Vec<CachedEntry<Uuid>> {
+        let unit_bytes = self.block_cache_unit_bytes();
+        self.block_manager
+            .block_cache
+            .largest_entries(limit)
+            .into_iter()
+            .map(|entry| CachedEntry {
+                size_bytes: entry.size_bytes * unit_bytes,
+                ..entry
+            })
+            .collect()
+    }
+
+    /// Bounds the bytes of the blocks that the cache holds in memory, and evicts blocks until
+    /// it holds no more
+    pub fn resize_cache(&self, capacity_bytes: usize) {
+        self.block_manager
+            .block_cache
+            .resize((capacity_bytes / self.block_cache_unit_bytes()).max(1));
+    }
+
+    /// Lists the roots and the blocks that are in storage, with when they were written
+    pub async fn list_objects(
+        &self,
+    ) -> Result<Vec<(BlockfileObject, SystemTime)>, Box<dyn ChromaError>> {
+        let roots = self
+            .root_manager
+            .list()
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn ChromaError>)?;
+        let blocks = self
+            .block_manager
+            .list()
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn ChromaError>)?;
+        Ok(roots
+            .into_iter()
+            .map(|(id, last_modified)| (BlockfileObject::Root(id), last_modified))
+            .chain(
+                blocks
+                    .into_iter()
+                    .map(|(id, last_modified)| (BlockfileObject::Block(id), last_modified)),
+            )
+            .collect())
+    }
+
+    /// Returns the ids of the blocks that the root references, or None if there is no such
+    /// root. The keys of the root are not decoded, so it works for a blockfile of any type
+    pub async fn referenced_block_ids(
+        &self,
+        root_id: &Uuid,
+    ) -> Result<Option<Vec<Uuid>>, Box<dyn ChromaError>> {
+        self.root_manager
+            .block_ids(root_id)
+            .await
+            .map_err(|e| Box::new(e) as _)
+    }
+
+    /// Deletes the object from storage and evicts it from the cache
+    pub async fn delete_object(&self, object: BlockfileObject) -> Result<(), Box<dyn ChromaError>> {
+        match object {
+            BlockfileObject::Root(id) => self.root_manager.delete(&id).await,
+            BlockfileObject::Block(id) => self.block_manager.delete(&id).await,
+        }
+        .map_err(|e| Box::new(e) as _)
+    }
 }
 
 #[async_trait]
@@ -141,6 +413,14 @@ impl Configurable<(ArrowBlockfileProviderConfig, Storage)> for ArrowBlockfilePro
         config: &(ArrowBlockfileProviderConfig, Storage),
     ) -> Result<Self, Box<dyn ChromaError>> {
         let (blockfile_config, storage) = config;
+        let disk_tier = match &blockfile_config.block_manager_config.disk_tier {
+            Some(disk_tier_config) => Some(
+                DiskTier::open(disk_tier_config)
+                    .await
+                    .map_err(|e| Box::new(e) as Box<dyn ChromaError>)?,
+            ),
+            None => None,
+        };
         let block_cache = match chroma_cache::from_config_persistent(
             &blockfile_config.block_manager_config.block_cache_config,
         )
@@ -167,23 +447,57 @@ impl Configurable<(ArrowBlockfileProviderConfig, Storage)> for ArrowBlockfilePro
             blockfile_config.block_manager_config.max_block_size_bytes,
             block_cache,
             sparse_index_cache,
+        )
+        .with_decode_offload_threshold_bytes(
+            blockfile_config
+                .block_manager_config
+                .decode_offload_threshold_bytes,
+        )
+        .with_compression(blockfile_config.block_manager_config.compression.clone())
+        .with_bloom_filter_bits_per_key(
+            blockfile_config
+                .block_manager_config
+                .bloom_filter_bits_per_key,
+        )
+        .with_block_fetch_concurrency(
+            blockfile_config
+                .block_manager_config
+                .block_fetch_concurrency,
+        )
+        .with_min_block_size_bytes(Some(
+            blockfile_config
+                .block_manager_config
+                .min_block_size_bytes
+                .unwrap_or(
+                    blockfile_config.block_manager_config.max_block_size_bytes
+                        / DEFAULT_MIN_BLOCK_SIZE_DIVISOR,
+                ),
         ))
+        .with_disk_tier(disk_tier))
     }
 }
 
 #[derive(Error, Debug)]
 pub enum GetError {
-    #[error(transparent)]
-    BlockLoadError(#[from] BlockLoadError),
-    #[error(transparent)]
-    StorageGetError(#[from] chroma_storage::GetError),
+    #[error("Error loading block {0}: {1}")]
+    BlockLoadError(Uuid, #[source] BlockLoadError),
+    #[error("Error fetching block {0}: {1}")]
+    StorageGetError(Uuid, #[source] chroma_storage::GetError),
 }
 
 impl ChromaError for GetError {
     fn code(&self) -> ErrorCodes {
         match self {
-            GetError::BlockLoadError(e) => e.code(),
-            GetError::StorageGetError(e) => e.code(),
+            GetError::BlockLoadError(_, e) => e.code(),
+            GetError::StorageGetError(_, e) => e.code(),
+        }
+    }
+
+    fn location(&self) -> ErrorLocation {
+        match self {
+            GetError::BlockLoadError(id, _) | GetError::StorageGetError(id, _) => {
+                ErrorLocation::block(id)
+            }
         }
     }
 }
@@ -203,6 +517,31 @@ impl ChromaError for ForkError {
             ForkError::GetError(e) => e.code(),
         }
     }
+
+    fn location(&self) -> ErrorLocation {
+        match self {
+            ForkError::BlockNotFound => ErrorLocation::default(),
+            ForkError::GetError(e) => e.location(),
+        }
+    }
+}
+
+// The number of times that each block is read, to tell the hottest blocks apart
+#[derive(Default)]
+struct BlockReadCounts {
+    counts: HashMap<Uuid, u64>,
+}
+
+impl BlockReadCounts {
+    fn record(&mut self, id: Uuid) {
+        *self.counts.entry(id).or_default() += 1;
+        if self.counts.len() > MAX_COUNTED_BLOCKS {
+            self.counts.retain(|_, count| {
+                *count /= 2;
+                *count > 0
+            });
+        }
+    }
 }
 
 /// A simple local cache of Arrow-backed blocks, the blockfile provider passes this
@@ -216,6 +555,17 @@ pub(super) struct BlockManager {
     block_cache: Arc<dyn PersistentCache<Uuid, Block>>,
     storage: Storage,
     max_block_size_bytes: usize,
+    decode_offload_threshold_bytes: usize,
+    decode_executor: Arc<OnceLock<Arc<dyn DecodeExecutor>>>,
+    compression: BlockCompressionConfig,
+    bloom_filter_bits_per_key: Option<u32>,
+    block_fetch_concurrency: usize,
+    min_block_size_bytes: Option<usize>,
+    disk_tier: Option<DiskTier>,
+    read_counts: Arc<Mutex<BlockReadCounts>>,
+    cache_partition: Option<Arc<str>>,
+    cache_metrics: CacheMetrics,
+    put_options: PutOptions,
     write_mutex: Arc<tokio::sync::Mutex<()>>,
 }
 
@@ -230,6 +580,17 @@ impl BlockManager {
             block_cache,
             storage,
             max_block_size_bytes,
+            decode_offload_threshold_bytes: DEFAULT_DECODE_OFFLOAD_THRESHOLD_BYTES,
+            decode_executor: Arc::new(OnceLock::new()),
+            compression: BlockCompressionConfig::default(),
+            bloom_filter_bits_per_key: None,
+            block_fetch_concurrency: DEFAULT_BLOCK_FETCH_CONCURRENCY,
+            min_block_size_bytes: None,
+            disk_tier: None,
+            read_counts: Arc::new(Mutex::new(BlockReadCounts::default())),
+            cache_partition: None,
+            cache_metrics: CacheMetrics::new("block"),
+            put_options: PutOptions::default(),
             write_mutex: Arc::new(tokio::sync::Mutex::new(())),
         }
     }
@@ -242,8 +603,9 @@ impl BlockManager {
     pub(super) async fn fork<K: ArrowWriteableKey, V: ArrowWriteableValue, D: Delta>(
         &self,
         block_id: &Uuid,
+        codec: BlockCodec,
     ) -> Result<D, ForkError> {
-        let block = self.get(block_id).await;
+        let block = self.get(block_id, codec).await;
         let block = match block {
             Ok(Some(block)) => block,
             Ok(None) => {
@@ -264,68 +626,221 @@ impl BlockManager {
         let delta_id = delta.id();
         let record_batch = delta.finish::<K, V>(None);
         let block = Block::from_record_batch(delta_id, record_batch);
-        self.block_cache.insert(delta_id, block.clone()).await;
+        self.cache(delta_id, block.clone()).await;
         block
     }
 
+    async fn cache(&self, id: Uuid, block: Block) {
+        match &self.cache_partition {
+            Some(partition) => {
+                self.block_cache
+                    .insert_in_partition(partition, id, block)
+                    .await
+            }
+            None => self.block_cache.insert(id, block).await,
+        }
+    }
+
     pub(super) async fn cached(&self, id: &Uuid) -> bool {
         self.block_cache.get(id).await.ok().is_some()
     }
 
-    pub(super) async fn get(&self, id: &Uuid) -> Result<Option<Block>, GetError> {
+    pub(super) async fn evict(&self, id: &Uuid) {
+        self.block_cache.remove(id).await;
+    }
+
+    /// Returns the block with the given id, which is decompressed with the given codec if it
+    /// is read from storage
+    pub(super) async fn get(
+        &self,
+        id: &Uuid,
+        codec: BlockCodec,
+    ) -> Result<Option<Block>, GetError> {
+        self.read_counts.lock().record(*id);
         let block = self.block_cache.get(id).await.ok().flatten();
         match block {
-            Some(block) => Ok(Some(block)),
+            Some(block) => {
+                record_cache_hit();
+                self.cache_metrics.record_hit(self.cache_partition.as_deref());
+                Ok(Some(block))
+            }
             None => async {
-                let key = format!("block/{}", id);
-                let bytes_res = self
-                    .storage
-                    .get(&key)
-                    .instrument(
-                        tracing::trace_span!(parent: Span::current(), "BlockManager storage get", id = id.to_string()),
-                    )
-                    .await;
-                match bytes_res {
-                    Ok(bytes) => {
-                        let deserialization_span = tracing::trace_span!(parent: Span::current(), "BlockManager deserialize block");
-                        let block =
-                            deserialization_span.in_scope(|| Block::from_bytes(&bytes, *id));
-                        match block {
-                            Ok(block) => {
-                                let _guard = self.write_mutex.lock().await;
-                                match self.block_cache.get(id).await {
-                                    Ok(Some(b)) => {
-                                        Ok(Some(b))
-                                    }
-                                    Ok(None) => {
-                                        self.block_cache.insert(*id, block.clone()).await;
-                                        Ok(Some(block))
-                                    }
-                                    Err(e) => {
-                                        tracing::error!("Error getting block from cache {:?}", e);
-                                        Err(GetError::BlockLoadError(e.into()))
-                                    }
-                                }
+                self.cache_metrics.record_miss(self.cache_partition.as_deref());
+                let key = format!("{}{}", BLOCK_KEY_PREFIX, id);
+                match self.load(&key, id, codec).await {
+                    Ok(block) => {
+                        let _guard = self.write_mutex.lock().await;
+                        match self.block_cache.get(id).await {
+                            Ok(Some(b)) => {
+                                Ok(Some(b))
+                            }
+                            Ok(None) => {
+                                self.cache(*id, block.clone()).await;
+                                Ok(Some(block))
                             }
                             Err(e) => {
-                                tracing::error!(
-                                    "Error converting bytes to Block {:?}/{:?}",
-                                    key,
-                                    e
-                                );
-                                Err(GetError::BlockLoadError(e))
+                                tracing::error!("Error getting block from cache {:?}", e);
+                                Err(GetError::BlockLoadError(*id, e.into()))
                             }
                         }
                     }
                     Err(e) => {
-                        tracing::error!("Error converting bytes to Block {:?}", e);
-                        Err(GetError::StorageGetError(e))
+                        tracing::error!("Error loading block {:?}: {}", key, e);
+                        Err(e)
                     }
                 }
             }.instrument(tracing::trace_span!(parent: Span::current(), "BlockManager get cold", block_id = id.to_string())).await
         }
     }
 
+    /// Returns the number of items of the block with the given id. A block that is not cached
+    /// and is stored uncompressed is counted from the header at the start of its object, which
+    /// is read by range instead of fetching the whole block
+    pub(super) async fn count(
+        &self,
+        id: &Uuid,
+        codec: BlockCodec,
+    ) -> Result<Option<usize>, GetError> {
+        if let Some(block) = self.block_cache.get(id).await.ok().flatten() {
+            return Ok(Some(block.len()));
+        }
+        if codec == BlockCodec::None {
+            let key = format!("{}{}", BLOCK_KEY_PREFIX, id);
+            let prefix = self
+                .storage
+                .get_range(&key, 0, BLOCK_HEADER_PREFIX_BYTES)
+                .await
+                .map_err(|e| GetError::StorageGetError(*id, e))?;
+            record_bytes_read(prefix.len());
+            match Block::len_from_bytes_prefix(&prefix) {
+                Ok(Some(len)) => return Ok(Some(len)),
+                // The header of the block is longer than the prefix
+                Ok(None) => {}
+                Err(e) => return Err(GetError::BlockLoadError(*id, e)),
+            }
+        }
+        Ok(self.get(id, codec).await?.map(|block| block.len()))
+    }
+
+    // The blocks that are read the most, up to the limit, out of the given blocks
+    fn hottest(&self, ids: impl IntoIterator<Item = Uuid>, limit: usize) -> Vec<Uuid> {
+        let mut reads = {
+            let read_counts = self.read_counts.lock();
+            ids.into_iter()
+                .filter_map(|id| Some((*read_counts.counts.get(&id)?, id)))
+                .collect::<Vec<_>>()
+        };
+        reads.sort_unstable_by(|a, b| b.cmp(a));
+        reads.into_iter().take(limit).map(|(_, id)| id).collect()
+    }
+
+    // Reads the block from the disk tier if it holds it, and from storage otherwise. A block in
+    // the disk tier that cannot be read, e.g. a file that a crash truncated, is removed from
+    // the disk tier and fetched from storage instead
+    async fn load(&self, key: &str, id: &Uuid, codec: BlockCodec) -> Result<Block, GetError> {
+        if let Some(disk_tier) = &self.disk_tier {
+            if let Some(bytes) = disk_tier.get(id).await {
+                match self.open(key, bytes, *id, codec).await {
+                    Ok(block) => return Ok(block),
+                    Err(e) => {
+                        tracing::warn!("Error loading block {} from the disk tier: {}", id, e);
+                        disk_tier.remove(id).await;
+                    }
+                }
+            }
+        }
+        let bytes = self
+            .get_from_storage(key, id)
+            .await
+            .map_err(|e| GetError::StorageGetError(*id, e))?;
+        self.open(key, bytes, *id, codec).await
+    }
+
+    // Decrypts the bytes of the block as they are stored under the key, and decodes them
+    async fn open(
+        &self,
+        key: &str,
+        bytes: Arc<Vec<u8>>,
+        id: Uuid,
+        codec: BlockCodec,
+    ) -> Result<Block, GetError> {
+        let bytes = self
+            .storage
+            .decrypt_stored(key, bytes)
+            .await
+            .map_err(|e| GetError::StorageGetError(id, e))?;
+        self.decode(bytes, id, codec)
+            .await
+            .map_err(|e| GetError::BlockLoadError(id, e))
+    }
+
+    // Fetches the bytes of the block from storage as they are stored, and keeps them in the
+    // disk tier if there is one, so that the disk tier holds no plaintext of an encrypted block
+    async fn get_from_storage(
+        &self,
+        key: &str,
+        id: &Uuid,
+    ) -> Result<Arc<Vec<u8>>, chroma_storage::GetError> {
+        let bytes = self
+            .storage
+            .get_stored(key)
+            .instrument(
+                tracing::trace_span!(parent: Span::current(), "BlockManager storage get", id = id.to_string()),
+            )
+            .await?;
+        record_bytes_read(bytes.len());
+        if let Some(disk_tier) = &self.disk_tier {
+            disk_tier.put(id, &bytes).await;
+        }
+        Ok(bytes)
+    }
+
+    // Decompresses and decodes the block inline if it is small, and with the decode executor
+    // otherwise, so that decoding large blocks does not stall the other tasks of the runtime
+    async fn decode(
+        &self,
+        bytes: Arc<Vec<u8>>,
+        id: Uuid,
+        codec: BlockCodec,
+    ) -> Result<Block, BlockLoadError> {
+        let deserialization_span =
+            tracing::trace_span!(parent: Span::current(), "BlockManager deserialize block");
+        let offload = bytes.len() >= self.decode_offload_threshold_bytes;
+        let decode = move || {
+            deserialization_span.in_scope(|| Block::from_bytes(&codec.decompress(&bytes)?, id))
+        };
+        let executor = match self.decode_executor.get() {
+            Some(executor) if offload => executor,
+            _ => return decode(),
+        };
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        executor
+            .run(Box::new(move || {
+                let _ = tx.send(decode());
+            }))
+            .await
+            .map_err(BlockLoadError::DecodeTaskError)?;
+        rx.await.map_err(|_| BlockLoadError::DecodeTaskDropped)?
+    }
+
+    /// The codec that the block is compressed with when it is flushed
+    pub(super) fn codec(&self, block: &Block) -> BlockCodec {
+        self.compression.codec(block)
+    }
+
+    /// The bloom filter of the keys of the block that is committed, if bloom filters are enabled
+    pub(super) fn bloom_filter<'me, K: ArrowReadableKey<'me>>(
+        &self,
+        block: &'me Block,
+    ) -> Option<BloomFilter> {
+        block.bloom_filter::<K>(self.bloom_filter_bits_per_key?)
+    }
+
+    /// The number of blocks that a reader fetches at once
+    pub(super) fn block_fetch_concurrency(&self) -> usize {
+        self.block_fetch_concurrency
+    }
+
     pub(super) async fn flush(&self, block: &Block) -> Result<(), Box<dyn ChromaError>> {
         let bytes = match block.to_bytes() {
             Ok(bytes) => bytes,
@@ -334,9 +849,22 @@ impl BlockManager {
                 return Err(Box::new(e));
             }
         };
-        let key = format!("block/{}", block.id);
+        let bytes = match self
+            .codec(block)
+            .compress(bytes, self.compression.zstd_level)
+        {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                tracing::error!("Failed to compress block {}: {}", block.id, e);
+                return Err(Box::new(BlockFlushError::CompressionError(e)));
+            }
+        };
+        let key = format!("{}{}", BLOCK_KEY_PREFIX, block.id);
         let block_bytes_len = bytes.len();
-        let res = self.storage.put_bytes(&key, bytes).await;
+        let res = self
+            .storage
+            .put_bytes_with_options(&key, bytes, &self.put_options)
+            .await;
         match res {
             Ok(_) => {
                 tracing::info!(
@@ -356,18 +884,41 @@ impl BlockManager {
     pub(super) fn max_block_size_bytes(&self) -> usize {
         self.max_block_size_bytes
     }
+
+    async fn list(&self) -> Result<Vec<(Uuid, SystemTime)>, ListError> {
+        let blocks = self.storage.list(BLOCK_KEY_PREFIX).await?;
+        Ok(parse_object_ids(BLOCK_KEY_PREFIX, blocks))
+    }
+
+    async fn delete(&self, id: &Uuid) -> Result<(), DeleteError> {
+        self.block_cache.remove(id).await;
+        if let Some(disk_tier) = &self.disk_tier {
+            disk_tier.remove(id).await;
+        }
+        self.storage
+            .delete(&format!("{}{}", BLOCK_KEY_PREFIX, id))
+            .await
+    }
+
+    /// The size under which the blocks of a blockfile are merged with their neighbours on commit
+    pub(super) fn min_block_size_bytes(&self) -> Option<usize> {
+        self.min_block_size_bytes
+    }
 }
 
 #[derive(Error, Debug)]
 pub enum BlockFlushError {
     #[error("Not found")]
     NotFound,
+    #[error("Error compressing block: {0}")]
+    CompressionError(#[from] std::io::Error),
 }
 
 impl ChromaError for BlockFlushError {
     fn code(&self) -> ErrorCodes {
         match self {
             BlockFlushError::NotFound => ErrorCodes::NotFound,
+            BlockFlushError::CompressionError(_) => ErrorCodes::Internal,
         }
     }
 }
@@ -405,13 +956,29 @@ impl ChromaError for RootManagerError {
 #[derive(Clone)]
 pub(super) struct RootManager {
     cache: Arc<dyn PersistentCache<Uuid, RootReader>>,
+    cache_metrics: CacheMetrics,
     storage: Storage,
+    put_options: PutOptions,
 }
 
 impl RootManager {
     pub fn new(storage: Storage, cache: Box<dyn PersistentCache<Uuid, RootReader>>) -> Self {
         let cache: Arc<dyn PersistentCache<Uuid, RootReader>> = cache.into();
-        Self { cache, storage }
+        Self {
+            cache,
+            cache_metrics: CacheMetrics::new("root"),
+            storage,
+            put_options: PutOptions::default(),
+        }
+    }
+
+    /// Returns the root only if it is in the cache
+    pub(super) async fn cached(&self, id: &Uuid) -> Option<RootReader> {
+        self.cache.get(id).await.ok().flatten()
+    }
+
+    pub(super) async fn evict(&self, id: &Uuid) {
+        self.cache.remove(id).await;
     }
 
     pub async fn get<'new, K: ArrowReadableKey<'new> + 'new>(
@@ -420,17 +987,24 @@ impl RootManager {
     ) -> Result<Option<RootReader>, RootManagerError> {
         let index = self.cache.get(id).await.ok().flatten();
         match index {
-            Some(index) => Ok(Some(index)),
+            Some(index) => {
+                self.cache_metrics.record_hit(None);
+                Ok(Some(index))
+            }
             None => {
+                self.cache_metrics.record_miss(None);
                 tracing::info!("Cache miss - fetching root from storage");
                 // TODO(hammadb): For legacy and temporary development purposes, we are reading the file
                 // from a fixed location. The path is sparse_index/ for legacy reasons.
                 // This will be replaced with a full prefix-based storage shortly
-                let key = format!("sparse_index/{}", id);
+                let key = format!("{}{}", ROOT_KEY_PREFIX, id);
                 tracing::debug!("Reading root from storage with key: {}", key);
                 match self.storage.get(&key).await {
                     Ok(bytes) => match RootReader::from_bytes::<K>(&bytes, *id) {
-                        Ok(root) => Ok(Some(root)),
+                        Ok(root) => {
+                            self.cache.insert(*id, root.clone()).await;
+                            Ok(Some(root))
+                        }
                         Err(e) => {
                             tracing::error!("Error turning bytes into root: {}", e);
                             Err(RootManagerError::FromBytesError(e))
@@ -456,8 +1030,11 @@ impl RootManager {
                 return Err(Box::new(e));
             }
         };
-        let key = format!("sparse_index/{}", root.id);
-        let res = self.storage.put_bytes(&key, bytes).await;
+        let key = format!("{}{}", ROOT_KEY_PREFIX, root.id);
+        let res = self
+            .storage
+            .put_bytes_with_options(&key, bytes, &self.put_options)
+            .await;
         match res {
             Ok(_) => {
                 tracing::info!("Root written to storage");
@@ -470,6 +1047,33 @@ impl RootManager {
         }
     }
 
+    async fn list(&self) -> Result<Vec<(Uuid, SystemTime)>, ListError> {
+        let roots = self.storage.list(ROOT_KEY_PREFIX).await?;
+        Ok(parse_object_ids(ROOT_KEY_PREFIX, roots))
+    }
+
+    // Reads the ids of the blocks of the root, or None if there is no such root
+    async fn block_ids(&self, id: &Uuid) -> Result<Option<Vec<Uuid>>, RootManagerError> {
+        if let Some(root) = self.cached(id).await {
+            return Ok(Some(
+                root.sparse_index.get_all_block_ids().into_iter().collect(),
+            ));
+        }
+        let key = format!("{}{}", ROOT_KEY_PREFIX, id);
+        match self.storage.get(&key).await {
+            Ok(bytes) => Ok(Some(RootReader::block_ids_from_bytes(&bytes, *id)?)),
+            Err(chroma_storage::GetError::NoSuchKey(_)) => Ok(None),
+            Err(e) => Err(RootManagerError::StorageGetError(e)),
+        }
+    }
+
+    async fn delete(&self, id: &Uuid) -> Result<(), DeleteError> {
+        self.cache.remove(id).await;
+        self.storage
+            .delete(&format!("{}{}", ROOT_KEY_PREFIX, id))
+            .await
+    }
+
     pub async fn fork<'key, K: ArrowWriteableKey + 'key>(
         &self,
         old_id: &Uuid,
@@ -1,13 +1,17 @@
 use super::{
     block::{Block, BlockToBytesError},
-    sparse_index::{SparseIndexReader, SparseIndexValue, SparseIndexWriter, SparseIndexWriterData},
+    bloom_filter::BloomFilter,
+    compression::BlockCodec,
+    sparse_index::{
+        SparseIndexReader, SparseIndexValue, SparseIndexWriter, SparseIndexWriterData, ZoneMap,
+    },
     types::{ArrowReadableKey, ArrowWriteableKey},
 };
 use crate::{arrow::sparse_index::SparseIndexDelimiter, key::CompositeKey};
 use arrow::{
     array::{
         Array, BinaryArray, BinaryBuilder, RecordBatch, StringArray, StringBuilder, UInt32Array,
-        UInt32Builder,
+        UInt32Builder, UInt8Array, UInt8Builder,
     },
     datatypes::{DataType, Field, Schema},
 };
@@ -21,7 +25,11 @@ use std::{
 use thiserror::Error;
 use uuid::Uuid;
 
-pub(super) const CURRENT_VERSION: Version = Version::V1_1;
+pub(super) const CURRENT_VERSION: Version = Version::V1_4;
+
+/// The prefix of the placeholder keys that are written for the start of the sparse index, and
+/// for the zone map of a block whose keys are not known
+const START_PREFIX: &str = "START";
 
 // ================
 // Version
@@ -31,6 +39,9 @@ pub(super) const CURRENT_VERSION: Version = Version::V1_1;
 pub(super) enum Version {
     V1 = 1,
     V1_1 = 2,
+    V1_2 = 3,
+    V1_3 = 4,
+    V1_4 = 5,
 }
 
 impl Display for Version {
@@ -38,6 +49,9 @@ impl Display for Version {
         match self {
             Version::V1 => write!(f, "v1"),
             Version::V1_1 => write!(f, "v1.1"),
+            Version::V1_2 => write!(f, "v1.2"),
+            Version::V1_3 => write!(f, "v1.3"),
+            Version::V1_4 => write!(f, "v1.4"),
         }
     }
 }
@@ -60,6 +74,9 @@ impl TryFrom<&str> for Version {
         match s {
             "v1" => Ok(Version::V1),
             "v1.1" => Ok(Version::V1_1),
+            "v1.2" => Ok(Version::V1_2),
+            "v1.3" => Ok(Version::V1_3),
+            "v1.4" => Ok(Version::V1_4),
             _ => Err(VersionError::UnknownVersion(s.to_string())),
         }
     }
@@ -124,6 +141,76 @@ impl RootWriter {
         )
     }
 
+    fn codecs_as_arrow(
+        &self,
+        sparse_index_data: &SparseIndexWriterData,
+    ) -> (Arc<dyn Array>, Field) {
+        let mut codec_builder = UInt8Builder::new();
+        for (key, _) in sparse_index_data.forward.iter() {
+            let codec = sparse_index_data
+                .codecs
+                .get(key)
+                .copied()
+                .unwrap_or_default();
+            codec_builder.append_value(codec.as_u8());
+        }
+        (
+            Arc::new(codec_builder.finish()),
+            Field::new("codec", DataType::UInt8, false),
+        )
+    }
+
+    fn bloom_filters_as_arrow(
+        &self,
+        sparse_index_data: &SparseIndexWriterData,
+    ) -> (Arc<dyn Array>, Field) {
+        let mut bloom_filter_builder = BinaryBuilder::new();
+        for (key, _) in sparse_index_data.forward.iter() {
+            match sparse_index_data.bloom_filters.get(key) {
+                Some(bloom_filter) => bloom_filter_builder.append_value(bloom_filter.to_bytes()),
+                None => bloom_filter_builder.append_null(),
+            }
+        }
+        (
+            Arc::new(bloom_filter_builder.finish()),
+            Field::new("bloom_filter", DataType::Binary, true),
+        )
+    }
+
+    fn zone_maps_as_arrow<K: ArrowWriteableKey>(
+        &self,
+        sparse_index_data: &SparseIndexWriterData,
+    ) -> Vec<(Field, Arc<dyn Array>)> {
+        let placeholder_key = CompositeKey {
+            prefix: START_PREFIX.to_string(),
+            key: K::default().into(),
+        };
+        let mut min_key_builder = K::get_arrow_builder(sparse_index_data.forward.len(), 0, 0);
+        let mut max_key_builder = K::get_arrow_builder(sparse_index_data.forward.len(), 0, 0);
+        for (key, _) in sparse_index_data.forward.iter() {
+            match sparse_index_data.zone_maps.get(key) {
+                Some(zone_map) => {
+                    min_key_builder.add_key(zone_map.min_key.clone());
+                    max_key_builder.add_key(zone_map.max_key.clone());
+                }
+                None => {
+                    min_key_builder.add_key(placeholder_key.clone());
+                    max_key_builder.add_key(placeholder_key.clone());
+                }
+            }
+        }
+        let (min_prefix_field, min_prefix_arr, min_key_field, min_key_arr) =
+            min_key_builder.as_arrow();
+        let (max_prefix_field, max_prefix_arr, max_key_field, max_key_arr) =
+            max_key_builder.as_arrow();
+        vec![
+            (min_prefix_field.with_name("min_prefix"), min_prefix_arr),
+            (min_key_field.with_name("min_key"), min_key_arr),
+            (max_prefix_field.with_name("max_prefix"), max_prefix_arr),
+            (max_key_field.with_name("max_key"), max_key_arr),
+        ]
+    }
+
     pub(super) fn to_bytes<K: ArrowWriteableKey>(&self) -> Result<Vec<u8>, Box<dyn ChromaError>> {
         // Serialize the sparse index as an arrow record batch
         // TODO(hammadb): Note that this should ideally use the Block API to serialize the sparse
@@ -148,7 +235,7 @@ impl RootWriter {
         for (key, _) in sparse_index_data.forward.iter() {
             match key {
                 SparseIndexDelimiter::Start => key_builder.add_key(CompositeKey {
-                    prefix: "START".to_string(),
+                    prefix: START_PREFIX.to_string(),
                     key: K::default().into(),
                 }),
                 SparseIndexDelimiter::Key(k) => {
@@ -178,6 +265,29 @@ impl RootWriter {
             data_arrays.push(built_counts);
         }
 
+        // MIGRATION(10/16/2026) -> Only RootWriter >= V1_2 will write the zone map fields
+        if self.version >= Version::V1_2 {
+            for (field, arr) in self.zone_maps_as_arrow::<K>(&sparse_index_data) {
+                schema_fields.push(field);
+                data_arrays.push(arr);
+            }
+        }
+
+        // MIGRATION(10/16/2026) -> Only RootWriter >= V1_3 will write the codec field
+        if self.version >= Version::V1_3 {
+            let (built_codecs, codec_field) = self.codecs_as_arrow(&sparse_index_data);
+            schema_fields.push(codec_field);
+            data_arrays.push(built_codecs);
+        }
+
+        // MIGRATION(10/16/2026) -> Only RootWriter >= V1_4 will write the bloom filter field
+        if self.version >= Version::V1_4 {
+            let (built_bloom_filters, bloom_filter_field) =
+                self.bloom_filters_as_arrow(&sparse_index_data);
+            schema_fields.push(bloom_filter_field);
+            data_arrays.push(built_bloom_filters);
+        }
+
         let metadata = HashMap::from_iter(vec![
             ("version".to_string(), self.version.to_string()),
             ("id".to_string(), self.id.to_string()),
@@ -234,6 +344,10 @@ impl chroma_cache::Weighted for RootReader {
     fn weight(&self) -> usize {
         1
     }
+
+    fn size_bytes(&self) -> usize {
+        self.sparse_index.size_bytes()
+    }
 }
 
 #[derive(Error, Debug)]
@@ -252,6 +366,10 @@ pub(super) enum FromBytesError {
     IdMismatch,
     #[error(transparent)]
     VersionError(#[from] VersionError),
+    #[error("Unknown block codec: {0}")]
+    UnknownCodec(u8),
+    #[error("Invalid bloom filter of block {0}")]
+    InvalidBloomFilter(Uuid),
 }
 
 impl ChromaError for FromBytesError {
@@ -264,15 +382,15 @@ impl ChromaError for FromBytesError {
             FromBytesError::NoDataError => chroma_error::ErrorCodes::Internal,
             FromBytesError::IdMismatch => chroma_error::ErrorCodes::InvalidArgument,
             FromBytesError::VersionError(e) => e.code(),
+            FromBytesError::UnknownCodec(_) => chroma_error::ErrorCodes::InvalidArgument,
+            FromBytesError::InvalidBloomFilter(_) => chroma_error::ErrorCodes::InvalidArgument,
         }
     }
 }
 
 impl RootReader {
-    pub(super) fn from_bytes<'data, K: ArrowReadableKey<'data>>(
-        bytes: &[u8],
-        id: Uuid,
-    ) -> Result<Self, FromBytesError> {
+    // Reads the record batch of the root, and the version that it is written with
+    fn read_record_batch(bytes: &[u8], id: Uuid) -> Result<(RecordBatch, Version), FromBytesError> {
         let mut cursor = std::io::Cursor::new(bytes);
         let arrow_reader = arrow::ipc::reader::FileReader::try_new(&mut cursor, None);
 
@@ -306,16 +424,11 @@ impl RootReader {
             return Err(FromBytesError::IdMismatch);
         }
 
-        let prefix_arr = record_batch
-            .column(0)
-            .as_any()
-            .downcast_ref::<StringArray>()
-            .expect("Prefix array to be a StringArray");
-        // Use unsafe to promote the liftimes using unsafe, we know record batch lives as long as it needs to.
-        // It only needs to live as long as the sparse index is being constructed.
-        // The sparse index copies the data so it can live as long as it needs to independently
-        let record_batch: &'data RecordBatch = unsafe { std::mem::transmute(&record_batch) };
-        let key_arr = record_batch.column(1);
+        Ok((record_batch, version))
+    }
+
+    // Reads the ids of the blocks of the root, which do not depend on the type of its keys
+    fn read_block_ids(record_batch: &RecordBatch, version: Version) -> Vec<Uuid> {
         let mut ids: Vec<uuid::Uuid> = Vec::new();
         // Versions after V1 store uuid as bytes
         if version == Version::V1 {
@@ -341,6 +454,36 @@ impl RootReader {
                 ids.push(id);
             }
         }
+        ids
+    }
+
+    /// Reads the ids of the blocks of the root without decoding its keys, so that the blocks
+    /// that a root references can be found without knowing the type of its keys
+    pub(super) fn block_ids_from_bytes(
+        bytes: &[u8],
+        id: Uuid,
+    ) -> Result<Vec<Uuid>, FromBytesError> {
+        let (record_batch, version) = Self::read_record_batch(bytes, id)?;
+        Ok(Self::read_block_ids(&record_batch, version))
+    }
+
+    pub(super) fn from_bytes<'data, K: ArrowReadableKey<'data>>(
+        bytes: &[u8],
+        id: Uuid,
+    ) -> Result<Self, FromBytesError> {
+        let (record_batch, version) = Self::read_record_batch(bytes, id)?;
+
+        let prefix_arr = record_batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .expect("Prefix array to be a StringArray");
+        // Use unsafe to promote the liftimes using unsafe, we know record batch lives as long as it needs to.
+        // It only needs to live as long as the sparse index is being constructed.
+        // The sparse index copies the data so it can live as long as it needs to independently
+        let record_batch: &'data RecordBatch = unsafe { std::mem::transmute(&record_batch) };
+        let key_arr = record_batch.column(1);
+        let ids = Self::read_block_ids(record_batch, version);
         // Version 1.1 is the first version to have a count column
         let mut counts = None;
         if version >= Version::V1_1 {
@@ -351,6 +494,52 @@ impl RootReader {
                 .expect("Count array to be a UInt32Array");
             counts = Some(count_arr);
         }
+        // Version 1.2 is the first version to have the zone map columns
+        let mut zone_map_arrs = None;
+        if version >= Version::V1_2 {
+            let min_prefix_arr = record_batch
+                .column(4)
+                .as_any()
+                .downcast_ref::<StringArray>()
+                .expect("Min prefix array to be a StringArray");
+            let max_prefix_arr = record_batch
+                .column(6)
+                .as_any()
+                .downcast_ref::<StringArray>()
+                .expect("Max prefix array to be a StringArray");
+            zone_map_arrs = Some((
+                min_prefix_arr,
+                record_batch.column(5),
+                max_prefix_arr,
+                record_batch.column(7),
+            ));
+        }
+
+        // Version 1.3 is the first version to have the codec column, the blocks of the earlier
+        // versions are uncompressed
+        let mut codec_arr = None;
+        if version >= Version::V1_3 {
+            codec_arr = Some(
+                record_batch
+                    .column(8)
+                    .as_any()
+                    .downcast_ref::<UInt8Array>()
+                    .expect("Codec array to be a UInt8Array"),
+            );
+        }
+
+        // Version 1.4 is the first version to have the bloom filter column, and the blocks
+        // without a bloom filter have a null in it
+        let mut bloom_filter_arr = None;
+        if version >= Version::V1_4 {
+            bloom_filter_arr = Some(
+                record_batch
+                    .column(9)
+                    .as_any()
+                    .downcast_ref::<BinaryArray>()
+                    .expect("Bloom filter array to be a BinaryArray"),
+            );
+        }
 
         let mut forward = BTreeMap::new();
         for (i, block_id) in ids.iter().enumerate() {
@@ -362,17 +551,46 @@ impl RootReader {
                 None => 0,
             };
 
+            let zone_map = match zone_map_arrs {
+                Some((min_prefix_arr, _, _, _)) if min_prefix_arr.value(i) == START_PREFIX => None,
+                Some((min_prefix_arr, min_key_arr, max_prefix_arr, max_key_arr)) => Some(ZoneMap {
+                    min_key: CompositeKey::new(
+                        min_prefix_arr.value(i).to_string(),
+                        K::get(min_key_arr, i),
+                    ),
+                    max_key: CompositeKey::new(
+                        max_prefix_arr.value(i).to_string(),
+                        K::get(max_key_arr, i),
+                    ),
+                }),
+                None => None,
+            };
+
+            let codec = match codec_arr {
+                Some(codec_arr) => BlockCodec::from_u8(codec_arr.value(i))
+                    .ok_or(FromBytesError::UnknownCodec(codec_arr.value(i)))?,
+                None => BlockCodec::None,
+            };
+
+            let bloom_filter = match bloom_filter_arr {
+                Some(bloom_filter_arr) if bloom_filter_arr.is_valid(i) => Some(
+                    BloomFilter::from_bytes(bloom_filter_arr.value(i))
+                        .ok_or(FromBytesError::InvalidBloomFilter(*block_id))?,
+                ),
+                _ => None,
+            };
+
             match prefix {
-                "START" => {
+                START_PREFIX => {
                     forward.insert(
                         SparseIndexDelimiter::Start,
-                        SparseIndexValue::new(*block_id, count),
+                        SparseIndexValue::new(*block_id, count, zone_map, codec, bloom_filter),
                     );
                 }
                 _ => {
                     forward.insert(
                         SparseIndexDelimiter::Key(CompositeKey::new(prefix.to_string(), key)),
-                        SparseIndexValue::new(*block_id, count),
+                        SparseIndexValue::new(*block_id, count, zone_map, codec, bloom_filter),
                     );
                 }
             }
@@ -443,6 +661,38 @@ mod test {
             .set_count(block_ids[3], 4)
             .expect("Set count should succeed");
 
+        // The first block is empty, so it has no zone map
+        for (block_id, (min_key, max_key)) in
+            block_ids[1..]
+                .iter()
+                .zip([("a", "aa"), ("b", "bz"), ("c", "c")])
+        {
+            root_writer
+                .sparse_index
+                .set_zone_map(
+                    *block_id,
+                    Some(ZoneMap {
+                        min_key: CompositeKey::new("prefix".to_string(), min_key),
+                        max_key: CompositeKey::new("prefix".to_string(), max_key),
+                    }),
+                )
+                .expect("Set zone map should succeed");
+        }
+        root_writer
+            .sparse_index
+            .set_codec(block_ids[2], BlockCodec::Zstd)
+            .expect("Set codec should succeed");
+        root_writer
+            .sparse_index
+            .set_bloom_filter(
+                block_ids[3],
+                Some(BloomFilter::new(
+                    &[CompositeKey::new("prefix".to_string(), "c")],
+                    10,
+                )),
+            )
+            .expect("Set bloom filter should succeed");
+
         let bytes = root_writer
             .to_bytes::<&str>()
             .expect("To be able to serialize");
@@ -464,7 +714,7 @@ mod test {
         }
 
         // Check that counts are the same
-        let writer_data = &root_writer.sparse_index.data.lock();
+        let writer_data = root_writer.sparse_index.data.lock();
         for (key, _) in writer_data.forward.iter() {
             assert_eq!(
                 root_reader
@@ -478,6 +728,52 @@ mod test {
             );
         }
 
+        // Check that zone maps are the same
+        for (key, _) in writer_data.forward.iter() {
+            assert_eq!(
+                root_reader
+                    .sparse_index
+                    .data
+                    .forward
+                    .get(key)
+                    .unwrap()
+                    .zone_map,
+                writer_data.zone_maps.get(key).cloned()
+            );
+        }
+
+        // Check that bloom filters are the same
+        for (key, _) in writer_data.forward.iter() {
+            assert_eq!(
+                root_reader
+                    .sparse_index
+                    .data
+                    .forward
+                    .get(key)
+                    .unwrap()
+                    .bloom_filter,
+                writer_data.bloom_filters.get(key).cloned()
+            );
+        }
+
+        drop(writer_data);
+
+        // Check that codecs are the same, where the blocks without a codec are uncompressed
+        for block_id in block_ids.iter() {
+            assert_eq!(
+                root_reader.sparse_index.codec(block_id),
+                root_writer.sparse_index.codec(block_id)
+            );
+        }
+        assert_eq!(
+            root_reader.sparse_index.codec(&block_ids[2]),
+            BlockCodec::Zstd
+        );
+        assert_eq!(
+            root_reader.sparse_index.codec(&block_ids[1]),
+            BlockCodec::None
+        );
+
         assert_eq!(root_writer.version, root_reader.version);
         assert_eq!(root_writer.id, root_reader.id);
     }
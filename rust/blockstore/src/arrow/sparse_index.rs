@@ -1,12 +1,14 @@
+use super::bloom_filter::BloomFilter;
+use super::compression::BlockCodec;
 use super::types::ArrowReadableKey;
 use crate::key::CompositeKey;
 use chroma_error::ChromaError;
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::Debug;
 use std::ops::{Bound, RangeBounds};
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use thiserror::Error;
 use uuid::Uuid;
 
@@ -72,6 +74,15 @@ pub(super) struct SparseIndexWriterData {
     // This is not intended updated incrementally, and is only populated
     // at commit time of the blockfile.
     pub(super) counts: BTreeMap<SparseIndexDelimiter, u32>,
+    // The smallest and largest keys in each block in the sparse index.
+    // Like the counts, these are only populated at commit time of the blockfile.
+    pub(super) zone_maps: BTreeMap<SparseIndexDelimiter, ZoneMap>,
+    // The codecs of the compressed blocks in the sparse index, the other blocks are
+    // uncompressed. Like the counts, these are only populated at commit time of the blockfile.
+    pub(super) codecs: BTreeMap<SparseIndexDelimiter, BlockCodec>,
+    // The bloom filters of the keys in each block in the sparse index.
+    // Like the counts, these are only populated at commit time of the blockfile.
+    pub(super) bloom_filters: BTreeMap<SparseIndexDelimiter, BloomFilter>,
 }
 
 impl SparseIndexWriterData {
@@ -94,6 +105,69 @@ impl ChromaError for AddError {
     }
 }
 
+/// The smallest and largest keys of a block. The start key of a block in the sparse index is
+/// only a lower bound of its keys, and its end is only known from the start key of the next
+/// block, so the zone map lets a range query skip the blocks whose keys are all outside of it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(super) struct ZoneMap {
+    pub(super) min_key: CompositeKey,
+    pub(super) max_key: CompositeKey,
+}
+
+impl ZoneMap {
+    /// Returns whether the block may contain a key with a prefix in the prefix range and a key
+    /// in the key range
+    fn may_contain<'prefix, 'referred_data, K, PrefixRange, KeyRange>(
+        &self,
+        prefix_range: &PrefixRange,
+        key_range: &KeyRange,
+    ) -> bool
+    where
+        K: ArrowReadableKey<'referred_data>,
+        PrefixRange: RangeBounds<&'prefix str>,
+        KeyRange: RangeBounds<K>,
+    {
+        let prefix_after_block = match prefix_range.start_bound() {
+            Bound::Included(prefix_start) => *prefix_start > self.max_key.prefix.as_str(),
+            Bound::Excluded(prefix_start) => *prefix_start >= self.max_key.prefix.as_str(),
+            Bound::Unbounded => false,
+        };
+        let prefix_before_block = match prefix_range.end_bound() {
+            Bound::Included(prefix_end) => *prefix_end < self.min_key.prefix.as_str(),
+            Bound::Excluded(prefix_end) => *prefix_end <= self.min_key.prefix.as_str(),
+            Bound::Unbounded => false,
+        };
+        if prefix_after_block || prefix_before_block {
+            return false;
+        }
+
+        // If the range starts at the prefix of the largest key, the keys of the block in the
+        // range have that prefix and are at most the largest key
+        let key_after_block = match prefix_range.start_bound() {
+            Bound::Included(prefix_start) if *prefix_start == self.max_key.prefix.as_str() => {
+                match key_range.start_bound() {
+                    Bound::Included(key_start) => key_start.clone().into() > self.max_key.key,
+                    Bound::Excluded(key_start) => key_start.clone().into() >= self.max_key.key,
+                    Bound::Unbounded => false,
+                }
+            }
+            _ => false,
+        };
+        // Likewise if the range ends at the prefix of the smallest key
+        let key_before_block = match prefix_range.end_bound() {
+            Bound::Included(prefix_end) if *prefix_end == self.min_key.prefix.as_str() => {
+                match key_range.end_bound() {
+                    Bound::Included(key_end) => key_end.clone().into() < self.min_key.key,
+                    Bound::Excluded(key_end) => key_end.clone().into() <= self.min_key.key,
+                    Bound::Unbounded => false,
+                }
+            }
+            _ => false,
+        };
+        !(key_after_block || key_before_block)
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum SetCountError {
     #[error("Block id does not exist in the sparse index")]
@@ -113,6 +187,9 @@ impl SparseIndexWriter {
         let mut forward = BTreeMap::new();
         let mut reverse = HashMap::new();
         let counts = BTreeMap::new();
+        let zone_maps = BTreeMap::new();
+        let codecs = BTreeMap::new();
+        let bloom_filters = BTreeMap::new();
 
         forward.insert(SparseIndexDelimiter::Start, initial_block_id);
         reverse.insert(initial_block_id, SparseIndexDelimiter::Start);
@@ -121,6 +198,9 @@ impl SparseIndexWriter {
             forward,
             reverse,
             counts,
+            zone_maps,
+            codecs,
+            bloom_filters,
         };
 
         Self {
@@ -157,7 +237,16 @@ impl SparseIndexWriter {
                 .counts
                 .remove(&old_start_key)
                 .expect("Invariant Violation, these maps are always in sync");
-            data.counts.insert(old_start_key, old_count);
+            data.counts.insert(old_start_key.clone(), old_count);
+            if let Some(old_zone_map) = data.zone_maps.remove(&old_start_key) {
+                data.zone_maps.insert(old_start_key.clone(), old_zone_map);
+            }
+            if let Some(old_bloom_filter) = data.bloom_filters.remove(&old_start_key) {
+                data.bloom_filters
+                    .insert(old_start_key.clone(), old_bloom_filter);
+            }
+            // The new block is not flushed yet, so it has no codec until it is committed
+            data.codecs.remove(&old_start_key);
         }
     }
 
@@ -179,6 +268,84 @@ impl SparseIndexWriter {
         }
     }
 
+    /// Set the smallest and largest keys of a block in the sparse index.
+    /// Like the count, this is only populated at commit time of the blockfile.
+    /// # Arguments
+    /// * `block_id` - The block id to set the zone map for
+    /// * `zone_map` - The smallest and largest keys in the block, or `None` if it is empty
+    pub(super) fn set_zone_map(
+        &self,
+        block_id: Uuid,
+        zone_map: Option<ZoneMap>,
+    ) -> Result<(), SetCountError> {
+        let mut data = self.data.lock();
+        let start_key = data.reverse.get(&block_id);
+        match start_key.cloned() {
+            Some(start_key) => {
+                match zone_map {
+                    Some(zone_map) => data.zone_maps.insert(start_key, zone_map),
+                    None => data.zone_maps.remove(&start_key),
+                };
+                Ok(())
+            }
+            None => Err(SetCountError::BlockIdDoesNotExist),
+        }
+    }
+
+    /// Set the codec that a block is compressed with in storage.
+    /// Like the count, this is only populated at commit time of the blockfile.
+    /// # Arguments
+    /// * `block_id` - The block id to set the codec for
+    /// * `codec` - The codec of the block
+    pub(super) fn set_codec(&self, block_id: Uuid, codec: BlockCodec) -> Result<(), SetCountError> {
+        let mut data = self.data.lock();
+        let start_key = data.reverse.get(&block_id);
+        match start_key.cloned() {
+            Some(start_key) => {
+                match codec {
+                    BlockCodec::None => data.codecs.remove(&start_key),
+                    codec => data.codecs.insert(start_key, codec),
+                };
+                Ok(())
+            }
+            None => Err(SetCountError::BlockIdDoesNotExist),
+        }
+    }
+
+    /// Set the bloom filter of the keys of a block in the sparse index.
+    /// Like the count, this is only populated at commit time of the blockfile.
+    /// # Arguments
+    /// * `block_id` - The block id to set the bloom filter for
+    /// * `bloom_filter` - The bloom filter of the keys in the block, or `None` if it has none
+    pub(super) fn set_bloom_filter(
+        &self,
+        block_id: Uuid,
+        bloom_filter: Option<BloomFilter>,
+    ) -> Result<(), SetCountError> {
+        let mut data = self.data.lock();
+        let start_key = data.reverse.get(&block_id);
+        match start_key.cloned() {
+            Some(start_key) => {
+                match bloom_filter {
+                    Some(bloom_filter) => data.bloom_filters.insert(start_key, bloom_filter),
+                    None => data.bloom_filters.remove(&start_key),
+                };
+                Ok(())
+            }
+            None => Err(SetCountError::BlockIdDoesNotExist),
+        }
+    }
+
+    /// Get the codec that a block is compressed with in storage
+    pub(super) fn codec(&self, block_id: &Uuid) -> BlockCodec {
+        let data = self.data.lock();
+        data.reverse
+            .get(block_id)
+            .and_then(|start_key| data.codecs.get(start_key))
+            .copied()
+            .unwrap_or_default()
+    }
+
     pub(super) fn get_target_block_id(&self, search_key: &CompositeKey) -> Uuid {
         let data = self.data.lock();
         let forward = &data.forward;
@@ -204,6 +371,9 @@ impl SparseIndexWriter {
                 data.forward.remove(&start_key);
                 // data.counts is not guaranteed to be in sync with forward, so ignore the result if the key doesn't exist
                 let _ = data.counts.remove(&start_key);
+                let _ = data.zone_maps.remove(&start_key);
+                let _ = data.codecs.remove(&start_key);
+                let _ = data.bloom_filters.remove(&start_key);
             }
             removed = true;
         }
@@ -240,6 +410,17 @@ impl SparseIndexWriter {
             if let Some(old_count) = data.counts.remove(&key_copy) {
                 data.counts.insert(SparseIndexDelimiter::Start, old_count);
             }
+            if let Some(old_zone_map) = data.zone_maps.remove(&key_copy) {
+                data.zone_maps
+                    .insert(SparseIndexDelimiter::Start, old_zone_map);
+            }
+            if let Some(old_codec) = data.codecs.remove(&key_copy) {
+                data.codecs.insert(SparseIndexDelimiter::Start, old_codec);
+            }
+            if let Some(old_bloom_filter) = data.bloom_filters.remove(&key_copy) {
+                data.bloom_filters
+                    .insert(SparseIndexDelimiter::Start, old_bloom_filter);
+            }
         }
     }
 
@@ -252,7 +433,16 @@ impl SparseIndexWriter {
 
         let zipped = data.forward.iter().zip(data.counts.iter());
         let new_forward = zipped.map(|((key, block_id), (_, count))| {
-            (key.clone(), SparseIndexValue::new(*block_id, *count))
+            (
+                key.clone(),
+                SparseIndexValue::new(
+                    *block_id,
+                    *count,
+                    data.zone_maps.get(key).cloned(),
+                    data.codecs.get(key).copied().unwrap_or_default(),
+                    data.bloom_filters.get(key).cloned(),
+                ),
+            )
         });
         let new_forward = BTreeMap::from_iter(new_forward);
         Ok(SparseIndexReader::new(new_forward))
@@ -285,28 +475,55 @@ pub struct SparseIndexReader {
 #[derive(Serialize, Deserialize)]
 pub(super) struct SparseIndexReaderData {
     pub(super) forward: BTreeMap<SparseIndexDelimiter, SparseIndexValue>,
+    // The codecs of the compressed blocks by block id, built on the first lookup
+    #[serde(skip)]
+    codecs: OnceLock<HashMap<Uuid, BlockCodec>>,
 }
 
 /// A value in the sparse index.
 /// # Fields
 /// * `id` - The block id that contains the keys in the range
 /// * `count` - The number of keys in the block
+/// * `zone_map` - The smallest and largest keys in the block, if they are known
+/// * `codec` - The codec that the block is compressed with in storage
+/// * `bloom_filter` - The bloom filter of the keys in the block, if it is known
 #[derive(Serialize, Deserialize)]
 pub(super) struct SparseIndexValue {
     pub(super) id: Uuid,
     pub(super) count: u32,
+    #[serde(default)]
+    pub(super) zone_map: Option<ZoneMap>,
+    #[serde(default)]
+    pub(super) codec: BlockCodec,
+    #[serde(default)]
+    pub(super) bloom_filter: Option<BloomFilter>,
 }
 
 impl SparseIndexValue {
-    pub(super) fn new(id: Uuid, count: u32) -> Self {
-        Self { id, count }
+    pub(super) fn new(
+        id: Uuid,
+        count: u32,
+        zone_map: Option<ZoneMap>,
+        codec: BlockCodec,
+        bloom_filter: Option<BloomFilter>,
+    ) -> Self {
+        Self {
+            id,
+            count,
+            zone_map,
+            codec,
+            bloom_filter,
+        }
     }
 }
 
 impl SparseIndexReader {
     pub(super) fn new(data: BTreeMap<SparseIndexDelimiter, SparseIndexValue>) -> Self {
         Self {
-            data: Arc::new(SparseIndexReaderData { forward: data }),
+            data: Arc::new(SparseIndexReaderData {
+                forward: data,
+                codecs: OnceLock::new(),
+            }),
         }
     }
 
@@ -315,12 +532,65 @@ impl SparseIndexReader {
         self.data.forward.len()
     }
 
+    /// An estimate of the bytes that the sparse index takes up in memory
+    pub(super) fn size_bytes(&self) -> usize {
+        let key_size = |key: &CompositeKey| key.prefix.len() + key.key.get_size();
+        self.data
+            .forward
+            .iter()
+            .map(|(delimiter, value)| {
+                let delimiter_size = match delimiter {
+                    SparseIndexDelimiter::Start => 0,
+                    SparseIndexDelimiter::Key(key) => key_size(key),
+                };
+                let zone_map_size = value.zone_map.as_ref().map_or(0, |zone_map| {
+                    key_size(&zone_map.min_key) + key_size(&zone_map.max_key)
+                });
+                let bloom_filter_size = value
+                    .bloom_filter
+                    .as_ref()
+                    .map_or(0, BloomFilter::size_bytes);
+                std::mem::size_of::<(SparseIndexDelimiter, SparseIndexValue)>()
+                    + delimiter_size
+                    + zone_map_size
+                    + bloom_filter_size
+            })
+            .sum()
+    }
+
     /// Get the block id for a given key
     pub(super) fn get_target_block_id(&self, search_key: &CompositeKey) -> Uuid {
         let forward = &self.data.forward;
         get_target_block(search_key, forward).id
     }
 
+    /// Returns whether the block that the key would be in may contain it. This is false only
+    /// if the bloom filter of the block rules the key out
+    pub(super) fn may_contain(&self, search_key: &CompositeKey) -> bool {
+        let forward = &self.data.forward;
+        match &get_target_block(search_key, forward).bloom_filter {
+            Some(bloom_filter) => bloom_filter.may_contain(search_key),
+            None => true,
+        }
+    }
+
+    /// Get the codec that a block is compressed with in storage
+    pub(super) fn codec(&self, block_id: &Uuid) -> BlockCodec {
+        self.data
+            .codecs
+            .get_or_init(|| {
+                self.data
+                    .forward
+                    .values()
+                    .filter(|value| value.codec != BlockCodec::None)
+                    .map(|value| (value.id, value.codec))
+                    .collect()
+            })
+            .get(block_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
     /// Get all the block ids that contain keys in the given input search keys
     pub(super) fn get_all_target_block_ids(&self, mut search_keys: Vec<CompositeKey>) -> Vec<Uuid> {
         // Sort so that we can search in one iteration.
@@ -361,6 +631,29 @@ impl SparseIndexReader {
         result_uuids
     }
 
+    /// Get the ids of up to `count` blocks that follow the block that the key would be in,
+    /// in key order
+    pub(super) fn get_next_block_ids(&self, search_key: &CompositeKey, count: usize) -> Vec<Uuid> {
+        self.data
+            .forward
+            .range((
+                Bound::Excluded(SparseIndexDelimiter::Key(search_key.clone())),
+                Bound::Unbounded,
+            ))
+            .take(count)
+            .map(|(_, sparse_index_value)| sparse_index_value.id)
+            .collect()
+    }
+
+    /// Get the ids of all the blocks in the sparse index
+    pub(super) fn get_all_block_ids(&self) -> HashSet<Uuid> {
+        self.data
+            .forward
+            .values()
+            .map(|sparse_index_value| sparse_index_value.id)
+            .collect()
+    }
+
     pub(super) fn get_block_ids_range<'prefix, 'referred_data, K, PrefixRange, KeyRange>(
         &self,
         // These key ranges are flattened instead of using a single RangeBounds<CompositeKey> because not all keys have a well-defined min and max value. E.x. if the key is a string, there would be no way to get the range for all keys within a specific prefix.
@@ -390,7 +683,7 @@ impl SparseIndexReader {
             .iter()
             .zip(start_keys_offset_by_1_iter)
             .map(|((start_key, block_uuid), end_key)| (block_uuid, start_key, end_key))
-            .filter(|(_, block_start_key, block_end_key)| {
+            .filter(|(sparse_index_value, block_start_key, block_end_key)| {
                 let prefix_start_valid = match block_start_key {
                     SparseIndexDelimiter::Start => true,
                     SparseIndexDelimiter::Key(start_key) => match prefix_range.start_bound() {
@@ -420,37 +713,55 @@ impl SparseIndexReader {
                     return false;
                 }
 
-                let key_start_valid = match block_end_key {
-                    Some(block_end_key) => match key_range.start_bound() {
-                        Bound::Included(key_range_start) => {
-                            key_range_start.clone().into() <= block_end_key.key
+                // The keys can only be compared if the block ends within the prefix of the range
+                let key_start_valid = match (prefix_range.start_bound(), block_end_key) {
+                    (Bound::Included(prefix_start), Some(block_end_key))
+                        if *prefix_start == block_end_key.prefix.as_str() =>
+                    {
+                        match key_range.start_bound() {
+                            Bound::Included(key_range_start) => {
+                                key_range_start.clone().into() <= block_end_key.key
+                            }
+                            Bound::Excluded(key_range_start) => {
+                                key_range_start.clone().into() < block_end_key.key
+                            }
+                            Bound::Unbounded => true,
                         }
-                        Bound::Excluded(key_range_start) => {
-                            key_range_start.clone().into() < block_end_key.key
-                        }
-                        Bound::Unbounded => true,
-                    },
-                    None => true,
+                    }
+                    _ => true,
                 };
 
                 if !key_start_valid {
                     return false;
                 }
 
-                let key_end_valid = match block_start_key {
-                    SparseIndexDelimiter::Start => true,
-                    SparseIndexDelimiter::Key(start_key) => match key_range.end_bound() {
-                        Bound::Included(key_range_end) => {
-                            key_range_end.clone().into() >= start_key.key
+                // Likewise if the block starts within the prefix of the range
+                let key_end_valid = match (prefix_range.end_bound(), block_start_key) {
+                    (Bound::Included(prefix_end), SparseIndexDelimiter::Key(start_key))
+                        if *prefix_end == start_key.prefix.as_str() =>
+                    {
+                        match key_range.end_bound() {
+                            Bound::Included(key_range_end) => {
+                                key_range_end.clone().into() >= start_key.key
+                            }
+                            Bound::Excluded(key_range_end) => {
+                                key_range_end.clone().into() > start_key.key
+                            }
+                            Bound::Unbounded => true,
                         }
-                        Bound::Excluded(key_range_end) => {
-                            key_range_end.clone().into() > start_key.key
-                        }
-                        Bound::Unbounded => true,
-                    },
+                    }
+                    _ => true,
                 };
 
-                key_end_valid
+                if !key_end_valid {
+                    return false;
+                }
+
+                // Skip the blocks whose smallest and largest keys are both outside of the range
+                match &sparse_index_value.zone_map {
+                    Some(zone_map) => zone_map.may_contain(&prefix_range, &key_range),
+                    None => true,
+                }
             })
             .map(|(sparse_index_value, _, _)| sparse_index_value.id)
             .collect()
@@ -462,12 +773,24 @@ impl SparseIndexReader {
         let mut new_forward = BTreeMap::new();
         let mut new_reverse = HashMap::new();
         let mut new_counts = BTreeMap::new();
+        let mut new_zone_maps = BTreeMap::new();
+        let mut new_codecs = BTreeMap::new();
+        let mut new_bloom_filters = BTreeMap::new();
         let old_data = &self.data;
         let old_forward = &old_data.forward;
         for (key, curr_block_value) in old_forward.iter() {
             new_forward.insert(key.clone(), curr_block_value.id);
             new_reverse.insert(curr_block_value.id, key.clone());
             new_counts.insert(key.clone(), curr_block_value.count);
+            if let Some(zone_map) = &curr_block_value.zone_map {
+                new_zone_maps.insert(key.clone(), zone_map.clone());
+            }
+            if curr_block_value.codec != BlockCodec::None {
+                new_codecs.insert(key.clone(), curr_block_value.codec);
+            }
+            if let Some(bloom_filter) = &curr_block_value.bloom_filter {
+                new_bloom_filters.insert(key.clone(), bloom_filter.clone());
+            }
         }
 
         SparseIndexWriter {
@@ -475,6 +798,9 @@ impl SparseIndexReader {
                 forward: new_forward,
                 reverse: new_reverse,
                 counts: new_counts,
+                zone_maps: new_zone_maps,
+                codecs: new_codecs,
+                bloom_filters: new_bloom_filters,
             })),
         }
     }
@@ -735,6 +1061,38 @@ mod tests {
         assert!(blocks.contains(&block_id_3));
     }
 
+    #[test]
+    fn test_get_next_block_ids() {
+        let block_ids = (0..4).map(|_| uuid::Uuid::new_v4()).collect::<Vec<_>>();
+        let writer = SparseIndexWriter::new(block_ids[0]);
+        for (block_id, start_key) in block_ids[1..].iter().zip(["c", "f", "i"]) {
+            writer
+                .add_block(
+                    CompositeKey::new("prefix".to_string(), start_key),
+                    *block_id,
+                )
+                .expect("No error");
+        }
+        for block_id in &block_ids {
+            writer
+                .set_count(*block_id, 3)
+                .expect("Set count should succeed");
+        }
+        let reader = writer.to_reader().expect("Conversion should succeed");
+
+        let key = |key: &str| CompositeKey::new("prefix".to_string(), key);
+        assert_eq!(
+            reader.get_next_block_ids(&key("a"), 2),
+            block_ids[1..3].to_vec()
+        );
+        // The key that starts a block is in that block
+        assert_eq!(
+            reader.get_next_block_ids(&key("c"), 5),
+            block_ids[2..].to_vec()
+        );
+        assert!(reader.get_next_block_ids(&key("j"), 2).is_empty());
+    }
+
     #[test]
     fn test_serde() {
         let ids = [uuid::Uuid::new_v4(), uuid::Uuid::new_v4()];
@@ -777,4 +1135,67 @@ mod tests {
             assert_eq!(new_data.forward.get(&target_key).unwrap().id, ids[i]);
         }
     }
+
+    #[test]
+    fn test_get_block_ids_range_with_zone_maps() {
+        let block_id_0 = uuid::Uuid::new_v4();
+        let sparse_index = SparseIndexWriter::new(block_id_0);
+
+        // Blocks (start, b/a), (b/a, b/m) and (b/m, end)
+        let block_id_1 = uuid::Uuid::new_v4();
+        sparse_index
+            .add_block(CompositeKey::new("b".to_string(), "a"), block_id_1)
+            .expect("No error");
+        let block_id_2 = uuid::Uuid::new_v4();
+        sparse_index
+            .add_block(CompositeKey::new("b".to_string(), "m"), block_id_2)
+            .expect("No error");
+
+        for (block_id, (min_key, max_key)) in
+            [block_id_0, block_id_1, block_id_2].into_iter().zip([
+                (("a", "a"), ("a", "z")),
+                (("b", "a"), ("b", "c")),
+                (("b", "m"), ("c", "z")),
+            ])
+        {
+            sparse_index
+                .set_count(block_id, 1)
+                .expect("Set count should succeed");
+            sparse_index
+                .set_zone_map(
+                    block_id,
+                    Some(ZoneMap {
+                        min_key: CompositeKey::new(min_key.0.to_string(), min_key.1),
+                        max_key: CompositeKey::new(max_key.0.to_string(), max_key.1),
+                    }),
+                )
+                .expect("Set zone map should succeed");
+        }
+
+        let reader = sparse_index.to_reader().expect("Conversion should succeed");
+
+        // The first block ends with prefix "b", but only holds keys of prefix "a"
+        assert_eq!(
+            reader.get_block_ids_range::<&str, _, _>("b"..="b", "a"..="z"),
+            vec![block_id_1, block_id_2]
+        );
+        // The second block only holds keys up to "c"
+        assert_eq!(
+            reader.get_block_ids_range::<&str, _, _>("b"..="b", "d"..="k"),
+            Vec::<Uuid>::new()
+        );
+        assert_eq!(
+            reader.get_block_ids_range::<&str, _, _>("b"..="b", "b"..="k"),
+            vec![block_id_1]
+        );
+        // The last block starts with prefix "b" and holds keys of prefix "c"
+        assert_eq!(
+            reader.get_block_ids_range::<&str, _, _>("c"..="c", "a"..="b"),
+            vec![block_id_2]
+        );
+        assert_eq!(
+            reader.get_block_ids_range::<&str, _, _>("a"..="a", "a"..),
+            vec![block_id_0]
+        );
+    }
 }
@@ -2,6 +2,7 @@ use super::Key;
 use chroma_error::{ChromaError, ErrorCodes};
 use serde::{Deserialize, Serialize};
 use std::hash::{Hash, Hasher};
+use uuid::Uuid;
 
 // TODO(rescrv):  This used to be a panic/unwrap, but could be a nicer type.
 #[derive(thiserror::Error, Debug)]
@@ -20,6 +21,8 @@ pub enum KeyWrapper {
     Float32(f32),
     Bool(bool),
     Uint32(u32),
+    Uint64(u64),
+    Uuid(Uuid),
 }
 
 impl KeyWrapper {
@@ -30,6 +33,8 @@ impl KeyWrapper {
             KeyWrapper::Float32(_) => 4,
             KeyWrapper::Bool(_) => 1,
             KeyWrapper::Uint32(_) => 4,
+            KeyWrapper::Uint64(_) => 8,
+            KeyWrapper::Uuid(_) => 16,
         }
     }
 }
@@ -102,6 +107,43 @@ impl TryFrom<&KeyWrapper> for u32 {
     }
 }
 
+impl From<u64> for KeyWrapper {
+    fn from(u: u64) -> KeyWrapper {
+        KeyWrapper::Uint64(u)
+    }
+}
+
+impl TryFrom<&KeyWrapper> for u64 {
+    type Error = InvalidKeyConversion;
+
+    fn try_from(key: &KeyWrapper) -> Result<Self, InvalidKeyConversion> {
+        match key {
+            KeyWrapper::Uint64(u) => Ok(*u),
+            _ => Err(InvalidKeyConversion),
+        }
+    }
+}
+
+impl From<Uuid> for KeyWrapper {
+    fn from(u: Uuid) -> KeyWrapper {
+        KeyWrapper::Uuid(u)
+    }
+}
+
+impl TryFrom<&KeyWrapper> for Uuid {
+    type Error = InvalidKeyConversion;
+
+    fn try_from(key: &KeyWrapper) -> Result<Self, InvalidKeyConversion> {
+        match key {
+            KeyWrapper::Uuid(u) => Ok(*u),
+            _ => Err(InvalidKeyConversion),
+        }
+    }
+}
+
+/// A key of a blockfile together with its prefix. Keys are ordered by their prefix first and
+/// then by the key, so the entries under one prefix are contiguous and ordered by their keys,
+/// e.g. the versions of a record under the prefix of the record
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CompositeKey {
     pub(super) prefix: String,
@@ -160,6 +202,15 @@ impl Ord for CompositeKey {
                     KeyWrapper::Uint32(u2) => u1.cmp(u2),
                     _ => panic!("Invalid comparison"),
                 },
+                KeyWrapper::Uint64(u1) => match &other.key {
+                    KeyWrapper::Uint64(u2) => u1.cmp(u2),
+                    _ => panic!("Invalid comparison"),
+                },
+                // UUIDs are ordered by their bytes, as they are stored in the blocks
+                KeyWrapper::Uuid(u1) => match &other.key {
+                    KeyWrapper::Uuid(u2) => u1.cmp(u2),
+                    _ => panic!("Invalid comparison"),
+                },
             }
         } else {
             self.prefix.cmp(&other.prefix)
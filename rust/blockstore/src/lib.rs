@@ -5,6 +5,7 @@ pub mod config;
 pub mod key;
 pub mod memory;
 pub mod provider;
+pub mod read_stats;
 use chroma_cache::new_cache_for_test;
 use chroma_storage::test_storage;
 use provider::BlockfileProvider;
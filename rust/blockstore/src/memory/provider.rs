@@ -81,6 +81,9 @@ mod tests {
                     encoding: None,
                     metadata: None,
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Add,
                 },
             },
@@ -92,6 +95,9 @@ mod tests {
                     encoding: None,
                     metadata: None,
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Add,
                 },
             },
@@ -103,6 +109,9 @@ mod tests {
                     encoding: None,
                     metadata: None,
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Add,
                 },
             },
@@ -114,6 +123,7 @@ mod tests {
                 id: &record.0.record.id,
                 embedding: record.0.record.embedding.as_ref().unwrap(),
                 document: None,
+                uri: None,
                 metadata: None,
             })
             .collect::<Vec<_>>();
@@ -123,6 +123,12 @@ impl<
             .map(|(key, value)| (K::try_from(&key.key).unwrap(), value)))
     }
 
+    pub(crate) fn get_all_iter(&'storage self) -> impl Iterator<Item = (&'storage str, K, V)> {
+        V::read_range_from_storage(.., .., &self.storage)
+            .into_iter()
+            .map(|(key, value)| (key.prefix.as_str(), K::try_from(&key.key).unwrap(), value))
+    }
+
     pub(crate) fn get_at_index(
         &'storage self,
         index: usize,
@@ -199,6 +205,7 @@ mod tests {
             embedding: &embedding,
             metadata: None,
             document: None,
+            uri: None,
         };
 
         let data = vec![
@@ -210,6 +217,9 @@ mod tests {
                     encoding: None,
                     metadata: None,
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Add,
                 },
             },
@@ -221,6 +231,9 @@ mod tests {
                     encoding: None,
                     metadata: None,
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Add,
                 },
             },
@@ -232,6 +245,9 @@ mod tests {
                     encoding: None,
                     metadata: None,
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Add,
                 },
             },
@@ -243,6 +259,7 @@ mod tests {
                 id: &record.0.record.id,
                 embedding: record.0.record.embedding.as_ref().unwrap(),
                 document: None,
+                uri: None,
                 metadata: None,
             })
             .collect::<Vec<_>>();
@@ -278,7 +295,7 @@ mod tests {
     fn test_u32_key() {
         let storage_manager = StorageManager::new();
         let writer = MemoryBlockfileWriter::new(storage_manager.clone());
-        let _ = writer.set("prefix", 1, "value1".to_string());
+        let _ = writer.set("prefix", 1u32, "value1".to_string());
         let _ = writer.commit();
 
         let reader: MemoryBlockfileReader<u32, &str> =
@@ -326,9 +343,9 @@ mod tests {
     fn test_get_gt_int_none_returned() {
         let storage_manager = StorageManager::new();
         let writer = MemoryBlockfileWriter::new(storage_manager.clone());
-        let _ = writer.set("prefix", 1, "value1".to_string());
-        let _ = writer.set("prefix", 2, "value2".to_string());
-        let _ = writer.set("prefix", 3, "value3".to_string());
+        let _ = writer.set("prefix", 1u32, "value1".to_string());
+        let _ = writer.set("prefix", 2u32, "value2".to_string());
+        let _ = writer.set("prefix", 3u32, "value3".to_string());
         let _ = writer.commit();
 
         let reader: MemoryBlockfileReader<u32, &str> =
@@ -342,9 +359,9 @@ mod tests {
     fn test_get_gt_int_all_returned() {
         let storage_manager = StorageManager::new();
         let writer = MemoryBlockfileWriter::new(storage_manager.clone());
-        let _ = writer.set("prefix", 1, "value1".to_string());
-        let _ = writer.set("prefix", 2, "value2".to_string());
-        let _ = writer.set("prefix", 3, "value3".to_string());
+        let _ = writer.set("prefix", 1u32, "value1".to_string());
+        let _ = writer.set("prefix", 2u32, "value2".to_string());
+        let _ = writer.set("prefix", 3u32, "value3".to_string());
         let _ = writer.commit();
 
         let reader: MemoryBlockfileReader<u32, &str> =
@@ -369,9 +386,9 @@ mod tests {
     fn test_get_gt_int_some_returned() {
         let storage_manager = StorageManager::new();
         let writer = MemoryBlockfileWriter::new(storage_manager.clone());
-        let _ = writer.set("prefix", 1, "value1".to_string());
-        let _ = writer.set("prefix", 2, "value2".to_string());
-        let _ = writer.set("prefix", 3, "value3".to_string());
+        let _ = writer.set("prefix", 1u32, "value1".to_string());
+        let _ = writer.set("prefix", 2u32, "value2".to_string());
+        let _ = writer.set("prefix", 3u32, "value3".to_string());
         let _ = writer.commit();
 
         let reader: MemoryBlockfileReader<u32, &str> =
@@ -468,9 +485,9 @@ mod tests {
     fn test_get_gte_int_none_returned() {
         let storage_manager = StorageManager::new();
         let writer = MemoryBlockfileWriter::new(storage_manager.clone());
-        let _ = writer.set("prefix", 1, "value1".to_string());
-        let _ = writer.set("prefix", 2, "value2".to_string());
-        let _ = writer.set("prefix", 3, "value3".to_string());
+        let _ = writer.set("prefix", 1u32, "value1".to_string());
+        let _ = writer.set("prefix", 2u32, "value2".to_string());
+        let _ = writer.set("prefix", 3u32, "value3".to_string());
         let _ = writer.commit();
 
         let reader: MemoryBlockfileReader<u32, &str> =
@@ -483,9 +500,9 @@ mod tests {
     fn test_get_gte_int_all_returned() {
         let storage_manager = StorageManager::new();
         let writer = MemoryBlockfileWriter::new(storage_manager.clone());
-        let _ = writer.set("prefix", 1, "value1".to_string());
-        let _ = writer.set("prefix", 2, "value2".to_string());
-        let _ = writer.set("prefix", 3, "value3".to_string());
+        let _ = writer.set("prefix", 1u32, "value1".to_string());
+        let _ = writer.set("prefix", 2u32, "value2".to_string());
+        let _ = writer.set("prefix", 3u32, "value3".to_string());
         let _ = writer.commit();
 
         let reader: MemoryBlockfileReader<u32, &str> =
@@ -508,9 +525,9 @@ mod tests {
     fn test_get_gte_int_some_returned() {
         let storage_manager = StorageManager::new();
         let writer = MemoryBlockfileWriter::new(storage_manager.clone());
-        let _ = writer.set("prefix", 1, "value1".to_string());
-        let _ = writer.set("prefix", 2, "value2".to_string());
-        let _ = writer.set("prefix", 3, "value3".to_string());
+        let _ = writer.set("prefix", 1u32, "value1".to_string());
+        let _ = writer.set("prefix", 2u32, "value2".to_string());
+        let _ = writer.set("prefix", 3u32, "value3".to_string());
         let _ = writer.commit();
 
         let reader: MemoryBlockfileReader<u32, &str> =
@@ -592,9 +609,9 @@ mod tests {
     fn test_get_lt_int_none_returned() {
         let storage_manager = StorageManager::new();
         let writer = MemoryBlockfileWriter::new(storage_manager.clone());
-        let _ = writer.set("prefix", 1, "value1".to_string());
-        let _ = writer.set("prefix", 2, "value2".to_string());
-        let _ = writer.set("prefix", 3, "value3".to_string());
+        let _ = writer.set("prefix", 1u32, "value1".to_string());
+        let _ = writer.set("prefix", 2u32, "value2".to_string());
+        let _ = writer.set("prefix", 3u32, "value3".to_string());
         let _ = writer.commit();
 
         let reader: MemoryBlockfileReader<u32, &str> =
@@ -607,9 +624,9 @@ mod tests {
     fn test_get_lt_int_all_returned() {
         let storage_manager = StorageManager::new();
         let writer = MemoryBlockfileWriter::new(storage_manager.clone());
-        let _ = writer.set("prefix", 1, "value1".to_string());
-        let _ = writer.set("prefix", 2, "value2".to_string());
-        let _ = writer.set("prefix", 3, "value3".to_string());
+        let _ = writer.set("prefix", 1u32, "value1".to_string());
+        let _ = writer.set("prefix", 2u32, "value2".to_string());
+        let _ = writer.set("prefix", 3u32, "value3".to_string());
         let _ = writer.commit();
 
         let reader: MemoryBlockfileReader<u32, &str> =
@@ -632,9 +649,9 @@ mod tests {
     fn test_get_lt_int_some_returned() {
         let storage_manager = StorageManager::new();
         let writer = MemoryBlockfileWriter::new(storage_manager.clone());
-        let _ = writer.set("prefix", 1, "value1".to_string());
-        let _ = writer.set("prefix", 2, "value2".to_string());
-        let _ = writer.set("prefix", 3, "value3".to_string());
+        let _ = writer.set("prefix", 1u32, "value1".to_string());
+        let _ = writer.set("prefix", 2u32, "value2".to_string());
+        let _ = writer.set("prefix", 3u32, "value3".to_string());
         let _ = writer.commit();
 
         let reader: MemoryBlockfileReader<u32, &str> =
@@ -716,9 +733,9 @@ mod tests {
     fn test_get_lte_int_none_returned() {
         let storage_manager = StorageManager::new();
         let writer = MemoryBlockfileWriter::new(storage_manager.clone());
-        let _ = writer.set("prefix", 1, "value1".to_string());
-        let _ = writer.set("prefix", 2, "value2".to_string());
-        let _ = writer.set("prefix", 3, "value3".to_string());
+        let _ = writer.set("prefix", 1u32, "value1".to_string());
+        let _ = writer.set("prefix", 2u32, "value2".to_string());
+        let _ = writer.set("prefix", 3u32, "value3".to_string());
         let _ = writer.commit();
 
         let reader: MemoryBlockfileReader<u32, &str> =
@@ -731,9 +748,9 @@ mod tests {
     fn test_get_lte_int_all_returned() {
         let storage_manager = StorageManager::new();
         let writer = MemoryBlockfileWriter::new(storage_manager.clone());
-        let _ = writer.set("prefix", 1, "value1".to_string());
-        let _ = writer.set("prefix", 2, "value2".to_string());
-        let _ = writer.set("prefix", 3, "value3".to_string());
+        let _ = writer.set("prefix", 1u32, "value1".to_string());
+        let _ = writer.set("prefix", 2u32, "value2".to_string());
+        let _ = writer.set("prefix", 3u32, "value3".to_string());
         let _ = writer.commit();
 
         let reader: MemoryBlockfileReader<u32, &str> =
@@ -756,9 +773,9 @@ mod tests {
     fn test_get_lte_int_some_returned() {
         let storage_manager = StorageManager::new();
         let writer = MemoryBlockfileWriter::new(storage_manager.clone());
-        let _ = writer.set("prefix", 1, "value1".to_string());
-        let _ = writer.set("prefix", 2, "value2".to_string());
-        let _ = writer.set("prefix", 3, "value3".to_string());
+        let _ = writer.set("prefix", 1u32, "value1".to_string());
+        let _ = writer.set("prefix", 2u32, "value2".to_string());
+        let _ = writer.set("prefix", 3u32, "value3".to_string());
         let _ = writer.commit();
 
         let reader: MemoryBlockfileReader<u32, &str> =
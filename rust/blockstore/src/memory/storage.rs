@@ -1,6 +1,6 @@
 use crate::key::{CompositeKey, KeyWrapper};
 use chroma_error::ChromaError;
-use chroma_types::DataRecord;
+use chroma_types::{DataRecord, DataRecordMetadata};
 use parking_lot::RwLock;
 use roaring::RoaringBitmap;
 use std::{
@@ -604,6 +604,7 @@ impl<'referred_data> Readable<'referred_data> for DataRecord<'referred_data> {
             embedding,
             metadata: None,
             document: None,
+            uri: None,
         })
     }
 
@@ -631,6 +632,7 @@ impl<'referred_data> Readable<'referred_data> for DataRecord<'referred_data> {
                         embedding,
                         metadata: None,
                         document: None,
+                        uri: None,
                     },
                 )
             })
@@ -651,6 +653,7 @@ impl<'referred_data> Readable<'referred_data> for DataRecord<'referred_data> {
                 embedding,
                 metadata: None,
                 document: None,
+                uri: None,
             },
         ))
     }
@@ -670,6 +673,49 @@ impl<'referred_data> Readable<'referred_data> for DataRecord<'referred_data> {
     }
 }
 
+// The storage does not keep the metadata of the data records, like for the records themselves
+impl<'referred_data> Readable<'referred_data> for DataRecordMetadata {
+    fn read_from_storage(
+        prefix: &str,
+        key: KeyWrapper,
+        storage: &'referred_data Storage,
+    ) -> Option<Self> {
+        DataRecord::read_from_storage(prefix, key, storage)
+            .map(|record| DataRecordMetadata(record.metadata))
+    }
+
+    fn read_range_from_storage<'prefix, PrefixRange, KeyRange>(
+        prefix_range: PrefixRange,
+        key_range: KeyRange,
+        storage: &'referred_data Storage,
+    ) -> Vec<(&'referred_data CompositeKey, Self)>
+    where
+        PrefixRange: std::ops::RangeBounds<&'prefix str>,
+        KeyRange: std::ops::RangeBounds<KeyWrapper>,
+    {
+        DataRecord::read_range_from_storage(prefix_range, key_range, storage)
+            .into_iter()
+            .map(|(key, record)| (key, DataRecordMetadata(record.metadata)))
+            .collect()
+    }
+
+    fn get_at_index(
+        storage: &'referred_data Storage,
+        index: usize,
+    ) -> Option<(&'referred_data CompositeKey, Self)> {
+        DataRecord::get_at_index(storage, index)
+            .map(|(key, record)| (key, DataRecordMetadata(record.metadata)))
+    }
+
+    fn count(storage: &Storage) -> Result<usize, Box<dyn ChromaError>> {
+        DataRecord::count(storage)
+    }
+
+    fn contains(prefix: &str, key: KeyWrapper, storage: &'referred_data Storage) -> bool {
+        DataRecord::contains(prefix, key, storage)
+    }
+}
+
 #[derive(Clone)]
 pub struct StorageBuilder {
     bool_storage: Arc<RwLock<Option<BTreeMap<CompositeKey, bool>>>>,
@@ -2,6 +2,7 @@ use crate::arrow::root::RootReader;
 use crate::BlockfileWriterOptions;
 
 use super::arrow::block::Block;
+use super::arrow::decode_executor::DecodeExecutor;
 use super::arrow::provider::ArrowBlockfileProvider;
 use super::arrow::types::{
     ArrowReadableKey, ArrowReadableValue, ArrowWriteableKey, ArrowWriteableValue,
@@ -13,19 +14,31 @@ use super::memory::storage::Readable;
 use super::types::BlockfileWriter;
 use super::{BlockfileReader, Key, Value};
 use async_trait::async_trait;
-use chroma_cache::PersistentCache;
+use chroma_cache::{CachedEntry, PersistentCache};
 use chroma_config::Configurable;
-use chroma_error::{ChromaError, ErrorCodes};
-use chroma_storage::Storage;
+use chroma_error::{ChromaError, ErrorCodes, ErrorLocation};
+use chroma_storage::{PutOptions, Storage};
 use core::fmt::{self, Debug};
+use std::collections::HashSet;
 use std::fmt::Formatter;
+use std::sync::Arc;
+use std::time::SystemTime;
 use thiserror::Error;
 use uuid::Uuid;
 
+/// An object that a provider writes to storage
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BlockfileObject {
+    /// The root of a blockfile, with its sparse index
+    Root(Uuid),
+    /// A block, which the roots of several blockfiles may reference
+    Block(Uuid),
+}
+
 #[derive(Clone)]
 pub enum BlockfileProvider {
     HashMapBlockfileProvider(MemoryBlockfileProvider),
-    ArrowBlockfileProvider(ArrowBlockfileProvider),
+    ArrowBlockfileProvider(Box<ArrowBlockfileProvider>),
 }
 
 impl Debug for BlockfileProvider {
@@ -52,12 +65,50 @@ impl BlockfileProvider {
         block_cache: Box<dyn PersistentCache<Uuid, Block>>,
         root_cache: Box<dyn PersistentCache<Uuid, RootReader>>,
     ) -> Self {
-        BlockfileProvider::ArrowBlockfileProvider(ArrowBlockfileProvider::new(
+        BlockfileProvider::ArrowBlockfileProvider(Box::new(ArrowBlockfileProvider::new(
             storage,
             max_block_size_bytes,
             block_cache,
             root_cache,
-        ))
+        )))
+    }
+
+    /// Returns a provider that writes its blockfiles with the given options, e.g. to encrypt
+    /// them with the key of a tenant. The memory provider does not write to storage
+    pub fn with_put_options(&self, put_options: PutOptions) -> Self {
+        match self {
+            BlockfileProvider::HashMapBlockfileProvider(_) => self.clone(),
+            BlockfileProvider::ArrowBlockfileProvider(provider) => {
+                BlockfileProvider::ArrowBlockfileProvider(Box::new(
+                    provider.as_ref().clone().with_put_options(put_options),
+                ))
+            }
+        }
+    }
+
+    /// Returns a provider that accounts the blocks that it reads and writes to the partition of
+    /// the block cache, e.g. a collection, so that they are bounded by the quota of the
+    /// partition. The memory provider has no cache
+    pub fn with_cache_partition(&self, partition: &str) -> Self {
+        match self {
+            BlockfileProvider::HashMapBlockfileProvider(_) => self.clone(),
+            BlockfileProvider::ArrowBlockfileProvider(provider) => {
+                BlockfileProvider::ArrowBlockfileProvider(Box::new(
+                    provider.as_ref().clone().with_cache_partition(partition),
+                ))
+            }
+        }
+    }
+
+    /// Sets the executor that decodes the large blocks read by this provider and all of its
+    /// clones. The memory provider does not decode blocks
+    pub fn set_decode_executor(&self, executor: Arc<dyn DecodeExecutor>) {
+        match self {
+            BlockfileProvider::HashMapBlockfileProvider(_) => {}
+            BlockfileProvider::ArrowBlockfileProvider(provider) => {
+                provider.set_decode_executor(executor)
+            }
+        }
     }
 
     pub async fn read<
@@ -100,6 +151,118 @@ impl BlockfileProvider {
         };
         Ok(())
     }
+
+    /// Proactively evicts the cached data of a blockfile that is superseded by `current_id`,
+    /// instead of waiting for it to fall out of the cache
+    pub async fn evict_superseded(&self, stale_id: &Uuid, current_id: &Uuid) {
+        match self {
+            // The memory provider holds the only copy of the data, so there is nothing to evict
+            BlockfileProvider::HashMapBlockfileProvider(_) => {}
+            BlockfileProvider::ArrowBlockfileProvider(provider) => {
+                provider.evict_superseded(stale_id, current_id).await
+            }
+        }
+    }
+
+    /// Returns up to `limit` of the blocks that the roots reference, the most read first. The
+    /// memory provider has no blocks
+    pub async fn hottest_block_ids(
+        &self,
+        root_ids: &[Uuid],
+        limit: usize,
+    ) -> Result<Vec<Uuid>, Box<dyn ChromaError>> {
+        match self {
+            BlockfileProvider::HashMapBlockfileProvider(_) => Ok(Vec::new()),
+            BlockfileProvider::ArrowBlockfileProvider(provider) => {
+                provider.hottest_block_ids(root_ids, limit).await
+            }
+        }
+    }
+
+    /// Loads the given blocks of the roots into the cache, so that the reads of them do not
+    /// go to storage, and returns the number of blocks that are loaded
+    pub async fn load_blocks(&self, root_ids: &[Uuid], block_ids: &HashSet<Uuid>) -> usize {
+        match self {
+            BlockfileProvider::HashMapBlockfileProvider(_) => 0,
+            BlockfileProvider::ArrowBlockfileProvider(provider) => {
+                provider.load_blocks(root_ids, block_ids).await
+            }
+        }
+    }
+
+    /// The bytes of the blocks that the provider holds in its cache in memory. The memory
+    /// provider has no cache, and its blockfiles are not accounted for
+    pub fn cache_usage_bytes(&self) -> usize {
+        match self {
+            BlockfileProvider::HashMapBlockfileProvider(_) => 0,
+            BlockfileProvider::ArrowBlockfileProvider(provider) => provider.cache_usage_bytes(),
+        }
+    }
+
+    /// The bytes of the blocks that the cache may hold in memory, if it is bounded
+    pub fn cache_capacity_bytes(&self) -> Option<usize> {
+        match self {
+            BlockfileProvider::HashMapBlockfileProvider(_) => None,
+            BlockfileProvider::ArrowBlockfileProvider(provider) => provider.cache_capacity_bytes(),
+        }
+    }
+
+    /// The largest blocks that the cache holds in memory, up to the limit and the largest
+    /// first. The memory provider has no cache
+    pub fn largest_cached_blocks(&self, limit: usize) -> Vec<CachedEntry<Uuid>> {
+        match self {
+            BlockfileProvider::HashMapBlockfileProvider(_) => Vec::new(),
+            BlockfileProvider::ArrowBlockfileProvider(provider) => {
+                provider.largest_cached_blocks(limit)
+            }
+        }
+    }
+
+    /// Bounds the bytes of the blocks that the cache holds in memory, and evicts blocks until
+    /// it holds no more
+    pub fn resize_cache(&self, capacity_bytes: usize) {
+        match self {
+            BlockfileProvider::HashMapBlockfileProvider(_) => {}
+            BlockfileProvider::ArrowBlockfileProvider(provider) => {
+                provider.resize_cache(capacity_bytes)
+            }
+        }
+    }
+
+    /// Lists the roots and the blocks that the provider wrote to storage, with when they were
+    /// written. The memory provider does not write to storage
+    pub async fn list_objects(
+        &self,
+    ) -> Result<Vec<(BlockfileObject, SystemTime)>, Box<dyn ChromaError>> {
+        match self {
+            BlockfileProvider::HashMapBlockfileProvider(_) => Ok(Vec::new()),
+            BlockfileProvider::ArrowBlockfileProvider(provider) => provider.list_objects().await,
+        }
+    }
+
+    /// Returns the ids of the blocks that the root of the blockfile references, or None if
+    /// there is no such root in storage
+    pub async fn referenced_block_ids(
+        &self,
+        root_id: &Uuid,
+    ) -> Result<Option<Vec<Uuid>>, Box<dyn ChromaError>> {
+        match self {
+            BlockfileProvider::HashMapBlockfileProvider(_) => Ok(None),
+            BlockfileProvider::ArrowBlockfileProvider(provider) => {
+                provider.referenced_block_ids(root_id).await
+            }
+        }
+    }
+
+    /// Deletes the object from storage and evicts it from the cache
+    pub async fn delete_object(&self, object: BlockfileObject) -> Result<(), Box<dyn ChromaError>> {
+        match self {
+            BlockfileProvider::HashMapBlockfileProvider(_) => Ok(()),
+            BlockfileProvider::ArrowBlockfileProvider(provider) => {
+                provider.delete_object(object).await
+            }
+        }
+    }
 }
 
 // =================== Configurable ===================
@@ -112,13 +275,13 @@ impl Configurable<(BlockfileProviderConfig, Storage)> for BlockfileProvider {
         let (blockfile_config, storage) = config;
         match blockfile_config {
             BlockfileProviderConfig::Arrow(blockfile_config) => {
-                Ok(BlockfileProvider::ArrowBlockfileProvider(
+                Ok(BlockfileProvider::ArrowBlockfileProvider(Box::new(
                     ArrowBlockfileProvider::try_from_config(&(
                         *blockfile_config.clone(),
                         storage.clone(),
                     ))
                     .await?,
-                ))
+                )))
             }
             BlockfileProviderConfig::Memory => Ok(BlockfileProvider::HashMapBlockfileProvider(
                 MemoryBlockfileProvider::new(),
@@ -143,6 +306,13 @@ impl ChromaError for OpenError {
             OpenError::Other(e) => e.code(),
         }
     }
+
+    fn location(&self) -> ErrorLocation {
+        match self {
+            OpenError::NotFound => ErrorLocation::default(),
+            OpenError::Other(e) => e.location(),
+        }
+    }
 }
 
 #[derive(Error, Debug)]
@@ -0,0 +1,106 @@
+use std::{cell::Cell, future::Future, ops::AddAssign};
+
+tokio::task_local! {
+    static READ_STATS: Cell<ReadStats>;
+}
+
+/// The block reads of the blockstore while a future runs
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ReadStats {
+    /// The number of blocks served from the block cache
+    pub cache_hits: u64,
+    /// The number of blocks read from storage
+    pub cache_misses: u64,
+    /// The number of bytes of the blocks read from storage
+    pub bytes_read: u64,
+}
+
+impl ReadStats {
+    /// The share of the blocks served from the block cache, or `None` if no block is read
+    pub fn cache_hit_ratio(&self) -> Option<f64> {
+        let blocks = self.cache_hits + self.cache_misses;
+        (blocks > 0).then(|| self.cache_hits as f64 / blocks as f64)
+    }
+}
+
+impl AddAssign for ReadStats {
+    fn add_assign(&mut self, other: Self) {
+        self.cache_hits += other.cache_hits;
+        self.cache_misses += other.cache_misses;
+        self.bytes_read += other.bytes_read;
+    }
+}
+
+/// Runs the future and returns its output together with the block reads of the blockstore
+/// while it runs. The reads of tasks spawned by the future are not counted, and the reads of
+/// a nested count are also added to the enclosing count
+pub async fn count_reads<F: Future>(future: F) -> (F::Output, ReadStats) {
+    let (output, read_stats) = READ_STATS
+        .scope(Cell::new(ReadStats::default()), async {
+            let output = future.await;
+            (output, READ_STATS.with(Cell::get))
+        })
+        .await;
+    record(read_stats);
+    (output, read_stats)
+}
+
+fn record(stats: ReadStats) {
+    let _ = READ_STATS.try_with(|read_stats| {
+        let mut total = read_stats.get();
+        total += stats;
+        read_stats.set(total);
+    });
+}
+
+/// Adds a block served from the block cache to the count of the enclosing `count_reads`, if any
+pub(crate) fn record_cache_hit() {
+    record(ReadStats {
+        cache_hits: 1,
+        ..Default::default()
+    });
+}
+
+/// Adds a block of the given size read from storage to the count of the enclosing
+/// `count_reads`, if any
+pub(crate) fn record_bytes_read(bytes: usize) {
+    record(ReadStats {
+        cache_misses: 1,
+        bytes_read: bytes as u64,
+        ..Default::default()
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{count_reads, record_bytes_read, record_cache_hit, ReadStats};
+
+    #[tokio::test]
+    async fn test_count_reads() {
+        // Reads outside of a count are ignored
+        record_bytes_read(1);
+        let ((), read_stats) = count_reads(async {
+            record_bytes_read(10);
+            tokio::task::yield_now().await;
+            record_cache_hit();
+            // The reads of a nested count are added to this count
+            let ((), nested) = count_reads(async {
+                record_bytes_read(32);
+                record_cache_hit();
+            })
+            .await;
+            assert_eq!(nested.bytes_read, 32);
+        })
+        .await;
+        assert_eq!(
+            read_stats,
+            ReadStats {
+                cache_hits: 2,
+                cache_misses: 2,
+                bytes_read: 42,
+            }
+        );
+        assert_eq!(read_stats.cache_hit_ratio(), Some(0.5));
+        assert_eq!(ReadStats::default().cache_hit_ratio(), None);
+    }
+}
@@ -31,4 +31,20 @@ impl BlockfileFlusher {
             BlockfileFlusher::ArrowBlockfileFlusher(flusher) => flusher.id(),
         }
     }
+
+    /// Returns the number of blocks that will be written by the flush
+    pub fn num_blocks(&self) -> usize {
+        match self {
+            BlockfileFlusher::MemoryBlockfileFlusher(_) => 0,
+            BlockfileFlusher::ArrowBlockfileFlusher(flusher) => flusher.num_blocks(),
+        }
+    }
+
+    /// Returns the number of bytes that will be written by the flush, excluding the root
+    pub fn num_bytes(&self) -> usize {
+        match self {
+            BlockfileFlusher::MemoryBlockfileFlusher(_) => 0,
+            BlockfileFlusher::ArrowBlockfileFlusher(flusher) => flusher.num_bytes(),
+        }
+    }
 }
@@ -1,6 +1,7 @@
 use std::fmt::{Debug, Display};
 
 use crate::key::KeyWrapper;
+use uuid::Uuid;
 
 pub trait Key: PartialEq + Debug + Display + Into<KeyWrapper> + Clone {
     fn get_size(&self) -> usize;
@@ -29,3 +30,15 @@ impl Key for u32 {
         4
     }
 }
+
+impl Key for u64 {
+    fn get_size(&self) -> usize {
+        8
+    }
+}
+
+impl Key for Uuid {
+    fn get_size(&self) -> usize {
+        16
+    }
+}
@@ -15,7 +15,7 @@ pub enum BlockfileReader<
     V: Value + ArrowReadableValue<'me>,
 > {
     MemoryBlockfileReader(MemoryBlockfileReader<K, V>),
-    ArrowBlockfileReader(ArrowBlockfileReader<'me, K, V>),
+    ArrowBlockfileReader(Box<ArrowBlockfileReader<'me, K, V>>),
 }
 
 impl<
@@ -38,6 +38,22 @@ impl<
         }
     }
 
+    /// Returns the values of the keys under the prefix, in the same order as the keys. The
+    /// blocks of the keys are fetched concurrently rather than one key at a time
+    pub async fn get_many(
+        &'referred_data self,
+        prefix: &str,
+        keys: Vec<K>,
+    ) -> Result<Vec<Option<V>>, Box<dyn ChromaError>> {
+        match self {
+            BlockfileReader::MemoryBlockfileReader(reader) => keys
+                .into_iter()
+                .map(|key| reader.get(prefix, key))
+                .collect(),
+            BlockfileReader::ArrowBlockfileReader(reader) => reader.get_many(prefix, keys).await,
+        }
+    }
+
     pub async fn contains(
         &'referred_data self,
         prefix: &str,
@@ -87,6 +103,24 @@ impl<
         }
     }
 
+    /// Streams every entry of the blockfile together with its prefix, in prefix and key order
+    pub fn get_all_stream(
+        &'referred_data self,
+    ) -> impl Stream<Item = Result<(&'referred_data str, K, V), Box<dyn ChromaError>>>
+           + 'referred_data
+           + Send
+    where
+        K: Sync + Send,
+        V: Sync + Send,
+    {
+        match self {
+            BlockfileReader::MemoryBlockfileReader(reader) => {
+                futures::stream::iter(reader.get_all_iter().map(Ok)).boxed()
+            }
+            BlockfileReader::ArrowBlockfileReader(reader) => reader.get_all_stream().boxed(),
+        }
+    }
+
     pub async fn get_range<'prefix, PrefixRange, KeyRange>(
         &'referred_data self,
         prefix_range: PrefixRange,
@@ -123,6 +157,25 @@ impl<
         }
     }
 
+    /// Loads all of the blocks of the blockfile into the cache, and returns the number of
+    /// blocks. The memory blockfile has no blocks to load
+    pub async fn load_all_blocks(&self) -> usize {
+        match self {
+            BlockfileReader::MemoryBlockfileReader(_reader) => 0,
+            BlockfileReader::ArrowBlockfileReader(reader) => reader.load_all_blocks().await,
+        }
+    }
+
+    /// Fetches up to `blocks` of the blocks after the block of the key in the background, so
+    /// that a sequential scan from the key does not wait on the storage for each block. The
+    /// memory blockfile has no blocks to read ahead
+    pub fn readahead(&self, prefix: &str, key: K, blocks: usize) {
+        match self {
+            BlockfileReader::MemoryBlockfileReader(_reader) => {}
+            BlockfileReader::ArrowBlockfileReader(reader) => reader.readahead(prefix, key, blocks),
+        }
+    }
+
     pub async fn load_blocks_for_keys(&self, prefixes: &[&str], keys: &[K]) {
         match self {
             BlockfileReader::MemoryBlockfileReader(_reader) => unimplemented!(),
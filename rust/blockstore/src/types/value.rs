@@ -1,6 +1,6 @@
 use std::mem::size_of;
 
-use chroma_types::{DataRecord, SpannPostingList};
+use chroma_types::{DataRecord, DataRecordMetadata, SpannPostingList};
 use roaring::RoaringBitmap;
 
 pub trait Value: Clone {
@@ -61,6 +61,12 @@ impl<'a> Value for &DataRecord<'a> {
     }
 }
 
+impl Value for DataRecordMetadata {
+    fn get_size(&self) -> usize {
+        DataRecordMetadata::get_size(self)
+    }
+}
+
 impl<'a> Value for &SpannPostingList<'a> {
     fn get_size(&self) -> usize {
         self.compute_size()
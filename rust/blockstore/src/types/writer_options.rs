@@ -11,6 +11,7 @@ pub enum BlockfileWriterMutationOrdering {
 pub struct BlockfileWriterOptions {
     pub(crate) mutation_ordering: BlockfileWriterMutationOrdering,
     pub(crate) fork_from: Option<Uuid>,
+    pub(crate) stream_blocks: bool,
 }
 
 impl BlockfileWriterOptions {
@@ -42,4 +43,10 @@ impl BlockfileWriterOptions {
         self.fork_from = Some(fork);
         self
     }
+
+    /// Seal and upload blocks to storage as soon as they can no longer receive writes, rather than holding every block in memory until the writer is flushed. Only writers of ordered mutations stream their blocks, since an unordered writer may write to any block until it is committed. The uploaded blocks are only referenced once the writer is flushed, so the blocks of a writer that is dropped are left for garbage collection.
+    pub fn stream_blocks(mut self) -> Self {
+        self.stream_blocks = true;
+        self
+    }
 }
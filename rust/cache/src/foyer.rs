@@ -7,8 +7,10 @@ use foyer::{
     StorageKey, StorageValue, TracingOptions,
 };
 use opentelemetry::global;
+use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use std::hash::Hash;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -240,6 +242,32 @@ impl<'a> Drop for Stopwatch<'a> {
     }
 }
 
+/// Whether inserting an entry of the weight would take a cache resized below the capacity it
+/// was built with past its limit. Foyer only evicts down to its own capacity, so the cache has
+/// to make room for such an entry itself.
+fn exceeds_limit<K, V>(cache: &foyer::Cache<K, V>, limit: &AtomicUsize, weight: usize) -> bool
+where
+    K: Clone + Send + Sync + Eq + PartialEq + Hash + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    let limit = limit.load(Ordering::Relaxed);
+    limit < cache.capacity() && cache.usage() + weight > limit
+}
+
+/// Limits the cache to the capacity, up to the capacity it was built with. Foyer cannot evict
+/// a part of the cache on demand, so a cache that holds more than the limit is cleared.
+fn limit_cache<K, V>(cache: &foyer::Cache<K, V>, limit: &AtomicUsize, capacity: usize)
+where
+    K: Clone + Send + Sync + Eq + PartialEq + Hash + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    let capacity = capacity.min(cache.capacity());
+    limit.store(capacity, Ordering::Relaxed);
+    if cache.usage() > capacity {
+        cache.clear();
+    }
+}
+
 #[derive(Clone)]
 pub struct FoyerHybridCache<K, V>
 where
@@ -247,6 +275,11 @@ where
     V: Clone + Send + Sync + StorageValue + Weighted + 'static,
 {
     cache: foyer::HybridCache<K, V>,
+    /// The bound that resize sets on the memory tier. Foyer cannot resize a cache in place, so
+    /// the cache enforces it itself and never exceeds the capacity it was built with. The
+    /// memory tier is cleared whenever an insert would take it past the bound, since its
+    /// entries are also written to disk
+    limit: Arc<AtomicUsize>,
     get_latency: opentelemetry::metrics::Histogram<u64>,
     insert_latency: opentelemetry::metrics::Histogram<u64>,
     remove_latency: opentelemetry::metrics::Histogram<u64>,
@@ -334,8 +367,10 @@ where
         let insert_latency = meter.u64_histogram("insert_latency").init();
         let remove_latency = meter.u64_histogram("remove_latency").init();
         let clear_latency = meter.u64_histogram("clear_latency").init();
+        let limit = Arc::new(AtomicUsize::new(cache.memory().capacity()));
         Ok(FoyerHybridCache {
             cache,
+            limit,
             get_latency,
             insert_latency,
             remove_latency,
@@ -359,6 +394,9 @@ where
     #[tracing::instrument(skip(self, key, value))]
     async fn insert(&self, key: K, value: V) {
         let _stopwatch = Stopwatch::new(&self.insert_latency);
+        if exceeds_limit(self.cache.memory(), &self.limit, value.weight()) {
+            self.cache.memory().clear();
+        }
         self.cache.insert(key, value);
     }
 
@@ -373,6 +411,18 @@ where
         let _stopwatch = Stopwatch::new(&self.clear_latency);
         Ok(self.cache.clear().await?)
     }
+
+    fn usage(&self) -> usize {
+        self.cache.memory().usage()
+    }
+
+    fn capacity(&self) -> Option<usize> {
+        Some(self.limit.load(Ordering::Relaxed))
+    }
+
+    fn resize(&self, capacity: usize) {
+        limit_cache(self.cache.memory(), &self.limit, capacity);
+    }
 }
 
 impl<K, V> super::PersistentCache<K, V> for FoyerHybridCache<K, V>
@@ -388,7 +438,10 @@ where
     K: Clone + Send + Sync + Eq + PartialEq + Hash + 'static,
     V: Clone + Send + Sync + Weighted + 'static,
 {
-    cache: foyer::Cache<K, V>,
+    /// Foyer cannot resize a cache in place, so resize replaces the cache with one that is
+    /// built at the new capacity, and the entries of the replaced cache are released
+    cache: Arc<RwLock<foyer::Cache<K, V>>>,
+    build: Arc<dyn Fn(usize) -> foyer::Cache<K, V> + Send + Sync>,
     insert_latency: opentelemetry::metrics::Histogram<u64>,
     get_latency: opentelemetry::metrics::Histogram<u64>,
     remove_latency: opentelemetry::metrics::Histogram<u64>,
@@ -404,10 +457,14 @@ where
     pub async fn memory(
         config: &FoyerCacheConfig,
     ) -> Result<FoyerPlainCache<K, V>, Box<dyn ChromaError>> {
-        let cache = CacheBuilder::new(config.capacity)
-            .with_shards(config.shards)
-            .with_weighter(|_: &_, v: &V| v.weight())
-            .build();
+        let shards = config.shards;
+        let build = Arc::new(move |capacity| {
+            CacheBuilder::new(capacity)
+                .with_shards(shards)
+                .with_weighter(|_: &_, v: &V| v.weight())
+                .build()
+        });
+        let cache = Arc::new(RwLock::new(build(config.capacity)));
         let meter = global::meter("chroma");
         let insert_latency = meter.u64_histogram("insert_latency").init();
         let get_latency = meter.u64_histogram("get_latency").init();
@@ -415,6 +472,7 @@ where
         let clear_latency = meter.u64_histogram("clear_latency").init();
         Ok(FoyerPlainCache {
             cache,
+            build,
             insert_latency,
             get_latency,
             remove_latency,
@@ -448,13 +506,17 @@ where
                 let _ = self.0.send((key, value));
             }
         }
-        let evl = TokioEventListener(tx);
-
-        let cache = CacheBuilder::new(config.capacity)
-            .with_shards(config.shards)
-            .with_weighter(|_: &_, v: &V| v.weight())
-            .with_event_listener(Arc::new(evl))
-            .build();
+        let evl = Arc::new(TokioEventListener(tx));
+
+        let shards = config.shards;
+        let build = Arc::new(move |capacity| {
+            CacheBuilder::new(capacity)
+                .with_shards(shards)
+                .with_weighter(|_: &_, v: &V| v.weight())
+                .with_event_listener(evl.clone())
+                .build()
+        });
+        let cache = Arc::new(RwLock::new(build(config.capacity)));
         let get_latency = global::meter("chroma").u64_histogram("get_latency").init();
         let insert_latency = global::meter("chroma")
             .u64_histogram("insert_latency")
@@ -467,6 +529,7 @@ where
             .init();
         Ok(FoyerPlainCache {
             cache,
+            build,
             insert_latency,
             get_latency,
             remove_latency,
@@ -484,27 +547,46 @@ where
     #[tracing::instrument(skip(self, key))]
     async fn get(&self, key: &K) -> Result<Option<V>, CacheError> {
         let _stopwatch = Stopwatch::new(&self.get_latency);
-        Ok(self.cache.get(key).map(|v| v.value().clone()))
+        Ok(self.cache.read().get(key).map(|v| v.value().clone()))
     }
 
     #[tracing::instrument(skip(self, key, value))]
     async fn insert(&self, key: K, value: V) {
         let _stopwatch = Stopwatch::new(&self.insert_latency);
-        self.cache.insert(key, value);
+        self.cache.read().insert(key, value);
     }
 
     #[tracing::instrument(skip(self, key))]
     async fn remove(&self, key: &K) {
         let _stopwatch = Stopwatch::new(&self.remove_latency);
-        self.cache.remove(key);
+        self.cache.read().remove(key);
     }
 
     #[tracing::instrument(skip(self))]
     async fn clear(&self) -> Result<(), CacheError> {
         let _stopwatch = Stopwatch::new(&self.clear_latency);
-        self.cache.clear();
+        self.cache.read().clear();
         Ok(())
     }
+
+    fn usage(&self) -> usize {
+        self.cache.read().usage()
+    }
+
+    fn capacity(&self) -> Option<usize> {
+        Some(self.cache.read().capacity())
+    }
+
+    fn resize(&self, capacity: usize) {
+        let mut cache = self.cache.write();
+        if cache.capacity() == capacity {
+            return;
+        }
+        let replaced = std::mem::replace(&mut *cache, (self.build)(capacity));
+        drop(cache);
+        // The event listener, if any, is told about the released entries
+        replaced.clear();
+    }
 }
 
 impl<K, V> super::PersistentCache<K, V> for FoyerPlainCache<K, V>
@@ -513,3 +595,88 @@ where
     V: Clone + Send + Sync + Weighted + StorageValue + 'static,
 {
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{FoyerCacheConfig, FoyerHybridCache, FoyerPlainCache};
+    use crate::{Cache, Weighted};
+    use clap::Parser;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    struct Unit;
+
+    impl Weighted for Unit {
+        fn weight(&self) -> usize {
+            1
+        }
+    }
+
+    #[tokio::test]
+    async fn test_plain_cache_evicts_after_shrink() {
+        let config = FoyerCacheConfig::parse_from(["test", "--capacity", "10", "--shards", "1"]);
+        let cache = FoyerPlainCache::<u32, Unit>::memory(&config).await.unwrap();
+        for key in 0..10 {
+            cache.insert(key, Unit).await;
+        }
+        assert_eq!(cache.usage(), 10);
+
+        cache.resize(5);
+        assert_eq!(cache.capacity(), Some(5));
+        assert!(cache.usage() <= 5);
+        // The entries inserted after the shrink evict the older ones
+        for key in 10..20 {
+            cache.insert(key, Unit).await;
+            assert!(cache.usage() <= 5);
+            assert_eq!(cache.get(&key).await.unwrap(), Some(Unit));
+        }
+        assert_eq!(cache.get(&10).await.unwrap(), None);
+
+        cache.resize(10);
+        assert_eq!(cache.capacity(), Some(10));
+        for key in 20..30 {
+            cache.insert(key, Unit).await;
+        }
+        assert_eq!(cache.usage(), 10);
+    }
+
+    #[tokio::test]
+    async fn test_hybrid_cache_evicts_after_shrink() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = FoyerCacheConfig::parse_from([
+            "test",
+            "--dir",
+            dir.path().to_str().unwrap(),
+            "--mem",
+            "10",
+            "--disk",
+            "4",
+            "--file-size",
+            "1",
+            "--shards",
+            "1",
+        ]);
+        let cache = FoyerHybridCache::<u32, Unit>::hybrid(&config)
+            .await
+            .unwrap();
+        for key in 0..10 {
+            cache.insert(key, Unit).await;
+        }
+        assert_eq!(cache.usage(), 10);
+
+        cache.resize(5);
+        assert_eq!(cache.capacity(), Some(5));
+        // The memory tier makes room for the entries inserted after the shrink. It only
+        // releases them once they are written to disk
+        for key in 10..20 {
+            cache.insert(key, Unit).await;
+            assert_eq!(cache.get(&key).await.unwrap(), Some(Unit));
+        }
+        cache.cache.close().await.unwrap();
+        assert!(cache.usage() <= 5);
+
+        // The memory tier is never grown past the capacity it was built with
+        cache.resize(20);
+        assert_eq!(cache.capacity(), Some(10));
+    }
+}
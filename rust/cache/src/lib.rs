@@ -6,14 +6,18 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 mod foyer;
+mod metrics;
 mod nop;
 mod unbounded;
+mod weighted;
 
 use crate::nop::NopCache;
 use crate::unbounded::UnboundedCache;
 
 pub use foyer::FoyerCacheConfig;
+pub use metrics::CacheMetrics;
 pub use unbounded::UnboundedCacheConfig;
+pub use weighted::{MemoryBudgetConfig, WeightedCache, WeightedCacheConfig};
 
 /// A CacheError represents an error that occurred while interacting with a cache.
 ///
@@ -40,6 +44,7 @@ impl ChromaError for CacheError {
 /// "unbounded" is a cache that doesn't evict.
 /// "disk" is a foyer-backed cache that lives on disk.
 /// "memory" is a foyer-backed cache that lives in memory.
+/// "weighted" is a cache that lives in memory and is bounded by the bytes of its entries.
 #[derive(Deserialize, Debug, Clone, Serialize)]
 pub enum CacheConfig {
     // case-insensitive
@@ -52,10 +57,21 @@ pub enum CacheConfig {
     #[serde(alias = "lfu")]
     #[serde(alias = "weighted_lru")]
     Memory(FoyerCacheConfig),
+    #[serde(rename = "weighted")]
+    Weighted(WeightedCacheConfig),
     #[serde(rename = "nop")]
     Nop,
 }
 
+/// An entry that a cache holds in memory, without its value
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CachedEntry<K> {
+    pub key: K,
+    pub size_bytes: usize,
+    /// The partition that the entry is inserted on behalf of, if any
+    pub partition: Option<String>,
+}
+
 /// A cache offers async access.  It's unspecified whether this cache is persistent or not.
 #[async_trait::async_trait]
 pub trait Cache<K, V>: Send + Sync
@@ -64,9 +80,42 @@ where
     V: Clone + Send + Sync + Weighted + 'static,
 {
     async fn insert(&self, key: K, value: V);
+
+    /// Inserts the entry on behalf of a partition of the cache, e.g. a collection or a tenant.
+    /// A cache with partition quotas bounds the entries of each partition, the others ignore it
+    async fn insert_in_partition(&self, _partition: &str, key: K, value: V) {
+        self.insert(key, value).await
+    }
+
     async fn get(&self, key: &K) -> Result<Option<V>, CacheError>;
     async fn remove(&self, key: &K);
     async fn clear(&self) -> Result<(), CacheError>;
+
+    /// The weight of the entries that the cache holds in memory
+    fn usage(&self) -> usize {
+        0
+    }
+
+    /// The weight of the entries that the cache may hold in memory, if it is bounded
+    fn capacity(&self) -> Option<usize> {
+        None
+    }
+
+    /// Bounds the weight of the entries that the cache holds in memory, and evicts entries
+    /// until it holds no more. A cache that is not bounded ignores it
+    fn resize(&self, _capacity: usize) {}
+
+    /// Whether the usage and the capacity of the cache are in bytes, rather than in the
+    /// weights of the entries
+    fn weighs_bytes(&self) -> bool {
+        false
+    }
+
+    /// The largest entries that the cache holds in memory, up to the limit and the largest
+    /// first. A cache that cannot list its entries returns none
+    fn largest_entries(&self, _limit: usize) -> Vec<CachedEntry<K>> {
+        Vec::new()
+    }
 }
 
 /// A persistent cache extends the traits of a cache to require StorageKey and StorageValue.
@@ -80,6 +129,12 @@ where
 /// A trait to capture the weight of objects in the system.
 pub trait Weighted {
     fn weight(&self) -> usize;
+
+    /// The bytes that the object takes up in memory, which a weighted cache bounds. Defaults
+    /// to its weight
+    fn size_bytes(&self) -> usize {
+        self.weight()
+    }
 }
 
 /// Create a new cache from the provided config.  This is solely for caches that cannot implement
@@ -100,6 +155,9 @@ where
         CacheConfig::Disk(_) => Err(Box::new(CacheError::InvalidCacheConfig(
             "from_config_with_event_listener was called with disk".to_string(),
         ))),
+        CacheConfig::Weighted(_) => Err(Box::new(CacheError::InvalidCacheConfig(
+            "from_config_with_event_listener was called with weighted".to_string(),
+        ))),
         CacheConfig::Nop => Err(Box::new(CacheError::InvalidCacheConfig(
             "from_config_with_event_listener was called with nop".to_string(),
         ))),
@@ -120,6 +178,10 @@ where
             Ok(Box::new(UnboundedCache::new(unbounded_config)))
         }
         CacheConfig::Memory(c) => Ok(c.build_memory().await?),
+        CacheConfig::Weighted(c) => Ok(Box::new(
+            c.build::<K, V>()
+                .map_err(|e| Box::new(e) as Box<dyn ChromaError>)?,
+        )),
         CacheConfig::Disk(_) => Err(Box::new(CacheError::InvalidCacheConfig(
             "from_config was called with disk".to_string(),
         ))),
@@ -140,6 +202,10 @@ where
             Ok(Box::new(UnboundedCache::new(unbounded_config)))
         }
         CacheConfig::Memory(c) => Ok(c.build_memory_persistent().await?),
+        CacheConfig::Weighted(c) => Ok(Box::new(
+            c.build::<K, V>()
+                .map_err(|e| Box::new(e) as Box<dyn ChromaError>)?,
+        )),
         CacheConfig::Disk(c) => Ok(c.build_hybrid().await?),
         CacheConfig::Nop => Ok(Box::new(NopCache)),
     }
@@ -0,0 +1,39 @@
+use opentelemetry::global;
+use opentelemetry::metrics::Counter;
+use opentelemetry::KeyValue;
+
+/// The hits and the misses of the reads of a cache, by the type of the cache, e.g. "block",
+/// and by the partition that the cache is read on behalf of, e.g. a collection
+#[derive(Clone)]
+pub struct CacheMetrics {
+    cache_type: &'static str,
+    hits: Counter<u64>,
+    misses: Counter<u64>,
+}
+
+impl CacheMetrics {
+    pub fn new(cache_type: &'static str) -> Self {
+        let meter = global::meter("chroma");
+        Self {
+            cache_type,
+            hits: meter.u64_counter("cache_hits").init(),
+            misses: meter.u64_counter("cache_misses").init(),
+        }
+    }
+
+    fn attributes(&self, partition: Option<&str>) -> Vec<KeyValue> {
+        let mut attributes = vec![KeyValue::new("cache_type", self.cache_type)];
+        if let Some(partition) = partition {
+            attributes.push(KeyValue::new("partition", partition.to_string()));
+        }
+        attributes
+    }
+
+    pub fn record_hit(&self, partition: Option<&str>) {
+        self.hits.add(1, &self.attributes(partition));
+    }
+
+    pub fn record_miss(&self, partition: Option<&str>) {
+        self.misses.add(1, &self.attributes(partition));
+    }
+}
@@ -66,6 +66,10 @@ where
         self.cache.write().clear();
         Ok(())
     }
+
+    fn usage(&self) -> usize {
+        self.cache.read().values().map(Weighted::weight).sum()
+    }
 }
 
 impl<K, V> super::PersistentCache<K, V> for UnboundedCache<K, V>
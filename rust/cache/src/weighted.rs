@@ -0,0 +1,531 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock, Weak};
+
+use opentelemetry::global;
+use opentelemetry::metrics::{Counter, ObservableGauge};
+use opentelemetry::KeyValue;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use super::{CacheError, CachedEntry, StorageKey, StorageValue, Weighted};
+
+/// A bound on the bytes that several caches hold in memory together, e.g. the block and the
+/// root caches of a blockfile provider. The caches that name the same budget share it
+#[derive(Deserialize, Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct MemoryBudgetConfig {
+    pub name: String,
+    pub capacity_bytes: usize,
+}
+
+/// A cache that bounds the bytes of its entries in memory, and evicts the least recently used
+/// entries beyond them.
+/// - `capacity_bytes`: The bytes that the entries of the cache may take up
+/// - `budget`: The budget that the cache shares with other caches, if any. The cache also
+///   evicts its entries while the caches of the budget hold more than it
+/// - `max_partition_share`: The share of the capacity that the entries of one partition of
+///   the cache, e.g. a collection, may take up. A partition beyond its share evicts its own
+///   entries, so that it does not evict the entries of the others
+#[derive(Deserialize, Debug, Clone, Serialize)]
+pub struct WeightedCacheConfig {
+    pub capacity_bytes: usize,
+    #[serde(default)]
+    pub budget: Option<MemoryBudgetConfig>,
+    #[serde(default)]
+    pub max_partition_share: Option<f64>,
+}
+
+impl WeightedCacheConfig {
+    pub fn build<K, V>(&self) -> Result<WeightedCache<K, V>, CacheError>
+    where
+        K: Clone + Send + Sync + Eq + PartialEq + Hash + 'static,
+        V: Clone + Send + Sync + Weighted + 'static,
+    {
+        WeightedCache::new(self)
+    }
+}
+
+// The bytes that the entries of a partition may take up, if partitions are bounded
+fn partition_quota(capacity_bytes: usize, max_partition_share: Option<f64>) -> Option<usize> {
+    max_partition_share.map(|share| (capacity_bytes as f64 * share.clamp(0.0, 1.0)) as usize)
+}
+
+#[derive(Debug)]
+struct MemoryBudget {
+    capacity_bytes: usize,
+    usage_bytes: AtomicUsize,
+}
+
+impl MemoryBudget {
+    // The budget of the name, which is created by the first cache that names it
+    fn shared(config: &MemoryBudgetConfig) -> Result<Arc<MemoryBudget>, CacheError> {
+        static BUDGETS: OnceLock<Mutex<HashMap<String, Arc<MemoryBudget>>>> = OnceLock::new();
+        let mut budgets = BUDGETS.get_or_init(Default::default).lock();
+        let budget = budgets.entry(config.name.clone()).or_insert_with(|| {
+            Arc::new(MemoryBudget {
+                capacity_bytes: config.capacity_bytes,
+                usage_bytes: AtomicUsize::new(0),
+            })
+        });
+        if budget.capacity_bytes != config.capacity_bytes {
+            return Err(CacheError::InvalidCacheConfig(format!(
+                "memory budget {} is shared with capacity {} bytes, not {}",
+                config.name, budget.capacity_bytes, config.capacity_bytes
+            )));
+        }
+        Ok(budget.clone())
+    }
+
+    fn exceeded(&self) -> bool {
+        self.usage_bytes.load(Ordering::Relaxed) > self.capacity_bytes
+    }
+}
+
+struct Entry<V> {
+    value: V,
+    size_bytes: usize,
+    last_used: u64,
+    partition: Option<Arc<str>>,
+}
+
+// The entries that are inserted on behalf of a partition
+struct Partition<K> {
+    usage_bytes: usize,
+    recency: BTreeMap<u64, K>,
+}
+
+struct Inner<K, V> {
+    entries: HashMap<K, Entry<V>>,
+    // The keys by when they were last used, the least recently used first
+    recency: BTreeMap<u64, K>,
+    partitions: HashMap<Arc<str>, Partition<K>>,
+    clock: u64,
+    usage_bytes: usize,
+    capacity_bytes: usize,
+    max_partition_share: Option<f64>,
+    budget: Option<Arc<MemoryBudget>>,
+}
+
+impl<K, V> Inner<K, V>
+where
+    K: Clone + Eq + Hash,
+{
+    fn touch(&mut self, key: &K) -> Option<&Entry<V>> {
+        self.clock += 1;
+        let entry = self.entries.get_mut(key)?;
+        self.recency.remove(&entry.last_used);
+        self.recency.insert(self.clock, key.clone());
+        if let Some(partition) = entry
+            .partition
+            .as_ref()
+            .and_then(|partition| self.partitions.get_mut(partition))
+        {
+            partition.recency.remove(&entry.last_used);
+            partition.recency.insert(self.clock, key.clone());
+        }
+        entry.last_used = self.clock;
+        Some(entry)
+    }
+
+    // The partition of the name, sharing the name with its entries
+    fn partition_name(&self, name: &str) -> Arc<str> {
+        match self.partitions.get_key_value(name) {
+            Some((name, _)) => name.clone(),
+            None => Arc::from(name),
+        }
+    }
+
+    fn insert(&mut self, key: K, value: V, size_bytes: usize, partition: Option<Arc<str>>) {
+        self.remove(&key);
+        self.clock += 1;
+        self.recency.insert(self.clock, key.clone());
+        if let Some(name) = &partition {
+            let partition = self
+                .partitions
+                .entry(name.clone())
+                .or_insert_with(|| Partition {
+                    usage_bytes: 0,
+                    recency: BTreeMap::new(),
+                });
+            partition.usage_bytes += size_bytes;
+            partition.recency.insert(self.clock, key.clone());
+        }
+        self.entries.insert(
+            key,
+            Entry {
+                value,
+                size_bytes,
+                last_used: self.clock,
+                partition,
+            },
+        );
+        self.usage_bytes += size_bytes;
+        if let Some(budget) = &self.budget {
+            budget.usage_bytes.fetch_add(size_bytes, Ordering::Relaxed);
+        }
+    }
+
+    fn remove(&mut self, key: &K) -> Option<Entry<V>> {
+        let entry = self.entries.remove(key)?;
+        self.recency.remove(&entry.last_used);
+        if let Some(name) = &entry.partition {
+            if let Some(partition) = self.partitions.get_mut(name) {
+                partition.recency.remove(&entry.last_used);
+                partition.usage_bytes -= entry.size_bytes;
+                if partition.recency.is_empty() {
+                    self.partitions.remove(name);
+                }
+            }
+        }
+        self.release(entry.size_bytes);
+        Some(entry)
+    }
+
+    fn release(&mut self, size_bytes: usize) {
+        self.usage_bytes -= size_bytes;
+        if let Some(budget) = &self.budget {
+            budget.usage_bytes.fetch_sub(size_bytes, Ordering::Relaxed);
+        }
+    }
+
+    fn over_capacity(&self) -> bool {
+        self.usage_bytes > self.capacity_bytes
+            || self.budget.as_ref().is_some_and(|budget| budget.exceeded())
+    }
+
+    // The least recently used entry of the partition while it is beyond its quota. The quota
+    // only applies while the cache holds other entries, which it protects
+    fn partition_victim(&self, name: &str) -> Option<K> {
+        let quota = partition_quota(self.capacity_bytes, self.max_partition_share)?;
+        let partition = self.partitions.get(name)?;
+        if partition.usage_bytes <= quota || partition.usage_bytes >= self.usage_bytes {
+            return None;
+        }
+        partition.recency.values().next().cloned()
+    }
+
+    // Evicts the least recently used entries of the partition until it is within its quota,
+    // then those of the cache until the cache and its budget are within their capacity, or
+    // the cache is empty. Returns the evicted entries
+    fn evict(&mut self, partition: Option<&str>) -> Vec<Entry<V>> {
+        let mut evicted = Vec::new();
+        loop {
+            let victim = match partition.and_then(|name| self.partition_victim(name)) {
+                Some(key) => key,
+                None if self.over_capacity() => match self.recency.values().next() {
+                    Some(key) => key.clone(),
+                    None => break,
+                },
+                None => break,
+            };
+            let entry = self
+                .remove(&victim)
+                .expect("Invariant violation: the recency and the entries have the same keys");
+            evicted.push(entry);
+        }
+        evicted
+    }
+}
+
+impl<K, V> Drop for Inner<K, V> {
+    fn drop(&mut self) {
+        // The entries of a dropped cache no longer count against its budget
+        if let Some(budget) = &self.budget {
+            budget
+                .usage_bytes
+                .fetch_sub(self.usage_bytes, Ordering::Relaxed);
+        }
+    }
+}
+
+/// A least recently used cache that weighs its entries by their bytes in memory. An entry
+/// larger than the capacity is evicted as soon as it is inserted
+pub struct WeightedCache<K, V>
+where
+    K: Clone + Send + Sync + Eq + PartialEq + Hash + 'static,
+    V: Clone + Send + Sync + Weighted + 'static,
+{
+    inner: Arc<Mutex<Inner<K, V>>>,
+    evictions: Counter<u64>,
+    evicted_bytes: Counter<u64>,
+    _partition_usage: ObservableGauge<u64>,
+}
+
+impl<K, V> WeightedCache<K, V>
+where
+    K: Clone + Send + Sync + Eq + PartialEq + Hash + 'static,
+    V: Clone + Send + Sync + Weighted + 'static,
+{
+    pub fn new(config: &WeightedCacheConfig) -> Result<Self, CacheError> {
+        let budget = config
+            .budget
+            .as_ref()
+            .map(MemoryBudget::shared)
+            .transpose()?;
+        let inner = Arc::new(Mutex::new(Inner {
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            partitions: HashMap::new(),
+            clock: 0,
+            usage_bytes: 0,
+            capacity_bytes: config.capacity_bytes,
+            max_partition_share: config.max_partition_share,
+            budget,
+        }));
+        let meter = global::meter("chroma");
+        // The gauge does not keep the cache alive, so that its entries are released from the
+        // budget once it is dropped
+        let observed: Weak<Mutex<Inner<K, V>>> = Arc::downgrade(&inner);
+        let partition_usage = meter
+            .u64_observable_gauge("cache_partition_usage_bytes")
+            .with_callback(move |observer| {
+                if let Some(inner) = observed.upgrade() {
+                    for (name, partition) in &inner.lock().partitions {
+                        observer.observe(
+                            partition.usage_bytes as u64,
+                            &[KeyValue::new("partition", name.to_string())],
+                        );
+                    }
+                }
+            })
+            .init();
+        Ok(Self {
+            inner,
+            evictions: meter.u64_counter("cache_evictions").init(),
+            evicted_bytes: meter.u64_counter("cache_evicted_bytes").init(),
+            _partition_usage: partition_usage,
+        })
+    }
+
+    /// The bytes of the entries of the partition
+    pub fn partition_usage(&self, partition: &str) -> usize {
+        self.inner
+            .lock()
+            .partitions
+            .get(partition)
+            .map_or(0, |partition| partition.usage_bytes)
+    }
+
+    fn insert_into(&self, partition: Option<&str>, key: K, value: V) {
+        let size_bytes = value.size_bytes();
+        let mut inner = self.inner.lock();
+        let name = partition.map(|name| inner.partition_name(name));
+        inner.insert(key, value, size_bytes, name);
+        self.evict(&mut inner, partition);
+    }
+
+    // Evicts the entries beyond the quota of the partition and the capacity, and counts them
+    // by the partition that they were inserted on behalf of
+    fn evict(&self, inner: &mut Inner<K, V>, partition: Option<&str>) {
+        for entry in inner.evict(partition) {
+            let attributes = match &entry.partition {
+                Some(name) => vec![KeyValue::new("partition", name.to_string())],
+                None => Vec::new(),
+            };
+            self.evictions.add(1, &attributes);
+            self.evicted_bytes.add(entry.size_bytes as u64, &attributes);
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<K, V> super::Cache<K, V> for WeightedCache<K, V>
+where
+    K: Clone + Send + Sync + Eq + PartialEq + Hash + 'static,
+    V: Clone + Send + Sync + Weighted + 'static,
+{
+    async fn get(&self, key: &K) -> Result<Option<V>, CacheError> {
+        Ok(self
+            .inner
+            .lock()
+            .touch(key)
+            .map(|entry| entry.value.clone()))
+    }
+
+    async fn insert(&self, key: K, value: V) {
+        self.insert_into(None, key, value);
+    }
+
+    async fn insert_in_partition(&self, partition: &str, key: K, value: V) {
+        self.insert_into(Some(partition), key, value);
+    }
+
+    async fn remove(&self, key: &K) {
+        self.inner.lock().remove(key);
+    }
+
+    async fn clear(&self) -> Result<(), CacheError> {
+        let mut inner = self.inner.lock();
+        let usage_bytes = inner.usage_bytes;
+        inner.entries.clear();
+        inner.recency.clear();
+        inner.partitions.clear();
+        inner.release(usage_bytes);
+        Ok(())
+    }
+
+    fn usage(&self) -> usize {
+        self.inner.lock().usage_bytes
+    }
+
+    fn capacity(&self) -> Option<usize> {
+        Some(self.inner.lock().capacity_bytes)
+    }
+
+    fn resize(&self, capacity: usize) {
+        let mut inner = self.inner.lock();
+        inner.capacity_bytes = capacity;
+        self.evict(&mut inner, None);
+    }
+
+    fn weighs_bytes(&self) -> bool {
+        true
+    }
+
+    fn largest_entries(&self, limit: usize) -> Vec<CachedEntry<K>> {
+        let inner = self.inner.lock();
+        let mut entries = inner.entries.iter().collect::<Vec<_>>();
+        entries.sort_unstable_by_key(|(_, entry)| std::cmp::Reverse(entry.size_bytes));
+        entries
+            .into_iter()
+            .take(limit)
+            .map(|(key, entry)| CachedEntry {
+                key: key.clone(),
+                size_bytes: entry.size_bytes,
+                partition: entry.partition.as_ref().map(|name| name.to_string()),
+            })
+            .collect()
+    }
+}
+
+impl<K, V> super::PersistentCache<K, V> for WeightedCache<K, V>
+where
+    K: Clone + Send + Sync + Eq + PartialEq + Hash + StorageKey + 'static,
+    V: Clone + Send + Sync + Weighted + StorageValue + 'static,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MemoryBudgetConfig, WeightedCache, WeightedCacheConfig};
+    use crate::{Cache, Weighted};
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Bytes(usize);
+
+    impl Weighted for Bytes {
+        fn weight(&self) -> usize {
+            1
+        }
+
+        fn size_bytes(&self) -> usize {
+            self.0
+        }
+    }
+
+    #[tokio::test]
+    async fn test_evicts_least_recently_used() {
+        let cache = WeightedCache::<u32, Bytes>::new(&WeightedCacheConfig {
+            capacity_bytes: 100,
+            budget: None,
+            max_partition_share: None,
+        })
+        .unwrap();
+        cache.insert(1, Bytes(40)).await;
+        cache.insert(2, Bytes(40)).await;
+        // The first entry is used more recently than the second
+        assert_eq!(cache.get(&1).await.unwrap(), Some(Bytes(40)));
+        cache.insert(3, Bytes(40)).await;
+        assert_eq!(cache.usage(), 80);
+        assert_eq!(cache.get(&2).await.unwrap(), None);
+        assert!(cache.get(&1).await.unwrap().is_some());
+        assert!(cache.get(&3).await.unwrap().is_some());
+
+        // An entry larger than the cache is not kept
+        cache.insert(4, Bytes(200)).await;
+        assert_eq!(cache.get(&4).await.unwrap(), None);
+        assert_eq!(cache.usage(), 0);
+
+        cache.insert(5, Bytes(60)).await;
+        cache.insert(6, Bytes(30)).await;
+        cache.resize(50);
+        assert_eq!(cache.usage(), 30);
+        assert_eq!(cache.get(&5).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_shared_budget() {
+        let budget = MemoryBudgetConfig {
+            name: "test_shared_budget".to_string(),
+            capacity_bytes: 100,
+        };
+        let config = WeightedCacheConfig {
+            capacity_bytes: 80,
+            budget: Some(budget.clone()),
+            max_partition_share: None,
+        };
+        let blocks = WeightedCache::<u32, Bytes>::new(&config).unwrap();
+        let roots = WeightedCache::<u32, Bytes>::new(&config).unwrap();
+        blocks.insert(1, Bytes(70)).await;
+        roots.insert(1, Bytes(20)).await;
+        // The roots exceed the budget with the blocks, so they evict their own entries
+        roots.insert(2, Bytes(20)).await;
+        assert_eq!(blocks.usage(), 70);
+        assert_eq!(roots.usage(), 20);
+        assert_eq!(roots.get(&1).await.unwrap(), None);
+
+        // The entries of a dropped cache are released from the budget
+        drop(blocks);
+        roots.insert(3, Bytes(60)).await;
+        assert_eq!(roots.usage(), 80);
+
+        // A budget is shared with the capacity that it was created with
+        let config = WeightedCacheConfig {
+            capacity_bytes: 80,
+            budget: Some(MemoryBudgetConfig {
+                capacity_bytes: 200,
+                ..budget
+            }),
+            max_partition_share: None,
+        };
+        assert!(WeightedCache::<u32, Bytes>::new(&config).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_partition_quota() {
+        let cache = WeightedCache::<u32, Bytes>::new(&WeightedCacheConfig {
+            capacity_bytes: 100,
+            budget: None,
+            max_partition_share: Some(0.5),
+        })
+        .unwrap();
+        // A partition may take up the whole cache while it is the only one
+        cache.insert_in_partition("large", 1, Bytes(40)).await;
+        cache.insert_in_partition("large", 2, Bytes(40)).await;
+        assert_eq!(cache.partition_usage("large"), 80);
+
+        // Beyond its share, the large partition evicts its own entries rather than those of
+        // the small partition
+        cache.insert_in_partition("small", 3, Bytes(20)).await;
+        cache.insert_in_partition("large", 4, Bytes(20)).await;
+        assert_eq!(cache.partition_usage("large"), 20);
+        assert_eq!(cache.partition_usage("small"), 20);
+        assert_eq!(cache.get(&1).await.unwrap(), None);
+        assert_eq!(cache.get(&2).await.unwrap(), None);
+        assert!(cache.get(&3).await.unwrap().is_some());
+
+        // The partitions and the entries without one share the capacity
+        cache.insert(5, Bytes(50)).await;
+        assert_eq!(cache.usage(), 90);
+        let largest = cache.largest_entries(2);
+        assert_eq!((largest[0].key, largest[0].size_bytes), (5, 50));
+        assert_eq!(largest[0].partition, None);
+        assert_eq!(largest[1].size_bytes, 20);
+        assert!(largest[1].partition.is_some());
+        cache.remove(&3).await;
+        assert_eq!(cache.partition_usage("small"), 0);
+        cache.clear().await.unwrap();
+        assert_eq!(cache.partition_usage("large"), 0);
+    }
+}
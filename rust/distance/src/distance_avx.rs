@@ -345,3 +345,110 @@ pub unsafe fn euclidean_distance(a: &[f32], b: &[f32]) -> f32 {
     }
     result
 }
+
+#[cfg(all(target_feature = "avx", target_feature = "fma"))]
+pub unsafe fn hsum256_pd_avx(x: __m256d) -> f64 {
+    let x128: __m128d = _mm_add_pd(_mm256_extractf128_pd(x, 1), _mm256_castpd256_pd128(x));
+    let x64: __m128d = _mm_add_sd(x128, _mm_unpackhi_pd(x128, x128));
+    _mm_cvtsd_f64(x64)
+}
+
+#[cfg(all(target_feature = "avx", target_feature = "fma"))]
+pub unsafe fn inner_product_f64(a: &[f32], b: &[f32]) -> f32 {
+    let n = a.len();
+    let m = n - (n % 16);
+    let mut ptr1: *const f32 = a.as_ptr();
+    let mut ptr2: *const f32 = b.as_ptr();
+    let mut sum256_1: __m256d = _mm256_setzero_pd();
+    let mut sum256_2: __m256d = _mm256_setzero_pd();
+    let mut sum256_3: __m256d = _mm256_setzero_pd();
+    let mut sum256_4: __m256d = _mm256_setzero_pd();
+    let mut i: usize = 0;
+    while i < m {
+        sum256_1 = _mm256_fmadd_pd(
+            _mm256_cvtps_pd(_mm_loadu_ps(ptr1)),
+            _mm256_cvtps_pd(_mm_loadu_ps(ptr2)),
+            sum256_1,
+        );
+        sum256_2 = _mm256_fmadd_pd(
+            _mm256_cvtps_pd(_mm_loadu_ps(ptr1.add(4))),
+            _mm256_cvtps_pd(_mm_loadu_ps(ptr2.add(4))),
+            sum256_2,
+        );
+        sum256_3 = _mm256_fmadd_pd(
+            _mm256_cvtps_pd(_mm_loadu_ps(ptr1.add(8))),
+            _mm256_cvtps_pd(_mm_loadu_ps(ptr2.add(8))),
+            sum256_3,
+        );
+        sum256_4 = _mm256_fmadd_pd(
+            _mm256_cvtps_pd(_mm_loadu_ps(ptr1.add(12))),
+            _mm256_cvtps_pd(_mm_loadu_ps(ptr2.add(12))),
+            sum256_4,
+        );
+
+        ptr1 = ptr1.add(16);
+        ptr2 = ptr2.add(16);
+        i += 16;
+    }
+
+    let mut result = hsum256_pd_avx(sum256_1)
+        + hsum256_pd_avx(sum256_2)
+        + hsum256_pd_avx(sum256_3)
+        + hsum256_pd_avx(sum256_4);
+
+    for i in 0..n - m {
+        result += (*ptr1.add(i) as f64) * (*ptr2.add(i) as f64);
+    }
+    (1.0_f64 - result) as f32
+}
+
+#[cfg(all(target_feature = "avx", target_feature = "fma"))]
+pub unsafe fn euclidean_distance_f64(a: &[f32], b: &[f32]) -> f32 {
+    let n = a.len();
+    let m = n - (n % 16);
+    let mut ptr1: *const f32 = a.as_ptr();
+    let mut ptr2: *const f32 = b.as_ptr();
+    let mut sum256_1: __m256d = _mm256_setzero_pd();
+    let mut sum256_2: __m256d = _mm256_setzero_pd();
+    let mut sum256_3: __m256d = _mm256_setzero_pd();
+    let mut sum256_4: __m256d = _mm256_setzero_pd();
+    let mut i: usize = 0;
+    while i < m {
+        let sub256_1: __m256d = _mm256_sub_pd(
+            _mm256_cvtps_pd(_mm_loadu_ps(ptr1)),
+            _mm256_cvtps_pd(_mm_loadu_ps(ptr2)),
+        );
+        sum256_1 = _mm256_fmadd_pd(sub256_1, sub256_1, sum256_1);
+
+        let sub256_2: __m256d = _mm256_sub_pd(
+            _mm256_cvtps_pd(_mm_loadu_ps(ptr1.add(4))),
+            _mm256_cvtps_pd(_mm_loadu_ps(ptr2.add(4))),
+        );
+        sum256_2 = _mm256_fmadd_pd(sub256_2, sub256_2, sum256_2);
+
+        let sub256_3: __m256d = _mm256_sub_pd(
+            _mm256_cvtps_pd(_mm_loadu_ps(ptr1.add(8))),
+            _mm256_cvtps_pd(_mm_loadu_ps(ptr2.add(8))),
+        );
+        sum256_3 = _mm256_fmadd_pd(sub256_3, sub256_3, sum256_3);
+
+        let sub256_4: __m256d = _mm256_sub_pd(
+            _mm256_cvtps_pd(_mm_loadu_ps(ptr1.add(12))),
+            _mm256_cvtps_pd(_mm_loadu_ps(ptr2.add(12))),
+        );
+        sum256_4 = _mm256_fmadd_pd(sub256_4, sub256_4, sum256_4);
+
+        ptr1 = ptr1.add(16);
+        ptr2 = ptr2.add(16);
+        i += 16;
+    }
+
+    let mut result = hsum256_pd_avx(sum256_1)
+        + hsum256_pd_avx(sum256_2)
+        + hsum256_pd_avx(sum256_3)
+        + hsum256_pd_avx(sum256_4);
+    for i in 0..n - m {
+        result += (*ptr1.add(i) as f64 - *ptr2.add(i) as f64).powi(2);
+    }
+    result as f32
+}
@@ -295,3 +295,90 @@ pub unsafe fn euclidean_distance(a: &[f32], b: &[f32]) -> f32 {
     }
     result
 }
+
+#[cfg(target_feature = "neon")]
+pub unsafe fn inner_product_f64(a: &[f32], b: &[f32]) -> f32 {
+    let n = a.len();
+    let m = n - (n % 8);
+    let mut ptr1: *const f32 = a.as_ptr();
+    let mut ptr2: *const f32 = b.as_ptr();
+    let mut sum1 = vdupq_n_f64(0.);
+    let mut sum2 = vdupq_n_f64(0.);
+    let mut sum3 = vdupq_n_f64(0.);
+    let mut sum4 = vdupq_n_f64(0.);
+
+    let mut i: usize = 0;
+    while i < m {
+        let a1 = vld1q_f32(ptr1);
+        let b1 = vld1q_f32(ptr2);
+        let a2 = vld1q_f32(ptr1.add(4));
+        let b2 = vld1q_f32(ptr2.add(4));
+        sum1 = vfmaq_f64(
+            sum1,
+            vcvt_f64_f32(vget_low_f32(a1)),
+            vcvt_f64_f32(vget_low_f32(b1)),
+        );
+        sum2 = vfmaq_f64(sum2, vcvt_high_f64_f32(a1), vcvt_high_f64_f32(b1));
+        sum3 = vfmaq_f64(
+            sum3,
+            vcvt_f64_f32(vget_low_f32(a2)),
+            vcvt_f64_f32(vget_low_f32(b2)),
+        );
+        sum4 = vfmaq_f64(sum4, vcvt_high_f64_f32(a2), vcvt_high_f64_f32(b2));
+        ptr1 = ptr1.add(8);
+        ptr2 = ptr2.add(8);
+        i += 8;
+    }
+    let mut result = vaddvq_f64(sum1) + vaddvq_f64(sum2) + vaddvq_f64(sum3) + vaddvq_f64(sum4);
+    for i in 0..n - m {
+        result += (*ptr1.add(i) as f64) * (*ptr2.add(i) as f64);
+    }
+    (1.0_f64 - result) as f32
+}
+
+#[cfg(target_feature = "neon")]
+pub unsafe fn euclidean_distance_f64(a: &[f32], b: &[f32]) -> f32 {
+    let n = a.len();
+    let m = n - (n % 8);
+    let mut ptr1: *const f32 = a.as_ptr();
+    let mut ptr2: *const f32 = b.as_ptr();
+    let mut sum1 = vdupq_n_f64(0.);
+    let mut sum2 = vdupq_n_f64(0.);
+    let mut sum3 = vdupq_n_f64(0.);
+    let mut sum4 = vdupq_n_f64(0.);
+
+    let mut i: usize = 0;
+    while i < m {
+        let a1 = vld1q_f32(ptr1);
+        let b1 = vld1q_f32(ptr2);
+        let a2 = vld1q_f32(ptr1.add(4));
+        let b2 = vld1q_f32(ptr2.add(4));
+
+        let sub1 = vsubq_f64(
+            vcvt_f64_f32(vget_low_f32(a1)),
+            vcvt_f64_f32(vget_low_f32(b1)),
+        );
+        sum1 = vfmaq_f64(sum1, sub1, sub1);
+
+        let sub2 = vsubq_f64(vcvt_high_f64_f32(a1), vcvt_high_f64_f32(b1));
+        sum2 = vfmaq_f64(sum2, sub2, sub2);
+
+        let sub3 = vsubq_f64(
+            vcvt_f64_f32(vget_low_f32(a2)),
+            vcvt_f64_f32(vget_low_f32(b2)),
+        );
+        sum3 = vfmaq_f64(sum3, sub3, sub3);
+
+        let sub4 = vsubq_f64(vcvt_high_f64_f32(a2), vcvt_high_f64_f32(b2));
+        sum4 = vfmaq_f64(sum4, sub4, sub4);
+
+        ptr1 = ptr1.add(8);
+        ptr2 = ptr2.add(8);
+        i += 8;
+    }
+    let mut result = vaddvq_f64(sum1) + vaddvq_f64(sum2) + vaddvq_f64(sum3) + vaddvq_f64(sum4);
+    for i in 0..n - m {
+        result += (*ptr1.add(i) as f64 - *ptr2.add(i) as f64).powi(2);
+    }
+    result as f32
+}
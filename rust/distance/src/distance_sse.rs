@@ -341,3 +341,115 @@ pub unsafe fn euclidean_distance(a: &[f32], b: &[f32]) -> f32 {
     }
     result
 }
+
+#[cfg(target_feature = "sse2")]
+pub unsafe fn hsum128_pd_sse(x: __m128d) -> f64 {
+    let x64: __m128d = _mm_add_sd(x, _mm_unpackhi_pd(x, x));
+    _mm_cvtsd_f64(x64)
+}
+
+#[cfg(target_feature = "sse2")]
+pub unsafe fn inner_product_f64(a: &[f32], b: &[f32]) -> f32 {
+    let n = a.len();
+    let m = n - (n % 8);
+    let mut ptr1: *const f32 = a.as_ptr();
+    let mut ptr2: *const f32 = b.as_ptr();
+    let mut sum128_1: __m128d = _mm_setzero_pd();
+    let mut sum128_2: __m128d = _mm_setzero_pd();
+    let mut sum128_3: __m128d = _mm_setzero_pd();
+    let mut sum128_4: __m128d = _mm_setzero_pd();
+
+    let mut i: usize = 0;
+    while i < m {
+        let a128_1 = _mm_loadu_ps(ptr1);
+        let b128_1 = _mm_loadu_ps(ptr2);
+        let a128_2 = _mm_loadu_ps(ptr1.add(4));
+        let b128_2 = _mm_loadu_ps(ptr2.add(4));
+
+        sum128_1 = _mm_add_pd(
+            _mm_mul_pd(_mm_cvtps_pd(a128_1), _mm_cvtps_pd(b128_1)),
+            sum128_1,
+        );
+        sum128_2 = _mm_add_pd(
+            _mm_mul_pd(
+                _mm_cvtps_pd(_mm_movehl_ps(a128_1, a128_1)),
+                _mm_cvtps_pd(_mm_movehl_ps(b128_1, b128_1)),
+            ),
+            sum128_2,
+        );
+        sum128_3 = _mm_add_pd(
+            _mm_mul_pd(_mm_cvtps_pd(a128_2), _mm_cvtps_pd(b128_2)),
+            sum128_3,
+        );
+        sum128_4 = _mm_add_pd(
+            _mm_mul_pd(
+                _mm_cvtps_pd(_mm_movehl_ps(a128_2, a128_2)),
+                _mm_cvtps_pd(_mm_movehl_ps(b128_2, b128_2)),
+            ),
+            sum128_4,
+        );
+
+        ptr1 = ptr1.add(8);
+        ptr2 = ptr2.add(8);
+        i += 8;
+    }
+
+    let mut result = hsum128_pd_sse(sum128_1)
+        + hsum128_pd_sse(sum128_2)
+        + hsum128_pd_sse(sum128_3)
+        + hsum128_pd_sse(sum128_4);
+    for i in 0..n - m {
+        result += (*ptr1.add(i) as f64) * (*ptr2.add(i) as f64);
+    }
+    (1.0_f64 - result) as f32
+}
+
+#[cfg(target_feature = "sse2")]
+pub unsafe fn euclidean_distance_f64(a: &[f32], b: &[f32]) -> f32 {
+    let n = a.len();
+    let m = n - (n % 8);
+    let mut ptr1: *const f32 = a.as_ptr();
+    let mut ptr2: *const f32 = b.as_ptr();
+    let mut sum128_1: __m128d = _mm_setzero_pd();
+    let mut sum128_2: __m128d = _mm_setzero_pd();
+    let mut sum128_3: __m128d = _mm_setzero_pd();
+    let mut sum128_4: __m128d = _mm_setzero_pd();
+    let mut i: usize = 0;
+    while i < m {
+        let a128_1 = _mm_loadu_ps(ptr1);
+        let b128_1 = _mm_loadu_ps(ptr2);
+        let a128_2 = _mm_loadu_ps(ptr1.add(4));
+        let b128_2 = _mm_loadu_ps(ptr2.add(4));
+
+        let sub128_1 = _mm_sub_pd(_mm_cvtps_pd(a128_1), _mm_cvtps_pd(b128_1));
+        sum128_1 = _mm_add_pd(_mm_mul_pd(sub128_1, sub128_1), sum128_1);
+
+        let sub128_2 = _mm_sub_pd(
+            _mm_cvtps_pd(_mm_movehl_ps(a128_1, a128_1)),
+            _mm_cvtps_pd(_mm_movehl_ps(b128_1, b128_1)),
+        );
+        sum128_2 = _mm_add_pd(_mm_mul_pd(sub128_2, sub128_2), sum128_2);
+
+        let sub128_3 = _mm_sub_pd(_mm_cvtps_pd(a128_2), _mm_cvtps_pd(b128_2));
+        sum128_3 = _mm_add_pd(_mm_mul_pd(sub128_3, sub128_3), sum128_3);
+
+        let sub128_4 = _mm_sub_pd(
+            _mm_cvtps_pd(_mm_movehl_ps(a128_2, a128_2)),
+            _mm_cvtps_pd(_mm_movehl_ps(b128_2, b128_2)),
+        );
+        sum128_4 = _mm_add_pd(_mm_mul_pd(sub128_4, sub128_4), sum128_4);
+
+        ptr1 = ptr1.add(8);
+        ptr2 = ptr2.add(8);
+        i += 8;
+    }
+
+    let mut result = hsum128_pd_sse(sum128_1)
+        + hsum128_pd_sse(sum128_2)
+        + hsum128_pd_sse(sum128_3)
+        + hsum128_pd_sse(sum128_4);
+    for i in 0..n - m {
+        result += (*ptr1.add(i) as f64 - *ptr2.add(i) as f64).powi(2);
+    }
+    result as f32
+}
@@ -330,6 +330,108 @@ impl DistanceFunction {
     }
 }
 
+/// The precision in which the distance between two vectors is accumulated.
+/// # Description
+/// The vectors are stored in single precision, but the sum over their dimensions can
+/// be accumulated in double precision to reduce the rounding error.
+/// # Variants
+/// - `F32` - Accumulate in single precision. This is the fastest.
+/// - `F64` - Accumulate in double precision. The distances of high dimensional
+///   vectors are more accurate, so records at almost the same distance are ranked stably.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum DistanceAccumulation {
+    #[default]
+    F32,
+    F64,
+}
+
+impl DistanceFunction {
+    /// Computes the distance between two vectors, accumulating in the given precision.
+    pub fn distance_with_accumulation(
+        &self,
+        a: &[f32],
+        b: &[f32],
+        accumulation: DistanceAccumulation,
+    ) -> f32 {
+        match accumulation {
+            DistanceAccumulation::F32 => self.distance(a, b),
+            DistanceAccumulation::F64 => self.distance_f64(a, b),
+        }
+    }
+
+    fn distance_f64(&self, a: &[f32], b: &[f32]) -> f32 {
+        match self {
+            DistanceFunction::Euclidean => {
+                #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+                {
+                    if std::arch::is_aarch64_feature_detected!("neon") {
+                        return unsafe { crate::distance_neon::euclidean_distance_f64(a, b) };
+                    }
+                }
+                #[cfg(all(
+                    target_arch = "x86_64",
+                    all(target_feature = "avx", target_feature = "fma")
+                ))]
+                {
+                    if std::arch::is_x86_feature_detected!("avx")
+                        && std::arch::is_x86_feature_detected!("fma")
+                    {
+                        return unsafe { crate::distance_avx::euclidean_distance_f64(a, b) };
+                    }
+                }
+                #[cfg(all(
+                    any(target_arch = "x86_64", target_arch = "x86"),
+                    target_feature = "sse2"
+                ))]
+                {
+                    if std::arch::is_x86_feature_detected!("sse2") {
+                        return unsafe { crate::distance_sse::euclidean_distance_f64(a, b) };
+                    }
+                }
+                a.iter()
+                    .zip(b)
+                    .map(|(x, y)| (*x as f64 - *y as f64).powi(2))
+                    .sum::<f64>() as f32
+            }
+            // For cosine we just assume the vectors have been normalized, like in `distance`.
+            DistanceFunction::Cosine | DistanceFunction::InnerProduct => {
+                #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+                {
+                    if std::arch::is_aarch64_feature_detected!("neon") {
+                        return unsafe { crate::distance_neon::inner_product_f64(a, b) };
+                    }
+                }
+                #[cfg(all(
+                    target_arch = "x86_64",
+                    all(target_feature = "avx", target_feature = "fma")
+                ))]
+                {
+                    if std::arch::is_x86_feature_detected!("avx")
+                        && std::arch::is_x86_feature_detected!("fma")
+                    {
+                        return unsafe { crate::distance_avx::inner_product_f64(a, b) };
+                    }
+                }
+                #[cfg(all(
+                    any(target_arch = "x86_64", target_arch = "x86"),
+                    target_feature = "sse2"
+                ))]
+                {
+                    if std::arch::is_x86_feature_detected!("sse2") {
+                        return unsafe { crate::distance_sse::inner_product_f64(a, b) };
+                    }
+                }
+                let sum = a
+                    .iter()
+                    .zip(b)
+                    .map(|(x, y)| *x as f64 * *y as f64)
+                    .sum::<f64>();
+                (1.0_f64 - sum) as f32
+            }
+        }
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum DistanceFunctionError {
     #[error("Invalid distance function `{0}`")]
@@ -418,4 +520,48 @@ mod tests {
             inner_product_sim
         );
     }
+
+    #[test]
+    fn test_distance_f64_accumulation() {
+        // The dimension is not a multiple of any lane width, so that the tail is also summed
+        let dimension = 1003;
+        let a = (0..dimension)
+            .map(|i| ((i * 7919) % 1000) as f32 / 1000.0)
+            .collect::<Vec<_>>();
+        let b = (0..dimension)
+            .map(|i| ((i * 104729) % 1000) as f32 / 1000.0)
+            .collect::<Vec<_>>();
+
+        let l2_sqr = a
+            .iter()
+            .zip(b.iter())
+            .map(|(a, b)| (*a as f64 - *b as f64).powi(2))
+            .sum::<f64>();
+        let dot = a
+            .iter()
+            .zip(b.iter())
+            .map(|(a, b)| *a as f64 * *b as f64)
+            .sum::<f64>();
+
+        let accumulation = DistanceAccumulation::F64;
+        let tolerance = |expected: f64| expected.abs() * f32::EPSILON as f64;
+        let l2_distance =
+            DistanceFunction::Euclidean.distance_with_accumulation(&a, &b, accumulation);
+        assert!((l2_distance as f64 - l2_sqr).abs() <= tolerance(l2_sqr));
+        let ip_distance =
+            DistanceFunction::InnerProduct.distance_with_accumulation(&a, &b, accumulation);
+        assert!((ip_distance as f64 - (1.0 - dot)).abs() <= tolerance(1.0 - dot));
+        assert_eq!(
+            DistanceFunction::Cosine.distance_with_accumulation(&a, &b, accumulation),
+            ip_distance
+        );
+        assert_eq!(
+            DistanceFunction::Euclidean.distance_with_accumulation(
+                &a,
+                &b,
+                DistanceAccumulation::F32
+            ),
+            DistanceFunction::Euclidean.distance(&a, &b)
+        );
+    }
 }
@@ -3,7 +3,7 @@
 // Custom errors can use these codes in order to allow for generic handling
 use std::error::Error;
 
-#[derive(PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum ErrorCodes {
     // OK is returned on success, we use "Success" since Ok is a keyword in Rust.
     Success = 0,
@@ -43,8 +43,101 @@ pub enum ErrorCodes {
     VersionMismatch = 17,
 }
 
+impl ErrorCodes {
+    // Whether the error is transient, so that the same request may succeed if it is retried.
+    // A version mismatch is resolved by retrying after the collection is refreshed.
+    pub fn is_retryable(&self) -> bool {
+        self.is_transient()
+            || matches!(
+                self,
+                ErrorCodes::DeadlineExceeded
+                    | ErrorCodes::ResourceExhausted
+                    | ErrorCodes::VersionMismatch
+            )
+    }
+
+    // Whether the error is caused by a transient failure of a dependency, e.g. the storage,
+    // so that the same operation may succeed if it is retried right away. Unlike the other
+    // retryable errors, it does not depend on the deadline, the load or the state of the request.
+    pub fn is_transient(&self) -> bool {
+        matches!(self, ErrorCodes::Unavailable)
+    }
+
+    // Whether the error is caused by the request of the user, rather than by the system.
+    pub fn is_user_error(&self) -> bool {
+        matches!(
+            self,
+            ErrorCodes::InvalidArgument
+                | ErrorCodes::NotFound
+                | ErrorCodes::AlreadyExists
+                | ErrorCodes::PermissionDenied
+                | ErrorCodes::FailedPrecondition
+                | ErrorCodes::OutOfRange
+                | ErrorCodes::Unauthenticated
+        )
+    }
+}
+
+// Where in the data of a collection an error occurred, so that an error on a block that is
+// missing can be told apart from a transient error on any block.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ErrorLocation {
+    // The segment that was read or written.
+    pub segment_id: Option<String>,
+    // The block of a blockfile that was read.
+    pub block_id: Option<String>,
+}
+
+impl ErrorLocation {
+    pub fn segment(segment_id: impl ToString) -> Self {
+        ErrorLocation {
+            segment_id: Some(segment_id.to_string()),
+            block_id: None,
+        }
+    }
+
+    pub fn block(block_id: impl ToString) -> Self {
+        ErrorLocation {
+            segment_id: None,
+            block_id: Some(block_id.to_string()),
+        }
+    }
+
+    // Adds the segment to the location of an error that occurred in it.
+    pub fn in_segment(self, segment_id: impl ToString) -> Self {
+        ErrorLocation {
+            segment_id: Some(segment_id.to_string()),
+            ..self
+        }
+    }
+}
+
 pub trait ChromaError: Error + Send {
     fn code(&self) -> ErrorCodes;
+
+    // The classification defaults to the one of the error code. Errors that wrap other errors
+    // should forward these to the wrapped error, so that they are propagated to the caller.
+    fn is_retryable(&self) -> bool {
+        self.code().is_retryable()
+    }
+
+    fn is_user_error(&self) -> bool {
+        self.code().is_user_error()
+    }
+
+    fn is_transient(&self) -> bool {
+        self.code().is_transient()
+    }
+
+    // The field of the request that caused the error, if it is known.
+    fn field(&self) -> Option<&str> {
+        None
+    }
+
+    // The segment and the block that the error occurred in, as far as they are known.
+    fn location(&self) -> ErrorLocation {
+        ErrorLocation::default()
+    }
 }
 
 impl Error for Box<dyn ChromaError> {}
@@ -53,6 +146,26 @@ impl ChromaError for Box<dyn ChromaError> {
     fn code(&self) -> ErrorCodes {
         self.as_ref().code()
     }
+
+    fn is_retryable(&self) -> bool {
+        self.as_ref().is_retryable()
+    }
+
+    fn is_user_error(&self) -> bool {
+        self.as_ref().is_user_error()
+    }
+
+    fn is_transient(&self) -> bool {
+        self.as_ref().is_transient()
+    }
+
+    fn field(&self) -> Option<&str> {
+        self.as_ref().field()
+    }
+
+    fn location(&self) -> ErrorLocation {
+        self.as_ref().location()
+    }
 }
 
 impl From<ErrorCodes> for tonic::Code {
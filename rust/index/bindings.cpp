@@ -24,6 +24,70 @@ public:
     }
 };
 
+// Distance functions over int8 scalar quantized vectors. The distances are only used
+// to rank the candidates, so they are computed in the quantized domain.
+static float Int8L2Sqr(const void *pVect1v, const void *pVect2v, const void *qty_ptr)
+{
+    const int8_t *pVect1 = (const int8_t *)pVect1v;
+    const int8_t *pVect2 = (const int8_t *)pVect2v;
+    size_t qty = *((size_t *)qty_ptr);
+
+    int32_t res = 0;
+    for (size_t i = 0; i < qty; i++)
+    {
+        int32_t t = (int32_t)pVect1[i] - (int32_t)pVect2[i];
+        res += t * t;
+    }
+    return (float)res;
+}
+
+static float Int8InnerProductDistance(const void *pVect1v, const void *pVect2v, const void *qty_ptr)
+{
+    const int8_t *pVect1 = (const int8_t *)pVect1v;
+    const int8_t *pVect2 = (const int8_t *)pVect2v;
+    size_t qty = *((size_t *)qty_ptr);
+
+    int32_t res = 0;
+    for (size_t i = 0; i < qty; i++)
+    {
+        res += (int32_t)pVect1[i] * (int32_t)pVect2[i];
+    }
+    return -(float)res;
+}
+
+// A space storing one byte per dimension. The vectors are quantized by the caller.
+class Int8Space : public hnswlib::SpaceInterface<float>
+{
+    hnswlib::DISTFUNC<float> fstdistfunc_;
+    size_t data_size_;
+    size_t dim_;
+
+public:
+    Int8Space(size_t dim, bool inner_product)
+    {
+        fstdistfunc_ = inner_product ? Int8InnerProductDistance : Int8L2Sqr;
+        dim_ = dim;
+        data_size_ = dim * sizeof(int8_t);
+    }
+
+    size_t get_data_size()
+    {
+        return data_size_;
+    }
+
+    hnswlib::DISTFUNC<float> get_dist_func()
+    {
+        return fstdistfunc_;
+    }
+
+    void *get_dist_func_param()
+    {
+        return &dim_;
+    }
+
+    ~Int8Space() {}
+};
+
 // thread-local for the last error message, callers are expected to check this
 // the empty string represents no error
 // this is currently shared across all instances of Index, but that's fine for now
@@ -39,6 +103,7 @@ public:
     size_t seed;
 
     bool normalize;
+    bool quantized;
     bool index_inited;
 
     hnswlib::HierarchicalNSW<dist_t> *appr_alg;
@@ -46,6 +111,7 @@ public:
 
     Index(const std::string &space_name, const int dim) : space_name(space_name), dim(dim)
     {
+        quantized = false;
         if (space_name == "l2")
         {
             l2space = new hnswlib::L2Space(dim);
@@ -62,6 +128,19 @@ public:
             l2space = new hnswlib::InnerProductSpace(dim);
             normalize = true;
         }
+        // The quantized spaces expect the caller to normalize the vectors before quantizing them
+        if (space_name == "l2_int8")
+        {
+            l2space = new Int8Space(dim, false);
+            normalize = false;
+            quantized = true;
+        }
+        if (space_name == "ip_int8" || space_name == "cosine_int8")
+        {
+            l2space = new Int8Space(dim, true);
+            normalize = false;
+            quantized = true;
+        }
         appr_alg = NULL;
         index_inited = false;
         last_error.clear();
@@ -116,6 +195,20 @@ public:
         appr_alg->addPoint(data, id);
     }
 
+    void add_item_int8(const int8_t *data, const hnswlib::labeltype id, const bool replace_deleted = false)
+    {
+        if (!index_inited)
+        {
+            throw std::runtime_error("Index not inited");
+        }
+        if (!quantized)
+        {
+            throw std::runtime_error("Index is not quantized");
+        }
+
+        appr_alg->addPoint(data, id);
+    }
+
     void get_item(const hnswlib::labeltype id, data_t *data)
     {
         if (!index_inited)
@@ -129,6 +222,23 @@ public:
         }
     }
 
+    void get_item_int8(const hnswlib::labeltype id, int8_t *data)
+    {
+        if (!index_inited)
+        {
+            throw std::runtime_error("Index not inited");
+        }
+        if (!quantized)
+        {
+            throw std::runtime_error("Index is not quantized");
+        }
+        std::vector<int8_t> ret_data = appr_alg->template getDataByLabel<int8_t>(id); // This checks if id is deleted
+        for (int i = 0; i < dim; i++)
+        {
+            data[i] = ret_data[i];
+        }
+    }
+
     void mark_deleted(const hnswlib::labeltype id)
     {
         if (!index_inited)
@@ -138,7 +248,7 @@ public:
         appr_alg->markDelete(id);
     }
 
-    size_t knn_query(const data_t *query_vector, const size_t k, hnswlib::labeltype *ids, data_t *distance, const hnswlib::labeltype *allowed_ids, const size_t allowed_id_length, const hnswlib::labeltype *disallowed_ids, const size_t disallowed_id_length)
+    size_t knn_query(const void *query_vector, const size_t k, hnswlib::labeltype *ids, data_t *distance, const hnswlib::labeltype *allowed_ids, const size_t allowed_id_length, const hnswlib::labeltype *disallowed_ids, const size_t disallowed_id_length)
     {
         if (!index_inited)
         {
@@ -307,6 +417,36 @@ extern "C"
         last_error.clear();
     }
 
+    // Can throw std::exception
+    void add_item_int8(Index<float> *index, const int8_t *data, const hnswlib::labeltype id, const bool replace_deleted)
+    {
+        try
+        {
+            index->add_item_int8(data, id, replace_deleted);
+        }
+        catch (std::exception &e)
+        {
+            last_error = e.what();
+            return;
+        }
+        last_error.clear();
+    }
+
+    // Can throw std::exception
+    void get_item_int8(Index<float> *index, const hnswlib::labeltype id, int8_t *data)
+    {
+        try
+        {
+            index->get_item_int8(id, data);
+        }
+        catch (std::exception &e)
+        {
+            last_error = e.what();
+            return;
+        }
+        last_error.clear();
+    }
+
     // Can throw std::exception
     void mark_deleted(Index<float> *index, const hnswlib::labeltype id)
     {
@@ -339,6 +479,27 @@ extern "C"
         return result;
     }
 
+    // Can throw std::exception
+    size_t knn_query_int8(Index<float> *index, const int8_t *query_vector, const size_t k, hnswlib::labeltype *ids, float *distance, const hnswlib::labeltype *allowed_ids, const size_t allowed_id_length, const hnswlib::labeltype *disallowed_ids, const size_t disallowed_id_length)
+    {
+        size_t result;
+        try
+        {
+            if (!index->quantized)
+            {
+                throw std::runtime_error("Index is not quantized");
+            }
+            result = index->knn_query(query_vector, k, ids, distance, allowed_ids, allowed_id_length, disallowed_ids, disallowed_id_length);
+        }
+        catch (std::exception &e)
+        {
+            last_error = e.what();
+            return 0;
+        }
+        last_error.clear();
+        return result;
+    }
+
     // Can throw std::exception
     int get_ef(Index<float> *index)
     {
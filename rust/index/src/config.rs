@@ -5,4 +5,8 @@ use serde::Deserialize;
 pub struct HnswProviderConfig {
     pub hnsw_temporary_path: String,
     pub hnsw_cache_config: CacheConfig,
+    /// Whether the index files are flushed as content-defined chunks, so that only the chunks
+    /// that changed since the index was forked are uploaded
+    #[serde(default)]
+    pub hnsw_delta_upload: bool,
 }
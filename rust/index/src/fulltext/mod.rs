@@ -1,2 +1,3 @@
+pub mod tokenizer;
 pub mod types;
 mod util;
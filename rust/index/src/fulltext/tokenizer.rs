@@ -0,0 +1,242 @@
+use chroma_error::{ChromaError, ErrorCodes};
+use rust_stemmers::{Algorithm, Stemmer};
+use std::borrow::Cow;
+use std::collections::HashSet;
+use thiserror::Error;
+
+/// The stopwords removed by the `english` stopword list.
+pub const ENGLISH_STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "but", "by", "for", "if", "in", "into", "is", "it",
+    "no", "not", "of", "on", "or", "such", "that", "the", "their", "then", "there", "these",
+    "they", "this", "to", "was", "will", "with",
+];
+
+#[derive(Error, Debug)]
+pub enum TokenizerConfigError {
+    #[error("Unsupported stemmer language: {0}")]
+    UnsupportedStemmer(String),
+}
+
+impl ChromaError for TokenizerConfigError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            TokenizerConfigError::UnsupportedStemmer(_) => ErrorCodes::InvalidArgument,
+        }
+    }
+}
+
+/// The normalization applied to documents and queries before they are split into the n-grams
+/// of the full-text index. Documents and queries must be normalized with the same configuration.
+///
+/// The default configuration leaves the text unchanged. Otherwise the text is split into words
+/// of alphanumeric characters, which are lowercased, filtered against the stopwords and stemmed
+/// in this order, and then joined with single spaces.
+///
+/// With `cjk_bigrams`, runs of CJK characters are segmented into overlapping bigrams instead.
+/// Each bigram is followed by an extra space, so that it is covered by a trigram of its own.
+#[derive(Clone, Debug, Default)]
+pub struct TokenizerConfig {
+    pub lowercase: bool,
+    pub stopwords: HashSet<String>,
+    pub stemmer: Option<Algorithm>,
+    pub cjk_bigrams: bool,
+}
+
+impl TokenizerConfig {
+    /// Parses a stopword list, which is either the name of a builtin list or comma separated words.
+    pub fn parse_stopwords(stopwords: &str) -> HashSet<String> {
+        match stopwords {
+            "english" => ENGLISH_STOPWORDS
+                .iter()
+                .map(|word| word.to_string())
+                .collect(),
+            _ => stopwords
+                .split(',')
+                .map(str::trim)
+                .filter(|word| !word.is_empty())
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+
+    pub fn parse_stemmer(language: &str) -> Result<Algorithm, TokenizerConfigError> {
+        match language {
+            "arabic" => Ok(Algorithm::Arabic),
+            "danish" => Ok(Algorithm::Danish),
+            "dutch" => Ok(Algorithm::Dutch),
+            "english" => Ok(Algorithm::English),
+            "finnish" => Ok(Algorithm::Finnish),
+            "french" => Ok(Algorithm::French),
+            "german" => Ok(Algorithm::German),
+            "greek" => Ok(Algorithm::Greek),
+            "hungarian" => Ok(Algorithm::Hungarian),
+            "italian" => Ok(Algorithm::Italian),
+            "norwegian" => Ok(Algorithm::Norwegian),
+            "portuguese" => Ok(Algorithm::Portuguese),
+            "romanian" => Ok(Algorithm::Romanian),
+            "russian" => Ok(Algorithm::Russian),
+            "spanish" => Ok(Algorithm::Spanish),
+            "swedish" => Ok(Algorithm::Swedish),
+            "tamil" => Ok(Algorithm::Tamil),
+            "turkish" => Ok(Algorithm::Turkish),
+            _ => Err(TokenizerConfigError::UnsupportedStemmer(
+                language.to_string(),
+            )),
+        }
+    }
+
+    /// Checks whether the configuration leaves the text unchanged, in which case the full-text
+    /// index contains the trigrams of the original documents.
+    pub fn is_identity(&self) -> bool {
+        !self.lowercase && self.stopwords.is_empty() && self.stemmer.is_none() && !self.cjk_bigrams
+    }
+
+    pub fn normalize<'text>(&self, text: &'text str) -> Cow<'text, str> {
+        if self.is_identity() {
+            return Cow::Borrowed(text);
+        }
+
+        let stemmer = self.stemmer.map(Stemmer::create);
+        let mut normalized = String::with_capacity(text.len());
+        for word in text
+            .split(|character: char| !character.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+        {
+            if !self.cjk_bigrams {
+                self.push_word(&mut normalized, word, stemmer.as_ref());
+                continue;
+            }
+
+            // Split the word into runs of CJK and other characters
+            let mut run_start = 0;
+            let mut run_is_cjk = None;
+            for (offset, character) in word.char_indices() {
+                let is_cjk = is_cjk(character);
+                if run_is_cjk.is_some_and(|run_is_cjk| run_is_cjk != is_cjk) {
+                    self.push_run(&mut normalized, &word[run_start..offset], stemmer.as_ref());
+                    run_start = offset;
+                }
+                run_is_cjk = Some(is_cjk);
+            }
+            self.push_run(&mut normalized, &word[run_start..], stemmer.as_ref());
+        }
+        Cow::Owned(normalized)
+    }
+
+    /// Checks whether the normalized document contains the normalized query. This matches
+    /// `FullTextIndexReader::search` for documents that are not indexed yet.
+    pub fn contains(&self, document: &str, query: &str) -> bool {
+        self.normalize(document)
+            .contains(self.normalize(query).as_ref())
+    }
+
+    fn push_run(&self, normalized: &mut String, run: &str, stemmer: Option<&Stemmer>) {
+        if !run.starts_with(is_cjk) {
+            self.push_word(normalized, run, stemmer);
+            return;
+        }
+
+        let characters = run.char_indices().collect::<Vec<_>>();
+        if characters.len() == 1 {
+            push_separated(normalized, run);
+            normalized.push(' ');
+            return;
+        }
+        for window in characters.windows(2) {
+            let end = window[1].0 + window[1].1.len_utf8();
+            push_separated(normalized, &run[window[0].0..end]);
+            normalized.push(' ');
+        }
+    }
+
+    fn push_word(&self, normalized: &mut String, word: &str, stemmer: Option<&Stemmer>) {
+        let word = if self.lowercase {
+            Cow::Owned(word.to_lowercase())
+        } else {
+            Cow::Borrowed(word)
+        };
+        if self.stopwords.contains(word.as_ref()) {
+            return;
+        }
+        match stemmer {
+            Some(stemmer) => push_separated(normalized, &stemmer.stem(&word)),
+            None => push_separated(normalized, &word),
+        }
+    }
+}
+
+fn push_separated(normalized: &mut String, word: &str) {
+    if !normalized.is_empty() {
+        normalized.push(' ');
+    }
+    normalized.push_str(word);
+}
+
+/// Checks whether the character belongs to a script written without spaces between words.
+fn is_cjk(character: char) -> bool {
+    matches!(
+        character,
+        '\u{3040}'..='\u{30FF}'
+            | '\u{3400}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{AC00}'..='\u{D7AF}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '\u{20000}'..='\u{2FA1F}'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config_is_identity() {
+        let config = TokenizerConfig::default();
+        assert_eq!(
+            config.normalize("The Quick-Brown fox"),
+            "The Quick-Brown fox"
+        );
+    }
+
+    #[test]
+    fn test_lowercase_stopwords_and_stemming() {
+        let config = TokenizerConfig {
+            lowercase: true,
+            stopwords: TokenizerConfig::parse_stopwords("english"),
+            stemmer: Some(TokenizerConfig::parse_stemmer("english").unwrap()),
+            cjk_bigrams: false,
+        };
+        assert_eq!(
+            config.normalize("The Dogs are RUNNING, jumping!"),
+            "dog run jump"
+        );
+        assert!(config.contains("The dogs were running", "Running"));
+        assert!(!config.contains("The dogs were running", "cats"));
+    }
+
+    #[test]
+    fn test_cjk_bigrams() {
+        let config = TokenizerConfig {
+            cjk_bigrams: true,
+            ..Default::default()
+        };
+        assert_eq!(config.normalize("中华人民"), "中华  华人  人民 ");
+        assert_eq!(config.normalize("hello世界"), "hello 世界 ");
+        assert_eq!(config.normalize("世"), "世 ");
+        assert!(config.contains("中华人民共和国", "人民"));
+        assert!(config.contains("中华人民共和国", "华人民"));
+        assert!(!config.contains("中华人民共和国", "民中"));
+    }
+
+    #[test]
+    fn test_parse_config() {
+        assert_eq!(
+            TokenizerConfig::parse_stopwords(" foo, bar ,,"),
+            HashSet::from(["foo".to_string(), "bar".to_string()])
+        );
+        assert!(matches!(
+            TokenizerConfig::parse_stemmer("klingon"),
+            Err(TokenizerConfigError::UnsupportedStemmer(_))
+        ));
+    }
+}
@@ -1,12 +1,14 @@
+use super::tokenizer::TokenizerConfig;
 use super::util::TokenInstance;
 use chroma_blockstore::{BlockfileFlusher, BlockfileReader, BlockfileWriter};
 use chroma_error::{ChromaError, ErrorCodes};
-use futures::StreamExt;
+use futures::{future, Stream, StreamExt, TryStreamExt};
 use itertools::Itertools;
 use parking_lot::Mutex;
 use roaring::RoaringBitmap;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::time::Instant;
 use tantivy::tokenizer::NgramTokenizer;
 use tantivy::tokenizer::TokenStream;
 use tantivy::tokenizer::Tokenizer;
@@ -46,11 +48,62 @@ pub enum DocumentMutation<'text> {
     },
 }
 
+/// The prefix under which the number of tokens in each document is stored in the posting lists blockfile,
+/// followed by the byte offsets at which the words of the document start.
+/// Tokens are never empty, so this does not collide with any posting list.
+const DOCUMENT_LENGTH_PREFIX: &str = "";
+
+/// The key under the document length prefix at which the corpus statistics are stored, after the
+/// lengths of all documents.
+const CORPUS_STATISTICS_KEY: u32 = u32::MAX;
+
+/// The number of documents and their total number of tokens, which BM25 uses to compute the
+/// average document length.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CorpusStatistics {
+    pub num_documents: u64,
+    pub total_length: u64,
+}
+
+impl CorpusStatistics {
+    fn encode(&self) -> Vec<u32> {
+        vec![
+            self.num_documents as u32,
+            (self.num_documents >> 32) as u32,
+            self.total_length as u32,
+            (self.total_length >> 32) as u32,
+        ]
+    }
+
+    fn decode(values: &[u32]) -> Option<Self> {
+        match values {
+            [num_documents_lo, num_documents_hi, total_length_lo, total_length_hi] => {
+                Some(CorpusStatistics {
+                    num_documents: (*num_documents_hi as u64) << 32 | *num_documents_lo as u64,
+                    total_length: (*total_length_hi as u64) << 32 | *total_length_lo as u64,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The offset id of a document with its word offsets, or `None` if it is deleted
+type DocumentLength = (u32, Option<Vec<u32>>);
+
 #[derive(Clone)]
 pub struct FullTextIndexWriter {
     tokenizer: NgramTokenizer,
+    tokenizer_config: TokenizerConfig,
     /// Deletes for a given trigram/offset ID pair are represented by a `None` position on the token instance.
     token_instances: Arc<Mutex<Vec<Vec<TokenInstance>>>>,
+    /// The new number of tokens and word offsets for each mutated document, where `None` represents a delete.
+    document_lengths: Arc<Mutex<Vec<DocumentLength>>>,
+    /// The corpus statistics before the mutations, or `None` if unknown, in which case they are
+    /// not written and readers compute them from the document lengths.
+    corpus_statistics: Option<CorpusStatistics>,
+    /// The change in the number of documents and in their total length from the mutations.
+    corpus_statistics_delta: Arc<Mutex<(i64, i64)>>,
     posting_lists_blockfile_writer: BlockfileWriter,
 }
 
@@ -58,11 +111,28 @@ impl FullTextIndexWriter {
     pub fn new(posting_lists_blockfile_writer: BlockfileWriter, tokenizer: NgramTokenizer) -> Self {
         FullTextIndexWriter {
             tokenizer,
+            tokenizer_config: TokenizerConfig::default(),
             posting_lists_blockfile_writer,
             token_instances: Arc::new(Mutex::new(Vec::new())),
+            document_lengths: Arc::new(Mutex::new(Vec::new())),
+            corpus_statistics: Some(CorpusStatistics::default()),
+            corpus_statistics_delta: Arc::new(Mutex::new((0, 0))),
         }
     }
 
+    /// Sets the corpus statistics of the blockfile that the writer forks, which default to those
+    /// of an empty blockfile
+    pub fn with_corpus_statistics(mut self, corpus_statistics: Option<CorpusStatistics>) -> Self {
+        self.corpus_statistics = corpus_statistics;
+        self
+    }
+
+    /// Sets the normalization applied to the documents before they are tokenized
+    pub fn with_tokenizer_config(mut self, tokenizer_config: TokenizerConfig) -> Self {
+        self.tokenizer_config = tokenizer_config;
+        self
+    }
+
     /// Processes a batch of mutations to the full-text index
     /// This assumes that there will never be mutations with the same offset ID across all calls to `handle_batch()` for the lifetime of a `FullTextIndexWriter` struct.
     ///
@@ -74,6 +144,8 @@ impl FullTextIndexWriter {
         mutations: M,
     ) -> Result<(), FullTextIndexError> {
         let mut token_instances = vec![];
+        let mut document_lengths = vec![];
+        let (mut num_documents_delta, mut total_length_delta) = (0i64, 0i64);
 
         for mutation in mutations {
             match mutation {
@@ -81,16 +153,24 @@ impl FullTextIndexWriter {
                     offset_id,
                     new_document,
                 } => {
+                    let new_document = self.tokenizer_config.normalize(new_document);
+                    let mut document_length = 0;
                     self.tokenizer
                         .clone()
-                        .token_stream(new_document)
+                        .token_stream(&new_document)
                         .process(&mut |token| {
+                            document_length += 1;
                             token_instances.push(TokenInstance::encode(
                                 token.text.as_str(),
                                 offset_id,
                                 Some(token.offset_from as u32),
                             ));
                         });
+                    num_documents_delta += 1;
+                    total_length_delta += document_length as i64;
+                    let mut document_statistics = vec![document_length];
+                    document_statistics.extend(word_offsets(&new_document));
+                    document_lengths.push((offset_id, Some(document_statistics)));
                 }
 
                 DocumentMutation::Update {
@@ -98,12 +178,16 @@ impl FullTextIndexWriter {
                     old_document,
                     new_document,
                 } => {
+                    let old_document = self.tokenizer_config.normalize(old_document);
+                    let new_document = self.tokenizer_config.normalize(new_document);
                     // Remove old version
                     let mut trigrams_to_delete = HashSet::new(); // (need to filter out duplicates, each trigram may appear multiple times in a document)
+                    let mut old_document_length = 0;
                     self.tokenizer
                         .clone()
-                        .token_stream(old_document)
+                        .token_stream(&old_document)
                         .process(&mut |token| {
+                            old_document_length += 1;
                             trigrams_to_delete.insert(TokenInstance::encode(
                                 token.text.as_str(),
                                 offset_id,
@@ -112,10 +196,12 @@ impl FullTextIndexWriter {
                         });
 
                     // Add doc
+                    let mut document_length = 0;
                     self.tokenizer
                         .clone()
-                        .token_stream(new_document)
+                        .token_stream(&new_document)
                         .process(&mut |token| {
+                            document_length += 1;
                             trigrams_to_delete.remove(&TokenInstance::encode(
                                 token.text.as_str(),
                                 offset_id,
@@ -130,19 +216,26 @@ impl FullTextIndexWriter {
                         });
 
                     token_instances.extend(trigrams_to_delete.into_iter());
+                    total_length_delta += document_length as i64 - old_document_length as i64;
+                    let mut document_statistics = vec![document_length];
+                    document_statistics.extend(word_offsets(&new_document));
+                    document_lengths.push((offset_id, Some(document_statistics)));
                 }
 
                 DocumentMutation::Delete {
                     offset_id,
                     old_document,
                 } => {
+                    let old_document = self.tokenizer_config.normalize(old_document);
                     let mut trigrams_to_delete = HashSet::new(); // (need to filter out duplicates, each trigram may appear multiple times in a document)
 
                     // Delete doc
+                    let mut old_document_length = 0;
                     self.tokenizer
                         .clone()
-                        .token_stream(old_document)
+                        .token_stream(&old_document)
                         .process(&mut |token| {
+                            old_document_length += 1;
                             trigrams_to_delete.insert(TokenInstance::encode(
                                 token.text.as_str(),
                                 offset_id,
@@ -151,12 +244,19 @@ impl FullTextIndexWriter {
                         });
 
                     token_instances.extend(trigrams_to_delete.into_iter());
+                    num_documents_delta -= 1;
+                    total_length_delta -= old_document_length as i64;
+                    document_lengths.push((offset_id, None));
                 }
             }
         }
 
         token_instances.sort_unstable();
         self.token_instances.lock().push(token_instances);
+        self.document_lengths.lock().extend(document_lengths);
+        let mut corpus_statistics_delta = self.corpus_statistics_delta.lock();
+        corpus_statistics_delta.0 += num_documents_delta;
+        corpus_statistics_delta.1 += total_length_delta;
 
         Ok(())
     }
@@ -166,6 +266,45 @@ impl FullTextIndexWriter {
         let mut posting_list: Vec<u32> = vec![];
 
         let token_instances = std::mem::take(&mut *self.token_instances.lock());
+        let mut document_lengths = std::mem::take(&mut *self.document_lengths.lock());
+
+        // The document lengths are written first since their prefix precedes all tokens
+        document_lengths.sort_unstable_by_key(|(offset_id, _)| *offset_id);
+        for (offset_id, document_statistics) in document_lengths {
+            match document_statistics {
+                Some(document_statistics) => {
+                    self.posting_lists_blockfile_writer
+                        .set(DOCUMENT_LENGTH_PREFIX, offset_id, document_statistics)
+                        .await?
+                }
+                None => {
+                    self.posting_lists_blockfile_writer
+                        .delete::<u32, Vec<u32>>(DOCUMENT_LENGTH_PREFIX, offset_id)
+                        .await?
+                }
+            }
+        }
+
+        // The corpus statistics follow the document lengths, since their key is the largest
+        let (num_documents_delta, total_length_delta) =
+            std::mem::take(&mut *self.corpus_statistics_delta.lock());
+        if let Some(corpus_statistics) = self.corpus_statistics.as_mut() {
+            // Documents indexed before their lengths were tracked may be missing from the base
+            // statistics, so the statistics are clamped rather than allowed to underflow
+            corpus_statistics.num_documents = corpus_statistics
+                .num_documents
+                .saturating_add_signed(num_documents_delta);
+            corpus_statistics.total_length = corpus_statistics
+                .total_length
+                .saturating_add_signed(total_length_delta);
+            self.posting_lists_blockfile_writer
+                .set(
+                    DOCUMENT_LENGTH_PREFIX,
+                    CORPUS_STATISTICS_KEY,
+                    corpus_statistics.encode(),
+                )
+                .await?;
+        }
 
         for encoded_instance in token_instances.into_iter().kmerge() {
             match encoded_instance.get_position() {
@@ -245,12 +384,133 @@ impl FullTextIndexFlusher {
     pub fn pls_id(&self) -> Uuid {
         self.posting_lists_blockfile_flusher.id()
     }
+
+    pub fn blockfile_flusher(&self) -> &BlockfileFlusher {
+        &self.posting_lists_blockfile_flusher
+    }
+}
+
+/// The term frequency saturation parameter of BM25
+const BM25_K1: f32 = 1.2;
+/// The document length normalization parameter of BM25
+const BM25_B: f32 = 0.75;
+
+/// Scores a token occurring `term_frequency` times in a document with BM25, where the token
+/// occurs in `document_frequency` out of `num_documents` documents. `length_ratio` is the length
+/// of the document divided by the average document length, which should be 1 if unknown.
+pub fn bm25_token_score(
+    term_frequency: u32,
+    document_frequency: u64,
+    num_documents: u64,
+    length_ratio: f32,
+) -> f32 {
+    let num_documents = num_documents.max(document_frequency) as f32;
+    let document_frequency = document_frequency as f32;
+    let idf = (1.0 + (num_documents - document_frequency + 0.5) / (document_frequency + 0.5)).ln();
+    let term_frequency = term_frequency as f32;
+    idf * term_frequency * (BM25_K1 + 1.0)
+        / (term_frequency + BM25_K1 * (1.0 - BM25_B + BM25_B * length_ratio))
+}
+
+/// The BM25 scores of the documents matching a full-text query, ordered by offset id
+#[derive(Clone, Debug, Default)]
+pub struct FullTextScores {
+    pub scores: Vec<(u32, f32)>,
+    /// Whether the scoring stopped early, in which case the scores only cover some of the tokens
+    pub truncated: bool,
+}
+
+/// Returns the byte offsets at which the whitespace separated words of the document start.
+pub fn word_offsets(document: &str) -> Vec<u32> {
+    let mut offsets = Vec::new();
+    let mut in_word = false;
+    for (offset, character) in document.char_indices() {
+        if character.is_whitespace() {
+            in_word = false;
+        } else if !in_word {
+            offsets.push(offset as u32);
+            in_word = true;
+        }
+    }
+    offsets
+}
+
+/// Returns the index of the word containing the byte at `position`, given the word offsets of the document.
+fn word_index(word_offsets: &[u32], position: u32) -> u32 {
+    word_offsets
+        .partition_point(|offset| *offset <= position)
+        .saturating_sub(1) as u32
+}
+
+/// Checks whether there is an occurrence of every term such that the occurrences span at most
+/// `distance` words. `term_word_positions` contains the word indices at which each term occurs.
+pub fn within_word_distance(term_word_positions: &[Vec<u32>], distance: u32) -> bool {
+    if term_word_positions.is_empty() || term_word_positions.iter().any(Vec::is_empty) {
+        return false;
+    }
+
+    let mut occurrences = term_word_positions
+        .iter()
+        .enumerate()
+        .flat_map(|(term, positions)| positions.iter().map(move |position| (*position, term)))
+        .collect::<Vec<_>>();
+    occurrences.sort_unstable();
+
+    // Slide a window over the occurrences and shrink it while it still covers every term
+    let mut counts = vec![0; term_word_positions.len()];
+    let mut covered_terms = 0;
+    let mut start = 0;
+    for &(end_position, term) in &occurrences {
+        if counts[term] == 0 {
+            covered_terms += 1;
+        }
+        counts[term] += 1;
+        while covered_terms == counts.len() {
+            let (start_position, start_term) = occurrences[start];
+            if end_position - start_position <= distance {
+                return true;
+            }
+            counts[start_term] -= 1;
+            if counts[start_term] == 0 {
+                covered_terms -= 1;
+            }
+            start += 1;
+        }
+    }
+    false
+}
+
+/// Checks whether the whitespace separated terms all occur in the document within `distance`
+/// words of each other. This matches `FullTextIndexReader::search_near` for documents that
+/// are not indexed yet.
+pub fn document_contains_near(document: &str, terms: &[&str], distance: u32) -> bool {
+    let word_offsets = word_offsets(document);
+    let term_word_positions = terms
+        .iter()
+        .map(|term| {
+            // Overlapping occurrences are included, like in the posting lists
+            let mut positions = Vec::new();
+            let mut start = 0;
+            while let Some(found) = document.get(start..).and_then(|rest| rest.find(term)) {
+                let position = start + found;
+                positions.push(word_index(&word_offsets, position as u32));
+                start = position
+                    + document[position..]
+                        .chars()
+                        .next()
+                        .map_or(1, char::len_utf8);
+            }
+            positions
+        })
+        .collect::<Vec<_>>();
+    within_word_distance(&term_word_positions, distance)
 }
 
 #[derive(Clone)]
 pub struct FullTextIndexReader<'me> {
     posting_lists_blockfile_reader: BlockfileReader<'me, u32, &'me [u32]>,
     tokenizer: NgramTokenizer,
+    tokenizer_config: TokenizerConfig,
 }
 
 impl<'me> FullTextIndexReader<'me> {
@@ -261,10 +521,138 @@ impl<'me> FullTextIndexReader<'me> {
         FullTextIndexReader {
             posting_lists_blockfile_reader,
             tokenizer,
+            tokenizer_config: TokenizerConfig::default(),
+        }
+    }
+
+    /// Sets the normalization applied to the queries before they are tokenized, which should
+    /// match the normalization of the indexed documents
+    pub fn with_tokenizer_config(mut self, tokenizer_config: TokenizerConfig) -> Self {
+        self.tokenizer_config = tokenizer_config;
+        self
+    }
+
+    pub fn tokenizer_config(&self) -> &TokenizerConfig {
+        &self.tokenizer_config
+    }
+
+    /// Returns the corpus statistics stored with the posting lists. Blockfiles written before the
+    /// statistics were stored fall back to summing the lengths of all documents.
+    pub async fn corpus_statistics(&self) -> Result<CorpusStatistics, FullTextIndexError> {
+        if let Some(corpus_statistics) = self
+            .posting_lists_blockfile_reader
+            .get(DOCUMENT_LENGTH_PREFIX, CORPUS_STATISTICS_KEY)
+            .await?
+            .and_then(CorpusStatistics::decode)
+        {
+            return Ok(corpus_statistics);
         }
+
+        let document_lengths = self
+            .posting_lists_blockfile_reader
+            .get_range(DOCUMENT_LENGTH_PREFIX..=DOCUMENT_LENGTH_PREFIX, ..)
+            .await?;
+        Ok(CorpusStatistics {
+            num_documents: document_lengths.len() as u64,
+            total_length: document_lengths
+                .iter()
+                .filter_map(|(_, length)| length.first())
+                .map(|length| *length as u64)
+                .sum(),
+        })
+    }
+
+    /// Streams every entry of the posting lists: a token, the offset id of a document that
+    /// contains it and the positions of the token in the document, in token and offset id order.
+    /// The document lengths stored with the posting lists are skipped
+    pub fn posting_lists_stream(
+        &'me self,
+    ) -> impl Stream<Item = Result<(&'me str, u32, &'me [u32]), FullTextIndexError>> + Send + 'me
+    {
+        self.posting_lists_blockfile_reader
+            .get_all_stream()
+            .map_err(FullTextIndexError::from)
+            .try_filter(|(token, _, _)| future::ready(*token != DOCUMENT_LENGTH_PREFIX))
     }
 
     pub async fn search(&self, query: &str) -> Result<RoaringBitmap, FullTextIndexError> {
+        Ok(self
+            .search_positions(&self.tokenizer_config.normalize(query))
+            .await?
+            .into_iter()
+            .map(|(doc_id, _)| doc_id)
+            .collect())
+    }
+
+    /// Searches for the documents containing every whitespace separated term of the query,
+    /// where the occurrences of the terms span at most `distance` words.
+    ///
+    /// Documents indexed before word offsets were tracked never match.
+    pub async fn search_near(
+        &self,
+        query: &str,
+        distance: u32,
+    ) -> Result<RoaringBitmap, FullTextIndexError> {
+        let mut term_matches = Vec::new();
+        for term in query.split_whitespace() {
+            // Terms that are removed by the normalization, such as stopwords, are ignored
+            let term = self.tokenizer_config.normalize(term);
+            if term.trim().is_empty() {
+                continue;
+            }
+            term_matches.push(
+                self.search_positions(&term)
+                    .await?
+                    .into_iter()
+                    .collect::<HashMap<_, _>>(),
+            );
+        }
+
+        let mut results = RoaringBitmap::new();
+        let Some((first_term_matches, other_term_matches)) = term_matches.split_first() else {
+            return Ok(results);
+        };
+        for doc_id in first_term_matches.keys() {
+            if !other_term_matches
+                .iter()
+                .all(|matches| matches.contains_key(doc_id))
+            {
+                continue;
+            }
+            let Some(document_statistics) = self
+                .posting_lists_blockfile_reader
+                .get(DOCUMENT_LENGTH_PREFIX, *doc_id)
+                .await?
+            else {
+                continue;
+            };
+            // The first value is the document length, followed by the word offsets
+            let word_offsets = document_statistics.get(1..).unwrap_or_default();
+            if word_offsets.is_empty() {
+                continue;
+            }
+            let term_word_positions = term_matches
+                .iter()
+                .map(|matches| {
+                    matches[doc_id]
+                        .iter()
+                        .map(|position| word_index(word_offsets, *position))
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            if within_word_distance(&term_word_positions, distance) {
+                results.insert(*doc_id);
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Returns the documents containing the normalized query, together with the sorted byte offsets at which the query occurs.
+    async fn search_positions(
+        &self,
+        query: &str,
+    ) -> Result<Vec<(u32, Vec<u32>)>, FullTextIndexError> {
         let mut tokens = vec![];
         self.tokenizer
             .clone()
@@ -274,8 +662,12 @@ impl<'me> FullTextIndexReader<'me> {
             });
 
         if tokens.is_empty() {
-            return Ok(RoaringBitmap::new());
+            return Ok(Vec::new());
         }
+        let token_offsets = tokens
+            .iter()
+            .map(|token| token.offset_from as u32)
+            .collect::<Vec<_>>();
 
         // Retrieve posting lists for each token.
         let posting_lists = futures::stream::iter(tokens)
@@ -293,7 +685,7 @@ impl<'me> FullTextIndexReader<'me> {
 
         let num_tokens = posting_lists.len();
         let mut pointers = vec![0; num_tokens];
-        let mut results = RoaringBitmap::new();
+        let mut results = Vec::new();
 
         loop {
             // Get current doc_ids from each posting list (aka for each token).
@@ -328,8 +720,9 @@ impl<'me> FullTextIndexReader<'me> {
 
                 // Adjust positions and check for sequential alignment.
                 // Imagine you're searching for "brown fox" over the document "the quick brown fox".
-                // The positions for "brown" are {2} and for "fox" are {3}. The adjusted positions after subtracting the token's position in the query are {2} for "brown" and 3 - 1 = {2} for "fox".
+                // The positions for "brown" are {10} and for "fox" are {16}. The adjusted positions after subtracting the token's byte offset in the query are {10} for "brown" and 16 - 6 = {10} for "fox".
                 // The intersection of these two sets is non-empty, so we know that the two tokens are adjacent.
+                // Byte offsets are used rather than token indices, since the positions are byte offsets and characters may span several bytes.
 
                 // Seed with the positions of the first token.
                 let mut adjusted_positions = positions_per_posting_list[0]
@@ -337,12 +730,15 @@ impl<'me> FullTextIndexReader<'me> {
                     .copied()
                     .collect::<HashSet<_>>();
 
-                for (offset, positions_set) in positions_per_posting_list.iter().enumerate().skip(1)
+                for (offset, positions_set) in token_offsets
+                    .iter()
+                    .zip(positions_per_posting_list.iter())
+                    .skip(1)
                 {
                     let positions_set = positions_set
                         .iter()
                         // (We can discard any positions that the token appears at before the current offset)
-                        .filter_map(|&p| p.checked_sub(offset as u32))
+                        .filter_map(|&p| p.checked_sub(*offset))
                         .collect::<HashSet<_>>();
                     adjusted_positions = &adjusted_positions & &positions_set;
 
@@ -353,7 +749,9 @@ impl<'me> FullTextIndexReader<'me> {
 
                 // All tokens are sequential
                 if !adjusted_positions.is_empty() {
-                    results.insert(min_doc_id);
+                    let mut positions = adjusted_positions.into_iter().collect::<Vec<_>>();
+                    positions.sort_unstable();
+                    results.push((min_doc_id, positions));
                 }
 
                 // Advance all pointers.
@@ -375,6 +773,103 @@ impl<'me> FullTextIndexReader<'me> {
         Ok(results)
     }
 
+    /// Searches for the documents containing the query like `search`, and scores each of them
+    /// by summing the BM25 scores of the query tokens. The returned documents are ordered by offset id.
+    ///
+    /// Only the lengths of the matching documents are read, together with the precomputed corpus
+    /// statistics. Documents indexed before document lengths were tracked are scored as if they
+    /// had the average length.
+    ///
+    /// If `stop_at` is reached while scoring, all the matching documents are still returned,
+    /// scored by the tokens processed so far, and the scores are marked as truncated.
+    pub async fn search_with_scores(
+        &self,
+        query: &str,
+        stop_at: Option<Instant>,
+    ) -> Result<FullTextScores, FullTextIndexError> {
+        let query = self.tokenizer_config.normalize(query);
+        let matches = self
+            .search_positions(&query)
+            .await?
+            .into_iter()
+            .map(|(doc_id, _)| doc_id)
+            .collect::<RoaringBitmap>();
+        if matches.is_empty() {
+            return Ok(FullTextScores::default());
+        }
+        let is_stopped = || stop_at.is_some_and(|stop_at| Instant::now() >= stop_at);
+        if is_stopped() {
+            return Ok(FullTextScores {
+                scores: matches.into_iter().map(|doc_id| (doc_id, 0.0)).collect(),
+                truncated: true,
+            });
+        }
+
+        let corpus_statistics = self.corpus_statistics().await?;
+        // Documents without a recorded length are still counted towards the corpus
+        let num_documents = corpus_statistics.num_documents.max(matches.len());
+        let average_length = match corpus_statistics.num_documents {
+            0 => None,
+            num_documents => Some(corpus_statistics.total_length as f32 / num_documents as f32),
+        };
+        let mut length_ratios = Vec::with_capacity(matches.len() as usize);
+        for doc_id in matches.iter() {
+            let length_ratio = match average_length {
+                Some(average_length) if average_length > 0.0 => self
+                    .posting_lists_blockfile_reader
+                    .get(DOCUMENT_LENGTH_PREFIX, doc_id)
+                    .await?
+                    .and_then(|document_statistics| document_statistics.first())
+                    .map_or(1.0, |length| *length as f32 / average_length),
+                _ => 1.0,
+            };
+            length_ratios.push(length_ratio);
+        }
+
+        let mut tokens = vec![];
+        self.tokenizer
+            .clone()
+            .token_stream(&query)
+            .process(&mut |token| {
+                tokens.push(token.text.clone());
+            });
+
+        let mut scores = vec![0.0; matches.len() as usize];
+        let mut truncated = false;
+        for token in tokens {
+            if is_stopped() {
+                truncated = true;
+                break;
+            }
+            let posting_list = self
+                .posting_lists_blockfile_reader
+                .get_range(token.as_str()..=token.as_str(), ..)
+                .await?;
+            let document_frequency = posting_list.len() as u64;
+            for ((score, doc_id), length_ratio) in scores
+                .iter_mut()
+                .zip(matches.iter())
+                .zip(length_ratios.iter())
+            {
+                // Posting lists are sorted by document id
+                if let Ok(index) = posting_list.binary_search_by_key(&doc_id, |(id, _)| *id) {
+                    let term_frequency = posting_list[index].1.len() as u32;
+                    *score += bm25_token_score(
+                        term_frequency,
+                        document_frequency,
+                        num_documents,
+                        *length_ratio,
+                    );
+                }
+            }
+        }
+
+        Ok(FullTextScores {
+            scores: matches.into_iter().zip(scores).collect(),
+            truncated,
+        })
+    }
+
     #[cfg(test)]
     async fn get_all_results_for_token(
         &self,
@@ -1033,4 +1528,356 @@ mod tests {
         let res = index_reader.search("world").await.unwrap();
         assert_eq!(res, RoaringBitmap::from([1]));
     }
+
+    #[tokio::test]
+    async fn test_search_with_scores() {
+        let provider = BlockfileProvider::new_memory();
+        let pl_blockfile_writer = provider
+            .write::<u32, Vec<u32>>(BlockfileWriterOptions::default())
+            .await
+            .unwrap();
+        let pl_blockfile_id = pl_blockfile_writer.id();
+
+        let tokenizer = NgramTokenizer::new(3, 3, false).unwrap();
+        let mut index_writer = FullTextIndexWriter::new(pl_blockfile_writer, tokenizer);
+        index_writer
+            .handle_batch([
+                DocumentMutation::Create {
+                    offset_id: 1,
+                    new_document: "hello world",
+                },
+                DocumentMutation::Create {
+                    offset_id: 2,
+                    new_document: "hello hello world",
+                },
+                DocumentMutation::Create {
+                    offset_id: 3,
+                    new_document: "goodbye world",
+                },
+            ])
+            .unwrap();
+        index_writer.write_to_blockfiles().await.unwrap();
+        let flusher = index_writer.commit().await.unwrap();
+        flusher.flush().await.unwrap();
+
+        let pl_blockfile_reader = provider
+            .read::<u32, &[u32]>(&pl_blockfile_id)
+            .await
+            .unwrap();
+        let tokenizer = NgramTokenizer::new(3, 3, false).unwrap();
+        let index_reader = FullTextIndexReader::new(pl_blockfile_reader, tokenizer);
+
+        let res = index_reader
+            .search_with_scores("hello", None)
+            .await
+            .unwrap()
+            .scores;
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[0].0, 1);
+        assert_eq!(res[1].0, 2);
+        // The document with more occurrences of the query scores higher
+        assert!(res[1].1 > res[0].1);
+        assert!(res[0].1 > 0.0);
+
+        let res = index_reader
+            .search_with_scores("world", None)
+            .await
+            .unwrap()
+            .scores;
+        assert_eq!(res.len(), 3);
+        // The shorter document scores higher for the same number of occurrences
+        assert!(res[0].1 > res[2].1);
+
+        let res = index_reader
+            .search_with_scores("absent", None)
+            .await
+            .unwrap();
+        assert!(res.scores.is_empty() && !res.truncated);
+
+        // The matching documents are still returned once the time limit is reached
+        let res = index_reader
+            .search_with_scores("world", Some(Instant::now()))
+            .await
+            .unwrap();
+        assert!(res.truncated);
+        assert_eq!(res.scores.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_corpus_statistics() {
+        let provider = BlockfileProvider::new_memory();
+        let pl_blockfile_writer = provider
+            .write::<u32, Vec<u32>>(BlockfileWriterOptions::default())
+            .await
+            .unwrap();
+        let pl_blockfile_id = pl_blockfile_writer.id();
+
+        let tokenizer = NgramTokenizer::new(3, 3, false).unwrap();
+        let mut index_writer = FullTextIndexWriter::new(pl_blockfile_writer, tokenizer);
+        index_writer
+            .handle_batch([
+                DocumentMutation::Create {
+                    offset_id: 1,
+                    new_document: "hello world",
+                },
+                DocumentMutation::Create {
+                    offset_id: 2,
+                    new_document: "hello hello world",
+                },
+                DocumentMutation::Create {
+                    offset_id: 3,
+                    new_document: "goodbye world",
+                },
+            ])
+            .unwrap();
+        index_writer.write_to_blockfiles().await.unwrap();
+        let flusher = index_writer.commit().await.unwrap();
+        flusher.flush().await.unwrap();
+
+        let pl_blockfile_reader = provider
+            .read::<u32, &[u32]>(&pl_blockfile_id)
+            .await
+            .unwrap();
+        let tokenizer = NgramTokenizer::new(3, 3, false).unwrap();
+        let index_reader = FullTextIndexReader::new(pl_blockfile_reader, tokenizer);
+        let corpus_statistics = index_reader.corpus_statistics().await.unwrap();
+        assert_eq!(
+            corpus_statistics,
+            CorpusStatistics {
+                num_documents: 3,
+                total_length: 9 + 15 + 11,
+            }
+        );
+
+        // The statistics of a fork are updated by its mutations
+        let pl_blockfile_writer = provider
+            .write::<u32, Vec<u32>>(BlockfileWriterOptions::new().fork(pl_blockfile_id))
+            .await
+            .unwrap();
+        let pl_blockfile_id = pl_blockfile_writer.id();
+        let tokenizer = NgramTokenizer::new(3, 3, false).unwrap();
+        let mut index_writer = FullTextIndexWriter::new(pl_blockfile_writer, tokenizer)
+            .with_corpus_statistics(Some(corpus_statistics));
+        index_writer
+            .handle_batch([
+                DocumentMutation::Update {
+                    offset_id: 1,
+                    old_document: "hello world",
+                    new_document: "hi world",
+                },
+                DocumentMutation::Delete {
+                    offset_id: 3,
+                    old_document: "goodbye world",
+                },
+            ])
+            .unwrap();
+        index_writer.write_to_blockfiles().await.unwrap();
+        let flusher = index_writer.commit().await.unwrap();
+        flusher.flush().await.unwrap();
+
+        let pl_blockfile_reader = provider
+            .read::<u32, &[u32]>(&pl_blockfile_id)
+            .await
+            .unwrap();
+        let tokenizer = NgramTokenizer::new(3, 3, false).unwrap();
+        let index_reader = FullTextIndexReader::new(pl_blockfile_reader, tokenizer);
+        assert_eq!(
+            index_reader.corpus_statistics().await.unwrap(),
+            CorpusStatistics {
+                num_documents: 2,
+                total_length: 6 + 15,
+            }
+        );
+        // The statistics are not mistaken for the length of a document
+        assert_eq!(
+            index_reader
+                .search_with_scores("world", None)
+                .await
+                .unwrap()
+                .scores
+                .len(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_phrase_and_near() {
+        let provider = BlockfileProvider::new_memory();
+        let pl_blockfile_writer = provider
+            .write::<u32, Vec<u32>>(BlockfileWriterOptions::default())
+            .await
+            .unwrap();
+        let pl_blockfile_id = pl_blockfile_writer.id();
+
+        let tokenizer = NgramTokenizer::new(3, 3, false).unwrap();
+        let mut index_writer = FullTextIndexWriter::new(pl_blockfile_writer, tokenizer);
+        index_writer
+            .handle_batch([
+                DocumentMutation::Create {
+                    offset_id: 1,
+                    new_document: "the quick brown fox jumps over the lazy dog",
+                },
+                DocumentMutation::Create {
+                    offset_id: 2,
+                    new_document: "the brown dog chases the quick fox",
+                },
+                DocumentMutation::Create {
+                    offset_id: 3,
+                    new_document: "a fox",
+                },
+            ])
+            .unwrap();
+        index_writer.write_to_blockfiles().await.unwrap();
+        let flusher = index_writer.commit().await.unwrap();
+        flusher.flush().await.unwrap();
+
+        let pl_blockfile_reader = provider
+            .read::<u32, &[u32]>(&pl_blockfile_id)
+            .await
+            .unwrap();
+        let tokenizer = NgramTokenizer::new(3, 3, false).unwrap();
+        let index_reader = FullTextIndexReader::new(pl_blockfile_reader, tokenizer);
+
+        // Phrases only match when the words are adjacent and in order
+        let res = index_reader.search("quick brown fox").await.unwrap();
+        assert_eq!(res, RoaringBitmap::from([1]));
+        let res = index_reader.search("quick fox").await.unwrap();
+        assert_eq!(res, RoaringBitmap::from([2]));
+
+        // "quick" and "fox" are 2 words apart in the first document and adjacent in the second
+        let res = index_reader.search_near("quick fox", 1).await.unwrap();
+        assert_eq!(res, RoaringBitmap::from([2]));
+        let res = index_reader.search_near("fox quick", 2).await.unwrap();
+        assert_eq!(res, RoaringBitmap::from([1, 2]));
+
+        // "brown" and "dog" are 6 words apart in the first document and adjacent in the second
+        let res = index_reader.search_near("brown dog", 5).await.unwrap();
+        assert_eq!(res, RoaringBitmap::from([2]));
+        let res = index_reader.search_near("brown dog", 6).await.unwrap();
+        assert_eq!(res, RoaringBitmap::from([1, 2]));
+
+        let res = index_reader.search_near("fox absent", 10).await.unwrap();
+        assert!(res.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_search_multibyte_characters() {
+        let provider = BlockfileProvider::new_memory();
+        let pl_blockfile_writer = provider
+            .write::<u32, Vec<u32>>(BlockfileWriterOptions::default())
+            .await
+            .unwrap();
+        let pl_blockfile_id = pl_blockfile_writer.id();
+
+        let tokenizer = NgramTokenizer::new(3, 3, false).unwrap();
+        let mut index_writer = FullTextIndexWriter::new(pl_blockfile_writer, tokenizer);
+        index_writer
+            .handle_batch([
+                DocumentMutation::Create {
+                    offset_id: 1,
+                    new_document: "größer als der Bär",
+                },
+                DocumentMutation::Create {
+                    offset_id: 2,
+                    new_document: "中华人民共和国",
+                },
+            ])
+            .unwrap();
+        index_writer.write_to_blockfiles().await.unwrap();
+        let flusher = index_writer.commit().await.unwrap();
+        flusher.flush().await.unwrap();
+
+        let pl_blockfile_reader = provider
+            .read::<u32, &[u32]>(&pl_blockfile_id)
+            .await
+            .unwrap();
+        let tokenizer = NgramTokenizer::new(3, 3, false).unwrap();
+        let index_reader = FullTextIndexReader::new(pl_blockfile_reader, tokenizer);
+
+        // The tokens of the query are aligned by their byte offsets
+        let res = index_reader.search("größer").await.unwrap();
+        assert_eq!(res, RoaringBitmap::from([1]));
+        let res = index_reader.search("der Bär").await.unwrap();
+        assert_eq!(res, RoaringBitmap::from([1]));
+        let res = index_reader.search("人民共和").await.unwrap();
+        assert_eq!(res, RoaringBitmap::from([2]));
+        let res = index_reader.search("人民和").await.unwrap();
+        assert!(res.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_search_with_tokenizer_config() {
+        let provider = BlockfileProvider::new_memory();
+        let pl_blockfile_writer = provider
+            .write::<u32, Vec<u32>>(BlockfileWriterOptions::default())
+            .await
+            .unwrap();
+        let pl_blockfile_id = pl_blockfile_writer.id();
+
+        let tokenizer_config = TokenizerConfig {
+            lowercase: true,
+            stopwords: TokenizerConfig::parse_stopwords("english"),
+            stemmer: Some(TokenizerConfig::parse_stemmer("english").unwrap()),
+            cjk_bigrams: true,
+        };
+        let tokenizer = NgramTokenizer::new(3, 3, false).unwrap();
+        let mut index_writer = FullTextIndexWriter::new(pl_blockfile_writer, tokenizer)
+            .with_tokenizer_config(tokenizer_config.clone());
+        index_writer
+            .handle_batch([
+                DocumentMutation::Create {
+                    offset_id: 1,
+                    new_document: "The Dogs are running in the park",
+                },
+                DocumentMutation::Create {
+                    offset_id: 2,
+                    new_document: "中华人民共和国",
+                },
+                DocumentMutation::Create {
+                    offset_id: 3,
+                    new_document: "A cat sleeps",
+                },
+            ])
+            .unwrap();
+        index_writer.write_to_blockfiles().await.unwrap();
+        let flusher = index_writer.commit().await.unwrap();
+        flusher.flush().await.unwrap();
+
+        let pl_blockfile_reader = provider
+            .read::<u32, &[u32]>(&pl_blockfile_id)
+            .await
+            .unwrap();
+        let tokenizer = NgramTokenizer::new(3, 3, false).unwrap();
+        let index_reader = FullTextIndexReader::new(pl_blockfile_reader, tokenizer)
+            .with_tokenizer_config(tokenizer_config);
+
+        let res = index_reader.search("DOG RUNS").await.unwrap();
+        assert_eq!(res, RoaringBitmap::from([1]));
+        let res = index_reader.search("cat sleeping").await.unwrap();
+        assert_eq!(res, RoaringBitmap::from([3]));
+
+        // Bigrams are searchable even though they are shorter than a trigram
+        let res = index_reader.search("人民").await.unwrap();
+        assert_eq!(res, RoaringBitmap::from([2]));
+        let res = index_reader.search("民中").await.unwrap();
+        assert!(res.is_empty());
+
+        // Stopwords are not counted towards the distance
+        let res = index_reader.search_near("park dogs", 1).await.unwrap();
+        assert!(res.is_empty());
+        let res = index_reader.search_near("the park dogs", 2).await.unwrap();
+        assert_eq!(res, RoaringBitmap::from([1]));
+    }
+
+    #[test]
+    fn test_document_contains_near() {
+        let document = "the quick brown fox jumps over the lazy dog";
+        assert_eq!(word_offsets("  a bc  d"), vec![2, 4, 8]);
+        assert!(document_contains_near(document, &["quick", "fox"], 2));
+        assert!(!document_contains_near(document, &["quick", "fox"], 1));
+        assert!(document_contains_near(document, &["dog", "the"], 2));
+        assert!(!document_contains_near(document, &["dog", "the"], 1));
+        assert!(!document_contains_near(document, &["dog", "cat"], 10));
+        assert!(!document_contains_near(document, &[], 10));
+    }
 }
@@ -1,4 +1,5 @@
-use super::{Index, IndexConfig, IndexUuid, PersistentIndex};
+use super::{Index, IndexConfig, IndexUuid, PersistentIndex, ScalarQuantization};
+use chroma_distance::{normalize, DistanceFunction};
 use chroma_error::{ChromaError, ErrorCodes};
 use chroma_types::MetadataValueConversionError;
 use std::ffi::CString;
@@ -83,9 +84,13 @@ impl HnswIndexConfig {
 /// # Notes
 /// This struct is not thread safe for concurrent reads and writes. Callers should
 /// synchronize access to the index between reads and writes.
+/// If the index is quantized, the vectors are stored as int8 and the returned distances
+/// are only meant for ranking.
 pub struct HnswIndex {
     ffi_ptr: *const IndexPtrFFI,
     dimensionality: i32,
+    distance_function: DistanceFunction,
+    quantization: Option<ScalarQuantization>,
     pub id: IndexUuid,
 }
 
@@ -137,17 +142,7 @@ impl Index<HnswIndexConfig> for HnswIndex {
         match hnsw_config {
             None => Err(Box::new(HnswIndexInitError::NoConfigProvided)),
             Some(config) => {
-                let distance_function_string: String =
-                    index_config.distance_function.clone().into();
-
-                let space_name = match CString::new(distance_function_string) {
-                    Ok(space_name) => space_name,
-                    Err(e) => {
-                        return Err(Box::new(HnswIndexInitError::InvalidDistanceFunction(
-                            e.to_string(),
-                        )))
-                    }
-                };
+                let space_name = space_name(index_config)?;
 
                 let ffi_ptr =
                     unsafe { create_index(space_name.as_ptr(), index_config.dimensionality) };
@@ -175,6 +170,8 @@ impl Index<HnswIndexConfig> for HnswIndex {
                 let hnsw_index = HnswIndex {
                     ffi_ptr,
                     dimensionality: index_config.dimensionality,
+                    distance_function: index_config.distance_function.clone(),
+                    quantization: index_config.quantization,
                     id,
                 };
                 hnsw_index.set_ef(config.ef_search)?;
@@ -184,7 +181,13 @@ impl Index<HnswIndexConfig> for HnswIndex {
     }
 
     fn add(&self, id: usize, vector: &[f32]) -> Result<(), Box<dyn ChromaError>> {
-        unsafe { add_item(self.ffi_ptr, vector.as_ptr(), id, true) }
+        match &self.quantization {
+            Some(quantization) => {
+                let vector = self.quantize(quantization, vector);
+                unsafe { add_item_int8(self.ffi_ptr, vector.as_ptr(), id, true) }
+            }
+            None => unsafe { add_item(self.ffi_ptr, vector.as_ptr(), id, true) },
+        }
         read_and_return_hnsw_error(self.ffi_ptr)
     }
 
@@ -203,18 +206,36 @@ impl Index<HnswIndexConfig> for HnswIndex {
         let actual_k = std::cmp::min(k, self.len());
         let mut ids = vec![0usize; actual_k];
         let mut distance = vec![0.0f32; actual_k];
-        let total_result = unsafe {
-            knn_query(
-                self.ffi_ptr,
-                vector.as_ptr(),
-                k,
-                ids.as_mut_ptr(),
-                distance.as_mut_ptr(),
-                allowed_ids.as_ptr(),
-                allowed_ids.len(),
-                disallowed_ids.as_ptr(),
-                disallowed_ids.len(),
-            ) as usize
+        let total_result = match &self.quantization {
+            Some(quantization) => {
+                let vector = self.quantize(quantization, vector);
+                unsafe {
+                    knn_query_int8(
+                        self.ffi_ptr,
+                        vector.as_ptr(),
+                        k,
+                        ids.as_mut_ptr(),
+                        distance.as_mut_ptr(),
+                        allowed_ids.as_ptr(),
+                        allowed_ids.len(),
+                        disallowed_ids.as_ptr(),
+                        disallowed_ids.len(),
+                    ) as usize
+                }
+            }
+            None => unsafe {
+                knn_query(
+                    self.ffi_ptr,
+                    vector.as_ptr(),
+                    k,
+                    ids.as_mut_ptr(),
+                    distance.as_mut_ptr(),
+                    allowed_ids.as_ptr(),
+                    allowed_ids.len(),
+                    disallowed_ids.as_ptr(),
+                    disallowed_ids.len(),
+                ) as usize
+            },
         };
         read_and_return_hnsw_error(self.ffi_ptr)?;
 
@@ -226,6 +247,12 @@ impl Index<HnswIndexConfig> for HnswIndex {
     }
 
     fn get(&self, id: usize) -> Result<Option<Vec<f32>>, Box<dyn ChromaError>> {
+        if let Some(quantization) = &self.quantization {
+            let mut data: Vec<i8> = vec![0i8; self.dimensionality as usize];
+            unsafe { get_item_int8(self.ffi_ptr, id, data.as_mut_ptr()) };
+            read_and_return_hnsw_error(self.ffi_ptr)?;
+            return Ok(Some(quantization.dequantize(&data)));
+        }
         unsafe {
             let mut data: Vec<f32> = vec![0.0f32; self.dimensionality as usize];
             get_item(self.ffi_ptr, id, data.as_mut_ptr());
@@ -248,15 +275,7 @@ impl PersistentIndex<HnswIndexConfig> for HnswIndex {
         index_config: &IndexConfig,
         id: IndexUuid,
     ) -> Result<Self, Box<dyn ChromaError>> {
-        let distance_function_string: String = index_config.distance_function.clone().into();
-        let space_name = match CString::new(distance_function_string) {
-            Ok(space_name) => space_name,
-            Err(e) => {
-                return Err(Box::new(HnswIndexInitError::InvalidDistanceFunction(
-                    e.to_string(),
-                )))
-            }
-        };
+        let space_name = space_name(index_config)?;
         let ffi_ptr = unsafe { create_index(space_name.as_ptr(), index_config.dimensionality) };
         read_and_return_hnsw_error(ffi_ptr)?;
 
@@ -272,6 +291,8 @@ impl PersistentIndex<HnswIndexConfig> for HnswIndex {
         let hnsw_index = HnswIndex {
             ffi_ptr,
             dimensionality: index_config.dimensionality,
+            distance_function: index_config.distance_function.clone(),
+            quantization: index_config.quantization,
             id,
         };
         Ok(hnsw_index)
@@ -297,6 +318,30 @@ impl HnswIndex {
         self.dimensionality
     }
 
+    pub fn quantization(&self) -> Option<ScalarQuantization> {
+        self.quantization
+    }
+
+    /// The number of values of the vector that would be clamped when it is added to the index,
+    /// which is always zero if the index is not quantized
+    pub fn clipped(&self, vector: &[f32]) -> usize {
+        match (&self.quantization, &self.distance_function) {
+            (Some(quantization), DistanceFunction::Cosine) => {
+                quantization.clipped(&normalize(vector))
+            }
+            (Some(quantization), _) => quantization.clipped(vector),
+            (None, _) => 0,
+        }
+    }
+
+    // The quantized spaces do not normalize the vectors, so they are normalized before quantization
+    fn quantize(&self, quantization: &ScalarQuantization, vector: &[f32]) -> Vec<i8> {
+        match self.distance_function {
+            DistanceFunction::Cosine => quantization.quantize(&normalize(vector)),
+            _ => quantization.quantize(vector),
+        }
+    }
+
     pub fn capacity(&self) -> usize {
         unsafe { capacity(self.ffi_ptr) as usize }
         // Does not return an error
@@ -322,6 +367,17 @@ impl Drop for HnswIndex {
     }
 }
 
+// The name of the hnswlib space for the index, where quantized indices use the int8 variant of the space
+fn space_name(index_config: &IndexConfig) -> Result<CString, Box<dyn ChromaError>> {
+    let mut distance_function_string: String = index_config.distance_function.clone().into();
+    if index_config.quantization.is_some() {
+        distance_function_string.push_str("_int8");
+    }
+    CString::new(distance_function_string).map_err(|e| {
+        Box::new(HnswIndexInitError::InvalidDistanceFunction(e.to_string())) as Box<dyn ChromaError>
+    })
+}
+
 fn read_and_return_hnsw_error(ffi_ptr: *const IndexPtrFFI) -> Result<(), Box<dyn ChromaError>> {
     let err = unsafe { get_last_error(ffi_ptr) };
     if !err.is_null() {
@@ -360,8 +416,10 @@ extern "C" {
     fn persist_dirty(index: *const IndexPtrFFI);
 
     fn add_item(index: *const IndexPtrFFI, data: *const f32, id: usize, replace_deleted: bool);
+    fn add_item_int8(index: *const IndexPtrFFI, data: *const i8, id: usize, replace_deleted: bool);
     fn mark_deleted(index: *const IndexPtrFFI, id: usize);
     fn get_item(index: *const IndexPtrFFI, id: usize, data: *mut f32);
+    fn get_item_int8(index: *const IndexPtrFFI, id: usize, data: *mut i8);
     fn knn_query(
         index: *const IndexPtrFFI,
         query_vector: *const f32,
@@ -373,6 +431,17 @@ extern "C" {
         disallowed_ids: *const usize,
         disallowed_ids_length: usize,
     ) -> c_int;
+    fn knn_query_int8(
+        index: *const IndexPtrFFI,
+        query_vector: *const i8,
+        k: usize,
+        ids: *mut usize,
+        distance: *mut f32,
+        allowed_ids: *const usize,
+        allowed_ids_length: usize,
+        disallowed_ids: *const usize,
+        disallowed_ids_length: usize,
+    ) -> c_int;
 
     #[cfg(test)]
     fn get_ef(index: *const IndexPtrFFI) -> c_int;
@@ -427,6 +496,7 @@ pub mod test {
             &IndexConfig {
                 dimensionality: d as i32,
                 distance_function,
+                quantization: None,
             },
             Some(&HnswIndexConfig {
                 max_elements: n,
@@ -459,6 +529,7 @@ pub mod test {
             &IndexConfig {
                 dimensionality: d as i32,
                 distance_function,
+                quantization: None,
             },
             Some(&HnswIndexConfig {
                 max_elements: n,
@@ -508,6 +579,7 @@ pub mod test {
             &IndexConfig {
                 dimensionality: d as i32,
                 distance_function,
+                quantization: None,
             },
             Some(&HnswIndexConfig {
                 max_elements: n,
@@ -563,6 +635,7 @@ pub mod test {
             &IndexConfig {
                 dimensionality: d as i32,
                 distance_function,
+                quantization: None,
             },
             Some(&HnswIndexConfig {
                 max_elements: n,
@@ -626,6 +699,7 @@ pub mod test {
             &IndexConfig {
                 dimensionality: d as i32,
                 distance_function: distance_function.clone(),
+                quantization: None,
             },
             Some(&HnswIndexConfig {
                 max_elements: n,
@@ -663,6 +737,7 @@ pub mod test {
             &IndexConfig {
                 dimensionality: d as i32,
                 distance_function,
+                quantization: None,
             },
             IndexUuid(id),
         );
@@ -689,6 +764,108 @@ pub mod test {
         index_data_same(&index, &ids, &data, d);
     }
 
+    #[test]
+    fn it_can_persist_and_load_quantized() {
+        let n = 1000;
+        let d: usize = 960;
+        let distance_function = DistanceFunction::Euclidean;
+        let quantization = ScalarQuantization::default();
+        let tmp_dir = tempdir().unwrap();
+        let persist_path = tmp_dir.path().to_str().unwrap().to_string();
+        let id = Uuid::new_v4();
+        let index = HnswIndex::init(
+            &IndexConfig {
+                dimensionality: d as i32,
+                distance_function: distance_function.clone(),
+                quantization: Some(quantization),
+            },
+            Some(&HnswIndexConfig {
+                max_elements: n,
+                m: 32,
+                ef_construction: 100,
+                ef_search: 100,
+                random_seed: 0,
+                persist_path: persist_path.clone(),
+            }),
+            IndexUuid(id),
+        );
+
+        let index = match index {
+            Err(e) => panic!("Error initializing index: {}", e),
+            Ok(index) => index,
+        };
+        assert_eq!(index.quantization(), Some(quantization));
+
+        let data: Vec<f32> = utils::generate_random_data(n, d);
+        let ids: Vec<usize> = (0..n).collect();
+
+        (0..n).for_each(|i| {
+            let data = &data[i * d..(i + 1) * d];
+            index.add(ids[i], data).expect("Should not error");
+        });
+
+        index.save().expect("Should not error");
+
+        let index = HnswIndex::load(
+            &persist_path,
+            &IndexConfig {
+                dimensionality: d as i32,
+                distance_function,
+                quantization: Some(quantization),
+            },
+            IndexUuid(id),
+        );
+
+        let index = match index {
+            Err(e) => panic!("Error loading index: {}", e),
+            Ok(index) => index,
+        };
+        index.set_ef(100).expect("Should not error");
+
+        // Query the data
+        let query = &data[0..d];
+        let (ids, distances) = index.query(query, 1, &[], &[]).unwrap();
+        assert_eq!(ids.len(), 1);
+        assert_eq!(distances.len(), 1);
+        assert_eq!(ids[0], 0);
+        assert_eq!(distances[0], 0.0);
+
+        // The data is only recovered within the quantization error
+        let actual_data = index
+            .get(0)
+            .expect("Should not error")
+            .expect("Should have data");
+        assert_eq!(actual_data.len(), d);
+        for (actual, expected) in actual_data.iter().zip(query) {
+            assert!((actual - expected).abs() <= quantization.scale / 2.0 + EPS);
+        }
+    }
+
+    #[test]
+    fn it_can_fit_quantization() {
+        let vectors = [vec![-3.0, 0.5, 2.0], vec![1.0, 5.0, 0.0]];
+        let quantization = ScalarQuantization::default();
+        assert_eq!(quantization.clipped(&vectors[0]), 2);
+        assert_eq!(quantization.clipped(&vectors[1]), 1);
+
+        let quantization = ScalarQuantization::fit(vectors.iter().map(Vec::as_slice))
+            .expect("There should be values to fit");
+        assert!((quantization.offset - 1.0).abs() <= EPS);
+        for vector in &vectors {
+            assert_eq!(quantization.clipped(vector), 0);
+            for (actual, expected) in quantization
+                .dequantize(&quantization.quantize(vector))
+                .iter()
+                .zip(vector)
+            {
+                assert!((actual - expected).abs() <= quantization.scale / 2.0 + EPS);
+            }
+        }
+        assert_eq!(quantization.clipped(&[-3.1, 5.1, 1.0]), 2);
+
+        assert_eq!(ScalarQuantization::fit(std::iter::empty()), None);
+    }
+
     #[test]
     fn it_can_add_and_query_with_allowed_and_disallowed_ids() {
         let n = 1000;
@@ -700,6 +877,7 @@ pub mod test {
             &IndexConfig {
                 dimensionality: d as i32,
                 distance_function,
+                quantization: None,
             },
             Some(&HnswIndexConfig {
                 max_elements: n,
@@ -745,6 +923,7 @@ pub mod test {
             &IndexConfig {
                 dimensionality: d as i32,
                 distance_function,
+                quantization: None,
             },
             Some(&HnswIndexConfig {
                 max_elements: n,
@@ -795,6 +974,7 @@ pub mod test {
             &IndexConfig {
                 dimensionality: d as i32,
                 distance_function,
+                quantization: None,
             },
             Some(&HnswIndexConfig {
                 max_elements: n,
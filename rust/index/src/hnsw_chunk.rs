@@ -0,0 +1,231 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::io::{AsyncRead, AsyncReadExt};
+use uuid::Uuid;
+
+// The gear table maps each byte to a random value. It is generated with a fixed seed, because
+// the chunk boundaries of a file must not change between versions of the service
+const GEAR: [u64; 256] = gear_table(0x6368_726f_6d61_6462);
+
+const fn gear_table(seed: u64) -> [u64; 256] {
+    let mut table = [0; 256];
+    let mut state = seed;
+    let mut i = 0;
+    while i < 256 {
+        // splitmix64
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        table[i] = z ^ (z >> 31);
+        i += 1;
+    }
+    table
+}
+
+/// Splits a file into chunks whose boundaries are defined by its content, so that a change to
+/// a part of the file only changes the chunks around it, and the chunks of the rest of the file
+/// are the same as before even if the change shifts them
+///
+/// # Parameters
+/// - `min_size`: The size below which a chunk is never cut
+/// - `max_size`: The size at which a chunk is always cut
+/// - `boundary_bits`: The number of bits of the rolling hash that must be zero at a boundary,
+///   so that the average chunk is `2^boundary_bits` bytes larger than `min_size`
+#[derive(Clone, Debug)]
+pub(crate) struct ContentChunker {
+    pub(crate) min_size: usize,
+    pub(crate) max_size: usize,
+    pub(crate) boundary_bits: u32,
+}
+
+impl Default for ContentChunker {
+    fn default() -> Self {
+        Self {
+            min_size: 1 << 20,
+            max_size: 16 << 20,
+            boundary_bits: 22,
+        }
+    }
+}
+
+impl ContentChunker {
+    #[cfg(test)]
+    pub(crate) fn split<'a>(&self, data: &'a [u8]) -> Vec<&'a [u8]> {
+        let mut chunks = Vec::new();
+        let mut rest = data;
+        while !rest.is_empty() {
+            let (chunk, tail) = rest.split_at(self.cut(rest));
+            chunks.push(chunk);
+            rest = tail;
+        }
+        chunks
+    }
+
+    // The length of the first chunk of the data, which only depends on its first `max_size`
+    // bytes
+    fn cut(&self, data: &[u8]) -> usize {
+        // The high bits of the gear hash depend on the most bytes
+        let mask = !(u64::MAX >> self.boundary_bits);
+        let limit = data.len().min(self.max_size);
+        let mut hash = 0u64;
+        for (i, byte) in data[..limit].iter().enumerate().skip(self.min_size) {
+            hash = (hash << 1).wrapping_add(GEAR[*byte as usize]);
+            if hash & mask == 0 {
+                return i + 1;
+            }
+        }
+        limit
+    }
+}
+
+/// Reads the chunks of a file one at a time, so that at most `max_size` bytes of the file are
+/// held at once. The chunks are the same as the ones that `ContentChunker::split` returns for
+/// the whole file
+pub(crate) struct ChunkReader<R> {
+    chunker: ContentChunker,
+    reader: R,
+    buffer: Vec<u8>,
+    exhausted: bool,
+}
+
+impl<R: AsyncRead + Unpin> ChunkReader<R> {
+    pub(crate) fn new(chunker: ContentChunker, reader: R) -> Self {
+        Self {
+            chunker,
+            reader,
+            buffer: Vec::new(),
+            exhausted: false,
+        }
+    }
+
+    pub(crate) async fn next_chunk(&mut self) -> std::io::Result<Option<Vec<u8>>> {
+        // A chunk is cut once `max_size` bytes are buffered or the file is read to its end
+        while !self.exhausted && self.buffer.len() < self.chunker.max_size {
+            let filled = self.buffer.len();
+            self.buffer.resize(self.chunker.max_size, 0);
+            let read = self.reader.read(&mut self.buffer[filled..]).await?;
+            self.buffer.truncate(filled + read);
+            self.exhausted = read == 0;
+        }
+        if self.buffer.is_empty() {
+            return Ok(None);
+        }
+        let rest = self.buffer.split_off(self.chunker.cut(&self.buffer));
+        Ok(Some(std::mem::replace(&mut self.buffer, rest)))
+    }
+}
+
+/// The hex encoded SHA-256 digest of a chunk, which names the chunk in storage
+pub(crate) fn chunk_hash(chunk: &[u8]) -> String {
+    Sha256::digest(chunk)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// A chunk of an HNSW index file
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct HnswChunk {
+    pub(crate) hash: String,
+    pub(crate) len: usize,
+}
+
+/// The chunks of each file of an HNSW index, in the order that they are concatenated in. The
+/// chunks are stored under the collection of the index
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct HnswManifest {
+    pub(crate) collection_id: Uuid,
+    pub(crate) files: BTreeMap<String, Vec<HnswChunk>>,
+}
+
+impl HnswManifest {
+    pub(crate) fn chunk_hashes(&self) -> impl Iterator<Item = &String> {
+        self.files.values().flatten().map(|chunk| &chunk.hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use rand::{Rng, SeedableRng};
+
+    use super::{chunk_hash, ChunkReader, ContentChunker};
+
+    fn chunker() -> ContentChunker {
+        ContentChunker {
+            min_size: 256,
+            max_size: 4096,
+            boundary_bits: 10,
+        }
+    }
+
+    #[test]
+    fn test_split_covers_data() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let data = (0..100_000).map(|_| rng.gen()).collect::<Vec<u8>>();
+        let chunks = chunker().split(&data);
+        assert!(chunks.len() > 1);
+        assert_eq!(chunks.concat(), data);
+        for chunk in &chunks[..chunks.len() - 1] {
+            assert!((256..=4096).contains(&chunk.len()));
+        }
+
+        // A file without any boundary is cut at the maximum size
+        let zeros = vec![0; 10_000];
+        let lens = chunker()
+            .split(&zeros)
+            .iter()
+            .map(|chunk| chunk.len())
+            .collect::<Vec<_>>();
+        assert_eq!(lens, vec![4096, 4096, 1808]);
+    }
+
+    #[test]
+    fn test_split_is_stable_under_insertion() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let data = (0..100_000).map(|_| rng.gen()).collect::<Vec<u8>>();
+        let mut changed = data.clone();
+        changed.splice(50_000..50_000, [1, 2, 3, 4, 5]);
+
+        let before = chunker()
+            .split(&data)
+            .into_iter()
+            .map(chunk_hash)
+            .collect::<HashSet<_>>();
+        let after = chunker()
+            .split(&changed)
+            .into_iter()
+            .map(chunk_hash)
+            .collect::<Vec<_>>();
+        let reused = after.iter().filter(|hash| before.contains(*hash)).count();
+        assert!(
+            after.len() - reused <= 3,
+            "{reused} of {} reused",
+            after.len()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_chunk_reader_matches_split() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(3);
+        let data = (0..100_000).map(|_| rng.gen()).collect::<Vec<u8>>();
+        let mut reader = ChunkReader::new(chunker(), data.as_slice());
+        let mut chunks = Vec::new();
+        while let Some(chunk) = reader.next_chunk().await.unwrap() {
+            assert!(chunk.len() <= 4096);
+            chunks.push(chunk);
+        }
+        assert_eq!(
+            chunks,
+            chunker()
+                .split(&data)
+                .into_iter()
+                .map(<[u8]>::to_vec)
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -1,9 +1,10 @@
+use crate::hnsw_chunk::{chunk_hash, ChunkReader, ContentChunker, HnswChunk, HnswManifest};
 use crate::PersistentIndex;
 
 use super::config::HnswProviderConfig;
 use super::{
     HnswIndex, HnswIndexConfig, HnswIndexFromSegmentError, Index, IndexConfig,
-    IndexConfigFromSegmentError, IndexUuid,
+    IndexConfigFromSegmentError, IndexUuid, ScalarQuantization,
 };
 
 use async_trait::async_trait;
@@ -12,11 +13,14 @@ use chroma_config::Configurable;
 use chroma_distance::DistanceFunction;
 use chroma_error::ChromaError;
 use chroma_error::ErrorCodes;
-use chroma_storage::Storage;
+use chroma_storage::{DeleteError, GetError, ListError, ObjectMetadata, PutOptions, Storage};
 use chroma_types::CollectionUuid;
-use parking_lot::RwLock;
+use futures::{future, stream, StreamExt, TryStreamExt};
+use parking_lot::{Mutex, RwLock};
+use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 use std::path::Path;
+use std::time::SystemTime;
 use std::{path::PathBuf, sync::Arc};
 use thiserror::Error;
 use tokio::io::AsyncWriteExt;
@@ -33,8 +37,14 @@ const FILES: [&str; 4] = [
     "link_lists.bin",
 ];
 
+// The number of chunks of an index file that are fetched or uploaded concurrently
+const CHUNK_CONCURRENCY: usize = 16;
+
 type CacheKey = CollectionUuid;
 
+// The weight of the indexes in the cache is in MiB
+const INDEX_WEIGHT_BYTES: usize = 1024 * 1024;
+
 // The key of the cache is the collection id and the value is
 // the HNSW index for that collection. This restricts the cache to
 // contain atmost one index per collection. Ideally, we would like
@@ -53,11 +63,48 @@ pub struct HnswIndexProvider {
     cache: Arc<dyn Cache<CollectionUuid, HnswIndexRef>>,
     pub temporary_storage_path: PathBuf,
     storage: Storage,
+    put_options: PutOptions,
+    delta_upload: bool,
+    // The chunks in storage that the forked indexes share with the indexes they are forked from
+    forked_chunks: Arc<Mutex<HashMap<IndexUuid, HashSet<String>>>>,
+    // The indexes whose local files are being removed after they failed to load
+    repairing: Arc<Mutex<HashSet<IndexUuid>>>,
     write_mutex: Arc<tokio::sync::Mutex<()>>,
     #[allow(dead_code)]
     purger: Option<Arc<tokio::task::JoinHandle<()>>>,
 }
 
+/// An object that the HNSW indexes keep in storage
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum HnswObject {
+    /// A file of an index that is flushed as whole files
+    File(IndexUuid, String),
+    /// The manifest of an index that is flushed as chunks
+    Manifest(IndexUuid),
+    /// A chunk of the indexes of a collection, which the manifests of several indexes may
+    /// reference
+    Chunk(CollectionUuid, String),
+}
+
+// The object that the key names, or None if it is not the key of an index object
+fn parse_object(object: &ObjectMetadata) -> Option<HnswObject> {
+    let key = object.key.strip_prefix("hnsw/")?;
+    if let Some(chunk) = key.strip_prefix("chunks/") {
+        let (collection_id, hash) = chunk.split_once('/')?;
+        let collection_id = CollectionUuid(Uuid::parse_str(collection_id).ok()?);
+        return Some(HnswObject::Chunk(collection_id, hash.to_string()));
+    }
+    let (id, file) = key.split_once('/')?;
+    let id = IndexUuid(Uuid::parse_str(id).ok()?);
+    if file == "manifest.json" {
+        Some(HnswObject::Manifest(id))
+    } else if FILES.contains(&file) {
+        Some(HnswObject::File(id, file.to_string()))
+    } else {
+        None
+    }
+}
+
 #[derive(Clone)]
 pub struct HnswIndexRef {
     pub inner: Arc<RwLock<HnswIndex>>,
@@ -87,7 +134,8 @@ impl Configurable<(HnswProviderConfig, Storage)> for HnswIndexProvider {
             PathBuf::from(&hnsw_config.hnsw_temporary_path),
             cache,
             rx,
-        ))
+        )
+        .with_delta_upload(hnsw_config.hnsw_delta_upload))
     }
 }
 
@@ -97,7 +145,11 @@ impl chroma_cache::Weighted for HnswIndexRef {
         if index.len() == 0 {
             return 1;
         }
-        let bytes = index.len() * std::mem::size_of::<f32>() * index.dimensionality() as usize;
+        let element_size = match index.quantization() {
+            Some(_) => std::mem::size_of::<i8>(),
+            None => std::mem::size_of::<f32>(),
+        };
+        let bytes = index.len() * element_size * index.dimensionality() as usize;
         let as_mb = bytes / 1024 / 1024;
         if as_mb == 0 {
             1
@@ -130,12 +182,50 @@ impl HnswIndexProvider {
         Self {
             cache,
             storage,
+            put_options: PutOptions::default(),
+            delta_upload: false,
+            forked_chunks: Arc::default(),
+            repairing: Arc::default(),
             temporary_storage_path: storage_path,
             write_mutex: Arc::new(tokio::sync::Mutex::new(())),
             purger,
         }
     }
 
+    /// Sets the options of the writes of the index files flushed by this provider
+    pub fn with_put_options(mut self, put_options: PutOptions) -> Self {
+        self.put_options = put_options;
+        self
+    }
+
+    /// Flushes the index files as content-defined chunks with a manifest, so that only the
+    /// chunks that changed since the index was forked are uploaded. Indexes are always loaded
+    /// from their manifest if they have one, so this must only be enabled once every reader
+    /// understands manifests
+    pub fn with_delta_upload(mut self, delta_upload: bool) -> Self {
+        self.delta_upload = delta_upload;
+        self
+    }
+
+    /// The bytes of the indexes that the provider holds in its cache
+    pub fn cache_usage_bytes(&self) -> usize {
+        self.cache.usage() * INDEX_WEIGHT_BYTES
+    }
+
+    /// The bytes of the indexes that the cache may hold, if it is bounded
+    pub fn cache_capacity_bytes(&self) -> Option<usize> {
+        self.cache
+            .capacity()
+            .map(|capacity| capacity.saturating_mul(INDEX_WEIGHT_BYTES))
+    }
+
+    /// Bounds the bytes of the indexes that the cache holds, and evicts indexes until it holds
+    /// no more. The files of the evicted indexes are purged from the temporary storage
+    pub fn resize_cache(&self, capacity_bytes: usize) {
+        self.cache
+            .resize((capacity_bytes / INDEX_WEIGHT_BYTES).max(1));
+    }
+
     pub async fn get(&self, index_id: &IndexUuid, cache_key: &CacheKey) -> Option<HnswIndexRef> {
         match self.cache.get(cache_key).await.ok().flatten() {
             Some(index) => {
@@ -155,12 +245,87 @@ impl HnswIndexProvider {
         format!("hnsw/{}/{}", id, file)
     }
 
+    fn format_manifest_key(&self, id: &IndexUuid) -> String {
+        format!("hnsw/{}/manifest.json", id)
+    }
+
+    // Chunks are named by their content, so that the indexes forked from each other share the
+    // chunks that they have in common. The chunks are not shared across collections, since the
+    // objects of a tenant can be encrypted with its own key and are deleted with its collections
+    fn format_chunk_key(&self, collection_id: &CollectionUuid, hash: &str) -> String {
+        format!("hnsw/chunks/{}/{}", collection_id, hash)
+    }
+
+    fn format_object_key(&self, object: &HnswObject) -> String {
+        match object {
+            HnswObject::File(id, file) => self.format_key(id, file),
+            HnswObject::Manifest(id) => self.format_manifest_key(id),
+            HnswObject::Chunk(collection_id, hash) => self.format_chunk_key(collection_id, hash),
+        }
+    }
+
+    /// Lists the index files, manifests and chunks that are in storage, with when they were
+    /// written
+    pub async fn list_objects(
+        &self,
+    ) -> Result<Vec<(HnswObject, SystemTime)>, Box<HnswIndexProviderObjectError>> {
+        let objects = self
+            .storage
+            .list("hnsw/")
+            .await
+            .map_err(|e| Box::new(HnswIndexProviderObjectError::ListError(e)))?;
+        Ok(objects
+            .into_iter()
+            .filter_map(|object| match parse_object(&object) {
+                Some(parsed) => Some((parsed, object.last_modified)),
+                None => {
+                    tracing::warn!("Skipping object with unexpected key {}", object.key);
+                    None
+                }
+            })
+            .collect())
+    }
+
+    /// Returns the chunks that the manifest of the index references, or None if the index has
+    /// no manifest in storage
+    pub async fn referenced_chunks(
+        &self,
+        id: &IndexUuid,
+    ) -> Result<Option<Vec<HnswObject>>, Box<HnswIndexProviderObjectError>> {
+        let bytes = match self.storage.get(&self.format_manifest_key(id)).await {
+            Ok(bytes) => bytes,
+            Err(GetError::NoSuchKey(_)) => return Ok(None),
+            Err(e) => return Err(Box::new(HnswIndexProviderObjectError::GetError(e))),
+        };
+        let manifest: HnswManifest = serde_json::from_slice(&bytes)
+            .map_err(|e| Box::new(HnswIndexProviderObjectError::ManifestError(e)))?;
+        let collection_id = CollectionUuid(manifest.collection_id);
+        Ok(Some(
+            manifest
+                .chunk_hashes()
+                .map(|hash| HnswObject::Chunk(collection_id, hash.clone()))
+                .collect(),
+        ))
+    }
+
+    /// Deletes the object from storage
+    pub async fn delete_object(
+        &self,
+        object: &HnswObject,
+    ) -> Result<(), Box<HnswIndexProviderObjectError>> {
+        self.storage
+            .delete(&self.format_object_key(object))
+            .await
+            .map_err(|e| Box::new(HnswIndexProviderObjectError::DeleteError(e)))
+    }
+
     pub async fn fork(
         &self,
         source_id: &IndexUuid,
         cache_key: &CacheKey,
         dimensionality: i32,
         distance_function: DistanceFunction,
+        quantization: Option<ScalarQuantization>,
     ) -> Result<HnswIndexRef, Box<HnswIndexProviderForkError>> {
         let new_id = IndexUuid(Uuid::new_v4());
         let new_storage_path = self.temporary_storage_path.join(new_id.to_string());
@@ -177,13 +342,20 @@ impl HnswIndexProvider {
             .load_hnsw_segment_into_directory(source_id, &new_storage_path)
             .await
         {
-            Ok(_) => {}
+            // Only the chunks of the same collection are shared with the forked index
+            Ok(Some(manifest)) if manifest.collection_id == cache_key.0 => {
+                self.forked_chunks
+                    .lock()
+                    .insert(new_id, manifest.chunk_hashes().cloned().collect());
+            }
+            Ok(Some(_)) => {}
+            Ok(None) => {}
             Err(e) => {
                 return Err(Box::new(HnswIndexProviderForkError::FileError(*e)));
             }
         }
 
-        let index_config = IndexConfig::new(dimensionality, distance_function);
+        let index_config = IndexConfig::new(dimensionality, distance_function, quantization);
 
         let storage_path_str = match new_storage_path.to_str() {
             Some(storage_path_str) => storage_path_str,
@@ -244,12 +416,31 @@ impl HnswIndexProvider {
         }
     }
 
+    /// Fetches the files of the index from storage into the index storage path, and returns
+    /// the manifest of the index if it is flushed as chunks
     #[instrument]
     async fn load_hnsw_segment_into_directory(
         &self,
         source_id: &IndexUuid,
         index_storage_path: &Path,
-    ) -> Result<(), Box<HnswIndexProviderFileError>> {
+    ) -> Result<Option<HnswManifest>, Box<HnswIndexProviderFileError>> {
+        let manifest_key = self.format_manifest_key(source_id);
+        match self.storage.get(&manifest_key).await {
+            Ok(bytes) => {
+                let manifest: HnswManifest = serde_json::from_slice(&bytes)
+                    .map_err(|e| Box::new(HnswIndexProviderFileError::ManifestError(e)))?;
+                self.load_hnsw_chunks_into_directory(&manifest, index_storage_path)
+                    .await?;
+                return Ok(Some(manifest));
+            }
+            // The index is flushed as whole files
+            Err(GetError::NoSuchKey(_)) => {}
+            Err(e) => {
+                tracing::error!("Failed to load hnsw index manifest from storage: {}", e);
+                return Err(Box::new(HnswIndexProviderFileError::StorageGetError(e)));
+            }
+        }
+
         // Fetch the files from storage and put them in the index storage path.
         for file in FILES.iter() {
             let s3_fetch_span =
@@ -282,6 +473,82 @@ impl HnswIndexProvider {
             let bytes_read = buf.len();
             self.copy_bytes_to_local_file(&file_path, buf).instrument(tracing::info_span!(parent: Span::current(), "hnsw provider copy bytes to local file", file = file, bytes = bytes_read)).await?;
         }
+        Ok(None)
+    }
+
+    async fn load_hnsw_chunks_into_directory(
+        &self,
+        manifest: &HnswManifest,
+        index_storage_path: &Path,
+    ) -> Result<(), Box<HnswIndexProviderFileError>> {
+        let collection_id = CollectionUuid(manifest.collection_id);
+        for file in FILES.iter() {
+            let chunks = manifest.files.get(*file).ok_or_else(|| {
+                Box::new(HnswIndexProviderFileError::MissingManifestFile(
+                    file.to_string(),
+                ))
+            })?;
+            // The chunks are written to a temporary file as they are fetched, which is renamed
+            // once it is complete so that a partially written file is never loaded
+            let file_path = index_storage_path.join(file);
+            let temp_path = index_storage_path.join(format!("{}.{}.tmp", file, Uuid::new_v4()));
+            let res = self
+                .write_chunks_to_file(&collection_id, chunks, &temp_path)
+                .await;
+            let res = match res {
+                Ok(()) => tokio::fs::rename(&temp_path, &file_path)
+                    .await
+                    .map_err(HnswIndexProviderFileError::IOError),
+                Err(e) => Err(e),
+            };
+            if let Err(e) = res {
+                tracing::error!("Failed to load hnsw index file {}: {}", file, e);
+                let _ = tokio::fs::remove_file(&temp_path).await;
+                return Err(Box::new(e));
+            }
+            tracing::info!(
+                "Fetched {} chunks for hnsw index file {}",
+                chunks.len(),
+                file
+            );
+        }
+        Ok(())
+    }
+
+    async fn write_chunks_to_file(
+        &self,
+        collection_id: &CollectionUuid,
+        chunks: &[HnswChunk],
+        file_path: &Path,
+    ) -> Result<(), HnswIndexProviderFileError> {
+        let mut file_handle = tokio::fs::File::create(file_path).await?;
+        // The fetches own what they read, so that the stream does not hold a closure over
+        // borrowed chunks, which would keep the future of the caller from being Send
+        let fetches = chunks
+            .iter()
+            .map(|chunk| {
+                let storage = self.storage.clone();
+                let key = self.format_chunk_key(collection_id, &chunk.hash);
+                let hash = chunk.hash.clone();
+                let expected = chunk.len;
+                async move {
+                    let buf = storage.get(&key).await?;
+                    if buf.len() != expected {
+                        return Err(HnswIndexProviderFileError::ChunkLengthMismatch {
+                            hash,
+                            expected,
+                            actual: buf.len(),
+                        });
+                    }
+                    Ok(buf)
+                }
+            })
+            .collect::<Vec<_>>();
+        let mut bufs = stream::iter(fetches).buffered(CHUNK_CONCURRENCY);
+        while let Some(buf) = bufs.try_next().await? {
+            file_handle.write_all(&buf).await?;
+        }
+        file_handle.flush().await?;
         Ok(())
     }
 
@@ -291,6 +558,7 @@ impl HnswIndexProvider {
         cache_key: &CacheKey,
         dimensionality: i32,
         distance_function: DistanceFunction,
+        quantization: Option<ScalarQuantization>,
     ) -> Result<HnswIndexRef, Box<HnswIndexProviderOpenError>> {
         let index_storage_path = self.temporary_storage_path.join(id.to_string());
 
@@ -313,7 +581,7 @@ impl HnswIndexProvider {
         }
 
         // Thread safe.
-        let index_config = IndexConfig::new(dimensionality, distance_function);
+        let index_config = IndexConfig::new(dimensionality, distance_function, quantization);
 
         let index_storage_path_str = match index_storage_path.to_str() {
             Some(index_storage_path_str) => index_storage_path_str,
@@ -352,6 +620,7 @@ impl HnswIndexProvider {
     // Cases
     // A query comes in and the index is in the cache -> we can query the index based on segment files id (Same as compactor case 3 where we have the index)
     // A query comes in and the index is not in the cache -> we need to load the index from s3 based on the segment files id
+    #[allow(clippy::too_many_arguments)]
     pub async fn create(
         &self,
         cache_key: &CacheKey,
@@ -360,6 +629,7 @@ impl HnswIndexProvider {
         ef_search: usize,
         dimensionality: i32,
         distance_function: DistanceFunction,
+        quantization: Option<ScalarQuantization>,
     ) -> Result<HnswIndexRef, Box<HnswIndexProviderCreateError>> {
         let id = IndexUuid(Uuid::new_v4());
         let index_storage_path = self.temporary_storage_path.join(id.to_string());
@@ -371,7 +641,7 @@ impl HnswIndexProvider {
             }
         }
 
-        let index_config = IndexConfig::new(dimensionality, distance_function);
+        let index_config = IndexConfig::new(dimensionality, distance_function, quantization);
 
         let hnsw_config =
             match HnswIndexConfig::new(m, ef_construction, ef_search, &index_storage_path) {
@@ -409,14 +679,21 @@ impl HnswIndexProvider {
         Ok(())
     }
 
-    pub async fn flush(&self, id: &IndexUuid) -> Result<(), Box<HnswIndexProviderFlushError>> {
+    pub async fn flush(
+        &self,
+        id: &IndexUuid,
+        cache_key: &CacheKey,
+    ) -> Result<(), Box<HnswIndexProviderFlushError>> {
+        if self.delta_upload {
+            return self.flush_chunks(id, cache_key).await;
+        }
         let index_storage_path = self.temporary_storage_path.join(id.to_string());
         for file in FILES.iter() {
             let file_path = index_storage_path.join(file);
             let key = self.format_key(id, file);
             let res = self
                 .storage
-                .put_file(&key, file_path.to_str().unwrap())
+                .put_file_with_options(&key, file_path.to_str().unwrap(), &self.put_options)
                 .await;
             match res {
                 Ok(_) => {
@@ -430,6 +707,96 @@ impl HnswIndexProvider {
         Ok(())
     }
 
+    /// Flushes the files of the index as content-defined chunks, and uploads only the chunks
+    /// that are not shared with the index it is forked from. The files are read a chunk at a
+    /// time. The manifest is written last, so that an index is never loaded before all its
+    /// chunks are in storage
+    async fn flush_chunks(
+        &self,
+        id: &IndexUuid,
+        cache_key: &CacheKey,
+    ) -> Result<(), Box<HnswIndexProviderFlushError>> {
+        let index_storage_path = self.temporary_storage_path.join(id.to_string());
+        let mut stored_chunks = self.forked_chunks.lock().remove(id).unwrap_or_default();
+        let chunker = ContentChunker::default();
+        let mut manifest = HnswManifest {
+            collection_id: cache_key.0,
+            ..Default::default()
+        };
+        let (mut uploaded_bytes, mut total_bytes) = (0, 0);
+        for file in FILES.iter() {
+            let file_handle = tokio::fs::File::open(index_storage_path.join(file))
+                .await
+                .map_err(|e| Box::new(HnswIndexProviderFlushError::IOError(e)))?;
+            let mut file_chunks = Vec::new();
+            stream::try_unfold(
+                ChunkReader::new(chunker.clone(), file_handle),
+                |mut reader| async move {
+                    let chunk = reader.next_chunk().await?;
+                    Ok::<_, std::io::Error>(chunk.map(|chunk| (chunk, reader)))
+                },
+            )
+            .map_err(HnswIndexProviderFlushError::IOError)
+            .try_filter_map(|chunk| {
+                let hash = chunk_hash(&chunk);
+                total_bytes += chunk.len();
+                file_chunks.push(HnswChunk {
+                    hash: hash.clone(),
+                    len: chunk.len(),
+                });
+                let new_chunk = stored_chunks.insert(hash.clone()).then(|| {
+                    uploaded_bytes += chunk.len();
+                    (self.format_chunk_key(cache_key, &hash), chunk)
+                });
+                future::ready(Ok(new_chunk))
+            })
+            .map_ok(|(key, chunk)| async move {
+                self.storage
+                    .put_bytes_with_options(&key, chunk, &self.put_options)
+                    .await
+                    .map_err(HnswIndexProviderFlushError::StoragePutError)
+            })
+            .try_buffer_unordered(CHUNK_CONCURRENCY)
+            .try_collect::<Vec<_>>()
+            .await
+            .map_err(Box::new)?;
+            manifest.files.insert(file.to_string(), file_chunks);
+        }
+
+        let manifest_bytes = serde_json::to_vec(&manifest)
+            .map_err(|e| Box::new(HnswIndexProviderFlushError::ManifestError(e)))?;
+        self.storage
+            .put_bytes_with_options(
+                &self.format_manifest_key(id),
+                manifest_bytes,
+                &self.put_options,
+            )
+            .await
+            .map_err(|e| Box::new(HnswIndexProviderFlushError::StoragePutError(e)))?;
+        tracing::info!(
+            "Flushed hnsw index {} uploading {} of {} bytes",
+            id,
+            uploaded_bytes,
+            total_bytes
+        );
+        Ok(())
+    }
+
+    /// Evicts the cached index of the collection if it is not the current index,
+    /// so that the memory held by a superseded index is released as soon as possible.
+    pub async fn evict_superseded(&self, cache_key: &CacheKey, current_id: &IndexUuid) {
+        let cached_id = self
+            .cache
+            .get(cache_key)
+            .await
+            .ok()
+            .flatten()
+            .map(|index| index.inner.read().id);
+        if cached_id.is_some_and(|id| id != *current_id) {
+            self.cache.remove(cache_key).await;
+        }
+    }
+
     /// Purge entries from the cache by index ID and remove temporary files from disk.
     pub async fn purge_by_id(&mut self, cache_keys: &[CacheKey]) {
         for collection_uuid in cache_keys {
@@ -452,6 +819,25 @@ impl HnswIndexProvider {
         }
     }
 
+    /// Removes the local files of an index that failed to load in the background, so that the
+    /// next attempt to open it downloads a fresh copy from storage. Returns whether a repair was
+    /// started, which is not the case if the index is already being repaired
+    pub fn repair(&self, id: IndexUuid) -> bool {
+        if !self.repairing.lock().insert(id) {
+            return false;
+        }
+        let temporary_storage_path = self.temporary_storage_path.clone();
+        let repairing = self.repairing.clone();
+        tokio::spawn(async move {
+            tracing::warn!("Repairing index: {}", id);
+            if let Err(e) = Self::purge_one_id(&temporary_storage_path, id).await {
+                tracing::error!("Failed to remove temporary files for {id}: {e}");
+            }
+            repairing.lock().remove(&id);
+        });
+        true
+    }
+
     pub async fn purge_one_id(path: &Path, id: IndexUuid) -> tokio::io::Result<()> {
         let index_storage_path = path.join(id.to_string());
         tracing::info!("Purging index: {}", index_storage_path.to_str().unwrap());
@@ -571,6 +957,10 @@ pub enum HnswIndexProviderFlushError {
     HnswSaveError(#[from] Box<dyn ChromaError>),
     #[error("Storage Put Error")]
     StoragePutError(#[from] chroma_storage::PutError),
+    #[error("IO Error")]
+    IOError(#[from] std::io::Error),
+    #[error("Manifest serialization error: {0}")]
+    ManifestError(#[from] serde_json::Error),
 }
 
 impl ChromaError for HnswIndexProviderFlushError {
@@ -579,6 +969,8 @@ impl ChromaError for HnswIndexProviderFlushError {
             HnswIndexProviderFlushError::NoIndexFound(_) => ErrorCodes::NotFound,
             HnswIndexProviderFlushError::HnswSaveError(e) => e.code(),
             HnswIndexProviderFlushError::StoragePutError(e) => e.code(),
+            HnswIndexProviderFlushError::IOError(_) => ErrorCodes::Internal,
+            HnswIndexProviderFlushError::ManifestError(_) => ErrorCodes::Internal,
         }
     }
 }
@@ -591,6 +983,39 @@ pub enum HnswIndexProviderFileError {
     StorageGetError(#[from] chroma_storage::GetError),
     #[error("Storage Put Error")]
     StoragePutError(#[from] chroma_storage::PutError),
+    #[error("Manifest deserialization error: {0}")]
+    ManifestError(#[from] serde_json::Error),
+    #[error("Manifest has no chunks for file: {0}")]
+    MissingManifestFile(String),
+    #[error("Chunk {hash} has {actual} bytes instead of {expected}")]
+    ChunkLengthMismatch {
+        hash: String,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+#[derive(Error, Debug)]
+pub enum HnswIndexProviderObjectError {
+    #[error("Storage List Error: {0}")]
+    ListError(#[from] ListError),
+    #[error("Storage Get Error: {0}")]
+    GetError(#[from] GetError),
+    #[error("Storage Delete Error: {0}")]
+    DeleteError(#[from] DeleteError),
+    #[error("Manifest deserialization error: {0}")]
+    ManifestError(#[from] serde_json::Error),
+}
+
+impl ChromaError for HnswIndexProviderObjectError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            HnswIndexProviderObjectError::ListError(e) => e.code(),
+            HnswIndexProviderObjectError::GetError(e) => e.code(),
+            HnswIndexProviderObjectError::DeleteError(e) => e.code(),
+            HnswIndexProviderObjectError::ManifestError(_) => ErrorCodes::Internal,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -601,6 +1026,37 @@ mod tests {
     use chroma_cache::new_non_persistent_cache_for_test;
     use chroma_storage::local::LocalStorage;
 
+    #[tokio::test]
+    async fn test_repair() {
+        let storage_dir = tempfile::tempdir().unwrap().path().to_path_buf();
+        let hnsw_tmp_path = storage_dir.join("hnsw");
+        let storage = Storage::Local(LocalStorage::new(storage_dir.to_str().unwrap()));
+        let cache = new_non_persistent_cache_for_test();
+        let (_tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        let provider = HnswIndexProvider::new(storage, hnsw_tmp_path.clone(), cache, rx);
+
+        let index_id = IndexUuid(Uuid::new_v4());
+        let index_path = hnsw_tmp_path.join(index_id.to_string());
+        tokio::fs::create_dir_all(&index_path).await.unwrap();
+        tokio::fs::write(index_path.join("header.bin"), b"corrupt")
+            .await
+            .unwrap();
+
+        // A repair in progress is not started again
+        assert!(provider.repair(index_id));
+        assert!(!provider.repair(index_id));
+
+        tokio::time::timeout(std::time::Duration::from_secs(10), async {
+            while !provider.repairing.lock().is_empty() {
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("Repair should finish");
+        assert!(!index_path.exists());
+        assert!(provider.repair(index_id));
+    }
+
     #[tokio::test]
     async fn test_fork() {
         let storage_dir = tempfile::tempdir().unwrap().path().to_path_buf();
@@ -625,6 +1081,7 @@ mod tests {
                 DEFAULT_HNSW_EF_SEARCH,
                 dimensionality,
                 distance_function.clone(),
+                None,
             )
             .await
             .unwrap();
@@ -636,6 +1093,7 @@ mod tests {
                 &collection_id,
                 dimensionality,
                 distance_function,
+                None,
             )
             .await
             .unwrap();
@@ -643,4 +1101,104 @@ mod tests {
 
         assert_ne!(created_index_id, forked_index_id);
     }
+
+    #[tokio::test]
+    async fn test_delta_upload() {
+        let storage_dir = tempfile::tempdir().unwrap().path().to_path_buf();
+        tokio::fs::create_dir_all(&storage_dir).await.unwrap();
+        let storage = Storage::Local(LocalStorage::new(storage_dir.to_str().unwrap()));
+        let new_provider = |tmp_dir: &str| {
+            let (_tx, rx) = tokio::sync::mpsc::unbounded_channel();
+            HnswIndexProvider::new(
+                storage.clone(),
+                storage_dir.join(tmp_dir),
+                new_non_persistent_cache_for_test(),
+                rx,
+            )
+            .with_delta_upload(true)
+        };
+        let collection_id = CollectionUuid(Uuid::new_v4());
+        let dimensionality = 4;
+        let distance_function = DistanceFunction::Euclidean;
+
+        let provider = new_provider("writer");
+        let created_index = provider
+            .create(
+                &collection_id,
+                DEFAULT_HNSW_M,
+                DEFAULT_HNSW_EF_CONSTRUCTION,
+                DEFAULT_HNSW_EF_SEARCH,
+                dimensionality,
+                distance_function.clone(),
+                None,
+            )
+            .await
+            .unwrap();
+        for id in 1..=10 {
+            created_index.inner.read().add(id, &[id as f32; 4]).unwrap();
+        }
+        let created_index_id = created_index.inner.read().id;
+        provider.commit(created_index).unwrap();
+        provider
+            .flush(&created_index_id, &collection_id)
+            .await
+            .unwrap();
+
+        // The index is flushed as chunks of its collection with a manifest instead of whole
+        // files
+        let manifest_bytes = storage
+            .get(&provider.format_manifest_key(&created_index_id))
+            .await
+            .expect("Manifest should be flushed");
+        let manifest: HnswManifest = serde_json::from_slice(&manifest_bytes).unwrap();
+        assert_eq!(manifest.collection_id, collection_id.0);
+        for hash in manifest.chunk_hashes() {
+            storage
+                .get(&provider.format_chunk_key(&collection_id, hash))
+                .await
+                .expect("Chunk should be flushed under its collection");
+        }
+        assert!(matches!(
+            storage
+                .get(&provider.format_key(&created_index_id, FILES[0]))
+                .await,
+            Err(GetError::NoSuchKey(_))
+        ));
+
+        let forked_index = provider
+            .fork(
+                &created_index_id,
+                &collection_id,
+                dimensionality,
+                distance_function.clone(),
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(forked_index.inner.read().len(), 10);
+        forked_index.inner.read().add(11, &[11.0; 4]).unwrap();
+        let forked_index_id = forked_index.inner.read().id;
+        provider.commit(forked_index).unwrap();
+        provider
+            .flush(&forked_index_id, &collection_id)
+            .await
+            .unwrap();
+        assert!(provider.forked_chunks.lock().is_empty());
+
+        let opened_index = new_provider("reader")
+            .open(
+                &forked_index_id,
+                &collection_id,
+                dimensionality,
+                distance_function,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(opened_index.inner.read().len(), 11);
+        assert_eq!(
+            opened_index.inner.read().get(11).unwrap(),
+            Some(vec![11.0; 4])
+        );
+    }
 }
@@ -1,6 +1,9 @@
+#![recursion_limit = "256"]
+
 pub mod config;
 pub mod fulltext;
 mod hnsw;
+mod hnsw_chunk;
 pub mod hnsw_provider;
 pub mod metadata;
 mod types;
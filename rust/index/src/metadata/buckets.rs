@@ -0,0 +1,439 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use chroma_blockstore::{key::KeyWrapper, BlockfileFlusher};
+use futures::{stream, stream::BoxStream, StreamExt, TryStreamExt};
+use roaring::RoaringBitmap;
+use uuid::Uuid;
+
+use super::types::{
+    MetadataIndexError, MetadataIndexFlusher, MetadataIndexReader, MetadataIndexWriter,
+};
+
+/// The most buckets that the metadata keys of an index are spread over
+pub const MAX_METADATA_KEY_BUCKETS: usize = 64;
+
+/// Returns the bucket of the metadata key, i.e. the blockfile of the index that holds its
+/// posting lists. The key is hashed with FNV-1a, so that the bucket is stable across processes
+/// and versions
+pub fn metadata_key_bucket(metadata_key: &str, num_buckets: usize) -> usize {
+    if num_buckets <= 1 {
+        return 0;
+    }
+    let hash = metadata_key
+        .bytes()
+        .fold(0xcbf29ce484222325u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+    (hash % num_buckets as u64) as usize
+}
+
+/// A metadata index whose metadata keys are spread over a blockfile per bucket. The posting
+/// lists of a key are only ever in the blockfile of its bucket, so a lookup reads a single
+/// blockfile, and a write to a key leaves the blockfiles of the other buckets untouched
+#[derive(Clone)]
+pub struct BucketedMetadataIndexReader<'me> {
+    buckets: Vec<MetadataIndexReader<'me>>,
+}
+
+impl<'me> BucketedMetadataIndexReader<'me> {
+    /// Creates the reader from the readers of the buckets, in bucket order
+    pub fn new(buckets: Vec<MetadataIndexReader<'me>>) -> Self {
+        assert!(
+            !buckets.is_empty(),
+            "A metadata index should have at least one bucket"
+        );
+        Self { buckets }
+    }
+
+    pub fn num_buckets(&self) -> usize {
+        self.buckets.len()
+    }
+
+    fn bucket(&self, metadata_key: &str) -> &MetadataIndexReader<'me> {
+        &self.buckets[metadata_key_bucket(metadata_key, self.buckets.len())]
+    }
+
+    pub async fn get(
+        &'me self,
+        metadata_key: &str,
+        metadata_value: &'me KeyWrapper,
+    ) -> Result<RoaringBitmap, MetadataIndexError> {
+        self.bucket(metadata_key)
+            .get(metadata_key, metadata_value)
+            .await
+    }
+
+    pub async fn get_within(
+        &'me self,
+        metadata_key: &str,
+        metadata_value: &'me KeyWrapper,
+        domain: Option<&RoaringBitmap>,
+    ) -> Result<RoaringBitmap, MetadataIndexError> {
+        self.bucket(metadata_key)
+            .get_within(metadata_key, metadata_value, domain)
+            .await
+    }
+
+    pub async fn get_any(
+        &'me self,
+        metadata_key: &'me str,
+        metadata_values: &'me [KeyWrapper],
+    ) -> Result<RoaringBitmap, MetadataIndexError> {
+        self.bucket(metadata_key)
+            .get_any(metadata_key, metadata_values)
+            .await
+    }
+
+    pub async fn starts_with(
+        &'me self,
+        metadata_key: &str,
+        prefix: &'me str,
+    ) -> Result<RoaringBitmap, MetadataIndexError> {
+        self.bucket(metadata_key)
+            .starts_with(metadata_key, prefix)
+            .await
+    }
+
+    pub async fn lt(
+        &'me self,
+        metadata_key: &str,
+        metadata_value: &'me KeyWrapper,
+    ) -> Result<RoaringBitmap, MetadataIndexError> {
+        self.bucket(metadata_key)
+            .lt(metadata_key, metadata_value)
+            .await
+    }
+
+    pub async fn lte(
+        &'me self,
+        metadata_key: &str,
+        metadata_value: &'me KeyWrapper,
+    ) -> Result<RoaringBitmap, MetadataIndexError> {
+        self.bucket(metadata_key)
+            .lte(metadata_key, metadata_value)
+            .await
+    }
+
+    pub async fn gt(
+        &'me self,
+        metadata_key: &str,
+        metadata_value: &'me KeyWrapper,
+    ) -> Result<RoaringBitmap, MetadataIndexError> {
+        self.bucket(metadata_key)
+            .gt(metadata_key, metadata_value)
+            .await
+    }
+
+    pub async fn gte(
+        &'me self,
+        metadata_key: &str,
+        metadata_value: &'me KeyWrapper,
+    ) -> Result<RoaringBitmap, MetadataIndexError> {
+        self.bucket(metadata_key)
+            .gte(metadata_key, metadata_value)
+            .await
+    }
+
+    pub async fn exists(
+        &'me self,
+        metadata_key: &str,
+    ) -> Result<RoaringBitmap, MetadataIndexError> {
+        self.bucket(metadata_key).exists(metadata_key).await
+    }
+
+    pub async fn group_by(
+        &'me self,
+        metadata_key: &'me str,
+    ) -> Result<Vec<(KeyWrapper, RoaringBitmap)>, MetadataIndexError> {
+        self.bucket(metadata_key).group_by(metadata_key).await
+    }
+
+    pub fn group_by_stream(
+        &'me self,
+        metadata_key: &'me str,
+    ) -> BoxStream<'me, Result<(KeyWrapper, RoaringBitmap), MetadataIndexError>> {
+        self.bucket(metadata_key).group_by_stream(metadata_key)
+    }
+
+    /// Streams every posting list of the index in key and value order, merging the posting
+    /// lists of the buckets as they are read
+    pub fn posting_lists_stream(
+        &'me self,
+    ) -> BoxStream<'me, Result<(&'me str, KeyWrapper, RoaringBitmap), MetadataIndexError>> {
+        if let [bucket] = self.buckets.as_slice() {
+            return bucket.posting_lists_stream();
+        }
+        let streams = self
+            .buckets
+            .iter()
+            .map(|bucket| bucket.posting_lists_stream().fuse())
+            .collect::<Vec<_>>();
+        let heads = streams.iter().map(|_| None).collect::<Vec<_>>();
+        stream::try_unfold((streams, heads), |(mut streams, mut heads)| async move {
+            for (stream, head) in streams.iter_mut().zip(heads.iter_mut()) {
+                if head.is_none() {
+                    *head = stream.try_next().await?;
+                }
+            }
+            // A metadata key is only ever in one bucket, so the heads never share a key
+            let next = heads
+                .iter()
+                .enumerate()
+                .filter_map(|(bucket, head)| head.as_ref().map(|(key, _, _)| (bucket, *key)))
+                .min_by_key(|(_, key)| *key)
+                .map(|(bucket, _)| bucket);
+            Ok(next.and_then(|bucket| {
+                heads[bucket]
+                    .take()
+                    .map(|posting_list| (posting_list, (streams, heads)))
+            }))
+        })
+        .boxed()
+    }
+}
+
+/// Writes a metadata index whose metadata keys are spread over a blockfile per bucket. A bucket
+/// that no write touches keeps the blockfile that it is forked from, which is neither committed
+/// nor flushed again
+#[derive(Clone)]
+pub struct BucketedMetadataIndexWriter<'me> {
+    buckets: Vec<MetadataIndexWriter<'me>>,
+    // The blockfiles that the buckets are forked from, which are None for new buckets
+    forked_from: Vec<Option<Uuid>>,
+    touched: Arc<[AtomicBool]>,
+}
+
+impl<'me> BucketedMetadataIndexWriter<'me> {
+    /// Creates the writer from the writers of the buckets, in bucket order, together with
+    /// the blockfiles that they are forked from
+    pub fn new(buckets: Vec<(MetadataIndexWriter<'me>, Option<Uuid>)>) -> Self {
+        assert!(
+            !buckets.is_empty(),
+            "A metadata index should have at least one bucket"
+        );
+        let touched = buckets.iter().map(|_| AtomicBool::new(false)).collect();
+        let (buckets, forked_from) = buckets.into_iter().unzip();
+        Self {
+            buckets,
+            forked_from,
+            touched,
+        }
+    }
+
+    pub fn num_buckets(&self) -> usize {
+        self.buckets.len()
+    }
+
+    fn touch(&self, metadata_key: &str) -> &MetadataIndexWriter<'me> {
+        let bucket = metadata_key_bucket(metadata_key, self.buckets.len());
+        self.touched[bucket].store(true, Ordering::Relaxed);
+        &self.buckets[bucket]
+    }
+
+    pub async fn set<K: Into<KeyWrapper>>(
+        &self,
+        prefix: &str,
+        key: K,
+        offset_id: u32,
+    ) -> Result<(), MetadataIndexError> {
+        self.touch(prefix).set(prefix, key, offset_id).await
+    }
+
+    pub async fn delete<K: Into<KeyWrapper>>(
+        &self,
+        prefix: &str,
+        key: K,
+        offset_id: u32,
+    ) -> Result<(), MetadataIndexError> {
+        self.touch(prefix).delete(prefix, key, offset_id).await
+    }
+
+    pub async fn update(
+        &self,
+        prefix: &str,
+        old_key: KeyWrapper,
+        new_key: KeyWrapper,
+        offset_id: u32,
+    ) -> Result<(), MetadataIndexError> {
+        self.touch(prefix)
+            .update(prefix, old_key, new_key, offset_id)
+            .await
+    }
+
+    pub async fn write_to_blockfile(&mut self) -> Result<(), MetadataIndexError> {
+        for (bucket, touched) in self.buckets.iter_mut().zip(self.touched.iter()) {
+            if touched.load(Ordering::Relaxed) {
+                bucket.write_to_blockfile().await?;
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn commit(self) -> Result<BucketedMetadataIndexFlusher, MetadataIndexError> {
+        let mut flushers = Vec::with_capacity(self.buckets.len());
+        for ((bucket, forked_from), touched) in self
+            .buckets
+            .into_iter()
+            .zip(self.forked_from)
+            .zip(self.touched.iter())
+        {
+            match forked_from {
+                Some(id) if !touched.load(Ordering::Relaxed) => {
+                    flushers.push(BucketFlusher::Unchanged(id))
+                }
+                _ => flushers.push(BucketFlusher::Changed(Box::new(bucket.commit().await?))),
+            }
+        }
+        Ok(BucketedMetadataIndexFlusher { buckets: flushers })
+    }
+}
+
+enum BucketFlusher {
+    Changed(Box<MetadataIndexFlusher>),
+    Unchanged(Uuid),
+}
+
+pub struct BucketedMetadataIndexFlusher {
+    buckets: Vec<BucketFlusher>,
+}
+
+impl BucketedMetadataIndexFlusher {
+    pub async fn flush(self) -> Result<(), MetadataIndexError> {
+        for bucket in self.buckets {
+            if let BucketFlusher::Changed(flusher) = bucket {
+                flusher.flush().await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The ids of the blockfiles of the buckets, in bucket order
+    pub fn ids(&self) -> Vec<Uuid> {
+        self.buckets
+            .iter()
+            .map(|bucket| match bucket {
+                BucketFlusher::Changed(flusher) => flusher.id(),
+                BucketFlusher::Unchanged(id) => *id,
+            })
+            .collect()
+    }
+
+    /// The flushers of the blockfiles that are written by the flush
+    pub fn blockfile_flushers(&self) -> impl Iterator<Item = &BlockfileFlusher> {
+        self.buckets.iter().filter_map(|bucket| match bucket {
+            BucketFlusher::Changed(flusher) => Some(flusher.blockfile_flusher()),
+            BucketFlusher::Unchanged(_) => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chroma_blockstore::{test_arrow_blockfile_provider, BlockfileWriterOptions};
+    use futures::TryStreamExt;
+    use roaring::RoaringBitmap;
+
+    use super::{
+        metadata_key_bucket, BucketedMetadataIndexReader, BucketedMetadataIndexWriter,
+        MetadataIndexReader, MetadataIndexWriter,
+    };
+
+    #[test]
+    fn test_metadata_key_bucket() {
+        assert_eq!(metadata_key_bucket("key", 1), 0);
+        assert_eq!(metadata_key_bucket("key", 0), 0);
+        // The bucket only depends on the key and the number of buckets
+        assert_eq!(metadata_key_bucket("key", 8), metadata_key_bucket("key", 8));
+        let buckets = (0..100)
+            .map(|i| metadata_key_bucket(&format!("key_{i}"), 8))
+            .collect::<std::collections::HashSet<_>>();
+        assert!(buckets.len() > 1);
+        assert!(buckets.iter().all(|bucket| *bucket < 8));
+    }
+
+    #[tokio::test]
+    async fn test_untouched_buckets_keep_their_blockfiles() {
+        let provider = test_arrow_blockfile_provider(2 << 22);
+        let mut buckets = Vec::new();
+        for _ in 0..4 {
+            let writer = provider
+                .write::<u32, RoaringBitmap>(BlockfileWriterOptions::default())
+                .await
+                .unwrap();
+            buckets.push((MetadataIndexWriter::new_u32(writer, None), None));
+        }
+        let mut writer = BucketedMetadataIndexWriter::new(buckets);
+        for i in 0..20 {
+            writer.set(&format!("key_{i}"), i, i + 1).await.unwrap();
+        }
+        writer.write_to_blockfile().await.unwrap();
+        let flusher = writer.commit().await.unwrap();
+        let ids = flusher.ids();
+        flusher.flush().await.unwrap();
+
+        let open = |ids: Vec<uuid::Uuid>| {
+            let provider = provider.clone();
+            async move {
+                let mut readers = Vec::new();
+                for id in ids {
+                    let reader = provider.read::<u32, RoaringBitmap>(&id).await.unwrap();
+                    readers.push(MetadataIndexReader::new_u32(reader));
+                }
+                BucketedMetadataIndexReader::new(readers)
+            }
+        };
+        let reader = open(ids.clone()).await;
+        for i in 0..20 {
+            let key = format!("key_{i}");
+            let value = i.into();
+            assert_eq!(
+                reader.get(&key, &value).await.unwrap(),
+                RoaringBitmap::from_iter([i + 1])
+            );
+        }
+        // The posting lists of the buckets are merged in key order
+        let keys = reader
+            .posting_lists_stream()
+            .map_ok(|(key, _, _)| key.to_string())
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        let mut sorted_keys = (0..20).map(|i| format!("key_{i}")).collect::<Vec<_>>();
+        sorted_keys.sort();
+        assert_eq!(keys, sorted_keys);
+
+        // Only the bucket of the updated key is written again
+        let mut buckets = Vec::new();
+        for (id, bucket) in ids.iter().zip(reader.buckets.iter()) {
+            let writer = provider
+                .write::<u32, RoaringBitmap>(BlockfileWriterOptions::new().fork(*id))
+                .await
+                .unwrap();
+            buckets.push((
+                MetadataIndexWriter::new_u32(writer, Some(bucket.clone())),
+                Some(*id),
+            ));
+        }
+        let mut writer = BucketedMetadataIndexWriter::new(buckets);
+        writer.set("key_0", 0, 100).await.unwrap();
+        writer.write_to_blockfile().await.unwrap();
+        let flusher = writer.commit().await.unwrap();
+        assert_eq!(flusher.blockfile_flushers().count(), 1);
+        let new_ids = flusher.ids();
+        flusher.flush().await.unwrap();
+        let updated_bucket = metadata_key_bucket("key_0", 4);
+        for (bucket, (id, new_id)) in ids.iter().zip(&new_ids).enumerate() {
+            assert_eq!(id == new_id, bucket != updated_bucket);
+        }
+
+        let reader = open(new_ids).await;
+        let value = 0.into();
+        assert_eq!(
+            reader.get("key_0", &value).await.unwrap(),
+            RoaringBitmap::from_iter([1, 100])
+        );
+    }
+}
@@ -1 +1,2 @@
+pub mod buckets;
 pub mod types;
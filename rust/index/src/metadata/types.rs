@@ -1,15 +1,17 @@
 use crate::fulltext::types::FullTextIndexError;
 use chroma_blockstore::{
-    key::KeyWrapper, types::errors::BlockfileError, BlockfileFlusher, BlockfileReader,
-    BlockfileWriter,
+    arrow::types::{ArrowReadableKey, ArrowWriteableKey},
+    key::{InvalidKeyConversion, KeyWrapper},
+    types::errors::BlockfileError,
+    BlockfileFlusher, BlockfileReader, BlockfileWriter, Key,
 };
 use chroma_error::{ChromaError, ErrorCodes};
-use futures::TryStreamExt;
+use futures::{future, stream::BoxStream, Stream, StreamExt, TryStreamExt};
 use thiserror::Error;
 use uuid::Uuid;
 
-use core::ops::BitOr;
 use roaring::RoaringBitmap;
+use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::ops::Bound;
@@ -19,6 +21,8 @@ use std::sync::Arc;
 pub enum MetadataIndexError {
     #[error("Invalid key type")]
     InvalidKeyType,
+    #[error("Metadata lists cannot be nested")]
+    NestedList,
     #[error("Blockfile error: {0}")]
     BlockfileError(#[from] Box<dyn ChromaError>),
     #[error("Full text index error: {0}")]
@@ -31,6 +35,273 @@ impl ChromaError for MetadataIndexError {
     }
 }
 
+/// Posting lists with more offset ids than this are split into partitions when they span
+/// more than one partition, so that a very common value is not stored as a single huge value
+const PARTITIONED_POSTING_LIST_THRESHOLD: u64 = 1 << 16;
+/// A partition covers the offset ids that share the bits above this many low bits
+const POSTING_LIST_PARTITION_BITS: u32 = 20;
+/// Separates the metadata key from the partition index in the prefix of a partition
+const POSTING_LIST_PARTITION_SEPARATOR: char = '\u{0}';
+/// Sets of at most this many values are read with a lookup per value, larger sets are merged
+/// against the posting lists between their smallest and largest value in a single pass
+const SET_POINT_LOOKUP_LIMIT: usize = 32;
+
+// A partitioned posting list is stored as one entry per partition under the prefix returned by
+// `posting_list_partition_prefix`, so that the partitions are spread over the blocks and read
+// one at a time. The entry of the metadata value itself holds a header instead of offset ids:
+// the indices of the partitions plus one, together with 0. Offset ids start at 1, so only a
+// header contains 0.
+fn posting_list_partition_prefix(metadata_key: &str, partition: u32) -> String {
+    format!(
+        "{}{}{:08x}",
+        metadata_key, POSTING_LIST_PARTITION_SEPARATOR, partition
+    )
+}
+
+/// Returns the partition indices if the posting list is a partition header
+fn posting_list_partitions(posting_list: &RoaringBitmap) -> Option<impl Iterator<Item = u32> + '_> {
+    if posting_list.contains(0) {
+        Some(posting_list.iter().skip(1).map(|partition| partition - 1))
+    } else {
+        None
+    }
+}
+
+/// Returns whether the prefix is the prefix of a partition of a posting list, rather than a
+/// metadata key
+fn is_posting_list_partition_prefix(prefix: &str) -> bool {
+    match prefix.rsplit_once(POSTING_LIST_PARTITION_SEPARATOR) {
+        Some((_, partition)) => {
+            partition.len() == 8 && partition.chars().all(|c| c.is_ascii_hexdigit())
+        }
+        None => false,
+    }
+}
+
+/// Returns whether any offset id of the domain falls in the partition
+fn partition_intersects(domain: &RoaringBitmap, partition: u32) -> bool {
+    let start = partition << POSTING_LIST_PARTITION_BITS;
+    let end = start | ((1 << POSTING_LIST_PARTITION_BITS) - 1);
+    domain.rank(end) > domain.rank(start) - domain.contains(start) as u64
+}
+
+/// Reads the partitions of a posting list, or returns it as is if it is not partitioned.
+/// If a domain is given, the partitions without any of its offset ids are skipped, so the
+/// result is only exact within the domain
+async fn resolve_posting_list<'me, K>(
+    blockfile_reader: &'me BlockfileReader<'me, K, RoaringBitmap>,
+    metadata_key: &str,
+    key: K,
+    posting_list: RoaringBitmap,
+    domain: Option<&RoaringBitmap>,
+) -> Result<RoaringBitmap, MetadataIndexError>
+where
+    K: Key + TryFrom<&'me KeyWrapper, Error = InvalidKeyConversion> + ArrowReadableKey<'me>,
+{
+    let partitions = posting_list_partitions(&posting_list).map(Iterator::collect::<Vec<_>>);
+    let Some(partitions) = partitions else {
+        return Ok(posting_list);
+    };
+    let mut resolved = RoaringBitmap::new();
+    for partition in partitions {
+        if domain.is_some_and(|domain| !partition_intersects(domain, partition)) {
+            continue;
+        }
+        let prefix = posting_list_partition_prefix(metadata_key, partition);
+        if let Some(rbm) = blockfile_reader.get(&prefix, key.clone()).await? {
+            resolved |= rbm;
+        }
+    }
+    Ok(resolved)
+}
+
+/// Unions the posting lists in a range of the blockfile, reading the partitioned ones lazily
+/// after the plain ones have been folded
+async fn union_posting_lists<'me, K, S>(
+    blockfile_reader: &'me BlockfileReader<'me, K, RoaringBitmap>,
+    metadata_key: &str,
+    posting_lists: S,
+) -> Result<RoaringBitmap, MetadataIndexError>
+where
+    K: Key + TryFrom<&'me KeyWrapper, Error = InvalidKeyConversion> + ArrowReadableKey<'me>,
+    S: Stream<Item = Result<(K, RoaringBitmap), Box<dyn ChromaError>>>,
+{
+    let (mut result, partitioned) = posting_lists
+        .try_fold(
+            (RoaringBitmap::new(), Vec::new()),
+            |(mut result, mut partitioned), (key, rbm)| async move {
+                if posting_list_partitions(&rbm).is_some() {
+                    partitioned.push((key, rbm));
+                } else {
+                    result |= rbm;
+                }
+                Ok((result, partitioned))
+            },
+        )
+        .await?;
+    for (key, header) in partitioned {
+        result |= resolve_posting_list(blockfile_reader, metadata_key, key, header, None).await?;
+    }
+    Ok(result)
+}
+
+/// Streams the values of a metadata key with their posting lists, resolving the partitioned
+/// posting lists as they are read
+fn group_posting_lists<'me, K>(
+    blockfile_reader: &'me BlockfileReader<'me, K, RoaringBitmap>,
+    metadata_key: &'me str,
+) -> impl Stream<Item = Result<(KeyWrapper, RoaringBitmap), MetadataIndexError>> + Send + 'me
+where
+    K: Key
+        + TryFrom<&'me KeyWrapper, Error = InvalidKeyConversion>
+        + ArrowReadableKey<'me>
+        + Send
+        + Sync,
+{
+    blockfile_reader
+        .get_range_stream(metadata_key..=metadata_key, ..)
+        .map_err(MetadataIndexError::from)
+        .and_then(move |(key, rbm)| async move {
+            let rbm = resolve_posting_list(blockfile_reader, metadata_key, key.clone(), rbm, None)
+                .await?;
+            Ok((key.into(), rbm))
+        })
+}
+
+fn all_posting_lists<'me, K>(
+    blockfile_reader: &'me BlockfileReader<'me, K, RoaringBitmap>,
+) -> impl Stream<Item = Result<(&'me str, KeyWrapper, RoaringBitmap), MetadataIndexError>> + Send + 'me
+where
+    K: Key
+        + TryFrom<&'me KeyWrapper, Error = InvalidKeyConversion>
+        + ArrowReadableKey<'me>
+        + Send
+        + Sync,
+{
+    blockfile_reader
+        .get_all_stream()
+        .map_err(MetadataIndexError::from)
+        .try_filter(|(prefix, _, _)| future::ready(!is_posting_list_partition_prefix(prefix)))
+        .and_then(move |(prefix, key, rbm)| async move {
+            let rbm =
+                resolve_posting_list(blockfile_reader, prefix, key.clone(), rbm, None).await?;
+            Ok((prefix, key.into(), rbm))
+        })
+}
+
+/// Unions the posting lists of a set of values. The values are sorted and deduplicated once,
+/// which is skipped if they are already sorted, so that a large set is merged against the
+/// posting lists in key order instead of being expanded into a lookup per value
+async fn union_posting_lists_of_set<'me, K>(
+    blockfile_reader: &'me BlockfileReader<'me, K, RoaringBitmap>,
+    metadata_key: &'me str,
+    metadata_values: &'me [KeyWrapper],
+) -> Result<RoaringBitmap, MetadataIndexError>
+where
+    K: Key
+        + PartialOrd
+        + TryFrom<&'me KeyWrapper, Error = InvalidKeyConversion>
+        + ArrowReadableKey<'me>
+        + Send
+        + Sync,
+{
+    let compare = |a: &K, b: &K| a.partial_cmp(b).unwrap_or(Ordering::Equal);
+    let mut keys = metadata_values
+        .iter()
+        .map(K::try_from)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| MetadataIndexError::InvalidKeyType)?;
+    if !keys
+        .windows(2)
+        .all(|pair| compare(&pair[0], &pair[1]).is_le())
+    {
+        keys.sort_by(compare);
+    }
+    keys.dedup();
+    let (first, last) = match (keys.first(), keys.last()) {
+        (Some(first), Some(last)) => (first.clone(), last.clone()),
+        _ => return Ok(RoaringBitmap::new()),
+    };
+    if keys.len() <= SET_POINT_LOOKUP_LIMIT {
+        let mut result = RoaringBitmap::new();
+        for key in keys {
+            if let Some(rbm) = blockfile_reader.get(metadata_key, key.clone()).await? {
+                result |=
+                    resolve_posting_list(blockfile_reader, metadata_key, key, rbm, None).await?;
+            }
+        }
+        return Ok(result);
+    }
+    let posting_lists = blockfile_reader
+        .get_range_stream(metadata_key..=metadata_key, first..=last)
+        .try_filter(move |(key, _)| {
+            future::ready(keys.binary_search_by(|probe| compare(probe, key)).is_ok())
+        });
+    union_posting_lists(blockfile_reader, metadata_key, posting_lists).await
+}
+
+/// Writes a posting list, partitioning it if it is large, and deletes the partitions of the
+/// previous version that are now empty
+async fn write_posting_list<K>(
+    blockfile_writer: &BlockfileWriter,
+    metadata_key: &str,
+    key: K,
+    posting_list: RoaringBitmap,
+    written_partitions: RoaringBitmap,
+) -> Result<(), MetadataIndexError>
+where
+    K: Key + ArrowWriteableKey,
+{
+    let mut partitions = RoaringBitmap::new();
+    let partition_of = |offset_id: u32| offset_id >> POSTING_LIST_PARTITION_BITS;
+    let spans_partitions =
+        posting_list.min().map(partition_of) != posting_list.max().map(partition_of);
+    if posting_list.len() > PARTITIONED_POSTING_LIST_THRESHOLD && spans_partitions {
+        let mut partition_posting_lists: Vec<(u32, RoaringBitmap)> = Vec::new();
+        for offset_id in posting_list.iter() {
+            let partition = partition_of(offset_id);
+            match partition_posting_lists.last_mut() {
+                Some((last_partition, rbm)) if *last_partition == partition => {
+                    rbm.push(offset_id);
+                }
+                _ => {
+                    partition_posting_lists.push((partition, RoaringBitmap::from_iter([offset_id])))
+                }
+            }
+        }
+
+        let mut header = RoaringBitmap::from_iter([0]);
+        for (partition, rbm) in partition_posting_lists {
+            blockfile_writer
+                .set(
+                    &posting_list_partition_prefix(metadata_key, partition),
+                    key.clone(),
+                    rbm,
+                )
+                .await?;
+            partitions.insert(partition);
+            header.insert(partition + 1);
+        }
+        blockfile_writer
+            .set(metadata_key, key.clone(), header)
+            .await?;
+    } else {
+        blockfile_writer
+            .set(metadata_key, key.clone(), posting_list)
+            .await?;
+    }
+
+    for stale_partition in written_partitions - partitions {
+        blockfile_writer
+            .delete::<K, RoaringBitmap>(
+                &posting_list_partition_prefix(metadata_key, stale_partition),
+                key.clone(),
+            )
+            .await?;
+    }
+    Ok(())
+}
+
 // This pattern for enum dispatch is weird. We do it for cause:
 // - We can't incrementally write rbms to the blockfile -- we have to build up
 //   each rbm then write them all at once.
@@ -338,52 +609,109 @@ impl<'me> MetadataIndexWriter<'me> {
         match self {
             MetadataIndexWriter::StringMetadataIndexWriter(
                 blockfile_writer,
-                _,
+                reader,
                 uncommitted_rbms,
             ) => {
                 let mut uncommitted_rbms = uncommitted_rbms.lock().await;
                 for (prefix, mut rbms) in uncommitted_rbms.drain() {
                     for (key, rbm) in rbms.drain() {
-                        match blockfile_writer
-                            .set(prefix.as_str(), key.as_str(), rbm)
-                            .await
-                        {
-                            Ok(_) => {}
-                            Err(e) => return Err(MetadataIndexError::BlockfileError(e)),
-                        }
+                        let written_partitions = match reader {
+                            Some(reader) => {
+                                reader
+                                    .get_partitions(&prefix, &KeyWrapper::String(key.clone()))
+                                    .await?
+                            }
+                            None => RoaringBitmap::new(),
+                        };
+                        write_posting_list(
+                            blockfile_writer,
+                            prefix.as_str(),
+                            key.as_str(),
+                            rbm,
+                            written_partitions,
+                        )
+                        .await?;
                     }
                 }
             }
-            MetadataIndexWriter::U32MetadataIndexWriter(blockfile_writer, _, uncommitted_rbms) => {
+            MetadataIndexWriter::U32MetadataIndexWriter(
+                blockfile_writer,
+                reader,
+                uncommitted_rbms,
+            ) => {
                 let mut uncommitted_rbms = uncommitted_rbms.lock().await;
                 for (prefix, mut rbms) in uncommitted_rbms.drain() {
                     for (key, rbm) in rbms.drain() {
-                        match blockfile_writer.set(prefix.as_str(), key, rbm).await {
-                            Ok(_) => {}
-                            Err(e) => return Err(MetadataIndexError::BlockfileError(e)),
-                        }
+                        let written_partitions = match reader {
+                            Some(reader) => {
+                                reader
+                                    .get_partitions(&prefix, &KeyWrapper::Uint32(key))
+                                    .await?
+                            }
+                            None => RoaringBitmap::new(),
+                        };
+                        write_posting_list(
+                            blockfile_writer,
+                            prefix.as_str(),
+                            key,
+                            rbm,
+                            written_partitions,
+                        )
+                        .await?;
                     }
                 }
             }
-            MetadataIndexWriter::F32MetadataIndexWriter(blockfile_writer, _, uncommitted_rbms) => {
+            MetadataIndexWriter::F32MetadataIndexWriter(
+                blockfile_writer,
+                reader,
+                uncommitted_rbms,
+            ) => {
                 let mut uncommitted_rbms = uncommitted_rbms.lock().await;
                 for (prefix, mut rbms) in uncommitted_rbms.drain() {
                     for (key, rbm) in rbms.drain(..) {
-                        match blockfile_writer.set(prefix.as_str(), key, rbm).await {
-                            Ok(_) => {}
-                            Err(e) => return Err(MetadataIndexError::BlockfileError(e)),
-                        }
+                        let written_partitions = match reader {
+                            Some(reader) => {
+                                reader
+                                    .get_partitions(&prefix, &KeyWrapper::Float32(key))
+                                    .await?
+                            }
+                            None => RoaringBitmap::new(),
+                        };
+                        write_posting_list(
+                            blockfile_writer,
+                            prefix.as_str(),
+                            key,
+                            rbm,
+                            written_partitions,
+                        )
+                        .await?;
                     }
                 }
             }
-            MetadataIndexWriter::BoolMetadataIndexWriter(blockfile_writer, _, uncommitted_rbms) => {
+            MetadataIndexWriter::BoolMetadataIndexWriter(
+                blockfile_writer,
+                reader,
+                uncommitted_rbms,
+            ) => {
                 let mut uncommitted_rbms = uncommitted_rbms.lock().await;
                 for (prefix, mut rbms) in uncommitted_rbms.drain() {
                     for (key, rbm) in rbms.drain() {
-                        match blockfile_writer.set(prefix.as_str(), key, rbm).await {
-                            Ok(_) => {}
-                            Err(e) => return Err(MetadataIndexError::BlockfileError(e)),
-                        }
+                        let written_partitions = match reader {
+                            Some(reader) => {
+                                reader
+                                    .get_partitions(&prefix, &KeyWrapper::Bool(key))
+                                    .await?
+                            }
+                            None => RoaringBitmap::new(),
+                        };
+                        write_posting_list(
+                            blockfile_writer,
+                            prefix.as_str(),
+                            key,
+                            rbm,
+                            written_partitions,
+                        )
+                        .await?;
                     }
                 }
             }
@@ -466,6 +794,15 @@ impl MetadataIndexFlusher {
             MetadataIndexFlusher::BoolMetadataIndexFlusher(flusher) => flusher.id(),
         }
     }
+
+    pub fn blockfile_flusher(&self) -> &BlockfileFlusher {
+        match self {
+            MetadataIndexFlusher::StringMetadataIndexFlusher(flusher) => flusher,
+            MetadataIndexFlusher::U32MetadataIndexFlusher(flusher) => flusher,
+            MetadataIndexFlusher::F32MetadataIndexFlusher(flusher) => flusher,
+            MetadataIndexFlusher::BoolMetadataIndexFlusher(flusher) => flusher,
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -499,6 +836,18 @@ impl<'me> MetadataIndexReader<'me> {
         &'me self,
         metadata_key: &str,
         metadata_value: &'me KeyWrapper,
+    ) -> Result<RoaringBitmap, MetadataIndexError> {
+        self.get_within(metadata_key, metadata_value, None).await
+    }
+
+    /// Returns the offset ids with the metadata value like `get`, but only reads the partitions
+    /// of a partitioned posting list that contain offset ids of the domain. The result is only
+    /// exact within the domain, so it should be intersected with it
+    pub async fn get_within(
+        &'me self,
+        metadata_key: &str,
+        metadata_value: &'me KeyWrapper,
+        domain: Option<&RoaringBitmap>,
     ) -> Result<RoaringBitmap, MetadataIndexError> {
         match self {
             MetadataIndexReader::StringMetadataIndexReader(blockfile_reader) => {
@@ -509,7 +858,10 @@ impl<'me> MetadataIndexReader<'me> {
                         }
                         let rbm = blockfile_reader.get(metadata_key, k).await;
                         match rbm {
-                            Ok(Some(rbm)) => Ok(rbm),
+                            Ok(Some(rbm)) => {
+                                resolve_posting_list(blockfile_reader, metadata_key, k, rbm, domain)
+                                    .await
+                            }
                             Ok(None) => Err(MetadataIndexError::BlockfileError(Box::new(
                                 BlockfileError::NotFoundError,
                             ))),
@@ -526,7 +878,10 @@ impl<'me> MetadataIndexReader<'me> {
                     }
                     let rbm = blockfile_reader.get(metadata_key, *k).await;
                     match rbm {
-                        Ok(Some(rbm)) => Ok(rbm),
+                        Ok(Some(rbm)) => {
+                            resolve_posting_list(blockfile_reader, metadata_key, *k, rbm, domain)
+                                .await
+                        }
                         Ok(None) => Err(MetadataIndexError::BlockfileError(Box::new(
                             BlockfileError::NotFoundError,
                         ))),
@@ -542,7 +897,10 @@ impl<'me> MetadataIndexReader<'me> {
                     }
                     let rbm = blockfile_reader.get(metadata_key, *k).await;
                     match rbm {
-                        Ok(Some(rbm)) => Ok(rbm),
+                        Ok(Some(rbm)) => {
+                            resolve_posting_list(blockfile_reader, metadata_key, *k, rbm, domain)
+                                .await
+                        }
                         Ok(None) => Err(MetadataIndexError::BlockfileError(Box::new(
                             BlockfileError::NotFoundError,
                         ))),
@@ -559,7 +917,16 @@ impl<'me> MetadataIndexReader<'me> {
                         }
                         let rbm = blockfile_reader.get(metadata_key, *k).await;
                         match rbm {
-                            Ok(Some(rbm)) => Ok(rbm),
+                            Ok(Some(rbm)) => {
+                                resolve_posting_list(
+                                    blockfile_reader,
+                                    metadata_key,
+                                    *k,
+                                    rbm,
+                                    domain,
+                                )
+                                .await
+                            }
                             Ok(None) => Err(MetadataIndexError::BlockfileError(Box::new(
                                 BlockfileError::NotFoundError,
                             ))),
@@ -572,6 +939,84 @@ impl<'me> MetadataIndexReader<'me> {
         }
     }
 
+    /// Returns the partitions in which the posting list of the metadata value is stored,
+    /// which is empty if the posting list is not partitioned
+    pub async fn get_partitions(
+        &'me self,
+        metadata_key: &str,
+        metadata_value: &'me KeyWrapper,
+    ) -> Result<RoaringBitmap, MetadataIndexError> {
+        let rbm = match self {
+            MetadataIndexReader::StringMetadataIndexReader(blockfile_reader) => {
+                match metadata_value {
+                    KeyWrapper::String(k) => blockfile_reader.get(metadata_key, k).await?,
+                    _ => return Err(MetadataIndexError::InvalidKeyType),
+                }
+            }
+            MetadataIndexReader::U32MetadataIndexReader(blockfile_reader) => match metadata_value {
+                KeyWrapper::Uint32(k) => blockfile_reader.get(metadata_key, *k).await?,
+                _ => return Err(MetadataIndexError::InvalidKeyType),
+            },
+            MetadataIndexReader::F32MetadataIndexReader(blockfile_reader) => match metadata_value {
+                KeyWrapper::Float32(k) => blockfile_reader.get(metadata_key, *k).await?,
+                _ => return Err(MetadataIndexError::InvalidKeyType),
+            },
+            MetadataIndexReader::BoolMetadataIndexReader(blockfile_reader) => {
+                match metadata_value {
+                    KeyWrapper::Bool(k) => blockfile_reader.get(metadata_key, *k).await?,
+                    _ => return Err(MetadataIndexError::InvalidKeyType),
+                }
+            }
+        };
+        Ok(rbm
+            .as_ref()
+            .and_then(posting_list_partitions)
+            .map(|partitions| partitions.collect())
+            .unwrap_or_default())
+    }
+
+    /// Returns the offset ids whose value for the metadata key is any of the metadata values,
+    /// as a single evaluation of the set rather than a union of equality lookups
+    pub async fn get_any(
+        &'me self,
+        metadata_key: &'me str,
+        metadata_values: &'me [KeyWrapper],
+    ) -> Result<RoaringBitmap, MetadataIndexError> {
+        match self {
+            MetadataIndexReader::StringMetadataIndexReader(blockfile_reader) => {
+                union_posting_lists_of_set(blockfile_reader, metadata_key, metadata_values).await
+            }
+            MetadataIndexReader::U32MetadataIndexReader(blockfile_reader) => {
+                union_posting_lists_of_set(blockfile_reader, metadata_key, metadata_values).await
+            }
+            MetadataIndexReader::F32MetadataIndexReader(blockfile_reader) => {
+                union_posting_lists_of_set(blockfile_reader, metadata_key, metadata_values).await
+            }
+            MetadataIndexReader::BoolMetadataIndexReader(blockfile_reader) => {
+                union_posting_lists_of_set(blockfile_reader, metadata_key, metadata_values).await
+            }
+        }
+    }
+
+    /// Returns the offset ids whose string value for the metadata key starts with the prefix.
+    /// The posting lists are read in key order from the prefix on, up to the first value that
+    /// does not start with it
+    pub async fn starts_with(
+        &'me self,
+        metadata_key: &str,
+        prefix: &'me str,
+    ) -> Result<RoaringBitmap, MetadataIndexError> {
+        match self {
+            MetadataIndexReader::StringMetadataIndexReader(blockfile_reader) => {
+                let posting_lists = blockfile_reader
+                    .get_range_stream(metadata_key..=metadata_key, prefix..)
+                    .try_take_while(move |(key, _)| future::ready(Ok(key.starts_with(prefix))));
+                union_posting_lists(blockfile_reader, metadata_key, posting_lists).await
+            }
+            _ => Err(MetadataIndexError::InvalidKeyType),
+        }
+    }
+
     pub async fn lt(
         &'me self,
         metadata_key: &str,
@@ -579,25 +1024,41 @@ impl<'me> MetadataIndexReader<'me> {
     ) -> Result<RoaringBitmap, MetadataIndexError> {
         match self {
             MetadataIndexReader::U32MetadataIndexReader(blockfile_reader) => match metadata_value {
-                KeyWrapper::Uint32(k) => blockfile_reader
-                    .get_range_stream(metadata_key..=metadata_key, ..*k)
-                    .try_fold(RoaringBitmap::new(), |result, record| async move {
-                        Ok(result.bitor(&record.1))
-                    })
+                KeyWrapper::Uint32(k) => {
+                    union_posting_lists(
+                        blockfile_reader,
+                        metadata_key,
+                        blockfile_reader.get_range_stream(metadata_key..=metadata_key, ..*k),
+                    )
                     .await
-                    .map_err(MetadataIndexError::BlockfileError),
+                }
                 _ => Err(MetadataIndexError::InvalidKeyType),
             },
             MetadataIndexReader::F32MetadataIndexReader(blockfile_reader) => match metadata_value {
-                KeyWrapper::Float32(k) => blockfile_reader
-                    .get_range_stream(metadata_key..=metadata_key, ..*k)
-                    .try_fold(RoaringBitmap::new(), |result, record| async move {
-                        Ok(result.bitor(&record.1))
-                    })
+                KeyWrapper::Float32(k) => {
+                    union_posting_lists(
+                        blockfile_reader,
+                        metadata_key,
+                        blockfile_reader.get_range_stream(metadata_key..=metadata_key, ..*k),
+                    )
                     .await
-                    .map_err(MetadataIndexError::BlockfileError),
+                }
                 _ => Err(MetadataIndexError::InvalidKeyType),
             },
+            MetadataIndexReader::StringMetadataIndexReader(blockfile_reader) => {
+                match metadata_value {
+                    KeyWrapper::String(k) => {
+                        union_posting_lists(
+                            blockfile_reader,
+                            metadata_key,
+                            blockfile_reader
+                                .get_range_stream(metadata_key..=metadata_key, ..k.as_str()),
+                        )
+                        .await
+                    }
+                    _ => Err(MetadataIndexError::InvalidKeyType),
+                }
+            }
             _ => Err(MetadataIndexError::InvalidKeyType),
         }
     }
@@ -609,25 +1070,41 @@ impl<'me> MetadataIndexReader<'me> {
     ) -> Result<RoaringBitmap, MetadataIndexError> {
         match self {
             MetadataIndexReader::U32MetadataIndexReader(blockfile_reader) => match metadata_value {
-                KeyWrapper::Uint32(k) => blockfile_reader
-                    .get_range_stream(metadata_key..=metadata_key, ..=*k)
-                    .try_fold(RoaringBitmap::new(), |result, record| async move {
-                        Ok(result.bitor(&record.1))
-                    })
+                KeyWrapper::Uint32(k) => {
+                    union_posting_lists(
+                        blockfile_reader,
+                        metadata_key,
+                        blockfile_reader.get_range_stream(metadata_key..=metadata_key, ..=*k),
+                    )
                     .await
-                    .map_err(MetadataIndexError::BlockfileError),
+                }
                 _ => Err(MetadataIndexError::InvalidKeyType),
             },
             MetadataIndexReader::F32MetadataIndexReader(blockfile_reader) => match metadata_value {
-                KeyWrapper::Float32(k) => blockfile_reader
-                    .get_range_stream(metadata_key..=metadata_key, ..=*k)
-                    .try_fold(RoaringBitmap::new(), |result, record| async move {
-                        Ok(result.bitor(&record.1))
-                    })
+                KeyWrapper::Float32(k) => {
+                    union_posting_lists(
+                        blockfile_reader,
+                        metadata_key,
+                        blockfile_reader.get_range_stream(metadata_key..=metadata_key, ..=*k),
+                    )
                     .await
-                    .map_err(MetadataIndexError::BlockfileError),
+                }
                 _ => Err(MetadataIndexError::InvalidKeyType),
             },
+            MetadataIndexReader::StringMetadataIndexReader(blockfile_reader) => {
+                match metadata_value {
+                    KeyWrapper::String(k) => {
+                        union_posting_lists(
+                            blockfile_reader,
+                            metadata_key,
+                            blockfile_reader
+                                .get_range_stream(metadata_key..=metadata_key, ..=k.as_str()),
+                        )
+                        .await
+                    }
+                    _ => Err(MetadataIndexError::InvalidKeyType),
+                }
+            }
             _ => Err(MetadataIndexError::InvalidKeyType),
         }
     }
@@ -639,31 +1116,49 @@ impl<'me> MetadataIndexReader<'me> {
     ) -> Result<RoaringBitmap, MetadataIndexError> {
         match self {
             MetadataIndexReader::U32MetadataIndexReader(blockfile_reader) => match metadata_value {
-                KeyWrapper::Uint32(k) => blockfile_reader
-                    .get_range_stream(
-                        metadata_key..=metadata_key,
-                        (Bound::Excluded(*k), Bound::Unbounded),
+                KeyWrapper::Uint32(k) => {
+                    union_posting_lists(
+                        blockfile_reader,
+                        metadata_key,
+                        blockfile_reader.get_range_stream(
+                            metadata_key..=metadata_key,
+                            (Bound::Excluded(*k), Bound::Unbounded),
+                        ),
                     )
-                    .try_fold(RoaringBitmap::new(), |result, record| async move {
-                        Ok(result.bitor(&record.1))
-                    })
                     .await
-                    .map_err(MetadataIndexError::BlockfileError),
+                }
                 _ => Err(MetadataIndexError::InvalidKeyType),
             },
             MetadataIndexReader::F32MetadataIndexReader(blockfile_reader) => match metadata_value {
-                KeyWrapper::Float32(k) => blockfile_reader
-                    .get_range_stream(
-                        metadata_key..=metadata_key,
-                        (Bound::Excluded(*k), Bound::Unbounded),
+                KeyWrapper::Float32(k) => {
+                    union_posting_lists(
+                        blockfile_reader,
+                        metadata_key,
+                        blockfile_reader.get_range_stream(
+                            metadata_key..=metadata_key,
+                            (Bound::Excluded(*k), Bound::Unbounded),
+                        ),
                     )
-                    .try_fold(RoaringBitmap::new(), |result, record| async move {
-                        Ok(result.bitor(&record.1))
-                    })
                     .await
-                    .map_err(MetadataIndexError::BlockfileError),
+                }
                 _ => Err(MetadataIndexError::InvalidKeyType),
             },
+            MetadataIndexReader::StringMetadataIndexReader(blockfile_reader) => {
+                match metadata_value {
+                    KeyWrapper::String(k) => {
+                        union_posting_lists(
+                            blockfile_reader,
+                            metadata_key,
+                            blockfile_reader.get_range_stream(
+                                metadata_key..=metadata_key,
+                                (Bound::Excluded(k.as_str()), Bound::Unbounded),
+                            ),
+                        )
+                        .await
+                    }
+                    _ => Err(MetadataIndexError::InvalidKeyType),
+                }
+            }
             _ => Err(MetadataIndexError::InvalidKeyType),
         }
     }
@@ -675,34 +1170,147 @@ impl<'me> MetadataIndexReader<'me> {
     ) -> Result<RoaringBitmap, MetadataIndexError> {
         match self {
             MetadataIndexReader::U32MetadataIndexReader(blockfile_reader) => match metadata_value {
-                KeyWrapper::Uint32(k) => blockfile_reader
-                    .get_range_stream(metadata_key..=metadata_key, *k..)
-                    .try_fold(RoaringBitmap::new(), |result, record| async move {
-                        Ok(result.bitor(&record.1))
-                    })
+                KeyWrapper::Uint32(k) => {
+                    union_posting_lists(
+                        blockfile_reader,
+                        metadata_key,
+                        blockfile_reader.get_range_stream(metadata_key..=metadata_key, *k..),
+                    )
                     .await
-                    .map_err(MetadataIndexError::BlockfileError),
+                }
                 _ => Err(MetadataIndexError::InvalidKeyType),
             },
             MetadataIndexReader::F32MetadataIndexReader(blockfile_reader) => match metadata_value {
-                KeyWrapper::Float32(k) => blockfile_reader
-                    .get_range_stream(metadata_key..=metadata_key, *k..)
-                    .try_fold(RoaringBitmap::new(), |result, record| async move {
-                        Ok(result.bitor(&record.1))
-                    })
+                KeyWrapper::Float32(k) => {
+                    union_posting_lists(
+                        blockfile_reader,
+                        metadata_key,
+                        blockfile_reader.get_range_stream(metadata_key..=metadata_key, *k..),
+                    )
                     .await
-                    .map_err(MetadataIndexError::BlockfileError),
+                }
                 _ => Err(MetadataIndexError::InvalidKeyType),
             },
+            MetadataIndexReader::StringMetadataIndexReader(blockfile_reader) => {
+                match metadata_value {
+                    KeyWrapper::String(k) => {
+                        union_posting_lists(
+                            blockfile_reader,
+                            metadata_key,
+                            blockfile_reader
+                                .get_range_stream(metadata_key..=metadata_key, k.as_str()..),
+                        )
+                        .await
+                    }
+                    _ => Err(MetadataIndexError::InvalidKeyType),
+                }
+            }
             _ => Err(MetadataIndexError::InvalidKeyType),
         }
     }
+
+    /// Returns the offset ids that have any value for the metadata key
+    pub async fn exists(
+        &'me self,
+        metadata_key: &str,
+    ) -> Result<RoaringBitmap, MetadataIndexError> {
+        let offset_ids = match self {
+            MetadataIndexReader::StringMetadataIndexReader(blockfile_reader) => {
+                union_posting_lists(
+                    blockfile_reader,
+                    metadata_key,
+                    blockfile_reader.get_range_stream(metadata_key..=metadata_key, ..),
+                )
+                .await?
+            }
+            MetadataIndexReader::U32MetadataIndexReader(blockfile_reader) => {
+                union_posting_lists(
+                    blockfile_reader,
+                    metadata_key,
+                    blockfile_reader.get_range_stream(metadata_key..=metadata_key, ..),
+                )
+                .await?
+            }
+            MetadataIndexReader::F32MetadataIndexReader(blockfile_reader) => {
+                union_posting_lists(
+                    blockfile_reader,
+                    metadata_key,
+                    blockfile_reader.get_range_stream(metadata_key..=metadata_key, ..),
+                )
+                .await?
+            }
+            MetadataIndexReader::BoolMetadataIndexReader(blockfile_reader) => {
+                union_posting_lists(
+                    blockfile_reader,
+                    metadata_key,
+                    blockfile_reader.get_range_stream(metadata_key..=metadata_key, ..),
+                )
+                .await?
+            }
+        };
+        Ok(offset_ids)
+    }
+
+    /// Returns every value of the metadata key together with the offset ids that have it
+    pub async fn group_by(
+        &'me self,
+        metadata_key: &'me str,
+    ) -> Result<Vec<(KeyWrapper, RoaringBitmap)>, MetadataIndexError> {
+        self.group_by_stream(metadata_key).try_collect().await
+    }
+
+    /// Streams every posting list of the index together with the metadata key and value it
+    /// belongs to, in key and value order. A partitioned posting list is returned whole, under
+    /// the key of its header
+    pub fn posting_lists_stream(
+        &'me self,
+    ) -> BoxStream<'me, Result<(&'me str, KeyWrapper, RoaringBitmap), MetadataIndexError>> {
+        match self {
+            MetadataIndexReader::StringMetadataIndexReader(blockfile_reader) => {
+                all_posting_lists(blockfile_reader).boxed()
+            }
+            MetadataIndexReader::U32MetadataIndexReader(blockfile_reader) => {
+                all_posting_lists(blockfile_reader).boxed()
+            }
+            MetadataIndexReader::F32MetadataIndexReader(blockfile_reader) => {
+                all_posting_lists(blockfile_reader).boxed()
+            }
+            MetadataIndexReader::BoolMetadataIndexReader(blockfile_reader) => {
+                all_posting_lists(blockfile_reader).boxed()
+            }
+        }
+    }
+
+    /// Streams every value of the metadata key together with the offset ids that have it,
+    /// in value order. The blocks are read as the stream is polled, so the values of a
+    /// key can be exported without holding all of them in memory
+    pub fn group_by_stream(
+        &'me self,
+        metadata_key: &'me str,
+    ) -> BoxStream<'me, Result<(KeyWrapper, RoaringBitmap), MetadataIndexError>> {
+        match self {
+            MetadataIndexReader::StringMetadataIndexReader(blockfile_reader) => {
+                group_posting_lists(blockfile_reader, metadata_key).boxed()
+            }
+            MetadataIndexReader::U32MetadataIndexReader(blockfile_reader) => {
+                group_posting_lists(blockfile_reader, metadata_key).boxed()
+            }
+            MetadataIndexReader::F32MetadataIndexReader(blockfile_reader) => {
+                group_posting_lists(blockfile_reader, metadata_key).boxed()
+            }
+            MetadataIndexReader::BoolMetadataIndexReader(blockfile_reader) => {
+                group_posting_lists(blockfile_reader, metadata_key).boxed()
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use chroma_blockstore::{provider::BlockfileProvider, BlockfileWriterOptions};
+    use chroma_blockstore::{
+        provider::BlockfileProvider, test_arrow_blockfile_provider, BlockfileWriterOptions,
+    };
 
     #[tokio::test]
     async fn test_new_string_writer() {
@@ -952,6 +1560,36 @@ mod test {
         assert!(bitmap.contains(3));
     }
 
+    #[tokio::test]
+    async fn test_string_metadata_exists() {
+        let provider = BlockfileProvider::new_memory();
+        let blockfile_writer = provider
+            .write::<&str, RoaringBitmap>(BlockfileWriterOptions::default())
+            .await
+            .unwrap();
+        let writer_id = blockfile_writer.id();
+        let mut writer = MetadataIndexWriter::new_string(blockfile_writer, None);
+        writer.set("key1", "value", 1).await.unwrap();
+        writer.set("key1", "value2", 2).await.unwrap();
+        writer.set("key2", "value", 3).await.unwrap();
+        writer.write_to_blockfile().await.unwrap();
+        let flusher = writer.commit().await.unwrap();
+        flusher.flush().await.unwrap();
+
+        let blockfile_reader = provider
+            .read::<&str, RoaringBitmap>(&writer_id)
+            .await
+            .unwrap();
+        let reader = MetadataIndexReader::new_string(blockfile_reader);
+        let bitmap = reader.exists("key1").await.unwrap();
+        assert_eq!(bitmap.len(), 2);
+        assert!(bitmap.contains(1));
+        assert!(bitmap.contains(2));
+
+        let bitmap = reader.exists("key3").await.unwrap();
+        assert!(bitmap.is_empty());
+    }
+
     #[tokio::test]
     async fn test_u32_metadata_multiple_keys() {
         let provider = BlockfileProvider::new_memory();
@@ -1343,6 +1981,185 @@ mod test {
     }
 
     // TODO enable this test once fork() is enabled for MemoryBlockfiles.
+    #[tokio::test]
+    async fn test_partitioned_posting_list() {
+        let provider = test_arrow_blockfile_provider(2 << 22);
+        let blockfile_writer = provider
+            .write::<&str, RoaringBitmap>(BlockfileWriterOptions::default())
+            .await
+            .unwrap();
+        let writer_id = blockfile_writer.id();
+        let mut writer = MetadataIndexWriter::new_string(blockfile_writer, None);
+        // The offset ids of `en` span three partitions
+        let en_offset_ids = (1..=100_000).map(|i| i * 30).collect::<RoaringBitmap>();
+        for offset_id in en_offset_ids.iter() {
+            writer.set("lang", "en", offset_id).await.unwrap();
+        }
+        writer.set("lang", "fr", 31).await.unwrap();
+        writer.write_to_blockfile().await.unwrap();
+        let flusher = writer.commit().await.unwrap();
+        flusher.flush().await.unwrap();
+
+        let blockfile_reader = provider
+            .read::<&str, RoaringBitmap>(&writer_id)
+            .await
+            .unwrap();
+        let reader = MetadataIndexReader::new_string(blockfile_reader);
+        let en = KeyWrapper::from("en");
+        assert_eq!(
+            reader.get_partitions("lang", &en).await.unwrap(),
+            (0..=2).collect::<RoaringBitmap>()
+        );
+        assert_eq!(reader.get("lang", &en).await.unwrap(), en_offset_ids);
+        // Only the partitions with offset ids of the domain are read
+        let domain = RoaringBitmap::from_iter([1 << POSTING_LIST_PARTITION_BITS]);
+        assert_eq!(
+            reader.get_within("lang", &en, Some(&domain)).await.unwrap(),
+            en_offset_ids
+                .iter()
+                .filter(|offset_id| *offset_id >> POSTING_LIST_PARTITION_BITS == 1)
+                .collect::<RoaringBitmap>()
+        );
+        let mut all_offset_ids = en_offset_ids.clone();
+        all_offset_ids.insert(31);
+        assert_eq!(reader.exists("lang").await.unwrap(), all_offset_ids);
+        assert_eq!(
+            reader.group_by("lang").await.unwrap(),
+            vec![
+                (en.clone(), en_offset_ids.clone()),
+                (KeyWrapper::from("fr"), RoaringBitmap::from_iter([31])),
+            ]
+        );
+
+        // Remove the offset ids in the last partition
+        let blockfile_writer = provider
+            .write::<&str, RoaringBitmap>(BlockfileWriterOptions::new().fork(writer_id))
+            .await
+            .unwrap();
+        let forked_writer_id = blockfile_writer.id();
+        let mut writer = MetadataIndexWriter::new_string(blockfile_writer, Some(reader));
+        let removed_offset_ids = en_offset_ids
+            .iter()
+            .filter(|offset_id| *offset_id >> POSTING_LIST_PARTITION_BITS == 2)
+            .collect::<RoaringBitmap>();
+        for offset_id in removed_offset_ids.iter() {
+            writer.delete("lang", "en", offset_id).await.unwrap();
+        }
+        writer.write_to_blockfile().await.unwrap();
+        let flusher = writer.commit().await.unwrap();
+        flusher.flush().await.unwrap();
+
+        let blockfile_reader = provider
+            .read::<&str, RoaringBitmap>(&forked_writer_id)
+            .await
+            .unwrap();
+        assert!(!blockfile_reader
+            .contains(&posting_list_partition_prefix("lang", 2), "en")
+            .await
+            .unwrap());
+        let reader = MetadataIndexReader::new_string(blockfile_reader);
+        assert_eq!(
+            reader.get_partitions("lang", &en).await.unwrap(),
+            (0..=1).collect::<RoaringBitmap>()
+        );
+        assert_eq!(
+            reader.get("lang", &en).await.unwrap(),
+            en_offset_ids - removed_offset_ids
+        );
+    }
+
+    #[tokio::test]
+    async fn test_string_metadata_starts_with() {
+        let provider = BlockfileProvider::new_memory();
+        let blockfile_writer = provider
+            .write::<&str, RoaringBitmap>(BlockfileWriterOptions::default())
+            .await
+            .unwrap();
+        let writer_id = blockfile_writer.id();
+        let mut writer = MetadataIndexWriter::new_string(blockfile_writer, None);
+        writer.set("key", "chroma", 1).await.unwrap();
+        writer.set("key", "chromadb", 2).await.unwrap();
+        writer.set("key", "chrome", 3).await.unwrap();
+        writer.set("key", "Chroma", 4).await.unwrap();
+        writer.set("key", "chr", 5).await.unwrap();
+        writer.set("other_key", "chroma", 6).await.unwrap();
+        writer.write_to_blockfile().await.unwrap();
+        let flusher = writer.commit().await.unwrap();
+        flusher.flush().await.unwrap();
+
+        let blockfile_reader = provider
+            .read::<&str, RoaringBitmap>(&writer_id)
+            .await
+            .unwrap();
+        let reader = MetadataIndexReader::new_string(blockfile_reader);
+        assert_eq!(
+            reader.starts_with("key", "chroma").await.unwrap(),
+            RoaringBitmap::from_iter([1, 2])
+        );
+        assert_eq!(
+            reader.starts_with("key", "chr").await.unwrap(),
+            RoaringBitmap::from_iter([1, 2, 3, 5])
+        );
+        assert!(reader.starts_with("key", "x").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_any() {
+        let provider = BlockfileProvider::new_memory();
+        let blockfile_writer = provider
+            .write::<u32, RoaringBitmap>(BlockfileWriterOptions::default())
+            .await
+            .unwrap();
+        let writer_id = blockfile_writer.id();
+        let mut writer = MetadataIndexWriter::new_u32(blockfile_writer, None);
+        for offset_id in 1..=1000 {
+            writer.set("key", offset_id % 100, offset_id).await.unwrap();
+        }
+        writer.set("other_key", 7, 1001).await.unwrap();
+        writer.write_to_blockfile().await.unwrap();
+        let flusher = writer.commit().await.unwrap();
+        flusher.flush().await.unwrap();
+
+        let blockfile_reader = provider
+            .read::<u32, RoaringBitmap>(&writer_id)
+            .await
+            .unwrap();
+        let reader = MetadataIndexReader::new_u32(blockfile_reader);
+        let expected = |values: &[u32]| {
+            (1..=1000)
+                .filter(|offset_id| values.contains(&(offset_id % 100)))
+                .collect::<RoaringBitmap>()
+        };
+
+        // A small unsorted set with duplicates and a missing value
+        let values = [7, 3, 7, 500];
+        let keys = values
+            .iter()
+            .map(|v| KeyWrapper::from(*v))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            reader.get_any("key", &keys).await.unwrap(),
+            expected(&values)
+        );
+
+        // A large set is merged against the posting lists
+        let values = (0..300).rev().step_by(3).collect::<Vec<u32>>();
+        let keys = values
+            .iter()
+            .map(|v| KeyWrapper::from(*v))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            reader.get_any("key", &keys).await.unwrap(),
+            expected(&values)
+        );
+
+        assert!(reader.get_any("key", &[]).await.unwrap().is_empty());
+        assert!(matches!(
+            reader.get_any("key", &[KeyWrapper::from("7")]).await,
+            Err(MetadataIndexError::InvalidKeyType)
+        ));
+    }
+
     // #[tokio::test]
     // async fn test_set_get_set_delete() {
     //     let provider = BlockfileProvider::new_memory();
@@ -7,6 +7,98 @@ use uuid::Uuid;
 pub struct IndexConfig {
     pub dimensionality: i32,
     pub distance_function: DistanceFunction,
+    pub quantization: Option<ScalarQuantization>,
+}
+
+/// The parameters of int8 scalar quantization, where each dimension of a vector is stored
+/// as `round((value - offset) / scale)` clamped to the int8 range.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScalarQuantization {
+    pub scale: f32,
+    pub offset: f32,
+}
+
+impl Default for ScalarQuantization {
+    // Covers the range of normalized vectors
+    fn default() -> Self {
+        ScalarQuantization {
+            scale: 1.0 / i8::MAX as f32,
+            offset: 0.0,
+        }
+    }
+}
+
+impl ScalarQuantization {
+    pub fn quantize(&self, vector: &[f32]) -> Vec<i8> {
+        vector
+            .iter()
+            .map(|value| {
+                ((value - self.offset) / self.scale)
+                    .round()
+                    .clamp(-(i8::MAX as f32), i8::MAX as f32) as i8
+            })
+            .collect()
+    }
+
+    pub fn dequantize(&self, vector: &[i8]) -> Vec<f32> {
+        vector
+            .iter()
+            .map(|value| *value as f32 * self.scale + self.offset)
+            .collect()
+    }
+
+    /// The number of values of the vector that are outside of the range covered by the
+    /// quantization, and are clamped when the vector is quantized. A growing share of such
+    /// values means that the distribution of the vectors has drifted away from the quantization.
+    pub fn clipped(&self, vector: &[f32]) -> usize {
+        let limit = self.scale * (i8::MAX as f32 + 0.5);
+        vector
+            .iter()
+            .filter(|value| (*value - self.offset).abs() > limit)
+            .count()
+    }
+
+    /// Fits the quantization to the range of the values of the vectors, or returns `None`
+    /// if there are no values.
+    pub fn fit<'vector>(vectors: impl IntoIterator<Item = &'vector [f32]>) -> Option<Self> {
+        let (min, max) = vectors.into_iter().flatten().fold(
+            None,
+            |range: Option<(f32, f32)>, value| match range {
+                Some((min, max)) => Some((min.min(*value), max.max(*value))),
+                None => Some((*value, *value)),
+            },
+        )?;
+        Some(ScalarQuantization::from_range(min, max))
+    }
+
+    /// The quantization that covers the values from `min` to `max`, for the callers that
+    /// track the range of the values themselves.
+    pub fn from_range(min: f32, max: f32) -> Self {
+        let scale = (max - min) / 2.0 / i8::MAX as f32;
+        ScalarQuantization {
+            scale: if scale > 0.0 {
+                scale
+            } else {
+                ScalarQuantization::default().scale
+            },
+            offset: (max + min) / 2.0,
+        }
+    }
+
+    /// The quantization that covers the values from `min` to `max` with no offset, for the
+    /// spaces whose int8 distance assumes that zero is quantized to zero, i.e. the inner
+    /// product and the cosine distance.
+    pub fn from_symmetric_range(min: f32, max: f32) -> Self {
+        let scale = min.abs().max(max.abs()) / i8::MAX as f32;
+        ScalarQuantization {
+            scale: if scale > 0.0 {
+                scale
+            } else {
+                ScalarQuantization::default().scale
+            },
+            offset: 0.0,
+        }
+    }
 }
 
 #[derive(Error, Debug)]
@@ -24,10 +116,15 @@ impl ChromaError for IndexConfigFromSegmentError {
 }
 
 impl IndexConfig {
-    pub fn new(dimensionality: i32, distance_function: DistanceFunction) -> Self {
+    pub fn new(
+        dimensionality: i32,
+        distance_function: DistanceFunction,
+        quantization: Option<ScalarQuantization>,
+    ) -> Self {
         IndexConfig {
             dimensionality,
             distance_function,
+            quantization,
         }
     }
 }
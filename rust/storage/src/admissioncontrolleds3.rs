@@ -1,9 +1,12 @@
 use crate::StorageConfigError;
 use crate::{
     config::{RateLimitingConfig, StorageConfig},
+    retry::RetryPolicy,
     s3::{S3GetError, S3PutError, S3Storage},
+    DeleteError, ListError, ObjectMetadata, PutOptions,
 };
 use async_trait::async_trait;
+use bytes::Bytes;
 use chroma_config::Configurable;
 use chroma_error::{ChromaError, ErrorCodes};
 use futures::{future::Shared, stream, FutureExt, StreamExt};
@@ -244,13 +247,57 @@ impl AdmissionControlledS3Storage {
         res
     }
 
+    /// Reads a range of the object once the rate limiter admits it. The ranges are not
+    /// coalesced, since requests for different ranges of an object rarely overlap
+    pub async fn get_range(
+        &self,
+        key: &str,
+        offset: usize,
+        len: usize,
+    ) -> Result<Arc<Vec<u8>>, AdmissionControlledS3StorageError> {
+        let _token = self.rate_limiter.enter().await;
+        Ok(self.storage.get_range(key, offset, len).await?)
+    }
+
+    pub(crate) fn retry_policy(&self) -> &RetryPolicy {
+        &self.storage.retry_policy
+    }
+
     pub async fn put_file(&self, key: &str, path: &str) -> Result<(), S3PutError> {
         self.storage.put_file(key, path).await
     }
 
-    pub async fn put_bytes(&self, key: &str, bytes: Vec<u8>) -> Result<(), S3PutError> {
+    pub async fn put_file_with_options(
+        &self,
+        key: &str,
+        path: &str,
+        options: &PutOptions,
+    ) -> Result<(), S3PutError> {
+        self.storage.put_file_with_options(key, path, options).await
+    }
+
+    pub async fn put_bytes(&self, key: &str, bytes: impl Into<Bytes>) -> Result<(), S3PutError> {
         self.storage.put_bytes(key, bytes).await
     }
+
+    pub async fn put_bytes_with_options(
+        &self,
+        key: &str,
+        bytes: impl Into<Bytes>,
+        options: &PutOptions,
+    ) -> Result<(), S3PutError> {
+        self.storage
+            .put_bytes_with_options(key, bytes, options)
+            .await
+    }
+
+    pub async fn list(&self, prefix: &str) -> Result<Vec<ObjectMetadata>, ListError> {
+        self.storage.list(prefix).await
+    }
+
+    pub async fn delete(&self, key: &str) -> Result<(), DeleteError> {
+        self.storage.delete(key).await
+    }
 }
 
 #[async_trait]
@@ -326,7 +373,9 @@ mod tests {
 
     use rand::{distributions::Alphanumeric, Rng};
 
-    use crate::{admissioncontrolleds3::AdmissionControlledS3Storage, s3::S3Storage};
+    use crate::{
+        admissioncontrolleds3::AdmissionControlledS3Storage, retry::RetryPolicy, s3::S3Storage,
+    };
 
     fn get_s3_client() -> aws_sdk_s3::Client {
         // Set up credentials assuming minio is running locally
@@ -358,6 +407,8 @@ mod tests {
             client,
             upload_part_size_bytes: 1024 * 1024 * 8,
             download_part_size_bytes: 1024 * 1024 * 8,
+            upload_concurrency: 4,
+            retry_policy: RetryPolicy::default(),
         };
         storage.create_bucket().await.unwrap();
         let admission_controlled_storage =
@@ -408,6 +459,8 @@ mod tests {
             client,
             upload_part_size_bytes: 1024 * 1024 * 8,
             download_part_size_bytes: 1024 * 1024 * 8,
+            upload_concurrency: 4,
+            retry_policy: RetryPolicy::default(),
         };
         storage.create_bucket().await.unwrap();
         let admission_controlled_storage =
@@ -1,9 +1,11 @@
+use crate::retry::RetryConfig;
 use serde::Deserialize;
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone)]
 /// The configuration for the chosen storage.
 /// # Options
 /// - S3: The configuration for the s3 storage.
+/// - Encrypted: The configuration for a storage whose objects are encrypted on the client side.
 /// # Notes
 /// See config.rs in the root of the worker crate for an example of how to use
 /// config files to configure the worker.
@@ -17,6 +19,8 @@ pub enum StorageConfig {
     Local(LocalStorageConfig),
     #[serde(alias = "admissioncontrolleds3")]
     AdmissionControlledS3(AdmissionControlledS3StorageConfig),
+    #[serde(alias = "encrypted")]
+    Encrypted(EncryptedStorageConfig),
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -25,12 +29,31 @@ pub enum ObjectStoreType {
     Minio,
     #[serde(alias = "s3")]
     S3,
+    #[serde(alias = "gcs")]
+    Gcs,
+    #[serde(alias = "azure")]
+    Azure,
 }
 
+/// The bucket of the object store
+/// # Fields
+/// - name: The name of the bucket, or of the container of an Azure storage account.
+/// - type: The service that holds the bucket.
+/// - service_account_path: The key file of the service account that a GCS bucket is accessed
+///   with. When it is not set, the credentials of the environment are used, e.g. the workload
+///   identity of the pod on GKE.
+/// - account_name: The Azure storage account of the container. When it is not set, it is
+///   read from AZURE_STORAGE_ACCOUNT_NAME. The container is accessed with the SAS token in
+///   AZURE_STORAGE_SAS_TOKEN if there is one, and with the managed identity of the host
+///   otherwise.
 #[derive(Deserialize, Debug, Clone)]
 pub struct ObjectStoreBucketConfig {
     pub name: String,
     pub r#type: ObjectStoreType,
+    #[serde(default)]
+    pub service_account_path: Option<String>,
+    #[serde(default)]
+    pub account_name: Option<String>,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -39,6 +62,8 @@ pub struct ObjectStoreConfig {
     pub upload_part_size_bytes: u64,
     pub download_part_size_bytes: u64,
     pub max_concurrent_requests: usize,
+    #[serde(default)]
+    pub retry: RetryConfig,
 }
 
 #[derive(Deserialize, PartialEq, Debug, Clone)]
@@ -51,6 +76,10 @@ pub enum S3CredentialsConfig {
 /// The configuration for the s3 storage type
 /// # Fields
 /// - bucket: The name of the bucket to use.
+/// - upload_part_size_bytes: The size of the parts of a multipart upload. Smaller objects are
+///   uploaded in a single request.
+/// - upload_concurrency: The number of parts of a multipart upload that are uploaded at once.
+/// - retry: The retries of the requests to the bucket.
 pub struct S3StorageConfig {
     pub bucket: String,
     pub credentials: S3CredentialsConfig,
@@ -58,6 +87,16 @@ pub struct S3StorageConfig {
     pub request_timeout_ms: u64,
     pub upload_part_size_bytes: usize,
     pub download_part_size_bytes: usize,
+    #[serde(default = "S3StorageConfig::default_upload_concurrency")]
+    pub upload_concurrency: usize,
+    #[serde(default)]
+    pub retry: RetryConfig,
+}
+
+impl S3StorageConfig {
+    fn default_upload_concurrency() -> usize {
+        4
+    }
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -86,3 +125,34 @@ pub struct CountBasedPolicyConfig {
 pub enum RateLimitingConfig {
     CountBasedPolicy(CountBasedPolicyConfig),
 }
+
+#[derive(Deserialize, Debug, Clone)]
+/// The configuration for the client side encryption of the objects of a storage
+/// # Fields
+/// - storage: The storage that the encrypted objects are written to.
+/// - master_key: The key that wraps the data keys of the objects that are written.
+/// - previous_master_keys: The keys that the data keys of older objects may be wrapped with,
+///   so that the objects remain readable after the master key is rotated.
+/// - read_unencrypted: Whether the objects that are not encrypted are read as they are, e.g.
+///   the objects written before the encryption is enabled. They fail to read otherwise.
+pub struct EncryptedStorageConfig {
+    pub storage: Box<StorageConfig>,
+    pub master_key: MasterKeyConfig,
+    #[serde(default)]
+    pub previous_master_keys: Vec<MasterKeyConfig>,
+    #[serde(default)]
+    pub read_unencrypted: bool,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+/// A key that wraps the data keys of the encrypted objects
+/// # Options
+/// - Kms: An AWS KMS key, by its id or ARN. The credentials of the environment are used.
+/// - Local: A 256-bit key that is read from the file at the path, under the id that is
+///   recorded in the objects that it wraps the data keys of.
+pub enum MasterKeyConfig {
+    #[serde(alias = "kms")]
+    Kms { key_id: String },
+    #[serde(alias = "local")]
+    Local { key_id: String, path: String },
+}
@@ -0,0 +1,576 @@
+use super::config::{EncryptedStorageConfig, MasterKeyConfig};
+use super::{
+    DeleteError, GetError, ListError, ObjectMetadata, PutError, PutOptions, Storage,
+    StorageConfigError,
+};
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use async_trait::async_trait;
+use aws_sdk_kms::primitives::Blob;
+use aws_sdk_kms::types::DataKeySpec;
+use chroma_config::Configurable;
+use chroma_error::{ChromaError, ErrorCodes};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::Arc;
+use thiserror::Error;
+
+// An encrypted object is laid out as
+// magic | version | key id length (u16) | key id | wrapped data key length (u16) |
+// wrapped data key | nonce | ciphertext and tag
+// where the header up to the nonce and the key of the object in storage are authenticated along
+// with the ciphertext, so that the ciphertext of an object cannot be swapped for the ciphertext
+// of another
+const MAGIC: &[u8; 4] = b"CENC";
+const VERSION: u8 = 1;
+const NONCE_LEN: usize = 12;
+// The objects that are encrypted with a data key before it is replaced by a new one. The nonces
+// are random, so the data key is rotated well before the collisions of 96-bit nonces become
+// likely, i.e. 2^32 encryptions under a key
+const DEFAULT_MAX_OBJECTS_PER_DATA_KEY: u64 = 1 << 24;
+
+#[derive(Error, Debug, Clone)]
+pub enum EncryptionError {
+    #[error("Object is not encrypted")]
+    NotEncrypted,
+    #[error("Malformed encrypted object: {0}")]
+    Malformed(String),
+    #[error("Unknown master key: {0}")]
+    UnknownMasterKey(String),
+    #[error("KMS error: {0}")]
+    Kms(String),
+    #[error("Failed to encrypt or decrypt object")]
+    Cipher,
+}
+
+impl ChromaError for EncryptionError {
+    fn code(&self) -> ErrorCodes {
+        ErrorCodes::Internal
+    }
+}
+
+enum MasterKey {
+    Kms {
+        client: aws_sdk_kms::Client,
+        key_id: String,
+    },
+    Local {
+        key_id: String,
+        cipher: Box<Aes256Gcm>,
+    },
+}
+
+impl MasterKey {
+    fn key_id(&self) -> &str {
+        match self {
+            MasterKey::Kms { key_id, .. } => key_id,
+            MasterKey::Local { key_id, .. } => key_id,
+        }
+    }
+
+    // Generates a data key, and returns its cipher and the data key wrapped by the master key
+    async fn generate_data_key(&self) -> Result<(Aes256Gcm, Vec<u8>), EncryptionError> {
+        match self {
+            MasterKey::Kms { client, key_id } => {
+                let output = client
+                    .generate_data_key()
+                    .key_id(key_id)
+                    .key_spec(DataKeySpec::Aes256)
+                    .send()
+                    .await
+                    .map_err(|e| EncryptionError::Kms(e.to_string()))?;
+                let (Some(plaintext), Some(wrapped)) =
+                    (output.plaintext(), output.ciphertext_blob())
+                else {
+                    return Err(EncryptionError::Kms(
+                        "No data key in the response".to_string(),
+                    ));
+                };
+                let cipher = Aes256Gcm::new_from_slice(plaintext.as_ref())
+                    .map_err(|_| EncryptionError::Kms("Invalid data key".to_string()))?;
+                Ok((cipher, wrapped.as_ref().to_vec()))
+            }
+            MasterKey::Local { cipher, .. } => {
+                let data_key = Aes256Gcm::generate_key(OsRng);
+                let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+                let mut wrapped = nonce.to_vec();
+                wrapped.extend(
+                    cipher
+                        .encrypt(&nonce, data_key.as_slice())
+                        .map_err(|_| EncryptionError::Cipher)?,
+                );
+                Ok((Aes256Gcm::new(&data_key), wrapped))
+            }
+        }
+    }
+
+    async fn unwrap_data_key(&self, wrapped: &[u8]) -> Result<Aes256Gcm, EncryptionError> {
+        match self {
+            MasterKey::Kms { client, key_id } => {
+                let output = client
+                    .decrypt()
+                    .key_id(key_id)
+                    .ciphertext_blob(Blob::new(wrapped))
+                    .send()
+                    .await
+                    .map_err(|e| EncryptionError::Kms(e.to_string()))?;
+                let plaintext = output.plaintext().ok_or_else(|| {
+                    EncryptionError::Kms("No data key in the response".to_string())
+                })?;
+                Aes256Gcm::new_from_slice(plaintext.as_ref())
+                    .map_err(|_| EncryptionError::Kms("Invalid data key".to_string()))
+            }
+            MasterKey::Local { cipher, .. } => {
+                if wrapped.len() < NONCE_LEN {
+                    return Err(EncryptionError::Malformed(
+                        "Wrapped data key is too short".to_string(),
+                    ));
+                }
+                let (nonce, wrapped) = wrapped.split_at(NONCE_LEN);
+                let data_key = cipher
+                    .decrypt(Nonce::from_slice(nonce), wrapped)
+                    .map_err(|_| EncryptionError::Cipher)?;
+                Aes256Gcm::new_from_slice(&data_key)
+                    .map_err(|_| EncryptionError::Malformed("Invalid data key".to_string()))
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Configurable<MasterKeyConfig> for MasterKey {
+    async fn try_from_config(config: &MasterKeyConfig) -> Result<Self, Box<dyn ChromaError>> {
+        match config {
+            MasterKeyConfig::Kms { key_id } => Ok(MasterKey::Kms {
+                client: aws_sdk_kms::Client::new(&aws_config::load_from_env().await),
+                key_id: key_id.clone(),
+            }),
+            MasterKeyConfig::Local { key_id, path } => {
+                let key = std::fs::read(path).map_err(|e| {
+                    Box::new(StorageConfigError::InvalidMasterKey(format!(
+                        "{}: {}",
+                        key_id, e
+                    ))) as Box<dyn ChromaError>
+                })?;
+                let cipher = Aes256Gcm::new_from_slice(&key).map_err(|_| {
+                    Box::new(StorageConfigError::InvalidMasterKey(format!(
+                        "{} is not 256 bits",
+                        key_id
+                    ))) as Box<dyn ChromaError>
+                })?;
+                Ok(MasterKey::Local {
+                    key_id: key_id.clone(),
+                    cipher: Box::new(cipher),
+                })
+            }
+        }
+    }
+}
+
+// The data key that the storage encrypts the objects it writes with, until it has encrypted
+// the maximum number of objects with it. Each object is encrypted with a random nonce
+struct DataKey {
+    key_id: String,
+    wrapped: Vec<u8>,
+    cipher: Aes256Gcm,
+    objects: u64,
+}
+
+// The fields of the header of an encrypted object
+struct Envelope<'a> {
+    key_id: &'a str,
+    wrapped_data_key: &'a [u8],
+    header: &'a [u8],
+    nonce: &'a [u8],
+    ciphertext: &'a [u8],
+}
+
+impl<'a> Envelope<'a> {
+    fn is_encrypted(bytes: &[u8]) -> bool {
+        bytes.starts_with(MAGIC)
+    }
+
+    fn header(key_id: &str, wrapped_data_key: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        let key_id_len = u16::try_from(key_id.len())
+            .map_err(|_| EncryptionError::Malformed("Master key id is too long".to_string()))?;
+        let wrapped_len = u16::try_from(wrapped_data_key.len())
+            .map_err(|_| EncryptionError::Malformed("Wrapped data key is too long".to_string()))?;
+        let mut header = Vec::with_capacity(9 + key_id.len() + wrapped_data_key.len());
+        header.extend_from_slice(MAGIC);
+        header.push(VERSION);
+        header.extend_from_slice(&key_id_len.to_be_bytes());
+        header.extend_from_slice(key_id.as_bytes());
+        header.extend_from_slice(&wrapped_len.to_be_bytes());
+        header.extend_from_slice(wrapped_data_key);
+        Ok(header)
+    }
+
+    // Reads the field of the given length at the offset, and moves the offset past it
+    fn field(bytes: &'a [u8], offset: &mut usize, len: usize) -> Result<&'a [u8], EncryptionError> {
+        let field = bytes
+            .get(*offset..*offset + len)
+            .ok_or_else(|| EncryptionError::Malformed("Object is truncated".to_string()))?;
+        *offset += len;
+        Ok(field)
+    }
+
+    fn parse(bytes: &'a [u8]) -> Result<Self, EncryptionError> {
+        let mut offset = MAGIC.len();
+        let version = Self::field(bytes, &mut offset, 1)?[0];
+        if version != VERSION {
+            return Err(EncryptionError::Malformed(format!(
+                "Unsupported version {}",
+                version
+            )));
+        }
+        let key_id_len = Self::field(bytes, &mut offset, 2)?;
+        let key_id_len = u16::from_be_bytes([key_id_len[0], key_id_len[1]]) as usize;
+        let key_id = std::str::from_utf8(Self::field(bytes, &mut offset, key_id_len)?)
+            .map_err(|_| EncryptionError::Malformed("Master key id is not UTF-8".to_string()))?;
+        let wrapped_len = Self::field(bytes, &mut offset, 2)?;
+        let wrapped_len = u16::from_be_bytes([wrapped_len[0], wrapped_len[1]]) as usize;
+        let wrapped_data_key = Self::field(bytes, &mut offset, wrapped_len)?;
+        let nonce = Self::field(bytes, &mut offset, NONCE_LEN)?;
+        let header_len = offset - NONCE_LEN;
+        Ok(Envelope {
+            key_id,
+            wrapped_data_key,
+            header: &bytes[..header_len],
+            nonce,
+            ciphertext: &bytes[offset..],
+        })
+    }
+
+    // The data that is authenticated along with the ciphertext of the object with the key
+    fn associated_data(header: &[u8], key: &str) -> Vec<u8> {
+        let mut aad = header.to_vec();
+        aad.extend_from_slice(key.as_bytes());
+        aad
+    }
+}
+
+/// The master keys of an encrypted storage, and the data keys that they wrap
+pub struct Keyring {
+    master_key: MasterKey,
+    previous_master_keys: Vec<MasterKey>,
+    read_unencrypted: bool,
+    data_key: tokio::sync::Mutex<Option<DataKey>>,
+    max_objects_per_data_key: u64,
+    // The data keys that are unwrapped by the master keys, by their wrapped key. A storage
+    // rotates its data key rarely, so there are few of them
+    unwrapped_data_keys: Mutex<HashMap<Vec<u8>, Aes256Gcm>>,
+}
+
+impl Keyring {
+    fn new(
+        master_key: MasterKey,
+        previous_master_keys: Vec<MasterKey>,
+        read_unencrypted: bool,
+    ) -> Self {
+        Self {
+            master_key,
+            previous_master_keys,
+            read_unencrypted,
+            data_key: tokio::sync::Mutex::new(None),
+            max_objects_per_data_key: DEFAULT_MAX_OBJECTS_PER_DATA_KEY,
+            unwrapped_data_keys: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // Returns the master key id, the wrapped data key and the cipher of the data key to encrypt
+    // an object with, and generates a new data key once the current one has encrypted the
+    // maximum number of objects
+    async fn data_key(&self) -> Result<(String, Vec<u8>, Aes256Gcm), EncryptionError> {
+        let mut data_key = self.data_key.lock().await;
+        let data_key = match data_key.as_mut() {
+            Some(current) if current.objects < self.max_objects_per_data_key => current,
+            _ => {
+                let (cipher, wrapped) = self.master_key.generate_data_key().await?;
+                data_key.insert(DataKey {
+                    key_id: self.master_key.key_id().to_string(),
+                    wrapped,
+                    cipher,
+                    objects: 0,
+                })
+            }
+        };
+        data_key.objects += 1;
+        Ok((
+            data_key.key_id.clone(),
+            data_key.wrapped.clone(),
+            data_key.cipher.clone(),
+        ))
+    }
+
+    async fn unwrap_data_key(
+        &self,
+        key_id: &str,
+        wrapped: &[u8],
+    ) -> Result<Aes256Gcm, EncryptionError> {
+        if let Some(cipher) = self.unwrapped_data_keys.lock().get(wrapped) {
+            return Ok(cipher.clone());
+        }
+        let master_key = std::iter::once(&self.master_key)
+            .chain(&self.previous_master_keys)
+            .find(|master_key| master_key.key_id() == key_id)
+            .ok_or_else(|| EncryptionError::UnknownMasterKey(key_id.to_string()))?;
+        let cipher = master_key.unwrap_data_key(wrapped).await?;
+        self.unwrapped_data_keys
+            .lock()
+            .insert(wrapped.to_vec(), cipher.clone());
+        Ok(cipher)
+    }
+
+    async fn encrypt(&self, key: &str, plaintext: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        let (key_id, wrapped, cipher) = self.data_key().await?;
+        let mut bytes = Envelope::header(&key_id, &wrapped)?;
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: plaintext,
+                    aad: &Envelope::associated_data(&bytes, key),
+                },
+            )
+            .map_err(|_| EncryptionError::Cipher)?;
+        bytes.extend_from_slice(&nonce);
+        bytes.extend(ciphertext);
+        Ok(bytes)
+    }
+
+    async fn decrypt(
+        &self,
+        key: &str,
+        bytes: Arc<Vec<u8>>,
+    ) -> Result<Arc<Vec<u8>>, EncryptionError> {
+        if !Envelope::is_encrypted(&bytes) {
+            return match self.read_unencrypted {
+                true => Ok(bytes),
+                false => Err(EncryptionError::NotEncrypted),
+            };
+        }
+        let envelope = Envelope::parse(&bytes)?;
+        let cipher = self
+            .unwrap_data_key(envelope.key_id, envelope.wrapped_data_key)
+            .await?;
+        let plaintext = cipher
+            .decrypt(
+                Nonce::from_slice(envelope.nonce),
+                Payload {
+                    msg: envelope.ciphertext,
+                    aad: &Envelope::associated_data(envelope.header, key),
+                },
+            )
+            .map_err(|_| EncryptionError::Cipher)?;
+        Ok(Arc::new(plaintext))
+    }
+}
+
+#[async_trait]
+impl Configurable<EncryptedStorageConfig> for Keyring {
+    async fn try_from_config(
+        config: &EncryptedStorageConfig,
+    ) -> Result<Self, Box<dyn ChromaError>> {
+        let master_key = MasterKey::try_from_config(&config.master_key).await?;
+        let mut previous_master_keys = Vec::with_capacity(config.previous_master_keys.len());
+        for previous_master_key in &config.previous_master_keys {
+            previous_master_keys.push(MasterKey::try_from_config(previous_master_key).await?);
+        }
+        Ok(Self::new(
+            master_key,
+            previous_master_keys,
+            config.read_unencrypted,
+        ))
+    }
+}
+
+// The calls to the underlying storage are boxed, since it is a `Storage` as well
+
+/// A storage whose objects are encrypted before they are written to the underlying storage,
+/// and decrypted as they are read. The objects are encrypted with AES-GCM under a data key,
+/// which is wrapped by the master key and recorded in each object along with the id of the
+/// master key, so that the objects remain readable after the master key is rotated
+#[derive(Clone)]
+pub struct EncryptedStorage {
+    storage: Box<Storage>,
+    keyring: Arc<Keyring>,
+}
+
+impl EncryptedStorage {
+    pub fn new(storage: Storage, keyring: Keyring) -> Self {
+        Self {
+            storage: Box::new(storage),
+            keyring: Arc::new(keyring),
+        }
+    }
+
+    pub async fn get(&self, key: &str) -> Result<Arc<Vec<u8>>, GetError> {
+        let bytes = self.get_stored(key).await?;
+        self.decrypt(key, bytes).await
+    }
+
+    // The bytes of the object as they are in the underlying storage, before they are decrypted
+    pub async fn get_stored(&self, key: &str) -> Result<Arc<Vec<u8>>, GetError> {
+        Box::pin(self.storage.get(key)).await
+    }
+
+    pub async fn decrypt(&self, key: &str, bytes: Arc<Vec<u8>>) -> Result<Arc<Vec<u8>>, GetError> {
+        Ok(self.keyring.decrypt(key, bytes).await?)
+    }
+
+    pub async fn get_parallel(&self, key: &str) -> Result<Arc<Vec<u8>>, GetError> {
+        let bytes = Box::pin(self.storage.get_parallel(key)).await?;
+        Ok(self.keyring.decrypt(key, bytes).await?)
+    }
+
+    // The object is authenticated as a whole, so a range of it falls back to reading it in full
+    pub async fn get_range(
+        &self,
+        key: &str,
+        offset: usize,
+        len: usize,
+    ) -> Result<Arc<Vec<u8>>, GetError> {
+        let bytes = self.get(key).await?;
+        let start = offset.min(bytes.len());
+        let end = offset.saturating_add(len).min(bytes.len());
+        Ok(Arc::new(bytes[start..end].to_vec()))
+    }
+
+    pub async fn put_bytes(
+        &self,
+        key: &str,
+        bytes: &[u8],
+        options: &PutOptions,
+    ) -> Result<(), PutError> {
+        let bytes = self.keyring.encrypt(key, bytes).await?;
+        Box::pin(self.storage.put_bytes_with_options(key, bytes, options)).await
+    }
+
+    // The file is encrypted in memory, so it is read in full rather than streamed
+    pub async fn put_file(
+        &self,
+        key: &str,
+        path: &str,
+        options: &PutOptions,
+    ) -> Result<(), PutError> {
+        let bytes = tokio::fs::read(path).await?;
+        self.put_bytes(key, &bytes, options).await
+    }
+
+    pub async fn list(&self, prefix: &str) -> Result<Vec<ObjectMetadata>, ListError> {
+        Box::pin(self.storage.list(prefix)).await
+    }
+
+    pub async fn delete(&self, key: &str) -> Result<(), DeleteError> {
+        Box::pin(self.storage.delete(key)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EncryptedStorage, EncryptionError, Envelope, Keyring, MasterKey, MAGIC};
+    use crate::local::LocalStorage;
+    use crate::{GetError, PutOptions, Storage};
+    use aes_gcm::aead::{KeyInit, OsRng};
+    use aes_gcm::Aes256Gcm;
+    use std::sync::Arc;
+
+    fn local_master_key(key_id: &str) -> MasterKey {
+        MasterKey::Local {
+            key_id: key_id.to_string(),
+            cipher: Box::new(Aes256Gcm::new(&Aes256Gcm::generate_key(OsRng))),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_storage() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let local = Storage::Local(LocalStorage::new(tmp_dir.path().to_str().unwrap()));
+        let old_master_key = local_master_key("old");
+        let old_cipher = match &old_master_key {
+            MasterKey::Local { cipher, .. } => cipher.clone(),
+            MasterKey::Kms { .. } => unreachable!(),
+        };
+        let storage =
+            EncryptedStorage::new(local.clone(), Keyring::new(old_master_key, vec![], false));
+        let options = PutOptions::default();
+
+        storage
+            .put_bytes("key", b"0123456789", &options)
+            .await
+            .unwrap();
+        let raw = local.get("key").await.unwrap();
+        assert!(raw.starts_with(MAGIC));
+        assert!(!raw.windows(10).any(|window| window == b"0123456789"));
+        assert_eq!(*storage.get("key").await.unwrap(), b"0123456789");
+        assert_eq!(*storage.get_range("key", 2, 3).await.unwrap(), b"234");
+        assert_eq!(*storage.get_range("key", 8, 5).await.unwrap(), b"89");
+
+        // The objects written with a rotated master key remain readable
+        let rotated = EncryptedStorage::new(
+            local.clone(),
+            Keyring::new(
+                local_master_key("new"),
+                vec![MasterKey::Local {
+                    key_id: "old".to_string(),
+                    cipher: old_cipher,
+                }],
+                false,
+            ),
+        );
+        assert_eq!(*rotated.get("key").await.unwrap(), b"0123456789");
+        rotated.put_bytes("other", b"abc", &options).await.unwrap();
+        assert!(matches!(
+            storage.get("other").await,
+            Err(GetError::EncryptionError(
+                EncryptionError::UnknownMasterKey(_)
+            ))
+        ));
+
+        // The ciphertext of an object fails to decrypt under the key of another object
+        local.put_bytes("swapped", raw.to_vec()).await.unwrap();
+        assert!(matches!(
+            storage.get("swapped").await,
+            Err(GetError::EncryptionError(EncryptionError::Cipher))
+        ));
+
+        // A tampered object fails to decrypt
+        let mut tampered = raw.to_vec();
+        *tampered.last_mut().unwrap() ^= 1;
+        local.put_bytes("key", tampered).await.unwrap();
+        assert!(matches!(
+            storage.get("key").await,
+            Err(GetError::EncryptionError(EncryptionError::Cipher))
+        ));
+
+        // The objects that are not encrypted are only read if it is allowed
+        local.put_bytes("plain", b"abc".to_vec()).await.unwrap();
+        assert!(matches!(
+            storage.get("plain").await,
+            Err(GetError::EncryptionError(EncryptionError::NotEncrypted))
+        ));
+        let lenient =
+            EncryptedStorage::new(local, Keyring::new(local_master_key("old"), vec![], true));
+        assert_eq!(*lenient.get("plain").await.unwrap(), b"abc");
+    }
+
+    #[tokio::test]
+    async fn test_data_key_rotation() {
+        let mut keyring = Keyring::new(local_master_key("master"), vec![], false);
+        keyring.max_objects_per_data_key = 2;
+        let mut wrapped_data_keys = Vec::new();
+        for i in 0..3 {
+            let key = format!("key{}", i);
+            let bytes = keyring.encrypt(&key, key.as_bytes()).await.unwrap();
+            wrapped_data_keys.push(Envelope::parse(&bytes).unwrap().wrapped_data_key.to_vec());
+            assert_eq!(
+                *keyring.decrypt(&key, Arc::new(bytes)).await.unwrap(),
+                key.as_bytes()
+            );
+        }
+        // The third object is encrypted with a new data key
+        assert_eq!(wrapped_data_keys[0], wrapped_data_keys[1]);
+        assert_ne!(wrapped_data_keys[1], wrapped_data_keys[2]);
+    }
+}
@@ -1,15 +1,20 @@
 use std::sync::Arc;
+use std::time::SystemTime;
 
 use self::config::StorageConfig;
+use self::retry::RetryPolicy;
 use self::s3::S3GetError;
 use admissioncontrolleds3::AdmissionControlledS3StorageError;
+use bytes::Bytes;
 use chroma_config::Configurable;
 use chroma_error::{ChromaError, ErrorCodes};
 
 pub mod admissioncontrolleds3;
 pub mod config;
+pub mod encrypted;
 pub mod local;
 pub mod object_store;
+pub mod retry;
 pub mod s3;
 pub mod stream;
 use local::LocalStorage;
@@ -22,6 +27,37 @@ pub enum Storage {
     S3(s3::S3Storage),
     Local(local::LocalStorage),
     AdmissionControlledS3(admissioncontrolleds3::AdmissionControlledS3Storage),
+    Encrypted(encrypted::EncryptedStorage),
+}
+
+/// The options of a write to the storage
+///
+/// # Fields
+/// - `kms_key_id`: The id of the KMS key that the object is encrypted with on the server side.
+///   When it is not set, the default encryption of the bucket is used
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PutOptions {
+    pub kms_key_id: Option<String>,
+}
+
+/// An object in the storage, as it is listed
+///
+/// # Fields
+/// - `key`: The key of the object
+/// - `last_modified`: When the object was last written
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObjectMetadata {
+    pub key: String,
+    pub last_modified: SystemTime,
+}
+
+// The error of a request to the object store is unavailable if it may succeed when retried
+fn object_store_error_code(err: &::object_store::Error) -> ErrorCodes {
+    if object_store::is_transient(err) {
+        ErrorCodes::Unavailable
+    } else {
+        ErrorCodes::Internal
+    }
 }
 
 #[derive(Error, Debug, Clone)]
@@ -34,15 +70,21 @@ pub enum GetError {
     S3Error(#[from] S3GetError),
     #[error("Local storage error: {0}")]
     LocalError(String),
+    #[error("Storage request shed while the storage is unhealthy")]
+    Shed,
+    #[error("Encryption error: {0}")]
+    EncryptionError(#[from] encrypted::EncryptionError),
 }
 
 impl ChromaError for GetError {
     fn code(&self) -> ErrorCodes {
         match self {
             GetError::NoSuchKey(_) => ErrorCodes::NotFound,
-            GetError::ObjectStoreError(_) => ErrorCodes::Internal,
-            GetError::S3Error(_) => ErrorCodes::Internal,
+            GetError::ObjectStoreError(e) => object_store_error_code(e),
+            GetError::S3Error(e) => e.code(),
             GetError::LocalError(_) => ErrorCodes::Internal,
+            GetError::Shed => ErrorCodes::Unavailable,
+            GetError::EncryptionError(e) => e.code(),
         }
     }
 }
@@ -66,14 +108,23 @@ pub enum PutError {
     S3Error(#[from] s3::S3PutError),
     #[error("Local storage error: {0}")]
     LocalError(String),
+    #[error("Unsupported put option: {0}")]
+    UnsupportedOption(String),
+    #[error("Storage request shed while the storage is unhealthy")]
+    Shed,
+    #[error("Encryption error: {0}")]
+    EncryptionError(#[from] encrypted::EncryptionError),
 }
 
 impl ChromaError for PutError {
     fn code(&self) -> ErrorCodes {
         match self {
-            PutError::ObjectStoreError(_) => ErrorCodes::Internal,
-            PutError::S3Error(_) => ErrorCodes::Internal,
+            PutError::ObjectStoreError(e) => object_store_error_code(e),
+            PutError::S3Error(e) => e.code(),
             PutError::LocalError(_) => ErrorCodes::Internal,
+            PutError::UnsupportedOption(_) => ErrorCodes::Unimplemented,
+            PutError::Shed => ErrorCodes::Unavailable,
+            PutError::EncryptionError(e) => e.code(),
         }
     }
 }
@@ -90,12 +141,74 @@ impl From<::object_store::Error> for PutError {
     }
 }
 
+#[derive(Error, Debug)]
+pub enum ListError {
+    #[error("ObjectStore error: {0}")]
+    ObjectStoreError(Arc<::object_store::Error>),
+    #[error("S3 error: {0}")]
+    S3Error(String),
+    #[error("Local storage error: {0}")]
+    LocalError(String),
+    #[error("Storage unavailable: {0}")]
+    Unavailable(String),
+    #[error("Storage request shed while the storage is unhealthy")]
+    Shed,
+}
+
+impl ChromaError for ListError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            ListError::ObjectStoreError(e) => object_store_error_code(e),
+            ListError::Unavailable(_) | ListError::Shed => ErrorCodes::Unavailable,
+            _ => ErrorCodes::Internal,
+        }
+    }
+}
+
+impl From<::object_store::Error> for ListError {
+    fn from(e: ::object_store::Error) -> Self {
+        Self::ObjectStoreError(Arc::new(e))
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum DeleteError {
+    #[error("ObjectStore error: {0}")]
+    ObjectStoreError(Arc<::object_store::Error>),
+    #[error("S3 error: {0}")]
+    S3Error(String),
+    #[error("Local storage error: {0}")]
+    LocalError(String),
+    #[error("Storage unavailable: {0}")]
+    Unavailable(String),
+    #[error("Storage request shed while the storage is unhealthy")]
+    Shed,
+}
+
+impl ChromaError for DeleteError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            DeleteError::ObjectStoreError(e) => object_store_error_code(e),
+            DeleteError::Unavailable(_) | DeleteError::Shed => ErrorCodes::Unavailable,
+            _ => ErrorCodes::Internal,
+        }
+    }
+}
+
+impl From<::object_store::Error> for DeleteError {
+    fn from(e: ::object_store::Error) -> Self {
+        Self::ObjectStoreError(Arc::new(e))
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum StorageConfigError {
     #[error("Invalid storage config")]
     InvalidStorageConfig,
     #[error("Failed to create bucket: {0}")]
     FailedToCreateBucket(String),
+    #[error("Invalid master key: {0}")]
+    InvalidMasterKey(String),
 }
 
 impl ChromaError for StorageConfigError {
@@ -103,12 +216,57 @@ impl ChromaError for StorageConfigError {
         match self {
             StorageConfigError::InvalidStorageConfig => ErrorCodes::InvalidArgument,
             StorageConfigError::FailedToCreateBucket(_) => ErrorCodes::Internal,
+            StorageConfigError::InvalidMasterKey(_) => ErrorCodes::InvalidArgument,
         }
     }
 }
 
+// The backends do not implement Debug, so only the kind of storage is printed
+impl std::fmt::Debug for Storage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self {
+            Storage::ObjectStore(_) => "ObjectStore",
+            Storage::S3(_) => "S3",
+            Storage::Local(_) => "Local",
+            Storage::AdmissionControlledS3(_) => "AdmissionControlledS3",
+            Storage::Encrypted(_) => "Encrypted",
+        };
+        f.debug_tuple("Storage").field(&kind).finish()
+    }
+}
+
 impl Storage {
+    // The retries of the requests to the backend. The local storage is not retried, and the
+    // encrypted storage retries the requests to its underlying storage
+    fn retry_policy(&self) -> Option<&RetryPolicy> {
+        match self {
+            Storage::ObjectStore(object_store) => Some(&object_store.retry_policy),
+            Storage::S3(s3) => Some(&s3.retry_policy),
+            Storage::Local(_) => None,
+            Storage::AdmissionControlledS3(as3) => Some(as3.retry_policy()),
+            Storage::Encrypted(_) => None,
+        }
+    }
+
+    // Sends the request with the retries of the backend, if it has any
+    async fn with_retries<T, E, F, Fut>(&self, shed: impl Fn() -> E, request: F) -> Result<T, E>
+    where
+        E: ChromaError,
+        F: Fn() -> Fut,
+        Fut: std::future::Future<Output = Result<T, E>>,
+    {
+        match self.retry_policy() {
+            Some(retry_policy) => retry_policy.run(shed, request).await,
+            None => request().await,
+        }
+    }
+
     pub async fn get(&self, key: &str) -> Result<Arc<Vec<u8>>, GetError> {
+        self.with_retries(|| GetError::Shed, || self.get_once(key))
+            .await
+    }
+
+    async fn get_once(&self, key: &str) -> Result<Arc<Vec<u8>>, GetError> {
         match self {
             Storage::ObjectStore(object_store) => object_store.get(key).await,
             Storage::S3(s3) => {
@@ -121,13 +279,8 @@ impl Storage {
                     },
                 }
             }
-            Storage::Local(local) => {
-                let res = local.get(key).await;
-                match res {
-                    Ok(res) => Ok(res),
-                    Err(e) => Err(GetError::LocalError(e)),
-                }
-            }
+            Storage::Local(local) => local.get(key).await,
+            Storage::Encrypted(encrypted) => encrypted.get(key).await,
             Storage::AdmissionControlledS3(admission_controlled_storage) => {
                 let res = admission_controlled_storage.get(key.to_string()).await;
                 match res {
@@ -143,7 +296,73 @@ impl Storage {
         }
     }
 
+    /// Reads the bytes of the object as they are stored, which are still encrypted if the
+    /// storage encrypts its objects, so that a copy of them that is kept outside of storage is
+    /// protected as well. They are read with `decrypt_stored`
+    pub async fn get_stored(&self, key: &str) -> Result<Arc<Vec<u8>>, GetError> {
+        match self {
+            Storage::Encrypted(encrypted) => encrypted.get_stored(key).await,
+            _ => self.get(key).await,
+        }
+    }
+
+    /// Decrypts the bytes of the object with the key that `get_stored` read, if the storage
+    /// encrypts its objects
+    pub async fn decrypt_stored(
+        &self,
+        key: &str,
+        bytes: Arc<Vec<u8>>,
+    ) -> Result<Arc<Vec<u8>>, GetError> {
+        match self {
+            Storage::Encrypted(encrypted) => encrypted.decrypt(key, bytes).await,
+            _ => Ok(bytes),
+        }
+    }
+
+    /// Reads the bytes of the object from the offset, up to the length, without reading the
+    /// rest of the object. Fewer bytes are returned if the object ends before. The backends
+    /// that cannot read a range of an object, such as the encrypted storage, read it in full
+    pub async fn get_range(
+        &self,
+        key: &str,
+        offset: usize,
+        len: usize,
+    ) -> Result<Arc<Vec<u8>>, GetError> {
+        self.with_retries(|| GetError::Shed, || self.get_range_once(key, offset, len))
+            .await
+    }
+
+    async fn get_range_once(
+        &self,
+        key: &str,
+        offset: usize,
+        len: usize,
+    ) -> Result<Arc<Vec<u8>>, GetError> {
+        match self {
+            Storage::ObjectStore(object_store) => object_store.get_range(key, offset, len).await,
+            Storage::S3(s3) => match s3.get_range(key, offset, len).await {
+                Ok(res) => Ok(res),
+                Err(S3GetError::NoSuchKey(_)) => Err(GetError::NoSuchKey(key.to_string())),
+                Err(e) => Err(GetError::S3Error(e)),
+            },
+            Storage::Local(local) => local.get_range(key, offset, len).await,
+            Storage::Encrypted(encrypted) => encrypted.get_range(key, offset, len).await,
+            Storage::AdmissionControlledS3(as3) => match as3.get_range(key, offset, len).await {
+                Ok(res) => Ok(res),
+                Err(AdmissionControlledS3StorageError::S3GetError(S3GetError::NoSuchKey(_))) => {
+                    Err(GetError::NoSuchKey(key.to_string()))
+                }
+                Err(AdmissionControlledS3StorageError::S3GetError(e)) => Err(GetError::S3Error(e)),
+            },
+        }
+    }
+
     pub async fn get_parallel(&self, key: &str) -> Result<Arc<Vec<u8>>, GetError> {
+        self.with_retries(|| GetError::Shed, || self.get_parallel_once(key))
+            .await
+    }
+
+    async fn get_parallel_once(&self, key: &str) -> Result<Arc<Vec<u8>>, GetError> {
         match self {
             Storage::ObjectStore(object_store) => object_store.get_parallel(key).await,
             Storage::S3(s3) => {
@@ -156,13 +375,8 @@ impl Storage {
                     },
                 }
             }
-            Storage::Local(local) => {
-                let res = local.get(key).await;
-                match res {
-                    Ok(res) => Ok(res),
-                    Err(e) => Err(GetError::LocalError(e)),
-                }
-            }
+            Storage::Local(local) => local.get(key).await,
+            Storage::Encrypted(encrypted) => encrypted.get_parallel(key).await,
             Storage::AdmissionControlledS3(admission_controlled_storage) => {
                 let res = admission_controlled_storage
                     .get_parallel(key.to_string())
@@ -181,30 +395,142 @@ impl Storage {
     }
 
     pub async fn put_file(&self, key: &str, path: &str) -> Result<(), PutError> {
+        self.put_file_with_options(key, path, &PutOptions::default())
+            .await
+    }
+
+    pub async fn put_file_with_options(
+        &self,
+        key: &str,
+        path: &str,
+        options: &PutOptions,
+    ) -> Result<(), PutError> {
+        self.with_retries(|| PutError::Shed, || self.put_file_once(key, path, options))
+            .await
+    }
+
+    async fn put_file_once(
+        &self,
+        key: &str,
+        path: &str,
+        options: &PutOptions,
+    ) -> Result<(), PutError> {
         match self {
-            Storage::ObjectStore(object_store) => object_store.put_file(key, path).await,
-            Storage::S3(s3) => s3.put_file(key, path).await.map_err(PutError::S3Error),
-            Storage::Local(local) => local
-                .put_file(key, path)
+            Storage::ObjectStore(object_store) => {
+                Self::check_kms_key_unsupported(options, "object store")?;
+                object_store.put_file(key, path).await
+            }
+            Storage::S3(s3) => s3
+                .put_file_with_options(key, path, options)
                 .await
-                .map_err(PutError::LocalError),
-            Storage::AdmissionControlledS3(as3) => {
-                as3.put_file(key, path).await.map_err(PutError::S3Error)
+                .map_err(PutError::S3Error),
+            Storage::Local(local) => {
+                Self::check_kms_key_unsupported(options, "local storage")?;
+                local
+                    .put_file(key, path)
+                    .await
+                    .map_err(PutError::LocalError)
             }
+            Storage::AdmissionControlledS3(as3) => as3
+                .put_file_with_options(key, path, options)
+                .await
+                .map_err(PutError::S3Error),
+            Storage::Encrypted(encrypted) => encrypted.put_file(key, path, options).await,
         }
     }
 
     pub async fn put_bytes(&self, key: &str, bytes: Vec<u8>) -> Result<(), PutError> {
+        self.put_bytes_with_options(key, bytes, &PutOptions::default())
+            .await
+    }
+
+    pub async fn put_bytes_with_options(
+        &self,
+        key: &str,
+        bytes: Vec<u8>,
+        options: &PutOptions,
+    ) -> Result<(), PutError> {
+        // The bytes are shared by the attempts rather than copied for each of them
+        let bytes = Bytes::from(bytes);
+        self.with_retries(
+            || PutError::Shed,
+            || self.put_bytes_once(key, bytes.clone(), options),
+        )
+        .await
+    }
+
+    async fn put_bytes_once(
+        &self,
+        key: &str,
+        bytes: Bytes,
+        options: &PutOptions,
+    ) -> Result<(), PutError> {
         match self {
-            Storage::ObjectStore(object_store) => object_store.put_bytes(key, bytes).await,
-            Storage::S3(s3) => s3.put_bytes(key, bytes).await.map_err(PutError::S3Error),
-            Storage::Local(local) => local
-                .put_bytes(key, &bytes)
+            Storage::ObjectStore(object_store) => {
+                Self::check_kms_key_unsupported(options, "object store")?;
+                object_store.put_bytes(key, bytes).await
+            }
+            Storage::S3(s3) => s3
+                .put_bytes_with_options(key, bytes, options)
                 .await
-                .map_err(PutError::LocalError),
-            Storage::AdmissionControlledS3(as3) => {
-                as3.put_bytes(key, bytes).await.map_err(PutError::S3Error)
+                .map_err(PutError::S3Error),
+            Storage::Local(local) => {
+                Self::check_kms_key_unsupported(options, "local storage")?;
+                local
+                    .put_bytes(key, &bytes)
+                    .await
+                    .map_err(PutError::LocalError)
             }
+            Storage::AdmissionControlledS3(as3) => as3
+                .put_bytes_with_options(key, bytes, options)
+                .await
+                .map_err(PutError::S3Error),
+            Storage::Encrypted(encrypted) => encrypted.put_bytes(key, &bytes, options).await,
+        }
+    }
+
+    /// Lists the objects whose keys start with the prefix, in no particular order
+    pub async fn list(&self, prefix: &str) -> Result<Vec<ObjectMetadata>, ListError> {
+        self.with_retries(|| ListError::Shed, || self.list_once(prefix))
+            .await
+    }
+
+    async fn list_once(&self, prefix: &str) -> Result<Vec<ObjectMetadata>, ListError> {
+        match self {
+            Storage::ObjectStore(object_store) => object_store.list(prefix).await,
+            Storage::S3(s3) => s3.list(prefix).await,
+            Storage::Local(local) => local.list(prefix).await,
+            Storage::AdmissionControlledS3(as3) => as3.list(prefix).await,
+            Storage::Encrypted(encrypted) => encrypted.list(prefix).await,
+        }
+    }
+
+    /// Deletes the object with the key. Deleting an object that does not exist succeeds
+    pub async fn delete(&self, key: &str) -> Result<(), DeleteError> {
+        self.with_retries(|| DeleteError::Shed, || self.delete_once(key))
+            .await
+    }
+
+    async fn delete_once(&self, key: &str) -> Result<(), DeleteError> {
+        match self {
+            Storage::ObjectStore(object_store) => object_store.delete(key).await,
+            Storage::S3(s3) => s3.delete(key).await,
+            Storage::Local(local) => local.delete(key).await.map_err(DeleteError::LocalError),
+            Storage::AdmissionControlledS3(as3) => as3.delete(key).await,
+            Storage::Encrypted(encrypted) => encrypted.delete(key).await,
+        }
+    }
+
+    // The object store encrypts with the key of its client and the local storage does not
+    // encrypt at all, so an object cannot be written with the key it asks for without breaking
+    // the isolation the key is asked for
+    fn check_kms_key_unsupported(options: &PutOptions, backend: &str) -> Result<(), PutError> {
+        match &options.kms_key_id {
+            Some(kms_key_id) => Err(PutError::UnsupportedOption(format!(
+                "kms key {} for {}",
+                kms_key_id, backend
+            ))),
+            None => Ok(()),
         }
     }
 }
@@ -221,6 +547,14 @@ pub async fn from_config(config: &StorageConfig) -> Result<Storage, Box<dyn Chro
         StorageConfig::AdmissionControlledS3(_) => Ok(Storage::AdmissionControlledS3(
             admissioncontrolleds3::AdmissionControlledS3Storage::try_from_config(config).await?,
         )),
+        StorageConfig::Encrypted(encrypted_config) => {
+            // The underlying storage is configured like any other, so the call is boxed
+            let storage = Box::pin(from_config(&encrypted_config.storage)).await?;
+            let keyring = encrypted::Keyring::try_from_config(encrypted_config).await?;
+            Ok(Storage::Encrypted(encrypted::EncryptedStorage::new(
+                storage, keyring,
+            )))
+        }
     }
 }
 
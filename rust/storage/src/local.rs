@@ -1,11 +1,12 @@
 use super::config::StorageConfig;
-use super::StorageConfigError;
+use super::{GetError, ListError, ObjectMetadata, StorageConfigError};
 use async_trait::async_trait;
 use chroma_config::Configurable;
 use chroma_error::ChromaError;
+use std::io::{Read, Seek, SeekFrom};
 use std::sync::Arc;
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct LocalStorage {
     root: String,
 }
@@ -18,14 +19,41 @@ impl LocalStorage {
         }
     }
 
-    pub async fn get(&self, key: &str) -> Result<Arc<Vec<u8>>, String> {
+    pub async fn get(&self, key: &str) -> Result<Arc<Vec<u8>>, GetError> {
         let file_path = format!("{}/{}", self.root, key);
         match std::fs::read(file_path) {
             Ok(bytes_u8) => Ok(Arc::new(bytes_u8)),
-            Err(e) => Err::<Arc<Vec<u8>>, String>(e.to_string()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Err(GetError::NoSuchKey(key.to_string()))
+            }
+            Err(e) => Err(GetError::LocalError(e.to_string())),
         }
     }
 
+    pub async fn get_range(
+        &self,
+        key: &str,
+        offset: usize,
+        len: usize,
+    ) -> Result<Arc<Vec<u8>>, GetError> {
+        let file_path = format!("{}/{}", self.root, key);
+        let local_error = |e: std::io::Error| GetError::LocalError(e.to_string());
+        let mut file = match std::fs::File::open(file_path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(GetError::NoSuchKey(key.to_string()))
+            }
+            Err(e) => return Err(local_error(e)),
+        };
+        file.seek(SeekFrom::Start(offset as u64))
+            .map_err(local_error)?;
+        let mut bytes = Vec::new();
+        file.take(len as u64)
+            .read_to_end(&mut bytes)
+            .map_err(local_error)?;
+        Ok(Arc::new(bytes))
+    }
+
     pub async fn put_bytes(&self, key: &str, bytes: &[u8]) -> Result<(), String> {
         let path = format!("{}/{}", self.root, key);
         tracing::debug!("Writing to path: {}", path);
@@ -40,6 +68,49 @@ impl LocalStorage {
         }
     }
 
+    pub async fn delete(&self, key: &str) -> Result<(), String> {
+        let path = format!("{}/{}", self.root, key);
+        match std::fs::remove_file(path) {
+            Ok(_) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err::<(), String>(e.to_string()),
+        }
+    }
+
+    pub async fn list(&self, prefix: &str) -> Result<Vec<ObjectMetadata>, ListError> {
+        let local_error = |e: std::io::Error| ListError::LocalError(e.to_string());
+        let root = std::path::Path::new(&self.root);
+        let mut objects = Vec::new();
+        let mut dirs = vec![root.to_path_buf()];
+        while let Some(dir) = dirs.pop() {
+            let entries = match std::fs::read_dir(&dir) {
+                Ok(entries) => entries,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(local_error(e)),
+            };
+            for entry in entries {
+                let entry = entry.map_err(local_error)?;
+                let metadata = entry.metadata().map_err(local_error)?;
+                if metadata.is_dir() {
+                    dirs.push(entry.path());
+                    continue;
+                }
+                let path = entry.path();
+                let key = match path.strip_prefix(root) {
+                    Ok(key) => key.to_string_lossy().to_string(),
+                    Err(_) => continue,
+                };
+                if key.starts_with(prefix) {
+                    objects.push(ObjectMetadata {
+                        key,
+                        last_modified: metadata.modified().map_err(local_error)?,
+                    });
+                }
+            }
+        }
+        Ok(objects)
+    }
+
     pub async fn put_file(&self, key: &str, path: &str) -> Result<(), String> {
         let file = std::fs::read(path);
         match file {
@@ -61,3 +132,38 @@ impl Configurable<StorageConfig> for LocalStorage {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::LocalStorage;
+    use crate::{GetError, PutError, PutOptions, Storage};
+
+    #[tokio::test]
+    async fn test_get_range() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let storage = LocalStorage::new(tmp_dir.path().to_str().unwrap());
+        storage.put_bytes("key", b"0123456789").await.unwrap();
+        assert_eq!(*storage.get_range("key", 2, 3).await.unwrap(), b"234");
+        // The range is cut short at the end of the object
+        assert_eq!(*storage.get_range("key", 8, 5).await.unwrap(), b"89");
+        assert!(storage.get_range("key", 12, 5).await.unwrap().is_empty());
+        assert!(matches!(
+            storage.get_range("missing", 0, 1).await,
+            Err(GetError::NoSuchKey(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_put_with_kms_key_is_rejected() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let storage = Storage::Local(LocalStorage::new(tmp_dir.path().to_str().unwrap()));
+        let options = PutOptions {
+            kms_key_id: Some("tenant-key".to_string()),
+        };
+        let result = storage
+            .put_bytes_with_options("key", b"0123456789".to_vec(), &options)
+            .await;
+        assert!(matches!(result, Err(PutError::UnsupportedOption(_))));
+        assert!(storage.get("key").await.is_err());
+    }
+}
@@ -1,13 +1,18 @@
 use std::ops::Range;
 use std::sync::Arc;
 
+use bytes::Bytes;
 use chroma_error::ChromaError;
+use futures::TryStreamExt;
 use object_store::path::Path;
-use object_store::{GetOptions, GetRange, ObjectStore as ObjectStoreTrait, PutOptions};
+use object_store::{
+    GetOptions, GetRange, ObjectStore as ObjectStoreTrait, PutOptions, RetryConfig,
+};
 use tokio::io::{AsyncReadExt, AsyncSeekExt};
 use tokio::sync::Mutex;
 
-use super::{GetError, PutError, StorageConfigError};
+use super::retry::RetryPolicy;
+use super::{DeleteError, GetError, ListError, ObjectMetadata, PutError, StorageConfigError};
 
 #[derive(Clone)]
 pub struct ObjectStore {
@@ -15,13 +20,14 @@ pub struct ObjectStore {
     upload_part_size_bytes: u64,
     #[allow(dead_code)]
     download_part_size_bytes: u64,
+    pub(super) retry_policy: RetryPolicy,
 }
 
 impl ObjectStore {
     pub async fn try_from_config(
         config: &super::config::ObjectStoreConfig,
     ) -> Result<Self, Box<dyn ChromaError>> {
-        match &config.bucket.r#type {
+        let object_store: Arc<dyn ObjectStoreTrait> = match &config.bucket.r#type {
             super::config::ObjectStoreType::Minio => {
                 let object_store = object_store::aws::AmazonS3Builder::new()
                     .with_region("us-east-1")
@@ -29,42 +35,79 @@ impl ObjectStore {
                     .with_bucket_name(&config.bucket.name)
                     .with_access_key_id("minio")
                     .with_secret_access_key("minio123")
+                    .with_retry(Self::client_retry_config())
                     .build()
-                    .map_err(|err| {
-                        tracing::error! {"Failed to create object store: {:?}", err};
-                        Box::new(StorageConfigError::InvalidStorageConfig) as _
-                    })?;
-                let object_store = object_store::limit::LimitStore::new(
+                    .map_err(Self::build_error)?;
+                Arc::new(object_store::limit::LimitStore::new(
                     object_store,
                     config.max_concurrent_requests,
-                );
-                Ok(ObjectStore {
-                    object_store: Arc::new(object_store),
-                    upload_part_size_bytes: config.upload_part_size_bytes,
-                    download_part_size_bytes: config.download_part_size_bytes,
-                })
+                ))
             }
             super::config::ObjectStoreType::S3 => {
                 let object_store = object_store::aws::AmazonS3Builder::from_env()
                     .with_bucket_name(&config.bucket.name)
+                    .with_retry(Self::client_retry_config())
                     .build()
-                    .map_err(|err| {
-                        tracing::error! {"Failed to create object store: {:?}", err};
-                        Box::new(StorageConfigError::InvalidStorageConfig) as _
-                    })?;
-                let object_store = object_store::limit::LimitStore::new(
+                    .map_err(Self::build_error)?;
+                Arc::new(object_store::limit::LimitStore::new(
                     object_store,
                     config.max_concurrent_requests,
-                );
-                Ok(ObjectStore {
-                    object_store: Arc::new(object_store),
-                    upload_part_size_bytes: config.upload_part_size_bytes,
-                    download_part_size_bytes: config.download_part_size_bytes,
-                })
+                ))
             }
+            // Without a service account, the builder falls back to the credentials of the
+            // metadata server, which are those of the workload identity on GKE
+            super::config::ObjectStoreType::Gcs => {
+                let mut builder = object_store::gcp::GoogleCloudStorageBuilder::from_env()
+                    .with_bucket_name(&config.bucket.name)
+                    .with_retry(Self::client_retry_config());
+                if let Some(service_account_path) = &config.bucket.service_account_path {
+                    builder = builder.with_service_account_path(service_account_path);
+                }
+                let object_store = builder.build().map_err(Self::build_error)?;
+                Arc::new(object_store::limit::LimitStore::new(
+                    object_store,
+                    config.max_concurrent_requests,
+                ))
+            }
+            // The builder reads the SAS token from the environment, and falls back to the
+            // managed identity of the host without one. Multipart uploads are staged as the
+            // blocks of a block blob, and committed with its block list
+            super::config::ObjectStoreType::Azure => {
+                let mut builder = object_store::azure::MicrosoftAzureBuilder::from_env()
+                    .with_container_name(&config.bucket.name)
+                    .with_retry(Self::client_retry_config());
+                if let Some(account_name) = &config.bucket.account_name {
+                    builder = builder.with_account(account_name);
+                }
+                let object_store = builder.build().map_err(Self::build_error)?;
+                Arc::new(object_store::limit::LimitStore::new(
+                    object_store,
+                    config.max_concurrent_requests,
+                ))
+            }
+        };
+        Ok(ObjectStore {
+            object_store,
+            upload_part_size_bytes: config.upload_part_size_bytes,
+            download_part_size_bytes: config.download_part_size_bytes,
+            retry_policy: RetryPolicy::new(&config.retry),
+        })
+    }
+
+    // The storage retries the requests with its own policy, so the client does not retry
+    // them as well
+    fn client_retry_config() -> RetryConfig {
+        RetryConfig {
+            max_retries: 0,
+            ..Default::default()
         }
     }
 
+    fn build_error(err: object_store::Error) -> Box<dyn ChromaError> {
+        tracing::error! {"Failed to create object store: {:?}", err};
+        Box::new(StorageConfigError::InvalidStorageConfig)
+    }
+
     pub async fn get(&self, key: &str) -> Result<Arc<Vec<u8>>, GetError> {
         Ok(self
             .object_store
@@ -76,6 +119,29 @@ impl ObjectStore {
             .into())
     }
 
+    pub async fn get_range(
+        &self,
+        key: &str,
+        offset: usize,
+        len: usize,
+    ) -> Result<Arc<Vec<u8>>, GetError> {
+        if len == 0 {
+            return Ok(Arc::new(Vec::new()));
+        }
+        let options = GetOptions {
+            range: Some(GetRange::Bounded(offset..offset + len)),
+            ..Default::default()
+        };
+        Ok(self
+            .object_store
+            .get_opts(&Path::from(key), options)
+            .await?
+            .bytes()
+            .await?
+            .to_vec()
+            .into())
+    }
+
     pub async fn get_parallel(&self, key: &str) -> Result<Arc<Vec<u8>>, GetError> {
         let meta = self.object_store.head(&Path::from(key)).await?;
         let file_size = meta.size;
@@ -157,12 +223,56 @@ impl ObjectStore {
         Ok(())
     }
 
-    pub async fn put_bytes(&self, key: &str, bytes: Vec<u8>) -> Result<(), PutError> {
+    pub async fn put_bytes(&self, key: &str, bytes: impl Into<Bytes>) -> Result<(), PutError> {
         self.object_store
-            .put_opts(&Path::from(key), bytes.into(), PutOptions::default())
+            .put_opts(&Path::from(key), bytes.into().into(), PutOptions::default())
             .await?;
         Ok(())
     }
+
+    pub async fn list(&self, prefix: &str) -> Result<Vec<ObjectMetadata>, ListError> {
+        // The object store lists by whole path segments, so a prefix that ends within a
+        // segment is matched against the keys of its parent
+        let parent = prefix.rsplit_once('/').map(|(parent, _)| parent);
+        let objects = self
+            .object_store
+            .list(parent.map(Path::from).as_ref())
+            .try_filter(|meta| futures::future::ready(meta.location.as_ref().starts_with(prefix)))
+            .map_ok(|meta| ObjectMetadata {
+                key: meta.location.to_string(),
+                last_modified: meta.last_modified.into(),
+            })
+            .try_collect::<Vec<_>>()
+            .await?;
+        Ok(objects)
+    }
+
+    pub async fn delete(&self, key: &str) -> Result<(), DeleteError> {
+        match self.object_store.delete(&Path::from(key)).await {
+            Ok(_) | Err(object_store::Error::NotFound { .. }) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+// Whether the request failed for a transient reason, e.g. the store answers with a 5xx or the
+// request times out, so that it may succeed if it is retried. The client keeps the error of
+// its http request as a source of the error it returns
+pub(super) fn is_transient(err: &object_store::Error) -> bool {
+    let mut source = std::error::Error::source(err);
+    while let Some(err) = source {
+        if let Some(err) = err.downcast_ref::<reqwest::Error>() {
+            return err.is_timeout()
+                || err.is_connect()
+                || err.is_request()
+                || err.is_body()
+                || err.status().is_some_and(|status| {
+                    status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+                });
+        }
+        source = err.source();
+    }
+    false
 }
 
 #[cfg(test)]
@@ -180,6 +290,7 @@ mod tests {
             object_store: Arc::new(object_store::memory::InMemory::new()),
             upload_part_size_bytes: 1024 * 1024 * 5,
             download_part_size_bytes: 1024 * 1024 * 5,
+            retry_policy: RetryPolicy::default(),
         }
     }
 
@@ -193,6 +304,19 @@ mod tests {
         assert_eq!(result, bytes.into());
     }
 
+    #[tokio::test]
+    async fn get_range() {
+        let object_store = get_object_store();
+        let key = "test";
+        object_store
+            .put_bytes(key, b"0123456789".to_vec())
+            .await
+            .unwrap();
+        let result = object_store.get_range(key, 2, 3).await.unwrap();
+        assert_eq!(*result, b"234");
+        assert!(object_store.get_range("missing", 0, 1).await.is_err());
+    }
+
     #[tokio::test]
     async fn get_parallel() {
         let object_store = get_object_store();
@@ -220,4 +344,49 @@ mod tests {
         assert_eq!(result, bytes.into());
         std::fs::remove_file(path).unwrap();
     }
+
+    #[tokio::test]
+    async fn list_delete() {
+        let object_store = get_object_store();
+        for key in ["block/1", "block/2", "sparse_index/1"] {
+            object_store
+                .put_bytes(key, b"test data".to_vec())
+                .await
+                .unwrap();
+        }
+        let mut keys = object_store
+            .list("block/")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|object| object.key)
+            .collect::<Vec<_>>();
+        keys.sort();
+        assert_eq!(keys, vec!["block/1", "block/2"]);
+
+        object_store.delete("block/1").await.unwrap();
+        object_store.delete("block/1").await.unwrap();
+        let keys = object_store
+            .list("block/")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|object| object.key)
+            .collect::<Vec<_>>();
+        assert_eq!(keys, vec!["block/2"]);
+        assert!(object_store.get("block/1").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn put_with_kms_key_is_rejected() {
+        let storage = crate::Storage::ObjectStore(get_object_store());
+        let options = crate::PutOptions {
+            kms_key_id: Some("tenant-key".to_string()),
+        };
+        let result = storage
+            .put_bytes_with_options("test", b"test data".to_vec(), &options)
+            .await;
+        assert!(matches!(result, Err(PutError::UnsupportedOption(_))));
+        assert!(storage.get("test").await.is_err());
+    }
 }
@@ -0,0 +1,375 @@
+use chroma_error::ChromaError;
+use parking_lot::Mutex;
+use serde::Deserialize;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+
+/// The retries of the requests to a storage backend
+/// # Fields
+/// - max_attempts: The number of times that a request is sent before its error is returned, if
+///   it fails for a transient reason, e.g. a 5xx response or a timeout.
+/// - initial_backoff_ms: The backoff before the first retry, which doubles with each further
+///   retry up to `max_backoff_ms`. The backoffs are jittered so that concurrent retries are
+///   spread out.
+/// - circuit_breaker: Sheds the requests while the backend is unhealthy, if set.
+/// - rate_limit: Bounds the rate of the requests to the backend, if set.
+/// - max_concurrent_requests: Bounds the requests to the backend that are in flight at once,
+///   if set. A request holds its slot while it is sent, and not while it backs off.
+#[derive(Deserialize, Debug, Clone)]
+pub struct RetryConfig {
+    #[serde(default = "RetryConfig::default_max_attempts")]
+    pub max_attempts: usize,
+    #[serde(default = "RetryConfig::default_initial_backoff_ms")]
+    pub initial_backoff_ms: u64,
+    #[serde(default = "RetryConfig::default_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+    #[serde(default)]
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    #[serde(default)]
+    pub rate_limit: Option<RateLimitConfig>,
+    #[serde(default)]
+    pub max_concurrent_requests: Option<usize>,
+}
+
+impl RetryConfig {
+    fn default_max_attempts() -> usize {
+        3
+    }
+
+    fn default_initial_backoff_ms() -> u64 {
+        100
+    }
+
+    fn default_max_backoff_ms() -> u64 {
+        5000
+    }
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: Self::default_max_attempts(),
+            initial_backoff_ms: Self::default_initial_backoff_ms(),
+            max_backoff_ms: Self::default_max_backoff_ms(),
+            circuit_breaker: None,
+            rate_limit: None,
+            max_concurrent_requests: None,
+        }
+    }
+}
+
+/// The circuit breaker opens after `failure_threshold` requests in a row fail for a transient
+/// reason, and fails the requests right away for `cooldown_ms`. The requests after the
+/// cooldown are sent again, and the breaker closes on the first of them that succeeds
+#[derive(Deserialize, Debug, Clone)]
+pub struct CircuitBreakerConfig {
+    pub failure_threshold: usize,
+    pub cooldown_ms: u64,
+}
+
+/// A token bucket that admits `requests_per_second` requests on average, and up to `burst`
+/// requests at once after it is idle
+#[derive(Deserialize, Debug, Clone)]
+pub struct RateLimitConfig {
+    pub requests_per_second: f64,
+    pub burst: usize,
+}
+
+#[derive(Debug)]
+struct CircuitBreaker {
+    failure_threshold: usize,
+    cooldown: Duration,
+    consecutive_failures: usize,
+    open_until: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    fn allows(&self, now: Instant) -> bool {
+        self.open_until.is_none_or(|open_until| now >= open_until)
+    }
+
+    fn record_success(&mut self) {
+        self.consecutive_failures = 0;
+        self.open_until = None;
+    }
+
+    // A failure after the cooldown opens the breaker again right away, since the failures
+    // in a row are only reset by a success
+    fn record_failure(&mut self, now: Instant) {
+        self.consecutive_failures += 1;
+        if self.consecutive_failures >= self.failure_threshold {
+            self.open_until = Some(now + self.cooldown);
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    requests_per_second: f64,
+    burst: f64,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    // Takes a token, and returns how long to wait for it if the bucket is empty
+    fn take(&mut self, now: Instant) -> Duration {
+        let elapsed = now.saturating_duration_since(self.refilled_at);
+        self.tokens =
+            (self.tokens + elapsed.as_secs_f64() * self.requests_per_second).min(self.burst);
+        self.refilled_at = now;
+        self.tokens -= 1.0;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.requests_per_second)
+        }
+    }
+}
+
+/// Retries the requests to a storage backend that fail for a transient reason, with the
+/// backoff of the config, behind its circuit breaker, rate limit and concurrency limit. The
+/// clones of a policy share the state of the breaker, the bucket and the limit
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    max_attempts: usize,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    circuit_breaker: Option<Arc<Mutex<CircuitBreaker>>>,
+    rate_limit: Option<Arc<Mutex<TokenBucket>>>,
+    concurrency_limit: Option<Arc<Semaphore>>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(&RetryConfig::default())
+    }
+}
+
+impl RetryPolicy {
+    pub fn new(config: &RetryConfig) -> Self {
+        Self {
+            max_attempts: config.max_attempts.max(1),
+            initial_backoff: Duration::from_millis(config.initial_backoff_ms),
+            max_backoff: Duration::from_millis(config.max_backoff_ms),
+            circuit_breaker: config.circuit_breaker.as_ref().map(|config| {
+                Arc::new(Mutex::new(CircuitBreaker {
+                    failure_threshold: config.failure_threshold.max(1),
+                    cooldown: Duration::from_millis(config.cooldown_ms),
+                    consecutive_failures: 0,
+                    open_until: None,
+                }))
+            }),
+            rate_limit: config
+                .rate_limit
+                .as_ref()
+                .filter(|config| config.requests_per_second > 0.0)
+                .map(|config| {
+                    let burst = config.burst.max(1) as f64;
+                    Arc::new(Mutex::new(TokenBucket {
+                        requests_per_second: config.requests_per_second,
+                        burst,
+                        tokens: burst,
+                        refilled_at: Instant::now(),
+                    }))
+                }),
+            concurrency_limit: config
+                .max_concurrent_requests
+                .map(|limit| Arc::new(Semaphore::new(limit.max(1)))),
+        }
+    }
+
+    // The backoff before the retry that follows the given number of failed attempts, jittered
+    // within [exponential / 2, exponential]
+    fn backoff(&self, failed_attempts: usize) -> Duration {
+        let exponential = u32::try_from(failed_attempts.saturating_sub(1))
+            .ok()
+            .and_then(|exponent| 2u32.checked_pow(exponent))
+            .map(|factor| self.initial_backoff.saturating_mul(factor))
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff);
+        let half = exponential / 2;
+        half + half.mul_f64(rand::random::<f64>())
+    }
+
+    /// Sends the request until it succeeds, fails for a reason that is not transient, or
+    /// runs out of attempts. While the circuit breaker is open, the request fails with the
+    /// error of `shed` without being sent
+    pub async fn run<T, E, F, Fut>(&self, shed: impl Fn() -> E, request: F) -> Result<T, E>
+    where
+        E: ChromaError,
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut failed_attempts = 0;
+        loop {
+            if let Some(circuit_breaker) = &self.circuit_breaker {
+                if !circuit_breaker.lock().allows(Instant::now()) {
+                    return Err(shed());
+                }
+            }
+            if let Some(rate_limit) = &self.rate_limit {
+                let wait = rate_limit.lock().take(Instant::now());
+                if !wait.is_zero() {
+                    tokio::time::sleep(wait).await;
+                }
+            }
+            let result = {
+                let _permit = match &self.concurrency_limit {
+                    Some(concurrency_limit) => Some(
+                        concurrency_limit
+                            .acquire()
+                            .await
+                            .expect("The concurrency limit is never closed"),
+                    ),
+                    None => None,
+                };
+                request().await
+            };
+            match result {
+                Err(e) if e.code().is_transient() => {
+                    if let Some(circuit_breaker) = &self.circuit_breaker {
+                        circuit_breaker.lock().record_failure(Instant::now());
+                    }
+                    failed_attempts += 1;
+                    if failed_attempts >= self.max_attempts {
+                        return Err(e);
+                    }
+                    let backoff = self.backoff(failed_attempts);
+                    tracing::warn!(
+                        "Storage request failed with {}, retrying in {:?}",
+                        e,
+                        backoff
+                    );
+                    tokio::time::sleep(backoff).await;
+                }
+                // The backend answered, even if the request is not served
+                result => {
+                    if let Some(circuit_breaker) = &self.circuit_breaker {
+                        circuit_breaker.lock().record_success();
+                    }
+                    return result;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CircuitBreakerConfig, RetryConfig, RetryPolicy};
+    use chroma_error::{ChromaError, ErrorCodes};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use thiserror::Error;
+
+    #[derive(Debug, Error)]
+    enum TestError {
+        #[error("unavailable")]
+        Unavailable,
+        #[error("not found")]
+        NotFound,
+        #[error("shed")]
+        Shed,
+    }
+
+    impl ChromaError for TestError {
+        fn code(&self) -> ErrorCodes {
+            match self {
+                TestError::Unavailable | TestError::Shed => ErrorCodes::Unavailable,
+                TestError::NotFound => ErrorCodes::NotFound,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_policy() {
+        let policy = RetryPolicy::new(&RetryConfig {
+            max_attempts: 3,
+            initial_backoff_ms: 1,
+            max_backoff_ms: 1,
+            circuit_breaker: Some(CircuitBreakerConfig {
+                failure_threshold: 4,
+                cooldown_ms: 60_000,
+            }),
+            rate_limit: None,
+            max_concurrent_requests: None,
+        });
+        let attempts = AtomicUsize::new(0);
+
+        // A transient error is retried until the request succeeds
+        let result = policy
+            .run(
+                || TestError::Shed,
+                || async {
+                    match attempts.fetch_add(1, Ordering::Relaxed) {
+                        0 => Err(TestError::Unavailable),
+                        _ => Ok(()),
+                    }
+                },
+            )
+            .await;
+        assert!(result.is_ok());
+        assert_eq!(attempts.swap(0, Ordering::Relaxed), 2);
+
+        // Other errors are returned right away
+        let result = policy
+            .run(
+                || TestError::Shed,
+                || async {
+                    attempts.fetch_add(1, Ordering::Relaxed);
+                    Err::<(), _>(TestError::NotFound)
+                },
+            )
+            .await;
+        assert!(matches!(result, Err(TestError::NotFound)));
+        assert_eq!(attempts.swap(0, Ordering::Relaxed), 1);
+
+        // The breaker opens after four failures in a row across the requests, and sheds the
+        // requests that follow
+        for _ in 0..2 {
+            let result = policy
+                .run(
+                    || TestError::Shed,
+                    || async {
+                        attempts.fetch_add(1, Ordering::Relaxed);
+                        Err::<(), _>(TestError::Unavailable)
+                    },
+                )
+                .await;
+            assert!(result.is_err());
+        }
+        assert_eq!(attempts.swap(0, Ordering::Relaxed), 4);
+        let result = policy
+            .run(|| TestError::Shed, || async { Ok::<_, TestError>(()) })
+            .await;
+        assert!(matches!(result, Err(TestError::Shed)));
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_limit() {
+        let policy = RetryPolicy::new(&RetryConfig {
+            max_concurrent_requests: Some(2),
+            ..Default::default()
+        });
+        let in_flight = AtomicUsize::new(0);
+        let max_in_flight = AtomicUsize::new(0);
+        let requests = (0..8).map(|_| {
+            policy.run(
+                || TestError::Shed,
+                || async {
+                    let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_in_flight.fetch_max(current, Ordering::SeqCst);
+                    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                    Ok::<_, TestError>(())
+                },
+            )
+        });
+        for result in futures::future::join_all(requests).await {
+            assert!(result.is_ok());
+        }
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 2);
+    }
+}
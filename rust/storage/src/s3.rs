@@ -8,21 +8,28 @@
 // Once we move to our own implementation of hnswlib we can support
 // streaming from s3.
 
-use super::config::StorageConfig;
+use super::config::{S3StorageConfig, StorageConfig};
 use super::stream::ByteStreamItem;
 use super::stream::S3ByteStream;
 use super::StorageConfigError;
+use crate::retry::RetryPolicy;
+use crate::DeleteError;
 use crate::GetError;
+use crate::ListError;
+use crate::ObjectMetadata;
+use crate::PutOptions;
 use async_trait::async_trait;
 use aws_config::retry::RetryConfig;
 use aws_config::timeout::TimeoutConfigBuilder;
 use aws_sdk_s3;
+use aws_sdk_s3::config::http::HttpResponse;
 use aws_sdk_s3::error::SdkError;
 use aws_sdk_s3::operation::create_bucket::CreateBucketError;
 use aws_sdk_s3::operation::get_object::GetObjectOutput;
 use aws_sdk_s3::primitives::ByteStream;
 use aws_sdk_s3::types::CompletedMultipartUpload;
 use aws_sdk_s3::types::CompletedPart;
+use aws_sdk_s3::types::ServerSideEncryption;
 use aws_smithy_types::byte_stream::Length;
 use bytes::Bytes;
 use chroma_config::Configurable;
@@ -33,10 +40,12 @@ use futures::stream;
 use futures::FutureExt;
 use futures::Stream;
 use futures::StreamExt;
+use futures::TryStreamExt;
 use std::clone::Clone;
 use std::ops::Range;
 use std::sync::Arc;
 use std::time::Duration;
+use std::time::SystemTime;
 use thiserror::Error;
 use tokio::io::AsyncReadExt;
 use tracing::Instrument;
@@ -48,6 +57,8 @@ pub struct S3Storage {
     pub(super) client: aws_sdk_s3::Client,
     pub(super) upload_part_size_bytes: usize,
     pub(super) download_part_size_bytes: usize,
+    pub(super) upload_concurrency: usize,
+    pub(super) retry_policy: RetryPolicy,
 }
 
 #[derive(Error, Debug)]
@@ -56,11 +67,16 @@ pub enum S3PutError {
     S3PutError(String),
     #[error("S3 Dispatch failure error")]
     S3DispatchFailure,
+    #[error("S3 unavailable: {0}")]
+    Unavailable(String),
 }
 
 impl ChromaError for S3PutError {
     fn code(&self) -> ErrorCodes {
-        ErrorCodes::Internal
+        match self {
+            S3PutError::Unavailable(_) => ErrorCodes::Unavailable,
+            _ => ErrorCodes::Internal,
+        }
     }
 }
 
@@ -72,26 +88,54 @@ pub enum S3GetError {
     NoSuchKey(String),
     #[error("ByteStream error: {0}")]
     ByteStreamError(String),
+    #[error("S3 unavailable: {0}")]
+    Unavailable(String),
 }
 
 impl ChromaError for S3GetError {
     fn code(&self) -> ErrorCodes {
-        ErrorCodes::Internal
+        match self {
+            S3GetError::Unavailable(_) => ErrorCodes::Unavailable,
+            _ => ErrorCodes::Internal,
+        }
+    }
+}
+
+// Whether the request failed for a transient reason, e.g. S3 throttles it with a 503 SlowDown
+// or the connection fails, so that it may succeed if it is retried
+fn is_transient<E>(err: &SdkError<E, HttpResponse>) -> bool {
+    match err {
+        SdkError::TimeoutError(_) | SdkError::DispatchFailure(_) | SdkError::ResponseError(_) => {
+            true
+        }
+        SdkError::ServiceError(_) => err
+            .raw_response()
+            .is_some_and(|response| response.status().as_u16() >= 500),
+        _ => false,
+    }
+}
+
+// The error of a request of an upload, which is unavailable if it may succeed when retried
+fn put_error<E>(err: SdkError<E, HttpResponse>) -> S3PutError
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    if is_transient(&err) {
+        S3PutError::Unavailable(err.to_string())
+    } else {
+        S3PutError::S3PutError(err.to_string())
     }
 }
 
 impl S3Storage {
-    fn new(
-        bucket: &str,
-        client: aws_sdk_s3::Client,
-        upload_part_size_bytes: usize,
-        download_part_size_bytes: usize,
-    ) -> S3Storage {
+    fn new(client: aws_sdk_s3::Client, config: &S3StorageConfig) -> S3Storage {
         S3Storage {
-            bucket: bucket.to_string(),
+            bucket: config.bucket.clone(),
             client,
-            upload_part_size_bytes,
-            download_part_size_bytes,
+            upload_part_size_bytes: config.upload_part_size_bytes,
+            download_part_size_bytes: config.download_part_size_bytes,
+            upload_concurrency: config.upload_concurrency.max(1),
+            retry_policy: RetryPolicy::new(&config.retry),
         }
     }
 
@@ -151,6 +195,9 @@ impl S3Storage {
             }
             Err(e) => {
                 tracing::error!("error: {}", e);
+                if is_transient(&e) {
+                    return Err(S3GetError::Unavailable(e.to_string()));
+                }
                 match e {
                     SdkError::ServiceError(err) => {
                         let inner = err.into_err();
@@ -194,6 +241,9 @@ impl S3Storage {
                     return Err(S3GetError::S3GetError("No content length".to_string()));
                 }
             },
+            Err(e) if is_transient(&e) => {
+                return Err(S3GetError::Unavailable(e.to_string()));
+            }
             Err(e) => {
                 return Err(S3GetError::S3GetError(e.to_string()));
             }
@@ -230,6 +280,9 @@ impl S3Storage {
             Ok(output) => Ok(output),
             Err(e) => {
                 tracing::error!("Error fetching range: {:?}", e);
+                if is_transient(&e) {
+                    return Err(S3GetError::Unavailable(e.to_string()));
+                }
                 match e {
                     SdkError::ServiceError(err) => {
                         let inner = err.into_err();
@@ -295,6 +348,29 @@ impl S3Storage {
         Ok(Arc::new(output_buffer))
     }
 
+    /// Reads the bytes of the object from the offset, up to the length, in a single range
+    /// request. Fewer bytes are returned if the object ends before
+    pub async fn get_range(
+        &self,
+        key: &str,
+        offset: usize,
+        len: usize,
+    ) -> Result<Arc<Vec<u8>>, S3GetError> {
+        if len == 0 {
+            return Ok(Arc::new(Vec::new()));
+        }
+        let range_str = format!("bytes={}-{}", offset, offset + len - 1);
+        let output = self.fetch_range(key.to_string(), range_str).await?;
+        let mut bytes = Vec::with_capacity(len);
+        output
+            .body
+            .into_async_read()
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(|e| S3GetError::ByteStreamError(e.to_string()))?;
+        Ok(Arc::new(bytes))
+    }
+
     pub async fn get(&self, key: &str) -> Result<Arc<Vec<u8>>, S3GetError> {
         let mut stream = self
             .get_stream(key)
@@ -322,6 +398,12 @@ impl S3Storage {
                                     return Err(S3GetError::NoSuchKey(e));
                                 }
                                 GetError::LocalError(_) => unreachable!(),
+                                GetError::Shed => {
+                                    return Err(S3GetError::Unavailable(err.to_string()));
+                                }
+                                GetError::EncryptionError(e) => {
+                                    return Err(S3GetError::S3GetError(e.to_string()));
+                                }
                             }
                         }
                     }
@@ -339,10 +421,20 @@ impl S3Storage {
         }
     }
 
-    pub async fn put_bytes(&self, key: &str, bytes: Vec<u8>) -> Result<(), S3PutError> {
-        let bytes = Arc::new(Bytes::from(bytes));
+    pub async fn put_bytes(&self, key: &str, bytes: impl Into<Bytes>) -> Result<(), S3PutError> {
+        self.put_bytes_with_options(key, bytes, &PutOptions::default())
+            .await
+    }
 
-        self.put_object(key, bytes.len(), move |range| {
+    pub async fn put_bytes_with_options(
+        &self,
+        key: &str,
+        bytes: impl Into<Bytes>,
+        options: &PutOptions,
+    ) -> Result<(), S3PutError> {
+        let bytes = Arc::new(bytes.into());
+
+        self.put_object(key, bytes.len(), options, move |range| {
             let bytes = bytes.clone();
             async move { Ok(ByteStream::from(bytes.slice(range))) }.boxed()
         })
@@ -350,6 +442,16 @@ impl S3Storage {
     }
 
     pub async fn put_file(&self, key: &str, path: &str) -> Result<(), S3PutError> {
+        self.put_file_with_options(key, path, &PutOptions::default())
+            .await
+    }
+
+    pub async fn put_file_with_options(
+        &self,
+        key: &str,
+        path: &str,
+        options: &PutOptions,
+    ) -> Result<(), S3PutError> {
         let file_size = tokio::fs::metadata(path)
             .await
             .map_err(|err| S3PutError::S3PutError(err.to_string()))?
@@ -357,7 +459,7 @@ impl S3Storage {
 
         let path = path.to_string();
 
-        self.put_object(key, file_size as usize, move |range| {
+        self.put_object(key, file_size as usize, options, move |range| {
             let path = path.clone();
 
             async move {
@@ -374,28 +476,82 @@ impl S3Storage {
         .await
     }
 
+    pub async fn list(&self, prefix: &str) -> Result<Vec<ObjectMetadata>, ListError> {
+        let mut objects = Vec::new();
+        let mut pages = self
+            .client
+            .list_objects_v2()
+            .bucket(&self.bucket)
+            .prefix(prefix)
+            .into_paginator()
+            .send();
+        while let Some(page) = pages.next().await {
+            let page = page.map_err(|err| match is_transient(&err) {
+                true => ListError::Unavailable(err.to_string()),
+                false => ListError::S3Error(err.to_string()),
+            })?;
+            for object in page.contents() {
+                let (Some(key), Some(last_modified)) = (object.key(), object.last_modified())
+                else {
+                    continue;
+                };
+                objects.push(ObjectMetadata {
+                    key: key.to_string(),
+                    last_modified: SystemTime::try_from(*last_modified)
+                        .map_err(|err| ListError::S3Error(err.to_string()))?,
+                });
+            }
+        }
+        Ok(objects)
+    }
+
+    pub async fn delete(&self, key: &str) -> Result<(), DeleteError> {
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|err| match is_transient(&err) {
+                true => DeleteError::Unavailable(err.to_string()),
+                false => DeleteError::S3Error(err.to_string()),
+            })?;
+        Ok(())
+    }
+
     async fn put_object(
         &self,
         key: &str,
         total_size_bytes: usize,
+        options: &PutOptions,
         create_bytestream_fn: impl Fn(
             Range<usize>,
         ) -> BoxFuture<'static, Result<ByteStream, S3PutError>>,
     ) -> Result<(), S3PutError> {
         if total_size_bytes < self.upload_part_size_bytes {
             return self
-                .oneshot_upload(key, total_size_bytes, create_bytestream_fn)
+                .oneshot_upload(key, total_size_bytes, options, create_bytestream_fn)
                 .await;
         }
 
-        self.multipart_upload(key, total_size_bytes, create_bytestream_fn)
+        self.multipart_upload(key, total_size_bytes, options, create_bytestream_fn)
             .await
     }
 
+    // The objects are encrypted with the KMS key of the options if there is one,
+    // otherwise the default encryption of the bucket applies
+    fn server_side_encryption(options: &PutOptions) -> Option<ServerSideEncryption> {
+        options
+            .kms_key_id
+            .as_ref()
+            .map(|_| ServerSideEncryption::AwsKms)
+    }
+
     async fn oneshot_upload(
         &self,
         key: &str,
         total_size_bytes: usize,
+        options: &PutOptions,
         create_bytestream_fn: impl Fn(
             Range<usize>,
         ) -> BoxFuture<'static, Result<ByteStream, S3PutError>>,
@@ -404,10 +560,12 @@ impl S3Storage {
             .put_object()
             .bucket(&self.bucket)
             .key(key)
+            .set_server_side_encryption(Self::server_side_encryption(options))
+            .set_ssekms_key_id(options.kms_key_id.clone())
             .body(create_bytestream_fn(0..total_size_bytes).await?)
             .send()
             .await
-            .map_err(|err| S3PutError::S3PutError(err.to_string()))?;
+            .map_err(put_error)?;
 
         Ok(())
     }
@@ -416,6 +574,7 @@ impl S3Storage {
         &self,
         key: &str,
         total_size_bytes: usize,
+        options: &PutOptions,
         create_bytestream_fn: impl Fn(
             Range<usize>,
         ) -> BoxFuture<'static, Result<ByteStream, S3PutError>>,
@@ -432,9 +591,11 @@ impl S3Storage {
             .create_multipart_upload()
             .bucket(&self.bucket)
             .key(key)
+            .set_server_side_encryption(Self::server_side_encryption(options))
+            .set_ssekms_key_id(options.kms_key_id.clone())
             .send()
             .await
-            .map_err(|err| S3PutError::S3PutError(err.to_string()))?
+            .map_err(put_error)?
             .upload_id
         {
             Some(upload_id) => upload_id,
@@ -445,54 +606,98 @@ impl S3Storage {
             }
         };
 
-        let mut upload_parts = Vec::new();
-        for part_index in 0..part_count {
-            let this_part = if part_count - 1 == part_index {
+        let parts = (0..part_count).map(|part_index| {
+            let length = if part_count - 1 == part_index {
                 size_of_last_part
             } else {
                 self.upload_part_size_bytes
             };
             let part_number = part_index as i32 + 1; // Part numbers start at 1
             let offset = part_index * self.upload_part_size_bytes;
-            let length = this_part;
-
-            let stream = create_bytestream_fn(offset..(offset + length)).await?;
+            (part_number, offset..(offset + length))
+        });
+        // The parts are completed in the order of their numbers, which buffered preserves
+        let uploaded = stream::iter(parts)
+            .map(|(part_number, range)| {
+                self.upload_part(key, &upload_id, part_number, range, &create_bytestream_fn)
+            })
+            .buffered(self.upload_concurrency)
+            .try_collect::<Vec<_>>()
+            .await;
 
-            let upload_part_res = self
+        let completed = match uploaded {
+            Ok(upload_parts) => self
                 .client
-                .upload_part()
-                .key(key)
+                .complete_multipart_upload()
                 .bucket(&self.bucket)
+                .key(key)
+                .multipart_upload(
+                    CompletedMultipartUpload::builder()
+                        .set_parts(Some(upload_parts))
+                        .build(),
+                )
                 .upload_id(&upload_id)
-                .body(stream)
-                .part_number(part_number)
                 .send()
                 .await
-                .map_err(|err| S3PutError::S3PutError(err.to_string()))?;
-
-            upload_parts.push(
-                CompletedPart::builder()
-                    .e_tag(upload_part_res.e_tag.unwrap_or_default())
-                    .part_number(part_number)
-                    .build(),
-            );
+                .map(|_| ())
+                .map_err(put_error),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = completed {
+            self.abort_multipart_upload(key, &upload_id).await;
+            return Err(e);
         }
 
-        self.client
-            .complete_multipart_upload()
-            .bucket(&self.bucket)
+        Ok(())
+    }
+
+    // Uploads a part of a multipart upload. A part that fails fails the upload, which the
+    // retry policy of the storage attempts again as a whole
+    async fn upload_part(
+        &self,
+        key: &str,
+        upload_id: &str,
+        part_number: i32,
+        range: Range<usize>,
+        create_bytestream_fn: &impl Fn(
+            Range<usize>,
+        ) -> BoxFuture<'static, Result<ByteStream, S3PutError>>,
+    ) -> Result<CompletedPart, S3PutError> {
+        let stream = create_bytestream_fn(range).await?;
+        let output = self
+            .client
+            .upload_part()
             .key(key)
-            .multipart_upload(
-                CompletedMultipartUpload::builder()
-                    .set_parts(Some(upload_parts))
-                    .build(),
-            )
-            .upload_id(&upload_id)
+            .bucket(&self.bucket)
+            .upload_id(upload_id)
+            .body(stream)
+            .part_number(part_number)
             .send()
             .await
-            .map_err(|err| S3PutError::S3PutError(err.to_string()))?;
+            .map_err(|err| {
+                tracing::warn!("Error uploading part {} of {}: {}", part_number, key, err);
+                put_error(err)
+            })?;
+        Ok(CompletedPart::builder()
+            .e_tag(output.e_tag.unwrap_or_default())
+            .part_number(part_number)
+            .build())
+    }
 
-        Ok(())
+    // Aborts the multipart upload, so that the bucket does not keep its uploaded parts. The
+    // upload has failed regardless, so an error aborting it is only logged
+    async fn abort_multipart_upload(&self, key: &str, upload_id: &str) {
+        if let Err(err) = self
+            .client
+            .abort_multipart_upload()
+            .bucket(&self.bucket)
+            .key(key)
+            .upload_id(upload_id)
+            .send()
+            .await
+        {
+            tracing::error!("Error aborting multipart upload of {}: {}", key, err);
+        }
     }
 }
 
@@ -515,7 +720,9 @@ impl Configurable<StorageConfig> for S3Storage {
                         let timeout_config_builder = TimeoutConfigBuilder::default()
                             .connect_timeout(Duration::from_millis(s3_config.connect_timeout_ms))
                             .read_timeout(Duration::from_millis(s3_config.request_timeout_ms));
-                        let retry_config = RetryConfig::standard();
+                        // The storage retries the requests with its own policy, so the client
+                        // does not retry them as well
+                        let retry_config = RetryConfig::disabled();
 
                         // Set up s3 client
                         let config = aws_sdk_s3::config::Builder::new()
@@ -534,7 +741,7 @@ impl Configurable<StorageConfig> for S3Storage {
                         let timeout_config_builder = TimeoutConfigBuilder::default()
                             .connect_timeout(Duration::from_millis(s3_config.connect_timeout_ms))
                             .read_timeout(Duration::from_millis(s3_config.request_timeout_ms));
-                        let retry_config = RetryConfig::standard();
+                        let retry_config = RetryConfig::disabled();
                         let config = config
                             .to_builder()
                             .timeout_config(timeout_config_builder.build())
@@ -543,12 +750,7 @@ impl Configurable<StorageConfig> for S3Storage {
                         aws_sdk_s3::Client::new(&config)
                     }
                 };
-                let storage = S3Storage::new(
-                    &s3_config.bucket,
-                    client,
-                    s3_config.upload_part_size_bytes,
-                    s3_config.download_part_size_bytes,
-                );
+                let storage = S3Storage::new(client, s3_config);
                 // for minio we create the bucket since it is only used for testing
 
                 if let super::config::S3CredentialsConfig::Minio = &s3_config.credentials {
@@ -606,6 +808,8 @@ mod tests {
             client,
             upload_part_size_bytes: 1024 * 1024 * 8,
             download_part_size_bytes: 1024 * 1024 * 8,
+            upload_concurrency: 4,
+            retry_policy: RetryPolicy::default(),
         };
         storage.create_bucket().await.unwrap();
 
@@ -632,6 +836,8 @@ mod tests {
             client,
             upload_part_size_bytes,
             download_part_size_bytes,
+            upload_concurrency: 4,
+            retry_policy: RetryPolicy::default(),
         };
         storage.create_bucket().await.unwrap();
 
@@ -666,6 +872,8 @@ mod tests {
             client,
             upload_part_size_bytes: 1024 * 1024 * 8,
             download_part_size_bytes: 1024 * 1024 * 8,
+            upload_concurrency: 4,
+            retry_policy: RetryPolicy::default(),
         };
         storage.create_bucket().await.unwrap();
 
@@ -728,6 +936,13 @@ mod tests {
             test_download_part_size_bytes,
         )
         .await;
+        // Over more parts than are uploaded at once
+        test_put_file(
+            (test_upload_part_size_bytes as f64 * 5.5) as usize,
+            test_upload_part_size_bytes,
+            test_download_part_size_bytes,
+        )
+        .await;
     }
 
     #[tokio::test]
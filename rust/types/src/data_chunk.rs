@@ -125,6 +125,9 @@ mod tests {
                     encoding: None,
                     metadata: None,
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Add,
                 },
             },
@@ -136,6 +139,9 @@ mod tests {
                     encoding: None,
                     metadata: None,
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Add,
                 },
             },
@@ -8,6 +8,7 @@ pub struct DataRecord<'a> {
     pub embedding: &'a [f32],
     pub metadata: Option<Metadata>,
     pub document: Option<&'a str>,
+    pub uri: Option<&'a str>,
 }
 
 impl DataRecord<'_> {
@@ -26,6 +27,26 @@ impl DataRecord<'_> {
             Some(document) => document.len(),
             None => 0,
         };
-        id_size + embedding_size + metadata_size + document_size
+        let uri_size = match self.uri {
+            Some(uri) => uri.len(),
+            None => 0,
+        };
+        id_size + embedding_size + metadata_size + document_size + uri_size
+    }
+}
+
+/// The metadata of a data record, which is read from a blockfile of data records without
+/// decoding the other fields of the record
+#[derive(Debug, Clone)]
+pub struct DataRecordMetadata(pub Option<Metadata>);
+
+impl DataRecordMetadata {
+    pub fn get_size(&self) -> usize {
+        match &self.0 {
+            Some(metadata) => {
+                Into::<chroma_proto::UpdateMetadata>::into(metadata.clone()).encoded_len()
+            }
+            None => 0,
+        }
     }
 }
@@ -0,0 +1,120 @@
+use crate::chroma_proto::ErrorDetails;
+use chroma_error::{ChromaError, ErrorCodes};
+use prost::Message;
+use tonic::Status;
+
+impl ErrorDetails {
+    pub fn from_error<E: ChromaError + ?Sized>(error: &E) -> Self {
+        let location = error.location();
+        ErrorDetails {
+            retryable: error.is_retryable(),
+            user_error: error.is_user_error(),
+            field: error.field().map(str::to_string),
+            segment_id: location.segment_id,
+            block_id: location.block_id,
+        }
+    }
+
+    /// The details of an invalid argument in the given field of the request
+    pub fn invalid_argument(field: &str) -> Self {
+        ErrorDetails {
+            retryable: ErrorCodes::InvalidArgument.is_retryable(),
+            user_error: ErrorCodes::InvalidArgument.is_user_error(),
+            field: Some(field.to_string()),
+            segment_id: None,
+            block_id: None,
+        }
+    }
+
+    /// Builds a status that carries these details, encoded as protobuf
+    pub fn into_status(self, code: tonic::Code, message: impl Into<String>) -> Status {
+        Status::with_details(code, message, self.encode_to_vec().into())
+    }
+
+    /// Decodes the details of a status, if it carries any
+    pub fn from_status(status: &Status) -> Option<Self> {
+        if status.details().is_empty() {
+            return None;
+        }
+        ErrorDetails::decode(status.details()).ok()
+    }
+}
+
+/// Converts an error into a status with its code and classification
+pub fn error_status<E: ChromaError + ?Sized>(error: &E, message: impl Into<String>) -> Status {
+    ErrorDetails::from_error(error).into_status(error.code().into(), message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chroma_error::ErrorLocation;
+    use thiserror::Error;
+
+    #[derive(Error, Debug)]
+    enum TestError {
+        #[error("Invalid regex")]
+        InvalidRegex,
+        #[error("Storage unavailable")]
+        Unavailable,
+    }
+
+    impl ChromaError for TestError {
+        fn code(&self) -> ErrorCodes {
+            match self {
+                TestError::InvalidRegex => ErrorCodes::InvalidArgument,
+                TestError::Unavailable => ErrorCodes::Unavailable,
+            }
+        }
+
+        fn field(&self) -> Option<&str> {
+            match self {
+                TestError::InvalidRegex => Some("where_document"),
+                TestError::Unavailable => None,
+            }
+        }
+
+        fn location(&self) -> ErrorLocation {
+            match self {
+                TestError::InvalidRegex => ErrorLocation::default(),
+                TestError::Unavailable => ErrorLocation::block("block").in_segment("segment"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_error_status_details() {
+        let status = error_status(&TestError::InvalidRegex, "Invalid regex");
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        assert_eq!(
+            ErrorDetails::from_status(&status),
+            Some(ErrorDetails {
+                retryable: false,
+                user_error: true,
+                field: Some("where_document".to_string()),
+                segment_id: None,
+                block_id: None,
+            })
+        );
+
+        // The classification and the location are forwarded through boxed errors
+        let error: Box<dyn ChromaError> = Box::new(TestError::Unavailable);
+        let status = error_status(&error, "Storage unavailable");
+        assert_eq!(status.code(), tonic::Code::Unavailable);
+        assert_eq!(
+            ErrorDetails::from_status(&status),
+            Some(ErrorDetails {
+                retryable: true,
+                user_error: false,
+                field: None,
+                segment_id: Some("segment".to_string()),
+                block_id: Some("block".to_string()),
+            })
+        );
+
+        assert_eq!(
+            ErrorDetails::from_status(&Status::internal("No details")),
+            None
+        );
+    }
+}
@@ -1,17 +1,19 @@
 use super::{CollectionUuid, ConversionError};
 use crate::{
     chroma_proto::{FilePaths, FlushCollectionCompactionResponse, FlushSegmentCompactionInfo},
-    SegmentUuid,
+    Metadata, SegmentUuid,
 };
 use chroma_error::{ChromaError, ErrorCodes};
 use std::collections::HashMap;
 use thiserror::Error;
 use uuid::Uuid;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct SegmentFlushInfo {
     pub segment_id: SegmentUuid,
     pub file_paths: HashMap<String, Vec<String>>,
+    /// The segment metadata that is registered together with the files
+    pub metadata: Option<Metadata>,
 }
 
 impl TryInto<FlushSegmentCompactionInfo> for &SegmentFlushInfo {
@@ -26,6 +28,7 @@ impl TryInto<FlushSegmentCompactionInfo> for &SegmentFlushInfo {
         Ok(FlushSegmentCompactionInfo {
             segment_id: self.segment_id.to_string(),
             file_paths,
+            metadata: self.metadata.clone().map(Into::into),
         })
     }
 }
@@ -3,6 +3,7 @@ mod types;
 mod collection;
 mod data_chunk;
 mod data_record;
+mod error_details;
 mod flush;
 mod metadata;
 mod operation;
@@ -18,6 +19,7 @@ mod tenant;
 pub use collection::*;
 pub use data_chunk::*;
 pub use data_record::*;
+pub use error_details::*;
 pub use flush::*;
 pub use metadata::*;
 pub use operation::*;
@@ -1,7 +1,9 @@
 use chroma_error::{ChromaError, ErrorCodes};
 use std::{
+    borrow::Cow,
     cmp::Ordering,
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 use thiserror::Error;
 
@@ -13,12 +15,17 @@ pub enum UpdateMetadataValue {
     Int(i64),
     Float(f64),
     Str(String),
+    Timestamp(i64),
+    List(Vec<MetadataValue>),
+    Object(BTreeMap<String, MetadataValue>),
     None,
 }
 
 #[derive(Error, Debug)]
 pub enum UpdateMetadataValueConversionError {
-    #[error("Invalid metadata value, valid values are: Int, Float, Str, Bool, None")]
+    #[error(
+        "Invalid metadata value, valid values are: Int, Float, Str, Bool, Timestamp, List, Object, None"
+    )]
     InvalidValue,
 }
 
@@ -47,6 +54,21 @@ impl TryFrom<&chroma_proto::UpdateMetadataValue> for UpdateMetadataValue {
             Some(chroma_proto::update_metadata_value::Value::StringValue(value)) => {
                 Ok(UpdateMetadataValue::Str(value.clone()))
             }
+            Some(chroma_proto::update_metadata_value::Value::TimestampValue(value)) => {
+                Ok(UpdateMetadataValue::Timestamp(*value))
+            }
+            Some(chroma_proto::update_metadata_value::Value::ListValue(list)) => {
+                match metadata_list_from_proto(list) {
+                    Ok(values) => Ok(UpdateMetadataValue::List(values)),
+                    Err(_) => Err(UpdateMetadataValueConversionError::InvalidValue),
+                }
+            }
+            Some(chroma_proto::update_metadata_value::Value::ObjectValue(object)) => {
+                match metadata_object_from_proto(object) {
+                    Ok(values) => Ok(UpdateMetadataValue::Object(values)),
+                    Err(_) => Err(UpdateMetadataValueConversionError::InvalidValue),
+                }
+            }
             // Used to communicate that the user wants to delete this key.
             None => Ok(UpdateMetadataValue::None),
         }
@@ -72,6 +94,13 @@ impl From<UpdateMetadataValue> for chroma_proto::UpdateMetadataValue {
                     value,
                 )),
             },
+            UpdateMetadataValue::Timestamp(value) => chroma_proto::UpdateMetadataValue {
+                value: Some(chroma_proto::update_metadata_value::Value::TimestampValue(
+                    value,
+                )),
+            },
+            UpdateMetadataValue::List(values) => MetadataValue::List(values).into(),
+            UpdateMetadataValue::Object(values) => MetadataValue::Object(values).into(),
             UpdateMetadataValue::None => chroma_proto::UpdateMetadataValue { value: None },
         }
     }
@@ -86,23 +115,106 @@ impl TryFrom<&UpdateMetadataValue> for MetadataValue {
             UpdateMetadataValue::Int(value) => Ok(MetadataValue::Int(*value)),
             UpdateMetadataValue::Float(value) => Ok(MetadataValue::Float(*value)),
             UpdateMetadataValue::Str(value) => Ok(MetadataValue::Str(value.clone())),
+            UpdateMetadataValue::Timestamp(value) => Ok(MetadataValue::Timestamp(*value)),
+            UpdateMetadataValue::List(values) => Ok(MetadataValue::List(values.clone())),
+            UpdateMetadataValue::Object(values) => Ok(MetadataValue::Object(values.clone())),
             UpdateMetadataValue::None => Err(MetadataValueConversionError::InvalidValue),
         }
     }
 }
 
+impl From<MetadataValue> for UpdateMetadataValue {
+    fn from(value: MetadataValue) -> Self {
+        match value {
+            MetadataValue::Bool(value) => UpdateMetadataValue::Bool(value),
+            MetadataValue::Int(value) => UpdateMetadataValue::Int(value),
+            MetadataValue::Float(value) => UpdateMetadataValue::Float(value),
+            MetadataValue::Str(value) => UpdateMetadataValue::Str(value),
+            MetadataValue::Timestamp(value) => UpdateMetadataValue::Timestamp(value),
+            MetadataValue::List(values) => UpdateMetadataValue::List(values),
+            MetadataValue::Object(values) => UpdateMetadataValue::Object(values),
+        }
+    }
+}
+
 /*
 ===========================================
 MetadataValue
 ===========================================
 */
 
+/// A metadata value of a record. The elements of a `List` are never lists themselves, and are
+/// indexed separately, so that a comparison matches a list if it matches any of its elements.
+/// A `Timestamp` is the number of microseconds since the Unix epoch. The values of an `Object`
+/// are indexed under their dotted paths, e.g. `author.name` for `{"author": {"name": "x"}}`.
 #[derive(Clone, Debug, PartialEq, PartialOrd)]
 pub enum MetadataValue {
     Bool(bool),
     Int(i64),
     Float(f64),
     Str(String),
+    Timestamp(i64),
+    List(Vec<MetadataValue>),
+    Object(BTreeMap<String, MetadataValue>),
+}
+
+impl MetadataValue {
+    /// The elements of a list, or the value itself if it is not a list
+    pub fn elements(&self) -> &[MetadataValue] {
+        match self {
+            MetadataValue::List(values) => values,
+            _ => std::slice::from_ref(self),
+        }
+    }
+
+    /// The values that are not objects below this value, together with their dotted paths,
+    /// where `key` is the path of this value
+    pub fn flatten<'me>(&'me self, key: &'me str) -> Vec<(Cow<'me, str>, &'me MetadataValue)> {
+        let mut leaves = Vec::new();
+        self.collect_leaves(Cow::Borrowed(key), &mut leaves);
+        leaves
+    }
+
+    fn collect_leaves<'me>(
+        &'me self,
+        path: Cow<'me, str>,
+        leaves: &mut Vec<(Cow<'me, str>, &'me MetadataValue)>,
+    ) {
+        match self {
+            MetadataValue::Object(values) => {
+                for (key, value) in values {
+                    value.collect_leaves(Cow::Owned(format!("{path}.{key}")), leaves);
+                }
+            }
+            _ => leaves.push((path, self)),
+        }
+    }
+
+    /// Resolves a dotted path below this value. Since keys may contain dots themselves, every
+    /// split of the path is tried
+    pub fn get_path(&self, path: &str) -> Option<&MetadataValue> {
+        match self {
+            MetadataValue::Object(values) => resolve_path(|key| values.get(key), path),
+            _ => None,
+        }
+    }
+}
+
+/// Resolves a dotted path in the metadata of a record, e.g. `author.name` resolves to the `name`
+/// of the `author` object. A key that is the whole path takes precedence
+pub fn get_metadata_path<'me>(metadata: &'me Metadata, path: &str) -> Option<&'me MetadataValue> {
+    resolve_path(|key| metadata.get(key), path)
+}
+
+fn resolve_path<'me>(
+    get: impl Fn(&str) -> Option<&'me MetadataValue>,
+    path: &str,
+) -> Option<&'me MetadataValue> {
+    get(path).or_else(|| {
+        path.match_indices('.').find_map(|(index, _)| {
+            get(&path[..index]).and_then(|value| value.get_path(&path[index + 1..]))
+        })
+    })
 }
 
 impl Eq for MetadataValue {}
@@ -159,16 +271,54 @@ impl TryFrom<&MetadataValue> for String {
     }
 }
 
+impl TryFrom<&MetadataValue> for SystemTime {
+    type Error = MetadataValueConversionError;
+
+    fn try_from(value: &MetadataValue) -> Result<Self, Self::Error> {
+        match value {
+            MetadataValue::Timestamp(micros) => {
+                let offset = Duration::from_micros(micros.unsigned_abs());
+                let time = if *micros >= 0 {
+                    UNIX_EPOCH.checked_add(offset)
+                } else {
+                    UNIX_EPOCH.checked_sub(offset)
+                };
+                time.ok_or(MetadataValueConversionError::TimestampOutOfRange)
+            }
+            _ => Err(MetadataValueConversionError::InvalidValue),
+        }
+    }
+}
+
+impl TryFrom<SystemTime> for MetadataValue {
+    type Error = MetadataValueConversionError;
+
+    fn try_from(time: SystemTime) -> Result<Self, Self::Error> {
+        let micros = match time.duration_since(UNIX_EPOCH) {
+            Ok(offset) => i64::try_from(offset.as_micros()),
+            Err(e) => i64::try_from(e.duration().as_micros()).map(|micros| -micros),
+        };
+        micros
+            .map(MetadataValue::Timestamp)
+            .map_err(|_| MetadataValueConversionError::TimestampOutOfRange)
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum MetadataValueConversionError {
-    #[error("Invalid metadata value, valid values are: Int, Float, Str")]
+    #[error(
+        "Invalid metadata value, valid values are: Int, Float, Str, Bool, Timestamp, List, Object"
+    )]
     InvalidValue,
+    #[error("Timestamp is out of the representable range")]
+    TimestampOutOfRange,
 }
 
 impl ChromaError for MetadataValueConversionError {
     fn code(&self) -> ErrorCodes {
         match self {
             MetadataValueConversionError::InvalidValue => ErrorCodes::InvalidArgument,
+            MetadataValueConversionError::TimestampOutOfRange => ErrorCodes::InvalidArgument,
         }
     }
 }
@@ -190,11 +340,46 @@ impl TryFrom<&chroma_proto::UpdateMetadataValue> for MetadataValue {
             Some(chroma_proto::update_metadata_value::Value::StringValue(value)) => {
                 Ok(MetadataValue::Str(value.clone()))
             }
+            Some(chroma_proto::update_metadata_value::Value::TimestampValue(value)) => {
+                Ok(MetadataValue::Timestamp(*value))
+            }
+            Some(chroma_proto::update_metadata_value::Value::ListValue(list)) => {
+                Ok(MetadataValue::List(metadata_list_from_proto(list)?))
+            }
+            Some(chroma_proto::update_metadata_value::Value::ObjectValue(object)) => {
+                Ok(MetadataValue::Object(metadata_object_from_proto(object)?))
+            }
             _ => Err(MetadataValueConversionError::InvalidValue),
         }
     }
 }
 
+/// Converts the elements of a list, which must be set and must not be lists or objects
+fn metadata_list_from_proto(
+    list: &chroma_proto::MetadataList,
+) -> Result<Vec<MetadataValue>, MetadataValueConversionError> {
+    list.values
+        .iter()
+        .map(|value| match value.value {
+            Some(chroma_proto::update_metadata_value::Value::ListValue(_))
+            | Some(chroma_proto::update_metadata_value::Value::ObjectValue(_))
+            | None => Err(MetadataValueConversionError::InvalidValue),
+            _ => MetadataValue::try_from(value),
+        })
+        .collect()
+}
+
+/// Converts the values of an object, which must be set
+fn metadata_object_from_proto(
+    object: &chroma_proto::MetadataObject,
+) -> Result<BTreeMap<String, MetadataValue>, MetadataValueConversionError> {
+    object
+        .values
+        .iter()
+        .map(|(key, value)| Ok((key.clone(), MetadataValue::try_from(value)?)))
+        .collect()
+}
+
 impl From<MetadataValue> for chroma_proto::UpdateMetadataValue {
     fn from(value: MetadataValue) -> Self {
         match value {
@@ -214,6 +399,28 @@ impl From<MetadataValue> for chroma_proto::UpdateMetadataValue {
             MetadataValue::Bool(value) => chroma_proto::UpdateMetadataValue {
                 value: Some(chroma_proto::update_metadata_value::Value::BoolValue(value)),
             },
+            MetadataValue::Timestamp(value) => chroma_proto::UpdateMetadataValue {
+                value: Some(chroma_proto::update_metadata_value::Value::TimestampValue(
+                    value,
+                )),
+            },
+            MetadataValue::List(values) => chroma_proto::UpdateMetadataValue {
+                value: Some(chroma_proto::update_metadata_value::Value::ListValue(
+                    chroma_proto::MetadataList {
+                        values: values.into_iter().map(Into::into).collect(),
+                    },
+                )),
+            },
+            MetadataValue::Object(values) => chroma_proto::UpdateMetadataValue {
+                value: Some(chroma_proto::update_metadata_value::Value::ObjectValue(
+                    chroma_proto::MetadataObject {
+                        values: values
+                            .into_iter()
+                            .map(|(key, value)| (key, value.into()))
+                            .collect(),
+                    },
+                )),
+            },
         }
     }
 }
@@ -373,6 +580,7 @@ pub struct DirectWhereComparison {
 pub enum WhereComparison {
     Primitive(PrimitiveOperator, MetadataValue),
     Set(SetOperator, MetadataSetValue),
+    Existence(ExistenceOperator),
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -383,6 +591,13 @@ pub enum PrimitiveOperator {
     GreaterThanOrEqual,
     LessThan,
     LessThanOrEqual,
+    // Whether a list has the value as an element, or a value that is not a list equals it
+    Contains,
+    NotContains,
+    // Whether a string equals the value when both are lowercased
+    EqualCaseInsensitive,
+    // Whether a string has the value as a prefix
+    StartsWith,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -391,6 +606,13 @@ pub enum SetOperator {
     NotIn,
 }
 
+/// Whether a record has a value for the key, regardless of the type of the value
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExistenceOperator {
+    Exists,
+    IsNull,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum MetadataSetValue {
     Bool(Vec<bool>),
@@ -421,6 +643,11 @@ pub struct DirectDocumentComparison {
 pub enum DocumentOperator {
     Contains,
     NotContains,
+    /// The whitespace separated terms of the document all occur within the given number of words
+    Near(u32),
+    /// The document matches the regular expression
+    Regex,
+    NotRegex,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -473,6 +700,16 @@ impl TryFrom<chroma_proto::DirectComparison> for WhereComparison {
                 .map_err(|_| WhereConversionError::InvalidWhereComparison)?
                 .try_into()
         };
+        // Case-insensitive and prefix matching only apply to strings
+        let id_to_non_string_generic_comparator =
+            |id: i32| -> Result<PrimitiveOperator, WhereConversionError> {
+                match id_to_generic_comparator(id)? {
+                    PrimitiveOperator::EqualCaseInsensitive | PrimitiveOperator::StartsWith => {
+                        Err(WhereConversionError::InvalidWhereComparison)
+                    }
+                    operator => Ok(operator),
+                }
+            };
         let id_to_number_comparator = |id| {
             TryInto::<chroma_proto::NumberComparator>::try_into(id)
                 .map_err(|_| WhereConversionError::InvalidWhereComparison)?
@@ -487,7 +724,7 @@ impl TryFrom<chroma_proto::DirectComparison> for WhereComparison {
             use chroma_proto::direct_comparison::Comparison::*;
             match proto_comp {
                 SingleBoolOperand(single_bool_comparison) => Ok(WhereComparison::Primitive(
-                    id_to_generic_comparator(single_bool_comparison.comparator)?,
+                    id_to_non_string_generic_comparator(single_bool_comparison.comparator)?,
                     MetadataValue::Bool(single_bool_comparison.value),
                 )),
                 SingleStringOperand(single_string_comparison) => Ok(WhereComparison::Primitive(
@@ -500,7 +737,7 @@ impl TryFrom<chroma_proto::DirectComparison> for WhereComparison {
                             chroma_proto::single_int_comparison::Comparator::GenericComparator(
                                 proto_generic_comparator,
                             ),
-                        ) => id_to_generic_comparator(proto_generic_comparator)?,
+                        ) => id_to_non_string_generic_comparator(proto_generic_comparator)?,
                         Some(
                             chroma_proto::single_int_comparison::Comparator::NumberComparator(
                                 proto_number_comparator,
@@ -516,7 +753,7 @@ impl TryFrom<chroma_proto::DirectComparison> for WhereComparison {
                             chroma_proto::single_double_comparison::Comparator::GenericComparator(
                                 proto_generic_comparator,
                             ),
-                        ) => id_to_generic_comparator(proto_generic_comparator)?,
+                        ) => id_to_non_string_generic_comparator(proto_generic_comparator)?,
                         Some(
                             chroma_proto::single_double_comparison::Comparator::NumberComparator(
                                 proto_number_comparator,
@@ -526,6 +763,21 @@ impl TryFrom<chroma_proto::DirectComparison> for WhereComparison {
                     },
                     MetadataValue::Float(single_double_comparison.value),
                 )),
+                SingleTimestampOperand(single_timestamp_comparison) => {
+                    use chroma_proto::single_timestamp_comparison::Comparator;
+                    Ok(WhereComparison::Primitive(
+                        match single_timestamp_comparison.comparator {
+                            Some(Comparator::GenericComparator(proto_generic_comparator)) => {
+                                id_to_non_string_generic_comparator(proto_generic_comparator)?
+                            }
+                            Some(Comparator::NumberComparator(proto_number_comparator)) => {
+                                id_to_number_comparator(proto_number_comparator)?
+                            }
+                            None => PrimitiveOperator::Equal,
+                        },
+                        MetadataValue::Timestamp(single_timestamp_comparison.value),
+                    ))
+                }
                 BoolListOperand(bool_list_comparison) => Ok(WhereComparison::Set(
                     id_to_set_comparator(bool_list_comparison.list_operator)?,
                     MetadataSetValue::Bool(bool_list_comparison.values),
@@ -542,6 +794,13 @@ impl TryFrom<chroma_proto::DirectComparison> for WhereComparison {
                     id_to_set_comparator(double_list_comparison.list_operator)?,
                     MetadataSetValue::Float(double_list_comparison.values),
                 )),
+                ExistenceOperand(existence_comparison) => Ok(WhereComparison::Existence(
+                    TryInto::<chroma_proto::ExistenceOperator>::try_into(
+                        existence_comparison.operator,
+                    )
+                    .map_err(|_| WhereConversionError::InvalidWhereComparison)?
+                    .try_into()?,
+                )),
             }
         } else {
             Err(WhereConversionError::InvalidWhereComparison)
@@ -549,6 +808,17 @@ impl TryFrom<chroma_proto::DirectComparison> for WhereComparison {
     }
 }
 
+impl TryFrom<chroma_proto::ExistenceOperator> for ExistenceOperator {
+    type Error = WhereConversionError;
+
+    fn try_from(proto_operator: chroma_proto::ExistenceOperator) -> Result<Self, Self::Error> {
+        match proto_operator {
+            chroma_proto::ExistenceOperator::Exists => Ok(ExistenceOperator::Exists),
+            chroma_proto::ExistenceOperator::IsNull => Ok(ExistenceOperator::IsNull),
+        }
+    }
+}
+
 impl TryFrom<chroma_proto::NumberComparator> for PrimitiveOperator {
     type Error = WhereConversionError;
 
@@ -569,6 +839,10 @@ impl TryFrom<chroma_proto::GenericComparator> for PrimitiveOperator {
         match proto_comparator {
             chroma_proto::GenericComparator::Eq => Ok(PrimitiveOperator::Equal),
             chroma_proto::GenericComparator::Ne => Ok(PrimitiveOperator::NotEqual),
+            chroma_proto::GenericComparator::ListContains => Ok(PrimitiveOperator::Contains),
+            chroma_proto::GenericComparator::ListNotContains => Ok(PrimitiveOperator::NotContains),
+            chroma_proto::GenericComparator::EqCi => Ok(PrimitiveOperator::EqualCaseInsensitive),
+            chroma_proto::GenericComparator::StartsWith => Ok(PrimitiveOperator::StartsWith),
         }
     }
 }
@@ -625,9 +899,16 @@ impl TryFrom<chroma_proto::WhereDocument> for Where {
                     Ok(operator) => operator,
                     Err(_) => return Err(WhereConversionError::InvalidWhereComparison),
                 };
+                let operator = match operator {
+                    chroma_proto::WhereDocumentOperator::Near => match proto_comparison.distance {
+                        Some(distance) => DocumentOperator::Near(distance),
+                        None => return Err(WhereConversionError::InvalidWhereComparison),
+                    },
+                    _ => operator.try_into()?,
+                };
                 let comparison = DirectDocumentComparison {
                     document: proto_comparison.document,
-                    operator: operator.try_into()?,
+                    operator,
                 };
                 Ok(Where::DirectWhereDocumentComparison(comparison))
             }
@@ -660,6 +941,12 @@ impl TryFrom<chroma_proto::WhereDocumentOperator> for DocumentOperator {
         match proto_operator {
             chroma_proto::WhereDocumentOperator::Contains => Ok(DocumentOperator::Contains),
             chroma_proto::WhereDocumentOperator::NotContains => Ok(DocumentOperator::NotContains),
+            chroma_proto::WhereDocumentOperator::Regex => Ok(DocumentOperator::Regex),
+            chroma_proto::WhereDocumentOperator::NotRegex => Ok(DocumentOperator::NotRegex),
+            // The distance of a near operator is not part of the proto operator
+            chroma_proto::WhereDocumentOperator::Near => {
+                Err(WhereConversionError::InvalidWhereComparison)
+            }
         }
     }
 }
@@ -750,6 +1037,110 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_metadata_list_try_from() {
+        let list = MetadataValue::List(vec![
+            MetadataValue::Str("foo".to_string()),
+            MetadataValue::Int(42),
+        ]);
+        let proto_value: chroma_proto::UpdateMetadataValue = list.clone().into();
+        assert_eq!(MetadataValue::try_from(&proto_value).unwrap(), list);
+
+        let nested_value = chroma_proto::UpdateMetadataValue {
+            value: Some(chroma_proto::update_metadata_value::Value::ListValue(
+                chroma_proto::MetadataList {
+                    values: vec![proto_value],
+                },
+            )),
+        };
+        assert!(MetadataValue::try_from(&nested_value).is_err());
+    }
+
+    #[test]
+    fn test_metadata_object_try_from() {
+        let object = MetadataValue::Object(BTreeMap::from([
+            (
+                "author".to_string(),
+                MetadataValue::Object(BTreeMap::from([(
+                    "name".to_string(),
+                    MetadataValue::Str("x".to_string()),
+                )])),
+            ),
+            ("pages".to_string(), MetadataValue::Int(42)),
+        ]));
+        let proto_value: chroma_proto::UpdateMetadataValue = object.clone().into();
+        assert_eq!(MetadataValue::try_from(&proto_value).unwrap(), object);
+
+        let list_of_objects = chroma_proto::UpdateMetadataValue {
+            value: Some(chroma_proto::update_metadata_value::Value::ListValue(
+                chroma_proto::MetadataList {
+                    values: vec![proto_value],
+                },
+            )),
+        };
+        assert!(MetadataValue::try_from(&list_of_objects).is_err());
+    }
+
+    #[test]
+    fn test_metadata_object_paths() {
+        let book = MetadataValue::Object(BTreeMap::from([
+            (
+                "author".to_string(),
+                MetadataValue::Object(BTreeMap::from([(
+                    "name".to_string(),
+                    MetadataValue::Str("x".to_string()),
+                )])),
+            ),
+            ("pages".to_string(), MetadataValue::Int(42)),
+        ]));
+        assert_eq!(
+            book.flatten("book"),
+            vec![
+                (
+                    Cow::<str>::Owned("book.author.name".to_string()),
+                    &MetadataValue::Str("x".to_string())
+                ),
+                (
+                    Cow::Owned("book.pages".to_string()),
+                    &MetadataValue::Int(42)
+                ),
+            ]
+        );
+
+        let metadata = Metadata::from([
+            ("book".to_string(), book),
+            ("book.pages".to_string(), MetadataValue::Int(7)),
+        ]);
+        assert_eq!(
+            get_metadata_path(&metadata, "book.author.name"),
+            Some(&MetadataValue::Str("x".to_string()))
+        );
+        // A key that is the whole path takes precedence over the nested value
+        assert_eq!(
+            get_metadata_path(&metadata, "book.pages"),
+            Some(&MetadataValue::Int(7))
+        );
+        assert_eq!(get_metadata_path(&metadata, "book.author.age"), None);
+    }
+
+    #[test]
+    fn test_metadata_timestamp_try_from() {
+        let timestamp = MetadataValue::Timestamp(-1_500_000);
+        let proto_value: chroma_proto::UpdateMetadataValue = timestamp.clone().into();
+        assert_eq!(MetadataValue::try_from(&proto_value).unwrap(), timestamp);
+
+        let time = SystemTime::try_from(&timestamp).unwrap();
+        assert_eq!(time, UNIX_EPOCH - Duration::from_micros(1_500_000));
+        assert_eq!(MetadataValue::try_from(time).unwrap(), timestamp);
+
+        let time = UNIX_EPOCH + Duration::from_micros(42);
+        assert_eq!(
+            MetadataValue::try_from(time).unwrap(),
+            MetadataValue::Timestamp(42)
+        );
+        assert!(SystemTime::try_from(&MetadataValue::Int(42)).is_err());
+    }
+
     #[test]
     fn test_where_clause_simple_from() {
         let proto_where = chroma_proto::Where {
@@ -782,6 +1173,162 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_where_clause_existence_from() {
+        let proto_where = chroma_proto::Where {
+            r#where: Some(chroma_proto::r#where::Where::DirectComparison(
+                chroma_proto::DirectComparison {
+                    key: "foo".to_string(),
+                    comparison: Some(
+                        chroma_proto::direct_comparison::Comparison::ExistenceOperand(
+                            chroma_proto::ExistenceComparison {
+                                operator: chroma_proto::ExistenceOperator::IsNull.into(),
+                            },
+                        ),
+                    ),
+                },
+            )),
+        };
+        let where_clause: Where = proto_where.try_into().unwrap();
+        match where_clause {
+            Where::DirectWhereComparison(comparison) => {
+                assert_eq!(comparison.key, "foo");
+                assert_eq!(
+                    comparison.comparison,
+                    WhereComparison::Existence(ExistenceOperator::IsNull)
+                );
+            }
+            _ => panic!("Invalid where type"),
+        }
+    }
+
+    #[test]
+    fn test_where_clause_contains_from() {
+        let proto_where = chroma_proto::Where {
+            r#where: Some(chroma_proto::r#where::Where::DirectComparison(
+                chroma_proto::DirectComparison {
+                    key: "tags".to_string(),
+                    comparison: Some(
+                        chroma_proto::direct_comparison::Comparison::SingleStringOperand(
+                            chroma_proto::SingleStringComparison {
+                                value: "foo".to_string(),
+                                comparator: chroma_proto::GenericComparator::ListContains.into(),
+                            },
+                        ),
+                    ),
+                },
+            )),
+        };
+        let where_clause: Where = proto_where.try_into().unwrap();
+        match where_clause {
+            Where::DirectWhereComparison(comparison) => {
+                assert_eq!(comparison.key, "tags");
+                assert_eq!(
+                    comparison.comparison,
+                    WhereComparison::Primitive(
+                        PrimitiveOperator::Contains,
+                        MetadataValue::Str("foo".to_string())
+                    )
+                );
+            }
+            _ => panic!("Invalid where type"),
+        }
+    }
+
+    #[test]
+    fn test_where_clause_string_matching_from() {
+        let proto_where = chroma_proto::Where {
+            r#where: Some(chroma_proto::r#where::Where::DirectComparison(
+                chroma_proto::DirectComparison {
+                    key: "title".to_string(),
+                    comparison: Some(
+                        chroma_proto::direct_comparison::Comparison::SingleStringOperand(
+                            chroma_proto::SingleStringComparison {
+                                value: "Chroma".to_string(),
+                                comparator: chroma_proto::GenericComparator::StartsWith.into(),
+                            },
+                        ),
+                    ),
+                },
+            )),
+        };
+        let where_clause: Where = proto_where.try_into().unwrap();
+        match where_clause {
+            Where::DirectWhereComparison(comparison) => {
+                assert_eq!(
+                    comparison.comparison,
+                    WhereComparison::Primitive(
+                        PrimitiveOperator::StartsWith,
+                        MetadataValue::Str("Chroma".to_string())
+                    )
+                );
+            }
+            _ => panic!("Invalid where type"),
+        }
+
+        // Case-insensitive matching does not apply to numbers
+        let proto_where = chroma_proto::Where {
+            r#where: Some(chroma_proto::r#where::Where::DirectComparison(
+                chroma_proto::DirectComparison {
+                    key: "count".to_string(),
+                    comparison: Some(
+                        chroma_proto::direct_comparison::Comparison::SingleIntOperand(
+                            chroma_proto::SingleIntComparison {
+                                value: 1,
+                                comparator: Some(
+                                    chroma_proto::single_int_comparison::Comparator::GenericComparator(
+                                        chroma_proto::GenericComparator::EqCi.into(),
+                                    ),
+                                ),
+                            },
+                        ),
+                    ),
+                },
+            )),
+        };
+        assert!(matches!(
+            Where::try_from(proto_where),
+            Err(WhereConversionError::InvalidWhereComparison)
+        ));
+    }
+
+    #[test]
+    fn test_where_clause_timestamp_from() {
+        let proto_where = chroma_proto::Where {
+            r#where: Some(chroma_proto::r#where::Where::DirectComparison(
+                chroma_proto::DirectComparison {
+                    key: "created_at".to_string(),
+                    comparison: Some(
+                        chroma_proto::direct_comparison::Comparison::SingleTimestampOperand(
+                            chroma_proto::SingleTimestampComparison {
+                                value: 1_700_000_000_000_000,
+                                comparator: Some(
+                                    chroma_proto::single_timestamp_comparison::Comparator::NumberComparator(
+                                        chroma_proto::NumberComparator::Gte.into(),
+                                    ),
+                                ),
+                            },
+                        ),
+                    ),
+                },
+            )),
+        };
+        let where_clause: Where = proto_where.try_into().unwrap();
+        match where_clause {
+            Where::DirectWhereComparison(comparison) => {
+                assert_eq!(comparison.key, "created_at");
+                assert_eq!(
+                    comparison.comparison,
+                    WhereComparison::Primitive(
+                        PrimitiveOperator::GreaterThanOrEqual,
+                        MetadataValue::Timestamp(1_700_000_000_000_000)
+                    )
+                );
+            }
+            _ => panic!("Invalid where type"),
+        }
+    }
+
     #[test]
     fn test_where_clause_with_children() {
         let proto_where = chroma_proto::Where {
@@ -840,6 +1387,7 @@ mod tests {
                 chroma_proto::DirectWhereDocument {
                     document: "foo".to_string(),
                     operator: chroma_proto::WhereDocumentOperator::Contains.into(),
+                    distance: None,
                 },
             )),
         };
@@ -853,6 +1401,74 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_where_document_regex() {
+        for (proto_operator, operator) in [
+            (
+                chroma_proto::WhereDocumentOperator::Regex,
+                DocumentOperator::Regex,
+            ),
+            (
+                chroma_proto::WhereDocumentOperator::NotRegex,
+                DocumentOperator::NotRegex,
+            ),
+        ] {
+            let proto_where = chroma_proto::WhereDocument {
+                r#where_document: Some(chroma_proto::where_document::WhereDocument::Direct(
+                    chroma_proto::DirectWhereDocument {
+                        document: "^fo+$".to_string(),
+                        operator: proto_operator.into(),
+                        distance: None,
+                    },
+                )),
+            };
+            let where_document: Where = proto_where.try_into().unwrap();
+            match where_document {
+                Where::DirectWhereDocumentComparison(comparison) => {
+                    assert_eq!(comparison.document, "^fo+$");
+                    assert_eq!(comparison.operator, operator);
+                }
+                _ => panic!("Invalid where document type"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_where_document_near() {
+        let proto_where = chroma_proto::WhereDocument {
+            r#where_document: Some(chroma_proto::where_document::WhereDocument::Direct(
+                chroma_proto::DirectWhereDocument {
+                    document: "foo bar".to_string(),
+                    operator: chroma_proto::WhereDocumentOperator::Near.into(),
+                    distance: Some(3),
+                },
+            )),
+        };
+        let where_document: Where = proto_where.try_into().unwrap();
+        match where_document {
+            Where::DirectWhereDocumentComparison(comparison) => {
+                assert_eq!(comparison.document, "foo bar");
+                assert_eq!(comparison.operator, DocumentOperator::Near(3));
+            }
+            _ => panic!("Invalid where document type"),
+        }
+
+        // The distance is required for a near operator
+        let proto_where = chroma_proto::WhereDocument {
+            r#where_document: Some(chroma_proto::where_document::WhereDocument::Direct(
+                chroma_proto::DirectWhereDocument {
+                    document: "foo bar".to_string(),
+                    operator: chroma_proto::WhereDocumentOperator::Near.into(),
+                    distance: None,
+                },
+            )),
+        };
+        assert_eq!(
+            Where::try_from(proto_where),
+            Err(WhereConversionError::InvalidWhereComparison)
+        );
+    }
+
     #[test]
     fn test_where_document_with_children() {
         let proto_where = chroma_proto::WhereDocument {
@@ -866,6 +1482,7 @@ mod tests {
                                         document: "foo".to_string(),
                                         operator: chroma_proto::WhereDocumentOperator::Contains
                                             .into(),
+                                        distance: None,
                                     },
                                 ),
                             ),
@@ -877,6 +1494,7 @@ mod tests {
                                         document: "bar".to_string(),
                                         operator: chroma_proto::WhereDocumentOperator::Contains
                                             .into(),
+                                        distance: None,
                                     },
                                 ),
                             ),
@@ -5,6 +5,7 @@ use super::{
 };
 use crate::chroma_proto;
 use chroma_error::{ChromaError, ErrorCodes};
+use prost::Message;
 use thiserror::Error;
 
 #[derive(Clone, Debug)]
@@ -17,9 +18,63 @@ pub struct OperationRecord {
     // This is ugly and clunky. In the rust code we choose to make it a separate field and
     // only let that concept live in the transport layer
     pub document: Option<String>,
+    // Uri is implemented in the python code as a special key "chroma:uri" in the metadata,
+    // and is extracted into a separate field in the same way as the document
+    pub uri: Option<String>,
+    // Idempotency key is sent as a special key "chroma:idempotency_key" in the metadata. Records
+    // of a retried write carry the same key, so that the log materializer can skip them
+    pub idempotency_key: Option<String>,
+    // The embedding is stored in an object instead of in the log if this is set, and it is
+    // resolved into `embedding` when the logs are compacted
+    pub embedding_reference: Option<EmbeddingReference>,
     pub operation: Operation,
 }
 
+/// A reference to an embedding that is stored in an object rather than in the log, e.g. a row
+/// of a .npy file written by a batch job
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EmbeddingReference {
+    pub uri: String,
+    pub row: u32,
+    pub dimension: usize,
+}
+
+impl OperationRecord {
+    /// Returns the approximate size in bytes of the record, as it is written to the log
+    pub fn get_size(&self) -> usize {
+        let id_size = self.id.len();
+        let embedding_size = match &self.embedding {
+            Some(embedding) => std::mem::size_of_val(embedding.as_slice()),
+            None => 0,
+        };
+        let metadata_size = match &self.metadata {
+            Some(metadata) => {
+                let metadata_proto = Into::<chroma_proto::UpdateMetadata>::into(metadata.clone());
+                metadata_proto.encoded_len()
+            }
+            None => 0,
+        };
+        let document_size = match &self.document {
+            Some(document) => document.len(),
+            None => 0,
+        };
+        let uri_size = match &self.uri {
+            Some(uri) => uri.len(),
+            None => 0,
+        };
+        let embedding_reference_size = match &self.embedding_reference {
+            Some(reference) => reference.uri.len() + 2 * std::mem::size_of::<u32>(),
+            None => 0,
+        };
+        id_size
+            + embedding_size
+            + metadata_size
+            + document_size
+            + uri_size
+            + embedding_reference_size
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct LogRecord {
     pub log_offset: i64,
@@ -40,10 +95,16 @@ pub enum RecordConversionError {
     UpdateMetadataValueConversionError(#[from] UpdateMetadataValueConversionError),
     #[error(transparent)]
     VectorConversionError(#[from] VectorConversionError),
+    #[error("Record has both a vector and a vector reference")]
+    VectorAndReference,
+    #[error("Invalid vector reference: {0}")]
+    InvalidVectorReference(String),
 }
 
 impl_base_convert_error!(RecordConversionError, {
     RecordConversionError::InvalidUuid => ErrorCodes::InvalidArgument,
+    RecordConversionError::VectorAndReference => ErrorCodes::InvalidArgument,
+    RecordConversionError::InvalidVectorReference(_) => ErrorCodes::InvalidArgument,
     RecordConversionError::OperationConversionError(inner) => inner.code(),
     RecordConversionError::ScalarEncodingConversionError(inner) => inner.code(),
     RecordConversionError::UpdateMetadataValueConversionError(inner) => inner.code(),
@@ -70,20 +131,49 @@ impl TryFrom<chroma_proto::OperationRecord> for OperationRecord {
             None => (None, None),
         };
 
-        let (metadata, document) = match operation_record_proto.metadata {
+        let embedding_reference = match operation_record_proto.vector_reference {
+            Some(_) if embedding.is_some() => {
+                return Err(RecordConversionError::VectorAndReference);
+            }
+            Some(reference) => {
+                let dimension = usize::try_from(reference.dimension)
+                    .ok()
+                    .filter(|dimension| *dimension > 0)
+                    .ok_or_else(|| {
+                        RecordConversionError::InvalidVectorReference(format!(
+                            "dimension {} of {}",
+                            reference.dimension, reference.uri
+                        ))
+                    })?;
+                Some(EmbeddingReference {
+                    uri: reference.uri,
+                    row: reference.row,
+                    dimension,
+                })
+            }
+            None => None,
+        };
+
+        let (metadata, document, uri, idempotency_key) = match operation_record_proto.metadata {
             Some(proto_metadata) => match UpdateMetadata::try_from(proto_metadata) {
                 Ok(mut metadata) => {
-                    let document = metadata.remove("chroma:document");
-                    match document {
-                        Some(UpdateMetadataValue::Str(document)) => {
-                            (Some(metadata), Some(document))
-                        }
-                        _ => (Some(metadata), None),
-                    }
+                    let document = match metadata.remove("chroma:document") {
+                        Some(UpdateMetadataValue::Str(document)) => Some(document),
+                        _ => None,
+                    };
+                    let uri = match metadata.remove("chroma:uri") {
+                        Some(UpdateMetadataValue::Str(uri)) => Some(uri),
+                        _ => None,
+                    };
+                    let idempotency_key = match metadata.remove("chroma:idempotency_key") {
+                        Some(UpdateMetadataValue::Str(key)) if !key.is_empty() => Some(key),
+                        _ => None,
+                    };
+                    (Some(metadata), document, uri, idempotency_key)
                 }
                 Err(e) => return Err(RecordConversionError::UpdateMetadataValueConversionError(e)),
             },
-            None => (None, None),
+            None => (None, None, None, None),
         };
 
         Ok(OperationRecord {
@@ -92,6 +182,9 @@ impl TryFrom<chroma_proto::OperationRecord> for OperationRecord {
             encoding,
             metadata,
             document,
+            uri,
+            idempotency_key,
+            embedding_reference,
             operation,
         })
     }
@@ -261,6 +354,8 @@ impl From<MetadataEmbeddingRecord> for chroma_proto::MetadataEmbeddingRecord {
         chroma_proto::MetadataEmbeddingRecord {
             id: record.id,
             metadata: Some(record.metadata.into()),
+            embedding: None,
+            embedding_handle: None,
         }
     }
 }
@@ -301,6 +396,26 @@ mod tests {
             },
         );
 
+        // Insert a chroma:uri field
+        metadata.metadata.insert(
+            "chroma:uri".to_string(),
+            chroma_proto::UpdateMetadataValue {
+                value: Some(chroma_proto::update_metadata_value::Value::StringValue(
+                    "s3://bucket/key".to_string(),
+                )),
+            },
+        );
+
+        // Insert a chroma:idempotency_key field
+        metadata.metadata.insert(
+            "chroma:idempotency_key".to_string(),
+            chroma_proto::UpdateMetadataValue {
+                value: Some(chroma_proto::update_metadata_value::Value::StringValue(
+                    "batch-1".to_string(),
+                )),
+            },
+        );
+
         let proto_vector = chroma_proto::Vector {
             vector: as_byte_view(&[1.0, 2.0, 3.0]),
             encoding: chroma_proto::ScalarEncoding::Float32 as i32,
@@ -311,6 +426,7 @@ mod tests {
             vector: Some(proto_vector),
             metadata: Some(metadata),
             operation: chroma_proto::Operation::Add as i32,
+            vector_reference: None,
         };
         let converted_operation_record = OperationRecord::try_from(proto_submit).unwrap();
         assert_eq!(converted_operation_record.id, Uuid::nil().to_string());
@@ -326,13 +442,23 @@ mod tests {
             converted_operation_record.document,
             Some("document_contents".to_string())
         );
+        assert_eq!(
+            converted_operation_record.uri,
+            Some("s3://bucket/key".to_string())
+        );
+        assert_eq!(
+            converted_operation_record.idempotency_key,
+            Some("batch-1".to_string())
+        );
         let metadata = converted_operation_record.metadata.unwrap();
         assert_eq!(metadata.len(), 1);
         assert_eq!(metadata.get("foo").unwrap(), &UpdateMetadataValue::Int(42));
         assert_eq!(converted_operation_record.operation, Operation::Add);
 
-        // Ensure metadata no longer has the document field
+        // Ensure metadata no longer has the document, uri and idempotency key fields
         assert_eq!(metadata.get("chroma:document"), None);
+        assert_eq!(metadata.get("chroma:uri"), None);
+        assert_eq!(metadata.get("chroma:idempotency_key"), None);
     }
 
     #[test]
@@ -356,6 +482,7 @@ mod tests {
             vector: Some(proto_vector),
             metadata: Some(metadata),
             operation: chroma_proto::Operation::Add as i32,
+            vector_reference: None,
         };
         let record_log = chroma_proto::LogRecord {
             log_offset: 42,
@@ -377,4 +504,47 @@ mod tests {
         assert_eq!(metadata.get("foo").unwrap(), &UpdateMetadataValue::Int(42));
         assert_eq!(converted_log_record.record.operation, Operation::Add);
     }
+
+    #[test]
+    fn test_operation_record_with_vector_reference() {
+        let reference = chroma_proto::VectorReference {
+            uri: "s3://bucket/vectors.npy".to_string(),
+            row: 7,
+            dimension: 3,
+        };
+        let proto_submit = chroma_proto::OperationRecord {
+            id: "00000000-0000-0000-0000-000000000000".to_string(),
+            vector: None,
+            metadata: None,
+            operation: chroma_proto::Operation::Add as i32,
+            vector_reference: Some(reference.clone()),
+        };
+        let converted_operation_record = OperationRecord::try_from(proto_submit).unwrap();
+        assert_eq!(converted_operation_record.embedding, None);
+        assert_eq!(
+            converted_operation_record.embedding_reference,
+            Some(EmbeddingReference {
+                uri: "s3://bucket/vectors.npy".to_string(),
+                row: 7,
+                dimension: 3,
+            })
+        );
+
+        // A record may not have both a vector and a reference
+        let proto_submit = chroma_proto::OperationRecord {
+            id: "00000000-0000-0000-0000-000000000000".to_string(),
+            vector: Some(chroma_proto::Vector {
+                vector: as_byte_view(&[1.0, 2.0, 3.0]),
+                encoding: chroma_proto::ScalarEncoding::Float32 as i32,
+                dimension: 3,
+            }),
+            metadata: None,
+            operation: chroma_proto::Operation::Add as i32,
+            vector_reference: Some(reference),
+        };
+        assert!(matches!(
+            OperationRecord::try_from(proto_submit),
+            Err(RecordConversionError::VectorAndReference)
+        ));
+    }
 }
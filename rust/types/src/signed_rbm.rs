@@ -1,6 +1,10 @@
 use std::ops::{BitAnd, BitOr};
 
+use chroma_error::{ChromaError, ErrorCodes};
 use roaring::RoaringBitmap;
+use thiserror::Error;
+
+use crate::chroma_proto;
 
 /// This enum helps to delay the evaluation of set minus in metadata filtering:
 /// - `Include(rbm)` suggests the result contains the specified ids in `rbm`.
@@ -48,6 +52,13 @@ impl SignedRoaringBitmap {
             Exclude(rbm) => Include(rbm),
         }
     }
+
+    pub fn contains(&self, offset_id: u32) -> bool {
+        match self {
+            SignedRoaringBitmap::Include(rbm) => rbm.contains(offset_id),
+            SignedRoaringBitmap::Exclude(rbm) => !rbm.contains(offset_id),
+        }
+    }
 }
 
 impl BitAnd for SignedRoaringBitmap {
@@ -77,3 +88,109 @@ impl BitOr for SignedRoaringBitmap {
         }
     }
 }
+
+/// Serializes the bitmap in the portable format of roaring bitmaps
+pub fn serialize_roaring_bitmap(rbm: &RoaringBitmap) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(rbm.serialized_size());
+    rbm.serialize_into(&mut bytes)
+        .expect("Writing to a vector should not fail");
+    bytes
+}
+
+pub fn deserialize_roaring_bitmap(
+    bytes: &[u8],
+) -> Result<RoaringBitmap, RoaringBitmapConversionError> {
+    Ok(RoaringBitmap::deserialize_from(bytes)?)
+}
+
+#[derive(Error, Debug)]
+pub enum RoaringBitmapConversionError {
+    #[error("Invalid roaring bitmap: {0}")]
+    InvalidBitmap(#[from] std::io::Error),
+    #[error("Missing roaring bitmap")]
+    MissingBitmap,
+}
+
+impl ChromaError for RoaringBitmapConversionError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            RoaringBitmapConversionError::InvalidBitmap(_) => ErrorCodes::InvalidArgument,
+            RoaringBitmapConversionError::MissingBitmap => ErrorCodes::InvalidArgument,
+        }
+    }
+}
+
+impl From<&SignedRoaringBitmap> for chroma_proto::SignedRoaringBitmap {
+    fn from(value: &SignedRoaringBitmap) -> Self {
+        use chroma_proto::signed_roaring_bitmap::Bitmap;
+        let bitmap = match value {
+            SignedRoaringBitmap::Include(rbm) => Bitmap::Include(serialize_roaring_bitmap(rbm)),
+            SignedRoaringBitmap::Exclude(rbm) => Bitmap::Exclude(serialize_roaring_bitmap(rbm)),
+        };
+        chroma_proto::SignedRoaringBitmap {
+            bitmap: Some(bitmap),
+        }
+    }
+}
+
+impl TryFrom<chroma_proto::SignedRoaringBitmap> for SignedRoaringBitmap {
+    type Error = RoaringBitmapConversionError;
+
+    fn try_from(value: chroma_proto::SignedRoaringBitmap) -> Result<Self, Self::Error> {
+        use chroma_proto::signed_roaring_bitmap::Bitmap;
+        match value.bitmap {
+            Some(Bitmap::Include(bytes)) => Ok(SignedRoaringBitmap::Include(
+                deserialize_roaring_bitmap(&bytes)?,
+            )),
+            Some(Bitmap::Exclude(bytes)) => Ok(SignedRoaringBitmap::Exclude(
+                deserialize_roaring_bitmap(&bytes)?,
+            )),
+            None => Err(RoaringBitmapConversionError::MissingBitmap),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signed_roaring_bitmap_proto_roundtrip() {
+        let include = SignedRoaringBitmap::Include((0..100).chain(1000..1010).collect());
+        let proto_include = chroma_proto::SignedRoaringBitmap::from(&include);
+        assert!(matches!(
+            proto_include.bitmap,
+            Some(chroma_proto::signed_roaring_bitmap::Bitmap::Include(_))
+        ));
+        assert_eq!(
+            SignedRoaringBitmap::try_from(proto_include).unwrap(),
+            include
+        );
+
+        let exclude = SignedRoaringBitmap::full();
+        let proto_exclude = chroma_proto::SignedRoaringBitmap::from(&exclude);
+        assert_eq!(
+            SignedRoaringBitmap::try_from(proto_exclude).unwrap(),
+            exclude
+        );
+    }
+
+    #[test]
+    fn test_signed_roaring_bitmap_invalid_proto() {
+        let missing = chroma_proto::SignedRoaringBitmap { bitmap: None };
+        assert!(matches!(
+            SignedRoaringBitmap::try_from(missing),
+            Err(RoaringBitmapConversionError::MissingBitmap)
+        ));
+
+        let invalid = chroma_proto::SignedRoaringBitmap {
+            bitmap: Some(chroma_proto::signed_roaring_bitmap::Bitmap::Exclude(vec![
+                1, 2, 3,
+            ])),
+        };
+        assert!(matches!(
+            SignedRoaringBitmap::try_from(invalid),
+            Err(RoaringBitmapConversionError::InvalidBitmap(_))
+        ));
+    }
+}
@@ -1,3 +1,5 @@
+#![recursion_limit = "256"]
+
 use std::iter::once;
 
 use chroma_benchmark::benchmark::{bench_run, tokio_multi_thread};
@@ -7,10 +9,13 @@ use chroma_types::{
 };
 use criterion::Criterion;
 use criterion::{criterion_group, criterion_main};
+use worker::execution::memory::MemoryContext;
 use worker::execution::operator::Operator;
 use worker::execution::operators::filter::{FilterInput, FilterOperator};
 use worker::log::test::{upsert_generator, LogGenerator};
+use worker::segment::offset_id_cache::OffsetIdCache;
 use worker::segment::test::TestSegment;
+use worker::segment::types::SharedMaterializedLogs;
 
 fn baseline_where_clauses() -> Vec<(&'static str, Option<Where>)> {
     use BooleanOperator::*;
@@ -84,9 +89,14 @@ fn bench_filter(criterion: &mut Criterion) {
 
         let filter_input = FilterInput {
             logs: Chunk::new(Vec::new().into()),
+            fetch_log: None,
             blockfile_provider: test_segment.blockfile_provider,
             metadata_segment: test_segment.metadata_segment,
             record_segment: test_segment.record_segment,
+            materialized_logs: SharedMaterializedLogs::default(),
+            memory: MemoryContext::default(),
+            offset_id_cache: OffsetIdCache::default(),
+            scan_limit: None,
         };
 
         for (op, where_clause) in baseline_where_clauses() {
@@ -1,11 +1,15 @@
+#![recursion_limit = "256"]
+
 use chroma_benchmark::benchmark::{bench_run, tokio_multi_thread};
 use chroma_types::{Chunk, SignedRoaringBitmap};
 use criterion::Criterion;
 use criterion::{criterion_group, criterion_main};
-use worker::execution::operator::Operator;
+use worker::execution::memory::MemoryContext;
+use worker::execution::operator::{Deadline, Operator};
 use worker::execution::operators::limit::{LimitInput, LimitOperator};
 use worker::log::test::{upsert_generator, LogGenerator};
 use worker::segment::test::TestSegment;
+use worker::segment::types::SharedMaterializedLogs;
 
 const FETCH: usize = 100;
 
@@ -28,6 +32,9 @@ fn bench_limit(criterion: &mut Criterion) {
             record_segment: test_segment.record_segment,
             log_offset_ids: SignedRoaringBitmap::empty(),
             compact_offset_ids: SignedRoaringBitmap::full(),
+            materialized_logs: SharedMaterializedLogs::default(),
+            deadline: Deadline::default(),
+            memory: MemoryContext::default(),
         };
 
         for offset in [0, record_count / 2, record_count - FETCH] {
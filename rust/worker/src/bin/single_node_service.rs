@@ -0,0 +1,6 @@
+use worker::single_node_entrypoint;
+
+#[tokio::main]
+async fn main() {
+    single_node_entrypoint().await;
+}
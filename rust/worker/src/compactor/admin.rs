@@ -0,0 +1,127 @@
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use chroma_types::chroma_proto::{
+    self, ErrorDetails, PauseCompactionRequest, PauseCompactionResponse, ResumeCompactionRequest,
+    ResumeCompactionResponse,
+};
+use chroma_types::CollectionUuid;
+use tokio::signal::unix::{signal, SignalKind};
+use tonic::{transport::Server, Code, Request, Response, Status};
+
+use super::pause::CompactionPauses;
+
+/// Serves the admin RPCs of the compactor, which let operators pause and resume the compaction
+/// of a collection during an incident without stopping the compactor
+#[derive(Clone, Debug)]
+pub(crate) struct CompactionAdminService {
+    pauses: CompactionPauses,
+}
+
+impl CompactionAdminService {
+    pub(crate) fn new(pauses: CompactionPauses) -> Self {
+        Self { pauses }
+    }
+
+    pub(crate) fn into_server(
+        self,
+    ) -> chroma_proto::compaction_admin_server::CompactionAdminServer<Self> {
+        chroma_proto::compaction_admin_server::CompactionAdminServer::new(self)
+    }
+
+    pub(crate) async fn run(self, port: u16) -> Result<(), Box<dyn std::error::Error>> {
+        let addr = format!("[::]:{}", port).parse().unwrap();
+        tracing::info!("Compaction admin listening on {}", addr);
+        Server::builder()
+            .add_service(self.into_server())
+            .serve_with_shutdown(addr, async {
+                let mut sigterm = match signal(SignalKind::terminate()) {
+                    Ok(sigterm) => sigterm,
+                    Err(e) => {
+                        tracing::error!("Failed to create signal handler: {:?}", e);
+                        return;
+                    }
+                };
+                sigterm.recv().await;
+                tracing::info!("Received SIGTERM, shutting down");
+            })
+            .await?;
+        Ok(())
+    }
+}
+
+fn to_collection_uuid(collection_id: &str) -> Result<CollectionUuid, Status> {
+    CollectionUuid::from_str(collection_id).map_err(|_| {
+        ErrorDetails::invalid_argument("collection_id").into_status(
+            Code::InvalidArgument,
+            format!("Invalid Collection UUID: {}", collection_id),
+        )
+    })
+}
+
+#[async_trait]
+impl chroma_proto::compaction_admin_server::CompactionAdmin for CompactionAdminService {
+    async fn pause_compaction(
+        &self,
+        request: Request<PauseCompactionRequest>,
+    ) -> Result<Response<PauseCompactionResponse>, Status> {
+        let collection_uuid = to_collection_uuid(&request.get_ref().collection_id)?;
+        let already_paused = self.pauses.pause(collection_uuid);
+        tracing::info!("Paused compaction of collection {}", collection_uuid);
+        Ok(Response::new(PauseCompactionResponse { already_paused }))
+    }
+
+    async fn resume_compaction(
+        &self,
+        request: Request<ResumeCompactionRequest>,
+    ) -> Result<Response<ResumeCompactionResponse>, Status> {
+        let collection_uuid = to_collection_uuid(&request.get_ref().collection_id)?;
+        let was_paused = self.pauses.resume(collection_uuid);
+        tracing::info!("Resumed compaction of collection {}", collection_uuid);
+        Ok(Response::new(ResumeCompactionResponse { was_paused }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chroma_types::chroma_proto::{
+        compaction_admin_server::CompactionAdmin, PauseCompactionRequest, ResumeCompactionRequest,
+    };
+    use chroma_types::CollectionUuid;
+    use tonic::{Code, Request};
+
+    use super::{CompactionAdminService, CompactionPauses};
+
+    #[tokio::test]
+    async fn test_pause_and_resume_compaction() {
+        let pauses = CompactionPauses::default();
+        let admin = CompactionAdminService::new(pauses.clone());
+        let collection_id = CollectionUuid::new();
+
+        let response = admin
+            .pause_compaction(Request::new(PauseCompactionRequest {
+                collection_id: collection_id.to_string(),
+            }))
+            .await
+            .expect("Pause should succeed");
+        assert!(!response.get_ref().already_paused);
+        assert!(pauses.is_paused(collection_id));
+
+        let response = admin
+            .resume_compaction(Request::new(ResumeCompactionRequest {
+                collection_id: collection_id.to_string(),
+            }))
+            .await
+            .expect("Resume should succeed");
+        assert!(response.get_ref().was_paused);
+        assert!(!pauses.is_paused(collection_id));
+
+        let err = admin
+            .pause_compaction(Request::new(PauseCompactionRequest {
+                collection_id: "not a uuid".to_string(),
+            }))
+            .await
+            .expect_err("An invalid collection id should be rejected");
+        assert_eq!(err.code(), Code::InvalidArgument);
+    }
+}
@@ -1,13 +1,19 @@
+use super::pause::CompactionPauses;
 use super::scheduler::Scheduler;
+use super::scheduler_policy::CompactionTriggerPolicy;
 use super::scheduler_policy::LasCompactionTimeSchedulerPolicy;
 use crate::compactor::types::CompactionJob;
 use crate::compactor::types::ScheduleMessage;
 use crate::config::CompactionServiceConfig;
+use crate::execution::decode_executor::DispatcherDecodeExecutor;
 use crate::execution::dispatcher::Dispatcher;
+use crate::execution::operators::fetch_embeddings::ExternalEmbeddingStorage;
 use crate::execution::orchestration::CompactOrchestrator;
 use crate::execution::orchestration::CompactionResponse;
+use crate::garbage_collector::{GarbageCollector, GarbageCollectorConfig};
 use crate::log::log::Log;
 use crate::memberlist::Memberlist;
+use crate::memory_watchdog::MemoryWatchdog;
 use crate::sysdb;
 use crate::sysdb::sysdb::SysDb;
 use crate::system::{Component, ComponentContext, ComponentHandle, Handler, System};
@@ -41,6 +47,7 @@ pub(crate) struct CompactionManager {
     storage: Storage,
     blockfile_provider: BlockfileProvider,
     hnsw_index_provider: HnswIndexProvider,
+    external_embeddings: Option<ExternalEmbeddingStorage>,
     // Dispatcher
     dispatcher: Option<ComponentHandle<Dispatcher>>,
     // Config
@@ -50,6 +57,9 @@ pub(crate) struct CompactionManager {
     min_compaction_size: usize,
     max_compaction_size: usize,
     max_partition_size: usize,
+    idempotency_key_ttl: Duration,
+    // Collections whose compaction is paused by an operator
+    pauses: CompactionPauses,
 }
 
 #[derive(Error, Debug)]
@@ -80,6 +90,7 @@ impl CompactionManager {
         min_compaction_size: usize,
         max_compaction_size: usize,
         max_partition_size: usize,
+        idempotency_key_ttl: Duration,
     ) -> Self {
         CompactionManager {
             system: None,
@@ -89,15 +100,43 @@ impl CompactionManager {
             storage,
             blockfile_provider,
             hnsw_index_provider,
+            external_embeddings: None,
             dispatcher: None,
             compaction_manager_queue_size,
             compaction_interval,
             min_compaction_size,
             max_compaction_size,
             max_partition_size,
+            idempotency_key_ttl,
+            pauses: CompactionPauses::default(),
         }
     }
 
+    /// The pauses that the compactions of this manager check, which are shared with the admin
+    /// service of the compactor
+    pub(crate) fn pauses(&self) -> CompactionPauses {
+        self.pauses.clone()
+    }
+
+    /// Adds the caches of the compactions to the memory watchdog, which pauses them under
+    /// pressure
+    pub(crate) fn watch_memory(&self, watchdog: MemoryWatchdog) -> MemoryWatchdog {
+        watchdog
+            .with_blockfile_provider(self.blockfile_provider.clone())
+            .with_hnsw_index_provider(self.hnsw_index_provider.clone())
+            .with_compaction_pauses(self.pauses.clone())
+    }
+
+    /// Returns the garbage collector of the blockfiles and HNSW indexes that the compactions write
+    pub(crate) fn garbage_collector(&self, config: &GarbageCollectorConfig) -> GarbageCollector {
+        GarbageCollector::new(
+            config,
+            self.sysdb.clone(),
+            self.blockfile_provider.clone(),
+            self.hnsw_index_provider.clone(),
+        )
+    }
+
     #[instrument(name = "CompactionManager::compact")]
     async fn compact(
         &self,
@@ -121,12 +160,15 @@ impl CompactionManager {
                     self.sysdb.clone(),
                     self.blockfile_provider.clone(),
                     self.hnsw_index_provider.clone(),
+                    self.external_embeddings.clone(),
                     dispatcher,
                     None,
                     None,
                     Arc::new(AtomicU32::new(0)),
                     self.max_compaction_size,
                     self.max_partition_size,
+                    self.idempotency_key_ttl,
+                    self.pauses.clone(),
                 );
 
                 match orchestrator.run().await {
@@ -156,6 +198,15 @@ impl CompactionManager {
         self.scheduler.schedule().await;
         let mut jobs = FuturesUnordered::new();
         for job in self.scheduler.get_jobs() {
+            // A paused collection is compacted by a later batch once it is resumed, and so are
+            // the collections that are held back while the worker is under memory pressure
+            if !self.pauses.admits(job.collection_id) {
+                tracing::info!(
+                    "Skipping compaction of paused collection {}",
+                    job.collection_id
+                );
+                continue;
+            }
             let instrumented_span = span!(parent: None, tracing::Level::INFO, "Compacting job", collection_id = ?job.collection_id);
             instrumented_span.follows_from(Span::current());
             jobs.push(self.compact(job).instrument(instrumented_span));
@@ -171,6 +222,11 @@ impl CompactionManager {
                     compacted.push(result.compaction_job.collection_id);
                     num_completed_jobs += 1;
                 }
+                // The compaction of a collection that is paused while it runs is aborted, and
+                // the collection is scheduled again by a later batch
+                Err(e) if e.code() == ErrorCodes::Aborted => {
+                    tracing::info!("Compaction aborted: {:?}", e);
+                }
                 Err(e) => {
                     println!("Compaction failed: {:?}", e);
                     num_failed_jobs += 1;
@@ -181,12 +237,21 @@ impl CompactionManager {
     }
 
     pub(crate) fn set_dispatcher(&mut self, dispatcher: ComponentHandle<Dispatcher>) {
+        self.blockfile_provider
+            .set_decode_executor(Arc::new(DispatcherDecodeExecutor::new(&dispatcher)));
         self.dispatcher = Some(dispatcher);
     }
 
     pub(crate) fn set_system(&mut self, system: System) {
         self.system = Some(system);
     }
+
+    pub(crate) fn set_external_embeddings(
+        &mut self,
+        external_embeddings: Option<ExternalEmbeddingStorage>,
+    ) {
+        self.external_embeddings = external_embeddings;
+    }
 }
 
 #[async_trait]
@@ -224,6 +289,7 @@ impl Configurable<CompactionServiceConfig> for CompactionManager {
         let min_compaction_size = config.compactor.min_compaction_size;
         let max_compaction_size = config.compactor.max_compaction_size;
         let max_partition_size = config.compactor.max_partition_size;
+        let idempotency_key_ttl = Duration::from_secs(config.compactor.idempotency_key_ttl_sec);
 
         let assignment_policy_config = &config.assignment_policy;
         let assignment_policy = match crate::assignment::from_config(assignment_policy_config).await
@@ -233,13 +299,18 @@ impl Configurable<CompactionServiceConfig> for CompactionManager {
                 return Err(err);
             }
         };
+        let trigger_policy = CompactionTriggerPolicy {
+            max_dirty_records: min_compaction_size as u64,
+            max_dirty_bytes: config.compactor.max_dirty_bytes,
+            max_staleness: config.compactor.max_staleness_sec.map(Duration::from_secs),
+        };
         let scheduler = Scheduler::new(
             my_ip,
             log.clone(),
             sysdb.clone(),
             policy,
             max_concurrent_jobs,
-            min_compaction_size,
+            trigger_policy,
             assignment_policy,
         );
 
@@ -253,7 +324,15 @@ impl Configurable<CompactionServiceConfig> for CompactionManager {
             HnswIndexProvider::try_from_config(&(config.hnsw_provider.clone(), storage.clone()))
                 .await?;
 
-        Ok(CompactionManager::new(
+        let external_embeddings = match &config.compactor.external_embeddings {
+            Some(external_embeddings) => Some(ExternalEmbeddingStorage {
+                bucket: external_embeddings.bucket.clone(),
+                storage: chroma_storage::from_config(&external_embeddings.storage).await?,
+            }),
+            None => None,
+        };
+
+        let mut manager = CompactionManager::new(
             scheduler,
             log,
             sysdb,
@@ -265,7 +344,10 @@ impl Configurable<CompactionServiceConfig> for CompactionManager {
             min_compaction_size,
             max_compaction_size,
             max_partition_size,
-        ))
+            idempotency_key_ttl,
+        );
+        manager.set_external_embeddings(external_embeddings);
+        Ok(manager)
     }
 }
 
@@ -346,8 +428,14 @@ mod tests {
     use std::path::PathBuf;
     use std::str::FromStr;
 
-    #[tokio::test]
-    async fn test_compaction_manager() {
+    // Sets up a manager with two collections that have one record each to compact. The
+    // returned directory holds the storage of the manager
+    async fn setup_compaction_manager() -> (
+        CompactionManager,
+        CollectionUuid,
+        CollectionUuid,
+        tempfile::TempDir,
+    ) {
         let mut log = Box::new(Log::InMemory(InMemoryLog::new()));
         let in_memory_log = match *log {
             Log::InMemory(ref mut log) => log,
@@ -372,6 +460,9 @@ mod tests {
                         encoding: None,
                         metadata: None,
                         document: None,
+                        uri: None,
+                        idempotency_key: None,
+                        embedding_reference: None,
                         operation: Operation::Add,
                     },
                 },
@@ -394,6 +485,9 @@ mod tests {
                         encoding: None,
                         metadata: None,
                         document: None,
+                        uri: None,
+                        idempotency_key: None,
+                        embedding_reference: None,
                         operation: Operation::Add,
                     },
                 },
@@ -510,6 +604,7 @@ mod tests {
         let min_compaction_size = 0;
         let max_compaction_size = 1000;
         let max_partition_size = 1000;
+        let idempotency_key_ttl = Duration::from_secs(60);
 
         // Set assignment policy
         let mut assignment_policy = Box::new(RendezvousHashingAssignmentPolicy::new());
@@ -521,7 +616,11 @@ mod tests {
             sysdb.clone(),
             Box::new(LasCompactionTimeSchedulerPolicy {}),
             max_concurrent_jobs,
-            min_compaction_size,
+            CompactionTriggerPolicy {
+                max_dirty_records: min_compaction_size as u64,
+                max_dirty_bytes: None,
+                max_staleness: None,
+            },
             assignment_policy,
         );
         // Set memberlist
@@ -553,14 +652,22 @@ mod tests {
             min_compaction_size,
             max_compaction_size,
             max_partition_size,
+            idempotency_key_ttl,
         );
 
         let system = System::new();
 
-        let dispatcher = Dispatcher::new(10, 10, 10);
+        let dispatcher = Dispatcher::new(10, 0, 10, 10);
         let dispatcher_handle = system.start_component(dispatcher);
         manager.set_dispatcher(dispatcher_handle);
         manager.set_system(system);
+        (manager, collection_uuid_1, collection_uuid_2, tmpdir)
+    }
+
+    #[tokio::test]
+    async fn test_compaction_manager() {
+        let (mut manager, collection_uuid_1, collection_uuid_2, _tmpdir) =
+            setup_compaction_manager().await;
         let mut compacted = vec![];
         let (num_completed, number_failed) = manager.compact_batch(&mut compacted).await;
         assert_eq!(num_completed, 2);
@@ -570,4 +677,23 @@ mod tests {
                 || (compacted == vec![collection_uuid_2, collection_uuid_1])
         );
     }
+
+    #[tokio::test]
+    async fn test_compaction_manager_pause() {
+        let (mut manager, collection_uuid_1, collection_uuid_2, _tmpdir) =
+            setup_compaction_manager().await;
+
+        // A paused collection is left to a later batch without holding up the others
+        manager.pauses().pause(collection_uuid_1);
+        let mut compacted = vec![];
+        let (num_completed, number_failed) = manager.compact_batch(&mut compacted).await;
+        assert_eq!(num_completed, 1);
+        assert_eq!(number_failed, 0);
+        assert_eq!(compacted, vec![collection_uuid_2]);
+
+        manager.pauses().resume(collection_uuid_1);
+        let mut compacted = vec![];
+        manager.compact_batch(&mut compacted).await;
+        assert!(compacted.contains(&collection_uuid_1));
+    }
 }
@@ -1,3 +1,4 @@
+use chroma_storage::config::StorageConfig;
 use serde::Deserialize;
 
 #[derive(Deserialize)]
@@ -6,6 +7,34 @@ pub(crate) struct CompactorConfig {
     pub(crate) max_concurrent_jobs: usize,
     pub(crate) compaction_interval_sec: u64,
     pub(crate) min_compaction_size: usize,
+    // The size in bytes and the age in seconds of the new log records that trigger a
+    // compaction, in addition to `min_compaction_size` records. Collections can override
+    // all three thresholds in their metadata.
+    #[serde(default)]
+    pub(crate) max_dirty_bytes: Option<u64>,
+    #[serde(default)]
+    pub(crate) max_staleness_sec: Option<u64>,
     pub(crate) max_compaction_size: usize,
     pub(crate) max_partition_size: usize,
+    #[serde(default = "CompactorConfig::default_idempotency_key_ttl_sec")]
+    pub(crate) idempotency_key_ttl_sec: u64,
+    // The bucket that the embeddings referenced by the logs are read from. Logs that
+    // reference their embeddings fail to compact if it is not set.
+    #[serde(default)]
+    pub(crate) external_embeddings: Option<ExternalEmbeddingsConfig>,
+}
+
+/// A bucket dedicated to the embeddings that the logs reference. Its storage has credentials
+/// of its own, separate from the storage of the worker, and the references may only name
+/// this bucket
+#[derive(Deserialize, Clone, Debug)]
+pub(crate) struct ExternalEmbeddingsConfig {
+    pub(crate) bucket: String,
+    pub(crate) storage: StorageConfig,
+}
+
+impl CompactorConfig {
+    fn default_idempotency_key_ttl_sec() -> u64 {
+        24 * 60 * 60
+    }
 }
@@ -0,0 +1,110 @@
+use std::{sync::OnceLock, time::Duration};
+
+use chroma_blockstore::BlockfileFlusher;
+use chroma_types::{CollectionUuid, SegmentType};
+use opentelemetry::{
+    global,
+    metrics::{Counter, Histogram},
+    KeyValue,
+};
+
+/// Metrics of the compaction write path, labeled by collection
+pub(crate) struct CompactionMetrics {
+    records_materialized: Counter<u64>,
+    records_materialized_per_second: Histogram<f64>,
+    hnsw_inserts: Counter<u64>,
+    hnsw_inserts_per_second: Histogram<f64>,
+    blocks_written: Counter<u64>,
+    bytes_flushed: Counter<u64>,
+}
+
+impl CompactionMetrics {
+    fn new() -> Self {
+        let meter = global::meter("chroma");
+        Self {
+            records_materialized: meter
+                .u64_counter("compactor_records_materialized")
+                .with_description("Number of log records materialized by the compactor")
+                .init(),
+            records_materialized_per_second: meter
+                .f64_histogram("compactor_records_materialized_per_second")
+                .with_description("Throughput of log materialization per partition")
+                .init(),
+            hnsw_inserts: meter
+                .u64_counter("compactor_hnsw_inserts")
+                .with_description("Number of embeddings inserted into HNSW indices")
+                .init(),
+            hnsw_inserts_per_second: meter
+                .f64_histogram("compactor_hnsw_inserts_per_second")
+                .with_description("Throughput of HNSW inserts per partition")
+                .init(),
+            blocks_written: meter
+                .u64_counter("compactor_blocks_written")
+                .with_description("Number of blocks flushed to storage")
+                .init(),
+            bytes_flushed: meter
+                .u64_counter("compactor_bytes_flushed")
+                .with_description("Number of block bytes flushed to storage")
+                .init(),
+        }
+    }
+
+    /// Returns the compaction metrics, registering them with the global meter on first use
+    pub(crate) fn get() -> &'static Self {
+        static METRICS: OnceLock<CompactionMetrics> = OnceLock::new();
+        METRICS.get_or_init(Self::new)
+    }
+
+    pub(crate) fn record_materialization(
+        &self,
+        collection_id: CollectionUuid,
+        records: usize,
+        elapsed: Duration,
+    ) {
+        let labels = [KeyValue::new("collection_id", collection_id.to_string())];
+        self.records_materialized.add(records as u64, &labels);
+        if let Some(rate) = per_second(records, elapsed) {
+            self.records_materialized_per_second.record(rate, &labels);
+        }
+    }
+
+    pub(crate) fn record_hnsw_inserts(
+        &self,
+        collection_id: CollectionUuid,
+        inserts: usize,
+        elapsed: Duration,
+    ) {
+        let labels = [KeyValue::new("collection_id", collection_id.to_string())];
+        self.hnsw_inserts.add(inserts as u64, &labels);
+        if let Some(rate) = per_second(inserts, elapsed) {
+            self.hnsw_inserts_per_second.record(rate, &labels);
+        }
+    }
+
+    pub(crate) fn record_flush(
+        &self,
+        collection_id: CollectionUuid,
+        segment_type: SegmentType,
+        flushers: &[&BlockfileFlusher],
+    ) {
+        let labels = [
+            KeyValue::new("collection_id", collection_id.to_string()),
+            KeyValue::new("segment_type", String::from(segment_type)),
+        ];
+        let blocks = flushers
+            .iter()
+            .map(|flusher| flusher.num_blocks())
+            .sum::<usize>();
+        let bytes = flushers
+            .iter()
+            .map(|flusher| flusher.num_bytes())
+            .sum::<usize>();
+        self.blocks_written.add(blocks as u64, &labels);
+        self.bytes_flushed.add(bytes as u64, &labels);
+    }
+}
+
+fn per_second(count: usize, elapsed: Duration) -> Option<f64> {
+    let seconds = elapsed.as_secs_f64();
+    (count > 0 && seconds > 0.0).then(|| count as f64 / seconds)
+}
@@ -1,8 +1,13 @@
+mod admin;
 mod compaction_manager;
 pub(crate) mod config;
+pub(crate) mod metrics;
+mod pause;
 mod scheduler;
 mod scheduler_policy;
 mod types;
 
+pub(crate) use admin::*;
 pub(crate) use compaction_manager::*;
+pub(crate) use pause::*;
 pub(crate) use types::*;
@@ -0,0 +1,79 @@
+use std::{collections::HashSet, sync::Arc};
+
+use chroma_types::CollectionUuid;
+use parking_lot::RwLock;
+
+/// The collections whose compaction is paused by an operator, and whether the worker is under
+/// memory pressure. A compaction of a paused collection is aborted at its next partition and
+/// its collection is left to a later batch, while memory pressure only holds back the
+/// compactions that are not started yet
+#[derive(Clone, Debug, Default)]
+pub(crate) struct CompactionPauses {
+    paused: Arc<RwLock<Paused>>,
+}
+
+#[derive(Debug, Default)]
+struct Paused {
+    collections: HashSet<CollectionUuid>,
+    memory_pressure: bool,
+}
+
+impl CompactionPauses {
+    /// Pauses the compaction of the collection, and returns whether it was already paused
+    pub(crate) fn pause(&self, collection_id: CollectionUuid) -> bool {
+        !self.paused.write().collections.insert(collection_id)
+    }
+
+    /// Resumes the compaction of the collection, and returns whether it was paused
+    pub(crate) fn resume(&self, collection_id: CollectionUuid) -> bool {
+        self.paused.write().collections.remove(&collection_id)
+    }
+
+    /// Holds back the compactions that are not started yet while the worker is under memory
+    /// pressure, without changing the pauses of the collections
+    pub(crate) fn set_memory_pressure(&self, under_pressure: bool) {
+        self.paused.write().memory_pressure = under_pressure;
+    }
+
+    pub(crate) fn is_paused(&self, collection_id: CollectionUuid) -> bool {
+        self.paused.read().collections.contains(&collection_id)
+    }
+
+    /// Whether a compaction of the collection may start
+    pub(crate) fn admits(&self, collection_id: CollectionUuid) -> bool {
+        let paused = self.paused.read();
+        !paused.memory_pressure && !paused.collections.contains(&collection_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chroma_types::CollectionUuid;
+
+    use super::CompactionPauses;
+
+    #[test]
+    fn test_pauses() {
+        let pauses = CompactionPauses::default();
+        let (paused, other) = (CollectionUuid::new(), CollectionUuid::new());
+        assert!(!pauses.pause(paused));
+        assert!(pauses.pause(paused));
+        assert!(pauses.is_paused(paused));
+        assert!(!pauses.admits(paused));
+
+        // The compaction of another collection is not affected
+        assert!(!pauses.is_paused(other));
+        assert!(pauses.admits(other));
+
+        // Memory pressure holds back new compactions without pausing the running ones
+        pauses.set_memory_pressure(true);
+        assert!(!pauses.admits(other));
+        assert!(!pauses.is_paused(other));
+        pauses.set_memory_pressure(false);
+        assert!(pauses.admits(other));
+
+        assert!(pauses.resume(paused));
+        assert!(!pauses.resume(paused));
+        assert!(pauses.admits(paused));
+    }
+}
@@ -1,4 +1,5 @@
 use crate::assignment::assignment_policy::AssignmentPolicy;
+use crate::compactor::scheduler_policy::CompactionTriggerPolicy;
 use crate::compactor::scheduler_policy::SchedulerPolicy;
 use crate::compactor::types::CompactionJob;
 use crate::log::log::CollectionInfo;
@@ -6,6 +7,15 @@ use crate::log::log::CollectionRecord;
 use crate::log::log::Log;
 use crate::memberlist::Memberlist;
 use crate::sysdb::sysdb::SysDb;
+use std::collections::HashSet;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+fn now_nanos() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos() as i64)
+        .unwrap_or_default()
+}
 
 pub(crate) struct Scheduler {
     my_ip: String,
@@ -14,7 +24,7 @@ pub(crate) struct Scheduler {
     policy: Box<dyn SchedulerPolicy>,
     job_queue: Vec<CompactionJob>,
     max_concurrent_jobs: usize,
-    min_compaction_size: usize,
+    trigger_policy: CompactionTriggerPolicy,
     memberlist: Option<Memberlist>,
     assignment_policy: Box<dyn AssignmentPolicy>,
 }
@@ -26,14 +36,14 @@ impl Scheduler {
         sysdb: Box<SysDb>,
         policy: Box<dyn SchedulerPolicy>,
         max_concurrent_jobs: usize,
-        min_compaction_size: usize,
+        trigger_policy: CompactionTriggerPolicy,
         assignment_policy: Box<dyn AssignmentPolicy>,
     ) -> Scheduler {
         Scheduler {
             my_ip,
             log,
             sysdb,
-            min_compaction_size,
+            trigger_policy,
             policy,
             job_queue: Vec::with_capacity(max_concurrent_jobs),
             max_concurrent_jobs,
@@ -43,18 +53,53 @@ impl Scheduler {
     }
 
     async fn get_collections_with_new_data(&mut self) -> Vec<CollectionInfo> {
-        let collections = self
-            .log
-            .get_collections_with_new_data(self.min_compaction_size as u64)
-            .await;
-
-        match collections {
+        // Collections can lower the thresholds that trigger their compaction, so every
+        // collection with new data is fetched and the thresholds are checked per collection
+        let collections = match self.log.get_collections_with_new_data(1).await {
             Ok(collections) => collections,
             Err(e) => {
                 tracing::error!("Error: {:?}", e);
-                Vec::new()
+                return Vec::new();
             }
+        };
+        if collections
+            .iter()
+            .all(|collection| collection.num_records > 0)
+        {
+            return collections;
         }
+
+        // A log service that does not count the records of the collections leaves the counts
+        // unset, so the collections that have at least the default number of records are
+        // fetched to bound the counts from below
+        let min_compaction_size = self.trigger_policy.max_dirty_records;
+        let compactable = match self
+            .log
+            .get_collections_with_new_data(min_compaction_size)
+            .await
+        {
+            Ok(compactable) => compactable
+                .into_iter()
+                .map(|collection| collection.collection_id)
+                .collect::<HashSet<_>>(),
+            Err(e) => {
+                tracing::error!("Error: {:?}", e);
+                HashSet::new()
+            }
+        };
+        collections
+            .into_iter()
+            .map(|mut collection| {
+                if collection.num_records == 0 {
+                    collection.num_records = if compactable.contains(&collection.collection_id) {
+                        min_compaction_size
+                    } else {
+                        1
+                    };
+                }
+                collection
+            })
+            .collect()
     }
 
     async fn verify_and_enrich_collections(
@@ -80,6 +125,20 @@ impl Scheduler {
                         continue;
                     }
 
+                    let trigger_policy = self
+                        .trigger_policy
+                        .for_collection(collection[0].metadata.as_ref());
+                    let staleness = Duration::from_nanos(
+                        (now_nanos() - collection_info.first_log_ts).max(0) as u64,
+                    );
+                    if !trigger_policy.should_compact(
+                        collection_info.num_records,
+                        collection_info.num_bytes,
+                        staleness,
+                    ) {
+                        continue;
+                    }
+
                     // TODO: make querying the last compaction time in batch
                     let log_position_in_collecion = collection[0].log_position;
                     let tenant_ids = vec![collection[0].tenant.clone()];
@@ -221,6 +280,9 @@ mod tests {
                         encoding: None,
                         metadata: None,
                         document: None,
+                        uri: None,
+                        idempotency_key: None,
+                        embedding_reference: None,
                         operation: Operation::Add,
                     },
                 },
@@ -243,6 +305,9 @@ mod tests {
                         encoding: None,
                         metadata: None,
                         document: None,
+                        uri: None,
+                        idempotency_key: None,
+                        embedding_reference: None,
                         operation: Operation::Add,
                     },
                 },
@@ -298,7 +363,11 @@ mod tests {
             sysdb.clone(),
             scheduler_policy,
             max_concurrent_jobs,
-            1,
+            CompactionTriggerPolicy {
+                max_dirty_records: 1,
+                max_dirty_bytes: None,
+                max_staleness: None,
+            },
             assignment_policy,
         );
         // Scheduler does nothing without memberlist
@@ -375,6 +444,9 @@ mod tests {
                         encoding: None,
                         metadata: None,
                         document: None,
+                        uri: None,
+                        idempotency_key: None,
+                        embedding_reference: None,
                         operation: Operation::Add,
                     },
                 },
@@ -394,6 +466,9 @@ mod tests {
                         encoding: None,
                         metadata: None,
                         document: None,
+                        uri: None,
+                        idempotency_key: None,
+                        embedding_reference: None,
                         operation: Operation::Add,
                     },
                 },
@@ -413,6 +488,9 @@ mod tests {
                         encoding: None,
                         metadata: None,
                         document: None,
+                        uri: None,
+                        idempotency_key: None,
+                        embedding_reference: None,
                         operation: Operation::Add,
                     },
                 },
@@ -432,6 +510,9 @@ mod tests {
                         encoding: None,
                         metadata: None,
                         document: None,
+                        uri: None,
+                        idempotency_key: None,
+                        embedding_reference: None,
                         operation: Operation::Add,
                     },
                 },
@@ -475,7 +556,11 @@ mod tests {
             sysdb.clone(),
             scheduler_policy,
             max_concurrent_jobs,
-            1,
+            CompactionTriggerPolicy {
+                max_dirty_records: 1,
+                max_dirty_bytes: None,
+                max_staleness: None,
+            },
             assignment_policy,
         );
 
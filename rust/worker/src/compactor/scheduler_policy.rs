@@ -1,5 +1,70 @@
 use crate::compactor::types::CompactionJob;
 use crate::log::log::CollectionRecord;
+use chroma_types::{get_metadata_value_as, Metadata};
+use std::time::Duration;
+
+/// The collection metadata key that overrides the number of log records that trigger a compaction
+pub(crate) const MAX_DIRTY_RECORDS_KEY: &str = "compaction:max_dirty_records";
+/// The collection metadata key that overrides the size in bytes of the log records that trigger
+/// a compaction
+pub(crate) const MAX_DIRTY_BYTES_KEY: &str = "compaction:max_dirty_bytes";
+/// The collection metadata key that overrides how long in seconds a log record may wait before it
+/// triggers a compaction
+pub(crate) const MAX_STALENESS_SEC_KEY: &str = "compaction:max_staleness_sec";
+
+/// Decides whether a collection has enough new data in the log to be compacted. A collection is
+/// compacted as soon as any of the thresholds is reached. The compactor config sets the default
+/// thresholds, which a collection can override in its metadata, so that small collections are
+/// not left uncompacted and large collections are not compacted for every few records.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct CompactionTriggerPolicy {
+    pub(crate) max_dirty_records: u64,
+    pub(crate) max_dirty_bytes: Option<u64>,
+    pub(crate) max_staleness: Option<Duration>,
+}
+
+impl CompactionTriggerPolicy {
+    /// Returns the policy with the thresholds set in the collection metadata. Thresholds that
+    /// are missing or are not positive integers are left as they are.
+    pub(crate) fn for_collection(&self, metadata: Option<&Metadata>) -> CompactionTriggerPolicy {
+        let metadata = match metadata {
+            Some(metadata) => metadata,
+            None => return self.clone(),
+        };
+        let get_threshold = |key: &str| match get_metadata_value_as::<i64>(metadata, key) {
+            Ok(threshold) if threshold > 0 => Some(threshold as u64),
+            _ => None,
+        };
+        CompactionTriggerPolicy {
+            max_dirty_records: get_threshold(MAX_DIRTY_RECORDS_KEY)
+                .unwrap_or(self.max_dirty_records),
+            max_dirty_bytes: get_threshold(MAX_DIRTY_BYTES_KEY).or(self.max_dirty_bytes),
+            max_staleness: get_threshold(MAX_STALENESS_SEC_KEY)
+                .map(Duration::from_secs)
+                .or(self.max_staleness),
+        }
+    }
+
+    /// Returns whether a collection should be compacted, given the number and size of its log
+    /// records that are not compacted, and how long ago the oldest of them was written
+    pub(crate) fn should_compact(
+        &self,
+        num_records: u64,
+        num_bytes: u64,
+        staleness: Duration,
+    ) -> bool {
+        if num_records == 0 {
+            return false;
+        }
+        num_records >= self.max_dirty_records
+            || self
+                .max_dirty_bytes
+                .is_some_and(|max_dirty_bytes| num_bytes >= max_dirty_bytes)
+            || self
+                .max_staleness
+                .is_some_and(|max_staleness| staleness >= max_staleness)
+    }
+}
 
 pub(crate) trait SchedulerPolicy: Send + Sync + SchedulerPolicyClone {
     fn determine(&self, collections: Vec<CollectionRecord>, number_jobs: i32)
@@ -57,9 +122,56 @@ impl SchedulerPolicy for LasCompactionTimeSchedulerPolicy {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chroma_types::CollectionUuid;
+    use chroma_types::{CollectionUuid, MetadataValue};
     use std::str::FromStr;
 
+    #[test]
+    fn test_compaction_trigger_policy() {
+        let default_policy = CompactionTriggerPolicy {
+            max_dirty_records: 100,
+            max_dirty_bytes: None,
+            max_staleness: None,
+        };
+        assert!(!default_policy.should_compact(0, 0, Duration::from_secs(3600)));
+        assert!(!default_policy.should_compact(99, 1 << 30, Duration::from_secs(3600)));
+        assert!(default_policy.should_compact(100, 0, Duration::ZERO));
+
+        let metadata = Metadata::from([
+            (
+                MAX_DIRTY_RECORDS_KEY.to_string(),
+                MetadataValue::Int(10_000),
+            ),
+            (MAX_DIRTY_BYTES_KEY.to_string(), MetadataValue::Int(1024)),
+            (MAX_STALENESS_SEC_KEY.to_string(), MetadataValue::Int(60)),
+        ]);
+        let collection_policy = default_policy.for_collection(Some(&metadata));
+        assert_eq!(
+            collection_policy,
+            CompactionTriggerPolicy {
+                max_dirty_records: 10_000,
+                max_dirty_bytes: Some(1024),
+                max_staleness: Some(Duration::from_secs(60)),
+            }
+        );
+        assert!(!collection_policy.should_compact(100, 512, Duration::from_secs(30)));
+        assert!(collection_policy.should_compact(100, 1024, Duration::from_secs(30)));
+        assert!(collection_policy.should_compact(1, 512, Duration::from_secs(60)));
+
+        // Invalid thresholds fall back to the defaults
+        let metadata = Metadata::from([
+            (MAX_DIRTY_RECORDS_KEY.to_string(), MetadataValue::Int(-1)),
+            (
+                MAX_STALENESS_SEC_KEY.to_string(),
+                MetadataValue::Str("1m".to_string()),
+            ),
+        ]);
+        assert_eq!(
+            default_policy.for_collection(Some(&metadata)),
+            default_policy
+        );
+        assert_eq!(default_policy.for_collection(None), default_policy);
+    }
+
     #[test]
     fn test_scheduler_policy() {
         let collection_uuid_1 =
@@ -91,12 +91,9 @@ impl RootConfig {
 pub(crate) struct QueryServiceConfig {
     pub(crate) service_name: String,
     pub(crate) otel_endpoint: String,
-    #[allow(dead_code)]
     pub(crate) my_member_id: String,
     pub(crate) my_port: u16,
-    #[allow(dead_code)]
     pub(crate) assignment_policy: crate::assignment::config::AssignmentPolicyConfig,
-    #[allow(dead_code)]
     pub(crate) memberlist_provider: crate::memberlist::config::MemberlistProviderConfig,
     pub(crate) sysdb: crate::sysdb::config::SysDbConfig,
     pub(crate) storage: chroma_storage::config::StorageConfig,
@@ -104,6 +101,62 @@ pub(crate) struct QueryServiceConfig {
     pub(crate) dispatcher: crate::execution::config::DispatcherConfig,
     pub(crate) blockfile_provider: chroma_blockstore::config::BlockfileProviderConfig,
     pub(crate) hnsw_provider: chroma_index::config::HnswProviderConfig,
+    #[serde(default)]
+    pub(crate) query_memory: crate::execution::config::QueryMemoryConfig,
+    #[serde(default = "QueryServiceConfig::default_collection_alias_cache_ttl_sec")]
+    pub(crate) collection_alias_cache_ttl_sec: u64,
+    #[serde(default = "QueryServiceConfig::default_collection_alias_cache")]
+    pub(crate) collection_alias_cache: chroma_cache::CacheConfig,
+    #[serde(default = "QueryServiceConfig::default_hydration_concurrency")]
+    pub(crate) hydration_concurrency: usize,
+    #[serde(default)]
+    pub(crate) offset_id_cache: Option<chroma_cache::CacheConfig>,
+    #[serde(default)]
+    pub(crate) result_cache: Option<chroma_cache::CacheConfig>,
+    #[serde(default)]
+    pub(crate) max_inline_embedding_bytes: Option<u64>,
+    #[serde(default)]
+    pub(crate) max_log_backlog: Option<u32>,
+    #[serde(default = "QueryServiceConfig::default_stream_batch_size")]
+    pub(crate) stream_batch_size: usize,
+    #[serde(default = "QueryServiceConfig::default_orchestrator_queue_size")]
+    pub(crate) orchestrator_queue_size: usize,
+    #[serde(default)]
+    pub(crate) slow_query_log: crate::execution::config::SlowQueryLogConfig,
+    #[serde(default)]
+    pub(crate) operator_middleware: crate::execution::config::OperatorMiddlewareConfig,
+    #[serde(default)]
+    pub(crate) query_spill: Option<crate::execution::config::QuerySpillConfig>,
+    #[serde(default)]
+    pub(crate) memory_watchdog: crate::memory_watchdog::MemoryWatchdogConfig,
+    #[serde(default)]
+    pub(crate) warm_up: crate::warm_up_scheduler::WarmUpSchedulerConfig,
+}
+
+impl QueryServiceConfig {
+    fn default_collection_alias_cache_ttl_sec() -> u64 {
+        60
+    }
+
+    fn default_collection_alias_cache() -> chroma_cache::CacheConfig {
+        chroma_cache::CacheConfig::Weighted(chroma_cache::WeightedCacheConfig {
+            capacity_bytes: 1024 * 1024,
+            budget: None,
+            max_partition_share: None,
+        })
+    }
+
+    fn default_hydration_concurrency() -> usize {
+        crate::execution::operators::projection::DEFAULT_HYDRATION_CONCURRENCY
+    }
+
+    fn default_stream_batch_size() -> usize {
+        crate::execution::orchestration::get::DEFAULT_STREAM_BATCH_SIZE
+    }
+
+    fn default_orchestrator_queue_size() -> usize {
+        1000
+    }
 }
 
 #[derive(Deserialize)]
@@ -121,7 +174,7 @@ pub(crate) struct CompactionServiceConfig {
     pub(crate) service_name: String,
     pub(crate) otel_endpoint: String,
     pub(crate) my_member_id: String,
-    #[allow(dead_code)]
+    // The port of the compaction admin service
     pub(crate) my_port: u16,
     pub(crate) assignment_policy: crate::assignment::config::AssignmentPolicyConfig,
     pub(crate) memberlist_provider: crate::memberlist::config::MemberlistProviderConfig,
@@ -132,6 +185,10 @@ pub(crate) struct CompactionServiceConfig {
     pub(crate) compactor: crate::compactor::config::CompactorConfig,
     pub(crate) blockfile_provider: chroma_blockstore::config::BlockfileProviderConfig,
     pub(crate) hnsw_provider: chroma_index::config::HnswProviderConfig,
+    #[serde(default)]
+    pub(crate) memory_watchdog: crate::memory_watchdog::MemoryWatchdogConfig,
+    #[serde(default)]
+    pub(crate) garbage_collector: crate::garbage_collector::GarbageCollectorConfig,
 }
 
 #[cfg(test)]
@@ -3,6 +3,101 @@ use serde::Deserialize;
 #[derive(Deserialize)]
 pub(crate) struct DispatcherConfig {
     pub(crate) num_worker_threads: usize,
+    /// The worker threads, out of `num_worker_threads`, that prefer batch tasks. The others
+    /// prefer interactive tasks, and every worker runs tasks of the other class when it is idle
+    #[serde(default)]
+    pub(crate) num_batch_worker_threads: usize,
     pub(crate) dispatcher_queue_size: usize,
     pub(crate) worker_queue_size: usize,
+    #[serde(default)]
+    pub(crate) retry: OperatorRetryConfig,
+}
+
+/// The retries of the operators that fail with a transient error
+/// - `max_attempts`: The attempts of a task before its error is returned, including the first
+/// - `initial_backoff_ms`: The backoff before the first retry, which doubles with each retry
+/// - `max_backoff_ms`: The longest backoff before a retry
+#[derive(Deserialize)]
+pub(crate) struct OperatorRetryConfig {
+    #[serde(default = "OperatorRetryConfig::default_max_attempts")]
+    pub(crate) max_attempts: usize,
+    #[serde(default = "OperatorRetryConfig::default_initial_backoff_ms")]
+    pub(crate) initial_backoff_ms: u64,
+    #[serde(default = "OperatorRetryConfig::default_max_backoff_ms")]
+    pub(crate) max_backoff_ms: u64,
+}
+
+impl OperatorRetryConfig {
+    fn default_max_attempts() -> usize {
+        3
+    }
+
+    fn default_initial_backoff_ms() -> u64 {
+        50
+    }
+
+    fn default_max_backoff_ms() -> u64 {
+        1000
+    }
+}
+
+impl Default for OperatorRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: Self::default_max_attempts(),
+            initial_backoff_ms: Self::default_initial_backoff_ms(),
+            max_backoff_ms: Self::default_max_backoff_ms(),
+        }
+    }
+}
+
+/// The memory limits of the queries of a worker. Without a limit, the memory is only accounted for
+/// - `query_budget_bytes`: The bytes that a single query may hold before it fails
+/// - `max_total_bytes`: The bytes that the running queries may hold in total before new queries
+///   are rejected
+#[derive(Default, Deserialize)]
+pub(crate) struct QueryMemoryConfig {
+    #[serde(default)]
+    pub(crate) query_budget_bytes: Option<usize>,
+    #[serde(default)]
+    pub(crate) max_total_bytes: Option<usize>,
+}
+
+/// The log of the queries that take longer than a threshold, which records what each of them
+/// asked for and how it was executed
+/// - `threshold_ms`: The latency above which a query is logged. Without it, no query is logged
+/// - `capacity`: The number of the most recent slow queries that are kept in memory for the
+///   admin service
+#[derive(Default, Deserialize)]
+pub(crate) struct SlowQueryLogConfig {
+    #[serde(default)]
+    pub(crate) threshold_ms: Option<u64>,
+    #[serde(default)]
+    pub(crate) capacity: usize,
+}
+
+/// Where the merges of the query results spill once they exceed a budget. Without it, the merges
+/// hold their results in memory
+/// - `path`: The local directory that holds the spilled results
+/// - `budget_bytes`: The bytes of results that a merge holds in memory before it spills them
+#[derive(Deserialize)]
+pub(crate) struct QuerySpillConfig {
+    pub(crate) path: String,
+    pub(crate) budget_bytes: usize,
+}
+
+/// The layers that the operators of the get queries run in, below the default layers
+/// - `timeout_ms`: The time after which a run of an operator is aborted. Without it, the runs
+///   are not timed out
+/// - `fault_injection_probability`: The probability that a run is aborted before the operator
+///   runs, so that tests can exercise how the queries handle failed operators
+/// - `fault_injection_latency_ms`: The delay before the runs that are not aborted
+#[derive(Default, Deserialize)]
+pub(crate) struct OperatorMiddlewareConfig {
+    #[serde(default)]
+    pub(crate) timeout_ms: Option<u64>,
+    #[serde(default)]
+    pub(crate) fault_injection_probability: f64,
+    #[serde(default)]
+    pub(crate) fault_injection_latency_ms: u64,
 }
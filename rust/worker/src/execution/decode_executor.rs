@@ -0,0 +1,158 @@
+use super::{
+    dispatcher::Dispatcher,
+    operator::{wrap, Operator, TaskMessage, TaskResult},
+    worker_thread::on_worker_thread,
+};
+use crate::system::{ChannelError, ComponentHandle, ReceiverForMessage};
+use async_trait::async_trait;
+use chroma_blockstore::arrow::decode_executor::DecodeExecutor;
+use chroma_error::{ChromaError, ErrorCodes};
+use parking_lot::Mutex;
+use std::{fmt::Debug, sync::Arc};
+use thiserror::Error;
+use tokio::sync::oneshot;
+use tracing::Span;
+
+/// Decodes the large blocks on the worker threads of the dispatcher, so that the blocks read
+/// by the orchestrators and the server do not stall the I/O runtime. The blocks read by the
+/// operators are decoded inline, since the operators already run on the worker threads and a
+/// worker thread waiting for another could exhaust them
+#[derive(Debug)]
+pub(crate) struct DispatcherDecodeExecutor {
+    dispatcher: Box<dyn ReceiverForMessage<TaskMessage>>,
+}
+
+impl DispatcherDecodeExecutor {
+    pub(crate) fn new(dispatcher: &ComponentHandle<Dispatcher>) -> Self {
+        Self {
+            dispatcher: dispatcher.receiver(),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+enum DecodeExecutorError {
+    #[error("Error sending the decode task to the dispatcher: {0}")]
+    Send(#[from] ChannelError),
+    #[error("The decode task was dropped before it completed")]
+    Dropped,
+}
+
+impl ChromaError for DecodeExecutorError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            DecodeExecutorError::Send(e) => e.code(),
+            DecodeExecutorError::Dropped => ErrorCodes::Internal,
+        }
+    }
+}
+
+#[async_trait]
+impl DecodeExecutor for DispatcherDecodeExecutor {
+    async fn run(&self, decode: Box<dyn FnOnce() + Send>) -> Result<(), Box<dyn ChromaError>> {
+        if on_worker_thread() {
+            decode();
+            return Ok(());
+        }
+        let (tx, rx) = oneshot::channel();
+        let task = wrap(
+            Box::new(DecodeOperator {}),
+            DecodeInput {
+                decode: Mutex::new(Some(decode)),
+            },
+            Box::new(OneshotReceiver {
+                sender: Arc::new(Mutex::new(Some(tx))),
+            }),
+        );
+        self.dispatcher
+            .send(task, Some(Span::current()))
+            .await
+            .map_err(|e| Box::new(DecodeExecutorError::Send(e)) as Box<dyn ChromaError>)?;
+        match rx.await {
+            Ok(result) => result.into_inner().map_err(|e| e.boxed()),
+            Err(_) => Err(Box::new(DecodeExecutorError::Dropped)),
+        }
+    }
+}
+
+/// Runs the decode of a block, which it takes from the input since a decode runs once
+#[derive(Debug)]
+struct DecodeOperator {}
+
+struct DecodeInput {
+    decode: Mutex<Option<Box<dyn FnOnce() + Send>>>,
+}
+
+impl Debug for DecodeInput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DecodeInput").finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl Operator<DecodeInput, ()> for DecodeOperator {
+    type Error = Box<dyn ChromaError>;
+
+    fn get_name(&self) -> &'static str {
+        "DecodeOperator"
+    }
+
+    async fn run(&self, input: &DecodeInput) -> Result<(), Self::Error> {
+        if let Some(decode) = input.decode.lock().take() {
+            decode();
+        }
+        Ok(())
+    }
+}
+
+type DecodeResult = TaskResult<(), Box<dyn ChromaError>>;
+
+/// Forwards the result of the decode task to the executor that waits for it
+#[derive(Clone, Debug)]
+struct OneshotReceiver {
+    sender: Arc<Mutex<Option<oneshot::Sender<DecodeResult>>>>,
+}
+
+#[async_trait]
+impl ReceiverForMessage<DecodeResult> for OneshotReceiver {
+    async fn send(
+        &self,
+        message: DecodeResult,
+        _tracing_context: Option<Span>,
+    ) -> Result<(), ChannelError> {
+        match self.sender.lock().take() {
+            Some(sender) => sender.send(message).map_err(|_| ChannelError::SendError),
+            None => Err(ChannelError::SendError),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use chroma_blockstore::arrow::decode_executor::DecodeExecutor;
+
+    use crate::{execution::dispatcher::Dispatcher, system::System};
+
+    use super::{on_worker_thread, DispatcherDecodeExecutor};
+
+    #[tokio::test]
+    async fn test_decode_on_worker_thread() {
+        let system = System::new();
+        let dispatcher = system.start_component(Dispatcher::new(2, 0, 10, 10));
+        let executor = DispatcherDecodeExecutor::new(&dispatcher);
+
+        // The decode runs on a worker thread rather than the task that waits for it
+        let decoded_on_worker_thread = std::sync::Arc::new(AtomicBool::new(false));
+        let flag = decoded_on_worker_thread.clone();
+        executor
+            .run(Box::new(move || {
+                flag.store(on_worker_thread(), Ordering::SeqCst);
+            }))
+            .await
+            .expect("The decode should run");
+        assert!(!on_worker_thread());
+        assert!(decoded_on_worker_thread.load(Ordering::SeqCst));
+    }
+}
@@ -1,11 +1,11 @@
-use super::operator::OperatorType;
+use super::operator::{OperatorType, RetryPolicy, TaskPriority, TaskStatus};
 use super::{operator::TaskMessage, worker_thread::WorkerThread};
 use crate::execution::config::DispatcherConfig;
 use crate::system::{Component, ComponentContext, Handler, ReceiverForMessage, System};
 use async_trait::async_trait;
 use chroma_config::Configurable;
 use chroma_error::ChromaError;
-use std::fmt::Debug;
+use std::{collections::VecDeque, fmt::Debug, time::Duration};
 use tracing::{trace_span, Instrument, Span};
 
 /// The dispatcher is responsible for distributing tasks to worker threads.
@@ -39,8 +39,13 @@ use tracing::{trace_span, Instrument, Span};
                      └────────────────┘   └────────────────┘   └────────────────┘
 ```
 ## Implementation notes
-- The dispatcher has a queue of tasks that it distributes to worker threads
+- The dispatcher has a queue of tasks for each priority class that it distributes to worker threads
+- Each worker thread prefers a priority class. It is sent the tasks of its class first, and steals
+  tasks of the other class when none of its class are queued, so that batch tasks (e.g. compaction
+  or full scans) do not starve interactive tasks while no worker thread is left idle
 - A worker thread sends a TaskRequestMessage to the dispatcher when it is ready for a new task
+- A task whose attempt fails with a transient error is requeued once its backoff has passed, so
+  that the worker thread runs other tasks in the meantime
 - If no task is available for the worker thread, the dispatcher will place that worker's reciever
     in a queue and send a task to the worker when it recieves another one
 - The reason to introduce this abstraction is to allow us to control fairness and dynamically adjust
@@ -52,59 +57,131 @@ use tracing::{trace_span, Instrument, Span};
 */
 #[derive(Debug)]
 pub(crate) struct Dispatcher {
-    task_queue: Vec<TaskMessage>,
+    interactive_queue: VecDeque<TaskMessage>,
+    batch_queue: VecDeque<TaskMessage>,
     waiters: Vec<TaskRequestMessage>,
     n_worker_threads: usize,
+    n_batch_worker_threads: usize,
     queue_size: usize,
     worker_queue_size: usize,
+    retry_policy: RetryPolicy,
 }
 
 impl Dispatcher {
     /// Create a new dispatcher
     /// # Parameters
     /// - n_worker_threads: The number of worker threads to use
+    /// - n_batch_worker_threads: The number of worker threads, out of n_worker_threads,
+    ///   that prefer batch tasks over interactive tasks
     /// - queue_size: The size of the components message queue
     /// - worker_queue_size: The size of the worker components queue
-    pub fn new(n_worker_threads: usize, queue_size: usize, worker_queue_size: usize) -> Self {
+    pub fn new(
+        n_worker_threads: usize,
+        n_batch_worker_threads: usize,
+        queue_size: usize,
+        worker_queue_size: usize,
+    ) -> Self {
         Dispatcher {
-            task_queue: Vec::new(),
+            interactive_queue: VecDeque::new(),
+            batch_queue: VecDeque::new(),
             waiters: Vec::new(),
             n_worker_threads,
+            n_batch_worker_threads: n_batch_worker_threads.min(n_worker_threads),
             queue_size,
             worker_queue_size,
+            retry_policy: RetryPolicy::default(),
         }
     }
 
+    /// Sets the policy of the retries of the tasks that the dispatcher runs
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
     /// Spawn worker threads
     /// # Parameters
     /// - system: The system to spawn the worker threads in
     /// - self_receiver: The receiver to send tasks to the worker threads, this is a address back to the dispatcher
+    /// - retry_receiver: The receiver that the worker threads requeue the retried tasks with, this is also an address back to the dispatcher
     fn spawn_workers(
         &self,
         system: &mut System,
         self_receiver: Box<dyn ReceiverForMessage<TaskRequestMessage>>,
+        retry_receiver: Box<dyn ReceiverForMessage<RetryTaskMessage>>,
     ) {
-        for _ in 0..self.n_worker_threads {
-            let worker = WorkerThread::new(self_receiver.clone(), self.worker_queue_size);
+        for index in 0..self.n_worker_threads {
+            let priority = if index < self.n_batch_worker_threads {
+                TaskPriority::Batch
+            } else {
+                TaskPriority::Interactive
+            };
+            let worker = WorkerThread::new(
+                self_receiver.clone(),
+                retry_receiver.clone(),
+                priority,
+                self.worker_queue_size,
+            );
             system.start_component(worker);
         }
     }
 
+    fn queue_mut(&mut self, priority: TaskPriority) -> &mut VecDeque<TaskMessage> {
+        match priority {
+            TaskPriority::Interactive => &mut self.interactive_queue,
+            TaskPriority::Batch => &mut self.batch_queue,
+        }
+    }
+
+    /// Take the next task for a worker, preferring the tasks of its priority class and
+    /// stealing from the other class when there are none
+    fn next_task(&mut self, priority: TaskPriority) -> Option<TaskMessage> {
+        let stolen = match priority {
+            TaskPriority::Interactive => TaskPriority::Batch,
+            TaskPriority::Batch => TaskPriority::Interactive,
+        };
+        self.queue_mut(priority)
+            .pop_front()
+            .or_else(|| self.queue_mut(stolen).pop_front())
+    }
+
+    /// Take a waiting worker for a task, preferring the workers of its priority class
+    fn next_waiter(&mut self, priority: TaskPriority) -> Option<TaskRequestMessage> {
+        match self
+            .waiters
+            .iter()
+            .position(|waiter| waiter.priority == priority)
+        {
+            Some(index) => Some(self.waiters.remove(index)),
+            None => self.waiters.pop(),
+        }
+    }
+
     /// Enqueue a task to be processed
     /// # Parameters
     /// - task: The task to enqueue
-    async fn enqueue_task(&mut self, task: TaskMessage) {
+    async fn enqueue_task(&mut self, mut task: TaskMessage) {
+        task.set_retry_policy(self.retry_policy.clone());
         match task.get_type() {
             OperatorType::IO => {
                 let child_span = trace_span!(parent: Span::current(), "IO task execution", name = task.get_name());
-                tokio::spawn(async move {
-                    task.run().instrument(child_span).await;
-                });
+                tokio::spawn(
+                    async move {
+                        // The IO tasks do not run on the worker threads, so they wait out
+                        // their backoff in place
+                        while let TaskStatus::RetryAfter(backoff) = task.run().await {
+                            tokio::time::sleep(backoff).await;
+                        }
+                    }
+                    .instrument(child_span),
+                );
             }
             OperatorType::Other => {
-                // If a worker is waiting for a task, send it to the worker in FIFO order
-                // Otherwise, add it to the task queue
-                match self.waiters.pop() {
+                // If a worker is waiting for a task, send it to the worker, preferring the
+                // workers of the task's priority class
+                // Otherwise, add it to the queue of its priority class
+                let priority = task.get_priority();
+                match self.next_waiter(priority) {
                     Some(channel) => match channel
                         .reply_to
                         .send(task, Some(Span::current().clone()))
@@ -116,7 +193,7 @@ impl Dispatcher {
                         }
                     },
                     None => {
-                        self.task_queue.push(task);
+                        self.queue_mut(priority).push_back(task);
                     }
                 }
             }
@@ -129,7 +206,7 @@ impl Dispatcher {
     ///   If no work is available, the worker will be placed in a queue and a task will be sent to
     ///   it when one is available
     async fn handle_work_request(&mut self, request: TaskRequestMessage) {
-        match self.task_queue.pop() {
+        match self.next_task(request.priority) {
             Some(task) => match request
                 .reply_to
                 .send(task, Some(Span::current().clone()))
@@ -152,18 +229,22 @@ impl Configurable<DispatcherConfig> for Dispatcher {
     async fn try_from_config(config: &DispatcherConfig) -> Result<Self, Box<dyn ChromaError>> {
         Ok(Dispatcher::new(
             config.num_worker_threads,
+            config.num_batch_worker_threads,
             config.dispatcher_queue_size,
             config.worker_queue_size,
-        ))
+        )
+        .with_retry_policy(RetryPolicy::from(&config.retry)))
     }
 }
 
 /// A message that a worker thread sends to the dispatcher to request a task
 /// # Members
 /// - reply_to: The receiver to send the task to, this is the worker thread
+/// - priority: The priority class that the worker thread prefers
 #[derive(Debug)]
 pub(super) struct TaskRequestMessage {
     reply_to: Box<dyn ReceiverForMessage<TaskMessage>>,
+    priority: TaskPriority,
 }
 
 impl TaskRequestMessage {
@@ -171,8 +252,29 @@ impl TaskRequestMessage {
     /// # Parameters
     /// - reply_to: The receiver to send the task to, this is the worker thread
     ///   that is requesting the task
-    pub(super) fn new(reply_to: Box<dyn ReceiverForMessage<TaskMessage>>) -> Self {
-        TaskRequestMessage { reply_to }
+    /// - priority: The priority class that the worker thread prefers
+    pub(super) fn new(
+        reply_to: Box<dyn ReceiverForMessage<TaskMessage>>,
+        priority: TaskPriority,
+    ) -> Self {
+        TaskRequestMessage { reply_to, priority }
+    }
+}
+
+/// A message that a worker thread sends to the dispatcher to requeue a task whose attempt
+/// failed with a transient error, once the backoff has passed
+/// # Members
+/// - task: The task to requeue
+/// - backoff: The time to wait before the task is requeued
+#[derive(Debug)]
+pub(super) struct RetryTaskMessage {
+    task: TaskMessage,
+    backoff: Duration,
+}
+
+impl RetryTaskMessage {
+    pub(super) fn new(task: TaskMessage, backoff: Duration) -> Self {
+        RetryTaskMessage { task, backoff }
     }
 }
 
@@ -189,7 +291,7 @@ impl Component for Dispatcher {
     }
 
     async fn on_start(&mut self, ctx: &ComponentContext<Self>) {
-        self.spawn_workers(&mut ctx.system.clone(), ctx.receiver());
+        self.spawn_workers(&mut ctx.system.clone(), ctx.receiver(), ctx.receiver());
     }
 }
 
@@ -212,6 +314,22 @@ impl Handler<TaskRequestMessage> for Dispatcher {
     }
 }
 
+// Worker sends a task to retry
+#[async_trait]
+impl Handler<RetryTaskMessage> for Dispatcher {
+    type Result = ();
+
+    async fn handle(&mut self, message: RetryTaskMessage, ctx: &ComponentContext<Dispatcher>) {
+        let receiver: Box<dyn ReceiverForMessage<TaskMessage>> = ctx.receiver();
+        tokio::spawn(async move {
+            tokio::time::sleep(message.backoff).await;
+            if let Err(e) = receiver.send(message.task, None).await {
+                tracing::error!("Error requeueing retried task: {:?}", e);
+            }
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use parking_lot::Mutex;
@@ -224,8 +342,8 @@ mod tests {
 
     use super::*;
     use crate::{
-        execution::operator::{wrap, Operator, TaskResult},
-        system::{ComponentHandle, System},
+        execution::operator::{wrap, wrap_with_priority, Operator, TaskResult},
+        system::{ChannelError, ComponentHandle, System},
     };
     use std::{
         collections::HashSet,
@@ -419,7 +537,7 @@ mod tests {
     #[tokio::test]
     async fn test_dispatcher_io_tasks() {
         let system = System::new();
-        let dispatcher = Dispatcher::new(THREAD_COUNT, 1000, 1000);
+        let dispatcher = Dispatcher::new(THREAD_COUNT, 0, 1000, 1000);
         let dispatcher_handle = system.start_component(dispatcher);
         let counter = Arc::new(AtomicUsize::new(0));
         let sent_tasks = Arc::new(Mutex::new(HashSet::new()));
@@ -447,7 +565,7 @@ mod tests {
     #[tokio::test]
     async fn test_dispatcher_non_io_tasks() {
         let system = System::new();
-        let dispatcher = Dispatcher::new(THREAD_COUNT, 1000, 1000);
+        let dispatcher = Dispatcher::new(THREAD_COUNT, 0, 1000, 1000);
         let dispatcher_handle = system.start_component(dispatcher);
         let counter = Arc::new(AtomicUsize::new(0));
         let sent_tasks = Arc::new(Mutex::new(HashSet::new()));
@@ -471,4 +589,100 @@ mod tests {
         assert_eq!(sent_tasks.lock().len(), DISPATCH_COUNT);
         assert_eq!(received_tasks.lock().len(), DISPATCH_COUNT);
     }
+
+    // A worker that records the priority of the tasks it is sent, without running them
+    #[derive(Clone, Debug, Default)]
+    struct RecordingWorker {
+        received: Arc<Mutex<Vec<TaskPriority>>>,
+    }
+    #[async_trait]
+    impl ReceiverForMessage<TaskMessage> for RecordingWorker {
+        async fn send(
+            &self,
+            message: TaskMessage,
+            _tracing_context: Option<Span>,
+        ) -> Result<(), ChannelError> {
+            self.received.lock().push(message.get_priority());
+            Ok(())
+        }
+    }
+    #[async_trait]
+    impl ReceiverForMessage<TaskResult<String, ()>> for RecordingWorker {
+        async fn send(
+            &self,
+            _message: TaskResult<String, ()>,
+            _tracing_context: Option<Span>,
+        ) -> Result<(), ChannelError> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dispatcher_priority() {
+        let mut dispatcher = Dispatcher::new(2, 1, 1000, 1000);
+        let worker = RecordingWorker::default();
+        let task = |priority| {
+            wrap_with_priority(
+                Box::new(MockOperator {}),
+                42.0,
+                Box::new(worker.clone()),
+                priority,
+            )
+        };
+        let request = |priority| TaskRequestMessage::new(Box::new(worker.clone()), priority);
+
+        // Without waiting workers, the tasks are queued by priority class
+        dispatcher.enqueue_task(task(TaskPriority::Batch)).await;
+        dispatcher.enqueue_task(task(TaskPriority::Batch)).await;
+        dispatcher
+            .enqueue_task(task(TaskPriority::Interactive))
+            .await;
+
+        // An interactive worker takes the interactive tasks before it steals batch tasks
+        dispatcher
+            .handle_work_request(request(TaskPriority::Interactive))
+            .await;
+        dispatcher
+            .handle_work_request(request(TaskPriority::Interactive))
+            .await;
+        assert_eq!(
+            *worker.received.lock(),
+            vec![TaskPriority::Interactive, TaskPriority::Batch]
+        );
+
+        // A batch worker prefers batch tasks, and steals interactive tasks when it is idle
+        dispatcher
+            .enqueue_task(task(TaskPriority::Interactive))
+            .await;
+        dispatcher
+            .handle_work_request(request(TaskPriority::Batch))
+            .await;
+        dispatcher
+            .handle_work_request(request(TaskPriority::Batch))
+            .await;
+        assert_eq!(
+            worker.received.lock()[2..],
+            [TaskPriority::Batch, TaskPriority::Interactive]
+        );
+
+        // A task is sent to a waiting worker of its priority class first
+        dispatcher
+            .handle_work_request(request(TaskPriority::Interactive))
+            .await;
+        dispatcher
+            .handle_work_request(request(TaskPriority::Batch))
+            .await;
+        assert_eq!(dispatcher.waiters.len(), 2);
+        dispatcher
+            .enqueue_task(task(TaskPriority::Interactive))
+            .await;
+        assert_eq!(dispatcher.waiters.len(), 1);
+        assert_eq!(dispatcher.waiters[0].priority, TaskPriority::Batch);
+        // The idle batch worker steals the interactive task
+        dispatcher
+            .enqueue_task(task(TaskPriority::Interactive))
+            .await;
+        assert!(dispatcher.waiters.is_empty());
+        assert_eq!(worker.received.lock().len(), 6);
+    }
 }
@@ -0,0 +1,204 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use chroma_blockstore::read_stats::ReadStats;
+use chroma_types::chroma_proto;
+use parking_lot::Mutex;
+use uuid::Uuid;
+
+/// The plan of a query as it is executed, which is returned when the query is explained so
+/// that users can see how it is evaluated and where it spends its time
+#[derive(Clone, Debug, Default)]
+pub struct QueryPlan {
+    /// The choices that the orchestrator makes while it evaluates the query, e.g. whether the
+    /// filter is evaluated to the included or the excluded records
+    pub strategy: BTreeMap<&'static str, String>,
+    /// The operators of the query in the order that they are scheduled
+    pub nodes: Vec<QueryPlanNode>,
+}
+
+impl QueryPlan {
+    /// The block reads of the operators of the plan
+    pub fn read_stats(&self) -> ReadStats {
+        let mut read_stats = ReadStats::default();
+        for node in &self.nodes {
+            read_stats += node.read_stats;
+        }
+        read_stats
+    }
+}
+
+/// An operator of a query plan
+#[derive(Clone, Debug)]
+pub struct QueryPlanNode {
+    pub operator: &'static str,
+    /// The indices of the nodes whose outputs the operator consumes
+    pub inputs: Vec<usize>,
+    /// The number of records that the operator is expected to produce when it is scheduled,
+    /// if it can be estimated from its inputs
+    pub estimated_rows: Option<u64>,
+    /// The number of records that the operator produced
+    pub output_rows: Option<u64>,
+    /// The time from when the operator is scheduled until its output is received, which
+    /// includes the time that it waits for a worker
+    pub elapsed: Option<Duration>,
+    /// The block reads of the operator
+    pub read_stats: ReadStats,
+}
+
+#[derive(Debug, Default)]
+struct PlanState {
+    plan: QueryPlan,
+    // The node and schedule time of each task that is not finished yet
+    running: HashMap<Uuid, (usize, Instant)>,
+    // The node of each scheduled task
+    nodes: HashMap<Uuid, usize>,
+}
+
+/// Records the plan of a query while its orchestrator runs. Every method is a no-op unless the
+/// recorder is enabled, so orchestrators record the plan unconditionally
+#[derive(Clone, Debug, Default)]
+pub struct QueryPlanRecorder {
+    state: Option<Arc<Mutex<PlanState>>>,
+}
+
+impl QueryPlanRecorder {
+    pub fn enabled() -> Self {
+        Self {
+            state: Some(Arc::default()),
+        }
+    }
+
+    pub fn set_strategy(&self, key: &'static str, value: impl Into<String>) {
+        if let Some(state) = &self.state {
+            state.lock().plan.strategy.insert(key, value.into());
+        }
+    }
+
+    /// Records that the task of an operator is scheduled, which consumes the outputs of the
+    /// given tasks
+    pub fn start(
+        &self,
+        task_id: Uuid,
+        operator: &'static str,
+        inputs: impl IntoIterator<Item = Uuid>,
+        estimated_rows: Option<u64>,
+    ) {
+        if let Some(state) = &self.state {
+            let mut state = state.lock();
+            let inputs = inputs
+                .into_iter()
+                .filter_map(|input| state.nodes.get(&input).copied())
+                .collect();
+            let node = state.plan.nodes.len();
+            state.plan.nodes.push(QueryPlanNode {
+                operator,
+                inputs,
+                estimated_rows,
+                output_rows: None,
+                elapsed: None,
+                read_stats: ReadStats::default(),
+            });
+            state.nodes.insert(task_id, node);
+            state.running.insert(task_id, (node, Instant::now()));
+        }
+    }
+
+    /// Records that the output of a task is received
+    pub fn finish(&self, task_id: Uuid, read_stats: ReadStats, output_rows: Option<u64>) {
+        if let Some(state) = &self.state {
+            let mut state = state.lock();
+            if let Some((node, scheduled)) = state.running.remove(&task_id) {
+                let node = &mut state.plan.nodes[node];
+                node.output_rows = output_rows;
+                node.elapsed = Some(scheduled.elapsed());
+                node.read_stats = read_stats;
+            }
+        }
+    }
+
+    /// The plan recorded so far, or `None` if the recorder is not enabled
+    pub fn plan(&self) -> Option<QueryPlan> {
+        self.state.as_ref().map(|state| state.lock().plan.clone())
+    }
+}
+
+impl From<QueryPlan> for chroma_proto::QueryPlan {
+    fn from(plan: QueryPlan) -> Self {
+        chroma_proto::QueryPlan {
+            strategy: plan
+                .strategy
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+            nodes: plan
+                .nodes
+                .into_iter()
+                .map(|node| chroma_proto::QueryPlanNode {
+                    operator: node.operator.to_string(),
+                    inputs: node.inputs.into_iter().map(|input| input as u32).collect(),
+                    estimated_rows: node.estimated_rows,
+                    output_rows: node.output_rows,
+                    elapsed_micros: node.elapsed.map(|elapsed| elapsed.as_micros() as u64),
+                    cache_hits: node.read_stats.cache_hits,
+                    cache_misses: node.read_stats.cache_misses,
+                    bytes_read: node.read_stats.bytes_read,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chroma_blockstore::read_stats::ReadStats;
+    use uuid::Uuid;
+
+    use super::QueryPlanRecorder;
+
+    #[test]
+    fn test_record_plan() {
+        let disabled = QueryPlanRecorder::default();
+        disabled.start(Uuid::new_v4(), "FilterOperator", [], None);
+        assert!(disabled.plan().is_none());
+
+        let recorder = QueryPlanRecorder::enabled();
+        let (fetch_log, fetch_segment, filter) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        recorder.start(fetch_log, "FetchLogOperator", [], None);
+        recorder.start(fetch_segment, "FetchSegmentOperator", [], None);
+        recorder.finish(fetch_segment, ReadStats::default(), None);
+        recorder.finish(
+            fetch_log,
+            ReadStats {
+                cache_hits: 3,
+                cache_misses: 1,
+                bytes_read: 1024,
+            },
+            Some(10),
+        );
+        recorder.start(
+            filter,
+            "FilterOperator",
+            [fetch_log, fetch_segment],
+            Some(3),
+        );
+        recorder.set_strategy("bitmap", "include");
+
+        let plan = recorder.plan().expect("Plan should be recorded");
+        assert_eq!(
+            plan.strategy.get("bitmap").map(String::as_str),
+            Some("include")
+        );
+        assert_eq!(plan.nodes.len(), 3);
+        assert_eq!(plan.nodes[0].output_rows, Some(10));
+        assert!(plan.nodes[0].elapsed.is_some());
+        assert_eq!(plan.nodes[2].inputs, vec![0, 1]);
+        assert_eq!(plan.nodes[2].estimated_rows, Some(3));
+        assert_eq!(plan.read_stats().cache_hit_ratio(), Some(0.75));
+        // The filter has not finished yet
+        assert!(plan.nodes[2].elapsed.is_none());
+    }
+}
@@ -0,0 +1,384 @@
+use std::{
+    cell::RefCell,
+    future::Future,
+    mem::size_of,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use chroma_error::{ChromaError, ErrorCodes};
+use chroma_types::{Chunk, LogRecord, MetadataValue, SignedRoaringBitmap};
+use roaring::RoaringBitmap;
+use thiserror::Error;
+
+use crate::segment::MaterializedLogRecord;
+
+tokio::task_local! {
+    static ATTEMPT_RESERVATIONS: RefCell<Vec<(MemoryContext, usize)>>;
+}
+
+#[derive(Debug, Error)]
+pub enum MemoryError {
+    #[error("Query holds {used} bytes, which exceeds its budget of {budget} bytes")]
+    QueryBudgetExceeded { used: usize, budget: usize },
+    #[error("Running queries hold {used} bytes, which exceeds the limit of {limit} bytes")]
+    WorkerLimitExceeded { used: usize, limit: usize },
+    #[error("Worker is under memory pressure and does not admit new queries")]
+    MemoryPressure,
+}
+
+impl ChromaError for MemoryError {
+    fn code(&self) -> ErrorCodes {
+        ErrorCodes::ResourceExhausted
+    }
+}
+
+/// The memory held by the running queries of a worker. A new query is admitted only while
+/// the memory held by the running queries is below the limit, and while the memory watchdog
+/// of the worker does not reject new queries
+///
+/// # Parameters
+/// - `max_total_bytes`: If provided, the bytes that the running queries may hold in total
+///   before new queries are rejected
+/// - `query_budget_bytes`: If provided, the bytes that a single query may hold before it fails
+#[derive(Clone, Debug, Default)]
+pub struct MemoryPool {
+    used: Arc<AtomicUsize>,
+    max_total_bytes: Option<usize>,
+    query_budget_bytes: Option<usize>,
+    rejecting: Arc<AtomicBool>,
+}
+
+impl MemoryPool {
+    pub fn new(max_total_bytes: Option<usize>, query_budget_bytes: Option<usize>) -> Self {
+        Self {
+            used: Arc::default(),
+            max_total_bytes,
+            query_budget_bytes,
+            rejecting: Arc::default(),
+        }
+    }
+
+    /// The bytes held by the running queries
+    pub fn used_bytes(&self) -> usize {
+        self.used.load(Ordering::Relaxed)
+    }
+
+    /// Rejects the new queries until it is called again with `false`, while the running
+    /// queries continue
+    pub fn reject_new_queries(&self, reject: bool) {
+        self.rejecting.store(reject, Ordering::Relaxed);
+    }
+
+    /// Admits a new query and returns the context that accounts for its memory
+    pub fn admit(&self) -> Result<MemoryContext, MemoryError> {
+        if self.rejecting.load(Ordering::Relaxed) {
+            return Err(MemoryError::MemoryPressure);
+        }
+        let used = self.used_bytes();
+        if let Some(limit) = self.max_total_bytes {
+            if used >= limit {
+                return Err(MemoryError::WorkerLimitExceeded { used, limit });
+            }
+        }
+        Ok(MemoryContext {
+            inner: Arc::new(MemoryContextInner {
+                used: AtomicUsize::new(0),
+                budget: self.query_budget_bytes,
+                pool: Some(self.used.clone()),
+            }),
+        })
+    }
+}
+
+/// The memory held by a query in materialized logs, bitmaps and hydrated results. The operators
+/// of a query share it through their inputs and account for the bytes they hold, so that the
+/// query fails fast once it exceeds its budget. The bytes are returned to the pool once the
+/// context and all its clones are dropped
+#[derive(Clone, Debug, Default)]
+pub struct MemoryContext {
+    inner: Arc<MemoryContextInner>,
+}
+
+#[derive(Debug, Default)]
+struct MemoryContextInner {
+    used: AtomicUsize,
+    budget: Option<usize>,
+    pool: Option<Arc<AtomicUsize>>,
+}
+
+impl Drop for MemoryContextInner {
+    fn drop(&mut self) {
+        if let Some(pool) = &self.pool {
+            pool.fetch_sub(*self.used.get_mut(), Ordering::Relaxed);
+        }
+    }
+}
+
+impl MemoryContext {
+    /// A context that is not admitted by any pool, which only enforces the budget of the query
+    pub fn with_budget(budget: usize) -> Self {
+        Self {
+            inner: Arc::new(MemoryContextInner {
+                used: AtomicUsize::new(0),
+                budget: Some(budget),
+                pool: None,
+            }),
+        }
+    }
+
+    /// The bytes held by the query
+    pub fn used_bytes(&self) -> usize {
+        self.inner.used.load(Ordering::Relaxed)
+    }
+
+    /// Accounts for the bytes held by the query, and returns an error if the query exceeds
+    /// its budget. The bytes are accounted for even if the budget is exceeded
+    pub fn reserve(&self, bytes: usize) -> Result<(), MemoryError> {
+        self.track(|reserved| *reserved += bytes);
+        let used = self.inner.used.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        if let Some(pool) = &self.inner.pool {
+            pool.fetch_add(bytes, Ordering::Relaxed);
+        }
+        match self.inner.budget {
+            Some(budget) if used > budget => Err(MemoryError::QueryBudgetExceeded { used, budget }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Stops accounting for bytes that the query no longer holds, e.g. a batch of a streamed
+    /// result that is already sent
+    pub fn release(&self, bytes: usize) {
+        self.track(|reserved| *reserved = reserved.saturating_sub(bytes));
+        self.inner.used.fetch_sub(bytes, Ordering::Relaxed);
+        if let Some(pool) = &self.inner.pool {
+            pool.fetch_sub(bytes, Ordering::Relaxed);
+        }
+    }
+
+    // Adjusts the bytes that the enclosing `track_reservations` holds in the context, if any
+    fn track(&self, adjust: impl FnOnce(&mut usize)) {
+        let _ = ATTEMPT_RESERVATIONS.try_with(|reservations| {
+            let mut reservations = reservations.borrow_mut();
+            match reservations
+                .iter_mut()
+                .find(|(memory, _)| Arc::ptr_eq(&memory.inner, &self.inner))
+            {
+                Some((_, reserved)) => adjust(reserved),
+                None => {
+                    let mut reserved = 0;
+                    adjust(&mut reserved);
+                    reservations.push((self.clone(), reserved));
+                }
+            }
+        });
+    }
+
+    /// Accounts for the bytes that the logs hold at most once they are materialized, before
+    /// they are materialized, so that the query fails before it materializes more logs than
+    /// its budget allows. Each log materializes into at most one record. Returns the reserved
+    /// bytes, whose excess is released by `settle_log_materialization`
+    pub fn reserve_log_materialization(
+        &self,
+        logs: &Chunk<LogRecord>,
+    ) -> Result<usize, MemoryError> {
+        let bytes = logs.total_len() * size_of::<MaterializedLogRecord>();
+        self.reserve(bytes)?;
+        Ok(bytes)
+    }
+
+    /// Releases the bytes reserved for the materialization of the logs beyond the bytes that
+    /// the materialized logs hold
+    pub(crate) fn settle_log_materialization(
+        &self,
+        reserved: usize,
+        logs: &Chunk<MaterializedLogRecord>,
+    ) {
+        self.release(reserved.saturating_sub(materialized_log_bytes(logs)));
+    }
+
+    /// Accounts for the bytes held by the bitmaps
+    pub fn reserve_bitmaps<'a>(
+        &self,
+        bitmaps: impl IntoIterator<Item = &'a RoaringBitmap>,
+    ) -> Result<(), MemoryError> {
+        self.reserve(
+            bitmaps
+                .into_iter()
+                .map(RoaringBitmap::serialized_size)
+                .sum(),
+        )
+    }
+
+    /// Accounts for the bytes held by the signed bitmaps
+    pub fn reserve_signed_bitmaps<'a>(
+        &self,
+        bitmaps: impl IntoIterator<Item = &'a SignedRoaringBitmap>,
+    ) -> Result<(), MemoryError> {
+        self.reserve_bitmaps(bitmaps.into_iter().map(|bitmap| match bitmap {
+            SignedRoaringBitmap::Include(rbm) | SignedRoaringBitmap::Exclude(rbm) => rbm,
+        }))
+    }
+}
+
+/// The bytes that a run of an operator holds in the memory contexts of its input, which are
+/// released if the run fails, so that a retry of the run does not account for them twice
+#[derive(Debug, Default)]
+pub(crate) struct Reservations(Vec<(MemoryContext, usize)>);
+
+impl Reservations {
+    /// The bytes held by the run in all the memory contexts
+    pub(crate) fn bytes(&self) -> usize {
+        self.0.iter().map(|(_, reserved)| reserved).sum()
+    }
+
+    pub(crate) fn release(self) {
+        for (memory, reserved) in self.0 {
+            memory.release(reserved);
+        }
+    }
+}
+
+/// Runs the future and returns its output together with the bytes that it reserves and does
+/// not release in the memory contexts. The reservations of tasks spawned by the future are not
+/// tracked
+pub(crate) async fn track_reservations<F: Future>(future: F) -> (F::Output, Reservations) {
+    ATTEMPT_RESERVATIONS
+        .scope(RefCell::new(Vec::new()), async {
+            let output = future.await;
+            let reservations = ATTEMPT_RESERVATIONS.with(|reservations| reservations.take());
+            (output, Reservations(reservations))
+        })
+        .await
+}
+
+/// Estimates the bytes held by the materialized logs. The records read from the record segment
+/// and the logs are referred to by the materialized logs and are not counted
+pub(crate) fn materialized_log_bytes(logs: &Chunk<MaterializedLogRecord>) -> usize {
+    logs.total_len() * size_of::<MaterializedLogRecord>()
+}
+
+/// Estimates the bytes held by a metadata value
+pub(crate) fn metadata_value_bytes(value: &MetadataValue) -> usize {
+    size_of::<MetadataValue>()
+        + match value {
+            MetadataValue::Str(s) => s.len(),
+            MetadataValue::List(values) => values.iter().map(metadata_value_bytes).sum(),
+            MetadataValue::Object(entries) => entries
+                .iter()
+                .map(|(key, value)| key.len() + metadata_value_bytes(value))
+                .sum(),
+            MetadataValue::Bool(_)
+            | MetadataValue::Int(_)
+            | MetadataValue::Float(_)
+            | MetadataValue::Timestamp(_) => 0,
+        }
+}
+
+#[cfg(test)]
+mod tests {
+    use chroma_types::Chunk;
+
+    use crate::log::test::{upsert_generator, LogGenerator};
+
+    use super::{track_reservations, MemoryContext, MemoryError, MemoryPool};
+
+    #[test]
+    fn test_query_budget() {
+        let memory = MemoryContext::with_budget(100);
+        memory
+            .reserve(60)
+            .expect("Reservation within budget should succeed");
+        assert!(matches!(
+            memory.reserve(60),
+            Err(MemoryError::QueryBudgetExceeded {
+                used: 120,
+                budget: 100
+            })
+        ));
+    }
+
+    #[test]
+    fn test_pool_admission() {
+        let pool = MemoryPool::new(Some(100), None);
+        let first = pool.admit().expect("First query should be admitted");
+        first.reserve(80).unwrap();
+        let second = pool.admit().expect("Second query should be admitted");
+        second.clone().reserve(20).unwrap();
+        assert_eq!(pool.used_bytes(), 100);
+        assert!(matches!(
+            pool.admit(),
+            Err(MemoryError::WorkerLimitExceeded { .. })
+        ));
+
+        // The memory of a query is released once all its clones are dropped
+        drop(first);
+        assert_eq!(pool.used_bytes(), 20);
+        drop(second);
+        assert_eq!(pool.used_bytes(), 0);
+        pool.admit()
+            .expect("Query should be admitted after memory is released");
+    }
+
+    #[test]
+    fn test_release() {
+        let pool = MemoryPool::new(Some(100), Some(100));
+        let memory = pool.admit().expect("Query should be admitted");
+        memory.reserve(80).unwrap();
+        memory.release(80);
+        assert_eq!(memory.used_bytes(), 0);
+        assert_eq!(pool.used_bytes(), 0);
+
+        // The released bytes are available to the rest of the query
+        memory
+            .reserve(80)
+            .expect("Reservation within budget should succeed");
+    }
+
+    #[test]
+    fn test_log_materialization_is_reserved_before_it_runs() {
+        let logs = LogGenerator {
+            generator: upsert_generator,
+        }
+        .generate_chunk(1..=10);
+        let memory = MemoryContext::with_budget(1);
+        assert!(matches!(
+            memory.reserve_log_materialization(&logs),
+            Err(MemoryError::QueryBudgetExceeded { .. })
+        ));
+
+        // The excess is released once the logs are materialized
+        let memory = MemoryContext::default();
+        let reserved = memory
+            .reserve_log_materialization(&logs)
+            .expect("Reservation without budget should succeed");
+        assert_eq!(memory.used_bytes(), reserved);
+        memory.settle_log_materialization(reserved, &Chunk::new(Vec::new().into()));
+        assert_eq!(memory.used_bytes(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_release_tracked_reservations() {
+        let memory = MemoryContext::with_budget(100);
+        memory.reserve(30).unwrap();
+        let (result, reservations) = track_reservations(async {
+            memory.reserve(50)?;
+            memory.release(10);
+            memory.reserve(40)
+        })
+        .await;
+        assert!(matches!(
+            result,
+            Err(MemoryError::QueryBudgetExceeded { used: 110, .. })
+        ));
+
+        // Only the bytes that the failed run holds are released
+        reservations.release();
+        assert_eq!(memory.used_bytes(), 30);
+        memory
+            .reserve(70)
+            .expect("Reservation within budget should succeed");
+    }
+}
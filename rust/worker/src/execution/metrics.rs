@@ -0,0 +1,289 @@
+use std::{sync::OnceLock, time::Duration};
+
+use chroma_types::{
+    BooleanOperator, CollectionUuid, DocumentOperator, ExistenceOperator, PrimitiveOperator,
+    SetOperator, Where, WhereComparison,
+};
+use opentelemetry::{
+    global,
+    metrics::{Counter, Histogram},
+    KeyValue,
+};
+
+/// Metrics of the shape of the queries served, labeled by collection. Only the operators and
+/// the sizes of a query are recorded, never its metadata keys, values or documents, so that
+/// the dominant query patterns of a collection can be seen without exposing its data
+pub(crate) struct QueryMetrics {
+    queries: Counter<u64>,
+    filter_operators: Counter<u64>,
+    n_results: Histogram<u64>,
+    candidate_set_size: Histogram<u64>,
+}
+
+/// The kind of query, which is used as the `query_type` label
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum QueryType {
+    Get,
+    Knn,
+}
+
+impl QueryType {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            QueryType::Get => "get",
+            QueryType::Knn => "knn",
+        }
+    }
+}
+
+impl QueryMetrics {
+    fn new() -> Self {
+        let meter = global::meter("chroma");
+        Self {
+            queries: meter
+                .u64_counter("query_requests")
+                .with_description("Number of queries, labeled by their include flags")
+                .init(),
+            filter_operators: meter
+                .u64_counter("query_filter_operators")
+                .with_description("Number of times each filter operator is used by queries")
+                .init(),
+            n_results: meter
+                .u64_histogram("query_n_results")
+                .with_description("Number of results requested by queries")
+                .init(),
+            candidate_set_size: meter
+                .u64_histogram("query_candidate_set_size")
+                .with_description("Number of records that pass the filters of queries")
+                .init(),
+        }
+    }
+
+    /// Returns the query metrics, registering them with the global meter on first use
+    pub(crate) fn get() -> &'static Self {
+        static METRICS: OnceLock<QueryMetrics> = OnceLock::new();
+        METRICS.get_or_init(Self::new)
+    }
+
+    pub(crate) fn record_query(
+        &self,
+        collection_id: CollectionUuid,
+        query_type: QueryType,
+        n_results: Option<u32>,
+        include_metadata: bool,
+        include_embeddings: bool,
+        where_clause: Option<&Where>,
+    ) {
+        let collection_label = KeyValue::new("collection_id", collection_id.to_string());
+        let query_type_label = KeyValue::new("query_type", query_type.as_str());
+        self.queries.add(
+            1,
+            &[
+                collection_label.clone(),
+                query_type_label.clone(),
+                KeyValue::new("include_metadata", include_metadata),
+                KeyValue::new("include_embeddings", include_embeddings),
+            ],
+        );
+        if let Some(n_results) = n_results {
+            self.n_results.record(
+                n_results as u64,
+                &[collection_label.clone(), query_type_label],
+            );
+        }
+        for operator in where_clause.map(where_operators).unwrap_or_default() {
+            self.filter_operators.add(
+                1,
+                &[
+                    collection_label.clone(),
+                    KeyValue::new("operator", operator),
+                ],
+            );
+        }
+    }
+
+    pub(crate) fn record_candidate_set_size(
+        &self,
+        collection_id: CollectionUuid,
+        query_type: QueryType,
+        size: u64,
+    ) {
+        let labels = [
+            KeyValue::new("collection_id", collection_id.to_string()),
+            KeyValue::new("query_type", query_type.as_str()),
+        ];
+        self.candidate_set_size.record(size, &labels);
+    }
+}
+
+/// Metrics of the runs of the operators, labeled by operator and, for the operators that know
+/// it, by collection
+pub(crate) struct OperatorMetrics {
+    run_duration: Histogram<f64>,
+    input_records: Histogram<u64>,
+    output_records: Histogram<u64>,
+    bytes_read: Histogram<u64>,
+    reserved_bytes: Histogram<u64>,
+}
+
+impl OperatorMetrics {
+    fn new() -> Self {
+        let meter = global::meter("chroma");
+        Self {
+            run_duration: meter
+                .f64_histogram("operator_run_duration_ms")
+                .with_description("Latency of operator runs in milliseconds, including retries")
+                .init(),
+            input_records: meter
+                .u64_histogram("operator_input_records")
+                .with_description("Number of records consumed by operator runs")
+                .init(),
+            output_records: meter
+                .u64_histogram("operator_output_records")
+                .with_description("Number of records produced by successful operator runs")
+                .init(),
+            bytes_read: meter
+                .u64_histogram("operator_blockstore_bytes_read")
+                .with_description("Number of block bytes read from storage by operator runs")
+                .init(),
+            reserved_bytes: meter
+                .u64_histogram("operator_reserved_bytes")
+                .with_description(
+                    "Number of bytes that successful operator runs hold in the memory of their query",
+                )
+                .init(),
+        }
+    }
+
+    /// Returns the operator metrics, registering them with the global meter on first use
+    pub(crate) fn get() -> &'static Self {
+        static METRICS: OnceLock<OperatorMetrics> = OnceLock::new();
+        METRICS.get_or_init(Self::new)
+    }
+
+    pub(crate) fn record_run(
+        &self,
+        operator: &'static str,
+        collection_id: Option<CollectionUuid>,
+        elapsed: Duration,
+        input_records: Option<usize>,
+        output_records: Option<usize>,
+        bytes_read: u64,
+    ) {
+        let mut labels = vec![KeyValue::new("operator", operator)];
+        if let Some(collection_id) = collection_id {
+            labels.push(KeyValue::new("collection_id", collection_id.to_string()));
+        }
+        self.run_duration
+            .record(elapsed.as_secs_f64() * 1000.0, &labels);
+        if let Some(input_records) = input_records {
+            self.input_records.record(input_records as u64, &labels);
+        }
+        if let Some(output_records) = output_records {
+            self.output_records.record(output_records as u64, &labels);
+        }
+        self.bytes_read.record(bytes_read, &labels);
+    }
+
+    pub(crate) fn record_reserved_bytes(
+        &self,
+        operator: &'static str,
+        collection_id: Option<CollectionUuid>,
+        reserved_bytes: usize,
+    ) {
+        let mut labels = vec![KeyValue::new("operator", operator)];
+        if let Some(collection_id) = collection_id {
+            labels.push(KeyValue::new("collection_id", collection_id.to_string()));
+        }
+        self.reserved_bytes.record(reserved_bytes as u64, &labels);
+    }
+}
+
+/// Returns the operators used by the where clause in depth first order, without its keys
+/// and values
+pub(crate) fn where_operators(clause: &Where) -> Vec<&'static str> {
+    let mut operators = Vec::new();
+    collect_where_operators(clause, &mut operators);
+    operators
+}
+
+fn collect_where_operators(clause: &Where, operators: &mut Vec<&'static str>) {
+    match clause {
+        Where::DirectWhereComparison(direct_comparison) => {
+            operators.push(match &direct_comparison.comparison {
+                WhereComparison::Primitive(operator, _) => match operator {
+                    PrimitiveOperator::Equal => "$eq",
+                    PrimitiveOperator::NotEqual => "$ne",
+                    PrimitiveOperator::GreaterThan => "$gt",
+                    PrimitiveOperator::GreaterThanOrEqual => "$gte",
+                    PrimitiveOperator::LessThan => "$lt",
+                    PrimitiveOperator::LessThanOrEqual => "$lte",
+                    PrimitiveOperator::Contains => "$contains",
+                    PrimitiveOperator::NotContains => "$not_contains",
+                    PrimitiveOperator::EqualCaseInsensitive => "$eq_ci",
+                    PrimitiveOperator::StartsWith => "$starts_with",
+                },
+                WhereComparison::Set(operator, _) => match operator {
+                    SetOperator::In => "$in",
+                    SetOperator::NotIn => "$nin",
+                },
+                WhereComparison::Existence(operator) => match operator {
+                    ExistenceOperator::Exists => "$exists",
+                    ExistenceOperator::IsNull => "$is_null",
+                },
+            });
+        }
+        Where::DirectWhereDocumentComparison(direct_document_comparison) => {
+            operators.push(match direct_document_comparison.operator {
+                DocumentOperator::Contains => "document_$contains",
+                DocumentOperator::NotContains => "document_$not_contains",
+                DocumentOperator::Near(_) => "document_$near",
+                DocumentOperator::Regex => "document_$regex",
+                DocumentOperator::NotRegex => "document_$not_regex",
+            });
+        }
+        Where::WhereChildren(where_children) => {
+            operators.push(match where_children.operator {
+                BooleanOperator::And => "$and",
+                BooleanOperator::Or => "$or",
+            });
+            for child in &where_children.children {
+                collect_where_operators(child, operators);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chroma_types::{
+        BooleanOperator, DirectDocumentComparison, DirectWhereComparison, DocumentOperator,
+        MetadataValue, PrimitiveOperator, Where, WhereChildren, WhereComparison,
+    };
+
+    use super::where_operators;
+
+    #[test]
+    fn test_where_operators() {
+        let clause = Where::WhereChildren(WhereChildren {
+            operator: BooleanOperator::And,
+            children: vec![
+                Where::DirectWhereComparison(DirectWhereComparison {
+                    key: "secret_key".to_string(),
+                    comparison: WhereComparison::Primitive(
+                        PrimitiveOperator::GreaterThan,
+                        MetadataValue::Int(42),
+                    ),
+                }),
+                Where::DirectWhereDocumentComparison(DirectDocumentComparison {
+                    operator: DocumentOperator::Contains,
+                    document: "secret document".to_string(),
+                }),
+            ],
+        });
+        assert_eq!(
+            where_operators(&clause),
+            vec!["$and", "$gt", "document_$contains"]
+        );
+    }
+}
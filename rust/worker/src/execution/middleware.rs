@@ -0,0 +1,470 @@
+use std::{
+    fmt::Debug,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use chroma_blockstore::read_stats::count_reads;
+use chroma_types::CollectionUuid;
+use futures::future::BoxFuture;
+use uuid::Uuid;
+
+use super::{
+    config::OperatorMiddlewareConfig,
+    memory::track_reservations,
+    metrics::OperatorMetrics,
+    operator::{Deadline, RetryPolicy},
+};
+
+/// The outcome of a run of an operator, as seen by the layers of its middleware. The layers
+/// do not know the types of the output and the error of the operator, so they only see what
+/// they need to decide on, e.g. whether a failed run may be retried
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RunOutcome {
+    /// The operator returned its output, with the number of its records if it is known
+    Succeeded { output_records: Option<usize> },
+    /// The operator returned an error, which may be retried if `retryable` is set
+    Failed { retryable: bool },
+    /// The operator panicked
+    Panicked,
+    /// A layer stopped the run before the operator returned, e.g. on a timeout
+    Aborted(String),
+    /// The operator returned an error that is retried by running the task again after the
+    /// backoff
+    RetryAfter(Duration),
+}
+
+/// The task whose operator a layer runs
+#[derive(Clone, Debug)]
+pub struct RunContext {
+    pub task_id: Uuid,
+    pub operator_name: &'static str,
+    pub collection_id: Option<CollectionUuid>,
+    pub input_records: Option<usize>,
+    pub deadline: Option<Deadline>,
+    /// The number of earlier attempts of the task that failed and were retried
+    pub failed_attempts: usize,
+    pub retry_policy: RetryPolicy,
+}
+
+/// A layer of the middleware wraps the runs of the operators of the tasks, e.g. to retry or
+/// to time them. It calls `next.run` to run the rest of the stack, which it may skip, repeat
+/// or abandon
+#[async_trait]
+pub trait OperatorLayer: Debug + Send + Sync {
+    async fn call(&self, context: &RunContext, next: Next<'_>) -> RunOutcome;
+}
+
+/// The rest of the stack below a layer, which ends with a run of the operator
+#[derive(Clone, Copy)]
+pub struct Next<'a> {
+    layers: &'a [Arc<dyn OperatorLayer>],
+    attempt: &'a (dyn Fn() -> BoxFuture<'a, RunOutcome> + Send + Sync),
+}
+
+impl Next<'_> {
+    pub async fn run(self, context: &RunContext) -> RunOutcome {
+        match self.layers.split_first() {
+            Some((layer, layers)) => {
+                layer
+                    .call(
+                        context,
+                        Next {
+                            layers,
+                            attempt: self.attempt,
+                        },
+                    )
+                    .await
+            }
+            None => (self.attempt)().await,
+        }
+    }
+}
+
+/// The stack of layers that the operators of a task run in, where the first layer is the
+/// outermost. Orchestrators configure it once and wrap all their tasks with it, so that
+/// cross-cutting concerns are not implemented by the operators themselves
+#[derive(Clone, Debug)]
+pub struct OperatorMiddleware {
+    layers: Vec<Arc<dyn OperatorLayer>>,
+}
+
+impl Default for OperatorMiddleware {
+    /// Accounts for the memory of the runs, records their metrics and retries the transient
+    /// errors of the operators that allow it
+    fn default() -> Self {
+        Self::empty()
+            .layer(MemoryTrackingLayer)
+            .layer(MetricsLayer)
+            .layer(RetryLayer)
+    }
+}
+
+impl OperatorMiddleware {
+    /// A stack without layers, which runs the operators once
+    pub fn empty() -> Self {
+        Self { layers: Vec::new() }
+    }
+
+    /// Adds the layer below the layers of the stack
+    pub fn layer(mut self, layer: impl OperatorLayer + 'static) -> Self {
+        self.layers.push(Arc::new(layer));
+        self
+    }
+
+    /// Runs the attempt, which runs the operator once, through the layers of the stack
+    pub(super) async fn run<'a>(
+        &'a self,
+        context: &RunContext,
+        attempt: &'a (dyn Fn() -> BoxFuture<'a, RunOutcome> + Send + Sync),
+    ) -> RunOutcome {
+        Next {
+            layers: &self.layers,
+            attempt,
+        }
+        .run(context)
+        .await
+    }
+}
+
+impl From<&OperatorMiddlewareConfig> for OperatorMiddleware {
+    /// The default layers, with the timeout below the retries so that each attempt is timed
+    /// out on its own, and the faults injected right above the operator
+    fn from(config: &OperatorMiddlewareConfig) -> Self {
+        let mut middleware = Self::default();
+        if let Some(timeout_ms) = config.timeout_ms {
+            middleware = middleware.layer(TimeoutLayer::new(Duration::from_millis(timeout_ms)));
+        }
+        if config.fault_injection_probability > 0.0 || config.fault_injection_latency_ms > 0 {
+            middleware = middleware.layer(FaultInjectionLayer {
+                failure_probability: config.fault_injection_probability,
+                latency: Duration::from_millis(config.fault_injection_latency_ms),
+            });
+        }
+        middleware
+    }
+}
+
+/// Tracks the bytes that the runs reserve in the memory contexts of their queries. The output
+/// of a run that does not succeed is dropped, so the bytes that it reserved are released and
+/// a retry of the run does not account for them twice. It should be above the layers that
+/// abandon runs, e.g. on a timeout, so that it sees their outcome
+#[derive(Debug)]
+pub struct MemoryTrackingLayer;
+
+#[async_trait]
+impl OperatorLayer for MemoryTrackingLayer {
+    async fn call(&self, context: &RunContext, next: Next<'_>) -> RunOutcome {
+        let (outcome, reservations) = track_reservations(next.run(context)).await;
+        match outcome {
+            RunOutcome::Succeeded { .. } => OperatorMetrics::get().record_reserved_bytes(
+                context.operator_name,
+                context.collection_id,
+                reservations.bytes(),
+            ),
+            _ => reservations.release(),
+        }
+        outcome
+    }
+}
+
+/// Records the duration, the cardinalities and the block reads of the runs, including the
+/// retries below the layer
+#[derive(Debug)]
+pub struct MetricsLayer;
+
+#[async_trait]
+impl OperatorLayer for MetricsLayer {
+    async fn call(&self, context: &RunContext, next: Next<'_>) -> RunOutcome {
+        let started = Instant::now();
+        let (outcome, read_stats) = count_reads(next.run(context)).await;
+        let output_records = match &outcome {
+            RunOutcome::Succeeded { output_records } => *output_records,
+            _ => None,
+        };
+        OperatorMetrics::get().record_run(
+            context.operator_name,
+            context.collection_id,
+            started.elapsed(),
+            context.input_records,
+            output_records,
+            read_stats.bytes_read,
+        );
+        outcome
+    }
+}
+
+/// Retries the runs that fail with an error that the operator allows to retry, with the
+/// backoff of the retry policy of the task. The retry is not run in place: the task is
+/// requeued once the backoff has passed, so that the backoff does not hold a worker thread.
+/// A failed run is not retried once its attempts are exhausted, or if the deadline of its
+/// query passes before the retry would run
+#[derive(Debug)]
+pub struct RetryLayer;
+
+#[async_trait]
+impl OperatorLayer for RetryLayer {
+    async fn call(&self, context: &RunContext, next: Next<'_>) -> RunOutcome {
+        match next.run(context).await {
+            RunOutcome::Failed { retryable: true }
+                if context.failed_attempts + 1 < context.retry_policy.max_attempts =>
+            {
+                let backoff = context.retry_policy.backoff(context.failed_attempts + 1);
+                let deadline_passes = context.deadline.as_ref().is_some_and(|deadline| {
+                    deadline.check().is_err()
+                        || deadline
+                            .remaining()
+                            .is_some_and(|remaining| remaining <= backoff)
+                });
+                if deadline_passes {
+                    RunOutcome::Failed { retryable: true }
+                } else {
+                    RunOutcome::RetryAfter(backoff)
+                }
+            }
+            outcome => outcome,
+        }
+    }
+}
+
+/// Aborts the runs that take longer than the timeout
+#[derive(Debug)]
+pub struct TimeoutLayer {
+    timeout: Duration,
+}
+
+impl TimeoutLayer {
+    pub fn new(timeout: Duration) -> Self {
+        Self { timeout }
+    }
+}
+
+#[async_trait]
+impl OperatorLayer for TimeoutLayer {
+    async fn call(&self, context: &RunContext, next: Next<'_>) -> RunOutcome {
+        match tokio::time::timeout(self.timeout, next.run(context)).await {
+            Ok(outcome) => outcome,
+            Err(_) => {
+                tracing::warn!(
+                    "Task {} of {} timed out after {:?}",
+                    context.task_id,
+                    context.operator_name,
+                    self.timeout
+                );
+                RunOutcome::Aborted(format!(
+                    "{} timed out after {:?}",
+                    context.operator_name, self.timeout
+                ))
+            }
+        }
+    }
+}
+
+/// Aborts a share of the runs before the operator runs and delays the others, so that tests
+/// can exercise how the orchestrators handle failed and slow operators
+#[derive(Debug, Default)]
+pub struct FaultInjectionLayer {
+    /// The probability that a run is aborted
+    pub failure_probability: f64,
+    /// The delay before the runs that are not aborted
+    pub latency: Duration,
+}
+
+#[async_trait]
+impl OperatorLayer for FaultInjectionLayer {
+    async fn call(&self, context: &RunContext, next: Next<'_>) -> RunOutcome {
+        if rand::random::<f64>() < self.failure_probability {
+            return RunOutcome::Aborted(format!("Fault injected into {}", context.operator_name));
+        }
+        if !self.latency.is_zero() {
+            tokio::time::sleep(self.latency).await;
+        }
+        next.run(context).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    use futures::FutureExt;
+    use parking_lot::Mutex;
+
+    use super::*;
+    use crate::execution::memory::MemoryContext;
+
+    // A layer that records the order in which the layers are entered
+    #[derive(Debug)]
+    struct TraceLayer {
+        name: &'static str,
+        trace: Arc<Mutex<Vec<&'static str>>>,
+    }
+    #[async_trait]
+    impl OperatorLayer for TraceLayer {
+        async fn call(&self, context: &RunContext, next: Next<'_>) -> RunOutcome {
+            self.trace.lock().push(self.name);
+            next.run(context).await
+        }
+    }
+
+    fn context() -> RunContext {
+        RunContext {
+            task_id: Uuid::new_v4(),
+            operator_name: "TestOperator",
+            collection_id: None,
+            input_records: None,
+            deadline: None,
+            failed_attempts: 0,
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_layer_order() {
+        let trace = Arc::new(Mutex::new(Vec::new()));
+        let middleware = OperatorMiddleware::empty()
+            .layer(TraceLayer {
+                name: "outer",
+                trace: trace.clone(),
+            })
+            .layer(TraceLayer {
+                name: "inner",
+                trace: trace.clone(),
+            });
+        let attempt = || {
+            let trace = trace.clone();
+            async move {
+                trace.lock().push("operator");
+                RunOutcome::Succeeded {
+                    output_records: Some(1),
+                }
+            }
+            .boxed()
+        };
+        let outcome = middleware.run(&context(), &attempt).await;
+        assert_eq!(
+            outcome,
+            RunOutcome::Succeeded {
+                output_records: Some(1)
+            }
+        );
+        assert_eq!(*trace.lock(), vec!["outer", "inner", "operator"]);
+    }
+
+    #[tokio::test]
+    async fn test_timeout_and_fault_injection() {
+        let attempts = AtomicUsize::new(0);
+        let attempt = || {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            async {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+                RunOutcome::Succeeded {
+                    output_records: None,
+                }
+            }
+            .boxed()
+        };
+
+        let middleware =
+            OperatorMiddleware::empty().layer(TimeoutLayer::new(Duration::from_millis(10)));
+        assert!(matches!(
+            middleware.run(&context(), &attempt).await,
+            RunOutcome::Aborted(_)
+        ));
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+
+        // An injected fault aborts the run before the operator runs
+        let middleware = OperatorMiddleware::empty().layer(FaultInjectionLayer {
+            failure_probability: 1.0,
+            ..Default::default()
+        });
+        assert!(matches!(
+            middleware.run(&context(), &attempt).await,
+            RunOutcome::Aborted(_)
+        ));
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_memory_tracking_layer() {
+        let memory = MemoryContext::default();
+        let succeeds = AtomicBool::new(false);
+        let attempt = || {
+            let memory = memory.clone();
+            let succeeds = succeeds.load(Ordering::SeqCst);
+            async move {
+                memory.reserve(10).unwrap();
+                if succeeds {
+                    RunOutcome::Succeeded {
+                        output_records: None,
+                    }
+                } else {
+                    RunOutcome::Failed { retryable: true }
+                }
+            }
+            .boxed()
+        };
+        let middleware = OperatorMiddleware::empty().layer(MemoryTrackingLayer);
+
+        // The bytes reserved by a failed run are released
+        middleware.run(&context(), &attempt).await;
+        assert_eq!(memory.used_bytes(), 0);
+
+        // The bytes reserved by a successful run are held by its output
+        succeeds.store(true, Ordering::SeqCst);
+        middleware.run(&context(), &attempt).await;
+        assert_eq!(memory.used_bytes(), 10);
+
+        // The bytes reserved by a run that is abandoned below the layer are released
+        let attempt = || {
+            let memory = memory.clone();
+            async move {
+                memory.reserve(10).unwrap();
+                tokio::time::sleep(Duration::from_secs(10)).await;
+                RunOutcome::Succeeded {
+                    output_records: None,
+                }
+            }
+            .boxed()
+        };
+        let middleware = OperatorMiddleware::empty()
+            .layer(MemoryTrackingLayer)
+            .layer(TimeoutLayer::new(Duration::from_millis(10)));
+        middleware.run(&context(), &attempt).await;
+        assert_eq!(memory.used_bytes(), 10);
+    }
+
+    #[tokio::test]
+    async fn test_retry_layer() {
+        let attempt = || async { RunOutcome::Failed { retryable: true } }.boxed();
+        let middleware = OperatorMiddleware::empty().layer(RetryLayer);
+
+        // A transient error is retried after a backoff until the attempts are exhausted
+        let mut context = context();
+        assert!(matches!(
+            middleware.run(&context, &attempt).await,
+            RunOutcome::RetryAfter(_)
+        ));
+        context.failed_attempts = context.retry_policy.max_attempts - 1;
+        assert_eq!(
+            middleware.run(&context, &attempt).await,
+            RunOutcome::Failed { retryable: true }
+        );
+
+        // A transient error is not retried past the deadline of the query
+        let mut context = self::context();
+        context.deadline = Some(Deadline::after(Duration::ZERO));
+        assert_eq!(
+            middleware.run(&context, &attempt).await,
+            RunOutcome::Failed { retryable: true }
+        );
+        let deadline = Deadline::default();
+        deadline.cancel();
+        context.deadline = Some(deadline);
+        assert_eq!(
+            middleware.run(&context, &attempt).await,
+            RunOutcome::Failed { retryable: true }
+        );
+    }
+}
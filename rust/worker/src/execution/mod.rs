@@ -1,8 +1,16 @@
 pub(crate) mod config;
+pub(crate) mod decode_executor;
 pub(crate) mod dispatcher;
+pub(crate) mod explain;
+pub(crate) mod metrics;
+pub(crate) mod middleware;
 pub(crate) mod orchestration;
+pub(crate) mod result_cache;
+pub(crate) mod slow_query;
+pub(crate) mod spill;
 mod worker_thread;
 
 // Required for benchmark
+pub mod memory;
 pub mod operator;
 pub mod operators;
@@ -1,9 +1,26 @@
-use crate::{system::ReceiverForMessage, utils::get_panic_message};
+use crate::{
+    execution::{
+        config::OperatorRetryConfig,
+        middleware::{OperatorMiddleware, RunContext, RunOutcome},
+    },
+    system::ReceiverForMessage,
+    utils::get_panic_message,
+};
 use async_trait::async_trait;
+use chroma_blockstore::read_stats::{count_reads, ReadStats};
 use chroma_error::{ChromaError, ErrorCodes};
+use chroma_types::CollectionUuid;
 use futures::FutureExt;
-use std::{any::type_name, fmt::Debug, panic::AssertUnwindSafe};
+use parking_lot::Mutex;
+use std::{
+    any::type_name,
+    fmt::Debug,
+    panic::AssertUnwindSafe,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
 use thiserror::Error;
+use tokio_util::sync::{CancellationToken, DropGuard};
 use uuid::Uuid;
 
 pub enum OperatorType {
@@ -11,6 +28,16 @@ pub enum OperatorType {
     Other,
 }
 
+/// The priority class of a task. The dispatcher keeps a queue for each class, and its workers
+/// prefer the tasks of their own class, so that large batch work such as compaction or full
+/// scans does not starve interactive queries
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TaskPriority {
+    #[default]
+    Interactive,
+    Batch,
+}
+
 /// An operator takes a generic input and returns a generic output.
 /// It is a definition of a function.
 #[async_trait]
@@ -29,6 +56,139 @@ where
     fn get_type(&self) -> OperatorType {
         OperatorType::Other
     }
+    /// Whether a run that fails with the error may be retried. Only idempotent operators,
+    /// e.g. readers without side effects, should allow it, and only for transient errors
+    /// (see `ChromaError::is_transient`), so that a retry is safe and may succeed
+    fn can_retry(&self, _error: &Self::Error) -> bool {
+        false
+    }
+    /// The deadline of the query that the input belongs to, past which a failed run is not
+    /// retried
+    fn deadline(&self, _input: &I) -> Option<Deadline> {
+        None
+    }
+    /// The collection that the input belongs to, which labels the metrics of the run
+    fn collection_id(&self, _input: &I) -> Option<CollectionUuid> {
+        None
+    }
+    /// The number of records that the operator consumes from the input, if it is known
+    /// without running the operator
+    fn input_cardinality(&self, _input: &I) -> Option<usize> {
+        None
+    }
+    /// The number of records that the operator produces in the output
+    fn output_cardinality(&self, _output: &O) -> Option<usize> {
+        None
+    }
+}
+
+/// The policy of the retries of the operators that allow them. The backoff before a retry
+/// grows exponentially from `initial_backoff` up to `max_backoff`, and is jittered so that
+/// the retries of concurrent tasks are spread out. The dispatcher applies its configured
+/// policy to the tasks that it runs
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    pub max_attempts: usize,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(50),
+            max_backoff: Duration::from_secs(1),
+        }
+    }
+}
+
+impl From<&OperatorRetryConfig> for RetryPolicy {
+    fn from(config: &OperatorRetryConfig) -> Self {
+        Self {
+            max_attempts: config.max_attempts,
+            initial_backoff: Duration::from_millis(config.initial_backoff_ms),
+            max_backoff: Duration::from_millis(config.max_backoff_ms),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// The backoff before the retry that follows the given number of failed attempts
+    pub fn backoff(&self, failed_attempts: usize) -> Duration {
+        let exponential = u32::try_from(failed_attempts.saturating_sub(1))
+            .ok()
+            .and_then(|exponent| 2u32.checked_pow(exponent))
+            .map(|factor| self.initial_backoff.saturating_mul(factor))
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff);
+        // Half of the backoff is jittered, so that it stays within [exponential / 2, exponential]
+        let half = exponential / 2;
+        half + half.mul_f64(rand::random::<f64>())
+    }
+}
+
+/// The deadline of a query, together with a token that cancels the query before the deadline,
+/// e.g. when the client stops waiting for the result. The operators of a query share it through
+/// their inputs and check it at block reads and loop boundaries, so that they stop early
+/// instead of running to completion
+#[derive(Clone, Debug, Default)]
+pub struct Deadline {
+    instant: Option<Instant>,
+    cancellation_token: CancellationToken,
+}
+
+#[derive(Debug, Error)]
+pub enum DeadlineError {
+    #[error("Query cancelled")]
+    Cancelled,
+    #[error("Query deadline exceeded")]
+    Exceeded,
+}
+
+impl ChromaError for DeadlineError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            DeadlineError::Cancelled => ErrorCodes::Cancelled,
+            DeadlineError::Exceeded => ErrorCodes::DeadlineExceeded,
+        }
+    }
+}
+
+impl Deadline {
+    /// A deadline that is reached after the timeout
+    pub fn after(timeout: Duration) -> Self {
+        Self {
+            instant: Instant::now().checked_add(timeout),
+            cancellation_token: CancellationToken::new(),
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.cancellation_token.cancel();
+    }
+
+    /// Returns a guard that cancels the query when it is dropped
+    pub fn cancel_on_drop(&self) -> DropGuard {
+        self.cancellation_token.clone().drop_guard()
+    }
+
+    /// The time left before the deadline is reached, or `None` if the query has no deadline
+    pub fn remaining(&self) -> Option<Duration> {
+        self.instant
+            .map(|instant| instant.saturating_duration_since(Instant::now()))
+    }
+
+    /// Returns an error if the query is cancelled or its deadline is reached
+    pub fn check(&self) -> Result<(), DeadlineError> {
+        if self.cancellation_token.is_cancelled() {
+            return Err(DeadlineError::Cancelled);
+        }
+        match self.instant {
+            Some(instant) if Instant::now() >= instant => Err(DeadlineError::Exceeded),
+            _ => Ok(()),
+        }
+    }
 }
 
 #[derive(Debug, Error)]
@@ -37,6 +197,8 @@ pub(super) enum TaskError<Err> {
     Panic(Option<String>),
     #[error("Task failed with error: {0:?}")]
     TaskFailed(#[from] Err),
+    #[error("Task aborted by its middleware: {0}")]
+    Aborted(String),
 }
 
 impl<Err> ChromaError for TaskError<Err>
@@ -47,6 +209,7 @@ where
         match self {
             TaskError::Panic(_) => ErrorCodes::Internal,
             TaskError::TaskFailed(e) => e.code(),
+            TaskError::Aborted(_) => ErrorCodes::Aborted,
         }
     }
 }
@@ -66,6 +229,7 @@ where
 pub(super) struct TaskResult<Output, Error> {
     result: Result<Output, TaskError<Error>>,
     task_id: Uuid,
+    read_stats: ReadStats,
 }
 
 impl<Output, Error> TaskResult<Output, Error> {
@@ -76,6 +240,21 @@ impl<Output, Error> TaskResult<Output, Error> {
     pub(super) fn id(&self) -> Uuid {
         self.task_id
     }
+
+    /// The block reads of the task, including the ones of its failed attempts
+    pub(super) fn read_stats(&self) -> ReadStats {
+        self.read_stats
+    }
+}
+
+/// Whether a run of a task is final, or the task is to run again after a backoff
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum TaskStatus {
+    /// The result of the task is sent to its reply channel
+    Done,
+    /// The attempt failed with a transient error, and the task is to be requeued once the
+    /// backoff has passed
+    RetryAfter(Duration),
 }
 
 /// A task is a wrapper around an operator and its input.
@@ -90,6 +269,12 @@ where
     input: Input,
     reply_channel: Box<dyn ReceiverForMessage<TaskResult<Output, Error>>>,
     task_id: Uuid,
+    priority: TaskPriority,
+    middleware: OperatorMiddleware,
+    retry_policy: RetryPolicy,
+    failed_attempts: AtomicUsize,
+    // The block reads of the attempts so far
+    read_stats: Mutex<ReadStats>,
 }
 
 /// A message type used by the dispatcher to send tasks to worker threads.
@@ -100,9 +285,11 @@ pub(crate) type TaskMessage = Box<dyn TaskWrapper>;
 #[async_trait]
 pub(crate) trait TaskWrapper: Send + Debug {
     fn get_name(&self) -> &'static str;
-    async fn run(&self);
+    async fn run(&self) -> TaskStatus;
+    fn set_retry_policy(&mut self, retry_policy: RetryPolicy);
     fn id(&self) -> Uuid;
     fn get_type(&self) -> OperatorType;
+    fn get_priority(&self) -> TaskPriority;
 }
 
 /// Implement the TaskWrapper trait for every Task. This allows us to
@@ -119,10 +306,61 @@ where
         self.operator.get_name()
     }
 
-    async fn run(&self) {
-        let result = AssertUnwindSafe(self.operator.run(&self.input))
-            .catch_unwind()
-            .await;
+    async fn run(&self) -> TaskStatus {
+        let context = RunContext {
+            task_id: self.task_id,
+            operator_name: self.operator.get_name(),
+            collection_id: self.operator.collection_id(&self.input),
+            input_records: self.operator.input_cardinality(&self.input),
+            deadline: self.operator.deadline(&self.input),
+            failed_attempts: self.failed_attempts.load(Ordering::Relaxed),
+            retry_policy: self.retry_policy.clone(),
+        };
+        // The layers only see the outcome of an attempt, so its result is kept aside
+        let last_result = Mutex::new(None);
+        let attempt = || {
+            async {
+                let result = AssertUnwindSafe(self.operator.run(&self.input))
+                    .catch_unwind()
+                    .await;
+                let outcome = match &result {
+                    Ok(Ok(output)) => RunOutcome::Succeeded {
+                        output_records: self.operator.output_cardinality(output),
+                    },
+                    Ok(Err(err)) => RunOutcome::Failed {
+                        retryable: self.operator.can_retry(err),
+                    },
+                    Err(_) => RunOutcome::Panicked,
+                };
+                *last_result.lock() = Some(result);
+                outcome
+            }
+            .boxed()
+        };
+        let (outcome, attempt_read_stats) =
+            count_reads(self.middleware.run(&context, &attempt)).await;
+        let read_stats = {
+            let mut read_stats = self.read_stats.lock();
+            *read_stats += attempt_read_stats;
+            *read_stats
+        };
+        if let RunOutcome::RetryAfter(backoff) = outcome {
+            let failed_attempts = self.failed_attempts.fetch_add(1, Ordering::Relaxed) + 1;
+            tracing::warn!(
+                "Task {} failed with transient error on attempt {}, retrying in {:?}",
+                self.task_id,
+                failed_attempts,
+                backoff
+            );
+            return TaskStatus::RetryAfter(backoff);
+        }
+        let result = match (outcome, last_result.into_inner()) {
+            (RunOutcome::Aborted(reason), _) => Ok(Err(TaskError::Aborted(reason))),
+            (_, Some(result)) => result.map(|result| result.map_err(TaskError::TaskFailed)),
+            (_, None) => Ok(Err(TaskError::Aborted(
+                "Task finished without running its operator".to_string(),
+            ))),
+        };
 
         match result {
             Ok(result) => {
@@ -138,8 +376,9 @@ where
                     .reply_channel
                     .send(
                         TaskResult {
-                            result: result.map_err(|e| TaskError::TaskFailed(e)),
+                            result,
                             task_id: self.task_id,
+                            read_stats,
                         },
                         None,
                     )
@@ -164,6 +403,7 @@ where
                         TaskResult {
                             result: Err(TaskError::Panic(panic_message.clone())),
                             task_id: self.task_id,
+                            read_stats,
                         },
                         None,
                     )
@@ -186,6 +426,11 @@ where
                 );
             }
         };
+        TaskStatus::Done
+    }
+
+    fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+        self.retry_policy = retry_policy;
     }
 
     fn id(&self) -> Uuid {
@@ -195,14 +440,56 @@ where
     fn get_type(&self) -> OperatorType {
         self.operator.get_type()
     }
+
+    fn get_priority(&self) -> TaskPriority {
+        self.priority
+    }
 }
 
-/// Wrap an operator and its input into a task message.
+/// Wrap an operator and its input into an interactive task message.
 pub(super) fn wrap<Input, Output, Error>(
     operator: Box<dyn Operator<Input, Output, Error = Error>>,
     input: Input,
     reply_channel: Box<dyn ReceiverForMessage<TaskResult<Output, Error>>>,
 ) -> TaskMessage
+where
+    Error: Debug + Send + 'static,
+    Input: Send + Sync + Debug + 'static,
+    Output: Send + Sync + Debug + 'static,
+{
+    wrap_with_priority(operator, input, reply_channel, TaskPriority::Interactive)
+}
+
+/// Wrap an operator and its input into a task message of the given priority class.
+pub(super) fn wrap_with_priority<Input, Output, Error>(
+    operator: Box<dyn Operator<Input, Output, Error = Error>>,
+    input: Input,
+    reply_channel: Box<dyn ReceiverForMessage<TaskResult<Output, Error>>>,
+    priority: TaskPriority,
+) -> TaskMessage
+where
+    Error: Debug + Send + 'static,
+    Input: Send + Sync + Debug + 'static,
+    Output: Send + Sync + Debug + 'static,
+{
+    wrap_with_middleware(
+        operator,
+        input,
+        reply_channel,
+        priority,
+        OperatorMiddleware::default(),
+    )
+}
+
+/// Wrap an operator and its input into a task message of the given priority class, whose
+/// operator runs in the given middleware.
+pub(super) fn wrap_with_middleware<Input, Output, Error>(
+    operator: Box<dyn Operator<Input, Output, Error = Error>>,
+    input: Input,
+    reply_channel: Box<dyn ReceiverForMessage<TaskResult<Output, Error>>>,
+    priority: TaskPriority,
+    middleware: OperatorMiddleware,
+) -> TaskMessage
 where
     Error: Debug + Send + 'static,
     Input: Send + Sync + Debug + 'static,
@@ -214,6 +501,11 @@ where
         input,
         reply_channel,
         task_id: id,
+        priority,
+        middleware,
+        retry_policy: RetryPolicy::default(),
+        failed_attempts: AtomicUsize::new(0),
+        read_stats: Mutex::new(ReadStats::default()),
     })
 }
 
@@ -224,9 +516,14 @@ mod tests {
 
     use parking_lot::Mutex;
 
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
     use crate::{
-        execution::dispatcher::Dispatcher,
-        system::{Component, ComponentContext, ComponentHandle, Handler, System},
+        execution::{dispatcher::Dispatcher, memory::MemoryContext},
+        system::{
+            ChannelError, Component, ComponentContext, ComponentHandle, Handler,
+            ReceiverForMessage, System,
+        },
     };
 
     use super::*;
@@ -284,7 +581,7 @@ mod tests {
     #[tokio::test]
     async fn task_catches_panic() {
         let system = System::new();
-        let dispatcher = Dispatcher::new(1, 1000, 1000);
+        let dispatcher = Dispatcher::new(1, 0, 1000, 1000);
         let dispatcher_handle = system.start_component(dispatcher);
 
         let received_results = Arc::new(Mutex::new(Vec::new()));
@@ -305,4 +602,163 @@ mod tests {
         assert!(result.is_err());
         matches!(result, Err(TaskError::Panic(Some(msg))) if msg == "MockOperator panicking");
     }
+
+    // An operator that fails with the error code a number of times before it succeeds. Each
+    // attempt reserves memory in the context of the query
+    #[derive(Debug)]
+    struct FlakyOperator {
+        failures: usize,
+        code: ErrorCodes,
+        attempts: Arc<AtomicUsize>,
+        memory: MemoryContext,
+        deadline: Deadline,
+    }
+    #[async_trait]
+    impl Operator<(), ()> for FlakyOperator {
+        type Error = ErrorCodes;
+
+        async fn run(&self, _: &()) -> Result<(), ErrorCodes> {
+            self.memory
+                .reserve(FLAKY_OPERATOR_RESERVED_BYTES)
+                .map_err(|e| e.code())?;
+            if self.attempts.fetch_add(1, Ordering::SeqCst) < self.failures {
+                Err(self.code)
+            } else {
+                Ok(())
+            }
+        }
+
+        fn can_retry(&self, error: &ErrorCodes) -> bool {
+            error.is_transient()
+        }
+
+        fn deadline(&self, _: &()) -> Option<Deadline> {
+            Some(self.deadline.clone())
+        }
+    }
+
+    const FLAKY_OPERATOR_RESERVED_BYTES: usize = 10;
+
+    #[derive(Clone, Debug, Default)]
+    struct ResultRecorder {
+        results: Arc<Mutex<Vec<Result<(), ErrorCodes>>>>,
+    }
+    #[async_trait]
+    impl ReceiverForMessage<TaskResult<(), ErrorCodes>> for ResultRecorder {
+        async fn send(
+            &self,
+            message: TaskResult<(), ErrorCodes>,
+            _tracing_context: Option<tracing::Span>,
+        ) -> Result<(), ChannelError> {
+            self.results.lock().push(match message.into_inner() {
+                Ok(()) => Ok(()),
+                Err(TaskError::TaskFailed(code)) => Err(code),
+                Err(TaskError::Panic(_)) => Err(ErrorCodes::Internal),
+                Err(TaskError::Aborted(_)) => Err(ErrorCodes::Aborted),
+            });
+            Ok(())
+        }
+    }
+
+    // Runs the task until it is done, without waiting out the backoffs of its retries
+    async fn run_flaky_task_with(
+        failures: usize,
+        code: ErrorCodes,
+        memory: MemoryContext,
+        deadline: Deadline,
+    ) -> (usize, Result<(), ErrorCodes>) {
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let recorder = ResultRecorder::default();
+        let task = wrap(
+            Box::new(FlakyOperator {
+                failures,
+                code,
+                attempts: attempts.clone(),
+                memory,
+                deadline,
+            }),
+            (),
+            Box::new(recorder.clone()),
+        );
+        while let TaskStatus::RetryAfter(backoff) = task.run().await {
+            assert!(backoff <= RetryPolicy::default().max_backoff);
+        }
+        let result = recorder
+            .results
+            .lock()
+            .pop()
+            .expect("Task should send its result");
+        (attempts.load(Ordering::SeqCst), result)
+    }
+
+    async fn run_flaky_task(failures: usize, code: ErrorCodes) -> (usize, Result<(), ErrorCodes>) {
+        run_flaky_task_with(
+            failures,
+            code,
+            MemoryContext::default(),
+            Deadline::default(),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn task_retries_transient_errors() {
+        let max_attempts = RetryPolicy::default().max_attempts;
+
+        // A transient error is retried until the operator succeeds
+        let (attempts, result) = run_flaky_task(max_attempts - 1, ErrorCodes::Unavailable).await;
+        assert_eq!(attempts, max_attempts);
+        assert!(result.is_ok());
+
+        // The error is surfaced once the attempts are exhausted
+        let (attempts, result) = run_flaky_task(max_attempts, ErrorCodes::Unavailable).await;
+        assert_eq!(attempts, max_attempts);
+        assert_eq!(result, Err(ErrorCodes::Unavailable));
+
+        // Other errors are surfaced immediately
+        let (attempts, result) = run_flaky_task(1, ErrorCodes::Internal).await;
+        assert_eq!(attempts, 1);
+        assert_eq!(result, Err(ErrorCodes::Internal));
+    }
+
+    #[tokio::test]
+    async fn task_retries_release_memory_and_stop_at_deadline() {
+        // The memory reserved by the failed attempts is released, so the retries fit the
+        // budget of a single attempt
+        let max_attempts = RetryPolicy::default().max_attempts;
+        let memory = MemoryContext::with_budget(FLAKY_OPERATOR_RESERVED_BYTES);
+        let (attempts, result) = run_flaky_task_with(
+            max_attempts - 1,
+            ErrorCodes::Unavailable,
+            memory.clone(),
+            Deadline::default(),
+        )
+        .await;
+        assert_eq!(attempts, max_attempts);
+        assert!(result.is_ok());
+        assert_eq!(memory.used_bytes(), FLAKY_OPERATOR_RESERVED_BYTES);
+
+        // A transient error is surfaced once the deadline of the query has passed
+        let (attempts, result) = run_flaky_task_with(
+            max_attempts - 1,
+            ErrorCodes::Unavailable,
+            MemoryContext::default(),
+            Deadline::after(Duration::ZERO),
+        )
+        .await;
+        assert_eq!(attempts, 1);
+        assert_eq!(result, Err(ErrorCodes::Unavailable));
+    }
+
+    #[test]
+    fn test_retry_backoff() {
+        let policy = RetryPolicy::default();
+        for failed_attempts in 1..10 {
+            let exponential = (policy.initial_backoff * 2u32.pow(failed_attempts as u32 - 1))
+                .min(policy.max_backoff);
+            let backoff = policy.backoff(failed_attempts);
+            assert!((exponential / 2..=exponential).contains(&backoff));
+        }
+        assert!(policy.backoff(usize::MAX) <= policy.max_backoff);
+    }
 }
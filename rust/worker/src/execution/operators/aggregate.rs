@@ -0,0 +1,336 @@
+use std::cmp::Ordering;
+
+use chroma_blockstore::provider::BlockfileProvider;
+use chroma_error::{ChromaError, ErrorCodes};
+use chroma_types::{
+    Chunk, LogRecord, MaterializedLogOperation, MetadataValue, Segment, SignedRoaringBitmap,
+};
+use futures::TryStreamExt;
+use roaring::RoaringBitmap;
+use thiserror::Error;
+use tonic::async_trait;
+use tracing::{trace, Instrument, Span};
+
+use crate::{
+    execution::operator::Operator,
+    segment::{
+        metadata_segment::{MetadataSegmentError, MetadataSegmentReader},
+        record_segment::{RecordSegmentReader, RecordSegmentReaderCreationError},
+        LogMaterializer, LogMaterializerError,
+    },
+};
+
+/// The `AggregateOperator` groups the records by the value of a metadata key and
+/// aggregates the numeric values of that key, without hydrating the records
+///
+/// # Parameters
+/// - `key`: The metadata key to group by and aggregate
+///
+/// # Inputs
+/// - `logs`: The latest logs of the collection
+/// - `blockfile_provider`: The blockfile provider
+/// - `metadata_segment`: The metadata segment information
+/// - `record_segment`: The record segment information
+/// - `log_offset_ids`: The offset ids in the logs to include or exclude before aggregating
+/// - `compact_offset_ids`: The offset ids in the blockfile to include or exclude before aggregating
+///
+/// # Outputs
+/// - `groups`: The number of records for each value of the key, in descending order of count
+/// - `numeric`: The min, max, sum and average of the numeric values of the key, if any
+///
+/// # Usage
+/// It can be used to aggregate the records in a collection, optionally after a `FilterOperator`
+/// Records without the key are not included in any group
+#[derive(Clone, Debug)]
+pub struct AggregateOperator {
+    pub key: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct AggregateInput {
+    pub logs: Chunk<LogRecord>,
+    pub blockfile_provider: BlockfileProvider,
+    pub metadata_segment: Segment,
+    pub record_segment: Segment,
+    pub log_offset_ids: SignedRoaringBitmap,
+    pub compact_offset_ids: SignedRoaringBitmap,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AggregateGroup {
+    pub value: MetadataValue,
+    pub count: u64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct NumericAggregate {
+    pub count: u64,
+    pub min: f64,
+    pub max: f64,
+    pub sum: f64,
+    pub avg: f64,
+}
+
+#[derive(Debug)]
+pub struct AggregateOutput {
+    pub groups: Vec<AggregateGroup>,
+    pub numeric: Option<NumericAggregate>,
+}
+
+#[derive(Error, Debug)]
+pub enum AggregateError {
+    #[error("Error materializing log: {0}")]
+    LogMaterializer(#[from] LogMaterializerError),
+    #[error("Error reading metadata segment: {0}")]
+    MetadataSegment(#[from] MetadataSegmentError),
+    #[error("Error creating record segment reader: {0}")]
+    RecordReader(#[from] RecordSegmentReaderCreationError),
+}
+
+impl ChromaError for AggregateError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            AggregateError::LogMaterializer(e) => e.code(),
+            AggregateError::MetadataSegment(e) => e.code(),
+            AggregateError::RecordReader(e) => e.code(),
+        }
+    }
+}
+
+pub(super) fn add_to_group(groups: &mut Vec<AggregateGroup>, value: &MetadataValue, count: u64) {
+    if count == 0 {
+        return;
+    }
+    // Linear scanning is fine since the number of distinct values is expected to be small,
+    // and `MetadataValue` does not implement `Hash` because of the float variant
+    match groups.iter_mut().find(|group| &group.value == value) {
+        Some(group) => group.count += count,
+        None => groups.push(AggregateGroup {
+            value: value.clone(),
+            count,
+        }),
+    }
+}
+
+#[async_trait]
+impl Operator<AggregateInput, AggregateOutput> for AggregateOperator {
+    type Error = AggregateError;
+
+    async fn run(&self, input: &AggregateInput) -> Result<AggregateOutput, AggregateError> {
+        trace!("[{}]: {:?}", self.get_name(), input);
+
+        let record_segment_reader = match RecordSegmentReader::from_segment(
+            &input.record_segment,
+            &input.blockfile_provider,
+        )
+        .await
+        {
+            Ok(reader) => Ok(Some(reader)),
+            Err(e) if matches!(*e, RecordSegmentReaderCreationError::UninitializedSegment) => {
+                Ok(None)
+            }
+            Err(e) => Err(*e),
+        }?;
+
+        let materializer =
+            LogMaterializer::new(record_segment_reader.clone(), input.logs.clone(), None);
+        let materialized_logs = materializer
+            .materialize()
+            .instrument(tracing::trace_span!(parent: Span::current(), "Materialize logs"))
+            .await?;
+
+        let mut groups = Vec::new();
+
+        // The offset ids in the record segment that are overwritten or deleted by the logs
+        let mut updated_offset_ids = RoaringBitmap::new();
+        for (log, _) in materialized_logs.iter() {
+            if !matches!(
+                log.final_operation,
+                MaterializedLogOperation::Initial | MaterializedLogOperation::AddNew
+            ) {
+                updated_offset_ids.insert(log.offset_id);
+            }
+            if matches!(
+                log.final_operation,
+                MaterializedLogOperation::DeleteExisting
+            ) || !input.log_offset_ids.contains(log.offset_id)
+            {
+                continue;
+            }
+            // The values of objects are grouped under their paths, and the elements of a list
+            // are grouped separately, like in the metadata segment
+            for (key, value) in log.merged_metadata_ref() {
+                for (path, value) in value.flatten(key) {
+                    if path == self.key {
+                        for element in value.elements() {
+                            add_to_group(&mut groups, element, 1);
+                        }
+                    }
+                }
+            }
+        }
+
+        if record_segment_reader.is_some() {
+            let metadata_segment_reader = MetadataSegmentReader::from_segment(
+                &input.metadata_segment,
+                &input.blockfile_provider,
+            )
+            .await?;
+            let mut values = Box::pin(metadata_segment_reader.group_by_stream(&self.key));
+            while let Some((value, mut rbm)) = values.try_next().await? {
+                rbm -= &updated_offset_ids;
+                match &input.compact_offset_ids {
+                    SignedRoaringBitmap::Include(include) => rbm &= include,
+                    SignedRoaringBitmap::Exclude(exclude) => rbm -= exclude,
+                }
+                add_to_group(&mut groups, &value, rbm.len());
+            }
+        }
+
+        let mut numeric: Option<NumericAggregate> = None;
+        for group in &groups {
+            let value = match group.value {
+                MetadataValue::Int(i) => i as f64,
+                MetadataValue::Float(f) => f,
+                _ => continue,
+            };
+            let aggregate = numeric.get_or_insert(NumericAggregate {
+                count: 0,
+                min: value,
+                max: value,
+                sum: 0.0,
+                avg: 0.0,
+            });
+            aggregate.count += group.count;
+            aggregate.min = aggregate.min.min(value);
+            aggregate.max = aggregate.max.max(value);
+            aggregate.sum += value * group.count as f64;
+        }
+        if let Some(aggregate) = numeric.as_mut() {
+            aggregate.avg = aggregate.sum / aggregate.count as f64;
+        }
+
+        groups.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then(a.value.partial_cmp(&b.value).unwrap_or(Ordering::Equal))
+        });
+
+        Ok(AggregateOutput { groups, numeric })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chroma_types::{MetadataValue, SignedRoaringBitmap};
+
+    use crate::{
+        execution::{
+            operator::Operator,
+            operators::aggregate::{AggregateGroup, AggregateOperator, NumericAggregate},
+        },
+        log::test::{add_delete_generator, LogGenerator},
+        segment::test::TestSegment,
+    };
+
+    use super::AggregateInput;
+
+    /// The unit tests for `AggregateOperator` uses the following test data
+    /// It generates 120 log records, where the first 60 is compacted:
+    /// - Log: Delete [11..=20], add [51..=100]
+    /// - Compacted: Delete [1..=10] deletion, add [11..=50]
+    async fn setup_aggregate_input(
+        log_offset_ids: SignedRoaringBitmap,
+        compact_offset_ids: SignedRoaringBitmap,
+    ) -> AggregateInput {
+        let mut test_segment = TestSegment::default();
+        let generator = LogGenerator {
+            generator: add_delete_generator,
+        };
+        test_segment.populate_with_generator(60, &generator).await;
+        AggregateInput {
+            logs: generator.generate_chunk(61..=120),
+            blockfile_provider: test_segment.blockfile_provider,
+            metadata_segment: test_segment.metadata_segment,
+            record_segment: test_segment.record_segment,
+            log_offset_ids,
+            compact_offset_ids,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_trivial_aggregate() {
+        let aggregate_input =
+            setup_aggregate_input(SignedRoaringBitmap::full(), SignedRoaringBitmap::full()).await;
+
+        let aggregate_operator = AggregateOperator {
+            key: "modulo_3".to_string(),
+        };
+
+        let aggregate_output = aggregate_operator
+            .run(&aggregate_input)
+            .await
+            .expect("AggregateOperator should not fail");
+
+        assert_eq!(
+            aggregate_output.groups,
+            vec![
+                AggregateGroup {
+                    value: MetadataValue::Int(0),
+                    count: 27
+                },
+                AggregateGroup {
+                    value: MetadataValue::Int(1),
+                    count: 27
+                },
+                AggregateGroup {
+                    value: MetadataValue::Int(2),
+                    count: 26
+                },
+            ]
+        );
+        assert_eq!(
+            aggregate_output.numeric,
+            Some(NumericAggregate {
+                count: 80,
+                min: 0.0,
+                max: 2.0,
+                sum: 79.0,
+                avg: 79.0 / 80.0,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_filtered_aggregate() {
+        let aggregate_input = setup_aggregate_input(
+            SignedRoaringBitmap::Include((51..=60).collect()),
+            SignedRoaringBitmap::Exclude((21..=40).collect()),
+        )
+        .await;
+
+        let aggregate_operator = AggregateOperator {
+            key: "is_even".to_string(),
+        };
+
+        let aggregate_output = aggregate_operator
+            .run(&aggregate_input)
+            .await
+            .expect("AggregateOperator should not fail");
+
+        assert_eq!(
+            aggregate_output.groups,
+            vec![
+                AggregateGroup {
+                    value: MetadataValue::Bool(false),
+                    count: 10
+                },
+                AggregateGroup {
+                    value: MetadataValue::Bool(true),
+                    count: 10
+                },
+            ]
+        );
+        assert_eq!(aggregate_output.numeric, None);
+    }
+}
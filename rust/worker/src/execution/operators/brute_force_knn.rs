@@ -1,17 +1,20 @@
-use crate::execution::operator::Operator;
+use crate::execution::memory::{MemoryContext, MemoryError};
+use crate::execution::operator::{Deadline, DeadlineError, Operator};
 use crate::execution::operators::normalize_vectors::normalize;
 use crate::segment::record_segment::RecordSegmentReader;
 use crate::segment::LogMaterializer;
 use crate::segment::LogMaterializerError;
 use async_trait::async_trait;
 use chroma_blockstore::provider::BlockfileProvider;
-use chroma_distance::DistanceFunction;
+use chroma_distance::{DistanceAccumulation, DistanceFunction};
 use chroma_error::{ChromaError, ErrorCodes};
 use chroma_types::Chunk;
+use chroma_types::CollectionUuid;
 use chroma_types::{LogRecord, MaterializedLogOperation, Segment};
 use std::cmp::Ordering;
 use std::collections::BinaryHeap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 use tracing::Instrument;
 use tracing::Span;
@@ -28,17 +31,29 @@ pub struct BruteForceKnnOperator {}
 /// * `data` - The vectors to query against.
 /// * `query` - The query vector.
 /// * `k` - The number of nearest neighbors to find.
+/// * `max_distance` - The records further than this distance from the query are dropped, if set.
 /// * `distance_metric` - The distance metric to use.
+/// * `accumulation` - The precision in which the distances are accumulated.
+/// * `time_limit` - The time after which the search stops and returns the nearest neighbors
+///   among the logs searched so far, if set.
+/// * `deadline` - The deadline of the query, checked while the logs are searched.
+/// * `memory` - The memory context of the query, which accounts for the materialized logs and the
+///   embeddings of the nearest neighbors.
 #[derive(Debug)]
 pub struct BruteForceKnnOperatorInput {
     pub log: Chunk<LogRecord>,
     pub query: Vec<f32>,
     pub k: usize,
+    pub max_distance: Option<f32>,
     pub distance_metric: DistanceFunction,
+    pub accumulation: DistanceAccumulation,
+    pub time_limit: Option<Duration>,
     pub allowed_ids: Arc<[String]>,
     // Deps to create the log materializer
     pub record_segment_definition: Segment,
     pub blockfile_provider: BlockfileProvider,
+    pub deadline: Deadline,
+    pub memory: MemoryContext,
 }
 
 /// The output of the brute force k-nearest neighbors operator.
@@ -47,11 +62,13 @@ pub struct BruteForceKnnOperatorInput {
 /// * `embeddings` - The embeddings of the nearest neighbors.
 /// * `distances` - The distances of the nearest neighbors.
 ///    One row for each query vector.
+/// * `truncated` - Whether the time limit was reached before all the logs were searched.
 #[derive(Debug)]
 pub struct BruteForceKnnOperatorOutput {
     pub user_ids: Vec<String>,
     pub embeddings: Vec<Vec<f32>>,
     pub distances: Vec<f32>,
+    pub truncated: bool,
 }
 
 #[derive(Debug)]
@@ -97,6 +114,10 @@ pub enum BruteForceKnnOperatorError {
     ),
     #[error("Error while materializing log records: {0}")]
     LogMaterializationError(#[from] LogMaterializerError),
+    #[error(transparent)]
+    Deadline(#[from] DeadlineError),
+    #[error("Error accounting for query memory: {0}")]
+    Memory(#[from] MemoryError),
 }
 
 impl ChromaError for BruteForceKnnOperatorError {
@@ -104,6 +125,8 @@ impl ChromaError for BruteForceKnnOperatorError {
         match self {
             BruteForceKnnOperatorError::RecordSegmentReaderCreationError(e) => e.code(),
             BruteForceKnnOperatorError::LogMaterializationError(e) => e.code(),
+            BruteForceKnnOperatorError::Deadline(e) => e.code(),
+            BruteForceKnnOperatorError::Memory(e) => e.code(),
         }
     }
 }
@@ -120,6 +143,10 @@ impl Operator<BruteForceKnnOperatorInput, BruteForceKnnOperatorOutput> for Brute
         &self,
         input: &BruteForceKnnOperatorInput,
     ) -> Result<BruteForceKnnOperatorOutput, Self::Error> {
+        let time_limit_deadline = input
+            .time_limit
+            .map(|time_limit| Instant::now() + time_limit);
+        input.deadline.check()?;
         // Materialize the log records
         let record_segment_reader = match RecordSegmentReader::from_segment(
             &input.record_segment_definition,
@@ -135,6 +162,7 @@ impl Operator<BruteForceKnnOperatorInput, BruteForceKnnOperatorOutput> for Brute
             }
         };
         let log_materializer = LogMaterializer::new(record_segment_reader, input.log.clone(), None);
+        let reserved = input.memory.reserve_log_materialization(&input.log)?;
         let logs = match log_materializer
             .materialize()
             .instrument(tracing::trace_span!(parent: Span::current(), "Materialize logs"))
@@ -145,6 +173,7 @@ impl Operator<BruteForceKnnOperatorInput, BruteForceKnnOperatorOutput> for Brute
                 return Err(BruteForceKnnOperatorError::LogMaterializationError(e));
             }
         };
+        input.memory.settle_log_materialization(reserved, &logs);
 
         let should_normalize = matches!(input.distance_metric, DistanceFunction::Cosine);
         let normalized_query = match should_normalize {
@@ -153,8 +182,14 @@ impl Operator<BruteForceKnnOperatorInput, BruteForceKnnOperatorOutput> for Brute
         };
 
         let mut heap = BinaryHeap::with_capacity(input.k);
+        let mut truncated = false;
         let data_chunk = logs;
         for data in data_chunk.iter() {
+            input.deadline.check()?;
+            if time_limit_deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                truncated = true;
+                break;
+            }
             let log_record = data.0;
 
             if log_record.final_operation == MaterializedLogOperation::DeleteExisting {
@@ -172,25 +207,32 @@ impl Operator<BruteForceKnnOperatorInput, BruteForceKnnOperatorOutput> for Brute
                 continue;
             }
             let embedding = &log_record.merged_embeddings();
-            if should_normalize {
+            let distance = if should_normalize {
                 let normalized_query = normalized_query.as_ref().expect("Invariant violation. Should have set normalized query if should_normalize is true.");
                 let normalized_embedding = normalize(&embedding[..]);
-                let distance = input
-                    .distance_metric
-                    .distance(&normalized_embedding[..], &normalized_query[..]);
-                heap.push(Entry {
-                    user_id: log_record.merged_user_id_ref(),
-                    embedding,
-                    distance,
-                });
+                input.distance_metric.distance_with_accumulation(
+                    &normalized_embedding[..],
+                    &normalized_query[..],
+                    input.accumulation,
+                )
             } else {
-                let distance = input.distance_metric.distance(&embedding[..], &input.query);
-                heap.push(Entry {
-                    user_id: log_record.merged_user_id_ref(),
-                    embedding,
-                    distance,
-                });
+                input.distance_metric.distance_with_accumulation(
+                    &embedding[..],
+                    &input.query,
+                    input.accumulation,
+                )
+            };
+            if input
+                .max_distance
+                .is_some_and(|max_distance| distance > max_distance)
+            {
+                continue;
             }
+            heap.push(Entry {
+                user_id: log_record.merged_user_id_ref(),
+                embedding,
+                distance,
+            });
         }
 
         let mut sorted_embeddings = Vec::with_capacity(input.k);
@@ -209,14 +251,33 @@ impl Operator<BruteForceKnnOperatorInput, BruteForceKnnOperatorOutput> for Brute
             sorted_distances.push(entry.distance);
             i += 1;
         }
+        input.memory.reserve(
+            sorted_embeddings
+                .iter()
+                .map(|embedding| std::mem::size_of_val(embedding.as_slice()))
+                .sum(),
+        )?;
 
         tracing::info!("Brute force Knn result. distances: {:?}", sorted_distances);
         Ok(BruteForceKnnOperatorOutput {
             user_ids: sorted_user_ids,
             embeddings: sorted_embeddings,
             distances: sorted_distances,
+            truncated,
         })
     }
+
+    fn collection_id(&self, input: &BruteForceKnnOperatorInput) -> Option<CollectionUuid> {
+        Some(input.record_segment_definition.collection)
+    }
+
+    fn input_cardinality(&self, input: &BruteForceKnnOperatorInput) -> Option<usize> {
+        Some(input.log.len())
+    }
+
+    fn output_cardinality(&self, output: &BruteForceKnnOperatorOutput) -> Option<usize> {
+        Some(output.user_ids.len())
+    }
 }
 
 #[cfg(test)]
@@ -257,6 +318,9 @@ mod tests {
                     encoding: None,
                     metadata: None,
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Add,
                 },
             },
@@ -268,6 +332,9 @@ mod tests {
                     encoding: None,
                     metadata: None,
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Add,
                 },
             },
@@ -279,6 +346,9 @@ mod tests {
                     encoding: None,
                     metadata: None,
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Add,
                 },
             },
@@ -289,10 +359,15 @@ mod tests {
             log: data_chunk,
             query: vec![0.0, 0.0, 0.0],
             k: 2,
+            max_distance: None,
             distance_metric: DistanceFunction::Euclidean,
+            accumulation: DistanceAccumulation::default(),
+            time_limit: None,
             allowed_ids: Arc::new([]),
             blockfile_provider,
             record_segment_definition,
+            deadline: Deadline::default(),
+            memory: MemoryContext::default(),
         };
 
         let output = operator.run(&input).await.unwrap();
@@ -305,6 +380,103 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_brute_force_knn_max_distance() {
+        let operator = BruteForceKnnOperator {};
+        let (blockfile_provider, record_segment_definition) =
+            get_blockfile_provider_and_record_segment_definition();
+        let data = vec![
+            LogRecord {
+                log_offset: 1,
+                record: OperationRecord {
+                    id: "embedding_id_1".to_string(),
+                    embedding: Some(vec![0.0, 0.0, 0.0]),
+                    encoding: None,
+                    metadata: None,
+                    document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
+                    operation: Operation::Add,
+                },
+            },
+            LogRecord {
+                log_offset: 2,
+                record: OperationRecord {
+                    id: "embedding_id_2".to_string(),
+                    embedding: Some(vec![0.0, 1.0, 1.0]),
+                    encoding: None,
+                    metadata: None,
+                    document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
+                    operation: Operation::Add,
+                },
+            },
+        ];
+
+        // The records further than the maximum distance are dropped
+        let input = BruteForceKnnOperatorInput {
+            log: Chunk::new(data.into()),
+            query: vec![0.0, 0.0, 0.0],
+            k: 2,
+            max_distance: Some(1.0),
+            distance_metric: DistanceFunction::Euclidean,
+            accumulation: DistanceAccumulation::default(),
+            time_limit: None,
+            allowed_ids: Arc::new([]),
+            blockfile_provider,
+            record_segment_definition,
+            deadline: Deadline::default(),
+            memory: MemoryContext::default(),
+        };
+        let output = operator.run(&input).await.unwrap();
+        assert_eq!(output.user_ids, vec!["embedding_id_1"]);
+        assert_eq!(output.distances, vec![0.0]);
+        assert!(!output.truncated);
+    }
+
+    #[tokio::test]
+    async fn test_brute_force_knn_time_limit() {
+        let operator = BruteForceKnnOperator {};
+        let (blockfile_provider, record_segment_definition) =
+            get_blockfile_provider_and_record_segment_definition();
+        let data = vec![LogRecord {
+            log_offset: 1,
+            record: OperationRecord {
+                id: "embedding_id_1".to_string(),
+                embedding: Some(vec![0.0, 0.0, 0.0]),
+                encoding: None,
+                metadata: None,
+                document: None,
+                uri: None,
+                idempotency_key: None,
+                embedding_reference: None,
+                operation: Operation::Add,
+            },
+        }];
+
+        // The search stops once the time limit is reached
+        let input = BruteForceKnnOperatorInput {
+            log: Chunk::new(data.into()),
+            query: vec![0.0, 0.0, 0.0],
+            k: 1,
+            max_distance: None,
+            distance_metric: DistanceFunction::Euclidean,
+            accumulation: DistanceAccumulation::default(),
+            time_limit: Some(Duration::ZERO),
+            allowed_ids: Arc::new([]),
+            blockfile_provider,
+            record_segment_definition,
+            deadline: Deadline::default(),
+            memory: MemoryContext::default(),
+        };
+        let output = operator.run(&input).await.unwrap();
+        assert!(output.truncated);
+        assert!(output.user_ids.is_empty());
+    }
+
     #[tokio::test]
     async fn test_brute_force_knn_cosine() {
         let operator = BruteForceKnnOperator {};
@@ -325,6 +497,9 @@ mod tests {
                     encoding: None,
                     metadata: None,
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Add,
                 },
             },
@@ -336,6 +511,9 @@ mod tests {
                     encoding: None,
                     metadata: None,
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Add,
                 },
             },
@@ -347,6 +525,9 @@ mod tests {
                     encoding: None,
                     metadata: None,
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Add,
                 },
             },
@@ -357,10 +538,15 @@ mod tests {
             log: data_chunk,
             query: vec![0.0, 1.0, 0.0],
             k: 2,
+            max_distance: None,
             distance_metric: DistanceFunction::InnerProduct,
+            accumulation: DistanceAccumulation::default(),
+            time_limit: None,
             allowed_ids: Arc::new([]),
             blockfile_provider,
             record_segment_definition,
+            deadline: Deadline::default(),
+            memory: MemoryContext::default(),
         };
         let output = operator.run(&input).await.unwrap();
 
@@ -391,6 +577,9 @@ mod tests {
                 encoding: None,
                 metadata: None,
                 document: None,
+                uri: None,
+                idempotency_key: None,
+                embedding_reference: None,
                 operation: Operation::Add,
             },
         }];
@@ -401,10 +590,15 @@ mod tests {
             log: data_chunk,
             query: vec![0.0, 0.0, 0.0],
             k: 2,
+            max_distance: None,
             distance_metric: DistanceFunction::Euclidean,
+            accumulation: DistanceAccumulation::default(),
+            time_limit: None,
             allowed_ids: Arc::new([]),
             blockfile_provider,
             record_segment_definition,
+            deadline: Deadline::default(),
+            memory: MemoryContext::default(),
         };
         let output = operator.run(&input).await.unwrap();
 
@@ -427,6 +621,9 @@ mod tests {
                     encoding: None,
                     metadata: None,
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Add,
                 },
             },
@@ -438,6 +635,9 @@ mod tests {
                     encoding: None,
                     metadata: None,
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Add,
                 },
             },
@@ -449,6 +649,9 @@ mod tests {
                     encoding: None,
                     metadata: None,
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Add,
                 },
             },
@@ -459,10 +662,15 @@ mod tests {
             log: data_chunk,
             query: vec![0.0, 0.0, 0.0],
             k: 2,
+            max_distance: None,
             distance_metric: DistanceFunction::Euclidean,
+            accumulation: DistanceAccumulation::default(),
+            time_limit: None,
             allowed_ids: Arc::new([]),
             blockfile_provider,
             record_segment_definition,
+            deadline: Deadline::default(),
+            memory: MemoryContext::default(),
         };
         let res = operator.run(&input).await;
         match res {
@@ -490,6 +698,9 @@ mod tests {
                     encoding: None,
                     metadata: None,
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Add,
                 },
             },
@@ -501,6 +712,9 @@ mod tests {
                     encoding: None,
                     metadata: None,
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Delete,
                 },
             },
@@ -512,6 +726,9 @@ mod tests {
                     encoding: None,
                     metadata: None,
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Add,
                 },
             },
@@ -522,10 +739,15 @@ mod tests {
             log: data_chunk,
             query: vec![0.0, 0.0, 0.0],
             k: 2,
+            max_distance: None,
             distance_metric: DistanceFunction::Euclidean,
+            accumulation: DistanceAccumulation::default(),
+            time_limit: None,
             allowed_ids: Arc::new([]),
             blockfile_provider,
             record_segment_definition,
+            deadline: Deadline::default(),
+            memory: MemoryContext::default(),
         };
         let output = operator.run(&input).await.unwrap();
 
@@ -533,4 +755,62 @@ mod tests {
         assert_eq!(output.distances, vec![0.0]);
         assert_eq!(output.embeddings, vec![vec![0.0, 0.0, 0.0]]);
     }
+
+    #[tokio::test]
+    async fn test_brute_force_knn_stops_at_deadline_or_budget() {
+        let operator = BruteForceKnnOperator {};
+        let (blockfile_provider, record_segment_definition) =
+            get_blockfile_provider_and_record_segment_definition();
+        let data = vec![LogRecord {
+            log_offset: 1,
+            record: OperationRecord {
+                id: "embedding_id_1".to_string(),
+                embedding: Some(vec![0.0, 0.0, 0.0]),
+                encoding: None,
+                metadata: None,
+                document: None,
+                uri: None,
+                idempotency_key: None,
+                embedding_reference: None,
+                operation: Operation::Add,
+            },
+        }];
+
+        let mut input = BruteForceKnnOperatorInput {
+            log: Chunk::new(data.into()),
+            query: vec![0.0, 0.0, 0.0],
+            k: 1,
+            max_distance: None,
+            distance_metric: DistanceFunction::Euclidean,
+            accumulation: DistanceAccumulation::default(),
+            time_limit: None,
+            allowed_ids: Arc::new([]),
+            blockfile_provider,
+            record_segment_definition,
+            deadline: Deadline::after(std::time::Duration::ZERO),
+            memory: MemoryContext::default(),
+        };
+        let error = operator
+            .run(&input)
+            .await
+            .expect_err("BruteForceKnnOperator should stop at the deadline");
+        assert_eq!(error.code(), ErrorCodes::DeadlineExceeded);
+
+        input.deadline = Deadline::default();
+        input.deadline.cancel();
+        let error = operator
+            .run(&input)
+            .await
+            .expect_err("BruteForceKnnOperator should stop once cancelled");
+        assert_eq!(error.code(), ErrorCodes::Cancelled);
+
+        // The logs are not materialized beyond the budget of the query
+        input.deadline = Deadline::default();
+        input.memory = MemoryContext::with_budget(1);
+        let error = operator
+            .run(&input)
+            .await
+            .expect_err("BruteForceKnnOperator should stop at the budget");
+        assert_eq!(error.code(), ErrorCodes::ResourceExhausted);
+    }
 }
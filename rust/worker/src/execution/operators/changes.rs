@@ -0,0 +1,250 @@
+use std::collections::{HashMap, HashSet};
+
+use async_trait::async_trait;
+use chroma_blockstore::provider::BlockfileProvider;
+use chroma_error::{ChromaError, ErrorCodes};
+use chroma_types::{Chunk, CollectionUuid, LogRecord, MaterializedLogOperation, Segment};
+use thiserror::Error;
+use tracing::{trace, Instrument, Span};
+
+use crate::{
+    execution::{
+        operator::Operator,
+        operators::projection::{ProjectionDefaults, ProjectionOperator, ProjectionRecord},
+    },
+    segment::{
+        record_segment::{RecordSegmentReader, RecordSegmentReaderCreationError},
+        LogMaterializer, LogMaterializerError,
+    },
+};
+
+/// The `ChangesOperator` materializes the changes that the logs of a collection make to its
+/// records from a log offset on, which downstream systems apply to mirror the collection
+///
+/// # Parameters
+/// - `start_log_offset`: The offset of the first log whose changes are returned
+/// - `projection`: The fields of the changed records to retrieve
+///
+/// # Inputs
+/// - `logs`: The logs of the collection that are not compacted into the record segment
+/// - `blockfile_provider`: The blockfile provider
+/// - `record_segment`: The record segment information
+/// - `defaults`: The projection defaults of the collection, which restrict the fields that
+///   could be retrieved regardless of the parameters
+///
+/// # Outputs
+/// - `changes`: A change for each record that the logs from `start_log_offset` on touch, in
+///   the order that the logs first touch them
+/// - `log_offset`: The offset of the last log that the changes cover
+///
+/// # Usage
+/// The logs before `start_log_offset` are materialized as well, since they are not compacted
+/// yet and the later logs are merged into the state of the records that they leave
+#[derive(Clone, Debug)]
+pub struct ChangesOperator {
+    pub start_log_offset: i64,
+    pub projection: ProjectionOperator,
+}
+
+#[derive(Debug)]
+pub struct ChangesInput {
+    pub logs: Chunk<LogRecord>,
+    pub blockfile_provider: BlockfileProvider,
+    pub record_segment: Segment,
+    pub defaults: ProjectionDefaults,
+}
+
+/// The state of a record after the logs
+#[derive(Clone, Debug)]
+pub enum RecordChange {
+    /// The record exists, with its fields merged across the logs
+    Upsert(ProjectionRecord),
+    /// The record with the user id does not exist
+    Delete(String),
+}
+
+#[derive(Debug)]
+pub struct ChangesOutput {
+    pub changes: Vec<RecordChange>,
+    pub log_offset: i64,
+}
+
+#[derive(Error, Debug)]
+pub enum ChangesError {
+    #[error("Error materializing log: {0}")]
+    LogMaterializer(#[from] LogMaterializerError),
+    #[error("Error creating record segment reader: {0}")]
+    RecordReader(#[from] RecordSegmentReaderCreationError),
+}
+
+impl ChromaError for ChangesError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            ChangesError::LogMaterializer(e) => e.code(),
+            ChangesError::RecordReader(e) => e.code(),
+        }
+    }
+}
+
+#[async_trait]
+impl Operator<ChangesInput, ChangesOutput> for ChangesOperator {
+    type Error = ChangesError;
+
+    async fn run(&self, input: &ChangesInput) -> Result<ChangesOutput, ChangesError> {
+        trace!("[{}]: {:?}", self.get_name(), input);
+
+        let projection = self.projection.with_defaults(&input.defaults);
+        let record_segment_reader = match RecordSegmentReader::from_segment(
+            &input.record_segment,
+            &input.blockfile_provider,
+        )
+        .await
+        {
+            Ok(reader) => Some(reader),
+            // Nothing is compacted for the collection, so its records are all in the logs
+            Err(e) if matches!(*e, RecordSegmentReaderCreationError::UninitializedSegment) => None,
+            Err(e) => return Err((*e).into()),
+        };
+        let materializer = LogMaterializer::new(record_segment_reader, input.logs.clone(), None);
+        let materialized_logs = materializer
+            .materialize()
+            .instrument(tracing::trace_span!(parent: Span::current(), "Materialize logs"))
+            .await?;
+        let materialized_records = materialized_logs
+            .iter()
+            .map(|(log, _)| (log.merged_user_id_ref(), log))
+            .collect::<HashMap<_, _>>();
+
+        let mut changed_ids = HashSet::new();
+        let mut changes = Vec::new();
+        for (log, _) in input.logs.iter() {
+            let id = log.record.id.as_str();
+            if log.log_offset < self.start_log_offset || !changed_ids.insert(id) {
+                continue;
+            }
+            // The materializer leaves out the records that are added and deleted again in the
+            // logs, which may have been added before the start offset
+            let change = match materialized_records.get(id) {
+                Some(record)
+                    if record.final_operation != MaterializedLogOperation::DeleteExisting =>
+                {
+                    RecordChange::Upsert(projection.project_log_record(record))
+                }
+                _ => RecordChange::Delete(id.to_string()),
+            };
+            changes.push(change);
+        }
+
+        let log_offset = input
+            .logs
+            .iter()
+            .map(|(log, _)| log.log_offset)
+            .fold(self.start_log_offset - 1, i64::max);
+        Ok(ChangesOutput {
+            changes,
+            log_offset,
+        })
+    }
+
+    fn collection_id(&self, input: &ChangesInput) -> Option<CollectionUuid> {
+        Some(input.record_segment.collection)
+    }
+
+    fn input_cardinality(&self, input: &ChangesInput) -> Option<usize> {
+        Some(input.logs.len())
+    }
+
+    fn output_cardinality(&self, output: &ChangesOutput) -> Option<usize> {
+        Some(output.changes.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chroma_types::{Chunk, LogRecord, Operation, OperationRecord};
+
+    use crate::{
+        execution::{
+            operator::Operator,
+            operators::{
+                changes::{ChangesInput, ChangesOperator, RecordChange},
+                projection::{
+                    ProjectionDefaults, ProjectionOperator, DEFAULT_HYDRATION_CONCURRENCY,
+                },
+            },
+        },
+        log::test::{int_as_id, upsert_generator, LogGenerator},
+        segment::test::TestSegment,
+    };
+
+    fn delete_log(log_offset: i64, id: usize) -> LogRecord {
+        LogRecord {
+            log_offset,
+            record: OperationRecord {
+                id: int_as_id(id),
+                embedding: None,
+                encoding: None,
+                metadata: None,
+                document: None,
+                uri: None,
+                idempotency_key: None,
+                embedding_reference: None,
+                operation: Operation::Delete,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_changes() {
+        // Compacted: Upsert [1..=10], Log: Upsert [11..=20], Delete 5, 12 and 15
+        let mut test_segment = TestSegment::default();
+        let generator = LogGenerator {
+            generator: upsert_generator,
+        };
+        test_segment.populate_with_generator(10, &generator).await;
+        let mut logs = generator.generate_vec(11..=20);
+        logs.extend([delete_log(21, 5), delete_log(22, 12), delete_log(23, 15)]);
+        let input = ChangesInput {
+            logs: Chunk::new(logs.into()),
+            blockfile_provider: test_segment.blockfile_provider,
+            record_segment: test_segment.record_segment,
+            defaults: ProjectionDefaults::default(),
+        };
+
+        let output = ChangesOperator {
+            start_log_offset: 15,
+            projection: ProjectionOperator {
+                document: true,
+                embedding: false,
+                metadata: true,
+                uri: true,
+                concurrency: DEFAULT_HYDRATION_CONCURRENCY,
+                default_include: false,
+            },
+        }
+        .run(&input)
+        .await
+        .expect("ChangesOperator should not fail");
+
+        assert_eq!(output.log_offset, 23);
+        // 15 is upserted and deleted again, and 12 is deleted after it is upserted before the
+        // start offset
+        let changes = output
+            .changes
+            .iter()
+            .map(|change| match change {
+                RecordChange::Upsert(record) => {
+                    assert!(record.document.is_some());
+                    assert!(record.metadata.is_some());
+                    assert!(record.embedding.is_none());
+                    (record.id.clone(), true)
+                }
+                RecordChange::Delete(id) => (id.clone(), false),
+            })
+            .collect::<Vec<_>>();
+        let mut expected = vec![(int_as_id(15), false)];
+        expected.extend((16..=20).map(|id| (int_as_id(id), true)));
+        expected.extend([(int_as_id(5), false), (int_as_id(12), false)]);
+        assert_eq!(changes, expected);
+    }
+}
@@ -0,0 +1,468 @@
+use std::collections::{BTreeSet, HashMap};
+
+use chroma_blockstore::provider::BlockfileProvider;
+use chroma_error::{ChromaError, ErrorCodes};
+use chroma_index::{
+    fulltext::types::FullTextIndexError, hnsw_provider::HnswIndexProvider,
+    metadata::types::MetadataIndexError,
+};
+use chroma_types::MetadataValue;
+use futures::TryStreamExt;
+use roaring::RoaringBitmap;
+use thiserror::Error;
+use tonic::async_trait;
+use tracing::trace;
+
+use crate::{
+    execution::operator::{Operator, OperatorType},
+    segment::{
+        distributed_hnsw_segment::{
+            DistributedHNSWSegmentFromSegmentError, DistributedHNSWSegmentReader,
+        },
+        metadata_segment::{MetadataSegmentError, MetadataSegmentReader, CASE_INSENSITIVE_MARKER},
+        record_segment::{RecordSegmentReader, RecordSegmentReaderCreationError},
+    },
+};
+
+use super::fetch_segment::FetchSegmentOutput;
+
+/// The number of issues that are described in the output. The checks keep counting the
+/// issues beyond it, so that a badly corrupted segment does not produce a huge report
+const MAX_REPORTED_ISSUES: usize = 100;
+
+/// A consistency check of the segments of a collection
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IntegrityCheck {
+    /// The user id to offset id mapping, the offset id to user id mapping and the data records
+    /// of the record segment agree with each other, and no offset id exceeds the max offset id
+    OffsetIdContinuity,
+    /// The metadata and full text indexes of the metadata segment refer to the records of the
+    /// record segment, and every record with metadata is indexed
+    MetadataAgreement,
+    /// The HNSW index of the vector segment holds an embedding for every record
+    VectorIndexCoverage,
+}
+
+#[derive(Clone, Debug)]
+pub struct IntegrityIssue {
+    pub check: IntegrityCheck,
+    pub description: String,
+}
+
+/// The `CheckIntegrityOperator` runs the consistency checks of the segments of a collection
+/// against its record segment
+///
+/// # Parameters
+/// None
+///
+/// # Inputs
+/// - `blockfile_provider`: The blockfile provider
+/// - `hnsw_provider`: The HNSW index provider
+/// - `segments`: The collection and segment information
+///
+/// # Outputs
+/// - `num_records`: The number of data records in the record segment
+/// - `num_issues`: The number of issues found
+/// - `issues`: The first issues found, at most `MAX_REPORTED_ISSUES` of them
+/// - `failed_checks`: The checks that found at least one issue
+///
+/// # Usage
+/// The record segment is the source of truth of a collection, so a failed metadata or vector
+/// check can be repaired by rebuilding the segment from the record segment. A failed offset id
+/// check cannot be repaired this way. The checks read every block of the segments, so they
+/// should not run on the query path
+#[derive(Clone, Debug)]
+pub struct CheckIntegrityOperator {}
+
+#[derive(Debug)]
+pub struct CheckIntegrityInput {
+    pub blockfile_provider: BlockfileProvider,
+    pub hnsw_provider: HnswIndexProvider,
+    pub segments: FetchSegmentOutput,
+}
+
+#[derive(Debug, Default)]
+pub struct CheckIntegrityOutput {
+    pub num_records: usize,
+    pub num_issues: usize,
+    pub issues: Vec<IntegrityIssue>,
+    pub failed_checks: BTreeSet<IntegrityCheck>,
+}
+
+impl CheckIntegrityOutput {
+    fn report(&mut self, check: IntegrityCheck, description: String) {
+        self.num_issues += 1;
+        self.failed_checks.insert(check);
+        if self.issues.len() < MAX_REPORTED_ISSUES {
+            self.issues.push(IntegrityIssue { check, description });
+        }
+    }
+
+    pub fn failed(&self, check: IntegrityCheck) -> bool {
+        self.failed_checks.contains(&check)
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum CheckIntegrityError {
+    #[error("Error reading full text index: {0}")]
+    FullTextIndex(#[from] FullTextIndexError),
+    #[error("Error creating hnsw segment reader: {0}")]
+    HnswReader(#[from] DistributedHNSWSegmentFromSegmentError),
+    #[error("Error reading metadata index: {0}")]
+    MetadataIndex(#[from] MetadataIndexError),
+    #[error("Error creating metadata segment reader: {0}")]
+    MetadataReader(#[from] MetadataSegmentError),
+    #[error("Error reading record segment: {0}")]
+    RecordSegment(#[from] Box<dyn ChromaError>),
+    #[error("Error creating record segment reader: {0}")]
+    RecordReader(#[from] RecordSegmentReaderCreationError),
+}
+
+impl ChromaError for CheckIntegrityError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            CheckIntegrityError::FullTextIndex(e) => e.code(),
+            CheckIntegrityError::HnswReader(e) => e.code(),
+            CheckIntegrityError::MetadataIndex(e) => e.code(),
+            CheckIntegrityError::MetadataReader(e) => e.code(),
+            CheckIntegrityError::RecordSegment(e) => e.code(),
+            CheckIntegrityError::RecordReader(e) => e.code(),
+        }
+    }
+}
+
+/// Whether any value of the metadata is written to the metadata indexes
+fn has_indexed_metadata<'a>(mut values: impl Iterator<Item = &'a MetadataValue>) -> bool {
+    values.any(|value| {
+        value
+            .flatten("")
+            .into_iter()
+            .any(|(_, leaf)| !leaf.elements().is_empty())
+    })
+}
+
+/// The offset ids of the records of the record segment
+struct Records {
+    all: RoaringBitmap,
+    with_metadata: RoaringBitmap,
+}
+
+#[async_trait]
+impl Operator<CheckIntegrityInput, CheckIntegrityOutput> for CheckIntegrityOperator {
+    type Error = CheckIntegrityError;
+
+    fn get_type(&self) -> OperatorType {
+        OperatorType::IO
+    }
+
+    async fn run(
+        &self,
+        input: &CheckIntegrityInput,
+    ) -> Result<CheckIntegrityOutput, CheckIntegrityError> {
+        trace!("[{}]: {:?}", self.get_name(), input);
+
+        let record_segment_reader = match RecordSegmentReader::from_segment(
+            &input.segments.record_segment,
+            &input.blockfile_provider,
+        )
+        .await
+        {
+            Ok(reader) => reader,
+            // Nothing is compacted for the collection, so there is nothing to check
+            Err(e) if matches!(*e, RecordSegmentReaderCreationError::UninitializedSegment) => {
+                return Ok(CheckIntegrityOutput::default())
+            }
+            Err(e) => return Err((*e).into()),
+        };
+
+        let mut output = CheckIntegrityOutput::default();
+        let records = check_offset_ids(&record_segment_reader, &mut output).await?;
+        output.num_records = records.all.len() as usize;
+
+        let metadata_segment_reader = MetadataSegmentReader::from_segment(
+            &input.segments.metadata_segment,
+            &input.blockfile_provider,
+        )
+        .await?;
+        check_metadata(&metadata_segment_reader, &records, &mut output).await?;
+
+        check_vectors(input, &records, &mut output).await?;
+
+        Ok(output)
+    }
+}
+
+async fn check_offset_ids(
+    reader: &RecordSegmentReader<'_>,
+    output: &mut CheckIntegrityOutput,
+) -> Result<Records, CheckIntegrityError> {
+    let check = IntegrityCheck::OffsetIdContinuity;
+    let max_offset_id = reader
+        .get_current_max_offset_id()
+        .load(std::sync::atomic::Ordering::Relaxed);
+
+    let mut user_ids = HashMap::new();
+    let mut offset_ids = Box::pin(reader.offset_id_to_user_id_stream());
+    while let Some((offset_id, user_id)) = offset_ids.try_next().await? {
+        if offset_id > max_offset_id {
+            output.report(
+                check,
+                format!(
+                    "Offset id {offset_id} of user id {user_id} exceeds the max offset id {max_offset_id}"
+                ),
+            );
+        }
+        user_ids.insert(offset_id, user_id);
+    }
+
+    let mut mapped_back = RoaringBitmap::new();
+    let mut user_id_offset_ids = Box::pin(reader.user_id_to_offset_id_stream());
+    while let Some((user_id, offset_id)) = user_id_offset_ids.try_next().await? {
+        match user_ids.get(&offset_id) {
+            Some(mapped_user_id) if *mapped_user_id == user_id => {
+                mapped_back.insert(offset_id);
+            }
+            Some(mapped_user_id) => output.report(
+                check,
+                format!(
+                    "User id {user_id} maps to offset id {offset_id}, which maps to user id {mapped_user_id}"
+                ),
+            ),
+            None => output.report(
+                check,
+                format!("User id {user_id} maps to offset id {offset_id}, which has no user id"),
+            ),
+        }
+    }
+
+    let mut records = Records {
+        all: RoaringBitmap::new(),
+        with_metadata: RoaringBitmap::new(),
+    };
+    let mut data_records = Box::pin(reader.scan(None));
+    while let Some((offset_id, data_record)) = data_records.try_next().await? {
+        match user_ids.get(&offset_id) {
+            Some(user_id) if *user_id == data_record.id => {}
+            Some(user_id) => output.report(
+                check,
+                format!(
+                    "Data record at offset id {offset_id} has id {}, but the offset id maps to user id {user_id}",
+                    data_record.id
+                ),
+            ),
+            None => output.report(
+                check,
+                format!("Data record at offset id {offset_id} has no user id"),
+            ),
+        }
+        records.all.insert(offset_id);
+        if data_record
+            .metadata
+            .as_ref()
+            .is_some_and(|metadata| has_indexed_metadata(metadata.values()))
+        {
+            records.with_metadata.insert(offset_id);
+        }
+    }
+
+    let mut offset_ids = user_ids.into_iter().collect::<Vec<_>>();
+    offset_ids.sort_unstable();
+    for (offset_id, user_id) in offset_ids {
+        if !mapped_back.contains(offset_id) {
+            output.report(
+                check,
+                format!("Offset id {offset_id} maps to user id {user_id}, which does not map back to it"),
+            );
+        }
+        if !records.all.contains(offset_id) {
+            output.report(
+                check,
+                format!("Offset id {offset_id} of user id {user_id} has no data record"),
+            );
+        }
+    }
+
+    Ok(records)
+}
+
+async fn check_metadata(
+    reader: &MetadataSegmentReader<'_>,
+    records: &Records,
+    output: &mut CheckIntegrityOutput,
+) -> Result<(), CheckIntegrityError> {
+    let check = IntegrityCheck::MetadataAgreement;
+
+    let mut indexed = RoaringBitmap::new();
+    for metadata_index_reader in [
+        &reader.string_metadata_index_reader,
+        &reader.bool_metadata_index_reader,
+        &reader.f32_metadata_index_reader,
+        &reader.u32_metadata_index_reader,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        let mut posting_lists = metadata_index_reader.posting_lists_stream();
+        while let Some((key, _, rbm)) = posting_lists.try_next().await? {
+            if key != CASE_INSENSITIVE_MARKER {
+                indexed |= rbm;
+            }
+        }
+    }
+    for offset_id in &indexed - &records.all {
+        output.report(
+            check,
+            format!("Metadata index refers to offset id {offset_id}, which has no record"),
+        );
+    }
+    for offset_id in &records.with_metadata - &indexed {
+        output.report(
+            check,
+            format!("Metadata of the record at offset id {offset_id} is not indexed"),
+        );
+    }
+
+    if let Some(full_text_index_reader) = &reader.full_text_index_reader {
+        let mut documents = RoaringBitmap::new();
+        let mut postings = Box::pin(full_text_index_reader.posting_lists_stream());
+        while let Some((_, offset_id, _)) = postings.try_next().await? {
+            documents.insert(offset_id);
+        }
+        for offset_id in documents - &records.all {
+            output.report(
+                check,
+                format!("Full text index refers to offset id {offset_id}, which has no record"),
+            );
+        }
+    }
+
+    Ok(())
+}
+
+async fn check_vectors(
+    input: &CheckIntegrityInput,
+    records: &Records,
+    output: &mut CheckIntegrityOutput,
+) -> Result<(), CheckIntegrityError> {
+    let check = IntegrityCheck::VectorIndexCoverage;
+    let dimension = match input.segments.collection.dimension {
+        Some(dimension) => dimension as usize,
+        None if records.all.is_empty() => return Ok(()),
+        None => {
+            output.report(
+                check,
+                format!(
+                    "Collection has {} records but no dimension",
+                    records.all.len()
+                ),
+            );
+            return Ok(());
+        }
+    };
+    let reader = match DistributedHNSWSegmentReader::from_segment(
+        &input.segments.vector_segment,
+        dimension,
+        input.hnsw_provider.clone(),
+    )
+    .await
+    {
+        Ok(reader) => reader,
+        Err(e) if matches!(*e, DistributedHNSWSegmentFromSegmentError::Uninitialized) => {
+            if !records.all.is_empty() {
+                output.report(
+                    check,
+                    format!(
+                        "Vector segment is not initialized, but there are {} records",
+                        records.all.len()
+                    ),
+                );
+            }
+            return Ok(());
+        }
+        Err(e) => return Err((*e).into()),
+    };
+
+    for offset_id in &records.all {
+        if !reader.contains(offset_id) {
+            output.report(
+                check,
+                format!("Vector index has no embedding for offset id {offset_id}"),
+            );
+        }
+    }
+    if reader.len() as u64 != records.all.len() {
+        output.report(
+            check,
+            format!(
+                "Vector index holds {} embeddings, but there are {} records",
+                reader.len(),
+                records.all.len()
+            ),
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        execution::{
+            operator::Operator,
+            operators::{
+                check_integrity::{CheckIntegrityInput, CheckIntegrityOperator, IntegrityCheck},
+                fetch_segment::FetchSegmentOutput,
+            },
+        },
+        log::test::{upsert_generator, LogGenerator},
+        segment::test::TestSegment,
+    };
+
+    fn check_integrity_input(test_segment: &TestSegment) -> CheckIntegrityInput {
+        CheckIntegrityInput {
+            blockfile_provider: test_segment.blockfile_provider.clone(),
+            hnsw_provider: test_segment.hnsw_provider.clone(),
+            segments: FetchSegmentOutput {
+                collection: test_segment.collection.clone(),
+                metadata_segment: test_segment.metadata_segment.clone(),
+                record_segment: test_segment.record_segment.clone(),
+                vector_segment: test_segment.vector_segment.clone(),
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_check_empty_collection() {
+        let test_segment = TestSegment::default();
+        let check_output = CheckIntegrityOperator {}
+            .run(&check_integrity_input(&test_segment))
+            .await
+            .expect("CheckIntegrityOperator should not fail");
+        assert_eq!(check_output.num_records, 0);
+        assert_eq!(check_output.num_issues, 0);
+    }
+
+    #[tokio::test]
+    async fn test_check_compacted_collection() {
+        let mut test_segment = TestSegment::default();
+        test_segment
+            .populate_with_generator(
+                100,
+                &LogGenerator {
+                    generator: upsert_generator,
+                },
+            )
+            .await;
+        let check_output = CheckIntegrityOperator {}
+            .run(&check_integrity_input(&test_segment))
+            .await
+            .expect("CheckIntegrityOperator should not fail");
+        assert_eq!(check_output.num_records, 100);
+        assert!(!check_output.failed(IntegrityCheck::OffsetIdContinuity));
+        assert!(!check_output.failed(IntegrityCheck::MetadataAgreement));
+        // The vector segment is not populated by the test segment
+        assert!(check_output.failed(IntegrityCheck::VectorIndexCoverage));
+        assert_eq!(check_output.num_issues, 1);
+    }
+}
@@ -0,0 +1,282 @@
+use std::sync::atomic;
+
+use chroma_blockstore::provider::BlockfileProvider;
+use chroma_error::{ChromaError, ErrorCodes};
+use chroma_types::{Chunk, LogRecord, MaterializedLogOperation, Segment, SignedRoaringBitmap};
+use futures::{future, TryStreamExt};
+use roaring::RoaringBitmap;
+use thiserror::Error;
+use tonic::async_trait;
+use tracing::{trace, Instrument, Span};
+
+use crate::{
+    execution::operator::Operator,
+    segment::{
+        record_segment::{RecordSegmentReader, RecordSegmentReaderCreationError},
+        LogMaterializer, LogMaterializerError, MaterializedLogFields,
+    },
+};
+
+/// The `CountOperator` counts the records in the collection without hydrating them
+///
+/// # Parameters
+/// None
+///
+/// # Inputs
+/// - `logs`: The latest logs of the collection
+/// - `blockfile_provider`: The blockfile provider
+/// - `record_segment`: The record segment information
+/// - `log_offset_ids`: The offset ids in the logs to include or exclude before counting
+/// - `compact_offset_ids`: The offset ids in the blockfile to include or exclude before counting
+///
+/// # Outputs
+/// - `count`: The number of records
+///
+/// # Usage
+/// It can be used to count the records in a collection, optionally after a `FilterOperator`
+/// The offset ids touched by the logs are always excluded from the record segment,
+/// so records overwritten or deleted in the logs are not counted twice
+#[derive(Clone, Debug)]
+pub struct CountOperator {}
+
+#[derive(Clone, Debug)]
+pub struct CountInput {
+    pub logs: Chunk<LogRecord>,
+    pub blockfile_provider: BlockfileProvider,
+    pub record_segment: Segment,
+    pub log_offset_ids: SignedRoaringBitmap,
+    pub compact_offset_ids: SignedRoaringBitmap,
+}
+
+#[derive(Debug)]
+pub struct CountOutput {
+    pub count: u64,
+}
+
+#[derive(Error, Debug)]
+pub enum CountError {
+    #[error("Error materializing log: {0}")]
+    LogMaterializer(#[from] LogMaterializerError),
+    #[error("Error creating record segment reader: {0}")]
+    RecordReader(#[from] RecordSegmentReaderCreationError),
+    #[error("Error reading record segment: {0}")]
+    RecordSegment(#[from] Box<dyn ChromaError>),
+}
+
+impl ChromaError for CountError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            CountError::LogMaterializer(e) => e.code(),
+            CountError::RecordReader(e) => e.code(),
+            CountError::RecordSegment(e) => e.code(),
+        }
+    }
+}
+
+#[async_trait]
+impl Operator<CountInput, CountOutput> for CountOperator {
+    type Error = CountError;
+
+    async fn run(&self, input: &CountInput) -> Result<CountOutput, CountError> {
+        trace!("[{}]: {:?}", self.get_name(), input);
+
+        let record_segment_reader = match RecordSegmentReader::from_segment(
+            &input.record_segment,
+            &input.blockfile_provider,
+        )
+        .await
+        {
+            Ok(reader) => Ok(Some(reader)),
+            Err(e) if matches!(*e, RecordSegmentReaderCreationError::UninitializedSegment) => {
+                Ok(None)
+            }
+            Err(e) => Err(*e),
+        }?;
+
+        let materializer =
+            LogMaterializer::new(record_segment_reader.clone(), input.logs.clone(), None)
+                .with_fields(MaterializedLogFields::OFFSET_IDS);
+        let materialized_logs = materializer
+            .materialize()
+            .instrument(tracing::trace_span!(parent: Span::current(), "Materialize logs"))
+            .await?;
+
+        // The offset ids that are alive after applying the logs
+        let mut active_log_offset_ids = RoaringBitmap::new();
+        // The offset ids in the record segment that are overwritten or deleted by the logs
+        let mut updated_offset_ids = RoaringBitmap::new();
+        for (log, _) in materialized_logs.iter() {
+            if !matches!(
+                log.final_operation,
+                MaterializedLogOperation::Initial | MaterializedLogOperation::AddNew
+            ) {
+                updated_offset_ids.insert(log.offset_id);
+            }
+            if !matches!(
+                log.final_operation,
+                MaterializedLogOperation::DeleteExisting
+            ) {
+                active_log_offset_ids.insert(log.offset_id);
+            }
+        }
+
+        let log_count = match &input.log_offset_ids {
+            SignedRoaringBitmap::Include(rbm) => active_log_offset_ids.intersection_len(rbm),
+            SignedRoaringBitmap::Exclude(rbm) => active_log_offset_ids.difference_len(rbm),
+        };
+
+        let compact_count = match record_segment_reader {
+            Some(reader) => match &input.compact_offset_ids {
+                SignedRoaringBitmap::Include(rbm) => rbm.difference_len(&updated_offset_ids),
+                SignedRoaringBitmap::Exclude(rbm) => {
+                    // The offset ids after the maximum offset id of the segment are not in it
+                    let max_offset_id = reader
+                        .get_current_max_offset_id()
+                        .load(atomic::Ordering::Relaxed);
+                    let mut excluded_offset_ids = rbm | &updated_offset_ids;
+                    if let Some(after_max_offset_id) = max_offset_id.checked_add(1) {
+                        excluded_offset_ids.remove_range(after_max_offset_id..);
+                    }
+                    if excluded_in_segment(&reader, &excluded_offset_ids).await? {
+                        (reader.count().await? as u64).saturating_sub(excluded_offset_ids.len())
+                    } else {
+                        // The excluded offset ids include ids that are not in the record segment,
+                        // so the offset ids of the segment are counted instead of subtracted from
+                        reader
+                            .offset_id_to_user_id_stream()
+                            .try_filter(|(offset_id, _)| {
+                                future::ready(!excluded_offset_ids.contains(*offset_id))
+                            })
+                            .try_fold(0, |count, _| future::ready(Ok(count + 1)))
+                            .await?
+                    }
+                }
+            },
+            None => 0,
+        };
+
+        Ok(CountOutput {
+            count: log_count + compact_count,
+        })
+    }
+}
+
+// Checks whether all the excluded offset ids are in the record segment, so that they can be
+// subtracted from the count of the segment
+async fn excluded_in_segment(
+    reader: &RecordSegmentReader<'_>,
+    excluded_offset_ids: &RoaringBitmap,
+) -> Result<bool, CountError> {
+    for offset_id in excluded_offset_ids {
+        if !reader.offset_id_exists(offset_id).await? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use chroma_types::SignedRoaringBitmap;
+
+    use crate::{
+        execution::{operator::Operator, operators::count::CountOperator},
+        log::test::{add_delete_generator, LogGenerator},
+        segment::test::TestSegment,
+    };
+
+    use super::CountInput;
+
+    /// The unit tests for `CountOperator` uses the following test data
+    /// It generates 120 log records, where the first 60 is compacted:
+    /// - Log: Delete [11..=20], add [51..=100]
+    /// - Compacted: Delete [1..=10] deletion, add [11..=50]
+    async fn setup_count_input(
+        log_offset_ids: SignedRoaringBitmap,
+        compact_offset_ids: SignedRoaringBitmap,
+    ) -> CountInput {
+        let mut test_segment = TestSegment::default();
+        let generator = LogGenerator {
+            generator: add_delete_generator,
+        };
+        test_segment.populate_with_generator(60, &generator).await;
+        CountInput {
+            logs: generator.generate_chunk(61..=120),
+            blockfile_provider: test_segment.blockfile_provider,
+            record_segment: test_segment.record_segment,
+            log_offset_ids,
+            compact_offset_ids,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_trivial_count() {
+        let count_input =
+            setup_count_input(SignedRoaringBitmap::full(), SignedRoaringBitmap::full()).await;
+
+        let count_operator = CountOperator {};
+
+        let count_output = count_operator
+            .run(&count_input)
+            .await
+            .expect("CountOperator should not fail");
+
+        assert_eq!(count_output.count, 80);
+    }
+
+    #[tokio::test]
+    async fn test_filtered_count() {
+        let count_input = setup_count_input(
+            SignedRoaringBitmap::Include((51..=60).collect()),
+            SignedRoaringBitmap::Exclude((21..=40).collect()),
+        )
+        .await;
+
+        let count_operator = CountOperator {};
+
+        let count_output = count_operator
+            .run(&count_input)
+            .await
+            .expect("CountOperator should not fail");
+
+        assert_eq!(count_output.count, 20);
+    }
+
+    #[tokio::test]
+    async fn test_count_excluding_log_offset_ids() {
+        let count_input = setup_count_input(
+            SignedRoaringBitmap::Include(Default::default()),
+            SignedRoaringBitmap::Exclude((45..=100).collect()),
+        )
+        .await;
+
+        let count_operator = CountOperator {};
+
+        let count_output = count_operator
+            .run(&count_input)
+            .await
+            .expect("CountOperator should not fail");
+
+        // The compacted records [11..=20] are deleted in the log and [45..=50] are excluded
+        assert_eq!(count_output.count, 24);
+    }
+
+    #[tokio::test]
+    async fn test_count_excluding_deleted_offset_ids() {
+        let count_input = setup_count_input(
+            SignedRoaringBitmap::Include(Default::default()),
+            SignedRoaringBitmap::Exclude((1..=30).collect()),
+        )
+        .await;
+
+        let count_operator = CountOperator {};
+
+        let count_output = count_operator
+            .run(&count_input)
+            .await
+            .expect("CountOperator should not fail");
+
+        // The excluded records [1..=10] are deleted during compaction, so the segment is scanned
+        assert_eq!(count_output.count, 20);
+    }
+}
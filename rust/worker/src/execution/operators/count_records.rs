@@ -103,7 +103,7 @@ impl Operator<CountRecordsInput, CountRecordsOutput> for CountRecordsOperator {
                             count: seen_id_set.len(),
                         });
                     }
-                    RecordSegmentReaderCreationError::BlockfileOpenError(_) => {
+                    RecordSegmentReaderCreationError::BlockfileOpenError(..) => {
                         return Err(CountRecordsError::RecordSegmentCreateError(*e));
                     }
                     RecordSegmentReaderCreationError::InvalidNumberOfFiles => {
@@ -195,6 +195,10 @@ impl Operator<CountRecordsInput, CountRecordsOutput> for CountRecordsOperator {
             count: res_count as usize,
         })
     }
+
+    fn can_retry(&self, error: &CountRecordsError) -> bool {
+        error.is_transient()
+    }
 }
 
 #[cfg(test)]
@@ -240,6 +244,9 @@ mod tests {
                         encoding: None,
                         metadata: None,
                         document: None,
+                        uri: None,
+                        idempotency_key: None,
+                        embedding_reference: None,
                         operation: Operation::Add,
                     },
                 },
@@ -251,6 +258,9 @@ mod tests {
                         encoding: None,
                         metadata: None,
                         document: None,
+                        uri: None,
+                        idempotency_key: None,
+                        embedding_reference: None,
                         operation: Operation::Add,
                     },
                 },
@@ -262,6 +272,9 @@ mod tests {
                         encoding: None,
                         metadata: None,
                         document: None,
+                        uri: None,
+                        idempotency_key: None,
+                        embedding_reference: None,
                         operation: Operation::Delete,
                     },
                 },
@@ -276,7 +289,7 @@ mod tests {
                             // Uninitialized segment is fine and means that the record
                             // segment is not yet initialized in storage.
                             RecordSegmentReaderCreationError::UninitializedSegment => None,
-                            RecordSegmentReaderCreationError::BlockfileOpenError(_) => {
+                            RecordSegmentReaderCreationError::BlockfileOpenError(..) => {
                                 panic!(
                                     "Error creating record segment reader. Blockfile open error."
                                 );
@@ -318,6 +331,9 @@ mod tests {
                     encoding: None,
                     metadata: None,
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Add,
                 },
             },
@@ -329,6 +345,9 @@ mod tests {
                     encoding: None,
                     metadata: None,
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Add,
                 },
             },
@@ -340,6 +359,9 @@ mod tests {
                     encoding: None,
                     metadata: None,
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Update,
                 },
             },
@@ -381,6 +403,9 @@ mod tests {
                     encoding: None,
                     metadata: None,
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Add,
                 },
             },
@@ -392,6 +417,9 @@ mod tests {
                     encoding: None,
                     metadata: None,
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Add,
                 },
             },
@@ -403,6 +431,9 @@ mod tests {
                     encoding: None,
                     metadata: None,
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Delete,
                 },
             },
@@ -414,6 +445,9 @@ mod tests {
                     encoding: None,
                     metadata: None,
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Add,
                 },
             },
@@ -425,6 +459,9 @@ mod tests {
                     encoding: None,
                     metadata: None,
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Upsert,
                 },
             },
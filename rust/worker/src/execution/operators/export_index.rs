@@ -0,0 +1,370 @@
+use std::path::{Path, PathBuf};
+
+use chroma_blockstore::{key::KeyWrapper, provider::BlockfileProvider};
+use chroma_error::{ChromaError, ErrorCodes};
+use chroma_index::{fulltext::types::FullTextIndexError, metadata::types::MetadataIndexError};
+use chroma_storage::{PutError, PutOptions, Storage};
+use chroma_types::Segment;
+use futures::TryStreamExt;
+use serde::Serialize;
+use serde_json::Value;
+use thiserror::Error;
+use tokio::{
+    fs::File,
+    io::{AsyncWriteExt, BufWriter},
+};
+use tonic::async_trait;
+use tracing::trace;
+
+use crate::{
+    execution::{
+        operator::{Operator, OperatorType},
+        spill::SpillDir,
+    },
+    segment::metadata_segment::{
+        case_insensitive_metadata_prefix, decode_timestamp, timestamp_metadata_prefix,
+        MetadataSegmentError, MetadataSegmentReader,
+    },
+    sysdb::sysdb::{GetTenantError, SysDb},
+};
+
+/// The `ExportIndexOperator` writes the full text index and the metadata indexes of a metadata
+/// segment to the storage in a documented format, so that an external search system can be
+/// bootstrapped from them or the content of the indexes can be audited
+///
+/// # Parameters
+/// - `prefix`: The storage prefix under which the exported files are written
+/// - `temporary_path`: The local directory under which the exported files are written before
+///   they are uploaded
+///
+/// # Inputs
+/// - `blockfile_provider`: The blockfile provider
+/// - `storage`: The storage to write the exported files to
+/// - `sysdb`: The sysdb client
+/// - `tenant`: The tenant of the collection, whose KMS key the exported files are encrypted with
+/// - `metadata_segment`: The metadata segment information
+///
+/// # Outputs
+/// - `num_tokens`: The number of tokens of the full text index that are exported
+/// - `num_posting_lists`: The number of metadata posting lists that are exported
+///
+/// # Usage
+/// The export consists of two JSON Lines files, with one JSON object per line:
+/// - `{prefix}/fulltext.jsonl`: One object per token of the full text index, in token order,
+///   e.g. `{"token":"abc","postings":[{"offset_id":1,"positions":[0,7]}]}`
+/// - `{prefix}/metadata.jsonl`: One object per metadata key and value, in key order,
+///   e.g. `{"key":"color","type":"string","value":"red","offset_ids":[1,2]}`. The `type` is one
+///   of `string`, `bool`, `float`, `int` and `timestamp`, and timestamps are in microseconds
+///   since the Unix epoch
+///
+/// The offset ids are the offset ids of the records in the record segment. The lowercased copies
+/// of the string values, which serve the case insensitive filters, are not exported
+///
+/// The lines are written to local files as they are read, and the files are uploaded from disk,
+/// so that an export is never held in memory
+#[derive(Clone, Debug)]
+pub struct ExportIndexOperator {
+    pub prefix: String,
+    pub temporary_path: PathBuf,
+}
+
+#[derive(Clone, Debug)]
+pub struct ExportIndexInput {
+    pub blockfile_provider: BlockfileProvider,
+    pub storage: Storage,
+    pub sysdb: Box<SysDb>,
+    pub tenant: String,
+    pub metadata_segment: Segment,
+}
+
+#[derive(Debug, Default)]
+pub struct ExportIndexOutput {
+    pub num_tokens: usize,
+    pub num_posting_lists: usize,
+}
+
+#[derive(Error, Debug)]
+pub enum ExportIndexError {
+    #[error("Error reading full text index: {0}")]
+    FullTextIndex(#[from] FullTextIndexError),
+    #[error("Error getting tenant: {0}")]
+    GetTenant(#[from] GetTenantError),
+    #[error("Error reading metadata index: {0}")]
+    MetadataIndex(#[from] MetadataIndexError),
+    #[error("Error creating metadata segment reader: {0}")]
+    MetadataReader(#[from] MetadataSegmentError),
+    #[error("Error writing exported index: {0}")]
+    Storage(#[from] PutError),
+    #[error("Error writing exported index to local file: {0}")]
+    Write(#[from] std::io::Error),
+}
+
+impl ChromaError for ExportIndexError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            ExportIndexError::FullTextIndex(e) => e.code(),
+            ExportIndexError::GetTenant(e) => e.code(),
+            ExportIndexError::MetadataIndex(e) => e.code(),
+            ExportIndexError::MetadataReader(e) => e.code(),
+            ExportIndexError::Storage(e) => e.code(),
+            ExportIndexError::Write(_) => ErrorCodes::Internal,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ExportedPosting<'me> {
+    offset_id: u32,
+    positions: &'me [u32],
+}
+
+#[derive(Serialize)]
+struct ExportedToken<'me> {
+    token: &'me str,
+    postings: Vec<ExportedPosting<'me>>,
+}
+
+#[derive(Serialize)]
+struct ExportedPostingList<'me> {
+    key: &'me str,
+    #[serde(rename = "type")]
+    value_type: &'static str,
+    value: Value,
+    offset_ids: Vec<u32>,
+}
+
+async fn write_line<T: Serialize>(
+    writer: &mut BufWriter<File>,
+    line: &mut Vec<u8>,
+    value: &T,
+) -> Result<(), std::io::Error> {
+    line.clear();
+    serde_json::to_writer(&mut *line, value).expect("Serializing to a vector should not fail");
+    line.push(b'\n');
+    writer.write_all(line).await
+}
+
+async fn create_file(path: &Path) -> Result<BufWriter<File>, std::io::Error> {
+    Ok(BufWriter::new(File::create(path).await?))
+}
+
+/// Returns the metadata key, the type and the value of an entry of a metadata index, or `None`
+/// if the entry is derived from another entry and is not exported
+fn exported_entry(prefix: &str, value: KeyWrapper) -> Option<(&str, &'static str, Value)> {
+    if prefix.ends_with(case_insensitive_metadata_prefix("").as_str()) {
+        return None;
+    }
+    if let Some(key) = prefix.strip_suffix(timestamp_metadata_prefix("").as_str()) {
+        return match value {
+            KeyWrapper::String(encoded) => {
+                decode_timestamp(&encoded).map(|micros| (key, "timestamp", Value::from(micros)))
+            }
+            _ => None,
+        };
+    }
+    let (value_type, value) = match value {
+        KeyWrapper::String(s) => ("string", Value::from(s)),
+        KeyWrapper::Bool(b) => ("bool", Value::from(b)),
+        KeyWrapper::Float32(f) => ("float", Value::from(f as f64)),
+        // Integers are stored as their truncated bit pattern
+        KeyWrapper::Uint32(i) => ("int", Value::from(i as i32 as i64)),
+        // The metadata indexes have no other key types
+        KeyWrapper::Uint64(_) | KeyWrapper::Uuid(_) => return None,
+    };
+    Some((prefix, value_type, value))
+}
+
+#[async_trait]
+impl Operator<ExportIndexInput, ExportIndexOutput> for ExportIndexOperator {
+    type Error = ExportIndexError;
+
+    fn get_type(&self) -> OperatorType {
+        OperatorType::IO
+    }
+
+    async fn run(&self, input: &ExportIndexInput) -> Result<ExportIndexOutput, ExportIndexError> {
+        trace!("[{}]: {:?}", self.get_name(), input);
+
+        let metadata_segment_reader =
+            MetadataSegmentReader::from_segment(&input.metadata_segment, &input.blockfile_provider)
+                .await?;
+
+        let export_dir = SpillDir::create(&self.temporary_path).await?;
+        let fulltext_path = export_dir.path().join("fulltext.jsonl");
+        let metadata_path = export_dir.path().join("metadata.jsonl");
+        let mut line = Vec::new();
+
+        // The entries of a token are consecutive, so each token is written once its last
+        // entry is read
+        let mut num_tokens = 0;
+        let mut fulltext_file = create_file(&fulltext_path).await?;
+        if let Some(reader) = &metadata_segment_reader.full_text_index_reader {
+            let mut postings = Box::pin(reader.posting_lists_stream());
+            let mut current: Option<ExportedToken> = None;
+            while let Some((token, offset_id, positions)) = postings.try_next().await? {
+                let posting = ExportedPosting {
+                    offset_id,
+                    positions,
+                };
+                if let Some(exported) = current.as_mut().filter(|exported| exported.token == token)
+                {
+                    exported.postings.push(posting);
+                    continue;
+                }
+                if let Some(exported) = current.replace(ExportedToken {
+                    token,
+                    postings: vec![posting],
+                }) {
+                    write_line(&mut fulltext_file, &mut line, &exported).await?;
+                    num_tokens += 1;
+                }
+            }
+            if let Some(exported) = current {
+                write_line(&mut fulltext_file, &mut line, &exported).await?;
+                num_tokens += 1;
+            }
+        }
+        fulltext_file.flush().await?;
+
+        let mut num_posting_lists = 0;
+        let mut metadata_file = create_file(&metadata_path).await?;
+        for reader in [
+            &metadata_segment_reader.string_metadata_index_reader,
+            &metadata_segment_reader.bool_metadata_index_reader,
+            &metadata_segment_reader.f32_metadata_index_reader,
+            &metadata_segment_reader.u32_metadata_index_reader,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            let mut posting_lists = reader.posting_lists_stream();
+            while let Some((prefix, value, rbm)) = posting_lists.try_next().await? {
+                if let Some((key, value_type, value)) = exported_entry(prefix, value) {
+                    write_line(
+                        &mut metadata_file,
+                        &mut line,
+                        &ExportedPostingList {
+                            key,
+                            value_type,
+                            value,
+                            offset_ids: rbm.iter().collect(),
+                        },
+                    )
+                    .await?;
+                    num_posting_lists += 1;
+                }
+            }
+        }
+        metadata_file.flush().await?;
+
+        // The exported files are encrypted like the compacted files of the tenant
+        let put_options = PutOptions {
+            kms_key_id: input
+                .sysdb
+                .clone()
+                .get_tenant_kms_key_id(input.tenant.clone())
+                .await?,
+        };
+        for (name, path) in [
+            ("fulltext.jsonl", &fulltext_path),
+            ("metadata.jsonl", &metadata_path),
+        ] {
+            input
+                .storage
+                .put_file_with_options(
+                    &format!("{}/{}", self.prefix, name),
+                    &path.to_string_lossy(),
+                    &put_options,
+                )
+                .await?;
+        }
+
+        Ok(ExportIndexOutput {
+            num_tokens,
+            num_posting_lists,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chroma_storage::{local::LocalStorage, Storage};
+    use serde_json::Value;
+
+    use crate::{
+        execution::{
+            operator::Operator,
+            operators::export_index::{ExportIndexInput, ExportIndexOperator},
+        },
+        log::test::{upsert_generator, LogGenerator},
+        segment::test::TestSegment,
+        sysdb::{sysdb::SysDb, test_sysdb::TestSysDb},
+    };
+
+    async fn read_lines(storage: &Storage, key: &str) -> Vec<Value> {
+        let bytes = storage.get(key).await.expect("Exported file should exist");
+        String::from_utf8(bytes.to_vec())
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_export_index() {
+        let mut test_segment = TestSegment::default();
+        test_segment
+            .populate_with_generator(
+                10,
+                &LogGenerator {
+                    generator: upsert_generator,
+                },
+            )
+            .await;
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let storage = Storage::Local(LocalStorage::new(tmp_dir.path().to_str().unwrap()));
+        let mut sysdb = TestSysDb::new();
+
+        let export_dir = tempfile::tempdir().unwrap();
+        let export_operator = ExportIndexOperator {
+            prefix: "export".to_string(),
+            temporary_path: export_dir.path().to_path_buf(),
+        };
+        let export_input = ExportIndexInput {
+            blockfile_provider: test_segment.blockfile_provider.clone(),
+            storage: storage.clone(),
+            sysdb: Box::new(SysDb::Test(sysdb.clone())),
+            tenant: test_segment.collection.tenant.clone(),
+            metadata_segment: test_segment.metadata_segment.clone(),
+        };
+        let export_output = export_operator
+            .run(&export_input)
+            .await
+            .expect("ExportIndexOperator should not fail");
+
+        let tokens = read_lines(&storage, "export/fulltext.jsonl").await;
+        assert_eq!(tokens.len(), export_output.num_tokens);
+        assert!(!tokens.is_empty());
+        assert!(tokens
+            .windows(2)
+            .all(|pair| pair[0]["token"].as_str().unwrap() < pair[1]["token"].as_str().unwrap()));
+
+        let posting_lists = read_lines(&storage, "export/metadata.jsonl").await;
+        assert_eq!(posting_lists.len(), export_output.num_posting_lists);
+        let is_even = posting_lists
+            .iter()
+            .find(|posting_list| {
+                posting_list["key"] == "is_even" && posting_list["value"] == Value::Bool(true)
+            })
+            .expect("The posting list of is_even should be exported");
+        assert_eq!(is_even["type"], "bool");
+        assert_eq!(is_even["offset_ids"], serde_json::json!([2, 4, 6, 8, 10]));
+
+        // The local storage cannot encrypt the exported files with the key of the tenant
+        sysdb.set_tenant_kms_key_id(
+            test_segment.collection.tenant.clone(),
+            "test-kms-key".to_string(),
+        );
+        assert!(export_operator.run(&export_input).await.is_err());
+    }
+}
@@ -0,0 +1,316 @@
+use std::cmp::Ordering;
+
+use chroma_blockstore::provider::BlockfileProvider;
+use chroma_error::{ChromaError, ErrorCodes};
+use chroma_types::{Chunk, LogRecord, MaterializedLogOperation, Segment, SignedRoaringBitmap};
+use futures::TryStreamExt;
+use roaring::RoaringBitmap;
+use thiserror::Error;
+use tonic::async_trait;
+use tracing::{trace, Instrument, Span};
+
+use crate::{
+    execution::{
+        operator::Operator,
+        operators::aggregate::{add_to_group, AggregateGroup},
+    },
+    segment::{
+        metadata_segment::{MetadataSegmentError, MetadataSegmentReader},
+        record_segment::{RecordSegmentReader, RecordSegmentReaderCreationError},
+        LogMaterializer, LogMaterializerError,
+    },
+};
+
+/// The `FacetOperator` counts the records for each value of several metadata keys,
+/// intersecting the posting lists of the values with the filtered offset ids
+///
+/// # Parameters
+/// - `keys`: The metadata keys to count the values of
+/// - `limit`: The maximum number of values to return for each key
+///
+/// # Inputs
+/// - `logs`: The latest logs of the collection
+/// - `blockfile_provider`: The blockfile provider
+/// - `metadata_segment`: The metadata segment information
+/// - `record_segment`: The record segment information
+/// - `log_offset_ids`: The offset ids in the logs to include or exclude before counting
+/// - `compact_offset_ids`: The offset ids in the blockfile to include or exclude before counting
+///
+/// # Outputs
+/// - `facets`: The values of each key with the most records and their number of records,
+///   in descending order of count, in the order of the keys
+///
+/// # Usage
+/// It can be used after a `FilterOperator` to show how the filtered records are distributed
+/// over the values of the keys. The logs are materialized once for all of the keys
+#[derive(Clone, Debug)]
+pub struct FacetOperator {
+    pub keys: Vec<String>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Clone, Debug)]
+pub struct FacetInput {
+    pub logs: Chunk<LogRecord>,
+    pub blockfile_provider: BlockfileProvider,
+    pub metadata_segment: Segment,
+    pub record_segment: Segment,
+    pub log_offset_ids: SignedRoaringBitmap,
+    pub compact_offset_ids: SignedRoaringBitmap,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Facet {
+    pub key: String,
+    pub groups: Vec<AggregateGroup>,
+}
+
+#[derive(Debug)]
+pub struct FacetOutput {
+    pub facets: Vec<Facet>,
+}
+
+#[derive(Error, Debug)]
+pub enum FacetError {
+    #[error("Error materializing log: {0}")]
+    LogMaterializer(#[from] LogMaterializerError),
+    #[error("Error reading metadata segment: {0}")]
+    MetadataSegment(#[from] MetadataSegmentError),
+    #[error("Error creating record segment reader: {0}")]
+    RecordReader(#[from] RecordSegmentReaderCreationError),
+}
+
+impl ChromaError for FacetError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            FacetError::LogMaterializer(e) => e.code(),
+            FacetError::MetadataSegment(e) => e.code(),
+            FacetError::RecordReader(e) => e.code(),
+        }
+    }
+}
+
+#[async_trait]
+impl Operator<FacetInput, FacetOutput> for FacetOperator {
+    type Error = FacetError;
+
+    async fn run(&self, input: &FacetInput) -> Result<FacetOutput, FacetError> {
+        trace!("[{}]: {:?}", self.get_name(), input);
+
+        let record_segment_reader = match RecordSegmentReader::from_segment(
+            &input.record_segment,
+            &input.blockfile_provider,
+        )
+        .await
+        {
+            Ok(reader) => Ok(Some(reader)),
+            Err(e) if matches!(*e, RecordSegmentReaderCreationError::UninitializedSegment) => {
+                Ok(None)
+            }
+            Err(e) => Err(*e),
+        }?;
+
+        let materializer =
+            LogMaterializer::new(record_segment_reader.clone(), input.logs.clone(), None);
+        let materialized_logs = materializer
+            .materialize()
+            .instrument(tracing::trace_span!(parent: Span::current(), "Materialize logs"))
+            .await?;
+
+        let mut facets = self
+            .keys
+            .iter()
+            .map(|key| Facet {
+                key: key.clone(),
+                groups: Vec::new(),
+            })
+            .collect::<Vec<_>>();
+
+        // The offset ids in the record segment that are overwritten or deleted by the logs
+        let mut updated_offset_ids = RoaringBitmap::new();
+        for (log, _) in materialized_logs.iter() {
+            if !matches!(
+                log.final_operation,
+                MaterializedLogOperation::Initial | MaterializedLogOperation::AddNew
+            ) {
+                updated_offset_ids.insert(log.offset_id);
+            }
+            if matches!(
+                log.final_operation,
+                MaterializedLogOperation::DeleteExisting
+            ) || !input.log_offset_ids.contains(log.offset_id)
+            {
+                continue;
+            }
+            for (key, value) in log.merged_metadata_ref() {
+                for (path, value) in value.flatten(key) {
+                    if let Some(facet) = facets.iter_mut().find(|facet| facet.key == path) {
+                        for element in value.elements() {
+                            add_to_group(&mut facet.groups, element, 1);
+                        }
+                    }
+                }
+            }
+        }
+
+        if record_segment_reader.is_some() {
+            let metadata_segment_reader = MetadataSegmentReader::from_segment(
+                &input.metadata_segment,
+                &input.blockfile_provider,
+            )
+            .await?;
+            for facet in facets.iter_mut() {
+                let mut values = Box::pin(metadata_segment_reader.group_by_stream(&facet.key));
+                while let Some((value, mut rbm)) = values.try_next().await? {
+                    rbm -= &updated_offset_ids;
+                    match &input.compact_offset_ids {
+                        SignedRoaringBitmap::Include(include) => rbm &= include,
+                        SignedRoaringBitmap::Exclude(exclude) => rbm -= exclude,
+                    }
+                    add_to_group(&mut facet.groups, &value, rbm.len());
+                }
+            }
+        }
+
+        for facet in facets.iter_mut() {
+            facet.groups.sort_by(|a, b| {
+                b.count
+                    .cmp(&a.count)
+                    .then(a.value.partial_cmp(&b.value).unwrap_or(Ordering::Equal))
+            });
+            if let Some(limit) = self.limit {
+                facet.groups.truncate(limit as usize);
+            }
+        }
+
+        Ok(FacetOutput { facets })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chroma_types::{MetadataValue, SignedRoaringBitmap};
+
+    use crate::{
+        execution::{
+            operator::Operator,
+            operators::{
+                aggregate::AggregateGroup,
+                facet::{Facet, FacetInput, FacetOperator},
+            },
+        },
+        log::test::{add_delete_generator, LogGenerator},
+        segment::test::TestSegment,
+    };
+
+    /// The unit tests for `FacetOperator` uses the following test data
+    /// It generates 120 log records, where the first 60 is compacted:
+    /// - Log: Delete [11..=20], add [51..=100]
+    /// - Compacted: Delete [1..=10] deletion, add [11..=50]
+    async fn setup_facet_input(
+        log_offset_ids: SignedRoaringBitmap,
+        compact_offset_ids: SignedRoaringBitmap,
+    ) -> FacetInput {
+        let mut test_segment = TestSegment::default();
+        let generator = LogGenerator {
+            generator: add_delete_generator,
+        };
+        test_segment.populate_with_generator(60, &generator).await;
+        FacetInput {
+            logs: generator.generate_chunk(61..=120),
+            blockfile_provider: test_segment.blockfile_provider,
+            metadata_segment: test_segment.metadata_segment,
+            record_segment: test_segment.record_segment,
+            log_offset_ids,
+            compact_offset_ids,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_trivial_facet() {
+        let facet_input =
+            setup_facet_input(SignedRoaringBitmap::full(), SignedRoaringBitmap::full()).await;
+
+        let facet_operator = FacetOperator {
+            keys: vec!["modulo_3".to_string(), "is_even".to_string()],
+            limit: None,
+        };
+
+        let facet_output = facet_operator
+            .run(&facet_input)
+            .await
+            .expect("FacetOperator should not fail");
+
+        assert_eq!(
+            facet_output.facets,
+            vec![
+                Facet {
+                    key: "modulo_3".to_string(),
+                    groups: vec![
+                        AggregateGroup {
+                            value: MetadataValue::Int(0),
+                            count: 27
+                        },
+                        AggregateGroup {
+                            value: MetadataValue::Int(1),
+                            count: 27
+                        },
+                        AggregateGroup {
+                            value: MetadataValue::Int(2),
+                            count: 26
+                        },
+                    ]
+                },
+                Facet {
+                    key: "is_even".to_string(),
+                    groups: vec![
+                        AggregateGroup {
+                            value: MetadataValue::Bool(false),
+                            count: 40
+                        },
+                        AggregateGroup {
+                            value: MetadataValue::Bool(true),
+                            count: 40
+                        },
+                    ]
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_filtered_facet_with_limit() {
+        let facet_input = setup_facet_input(
+            SignedRoaringBitmap::Include((51..=60).collect()),
+            SignedRoaringBitmap::Exclude((21..=40).collect()),
+        )
+        .await;
+
+        let facet_operator = FacetOperator {
+            keys: vec!["is_even".to_string(), "missing".to_string()],
+            limit: Some(1),
+        };
+
+        let facet_output = facet_operator
+            .run(&facet_input)
+            .await
+            .expect("FacetOperator should not fail");
+
+        assert_eq!(
+            facet_output.facets,
+            vec![
+                Facet {
+                    key: "is_even".to_string(),
+                    groups: vec![AggregateGroup {
+                        value: MetadataValue::Bool(false),
+                        count: 10
+                    }],
+                },
+                Facet {
+                    key: "missing".to_string(),
+                    groups: Vec::new(),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,554 @@
+use std::{collections::HashMap, io::Cursor, sync::Arc};
+
+use arrow::{
+    array::{Array, AsArray},
+    datatypes::Float32Type,
+    error::ArrowError,
+    ipc::reader::FileReader,
+    record_batch::RecordBatch,
+};
+use chroma_error::{ChromaError, ErrorCodes};
+use chroma_storage::{GetError, Storage};
+use chroma_types::{Chunk, EmbeddingReference, LogRecord, ScalarEncoding};
+use futures::{StreamExt, TryStreamExt};
+use thiserror::Error;
+use tonic::async_trait;
+use tracing::trace;
+
+use crate::execution::operator::{Operator, OperatorType};
+
+// The number of referenced objects that are held in memory at once
+const MAX_CONCURRENT_FETCHES: usize = 8;
+
+const NPY_MAGIC: &[u8] = b"\x93NUMPY";
+
+// The prefixes of the keys that the worker writes, which a reference may never read even if
+// the external bucket is misconfigured to be the bucket of the worker
+const INTERNAL_KEY_PREFIXES: [&str; 4] = ["block/", "sparse_index/", "hnsw/", "warm_up/"];
+
+/// The `FetchEmbeddingsOperator` resolves the embeddings of the records that reference an
+/// object in the storage instead of carrying their embedding in the log
+///
+/// # Inputs
+/// - `logs`: The logs, some of which may reference their embeddings
+/// - `external_storage`: The external bucket that the referenced objects are read from, if
+///   the worker allows references
+///
+/// # Outputs
+/// - The logs with the referenced embeddings resolved into their `embedding`
+///
+/// # Usage
+/// A reference is a uri of the form `s3://<bucket>/<key>` into the external bucket, together
+/// with a row and the expected dimension. The object is either a 2-dimensional little endian
+/// float32 `.npy` array, or an Arrow IPC file whose first column holds the embeddings as lists
+/// of float32. Each object is fetched once for all of its rows
+#[derive(Clone, Debug)]
+pub struct FetchEmbeddingsOperator {}
+
+/// The bucket that the embeddings referenced by the logs are read from. It is dedicated to
+/// the references and is read with credentials of its own, so that a reference never reads
+/// the blocks or the indexes that the worker writes
+#[derive(Clone, Debug)]
+pub struct ExternalEmbeddingStorage {
+    pub bucket: String,
+    pub storage: Storage,
+}
+
+#[derive(Clone, Debug)]
+pub struct FetchEmbeddingsInput {
+    pub logs: Chunk<LogRecord>,
+    pub external_storage: Option<ExternalEmbeddingStorage>,
+}
+
+pub type FetchEmbeddingsOutput = Chunk<LogRecord>;
+
+#[derive(Error, Debug)]
+pub enum FetchEmbeddingsError {
+    #[error("Invalid uri of referenced embeddings: {0}")]
+    InvalidUri(String),
+    #[error("Referenced embeddings are not allowed outside of the external bucket: {0}")]
+    ForbiddenUri(String),
+    #[error("Referenced embeddings are not enabled on this worker")]
+    NotEnabled,
+    #[error("Error reading referenced embeddings {0}: {1}")]
+    Storage(String, GetError),
+    #[error("Invalid object of referenced embeddings {0}: {1}")]
+    InvalidObject(String, String),
+    #[error("Row {row} is out of range of the {rows} embeddings in {uri}")]
+    RowOutOfRange { uri: String, row: u32, rows: usize },
+    #[error("The embeddings in {uri} have dimension {actual} instead of {expected}")]
+    DimensionMismatch {
+        uri: String,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+impl ChromaError for FetchEmbeddingsError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            FetchEmbeddingsError::InvalidUri(_) => ErrorCodes::InvalidArgument,
+            FetchEmbeddingsError::ForbiddenUri(_) => ErrorCodes::PermissionDenied,
+            FetchEmbeddingsError::NotEnabled => ErrorCodes::FailedPrecondition,
+            FetchEmbeddingsError::Storage(_, e) => e.code(),
+            FetchEmbeddingsError::InvalidObject(_, _) => ErrorCodes::InvalidArgument,
+            FetchEmbeddingsError::RowOutOfRange { .. } => ErrorCodes::InvalidArgument,
+            FetchEmbeddingsError::DimensionMismatch { .. } => ErrorCodes::InvalidArgument,
+        }
+    }
+}
+
+/// Returns whether any of the logs references its embedding
+pub(crate) fn has_embedding_references(logs: &Chunk<LogRecord>) -> bool {
+    logs.iter()
+        .any(|(log, _)| log.record.embedding_reference.is_some())
+}
+
+// The embeddings of an object, decoded once for all the rows that reference it
+enum EmbeddingObject {
+    Npy {
+        bytes: Arc<Vec<u8>>,
+        data_offset: usize,
+        rows: usize,
+        dimension: usize,
+    },
+    Arrow(Vec<RecordBatch>),
+}
+
+impl EmbeddingObject {
+    fn decode(uri: &str, bytes: Arc<Vec<u8>>) -> Result<Self, FetchEmbeddingsError> {
+        let invalid = |reason: String| FetchEmbeddingsError::InvalidObject(uri.to_string(), reason);
+        if bytes.starts_with(NPY_MAGIC) {
+            let (data_offset, rows, dimension) = parse_npy_header(&bytes).map_err(invalid)?;
+            if bytes.len() < data_offset + rows * dimension * 4 {
+                return Err(invalid("the array is truncated".to_string()));
+            }
+            Ok(EmbeddingObject::Npy {
+                bytes,
+                data_offset,
+                rows,
+                dimension,
+            })
+        } else {
+            FileReader::try_new(Cursor::new(bytes.as_slice()), None)
+                .and_then(|reader| reader.collect::<Result<Vec<_>, ArrowError>>())
+                .map(EmbeddingObject::Arrow)
+                .map_err(|e| invalid(e.to_string()))
+        }
+    }
+
+    fn embedding(&self, uri: &str, row: u32) -> Result<Vec<f32>, FetchEmbeddingsError> {
+        let out_of_range = |rows| FetchEmbeddingsError::RowOutOfRange {
+            uri: uri.to_string(),
+            row,
+            rows,
+        };
+        match self {
+            EmbeddingObject::Npy {
+                bytes,
+                data_offset,
+                rows,
+                dimension,
+            } => {
+                let row = row as usize;
+                if row >= *rows {
+                    return Err(out_of_range(*rows));
+                }
+                let start = data_offset + row * dimension * 4;
+                Ok(bytes[start..start + dimension * 4]
+                    .chunks_exact(4)
+                    .map(|value| {
+                        f32::from_le_bytes(value.try_into().expect("Chunks are of 4 bytes"))
+                    })
+                    .collect())
+            }
+            EmbeddingObject::Arrow(batches) => {
+                let mut index = row as usize;
+                for batch in batches {
+                    if index >= batch.num_rows() {
+                        index -= batch.num_rows();
+                        continue;
+                    }
+                    let column = batch.columns().first().ok_or_else(|| {
+                        FetchEmbeddingsError::InvalidObject(
+                            uri.to_string(),
+                            "the file has no columns".to_string(),
+                        )
+                    })?;
+                    let embedding = match column.as_fixed_size_list_opt() {
+                        Some(list) => list.value(index),
+                        None => match column.as_list_opt::<i32>() {
+                            Some(list) => list.value(index),
+                            None => {
+                                return Err(FetchEmbeddingsError::InvalidObject(
+                                    uri.to_string(),
+                                    format!("the first column is of type {}", column.data_type()),
+                                ))
+                            }
+                        },
+                    };
+                    return match embedding.as_primitive_opt::<Float32Type>() {
+                        Some(values) if values.null_count() == 0 => Ok(values.values().to_vec()),
+                        _ => Err(FetchEmbeddingsError::InvalidObject(
+                            uri.to_string(),
+                            format!("row {} is not a list of float32", row),
+                        )),
+                    };
+                }
+                Err(out_of_range(
+                    batches.iter().map(RecordBatch::num_rows).sum::<usize>(),
+                ))
+            }
+        }
+    }
+}
+
+// Parses the header of a .npy array, and returns the offset of its data together with its
+// number of rows and its dimension
+fn parse_npy_header(bytes: &[u8]) -> Result<(usize, usize, usize), String> {
+    let major_version = *bytes.get(NPY_MAGIC.len()).ok_or("missing version")?;
+    let (header_start, header_len) = match major_version {
+        1 => (
+            10,
+            u16::from_le_bytes(
+                bytes
+                    .get(8..10)
+                    .ok_or("missing header")?
+                    .try_into()
+                    .unwrap(),
+            ) as usize,
+        ),
+        2 | 3 => (
+            12,
+            u32::from_le_bytes(
+                bytes
+                    .get(8..12)
+                    .ok_or("missing header")?
+                    .try_into()
+                    .unwrap(),
+            ) as usize,
+        ),
+        version => return Err(format!("unsupported version {}", version)),
+    };
+    let header = bytes
+        .get(header_start..header_start + header_len)
+        .and_then(|header| std::str::from_utf8(header).ok())
+        .ok_or("invalid header")?;
+    let field = |name: &str| {
+        let start = header.find(&format!("'{}':", name))? + name.len() + 3;
+        Some(header[start..].trim_start())
+    };
+    if !field("descr").is_some_and(|descr| descr.starts_with("'<f4'")) {
+        return Err("the array is not of little endian float32".to_string());
+    }
+    if !field("fortran_order").is_some_and(|order| order.starts_with("False")) {
+        return Err("the array is in fortran order".to_string());
+    }
+    let shape = field("shape")
+        .and_then(|shape| shape.strip_prefix('('))
+        .and_then(|shape| shape.split(')').next())
+        .ok_or("missing shape")?
+        .split(',')
+        .map(str::trim)
+        .filter(|dimension| !dimension.is_empty())
+        .map(str::parse::<usize>)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("invalid shape: {}", e))?;
+    match shape.as_slice() {
+        [rows, dimension] => Ok((header_start + header_len, *rows, *dimension)),
+        _ => Err(format!(
+            "the array has shape {:?} instead of 2 dimensions",
+            shape
+        )),
+    }
+}
+
+// The key of the object in the external bucket. The uri must name the external bucket, and
+// its key may neither escape the bucket nor point at the objects that the worker writes
+fn storage_key<'a>(uri: &'a str, bucket: &str) -> Result<&'a str, FetchEmbeddingsError> {
+    let (uri_bucket, key) = uri
+        .strip_prefix("s3://")
+        .and_then(|path| path.split_once('/'))
+        .filter(|(_, key)| !key.is_empty())
+        .ok_or_else(|| FetchEmbeddingsError::InvalidUri(uri.to_string()))?;
+    if uri_bucket != bucket
+        || key.starts_with('/')
+        || key.split('/').any(|segment| segment == "..")
+        || INTERNAL_KEY_PREFIXES
+            .iter()
+            .any(|prefix| key.starts_with(prefix))
+    {
+        return Err(FetchEmbeddingsError::ForbiddenUri(uri.to_string()));
+    }
+    Ok(key)
+}
+
+#[async_trait]
+impl Operator<FetchEmbeddingsInput, FetchEmbeddingsOutput> for FetchEmbeddingsOperator {
+    type Error = FetchEmbeddingsError;
+
+    fn get_type(&self) -> OperatorType {
+        OperatorType::IO
+    }
+
+    async fn run(
+        &self,
+        input: &FetchEmbeddingsInput,
+    ) -> Result<FetchEmbeddingsOutput, FetchEmbeddingsError> {
+        trace!("[{}]: {:?}", self.get_name(), input.logs.len());
+
+        // The references of each object, by the index of their log
+        let mut references = HashMap::<&str, Vec<(usize, &EmbeddingReference)>>::new();
+        for (log, index) in input.logs.iter() {
+            if let Some(reference) = log.record.embedding_reference.as_ref() {
+                references
+                    .entry(reference.uri.as_str())
+                    .or_default()
+                    .push((index, reference));
+            }
+        }
+
+        if references.is_empty() {
+            return Ok(input.logs.clone());
+        }
+        let external_storage = input
+            .external_storage
+            .as_ref()
+            .ok_or(FetchEmbeddingsError::NotEnabled)?;
+
+        // The objects are fetched concurrently and dropped once their rows are resolved. The
+        // fetches are created upfront so that the stream does not hold a closure over borrowed
+        // references, which would keep the future of the operator from being Send
+        let fetches = references
+            .into_iter()
+            .map(|(uri, references)| async move {
+                let key = storage_key(uri, &external_storage.bucket)?;
+                let bytes = external_storage
+                    .storage
+                    .get(key)
+                    .await
+                    .map_err(|e| FetchEmbeddingsError::Storage(uri.to_string(), e))?;
+                let object = EmbeddingObject::decode(uri, bytes)?;
+                references
+                    .into_iter()
+                    .map(|(index, reference)| {
+                        let embedding = object.embedding(uri, reference.row)?;
+                        if embedding.len() != reference.dimension {
+                            return Err(FetchEmbeddingsError::DimensionMismatch {
+                                uri: uri.to_string(),
+                                expected: reference.dimension,
+                                actual: embedding.len(),
+                            });
+                        }
+                        Ok((index, embedding))
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Vec<_>>();
+        let resolved = futures::stream::iter(fetches)
+            .buffer_unordered(MAX_CONCURRENT_FETCHES)
+            .try_concat()
+            .await?;
+
+        let mut logs = (0..input.logs.total_len())
+            .filter_map(|index| input.logs.get(index).cloned())
+            .collect::<Vec<_>>();
+        for (index, embedding) in resolved {
+            let record = &mut logs[index].record;
+            record.embedding = Some(embedding);
+            record.encoding = Some(ScalarEncoding::FLOAT32);
+            record.embedding_reference = None;
+        }
+        let mut output = Chunk::new(logs.into());
+        output.set_visibility(
+            (0..input.logs.total_len())
+                .map(|index| input.logs.get_visibility(index).unwrap_or(false))
+                .collect(),
+        );
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::{
+        array::{Float32Builder, ListBuilder},
+        datatypes::{DataType, Field, Schema},
+        ipc::writer::FileWriter,
+        record_batch::RecordBatch,
+    };
+    use chroma_storage::test_storage;
+    use chroma_types::{Chunk, EmbeddingReference, LogRecord, Operation, OperationRecord};
+
+    use crate::execution::operator::Operator;
+
+    use super::{
+        ExternalEmbeddingStorage, FetchEmbeddingsError, FetchEmbeddingsInput,
+        FetchEmbeddingsOperator,
+    };
+
+    fn npy(embeddings: &[[f32; 3]]) -> Vec<u8> {
+        let mut header = format!(
+            "{{'descr': '<f4', 'fortran_order': False, 'shape': ({}, 3), }}",
+            embeddings.len()
+        );
+        // The header is padded with spaces and a newline to a multiple of 64 bytes
+        while (10 + header.len() + 1) % 64 != 0 {
+            header.push(' ');
+        }
+        header.push('\n');
+        let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+        bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(header.as_bytes());
+        for value in embeddings.iter().flatten() {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        bytes
+    }
+
+    fn arrow_ipc(embeddings: &[[f32; 3]]) -> Vec<u8> {
+        let mut builder = ListBuilder::new(Float32Builder::new());
+        for embedding in embeddings {
+            builder.values().append_slice(embedding);
+            builder.append(true);
+        }
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "embedding",
+            DataType::List(Arc::new(Field::new("item", DataType::Float32, true))),
+            true,
+        )]));
+        let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(builder.finish())]).unwrap();
+        let mut bytes = Vec::new();
+        let mut writer = FileWriter::try_new(&mut bytes, &schema).unwrap();
+        writer.write(&batch).unwrap();
+        writer.finish().unwrap();
+        drop(writer);
+        bytes
+    }
+
+    fn log(offset: i64, uri: &str, row: u32, dimension: usize) -> LogRecord {
+        LogRecord {
+            log_offset: offset,
+            record: OperationRecord {
+                id: format!("id_{}", offset),
+                embedding: None,
+                encoding: None,
+                metadata: None,
+                document: None,
+                uri: None,
+                idempotency_key: None,
+                embedding_reference: Some(EmbeddingReference {
+                    uri: uri.to_string(),
+                    row,
+                    dimension,
+                }),
+                operation: Operation::Add,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_embeddings() {
+        let storage = test_storage();
+        let embeddings = [[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]];
+        storage
+            .put_bytes("batch/vectors.npy", npy(&embeddings))
+            .await
+            .unwrap();
+        storage
+            .put_bytes("batch/vectors.arrow", arrow_ipc(&embeddings))
+            .await
+            .unwrap();
+
+        let logs = Chunk::new(
+            vec![
+                log(1, "s3://bucket/batch/vectors.npy", 2, 3),
+                log(2, "s3://bucket/batch/vectors.arrow", 1, 3),
+                log(3, "s3://bucket/batch/vectors.npy", 0, 3),
+            ]
+            .into(),
+        );
+        let external_storage = Some(ExternalEmbeddingStorage {
+            bucket: "bucket".to_string(),
+            storage: storage.clone(),
+        });
+        let output = FetchEmbeddingsOperator {}
+            .run(&FetchEmbeddingsInput {
+                logs,
+                external_storage: external_storage.clone(),
+            })
+            .await
+            .unwrap();
+        let resolved = output
+            .iter()
+            .map(|(log, _)| {
+                assert!(log.record.embedding_reference.is_none());
+                log.record.embedding.clone().unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            resolved,
+            vec![
+                embeddings[2].to_vec(),
+                embeddings[1].to_vec(),
+                embeddings[0].to_vec()
+            ]
+        );
+
+        // The references are validated against the object
+        for (reference, check) in [
+            (
+                log(1, "s3://bucket/batch/vectors.npy", 3, 3),
+                (|e: &FetchEmbeddingsError| matches!(e, FetchEmbeddingsError::RowOutOfRange { .. }))
+                    as fn(&FetchEmbeddingsError) -> bool,
+            ),
+            (log(1, "s3://bucket/batch/vectors.arrow", 0, 4), |e| {
+                matches!(e, FetchEmbeddingsError::DimensionMismatch { .. })
+            }),
+            (log(1, "s3://bucket/batch/missing.npy", 0, 3), |e| {
+                matches!(e, FetchEmbeddingsError::Storage(_, _))
+            }),
+            (log(1, "file:///vectors.npy", 0, 3), |e| {
+                matches!(e, FetchEmbeddingsError::InvalidUri(_))
+            }),
+            // The references may only read the external bucket, outside of the objects that
+            // the worker writes
+            (log(1, "s3://other/batch/vectors.npy", 0, 3), |e| {
+                matches!(e, FetchEmbeddingsError::ForbiddenUri(_))
+            }),
+            (log(1, "s3://bucket/block/00000000", 0, 3), |e| {
+                matches!(e, FetchEmbeddingsError::ForbiddenUri(_))
+            }),
+            (log(1, "s3://bucket/sparse_index/00000000", 0, 3), |e| {
+                matches!(e, FetchEmbeddingsError::ForbiddenUri(_))
+            }),
+            (log(1, "s3://bucket/hnsw/00000000/header.bin", 0, 3), |e| {
+                matches!(e, FetchEmbeddingsError::ForbiddenUri(_))
+            }),
+            (log(1, "s3://bucket/batch/../block/00000000", 0, 3), |e| {
+                matches!(e, FetchEmbeddingsError::ForbiddenUri(_))
+            }),
+        ] {
+            let error = FetchEmbeddingsOperator {}
+                .run(&FetchEmbeddingsInput {
+                    logs: Chunk::new(vec![reference].into()),
+                    external_storage: external_storage.clone(),
+                })
+                .await
+                .unwrap_err();
+            assert!(check(&error), "Unexpected error: {:?}", error);
+        }
+
+        // The references are rejected if the worker has no external bucket
+        let error = FetchEmbeddingsOperator {}
+            .run(&FetchEmbeddingsInput {
+                logs: Chunk::new(vec![log(1, "s3://bucket/batch/vectors.npy", 0, 3)].into()),
+                external_storage: None,
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(error, FetchEmbeddingsError::NotEnabled));
+    }
+}
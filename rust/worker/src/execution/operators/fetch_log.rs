@@ -2,12 +2,16 @@ use std::time::{SystemTime, SystemTimeError, UNIX_EPOCH};
 
 use chroma_error::{ChromaError, ErrorCodes};
 use chroma_types::{Chunk, CollectionUuid, LogRecord};
+use futures::{future, stream::BoxStream, StreamExt, TryStreamExt};
 use thiserror::Error;
 use tonic::async_trait;
 use tracing::trace;
 
 use crate::{
-    execution::operator::{Operator, OperatorType},
+    execution::{
+        memory::{MemoryContext, MemoryError},
+        operator::{Operator, OperatorType},
+    },
     log::log::{Log, PullLogsError},
 };
 
@@ -18,10 +22,12 @@ use crate::{
 /// - `batch_size`: The maximum number of logs to fetch by `log_client` at a time
 /// - `start_log_offset_id`: The offset id of the first log to read
 /// - `maximum_fetch_count`: The maximum number of logs to fetch in total
+/// - `maximum_backlog`: The maximum number of logs that the collection may have past
+///   `start_log_offset_id`, beyond which the logs are not fetched and the operator fails
 /// - `collection_uuid`: The uuid of the collection where the fetched logs should belong
 ///
 /// # Inputs
-/// - No input is required
+/// - `memory`: The memory of the query, which the fetched logs are accounted to
 ///
 /// # Outputs
 /// - The contiguous chunk of logs belong to the collection with `collection_uuid`
@@ -29,26 +35,40 @@ use crate::{
 ///   will be fetched
 ///
 /// # Usage
-/// It should be run at the start of an orchestrator to get the latest data of a collection
+/// It should be run at the start of an orchestrator to get the latest data of a collection.
+/// The logs are fetched in batches of `batch_size` logs, and the fetch fails as soon as the
+/// logs fetched so far exceed the budget of the query, rather than once the whole log is held.
+/// A consumer that processes the logs batch by batch uses `stream` instead
 #[derive(Clone, Debug)]
 pub struct FetchLogOperator {
     pub(crate) log_client: Box<Log>,
     pub batch_size: u32,
     pub start_log_offset_id: u32,
     pub maximum_fetch_count: Option<u32>,
+    pub maximum_backlog: Option<u32>,
     pub collection_uuid: CollectionUuid,
 }
 
-type FetchLogInput = ();
+#[derive(Clone, Debug, Default)]
+pub struct FetchLogInput {
+    pub memory: MemoryContext,
+}
 
 pub type FetchLogOutput = Chunk<LogRecord>;
 
+/// The logs fetched by `FetchLogOperator::stream`, in batches of at most `batch_size` logs
+pub type FetchLogStream = BoxStream<'static, Result<FetchLogOutput, FetchLogError>>;
+
 #[derive(Error, Debug)]
 pub enum FetchLogError {
     #[error("Error when pulling log: {0}")]
     PullLog(#[from] PullLogsError),
     #[error("Error when capturing system time: {0}")]
     SystemTime(#[from] SystemTimeError),
+    #[error("The log of the collection has more than {0} records that are not compacted")]
+    Stale(u32),
+    #[error("Error accounting for the fetched logs: {0}")]
+    Memory(#[from] MemoryError),
 }
 
 impl ChromaError for FetchLogError {
@@ -56,10 +76,124 @@ impl ChromaError for FetchLogError {
         match self {
             FetchLogError::PullLog(e) => e.code(),
             FetchLogError::SystemTime(_) => ErrorCodes::Internal,
+            FetchLogError::Stale(_) => ErrorCodes::FailedPrecondition,
+            FetchLogError::Memory(e) => e.code(),
+        }
+    }
+}
+
+// The state of the batches that are fetched so far
+struct FetchLogState {
+    log_client: Box<Log>,
+    offset: i64,
+    timestamp: Option<i64>,
+    num_fetched: usize,
+    exhausted: bool,
+}
+
+impl FetchLogOperator {
+    /// The offset of the last of the fetched logs, or of the log before `start_log_offset_id`
+    /// if none are fetched. A read at a later log offset that fetches up to it reads the same
+    /// logs
+    pub fn log_offset(&self, logs: &FetchLogOutput) -> i64 {
+        logs.iter()
+            .map(|(log, _)| log.log_offset)
+            .fold(self.start_log_offset_id as i64 - 1, i64::max)
+    }
+
+    /// Fetches the logs in batches of at most `batch_size` logs, which are accounted to the
+    /// memory of the query as they arrive. A batch is only fetched once the stream is polled
+    /// for it, so a slow consumer holds back the fetch instead of the logs piling up in memory
+    pub fn stream(&self, memory: MemoryContext) -> FetchLogStream {
+        self.batches()
+            .and_then(move |log_batch| {
+                future::ready(reserve(&memory, &log_batch).map(|_| Chunk::new(log_batch.into())))
+            })
+            .boxed()
+    }
+
+    // Fetches the logs in batches of at most `batch_size` logs, each once the stream is polled
+    // for it
+    fn batches(&self) -> BoxStream<'static, Result<Vec<LogRecord>, FetchLogError>> {
+        let operator = self.clone();
+        let state = FetchLogState {
+            log_client: self.log_client.clone(),
+            offset: self.start_log_offset_id as i64,
+            timestamp: None,
+            num_fetched: 0,
+            exhausted: false,
+        };
+        futures::stream::try_unfold(state, move |state| {
+            let operator = operator.clone();
+            async move { operator.next_batch(state).await }
+        })
+        .boxed()
+    }
+
+    async fn next_batch(
+        &self,
+        mut state: FetchLogState,
+    ) -> Result<Option<(Vec<LogRecord>, FetchLogState)>, FetchLogError> {
+        if state.exhausted {
+            return Ok(None);
+        }
+        // The logs written after the first batch is fetched are left for the next query
+        let timestamp = match state.timestamp {
+            Some(timestamp) => timestamp,
+            None => *state
+                .timestamp
+                .insert(SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos() as i64),
+        };
+        let mut log_batch = state
+            .log_client
+            .read(
+                self.collection_uuid,
+                state.offset,
+                self.batch_size as i32,
+                Some(timestamp),
+            )
+            .await?;
+
+        // No more logs to fetch past a partial batch
+        state.exhausted = log_batch.len() < self.batch_size as usize;
+        if let Some(last_log) = log_batch.last() {
+            state.offset = last_log.log_offset + 1;
+        }
+        state.num_fetched += log_batch.len();
+        if let Some(backlog) = self.maximum_backlog {
+            if state.num_fetched > backlog as usize {
+                // The logs are not fetched any further, since the query would not
+                // materialize them anyway
+                return Err(FetchLogError::Stale(backlog));
+            }
+        }
+        if let Some(limit) = self.maximum_fetch_count {
+            if state.num_fetched >= limit as usize {
+                // Enough logs have been fetched
+                let excess = state.num_fetched - limit as usize;
+                log_batch.truncate(log_batch.len() - excess);
+                state.exhausted = true;
+            }
+        }
+
+        if log_batch.is_empty() {
+            return Ok(None);
         }
+        Ok(Some((log_batch, state)))
     }
 }
 
+// Accounts for a batch of fetched logs to the memory of the query
+fn reserve(memory: &MemoryContext, log_batch: &[LogRecord]) -> Result<(), FetchLogError> {
+    memory.reserve(
+        log_batch
+            .iter()
+            .map(|log| log.record.get_size())
+            .sum::<usize>(),
+    )?;
+    Ok(())
+}
+
 #[async_trait]
 impl Operator<FetchLogInput, FetchLogOutput> for FetchLogOperator {
     type Error = FetchLogError;
@@ -68,53 +202,35 @@ impl Operator<FetchLogInput, FetchLogOutput> for FetchLogOperator {
         OperatorType::IO
     }
 
-    async fn run(&self, _: &FetchLogInput) -> Result<FetchLogOutput, FetchLogError> {
+    async fn run(&self, input: &FetchLogInput) -> Result<FetchLogOutput, FetchLogError> {
         trace!("[{}]: {:?}", self.get_name(), self);
 
         let mut fetched = Vec::new();
-        let mut log_client = self.log_client.clone();
-        let mut offset = self.start_log_offset_id as i64;
-        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos() as i64;
-        loop {
-            let mut log_batch = log_client
-                .read(
-                    self.collection_uuid,
-                    offset,
-                    self.batch_size as i32,
-                    Some(timestamp),
-                )
-                .await?;
-
-            let retrieve_count = log_batch.len();
-
-            if let Some(last_log) = log_batch.last() {
-                offset = last_log.log_offset + 1;
-                fetched.append(&mut log_batch);
-                if let Some(limit) = self.maximum_fetch_count {
-                    if fetched.len() >= limit as usize {
-                        // Enough logs have been fetched
-                        fetched.truncate(limit as usize);
-                        break;
-                    }
-                }
-            }
-
-            if retrieve_count < self.batch_size as usize {
-                // No more logs to fetch
-                break;
-            }
+        let mut batches = self.batches();
+        while let Some(mut log_batch) = batches.try_next().await? {
+            reserve(&input.memory, &log_batch)?;
+            fetched.append(&mut log_batch);
         }
         tracing::info!(name: "Fetched log records", num_records = fetched.len());
         Ok(Chunk::new(fetched.into()))
     }
+
+    fn can_retry(&self, error: &FetchLogError) -> bool {
+        error.is_transient()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use chroma_types::CollectionUuid;
+    use futures::TryStreamExt;
 
     use crate::{
-        execution::{operator::Operator, operators::fetch_log::FetchLogOperator},
+        execution::{
+            memory::{MemoryContext, MemoryError},
+            operator::Operator,
+            operators::fetch_log::{FetchLogError, FetchLogInput, FetchLogOperator},
+        },
         log::{
             log::{InMemoryLog, InternalLogRecord},
             test::{upsert_generator, LogGenerator},
@@ -152,11 +268,12 @@ mod tests {
             batch_size: 2,
             start_log_offset_id: 0,
             maximum_fetch_count: None,
+            maximum_backlog: None,
             collection_uuid,
         };
 
         let logs = fetch_log_operator
-            .run(&())
+            .run(&FetchLogInput::default())
             .await
             .expect("Fetch log operator should not fail");
 
@@ -176,11 +293,12 @@ mod tests {
             batch_size: 2,
             start_log_offset_id: 3,
             maximum_fetch_count: Some(3),
+            maximum_backlog: None,
             collection_uuid,
         };
 
         let logs = fetch_log_operator
-            .run(&())
+            .run(&FetchLogInput::default())
             .await
             .expect("FetchLogOperator should not fail");
 
@@ -190,4 +308,124 @@ mod tests {
             .zip(3..6)
             .for_each(|(log, offset)| assert_eq!(log.log_offset, offset));
     }
+
+    #[tokio::test]
+    async fn test_pull_up_to_log_offset() {
+        let (collection_uuid, log_client) = setup_in_memory_log();
+
+        // The logs after the log offset that a read ended at are left out when it is repeated
+        let fetch_log_operator = FetchLogOperator {
+            log_client: log_client.clone(),
+            batch_size: 2,
+            start_log_offset_id: 3,
+            maximum_fetch_count: Some(6 - 2),
+            maximum_backlog: None,
+            collection_uuid,
+        };
+        let logs = fetch_log_operator
+            .run(&FetchLogInput::default())
+            .await
+            .expect("FetchLogOperator should not fail");
+        assert_eq!(logs.len(), 4);
+        assert_eq!(fetch_log_operator.log_offset(&logs), 6);
+
+        // Without logs the read ends at the log before the first one it reads
+        let fetch_log_operator = FetchLogOperator {
+            start_log_offset_id: 10,
+            maximum_fetch_count: None,
+            ..fetch_log_operator
+        };
+        let logs = fetch_log_operator
+            .run(&FetchLogInput::default())
+            .await
+            .expect("FetchLogOperator should not fail");
+        assert_eq!(logs.len(), 0);
+        assert_eq!(fetch_log_operator.log_offset(&logs), 9);
+    }
+
+    #[tokio::test]
+    async fn test_pull_stale() {
+        let (collection_uuid, log_client) = setup_in_memory_log();
+
+        let fetch_log_operator = FetchLogOperator {
+            log_client: log_client.clone(),
+            batch_size: 2,
+            start_log_offset_id: 0,
+            maximum_fetch_count: None,
+            maximum_backlog: Some(9),
+            collection_uuid,
+        };
+        assert!(matches!(
+            fetch_log_operator.run(&FetchLogInput::default()).await,
+            Err(FetchLogError::Stale(9))
+        ));
+
+        // The backlog is within the maximum once the earlier logs are compacted
+        let fetch_log_operator = FetchLogOperator {
+            log_client,
+            batch_size: 2,
+            start_log_offset_id: 1,
+            maximum_fetch_count: None,
+            maximum_backlog: Some(9),
+            collection_uuid,
+        };
+        let logs = fetch_log_operator
+            .run(&FetchLogInput::default())
+            .await
+            .expect("FetchLogOperator should not fail");
+        assert_eq!(logs.len(), 9);
+    }
+
+    #[tokio::test]
+    async fn test_pull_stream() {
+        let (collection_uuid, log_client) = setup_in_memory_log();
+
+        let fetch_log_operator = FetchLogOperator {
+            log_client,
+            batch_size: 2,
+            start_log_offset_id: 3,
+            maximum_fetch_count: Some(5),
+            maximum_backlog: None,
+            collection_uuid,
+        };
+
+        // The logs are fetched in batches of the batch size, cut short at the fetch count
+        let batches = fetch_log_operator
+            .stream(MemoryContext::default())
+            .try_collect::<Vec<_>>()
+            .await
+            .expect("FetchLogOperator should not fail");
+        assert_eq!(
+            batches.iter().map(|batch| batch.len()).collect::<Vec<_>>(),
+            vec![2, 2, 1]
+        );
+        let log_offsets = batches
+            .iter()
+            .flat_map(|batch| batch.iter().map(|(log, _)| log.log_offset))
+            .collect::<Vec<_>>();
+        assert_eq!(log_offsets, (3..8).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_pull_over_budget() {
+        let (collection_uuid, log_client) = setup_in_memory_log();
+
+        let fetch_log_operator = FetchLogOperator {
+            log_client,
+            batch_size: 2,
+            start_log_offset_id: 0,
+            maximum_fetch_count: None,
+            maximum_backlog: None,
+            collection_uuid,
+        };
+        let input = FetchLogInput {
+            memory: MemoryContext::with_budget(1),
+        };
+        assert!(matches!(
+            fetch_log_operator.run(&input).await,
+            Err(FetchLogError::Memory(
+                MemoryError::QueryBudgetExceeded { .. }
+            ))
+        ));
+    }
 }
@@ -6,6 +6,7 @@ use tracing::trace;
 
 use crate::{
     execution::operator::{Operator, OperatorType},
+    segment::cache_invalidation::SegmentCacheInvalidator,
     sysdb::sysdb::{GetCollectionsError, GetSegmentsError, SysDb},
 };
 
@@ -15,16 +16,20 @@ use crate::{
 /// - `sysdb`: The SysDB reader
 /// - `*_uuid`: The uuids of the collection and segments
 /// - `collection_version`: The version of the collection to verify against
+/// - `cache_invalidator`: If provided, evicts the cache entries of superseded segment files
+///   once a newer version of the collection is observed
 ///
 /// # Inputs
 /// - No input is required
 ///
 /// # Outputs
 /// - `collection`: The collection information
-/// - `*_segment`: The segment information
+/// - `*_segment`: The segment information, all of them at the version of the collection
 ///
 /// # Usage
-/// It should be run at the start of an orchestrator to get the latest data of a collection
+/// It should be run at the start of an orchestrator to get the latest data of a collection.
+/// The blockfiles of a segment version are immutable, so the readers of a query plan should
+/// all be created from this output to read a consistent snapshot of the collection
 #[derive(Clone, Debug)]
 pub struct FetchSegmentOperator {
     pub(crate) sysdb: Box<SysDb>,
@@ -34,6 +39,7 @@ pub struct FetchSegmentOperator {
     pub metadata_uuid: Option<SegmentUuid>,
     pub record_uuid: Option<SegmentUuid>,
     pub vector_uuid: Option<SegmentUuid>,
+    pub(crate) cache_invalidator: Option<SegmentCacheInvalidator>,
 }
 
 type FetchSegmentInput = ();
@@ -115,6 +121,40 @@ impl FetchSegmentOperator {
             .pop()
             .ok_or(FetchSegmentError::NoSegment)
     }
+
+    /// Pins the collection and its segments at `collection_version`. The output is the
+    /// snapshot that every reader of a query plan is created from, so that the record,
+    /// metadata and vector readers never observe different versions of the collection
+    pub(crate) async fn fetch(&self) -> Result<FetchSegmentOutput, FetchSegmentError> {
+        // The collection and its segments are read with separate calls. The segments are read
+        // first: the version of a collection never decreases and the request was made at
+        // `collection_version`, so if the collection is still at that version afterwards the
+        // segments were read at that version as well
+        let metadata_segment = self.get_segment(SegmentScope::METADATA).await?;
+        let record_segment = self.get_segment(SegmentScope::RECORD).await?;
+        let vector_segment = self.get_segment(SegmentScope::VECTOR).await?;
+        let output = FetchSegmentOutput {
+            collection: self.get_collection().await?,
+            metadata_segment,
+            record_segment,
+            vector_segment,
+        };
+
+        if let Some(cache_invalidator) = self.cache_invalidator.as_ref() {
+            cache_invalidator
+                .observe(
+                    &output.collection,
+                    &[
+                        &output.metadata_segment,
+                        &output.record_segment,
+                        &output.vector_segment,
+                    ],
+                )
+                .await;
+        }
+
+        Ok(output)
+    }
 }
 
 #[async_trait]
@@ -127,12 +167,80 @@ impl Operator<FetchSegmentInput, FetchSegmentOutput> for FetchSegmentOperator {
 
     async fn run(&self, _: &FetchSegmentInput) -> Result<FetchSegmentOutput, FetchSegmentError> {
         trace!("[{}]: {:?}", self.get_name(), self);
+        self.fetch().await
+    }
 
-        Ok(FetchSegmentOutput {
-            collection: self.get_collection().await?,
-            metadata_segment: self.get_segment(SegmentScope::METADATA).await?,
-            record_segment: self.get_segment(SegmentScope::RECORD).await?,
-            vector_segment: self.get_segment(SegmentScope::VECTOR).await?,
-        })
+    fn can_retry(&self, error: &FetchSegmentError) -> bool {
+        error.is_transient()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chroma_error::{ChromaError, ErrorCodes};
+    use chroma_types::{
+        Collection, CollectionUuid, Segment, SegmentScope, SegmentType, SegmentUuid,
+    };
+
+    use crate::sysdb::{sysdb::SysDb, test_sysdb::TestSysDb};
+
+    use super::FetchSegmentOperator;
+
+    #[tokio::test]
+    async fn test_fetch_verifies_collection_version() {
+        let mut sysdb = TestSysDb::new();
+        let collection = Collection {
+            collection_id: CollectionUuid::new(),
+            name: "collection".to_string(),
+            metadata: None,
+            dimension: Some(1),
+            tenant: "tenant".to_string(),
+            database: "database".to_string(),
+            log_position: 0,
+            version: 1,
+        };
+        sysdb.add_collection(collection.clone());
+        for (r#type, scope) in [
+            (SegmentType::BlockfileMetadata, SegmentScope::METADATA),
+            (SegmentType::BlockfileRecord, SegmentScope::RECORD),
+            (SegmentType::HnswDistributed, SegmentScope::VECTOR),
+        ] {
+            sysdb.add_segment(Segment {
+                id: SegmentUuid::new(),
+                r#type,
+                scope,
+                collection: collection.collection_id,
+                metadata: None,
+                file_path: HashMap::new(),
+            });
+        }
+
+        let fetch_segment_operator = |collection_version| FetchSegmentOperator {
+            sysdb: Box::new(SysDb::Test(sysdb.clone())),
+            collection_uuid: collection.collection_id,
+            collection_version,
+            metadata_uuid: None,
+            record_uuid: None,
+            vector_uuid: None,
+            cache_invalidator: None,
+        };
+
+        let output = fetch_segment_operator(1)
+            .fetch()
+            .await
+            .expect("The segments should be fetched");
+        assert_eq!(output.collection.version, 1);
+        assert_eq!(output.record_segment.scope, SegmentScope::RECORD);
+        // A compaction registered a newer version after the request was made
+        assert_eq!(
+            fetch_segment_operator(0)
+                .fetch()
+                .await
+                .expect_err("The version should not match")
+                .code(),
+            ErrorCodes::VersionMismatch
+        );
     }
 }
@@ -1,27 +1,47 @@
 use std::{
-    collections::{BTreeMap, HashMap},
-    ops::{BitAnd, BitOr, Bound},
+    borrow::Cow,
+    collections::{BTreeMap, HashMap, HashSet},
+    ops::{BitOr, Bound},
 };
 
-use chroma_blockstore::provider::BlockfileProvider;
-use chroma_error::{ChromaError, ErrorCodes};
-use chroma_index::metadata::types::MetadataIndexError;
+use chroma_blockstore::{key::KeyWrapper, provider::BlockfileProvider};
+use chroma_error::{ChromaError, ErrorCodes, ErrorLocation};
+use chroma_index::{
+    fulltext::{tokenizer::TokenizerConfig, types::document_contains_near},
+    metadata::types::MetadataIndexError,
+};
 use chroma_types::{
-    BooleanOperator, Chunk, DirectDocumentComparison, DirectWhereComparison, DocumentOperator,
-    LogRecord, MaterializedLogOperation, MetadataSetValue, MetadataValue, PrimitiveOperator,
-    Segment, SetOperator, SignedRoaringBitmap, Where, WhereChildren, WhereComparison,
+    chroma_proto, BooleanOperator, Chunk, CollectionUuid, DirectDocumentComparison,
+    DirectWhereComparison, DocumentOperator, ExistenceOperator, LogRecord,
+    MaterializedLogOperation, MetadataSetValue, MetadataValue, PrimitiveOperator,
+    RoaringBitmapConversionError, Segment, SetOperator, SignedRoaringBitmap, Where, WhereChildren,
+    WhereComparison,
 };
+use futures::{StreamExt, TryStreamExt};
+use regex::Regex;
+use regex_syntax::hir::{Hir, HirKind, Literal};
 use roaring::RoaringBitmap;
 use thiserror::Error;
 use tonic::async_trait;
 use tracing::{trace, Instrument, Span};
 
 use crate::{
-    execution::operator::Operator,
+    execution::{
+        memory::{MemoryContext, MemoryError},
+        operator::Operator,
+        operators::fetch_log::{FetchLogError, FetchLogOperator},
+    },
     segment::{
-        metadata_segment::{MetadataSegmentError, MetadataSegmentReader},
-        record_segment::{RecordSegmentReader, RecordSegmentReaderCreationError},
-        LogMaterializer, LogMaterializerError, MaterializedLogRecord,
+        metadata_segment::{
+            encode_timestamp, timestamp_metadata_prefix, tokenizer_config_from_segment,
+            MetadataSegmentError, MetadataSegmentReader,
+        },
+        offset_id_cache::OffsetIdCache,
+        record_segment::{
+            RecordScanPredicate, RecordSegmentReader, RecordSegmentReaderCreationError,
+        },
+        LogMaterializer, LogMaterializerError, MaterializedLogFields, MaterializedLogRecord,
+        SharedMaterializedLogs,
     },
 };
 
@@ -33,14 +53,26 @@ use crate::{
 ///
 /// # Inputs
 /// - `logs`: The latest log of the collection
+/// - `fetch_log`: Fetches the latest log of the collection batch by batch instead of taking it
+///   from `logs`, if set, so that the fetch is held back while the batches are materialized
 /// - `blockfile_provider`: The blockfile provider
 /// - `metadata_segment`: The metadata segment information
 /// - `record_segment`: The record segment information
+/// - `materialized_logs`: The handle that keeps the materialized logs for the later operators
+/// - `memory`: The memory context of the query, which accounts for the materialized logs and
+///   the output bitmaps
+/// - `offset_id_cache`: The cache of the offset ids that the user provided ids resolve to in the
+///   record segment
+/// - `scan_limit`: The number of matching records in the blockfile that the next operator needs,
+///   if it only needs the first ones. A where clause on a single metadata key is then evaluated
+///   by scanning the records for the first matching ones, rather than on the metadata index
 ///
 /// # Outputs
+/// - `logs`: The logs fetched by `fetch_log`, if set, without the retried and redelivered logs
 /// - `log_offset_ids`: The offset ids in the logs to include or exclude
 /// - `compact_offset_ids`: The offset ids in the blockfile to include or exclude
 ///   All offsets ids present in the logs should be excluded in `compact_offset_ids`
+///   If the records are scanned, only the first `scan_limit` matching ones are included
 ///
 /// # Usage
 /// It can be used to derive the mask of offset ids that should be included or excluded by the next operator
@@ -53,39 +85,143 @@ pub struct FilterOperator {
 #[derive(Clone, Debug)]
 pub struct FilterInput {
     pub logs: Chunk<LogRecord>,
+    pub fetch_log: Option<FetchLogOperator>,
     pub blockfile_provider: BlockfileProvider,
     pub metadata_segment: Segment,
     pub record_segment: Segment,
+    pub materialized_logs: SharedMaterializedLogs,
+    pub memory: MemoryContext,
+    pub offset_id_cache: OffsetIdCache,
+    pub scan_limit: Option<u32>,
 }
 
 #[derive(Clone, Debug)]
 pub struct FilterOutput {
+    pub logs: Option<Chunk<LogRecord>>,
     pub log_offset_ids: SignedRoaringBitmap,
     pub compact_offset_ids: SignedRoaringBitmap,
 }
 
+impl From<&FilterOutput> for chroma_proto::FilterOutput {
+    fn from(value: &FilterOutput) -> Self {
+        chroma_proto::FilterOutput {
+            log_offset_ids: Some((&value.log_offset_ids).into()),
+            compact_offset_ids: Some((&value.compact_offset_ids).into()),
+        }
+    }
+}
+
+impl TryFrom<chroma_proto::FilterOutput> for FilterOutput {
+    type Error = RoaringBitmapConversionError;
+
+    fn try_from(value: chroma_proto::FilterOutput) -> Result<Self, Self::Error> {
+        Ok(FilterOutput {
+            logs: None,
+            log_offset_ids: value
+                .log_offset_ids
+                .ok_or(RoaringBitmapConversionError::MissingBitmap)?
+                .try_into()?,
+            compact_offset_ids: value
+                .compact_offset_ids
+                .ok_or(RoaringBitmapConversionError::MissingBitmap)?
+                .try_into()?,
+        })
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum FilterError {
     #[error("Error reading metadata index: {0}")]
     Index(#[from] MetadataIndexError),
+    #[error("Error fetching log: {0}")]
+    FetchLog(#[from] FetchLogError),
     #[error("Error materializing log: {0}")]
     LogMaterializer(#[from] LogMaterializerError),
+    #[error("Error accounting for query memory: {0}")]
+    Memory(#[from] MemoryError),
     #[error("Error creating metadata segment reader: {0}")]
     MetadataReader(#[from] MetadataSegmentError),
     #[error("Error creating record segment reader: {0}")]
     RecordReader(#[from] RecordSegmentReaderCreationError),
     #[error("Error getting record: {0}")]
     GetError(Box<dyn ChromaError>),
+    #[error("Invalid regular expression `{pattern}`: {source}")]
+    Regex {
+        pattern: String,
+        source: regex::Error,
+    },
 }
 
 impl ChromaError for FilterError {
     fn code(&self) -> ErrorCodes {
         match self {
             FilterError::Index(e) => e.code(),
+            FilterError::FetchLog(e) => e.code(),
             FilterError::LogMaterializer(e) => e.code(),
+            FilterError::Memory(e) => e.code(),
             FilterError::MetadataReader(e) => e.code(),
             FilterError::RecordReader(e) => e.code(),
             FilterError::GetError(e) => e.code(),
+            FilterError::Regex { .. } => ErrorCodes::InvalidArgument,
+        }
+    }
+
+    fn field(&self) -> Option<&str> {
+        match self {
+            FilterError::GetError(e) => e.field(),
+            FilterError::Regex { .. } => Some("where_document"),
+            _ => None,
+        }
+    }
+
+    fn location(&self) -> ErrorLocation {
+        match self {
+            FilterError::RecordReader(e) => e.location(),
+            FilterError::GetError(e) => e.location(),
+            _ => ErrorLocation::default(),
+        }
+    }
+}
+
+/// The metadata keys referenced by a where clause, and whether it references the document
+#[derive(Debug, Default)]
+pub(crate) struct WhereReferences<'me> {
+    keys: HashSet<&'me str>,
+    document: bool,
+}
+
+impl<'me> WhereReferences<'me> {
+    pub(crate) fn from_where(clause: &'me Where) -> Self {
+        let mut references = Self::default();
+        references.collect(clause);
+        references
+    }
+
+    fn collect(&mut self, clause: &'me Where) {
+        match clause {
+            Where::DirectWhereComparison(direct_where_comparison) => {
+                // A dotted path may address a value nested in the object of any key before a dot
+                let key = direct_where_comparison.key.as_str();
+                self.keys.insert(key);
+                for (index, _) in key.match_indices('.') {
+                    self.keys.insert(&key[..index]);
+                }
+            }
+            Where::DirectWhereDocumentComparison(_) => self.document = true,
+            Where::WhereChildren(where_children) => {
+                for child in &where_children.children {
+                    self.collect(child);
+                }
+            }
+        }
+    }
+
+    /// The fields of the logs that have to be materialized to evaluate the clause
+    pub(crate) fn fields(&self) -> MaterializedLogFields {
+        MaterializedLogFields {
+            metadata: !self.keys.is_empty(),
+            document: self.document,
+            ..MaterializedLogFields::OFFSET_IDS
         }
     }
 }
@@ -95,17 +231,27 @@ pub(crate) struct MetadataLogReader<'me> {
     // This maps metadata keys to `BTreeMap`s, which further map values to offset ids
     // This mimics the layout in the metadata segment
     // //TODO: Maybe a sorted vector with binary search is more lightweight and performant?
-    compact_metadata: HashMap<&'me str, BTreeMap<&'me MetadataValue, RoaringBitmap>>,
+    // The values of objects are flattened under their dotted paths
+    compact_metadata: HashMap<Cow<'me, str>, BTreeMap<&'me MetadataValue, RoaringBitmap>>,
     // This maps offset ids to documents, excluding deleted ones
     document: HashMap<u32, &'me str>,
     // This contains all existing offset ids that are touched by the logs
     updated_offset_ids: RoaringBitmap,
     // This maps user ids to offset ids, excluding deleted ones
     user_id_to_offset_id: HashMap<&'me str, u32>,
+    // This normalizes the documents like the full-text index of the metadata segment
+    tokenizer_config: TokenizerConfig,
 }
 
 impl<'me> MetadataLogReader<'me> {
-    pub(crate) fn new(logs: &'me Chunk<MaterializedLogRecord<'me>>) -> Self {
+    /// Only the metadata keys and the document referenced by `references` are read from the logs,
+    /// so that the merged metadata of each record is not built in full. All metadata keys and the
+    /// documents are read if `references` is `None`.
+    pub(crate) fn new(
+        logs: &'me Chunk<MaterializedLogRecord<'me>>,
+        references: Option<&WhereReferences<'_>>,
+        tokenizer_config: &TokenizerConfig,
+    ) -> Self {
         let mut compact_metadata: HashMap<_, BTreeMap<&MetadataValue, RoaringBitmap>> =
             HashMap::new();
         let mut document = HashMap::new();
@@ -123,17 +269,31 @@ impl<'me> MetadataLogReader<'me> {
                 MaterializedLogOperation::DeleteExisting
             ) {
                 user_id_to_offset_id.insert(log.merged_user_id_ref(), log.offset_id);
-                let log_metadata = log.merged_metadata_ref();
+                let log_metadata: HashMap<&str, &MetadataValue> = match references {
+                    Some(references) => references
+                        .keys
+                        .iter()
+                        .filter_map(|key| log.merged_metadata_entry(key))
+                        .collect(),
+                    None => log.merged_metadata_ref(),
+                };
                 for (key, val) in log_metadata.into_iter() {
-                    compact_metadata
-                        .entry(key)
-                        .or_default()
-                        .entry(val)
-                        .or_default()
-                        .insert(log.offset_id);
+                    // The elements of a list are indexed separately, like in the metadata segment
+                    for (path, val) in val.flatten(key) {
+                        let metadata_value_to_offset_ids =
+                            compact_metadata.entry(path).or_default();
+                        for element in val.elements() {
+                            metadata_value_to_offset_ids
+                                .entry(element)
+                                .or_default()
+                                .insert(log.offset_id);
+                        }
+                    }
                 }
-                if let Some(doc) = log.merged_document_ref() {
-                    document.insert(log.offset_id, doc);
+                if references.is_none_or(|references| references.document) {
+                    if let Some(doc) = log.merged_document_ref() {
+                        document.insert(log.offset_id, doc);
+                    }
                 }
             }
         }
@@ -142,6 +302,7 @@ impl<'me> MetadataLogReader<'me> {
             document,
             updated_offset_ids,
             user_id_to_offset_id,
+            tokenizer_config: tokenizer_config.clone(),
         }
     }
     pub(crate) fn get(
@@ -151,18 +312,56 @@ impl<'me> MetadataLogReader<'me> {
         op: &PrimitiveOperator,
     ) -> Result<RoaringBitmap, FilterError> {
         if let Some(metadata_value_to_offset_ids) = self.compact_metadata.get(key) {
+            // Case-insensitive and prefix matching only apply to strings
+            match (op, val) {
+                (PrimitiveOperator::EqualCaseInsensitive, MetadataValue::Str(s)) => {
+                    let lowercase = s.to_lowercase();
+                    let matches_lowercase = |k: &MetadataValue| match k {
+                        MetadataValue::Str(v) => v.to_lowercase() == lowercase,
+                        _ => false,
+                    };
+                    return Ok(metadata_value_to_offset_ids
+                        .iter()
+                        .filter(|(k, _)| matches_lowercase(k))
+                        .map(|(_, v)| v)
+                        .fold(RoaringBitmap::new(), BitOr::bitor));
+                }
+                (PrimitiveOperator::StartsWith, MetadataValue::Str(s)) => {
+                    // Strings with the prefix are ordered right after the prefix itself
+                    let has_prefix = |k: &MetadataValue| match k {
+                        MetadataValue::Str(v) => v.starts_with(s.as_str()),
+                        _ => false,
+                    };
+                    return Ok(metadata_value_to_offset_ids
+                        .range::<&MetadataValue, _>((Bound::Included(&val), Bound::Unbounded))
+                        .take_while(|(k, _)| has_prefix(k))
+                        .map(|(_, v)| v)
+                        .fold(RoaringBitmap::new(), BitOr::bitor));
+                }
+                (PrimitiveOperator::EqualCaseInsensitive | PrimitiveOperator::StartsWith, _) => {
+                    return Ok(RoaringBitmap::new())
+                }
+                _ => {}
+            }
             let bounds = match op {
                 PrimitiveOperator::Equal => (Bound::Included(&val), Bound::Included(&val)),
                 PrimitiveOperator::GreaterThan => (Bound::Excluded(&val), Bound::Unbounded),
                 PrimitiveOperator::GreaterThanOrEqual => (Bound::Included(&val), Bound::Unbounded),
                 PrimitiveOperator::LessThan => (Bound::Unbounded, Bound::Excluded(&val)),
                 PrimitiveOperator::LessThanOrEqual => (Bound::Unbounded, Bound::Included(&val)),
-                PrimitiveOperator::NotEqual => unreachable!(
-                    "Inequality filter should be handled above the metadata provider level"
+                PrimitiveOperator::EqualCaseInsensitive | PrimitiveOperator::StartsWith => {
+                    unreachable!("String matching filters are handled above")
+                }
+                PrimitiveOperator::NotEqual
+                | PrimitiveOperator::Contains
+                | PrimitiveOperator::NotContains => unreachable!(
+                    "Inequality and containment filters should be handled above the metadata provider level"
                 ),
             };
+            // Only values of the same type are compared, like in the metadata segment
             Ok(metadata_value_to_offset_ids
                 .range::<&MetadataValue, _>(bounds)
+                .filter(|(k, _)| std::mem::discriminant(**k) == std::mem::discriminant(val))
                 .map(|(_, v)| v)
                 .fold(RoaringBitmap::new(), BitOr::bitor))
         } else {
@@ -170,6 +369,31 @@ impl<'me> MetadataLogReader<'me> {
         }
     }
 
+    /// Returns the offset ids that have any value for the key, excluding those whose value
+    /// was removed by the logs
+    pub(crate) fn exists(&self, key: &str) -> RoaringBitmap {
+        self.compact_metadata
+            .get(key)
+            .map(|metadata_value_to_offset_ids| {
+                metadata_value_to_offset_ids
+                    .values()
+                    .fold(RoaringBitmap::new(), BitOr::bitor)
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the offset ids whose value for the key is any of the values
+    pub(crate) fn get_any(&self, key: &str, vals: &[MetadataValue]) -> RoaringBitmap {
+        self.compact_metadata
+            .get(key)
+            .map(|metadata_value_to_offset_ids| {
+                vals.iter()
+                    .filter_map(|val| metadata_value_to_offset_ids.get(&val))
+                    .fold(RoaringBitmap::new(), BitOr::bitor)
+            })
+            .unwrap_or_default()
+    }
+
     pub(crate) fn search_user_ids(&self, user_ids: &[&str]) -> RoaringBitmap {
         user_ids
             .iter()
@@ -178,14 +402,145 @@ impl<'me> MetadataLogReader<'me> {
     }
 }
 
+// The number of records that a scan reads for each matching record it looks for, beyond which
+// the predicate is selective enough that the metadata index is used instead
+const SCANNED_RECORDS_PER_MATCH: usize = 16;
+
+// Scans the records of the blockfile for the first ones that satisfy the predicate and are not
+// updated in the logs. Returns `None` if too many records are read before finding enough of them
+async fn scan_compact_offset_ids(
+    reader: &RecordSegmentReader<'_>,
+    predicate: RecordScanPredicate,
+    updated_offset_ids: &RoaringBitmap,
+    limit: usize,
+) -> Result<Option<RoaringBitmap>, FilterError> {
+    let max_scanned = limit.saturating_mul(SCANNED_RECORDS_PER_MATCH);
+    let mut offset_ids = RoaringBitmap::new();
+    let mut scanned = Box::pin(reader.scan_offset_ids(predicate).enumerate());
+    while (offset_ids.len() as usize) < limit {
+        let Some((index, offset_id)) = scanned.next().await else {
+            break;
+        };
+        if index >= max_scanned {
+            return Ok(None);
+        }
+        match offset_id.map_err(FilterError::GetError)? {
+            Some(offset_id) if !updated_offset_ids.contains(offset_id) => {
+                offset_ids.insert(offset_id);
+            }
+            _ => {}
+        }
+    }
+    Ok(Some(offset_ids))
+}
+
+fn compile_regex(pattern: &str) -> Result<Regex, FilterError> {
+    Regex::new(pattern).map_err(|source| FilterError::Regex {
+        pattern: pattern.to_string(),
+        source,
+    })
+}
+
+/// Checks that the regular expressions in the where clause are valid, so that an invalid
+/// pattern is reported before the logs are materialized
+fn validate_regexes(clause: &Where) -> Result<(), FilterError> {
+    match clause {
+        Where::DirectWhereDocumentComparison(direct_document_comparison)
+            if matches!(
+                direct_document_comparison.operator,
+                DocumentOperator::Regex | DocumentOperator::NotRegex
+            ) =>
+        {
+            compile_regex(&direct_document_comparison.document).map(|_| ())
+        }
+        Where::WhereChildren(where_children) => where_children
+            .children
+            .iter()
+            .try_for_each(validate_regexes),
+        _ => Ok(()),
+    }
+}
+
+/// The minimum length in characters of the literals searched in the trigram full-text index
+const MIN_PREFILTER_LITERAL_LENGTH: usize = 3;
+
+/// Returns literals that occur in every match of the regular expression. The extraction is
+/// conservative, so the result may be empty even if every match shares some literal.
+fn regex_required_literals(pattern: &str) -> Vec<String> {
+    let mut literals = Vec::new();
+    if let Ok(hir) = regex_syntax::parse(pattern) {
+        collect_required_literals(&hir, &mut literals);
+    }
+    literals.retain(|literal| !literal.is_empty());
+    literals
+}
+
+fn collect_required_literals(hir: &Hir, literals: &mut Vec<String>) {
+    match hir.kind() {
+        HirKind::Literal(Literal(bytes)) => push_utf8_literal(bytes, literals),
+        HirKind::Concat(subs) => {
+            // Adjacent literals are joined into a single literal
+            let mut run = Vec::new();
+            for sub in subs {
+                match sub.kind() {
+                    HirKind::Literal(Literal(bytes)) => run.extend_from_slice(bytes),
+                    _ => {
+                        push_utf8_literal(&run, literals);
+                        run.clear();
+                        collect_required_literals(sub, literals);
+                    }
+                }
+            }
+            push_utf8_literal(&run, literals);
+        }
+        HirKind::Capture(capture) => collect_required_literals(&capture.sub, literals),
+        HirKind::Repetition(repetition) if repetition.min > 0 => {
+            collect_required_literals(&repetition.sub, literals)
+        }
+        // Nothing is required by an alternation, a class, a look-around or an optional repetition
+        _ => {}
+    }
+}
+
+fn push_utf8_literal(bytes: &[u8], literals: &mut Vec<String>) {
+    if let Ok(literal) = std::str::from_utf8(bytes) {
+        literals.push(literal.to_string());
+    }
+}
+
 pub(crate) enum MetadataProvider<'me> {
-    CompactData(&'me MetadataSegmentReader<'me>),
+    // The record segment reader is used to verify the documents matched by a regex. The offset
+    // ids, if any, are the domain that the results are intersected with, so that the partitions
+    // of the posting lists outside of it are not read
+    CompactData(
+        &'me MetadataSegmentReader<'me>,
+        Option<&'me RecordSegmentReader<'me>>,
+        Option<RoaringBitmap>,
+    ),
     Log(&'me MetadataLogReader<'me>),
 }
 
 impl<'me> MetadataProvider<'me> {
-    pub(crate) fn from_metadata_segment_reader(reader: &'me MetadataSegmentReader<'me>) -> Self {
-        Self::CompactData(reader)
+    pub(crate) fn from_metadata_segment_reader(
+        reader: &'me MetadataSegmentReader<'me>,
+        record_segment_reader: Option<&'me RecordSegmentReader<'me>>,
+    ) -> Self {
+        Self::CompactData(reader, record_segment_reader, None)
+    }
+
+    /// Restricts the domain of the compacted data to the offset ids. The results are only exact
+    /// within the domain, so they should be intersected with it
+    pub(crate) fn within(&self, offset_ids: &RoaringBitmap) -> Self {
+        match self {
+            MetadataProvider::CompactData(reader, record_segment_reader, domain) => {
+                let domain = match domain {
+                    Some(domain) => domain & offset_ids,
+                    None => offset_ids.clone(),
+                };
+                MetadataProvider::CompactData(reader, *record_segment_reader, Some(domain))
+            }
+            MetadataProvider::Log(reader) => MetadataProvider::Log(reader),
+        }
     }
 
     pub(crate) fn from_metadata_log_reader(reader: &'me MetadataLogReader<'me>) -> Self {
@@ -197,7 +552,7 @@ impl<'me> MetadataProvider<'me> {
         query: &str,
     ) -> Result<RoaringBitmap, FilterError> {
         match self {
-            MetadataProvider::CompactData(metadata_segment_reader) => {
+            MetadataProvider::CompactData(metadata_segment_reader, _, _) => {
                 if let Some(reader) = metadata_segment_reader.full_text_index_reader.as_ref() {
                     Ok(reader
                         .search(query)
@@ -210,11 +565,164 @@ impl<'me> MetadataProvider<'me> {
             MetadataProvider::Log(metadata_log_reader) => Ok(metadata_log_reader
                 .document
                 .iter()
-                .filter_map(|(offset_id, document)| document.contains(query).then_some(offset_id))
+                .filter_map(|(offset_id, document)| {
+                    metadata_log_reader
+                        .tokenizer_config
+                        .contains(document, query)
+                        .then_some(offset_id)
+                })
+                .collect()),
+        }
+    }
+
+    pub(crate) async fn filter_by_document_near(
+        &self,
+        query: &str,
+        distance: u32,
+    ) -> Result<RoaringBitmap, FilterError> {
+        match self {
+            MetadataProvider::CompactData(metadata_segment_reader, _, _) => {
+                if let Some(reader) = metadata_segment_reader.full_text_index_reader.as_ref() {
+                    Ok(reader
+                        .search_near(query, distance)
+                        .await
+                        .map_err(MetadataIndexError::FullTextError)?)
+                } else {
+                    Ok(RoaringBitmap::new())
+                }
+            }
+            MetadataProvider::Log(metadata_log_reader) => {
+                let tokenizer_config = &metadata_log_reader.tokenizer_config;
+                // Terms that are removed by the normalization, such as stopwords, are ignored
+                let terms = query
+                    .split_whitespace()
+                    .map(|term| tokenizer_config.normalize(term))
+                    .filter(|term| !term.trim().is_empty())
+                    .collect::<Vec<_>>();
+                let terms = terms.iter().map(AsRef::as_ref).collect::<Vec<_>>();
+                Ok(metadata_log_reader
+                    .document
+                    .iter()
+                    .filter_map(|(offset_id, document)| {
+                        document_contains_near(
+                            &tokenizer_config.normalize(document),
+                            &terms,
+                            distance,
+                        )
+                        .then_some(offset_id)
+                    })
+                    .collect())
+            }
+        }
+    }
+
+    /// Searches for the documents matching the regular expression.
+    ///
+    /// In the metadata segment, the literals required by the regular expression are searched in
+    /// the trigram full-text index to find the candidate documents, which are then verified
+    /// against the documents in the record segment. Every document in the record segment is
+    /// scanned instead if there is no literal long enough, or if the full-text index is absent
+    /// or built over normalized documents.
+    pub(crate) async fn filter_by_document_regex(
+        &self,
+        regex: &Regex,
+    ) -> Result<RoaringBitmap, FilterError> {
+        match self {
+            MetadataProvider::CompactData(metadata_segment_reader, record_segment_reader, _) => {
+                let Some(record_segment_reader) = record_segment_reader else {
+                    return Ok(RoaringBitmap::new());
+                };
+
+                let mut candidate_offset_ids: Option<RoaringBitmap> = None;
+                if let Some(reader) = metadata_segment_reader
+                    .full_text_index_reader
+                    .as_ref()
+                    .filter(|reader| reader.tokenizer_config().is_identity())
+                {
+                    for literal in regex_required_literals(regex.as_str())
+                        .iter()
+                        .filter(|literal| literal.chars().count() >= MIN_PREFILTER_LITERAL_LENGTH)
+                    {
+                        let offset_ids = reader
+                            .search(literal)
+                            .await
+                            .map_err(MetadataIndexError::FullTextError)?;
+                        candidate_offset_ids = Some(match candidate_offset_ids {
+                            Some(candidates) => candidates & offset_ids,
+                            None => offset_ids,
+                        });
+                    }
+                }
+
+                let mut offset_ids = RoaringBitmap::new();
+                match candidate_offset_ids {
+                    Some(candidate_offset_ids) => {
+                        let candidates = candidate_offset_ids.iter().collect::<Vec<_>>();
+                        record_segment_reader.prefetch_id_to_data(&candidates).await;
+                        for offset_id in candidates {
+                            let record = record_segment_reader
+                                .get_data_for_offset_id(offset_id)
+                                .await
+                                .map_err(FilterError::GetError)?;
+                            if record
+                                .and_then(|record| record.document)
+                                .is_some_and(|document| regex.is_match(document))
+                            {
+                                offset_ids.insert(offset_id);
+                            }
+                        }
+                    }
+                    None => {
+                        let mut records = Box::pin(record_segment_reader.scan(None));
+                        while let Some((offset_id, record)) =
+                            records.try_next().await.map_err(FilterError::GetError)?
+                        {
+                            if record
+                                .document
+                                .is_some_and(|document| regex.is_match(document))
+                            {
+                                offset_ids.insert(offset_id);
+                            }
+                        }
+                    }
+                }
+                Ok(offset_ids)
+            }
+            MetadataProvider::Log(metadata_log_reader) => Ok(metadata_log_reader
+                .document
+                .iter()
+                .filter_map(|(offset_id, document)| regex.is_match(document).then_some(offset_id))
                 .collect()),
         }
     }
 
+    pub(crate) async fn filter_by_key_existence(
+        &self,
+        key: &str,
+    ) -> Result<RoaringBitmap, FilterError> {
+        match self {
+            MetadataProvider::CompactData(metadata_segment_reader, _, _) => {
+                let mut offset_ids = RoaringBitmap::new();
+                for reader in [
+                    &metadata_segment_reader.string_metadata_index_reader,
+                    &metadata_segment_reader.bool_metadata_index_reader,
+                    &metadata_segment_reader.f32_metadata_index_reader,
+                    &metadata_segment_reader.u32_metadata_index_reader,
+                ]
+                .into_iter()
+                .flatten()
+                {
+                    offset_ids |= reader.exists(key).await?;
+                }
+                if let Some(reader) = &metadata_segment_reader.string_metadata_index_reader {
+                    offset_ids |= reader.exists(&timestamp_metadata_prefix(key)).await?;
+                }
+                Ok(offset_ids)
+            }
+            MetadataProvider::Log(metadata_log_reader) => Ok(metadata_log_reader.exists(key)),
+        }
+    }
+
     pub(crate) async fn filter_by_metadata(
         &self,
         key: &str,
@@ -222,36 +730,86 @@ impl<'me> MetadataProvider<'me> {
         op: &PrimitiveOperator,
     ) -> Result<RoaringBitmap, FilterError> {
         match self {
-            MetadataProvider::CompactData(metadata_segment_reader) => {
-                let (metadata_index_reader, kw) = match val {
+            MetadataProvider::CompactData(metadata_segment_reader, _, domain) => {
+                // Case-insensitive and prefix matching only apply to strings
+                match (op, val) {
+                    (PrimitiveOperator::EqualCaseInsensitive, MetadataValue::Str(s)) => {
+                        return Ok(metadata_segment_reader.get_case_insensitive(key, s).await?);
+                    }
+                    (PrimitiveOperator::StartsWith, MetadataValue::Str(s)) => {
+                        return match metadata_segment_reader
+                            .string_metadata_index_reader
+                            .as_ref()
+                        {
+                            Some(reader) => Ok(reader.starts_with(key, s).await?),
+                            None => Ok(RoaringBitmap::new()),
+                        };
+                    }
+                    (
+                        PrimitiveOperator::EqualCaseInsensitive | PrimitiveOperator::StartsWith,
+                        _,
+                    ) => return Ok(RoaringBitmap::new()),
+                    _ => {}
+                }
+                let timestamp_key;
+                let timestamp_kw;
+                let (metadata_index_reader, kw, key) = match val {
                     MetadataValue::Bool(b) => (
                         metadata_segment_reader.bool_metadata_index_reader.as_ref(),
                         &(*b).into(),
+                        key,
                     ),
                     MetadataValue::Int(i) => (
                         metadata_segment_reader.u32_metadata_index_reader.as_ref(),
                         &(*i as u32).into(),
+                        key,
                     ),
                     MetadataValue::Float(f) => (
                         metadata_segment_reader.f32_metadata_index_reader.as_ref(),
                         &(*f as f32).into(),
+                        key,
                     ),
                     MetadataValue::Str(s) => (
                         metadata_segment_reader
                             .string_metadata_index_reader
                             .as_ref(),
                         &s.as_str().into(),
+                        key,
                     ),
+                    // Timestamps are indexed as order preserving strings under a separate prefix
+                    MetadataValue::Timestamp(t) => {
+                        timestamp_key = timestamp_metadata_prefix(key);
+                        timestamp_kw = KeyWrapper::String(encode_timestamp(*t));
+                        (
+                            metadata_segment_reader
+                                .string_metadata_index_reader
+                                .as_ref(),
+                            &timestamp_kw,
+                            timestamp_key.as_str(),
+                        )
+                    }
+                    // Where clauses only compare against single values
+                    MetadataValue::List(_) | MetadataValue::Object(_) => {
+                        return Ok(RoaringBitmap::new())
+                    }
                 };
                 if let Some(reader) = metadata_index_reader {
                     match op {
-                        PrimitiveOperator::Equal => Ok(reader.get(key, kw).await?),
+                        PrimitiveOperator::Equal => {
+                            Ok(reader.get_within(key, kw, domain.as_ref()).await?)
+                        }
                         PrimitiveOperator::GreaterThan => Ok(reader.gt(key, kw).await?),
                         PrimitiveOperator::GreaterThanOrEqual => Ok(reader.gte(key, kw).await?),
                         PrimitiveOperator::LessThan => Ok(reader.lt(key, kw).await?),
                         PrimitiveOperator::LessThanOrEqual => Ok(reader.lte(key, kw).await?),
-                        PrimitiveOperator::NotEqual => unreachable!(
-                            "Inequality filter should be handled above the metadata provider level"
+                        PrimitiveOperator::EqualCaseInsensitive
+                        | PrimitiveOperator::StartsWith => {
+                            unreachable!("String matching filters are handled above")
+                        }
+                        PrimitiveOperator::NotEqual
+                        | PrimitiveOperator::Contains
+                        | PrimitiveOperator::NotContains => unreachable!(
+                            "Inequality and containment filters should be handled above the metadata provider level"
                         ),
                     }
                 } else {
@@ -261,6 +819,62 @@ impl<'me> MetadataProvider<'me> {
             MetadataProvider::Log(metadata_log_reader) => metadata_log_reader.get(key, val, op),
         }
     }
+
+    /// Returns the offset ids whose value for the key is any of the values in the set. The set
+    /// is evaluated against the metadata index at once, so that a large `$in` or `$nin` is not
+    /// expanded into an equality filter per value
+    pub(crate) async fn filter_by_metadata_set(
+        &self,
+        key: &str,
+        vals: &MetadataSetValue,
+    ) -> Result<RoaringBitmap, FilterError> {
+        match self {
+            MetadataProvider::CompactData(metadata_segment_reader, _, _) => {
+                let (metadata_index_reader, kws): (_, Vec<KeyWrapper>) = match vals {
+                    MetadataSetValue::Bool(vec) => (
+                        metadata_segment_reader.bool_metadata_index_reader.as_ref(),
+                        vec.iter().map(|b| (*b).into()).collect(),
+                    ),
+                    MetadataSetValue::Int(vec) => (
+                        metadata_segment_reader.u32_metadata_index_reader.as_ref(),
+                        vec.iter().map(|i| (*i as u32).into()).collect(),
+                    ),
+                    MetadataSetValue::Float(vec) => (
+                        metadata_segment_reader.f32_metadata_index_reader.as_ref(),
+                        vec.iter().map(|f| (*f as f32).into()).collect(),
+                    ),
+                    MetadataSetValue::Str(vec) => (
+                        metadata_segment_reader
+                            .string_metadata_index_reader
+                            .as_ref(),
+                        vec.iter().map(|s| s.as_str().into()).collect(),
+                    ),
+                };
+                if let Some(reader) = metadata_index_reader {
+                    Ok(reader.get_any(key, &kws).await?)
+                } else {
+                    Ok(RoaringBitmap::new())
+                }
+            }
+            MetadataProvider::Log(metadata_log_reader) => {
+                let vals: Vec<_> = match vals {
+                    MetadataSetValue::Bool(vec) => {
+                        vec.iter().map(|b| MetadataValue::Bool(*b)).collect()
+                    }
+                    MetadataSetValue::Int(vec) => {
+                        vec.iter().map(|i| MetadataValue::Int(*i)).collect()
+                    }
+                    MetadataSetValue::Float(vec) => {
+                        vec.iter().map(|f| MetadataValue::Float(*f)).collect()
+                    }
+                    MetadataSetValue::Str(vec) => {
+                        vec.iter().map(|s| MetadataValue::Str(s.clone())).collect()
+                    }
+                };
+                Ok(metadata_log_reader.get_any(key, &vals))
+            }
+        }
+    }
 }
 
 pub(crate) trait RoaringMetadataFilter<'me> {
@@ -299,7 +913,19 @@ impl<'me> RoaringMetadataFilter<'me> for DirectWhereComparison {
             WhereComparison::Primitive(primitive_operator, metadata_value) => {
                 match primitive_operator {
                     // We convert the inequality check in to an equality check, and then negate the result
-                    PrimitiveOperator::NotEqual => SignedRoaringBitmap::Exclude(
+                    // Containment is equality, since the elements of lists are indexed separately
+                    PrimitiveOperator::NotEqual | PrimitiveOperator::NotContains => {
+                        SignedRoaringBitmap::Exclude(
+                            metadata_provider
+                                .filter_by_metadata(
+                                    &self.key,
+                                    metadata_value,
+                                    &PrimitiveOperator::Equal,
+                                )
+                                .await?,
+                        )
+                    }
+                    PrimitiveOperator::Contains => SignedRoaringBitmap::Include(
                         metadata_provider
                             .filter_by_metadata(
                                 &self.key,
@@ -309,6 +935,8 @@ impl<'me> RoaringMetadataFilter<'me> for DirectWhereComparison {
                             .await?,
                     ),
                     PrimitiveOperator::Equal
+                    | PrimitiveOperator::EqualCaseInsensitive
+                    | PrimitiveOperator::StartsWith
                     | PrimitiveOperator::GreaterThan
                     | PrimitiveOperator::GreaterThanOrEqual
                     | PrimitiveOperator::LessThan
@@ -320,41 +948,20 @@ impl<'me> RoaringMetadataFilter<'me> for DirectWhereComparison {
                 }
             }
             WhereComparison::Set(set_operator, metadata_set_value) => {
-                let child_values: Vec<_> = match metadata_set_value {
-                    MetadataSetValue::Bool(vec) => {
-                        vec.iter().map(|b| MetadataValue::Bool(*b)).collect()
-                    }
-                    MetadataSetValue::Int(vec) => {
-                        vec.iter().map(|i| MetadataValue::Int(*i)).collect()
-                    }
-                    MetadataSetValue::Float(vec) => {
-                        vec.iter().map(|f| MetadataValue::Float(*f)).collect()
-                    }
-                    MetadataSetValue::Str(vec) => {
-                        vec.iter().map(|s| MetadataValue::Str(s.clone())).collect()
-                    }
-                };
-                let mut child_evaluations = Vec::with_capacity(child_values.len());
-                for value in child_values {
-                    let eval = metadata_provider
-                        .filter_by_metadata(&self.key, &value, &PrimitiveOperator::Equal)
-                        .await?;
-                    match set_operator {
-                        SetOperator::In => {
-                            child_evaluations.push(SignedRoaringBitmap::Include(eval))
-                        }
-                        SetOperator::NotIn => {
-                            child_evaluations.push(SignedRoaringBitmap::Exclude(eval))
-                        }
-                    };
-                }
+                let offset_ids = metadata_provider
+                    .filter_by_metadata_set(&self.key, metadata_set_value)
+                    .await?;
                 match set_operator {
-                    SetOperator::In => child_evaluations
-                        .into_iter()
-                        .fold(SignedRoaringBitmap::empty(), BitOr::bitor),
-                    SetOperator::NotIn => child_evaluations
-                        .into_iter()
-                        .fold(SignedRoaringBitmap::full(), BitAnd::bitand),
+                    SetOperator::In => SignedRoaringBitmap::Include(offset_ids),
+                    SetOperator::NotIn => SignedRoaringBitmap::Exclude(offset_ids),
+                }
+            }
+            WhereComparison::Existence(existence_operator) => {
+                let offset_ids = metadata_provider.filter_by_key_existence(&self.key).await?;
+                match existence_operator {
+                    ExistenceOperator::Exists => SignedRoaringBitmap::Include(offset_ids),
+                    // Records without the key include the ones whose value was removed
+                    ExistenceOperator::IsNull => SignedRoaringBitmap::Exclude(offset_ids),
                 }
             }
         };
@@ -367,12 +974,32 @@ impl<'me> RoaringMetadataFilter<'me> for DirectDocumentComparison {
         &'me self,
         metadata_provider: &MetadataProvider<'me>,
     ) -> Result<SignedRoaringBitmap, FilterError> {
-        let contain = metadata_provider
-            .filter_by_document(self.document.as_str())
-            .await?;
         match self.operator {
-            DocumentOperator::Contains => Ok(SignedRoaringBitmap::Include(contain)),
-            DocumentOperator::NotContains => Ok(SignedRoaringBitmap::Exclude(contain)),
+            DocumentOperator::Contains => Ok(SignedRoaringBitmap::Include(
+                metadata_provider
+                    .filter_by_document(self.document.as_str())
+                    .await?,
+            )),
+            DocumentOperator::NotContains => Ok(SignedRoaringBitmap::Exclude(
+                metadata_provider
+                    .filter_by_document(self.document.as_str())
+                    .await?,
+            )),
+            DocumentOperator::Near(distance) => Ok(SignedRoaringBitmap::Include(
+                metadata_provider
+                    .filter_by_document_near(self.document.as_str(), distance)
+                    .await?,
+            )),
+            DocumentOperator::Regex => Ok(SignedRoaringBitmap::Include(
+                metadata_provider
+                    .filter_by_document_regex(&compile_regex(&self.document)?)
+                    .await?,
+            )),
+            DocumentOperator::NotRegex => Ok(SignedRoaringBitmap::Exclude(
+                metadata_provider
+                    .filter_by_document_regex(&compile_regex(&self.document)?)
+                    .await?,
+            )),
         }
     }
 }
@@ -382,14 +1009,26 @@ impl<'me> RoaringMetadataFilter<'me> for WhereChildren {
         &'me self,
         metadata_provider: &MetadataProvider<'me>,
     ) -> Result<SignedRoaringBitmap, FilterError> {
+        if let BooleanOperator::And = self.operator {
+            // The offset ids matched so far restrict the domain of the remaining children
+            let mut result = SignedRoaringBitmap::full();
+            for child in &self.children {
+                let child_evaluation = match &result {
+                    SignedRoaringBitmap::Include(offset_ids) => {
+                        child.eval(&metadata_provider.within(offset_ids)).await?
+                    }
+                    SignedRoaringBitmap::Exclude(_) => child.eval(metadata_provider).await?,
+                };
+                result = result & child_evaluation;
+            }
+            return Ok(result);
+        }
         let mut child_evaluations = Vec::new();
         for child in &self.children {
             child_evaluations.push(child.eval(metadata_provider).await?);
         }
         match self.operator {
-            BooleanOperator::And => Ok(child_evaluations
-                .into_iter()
-                .fold(SignedRoaringBitmap::full(), BitAnd::bitand)),
+            BooleanOperator::And => unreachable!("Conjunctions are evaluated above"),
             BooleanOperator::Or => Ok(child_evaluations
                 .into_iter()
                 .fold(SignedRoaringBitmap::empty(), BitOr::bitor)),
@@ -404,6 +1043,10 @@ impl Operator<FilterInput, FilterOutput> for FilterOperator {
     async fn run(&self, input: &FilterInput) -> Result<FilterOutput, FilterError> {
         trace!("[{}]: {:?}", self.get_name(), input);
 
+        if let Some(clause) = self.where_clause.as_ref() {
+            validate_regexes(clause)?;
+        }
+
         let record_segment_reader = match RecordSegmentReader::from_segment(
             &input.record_segment,
             &input.blockfile_provider,
@@ -416,51 +1059,77 @@ impl Operator<FilterInput, FilterOutput> for FilterOperator {
             }
             Err(e) => Err(*e),
         }?;
-        let materializer =
-            LogMaterializer::new(record_segment_reader.clone(), input.logs.clone(), None);
+        // Only the metadata keys and the document referenced by the where clause are read from the logs
+        let where_references = self
+            .where_clause
+            .as_ref()
+            .map(WhereReferences::from_where)
+            .unwrap_or_default();
+        // The logs are also materialized with the fields that the later operators read from them
+        let fields = where_references
+            .fields()
+            .union(input.materialized_logs.fields());
+        let materializer = match input.fetch_log.as_ref() {
+            Some(fetch_log) => {
+                LogMaterializer::from_stream(
+                    record_segment_reader.clone(),
+                    fetch_log
+                        .stream(input.memory.clone())
+                        .map_err(FilterError::from),
+                    None,
+                )
+                .instrument(tracing::trace_span!(parent: Span::current(), "Fetch logs"))
+                .await?
+            }
+            None => LogMaterializer::new(record_segment_reader.clone(), input.logs.clone(), None),
+        }
+        .with_fields(fields);
+        let reserved = input
+            .memory
+            .reserve_log_materialization(&materializer.logs)?;
         let materialized_logs = materializer
             .materialize()
             .instrument(tracing::trace_span!(parent: Span::current(), "Materialize logs"))
             .await?;
-        let metadata_log_reader = MetadataLogReader::new(&materialized_logs);
+        input
+            .memory
+            .settle_log_materialization(reserved, &materialized_logs);
+        input.materialized_logs.record(&materialized_logs, fields);
+        let tokenizer_config = tokenizer_config_from_segment(&input.metadata_segment)?;
+        let metadata_log_reader = MetadataLogReader::new(
+            &materialized_logs,
+            Some(&where_references),
+            &tokenizer_config,
+        );
         let log_metadata_provider =
             MetadataProvider::from_metadata_log_reader(&metadata_log_reader);
 
         let metadata_segement_reader =
             MetadataSegmentReader::from_segment(&input.metadata_segment, &input.blockfile_provider)
                 .await?;
-        let compact_metadata_provider =
-            MetadataProvider::from_metadata_segment_reader(&metadata_segement_reader);
+        let compact_metadata_provider = MetadataProvider::from_metadata_segment_reader(
+            &metadata_segement_reader,
+            record_segment_reader.as_ref(),
+        );
 
         // Get offset ids corresponding to user ids
         let (user_allowed_log_offset_ids, user_allowed_compact_offset_ids) =
             if let Some(user_allowed_ids) = self.query_ids.as_ref() {
+                let user_allowed_ids = user_allowed_ids
+                    .iter()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>();
                 let log_offset_ids = SignedRoaringBitmap::Include(
-                    metadata_log_reader.search_user_ids(
-                        user_allowed_ids
-                            .iter()
-                            .map(String::as_str)
-                            .collect::<Vec<_>>()
-                            .as_slice(),
-                    ),
+                    metadata_log_reader.search_user_ids(&user_allowed_ids),
                 );
-                let compact_offset_ids = if let Some(reader) = record_segment_reader {
-                    let mut offset_ids = RoaringBitmap::new();
-                    for user_id in user_allowed_ids {
-                        match reader.get_offset_id_for_user_id(user_id.as_str()).await {
-                            Ok(Some(offset_id)) => {
-                                offset_ids.insert(offset_id);
-                            }
-                            Ok(None) => {
-                                // NOTE(rescrv):  We are filtering by user id, and there is no
-                                // document.  Drop it.
-                            }
-                            Err(e) => {
-                                return Err(FilterError::GetError(e));
-                            }
-                        };
-                    }
-                    SignedRoaringBitmap::Include(offset_ids)
+                let compact_offset_ids = if let Some(reader) = record_segment_reader.as_ref() {
+                    // User ids that have no record are dropped
+                    let offset_ids = input
+                        .offset_id_cache
+                        .get_offset_ids_for_user_ids(reader, &user_allowed_ids)
+                        .await
+                        .map_err(FilterError::GetError)?;
+                    SignedRoaringBitmap::Include(offset_ids.into_iter().flatten().collect())
                 } else {
                     SignedRoaringBitmap::full()
                 };
@@ -479,36 +1148,127 @@ impl Operator<FilterInput, FilterOutput> for FilterOperator {
         // Filter the offset ids in the metadata segment if the where clause is provided
         // This always exclude all offsets that is present in the materialized log
         let compact_offset_ids = if let Some(clause) = self.where_clause.as_ref() {
-            clause.eval(&compact_metadata_provider).await?
+            // Only the first matching records are needed, which a scan may find before the
+            // metadata index is read
+            let scanned_offset_ids = match (
+                input.scan_limit,
+                &user_allowed_compact_offset_ids,
+                record_segment_reader.as_ref(),
+                RecordScanPredicate::from_where(clause),
+            ) {
+                (
+                    Some(scan_limit),
+                    SignedRoaringBitmap::Exclude(_),
+                    Some(reader),
+                    Some(predicate),
+                ) => {
+                    scan_compact_offset_ids(
+                        reader,
+                        predicate,
+                        &metadata_log_reader.updated_offset_ids,
+                        scan_limit as usize,
+                    )
+                    .await?
+                }
+                _ => None,
+            };
+            // Use the precomputed offset ids if the where clause is materialized
+            let materialized_offset_ids = match scanned_offset_ids {
+                Some(offset_ids) => Some(offset_ids),
+                None => {
+                    metadata_segement_reader
+                        .get_materialized_filter(clause)
+                        .await?
+                }
+            };
+            let compact_clause_offset_ids = match materialized_offset_ids {
+                Some(offset_ids) => SignedRoaringBitmap::Include(offset_ids),
+                // The result is intersected with the user allowed offset ids below
+                None => match &user_allowed_compact_offset_ids {
+                    SignedRoaringBitmap::Include(offset_ids) => {
+                        clause
+                            .eval(&compact_metadata_provider.within(offset_ids))
+                            .await?
+                    }
+                    SignedRoaringBitmap::Exclude(_) => {
+                        clause.eval(&compact_metadata_provider).await?
+                    }
+                },
+            };
+            compact_clause_offset_ids
                 & user_allowed_compact_offset_ids
                 & SignedRoaringBitmap::Exclude(metadata_log_reader.updated_offset_ids)
         } else {
             user_allowed_compact_offset_ids
                 & SignedRoaringBitmap::Exclude(metadata_log_reader.updated_offset_ids)
         };
+        input
+            .memory
+            .reserve_signed_bitmaps([&log_offset_ids, &compact_offset_ids])?;
 
         Ok(FilterOutput {
+            logs: input.fetch_log.is_some().then(|| materializer.logs.clone()),
             log_offset_ids,
             compact_offset_ids,
         })
     }
+
+    fn can_retry(&self, error: &FilterError) -> bool {
+        error.is_transient()
+    }
+
+    fn collection_id(&self, input: &FilterInput) -> Option<CollectionUuid> {
+        Some(input.record_segment.collection)
+    }
+
+    fn input_cardinality(&self, input: &FilterInput) -> Option<usize> {
+        // The number of the streamed logs is not known upfront
+        input.fetch_log.is_none().then(|| input.logs.len())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use chroma_types::{
-        BooleanOperator, DirectDocumentComparison, DirectWhereComparison, MetadataSetValue,
-        MetadataValue, PrimitiveOperator, SetOperator, SignedRoaringBitmap, Where, WhereChildren,
-        WhereComparison,
+        BooleanOperator, Chunk, CollectionUuid, DirectDocumentComparison, DirectWhereComparison,
+        ExistenceOperator, LogRecord, MetadataSetValue, MetadataValue, Operation, OperationRecord,
+        PrimitiveOperator, SetOperator, SignedRoaringBitmap, UpdateMetadataValue, Where,
+        WhereChildren, WhereComparison,
     };
 
+    use std::collections::{BTreeMap, HashMap};
+
     use crate::{
-        execution::{operator::Operator, operators::filter::FilterOperator},
-        log::test::{add_delete_generator, int_as_id, LogGenerator},
-        segment::test::TestSegment,
+        execution::{
+            memory::MemoryContext,
+            operator::Operator,
+            operators::{fetch_log::FetchLogOperator, filter::FilterOperator},
+        },
+        log::{
+            log::{InMemoryLog, InternalLogRecord, Log},
+            test::{
+                add_delete_generator, int_as_id, random_embedding, LogGenerator,
+                TEST_EMBEDDING_DIMENSION,
+            },
+        },
+        segment::{
+            materialized_filter::MATERIALIZED_FILTERS_KEY,
+            metadata_segment::{
+                case_insensitive_metadata_prefix, MetadataSegmentReader, MetadataSegmentWriter,
+                CASE_INSENSITIVE_MARKER,
+            },
+            offset_id_cache::OffsetIdCache,
+            test::TestSegment,
+            LogMaterializer, SegmentFlusher, SegmentWriter, SharedMaterializedLogs,
+        },
     };
+    use chroma_blockstore::key::KeyWrapper;
+    use chroma_error::ChromaError;
+    use chroma_index::fulltext::tokenizer::TokenizerConfig;
 
-    use super::FilterInput;
+    use super::{
+        regex_required_literals, FilterError, FilterInput, MetadataLogReader, WhereReferences,
+    };
 
     /// The unit tests for `FilterOperator` uses the following test data
     /// It generates 120 log records, where the first 60 is compacted:
@@ -522,9 +1282,14 @@ mod tests {
         test_segment.populate_with_generator(60, &generator).await;
         FilterInput {
             logs: generator.generate_chunk(61..=120),
+            fetch_log: None,
             blockfile_provider: test_segment.blockfile_provider,
             metadata_segment: test_segment.metadata_segment,
             record_segment: test_segment.record_segment,
+            materialized_logs: SharedMaterializedLogs::default(),
+            memory: MemoryContext::default(),
+            offset_id_cache: OffsetIdCache::default(),
+            scan_limit: None,
         }
     }
 
@@ -550,11 +1315,33 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_simple_user_allowed_ids() {
-        let filter_input = setup_filter_input().await;
+    async fn test_trivial_filter_streamed_logs() {
+        let mut filter_input = setup_filter_input().await;
+        let collection_uuid = CollectionUuid::new();
+        let mut in_memory_log = InMemoryLog::new();
+        filter_input.logs.iter().for_each(|(log, _)| {
+            in_memory_log.add_log(
+                collection_uuid,
+                InternalLogRecord {
+                    collection_id: collection_uuid,
+                    log_offset: log.log_offset,
+                    log_ts: log.log_offset,
+                    record: log.clone(),
+                },
+            )
+        });
+        let logs = std::mem::replace(&mut filter_input.logs, Chunk::new(Vec::new().into()));
+        filter_input.fetch_log = Some(FetchLogOperator {
+            log_client: Box::new(Log::InMemory(in_memory_log)),
+            batch_size: 7,
+            start_log_offset_id: 61,
+            maximum_fetch_count: None,
+            maximum_backlog: None,
+            collection_uuid,
+        });
 
         let filter_operator = FilterOperator {
-            query_ids: Some((0..30).map(int_as_id).collect()),
+            query_ids: None,
             where_clause: None,
         };
 
@@ -563,10 +1350,156 @@ mod tests {
             .await
             .expect("FilterOperator should not fail");
 
-        assert_eq!(filter_output.log_offset_ids, SignedRoaringBitmap::empty());
+        // The streamed logs yield the same result as the fetched logs, and are returned
+        assert_eq!(filter_output.log_offset_ids, SignedRoaringBitmap::full());
         assert_eq!(
             filter_output.compact_offset_ids,
-            SignedRoaringBitmap::Include((21..30).collect())
+            SignedRoaringBitmap::Exclude((11..=20).collect())
+        );
+        assert_eq!(
+            filter_output
+                .logs
+                .expect("FilterOperator should return the streamed logs")
+                .iter()
+                .map(|(log, _)| log.log_offset)
+                .collect::<Vec<_>>(),
+            logs.iter()
+                .map(|(log, _)| log.log_offset)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_simple_user_allowed_ids() {
+        let filter_input = setup_filter_input().await;
+
+        let filter_operator = FilterOperator {
+            query_ids: Some((0..30).map(int_as_id).collect()),
+            where_clause: None,
+        };
+
+        let filter_output = filter_operator
+            .run(&filter_input)
+            .await
+            .expect("FilterOperator should not fail");
+
+        assert_eq!(filter_output.log_offset_ids, SignedRoaringBitmap::empty());
+        assert_eq!(
+            filter_output.compact_offset_ids,
+            SignedRoaringBitmap::Include((21..30).collect())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_many_user_allowed_ids() {
+        let filter_input = setup_filter_input().await;
+
+        // Enough unsorted and repeated ids to be merged against the record segment
+        let filter_operator = FilterOperator {
+            query_ids: Some((0..1000).rev().chain(25..35).map(int_as_id).collect()),
+            where_clause: None,
+        };
+
+        let filter_output = filter_operator
+            .run(&filter_input)
+            .await
+            .expect("FilterOperator should not fail");
+
+        assert_eq!(
+            filter_output.log_offset_ids,
+            SignedRoaringBitmap::Include((51..=100).collect())
+        );
+        assert_eq!(
+            filter_output.compact_offset_ids,
+            SignedRoaringBitmap::Include((21..=50).collect())
+        );
+    }
+
+    /// Appends a log that removes the `modulo_3` key from the compacted record with offset id 30
+    fn remove_modulo_3_from_record_30(filter_input: &mut FilterInput) {
+        let mut logs = filter_input
+            .logs
+            .iter()
+            .map(|(log, _)| log.clone())
+            .collect::<Vec<_>>();
+        logs.push(LogRecord {
+            log_offset: 121,
+            record: OperationRecord {
+                id: int_as_id(30),
+                embedding: None,
+                encoding: None,
+                metadata: Some(HashMap::from([(
+                    "modulo_3".to_string(),
+                    UpdateMetadataValue::None,
+                )])),
+                document: None,
+                uri: None,
+                idempotency_key: None,
+                embedding_reference: None,
+                operation: Operation::Update,
+            },
+        });
+        filter_input.logs = Chunk::new(logs.into());
+    }
+
+    #[tokio::test]
+    async fn test_simple_exists() {
+        let mut filter_input = setup_filter_input().await;
+        remove_modulo_3_from_record_30(&mut filter_input);
+
+        let where_clause = Where::DirectWhereComparison(DirectWhereComparison {
+            key: "modulo_3".to_string(),
+            comparison: WhereComparison::Existence(ExistenceOperator::Exists),
+        });
+
+        let filter_operator = FilterOperator {
+            query_ids: None,
+            where_clause: Some(where_clause),
+        };
+
+        let filter_output = filter_operator
+            .run(&filter_input)
+            .await
+            .expect("FilterOperator should not fail");
+
+        assert_eq!(
+            filter_output.log_offset_ids,
+            SignedRoaringBitmap::Include((51..=100).collect())
+        );
+        assert_eq!(
+            filter_output.compact_offset_ids,
+            SignedRoaringBitmap::Include((21..=50).filter(|offset| *offset != 30).collect())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_simple_is_null() {
+        let mut filter_input = setup_filter_input().await;
+        remove_modulo_3_from_record_30(&mut filter_input);
+
+        let where_clause = Where::DirectWhereComparison(DirectWhereComparison {
+            key: "modulo_3".to_string(),
+            comparison: WhereComparison::Existence(ExistenceOperator::IsNull),
+        });
+
+        let filter_operator = FilterOperator {
+            query_ids: None,
+            where_clause: Some(where_clause),
+        };
+
+        let filter_output = filter_operator
+            .run(&filter_input)
+            .await
+            .expect("FilterOperator should not fail");
+
+        // The record with offset id 30 is matched in the logs after its key is removed
+        assert_eq!(
+            filter_output.log_offset_ids,
+            SignedRoaringBitmap::Exclude((51..=100).collect())
+        );
+        assert_eq!(
+            filter_output.compact_offset_ids,
+            SignedRoaringBitmap::Exclude((11..=50).collect())
         );
     }
 
@@ -705,6 +1638,39 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_large_in() {
+        let filter_input = setup_filter_input().await;
+
+        // Enough values to be merged against the posting lists of the metadata segment
+        let where_clause = Where::DirectWhereComparison(DirectWhereComparison {
+            key: "id".to_string(),
+            comparison: WhereComparison::Set(
+                SetOperator::In,
+                MetadataSetValue::Int((0..1000).rev().step_by(2).collect()),
+            ),
+        });
+
+        let filter_operator = FilterOperator {
+            query_ids: None,
+            where_clause: Some(where_clause),
+        };
+
+        let filter_output = filter_operator
+            .run(&filter_input)
+            .await
+            .expect("FilterOperator should not fail");
+
+        assert_eq!(
+            filter_output.log_offset_ids,
+            SignedRoaringBitmap::Include((51..=100).filter(|offset| offset % 2 == 1).collect())
+        );
+        assert_eq!(
+            filter_output.compact_offset_ids,
+            SignedRoaringBitmap::Include((21..=50).filter(|offset| offset % 2 == 1).collect())
+        );
+    }
+
     #[tokio::test]
     async fn test_simple_gt() {
         let filter_input = setup_filter_input().await;
@@ -737,6 +1703,61 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_scan_limit() {
+        let mut filter_input = setup_filter_input().await;
+        filter_input.scan_limit = Some(5);
+
+        let filter_operator = FilterOperator {
+            query_ids: None,
+            where_clause: Some(Where::DirectWhereComparison(DirectWhereComparison {
+                key: "is_even".to_string(),
+                comparison: WhereComparison::Primitive(
+                    PrimitiveOperator::Equal,
+                    MetadataValue::Bool(true),
+                ),
+            })),
+        };
+
+        let filter_output = filter_operator
+            .run(&filter_input)
+            .await
+            .expect("FilterOperator should not fail");
+
+        // Only the first matching records that are not updated in the logs are scanned
+        assert_eq!(
+            filter_output.log_offset_ids,
+            SignedRoaringBitmap::Include((51..=100).filter(|offset| offset % 2 == 0).collect())
+        );
+        assert_eq!(
+            filter_output.compact_offset_ids,
+            SignedRoaringBitmap::Include([22, 24, 26, 28, 30].into_iter().collect())
+        );
+
+        // A selective predicate is evaluated on the metadata index instead
+        filter_input.scan_limit = Some(1);
+        let filter_operator = FilterOperator {
+            query_ids: None,
+            where_clause: Some(Where::DirectWhereComparison(DirectWhereComparison {
+                key: "id".to_string(),
+                comparison: WhereComparison::Primitive(
+                    PrimitiveOperator::GreaterThan,
+                    MetadataValue::Int(48),
+                ),
+            })),
+        };
+
+        let filter_output = filter_operator
+            .run(&filter_input)
+            .await
+            .expect("FilterOperator should not fail");
+
+        assert_eq!(
+            filter_output.compact_offset_ids,
+            SignedRoaringBitmap::Include((49..=50).collect())
+        );
+    }
+
     #[tokio::test]
     async fn test_simple_contains() {
         let filter_input = setup_filter_input().await;
@@ -766,6 +1787,36 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_simple_near() {
+        let filter_input = setup_filter_input().await;
+
+        // Both terms only occur in the same word of the documents for multiples of 15
+        let where_clause = Where::DirectWhereDocumentComparison(DirectDocumentComparison {
+            operator: chroma_types::DocumentOperator::Near(0),
+            document: "<dog> <cat>".to_string(),
+        });
+
+        let filter_operator = FilterOperator {
+            query_ids: None,
+            where_clause: Some(where_clause),
+        };
+
+        let filter_output = filter_operator
+            .run(&filter_input)
+            .await
+            .expect("FilterOperator should not fail");
+
+        assert_eq!(
+            filter_output.log_offset_ids,
+            SignedRoaringBitmap::Include((51..=100).filter(|offset| offset % 15 == 0).collect())
+        );
+        assert_eq!(
+            filter_output.compact_offset_ids,
+            SignedRoaringBitmap::Include((21..=50).filter(|offset| offset % 15 == 0).collect())
+        );
+    }
+
     #[tokio::test]
     async fn test_simple_not_contains() {
         let filter_input = setup_filter_input().await;
@@ -800,29 +1851,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_regex_required_literals() {
+        assert_eq!(regex_required_literals("<cat>"), vec!["<cat>"]);
+        assert_eq!(
+            regex_required_literals("^<cat>.*(<dog>)+$"),
+            vec!["<cat>", "<dog>"]
+        );
+        assert_eq!(regex_required_literals("(cat|dog)s?"), Vec::<String>::new());
+        assert_eq!(regex_required_literals("[a-z]+ing"), vec!["ing"]);
+    }
+
     #[tokio::test]
-    async fn test_simple_and() {
+    async fn test_simple_regex() {
         let filter_input = setup_filter_input().await;
 
-        let where_sub_clause_1 = Where::DirectWhereComparison(DirectWhereComparison {
-            key: "id".to_string(),
-            comparison: WhereComparison::Primitive(
-                PrimitiveOperator::GreaterThan,
-                MetadataValue::Int(36),
-            ),
-        });
-
-        let where_sub_clause_2 = Where::DirectWhereComparison(DirectWhereComparison {
-            key: "is_even".to_string(),
-            comparison: WhereComparison::Primitive(
-                PrimitiveOperator::Equal,
-                MetadataValue::Bool(false),
-            ),
-        });
-
-        let where_clause = Where::WhereChildren(WhereChildren {
-            operator: BooleanOperator::And,
-            children: vec![where_sub_clause_1, where_sub_clause_2],
+        // The candidates are prefiltered by the trigram index with the required literal
+        let where_clause = Where::DirectWhereDocumentComparison(DirectDocumentComparison {
+            operator: chroma_types::DocumentOperator::Regex,
+            document: "^<cat><dog>$".to_string(),
         });
 
         let filter_operator = FilterOperator {
@@ -837,31 +1884,22 @@ mod tests {
 
         assert_eq!(
             filter_output.log_offset_ids,
-            SignedRoaringBitmap::Include((51..=100).filter(|offset| offset % 2 == 1).collect())
+            SignedRoaringBitmap::Include((51..=100).filter(|offset| offset % 15 == 0).collect())
         );
         assert_eq!(
             filter_output.compact_offset_ids,
-            SignedRoaringBitmap::Include((37..=50).filter(|offset| offset % 2 == 1).collect())
+            SignedRoaringBitmap::Include((21..=50).filter(|offset| offset % 15 == 0).collect())
         );
     }
 
     #[tokio::test]
-    async fn test_simple_or() {
+    async fn test_simple_not_regex() {
         let filter_input = setup_filter_input().await;
 
-        let where_sub_clause_1 = Where::DirectWhereComparison(DirectWhereComparison {
-            key: "modulo_3".to_string(),
-            comparison: WhereComparison::Primitive(PrimitiveOperator::Equal, MetadataValue::Int(0)),
-        });
-
-        let where_sub_clause_2 = Where::DirectWhereComparison(DirectWhereComparison {
-            key: "modulo_3".to_string(),
-            comparison: WhereComparison::Primitive(PrimitiveOperator::Equal, MetadataValue::Int(2)),
-        });
-
-        let where_clause = Where::WhereChildren(WhereChildren {
-            operator: BooleanOperator::Or,
-            children: vec![where_sub_clause_1, where_sub_clause_2],
+        // There is no required literal, so the documents in the record segment are scanned
+        let where_clause = Where::DirectWhereDocumentComparison(DirectDocumentComparison {
+            operator: chroma_types::DocumentOperator::NotRegex,
+            document: "^(<cat>|<dog>)$".to_string(),
         });
 
         let filter_operator = FilterOperator {
@@ -874,51 +1912,40 @@ mod tests {
             .await
             .expect("FilterOperator should not fail");
 
+        let matches = |offset: &u32| offset.is_multiple_of(3) != offset.is_multiple_of(5);
         assert_eq!(
             filter_output.log_offset_ids,
-            SignedRoaringBitmap::Include((51..=100).filter(|offset| offset % 3 != 1).collect())
+            SignedRoaringBitmap::Exclude((51..=100).filter(matches).collect())
         );
         assert_eq!(
             filter_output.compact_offset_ids,
-            SignedRoaringBitmap::Include((21..=50).filter(|offset| offset % 3 != 1).collect())
+            SignedRoaringBitmap::Exclude((21..=50).filter(matches).chain(11..=20).collect())
         );
     }
 
     #[tokio::test]
-    async fn test_complex_filter() {
+    async fn test_regex_and_metadata() {
         let filter_input = setup_filter_input().await;
 
-        let where_sub_clause_1 = Where::DirectWhereDocumentComparison(DirectDocumentComparison {
-            operator: chroma_types::DocumentOperator::NotContains,
-            document: "<dog>".to_string(),
-        });
-
-        let where_sub_clause_2 = Where::DirectWhereComparison(DirectWhereComparison {
-            key: "id".to_string(),
-            comparison: WhereComparison::Primitive(
-                PrimitiveOperator::LessThan,
-                MetadataValue::Int(72),
-            ),
-        });
-
-        let where_sub_clause_3 = Where::DirectWhereComparison(DirectWhereComparison {
-            key: "modulo_3".to_string(),
-            comparison: WhereComparison::Set(SetOperator::NotIn, MetadataSetValue::Int(vec![0, 1])),
-        });
-
         let where_clause = Where::WhereChildren(WhereChildren {
             operator: BooleanOperator::And,
             children: vec![
-                where_sub_clause_1,
-                Where::WhereChildren(WhereChildren {
-                    operator: BooleanOperator::Or,
-                    children: vec![where_sub_clause_2, where_sub_clause_3],
+                Where::DirectWhereComparison(DirectWhereComparison {
+                    key: "is_even".to_string(),
+                    comparison: WhereComparison::Primitive(
+                        PrimitiveOperator::Equal,
+                        MetadataValue::Bool(true),
+                    ),
+                }),
+                Where::DirectWhereDocumentComparison(DirectDocumentComparison {
+                    operator: chroma_types::DocumentOperator::Regex,
+                    document: "^<cat>".to_string(),
                 }),
             ],
         });
 
         let filter_operator = FilterOperator {
-            query_ids: Some((0..96).map(int_as_id).collect()),
+            query_ids: None,
             where_clause: Some(where_clause),
         };
 
@@ -929,7 +1956,177 @@ mod tests {
 
         assert_eq!(
             filter_output.log_offset_ids,
-            SignedRoaringBitmap::Include(
+            SignedRoaringBitmap::Include((51..=100).filter(|offset| offset % 6 == 0).collect())
+        );
+        assert_eq!(
+            filter_output.compact_offset_ids,
+            SignedRoaringBitmap::Include((21..=50).filter(|offset| offset % 6 == 0).collect())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invalid_regex() {
+        let filter_input = setup_filter_input().await;
+
+        // The pattern is validated even if it is nested in the where clause
+        let where_clause = Where::WhereChildren(WhereChildren {
+            operator: BooleanOperator::Or,
+            children: vec![
+                Where::DirectWhereDocumentComparison(DirectDocumentComparison {
+                    operator: chroma_types::DocumentOperator::Contains,
+                    document: "<dog>".to_string(),
+                }),
+                Where::DirectWhereDocumentComparison(DirectDocumentComparison {
+                    operator: chroma_types::DocumentOperator::Regex,
+                    document: "(<cat>".to_string(),
+                }),
+            ],
+        });
+
+        let filter_operator = FilterOperator {
+            query_ids: None,
+            where_clause: Some(where_clause),
+        };
+
+        match filter_operator.run(&filter_input).await {
+            Err(error @ FilterError::Regex { .. }) => {
+                assert!(error.to_string().contains("(<cat>"));
+                assert_eq!(error.field(), Some("where_document"));
+            }
+            result => panic!("Expected an invalid regex error, got {:?}", result),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_simple_and() {
+        let filter_input = setup_filter_input().await;
+
+        let where_sub_clause_1 = Where::DirectWhereComparison(DirectWhereComparison {
+            key: "id".to_string(),
+            comparison: WhereComparison::Primitive(
+                PrimitiveOperator::GreaterThan,
+                MetadataValue::Int(36),
+            ),
+        });
+
+        let where_sub_clause_2 = Where::DirectWhereComparison(DirectWhereComparison {
+            key: "is_even".to_string(),
+            comparison: WhereComparison::Primitive(
+                PrimitiveOperator::Equal,
+                MetadataValue::Bool(false),
+            ),
+        });
+
+        let where_clause = Where::WhereChildren(WhereChildren {
+            operator: BooleanOperator::And,
+            children: vec![where_sub_clause_1, where_sub_clause_2],
+        });
+
+        let filter_operator = FilterOperator {
+            query_ids: None,
+            where_clause: Some(where_clause),
+        };
+
+        let filter_output = filter_operator
+            .run(&filter_input)
+            .await
+            .expect("FilterOperator should not fail");
+
+        assert_eq!(
+            filter_output.log_offset_ids,
+            SignedRoaringBitmap::Include((51..=100).filter(|offset| offset % 2 == 1).collect())
+        );
+        assert_eq!(
+            filter_output.compact_offset_ids,
+            SignedRoaringBitmap::Include((37..=50).filter(|offset| offset % 2 == 1).collect())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_simple_or() {
+        let filter_input = setup_filter_input().await;
+
+        let where_sub_clause_1 = Where::DirectWhereComparison(DirectWhereComparison {
+            key: "modulo_3".to_string(),
+            comparison: WhereComparison::Primitive(PrimitiveOperator::Equal, MetadataValue::Int(0)),
+        });
+
+        let where_sub_clause_2 = Where::DirectWhereComparison(DirectWhereComparison {
+            key: "modulo_3".to_string(),
+            comparison: WhereComparison::Primitive(PrimitiveOperator::Equal, MetadataValue::Int(2)),
+        });
+
+        let where_clause = Where::WhereChildren(WhereChildren {
+            operator: BooleanOperator::Or,
+            children: vec![where_sub_clause_1, where_sub_clause_2],
+        });
+
+        let filter_operator = FilterOperator {
+            query_ids: None,
+            where_clause: Some(where_clause),
+        };
+
+        let filter_output = filter_operator
+            .run(&filter_input)
+            .await
+            .expect("FilterOperator should not fail");
+
+        assert_eq!(
+            filter_output.log_offset_ids,
+            SignedRoaringBitmap::Include((51..=100).filter(|offset| offset % 3 != 1).collect())
+        );
+        assert_eq!(
+            filter_output.compact_offset_ids,
+            SignedRoaringBitmap::Include((21..=50).filter(|offset| offset % 3 != 1).collect())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_complex_filter() {
+        let filter_input = setup_filter_input().await;
+
+        let where_sub_clause_1 = Where::DirectWhereDocumentComparison(DirectDocumentComparison {
+            operator: chroma_types::DocumentOperator::NotContains,
+            document: "<dog>".to_string(),
+        });
+
+        let where_sub_clause_2 = Where::DirectWhereComparison(DirectWhereComparison {
+            key: "id".to_string(),
+            comparison: WhereComparison::Primitive(
+                PrimitiveOperator::LessThan,
+                MetadataValue::Int(72),
+            ),
+        });
+
+        let where_sub_clause_3 = Where::DirectWhereComparison(DirectWhereComparison {
+            key: "modulo_3".to_string(),
+            comparison: WhereComparison::Set(SetOperator::NotIn, MetadataSetValue::Int(vec![0, 1])),
+        });
+
+        let where_clause = Where::WhereChildren(WhereChildren {
+            operator: BooleanOperator::And,
+            children: vec![
+                where_sub_clause_1,
+                Where::WhereChildren(WhereChildren {
+                    operator: BooleanOperator::Or,
+                    children: vec![where_sub_clause_2, where_sub_clause_3],
+                }),
+            ],
+        });
+
+        let filter_operator = FilterOperator {
+            query_ids: Some((0..96).map(int_as_id).collect()),
+            where_clause: Some(where_clause),
+        };
+
+        let filter_output = filter_operator
+            .run(&filter_input)
+            .await
+            .expect("FilterOperator should not fail");
+
+        assert_eq!(
+            filter_output.log_offset_ids,
+            SignedRoaringBitmap::Include(
                 (51..96)
                     .filter(|offset| offset % 5 != 0 && (offset < &72 || offset % 3 == 2))
                     .collect()
@@ -940,4 +2137,597 @@ mod tests {
             SignedRoaringBitmap::Include((21..=50).filter(|offset| offset % 5 != 0).collect())
         );
     }
+
+    #[tokio::test]
+    async fn test_materialized_eq() {
+        let mut test_segment = TestSegment::default();
+        test_segment.collection.metadata = Some(HashMap::from([(
+            MATERIALIZED_FILTERS_KEY.to_string(),
+            MetadataValue::Str(r#"[{"is_even": true, "modulo_3": 0}]"#.to_string()),
+        )]));
+        let generator = LogGenerator {
+            generator: add_delete_generator,
+        };
+        test_segment.populate_with_generator(60, &generator).await;
+
+        let where_clause = Where::conjunction(vec![
+            Where::DirectWhereComparison(DirectWhereComparison {
+                key: "modulo_3".to_string(),
+                comparison: WhereComparison::Primitive(
+                    PrimitiveOperator::Equal,
+                    MetadataValue::Int(0),
+                ),
+            }),
+            Where::DirectWhereComparison(DirectWhereComparison {
+                key: "is_even".to_string(),
+                comparison: WhereComparison::Primitive(
+                    PrimitiveOperator::Equal,
+                    MetadataValue::Bool(true),
+                ),
+            }),
+        ]);
+
+        let metadata_segment_reader = MetadataSegmentReader::from_segment(
+            &test_segment.metadata_segment,
+            &test_segment.blockfile_provider,
+        )
+        .await
+        .expect("MetadataSegmentReader should be created");
+        assert_eq!(
+            metadata_segment_reader
+                .get_materialized_filter(&where_clause)
+                .await
+                .expect("Materialized filter should be readable"),
+            Some((11..=50).filter(|offset| offset % 6 == 0).collect())
+        );
+
+        let filter_input = FilterInput {
+            logs: generator.generate_chunk(61..=120),
+            fetch_log: None,
+            blockfile_provider: test_segment.blockfile_provider,
+            metadata_segment: test_segment.metadata_segment,
+            record_segment: test_segment.record_segment,
+            materialized_logs: SharedMaterializedLogs::default(),
+            memory: MemoryContext::default(),
+            offset_id_cache: OffsetIdCache::default(),
+            scan_limit: None,
+        };
+
+        let filter_operator = FilterOperator {
+            query_ids: None,
+            where_clause: Some(where_clause),
+        };
+
+        let filter_output = filter_operator
+            .run(&filter_input)
+            .await
+            .expect("FilterOperator should not fail");
+
+        assert_eq!(
+            filter_output.log_offset_ids,
+            SignedRoaringBitmap::Include((51..=100).filter(|offset| offset % 6 == 0).collect())
+        );
+        assert_eq!(
+            filter_output.compact_offset_ids,
+            SignedRoaringBitmap::Include((21..=50).filter(|offset| offset % 6 == 0).collect())
+        );
+    }
+
+    /// Adds records with a `tags` list containing the parity and the remainder modulo 3 of the offset
+    fn tag_list_generator(offset: usize) -> OperationRecord {
+        let parity = if offset.is_multiple_of(2) {
+            "even"
+        } else {
+            "odd"
+        };
+        OperationRecord {
+            id: int_as_id(offset),
+            embedding: Some(random_embedding(TEST_EMBEDDING_DIMENSION)),
+            encoding: None,
+            metadata: Some(HashMap::from([(
+                "tags".to_string(),
+                UpdateMetadataValue::List(vec![
+                    MetadataValue::Str(parity.to_string()),
+                    MetadataValue::Str(format!("mod_{}", offset % 3)),
+                ]),
+            )])),
+            document: None,
+            uri: None,
+            idempotency_key: None,
+            embedding_reference: None,
+            operation: Operation::Add,
+        }
+    }
+
+    /// The first 30 records are compacted and the next 30 records are in the logs
+    #[tokio::test]
+    async fn test_list_contains() {
+        let mut test_segment = TestSegment::default();
+        let generator = LogGenerator {
+            generator: tag_list_generator,
+        };
+        test_segment.populate_with_generator(30, &generator).await;
+        let filter_input = FilterInput {
+            logs: generator.generate_chunk(31..=60),
+            fetch_log: None,
+            blockfile_provider: test_segment.blockfile_provider,
+            metadata_segment: test_segment.metadata_segment,
+            record_segment: test_segment.record_segment,
+            materialized_logs: SharedMaterializedLogs::default(),
+            memory: MemoryContext::default(),
+            offset_id_cache: OffsetIdCache::default(),
+            scan_limit: None,
+        };
+
+        let contains_operator = FilterOperator {
+            query_ids: None,
+            where_clause: Some(Where::DirectWhereComparison(DirectWhereComparison {
+                key: "tags".to_string(),
+                comparison: WhereComparison::Primitive(
+                    PrimitiveOperator::Contains,
+                    MetadataValue::Str("mod_0".to_string()),
+                ),
+            })),
+        };
+        let contains_output = contains_operator
+            .run(&filter_input)
+            .await
+            .expect("FilterOperator should not fail");
+        assert_eq!(
+            contains_output.log_offset_ids,
+            SignedRoaringBitmap::Include((31..=60).filter(|offset| offset % 3 == 0).collect())
+        );
+        assert_eq!(
+            contains_output.compact_offset_ids,
+            SignedRoaringBitmap::Include((1..=30).filter(|offset| offset % 3 == 0).collect())
+        );
+
+        let not_contains_operator = FilterOperator {
+            query_ids: None,
+            where_clause: Some(Where::DirectWhereComparison(DirectWhereComparison {
+                key: "tags".to_string(),
+                comparison: WhereComparison::Primitive(
+                    PrimitiveOperator::NotContains,
+                    MetadataValue::Str("even".to_string()),
+                ),
+            })),
+        };
+        let not_contains_output = not_contains_operator
+            .run(&filter_input)
+            .await
+            .expect("FilterOperator should not fail");
+        assert_eq!(
+            not_contains_output.log_offset_ids,
+            SignedRoaringBitmap::Exclude((31..=60).filter(|offset| offset % 2 == 0).collect())
+        );
+        assert_eq!(
+            not_contains_output.compact_offset_ids,
+            SignedRoaringBitmap::Exclude((1..=30).filter(|offset| offset % 2 == 0).collect())
+        );
+    }
+
+    /// Adds records whose `name` is `ChromaDB` for even offsets and `chroma` for odd offsets
+    fn name_generator(offset: usize) -> OperationRecord {
+        let name = if offset.is_multiple_of(2) {
+            "ChromaDB"
+        } else {
+            "chroma"
+        };
+        OperationRecord {
+            id: int_as_id(offset),
+            embedding: Some(random_embedding(TEST_EMBEDDING_DIMENSION)),
+            encoding: None,
+            metadata: Some(HashMap::from([(
+                "name".to_string(),
+                UpdateMetadataValue::Str(name.to_string()),
+            )])),
+            document: None,
+            uri: None,
+            idempotency_key: None,
+            embedding_reference: None,
+            operation: Operation::Add,
+        }
+    }
+
+    /// The first 30 records are compacted and the next 30 records are in the logs
+    #[tokio::test]
+    async fn test_string_matching() {
+        let mut test_segment = TestSegment::default();
+        let generator = LogGenerator {
+            generator: name_generator,
+        };
+        test_segment.populate_with_generator(30, &generator).await;
+        let filter_input = FilterInput {
+            logs: generator.generate_chunk(31..=60),
+            fetch_log: None,
+            blockfile_provider: test_segment.blockfile_provider,
+            metadata_segment: test_segment.metadata_segment,
+            record_segment: test_segment.record_segment,
+            materialized_logs: SharedMaterializedLogs::default(),
+            memory: MemoryContext::default(),
+            offset_id_cache: OffsetIdCache::default(),
+            scan_limit: None,
+        };
+
+        let eq_ci_operator = FilterOperator {
+            query_ids: None,
+            where_clause: Some(Where::DirectWhereComparison(DirectWhereComparison {
+                key: "name".to_string(),
+                comparison: WhereComparison::Primitive(
+                    PrimitiveOperator::EqualCaseInsensitive,
+                    MetadataValue::Str("chromadb".to_string()),
+                ),
+            })),
+        };
+        let eq_ci_output = eq_ci_operator
+            .run(&filter_input)
+            .await
+            .expect("FilterOperator should not fail");
+        assert_eq!(
+            eq_ci_output.log_offset_ids,
+            SignedRoaringBitmap::Include((31..=60).filter(|offset| offset % 2 == 0).collect())
+        );
+        assert_eq!(
+            eq_ci_output.compact_offset_ids,
+            SignedRoaringBitmap::Include((1..=30).filter(|offset| offset % 2 == 0).collect())
+        );
+
+        let starts_with_operator = FilterOperator {
+            query_ids: None,
+            where_clause: Some(Where::DirectWhereComparison(DirectWhereComparison {
+                key: "name".to_string(),
+                comparison: WhereComparison::Primitive(
+                    PrimitiveOperator::StartsWith,
+                    MetadataValue::Str("chr".to_string()),
+                ),
+            })),
+        };
+        let starts_with_output = starts_with_operator
+            .run(&filter_input)
+            .await
+            .expect("FilterOperator should not fail");
+        assert_eq!(
+            starts_with_output.log_offset_ids,
+            SignedRoaringBitmap::Include((31..=60).filter(|offset| offset % 2 == 1).collect())
+        );
+        assert_eq!(
+            starts_with_output.compact_offset_ids,
+            SignedRoaringBitmap::Include((1..=30).filter(|offset| offset % 2 == 1).collect())
+        );
+    }
+
+    /// The first 30 records are compacted into a segment whose strings are not stored
+    /// lowercased, as before they were, and the next 30 records are in the logs
+    #[tokio::test]
+    async fn test_case_insensitive_matching_before_backfill() {
+        let mut test_segment = TestSegment::default();
+        let generator = LogGenerator {
+            generator: name_generator,
+        };
+        test_segment.populate_with_generator(30, &generator).await;
+        let mut metadata_writer = MetadataSegmentWriter::from_segment(
+            &test_segment.metadata_segment,
+            &test_segment.blockfile_provider,
+        )
+        .await
+        .expect("Metadata segment writer should be created");
+        let string_metadata_index_writer = metadata_writer
+            .string_metadata_index_writer
+            .as_ref()
+            .expect("String metadata index writer should exist");
+        for offset_id in 1..=30 {
+            let lowercase = if offset_id % 2 == 0 {
+                "chromadb"
+            } else {
+                "chroma"
+            };
+            string_metadata_index_writer
+                .delete(
+                    &case_insensitive_metadata_prefix("name"),
+                    lowercase,
+                    offset_id,
+                )
+                .await
+                .expect("Lowercased string should be deleted");
+        }
+        string_metadata_index_writer
+            .delete(CASE_INSENSITIVE_MARKER, "", 0)
+            .await
+            .expect("Marker should be deleted");
+        metadata_writer
+            .write_to_blockfiles()
+            .await
+            .expect("Metadata segment should be written");
+        test_segment.metadata_segment.file_path = metadata_writer
+            .commit()
+            .await
+            .expect("Metadata segment should be committed")
+            .flush()
+            .await
+            .expect("Metadata segment should be flushed");
+
+        // The values of the segment are lowercased as they are read
+        let filter_input = FilterInput {
+            logs: generator.generate_chunk(31..=60),
+            fetch_log: None,
+            blockfile_provider: test_segment.blockfile_provider.clone(),
+            metadata_segment: test_segment.metadata_segment.clone(),
+            record_segment: test_segment.record_segment.clone(),
+            materialized_logs: SharedMaterializedLogs::default(),
+            memory: MemoryContext::default(),
+            offset_id_cache: OffsetIdCache::default(),
+            scan_limit: None,
+        };
+        let eq_ci_operator = FilterOperator {
+            query_ids: None,
+            where_clause: Some(Where::DirectWhereComparison(DirectWhereComparison {
+                key: "name".to_string(),
+                comparison: WhereComparison::Primitive(
+                    PrimitiveOperator::EqualCaseInsensitive,
+                    MetadataValue::Str("CHROMADB".to_string()),
+                ),
+            })),
+        };
+        let eq_ci_output = eq_ci_operator
+            .run(&filter_input)
+            .await
+            .expect("FilterOperator should not fail");
+        assert_eq!(
+            eq_ci_output.compact_offset_ids,
+            SignedRoaringBitmap::Include((1..=30).filter(|offset| offset % 2 == 0).collect())
+        );
+
+        // The next compaction stores the strings of the segment lowercased
+        test_segment
+            .compact_log(generator.generate_chunk(31..=60), 30)
+            .await;
+        let metadata_segment_reader = MetadataSegmentReader::from_segment(
+            &test_segment.metadata_segment,
+            &test_segment.blockfile_provider,
+        )
+        .await
+        .expect("Metadata segment reader should be created");
+        let string_metadata_index_reader = metadata_segment_reader
+            .string_metadata_index_reader
+            .as_ref()
+            .expect("String metadata index reader should exist");
+        let lowercase_kw = KeyWrapper::String("chromadb".to_string());
+        assert_eq!(
+            string_metadata_index_reader
+                .get(&case_insensitive_metadata_prefix("name"), &lowercase_kw)
+                .await
+                .expect("Lowercased strings should be read"),
+            (1..=60).filter(|offset| offset % 2 == 0).collect()
+        );
+        assert_eq!(
+            metadata_segment_reader
+                .get_case_insensitive("name", "ChromaDB")
+                .await
+                .expect("Case-insensitive match should not fail"),
+            (1..=60).filter(|offset| offset % 2 == 0).collect()
+        );
+    }
+
+    /// Adds records with a `created_at` timestamp that is `offset - 30` seconds after the epoch
+    fn timestamp_generator(offset: usize) -> OperationRecord {
+        OperationRecord {
+            id: int_as_id(offset),
+            embedding: Some(random_embedding(TEST_EMBEDDING_DIMENSION)),
+            encoding: None,
+            metadata: Some(HashMap::from([(
+                "created_at".to_string(),
+                UpdateMetadataValue::Timestamp((offset as i64 - 30) * 1_000_000),
+            )])),
+            document: None,
+            uri: None,
+            idempotency_key: None,
+            embedding_reference: None,
+            operation: Operation::Add,
+        }
+    }
+
+    /// The first 30 records are compacted and the next 30 records are in the logs
+    #[tokio::test]
+    async fn test_timestamp_range() {
+        let mut test_segment = TestSegment::default();
+        let generator = LogGenerator {
+            generator: timestamp_generator,
+        };
+        test_segment.populate_with_generator(30, &generator).await;
+        let filter_input = FilterInput {
+            logs: generator.generate_chunk(31..=60),
+            fetch_log: None,
+            blockfile_provider: test_segment.blockfile_provider,
+            metadata_segment: test_segment.metadata_segment,
+            record_segment: test_segment.record_segment,
+            materialized_logs: SharedMaterializedLogs::default(),
+            memory: MemoryContext::default(),
+            offset_id_cache: OffsetIdCache::default(),
+            scan_limit: None,
+        };
+
+        let gt_operator = FilterOperator {
+            query_ids: None,
+            where_clause: Some(Where::DirectWhereComparison(DirectWhereComparison {
+                key: "created_at".to_string(),
+                comparison: WhereComparison::Primitive(
+                    PrimitiveOperator::GreaterThan,
+                    MetadataValue::Timestamp(-10_000_000),
+                ),
+            })),
+        };
+        let gt_output = gt_operator
+            .run(&filter_input)
+            .await
+            .expect("FilterOperator should not fail");
+        assert_eq!(
+            gt_output.log_offset_ids,
+            SignedRoaringBitmap::Include((31..=60).collect())
+        );
+        assert_eq!(
+            gt_output.compact_offset_ids,
+            SignedRoaringBitmap::Include((21..=30).collect())
+        );
+
+        let lte_operator = FilterOperator {
+            query_ids: None,
+            where_clause: Some(Where::DirectWhereComparison(DirectWhereComparison {
+                key: "created_at".to_string(),
+                comparison: WhereComparison::Primitive(
+                    PrimitiveOperator::LessThanOrEqual,
+                    MetadataValue::Timestamp(15_000_000),
+                ),
+            })),
+        };
+        let lte_output = lte_operator
+            .run(&filter_input)
+            .await
+            .expect("FilterOperator should not fail");
+        assert_eq!(
+            lte_output.log_offset_ids,
+            SignedRoaringBitmap::Include((31..=45).collect())
+        );
+        assert_eq!(
+            lte_output.compact_offset_ids,
+            SignedRoaringBitmap::Include((1..=30).collect())
+        );
+    }
+
+    /// Adds records with an `author` object that has a `name` and an `age`
+    fn nested_object_generator(offset: usize) -> OperationRecord {
+        OperationRecord {
+            id: int_as_id(offset),
+            embedding: Some(random_embedding(TEST_EMBEDDING_DIMENSION)),
+            encoding: None,
+            metadata: Some(HashMap::from([(
+                "author".to_string(),
+                UpdateMetadataValue::Object(BTreeMap::from([
+                    (
+                        "name".to_string(),
+                        MetadataValue::Str(format!("author_{}", offset % 3)),
+                    ),
+                    ("age".to_string(), MetadataValue::Int(offset as i64)),
+                ])),
+            )])),
+            document: None,
+            uri: None,
+            idempotency_key: None,
+            embedding_reference: None,
+            operation: Operation::Add,
+        }
+    }
+
+    /// The first 30 records are compacted and the next 30 records are in the logs
+    #[tokio::test]
+    async fn test_nested_object_paths() {
+        let mut test_segment = TestSegment::default();
+        let generator = LogGenerator {
+            generator: nested_object_generator,
+        };
+        test_segment.populate_with_generator(30, &generator).await;
+        let filter_input = FilterInput {
+            logs: generator.generate_chunk(31..=60),
+            fetch_log: None,
+            blockfile_provider: test_segment.blockfile_provider,
+            metadata_segment: test_segment.metadata_segment,
+            record_segment: test_segment.record_segment,
+            materialized_logs: SharedMaterializedLogs::default(),
+            memory: MemoryContext::default(),
+            offset_id_cache: OffsetIdCache::default(),
+            scan_limit: None,
+        };
+
+        let filter_operator = FilterOperator {
+            query_ids: None,
+            where_clause: Some(Where::conjunction(vec![
+                Where::DirectWhereComparison(DirectWhereComparison {
+                    key: "author.name".to_string(),
+                    comparison: WhereComparison::Primitive(
+                        PrimitiveOperator::Equal,
+                        MetadataValue::Str("author_0".to_string()),
+                    ),
+                }),
+                Where::DirectWhereComparison(DirectWhereComparison {
+                    key: "author.age".to_string(),
+                    comparison: WhereComparison::Primitive(
+                        PrimitiveOperator::GreaterThan,
+                        MetadataValue::Int(20),
+                    ),
+                }),
+            ])),
+        };
+        let filter_output = filter_operator
+            .run(&filter_input)
+            .await
+            .expect("FilterOperator should not fail");
+        assert_eq!(
+            filter_output.log_offset_ids,
+            SignedRoaringBitmap::Include((31..=60).filter(|offset| offset % 3 == 0).collect())
+        );
+        assert_eq!(
+            filter_output.compact_offset_ids,
+            SignedRoaringBitmap::Include((21..=30).filter(|offset| offset % 3 == 0).collect())
+        );
+    }
+
+    /// The logs delete [1..=10] and add [11..=50]
+    #[tokio::test]
+    async fn test_log_reader_reads_referenced_keys() {
+        let generator = LogGenerator {
+            generator: add_delete_generator,
+        };
+        let logs = generator.generate_chunk(1..=60);
+        let materializer = LogMaterializer::new(None, logs, None);
+        let materialized_logs = materializer
+            .materialize()
+            .await
+            .expect("Logs should be materialized");
+
+        let where_clause = Where::conjunction(vec![
+            Where::DirectWhereComparison(DirectWhereComparison {
+                key: "is_even".to_string(),
+                comparison: WhereComparison::Primitive(
+                    PrimitiveOperator::Equal,
+                    MetadataValue::Bool(true),
+                ),
+            }),
+            Where::DirectWhereComparison(DirectWhereComparison {
+                key: "absent".to_string(),
+                comparison: WhereComparison::Primitive(
+                    PrimitiveOperator::Equal,
+                    MetadataValue::Int(0),
+                ),
+            }),
+        ]);
+        let references = WhereReferences::from_where(&where_clause);
+        let metadata_log_reader = MetadataLogReader::new(
+            &materialized_logs,
+            Some(&references),
+            &TokenizerConfig::default(),
+        );
+        assert_eq!(
+            metadata_log_reader
+                .compact_metadata
+                .keys()
+                .map(AsRef::as_ref)
+                .collect::<Vec<&str>>(),
+            vec!["is_even"]
+        );
+        assert!(metadata_log_reader.document.is_empty());
+        assert_eq!(
+            metadata_log_reader
+                .get(
+                    "is_even",
+                    &MetadataValue::Bool(true),
+                    &PrimitiveOperator::Equal
+                )
+                .expect("Metadata should be read"),
+            (11..=50).filter(|offset| offset % 2 == 0).collect()
+        );
+
+        let metadata_log_reader =
+            MetadataLogReader::new(&materialized_logs, None, &TokenizerConfig::default());
+        assert_eq!(metadata_log_reader.compact_metadata.len(), 3);
+        assert_eq!(metadata_log_reader.document.len(), 40);
+    }
 }
@@ -1,3 +1,4 @@
+use crate::compactor::metrics::CompactionMetrics;
 use crate::segment::metadata_segment::MetadataSegmentWriter;
 use crate::segment::SegmentFlusher;
 use crate::{
@@ -9,7 +10,7 @@ use crate::{
 };
 use async_trait::async_trait;
 use chroma_error::ChromaError;
-use chroma_types::SegmentFlushInfo;
+use chroma_types::{CollectionUuid, SegmentFlushInfo, SegmentType};
 use std::sync::Arc;
 use tracing::Instrument;
 
@@ -24,6 +25,7 @@ impl FlushS3Operator {
 
 #[derive(Debug)]
 pub struct FlushS3Input {
+    collection_id: CollectionUuid,
     record_segment_writer: RecordSegmentWriter,
     hnsw_segment_writer: Box<DistributedHNSWSegmentWriter>,
     metadata_segment_writer: MetadataSegmentWriter<'static>,
@@ -31,11 +33,13 @@ pub struct FlushS3Input {
 
 impl FlushS3Input {
     pub fn new(
+        collection_id: CollectionUuid,
         record_segment_writer: RecordSegmentWriter,
         hnsw_segment_writer: Box<DistributedHNSWSegmentWriter>,
         metadata_segment_writer: MetadataSegmentWriter<'static>,
     ) -> Self {
         Self {
+            collection_id,
             record_segment_writer,
             hnsw_segment_writer,
             metadata_segment_writer,
@@ -77,10 +81,17 @@ impl Operator<FlushS3Input, FlushS3Output> for FlushS3Operator {
                 return Err(Box::new(e));
             }
         }
+        let metrics = CompactionMetrics::get();
         let record_segment_flusher = input.record_segment_writer.clone().commit().await;
         let record_segment_flush_info = match record_segment_flusher {
             Ok(flusher) => {
                 let segment_id = input.record_segment_writer.id;
+                metrics.record_flush(
+                    input.collection_id,
+                    SegmentType::BlockfileRecord,
+                    &flusher.blockfile_flushers(),
+                );
+                let metadata = flusher.metadata();
                 let res = flusher
                     .flush()
                     .instrument(tracing::info_span!("Flush record segment"))
@@ -91,6 +102,7 @@ impl Operator<FlushS3Input, FlushS3Output> for FlushS3Operator {
                         SegmentFlushInfo {
                             segment_id,
                             file_paths: res,
+                            metadata,
                         }
                     }
                     Err(e) => {
@@ -109,6 +121,12 @@ impl Operator<FlushS3Input, FlushS3Output> for FlushS3Operator {
         let hnsw_segment_flush_info = match hnsw_segment_flusher {
             Ok(flusher) => {
                 let segment_id = input.hnsw_segment_writer.id;
+                metrics.record_flush(
+                    input.collection_id,
+                    SegmentType::HnswDistributed,
+                    &flusher.blockfile_flushers(),
+                );
+                let metadata = flusher.metadata();
                 let res = flusher
                     .flush()
                     .instrument(tracing::info_span!("Flush HNSW segment"))
@@ -119,6 +137,7 @@ impl Operator<FlushS3Input, FlushS3Output> for FlushS3Operator {
                         SegmentFlushInfo {
                             segment_id,
                             file_paths: res,
+                            metadata,
                         }
                     }
                     Err(e) => {
@@ -137,6 +156,12 @@ impl Operator<FlushS3Input, FlushS3Output> for FlushS3Operator {
         let metadata_segment_flush_info = match metadata_segment_flusher {
             Ok(flusher) => {
                 let segment_id = input.metadata_segment_writer.id;
+                metrics.record_flush(
+                    input.collection_id,
+                    SegmentType::BlockfileMetadata,
+                    &flusher.blockfile_flushers(),
+                );
+                let metadata = flusher.metadata();
                 let res = flusher
                     .flush()
                     .instrument(tracing::info_span!("Flush metadata segment"))
@@ -147,6 +172,7 @@ impl Operator<FlushS3Input, FlushS3Output> for FlushS3Operator {
                         SegmentFlushInfo {
                             segment_id,
                             file_paths: res,
+                            metadata,
                         }
                     }
                     Err(e) => {
@@ -105,7 +105,7 @@ impl Operator<GetVectorsOperatorInput, GetVectorsOperatorOutput> for GetVectorsO
             Ok(reader) => Some(reader),
             Err(e) => match *e {
                 record_segment::RecordSegmentReaderCreationError::UninitializedSegment => None,
-                record_segment::RecordSegmentReaderCreationError::BlockfileOpenError(_) => {
+                record_segment::RecordSegmentReaderCreationError::BlockfileOpenError(..) => {
                     return Err(GetVectorsOperatorError::RecordSegmentReaderCreation(*e))
                 }
                 record_segment::RecordSegmentReaderCreationError::InvalidNumberOfFiles => {
@@ -154,16 +154,15 @@ impl Operator<GetVectorsOperatorInput, GetVectorsOperatorOutput> for GetVectorsO
         // Search the record segment for the remaining user ids
         if !remaining_search_user_ids.is_empty() {
             if let Some(reader) = record_segment_reader {
-                for user_id in remaining_search_user_ids.iter() {
-                    let read_data = reader.get_data_and_offset_id_for_user_id(user_id).await;
-                    match read_data {
-                        Ok(Some((record, _))) => {
-                            output_vectors.insert(record.id.to_string(), record.embedding.to_vec());
-                        }
-                        Ok(None) => {}
-                        Err(_) => {
-                            // If the user id is not found in the record segment, we do not add it to the output
-                        }
+                let user_ids = remaining_search_user_ids
+                    .iter()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>();
+                // If the user ids cannot be read from the record segment, we do not add them to the output
+                if let Ok(read_data) = reader.get_data_and_offset_ids_for_user_ids(&user_ids).await
+                {
+                    for (record, _) in read_data.into_iter().flatten() {
+                        output_vectors.insert(record.id.to_string(), record.embedding.to_vec());
                     }
                 }
             }
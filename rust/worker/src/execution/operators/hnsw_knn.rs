@@ -1,18 +1,30 @@
 use crate::segment::record_segment::RecordSegmentReaderCreationError;
 use crate::segment::{LogMaterializer, LogMaterializerError, MaterializedLogRecord};
 use crate::{
-    execution::operator::Operator,
+    execution::{
+        memory::{MemoryContext, MemoryError},
+        operator::{Deadline, DeadlineError, Operator},
+        operators::{
+            knn::RecordDistance,
+            knn_hnsw::{initial_overfetch, MAX_OVERFETCH_FACTOR, QUANTIZED_OVERSAMPLING_FACTOR},
+        },
+    },
     segment::{
-        distributed_hnsw_segment::DistributedHNSWSegmentReader, record_segment::RecordSegmentReader,
+        distributed_hnsw_segment::DistributedHNSWSegmentReader, offset_id_cache::OffsetIdCache,
+        record_segment::RecordSegmentReader,
     },
 };
 use async_trait::async_trait;
 use chroma_blockstore::provider::BlockfileProvider;
+use chroma_distance::{normalize, DistanceAccumulation, DistanceFunction};
 use chroma_error::{ChromaError, ErrorCodes};
+use chroma_types::CollectionUuid;
 use chroma_types::Segment;
 use chroma_types::{Chunk, LogRecord, MaterializedLogOperation};
-use std::collections::HashSet;
+use futures::TryStreamExt;
+use std::collections::{BinaryHeap, HashSet};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 use tracing::{Instrument, Span};
 
@@ -21,19 +33,39 @@ pub struct HnswKnnOperator {}
 
 #[derive(Debug)]
 pub struct HnswKnnOperatorInput {
-    pub segment: Box<DistributedHNSWSegmentReader>,
+    /// The HNSW index to query, or `None` if it could not be loaded, in which case the
+    /// record segment is searched by brute force
+    pub segment: Option<Box<DistributedHNSWSegmentReader>>,
+    pub distance_function: DistanceFunction,
+    /// The precision in which the exact distances to the records are accumulated
+    pub accumulation: DistanceAccumulation,
     pub query: Vec<f32>,
     pub k: usize,
+    /// The records further than this distance from the query are dropped, if set
+    pub max_distance: Option<f32>,
+    /// The time after which the brute force search of the record segment stops and returns
+    /// the nearest records among those searched so far, if set
+    pub time_limit: Option<Duration>,
     pub record_segment: Segment,
     pub blockfile_provider: BlockfileProvider,
     pub allowed_ids: Arc<[String]>,
     pub logs: Chunk<LogRecord>,
+    /// The offset ids that the allowed ids resolve to, shared by the searches of all the query
+    /// vectors of a query
+    pub offset_id_cache: OffsetIdCache,
+    /// The deadline of the query, checked before each search of the index and while the
+    /// record segment is searched
+    pub deadline: Deadline,
+    /// The memory context of the query, which accounts for the materialized logs
+    pub memory: MemoryContext,
 }
 
 #[derive(Debug)]
 pub struct HnswKnnOperatorOutput {
     pub offset_ids: Vec<usize>,
     pub distances: Vec<f32>,
+    /// Whether the time limit was reached before the whole record segment was searched
+    pub truncated: bool,
 }
 
 #[derive(Error, Debug)]
@@ -48,6 +80,10 @@ pub enum HnswKnnOperatorError {
     LogMaterializationError(#[from] LogMaterializerError),
     #[error("Error querying HNSW {0}")]
     QueryError(#[from] Box<dyn ChromaError>),
+    #[error(transparent)]
+    Deadline(#[from] DeadlineError),
+    #[error("Error accounting for query memory: {0}")]
+    Memory(#[from] MemoryError),
 }
 
 impl ChromaError for HnswKnnOperatorError {
@@ -58,6 +94,8 @@ impl ChromaError for HnswKnnOperatorError {
             HnswKnnOperatorError::InvalidAllowedAndDisallowedIds => ErrorCodes::InvalidArgument,
             HnswKnnOperatorError::LogMaterializationError(e) => e.code(),
             HnswKnnOperatorError::QueryError(e) => e.code(),
+            HnswKnnOperatorError::Deadline(e) => e.code(),
+            HnswKnnOperatorError::Memory(e) => e.code(),
         }
     }
 }
@@ -94,6 +132,151 @@ impl HnswKnnOperator {
         Ok(disallowed_ids)
     }
 
+    /// Searches the embeddings in the record segment by brute force, which is used when the
+    /// HNSW index is unavailable, and returns whether the time limit cut the search short.
+    /// The query is expected to be normalized for cosine distance
+    async fn brute_force_record_segment(
+        &self,
+        input: &HnswKnnOperatorInput,
+        record_segment_reader: &RecordSegmentReader<'_>,
+        allowed_offset_ids: &[usize],
+        disallowed_offset_ids: &[usize],
+    ) -> Result<((Vec<usize>, Vec<f32>), bool), Box<dyn ChromaError>> {
+        let time_limit_deadline = input
+            .time_limit
+            .map(|time_limit| Instant::now() + time_limit);
+        let mut truncated = false;
+        let allowed_offset_ids: HashSet<&usize> = allowed_offset_ids.iter().collect();
+        let disallowed_offset_ids: HashSet<&usize> = disallowed_offset_ids.iter().collect();
+        let mut max_heap = BinaryHeap::with_capacity(input.k);
+        let mut records = std::pin::pin!(record_segment_reader.scan(None));
+        while let Some((offset_id, record)) = records.try_next().await? {
+            check_deadline(&input.deadline)?;
+            if time_limit_deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                truncated = true;
+                break;
+            }
+            let offset_id_key = offset_id as usize;
+            if (!allowed_offset_ids.is_empty() && !allowed_offset_ids.contains(&offset_id_key))
+                || disallowed_offset_ids.contains(&offset_id_key)
+            {
+                continue;
+            }
+            let record_vector;
+            let record_embedding = if let DistanceFunction::Cosine = input.distance_function {
+                record_vector = normalize(record.embedding);
+                &record_vector
+            } else {
+                record.embedding
+            };
+            let distance = RecordDistance {
+                offset_id,
+                measure: input.distance_function.distance_with_accumulation(
+                    &input.query,
+                    record_embedding,
+                    input.accumulation,
+                ),
+            };
+            if max_heap.len() < input.k {
+                max_heap.push(distance);
+            } else if let Some(furthest_distance) = max_heap.peek() {
+                if &distance < furthest_distance {
+                    max_heap.pop();
+                    max_heap.push(distance);
+                }
+            }
+        }
+        Ok((
+            max_heap
+                .into_sorted_vec()
+                .into_iter()
+                .map(|distance| (distance.offset_id as usize, distance.measure))
+                .unzip(),
+            truncated,
+        ))
+    }
+
+    /// Replaces the distances from the index with the exact distances to the embeddings in the
+    /// record segment, accumulated in the precision of the query, and keeps the nearest `k`
+    /// records. The records that are not in the record segment are dropped. The query is
+    /// expected to be normalized for cosine distance
+    async fn rescore(
+        &self,
+        input: &HnswKnnOperatorInput,
+        record_segment_reader: &RecordSegmentReader<'_>,
+        offset_ids: Vec<usize>,
+    ) -> Result<(Vec<usize>, Vec<f32>), Box<dyn ChromaError>> {
+        check_deadline(&input.deadline)?;
+        let offset_ids = offset_ids
+            .into_iter()
+            .map(|offset_id| offset_id as u32)
+            .collect::<Vec<_>>();
+        let records = record_segment_reader
+            .get_data_for_offset_ids_if_present(&offset_ids)
+            .await?;
+        let mut rescored_distances = offset_ids
+            .into_iter()
+            .zip(records)
+            .filter_map(|(offset_id, record)| Some((offset_id, record?)))
+            .map(|(offset_id, record)| {
+                let measure = match input.distance_function {
+                    DistanceFunction::Cosine => input.distance_function.distance_with_accumulation(
+                        &input.query,
+                        &normalize(record.embedding),
+                        input.accumulation,
+                    ),
+                    _ => input.distance_function.distance_with_accumulation(
+                        &input.query,
+                        record.embedding,
+                        input.accumulation,
+                    ),
+                };
+                RecordDistance { offset_id, measure }
+            })
+            .collect::<Vec<_>>();
+        rescored_distances.sort();
+        rescored_distances.truncate(input.k);
+        Ok(rescored_distances
+            .into_iter()
+            .map(|distance| (distance.offset_id as usize, distance.measure))
+            .unzip())
+    }
+
+    /// Queries the index for the nearest `needed` records that pass the filter. The filter is
+    /// applied while the graph is traversed, so a selective filter can end the search before
+    /// enough allowed records are found, in which case the search is widened until enough
+    /// records are found, all the eligible records are found, or the budget is spent
+    fn query_segment(
+        &self,
+        input: &HnswKnnOperatorInput,
+        segment: &DistributedHNSWSegmentReader,
+        needed: usize,
+        allowed_offset_ids: &[usize],
+        disallowed_offset_ids: &[usize],
+    ) -> Result<(Vec<usize>, Vec<f32>), Box<dyn ChromaError>> {
+        let total = segment.len();
+        let eligible = if allowed_offset_ids.is_empty() {
+            total.saturating_sub(disallowed_offset_ids.len())
+        } else {
+            allowed_offset_ids.len()
+        };
+        let budget = needed
+            .saturating_mul(MAX_OVERFETCH_FACTOR)
+            .min(total.max(needed));
+        let mut k = initial_overfetch(needed, eligible, total).min(budget);
+        loop {
+            check_deadline(&input.deadline)?;
+            let (mut offset_ids, mut distances) =
+                segment.query(&input.query, k, allowed_offset_ids, disallowed_offset_ids)?;
+            if offset_ids.len() >= needed.min(eligible) || k >= budget {
+                offset_ids.truncate(needed);
+                distances.truncate(needed);
+                return Ok((offset_ids, distances));
+            }
+            k = k.saturating_mul(2).min(budget);
+        }
+    }
+
     // Validate that the allowed ids are not in the disallowed ids
     fn validate_allowed_and_disallowed_ids(
         &self,
@@ -111,6 +294,12 @@ impl HnswKnnOperator {
     }
 }
 
+fn check_deadline(deadline: &Deadline) -> Result<(), Box<dyn ChromaError>> {
+    deadline
+        .check()
+        .map_err(|e| Box::new(HnswKnnOperatorError::Deadline(e)) as Box<dyn ChromaError>)
+}
+
 #[async_trait]
 impl Operator<HnswKnnOperatorInput, HnswKnnOperatorOutput> for HnswKnnOperator {
     type Error = Box<dyn ChromaError>;
@@ -123,6 +312,7 @@ impl Operator<HnswKnnOperatorInput, HnswKnnOperatorOutput> for HnswKnnOperator {
         &self,
         input: &HnswKnnOperatorInput,
     ) -> Result<HnswKnnOperatorOutput, Self::Error> {
+        check_deadline(&input.deadline)?;
         let record_segment_reader = match RecordSegmentReader::from_segment(
             &input.record_segment,
             &input.blockfile_provider,
@@ -139,6 +329,7 @@ impl Operator<HnswKnnOperatorInput, HnswKnnOperatorOutput> for HnswKnnOperator {
                     return Ok(HnswKnnOperatorOutput {
                         offset_ids: vec![],
                         distances: vec![],
+                        truncated: false,
                     });
                 }
                 _ => {
@@ -152,6 +343,10 @@ impl Operator<HnswKnnOperatorInput, HnswKnnOperatorOutput> for HnswKnnOperator {
             input.logs.clone(),
             None,
         );
+        let reserved = input
+            .memory
+            .reserve_log_materialization(&input.logs)
+            .map_err(|e| Box::new(HnswKnnOperatorError::Memory(e)) as Box<dyn ChromaError>)?;
         let logs = match log_materializer
             .materialize()
             .instrument(tracing::trace_span!(parent: Span::current(), "Materialize logs"))
@@ -163,6 +358,8 @@ impl Operator<HnswKnnOperatorInput, HnswKnnOperatorOutput> for HnswKnnOperator {
                 return Err(Box::new(HnswKnnOperatorError::LogMaterializationError(e)));
             }
         };
+        input.memory.settle_log_materialization(reserved, &logs);
+        check_deadline(&input.deadline)?;
         let mut remaining_allowed_ids: HashSet<&String> =
             HashSet::from_iter(input.allowed_ids.iter());
         for (log, _) in logs.iter() {
@@ -175,27 +372,32 @@ impl Operator<HnswKnnOperatorInput, HnswKnnOperatorOutput> for HnswKnnOperator {
             return Ok(HnswKnnOperatorOutput {
                 offset_ids: vec![],
                 distances: vec![],
+                truncated: false,
             });
         }
-        let mut allowed_offset_ids = Vec::new();
-        for user_id in remaining_allowed_ids {
-            let offset_id = record_segment_reader
-                .get_offset_id_for_user_id(user_id)
-                .await;
-            match offset_id {
-                Ok(Some(offset_id)) => allowed_offset_ids.push(offset_id),
-                Ok(None) => {
-                    return Err(Box::new(HnswKnnOperatorError::RecordSegmentReadError));
-                }
-                Err(e) => {
-                    tracing::error!(
-                        "[HnswKnnOperation]: Record segment read error for allowed ids {:?}",
-                        e
-                    );
+        let remaining_allowed_ids = remaining_allowed_ids
+            .into_iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        let allowed_offset_ids = match input
+            .offset_id_cache
+            .get_offset_ids_for_user_ids(&record_segment_reader, &remaining_allowed_ids)
+            .await
+        {
+            Ok(offset_ids) => match offset_ids.into_iter().collect::<Option<Vec<_>>>() {
+                Some(allowed_offset_ids) => allowed_offset_ids,
+                None => {
                     return Err(Box::new(HnswKnnOperatorError::RecordSegmentReadError));
                 }
+            },
+            Err(e) => {
+                tracing::error!(
+                    "[HnswKnnOperation]: Record segment read error for allowed ids {:?}",
+                    e
+                );
+                return Err(Box::new(HnswKnnOperatorError::RecordSegmentReadError));
             }
-        }
+        };
         tracing::info!(
             "[HnswKnnOperation]: Allowed {} offset ids",
             allowed_offset_ids.len()
@@ -231,12 +433,65 @@ impl Operator<HnswKnnOperatorInput, HnswKnnOperatorOutput> for HnswKnnOperator {
         let disallowed_offset_ids: Vec<usize> =
             disallowed_offset_ids.iter().map(|&x| x as usize).collect();
 
-        let query_results = input.segment.query(
-            &input.query,
-            input.k,
-            &allowed_offset_ids,
-            &disallowed_offset_ids,
-        );
+        let mut truncated = false;
+        let query_results = match &input.segment {
+            // The distances of a quantized index are approximate, so more candidates are
+            // fetched and rescored before they are merged with the distances of the logs
+            Some(segment) if segment.quantization().is_some() => {
+                match self.query_segment(
+                    input,
+                    segment,
+                    input.k.saturating_mul(QUANTIZED_OVERSAMPLING_FACTOR),
+                    &allowed_offset_ids,
+                    &disallowed_offset_ids,
+                ) {
+                    Ok((offset_ids, _)) => {
+                        self.rescore(input, &record_segment_reader, offset_ids)
+                            .await
+                    }
+                    Err(e) => Err(e),
+                }
+            }
+            // The index accumulates the distances in single precision, so they are rescored
+            // if the query accumulates them in double precision
+            Some(segment) if input.accumulation == DistanceAccumulation::F64 => {
+                match self.query_segment(
+                    input,
+                    segment,
+                    input.k,
+                    &allowed_offset_ids,
+                    &disallowed_offset_ids,
+                ) {
+                    Ok((offset_ids, _)) => {
+                        self.rescore(input, &record_segment_reader, offset_ids)
+                            .await
+                    }
+                    Err(e) => Err(e),
+                }
+            }
+            Some(segment) => self.query_segment(
+                input,
+                segment,
+                input.k,
+                &allowed_offset_ids,
+                &disallowed_offset_ids,
+            ),
+            None => match self
+                .brute_force_record_segment(
+                    input,
+                    &record_segment_reader,
+                    &allowed_offset_ids,
+                    &disallowed_offset_ids,
+                )
+                .await
+            {
+                Ok((results, scan_truncated)) => {
+                    truncated = scan_truncated;
+                    Ok(results)
+                }
+                Err(e) => Err(e),
+            },
+        };
         let (offset_ids, distances) = match query_results {
             Ok(results) => results,
             Err(e) => {
@@ -244,10 +499,537 @@ impl Operator<HnswKnnOperatorInput, HnswKnnOperatorOutput> for HnswKnnOperator {
                 return Err(Box::new(HnswKnnOperatorError::QueryError(e)));
             }
         };
+        let (offset_ids, distances) = match input.max_distance {
+            Some(max_distance) => offset_ids
+                .into_iter()
+                .zip(distances)
+                .filter(|(_, distance)| *distance <= max_distance)
+                .unzip(),
+            None => (offset_ids, distances),
+        };
 
         Ok(HnswKnnOperatorOutput {
             offset_ids,
             distances,
+            truncated,
         })
     }
+
+    fn collection_id(&self, input: &HnswKnnOperatorInput) -> Option<CollectionUuid> {
+        Some(input.record_segment.collection)
+    }
+
+    fn output_cardinality(&self, output: &HnswKnnOperatorOutput) -> Option<usize> {
+        Some(output.offset_ids.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Arc, time::Duration};
+
+    use chroma_distance::{DistanceAccumulation, DistanceFunction};
+    use chroma_error::{ChromaError, ErrorCodes};
+    use chroma_types::{Chunk, MetadataValue};
+    use futures::TryStreamExt;
+
+    use crate::{
+        execution::{
+            memory::MemoryContext,
+            operator::{Deadline, Operator},
+        },
+        log::test::{random_embedding, upsert_generator, LogGenerator, TEST_EMBEDDING_DIMENSION},
+        segment::{
+            distributed_hnsw_segment::DistributedHNSWSegmentReader, offset_id_cache::OffsetIdCache,
+            record_segment::RecordSegmentReader, test::TestSegment,
+        },
+    };
+
+    use super::{HnswKnnOperator, HnswKnnOperatorInput};
+
+    #[tokio::test]
+    async fn test_quantized_distances_are_rescored() {
+        let mut test_segment = TestSegment::default();
+        test_segment.vector_segment.metadata = Some(HashMap::from([(
+            "hnsw:quantization".to_string(),
+            MetadataValue::Str("int8".to_string()),
+        )]));
+        test_segment
+            .populate_with_vectors(
+                100,
+                &LogGenerator {
+                    generator: upsert_generator,
+                },
+            )
+            .await;
+        let record_segment_reader = RecordSegmentReader::from_segment(
+            &test_segment.record_segment,
+            &test_segment.blockfile_provider,
+        )
+        .await
+        .expect("Record segment reader should be created");
+        let embeddings: Vec<(u32, Vec<f32>)> = record_segment_reader
+            .scan(None)
+            .map_ok(|(offset_id, record)| (offset_id, record.embedding.to_vec()))
+            .try_collect()
+            .await
+            .expect("Record segment should be scanned");
+        let hnsw_segment_reader = DistributedHNSWSegmentReader::from_segment(
+            &test_segment.vector_segment,
+            TEST_EMBEDDING_DIMENSION,
+            test_segment.hnsw_provider.clone(),
+        )
+        .await
+        .expect("Hnsw segment reader should be created");
+        assert!(hnsw_segment_reader.quantization().is_some());
+
+        let query = random_embedding(TEST_EMBEDDING_DIMENSION);
+        let hnsw_knn_input = HnswKnnOperatorInput {
+            segment: Some(hnsw_segment_reader),
+            distance_function: DistanceFunction::Euclidean,
+            accumulation: DistanceAccumulation::default(),
+            query: query.clone(),
+            k: 10,
+            max_distance: None,
+            time_limit: None,
+            record_segment: test_segment.record_segment.clone(),
+            blockfile_provider: test_segment.blockfile_provider.clone(),
+            allowed_ids: Arc::new([]),
+            logs: Chunk::new(Vec::new().into()),
+            offset_id_cache: OffsetIdCache::new(None),
+            deadline: Deadline::default(),
+            memory: MemoryContext::default(),
+        };
+        let hnsw_knn_output = HnswKnnOperator {}
+            .run(&hnsw_knn_input)
+            .await
+            .expect("HnswKnnOperator should not fail");
+
+        // The distances are exact rather than those of the quantized embeddings
+        let mut expected = embeddings
+            .iter()
+            .map(|(offset_id, embedding)| {
+                (
+                    *offset_id as usize,
+                    DistanceFunction::Euclidean.distance(&query, embedding),
+                )
+            })
+            .collect::<Vec<_>>();
+        expected.sort_by(|a, b| a.1.total_cmp(&b.1));
+        expected.truncate(10);
+        let (expected_offset_ids, expected_distances): (Vec<_>, Vec<_>) =
+            expected.into_iter().unzip();
+        assert_eq!(hnsw_knn_output.offset_ids, expected_offset_ids);
+        assert_eq!(hnsw_knn_output.distances, expected_distances);
+    }
+
+    #[tokio::test]
+    async fn test_brute_force_without_hnsw_segment() {
+        let mut test_segment = TestSegment::default();
+        test_segment
+            .populate_with_vectors(
+                100,
+                &LogGenerator {
+                    generator: upsert_generator,
+                },
+            )
+            .await;
+        let record_segment_reader = RecordSegmentReader::from_segment(
+            &test_segment.record_segment,
+            &test_segment.blockfile_provider,
+        )
+        .await
+        .expect("Record segment reader should be created");
+        let embeddings: Vec<(u32, Vec<f32>)> = record_segment_reader
+            .scan(None)
+            .map_ok(|(offset_id, record)| (offset_id, record.embedding.to_vec()))
+            .try_collect()
+            .await
+            .expect("Record segment should be scanned");
+
+        // The index could not be loaded, so the record segment is searched instead
+        let query = random_embedding(TEST_EMBEDDING_DIMENSION);
+        let hnsw_knn_input = HnswKnnOperatorInput {
+            segment: None,
+            distance_function: DistanceFunction::Euclidean,
+            accumulation: DistanceAccumulation::default(),
+            query: query.clone(),
+            k: 10,
+            max_distance: None,
+            time_limit: None,
+            record_segment: test_segment.record_segment.clone(),
+            blockfile_provider: test_segment.blockfile_provider.clone(),
+            allowed_ids: Arc::new([]),
+            logs: Chunk::new(Vec::new().into()),
+            offset_id_cache: OffsetIdCache::new(None),
+            deadline: Deadline::default(),
+            memory: MemoryContext::default(),
+        };
+        let hnsw_knn_output = HnswKnnOperator {}
+            .run(&hnsw_knn_input)
+            .await
+            .expect("HnswKnnOperator should not fail");
+
+        let mut expected = embeddings
+            .iter()
+            .map(|(offset_id, embedding)| {
+                (
+                    *offset_id as usize,
+                    DistanceFunction::Euclidean.distance(&query, embedding),
+                )
+            })
+            .collect::<Vec<_>>();
+        expected.sort_by(|a, b| a.1.total_cmp(&b.1));
+        expected.truncate(10);
+        let (expected_offset_ids, expected_distances): (Vec<_>, Vec<_>) =
+            expected.into_iter().unzip();
+        assert_eq!(hnsw_knn_output.offset_ids, expected_offset_ids);
+        assert_eq!(hnsw_knn_output.distances, expected_distances);
+    }
+
+    #[tokio::test]
+    async fn test_brute_force_without_hnsw_segment_max_distance() {
+        let mut test_segment = TestSegment::default();
+        test_segment
+            .populate_with_vectors(
+                100,
+                &LogGenerator {
+                    generator: upsert_generator,
+                },
+            )
+            .await;
+        let record_segment_reader = RecordSegmentReader::from_segment(
+            &test_segment.record_segment,
+            &test_segment.blockfile_provider,
+        )
+        .await
+        .expect("Record segment reader should be created");
+        let embeddings: Vec<(u32, Vec<f32>)> = record_segment_reader
+            .scan(None)
+            .map_ok(|(offset_id, record)| (offset_id, record.embedding.to_vec()))
+            .try_collect()
+            .await
+            .expect("Record segment should be scanned");
+
+        let query = random_embedding(TEST_EMBEDDING_DIMENSION);
+        let mut expected = embeddings
+            .iter()
+            .map(|(offset_id, embedding)| {
+                (
+                    *offset_id as usize,
+                    DistanceFunction::Euclidean.distance(&query, embedding),
+                )
+            })
+            .collect::<Vec<_>>();
+        expected.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let max_distance = (expected[2].1 + expected[3].1) / 2.0;
+        expected.truncate(3);
+        let (expected_offset_ids, expected_distances): (Vec<_>, Vec<_>) =
+            expected.into_iter().unzip();
+
+        // The records further than the maximum distance are dropped
+        let hnsw_knn_input = HnswKnnOperatorInput {
+            segment: None,
+            distance_function: DistanceFunction::Euclidean,
+            accumulation: DistanceAccumulation::default(),
+            query,
+            k: 10,
+            max_distance: Some(max_distance),
+            time_limit: None,
+            record_segment: test_segment.record_segment.clone(),
+            blockfile_provider: test_segment.blockfile_provider.clone(),
+            allowed_ids: Arc::new([]),
+            logs: Chunk::new(Vec::new().into()),
+            offset_id_cache: OffsetIdCache::new(None),
+            deadline: Deadline::default(),
+            memory: MemoryContext::default(),
+        };
+        let hnsw_knn_output = HnswKnnOperator {}
+            .run(&hnsw_knn_input)
+            .await
+            .expect("HnswKnnOperator should not fail");
+        assert_eq!(hnsw_knn_output.offset_ids, expected_offset_ids);
+        assert_eq!(hnsw_knn_output.distances, expected_distances);
+        assert!(!hnsw_knn_output.truncated);
+    }
+
+    #[tokio::test]
+    async fn test_brute_force_without_hnsw_segment_time_limit() {
+        let mut test_segment = TestSegment::default();
+        test_segment
+            .populate_with_vectors(
+                100,
+                &LogGenerator {
+                    generator: upsert_generator,
+                },
+            )
+            .await;
+
+        // The search of the record segment stops once the time limit is reached
+        let hnsw_knn_input = HnswKnnOperatorInput {
+            segment: None,
+            distance_function: DistanceFunction::Euclidean,
+            accumulation: DistanceAccumulation::default(),
+            query: random_embedding(TEST_EMBEDDING_DIMENSION),
+            k: 10,
+            max_distance: None,
+            time_limit: Some(Duration::ZERO),
+            record_segment: test_segment.record_segment.clone(),
+            blockfile_provider: test_segment.blockfile_provider.clone(),
+            allowed_ids: Arc::new([]),
+            logs: Chunk::new(Vec::new().into()),
+            offset_id_cache: OffsetIdCache::new(None),
+            deadline: Deadline::default(),
+            memory: MemoryContext::default(),
+        };
+        let hnsw_knn_output = HnswKnnOperator {}
+            .run(&hnsw_knn_input)
+            .await
+            .expect("HnswKnnOperator should not fail");
+        assert!(hnsw_knn_output.truncated);
+        assert!(hnsw_knn_output.offset_ids.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_selective_filter_finds_allowed_records() {
+        let mut test_segment = TestSegment::default();
+        test_segment
+            .populate_with_vectors(
+                100,
+                &LogGenerator {
+                    generator: upsert_generator,
+                },
+            )
+            .await;
+        let record_segment_reader = RecordSegmentReader::from_segment(
+            &test_segment.record_segment,
+            &test_segment.blockfile_provider,
+        )
+        .await
+        .expect("Record segment reader should be created");
+        let records: Vec<(u32, String, Vec<f32>)> = record_segment_reader
+            .scan(None)
+            .map_ok(|(offset_id, record)| {
+                (offset_id, record.id.to_string(), record.embedding.to_vec())
+            })
+            .try_collect()
+            .await
+            .expect("Record segment should be scanned");
+        let hnsw_segment_reader = DistributedHNSWSegmentReader::from_segment(
+            &test_segment.vector_segment,
+            TEST_EMBEDDING_DIMENSION,
+            test_segment.hnsw_provider.clone(),
+        )
+        .await
+        .expect("Hnsw segment reader should be created");
+
+        // Only every tenth record passes the filter
+        let allowed = records.iter().step_by(10).collect::<Vec<_>>();
+        let query = random_embedding(TEST_EMBEDDING_DIMENSION);
+        let hnsw_knn_input = HnswKnnOperatorInput {
+            segment: Some(hnsw_segment_reader),
+            distance_function: DistanceFunction::Euclidean,
+            accumulation: DistanceAccumulation::default(),
+            query: query.clone(),
+            k: 5,
+            max_distance: None,
+            time_limit: None,
+            record_segment: test_segment.record_segment.clone(),
+            blockfile_provider: test_segment.blockfile_provider.clone(),
+            allowed_ids: allowed.iter().map(|(_, id, _)| id.clone()).collect(),
+            logs: Chunk::new(Vec::new().into()),
+            offset_id_cache: OffsetIdCache::new(None),
+            deadline: Deadline::default(),
+            memory: MemoryContext::default(),
+        };
+        let hnsw_knn_output = HnswKnnOperator {}
+            .run(&hnsw_knn_input)
+            .await
+            .expect("HnswKnnOperator should not fail");
+
+        let mut expected = allowed
+            .iter()
+            .map(|(offset_id, _, embedding)| {
+                (
+                    *offset_id as usize,
+                    DistanceFunction::Euclidean.distance(&query, embedding),
+                )
+            })
+            .collect::<Vec<_>>();
+        expected.sort_by(|a, b| a.1.total_cmp(&b.1));
+        expected.truncate(5);
+        assert_eq!(
+            hnsw_knn_output.offset_ids,
+            expected
+                .into_iter()
+                .map(|(offset_id, _)| offset_id)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_f64_accumulation_is_rescored() {
+        let mut test_segment = TestSegment::default();
+        test_segment
+            .populate_with_vectors(
+                100,
+                &LogGenerator {
+                    generator: upsert_generator,
+                },
+            )
+            .await;
+        let record_segment_reader = RecordSegmentReader::from_segment(
+            &test_segment.record_segment,
+            &test_segment.blockfile_provider,
+        )
+        .await
+        .expect("Record segment reader should be created");
+        let embeddings: Vec<(u32, Vec<f32>)> = record_segment_reader
+            .scan(None)
+            .map_ok(|(offset_id, record)| (offset_id, record.embedding.to_vec()))
+            .try_collect()
+            .await
+            .expect("Record segment should be scanned");
+        let hnsw_segment_reader = DistributedHNSWSegmentReader::from_segment(
+            &test_segment.vector_segment,
+            TEST_EMBEDDING_DIMENSION,
+            test_segment.hnsw_provider.clone(),
+        )
+        .await
+        .expect("Hnsw segment reader should be created");
+
+        let query = random_embedding(TEST_EMBEDDING_DIMENSION);
+        let hnsw_knn_input = HnswKnnOperatorInput {
+            segment: Some(hnsw_segment_reader),
+            distance_function: DistanceFunction::Euclidean,
+            accumulation: DistanceAccumulation::F64,
+            query: query.clone(),
+            k: 10,
+            max_distance: None,
+            time_limit: None,
+            record_segment: test_segment.record_segment.clone(),
+            blockfile_provider: test_segment.blockfile_provider.clone(),
+            allowed_ids: Arc::new([]),
+            logs: Chunk::new(Vec::new().into()),
+            offset_id_cache: OffsetIdCache::new(None),
+            deadline: Deadline::default(),
+            memory: MemoryContext::default(),
+        };
+        let hnsw_knn_output = HnswKnnOperator {}
+            .run(&hnsw_knn_input)
+            .await
+            .expect("HnswKnnOperator should not fail");
+
+        // The distances are accumulated in double precision rather than those of the index
+        let embeddings = embeddings.into_iter().collect::<HashMap<_, _>>();
+        assert_eq!(hnsw_knn_output.offset_ids.len(), 10);
+        for (offset_id, distance) in hnsw_knn_output
+            .offset_ids
+            .iter()
+            .zip(hnsw_knn_output.distances.iter())
+        {
+            assert_eq!(
+                *distance,
+                DistanceFunction::Euclidean.distance_with_accumulation(
+                    &query,
+                    &embeddings[&(*offset_id as u32)],
+                    DistanceAccumulation::F64,
+                )
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rescore_skips_missing_records() {
+        let mut test_segment = TestSegment::default();
+        test_segment
+            .populate_with_vectors(
+                10,
+                &LogGenerator {
+                    generator: upsert_generator,
+                },
+            )
+            .await;
+        let record_segment_reader = RecordSegmentReader::from_segment(
+            &test_segment.record_segment,
+            &test_segment.blockfile_provider,
+        )
+        .await
+        .expect("Record segment reader should be created");
+        let hnsw_knn_input = HnswKnnOperatorInput {
+            segment: None,
+            distance_function: DistanceFunction::Euclidean,
+            accumulation: DistanceAccumulation::default(),
+            query: random_embedding(TEST_EMBEDDING_DIMENSION),
+            k: 10,
+            max_distance: None,
+            time_limit: None,
+            record_segment: test_segment.record_segment.clone(),
+            blockfile_provider: test_segment.blockfile_provider.clone(),
+            allowed_ids: Arc::new([]),
+            logs: Chunk::new(Vec::new().into()),
+            offset_id_cache: OffsetIdCache::new(None),
+            deadline: Deadline::default(),
+            memory: MemoryContext::default(),
+        };
+
+        // The index can hold offset ids that were since removed from the record segment
+        let (mut offset_ids, _) = HnswKnnOperator {}
+            .rescore(&hnsw_knn_input, &record_segment_reader, vec![3, 100, 7])
+            .await
+            .expect("Rescoring should not fail");
+        offset_ids.sort();
+        assert_eq!(offset_ids, vec![3, 7]);
+    }
+
+    #[tokio::test]
+    async fn test_search_stops_at_deadline() {
+        let mut test_segment = TestSegment::default();
+        test_segment
+            .populate_with_vectors(
+                100,
+                &LogGenerator {
+                    generator: upsert_generator,
+                },
+            )
+            .await;
+        let hnsw_segment_reader = DistributedHNSWSegmentReader::from_segment(
+            &test_segment.vector_segment,
+            TEST_EMBEDDING_DIMENSION,
+            test_segment.hnsw_provider.clone(),
+        )
+        .await
+        .expect("Hnsw segment reader should be created");
+        let mut hnsw_knn_input = HnswKnnOperatorInput {
+            segment: None,
+            distance_function: DistanceFunction::Euclidean,
+            accumulation: DistanceAccumulation::default(),
+            query: random_embedding(TEST_EMBEDDING_DIMENSION),
+            k: 10,
+            max_distance: None,
+            time_limit: None,
+            record_segment: test_segment.record_segment.clone(),
+            blockfile_provider: test_segment.blockfile_provider.clone(),
+            allowed_ids: Arc::new([]),
+            logs: Chunk::new(Vec::new().into()),
+            offset_id_cache: OffsetIdCache::new(None),
+            deadline: Deadline::after(Duration::ZERO),
+            memory: MemoryContext::default(),
+        };
+
+        // The widening searches of the index stop once the deadline is reached
+        let error = HnswKnnOperator {}
+            .query_segment(&hnsw_knn_input, &hnsw_segment_reader, 10, &[], &[])
+            .expect_err("The search should stop at the deadline");
+        assert_eq!(error.code(), ErrorCodes::DeadlineExceeded);
+
+        hnsw_knn_input.segment = Some(hnsw_segment_reader);
+        hnsw_knn_input.deadline = Deadline::default();
+        hnsw_knn_input.deadline.cancel();
+        let error = HnswKnnOperator {}
+            .run(&hnsw_knn_input)
+            .await
+            .expect_err("HnswKnnOperator should stop once cancelled");
+        assert_eq!(error.code(), ErrorCodes::Cancelled);
+    }
 }
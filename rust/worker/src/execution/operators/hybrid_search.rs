@@ -0,0 +1,591 @@
+use std::{collections::HashMap, time::Instant};
+
+use chroma_blockstore::provider::BlockfileProvider;
+use chroma_distance::DistanceFunction;
+use chroma_error::{ChromaError, ErrorCodes};
+use chroma_index::{
+    fulltext::types::{bm25_token_score, FullTextScores},
+    hnsw_provider::HnswIndexProvider,
+};
+use chroma_types::{MaterializedLogOperation, Segment, SignedRoaringBitmap};
+use roaring::RoaringBitmap;
+use tantivy::tokenizer::{NgramTokenizer, TokenStream, Tokenizer};
+use thiserror::Error;
+use tonic::async_trait;
+use tracing::{trace, Instrument, Span};
+
+use crate::{
+    execution::operator::{Deadline, Operator},
+    segment::{
+        metadata_segment::{
+            tokenizer_config_from_segment, MetadataSegmentError, MetadataSegmentReader,
+        },
+        record_segment::{RecordSegmentReader, RecordSegmentReaderCreationError},
+        LogMaterializer, LogMaterializerError,
+    },
+};
+
+use super::{
+    fetch_log::FetchLogOutput,
+    knn::{KnnOperator, RecordDistance},
+    knn_hnsw::{KnnHnswError, KnnHnswInput},
+    knn_log::{KnnLogError, KnnLogInput},
+};
+
+/// The function used to fuse the full-text and vector rankings
+#[derive(Clone, Debug)]
+pub enum FusionFunction {
+    /// Each ranking contributes `weight / (k + rank)` to the score of a record, where rank starts from 1
+    ReciprocalRank { k: f32 },
+    /// Each ranking contributes `weight * normalized score` to the score of a record, where the
+    /// full-text scores are scaled to `[0, 1]` and the distances are flipped and scaled to `[0, 1]`
+    WeightedSum,
+}
+
+/// The default rank constant of the reciprocal rank fusion
+pub const DEFAULT_RRF_K: f32 = 60.0;
+
+/// The `HybridSearchOperator` searches for the records that best match both the full-text query
+/// and the target embedding
+///
+/// # Parameters
+/// - `knn`: The `KnnOperator` that searches for the nearest neighbours of the target embedding.
+///   Its time limit also applies to the full-text scoring
+/// - `query`: The full-text query. Matching records are scored with BM25 over the query tokens
+/// - `fetch`: The number of records to return
+/// - `fusion`: The function used to fuse the full-text and vector rankings
+/// - `text_weight`: The weight of the full-text ranking
+/// - `vector_weight`: The weight of the vector ranking
+///
+/// # Inputs
+/// - `logs`: The latest logs of the collection
+/// - `blockfile_provider`: The blockfile provider
+/// - `hnsw_provider`: The hnsw provider
+/// - `metadata_segment`: The metadata segment information
+/// - `record_segment`: The record segment information
+/// - `vector_segment`: The vector segment information
+/// - `collection_dimension`: The dimension of the collection
+/// - `log_offset_ids`: The offset ids in the logs to include or exclude before searching
+/// - `compact_offset_ids`: The offset ids in the segments to include or exclude before searching
+/// - `distance_function`: The distance function of the collection
+/// - `deadline`: The deadline of the query, which is passed on to the vector search
+///
+/// # Outputs
+/// - `records`: The fused records, sorted by score in descending order
+/// - `truncated`: Whether the full-text scoring or the nearest neighbour search over the logs
+///   stopped early at the time limit, in which case the records are best-effort
+///
+/// # Usage
+/// It can be used after a `FilterOperator`. The full-text search and the nearest neighbour searches over
+/// the logs and the vector segment run concurrently. At most `knn.fetch` candidates are taken from each ranking.
+#[derive(Clone, Debug)]
+pub struct HybridSearchOperator {
+    pub knn: KnnOperator,
+    pub query: String,
+    pub fetch: u32,
+    pub fusion: FusionFunction,
+    pub text_weight: f32,
+    pub vector_weight: f32,
+}
+
+#[derive(Clone, Debug)]
+pub struct HybridSearchInput {
+    pub logs: FetchLogOutput,
+    pub blockfile_provider: BlockfileProvider,
+    pub hnsw_provider: HnswIndexProvider,
+    pub metadata_segment: Segment,
+    pub record_segment: Segment,
+    pub vector_segment: Segment,
+    pub collection_dimension: u32,
+    pub log_offset_ids: SignedRoaringBitmap,
+    pub compact_offset_ids: SignedRoaringBitmap,
+    pub distance_function: DistanceFunction,
+    pub deadline: Deadline,
+}
+
+#[derive(Clone, Debug)]
+pub struct HybridScore {
+    pub offset_id: u32,
+    pub score: f32,
+    pub text_score: Option<f32>,
+    pub distance: Option<f32>,
+}
+
+#[derive(Debug)]
+pub struct HybridSearchOutput {
+    pub records: Vec<HybridScore>,
+    pub truncated: bool,
+}
+
+#[derive(Error, Debug)]
+pub enum HybridSearchError {
+    #[error("Error running knn over hnsw index: {0}")]
+    KnnHnsw(#[from] KnnHnswError),
+    #[error("Error running knn over log: {0}")]
+    KnnLog(#[from] KnnLogError),
+    #[error("Error materializing log: {0}")]
+    LogMaterializer(#[from] LogMaterializerError),
+    #[error("Error reading metadata segment: {0}")]
+    MetadataSegment(#[from] MetadataSegmentError),
+    #[error("Error creating record segment reader: {0}")]
+    RecordReader(#[from] RecordSegmentReaderCreationError),
+    #[error("Error reading record segment: {0}")]
+    RecordSegment(#[from] Box<dyn ChromaError>),
+}
+
+impl ChromaError for HybridSearchError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            HybridSearchError::KnnHnsw(e) => e.code(),
+            HybridSearchError::KnnLog(e) => e.code(),
+            HybridSearchError::LogMaterializer(e) => e.code(),
+            HybridSearchError::MetadataSegment(e) => e.code(),
+            HybridSearchError::RecordReader(e) => e.code(),
+            HybridSearchError::RecordSegment(e) => e.code(),
+        }
+    }
+}
+
+/// Counts the possibly overlapping occurrences of the token in the document
+fn count_occurrences(document: &str, token: &str) -> u32 {
+    document
+        .char_indices()
+        .filter(|(index, _)| document[*index..].starts_with(token))
+        .count() as u32
+}
+
+impl HybridSearchOperator {
+    /// Scores the records matching the full-text query, sorted by score in descending order
+    async fn search_full_text(
+        &self,
+        input: &HybridSearchInput,
+    ) -> Result<FullTextScores, HybridSearchError> {
+        let stop_at = self
+            .knn
+            .time_limit
+            .map(|time_limit| Instant::now() + time_limit);
+        let is_stopped = || stop_at.is_some_and(|stop_at| Instant::now() >= stop_at);
+        let record_segment_reader = match RecordSegmentReader::from_segment(
+            &input.record_segment,
+            &input.blockfile_provider,
+        )
+        .await
+        {
+            Ok(reader) => Ok(Some(reader)),
+            Err(e) if matches!(*e, RecordSegmentReaderCreationError::UninitializedSegment) => {
+                Ok(None)
+            }
+            Err(e) => Err(*e),
+        }?;
+
+        let materializer =
+            LogMaterializer::new(record_segment_reader.clone(), input.logs.clone(), None);
+        let materialized_logs = materializer
+            .materialize()
+            .instrument(tracing::trace_span!(parent: Span::current(), "Materialize logs"))
+            .await?;
+
+        // The documents in the logs are normalized like the full-text index
+        let tokenizer_config = tokenizer_config_from_segment(&input.metadata_segment)?;
+        let query = tokenizer_config.normalize(&self.query);
+
+        // The offset ids in the record segment that are overwritten or deleted by the logs
+        let mut updated_offset_ids = RoaringBitmap::new();
+        let mut new_document_count = 0;
+        let mut log_documents = Vec::new();
+        for (log, _) in materialized_logs.iter() {
+            match log.final_operation {
+                MaterializedLogOperation::Initial | MaterializedLogOperation::AddNew => {
+                    new_document_count += 1
+                }
+                _ => {
+                    updated_offset_ids.insert(log.offset_id);
+                }
+            }
+            if matches!(
+                log.final_operation,
+                MaterializedLogOperation::DeleteExisting
+            ) || !input.log_offset_ids.contains(log.offset_id)
+            {
+                continue;
+            }
+            if let Some(document) = log.merged_document_ref() {
+                let document = tokenizer_config.normalize(document);
+                if document.contains(query.as_ref()) {
+                    log_documents.push((log.offset_id, document));
+                }
+            }
+        }
+
+        let mut text_scores = Vec::new();
+        let mut truncated = false;
+
+        if !log_documents.is_empty() {
+            let num_documents = match record_segment_reader.as_ref() {
+                Some(reader) => reader.count().await? as u64,
+                None => 0,
+            } + new_document_count;
+            let mut tokens = Vec::new();
+            NgramTokenizer::new(3, 3, false)
+                .expect("Trigram tokenizer should be valid")
+                .token_stream(&query)
+                .process(&mut |token| tokens.push(token.text.clone()));
+            // The document frequencies of the tokens in the logs are estimated from the matching documents alone
+            let document_frequencies = tokens
+                .iter()
+                .map(|token| {
+                    log_documents
+                        .iter()
+                        .filter(|(_, document)| document.contains(token.as_str()))
+                        .count() as u64
+                })
+                .collect::<Vec<_>>();
+            for (offset_id, document) in &log_documents {
+                // The remaining documents still match the query, but are not scored
+                if is_stopped() {
+                    truncated = true;
+                    text_scores.push((*offset_id, 0.0));
+                    continue;
+                }
+                let score = tokens
+                    .iter()
+                    .zip(&document_frequencies)
+                    .map(|(token, document_frequency)| {
+                        bm25_token_score(
+                            count_occurrences(document, token),
+                            *document_frequency,
+                            num_documents,
+                            1.0,
+                        )
+                    })
+                    .sum();
+                text_scores.push((*offset_id, score));
+            }
+        }
+
+        if record_segment_reader.is_some() {
+            let metadata_segment_reader = MetadataSegmentReader::from_segment(
+                &input.metadata_segment,
+                &input.blockfile_provider,
+            )
+            .await?;
+            let segment_scores = metadata_segment_reader
+                .search_document_with_scores(&self.query, stop_at)
+                .await?;
+            truncated |= segment_scores.truncated;
+            text_scores.extend(segment_scores.scores.into_iter().filter(|(offset_id, _)| {
+                !updated_offset_ids.contains(*offset_id)
+                    && input.compact_offset_ids.contains(*offset_id)
+            }));
+        }
+
+        text_scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+        text_scores.truncate(self.knn.fetch as usize);
+        Ok(FullTextScores {
+            scores: text_scores,
+            truncated,
+        })
+    }
+
+    /// Searches for the nearest neighbours in both the logs and the vector segment,
+    /// sorted by distance in ascending order, and whether the search over the logs is truncated
+    async fn search_vector(
+        &self,
+        input: &HybridSearchInput,
+    ) -> Result<(Vec<RecordDistance>, bool), HybridSearchError> {
+        let knn_log_input = KnnLogInput {
+            logs: input.logs.clone(),
+            blockfile_provider: input.blockfile_provider.clone(),
+            record_segment: input.record_segment.clone(),
+            log_offset_ids: input.log_offset_ids.clone(),
+            distance_function: input.distance_function.clone(),
+            deadline: input.deadline.clone(),
+        };
+        let knn_hnsw_input = KnnHnswInput {
+            hnsw_provider: input.hnsw_provider.clone(),
+            hnsw_segment: input.vector_segment.clone(),
+            blockfile_provider: input.blockfile_provider.clone(),
+            record_segment: input.record_segment.clone(),
+            collection_dimension: input.collection_dimension,
+            compact_offset_ids: input.compact_offset_ids.clone(),
+            distance_function: input.distance_function.clone(),
+            deadline: input.deadline.clone(),
+        };
+        let (knn_log_output, knn_hnsw_output) =
+            futures::join!(self.knn.run(&knn_log_input), self.knn.run(&knn_hnsw_input));
+        let knn_log_output = knn_log_output?;
+        let mut record_distances = knn_log_output.record_distances;
+        record_distances.extend(knn_hnsw_output?.record_distances);
+        record_distances.sort();
+        record_distances.truncate(self.knn.fetch as usize);
+        Ok((record_distances, knn_log_output.truncated))
+    }
+}
+
+#[async_trait]
+impl Operator<HybridSearchInput, HybridSearchOutput> for HybridSearchOperator {
+    type Error = HybridSearchError;
+
+    async fn run(
+        &self,
+        input: &HybridSearchInput,
+    ) -> Result<HybridSearchOutput, HybridSearchError> {
+        trace!("[{}]: {:?}", self.get_name(), input);
+
+        let (text_scores, vector_search) =
+            futures::join!(self.search_full_text(input), self.search_vector(input));
+        let (text_scores, (record_distances, vector_truncated)) = (text_scores?, vector_search?);
+        let truncated = text_scores.truncated || vector_truncated;
+        let text_scores = text_scores.scores;
+
+        let mut records: HashMap<u32, HybridScore> = HashMap::new();
+        let max_text_score = text_scores.first().map(|(_, score)| *score);
+        for (rank, (offset_id, text_score)) in text_scores.into_iter().enumerate() {
+            let contribution = match self.fusion {
+                FusionFunction::ReciprocalRank { k } => self.text_weight / (k + rank as f32 + 1.0),
+                FusionFunction::WeightedSum => match max_text_score {
+                    Some(max_text_score) if max_text_score > 0.0 => {
+                        self.text_weight * text_score / max_text_score
+                    }
+                    _ => self.text_weight,
+                },
+            };
+            records.insert(
+                offset_id,
+                HybridScore {
+                    offset_id,
+                    score: contribution,
+                    text_score: Some(text_score),
+                    distance: None,
+                },
+            );
+        }
+
+        let min_distance = record_distances.first().map(|record| record.measure);
+        let max_distance = record_distances.last().map(|record| record.measure);
+        for (rank, record) in record_distances.into_iter().enumerate() {
+            let contribution = match self.fusion {
+                FusionFunction::ReciprocalRank { k } => {
+                    self.vector_weight / (k + rank as f32 + 1.0)
+                }
+                FusionFunction::WeightedSum => match (min_distance, max_distance) {
+                    (Some(min_distance), Some(max_distance)) if max_distance > min_distance => {
+                        self.vector_weight * (max_distance - record.measure)
+                            / (max_distance - min_distance)
+                    }
+                    _ => self.vector_weight,
+                },
+            };
+            let hybrid_score = records
+                .entry(record.offset_id)
+                .or_insert_with(|| HybridScore {
+                    offset_id: record.offset_id,
+                    score: 0.0,
+                    text_score: None,
+                    distance: None,
+                });
+            hybrid_score.score += contribution;
+            hybrid_score.distance = Some(record.measure);
+        }
+
+        let mut records = records.into_values().collect::<Vec<_>>();
+        records.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then(a.offset_id.cmp(&b.offset_id))
+        });
+        records.truncate(self.fetch as usize);
+
+        Ok(HybridSearchOutput { records, truncated })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chroma_distance::{DistanceAccumulation, DistanceFunction};
+    use chroma_types::SignedRoaringBitmap;
+
+    use crate::{
+        execution::{
+            operator::{Deadline, Operator},
+            operators::{
+                hybrid_search::{FusionFunction, HybridSearchOperator},
+                knn::KnnOperator,
+                knn_log::KnnLogInput,
+            },
+        },
+        log::test::{
+            add_delete_generator, random_embedding, LogGenerator, TEST_EMBEDDING_DIMENSION,
+        },
+        segment::test::TestSegment,
+    };
+
+    use super::HybridSearchInput;
+
+    /// The unit tests for `HybridSearchOperator` uses the following test data
+    /// It generates 120 log records, where the first 60 is compacted:
+    /// - Log: Delete [11..=20], add [51..=100]
+    /// - Compacted: Delete [1..=10] deletion, add [11..=50]
+    ///
+    /// The documents of records with an id divisible by 3 contain `<cat>`.
+    /// The vector segment is not populated, thus only the records in the logs have distances.
+    async fn setup_hybrid_search_input() -> HybridSearchInput {
+        let mut test_segment = TestSegment::default();
+        let generator = LogGenerator {
+            generator: add_delete_generator,
+        };
+        test_segment.populate_with_generator(60, &generator).await;
+        HybridSearchInput {
+            logs: generator.generate_chunk(61..=120),
+            blockfile_provider: test_segment.blockfile_provider,
+            hnsw_provider: test_segment.hnsw_provider,
+            metadata_segment: test_segment.metadata_segment,
+            record_segment: test_segment.record_segment,
+            vector_segment: test_segment.vector_segment,
+            collection_dimension: TEST_EMBEDDING_DIMENSION as u32,
+            log_offset_ids: SignedRoaringBitmap::full(),
+            compact_offset_ids: SignedRoaringBitmap::full(),
+            distance_function: DistanceFunction::Euclidean,
+            deadline: Deadline::default(),
+        }
+    }
+
+    fn knn_operator() -> KnnOperator {
+        KnnOperator {
+            embedding: random_embedding(TEST_EMBEDDING_DIMENSION),
+            fetch: 100,
+            max_distance: None,
+            time_limit: None,
+            accumulation: DistanceAccumulation::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_time_limit() {
+        let hybrid_search_input = setup_hybrid_search_input().await;
+
+        let hybrid_search_operator = HybridSearchOperator {
+            knn: KnnOperator {
+                time_limit: Some(Duration::ZERO),
+                ..knn_operator()
+            },
+            query: "<cat>".to_string(),
+            fetch: 27,
+            fusion: FusionFunction::ReciprocalRank { k: 60.0 },
+            text_weight: 1.0,
+            vector_weight: 0.0,
+        };
+
+        let hybrid_search_output = hybrid_search_operator
+            .run(&hybrid_search_input)
+            .await
+            .expect("HybridSearchOperator should not fail");
+
+        assert!(hybrid_search_output.truncated);
+        assert_eq!(hybrid_search_output.records.len(), 27);
+        for record in hybrid_search_output.records {
+            assert_eq!(record.offset_id % 3, 0);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_text_only() {
+        let hybrid_search_input = setup_hybrid_search_input().await;
+
+        let hybrid_search_operator = HybridSearchOperator {
+            knn: knn_operator(),
+            query: "<cat>".to_string(),
+            fetch: 27,
+            fusion: FusionFunction::ReciprocalRank { k: 60.0 },
+            text_weight: 1.0,
+            vector_weight: 0.0,
+        };
+
+        let hybrid_search_output = hybrid_search_operator
+            .run(&hybrid_search_input)
+            .await
+            .expect("HybridSearchOperator should not fail");
+
+        assert_eq!(hybrid_search_output.records.len(), 27);
+        for record in hybrid_search_output.records {
+            assert_eq!(record.offset_id % 3, 0);
+            assert!(record.text_score.is_some());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_vector_only() {
+        let hybrid_search_input = setup_hybrid_search_input().await;
+
+        let knn_operator = knn_operator();
+        let knn_log_output = knn_operator
+            .run(&KnnLogInput {
+                logs: hybrid_search_input.logs.clone(),
+                blockfile_provider: hybrid_search_input.blockfile_provider.clone(),
+                record_segment: hybrid_search_input.record_segment.clone(),
+                log_offset_ids: hybrid_search_input.log_offset_ids.clone(),
+                distance_function: hybrid_search_input.distance_function.clone(),
+                deadline: Deadline::default(),
+            })
+            .await
+            .expect("KnnOperator should not fail");
+
+        let hybrid_search_operator = HybridSearchOperator {
+            knn: knn_operator,
+            query: "<cat>".to_string(),
+            fetch: 10,
+            fusion: FusionFunction::WeightedSum,
+            text_weight: 0.0,
+            vector_weight: 1.0,
+        };
+
+        let hybrid_search_output = hybrid_search_operator
+            .run(&hybrid_search_input)
+            .await
+            .expect("HybridSearchOperator should not fail");
+
+        assert_eq!(
+            hybrid_search_output
+                .records
+                .iter()
+                .map(|record| record.offset_id)
+                .collect::<Vec<_>>(),
+            knn_log_output.record_distances[..10]
+                .iter()
+                .map(|record| record.offset_id)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reciprocal_rank_fusion() {
+        let hybrid_search_input = setup_hybrid_search_input().await;
+
+        let hybrid_search_operator = HybridSearchOperator {
+            knn: knn_operator(),
+            query: "<cat>".to_string(),
+            fetch: 100,
+            fusion: FusionFunction::ReciprocalRank { k: 60.0 },
+            text_weight: 1.0,
+            vector_weight: 1.0,
+        };
+
+        let hybrid_search_output = hybrid_search_operator
+            .run(&hybrid_search_input)
+            .await
+            .expect("HybridSearchOperator should not fail");
+
+        // The union of the 50 records in the logs and the 10 compacted records in [21..=50] that match the query
+        assert_eq!(hybrid_search_output.records.len(), 60);
+        assert!(hybrid_search_output
+            .records
+            .windows(2)
+            .all(|pair| pair[0].score >= pair[1].score));
+        for record in hybrid_search_output.records {
+            assert_eq!(record.text_score.is_some(), record.offset_id % 3 == 0);
+            assert_eq!(record.distance.is_some(), record.offset_id > 50);
+        }
+    }
+}
@@ -1,4 +1,6 @@
-use std::cmp::Ordering;
+use std::{cmp::Ordering, time::Duration};
+
+use chroma_distance::DistanceAccumulation;
 
 #[derive(Clone, Debug)]
 pub struct RecordDistance {
@@ -31,6 +33,12 @@ impl PartialOrd for RecordDistance {
 /// # Parameters
 /// - `embedding`: The target embedding to search around
 /// - `fetch`: The number of records to fetch around the target
+/// - `max_distance`: If provided, records further than this distance from the target are dropped.
+///   For cosine and inner product spaces, a minimum similarity score `s` corresponds to `1 - s`
+/// - `time_limit`: If provided, the brute force search over the logs stops once the limit is reached
+///   and returns the nearest records found so far, with the output marked as truncated
+/// - `accumulation`: The precision in which the distances are accumulated by the brute force search
+///   over the logs and by the rescoring of the candidates from a quantized index
 ///
 /// # Implementation
 /// `KnnOperator` has multiple implementations for the `Operator<I, O>` trait:
@@ -43,4 +51,17 @@ impl PartialOrd for RecordDistance {
 pub struct KnnOperator {
     pub embedding: Vec<f32>,
     pub fetch: u32,
+    pub max_distance: Option<f32>,
+    pub time_limit: Option<Duration>,
+    pub accumulation: DistanceAccumulation,
+}
+
+impl KnnOperator {
+    /// Checks if the distance is within the relevance threshold of the operator
+    pub(super) fn is_within_threshold(&self, measure: f32) -> bool {
+        match self.max_distance {
+            Some(max_distance) => measure <= max_distance,
+            None => true,
+        }
+    }
 }
@@ -1,26 +1,57 @@
+use chroma_blockstore::provider::BlockfileProvider;
 use chroma_distance::{normalize, DistanceFunction};
-use chroma_error::{ChromaError, ErrorCodes};
+use chroma_error::{ChromaError, ErrorCodes, ErrorLocation};
 use chroma_index::hnsw_provider::HnswIndexProvider;
-use chroma_types::{Segment, SignedRoaringBitmap};
+use chroma_types::{CollectionUuid, Segment, SignedRoaringBitmap};
 use thiserror::Error;
 use tonic::async_trait;
 
 use crate::{
-    execution::operator::Operator,
-    segment::distributed_hnsw_segment::{
-        DistributedHNSWSegmentFromSegmentError, DistributedHNSWSegmentReader,
+    execution::operator::{Deadline, DeadlineError, Operator},
+    segment::{
+        distributed_hnsw_segment::{
+            DistributedHNSWSegmentFromSegmentError, DistributedHNSWSegmentReader,
+        },
+        record_segment::{RecordSegmentReader, RecordSegmentReaderCreationError},
     },
 };
 
 use super::knn::{KnnOperator, RecordDistance};
 
+/// The number of candidates fetched from a quantized index for each requested result,
+/// which are rescored with the exact embeddings in the record segment
+pub(crate) const QUANTIZED_OVERSAMPLING_FACTOR: usize = 4;
+
+/// The largest factor by which the candidates requested from the index are widened up front
+/// for a selective filter, relative to the number of candidates the query needs
+const MAX_INITIAL_OVERFETCH_FACTOR: usize = 8;
+
+/// The largest factor by which a filtered query widens the candidates requested from the index
+/// while fewer candidates than it needs survive the filter
+pub(crate) const MAX_OVERFETCH_FACTOR: usize = 64;
+
+/// Returns the number of candidates to request from the index on the first attempt of a
+/// filtered query. The filter is applied while the graph is traversed, so a selective filter
+/// can end the search before enough allowed records are found. The fewer records of the
+/// index are estimated to pass the filter, the more candidates are requested.
+pub(crate) fn initial_overfetch(needed: usize, eligible: usize, total: usize) -> usize {
+    if eligible == 0 || eligible >= total {
+        return needed;
+    }
+    let factor = total.div_ceil(eligible).min(MAX_INITIAL_OVERFETCH_FACTOR);
+    needed.saturating_mul(factor)
+}
+
 #[derive(Debug)]
 pub struct KnnHnswInput {
     pub hnsw_provider: HnswIndexProvider,
     pub hnsw_segment: Segment,
+    pub blockfile_provider: BlockfileProvider,
+    pub record_segment: Segment,
     pub collection_dimension: u32,
     pub compact_offset_ids: SignedRoaringBitmap,
     pub distance_function: DistanceFunction,
+    pub deadline: Deadline,
 }
 
 #[derive(Debug)]
@@ -30,26 +61,102 @@ pub struct KnnHnswOutput {
 
 #[derive(Error, Debug)]
 pub enum KnnHnswError {
+    #[error("Knn hnsw stopped early: {0}")]
+    Deadline(#[from] DeadlineError),
     #[error("Error querying hnsw index: {0}")]
     HnswIndex(#[from] Box<dyn ChromaError>),
     #[error("Error creating hnsw segment reader: {0}")]
     HnswReader(#[from] DistributedHNSWSegmentFromSegmentError),
+    #[error("Error creating record segment reader: {0}")]
+    RecordReader(#[from] RecordSegmentReaderCreationError),
+    #[error("Error reading record segment: {0}")]
+    RecordSegment(Box<dyn ChromaError>),
 }
 
 impl ChromaError for KnnHnswError {
     fn code(&self) -> ErrorCodes {
         match self {
+            KnnHnswError::Deadline(e) => e.code(),
             KnnHnswError::HnswReader(e) => e.code(),
             KnnHnswError::HnswIndex(e) => e.code(),
+            KnnHnswError::RecordReader(e) => e.code(),
+            KnnHnswError::RecordSegment(e) => e.code(),
+        }
+    }
+
+    fn location(&self) -> ErrorLocation {
+        match self {
+            KnnHnswError::RecordReader(e) => e.location(),
+            KnnHnswError::RecordSegment(e) => e.location(),
+            _ => ErrorLocation::default(),
         }
     }
 }
 
+impl KnnOperator {
+    /// Replaces the approximate distances from a quantized index with the exact distances
+    /// to the embeddings in the record segment, and keeps the nearest `fetch` records. The
+    /// records that are not in the record segment are dropped
+    async fn rescore(
+        &self,
+        input: &KnnHnswInput,
+        embedding: &[f32],
+        record_distances: Vec<RecordDistance>,
+    ) -> Result<Vec<RecordDistance>, KnnHnswError> {
+        let record_segment_reader = match RecordSegmentReader::from_segment(
+            &input.record_segment,
+            &input.blockfile_provider,
+        )
+        .await
+        {
+            Ok(reader) => reader,
+            Err(e) if matches!(*e, RecordSegmentReaderCreationError::UninitializedSegment) => {
+                return Ok(record_distances);
+            }
+            Err(e) => return Err((*e).into()),
+        };
+
+        input.deadline.check()?;
+        let offset_ids = record_distances
+            .iter()
+            .map(|record_distance| record_distance.offset_id)
+            .collect::<Vec<_>>();
+        let records = record_segment_reader
+            .get_data_for_offset_ids_if_present(&offset_ids)
+            .await
+            .map_err(KnnHnswError::RecordSegment)?;
+        let mut rescored_distances = offset_ids
+            .into_iter()
+            .zip(records)
+            .filter_map(|(offset_id, record)| Some((offset_id, record?)))
+            .map(|(offset_id, record)| {
+                let measure = match input.distance_function {
+                    DistanceFunction::Cosine => input.distance_function.distance_with_accumulation(
+                        embedding,
+                        &normalize(record.embedding),
+                        self.accumulation,
+                    ),
+                    _ => input.distance_function.distance_with_accumulation(
+                        embedding,
+                        record.embedding,
+                        self.accumulation,
+                    ),
+                };
+                RecordDistance { offset_id, measure }
+            })
+            .collect::<Vec<_>>();
+        rescored_distances.sort();
+        rescored_distances.truncate(self.fetch as usize);
+        Ok(rescored_distances)
+    }
+}
+
 #[async_trait]
 impl Operator<KnnHnswInput, KnnHnswOutput> for KnnOperator {
     type Error = KnnHnswError;
 
     async fn run(&self, input: &KnnHnswInput) -> Result<KnnHnswOutput, KnnHnswError> {
+        input.deadline.check()?;
         let (allowed, disallowed) = match &input.compact_offset_ids {
             SignedRoaringBitmap::Include(rbm) if rbm.is_empty() => {
                 return Ok(KnnHnswOutput {
@@ -82,14 +189,51 @@ impl Operator<KnnHnswInput, KnnHnswOutput> for KnnOperator {
         .await
         {
             Ok(reader) => {
-                let (offset_ids, distances) =
-                    reader.query(embedding, self.fetch as usize, &allowed, &disallowed)?;
+                let quantized = reader.quantization().is_some();
+                let needed = if quantized {
+                    self.fetch as usize * QUANTIZED_OVERSAMPLING_FACTOR
+                } else {
+                    self.fetch as usize
+                };
+                let total = reader.len();
+                let eligible = match &input.compact_offset_ids {
+                    SignedRoaringBitmap::Include(rbm) => rbm.len() as usize,
+                    SignedRoaringBitmap::Exclude(rbm) => total.saturating_sub(rbm.len() as usize),
+                };
+                let budget = needed
+                    .saturating_mul(MAX_OVERFETCH_FACTOR)
+                    .min(total.max(needed));
+
+                // Widen the search until enough candidates survive the filter, all the records
+                // that may pass the filter are found, or the budget is spent
+                let mut k = initial_overfetch(needed, eligible, total).min(budget);
+                let (offset_ids, distances) = loop {
+                    input.deadline.check()?;
+                    let (offset_ids, distances) =
+                        reader.query(embedding, k, &allowed, &disallowed)?;
+                    if offset_ids.len() >= needed.min(eligible) || k >= budget {
+                        break (offset_ids, distances);
+                    }
+                    k = k.saturating_mul(2).min(budget);
+                };
+                let mut record_distances: Vec<RecordDistance> = offset_ids
+                    .into_iter()
+                    .zip(distances)
+                    .map(|(offset_id, measure)| RecordDistance {
+                        offset_id: offset_id as u32,
+                        measure,
+                    })
+                    .collect();
+                if quantized {
+                    record_distances = self.rescore(input, embedding, record_distances).await?;
+                } else {
+                    record_distances.sort();
+                    record_distances.truncate(self.fetch as usize);
+                }
                 Ok(KnnHnswOutput {
-                    record_distances: offset_ids
+                    record_distances: record_distances
                         .into_iter()
-                        .map(|offset_id| offset_id as u32)
-                        .zip(distances)
-                        .map(|(offset_id, measure)| RecordDistance { offset_id, measure })
+                        .filter(|record| self.is_within_threshold(record.measure))
                         .collect(),
                 })
             }
@@ -101,4 +245,167 @@ impl Operator<KnnHnswInput, KnnHnswOutput> for KnnOperator {
             Err(e) => Err((*e).into()),
         }
     }
+
+    fn can_retry(&self, error: &KnnHnswError) -> bool {
+        error.is_transient()
+    }
+
+    fn deadline(&self, input: &KnnHnswInput) -> Option<Deadline> {
+        Some(input.deadline.clone())
+    }
+
+    fn collection_id(&self, input: &KnnHnswInput) -> Option<CollectionUuid> {
+        Some(input.record_segment.collection)
+    }
+
+    fn output_cardinality(&self, output: &KnnHnswOutput) -> Option<usize> {
+        Some(output.record_distances.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chroma_distance::{DistanceAccumulation, DistanceFunction};
+    use chroma_types::SignedRoaringBitmap;
+    use futures::TryStreamExt;
+
+    use crate::{
+        execution::{
+            operator::{Deadline, Operator},
+            operators::knn::{KnnOperator, RecordDistance},
+        },
+        log::test::{random_embedding, upsert_generator, LogGenerator, TEST_EMBEDDING_DIMENSION},
+        segment::{record_segment::RecordSegmentReader, test::TestSegment},
+    };
+
+    use super::KnnHnswInput;
+
+    /// The unit tests for `KnnHnswOperator` index 100 records with random embeddings
+    async fn setup_knn_hnsw_input(
+        compact_offset_ids: SignedRoaringBitmap,
+    ) -> (KnnHnswInput, Vec<(u32, Vec<f32>)>) {
+        let mut test_segment = TestSegment::default();
+        test_segment
+            .populate_with_vectors(
+                100,
+                &LogGenerator {
+                    generator: upsert_generator,
+                },
+            )
+            .await;
+        let reader = RecordSegmentReader::from_segment(
+            &test_segment.record_segment,
+            &test_segment.blockfile_provider,
+        )
+        .await
+        .expect("Record segment reader should be created");
+        let embeddings = reader
+            .scan(None)
+            .map_ok(|(offset_id, record)| (offset_id, record.embedding.to_vec()))
+            .try_collect()
+            .await
+            .expect("Record segment should be scanned");
+        (
+            KnnHnswInput {
+                hnsw_provider: test_segment.hnsw_provider,
+                hnsw_segment: test_segment.vector_segment,
+                blockfile_provider: test_segment.blockfile_provider,
+                record_segment: test_segment.record_segment,
+                collection_dimension: TEST_EMBEDDING_DIMENSION as u32,
+                compact_offset_ids,
+                distance_function: DistanceFunction::Euclidean,
+                deadline: Deadline::default(),
+            },
+            embeddings,
+        )
+    }
+
+    fn brute_force(embeddings: &[(u32, Vec<f32>)], target: &[f32]) -> Vec<RecordDistance> {
+        let mut record_distances = embeddings
+            .iter()
+            .map(|(offset_id, embedding)| RecordDistance {
+                offset_id: *offset_id,
+                measure: DistanceFunction::Euclidean.distance(embedding, target),
+            })
+            .collect::<Vec<_>>();
+        record_distances.sort();
+        record_distances
+    }
+
+    #[tokio::test]
+    async fn test_max_distance() {
+        let (knn_hnsw_input, embeddings) = setup_knn_hnsw_input(SignedRoaringBitmap::full()).await;
+        let embedding = random_embedding(TEST_EMBEDDING_DIMENSION);
+        let expected = brute_force(&embeddings, &embedding);
+
+        // Only the nearest 3 records are within the threshold
+        let knn_operator = KnnOperator {
+            embedding,
+            fetch: 6,
+            max_distance: Some((expected[2].measure + expected[3].measure) / 2.0),
+            time_limit: None,
+            accumulation: DistanceAccumulation::default(),
+        };
+
+        let knn_hnsw_output = knn_operator
+            .run(&knn_hnsw_input)
+            .await
+            .expect("KnnHnswOperator should not fail");
+
+        assert_eq!(
+            knn_hnsw_output
+                .record_distances
+                .iter()
+                .map(|record| record.offset_id)
+                .collect::<Vec<_>>(),
+            expected
+                .iter()
+                .take(3)
+                .map(|record| record.offset_id)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rescore_skips_missing_records() {
+        let (knn_hnsw_input, embeddings) = setup_knn_hnsw_input(SignedRoaringBitmap::full()).await;
+        let embedding = random_embedding(TEST_EMBEDDING_DIMENSION);
+        let knn_operator = KnnOperator {
+            embedding: embedding.clone(),
+            fetch: 10,
+            max_distance: None,
+            time_limit: None,
+            accumulation: DistanceAccumulation::default(),
+        };
+
+        // The index can hold offset ids that were since removed from the record segment
+        let record_distances = [3, 1000, 7]
+            .into_iter()
+            .map(|offset_id| RecordDistance {
+                offset_id,
+                measure: 0.0,
+            })
+            .collect();
+        let rescored = knn_operator
+            .rescore(&knn_hnsw_input, &embedding, record_distances)
+            .await
+            .expect("Rescoring should not fail");
+        let expected = brute_force(
+            &embeddings
+                .into_iter()
+                .filter(|(offset_id, _)| [3, 7].contains(offset_id))
+                .collect::<Vec<_>>(),
+            &embedding,
+        );
+        assert_eq!(
+            rescored
+                .iter()
+                .map(|record| (record.offset_id, record.measure))
+                .collect::<Vec<_>>(),
+            expected
+                .iter()
+                .map(|record| (record.offset_id, record.measure))
+                .collect::<Vec<_>>()
+        );
+    }
 }
@@ -1,17 +1,17 @@
-use std::collections::BinaryHeap;
+use std::{collections::BinaryHeap, time::Instant};
 
 use chroma_blockstore::provider::BlockfileProvider;
 use chroma_distance::{normalize, DistanceFunction};
 use chroma_error::ChromaError;
-use chroma_types::{MaterializedLogOperation, Segment, SignedRoaringBitmap};
+use chroma_types::{CollectionUuid, MaterializedLogOperation, Segment, SignedRoaringBitmap};
 use thiserror::Error;
 use tonic::async_trait;
 
 use crate::{
-    execution::operator::Operator,
+    execution::operator::{Deadline, DeadlineError, Operator},
     segment::{
         record_segment::{RecordSegmentReader, RecordSegmentReaderCreationError},
-        LogMaterializer, LogMaterializerError,
+        LogMaterializer, LogMaterializerError, MaterializedLogFields,
     },
 };
 
@@ -27,15 +27,19 @@ pub struct KnnLogInput {
     pub record_segment: Segment,
     pub log_offset_ids: SignedRoaringBitmap,
     pub distance_function: DistanceFunction,
+    pub deadline: Deadline,
 }
 
 #[derive(Debug)]
 pub struct KnnLogOutput {
     pub record_distances: Vec<RecordDistance>,
+    pub truncated: bool,
 }
 
 #[derive(Error, Debug)]
 pub enum KnnLogError {
+    #[error("Knn log stopped early: {0}")]
+    Deadline(#[from] DeadlineError),
     #[error("Error processing fetch log output: {0}")]
     FetchLog(#[from] FetchLogError),
     #[error("Error materializing log: {0}")]
@@ -47,6 +51,7 @@ pub enum KnnLogError {
 impl ChromaError for KnnLogError {
     fn code(&self) -> chroma_error::ErrorCodes {
         match self {
+            KnnLogError::Deadline(e) => e.code(),
             KnnLogError::FetchLog(e) => e.code(),
             KnnLogError::LogMaterializer(e) => e.code(),
             KnnLogError::RecordReader(e) => e.code(),
@@ -59,6 +64,11 @@ impl Operator<KnnLogInput, KnnLogOutput> for KnnOperator {
     type Error = KnnLogError;
 
     async fn run(&self, input: &KnnLogInput) -> Result<KnnLogOutput, KnnLogError> {
+        let deadline = self
+            .time_limit
+            .map(|time_limit| Instant::now() + time_limit);
+        input.deadline.check()?;
+
         let record_segment_reader = match RecordSegmentReader::from_segment(
             &input.record_segment,
             &input.blockfile_provider,
@@ -72,8 +82,13 @@ impl Operator<KnnLogInput, KnnLogOutput> for KnnOperator {
             Err(e) => Err(*e),
         }?;
 
-        let materializer = LogMaterializer::new(record_segment_reader, input.logs.clone(), None);
+        let materializer = LogMaterializer::new(record_segment_reader, input.logs.clone(), None)
+            .with_fields(MaterializedLogFields {
+                embedding: true,
+                ..MaterializedLogFields::OFFSET_IDS
+            });
         let logs = materializer.materialize().await?;
+        input.deadline.check()?;
 
         let target_vector;
         let target_embedding = if let DistanceFunction::Cosine = input.distance_function {
@@ -84,8 +99,14 @@ impl Operator<KnnLogInput, KnnLogOutput> for KnnOperator {
         };
 
         let mut max_heap = BinaryHeap::with_capacity(self.fetch as usize);
+        let mut truncated = false;
 
         for (log, _) in logs.iter() {
+            input.deadline.check()?;
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                truncated = true;
+                break;
+            }
             if !matches!(
                 log.final_operation,
                 MaterializedLogOperation::DeleteExisting
@@ -103,10 +124,15 @@ impl Operator<KnnLogInput, KnnLogOutput> for KnnOperator {
 
                 let distance = RecordDistance {
                     offset_id: log.offset_id,
-                    measure: input
-                        .distance_function
-                        .distance(target_embedding, log_embedding),
+                    measure: input.distance_function.distance_with_accumulation(
+                        target_embedding,
+                        log_embedding,
+                        self.accumulation,
+                    ),
                 };
+                if !self.is_within_threshold(distance.measure) {
+                    continue;
+                }
                 if max_heap.len() < self.fetch as usize {
                     max_heap.push(distance);
                 } else if let Some(furthest_distance) = max_heap.peek() {
@@ -119,17 +145,36 @@ impl Operator<KnnLogInput, KnnLogOutput> for KnnOperator {
         }
         Ok(KnnLogOutput {
             record_distances: max_heap.into_sorted_vec(),
+            truncated,
         })
     }
+
+    fn collection_id(&self, input: &KnnLogInput) -> Option<CollectionUuid> {
+        Some(input.record_segment.collection)
+    }
+
+    fn input_cardinality(&self, input: &KnnLogInput) -> Option<usize> {
+        Some(input.logs.len())
+    }
+
+    fn output_cardinality(&self, output: &KnnLogOutput) -> Option<usize> {
+        Some(output.record_distances.len())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use chroma_distance::{normalize, DistanceFunction};
+    use std::time::Duration;
+
+    use chroma_distance::{normalize, DistanceAccumulation, DistanceFunction};
+    use chroma_error::{ChromaError, ErrorCodes};
     use chroma_types::SignedRoaringBitmap;
 
     use crate::{
-        execution::{operator::Operator, operators::knn::KnnOperator},
+        execution::{
+            operator::{Deadline, Operator},
+            operators::knn::KnnOperator,
+        },
         log::test::{random_embedding, upsert_generator, LogGenerator, TEST_EMBEDDING_DIMENSION},
         segment::test::TestSegment,
     };
@@ -152,6 +197,7 @@ mod tests {
             record_segment: test_segment.record_segment,
             distance_function: metric,
             log_offset_ids,
+            deadline: Deadline::default(),
         }
     }
 
@@ -163,6 +209,9 @@ mod tests {
         let knn_operator = KnnOperator {
             embedding: random_embedding(TEST_EMBEDDING_DIMENSION),
             fetch: 6,
+            max_distance: None,
+            time_limit: None,
+            accumulation: DistanceAccumulation::default(),
         };
 
         let mut brute_force_distances: Vec<_> = knn_log_input
@@ -194,6 +243,49 @@ mod tests {
             .all(|(record, distance)| record.measure == distance));
     }
 
+    #[tokio::test]
+    async fn test_f64_accumulation() {
+        let knn_log_input =
+            setup_knn_log_input(DistanceFunction::Euclidean, SignedRoaringBitmap::full());
+
+        let knn_operator = KnnOperator {
+            embedding: random_embedding(TEST_EMBEDDING_DIMENSION),
+            fetch: 6,
+            max_distance: None,
+            time_limit: None,
+            accumulation: DistanceAccumulation::F64,
+        };
+
+        let mut brute_force_distances: Vec<_> = knn_log_input
+            .logs
+            .iter()
+            .map(|(log, _)| {
+                knn_log_input.distance_function.distance_with_accumulation(
+                    log.record
+                        .embedding
+                        .as_ref()
+                        .expect("Embedding should be present in generated logs"),
+                    &knn_operator.embedding,
+                    DistanceAccumulation::F64,
+                )
+            })
+            .collect();
+
+        brute_force_distances.sort_by(|x, y| x.total_cmp(y));
+
+        let knn_log_output = knn_operator
+            .run(&knn_log_input)
+            .await
+            .expect("KnnLogOperator should not fail");
+
+        assert_eq!(knn_log_output.record_distances.len(), 6);
+        assert!(knn_log_output
+            .record_distances
+            .iter()
+            .zip(brute_force_distances)
+            .all(|(record, distance)| record.measure == distance));
+    }
+
     #[tokio::test]
     async fn test_overfetch() {
         let knn_log_input =
@@ -202,6 +294,9 @@ mod tests {
         let knn_operator = KnnOperator {
             embedding: random_embedding(TEST_EMBEDDING_DIMENSION),
             fetch: 200,
+            max_distance: None,
+            time_limit: None,
+            accumulation: DistanceAccumulation::default(),
         };
 
         let mut brute_force_distances: Vec<_> = knn_log_input
@@ -245,6 +340,9 @@ mod tests {
         let knn_operator = KnnOperator {
             embedding: random_embedding(TEST_EMBEDDING_DIMENSION),
             fetch: 6,
+            max_distance: None,
+            time_limit: None,
+            accumulation: DistanceAccumulation::default(),
         };
 
         let mut brute_force_distances: Vec<_> = knn_log_input
@@ -279,4 +377,92 @@ mod tests {
             .zip(brute_force_distances)
             .all(|(record, distance)| { record.measure == distance }));
     }
+
+    #[tokio::test]
+    async fn test_max_distance() {
+        let knn_log_input =
+            setup_knn_log_input(DistanceFunction::Euclidean, SignedRoaringBitmap::full());
+
+        let embedding = random_embedding(TEST_EMBEDDING_DIMENSION);
+        let mut brute_force_distances: Vec<_> = knn_log_input
+            .logs
+            .iter()
+            .map(|(log, _)| {
+                knn_log_input.distance_function.distance(
+                    log.record
+                        .embedding
+                        .as_ref()
+                        .expect("Embedding should be present in generated logs"),
+                    &embedding,
+                )
+            })
+            .collect();
+
+        brute_force_distances.sort_by(|x, y| x.total_cmp(y));
+
+        // Only the nearest 3 records are within the threshold
+        let knn_operator = KnnOperator {
+            embedding,
+            fetch: 6,
+            max_distance: Some(brute_force_distances[2]),
+            time_limit: None,
+            accumulation: DistanceAccumulation::default(),
+        };
+
+        let knn_log_output = knn_operator
+            .run(&knn_log_input)
+            .await
+            .expect("KnnLogOperator should not fail");
+
+        assert_eq!(knn_log_output.record_distances.len(), 3);
+        assert!(knn_log_output
+            .record_distances
+            .iter()
+            .zip(brute_force_distances)
+            .all(|(record, distance)| record.measure == distance));
+    }
+
+    #[tokio::test]
+    async fn test_time_limit() {
+        let knn_log_input =
+            setup_knn_log_input(DistanceFunction::Euclidean, SignedRoaringBitmap::full());
+
+        let knn_operator = KnnOperator {
+            embedding: random_embedding(TEST_EMBEDDING_DIMENSION),
+            fetch: 6,
+            max_distance: None,
+            time_limit: Some(Duration::ZERO),
+            accumulation: DistanceAccumulation::default(),
+        };
+
+        let knn_log_output = knn_operator
+            .run(&knn_log_input)
+            .await
+            .expect("KnnLogOperator should not fail");
+
+        assert!(knn_log_output.truncated);
+        assert!(knn_log_output.record_distances.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_deadline() {
+        let mut knn_log_input =
+            setup_knn_log_input(DistanceFunction::Euclidean, SignedRoaringBitmap::full());
+        knn_log_input.deadline = Deadline::after(Duration::ZERO);
+
+        let knn_operator = KnnOperator {
+            embedding: random_embedding(TEST_EMBEDDING_DIMENSION),
+            fetch: 6,
+            max_distance: None,
+            time_limit: None,
+            accumulation: DistanceAccumulation::default(),
+        };
+
+        // Unlike the time limit, the deadline fails the query instead of truncating the output
+        let knn_log_error = knn_operator
+            .run(&knn_log_input)
+            .await
+            .expect_err("KnnLogOperator should stop at the deadline");
+        assert_eq!(knn_log_error.code(), ErrorCodes::DeadlineExceeded);
+    }
 }
@@ -79,6 +79,14 @@ impl Operator<KnnMergeInput, KnnMergeOutput> for KnnMergeOperator {
             record_distances: merged_distance,
         })
     }
+
+    fn input_cardinality(&self, input: &KnnMergeInput) -> Option<usize> {
+        Some(input.first_distances.len() + input.second_distances.len())
+    }
+
+    fn output_cardinality(&self, output: &KnnMergeOutput) -> Option<usize> {
+        Some(output.record_distances.len())
+    }
 }
 
 #[cfg(test)]
@@ -1,16 +1,24 @@
 use chroma_blockstore::provider::BlockfileProvider;
 use chroma_error::ChromaError;
+use chroma_types::CollectionUuid;
 use chroma_types::Segment;
 use thiserror::Error;
 use tonic::async_trait;
 use tracing::trace;
 
-use crate::execution::{operator::Operator, operators::projection::ProjectionInput};
+use crate::{
+    execution::{
+        memory::MemoryContext,
+        operator::{Deadline, Operator},
+        operators::projection::ProjectionInput,
+    },
+    segment::SharedMaterializedLogs,
+};
 
 use super::{
     fetch_log::FetchLogOutput,
     knn::RecordDistance,
-    projection::{ProjectionError, ProjectionOperator, ProjectionRecord},
+    projection::{ProjectionDefaults, ProjectionError, ProjectionOperator, ProjectionRecord},
 };
 
 /// The `KnnProjectionOperator` retrieves record content by offset ids
@@ -27,6 +35,9 @@ use super::{
 /// - `record_segment`: The record segment information
 /// - `record_distances`: The offset ids of the record to retrieve for,
 ///   along with their distances to the target embedding
+/// - `projection_defaults`: The projection defaults of the collection
+/// - `deadline`: The deadline of the query, which is passed on to the `ProjectionOperator`
+/// - `memory`: The memory context of the query, which is passed on to the `ProjectionOperator`
 ///
 /// # Outputs
 /// - `records`: The retrieved records in the same order as `record_distances`
@@ -46,6 +57,10 @@ pub struct KnnProjectionInput {
     pub blockfile_provider: BlockfileProvider,
     pub record_segment: Segment,
     pub record_distances: Vec<RecordDistance>,
+    pub projection_defaults: ProjectionDefaults,
+    pub deadline: Deadline,
+    pub memory: MemoryContext,
+    pub materialized_logs: SharedMaterializedLogs,
 }
 
 #[derive(Clone, Debug)]
@@ -57,6 +72,7 @@ pub struct KnnProjectionRecord {
 #[derive(Clone, Debug)]
 pub struct KnnProjectionOutput {
     pub records: Vec<KnnProjectionRecord>,
+    pub truncated: bool,
 }
 
 #[derive(Error, Debug)]
@@ -92,6 +108,10 @@ impl Operator<KnnProjectionInput, KnnProjectionOutput> for KnnProjectionOperator
                 .iter()
                 .map(|record| record.offset_id)
                 .collect(),
+            defaults: input.projection_defaults.clone(),
+            deadline: input.deadline.clone(),
+            memory: input.memory.clone(),
+            materialized_logs: input.materialized_logs.clone(),
         };
 
         let result = self.projection.run(&projection_input).await?;
@@ -114,22 +134,47 @@ impl Operator<KnnProjectionInput, KnnProjectionOutput> for KnnProjectionOperator
                     },
                 )
                 .collect(),
+            truncated: false,
         });
     }
+
+    fn can_retry(&self, error: &KnnProjectionError) -> bool {
+        error.is_transient()
+    }
+
+    fn deadline(&self, input: &KnnProjectionInput) -> Option<Deadline> {
+        Some(input.deadline.clone())
+    }
+
+    fn collection_id(&self, input: &KnnProjectionInput) -> Option<CollectionUuid> {
+        Some(input.record_segment.collection)
+    }
+
+    fn input_cardinality(&self, input: &KnnProjectionInput) -> Option<usize> {
+        Some(input.record_distances.len())
+    }
+
+    fn output_cardinality(&self, output: &KnnProjectionOutput) -> Option<usize> {
+        Some(output.records.len())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
         execution::{
-            operator::Operator,
+            memory::MemoryContext,
+            operator::{Deadline, Operator},
             operators::{
-                knn::RecordDistance, knn_projection::KnnProjectionOperator,
-                projection::ProjectionOperator,
+                knn::RecordDistance,
+                knn_projection::KnnProjectionOperator,
+                projection::{
+                    ProjectionDefaults, ProjectionOperator, DEFAULT_HYDRATION_CONCURRENCY,
+                },
             },
         },
         log::test::{int_as_id, upsert_generator, LogGenerator},
-        segment::test::TestSegment,
+        segment::{test::TestSegment, SharedMaterializedLogs},
     };
 
     use super::KnnProjectionInput;
@@ -154,6 +199,10 @@ mod tests {
             blockfile_provider: test_segment.blockfile_provider,
             record_segment: test_segment.record_segment,
             record_distances,
+            projection_defaults: ProjectionDefaults::default(),
+            deadline: Deadline::default(),
+            memory: MemoryContext::default(),
+            materialized_logs: SharedMaterializedLogs::default(),
         }
     }
 
@@ -175,6 +224,9 @@ mod tests {
                 document: false,
                 embedding: false,
                 metadata: false,
+                uri: false,
+                concurrency: DEFAULT_HYDRATION_CONCURRENCY,
+                default_include: false,
             },
             distance: false,
         };
@@ -216,6 +268,9 @@ mod tests {
                 document: false,
                 embedding: true,
                 metadata: false,
+                uri: false,
+                concurrency: DEFAULT_HYDRATION_CONCURRENCY,
+                default_include: false,
             },
             distance: true,
         };
@@ -1,21 +1,30 @@
 use std::{cmp::Ordering, num::TryFromIntError, sync::atomic};
 
 use chroma_blockstore::provider::BlockfileProvider;
-use chroma_error::{ChromaError, ErrorCodes};
-use chroma_types::{Chunk, LogRecord, MaterializedLogOperation, Segment, SignedRoaringBitmap};
+use chroma_error::{ChromaError, ErrorCodes, ErrorLocation};
+use chroma_types::{
+    chroma_proto, deserialize_roaring_bitmap, serialize_roaring_bitmap, Chunk, CollectionUuid,
+    LogRecord, RoaringBitmapConversionError, Segment, SignedRoaringBitmap,
+};
 use roaring::RoaringBitmap;
 use thiserror::Error;
 use tonic::async_trait;
-use tracing::{trace, Instrument, Span};
+use tracing::trace;
 
 use crate::{
-    execution::operator::Operator,
+    execution::{
+        memory::{MemoryContext, MemoryError},
+        operator::{Deadline, DeadlineError, Operator},
+    },
     segment::{
         record_segment::{RecordSegmentReader, RecordSegmentReaderCreationError},
-        LogMaterializer, LogMaterializerError,
+        LogMaterializerError, SharedMaterializedLogs,
     },
 };
 
+// The number of blocks of offset ids that the seek scanner reads ahead of its position
+const SCAN_READAHEAD_BLOCKS: usize = 4;
+
 /// The `LimitOperator` selects a range or records sorted by their offset ids
 ///
 /// # Parameters
@@ -28,6 +37,9 @@ use crate::{
 /// - `record_segment`: The record segment information
 /// - `log_offset_ids`: The offset ids in the logs to include or exclude before range selection
 /// - `compact_offset_ids`: The offset ids in the blockfile to include or exclude before range selection
+/// - `materialized_logs`: The handle to the logs materialized by the previous operators
+/// - `deadline`: The deadline of the query, checked before each read of the record segment
+/// - `memory`: The memory context of the query, which accounts for the selected offset ids
 ///
 /// # Outputs
 /// - `offset_ids`: The selected offset ids in either logs or blockfile
@@ -47,6 +59,9 @@ pub struct LimitInput {
     pub record_segment: Segment,
     pub log_offset_ids: SignedRoaringBitmap,
     pub compact_offset_ids: SignedRoaringBitmap,
+    pub materialized_logs: SharedMaterializedLogs,
+    pub deadline: Deadline,
+    pub memory: MemoryContext,
 }
 
 #[derive(Debug)]
@@ -54,10 +69,32 @@ pub struct LimitOutput {
     pub offset_ids: RoaringBitmap,
 }
 
+impl From<&LimitOutput> for chroma_proto::LimitOutput {
+    fn from(value: &LimitOutput) -> Self {
+        chroma_proto::LimitOutput {
+            offset_ids: serialize_roaring_bitmap(&value.offset_ids),
+        }
+    }
+}
+
+impl TryFrom<chroma_proto::LimitOutput> for LimitOutput {
+    type Error = RoaringBitmapConversionError;
+
+    fn try_from(value: chroma_proto::LimitOutput) -> Result<Self, Self::Error> {
+        Ok(LimitOutput {
+            offset_ids: deserialize_roaring_bitmap(&value.offset_ids)?,
+        })
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum LimitError {
+    #[error("Limit stopped early: {0}")]
+    Deadline(#[from] DeadlineError),
     #[error("Error materializing log: {0}")]
     LogMaterializer(#[from] LogMaterializerError),
+    #[error("Error accounting for query memory: {0}")]
+    Memory(#[from] MemoryError),
     #[error("Integer conversion out of bound: {0}")]
     OutOfBound(#[from] TryFromIntError),
     #[error("Error creating record segment reader: {0}")]
@@ -69,12 +106,91 @@ pub enum LimitError {
 impl ChromaError for LimitError {
     fn code(&self) -> ErrorCodes {
         match self {
+            LimitError::Deadline(e) => e.code(),
             LimitError::LogMaterializer(e) => e.code(),
+            LimitError::Memory(e) => e.code(),
             LimitError::OutOfBound(_) => ErrorCodes::OutOfRange,
             LimitError::RecordReader(e) => e.code(),
             LimitError::RecordSegment(e) => e.code(),
         }
     }
+
+    fn location(&self) -> ErrorLocation {
+        match self {
+            LimitError::RecordReader(e) => e.location(),
+            LimitError::RecordSegment(e) => e.location(),
+            _ => ErrorLocation::default(),
+        }
+    }
+}
+
+// Select the range of offset ids in the union of two bitmaps without materializing the union
+//
+// The starting offset is found by a binary search on the rank of the union, which is derived
+// from the ranks of both bitmaps and their overlap. The page is then merged from both bitmaps.
+fn select_union_range(
+    left: &RoaringBitmap,
+    right: &RoaringBitmap,
+    skip: u64,
+    fetch: Option<u32>,
+) -> Result<RoaringBitmap, LimitError> {
+    let overlap = left & right;
+    // # of elements less than or equal to target in the union
+    let union_rank = |target: u32| left.rank(target) + right.rank(target) - overlap.rank(target);
+
+    let max_offset_id = match (left.max(), right.max()) {
+        (Some(left_max), Some(right_max)) => left_max.max(right_max),
+        (Some(max), None) | (None, Some(max)) => max,
+        (None, None) => return Ok(RoaringBitmap::new()),
+    };
+    if union_rank(max_offset_id) <= skip {
+        return Ok(RoaringBitmap::new());
+    }
+
+    // Find the smallest offset id with exactly skip elements before it in the union
+    let mut base = 0;
+    let mut size = max_offset_id;
+    while base < size {
+        let mid = base + (size - base) / 2;
+        if union_rank(mid) > skip {
+            size = mid;
+        } else {
+            base = mid + 1;
+        }
+    }
+
+    let mut left_index = left.rank(base) - left.contains(base) as u64;
+    let mut right_index = right.rank(base) - right.contains(base) as u64;
+    let mut fetch = fetch.map(u64::from).unwrap_or(u64::MAX);
+    let mut merged_result = Vec::new();
+    while fetch > 0 {
+        let left_offset_id = left.select(u32::try_from(left_index)?);
+        let right_offset_id = right.select(u32::try_from(right_index)?);
+        let offset_id = match (left_offset_id, right_offset_id) {
+            (Some(left_oid), Some(right_oid)) => {
+                if left_oid <= right_oid {
+                    left_index += 1;
+                }
+                if right_oid <= left_oid {
+                    right_index += 1;
+                }
+                left_oid.min(right_oid)
+            }
+            (Some(oid), None) => {
+                left_index += 1;
+                oid
+            }
+            (None, Some(oid)) => {
+                right_index += 1;
+                oid
+            }
+            (None, None) => break,
+        };
+        merged_result.push(offset_id);
+        fetch -= 1;
+    }
+
+    Ok(RoaringBitmap::from_sorted_iter(merged_result).expect("Merged offset ids should be sorted"))
 }
 
 // This struct aims to help scanning a number of elements starting from a given offset
@@ -83,6 +199,7 @@ struct SeekScanner<'me> {
     log_offset_ids: &'me RoaringBitmap,
     record_segment: &'me RecordSegmentReader<'me>,
     mask: &'me RoaringBitmap,
+    deadline: &'me Deadline,
 }
 
 impl<'me> SeekScanner<'me> {
@@ -127,6 +244,7 @@ impl<'me> SeekScanner<'me> {
 
         let mut base = 0;
         while size > 1 {
+            self.deadline.check()?;
             let half = size / 2;
             let mid = base + half;
 
@@ -151,12 +269,18 @@ impl<'me> SeekScanner<'me> {
         let mut merged_result = Vec::new();
 
         while fetch > 0 {
+            self.deadline.check()?;
             let log_offset_id = self.log_offset_ids.select(u32::try_from(log_index)?);
             let record_offset_id = (record_index < record_count).then_some(
                 self.record_segment
                     .get_offset_id_at_index(record_index)
                     .await?,
             );
+            if let Some(oid) = record_offset_id {
+                // The next blocks are fetched while the offset ids of this block are scanned
+                self.record_segment
+                    .readahead_offset_ids(oid, SCAN_READAHEAD_BLOCKS);
+            }
             match (log_offset_id, record_offset_id) {
                 (_, Some(oid)) if self.mask.contains(oid) => {
                     record_index += 1;
@@ -195,6 +319,7 @@ impl Operator<LimitInput, LimitOutput> for LimitOperator {
 
     async fn run(&self, input: &LimitInput) -> Result<LimitOutput, LimitError> {
         trace!("[{}]: {:?}", self.get_name(), input);
+        input.deadline.check()?;
 
         let record_segment_reader = match RecordSegmentReader::from_segment(
             &input.record_segment,
@@ -213,39 +338,22 @@ impl Operator<LimitInput, LimitOutput> for LimitOperator {
         let mut materialized_log_offset_ids = match &input.log_offset_ids {
             SignedRoaringBitmap::Include(rbm) => rbm.clone(),
             SignedRoaringBitmap::Exclude(rbm) => {
-                let materializer =
-                    LogMaterializer::new(record_segment_reader.clone(), input.logs.clone(), None);
-                let materialized_logs = materializer
-                    .materialize()
-                    .instrument(tracing::trace_span!(parent: Span::current(), "Materialize logs"))
+                let active_domain = input
+                    .materialized_logs
+                    .active_offset_ids(record_segment_reader.clone(), &input.logs)
                     .await?;
-
-                let active_domain: RoaringBitmap = materialized_logs
-                    .iter()
-                    .filter_map(|(log, _)| {
-                        (!matches!(
-                            log.final_operation,
-                            MaterializedLogOperation::DeleteExisting
-                        ))
-                        .then_some(log.offset_id)
-                    })
-                    .collect();
                 active_domain - rbm
             }
         };
 
         // Materialize all filtered offset ids with the compact segment
         let materialized_offset_ids = match &input.compact_offset_ids {
-            SignedRoaringBitmap::Include(rbm) => {
-                let mut merged_offset_ids = materialized_log_offset_ids | rbm;
-                merged_offset_ids.remove_smallest(self.skip as u64);
-                if let Some(fetch_count) = self.fetch {
-                    let truncated_fetch_count = merged_offset_ids.len().min(fetch_count as u64);
-                    merged_offset_ids
-                        .remove_biggest(merged_offset_ids.len() - truncated_fetch_count);
-                }
-                merged_offset_ids
-            }
+            SignedRoaringBitmap::Include(rbm) => select_union_range(
+                &materialized_log_offset_ids,
+                rbm,
+                self.skip as u64,
+                self.fetch,
+            )?,
             SignedRoaringBitmap::Exclude(rbm) => {
                 if let Some(reader) = record_segment_reader {
                     let record_count = reader.count().await?;
@@ -259,6 +367,7 @@ impl Operator<LimitInput, LimitOutput> for LimitOperator {
                         log_offset_ids: &materialized_log_offset_ids,
                         record_segment: &reader,
                         mask: rbm,
+                        deadline: &input.deadline,
                     };
                     seek_scanner
                         .seek_and_scan(truncated_skip, truncated_fetch)
@@ -276,22 +385,46 @@ impl Operator<LimitInput, LimitOutput> for LimitOperator {
                 }
             }
         };
+        input.memory.reserve_bitmaps([&materialized_offset_ids])?;
 
         Ok(LimitOutput {
             offset_ids: materialized_offset_ids,
         })
     }
+
+    fn can_retry(&self, error: &LimitError) -> bool {
+        error.is_transient()
+    }
+
+    fn deadline(&self, input: &LimitInput) -> Option<Deadline> {
+        Some(input.deadline.clone())
+    }
+
+    fn collection_id(&self, input: &LimitInput) -> Option<CollectionUuid> {
+        Some(input.record_segment.collection)
+    }
+
+    fn output_cardinality(&self, output: &LimitOutput) -> Option<usize> {
+        Some(output.offset_ids.len() as usize)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use chroma_types::SignedRoaringBitmap;
+    use std::time::Duration;
+
+    use chroma_error::{ChromaError, ErrorCodes};
+    use chroma_types::{Chunk, SignedRoaringBitmap};
     use roaring::RoaringBitmap;
 
     use crate::{
-        execution::{operator::Operator, operators::limit::LimitOperator},
+        execution::{
+            memory::MemoryContext,
+            operator::{Deadline, Operator},
+            operators::limit::{LimitError, LimitOperator},
+        },
         log::test::{upsert_generator, LogGenerator},
-        segment::test::TestSegment,
+        segment::{test::TestSegment, SharedMaterializedLogs},
     };
 
     use super::LimitInput;
@@ -316,6 +449,9 @@ mod tests {
             record_segment: test_segment.record_segment,
             log_offset_ids,
             compact_offset_ids,
+            materialized_logs: SharedMaterializedLogs::default(),
+            deadline: Deadline::default(),
+            memory: MemoryContext::default(),
         }
     }
 
@@ -340,6 +476,34 @@ mod tests {
         assert_eq!(limit_output.offset_ids, (1..=100).collect());
     }
 
+    #[tokio::test]
+    async fn test_limit_reuses_materialized_logs() {
+        let mut limit_input = setup_limit_input(
+            SignedRoaringBitmap::full(),
+            SignedRoaringBitmap::Exclude((31..=60).collect()),
+        )
+        .await;
+
+        let limit_operator = LimitOperator {
+            skip: 0,
+            fetch: None,
+        };
+
+        limit_operator
+            .run(&limit_input)
+            .await
+            .expect("LimitOperator should not fail");
+
+        // The logs materialized by the first run are reused even if the logs are not given
+        limit_input.logs = Chunk::new(Vec::new().into());
+        let limit_output = limit_operator
+            .run(&limit_input)
+            .await
+            .expect("LimitOperator should not fail");
+
+        assert_eq!(limit_output.offset_ids, (1..=100).collect());
+    }
+
     #[tokio::test]
     async fn test_overskip() {
         let limit_input = setup_limit_input(
@@ -429,4 +593,60 @@ mod tests {
                 .collect()
         );
     }
+
+    #[tokio::test]
+    async fn test_include_limit() {
+        let limit_input = setup_limit_input(
+            SignedRoaringBitmap::Include((31..=60).filter(|offset| offset % 2 == 0).collect()),
+            SignedRoaringBitmap::Include((1..=100).filter(|offset| offset % 3 == 0).collect()),
+        )
+        .await;
+
+        let limit_operator = LimitOperator {
+            skip: 10,
+            fetch: Some(20),
+        };
+
+        let limit_output = limit_operator
+            .run(&limit_input)
+            .await
+            .expect("LimitOperator should not fail");
+
+        assert_eq!(
+            limit_output.offset_ids,
+            (31..=60)
+                .filter(|offset| offset % 2 == 0 || offset % 3 == 0)
+                .collect()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_limit_stops_at_deadline() {
+        let mut limit_input = setup_limit_input(
+            SignedRoaringBitmap::full(),
+            SignedRoaringBitmap::Exclude((31..=60).collect()),
+        )
+        .await;
+        limit_input.deadline = Deadline::after(Duration::ZERO);
+
+        let limit_operator = LimitOperator {
+            skip: 10,
+            fetch: Some(20),
+        };
+
+        let limit_error = limit_operator
+            .run(&limit_input)
+            .await
+            .expect_err("LimitOperator should stop at the deadline");
+        assert_eq!(limit_error.code(), ErrorCodes::DeadlineExceeded);
+
+        limit_input.deadline = Deadline::default();
+        limit_input.deadline.cancel();
+        let limit_error = limit_operator
+            .run(&limit_input)
+            .await
+            .expect_err("LimitOperator should stop once cancelled");
+        assert!(matches!(limit_error, LimitError::Deadline(_)));
+        assert_eq!(limit_error.code(), ErrorCodes::Cancelled);
+    }
 }
@@ -0,0 +1,239 @@
+use std::collections::{HashMap, HashSet};
+
+use async_trait::async_trait;
+use chroma_blockstore::provider::BlockfileProvider;
+use chroma_error::{ChromaError, ErrorCodes};
+use chroma_types::{Chunk, CollectionUuid, LogRecord, MaterializedLogOperation, Segment};
+use futures::{stream, StreamExt, TryStreamExt};
+use thiserror::Error;
+use tracing::{trace, Instrument, Span};
+
+use crate::{
+    execution::{
+        operator::Operator,
+        operators::projection::{ProjectionDefaults, ProjectionOperator, ProjectionRecord},
+    },
+    segment::{
+        record_segment::{RecordSegmentReader, RecordSegmentReaderCreationError},
+        LogMaterializer, LogMaterializerError,
+    },
+};
+
+/// The `LookupOperator` fetches the records of a collection by their user ids, which are
+/// referenced by the records of a query on another collection
+///
+/// # Parameters
+/// - `projection`: The fields of the records to retrieve
+///
+/// # Inputs
+/// - `logs`: The latest logs of the collection
+/// - `blockfile_provider`: The blockfile provider
+/// - `record_segment`: The record segment information
+/// - `ids`: The user ids of the records to fetch
+/// - `defaults`: The projection defaults of the collection, which restrict the fields that
+///   could be retrieved regardless of the parameters
+///
+/// # Outputs
+/// - `records`: The fetched records by user id. The ids without a record are left out
+///
+/// # Usage
+/// It can be used to join the result of a query with the records of another collection, e.g.
+/// to return the parent document of each chunk that a query matches
+#[derive(Clone, Debug)]
+pub struct LookupOperator {
+    pub projection: ProjectionOperator,
+}
+
+#[derive(Debug)]
+pub struct LookupInput {
+    pub logs: Chunk<LogRecord>,
+    pub blockfile_provider: BlockfileProvider,
+    pub record_segment: Segment,
+    pub ids: Vec<String>,
+    pub defaults: ProjectionDefaults,
+}
+
+#[derive(Debug)]
+pub struct LookupOutput {
+    pub records: HashMap<String, ProjectionRecord>,
+}
+
+#[derive(Error, Debug)]
+pub enum LookupError {
+    #[error("Error materializing log: {0}")]
+    LogMaterializer(#[from] LogMaterializerError),
+    #[error("Error creating record segment reader: {0}")]
+    RecordReader(#[from] RecordSegmentReaderCreationError),
+    #[error("Error reading record segment: {0}")]
+    RecordSegment(#[from] Box<dyn ChromaError>),
+}
+
+impl ChromaError for LookupError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            LookupError::LogMaterializer(e) => e.code(),
+            LookupError::RecordReader(e) => e.code(),
+            LookupError::RecordSegment(e) => e.code(),
+        }
+    }
+}
+
+#[async_trait]
+impl Operator<LookupInput, LookupOutput> for LookupOperator {
+    type Error = LookupError;
+
+    async fn run(&self, input: &LookupInput) -> Result<LookupOutput, LookupError> {
+        trace!("[{}]: {:?}", self.get_name(), input);
+
+        let projection = self.projection.with_defaults(&input.defaults);
+        let ids = input.ids.iter().map(String::as_str).collect::<HashSet<_>>();
+
+        let materializer = LogMaterializer::new(
+            open_record_segment_reader(&input.record_segment, &input.blockfile_provider).await?,
+            input.logs.clone(),
+            None,
+        );
+        let materialized_logs = materializer
+            .materialize()
+            .instrument(tracing::trace_span!(parent: Span::current(), "Materialize logs"))
+            .await?;
+
+        // The logs shadow the record segment, so an id that is deleted in the logs is not
+        // looked up in the record segment
+        let mut records = HashMap::new();
+        let mut logged_ids = HashSet::new();
+        for (log, _) in materialized_logs.iter() {
+            let id = log.merged_user_id_ref();
+            if !ids.contains(id) {
+                continue;
+            }
+            logged_ids.insert(id);
+            if log.final_operation != MaterializedLogOperation::DeleteExisting {
+                records.insert(id.to_string(), projection.project_log_record(log));
+            }
+        }
+
+        let segment_ids = ids
+            .into_iter()
+            .filter(|id| !logged_ids.contains(id))
+            .collect::<Vec<_>>();
+        let record_segment_reader =
+            open_record_segment_reader(&input.record_segment, &input.blockfile_provider).await?;
+        if let (Some(reader), false) = (&record_segment_reader, segment_ids.is_empty()) {
+            let projection = &projection;
+            // The lookups are created upfront so that the stream does not hold a closure over
+            // borrowed ids, which would keep the future of the operator from being Send
+            let lookups = segment_ids
+                .into_iter()
+                .map(|id| async move {
+                    let offset_id = match reader.get_offset_id_for_user_id(id).await? {
+                        Some(offset_id) => offset_id,
+                        None => return Ok::<_, LookupError>(None),
+                    };
+                    let data_record = reader.get_data_for_offset_id(offset_id).await?.ok_or(
+                        RecordSegmentReaderCreationError::DataRecordNotFound(offset_id),
+                    )?;
+                    Ok(Some(projection.project_data_record(offset_id, data_record)))
+                })
+                .collect::<Vec<_>>();
+            let mut segment_records =
+                stream::iter(lookups).buffer_unordered(projection.concurrency.max(1));
+            while let Some(record) = segment_records.try_next().await? {
+                if let Some(record) = record {
+                    records.insert(record.id.clone(), record);
+                }
+            }
+        }
+
+        Ok(LookupOutput { records })
+    }
+
+    fn collection_id(&self, input: &LookupInput) -> Option<CollectionUuid> {
+        Some(input.record_segment.collection)
+    }
+
+    fn input_cardinality(&self, input: &LookupInput) -> Option<usize> {
+        Some(input.ids.len())
+    }
+
+    fn output_cardinality(&self, output: &LookupOutput) -> Option<usize> {
+        Some(output.records.len())
+    }
+}
+
+async fn open_record_segment_reader<'me>(
+    record_segment: &Segment,
+    blockfile_provider: &BlockfileProvider,
+) -> Result<Option<RecordSegmentReader<'me>>, LookupError> {
+    match RecordSegmentReader::from_segment(record_segment, blockfile_provider).await {
+        Ok(reader) => Ok(Some(reader)),
+        // Nothing is compacted for the collection, so its records are all in the logs
+        Err(e) if matches!(*e, RecordSegmentReaderCreationError::UninitializedSegment) => Ok(None),
+        Err(e) => Err((*e).into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        execution::{
+            operator::Operator,
+            operators::{
+                lookup::{LookupInput, LookupOperator},
+                projection::{
+                    ProjectionDefaults, ProjectionOperator, DEFAULT_HYDRATION_CONCURRENCY,
+                },
+            },
+        },
+        log::test::{int_as_id, upsert_generator, LogGenerator},
+        segment::test::TestSegment,
+    };
+
+    #[tokio::test]
+    async fn test_lookup() {
+        // Compacted: Upsert [1..=100], Log: Upsert [91..=110]
+        let mut test_segment = TestSegment::default();
+        let generator = LogGenerator {
+            generator: upsert_generator,
+        };
+        test_segment.populate_with_generator(100, &generator).await;
+        let input = LookupInput {
+            logs: generator.generate_chunk(91..=110),
+            blockfile_provider: test_segment.blockfile_provider,
+            record_segment: test_segment.record_segment,
+            ids: [5, 95, 105, 500].into_iter().map(int_as_id).collect(),
+            defaults: ProjectionDefaults {
+                embedding: false,
+                ..Default::default()
+            },
+        };
+
+        let output = LookupOperator {
+            projection: ProjectionOperator {
+                document: true,
+                embedding: true,
+                metadata: true,
+                uri: false,
+                concurrency: DEFAULT_HYDRATION_CONCURRENCY,
+                default_include: false,
+            },
+        }
+        .run(&input)
+        .await
+        .expect("LookupOperator should not fail");
+
+        assert_eq!(output.records.len(), 3);
+        for id in [5, 95, 105] {
+            let record = output
+                .records
+                .get(&int_as_id(id))
+                .expect("Record should be found");
+            assert!(record.document.is_some());
+            assert!(record.metadata.is_some());
+            // The embedding is not allowed by the defaults of the collection
+            assert!(record.embedding.is_none());
+            assert!(record.uri.is_none());
+        }
+        assert!(!output.records.contains_key(&int_as_id(500)));
+    }
+}
@@ -1,15 +1,28 @@
+use std::mem::size_of;
+
 use crate::{
-    execution::operator::Operator,
+    execution::{
+        operator::Operator,
+        spill::{SpillConfig, Spillable, SpillableVec},
+    },
     segment::record_segment::{RecordSegmentReader, RecordSegmentReaderCreationError},
 };
 use async_trait::async_trait;
 use chroma_blockstore::provider::BlockfileProvider;
-use chroma_error::{ChromaError, ErrorCodes};
+use chroma_error::ChromaError;
+use chroma_types::CollectionUuid;
 use chroma_types::Segment;
-use thiserror::Error;
+use futures::TryStreamExt;
 
+/// Merges the HNSW and the brute force results of a query vector into the top k results
+///
+/// The merged results are spilled to disk once they exceed the budget of the spill config,
+/// and are read back a chunk at a time, so that the user ids and the vectors of the HNSW
+/// results are only read for the results that are selected, in a batch per chunk
 #[derive(Debug)]
-pub struct MergeKnnResultsOperator {}
+pub struct MergeKnnResultsOperator {
+    pub spill: Option<SpillConfig>,
+}
 
 #[derive(Debug)]
 pub struct MergeKnnBruteForceResultInput {
@@ -60,15 +73,6 @@ pub struct MergeKnnResultsOperatorOutput {
     pub vectors: Option<Vec<Vec<f32>>>,
 }
 
-#[derive(Error, Debug)]
-pub enum MergeKnnResultsOperatorError {}
-
-impl ChromaError for MergeKnnResultsOperatorError {
-    fn code(&self) -> ErrorCodes {
-        ErrorCodes::Unknown
-    }
-}
-
 #[async_trait]
 impl Operator<MergeKnnResultsOperatorInput, MergeKnnResultsOperatorOutput>
     for MergeKnnResultsOperator
@@ -83,114 +87,117 @@ impl Operator<MergeKnnResultsOperatorInput, MergeKnnResultsOperatorOutput>
         &self,
         input: &MergeKnnResultsOperatorInput,
     ) -> Result<MergeKnnResultsOperatorOutput, Self::Error> {
-        let (result_user_ids, result_distances, result_vectors) =
-            match RecordSegmentReader::from_segment(
-                &input.record_segment_definition,
-                &input.blockfile_provider,
-            )
-            .await
-            {
-                Ok(reader) => {
-                    // Convert the HNSW result offset IDs to user IDs
-                    let mut hnsw_result_user_ids = Vec::new();
-                    let mut hnsw_result_vectors = None;
-                    if input.include_vectors {
-                        hnsw_result_vectors = Some(Vec::new());
-                    }
-                    for offset_id in &input.hnsw_result_offset_ids {
-                        let user_id = reader.get_user_id_for_offset_id(*offset_id as u32).await;
-                        match user_id {
-                            Ok(user_id) => hnsw_result_user_ids.push(user_id),
-                            Err(e) => return Err(e),
-                        }
-                        if let Some(hnsw_result_vectors) = &mut hnsw_result_vectors {
-                            let record = reader.get_data_for_offset_id(*offset_id as u32).await;
-                            match record {
-                                Ok(Some(record)) => {
-                                    hnsw_result_vectors.push(record.embedding.to_vec())
-                                }
-                                Ok(None) => {
-                                    return Err(Box::new(
-                                        RecordSegmentReaderCreationError::DataRecordNotFound(
-                                            *offset_id as u32,
-                                        ),
-                                    ));
-                                }
-                                Err(e) => return Err(e),
-                            }
-                        }
-                    }
+        let reader = match RecordSegmentReader::from_segment(
+            &input.record_segment_definition,
+            &input.blockfile_provider,
+        )
+        .await
+        {
+            Ok(reader) => Some(reader),
+            Err(e) => match *e {
+                // The record segment doesn't exist - which implies no HNSW results
+                RecordSegmentReaderCreationError::UninitializedSegment => None,
+                _ => return Err(e),
+            },
+        };
+        let hnsw_result_count = match reader {
+            Some(_) => input.hnsw_result_offset_ids.len(),
+            None => 0,
+        };
+        let (brute_force_user_ids, brute_force_distances, brute_force_vectors) =
+            match &input.brute_force_result {
+                Some(brute_force_result) => (
+                    brute_force_result.user_ids.as_slice(),
+                    brute_force_result.distances.as_slice(),
+                    brute_force_result.vectors.as_slice(),
+                ),
+                None => (&[][..], &[][..], &[][..]),
+            };
 
-                    match &input.brute_force_result {
-                        Some(brute_force_result) => merge_results(
-                            &hnsw_result_user_ids,
-                            &input.hnsw_result_distances,
-                            &hnsw_result_vectors,
-                            &brute_force_result.user_ids,
-                            &brute_force_result.distances,
-                            &brute_force_result.vectors,
-                            input.include_vectors,
-                            input.k,
-                        ),
-                        None => {
-                            // There are no brute force results
-                            (
-                                hnsw_result_user_ids
-                                    .iter()
-                                    .map(|x| x.to_string())
-                                    .collect::<Vec<_>>(),
-                                input.hnsw_result_distances.clone(),
-                                hnsw_result_vectors,
-                            )
-                        }
-                    }
+        // Merge the HNSW and brute force results together by the minimum distance top k
+        let mut merged_results = SpillableVec::new(self.spill.clone());
+        let mut merged_count = 0;
+        let mut hnsw_index = 0;
+        let mut brute_force_index = 0;
+        while merged_count < input.k
+            && (hnsw_index < hnsw_result_count || brute_force_index < brute_force_user_ids.len())
+        {
+            let from_hnsw = match (
+                hnsw_index < hnsw_result_count,
+                brute_force_index < brute_force_user_ids.len(),
+            ) {
+                (true, true) => {
+                    input.hnsw_result_distances[hnsw_index]
+                        < brute_force_distances[brute_force_index]
                 }
-                Err(e) => match *e {
-                    RecordSegmentReaderCreationError::BlockfileOpenError(e) => {
-                        return Err(e);
-                    }
-                    RecordSegmentReaderCreationError::InvalidNumberOfFiles => {
-                        return Err(e);
-                    }
-                    RecordSegmentReaderCreationError::DataRecordNotFound(_) => {
-                        return Err(e);
-                    }
-                    RecordSegmentReaderCreationError::UserRecordNotFound(_) => {
-                        return Err(e);
-                    }
-                    RecordSegmentReaderCreationError::UninitializedSegment => {
-                        // The record segment doesn't exist - which implies no HNSW results
-                        let hnsw_result_user_ids = Vec::new();
-                        let hnsw_result_distances = Vec::new();
-                        let hnsw_result_vectors = None;
+                (from_hnsw, _) => from_hnsw,
+            };
+            let merged_result = if from_hnsw {
+                hnsw_index += 1;
+                MergedResult {
+                    brute_force: false,
+                    position: (hnsw_index - 1) as u32,
+                    distance: input.hnsw_result_distances[hnsw_index - 1],
+                }
+            } else {
+                brute_force_index += 1;
+                MergedResult {
+                    brute_force: true,
+                    position: (brute_force_index - 1) as u32,
+                    distance: brute_force_distances[brute_force_index - 1],
+                }
+            };
+            merged_results
+                .push(merged_result)
+                .await
+                .map_err(|e| Box::new(e) as Box<dyn ChromaError>)?;
+            merged_count += 1;
+        }
 
-                        match &input.brute_force_result {
-                            Some(brute_force_result) => merge_results(
-                                &hnsw_result_user_ids,
-                                &hnsw_result_distances,
-                                &hnsw_result_vectors,
-                                &brute_force_result.user_ids,
-                                &brute_force_result.distances,
-                                &brute_force_result.vectors,
-                                input.include_vectors,
-                                input.k,
-                            ),
-                            None => {
-                                // There are no HNSW results and no brute force results
-                                (
-                                    Vec::new(),
-                                    Vec::new(),
-                                    if input.include_vectors {
-                                        Some(Vec::new())
-                                    } else {
-                                        None
-                                    },
-                                )
-                            }
-                        }
+        let mut result_user_ids = Vec::with_capacity(merged_count);
+        let mut result_distances = Vec::with_capacity(merged_count);
+        let mut result_vectors = input
+            .include_vectors
+            .then(|| Vec::with_capacity(merged_count));
+        let mut merged_chunks = Box::pin(merged_results.into_stream());
+        while let Some(merged_chunk) = merged_chunks
+            .try_next()
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn ChromaError>)?
+        {
+            let hnsw_offset_ids = merged_chunk
+                .iter()
+                .filter(|merged_result| !merged_result.brute_force)
+                .map(|merged_result| {
+                    input.hnsw_result_offset_ids[merged_result.position as usize] as u32
+                })
+                .collect::<Vec<_>>();
+            let mut hnsw_records = match &reader {
+                Some(reader) if !hnsw_offset_ids.is_empty() => {
+                    reader.get_data_for_offset_ids(&hnsw_offset_ids).await?
+                }
+                _ => Vec::new(),
+            }
+            .into_iter();
+            for merged_result in merged_chunk {
+                let position = merged_result.position as usize;
+                result_distances.push(merged_result.distance);
+                if merged_result.brute_force {
+                    result_user_ids.push(brute_force_user_ids[position].clone());
+                    if let Some(result_vectors) = result_vectors.as_mut() {
+                        result_vectors.push(brute_force_vectors[position].clone());
                     }
-                },
-            };
+                } else {
+                    let record = hnsw_records
+                        .next()
+                        .expect("There should be a record for each HNSW result");
+                    result_user_ids.push(record.id.to_string());
+                    if let Some(result_vectors) = result_vectors.as_mut() {
+                        result_vectors.push(record.embedding.to_vec());
+                    }
+                }
+            }
+        }
 
         Ok(MergeKnnResultsOperatorOutput {
             user_ids: result_user_ids,
@@ -198,92 +205,128 @@ impl Operator<MergeKnnResultsOperatorInput, MergeKnnResultsOperatorOutput>
             vectors: result_vectors,
         })
     }
-}
 
-#[allow(clippy::too_many_arguments)]
-fn merge_results(
-    hnsw_result_user_ids: &[&str],
-    hnsw_result_distances: &[f32],
-    hnsw_result_vectors: &Option<Vec<Vec<f32>>>,
-    brute_force_result_user_ids: &[String],
-    brute_force_result_distances: &[f32],
-    brute_force_result_vectors: &[Vec<f32>],
-    include_vectors: bool,
-    k: usize,
-) -> (Vec<String>, Vec<f32>, Option<Vec<Vec<f32>>>) {
-    let mut result_user_ids = Vec::with_capacity(k);
-    let mut result_distances = Vec::with_capacity(k);
-    let mut result_vectors = None;
-    if include_vectors {
-        result_vectors = Some(Vec::with_capacity(k));
+    fn collection_id(&self, input: &MergeKnnResultsOperatorInput) -> Option<CollectionUuid> {
+        Some(input.record_segment_definition.collection)
     }
 
-    // Merge the HNSW and brute force results together by the minimum distance top k
-    let mut hnsw_index = 0;
-    let mut brute_force_index = 0;
+    fn input_cardinality(&self, input: &MergeKnnResultsOperatorInput) -> Option<usize> {
+        Some(
+            input.hnsw_result_offset_ids.len()
+                + input
+                    .brute_force_result
+                    .as_ref()
+                    .map_or(0, |result| result.user_ids.len()),
+        )
+    }
 
-    // TODO: This doesn't have to clone the user IDs, but it's easier for now
-    while (result_user_ids.len() < k)
-        && (hnsw_index < hnsw_result_user_ids.len()
-            || brute_force_index < brute_force_result_user_ids.len())
-    {
-        if hnsw_index < hnsw_result_user_ids.len()
-            && brute_force_index < brute_force_result_user_ids.len()
-        {
-            if hnsw_result_distances[hnsw_index] < brute_force_result_distances[brute_force_index] {
-                result_user_ids.push(hnsw_result_user_ids[hnsw_index].to_string());
-                result_distances.push(hnsw_result_distances[hnsw_index]);
-                if include_vectors {
-                    result_vectors
-                        .as_mut()
-                        .expect("Include vectors is true, result_vectors should be Some")
-                        .push(
-                            hnsw_result_vectors.as_ref().expect(
-                                "Include vectors is true, hnsw_result_vectors should be Some",
-                            )[hnsw_index]
-                                .to_vec(),
-                        );
-                }
-                hnsw_index += 1;
-            } else {
-                result_user_ids.push(brute_force_result_user_ids[brute_force_index].to_string());
-                result_distances.push(brute_force_result_distances[brute_force_index]);
-                if include_vectors {
-                    result_vectors
-                        .as_mut()
-                        .expect("Include vectors is true, result_vectors should be Some")
-                        .push(brute_force_result_vectors[brute_force_index].to_vec());
-                }
-                brute_force_index += 1;
-            }
-        } else if hnsw_index < hnsw_result_user_ids.len() {
-            result_user_ids.push(hnsw_result_user_ids[hnsw_index].to_string());
-            result_distances.push(hnsw_result_distances[hnsw_index]);
-            if include_vectors {
-                result_vectors
-                    .as_mut()
-                    .expect("Include vectors is true, result_vectors should be Some")
-                    .push(
-                        hnsw_result_vectors
-                            .as_ref()
-                            .expect("Include vectors is true, hnsw_result_vectors should be Some")
-                            [hnsw_index]
-                            .to_vec(),
-                    );
-            }
-            hnsw_index += 1;
-        } else if brute_force_index < brute_force_result_user_ids.len() {
-            result_user_ids.push(brute_force_result_user_ids[brute_force_index].to_string());
-            result_distances.push(brute_force_result_distances[brute_force_index]);
-            if include_vectors {
-                result_vectors
-                    .as_mut()
-                    .expect("Include vectors is true, result_vectors should be Some")
-                    .push(brute_force_result_vectors[brute_force_index].to_vec());
-            }
-            brute_force_index += 1;
+    fn output_cardinality(&self, output: &MergeKnnResultsOperatorOutput) -> Option<usize> {
+        Some(output.user_ids.len())
+    }
+}
+
+/// A result of the merge, as the position of the result in the HNSW or the brute force
+/// results of the query vector
+#[derive(Clone, Debug)]
+struct MergedResult {
+    brute_force: bool,
+    position: u32,
+    distance: f32,
+}
+
+impl Spillable for MergedResult {
+    const ENCODED_SIZE: usize = size_of::<u8>() + size_of::<u32>() + size_of::<f32>();
+
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        bytes.push(self.brute_force as u8);
+        bytes.extend_from_slice(&self.position.to_le_bytes());
+        bytes.extend_from_slice(&self.distance.to_le_bytes());
+    }
+
+    fn decode(bytes: &[u8]) -> Self {
+        let (brute_force, bytes) = bytes.split_at(size_of::<u8>());
+        let (position, distance) = bytes.split_at(size_of::<u32>());
+        MergedResult {
+            brute_force: brute_force[0] != 0,
+            position: u32::from_le_bytes(position.try_into().expect("Should be 4 bytes")),
+            distance: f32::from_le_bytes(distance.try_into().expect("Should be 4 bytes")),
         }
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::size_of;
 
-    (result_user_ids, result_distances, result_vectors)
+    use crate::{
+        execution::{operator::Operator, spill::SpillConfig},
+        log::test::{int_as_id, upsert_generator, LogGenerator},
+        segment::test::TestSegment,
+    };
+
+    use super::{
+        MergeKnnBruteForceResultInput, MergeKnnResultsOperator, MergeKnnResultsOperatorInput,
+        MergedResult,
+    };
+
+    #[tokio::test]
+    async fn test_spilled_merge() {
+        let mut test_segment = TestSegment::default();
+        test_segment
+            .populate_with_generator(
+                20,
+                &LogGenerator {
+                    generator: upsert_generator,
+                },
+            )
+            .await;
+        let tmp_dir = tempfile::tempdir().unwrap();
+
+        // The HNSW results have the even distances and the brute force results the odd ones
+        let input = MergeKnnResultsOperatorInput::new(
+            (1..=10).map(|offset_id| offset_id * 2).collect(),
+            (1..=10).map(|offset_id| (offset_id * 2) as f32).collect(),
+            Some(MergeKnnBruteForceResultInput {
+                user_ids: (1..=10)
+                    .map(|offset_id| format!("brute_force_{}", offset_id * 2 - 1))
+                    .collect(),
+                distances: (1..=10)
+                    .map(|offset_id| (offset_id * 2 - 1) as f32)
+                    .collect(),
+                vectors: (1..=10)
+                    .map(|offset_id| vec![(offset_id * 2 - 1) as f32])
+                    .collect(),
+            }),
+            true,
+            15,
+            test_segment.record_segment.clone(),
+            test_segment.blockfile_provider.clone(),
+        );
+        let output = MergeKnnResultsOperator {
+            spill: Some(SpillConfig {
+                path: tmp_dir.path().to_path_buf(),
+                budget_bytes: 4 * size_of::<MergedResult>(),
+            }),
+        }
+        .run(&input)
+        .await
+        .expect("MergeKnnResultsOperator should not fail");
+
+        assert_eq!(
+            output.user_ids,
+            (1..=15)
+                .map(|distance| match distance % 2 {
+                    0 => int_as_id(distance),
+                    _ => format!("brute_force_{}", distance),
+                })
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            output.distances,
+            (1..=15).map(|distance| distance as f32).collect::<Vec<_>>()
+        );
+        let vectors = output.vectors.expect("The vectors should be included");
+        assert_eq!(vectors.len(), 15);
+        assert_eq!(vectors[0], vec![1.0]);
+    }
 }
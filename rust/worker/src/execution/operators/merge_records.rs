@@ -0,0 +1,273 @@
+use std::collections::HashMap;
+
+use chroma_blockstore::provider::BlockfileProvider;
+use chroma_error::{ChromaError, ErrorCodes};
+use chroma_types::{Chunk, LogRecord, Operation, OperationRecord, Segment};
+use futures::TryStreamExt;
+use thiserror::Error;
+use tonic::async_trait;
+use tracing::trace;
+
+use crate::{
+    execution::operator::Operator,
+    segment::record_segment::{RecordSegmentReader, RecordSegmentReaderCreationError},
+};
+
+/// How to resolve a user id that is present in more than one of the merged collections
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MergeDuplicatePolicy {
+    /// Keep the record of the first collection that has the user id
+    #[default]
+    KeepFirst,
+    /// Keep the record of the last collection that has the user id
+    KeepLast,
+    /// Fail the merge
+    Fail,
+}
+
+/// The `MergeRecordsOperator` reads the records of several record segments and
+/// turns them into a single log of `Add` records, with at most one record per user id
+///
+/// # Parameters
+/// - `duplicate_policy`: How to resolve a user id that is present in more than one segment
+///
+/// # Inputs
+/// - `blockfile_provider`: The blockfile provider
+/// - `record_segments`: The record segments to merge, in order
+///
+/// # Outputs
+/// - `logs`: The `Add` records of the merged record segments, with consecutive log offsets
+///   starting from 1
+///
+/// # Usage
+/// It is used to build the segments of a collection that merges several collections. The offset
+/// ids of the records are not kept, so that new offset ids are assigned when the logs are applied
+#[derive(Clone, Debug)]
+pub struct MergeRecordsOperator {
+    pub duplicate_policy: MergeDuplicatePolicy,
+}
+
+#[derive(Debug)]
+pub struct MergeRecordsInput {
+    pub blockfile_provider: BlockfileProvider,
+    pub record_segments: Vec<Segment>,
+}
+
+#[derive(Debug)]
+pub struct MergeRecordsOutput {
+    pub logs: Chunk<LogRecord>,
+}
+
+#[derive(Error, Debug)]
+pub enum MergeRecordsError {
+    #[error("User id {0} is present in more than one collection")]
+    DuplicateUserId(String),
+    #[error("Error creating record segment reader: {0}")]
+    RecordReader(#[from] RecordSegmentReaderCreationError),
+    #[error("Error reading record segment: {0}")]
+    RecordSegment(#[from] Box<dyn ChromaError>),
+}
+
+impl ChromaError for MergeRecordsError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            MergeRecordsError::DuplicateUserId(_) => ErrorCodes::AlreadyExists,
+            MergeRecordsError::RecordReader(e) => e.code(),
+            MergeRecordsError::RecordSegment(e) => e.code(),
+        }
+    }
+}
+
+#[async_trait]
+impl Operator<MergeRecordsInput, MergeRecordsOutput> for MergeRecordsOperator {
+    type Error = MergeRecordsError;
+
+    async fn run(
+        &self,
+        input: &MergeRecordsInput,
+    ) -> Result<MergeRecordsOutput, MergeRecordsError> {
+        trace!("[{}]: {:?}", self.get_name(), input);
+
+        let mut records: Vec<OperationRecord> = Vec::new();
+        let mut positions: HashMap<String, usize> = HashMap::new();
+        for record_segment in &input.record_segments {
+            let reader =
+                match RecordSegmentReader::from_segment(record_segment, &input.blockfile_provider)
+                    .await
+                {
+                    Ok(reader) => reader,
+                    // The segment of an empty collection is not initialized
+                    Err(e)
+                        if matches!(*e, RecordSegmentReaderCreationError::UninitializedSegment) =>
+                    {
+                        continue
+                    }
+                    Err(e) => return Err((*e).into()),
+                };
+
+            let mut data_records = Box::pin(reader.scan(None));
+            while let Some((_, data_record)) = data_records.try_next().await? {
+                let record = OperationRecord {
+                    id: data_record.id.to_string(),
+                    embedding: Some(data_record.embedding.to_vec()),
+                    encoding: None,
+                    metadata: data_record.metadata.map(|metadata| {
+                        metadata
+                            .into_iter()
+                            .map(|(key, value)| (key, value.into()))
+                            .collect()
+                    }),
+                    document: data_record.document.map(str::to_string),
+                    uri: data_record.uri.map(str::to_string),
+                    idempotency_key: None,
+                    embedding_reference: None,
+                    operation: Operation::Add,
+                };
+                match positions.get(&record.id) {
+                    Some(position) => match self.duplicate_policy {
+                        MergeDuplicatePolicy::KeepFirst => {}
+                        MergeDuplicatePolicy::KeepLast => records[*position] = record,
+                        MergeDuplicatePolicy::Fail => {
+                            return Err(MergeRecordsError::DuplicateUserId(record.id))
+                        }
+                    },
+                    None => {
+                        positions.insert(record.id.clone(), records.len());
+                        records.push(record);
+                    }
+                }
+            }
+        }
+
+        let logs = records
+            .into_iter()
+            .enumerate()
+            .map(|(index, record)| LogRecord {
+                log_offset: index as i64 + 1,
+                record,
+            })
+            .collect::<Vec<_>>();
+        Ok(MergeRecordsOutput {
+            logs: Chunk::new(logs.into()),
+        })
+    }
+
+    fn output_cardinality(&self, output: &MergeRecordsOutput) -> Option<usize> {
+        Some(output.logs.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chroma_types::{Operation, OperationRecord};
+
+    use crate::{
+        execution::{
+            operator::Operator,
+            operators::merge_records::{
+                MergeDuplicatePolicy, MergeRecordsError, MergeRecordsInput, MergeRecordsOperator,
+            },
+        },
+        log::test::{int_as_id, upsert_generator, LogGenerator},
+        segment::test::TestSegment,
+    };
+
+    /// The two segments share the user ids 6 to 10, and the records of the second segment
+    /// have a distinct document
+    async fn setup_merge_input() -> MergeRecordsInput {
+        let mut first_segment = TestSegment::default();
+        first_segment
+            .populate_with_generator(
+                10,
+                &LogGenerator {
+                    generator: upsert_generator,
+                },
+            )
+            .await;
+
+        let mut second_segment = TestSegment {
+            blockfile_provider: first_segment.blockfile_provider.clone(),
+            ..Default::default()
+        };
+        second_segment
+            .populate_with_generator(
+                10,
+                &LogGenerator {
+                    generator: |offset| OperationRecord {
+                        id: int_as_id(offset + 5),
+                        document: Some("from second collection".to_string()),
+                        operation: Operation::Add,
+                        ..upsert_generator(offset + 5)
+                    },
+                },
+            )
+            .await;
+
+        MergeRecordsInput {
+            blockfile_provider: first_segment.blockfile_provider,
+            record_segments: vec![first_segment.record_segment, second_segment.record_segment],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_merge_keep_first() {
+        let merge_input = setup_merge_input().await;
+        let merge_operator = MergeRecordsOperator {
+            duplicate_policy: MergeDuplicatePolicy::KeepFirst,
+        };
+        let merge_output = merge_operator
+            .run(&merge_input)
+            .await
+            .expect("MergeRecordsOperator should not fail");
+
+        assert_eq!(merge_output.logs.len(), 15);
+        for (offset, (log, _)) in merge_output.logs.iter().enumerate() {
+            assert_eq!(log.log_offset, offset as i64 + 1);
+            assert_eq!(log.record.operation, Operation::Add);
+            let from_second = log.record.document.as_deref() == Some("from second collection");
+            let user_id = log
+                .record
+                .id
+                .trim_start_matches("id_")
+                .parse::<usize>()
+                .unwrap();
+            assert_eq!(from_second, user_id > 10);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_merge_keep_last() {
+        let merge_input = setup_merge_input().await;
+        let merge_operator = MergeRecordsOperator {
+            duplicate_policy: MergeDuplicatePolicy::KeepLast,
+        };
+        let merge_output = merge_operator
+            .run(&merge_input)
+            .await
+            .expect("MergeRecordsOperator should not fail");
+
+        assert_eq!(merge_output.logs.len(), 15);
+        for (log, _) in merge_output.logs.iter() {
+            let from_second = log.record.document.as_deref() == Some("from second collection");
+            let user_id = log
+                .record
+                .id
+                .trim_start_matches("id_")
+                .parse::<usize>()
+                .unwrap();
+            assert_eq!(from_second, user_id > 5);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_merge_fail_on_duplicate() {
+        let merge_input = setup_merge_input().await;
+        let merge_operator = MergeRecordsOperator {
+            duplicate_policy: MergeDuplicatePolicy::Fail,
+        };
+        assert!(matches!(
+            merge_operator.run(&merge_input).await,
+            Err(MergeRecordsError::DuplicateUserId(_))
+        ));
+    }
+}
@@ -0,0 +1,300 @@
+use std::collections::HashMap;
+
+use chroma_blockstore::provider::BlockfileProvider;
+use chroma_distance::{normalize, DistanceFunction};
+use chroma_error::{ChromaError, ErrorCodes};
+use chroma_types::{Chunk, LogRecord, MaterializedLogOperation, Segment};
+use thiserror::Error;
+use tonic::async_trait;
+use tracing::{trace, Instrument, Span};
+
+use crate::{
+    execution::operator::Operator,
+    segment::{
+        record_segment::{RecordSegmentReader, RecordSegmentReaderCreationError},
+        LogMaterializer, LogMaterializerError,
+    },
+};
+
+use super::knn::RecordDistance;
+
+/// The `MmrOperator` re-ranks the nearest neighbours with Maximal Marginal Relevance,
+/// trading off the relevance to the target against the diversity of the selected records
+///
+/// # Parameters
+/// - `fetch`: The number of records to select from the candidates
+/// - `lambda`: The weight of relevance against diversity, between 0 and 1.
+///   A lambda of 1 keeps the original order, while a lambda of 0 only maximizes diversity
+///
+/// # Inputs
+/// - `logs`: The latest logs of the collection
+/// - `blockfile_provider`: The blockfile provider
+/// - `record_segment`: The record segment information
+/// - `record_distances`: The candidate records, sorted by distance to the target in ascending order
+/// - `distance_function`: The distance function of the collection
+///
+/// # Outputs
+/// - `record_distances`: The selected records in the order of selection, with their distance to the target
+///
+/// # Usage
+/// It can be used after the `KnnMergeOperator` to diversify the nearest neighbours before projection.
+/// The embeddings of the candidates are read from the logs and the record segment.
+#[derive(Clone, Debug)]
+pub struct MmrOperator {
+    pub fetch: u32,
+    pub lambda: f32,
+}
+
+#[derive(Clone, Debug)]
+pub struct MmrInput {
+    pub logs: Chunk<LogRecord>,
+    pub blockfile_provider: BlockfileProvider,
+    pub record_segment: Segment,
+    pub record_distances: Vec<RecordDistance>,
+    pub distance_function: DistanceFunction,
+}
+
+#[derive(Debug)]
+pub struct MmrOutput {
+    pub record_distances: Vec<RecordDistance>,
+}
+
+#[derive(Error, Debug)]
+pub enum MmrError {
+    #[error("Error materializing log: {0}")]
+    LogMaterializer(#[from] LogMaterializerError),
+    #[error("Error creating record segment reader: {0}")]
+    RecordReader(#[from] RecordSegmentReaderCreationError),
+    #[error("Error reading record segment: {0}")]
+    RecordSegment(#[from] Box<dyn ChromaError>),
+}
+
+impl ChromaError for MmrError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            MmrError::LogMaterializer(e) => e.code(),
+            MmrError::RecordReader(e) => e.code(),
+            MmrError::RecordSegment(e) => e.code(),
+        }
+    }
+}
+
+#[async_trait]
+impl Operator<MmrInput, MmrOutput> for MmrOperator {
+    type Error = MmrError;
+
+    async fn run(&self, input: &MmrInput) -> Result<MmrOutput, MmrError> {
+        trace!("[{}]: {:?}", self.get_name(), input);
+
+        let record_segment_reader = match RecordSegmentReader::from_segment(
+            &input.record_segment,
+            &input.blockfile_provider,
+        )
+        .await
+        {
+            Ok(reader) => Ok(Some(reader)),
+            Err(e) if matches!(*e, RecordSegmentReaderCreationError::UninitializedSegment) => {
+                Ok(None)
+            }
+            Err(e) => Err(*e),
+        }?;
+
+        let materializer =
+            LogMaterializer::new(record_segment_reader.clone(), input.logs.clone(), None);
+        let materialized_logs = materializer
+            .materialize()
+            .instrument(tracing::trace_span!(parent: Span::current(), "Materialize logs"))
+            .await?;
+
+        let mut candidate_embeddings: HashMap<u32, Vec<f32>> = input
+            .record_distances
+            .iter()
+            .map(|record| (record.offset_id, Vec::new()))
+            .collect();
+
+        // Read the embeddings of the candidates present in the logs
+        for (log, _) in materialized_logs.iter() {
+            if matches!(
+                log.final_operation,
+                MaterializedLogOperation::DeleteExisting
+            ) {
+                continue;
+            }
+            if let Some(embedding) = candidate_embeddings.get_mut(&log.offset_id) {
+                *embedding = log.merged_embeddings().to_vec();
+            }
+        }
+
+        // Read the remaining embeddings from the record segment
+        if let Some(reader) = record_segment_reader.as_ref() {
+            for (offset_id, embedding) in candidate_embeddings.iter_mut() {
+                if !embedding.is_empty() {
+                    continue;
+                }
+                if let Some(record) = reader.get_data_for_offset_id(*offset_id).await? {
+                    *embedding = record.embedding.to_vec();
+                }
+            }
+        }
+
+        let mut candidates = Vec::with_capacity(input.record_distances.len());
+        for record in &input.record_distances {
+            match candidate_embeddings.remove(&record.offset_id) {
+                Some(embedding) if !embedding.is_empty() => {
+                    let embedding = if let DistanceFunction::Cosine = input.distance_function {
+                        normalize(&embedding)
+                    } else {
+                        embedding
+                    };
+                    candidates.push((record.clone(), embedding));
+                }
+                _ => tracing::warn!(
+                    "Embedding of offset id {} not found for MMR",
+                    record.offset_id
+                ),
+            }
+        }
+
+        // The minimum distance from each candidate to the selected records
+        let mut min_selected_distances = vec![f32::INFINITY; candidates.len()];
+        let mut selected = vec![false; candidates.len()];
+        let mut record_distances = Vec::with_capacity(self.fetch as usize);
+
+        while record_distances.len() < self.fetch as usize {
+            let mut best: Option<(usize, f32)> = None;
+            for (index, (record, _)) in candidates.iter().enumerate() {
+                if selected[index] {
+                    continue;
+                }
+                let diversity = if record_distances.is_empty() {
+                    0.0
+                } else {
+                    min_selected_distances[index]
+                };
+                let score = (1.0 - self.lambda) * diversity - self.lambda * record.measure;
+                match best {
+                    Some((_, best_score)) if score <= best_score => {}
+                    _ => best = Some((index, score)),
+                }
+            }
+
+            let Some((best_index, _)) = best else {
+                break;
+            };
+            selected[best_index] = true;
+            let (best_record, best_embedding) = &candidates[best_index];
+            record_distances.push(best_record.clone());
+
+            for (index, (_, embedding)) in candidates.iter().enumerate() {
+                if !selected[index] {
+                    let distance = input.distance_function.distance(best_embedding, embedding);
+                    min_selected_distances[index] = min_selected_distances[index].min(distance);
+                }
+            }
+        }
+
+        Ok(MmrOutput { record_distances })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chroma_distance::DistanceFunction;
+
+    use crate::{
+        execution::{
+            operator::Operator,
+            operators::{knn::RecordDistance, mmr::MmrOperator},
+        },
+        log::test::{random_embedding, upsert_generator, LogGenerator, TEST_EMBEDDING_DIMENSION},
+        segment::test::TestSegment,
+    };
+
+    use super::MmrInput;
+
+    /// The unit tests for `MmrOperator` uses 100 log records with random embeddings
+    fn setup_mmr_input() -> MmrInput {
+        let test_segment = TestSegment::default();
+        let generator = LogGenerator {
+            generator: upsert_generator,
+        };
+        let logs = generator.generate_chunk(1..=100);
+        let target = random_embedding(TEST_EMBEDDING_DIMENSION);
+        let distance_function = DistanceFunction::Euclidean;
+        let mut record_distances: Vec<_> = logs
+            .iter()
+            .map(|(log, _)| RecordDistance {
+                offset_id: log.log_offset as u32,
+                measure: distance_function.distance(
+                    log.record
+                        .embedding
+                        .as_ref()
+                        .expect("Embedding should be present in generated logs"),
+                    &target,
+                ),
+            })
+            .collect();
+        record_distances.sort();
+        MmrInput {
+            logs,
+            blockfile_provider: test_segment.blockfile_provider,
+            record_segment: test_segment.record_segment,
+            record_distances,
+            distance_function,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_relevance_only() {
+        let mmr_input = setup_mmr_input();
+
+        let mmr_operator = MmrOperator {
+            fetch: 10,
+            lambda: 1.0,
+        };
+
+        let mmr_output = mmr_operator
+            .run(&mmr_input)
+            .await
+            .expect("MmrOperator should not fail");
+
+        assert_eq!(
+            mmr_output
+                .record_distances
+                .iter()
+                .map(|record| record.offset_id)
+                .collect::<Vec<_>>(),
+            mmr_input.record_distances[..10]
+                .iter()
+                .map(|record| record.offset_id)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_diversified() {
+        let mmr_input = setup_mmr_input();
+
+        let mmr_operator = MmrOperator {
+            fetch: 10,
+            lambda: 0.5,
+        };
+
+        let mmr_output = mmr_operator
+            .run(&mmr_input)
+            .await
+            .expect("MmrOperator should not fail");
+
+        let mut offset_ids = mmr_output
+            .record_distances
+            .iter()
+            .map(|record| record.offset_id)
+            .collect::<Vec<_>>();
+        assert_eq!(offset_ids.len(), 10);
+        // The nearest record is always selected first
+        assert_eq!(offset_ids[0], mmr_input.record_distances[0].offset_id);
+        offset_ids.sort();
+        offset_ids.dedup();
+        assert_eq!(offset_ids.len(), 10);
+    }
+}
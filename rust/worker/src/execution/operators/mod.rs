@@ -1,14 +1,29 @@
+pub(crate) mod aggregate;
 pub(super) mod brute_force_knn;
+pub(crate) mod changes;
+pub(crate) mod check_integrity;
+pub(crate) mod count;
 pub(super) mod count_records;
+pub(crate) mod export_index;
+pub(crate) mod facet;
+pub(crate) mod fetch_embeddings;
 pub(super) mod flush_s3;
 pub(super) mod get_vectors_operator;
 pub(super) mod hnsw_knn;
+pub(crate) mod hybrid_search;
+pub(crate) mod lookup;
 pub(super) mod merge_knn_results;
+pub(crate) mod merge_records;
+pub(crate) mod mmr;
 pub(super) mod normalize_vectors;
 pub(super) mod partition;
 pub(super) mod pull_log;
+pub(super) mod rebuild_segment;
 pub(super) mod record_segment_prefetch;
 pub(super) mod register;
+pub(super) mod retrain_quantization;
+pub(super) mod verify_checksum;
+pub(crate) mod warm_up;
 pub(super) mod write_segments;
 
 // Required for benchmark
@@ -2,7 +2,7 @@ use std::collections::HashSet;
 
 use chroma_blockstore::provider::BlockfileProvider;
 use chroma_error::{ChromaError, ErrorCodes};
-use chroma_types::{Chunk, LogRecord, Segment};
+use chroma_types::{Chunk, CollectionUuid, LogRecord, Segment};
 use thiserror::Error;
 use tonic::async_trait;
 use tracing::{trace, Instrument, Span};
@@ -106,4 +106,16 @@ impl Operator<PrefetchRecordInput, PrefetchRecordOutput> for PrefetchRecordOpera
 
         Ok(())
     }
+
+    fn can_retry(&self, error: &PrefetchRecordError) -> bool {
+        error.is_transient()
+    }
+
+    fn collection_id(&self, input: &PrefetchRecordInput) -> Option<CollectionUuid> {
+        Some(input.record_segment.collection)
+    }
+
+    fn input_cardinality(&self, input: &PrefetchRecordInput) -> Option<usize> {
+        Some(input.offset_ids.len())
+    }
 }
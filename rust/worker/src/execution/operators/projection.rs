@@ -1,17 +1,25 @@
-use std::collections::{HashMap, HashSet};
+use std::{collections::HashMap, mem::size_of_val};
 
 use async_trait::async_trait;
 use chroma_blockstore::provider::BlockfileProvider;
-use chroma_error::{ChromaError, ErrorCodes};
-use chroma_types::{Chunk, LogRecord, Metadata, Segment};
+use chroma_error::{ChromaError, ErrorCodes, ErrorLocation};
+use chroma_types::{
+    get_metadata_value_as, Chunk, Collection, CollectionUuid, DataRecord, LogRecord, Metadata,
+    Segment,
+};
+use futures::{stream, Stream, StreamExt, TryStreamExt};
 use thiserror::Error;
-use tracing::{error, trace, Instrument, Span};
+use tracing::trace;
 
 use crate::{
-    execution::operator::Operator,
+    execution::{
+        memory::{metadata_value_bytes, MemoryContext, MemoryError},
+        operator::{Deadline, DeadlineError, Operator},
+    },
     segment::{
         record_segment::{RecordSegmentReader, RecordSegmentReaderCreationError},
-        LogMaterializer, LogMaterializerError,
+        LogMaterializerError, MaterializedLogContent, MaterializedLogFields, MaterializedLogRecord,
+        SharedMaterializedLogs,
     },
 };
 
@@ -21,12 +29,26 @@ use crate::{
 /// - `document`: Whether to retrieve document
 /// - `embedding`: Whether to retrieve embedding
 /// - `metadata`: Whether to retrieve metadata
+/// - `uri`: Whether to retrieve uri
+/// - `concurrency`: The number of batches of records that are read from the record segment at
+///   once
+/// - `default_include`: Whether the caller left the fields unspecified, in which case the
+///   projection defaults of the collection select them instead
+///
+/// The id of the record is always retrieved. If no other field is selected,
+/// only the user ids are read from the record segment.
 ///
 /// # Inputs
 /// - `logs`: The latest logs of the collection
 /// - `blockfile_provider`: The blockfile provider
 /// - `record_segment`: The record segment information
 /// - `offset_ids`: The offset ids in either logs or blockfile to retrieve for
+/// - `defaults`: The projection defaults of the collection, which select the
+///   fields to retrieve if the caller left them unspecified
+/// - `deadline`: The deadline of the query, checked before the records are read
+/// - `memory`: The memory context of the query, which accounts for the retrieved records
+/// - `materialized_logs`: The logs materialized by the earlier operators of the query, which
+///   are materialized here only if no operator has done so
 ///
 /// # Outputs
 /// - `records`: The retrieved records in the same order as `offset_ids`
@@ -34,27 +56,108 @@ use crate::{
 /// # Usage
 /// It can be used to retrieve record contents as user requested
 /// It should be run as the last step of an orchestrator
+/// Alternatively, [`ProjectionOperator::stream`] yields the records in batches
 #[derive(Clone, Debug)]
 pub struct ProjectionOperator {
     pub document: bool,
     pub embedding: bool,
     pub metadata: bool,
+    pub uri: bool,
+    pub concurrency: usize,
+    pub default_include: bool,
 }
 
+/// The default number of batches of records that are read at once by a projection
+pub const DEFAULT_HYDRATION_CONCURRENCY: usize = 16;
+
 #[derive(Debug)]
 pub struct ProjectionInput {
     pub logs: Chunk<LogRecord>,
     pub blockfile_provider: BlockfileProvider,
     pub record_segment: Segment,
     pub offset_ids: Vec<u32>,
+    pub defaults: ProjectionDefaults,
+    pub deadline: Deadline,
+    pub memory: MemoryContext,
+    pub materialized_logs: SharedMaterializedLogs,
+}
+
+pub const PROJECTION_DOCUMENT_KEY: &str = "projection:document";
+pub const PROJECTION_EMBEDDING_KEY: &str = "projection:embedding";
+pub const PROJECTION_METADATA_KEY: &str = "projection:metadata";
+
+/// The default projection settings of a collection
+///
+/// Each field specifies whether the corresponding record content is returned
+/// when the caller leaves the fields to retrieve unspecified. They are
+/// configured with the `projection:document`, `projection:embedding` and
+/// `projection:metadata` boolean keys in the collection metadata, and any
+/// missing key falls back to returning documents and metadata without
+/// embeddings. The uri of a record is returned together with its metadata.
+/// For example, an embedding-only workload could set `projection:document`
+/// to `false` so that documents are only returned when they are asked for.
+#[derive(Clone, Debug)]
+pub struct ProjectionDefaults {
+    pub document: bool,
+    pub embedding: bool,
+    pub metadata: bool,
+}
+
+impl Default for ProjectionDefaults {
+    fn default() -> Self {
+        Self {
+            document: true,
+            embedding: false,
+            metadata: true,
+        }
+    }
+}
+
+impl From<&Collection> for ProjectionDefaults {
+    fn from(collection: &Collection) -> Self {
+        let metadata = match &collection.metadata {
+            Some(metadata) => metadata,
+            None => return Self::default(),
+        };
+        Self {
+            document: get_metadata_value_as::<bool>(metadata, PROJECTION_DOCUMENT_KEY)
+                .unwrap_or(true),
+            embedding: get_metadata_value_as::<bool>(metadata, PROJECTION_EMBEDDING_KEY)
+                .unwrap_or(false),
+            metadata: get_metadata_value_as::<bool>(metadata, PROJECTION_METADATA_KEY)
+                .unwrap_or(true),
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct ProjectionRecord {
+    pub offset_id: u32,
     pub id: String,
     pub document: Option<String>,
     pub embedding: Option<Vec<f32>>,
     pub metadata: Option<Metadata>,
+    pub uri: Option<String>,
+}
+
+impl ProjectionRecord {
+    /// Returns the approximate size in bytes of the record content
+    pub fn get_size(&self) -> usize {
+        let id_size = self.id.len();
+        let document_size = self.document.as_ref().map_or(0, String::len);
+        let embedding_size = self
+            .embedding
+            .as_ref()
+            .map_or(0, |embedding| size_of_val(embedding.as_slice()));
+        let metadata_size = self.metadata.as_ref().map_or(0, |metadata| {
+            metadata
+                .iter()
+                .map(|(key, value)| key.len() + metadata_value_bytes(value))
+                .sum()
+        });
+        let uri_size = self.uri.as_ref().map_or(0, String::len);
+        id_size + document_size + embedding_size + metadata_size + uri_size
+    }
 }
 
 #[derive(Debug)]
@@ -64,8 +167,12 @@ pub struct ProjectionOutput {
 
 #[derive(Error, Debug)]
 pub enum ProjectionError {
+    #[error("Projection stopped early: {0}")]
+    Deadline(#[from] DeadlineError),
     #[error("Error materializing log: {0}")]
     LogMaterializer(#[from] LogMaterializerError),
+    #[error("Error accounting for query memory: {0}")]
+    Memory(#[from] MemoryError),
     #[error("Error creating record segment reader: {0}")]
     RecordReader(#[from] RecordSegmentReaderCreationError),
     #[error("Error reading record segment: {0}")]
@@ -77,104 +184,380 @@ pub enum ProjectionError {
 impl ChromaError for ProjectionError {
     fn code(&self) -> ErrorCodes {
         match self {
+            ProjectionError::Deadline(e) => e.code(),
             ProjectionError::LogMaterializer(e) => e.code(),
+            ProjectionError::Memory(e) => e.code(),
             ProjectionError::RecordReader(e) => e.code(),
             ProjectionError::RecordSegment(e) => e.code(),
             ProjectionError::RecordSegmentUninitialized => ErrorCodes::Internal,
         }
     }
+
+    fn location(&self) -> ErrorLocation {
+        match self {
+            ProjectionError::RecordReader(e) => e.location(),
+            ProjectionError::RecordSegment(e) => e.location(),
+            _ => ErrorLocation::default(),
+        }
+    }
 }
 
-#[async_trait]
-impl Operator<ProjectionInput, ProjectionOutput> for ProjectionOperator {
-    type Error = ProjectionError;
+impl ProjectionOperator {
+    // Selects the fields of the collection defaults if the caller left them unspecified. The
+    // fields that the caller asks for are retrieved as they are
+    pub(super) fn with_defaults(&self, defaults: &ProjectionDefaults) -> Self {
+        if !self.default_include {
+            return self.clone();
+        }
+        Self {
+            document: defaults.document,
+            embedding: defaults.embedding,
+            metadata: defaults.metadata,
+            uri: defaults.metadata,
+            concurrency: self.concurrency,
+            default_include: false,
+        }
+    }
 
-    async fn run(&self, input: &ProjectionInput) -> Result<ProjectionOutput, ProjectionError> {
-        trace!("[{}]: {:?}", self.get_name(), input);
+    // Whether any field other than the id is selected
+    fn hydrates_content(&self) -> bool {
+        self.document || self.embedding || self.metadata || self.uri
+    }
 
-        let record_segment_reader = match RecordSegmentReader::from_segment(
-            &input.record_segment,
-            &input.blockfile_provider,
-        )
-        .await
-        {
-            Ok(reader) => Ok(Some(reader)),
-            Err(e) if matches!(*e, RecordSegmentReaderCreationError::UninitializedSegment) => {
-                Ok(None)
-            }
-            Err(e) => Err(*e),
-        }?;
-        let materializer =
-            LogMaterializer::new(record_segment_reader.clone(), input.logs.clone(), None);
-        let materialized_logs = materializer
-            .materialize()
-            .instrument(tracing::trace_span!(parent: Span::current(), "Materialize logs"))
-            .await?;
+    /// The fields of the logs that the projection reads
+    pub(crate) fn fields(&self) -> MaterializedLogFields {
+        MaterializedLogFields {
+            metadata: self.metadata,
+            document: self.document,
+            uri: self.uri,
+            embedding: self.embedding,
+        }
+    }
+
+    pub(super) fn project_log_record(&self, log: &MaterializedLogRecord) -> ProjectionRecord {
+        ProjectionRecord {
+            offset_id: log.offset_id,
+            id: log.merged_user_id().to_string(),
+            document: log.merged_document().filter(|_| self.document),
+            embedding: self.embedding.then_some(log.merged_embeddings().to_vec()),
+            metadata: self
+                .metadata
+                .then_some(log.merged_metadata())
+                .filter(|metadata| !metadata.is_empty()),
+            uri: log
+                .merged_uri_ref()
+                .filter(|_| self.uri)
+                .map(str::to_string),
+        }
+    }
+
+    fn project_log_content(
+        &self,
+        offset_id: u32,
+        content: &MaterializedLogContent,
+    ) -> ProjectionRecord {
+        ProjectionRecord {
+            offset_id,
+            id: content.user_id.clone(),
+            document: content.document.clone().filter(|_| self.document),
+            embedding: self.embedding.then(|| content.embedding.clone()),
+            metadata: self
+                .metadata
+                .then(|| content.metadata.clone())
+                .filter(|metadata| !metadata.is_empty()),
+            uri: content.uri.clone().filter(|_| self.uri),
+        }
+    }
+
+    pub(super) fn project_data_record(
+        &self,
+        offset_id: u32,
+        record: DataRecord,
+    ) -> ProjectionRecord {
+        ProjectionRecord {
+            offset_id,
+            id: record.id.to_string(),
+            document: record
+                .document
+                .filter(|_| self.document)
+                .map(str::to_string),
+            embedding: self.embedding.then_some(record.embedding.to_vec()),
+            metadata: record.metadata.filter(|_| self.metadata),
+            uri: record.uri.filter(|_| self.uri).map(str::to_string),
+        }
+    }
 
-        let offset_id_set: HashSet<_> = HashSet::from_iter(input.offset_ids.iter().cloned());
+    // Projects the log records whose offset ids are requested, from the logs materialized by
+    // the earlier operators of the query
+    async fn project_logs(
+        &self,
+        record_segment_reader: Option<RecordSegmentReader<'_>>,
+        logs: &Chunk<LogRecord>,
+        materialized_logs: &SharedMaterializedLogs,
+        offset_ids: &[u32],
+        memory: &MemoryContext,
+    ) -> Result<HashMap<u32, ProjectionRecord>, ProjectionError> {
+        let active_contents = materialized_logs
+            .active_contents(record_segment_reader, logs)
+            .await?;
 
         // Create a hash map that maps an offset id to the corresponding log
         // It contains all records from the logs that should be present in the final result
-        let offset_id_to_log_record: HashMap<_, _> = materialized_logs
+        let log_records: HashMap<_, _> = offset_ids
             .iter()
-            .flat_map(|(log, _)| {
-                offset_id_set
-                    .contains(&log.offset_id)
-                    .then_some((log.offset_id, log))
+            .filter_map(|offset_id| {
+                active_contents
+                    .get(offset_id)
+                    .map(|content| (*offset_id, self.project_log_content(*offset_id, content)))
             })
             .collect();
+        memory.reserve(log_records.values().map(ProjectionRecord::get_size).sum())?;
+        Ok(log_records)
+    }
 
-        let mut records = Vec::with_capacity(input.offset_ids.len());
-
-        for offset_id in &input.offset_ids {
-            let record = match offset_id_to_log_record.get(offset_id) {
-                // The offset id is in the log
-                Some(&log) => ProjectionRecord {
-                    id: log.merged_user_id().to_string(),
-                    document: log.merged_document().filter(|_| self.document),
-                    embedding: self.embedding.then_some(log.merged_embeddings().to_vec()),
-                    metadata: self
-                        .metadata
-                        .then_some(log.merged_metadata())
-                        .filter(|metadata| !metadata.is_empty()),
-                },
-                // The offset id is in the record segment
-                None => {
-                    if let Some(reader) = &record_segment_reader {
-                        let record = reader
-                            .get_data_for_offset_id(*offset_id)
-                            .await?
-                            .ok_or(ProjectionError::RecordSegmentUninitialized)?;
-                        ProjectionRecord {
-                            id: record.id.to_string(),
-                            document: record
-                                .document
-                                .filter(|_| self.document)
-                                .map(str::to_string),
-                            embedding: self.embedding.then_some(record.embedding.to_vec()),
-                            metadata: record.metadata.filter(|_| self.metadata),
+    // Projects the records for the offset ids in order, looking them up in the projected logs first
+    async fn project_offset_ids(
+        &self,
+        record_segment_reader: Option<&RecordSegmentReader<'_>>,
+        log_records: &HashMap<u32, ProjectionRecord>,
+        offset_ids: &[u32],
+        deadline: &Deadline,
+        memory: &MemoryContext,
+    ) -> Result<Vec<ProjectionRecord>, ProjectionError> {
+        // The records that are not in the log are read from the record segment in up to
+        // `concurrency` batches in flight. Each batch fetches the blocks of its offset ids at
+        // once, and the batches are reassembled in the requested order below
+        let segment_offset_ids = offset_ids
+            .iter()
+            .filter(|offset_id| !log_records.contains_key(offset_id))
+            .copied()
+            .collect::<Vec<_>>();
+        let mut segment_records = HashMap::with_capacity(segment_offset_ids.len());
+        if !segment_offset_ids.is_empty() {
+            let reader =
+                record_segment_reader.ok_or(ProjectionError::RecordSegmentUninitialized)?;
+            let concurrency = self.concurrency.max(1);
+            let batch_size = segment_offset_ids.len().div_ceil(concurrency);
+            // The batches are created upfront so that the stream does not hold a closure over
+            // the borrowed offset ids, which would keep the future of the operator from being Send
+            let batches = segment_offset_ids
+                .chunks(batch_size)
+                .map(|batch| async move {
+                    deadline.check()?;
+                    let mut records = Vec::with_capacity(batch.len());
+                    if self.hydrates_content() {
+                        let data_records = reader.get_data_for_offset_ids(batch).await?;
+                        for (offset_id, data_record) in batch.iter().zip(data_records) {
+                            records.push((
+                                *offset_id,
+                                self.project_data_record(*offset_id, data_record),
+                            ));
                         }
                     } else {
-                        return Err(ProjectionError::RecordSegmentUninitialized);
+                        // Only the id is requested, which avoids reading the record data
+                        reader.prefetch_id_to_user_id(batch).await;
+                        for offset_id in batch {
+                            records.push((
+                                *offset_id,
+                                ProjectionRecord {
+                                    offset_id: *offset_id,
+                                    id: reader
+                                        .get_user_id_for_offset_id(*offset_id)
+                                        .await?
+                                        .to_string(),
+                                    document: None,
+                                    embedding: None,
+                                    metadata: None,
+                                    uri: None,
+                                },
+                            ));
+                        }
                     }
-                }
-            };
-            records.push(record);
+                    for (_, record) in &records {
+                        memory.reserve(record.get_size())?;
+                    }
+                    Ok::<_, ProjectionError>(records)
+                })
+                .collect::<Vec<_>>();
+            let mut hydrated_batches = stream::iter(batches).buffer_unordered(concurrency);
+            while let Some(records) = hydrated_batches.try_next().await? {
+                segment_records.extend(records);
+            }
+        }
+
+        Ok(offset_ids
+            .iter()
+            .filter_map(|offset_id| {
+                log_records
+                    .get(offset_id)
+                    .or_else(|| segment_records.get(offset_id))
+                    .cloned()
+            })
+            .collect())
+    }
+
+    /// Streams the projected records in batches of at most `batch_size` records,
+    /// in the same order as `offset_ids` in the input
+    ///
+    /// The logs are materialized once upfront, while the records in the record
+    /// segment are only hydrated when the corresponding batch is polled. This
+    /// bounds the memory held by large projections to roughly one batch, and
+    /// allows the caller to forward each batch as soon as it is ready.
+    pub fn stream(
+        &self,
+        input: ProjectionInput,
+        batch_size: usize,
+    ) -> impl Stream<Item = Result<ProjectionOutput, ProjectionError>> + Send {
+        let operator = self.with_defaults(&input.defaults);
+        stream::once(async move {
+            input.deadline.check()?;
+            let record_segment_reader =
+                open_record_segment_reader(&input.record_segment, &input.blockfile_provider)
+                    .await?;
+            let log_records = operator
+                .project_logs(
+                    record_segment_reader,
+                    &input.logs,
+                    &input.materialized_logs,
+                    &input.offset_ids,
+                    &input.memory,
+                )
+                .await?;
+            Ok::<_, ProjectionError>(ProjectionStreamState {
+                operator,
+                input,
+                log_records,
+                batch_size: batch_size.max(1),
+                cursor: 0,
+            })
+        })
+        .map_ok(|state| stream::try_unfold(state, ProjectionStreamState::next_batch))
+        .try_flatten()
+    }
+}
+
+// The state of a streaming projection
+// The record segment reader is reopened for every batch because it borrows
+// from itself, which prevents it from being stored across batches
+struct ProjectionStreamState {
+    operator: ProjectionOperator,
+    input: ProjectionInput,
+    log_records: HashMap<u32, ProjectionRecord>,
+    batch_size: usize,
+    cursor: usize,
+}
+
+impl ProjectionStreamState {
+    async fn next_batch(mut self) -> Result<Option<(ProjectionOutput, Self)>, ProjectionError> {
+        if self.cursor >= self.input.offset_ids.len() {
+            return Ok(None);
         }
+        let end = (self.cursor + self.batch_size).min(self.input.offset_ids.len());
+        let record_segment_reader =
+            open_record_segment_reader(&self.input.record_segment, &self.input.blockfile_provider)
+                .await?;
+        let records = self
+            .operator
+            .project_offset_ids(
+                record_segment_reader.as_ref(),
+                &self.log_records,
+                &self.input.offset_ids[self.cursor..end],
+                &self.input.deadline,
+                &self.input.memory,
+            )
+            .await?;
+        self.cursor = end;
+        Ok(Some((ProjectionOutput { records }, self)))
+    }
+}
+
+async fn open_record_segment_reader<'me>(
+    record_segment: &Segment,
+    blockfile_provider: &BlockfileProvider,
+) -> Result<Option<RecordSegmentReader<'me>>, ProjectionError> {
+    match RecordSegmentReader::from_segment(record_segment, blockfile_provider).await {
+        Ok(reader) => Ok(Some(reader)),
+        Err(e) if matches!(*e, RecordSegmentReaderCreationError::UninitializedSegment) => Ok(None),
+        Err(e) => Err((*e).into()),
+    }
+}
+
+#[async_trait]
+impl Operator<ProjectionInput, ProjectionOutput> for ProjectionOperator {
+    type Error = ProjectionError;
+
+    async fn run(&self, input: &ProjectionInput) -> Result<ProjectionOutput, ProjectionError> {
+        trace!("[{}]: {:?}", self.get_name(), input);
+        input.deadline.check()?;
+
+        let projection = self.with_defaults(&input.defaults);
+        let record_segment_reader =
+            open_record_segment_reader(&input.record_segment, &input.blockfile_provider).await?;
+        let log_records = projection
+            .project_logs(
+                record_segment_reader.clone(),
+                &input.logs,
+                &input.materialized_logs,
+                &input.offset_ids,
+                &input.memory,
+            )
+            .await?;
+        let records = projection
+            .project_offset_ids(
+                record_segment_reader.as_ref(),
+                &log_records,
+                &input.offset_ids,
+                &input.deadline,
+                &input.memory,
+            )
+            .await?;
 
         Ok(ProjectionOutput { records })
     }
+
+    fn can_retry(&self, error: &ProjectionError) -> bool {
+        error.is_transient()
+    }
+
+    fn deadline(&self, input: &ProjectionInput) -> Option<Deadline> {
+        Some(input.deadline.clone())
+    }
+
+    fn collection_id(&self, input: &ProjectionInput) -> Option<CollectionUuid> {
+        Some(input.record_segment.collection)
+    }
+
+    fn input_cardinality(&self, input: &ProjectionInput) -> Option<usize> {
+        Some(input.offset_ids.len())
+    }
+
+    fn output_cardinality(&self, output: &ProjectionOutput) -> Option<usize> {
+        Some(output.records.len())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        execution::{operator::Operator, operators::projection::ProjectionOperator},
-        log::test::{int_as_id, upsert_generator, LogGenerator},
-        segment::test::TestSegment,
+        execution::{
+            memory::MemoryContext,
+            operator::{Deadline, Operator},
+            operators::projection::ProjectionOperator,
+        },
+        log::test::{int_as_id, int_as_uri, upsert_generator, LogGenerator},
+        segment::{
+            record_segment::RecordSegmentReader, test::TestSegment, LogMaterializer,
+            MaterializedLogFields, SharedMaterializedLogs,
+        },
     };
 
-    use super::ProjectionInput;
+    use chroma_error::{ChromaError, ErrorCodes};
+    use chroma_types::Chunk;
+    use futures::TryStreamExt;
+
+    use super::{
+        ProjectionDefaults, ProjectionInput, ProjectionRecord, DEFAULT_HYDRATION_CONCURRENCY,
+    };
 
     /// The unit tests for `ProjectionOperator` uses the following test data
     /// It first generates 100 log records and compact them,
@@ -194,6 +577,10 @@ mod tests {
             blockfile_provider: test_segment.blockfile_provider,
             record_segment: test_segment.record_segment,
             offset_ids,
+            defaults: ProjectionDefaults::default(),
+            deadline: Deadline::default(),
+            memory: MemoryContext::default(),
+            materialized_logs: SharedMaterializedLogs::default(),
         }
     }
 
@@ -205,6 +592,9 @@ mod tests {
             document: false,
             embedding: false,
             metadata: false,
+            uri: false,
+            concurrency: DEFAULT_HYDRATION_CONCURRENCY,
+            default_include: false,
         };
 
         let projection_output = projection_operator
@@ -218,6 +608,55 @@ mod tests {
             assert!(record.document.is_none());
             assert!(record.embedding.is_none());
             assert!(record.metadata.is_none());
+            assert!(record.uri.is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_projection_reuses_materialized_logs() {
+        let mut projection_input = setup_projection_input((96..=105).collect()).await;
+        {
+            let record_segment_reader = RecordSegmentReader::from_segment(
+                &projection_input.record_segment,
+                &projection_input.blockfile_provider,
+            )
+            .await
+            .expect("The record segment should be initialized");
+            let materializer = LogMaterializer::new(
+                Some(record_segment_reader),
+                projection_input.logs.clone(),
+                None,
+            );
+            let materialized_logs = materializer
+                .materialize()
+                .await
+                .expect("The logs should be materialized");
+            projection_input
+                .materialized_logs
+                .record(&materialized_logs, MaterializedLogFields::ALL);
+        }
+        // The records that are only in the logs are projected from the logs materialized above
+        projection_input.logs = Chunk::new(Vec::new().into());
+
+        let projection_operator = ProjectionOperator {
+            document: true,
+            embedding: false,
+            metadata: false,
+            uri: true,
+            concurrency: DEFAULT_HYDRATION_CONCURRENCY,
+            default_include: false,
+        };
+
+        let projection_output = projection_operator
+            .run(&projection_input)
+            .await
+            .expect("ProjectionOperator should not fail");
+
+        assert_eq!(projection_output.records.len(), 10);
+        for (offset, record) in (96..=105).zip(projection_output.records) {
+            assert_eq!(record.id, int_as_id(offset));
+            assert!(record.document.is_some());
+            assert_eq!(record.uri, Some(int_as_uri(offset)));
         }
     }
 
@@ -229,6 +668,9 @@ mod tests {
             document: true,
             embedding: true,
             metadata: true,
+            uri: true,
+            concurrency: DEFAULT_HYDRATION_CONCURRENCY,
+            default_include: false,
         };
 
         let projection_output = projection_operator
@@ -242,6 +684,171 @@ mod tests {
             assert!(record.document.is_some());
             assert!(record.embedding.is_some());
             assert!(record.metadata.is_some());
+            assert_eq!(record.uri, Some(int_as_uri(offset + 1)));
+            // The uri is a separate field and is not kept in the metadata
+            assert!(!record.metadata.unwrap().contains_key("chroma:uri"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_projection_order() {
+        // The offset ids are spread over the logs and the record segment, out of order
+        let offset_ids: Vec<u32> = (1..=120)
+            .rev()
+            .step_by(3)
+            .chain((1..=120).step_by(3))
+            .collect();
+        let projection_input = setup_projection_input(offset_ids.clone()).await;
+
+        for concurrency in [1, 4, 64] {
+            let projection_operator = ProjectionOperator {
+                document: true,
+                embedding: false,
+                metadata: false,
+                uri: false,
+                concurrency,
+                default_include: false,
+            };
+
+            let projection_output = projection_operator
+                .run(&projection_input)
+                .await
+                .expect("ProjectionOperator should not fail");
+
+            assert_eq!(
+                projection_output
+                    .records
+                    .iter()
+                    .map(|record| record.id.clone())
+                    .collect::<Vec<_>>(),
+                offset_ids
+                    .iter()
+                    .map(|offset_id| int_as_id(*offset_id as usize))
+                    .collect::<Vec<_>>()
+            );
+            assert!(projection_output
+                .records
+                .iter()
+                .all(|record| record.document.is_some()));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stream_projection() {
+        let projection_input = setup_projection_input((1..=120).collect()).await;
+
+        let projection_operator = ProjectionOperator {
+            document: true,
+            embedding: false,
+            metadata: true,
+            uri: false,
+            concurrency: DEFAULT_HYDRATION_CONCURRENCY,
+            default_include: false,
+        };
+
+        let batches: Vec<_> = projection_operator
+            .stream(projection_input, 32)
+            .try_collect()
+            .await
+            .expect("ProjectionOperator should not fail");
+
+        assert_eq!(
+            batches
+                .iter()
+                .map(|batch| batch.records.len())
+                .collect::<Vec<_>>(),
+            vec![32, 32, 32, 24]
+        );
+        for (offset, record) in batches
+            .into_iter()
+            .flat_map(|batch| batch.records)
+            .enumerate()
+        {
+            assert_eq!(record.id, int_as_id(offset + 1));
+            assert!(record.document.is_some());
+            assert!(record.embedding.is_none());
+            assert!(record.metadata.is_some());
         }
     }
+
+    #[tokio::test]
+    async fn test_default_projection() {
+        let mut projection_input = setup_projection_input((1..=120).collect()).await;
+        projection_input.defaults = ProjectionDefaults {
+            document: false,
+            embedding: true,
+            metadata: true,
+        };
+
+        // The fields that the caller asks for are retrieved regardless of the defaults
+        let mut projection_operator = ProjectionOperator {
+            document: true,
+            embedding: false,
+            metadata: true,
+            uri: false,
+            concurrency: DEFAULT_HYDRATION_CONCURRENCY,
+            default_include: false,
+        };
+
+        let projection_output = projection_operator
+            .run(&projection_input)
+            .await
+            .expect("ProjectionOperator should not fail");
+
+        assert_eq!(projection_output.records.len(), 120);
+        for (offset, record) in projection_output.records.into_iter().enumerate() {
+            assert_eq!(record.id, int_as_id(offset + 1));
+            assert!(record.document.is_some());
+            assert!(record.embedding.is_none());
+            assert!(record.metadata.is_some());
+        }
+
+        // The defaults select the fields if the caller leaves them unspecified
+        projection_operator.default_include = true;
+        let projection_output = projection_operator
+            .run(&projection_input)
+            .await
+            .expect("ProjectionOperator should not fail");
+
+        assert_eq!(projection_output.records.len(), 120);
+        for (offset, record) in projection_output.records.into_iter().enumerate() {
+            assert_eq!(record.id, int_as_id(offset + 1));
+            assert!(record.document.is_none());
+            assert!(record.embedding.is_some());
+            assert!(record.metadata.is_some());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_projection_memory_budget() {
+        let mut projection_input = setup_projection_input((1..=120).collect()).await;
+
+        let projection_operator = ProjectionOperator {
+            document: true,
+            embedding: true,
+            metadata: true,
+            uri: true,
+            concurrency: DEFAULT_HYDRATION_CONCURRENCY,
+            default_include: false,
+        };
+
+        let projection_output = projection_operator
+            .run(&projection_input)
+            .await
+            .expect("ProjectionOperator should not fail");
+        let output_size = projection_output
+            .records
+            .iter()
+            .map(ProjectionRecord::get_size)
+            .sum::<usize>();
+        assert!(projection_input.memory.used_bytes() >= output_size);
+
+        // The query fails once the hydrated records exceed its budget
+        projection_input.memory = MemoryContext::with_budget(output_size / 2);
+        let projection_error = projection_operator
+            .run(&projection_input)
+            .await
+            .expect_err("ProjectionOperator should exceed the memory budget");
+        assert_eq!(projection_error.code(), ErrorCodes::ResourceExhausted);
+    }
 }
@@ -1,6 +1,7 @@
 use crate::execution::operator::{Operator, OperatorType};
 use crate::log::log::{Log, PullLogsError};
 use async_trait::async_trait;
+use chroma_error::ChromaError;
 use chroma_types::{Chunk, CollectionUuid, LogRecord};
 
 /// The pull logs operator is responsible for reading logs from the log service.
@@ -143,6 +144,10 @@ impl Operator<PullLogsInput, PullLogsOutput> for PullLogsOperator {
         let data_chunk = Chunk::new(result.into());
         Ok(PullLogsOutput::new(data_chunk))
     }
+
+    fn can_retry(&self, error: &PullLogsError) -> bool {
+        error.is_transient()
+    }
 }
 
 #[cfg(test)]
@@ -175,6 +180,9 @@ mod tests {
                                 encoding: None,
                                 metadata: None,
                                 document: None,
+                                uri: None,
+                                idempotency_key: None,
+                                embedding_reference: None,
                                 operation: Operation::Add,
                             },
                         },
@@ -194,6 +202,9 @@ mod tests {
                                 encoding: None,
                                 metadata: None,
                                 document: None,
+                                uri: None,
+                                idempotency_key: None,
+                                embedding_reference: None,
                                 operation: Operation::Add,
                             },
                         },
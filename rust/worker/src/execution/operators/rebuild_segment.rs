@@ -0,0 +1,460 @@
+use std::{collections::HashMap, sync::Arc};
+
+use chroma_blockstore::provider::BlockfileProvider;
+use chroma_error::{ChromaError, ErrorCodes};
+use chroma_index::hnsw_provider::HnswIndexProvider;
+use chroma_storage::PutOptions;
+use chroma_types::{Chunk, MaterializedLogOperation, Segment, SegmentFlushInfo, SegmentUuid};
+use futures::TryStreamExt;
+use thiserror::Error;
+use tonic::async_trait;
+use tracing::trace;
+
+use crate::{
+    execution::operator::{Operator, OperatorType},
+    segment::{
+        distributed_hnsw_segment::{
+            DistributedHNSWSegmentFromSegmentError, DistributedHNSWSegmentWriter,
+        },
+        materialized_filter::materialized_filters_from_collection,
+        metadata_segment::{MetadataSegmentError, MetadataSegmentWriter},
+        record_segment::{
+            ApplyMaterializedLogError, RecordSegmentReader, RecordSegmentReaderCreationError,
+        },
+        MaterializedLogRecord, SegmentFlusher, SegmentWriter,
+    },
+    sysdb::sysdb::{FlushCompactionError, GetTenantError, SysDb},
+};
+
+use super::fetch_segment::FetchSegmentOutput;
+
+/// The `RebuildSegmentOperator` rebuilds the metadata segment and the vector segment of a
+/// collection from its record segment, and registers the rebuilt segments in the sysdb
+///
+/// # Parameters
+/// - `rebuild_metadata`: Whether the metadata segment is rebuilt
+/// - `rebuild_vector`: Whether the vector segment is rebuilt
+/// - `batch_size`: The number of records that are read and added to the segments at a time
+///
+/// # Inputs
+/// - `blockfile_provider`: The blockfile provider
+/// - `hnsw_provider`: The HNSW index provider
+/// - `sysdb`: The sysdb client
+/// - `segments`: The collection and segment information
+///
+/// # Outputs
+/// - `rebuilt_segments`: The ids of the segments that are rebuilt
+/// - `collection_version`: The version of the collection after the rebuilt segments are
+///   registered
+///
+/// # Usage
+/// The rebuilt segments are written to new files, so that the queries that are running keep
+/// reading the old files. They are registered like the output of a compaction, at the log
+/// position and the version of the collection that were fetched, so the registration fails if
+/// the collection is compacted in the meantime. The record segment is streamed in batches, so
+/// that its records are never held in memory all at once
+#[derive(Clone, Debug)]
+pub struct RebuildSegmentOperator {
+    pub rebuild_metadata: bool,
+    pub rebuild_vector: bool,
+    pub batch_size: usize,
+}
+
+#[derive(Debug)]
+pub struct RebuildSegmentInput {
+    pub blockfile_provider: BlockfileProvider,
+    pub hnsw_provider: HnswIndexProvider,
+    pub sysdb: Box<SysDb>,
+    pub segments: FetchSegmentOutput,
+}
+
+#[derive(Debug, Default)]
+pub struct RebuildSegmentOutput {
+    pub rebuilt_segments: Vec<SegmentUuid>,
+    pub collection_version: i32,
+}
+
+#[derive(Error, Debug)]
+pub enum RebuildSegmentError {
+    #[error("Error applying records to rebuilt segment: {0}")]
+    Apply(#[from] ApplyMaterializedLogError),
+    #[error("Error flushing rebuilt segment: {0}")]
+    Flush(Box<dyn ChromaError>),
+    #[error("Error creating hnsw segment writer: {0}")]
+    HnswWriter(#[from] DistributedHNSWSegmentFromSegmentError),
+    #[error("Error writing metadata segment: {0}")]
+    MetadataWriter(#[from] MetadataSegmentError),
+    #[error("Collection has no dimension")]
+    MissingDimension,
+    #[error("Error reading record segment: {0}")]
+    RecordSegment(#[from] Box<dyn ChromaError>),
+    #[error("Error creating record segment reader: {0}")]
+    RecordReader(#[from] RecordSegmentReaderCreationError),
+    #[error("Error registering rebuilt segments: {0}")]
+    Register(#[from] FlushCompactionError),
+    #[error("Error getting tenant: {0}")]
+    Tenant(#[from] GetTenantError),
+}
+
+impl ChromaError for RebuildSegmentError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            RebuildSegmentError::Apply(e) => e.code(),
+            RebuildSegmentError::Flush(e) => e.code(),
+            RebuildSegmentError::HnswWriter(e) => e.code(),
+            RebuildSegmentError::MetadataWriter(e) => e.code(),
+            RebuildSegmentError::MissingDimension => ErrorCodes::FailedPrecondition,
+            RebuildSegmentError::RecordSegment(e) => e.code(),
+            RebuildSegmentError::RecordReader(e) => e.code(),
+            RebuildSegmentError::Register(e) => e.code(),
+            RebuildSegmentError::Tenant(e) => e.code(),
+        }
+    }
+}
+
+/// A copy of the segment without files, so that its writer creates new files
+fn empty_segment(segment: &Segment) -> Segment {
+    Segment {
+        file_path: HashMap::new(),
+        ..segment.clone()
+    }
+}
+
+async fn flush(
+    flusher: Result<impl SegmentFlusher, Box<dyn ChromaError>>,
+    segment_id: SegmentUuid,
+) -> Result<SegmentFlushInfo, RebuildSegmentError> {
+    let file_paths = flusher
+        .map_err(RebuildSegmentError::Flush)?
+        .flush()
+        .await
+        .map_err(RebuildSegmentError::Flush)?;
+    Ok(SegmentFlushInfo {
+        segment_id,
+        file_paths,
+        metadata: None,
+    })
+}
+
+#[async_trait]
+impl Operator<RebuildSegmentInput, RebuildSegmentOutput> for RebuildSegmentOperator {
+    type Error = RebuildSegmentError;
+
+    fn get_type(&self) -> OperatorType {
+        OperatorType::IO
+    }
+
+    async fn run(
+        &self,
+        input: &RebuildSegmentInput,
+    ) -> Result<RebuildSegmentOutput, RebuildSegmentError> {
+        trace!("[{}]: {:?}", self.get_name(), input);
+
+        let collection = &input.segments.collection;
+        let mut sysdb = input.sysdb.clone();
+
+        // The rebuilt files are encrypted like the compacted files of the tenant
+        let mut blockfile_provider = input.blockfile_provider.clone();
+        let mut hnsw_provider = input.hnsw_provider.clone();
+        if let Some(kms_key_id) = sysdb
+            .get_tenant_kms_key_id(collection.tenant.clone())
+            .await?
+        {
+            let put_options = PutOptions {
+                kms_key_id: Some(kms_key_id),
+            };
+            blockfile_provider = blockfile_provider.with_put_options(put_options.clone());
+            hnsw_provider = hnsw_provider.with_put_options(put_options);
+        }
+
+        let record_segment_reader = match RecordSegmentReader::from_segment(
+            &input.segments.record_segment,
+            &input.blockfile_provider,
+        )
+        .await
+        {
+            Ok(reader) => Some(reader),
+            // Nothing is compacted for the collection, so the segments are rebuilt empty
+            Err(e) if matches!(*e, RecordSegmentReaderCreationError::UninitializedSegment) => None,
+            Err(e) => return Err((*e).into()),
+        };
+
+        let metadata_segment = empty_segment(&input.segments.metadata_segment);
+        let mut metadata_segment_writer = None;
+        if self.rebuild_metadata {
+            let mut writer =
+                MetadataSegmentWriter::from_segment(&metadata_segment, &blockfile_provider).await?;
+            writer
+                .set_materialized_filters(
+                    &metadata_segment,
+                    &blockfile_provider,
+                    materialized_filters_from_collection(collection),
+                )
+                .await?;
+            metadata_segment_writer = Some(writer);
+        }
+
+        let vector_segment = empty_segment(&input.segments.vector_segment);
+        let mut hnsw_segment_writer = None;
+        if self.rebuild_vector {
+            let dimension = collection
+                .dimension
+                .ok_or(RebuildSegmentError::MissingDimension)?;
+            hnsw_segment_writer = Some(
+                DistributedHNSWSegmentWriter::from_segment(
+                    &vector_segment,
+                    dimension as usize,
+                    hnsw_provider,
+                )
+                .await
+                .map_err(|e| *e)?,
+            );
+        }
+
+        // Each record is added anew to the empty segments
+        if let Some(reader) = &record_segment_reader {
+            let mut data_records = Box::pin(reader.scan(None));
+            let mut batch = Vec::with_capacity(self.batch_size);
+            loop {
+                let next_record = data_records.try_next().await?;
+                let exhausted = next_record.is_none();
+                if let Some((offset_id, data_record)) = next_record {
+                    batch.push(MaterializedLogRecord {
+                        data_record: None,
+                        offset_id,
+                        user_id: Some(data_record.id),
+                        final_operation: MaterializedLogOperation::AddNew,
+                        metadata_to_be_merged: data_record.metadata,
+                        metadata_to_be_deleted: None,
+                        final_document: data_record.document,
+                        final_uri: data_record.uri,
+                        final_embedding: Some(data_record.embedding),
+                    });
+                }
+                if batch.len() >= self.batch_size.max(1) || (exhausted && !batch.is_empty()) {
+                    let records = Chunk::new(std::mem::take(&mut batch).into());
+                    if let Some(writer) = &metadata_segment_writer {
+                        writer.apply_materialized_log_chunk(records.clone()).await?;
+                    }
+                    if let Some(writer) = &hnsw_segment_writer {
+                        writer.apply_materialized_log_chunk(records).await?;
+                    }
+                }
+                if exhausted {
+                    break;
+                }
+            }
+        }
+
+        let mut segment_flush_info = Vec::new();
+        if let Some(mut writer) = metadata_segment_writer {
+            writer.write_to_blockfiles().await?;
+            segment_flush_info.push(flush(writer.commit().await, metadata_segment.id).await?);
+        }
+        if let Some(writer) = hnsw_segment_writer {
+            segment_flush_info.push(flush(writer.commit().await, vector_segment.id).await?);
+        }
+
+        let rebuilt_segments = segment_flush_info
+            .iter()
+            .map(|info| info.segment_id)
+            .collect();
+        if segment_flush_info.is_empty() {
+            return Ok(RebuildSegmentOutput {
+                rebuilt_segments,
+                collection_version: collection.version,
+            });
+        }
+        let response = sysdb
+            .flush_compaction(
+                collection.tenant.clone(),
+                collection.collection_id,
+                collection.log_position,
+                collection.version,
+                Arc::from(segment_flush_info),
+            )
+            .await?;
+        tracing::info!(
+            "Rebuilt segments {:?} of collection {}",
+            rebuilt_segments,
+            collection.collection_id
+        );
+
+        Ok(RebuildSegmentOutput {
+            rebuilt_segments,
+            collection_version: response.collection_version,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        execution::{
+            operator::Operator,
+            operators::{
+                check_integrity::{CheckIntegrityInput, CheckIntegrityOperator, IntegrityCheck},
+                fetch_segment::FetchSegmentOutput,
+                rebuild_segment::{RebuildSegmentInput, RebuildSegmentOperator},
+            },
+        },
+        log::test::{upsert_generator, LogGenerator},
+        segment::test::TestSegment,
+        sysdb::{sysdb::SysDb, test_sysdb::TestSysDb},
+    };
+
+    fn segments(test_segment: &TestSegment) -> FetchSegmentOutput {
+        FetchSegmentOutput {
+            collection: test_segment.collection.clone(),
+            metadata_segment: test_segment.metadata_segment.clone(),
+            record_segment: test_segment.record_segment.clone(),
+            vector_segment: test_segment.vector_segment.clone(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rebuild_segments() {
+        let mut test_segment = TestSegment::default();
+        test_segment
+            .populate_with_generator(
+                50,
+                &LogGenerator {
+                    generator: upsert_generator,
+                },
+            )
+            .await;
+        let mut sysdb = TestSysDb::new();
+        sysdb.add_collection(test_segment.collection.clone());
+        sysdb.add_segment(test_segment.metadata_segment.clone());
+        sysdb.add_segment(test_segment.record_segment.clone());
+        sysdb.add_segment(test_segment.vector_segment.clone());
+        let mut sysdb = Box::new(SysDb::Test(sysdb));
+
+        let rebuild_output = RebuildSegmentOperator {
+            rebuild_metadata: true,
+            rebuild_vector: true,
+            batch_size: 1000,
+        }
+        .run(&RebuildSegmentInput {
+            blockfile_provider: test_segment.blockfile_provider.clone(),
+            hnsw_provider: test_segment.hnsw_provider.clone(),
+            sysdb: sysdb.clone(),
+            segments: segments(&test_segment),
+        })
+        .await
+        .expect("RebuildSegmentOperator should not fail");
+        assert_eq!(
+            rebuild_output.rebuilt_segments,
+            vec![
+                test_segment.metadata_segment.id,
+                test_segment.vector_segment.id
+            ]
+        );
+        assert_eq!(
+            rebuild_output.collection_version,
+            test_segment.collection.version + 1
+        );
+
+        // The rebuilt segments are registered and pass the checks
+        let registered = sysdb
+            .get_segments(None, None, None, test_segment.collection.collection_id)
+            .await
+            .expect("Segments should be registered");
+        for segment in registered {
+            if segment.id == test_segment.metadata_segment.id {
+                assert_ne!(segment.file_path, test_segment.metadata_segment.file_path);
+                test_segment.metadata_segment = segment;
+            } else if segment.id == test_segment.vector_segment.id {
+                assert!(!segment.file_path.is_empty());
+                test_segment.vector_segment = segment;
+            }
+        }
+        let check_output = CheckIntegrityOperator {}
+            .run(&CheckIntegrityInput {
+                blockfile_provider: test_segment.blockfile_provider.clone(),
+                hnsw_provider: test_segment.hnsw_provider.clone(),
+                segments: segments(&test_segment),
+            })
+            .await
+            .expect("CheckIntegrityOperator should not fail");
+        assert_eq!(check_output.num_records, 50);
+        assert_eq!(check_output.num_issues, 0, "{:?}", check_output.issues);
+    }
+
+    #[tokio::test]
+    async fn test_rebuild_corrupted_segments_in_batches() {
+        let mut test_segment = TestSegment::default();
+        test_segment
+            .populate_with_generator(
+                50,
+                &LogGenerator {
+                    generator: upsert_generator,
+                },
+            )
+            .await;
+        // The metadata segment indexes records that are not in the record segment, and the
+        // vector segment holds no embedding
+        let record_segment = test_segment.record_segment.clone();
+        let extra_logs = LogGenerator {
+            generator: upsert_generator,
+        }
+        .generate_chunk(51..=60);
+        test_segment.compact_log(extra_logs, 50).await;
+        test_segment.record_segment = record_segment;
+
+        let check_output = CheckIntegrityOperator {}
+            .run(&CheckIntegrityInput {
+                blockfile_provider: test_segment.blockfile_provider.clone(),
+                hnsw_provider: test_segment.hnsw_provider.clone(),
+                segments: segments(&test_segment),
+            })
+            .await
+            .expect("CheckIntegrityOperator should not fail");
+        assert!(check_output.failed(IntegrityCheck::MetadataAgreement));
+        assert!(check_output.failed(IntegrityCheck::VectorIndexCoverage));
+
+        let mut sysdb = TestSysDb::new();
+        sysdb.add_collection(test_segment.collection.clone());
+        sysdb.add_segment(test_segment.metadata_segment.clone());
+        sysdb.add_segment(test_segment.record_segment.clone());
+        sysdb.add_segment(test_segment.vector_segment.clone());
+        let mut sysdb = Box::new(SysDb::Test(sysdb));
+
+        // The batch size does not divide the number of records, so the last batch is partial
+        RebuildSegmentOperator {
+            rebuild_metadata: true,
+            rebuild_vector: true,
+            batch_size: 7,
+        }
+        .run(&RebuildSegmentInput {
+            blockfile_provider: test_segment.blockfile_provider.clone(),
+            hnsw_provider: test_segment.hnsw_provider.clone(),
+            sysdb: sysdb.clone(),
+            segments: segments(&test_segment),
+        })
+        .await
+        .expect("RebuildSegmentOperator should not fail");
+
+        let registered = sysdb
+            .get_segments(None, None, None, test_segment.collection.collection_id)
+            .await
+            .expect("Segments should be registered");
+        for segment in registered {
+            if segment.id == test_segment.metadata_segment.id {
+                test_segment.metadata_segment = segment;
+            } else if segment.id == test_segment.vector_segment.id {
+                test_segment.vector_segment = segment;
+            }
+        }
+        let check_output = CheckIntegrityOperator {}
+            .run(&CheckIntegrityInput {
+                blockfile_provider: test_segment.blockfile_provider.clone(),
+                hnsw_provider: test_segment.hnsw_provider.clone(),
+                segments: segments(&test_segment),
+            })
+            .await
+            .expect("CheckIntegrityOperator should not fail");
+        assert_eq!(check_output.num_records, 50);
+        assert_eq!(check_output.num_issues, 0, "{:?}", check_output.issues);
+    }
+}
@@ -0,0 +1,395 @@
+use std::collections::HashMap;
+
+use chroma_blockstore::provider::BlockfileProvider;
+use chroma_distance::{normalize, DistanceFunction};
+use chroma_error::{ChromaError, ErrorCodes};
+use chroma_index::{hnsw_provider::HnswIndexProvider, ScalarQuantization};
+use chroma_types::{Chunk, MaterializedLogOperation, Segment, SegmentFlushInfo};
+use futures::TryStreamExt;
+use thiserror::Error;
+use tonic::async_trait;
+use tracing::trace;
+
+use crate::{
+    execution::operator::{Operator, OperatorType},
+    segment::{
+        distributed_hnsw_segment::{
+            distance_function_from_segment, quantization_metadata,
+            DistributedHNSWSegmentFromSegmentError, DistributedHNSWSegmentWriter,
+        },
+        record_segment::{
+            ApplyMaterializedLogError, RecordSegmentReader, RecordSegmentReaderCreationError,
+        },
+        MaterializedLogRecord, SegmentFlusher, SegmentWriter,
+    },
+};
+
+/// The `RetrainQuantizationOperator` fits the int8 quantization of a vector segment to the
+/// embeddings of the record segment, and writes a new index with the fitted quantization
+///
+/// # Parameters
+/// - `batch_size`: The number of records that are read and added to the index at a time
+///
+/// # Inputs
+/// - `blockfile_provider`: The blockfile provider
+/// - `hnsw_provider`: The HNSW index provider
+/// - `record_segment`: The record segment that holds the embeddings
+/// - `vector_segment`: The vector segment whose quantization has drifted
+/// - `dimension`: The dimension of the embeddings
+///
+/// # Outputs
+/// - `segment_flush_info`: The files of the new index, and the segment metadata with the
+///   fitted quantization
+///
+/// # Usage
+/// The compaction runs it after the segments are flushed, when the embeddings that it wrote
+/// have drifted away from the quantization of the index. The record segment is read twice,
+/// once to fit the quantization and once to build the index, so that the embeddings are never
+/// held in memory all at once. The new files replace the flushed files of the vector segment
+/// when the compaction is registered, together with the fitted quantization
+#[derive(Clone, Debug)]
+pub struct RetrainQuantizationOperator {
+    pub batch_size: usize,
+}
+
+#[derive(Debug)]
+pub struct RetrainQuantizationInput {
+    pub blockfile_provider: BlockfileProvider,
+    pub hnsw_provider: HnswIndexProvider,
+    pub record_segment: Segment,
+    pub vector_segment: Segment,
+    pub dimension: usize,
+}
+
+#[derive(Debug)]
+pub struct RetrainQuantizationOutput {
+    pub segment_flush_info: SegmentFlushInfo,
+}
+
+#[derive(Error, Debug)]
+pub enum RetrainQuantizationError {
+    #[error("Error applying records to retrained index: {0}")]
+    Apply(#[from] ApplyMaterializedLogError),
+    #[error("Error flushing retrained index: {0}")]
+    Flush(Box<dyn ChromaError>),
+    #[error("Error creating hnsw segment writer: {0}")]
+    HnswWriter(#[from] DistributedHNSWSegmentFromSegmentError),
+    #[error("Error reading record segment: {0}")]
+    RecordSegment(#[from] Box<dyn ChromaError>),
+    #[error("Error creating record segment reader: {0}")]
+    RecordReader(#[from] RecordSegmentReaderCreationError),
+}
+
+impl ChromaError for RetrainQuantizationError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            RetrainQuantizationError::Apply(e) => e.code(),
+            RetrainQuantizationError::Flush(e) => e.code(),
+            RetrainQuantizationError::HnswWriter(e) => e.code(),
+            RetrainQuantizationError::RecordSegment(e) => e.code(),
+            RetrainQuantizationError::RecordReader(e) => e.code(),
+        }
+    }
+}
+
+impl RetrainQuantizationOperator {
+    // The quantized spaces quantize the normalized embeddings, so the range is fitted to them.
+    // The int8 inner product ignores the offset, so the range of the inner product and cosine
+    // spaces is centered on zero
+    async fn fit(
+        &self,
+        record_segment_reader: &RecordSegmentReader<'_>,
+        distance_function: &DistanceFunction,
+    ) -> Result<Option<ScalarQuantization>, RetrainQuantizationError> {
+        let mut range: Option<(f32, f32)> = None;
+        let mut data_records = Box::pin(record_segment_reader.scan(None));
+        while let Some((_, data_record)) = data_records.try_next().await? {
+            let normalized;
+            let embedding = match distance_function {
+                DistanceFunction::Cosine => {
+                    normalized = normalize(data_record.embedding);
+                    normalized.as_slice()
+                }
+                _ => data_record.embedding,
+            };
+            for value in embedding {
+                range = match range {
+                    Some((min, max)) => Some((min.min(*value), max.max(*value))),
+                    None => Some((*value, *value)),
+                };
+            }
+        }
+        Ok(range.map(|(min, max)| match distance_function {
+            DistanceFunction::InnerProduct | DistanceFunction::Cosine => {
+                ScalarQuantization::from_symmetric_range(min, max)
+            }
+            _ => ScalarQuantization::from_range(min, max),
+        }))
+    }
+}
+
+#[async_trait]
+impl Operator<RetrainQuantizationInput, RetrainQuantizationOutput> for RetrainQuantizationOperator {
+    type Error = RetrainQuantizationError;
+
+    fn get_type(&self) -> OperatorType {
+        OperatorType::IO
+    }
+
+    async fn run(
+        &self,
+        input: &RetrainQuantizationInput,
+    ) -> Result<RetrainQuantizationOutput, RetrainQuantizationError> {
+        trace!("[{}]: {:?}", self.get_name(), input);
+
+        let record_segment_reader =
+            RecordSegmentReader::from_segment(&input.record_segment, &input.blockfile_provider)
+                .await
+                .map_err(|e| *e)?;
+        let distance_function =
+            distance_function_from_segment(&input.vector_segment).map_err(|e| *e)?;
+        let fitted_quantization = self
+            .fit(&record_segment_reader, &distance_function)
+            .await?
+            .unwrap_or_default();
+        tracing::info!(
+            "Retraining the quantization of hnsw segment {} to {:?}",
+            input.vector_segment.id,
+            fitted_quantization
+        );
+
+        // The index is written to new files, with the fitted quantization in the metadata
+        // of the segment that its writer is created from
+        let metadata = quantization_metadata(&fitted_quantization);
+        let mut vector_segment = Segment {
+            file_path: HashMap::new(),
+            ..input.vector_segment.clone()
+        };
+        vector_segment
+            .metadata
+            .get_or_insert_with(Default::default)
+            .extend(metadata.clone());
+        let hnsw_segment_writer = DistributedHNSWSegmentWriter::from_segment(
+            &vector_segment,
+            input.dimension,
+            input.hnsw_provider.clone(),
+        )
+        .await
+        .map_err(|e| *e)?;
+
+        let mut data_records = Box::pin(record_segment_reader.scan(None));
+        let mut batch = Vec::with_capacity(self.batch_size);
+        loop {
+            let next_record = data_records.try_next().await?;
+            let exhausted = next_record.is_none();
+            if let Some((offset_id, data_record)) = next_record {
+                batch.push(MaterializedLogRecord {
+                    data_record: None,
+                    offset_id,
+                    user_id: Some(data_record.id),
+                    final_operation: MaterializedLogOperation::AddNew,
+                    metadata_to_be_merged: None,
+                    metadata_to_be_deleted: None,
+                    final_document: None,
+                    final_uri: None,
+                    final_embedding: Some(data_record.embedding),
+                });
+            }
+            if batch.len() >= self.batch_size.max(1) || (exhausted && !batch.is_empty()) {
+                hnsw_segment_writer
+                    .apply_materialized_log_chunk(Chunk::new(std::mem::take(&mut batch).into()))
+                    .await?;
+            }
+            if exhausted {
+                break;
+            }
+        }
+
+        let file_paths = hnsw_segment_writer
+            .commit()
+            .await
+            .map_err(RetrainQuantizationError::Flush)?
+            .flush()
+            .await
+            .map_err(RetrainQuantizationError::Flush)?;
+        Ok(RetrainQuantizationOutput {
+            segment_flush_info: SegmentFlushInfo {
+                segment_id: input.vector_segment.id,
+                file_paths,
+                metadata: Some(metadata),
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chroma_types::{MetadataValue, OperationRecord};
+
+    use crate::{
+        execution::{
+            operator::Operator,
+            operators::retrain_quantization::{
+                RetrainQuantizationInput, RetrainQuantizationOperator,
+            },
+        },
+        log::test::{upsert_generator, LogGenerator, TEST_EMBEDDING_DIMENSION},
+        segment::{
+            distributed_hnsw_segment::{quantization_from_segment, DistributedHNSWSegmentReader},
+            test::TestSegment,
+        },
+    };
+
+    #[tokio::test]
+    async fn test_retrain_quantization() {
+        // The embeddings are far outside of the range of the default quantization
+        let mut test_segment = TestSegment::default();
+        test_segment.vector_segment.metadata = Some(HashMap::from([
+            (
+                "hnsw:quantization".to_string(),
+                MetadataValue::Str("int8".to_string()),
+            ),
+            (
+                "hnsw:space".to_string(),
+                MetadataValue::Str("l2".to_string()),
+            ),
+        ]));
+        test_segment
+            .populate_with_generator(
+                20,
+                &LogGenerator {
+                    generator: |offset| OperationRecord {
+                        embedding: Some(vec![offset as f32 * 10.0; TEST_EMBEDDING_DIMENSION]),
+                        ..upsert_generator(offset)
+                    },
+                },
+            )
+            .await;
+
+        let retrain_input = RetrainQuantizationInput {
+            blockfile_provider: test_segment.blockfile_provider.clone(),
+            hnsw_provider: test_segment.hnsw_provider.clone(),
+            record_segment: test_segment.record_segment.clone(),
+            vector_segment: test_segment.vector_segment.clone(),
+            dimension: TEST_EMBEDDING_DIMENSION,
+        };
+        let retrain_output = RetrainQuantizationOperator { batch_size: 7 }
+            .run(&retrain_input)
+            .await
+            .expect("RetrainQuantizationOperator should not fail");
+        let segment_flush_info = retrain_output.segment_flush_info;
+        assert_eq!(
+            segment_flush_info.segment_id,
+            test_segment.vector_segment.id
+        );
+
+        let mut retrained_segment = test_segment.vector_segment.clone();
+        retrained_segment.file_path = segment_flush_info.file_paths;
+        retrained_segment
+            .metadata
+            .get_or_insert_with(Default::default)
+            .extend(
+                segment_flush_info
+                    .metadata
+                    .expect("The metadata should be set"),
+            );
+        let quantization =
+            quantization_from_segment(&retrained_segment).expect("The segment should be quantized");
+        for offset in 1..=20 {
+            assert_eq!(
+                quantization.clipped(&[offset as f32 * 10.0; TEST_EMBEDDING_DIMENSION]),
+                0
+            );
+        }
+
+        let reader = DistributedHNSWSegmentReader::from_segment(
+            &retrained_segment,
+            TEST_EMBEDDING_DIMENSION,
+            test_segment.hnsw_provider.clone(),
+        )
+        .await
+        .expect("The retrained index should open");
+        assert_eq!(reader.len(), 20);
+        assert_eq!(reader.quantization(), Some(quantization));
+        let (offset_ids, _) = reader
+            .query(&[150.0; TEST_EMBEDDING_DIMENSION], 1, &[], &[])
+            .expect("The retrained index should be queried");
+        assert_eq!(offset_ids, vec![15]);
+    }
+
+    #[tokio::test]
+    async fn test_retrain_quantization_inner_product() {
+        // The embeddings are all positive, so their range is not centered on zero
+        let mut test_segment = TestSegment::default();
+        test_segment.vector_segment.metadata = Some(HashMap::from([
+            (
+                "hnsw:quantization".to_string(),
+                MetadataValue::Str("int8".to_string()),
+            ),
+            (
+                "hnsw:space".to_string(),
+                MetadataValue::Str("ip".to_string()),
+            ),
+        ]));
+        test_segment
+            .populate_with_generator(
+                20,
+                &LogGenerator {
+                    generator: |offset| OperationRecord {
+                        embedding: Some(vec![offset as f32 * 10.0; TEST_EMBEDDING_DIMENSION]),
+                        ..upsert_generator(offset)
+                    },
+                },
+            )
+            .await;
+
+        let retrain_input = RetrainQuantizationInput {
+            blockfile_provider: test_segment.blockfile_provider.clone(),
+            hnsw_provider: test_segment.hnsw_provider.clone(),
+            record_segment: test_segment.record_segment.clone(),
+            vector_segment: test_segment.vector_segment.clone(),
+            dimension: TEST_EMBEDDING_DIMENSION,
+        };
+        let segment_flush_info = RetrainQuantizationOperator { batch_size: 7 }
+            .run(&retrain_input)
+            .await
+            .expect("RetrainQuantizationOperator should not fail")
+            .segment_flush_info;
+
+        let mut retrained_segment = test_segment.vector_segment.clone();
+        retrained_segment.file_path = segment_flush_info.file_paths;
+        retrained_segment
+            .metadata
+            .get_or_insert_with(Default::default)
+            .extend(
+                segment_flush_info
+                    .metadata
+                    .expect("The metadata should be set"),
+            );
+        let quantization =
+            quantization_from_segment(&retrained_segment).expect("The segment should be quantized");
+        assert_eq!(quantization.offset, 0.0);
+        for offset in 1..=20 {
+            assert_eq!(
+                quantization.clipped(&[offset as f32 * 10.0; TEST_EMBEDDING_DIMENSION]),
+                0
+            );
+        }
+
+        // The largest embedding has the largest inner product with a positive query, which
+        // an offset that the int8 inner product ignores would turn around
+        let reader = DistributedHNSWSegmentReader::from_segment(
+            &retrained_segment,
+            TEST_EMBEDDING_DIMENSION,
+            test_segment.hnsw_provider.clone(),
+        )
+        .await
+        .expect("The retrained index should open");
+        let (offset_ids, _) = reader
+            .query(&[1.0; TEST_EMBEDDING_DIMENSION], 1, &[], &[])
+            .expect("The retrained index should be queried");
+        assert_eq!(offset_ids, vec![20]);
+    }
+}
@@ -0,0 +1,142 @@
+use chroma_blockstore::provider::BlockfileProvider;
+use chroma_error::{ChromaError, ErrorCodes};
+use chroma_types::Segment;
+use thiserror::Error;
+use tonic::async_trait;
+use tracing::trace;
+
+use crate::{
+    execution::operator::{Operator, OperatorType},
+    segment::{
+        checksum::ContentChecksum,
+        record_segment::{
+            stored_content_checksum, RecordSegmentReader, RecordSegmentReaderCreationError,
+        },
+    },
+};
+
+/// The `VerifyChecksumOperator` recomputes the content checksum of a record segment from its
+/// records
+///
+/// # Parameters
+/// None
+///
+/// # Inputs
+/// - `blockfile_provider`: The blockfile provider
+/// - `record_segment`: The record segment information
+///
+/// # Outputs
+/// - `stored`: The checksum stored with the record segment, if any
+/// - `computed`: The checksum computed from the records
+///
+/// # Usage
+/// It can be used to verify that a replica or a restored snapshot of a collection holds the
+/// same records as the source, without comparing their files
+#[derive(Clone, Debug)]
+pub struct VerifyChecksumOperator {}
+
+#[derive(Debug)]
+pub struct VerifyChecksumInput {
+    pub blockfile_provider: BlockfileProvider,
+    pub record_segment: Segment,
+}
+
+#[derive(Debug)]
+pub struct VerifyChecksumOutput {
+    pub stored: Option<ContentChecksum>,
+    pub computed: ContentChecksum,
+}
+
+#[derive(Error, Debug)]
+pub enum VerifyChecksumError {
+    #[error("Error creating record segment reader: {0}")]
+    RecordReader(#[from] RecordSegmentReaderCreationError),
+    #[error("Error reading record segment: {0}")]
+    RecordSegment(#[from] Box<dyn ChromaError>),
+}
+
+impl ChromaError for VerifyChecksumError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            VerifyChecksumError::RecordReader(e) => e.code(),
+            VerifyChecksumError::RecordSegment(e) => e.code(),
+        }
+    }
+}
+
+#[async_trait]
+impl Operator<VerifyChecksumInput, VerifyChecksumOutput> for VerifyChecksumOperator {
+    type Error = VerifyChecksumError;
+
+    fn get_type(&self) -> OperatorType {
+        OperatorType::IO
+    }
+
+    async fn run(
+        &self,
+        input: &VerifyChecksumInput,
+    ) -> Result<VerifyChecksumOutput, VerifyChecksumError> {
+        trace!("[{}]: {:?}", self.get_name(), input);
+
+        let stored = stored_content_checksum(&input.record_segment);
+        let computed = match RecordSegmentReader::from_segment(
+            &input.record_segment,
+            &input.blockfile_provider,
+        )
+        .await
+        {
+            Ok(reader) => ContentChecksum::compute(&reader).await?,
+            // Nothing is compacted for the collection, so it has no records
+            Err(e) if matches!(*e, RecordSegmentReaderCreationError::UninitializedSegment) => {
+                ContentChecksum::default()
+            }
+            Err(e) => return Err((*e).into()),
+        };
+
+        Ok(VerifyChecksumOutput { stored, computed })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        execution::{
+            operator::Operator,
+            operators::verify_checksum::{VerifyChecksumInput, VerifyChecksumOperator},
+        },
+        log::test::{upsert_generator, LogGenerator},
+        segment::{checksum::ContentChecksum, test::TestSegment},
+    };
+
+    #[tokio::test]
+    async fn test_verify_checksum() {
+        let mut test_segment = TestSegment::default();
+        let output = VerifyChecksumOperator {}
+            .run(&VerifyChecksumInput {
+                blockfile_provider: test_segment.blockfile_provider.clone(),
+                record_segment: test_segment.record_segment.clone(),
+            })
+            .await
+            .expect("VerifyChecksumOperator should not fail");
+        assert_eq!(output.stored, None);
+        assert_eq!(output.computed, ContentChecksum::default());
+
+        test_segment
+            .populate_with_generator(
+                100,
+                &LogGenerator {
+                    generator: upsert_generator,
+                },
+            )
+            .await;
+        let output = VerifyChecksumOperator {}
+            .run(&VerifyChecksumInput {
+                blockfile_provider: test_segment.blockfile_provider.clone(),
+                record_segment: test_segment.record_segment.clone(),
+            })
+            .await
+            .expect("VerifyChecksumOperator should not fail");
+        assert_eq!(output.computed.num_records(), 100);
+        assert_eq!(output.stored, Some(output.computed));
+    }
+}
@@ -0,0 +1,359 @@
+use std::collections::HashSet;
+
+use chroma_blockstore::provider::BlockfileProvider;
+use chroma_error::{ChromaError, ErrorCodes};
+use chroma_index::hnsw_provider::HnswIndexProvider;
+use chroma_storage::{GetError, PutError, Storage};
+use chroma_types::{CollectionUuid, Segment};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tonic::async_trait;
+use tracing::trace;
+use uuid::Uuid;
+
+use crate::{
+    execution::operator::{Operator, OperatorType},
+    segment::{
+        distributed_hnsw_segment::{
+            DistributedHNSWSegmentFromSegmentError, DistributedHNSWSegmentReader,
+        },
+        metadata_segment::{MetadataSegmentError, MetadataSegmentReader},
+        record_segment::{RecordSegmentReader, RecordSegmentReaderCreationError},
+    },
+};
+
+use super::fetch_segment::FetchSegmentOutput;
+
+// The ids in the file paths of the segments, which are the roots of their blockfiles
+fn blockfile_ids<'a>(segments: impl IntoIterator<Item = &'a Segment>) -> Vec<Uuid> {
+    segments
+        .into_iter()
+        .flat_map(|segment| segment.file_path.values().flatten())
+        .filter_map(|path| Uuid::parse_str(path).ok())
+        .collect()
+}
+
+#[derive(Error, Debug)]
+pub enum WarmUpManifestError {
+    #[error("Error decoding warm up manifest: {0}")]
+    Decode(#[from] serde_json::Error),
+    #[error("Error reading warm up manifest: {0}")]
+    Read(#[from] GetError),
+    #[error("Error reading hottest blocks: {0}")]
+    Record(Box<dyn ChromaError>),
+    #[error("Error writing warm up manifest: {0}")]
+    Write(#[from] PutError),
+}
+
+impl ChromaError for WarmUpManifestError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            WarmUpManifestError::Decode(_) => ErrorCodes::Internal,
+            WarmUpManifestError::Read(e) => e.code(),
+            WarmUpManifestError::Record(e) => e.code(),
+            WarmUpManifestError::Write(e) => e.code(),
+        }
+    }
+}
+
+/// The hottest blocks of a collection, which a worker records in storage when the collection
+/// is assigned away from it, so that the next owner of the collection loads them
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct WarmUpManifest {
+    pub block_ids: Vec<Uuid>,
+}
+
+impl WarmUpManifest {
+    fn key(collection_id: CollectionUuid) -> String {
+        format!("warm_up/{}", collection_id)
+    }
+
+    /// Records up to `limit` of the blocks of the blockfile segments, the most read by this
+    /// worker first
+    pub async fn record<'a>(
+        blockfile_provider: &BlockfileProvider,
+        segments: impl IntoIterator<Item = &'a Segment>,
+        limit: usize,
+    ) -> Result<Self, WarmUpManifestError> {
+        let root_ids = blockfile_ids(segments);
+        let block_ids = blockfile_provider
+            .hottest_block_ids(&root_ids, limit)
+            .await
+            .map_err(WarmUpManifestError::Record)?;
+        Ok(Self { block_ids })
+    }
+
+    /// Returns the manifest of the collection, or None if no worker recorded one
+    pub async fn read(
+        storage: &Storage,
+        collection_id: CollectionUuid,
+    ) -> Result<Option<Self>, WarmUpManifestError> {
+        match storage.get(&Self::key(collection_id)).await {
+            Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            Err(GetError::NoSuchKey(_)) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn write(
+        &self,
+        storage: &Storage,
+        collection_id: CollectionUuid,
+    ) -> Result<(), WarmUpManifestError> {
+        let bytes = serde_json::to_vec(self)?;
+        storage.put_bytes(&Self::key(collection_id), bytes).await?;
+        Ok(())
+    }
+}
+
+/// The `WarmUpOperator` opens the segments of a collection like a query does, so that the
+/// data needed by the queries is cached before they arrive
+///
+/// # Parameters
+/// None
+///
+/// # Inputs
+/// - `blockfile_provider`: The blockfile provider
+/// - `hnsw_provider`: The HNSW index provider
+/// - `segments`: The collection and segment information
+/// - `storage`: The storage of the warm up manifests
+///
+/// # Outputs
+/// - `num_blocks`: The number of blocks of the record segment that are loaded
+/// - `num_hot_blocks`: The number of blocks of the warm up manifest that are loaded
+/// - `hnsw_loaded`: Whether the HNSW index is loaded
+///
+/// # Usage
+/// It can be used to warm up a worker before queries are routed to it. The roots of all the
+/// blockfiles are loaded by opening the readers, the blocks mapping between user ids and
+/// offset ids are loaded in full, and the HNSW index is loaded into the index cache. The
+/// hottest blocks that the previous owner of the collection recorded in its warm up manifest
+/// are loaded as well
+#[derive(Clone, Debug)]
+pub struct WarmUpOperator {}
+
+#[derive(Debug)]
+pub struct WarmUpInput {
+    pub blockfile_provider: BlockfileProvider,
+    pub hnsw_provider: HnswIndexProvider,
+    pub segments: FetchSegmentOutput,
+    pub storage: Storage,
+}
+
+#[derive(Debug, Default)]
+pub struct WarmUpOutput {
+    pub num_blocks: usize,
+    pub num_hot_blocks: usize,
+    pub hnsw_loaded: bool,
+}
+
+#[derive(Error, Debug)]
+pub enum WarmUpError {
+    #[error("Error creating hnsw segment reader: {0}")]
+    Hnsw(#[from] DistributedHNSWSegmentFromSegmentError),
+    #[error("Error creating metadata segment reader: {0}")]
+    Metadata(#[from] MetadataSegmentError),
+    #[error("Error creating record segment reader: {0}")]
+    Record(#[from] RecordSegmentReaderCreationError),
+}
+
+impl ChromaError for WarmUpError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            WarmUpError::Hnsw(e) => e.code(),
+            WarmUpError::Metadata(e) => e.code(),
+            WarmUpError::Record(e) => e.code(),
+        }
+    }
+}
+
+#[async_trait]
+impl Operator<WarmUpInput, WarmUpOutput> for WarmUpOperator {
+    type Error = WarmUpError;
+
+    fn get_type(&self) -> OperatorType {
+        OperatorType::IO
+    }
+
+    async fn run(&self, input: &WarmUpInput) -> Result<WarmUpOutput, WarmUpError> {
+        trace!("[{}]: {:?}", self.get_name(), input);
+
+        let record_segment_reader = match RecordSegmentReader::from_segment(
+            &input.segments.record_segment,
+            &input.blockfile_provider,
+        )
+        .await
+        {
+            Ok(reader) => reader,
+            // Nothing is compacted for the collection, so there is nothing to load
+            Err(e) if matches!(*e, RecordSegmentReaderCreationError::UninitializedSegment) => {
+                return Ok(WarmUpOutput::default())
+            }
+            Err(e) => return Err((*e).into()),
+        };
+        let num_blocks = record_segment_reader.load_id_blocks().await;
+
+        MetadataSegmentReader::from_segment(
+            &input.segments.metadata_segment,
+            &input.blockfile_provider,
+        )
+        .await?;
+
+        // The manifest only speeds up the queries, so the warm up does not fail without it
+        let collection_id = input.segments.collection.collection_id;
+        let num_hot_blocks = match WarmUpManifest::read(&input.storage, collection_id).await {
+            Ok(Some(manifest)) => {
+                let root_ids = blockfile_ids([
+                    &input.segments.record_segment,
+                    &input.segments.metadata_segment,
+                ]);
+                let block_ids = manifest.block_ids.into_iter().collect::<HashSet<_>>();
+                input
+                    .blockfile_provider
+                    .load_blocks(&root_ids, &block_ids)
+                    .await
+            }
+            Ok(None) => 0,
+            Err(e) => {
+                tracing::warn!("Error reading warm up manifest of {}: {}", collection_id, e);
+                0
+            }
+        };
+
+        let hnsw_loaded = match input.segments.collection.dimension {
+            Some(dimension) => match DistributedHNSWSegmentReader::from_segment(
+                &input.segments.vector_segment,
+                dimension as usize,
+                input.hnsw_provider.clone(),
+            )
+            .await
+            {
+                Ok(_) => true,
+                Err(e) if matches!(*e, DistributedHNSWSegmentFromSegmentError::Uninitialized) => {
+                    false
+                }
+                Err(e) => return Err((*e).into()),
+            },
+            None => false,
+        };
+
+        Ok(WarmUpOutput {
+            num_blocks,
+            num_hot_blocks,
+            hnsw_loaded,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chroma_storage::{test_storage, Storage};
+
+    use crate::{
+        execution::{
+            operator::Operator,
+            operators::{
+                fetch_segment::FetchSegmentOutput,
+                warm_up::{WarmUpInput, WarmUpManifest, WarmUpOperator},
+            },
+        },
+        log::test::{upsert_generator, LogGenerator},
+        segment::test::TestSegment,
+    };
+
+    fn warm_up_input(test_segment: &TestSegment, storage: &Storage) -> WarmUpInput {
+        WarmUpInput {
+            blockfile_provider: test_segment.blockfile_provider.clone(),
+            hnsw_provider: test_segment.hnsw_provider.clone(),
+            segments: FetchSegmentOutput {
+                collection: test_segment.collection.clone(),
+                metadata_segment: test_segment.metadata_segment.clone(),
+                record_segment: test_segment.record_segment.clone(),
+                vector_segment: test_segment.vector_segment.clone(),
+            },
+            storage: storage.clone(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_warm_up_empty_collection() {
+        let test_segment = TestSegment::default();
+        let warm_up_output = WarmUpOperator {}
+            .run(&warm_up_input(&test_segment, &test_storage()))
+            .await
+            .expect("WarmUpOperator should not fail");
+        assert_eq!(warm_up_output.num_blocks, 0);
+        assert!(!warm_up_output.hnsw_loaded);
+    }
+
+    #[tokio::test]
+    async fn test_warm_up_compacted_collection() {
+        let mut test_segment = TestSegment::default();
+        test_segment
+            .populate_with_generator(
+                100,
+                &LogGenerator {
+                    generator: upsert_generator,
+                },
+            )
+            .await;
+        let warm_up_output = WarmUpOperator {}
+            .run(&warm_up_input(&test_segment, &test_storage()))
+            .await
+            .expect("WarmUpOperator should not fail");
+        // There is at least one block for each of the two id mappings
+        assert!(warm_up_output.num_blocks >= 2);
+        // The vector segment is not populated by the test segment
+        assert!(!warm_up_output.hnsw_loaded);
+    }
+
+    #[tokio::test]
+    async fn test_warm_up_hot_blocks() {
+        let mut test_segment = TestSegment::default();
+        test_segment
+            .populate_with_generator(
+                100,
+                &LogGenerator {
+                    generator: upsert_generator,
+                },
+            )
+            .await;
+        let storage = test_storage();
+        let input = warm_up_input(&test_segment, &storage);
+        let warm_up_output = WarmUpOperator {}
+            .run(&input)
+            .await
+            .expect("WarmUpOperator should not fail");
+        assert_eq!(warm_up_output.num_hot_blocks, 0);
+
+        // The blocks loaded by the warm up are the hottest blocks of the collection
+        let manifest = WarmUpManifest::record(
+            &input.blockfile_provider,
+            [
+                &input.segments.record_segment,
+                &input.segments.metadata_segment,
+            ],
+            10,
+        )
+        .await
+        .expect("Recording the manifest should not fail");
+        assert!(!manifest.block_ids.is_empty());
+        let collection_id = test_segment.collection.collection_id;
+        manifest
+            .write(&storage, collection_id)
+            .await
+            .expect("Writing the manifest should not fail");
+        assert_eq!(
+            WarmUpManifest::read(&storage, collection_id)
+                .await
+                .expect("Reading the manifest should not fail"),
+            Some(manifest.clone())
+        );
+
+        let warm_up_output = WarmUpOperator {}
+            .run(&input)
+            .await
+            .expect("WarmUpOperator should not fail");
+        assert_eq!(warm_up_output.num_hot_blocks, manifest.block_ids.len());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::compactor::metrics::CompactionMetrics;
 use crate::segment::metadata_segment::MetadataSegmentError;
 use crate::segment::metadata_segment::MetadataSegmentWriter;
 use crate::segment::record_segment::ApplyMaterializedLogError;
@@ -17,10 +18,13 @@ use chroma_blockstore::provider::BlockfileProvider;
 use chroma_error::ChromaError;
 use chroma_error::ErrorCodes;
 use chroma_types::Chunk;
+use chroma_types::CollectionUuid;
 use chroma_types::LogRecord;
+use chroma_types::MaterializedLogOperation;
 use chroma_types::Segment;
 use std::sync::atomic::AtomicU32;
 use std::sync::Arc;
+use std::time::Instant;
 use thiserror::Error;
 use tracing::Instrument;
 use tracing::Span;
@@ -120,11 +124,11 @@ impl Operator<WriteSegmentsInput, WriteSegmentsOutput> for WriteSegmentsOperator
                     RecordSegmentReaderCreationError::UninitializedSegment => {
                         record_segment_reader = None;
                     }
-                    RecordSegmentReaderCreationError::BlockfileOpenError(e) => {
+                    RecordSegmentReaderCreationError::BlockfileOpenError(segment_id, e) => {
                         tracing::error!("Error creating record segment reader {}", e);
                         return Err(
                             WriteSegmentsOperatorError::LogMaterializationPreparationError(
-                                RecordSegmentReaderCreationError::BlockfileOpenError(e),
+                                RecordSegmentReaderCreationError::BlockfileOpenError(segment_id, e),
                             ),
                         );
                     }
@@ -162,7 +166,10 @@ impl Operator<WriteSegmentsInput, WriteSegmentsOutput> for WriteSegmentsOperator
             input.chunk.clone(),
             Some(input.offset_id.clone()),
         );
+        let metrics = CompactionMetrics::get();
+        let collection_id = input.record_segment.collection;
         // Materialize the logs.
+        let materialization_start = Instant::now();
         let res = match materializer
             .materialize()
             .instrument(tracing::trace_span!(parent: Span::current(), "Materialize logs"))
@@ -174,6 +181,7 @@ impl Operator<WriteSegmentsInput, WriteSegmentsOutput> for WriteSegmentsOperator
                 return Err(WriteSegmentsOperatorError::LogMaterializationError(e));
             }
         };
+        metrics.record_materialization(collection_id, res.len(), materialization_start.elapsed());
         // Apply materialized records.
         match input
             .record_segment_writer
@@ -188,6 +196,14 @@ impl Operator<WriteSegmentsInput, WriteSegmentsOutput> for WriteSegmentsOperator
                 return Err(WriteSegmentsOperatorError::ApplyMaterializatedLogsError(e));
             }
         }
+        // Remember the idempotency keys, so that retried writes are skipped in later compactions.
+        if let Err(e) = input
+            .record_segment_writer
+            .apply_idempotency_keys(&input.chunk)
+            .await
+        {
+            return Err(WriteSegmentsOperatorError::ApplyMaterializatedLogsError(e));
+        }
         tracing::debug!("Applied materialized records to record segment");
         match input
             .metadata_segment_writer
@@ -203,6 +219,18 @@ impl Operator<WriteSegmentsInput, WriteSegmentsOutput> for WriteSegmentsOperator
             }
         }
         tracing::debug!("Applied materialized records to metadata segment");
+        let hnsw_inserts = res
+            .iter()
+            .filter(|(record, _)| {
+                matches!(
+                    record.final_operation,
+                    MaterializedLogOperation::AddNew
+                        | MaterializedLogOperation::UpdateExisting
+                        | MaterializedLogOperation::OverwriteExisting
+                )
+            })
+            .count();
+        let hnsw_start = Instant::now();
         match input
             .hnsw_segment_writer
             .apply_materialized_log_chunk(res)
@@ -216,6 +244,7 @@ impl Operator<WriteSegmentsInput, WriteSegmentsOutput> for WriteSegmentsOperator
                 return Err(WriteSegmentsOperatorError::ApplyMaterializatedLogsError(e));
             }
         }
+        metrics.record_hnsw_inserts(collection_id, hnsw_inserts, hnsw_start.elapsed());
         tracing::debug!("Applied Materialized Records to HNSW Segment");
         Ok(WriteSegmentsOutput {
             record_segment_writer: input.record_segment_writer.clone(),
@@ -223,4 +252,12 @@ impl Operator<WriteSegmentsInput, WriteSegmentsOutput> for WriteSegmentsOperator
             metadata_segment_writer: input.metadata_segment_writer.clone(),
         })
     }
+
+    fn collection_id(&self, input: &WriteSegmentsInput) -> Option<CollectionUuid> {
+        Some(input.record_segment.collection)
+    }
+
+    fn input_cardinality(&self, input: &WriteSegmentsInput) -> Option<usize> {
+        Some(input.chunk.len())
+    }
 }
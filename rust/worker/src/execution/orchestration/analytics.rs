@@ -0,0 +1,276 @@
+use chroma_blockstore::provider::BlockfileProvider;
+use chroma_error::{ChromaError, ErrorCodes};
+use thiserror::Error;
+use tokio::sync::oneshot::{self, error::RecvError, Sender};
+use tonic::async_trait;
+use tracing::Span;
+
+use crate::{
+    execution::{
+        dispatcher::Dispatcher,
+        operator::{wrap, TaskError, TaskResult},
+        operators::{
+            aggregate::{AggregateError, AggregateInput, AggregateOperator, AggregateOutput},
+            count::{CountError, CountInput, CountOperator, CountOutput},
+            facet::{FacetError, FacetInput, FacetOperator, FacetOutput},
+        },
+        orchestration::{common::terminate_with_error, knn::KnnFilterOutput},
+    },
+    system::{ChannelError, Component, ComponentContext, ComponentHandle, Handler, System},
+};
+
+#[derive(Error, Debug)]
+pub enum AnalyticsError {
+    #[error("Error running Aggregate Operator: {0}")]
+    Aggregate(#[from] AggregateError),
+    #[error("Error sending message through channel: {0}")]
+    Channel(#[from] ChannelError),
+    #[error("Error running Count Operator: {0}")]
+    Count(#[from] CountError),
+    #[error("Error running Facet Operator: {0}")]
+    Facet(#[from] FacetError),
+    #[error("Task aborted: {0}")]
+    Aborted(String),
+    #[error("Panic running task: {0}")]
+    Panic(String),
+    #[error("Error receiving final result: {0}")]
+    Result(#[from] RecvError),
+}
+
+impl ChromaError for AnalyticsError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            AnalyticsError::Aggregate(e) => e.code(),
+            AnalyticsError::Channel(e) => e.code(),
+            AnalyticsError::Count(e) => e.code(),
+            AnalyticsError::Facet(e) => e.code(),
+            AnalyticsError::Aborted(_) => ErrorCodes::Aborted,
+            AnalyticsError::Panic(_) => ErrorCodes::Aborted,
+            AnalyticsError::Result(_) => ErrorCodes::Internal,
+        }
+    }
+}
+
+impl<E> From<TaskError<E>> for AnalyticsError
+where
+    E: Into<AnalyticsError>,
+{
+    fn from(value: TaskError<E>) -> Self {
+        match value {
+            TaskError::Panic(e) => AnalyticsError::Panic(e.unwrap_or_default()),
+            TaskError::TaskFailed(e) => e.into(),
+            TaskError::Aborted(e) => AnalyticsError::Aborted(e),
+        }
+    }
+}
+
+/// The operator that summarizes the filtered records
+#[derive(Clone, Debug)]
+pub enum AnalyticsQuery {
+    Aggregate(AggregateOperator),
+    Count(CountOperator),
+    Facet(FacetOperator),
+}
+
+#[derive(Debug)]
+pub enum AnalyticsOutput {
+    Aggregate(AggregateOutput),
+    Count(CountOutput),
+    Facet(FacetOutput),
+}
+
+type AnalyticsResult = Result<AnalyticsOutput, AnalyticsError>;
+
+/// The `AnalyticsOrchestrator` summarizes the records that match the filter of a query, e.g.
+/// by counting them, by aggregating a metadata key or by counting the values of several keys,
+/// without hydrating the records. It takes the output of a `KnnFilterOrchestrator`, which
+/// evaluates the filter of the query
+///
+/// # Pipeline
+/// ```text
+///  ┌────────────┐
+///  │            │
+///  │  on_start  │
+///  │            │
+///  └─────┬──────┘
+///        │
+///        ▼
+///  ┌─────────────────────────────────────────────────────┐
+///  │                                                     │
+///  │  CountOperator, AggregateOperator or FacetOperator  │
+///  │                                                     │
+///  └─────┬───────────────────────────────────────────────┘
+///        │
+///        ▼
+///  ┌──────────────────┐
+///  │                  │
+///  │  result_channel  │
+///  │                  │
+///  └──────────────────┘
+/// ```
+#[derive(Debug)]
+pub struct AnalyticsOrchestrator {
+    // Orchestrator parameters
+    blockfile_provider: BlockfileProvider,
+    dispatcher: ComponentHandle<Dispatcher>,
+    queue: usize,
+
+    // Output from KnnFilterOrchestrator
+    knn_filter_output: KnnFilterOutput,
+
+    // The operator that summarizes the filtered records
+    query: AnalyticsQuery,
+
+    // Result channel
+    result_channel: Option<Sender<AnalyticsResult>>,
+}
+
+impl AnalyticsOrchestrator {
+    pub fn new(
+        blockfile_provider: BlockfileProvider,
+        dispatcher: ComponentHandle<Dispatcher>,
+        queue: usize,
+        knn_filter_output: KnnFilterOutput,
+        query: AnalyticsQuery,
+    ) -> Self {
+        Self {
+            blockfile_provider,
+            dispatcher,
+            queue,
+            knn_filter_output,
+            query,
+            result_channel: None,
+        }
+    }
+
+    pub async fn run(mut self, system: System) -> AnalyticsResult {
+        let (tx, rx) = oneshot::channel();
+        self.result_channel = Some(tx);
+        let mut handle = system.start_component(self);
+        let result = rx.await;
+        handle.stop();
+        result?
+    }
+
+    fn terminate_with_error<E>(&mut self, ctx: &ComponentContext<Self>, err: E)
+    where
+        E: Into<AnalyticsError>,
+    {
+        let analytics_err = err.into();
+        tracing::error!("Error running orchestrator: {}", &analytics_err);
+        terminate_with_error(self.result_channel.take(), analytics_err, ctx);
+    }
+
+    fn send_result(&mut self, output: AnalyticsOutput) {
+        if let Some(chan) = self.result_channel.take() {
+            if chan.send(Ok(output)).is_err() {
+                tracing::error!("Error sending final result");
+            };
+        }
+    }
+}
+
+#[async_trait]
+impl Component for AnalyticsOrchestrator {
+    fn get_name() -> &'static str {
+        "Analytics Orchestrator"
+    }
+
+    fn queue_size(&self) -> usize {
+        self.queue
+    }
+
+    async fn on_start(&mut self, ctx: &ComponentContext<Self>) {
+        let logs = self.knn_filter_output.logs.clone();
+        let segments = &self.knn_filter_output.segments;
+        let filter_output = &self.knn_filter_output.filter_output;
+        let task = match &self.query {
+            AnalyticsQuery::Aggregate(aggregate) => wrap(
+                Box::new(aggregate.clone()),
+                AggregateInput {
+                    logs,
+                    blockfile_provider: self.blockfile_provider.clone(),
+                    metadata_segment: segments.metadata_segment.clone(),
+                    record_segment: segments.record_segment.clone(),
+                    log_offset_ids: filter_output.log_offset_ids.clone(),
+                    compact_offset_ids: filter_output.compact_offset_ids.clone(),
+                },
+                ctx.receiver(),
+            ),
+            AnalyticsQuery::Count(count) => wrap(
+                Box::new(count.clone()),
+                CountInput {
+                    logs,
+                    blockfile_provider: self.blockfile_provider.clone(),
+                    record_segment: segments.record_segment.clone(),
+                    log_offset_ids: filter_output.log_offset_ids.clone(),
+                    compact_offset_ids: filter_output.compact_offset_ids.clone(),
+                },
+                ctx.receiver(),
+            ),
+            AnalyticsQuery::Facet(facet) => wrap(
+                Box::new(facet.clone()),
+                FacetInput {
+                    logs,
+                    blockfile_provider: self.blockfile_provider.clone(),
+                    metadata_segment: segments.metadata_segment.clone(),
+                    record_segment: segments.record_segment.clone(),
+                    log_offset_ids: filter_output.log_offset_ids.clone(),
+                    compact_offset_ids: filter_output.compact_offset_ids.clone(),
+                },
+                ctx.receiver(),
+            ),
+        };
+        if let Err(err) = self.dispatcher.send(task, Some(Span::current())).await {
+            self.terminate_with_error(ctx, err);
+        }
+    }
+}
+
+#[async_trait]
+impl Handler<TaskResult<CountOutput, CountError>> for AnalyticsOrchestrator {
+    type Result = ();
+
+    async fn handle(
+        &mut self,
+        message: TaskResult<CountOutput, CountError>,
+        ctx: &ComponentContext<Self>,
+    ) {
+        match message.into_inner() {
+            Ok(output) => self.send_result(AnalyticsOutput::Count(output)),
+            Err(err) => self.terminate_with_error(ctx, err),
+        }
+    }
+}
+
+#[async_trait]
+impl Handler<TaskResult<AggregateOutput, AggregateError>> for AnalyticsOrchestrator {
+    type Result = ();
+
+    async fn handle(
+        &mut self,
+        message: TaskResult<AggregateOutput, AggregateError>,
+        ctx: &ComponentContext<Self>,
+    ) {
+        match message.into_inner() {
+            Ok(output) => self.send_result(AnalyticsOutput::Aggregate(output)),
+            Err(err) => self.terminate_with_error(ctx, err),
+        }
+    }
+}
+
+#[async_trait]
+impl Handler<TaskResult<FacetOutput, FacetError>> for AnalyticsOrchestrator {
+    type Result = ();
+
+    async fn handle(
+        &mut self,
+        message: TaskResult<FacetOutput, FacetError>,
+        ctx: &ComponentContext<Self>,
+    ) {
+        match message.into_inner() {
+            Ok(output) => self.send_result(AnalyticsOutput::Facet(output)),
+            Err(err) => self.terminate_with_error(ctx, err),
+        }
+    }
+}
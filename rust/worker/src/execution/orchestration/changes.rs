@@ -0,0 +1,299 @@
+use chroma_blockstore::provider::BlockfileProvider;
+use chroma_error::{ChromaError, ErrorCodes};
+use thiserror::Error;
+use tokio::sync::oneshot::{self, error::RecvError, Sender};
+use tonic::async_trait;
+use tracing::Span;
+
+use crate::{
+    execution::{
+        dispatcher::Dispatcher,
+        operator::{wrap_with_priority, TaskError, TaskPriority, TaskResult},
+        operators::{
+            changes::{ChangesError, ChangesInput, ChangesOperator, ChangesOutput},
+            fetch_log::{FetchLogError, FetchLogInput, FetchLogOperator, FetchLogOutput},
+            fetch_segment::{FetchSegmentError, FetchSegmentOperator, FetchSegmentOutput},
+            projection::ProjectionDefaults,
+        },
+        orchestration::common::terminate_with_error,
+    },
+    system::{ChannelError, Component, ComponentContext, ComponentHandle, Handler, System},
+};
+
+#[derive(Error, Debug)]
+pub enum ChangesOrchestratorError {
+    #[error("Error sending message through channel: {0}")]
+    Channel(#[from] ChannelError),
+    #[error("Error running Fetch Log Operator: {0}")]
+    FetchLog(#[from] FetchLogError),
+    #[error("Error running Fetch Segment Operator: {0}")]
+    FetchSegment(#[from] FetchSegmentError),
+    #[error("Error running Changes Operator: {0}")]
+    Changes(#[from] ChangesError),
+    #[error("Task aborted: {0}")]
+    Aborted(String),
+    #[error("Panic running task: {0}")]
+    Panic(String),
+    #[error("Error receiving final result: {0}")]
+    Result(#[from] RecvError),
+}
+
+impl ChromaError for ChangesOrchestratorError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            ChangesOrchestratorError::Channel(e) => e.code(),
+            ChangesOrchestratorError::FetchLog(e) => e.code(),
+            ChangesOrchestratorError::FetchSegment(e) => e.code(),
+            ChangesOrchestratorError::Changes(e) => e.code(),
+            ChangesOrchestratorError::Aborted(_) => ErrorCodes::Aborted,
+            ChangesOrchestratorError::Panic(_) => ErrorCodes::Aborted,
+            ChangesOrchestratorError::Result(_) => ErrorCodes::Internal,
+        }
+    }
+}
+
+impl<E> From<TaskError<E>> for ChangesOrchestratorError
+where
+    E: Into<ChangesOrchestratorError>,
+{
+    fn from(value: TaskError<E>) -> Self {
+        match value {
+            TaskError::Panic(e) => ChangesOrchestratorError::Panic(e.unwrap_or_default()),
+            TaskError::TaskFailed(e) => e.into(),
+            TaskError::Aborted(e) => ChangesOrchestratorError::Aborted(e),
+        }
+    }
+}
+
+type ChangesResult = Result<ChangesOutput, ChangesOrchestratorError>;
+
+/// The `ChangesOrchestrator` materializes the changes that the logs of a collection make to
+/// its records from a log offset on, so that downstream systems can mirror the collection
+///
+/// # Pipeline
+/// ```text
+///                       ┌────────────┐
+///                       │            │
+///           ┌───────────┤  on_start  ├────────────────┐
+///           │           │            │                │
+///           │           └────────────┘                │
+///           │                                         │
+///           ▼                                         ▼
+///  ┌────────────────────┐            ┌────────────────────────┐
+///  │                    │            │                        │
+///  │  FetchLogOperator  │            │  FetchSegmentOperator  │
+///  │                    │            │                        │
+///  └────────┬───────────┘            └────────────────┬───────┘
+///           │                                         │
+///           │                                         │
+///           │     ┌──────────────────────────────┐    │
+///           │     │                              │    │
+///           └────►│  try_start_changes_operator  │◄───┘
+///                 │                              │
+///                 └────────────┬─────────────────┘
+///                              │
+///                              ▼
+///                    ┌───────────────────┐
+///                    │                   │
+///                    │  ChangesOperator  │
+///                    │                   │
+///                    └─────────┬─────────┘
+///                              │
+///                              ▼
+///                    ┌───────────────────┐
+///                    │                   │
+///                    │  result_channel   │
+///                    │                   │
+///                    └───────────────────┘
+/// ```
+#[derive(Debug)]
+pub struct ChangesOrchestrator {
+    // Orchestrator parameters
+    blockfile_provider: BlockfileProvider,
+    dispatcher: ComponentHandle<Dispatcher>,
+    queue: usize,
+
+    // Fetch logs and segments
+    fetch_log: FetchLogOperator,
+    fetch_segment: FetchSegmentOperator,
+
+    // Fetched logs and segments
+    fetch_log_output: Option<FetchLogOutput>,
+    fetch_segment_output: Option<FetchSegmentOutput>,
+
+    // Materialize the changes
+    changes: ChangesOperator,
+
+    // Result channel
+    result_channel: Option<Sender<ChangesResult>>,
+}
+
+impl ChangesOrchestrator {
+    pub fn new(
+        blockfile_provider: BlockfileProvider,
+        dispatcher: ComponentHandle<Dispatcher>,
+        queue: usize,
+        fetch_log: FetchLogOperator,
+        fetch_segment: FetchSegmentOperator,
+        changes: ChangesOperator,
+    ) -> Self {
+        Self {
+            blockfile_provider,
+            dispatcher,
+            queue,
+            fetch_log,
+            fetch_segment,
+            fetch_log_output: None,
+            fetch_segment_output: None,
+            changes,
+            result_channel: None,
+        }
+    }
+
+    pub async fn run(mut self, system: System) -> ChangesResult {
+        let (tx, rx) = oneshot::channel();
+        self.result_channel = Some(tx);
+        let mut handle = system.start_component(self);
+        let result = rx.await;
+        handle.stop();
+        result?
+    }
+
+    fn terminate_with_error<E>(&mut self, ctx: &ComponentContext<Self>, err: E)
+    where
+        E: Into<ChangesOrchestratorError>,
+    {
+        let changes_err = err.into();
+        tracing::error!("Error running orchestrator: {}", &changes_err);
+        terminate_with_error(self.result_channel.take(), changes_err, ctx);
+    }
+
+    async fn try_start_changes_operator(&mut self, ctx: &ComponentContext<Self>) {
+        let (logs, segments) = match (
+            self.fetch_log_output.take(),
+            self.fetch_segment_output.take(),
+        ) {
+            (Some(logs), Some(segments)) => (logs, segments),
+            (logs, segments) => {
+                self.fetch_log_output = logs;
+                self.fetch_segment_output = segments;
+                return;
+            }
+        };
+        let task = wrap_with_priority(
+            Box::new(self.changes.clone()),
+            ChangesInput {
+                logs,
+                blockfile_provider: self.blockfile_provider.clone(),
+                record_segment: segments.record_segment,
+                defaults: ProjectionDefaults::from(&segments.collection),
+            },
+            ctx.receiver(),
+            TaskPriority::Batch,
+        );
+        if let Err(err) = self.dispatcher.send(task, Some(Span::current())).await {
+            self.terminate_with_error(ctx, err);
+        }
+    }
+}
+
+#[async_trait]
+impl Component for ChangesOrchestrator {
+    fn get_name() -> &'static str {
+        "Changes Orchestrator"
+    }
+
+    fn queue_size(&self) -> usize {
+        self.queue
+    }
+
+    async fn on_start(&mut self, ctx: &ComponentContext<Self>) {
+        let log_task = wrap_with_priority(
+            Box::new(self.fetch_log.clone()),
+            FetchLogInput::default(),
+            ctx.receiver(),
+            TaskPriority::Batch,
+        );
+        let segment_task = wrap_with_priority(
+            Box::new(self.fetch_segment.clone()),
+            (),
+            ctx.receiver(),
+            TaskPriority::Batch,
+        );
+        if let Err(err) = self.dispatcher.send(log_task, Some(Span::current())).await {
+            self.terminate_with_error(ctx, err);
+        } else if let Err(err) = self
+            .dispatcher
+            .send(segment_task, Some(Span::current()))
+            .await
+        {
+            self.terminate_with_error(ctx, err);
+        }
+    }
+}
+
+#[async_trait]
+impl Handler<TaskResult<FetchLogOutput, FetchLogError>> for ChangesOrchestrator {
+    type Result = ();
+
+    async fn handle(
+        &mut self,
+        message: TaskResult<FetchLogOutput, FetchLogError>,
+        ctx: &ComponentContext<Self>,
+    ) {
+        let output = match message.into_inner() {
+            Ok(output) => output,
+            Err(err) => {
+                self.terminate_with_error(ctx, err);
+                return;
+            }
+        };
+        self.fetch_log_output = Some(output);
+        self.try_start_changes_operator(ctx).await;
+    }
+}
+
+#[async_trait]
+impl Handler<TaskResult<FetchSegmentOutput, FetchSegmentError>> for ChangesOrchestrator {
+    type Result = ();
+
+    async fn handle(
+        &mut self,
+        message: TaskResult<FetchSegmentOutput, FetchSegmentError>,
+        ctx: &ComponentContext<Self>,
+    ) {
+        let output = match message.into_inner() {
+            Ok(output) => output,
+            Err(err) => {
+                self.terminate_with_error(ctx, err);
+                return;
+            }
+        };
+        self.fetch_segment_output = Some(output);
+        self.try_start_changes_operator(ctx).await;
+    }
+}
+
+#[async_trait]
+impl Handler<TaskResult<ChangesOutput, ChangesError>> for ChangesOrchestrator {
+    type Result = ();
+
+    async fn handle(
+        &mut self,
+        message: TaskResult<ChangesOutput, ChangesError>,
+        ctx: &ComponentContext<Self>,
+    ) {
+        let output = match message.into_inner() {
+            Ok(output) => output,
+            Err(err) => {
+                self.terminate_with_error(ctx, err);
+                return;
+            }
+        };
+        if let Some(chan) = self.result_channel.take() {
+            if chan.send(Ok(output)).is_err() {
+                tracing::error!("Error sending final result");
+            };
+        }
+    }
+}
@@ -0,0 +1,332 @@
+use chroma_blockstore::provider::BlockfileProvider;
+use chroma_error::{ChromaError, ErrorCodes};
+use chroma_index::hnsw_provider::HnswIndexProvider;
+use thiserror::Error;
+use tokio::sync::oneshot::{self, error::RecvError, Sender};
+use tonic::async_trait;
+use tracing::Span;
+
+use crate::{
+    execution::{
+        dispatcher::Dispatcher,
+        operator::{wrap_with_priority, TaskError, TaskPriority, TaskResult},
+        operators::{
+            check_integrity::{
+                CheckIntegrityError, CheckIntegrityInput, CheckIntegrityOperator,
+                CheckIntegrityOutput, IntegrityCheck,
+            },
+            fetch_segment::{FetchSegmentError, FetchSegmentOperator, FetchSegmentOutput},
+            rebuild_segment::{
+                RebuildSegmentError, RebuildSegmentInput, RebuildSegmentOperator,
+                RebuildSegmentOutput,
+            },
+        },
+        orchestration::common::terminate_with_error,
+    },
+    sysdb::sysdb::SysDb,
+    system::{ChannelError, Component, ComponentContext, ComponentHandle, Handler, System},
+};
+
+#[derive(Error, Debug)]
+pub enum CheckIntegrityOrchestratorError {
+    #[error("Error sending message through channel: {0}")]
+    Channel(#[from] ChannelError),
+    #[error("Error running Check Integrity Operator: {0}")]
+    CheckIntegrity(#[from] CheckIntegrityError),
+    #[error("Error running Fetch Segment Operator: {0}")]
+    FetchSegment(#[from] FetchSegmentError),
+    #[error("Task aborted: {0}")]
+    Aborted(String),
+    #[error("Panic running task: {0}")]
+    Panic(String),
+    #[error("Error running Rebuild Segment Operator: {0}")]
+    RebuildSegment(#[from] RebuildSegmentError),
+    #[error("Error receiving final result: {0}")]
+    Result(#[from] RecvError),
+}
+
+impl ChromaError for CheckIntegrityOrchestratorError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            CheckIntegrityOrchestratorError::Channel(e) => e.code(),
+            CheckIntegrityOrchestratorError::CheckIntegrity(e) => e.code(),
+            CheckIntegrityOrchestratorError::FetchSegment(e) => e.code(),
+            CheckIntegrityOrchestratorError::Aborted(_) => ErrorCodes::Aborted,
+            CheckIntegrityOrchestratorError::Panic(_) => ErrorCodes::Aborted,
+            CheckIntegrityOrchestratorError::RebuildSegment(e) => e.code(),
+            CheckIntegrityOrchestratorError::Result(_) => ErrorCodes::Internal,
+        }
+    }
+}
+
+impl<E> From<TaskError<E>> for CheckIntegrityOrchestratorError
+where
+    E: Into<CheckIntegrityOrchestratorError>,
+{
+    fn from(value: TaskError<E>) -> Self {
+        match value {
+            TaskError::Panic(e) => CheckIntegrityOrchestratorError::Panic(e.unwrap_or_default()),
+            TaskError::TaskFailed(e) => e.into(),
+            TaskError::Aborted(e) => CheckIntegrityOrchestratorError::Aborted(e),
+        }
+    }
+}
+
+/// The result of the consistency checks of a collection, together with the segments that
+/// are rebuilt to repair them
+#[derive(Debug)]
+pub struct CheckIntegrityReport {
+    pub check: CheckIntegrityOutput,
+    pub rebuild: Option<RebuildSegmentOutput>,
+}
+
+type CheckIntegrityResult = Result<CheckIntegrityReport, CheckIntegrityOrchestratorError>;
+
+/// The `CheckIntegrityOrchestrator` runs the consistency checks of a collection, and with
+/// repair rebuilds the segments that fail them from the record segment. See
+/// `CheckIntegrityOperator` for the checks
+///
+/// # Pipeline
+/// ```text
+///  ┌────────────┐
+///  │            │
+///  │  on_start  │
+///  │            │
+///  └─────┬──────┘
+///        │
+///        ▼
+///  ┌────────────────────────┐
+///  │                        │
+///  │  FetchSegmentOperator  │
+///  │                        │
+///  └─────┬──────────────────┘
+///        │
+///        ▼
+///  ┌──────────────────────────┐
+///  │                          │
+///  │  CheckIntegrityOperator  │
+///  │                          │
+///  └─────┬─────────────┬──────┘
+///        │             │
+///        │             ▼ repair and any repairable check fails
+///        │     ┌──────────────────────────┐
+///        │     │                          │
+///        │     │  RebuildSegmentOperator  │
+///        │     │                          │
+///        │     └─────┬────────────────────┘
+///        │           │
+///        ▼           ▼
+///  ┌──────────────────────┐
+///  │                      │
+///  │    result_channel    │
+///  │                      │
+///  └──────────────────────┘
+/// ```
+#[derive(Debug)]
+pub struct CheckIntegrityOrchestrator {
+    // Orchestrator parameters
+    blockfile_provider: BlockfileProvider,
+    dispatcher: ComponentHandle<Dispatcher>,
+    hnsw_provider: HnswIndexProvider,
+    queue: usize,
+    sysdb: Box<SysDb>,
+
+    // Fetch segments
+    fetch_segment: FetchSegmentOperator,
+    fetched_segments: Option<FetchSegmentOutput>,
+
+    // Check integrity
+    repair: bool,
+    rebuild_batch_size: usize,
+    check_output: Option<CheckIntegrityOutput>,
+
+    // Result channel
+    result_channel: Option<Sender<CheckIntegrityResult>>,
+}
+
+impl CheckIntegrityOrchestrator {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        blockfile_provider: BlockfileProvider,
+        dispatcher: ComponentHandle<Dispatcher>,
+        hnsw_provider: HnswIndexProvider,
+        queue: usize,
+        sysdb: Box<SysDb>,
+        fetch_segment: FetchSegmentOperator,
+        repair: bool,
+        rebuild_batch_size: usize,
+    ) -> Self {
+        Self {
+            blockfile_provider,
+            dispatcher,
+            hnsw_provider,
+            queue,
+            sysdb,
+            fetch_segment,
+            fetched_segments: None,
+            repair,
+            rebuild_batch_size,
+            check_output: None,
+            result_channel: None,
+        }
+    }
+
+    pub async fn run(mut self, system: System) -> CheckIntegrityResult {
+        let (tx, rx) = oneshot::channel();
+        self.result_channel = Some(tx);
+        let mut handle = system.start_component(self);
+        let result = rx.await;
+        handle.stop();
+        result?
+    }
+
+    fn terminate_with_error<E>(&mut self, ctx: &ComponentContext<Self>, err: E)
+    where
+        E: Into<CheckIntegrityOrchestratorError>,
+    {
+        let check_integrity_err = err.into();
+        tracing::error!("Error running orchestrator: {}", &check_integrity_err);
+        terminate_with_error(self.result_channel.take(), check_integrity_err, ctx);
+    }
+
+    fn send_report(&mut self, rebuild: Option<RebuildSegmentOutput>) {
+        let check = self
+            .check_output
+            .take()
+            .expect("Check output should be set before the report is sent");
+        if let Some(chan) = self.result_channel.take() {
+            if chan
+                .send(Ok(CheckIntegrityReport { check, rebuild }))
+                .is_err()
+            {
+                tracing::error!("Error sending final result");
+            };
+        }
+    }
+}
+
+#[async_trait]
+impl Component for CheckIntegrityOrchestrator {
+    fn get_name() -> &'static str {
+        "Check Integrity Orchestrator"
+    }
+
+    fn queue_size(&self) -> usize {
+        self.queue
+    }
+
+    async fn on_start(&mut self, ctx: &ComponentContext<Self>) {
+        let task = wrap_with_priority(
+            Box::new(self.fetch_segment.clone()),
+            (),
+            ctx.receiver(),
+            TaskPriority::Batch,
+        );
+        if let Err(err) = self.dispatcher.send(task, Some(Span::current())).await {
+            self.terminate_with_error(ctx, err);
+        }
+    }
+}
+
+#[async_trait]
+impl Handler<TaskResult<FetchSegmentOutput, FetchSegmentError>> for CheckIntegrityOrchestrator {
+    type Result = ();
+
+    async fn handle(
+        &mut self,
+        message: TaskResult<FetchSegmentOutput, FetchSegmentError>,
+        ctx: &ComponentContext<Self>,
+    ) {
+        let output = match message.into_inner() {
+            Ok(output) => output,
+            Err(err) => {
+                self.terminate_with_error(ctx, err);
+                return;
+            }
+        };
+        self.fetched_segments = Some(output.clone());
+        let task = wrap_with_priority(
+            Box::new(CheckIntegrityOperator {}),
+            CheckIntegrityInput {
+                blockfile_provider: self.blockfile_provider.clone(),
+                hnsw_provider: self.hnsw_provider.clone(),
+                segments: output,
+            },
+            ctx.receiver(),
+            TaskPriority::Batch,
+        );
+        if let Err(err) = self.dispatcher.send(task, Some(Span::current())).await {
+            self.terminate_with_error(ctx, err);
+        }
+    }
+}
+
+#[async_trait]
+impl Handler<TaskResult<CheckIntegrityOutput, CheckIntegrityError>> for CheckIntegrityOrchestrator {
+    type Result = ();
+
+    async fn handle(
+        &mut self,
+        message: TaskResult<CheckIntegrityOutput, CheckIntegrityError>,
+        ctx: &ComponentContext<Self>,
+    ) {
+        let output = match message.into_inner() {
+            Ok(output) => output,
+            Err(err) => {
+                self.terminate_with_error(ctx, err);
+                return;
+            }
+        };
+        // The record segment is the source of truth, so only the segments derived from it
+        // can be repaired
+        let rebuild_segment = RebuildSegmentOperator {
+            rebuild_metadata: output.failed(IntegrityCheck::MetadataAgreement),
+            rebuild_vector: output.failed(IntegrityCheck::VectorIndexCoverage),
+            batch_size: self.rebuild_batch_size,
+        };
+        self.check_output = Some(output);
+        if !self.repair || !(rebuild_segment.rebuild_metadata || rebuild_segment.rebuild_vector) {
+            self.send_report(None);
+            return;
+        }
+
+        let segments = self
+            .fetched_segments
+            .take()
+            .expect("Segments should be fetched before they are checked");
+        let task = wrap_with_priority(
+            Box::new(rebuild_segment),
+            RebuildSegmentInput {
+                blockfile_provider: self.blockfile_provider.clone(),
+                hnsw_provider: self.hnsw_provider.clone(),
+                sysdb: self.sysdb.clone(),
+                segments,
+            },
+            ctx.receiver(),
+            TaskPriority::Batch,
+        );
+        if let Err(err) = self.dispatcher.send(task, Some(Span::current())).await {
+            self.terminate_with_error(ctx, err);
+        }
+    }
+}
+
+#[async_trait]
+impl Handler<TaskResult<RebuildSegmentOutput, RebuildSegmentError>> for CheckIntegrityOrchestrator {
+    type Result = ();
+
+    async fn handle(
+        &mut self,
+        message: TaskResult<RebuildSegmentOutput, RebuildSegmentError>,
+        ctx: &ComponentContext<Self>,
+    ) {
+        let output = match message.into_inner() {
+            Ok(output) => output,
+            Err(err) => {
+                self.terminate_with_error(ctx, err);
+                return;
+            }
+        };
+        self.send_report(Some(output));
+    }
+}
@@ -1,7 +1,15 @@
-use super::super::operator::wrap;
+use super::super::operator::wrap_with_priority;
 use crate::compactor::CompactionJob;
+use crate::compactor::CompactionPauses;
 use crate::execution::dispatcher::Dispatcher;
+use crate::execution::operator::TaskPriority;
 use crate::execution::operator::TaskResult;
+use crate::execution::operators::fetch_embeddings::has_embedding_references;
+use crate::execution::operators::fetch_embeddings::ExternalEmbeddingStorage;
+use crate::execution::operators::fetch_embeddings::FetchEmbeddingsError;
+use crate::execution::operators::fetch_embeddings::FetchEmbeddingsInput;
+use crate::execution::operators::fetch_embeddings::FetchEmbeddingsOperator;
+use crate::execution::operators::fetch_embeddings::FetchEmbeddingsOutput;
 use crate::execution::operators::flush_s3::FlushS3Input;
 use crate::execution::operators::flush_s3::FlushS3Operator;
 use crate::execution::operators::flush_s3::FlushS3Output;
@@ -16,6 +24,10 @@ use crate::execution::operators::register::RegisterError;
 use crate::execution::operators::register::RegisterInput;
 use crate::execution::operators::register::RegisterOperator;
 use crate::execution::operators::register::RegisterOutput;
+use crate::execution::operators::retrain_quantization::RetrainQuantizationError;
+use crate::execution::operators::retrain_quantization::RetrainQuantizationInput;
+use crate::execution::operators::retrain_quantization::RetrainQuantizationOperator;
+use crate::execution::operators::retrain_quantization::RetrainQuantizationOutput;
 use crate::execution::operators::write_segments::WriteSegmentsInput;
 use crate::execution::operators::write_segments::WriteSegmentsOperator;
 use crate::execution::operators::write_segments::WriteSegmentsOperatorError;
@@ -24,11 +36,13 @@ use crate::execution::orchestration::common::terminate_with_error;
 use crate::log::log::Log;
 use crate::log::log::PullLogsError;
 use crate::segment::distributed_hnsw_segment::DistributedHNSWSegmentWriter;
+use crate::segment::materialized_filter::materialized_filters_from_collection;
 use crate::segment::metadata_segment::MetadataSegmentWriter;
 use crate::segment::record_segment::RecordSegmentReader;
 use crate::segment::record_segment::RecordSegmentWriter;
 use crate::sysdb::sysdb::GetCollectionsError;
 use crate::sysdb::sysdb::GetSegmentsError;
+use crate::sysdb::sysdb::GetTenantError;
 use crate::sysdb::sysdb::SysDb;
 use crate::system::Component;
 use crate::system::ComponentHandle;
@@ -40,11 +54,13 @@ use chroma_blockstore::provider::BlockfileProvider;
 use chroma_error::ChromaError;
 use chroma_error::ErrorCodes;
 use chroma_index::hnsw_provider::HnswIndexProvider;
+use chroma_storage::PutOptions;
 use chroma_types::Chunk;
 use chroma_types::{CollectionUuid, LogRecord, Segment, SegmentFlushInfo, SegmentType};
 use core::panic;
 use std::sync::atomic::AtomicU32;
 use std::sync::Arc;
+use std::time::Duration;
 use std::time::SystemTime;
 use std::time::UNIX_EPOCH;
 use thiserror::Error;
@@ -58,11 +74,11 @@ expect to encounter for a given query plan. This is a bit more rigid, but it's a
 understand. We can always add more abstraction later if we need it.
 ```plaintext
 
-                                   ┌───► Write─────-------┐
-                                   │                      │
-  Pending ─► PullLogs ─► Partition │                      ├─► Flush ─► Finished
-                                   │                      │
-                                   └───► Write ───────────┘
+                                                     ┌───► Write ───┐
+                                                     │              │
+  Pending ─► PullLogs ─┬─► FetchEmbeddings ─┬─► Partition           ├─► Flush ─┬─► RetrainQuantization ─┬─► Register ─► Finished
+                       └────────────────────┘        │              │          └────────────────────────┘
+                                                     └───► Write ───┘
 
 ```
 */
@@ -70,9 +86,11 @@ understand. We can always add more abstraction later if we need it.
 enum ExecutionState {
     Pending,
     PullLogs,
+    FetchEmbeddings,
     Partition,
     Write,
     Flush,
+    RetrainQuantization,
     Register,
 }
 
@@ -89,9 +107,18 @@ pub struct CompactOrchestrator {
     sysdb: Box<SysDb>,
     blockfile_provider: BlockfileProvider,
     hnsw_index_provider: HnswIndexProvider,
+    // The external bucket that the embeddings referenced by the logs are read from
+    external_embeddings: Option<ExternalEmbeddingStorage>,
     // State we hold across the execution
     pulled_log_offset: Option<i64>,
     record_segment: Option<Segment>,
+    hnsw_segment: Option<Segment>,
+    dimension: Option<usize>,
+    // Whether the embeddings written by the compaction have drifted away from the
+    // quantization of the hnsw segment, which is then retrained before it is registered
+    retrain_quantization: bool,
+    // The flushed segments, while the quantization is retrained
+    segment_flush_info: Option<Arc<[SegmentFlushInfo]>>,
     // Dispatcher
     dispatcher: ComponentHandle<Dispatcher>,
     // number of write segments tasks
@@ -103,6 +130,8 @@ pub struct CompactOrchestrator {
     curr_max_offset_id: Arc<AtomicU32>,
     max_compaction_size: usize,
     max_partition_size: usize,
+    idempotency_key_ttl: Duration,
+    pauses: CompactionPauses,
 }
 
 #[derive(Error, Debug)]
@@ -127,6 +156,8 @@ enum GetSegmentWritersError {
     GetCollectionError(#[from] GetCollectionsError),
     #[error("No hnsw segment found for collection")]
     NoHnswSegmentFound,
+    #[error("Error getting tenant")]
+    GetTenantError(#[from] GetTenantError),
 }
 
 impl ChromaError for GetSegmentWritersError {
@@ -141,11 +172,16 @@ enum CompactionError {
     SystemTimeError(#[from] std::time::SystemTimeError),
     #[error("Result channel dropped")]
     ResultChannelDropped,
+    #[error("Compaction of collection {0} is paused")]
+    Paused(CollectionUuid),
 }
 
 impl ChromaError for CompactionError {
     fn code(&self) -> ErrorCodes {
-        ErrorCodes::Internal
+        match self {
+            CompactionError::Paused(_) => ErrorCodes::Aborted,
+            _ => ErrorCodes::Internal,
+        }
     }
 }
 
@@ -169,6 +205,7 @@ impl CompactOrchestrator {
         sysdb: Box<SysDb>,
         blockfile_provider: BlockfileProvider,
         hnsw_index_provider: HnswIndexProvider,
+        external_embeddings: Option<ExternalEmbeddingStorage>,
         dispatcher: ComponentHandle<Dispatcher>,
         result_channel: Option<
             tokio::sync::oneshot::Sender<Result<CompactionResponse, Box<dyn ChromaError>>>,
@@ -177,6 +214,8 @@ impl CompactOrchestrator {
         curr_max_offset_id: Arc<AtomicU32>,
         max_compaction_size: usize,
         max_partition_size: usize,
+        idempotency_key_ttl: Duration,
+        pauses: CompactionPauses,
     ) -> Self {
         CompactOrchestrator {
             id: Uuid::new_v4(),
@@ -188,14 +227,21 @@ impl CompactOrchestrator {
             sysdb,
             blockfile_provider,
             hnsw_index_provider,
+            external_embeddings,
             pulled_log_offset: None,
             dispatcher,
             num_write_tasks: 0,
             result_channel,
             record_segment,
+            hnsw_segment: None,
+            dimension: None,
+            retrain_quantization: false,
+            segment_flush_info: None,
             curr_max_offset_id,
             max_compaction_size,
             max_partition_size,
+            idempotency_key_ttl,
+            pauses,
         }
     }
 
@@ -229,7 +275,7 @@ impl CompactOrchestrator {
             Some(self.max_compaction_size as i32),
             Some(end_timestamp),
         );
-        let task = wrap(operator, input, self_address);
+        let task = wrap_with_priority(operator, input, self_address, TaskPriority::Batch);
         match self.dispatcher.send(task, Some(Span::current())).await {
             Ok(_) => (),
             Err(e) => {
@@ -242,6 +288,32 @@ impl CompactOrchestrator {
         }
     }
 
+    async fn fetch_embeddings(
+        &mut self,
+        logs: Chunk<LogRecord>,
+        self_address: Box<
+            dyn ReceiverForMessage<TaskResult<FetchEmbeddingsOutput, FetchEmbeddingsError>>,
+        >,
+    ) {
+        self.state = ExecutionState::FetchEmbeddings;
+        let operator = Box::new(FetchEmbeddingsOperator {});
+        let input = FetchEmbeddingsInput {
+            logs,
+            external_storage: self.external_embeddings.clone(),
+        };
+        let task = wrap_with_priority(operator, input, self_address, TaskPriority::Batch);
+        match self.dispatcher.send(task, Some(Span::current())).await {
+            Ok(_) => (),
+            Err(e) => {
+                tracing::error!("Error dispatching fetch embeddings for compaction {:?}", e);
+                panic!(
+                    "Invariant violation. Somehow the dispatcher receiver is dropped. Error: {:?}",
+                    e
+                )
+            }
+        }
+    }
+
     async fn partition(
         &mut self,
         records: Chunk<LogRecord>,
@@ -252,7 +324,7 @@ impl CompactOrchestrator {
         tracing::info!("Sending N Records: {:?}", records.len());
         println!("Sending N Records: {:?}", records.len());
         let input = PartitionInput::new(records, self.max_partition_size);
-        let task = wrap(operator, input, self_address);
+        let task = wrap_with_priority(operator, input, self_address, TaskPriority::Batch);
         match self.dispatcher.send(task, Some(Span::current())).await {
             Ok(_) => (),
             Err(e) => {
@@ -268,12 +340,12 @@ impl CompactOrchestrator {
     async fn write(
         &mut self,
         partitions: Vec<Chunk<LogRecord>>,
-        self_address: Box<
-            dyn ReceiverForMessage<TaskResult<WriteSegmentsOutput, WriteSegmentsOperatorError>>,
-        >,
         ctx: &crate::system::ComponentContext<CompactOrchestrator>,
     ) {
         self.state = ExecutionState::Write;
+        if self.abort_if_paused(ctx) {
+            return;
+        }
 
         let writer_res = self.get_segment_writers().await;
         let (record_segment_writer, hnsw_segment_writer, metadata_segment_writer) = match writer_res
@@ -287,13 +359,13 @@ impl CompactOrchestrator {
         };
 
         self.num_write_tasks = partitions.len() as i32;
-        for parition in partitions.iter() {
+        for partition in partitions.iter() {
             let operator = WriteSegmentsOperator::new();
             let input = WriteSegmentsInput::new(
                 record_segment_writer.clone(),
                 hnsw_segment_writer.clone(),
                 metadata_segment_writer.clone(),
-                parition.clone(),
+                partition.clone(),
                 self.blockfile_provider.clone(),
                 self.record_segment
                     .as_ref()
@@ -301,19 +373,42 @@ impl CompactOrchestrator {
                     .clone(),
                 self.curr_max_offset_id.clone(),
             );
-            let task = wrap(operator, input, self_address.clone());
+            let task = wrap_with_priority(operator, input, ctx.receiver(), TaskPriority::Batch);
             match self.dispatcher.send(task, Some(Span::current())).await {
                 Ok(_) => (),
                 Err(e) => {
                     tracing::error!("Error dispatching writers for compaction {:?}", e);
                     panic!(
                         "Invariant violation. Somehow the dispatcher receiver is dropped. Error: {:?}",
-                        e)
+                        e
+                    )
                 }
             }
         }
     }
 
+    // A paused compaction is aborted rather than waiting to be resumed, so that it does not
+    // hold up the other compactions of its batch. Nothing is registered before the flush, so
+    // the collection is compacted from its last compaction by a later batch once it is resumed
+    fn abort_if_paused(
+        &mut self,
+        ctx: &crate::system::ComponentContext<CompactOrchestrator>,
+    ) -> bool {
+        if !self.pauses.is_paused(self.collection_id) {
+            return false;
+        }
+        tracing::info!(
+            "Aborting compaction of paused collection {}",
+            self.collection_id
+        );
+        terminate_with_error(
+            self.result_channel.take(),
+            Box::new(CompactionError::Paused(self.collection_id)),
+            ctx,
+        );
+        true
+    }
+
     async fn flush_s3(
         &mut self,
         record_segment_writer: RecordSegmentWriter,
@@ -325,12 +420,13 @@ impl CompactOrchestrator {
 
         let operator = FlushS3Operator::new();
         let input = FlushS3Input::new(
+            self.collection_id,
             record_segment_writer,
             hnsw_segment_writer,
             metadata_segment_writer,
         );
 
-        let task = wrap(operator, input, self_address);
+        let task = wrap_with_priority(operator, input, self_address, TaskPriority::Batch);
         match self.dispatcher.send(task, Some(Span::current())).await {
             Ok(_) => (),
             Err(e) => {
@@ -343,6 +439,60 @@ impl CompactOrchestrator {
         }
     }
 
+    // The index is rebuilt from the flushed record segment, so that it holds the embeddings
+    // written by this compaction. The files of the drifted index that were flushed are not
+    // registered, and are left to the garbage collector
+    async fn retrain_quantization(
+        &mut self,
+        segment_flush_info: Arc<[SegmentFlushInfo]>,
+        self_address: Box<
+            dyn ReceiverForMessage<TaskResult<RetrainQuantizationOutput, RetrainQuantizationError>>,
+        >,
+    ) {
+        self.state = ExecutionState::RetrainQuantization;
+        let mut record_segment = self
+            .record_segment
+            .clone()
+            .expect("Invariant violation. Record segment is not set.");
+        if let Some(record_segment_flush_info) = segment_flush_info
+            .iter()
+            .find(|flush_info| flush_info.segment_id == record_segment.id)
+        {
+            record_segment.file_path = record_segment_flush_info.file_paths.clone();
+        }
+        let operator = Box::new(RetrainQuantizationOperator {
+            batch_size: self.max_partition_size,
+        });
+        let input = RetrainQuantizationInput {
+            blockfile_provider: self.blockfile_provider.clone(),
+            hnsw_provider: self.hnsw_index_provider.clone(),
+            record_segment,
+            vector_segment: self
+                .hnsw_segment
+                .clone()
+                .expect("Invariant violation. Hnsw segment is not set."),
+            dimension: self
+                .dimension
+                .expect("Invariant violation. Dimension is not set."),
+        };
+        self.segment_flush_info = Some(segment_flush_info);
+
+        let task = wrap_with_priority(operator, input, self_address, TaskPriority::Batch);
+        match self.dispatcher.send(task, Some(Span::current())).await {
+            Ok(_) => (),
+            Err(e) => {
+                tracing::error!(
+                    "Error dispatching retrain quantization for compaction {:?}",
+                    e
+                );
+                panic!(
+                    "Invariant violation. Somehow the dispatcher receiver is dropped. Error: {:?}",
+                    e
+                );
+            }
+        }
+    }
+
     async fn register(
         &mut self,
         log_position: i64,
@@ -361,7 +511,7 @@ impl CompactOrchestrator {
             self.log.clone(),
         );
 
-        let task = wrap(operator, input, self_address);
+        let task = wrap_with_priority(operator, input, self_address, TaskPriority::Batch);
         match self.dispatcher.send(task, Some(Span::current())).await {
             Ok(_) => (),
             Err(e) => {
@@ -390,6 +540,35 @@ impl CompactOrchestrator {
         // This method is for convenience to create the writers in a single place
         // It is not meant to be called multiple times in the same compaction job
 
+        // The files of a tenant with its own KMS key are encrypted with that key, so the
+        // providers of the writers are scoped to the tenant before any writer is created
+        let kms_key_id = match self
+            .sysdb
+            .get_tenant_kms_key_id(self.compaction_job.tenant_id.clone())
+            .await
+        {
+            Ok(kms_key_id) => kms_key_id,
+            Err(e) => {
+                return Err(Box::new(GetSegmentWritersError::GetTenantError(e)));
+            }
+        };
+        if let Some(kms_key_id) = kms_key_id {
+            tracing::info!(
+                "Encrypting files of tenant {} with its kms key",
+                self.compaction_job.tenant_id
+            );
+            let put_options = PutOptions {
+                kms_key_id: Some(kms_key_id),
+            };
+            self.blockfile_provider = self
+                .blockfile_provider
+                .with_put_options(put_options.clone());
+            self.hnsw_index_provider = self
+                .hnsw_index_provider
+                .clone()
+                .with_put_options(put_options);
+        }
+
         let segments = self
             .sysdb
             .get_segments(None, None, None, self.collection_id)
@@ -420,7 +599,7 @@ impl CompactOrchestrator {
         }
         // Create a record segment writer
         let record_segment = record_segment.unwrap();
-        let record_segment_writer =
+        let mut record_segment_writer =
             match RecordSegmentWriter::from_segment(record_segment, &self.blockfile_provider).await
             {
                 Ok(writer) => writer,
@@ -434,6 +613,21 @@ impl CompactOrchestrator {
         match RecordSegmentReader::from_segment(record_segment, &self.blockfile_provider).await {
             Ok(reader) => {
                 self.curr_max_offset_id = reader.get_current_max_offset_id();
+                // Expire the idempotency keys once per compaction, before the partitions are written
+                if let Err(e) = record_segment_writer
+                    .expire_idempotency_keys(&reader, self.idempotency_key_ttl)
+                    .await
+                {
+                    tracing::error!("Error expiring idempotency keys: {:?}", e);
+                    return Err(Box::new(GetSegmentWritersError::RecordSegmentWriterError));
+                }
+                // A segment that was flushed without a content checksum gets one from
+                // its records once, after which it is updated with every compaction. The
+                // checksum is best effort, so the compaction goes on without it if the
+                // records cannot be read, and a later compaction computes it instead
+                if let Err(e) = record_segment_writer.backfill_checksum(&reader).await {
+                    tracing::warn!("Skipping the backfill of the content checksum: {:?}", e);
+                }
             }
             Err(_) => {
                 self.curr_max_offset_id = Arc::new(AtomicU32::new(0));
@@ -452,7 +646,7 @@ impl CompactOrchestrator {
         }
         // Create a record segment writer
         let mt_segment = metadata_segment.unwrap(); // safe to unwrap here.
-        let mt_segment_writer =
+        let mut mt_segment_writer =
             match MetadataSegmentWriter::from_segment(mt_segment, &self.blockfile_provider).await {
                 Ok(writer) => writer,
                 Err(e) => {
@@ -482,6 +676,18 @@ impl CompactOrchestrator {
         };
         let collection = &collection_res[0];
 
+        if let Err(e) = mt_segment_writer
+            .set_materialized_filters(
+                mt_segment,
+                &self.blockfile_provider,
+                materialized_filters_from_collection(collection),
+            )
+            .await
+        {
+            tracing::error!("Error setting materialized filters: {:?}", e);
+            return Err(Box::new(GetSegmentWritersError::MetadataSegmentWriterError));
+        }
+
         let hnsw_segment = segments
             .iter()
             .find(|segment| segment.r#type == SegmentType::HnswDistributed);
@@ -492,6 +698,8 @@ impl CompactOrchestrator {
         let dimension = collection
             .dimension
             .expect("Dimension is required in the compactor");
+        self.hnsw_segment = Some(hnsw_segment.clone());
+        self.dimension = Some(dimension as usize);
 
         let hnsw_segment_writer = match DistributedHNSWSegmentWriter::from_segment(
             hnsw_segment,
@@ -567,7 +775,13 @@ impl Handler<TaskResult<PullLogsOutput, PullLogsError>> for CompactOrchestrator
             Some(record) => {
                 self.pulled_log_offset = Some(record.log_offset);
                 tracing::info!("Pulled Logs Up To Offset: {:?}", self.pulled_log_offset);
-                self.partition(records, ctx.receiver()).await;
+                // The embeddings that the logs reference in the storage are resolved before
+                // they are written to the segments
+                if has_embedding_references(&records) {
+                    self.fetch_embeddings(records, ctx.receiver()).await;
+                } else {
+                    self.partition(records, ctx.receiver()).await;
+                }
             }
             None => {
                 tracing::error!(
@@ -579,6 +793,28 @@ impl Handler<TaskResult<PullLogsOutput, PullLogsError>> for CompactOrchestrator
     }
 }
 
+#[async_trait]
+impl Handler<TaskResult<FetchEmbeddingsOutput, FetchEmbeddingsError>> for CompactOrchestrator {
+    type Result = ();
+
+    async fn handle(
+        &mut self,
+        message: TaskResult<FetchEmbeddingsOutput, FetchEmbeddingsError>,
+        ctx: &crate::system::ComponentContext<CompactOrchestrator>,
+    ) {
+        let message = message.into_inner();
+        let records = match message {
+            Ok(records) => records,
+            Err(e) => {
+                tracing::error!("Error fetching referenced embeddings: {:?}", e);
+                terminate_with_error(self.result_channel.take(), Box::new(e), ctx);
+                return;
+            }
+        };
+        self.partition(records, ctx.receiver()).await;
+    }
+}
+
 #[async_trait]
 impl Handler<TaskResult<PartitionOutput, PartitionError>> for CompactOrchestrator {
     type Result = ();
@@ -597,7 +833,7 @@ impl Handler<TaskResult<PartitionOutput, PartitionError>> for CompactOrchestrato
                 return;
             }
         };
-        self.write(records, ctx.receiver(), ctx).await;
+        self.write(records, ctx).await;
     }
 }
 
@@ -622,7 +858,23 @@ impl Handler<TaskResult<WriteSegmentsOutput, WriteSegmentsOperatorError>> for Co
                 return;
             }
         };
+        if self.abort_if_paused(ctx) {
+            return;
+        }
         if self.num_write_tasks == 0 {
+            // The segments are flushed with the offset of the last pulled log, so that the
+            // logs are skipped if the log service delivers them again
+            if let Some(log_offset) = self.pulled_log_offset {
+                if let Err(e) = output
+                    .record_segment_writer
+                    .set_applied_log_offset(log_offset)
+                    .await
+                {
+                    terminate_with_error(self.result_channel.take(), Box::new(e), ctx);
+                    return;
+                }
+            }
+            self.retrain_quantization = output.hnsw_segment_writer.quantization_drifted();
             self.flush_s3(
                 output.record_segment_writer,
                 output.hnsw_segment_writer,
@@ -646,6 +898,11 @@ impl Handler<TaskResult<FlushS3Output, Box<dyn ChromaError>>> for CompactOrchest
         let message = message.into_inner();
         match message {
             Ok(msg) => {
+                if self.retrain_quantization {
+                    self.retrain_quantization(msg.segment_flush_info, ctx.receiver())
+                        .await;
+                    return;
+                }
                 // Unwrap should be safe here as we are guaranteed to have a value by construction
                 self.register(
                     self.pulled_log_offset.unwrap(),
@@ -662,6 +919,50 @@ impl Handler<TaskResult<FlushS3Output, Box<dyn ChromaError>>> for CompactOrchest
     }
 }
 
+#[async_trait]
+impl Handler<TaskResult<RetrainQuantizationOutput, RetrainQuantizationError>>
+    for CompactOrchestrator
+{
+    type Result = ();
+
+    async fn handle(
+        &mut self,
+        message: TaskResult<RetrainQuantizationOutput, RetrainQuantizationError>,
+        ctx: &crate::system::ComponentContext<CompactOrchestrator>,
+    ) {
+        let message = message.into_inner();
+        let retrained_flush_info = match message {
+            Ok(output) => output.segment_flush_info,
+            Err(e) => {
+                tracing::error!("Error retraining quantization: {:?}", e);
+                terminate_with_error(self.result_channel.take(), Box::new(e), ctx);
+                return;
+            }
+        };
+        // The retrained index replaces the flushed index of the hnsw segment
+        let segment_flush_info = self
+            .segment_flush_info
+            .take()
+            .expect("Invariant violation. Segment flush info is not set.")
+            .iter()
+            .map(|flush_info| {
+                if flush_info.segment_id == retrained_flush_info.segment_id {
+                    retrained_flush_info.clone()
+                } else {
+                    flush_info.clone()
+                }
+            })
+            .collect::<Vec<_>>();
+        // Unwrap should be safe here as we are guaranteed to have a value by construction
+        self.register(
+            self.pulled_log_offset.unwrap(),
+            segment_flush_info.into(),
+            ctx.receiver(),
+        )
+        .await;
+    }
+}
+
 #[async_trait]
 impl Handler<TaskResult<RegisterOutput, RegisterError>> for CompactOrchestrator {
     type Result = ();
@@ -3,16 +3,16 @@ use crate::execution::operator::{wrap, TaskResult};
 use crate::execution::operators::count_records::{
     CountRecordsError, CountRecordsInput, CountRecordsOperator, CountRecordsOutput,
 };
+use crate::execution::operators::fetch_segment::{FetchSegmentOperator, FetchSegmentOutput};
 use crate::execution::operators::pull_log::{PullLogsInput, PullLogsOperator, PullLogsOutput};
 use crate::execution::orchestration::common::terminate_with_error;
 use crate::log::log::PullLogsError;
-use crate::sysdb::sysdb::{GetCollectionsError, GetSegmentsError};
 use crate::system::{Component, ComponentContext, ComponentHandle, Handler};
 use crate::{log::log::Log, sysdb::sysdb::SysDb, system::System};
 use async_trait::async_trait;
 use chroma_blockstore::provider::BlockfileProvider;
 use chroma_error::{ChromaError, ErrorCodes};
-use chroma_types::{Collection, CollectionUuid, Segment, SegmentType, SegmentUuid};
+use chroma_types::{Collection, CollectionUuid, Segment, SegmentUuid};
 use std::time::{SystemTime, UNIX_EPOCH};
 use thiserror::Error;
 use tracing::Span;
@@ -42,34 +42,14 @@ pub(crate) struct CountQueryOrchestrator {
 
 #[derive(Error, Debug)]
 enum CountQueryOrchestratorError {
-    #[error("Blockfile metadata segment with id: {0} not found")]
-    BlockfileMetadataSegmentNotFound(Uuid),
-    #[error("Get segments error: {0}")]
-    GetSegmentsError(#[from] GetSegmentsError),
-    #[error("Record segment not found for collection: {0}")]
-    RecordSegmentNotFound(CollectionUuid),
     #[error("System Time Error")]
     SystemTimeError(#[from] std::time::SystemTimeError),
-    #[error("Collection not found for id: {0}")]
-    CollectionNotFound(CollectionUuid),
-    #[error("Get collection error: {0}")]
-    GetCollectionError(#[from] GetCollectionsError),
-    #[error("Collection version mismatch")]
-    CollectionVersionMismatch,
 }
 
 impl ChromaError for CountQueryOrchestratorError {
     fn code(&self) -> ErrorCodes {
         match self {
-            CountQueryOrchestratorError::BlockfileMetadataSegmentNotFound(_) => {
-                ErrorCodes::NotFound
-            }
-            CountQueryOrchestratorError::GetSegmentsError(e) => e.code(),
-            CountQueryOrchestratorError::RecordSegmentNotFound(_) => ErrorCodes::NotFound,
             CountQueryOrchestratorError::SystemTimeError(_) => ErrorCodes::Internal,
-            CountQueryOrchestratorError::CollectionNotFound(_) => ErrorCodes::NotFound,
-            CountQueryOrchestratorError::GetCollectionError(e) => e.code(),
-            CountQueryOrchestratorError::CollectionVersionMismatch => ErrorCodes::VersionMismatch,
         }
     }
 }
@@ -105,61 +85,32 @@ impl CountQueryOrchestrator {
 
     async fn start(&mut self, ctx: &ComponentContext<Self>) {
         println!("Starting Count Query Orchestrator");
-        // Populate the orchestrator with the initial state - The Record Segment and the Collection
-        let metdata_segment = self
-            .get_metadata_segment_from_id(
-                self.sysdb.clone(),
-                &self.metadata_segment_id,
-                &self.collection_id,
-            )
-            .await;
-
-        let metadata_segment = match metdata_segment {
-            Ok(segment) => segment,
-            Err(e) => {
-                tracing::error!("Error getting metadata segment: {:?}", e);
-                terminate_with_error(self.result_channel.take(), e, ctx);
-                return;
-            }
-        };
-
-        let collection_id = metadata_segment.collection;
-
-        let record_segment = self
-            .get_record_segment_from_collection_id(self.sysdb.clone(), &collection_id)
-            .await;
-
-        let record_segment = match record_segment {
-            Ok(segment) => segment,
-            Err(e) => {
-                tracing::error!("Error getting record segment: {:?}", e);
-                terminate_with_error(self.result_channel.take(), e, ctx);
-                return;
-            }
-        };
-
-        let collection = match self
-            .get_collection_from_id(self.sysdb.clone(), &collection_id, ctx)
-            .await
+        // Pin the collection and its segments at the version of the request, every reader of
+        // the query is created from this snapshot
+        let FetchSegmentOutput {
+            collection,
+            record_segment,
+            ..
+        } = match (FetchSegmentOperator {
+            sysdb: self.sysdb.clone(),
+            collection_uuid: self.collection_id,
+            collection_version: self.collection_version,
+            metadata_uuid: Some(SegmentUuid(self.metadata_segment_id)),
+            record_uuid: None,
+            vector_uuid: None,
+            cache_invalidator: None,
+        })
+        .fetch()
+        .await
         {
-            Ok(collection) => collection,
+            Ok(output) => output,
             Err(e) => {
-                tracing::error!("Error getting collection: {:?}", e);
-                terminate_with_error(self.result_channel.take(), e, ctx);
+                tracing::error!("Error fetching segments: {:?}", e);
+                terminate_with_error(self.result_channel.take(), Box::new(e), ctx);
                 return;
             }
         };
 
-        // If the collection version does not match the request version then we terminate with an error
-        if collection.version as u32 != self.collection_version {
-            terminate_with_error(
-                self.result_channel.take(),
-                Box::new(CountQueryOrchestratorError::CollectionVersionMismatch),
-                ctx,
-            );
-            return;
-        }
-
         self.record_segment = Some(record_segment);
         self.collection = Some(collection);
         self.pull_logs(ctx).await;
@@ -210,101 +161,6 @@ impl CountQueryOrchestrator {
         }
     }
 
-    // shared
-    async fn get_metadata_segment_from_id(
-        &self,
-        mut sysdb: Box<SysDb>,
-        metadata_segment_id: &Uuid,
-        collection_id: &CollectionUuid,
-    ) -> Result<Segment, Box<dyn ChromaError>> {
-        let segments = sysdb
-            .get_segments(
-                Some(SegmentUuid(*metadata_segment_id)),
-                None,
-                None,
-                *collection_id,
-            )
-            .await;
-        let segment = match segments {
-            Ok(segments) => {
-                if segments.is_empty() {
-                    return Err(Box::new(
-                        CountQueryOrchestratorError::BlockfileMetadataSegmentNotFound(
-                            *metadata_segment_id,
-                        ),
-                    ));
-                }
-                segments[0].clone()
-            }
-            Err(e) => {
-                return Err(Box::new(CountQueryOrchestratorError::GetSegmentsError(e)));
-            }
-        };
-
-        if segment.r#type != SegmentType::BlockfileMetadata {
-            return Err(Box::new(
-                CountQueryOrchestratorError::BlockfileMetadataSegmentNotFound(*metadata_segment_id),
-            ));
-        }
-        Ok(segment)
-    }
-
-    // shared
-    async fn get_record_segment_from_collection_id(
-        &self,
-        mut sysdb: Box<SysDb>,
-        collection_id: &CollectionUuid,
-    ) -> Result<Segment, Box<dyn ChromaError>> {
-        let segments = sysdb
-            .get_segments(
-                None,
-                Some(SegmentType::BlockfileRecord.into()),
-                None,
-                *collection_id,
-            )
-            .await;
-
-        match segments {
-            Ok(segments) => {
-                if segments.is_empty() {
-                    return Err(Box::new(
-                        CountQueryOrchestratorError::RecordSegmentNotFound(*collection_id),
-                    ));
-                }
-                // Unwrap is safe as we know at least one segment exists from
-                // the check above
-                Ok(segments.into_iter().next().unwrap())
-            }
-            Err(e) => Err(Box::new(CountQueryOrchestratorError::GetSegmentsError(e))),
-        }
-    }
-
-    // shared
-    async fn get_collection_from_id(
-        &self,
-        mut sysdb: Box<SysDb>,
-        collection_id: &CollectionUuid,
-        _ctx: &ComponentContext<Self>,
-    ) -> Result<Collection, Box<dyn ChromaError>> {
-        let collections = sysdb
-            .get_collections(Some(*collection_id), None, None, None)
-            .await;
-
-        match collections {
-            Ok(collections) => {
-                if collections.is_empty() {
-                    return Err(Box::new(CountQueryOrchestratorError::CollectionNotFound(
-                        *collection_id,
-                    )));
-                }
-                // Unwrap is safe as we know at least one collection exists from
-                // the check above
-                Ok(collections.into_iter().next().unwrap())
-            }
-            Err(e) => Err(Box::new(CountQueryOrchestratorError::GetCollectionError(e))),
-        }
-    }
-
     ///  Run the orchestrator and return the result.
     ///  # Note
     ///  Use this over spawning the component directly. This method will start the component and
@@ -0,0 +1,236 @@
+use chroma_blockstore::provider::BlockfileProvider;
+use chroma_error::{ChromaError, ErrorCodes};
+use chroma_storage::Storage;
+use thiserror::Error;
+use tokio::sync::oneshot::{self, error::RecvError, Sender};
+use tonic::async_trait;
+use tracing::Span;
+
+use crate::{
+    execution::{
+        dispatcher::Dispatcher,
+        operator::{wrap_with_priority, TaskError, TaskPriority, TaskResult},
+        operators::{
+            export_index::{
+                ExportIndexError, ExportIndexInput, ExportIndexOperator, ExportIndexOutput,
+            },
+            fetch_segment::{FetchSegmentError, FetchSegmentOperator, FetchSegmentOutput},
+        },
+        orchestration::common::terminate_with_error,
+    },
+    system::{ChannelError, Component, ComponentContext, ComponentHandle, Handler, System},
+};
+
+#[derive(Error, Debug)]
+pub enum ExportIndexOrchestratorError {
+    #[error("Error sending message through channel: {0}")]
+    Channel(#[from] ChannelError),
+    #[error("Error running Export Index Operator: {0}")]
+    ExportIndex(#[from] ExportIndexError),
+    #[error("Error running Fetch Segment Operator: {0}")]
+    FetchSegment(#[from] FetchSegmentError),
+    #[error("Task aborted: {0}")]
+    Aborted(String),
+    #[error("Panic running task: {0}")]
+    Panic(String),
+    #[error("Error receiving final result: {0}")]
+    Result(#[from] RecvError),
+}
+
+impl ChromaError for ExportIndexOrchestratorError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            ExportIndexOrchestratorError::Channel(e) => e.code(),
+            ExportIndexOrchestratorError::ExportIndex(e) => e.code(),
+            ExportIndexOrchestratorError::FetchSegment(e) => e.code(),
+            ExportIndexOrchestratorError::Aborted(_) => ErrorCodes::Aborted,
+            ExportIndexOrchestratorError::Panic(_) => ErrorCodes::Aborted,
+            ExportIndexOrchestratorError::Result(_) => ErrorCodes::Internal,
+        }
+    }
+}
+
+impl<E> From<TaskError<E>> for ExportIndexOrchestratorError
+where
+    E: Into<ExportIndexOrchestratorError>,
+{
+    fn from(value: TaskError<E>) -> Self {
+        match value {
+            TaskError::Panic(e) => ExportIndexOrchestratorError::Panic(e.unwrap_or_default()),
+            TaskError::TaskFailed(e) => e.into(),
+            TaskError::Aborted(e) => ExportIndexOrchestratorError::Aborted(e),
+        }
+    }
+}
+
+type ExportIndexResult = Result<ExportIndexOutput, ExportIndexOrchestratorError>;
+
+/// The `ExportIndexOrchestrator` exports the full text index and the metadata indexes of a
+/// collection to the storage. See `ExportIndexOperator` for the format of the export
+///
+/// # Pipeline
+/// ```text
+///  ┌────────────┐
+///  │            │
+///  │  on_start  │
+///  │            │
+///  └─────┬──────┘
+///        │
+///        ▼
+///  ┌────────────────────────┐
+///  │                        │
+///  │  FetchSegmentOperator  │
+///  │                        │
+///  └─────┬──────────────────┘
+///        │
+///        ▼
+///  ┌───────────────────────┐
+///  │                       │
+///  │  ExportIndexOperator  │
+///  │                       │
+///  └─────┬─────────────────┘
+///        │
+///        ▼
+///  ┌──────────────────┐
+///  │                  │
+///  │  result_channel  │
+///  │                  │
+///  └──────────────────┘
+/// ```
+#[derive(Debug)]
+pub struct ExportIndexOrchestrator {
+    // Orchestrator parameters
+    blockfile_provider: BlockfileProvider,
+    dispatcher: ComponentHandle<Dispatcher>,
+    queue: usize,
+    storage: Storage,
+
+    // Fetch segments
+    fetch_segment: FetchSegmentOperator,
+
+    // Export the indexes
+    export_index: ExportIndexOperator,
+
+    // Result channel
+    result_channel: Option<Sender<ExportIndexResult>>,
+}
+
+impl ExportIndexOrchestrator {
+    pub fn new(
+        blockfile_provider: BlockfileProvider,
+        dispatcher: ComponentHandle<Dispatcher>,
+        queue: usize,
+        storage: Storage,
+        fetch_segment: FetchSegmentOperator,
+        export_index: ExportIndexOperator,
+    ) -> Self {
+        Self {
+            blockfile_provider,
+            dispatcher,
+            queue,
+            storage,
+            fetch_segment,
+            export_index,
+            result_channel: None,
+        }
+    }
+
+    pub async fn run(mut self, system: System) -> ExportIndexResult {
+        let (tx, rx) = oneshot::channel();
+        self.result_channel = Some(tx);
+        let mut handle = system.start_component(self);
+        let result = rx.await;
+        handle.stop();
+        result?
+    }
+
+    fn terminate_with_error<E>(&mut self, ctx: &ComponentContext<Self>, err: E)
+    where
+        E: Into<ExportIndexOrchestratorError>,
+    {
+        let export_index_err = err.into();
+        tracing::error!("Error running orchestrator: {}", &export_index_err);
+        terminate_with_error(self.result_channel.take(), export_index_err, ctx);
+    }
+}
+
+#[async_trait]
+impl Component for ExportIndexOrchestrator {
+    fn get_name() -> &'static str {
+        "Export Index Orchestrator"
+    }
+
+    fn queue_size(&self) -> usize {
+        self.queue
+    }
+
+    async fn on_start(&mut self, ctx: &ComponentContext<Self>) {
+        let task = wrap_with_priority(
+            Box::new(self.fetch_segment.clone()),
+            (),
+            ctx.receiver(),
+            TaskPriority::Batch,
+        );
+        if let Err(err) = self.dispatcher.send(task, Some(Span::current())).await {
+            self.terminate_with_error(ctx, err);
+        }
+    }
+}
+
+#[async_trait]
+impl Handler<TaskResult<FetchSegmentOutput, FetchSegmentError>> for ExportIndexOrchestrator {
+    type Result = ();
+
+    async fn handle(
+        &mut self,
+        message: TaskResult<FetchSegmentOutput, FetchSegmentError>,
+        ctx: &ComponentContext<Self>,
+    ) {
+        let output = match message.into_inner() {
+            Ok(output) => output,
+            Err(err) => {
+                self.terminate_with_error(ctx, err);
+                return;
+            }
+        };
+        let task = wrap_with_priority(
+            Box::new(self.export_index.clone()),
+            ExportIndexInput {
+                blockfile_provider: self.blockfile_provider.clone(),
+                storage: self.storage.clone(),
+                sysdb: self.fetch_segment.sysdb.clone(),
+                tenant: output.collection.tenant,
+                metadata_segment: output.metadata_segment,
+            },
+            ctx.receiver(),
+            TaskPriority::Batch,
+        );
+        if let Err(err) = self.dispatcher.send(task, Some(Span::current())).await {
+            self.terminate_with_error(ctx, err);
+        }
+    }
+}
+
+#[async_trait]
+impl Handler<TaskResult<ExportIndexOutput, ExportIndexError>> for ExportIndexOrchestrator {
+    type Result = ();
+
+    async fn handle(
+        &mut self,
+        message: TaskResult<ExportIndexOutput, ExportIndexError>,
+        ctx: &ComponentContext<Self>,
+    ) {
+        let output = match message.into_inner() {
+            Ok(output) => output,
+            Err(err) => {
+                self.terminate_with_error(ctx, err);
+                return;
+            }
+        };
+        if let Some(chan) = self.result_channel.take() {
+            if chan.send(Ok(output)).is_err() {
+                tracing::error!("Error sending final result");
+            };
+        }
+    }
+}
@@ -1,16 +1,23 @@
 use chroma_blockstore::provider::BlockfileProvider;
-use chroma_error::{ChromaError, ErrorCodes};
+use chroma_error::{ChromaError, ErrorCodes, ErrorLocation};
+use chroma_types::{Chunk, SignedRoaringBitmap};
+use futures::{Stream, TryStreamExt};
 use thiserror::Error;
 use tokio::sync::oneshot::{self, error::RecvError, Sender};
 use tonic::async_trait;
 use tracing::Span;
+use uuid::Uuid;
 
 use crate::{
     execution::{
         dispatcher::Dispatcher,
-        operator::{wrap, TaskError, TaskResult},
+        explain::QueryPlanRecorder,
+        memory::{MemoryContext, MemoryError, MemoryPool},
+        metrics::{QueryMetrics, QueryType},
+        middleware::OperatorMiddleware,
+        operator::{wrap_with_middleware, Deadline, TaskError, TaskPriority, TaskResult},
         operators::{
-            fetch_log::{FetchLogError, FetchLogOperator, FetchLogOutput},
+            fetch_log::{FetchLogError, FetchLogInput, FetchLogOperator, FetchLogOutput},
             fetch_segment::{FetchSegmentError, FetchSegmentOperator, FetchSegmentOutput},
             filter::{FilterError, FilterInput, FilterOperator, FilterOutput},
             limit::{LimitError, LimitInput, LimitOperator, LimitOutput},
@@ -18,9 +25,17 @@ use crate::{
                 PrefetchRecordError, PrefetchRecordInput, PrefetchRecordOperator,
                 PrefetchRecordOutput,
             },
-            projection::{ProjectionError, ProjectionInput, ProjectionOperator, ProjectionOutput},
+            projection::{
+                ProjectionDefaults, ProjectionError, ProjectionInput, ProjectionOperator,
+                ProjectionOutput, ProjectionRecord,
+            },
         },
         orchestration::common::terminate_with_error,
+        result_cache::{ResultCache, ResultCacheKey, SharedResultCache},
+    },
+    segment::{
+        offset_id_cache::{OffsetIdCache, SharedOffsetIdCache},
+        SharedMaterializedLogs,
     },
     system::{ChannelError, Component, ComponentContext, ComponentHandle, Handler, System},
 };
@@ -37,6 +52,10 @@ pub enum GetError {
     Filter(#[from] FilterError),
     #[error("Error running Limit Operator: {0}")]
     Limit(#[from] LimitError),
+    #[error("Error admitting query: {0}")]
+    Memory(#[from] MemoryError),
+    #[error("Task aborted: {0}")]
+    Aborted(String),
     #[error("Panic running task: {0}")]
     Panic(String),
     #[error("Error running Projection Operator: {0}")]
@@ -53,11 +72,29 @@ impl ChromaError for GetError {
             GetError::FetchSegment(e) => e.code(),
             GetError::Filter(e) => e.code(),
             GetError::Limit(e) => e.code(),
+            GetError::Memory(e) => e.code(),
+            GetError::Aborted(_) => ErrorCodes::Aborted,
             GetError::Panic(_) => ErrorCodes::Aborted,
             GetError::Projection(e) => e.code(),
             GetError::Result(_) => ErrorCodes::Internal,
         }
     }
+
+    fn field(&self) -> Option<&str> {
+        match self {
+            GetError::Filter(e) => e.field(),
+            _ => None,
+        }
+    }
+
+    fn location(&self) -> ErrorLocation {
+        match self {
+            GetError::Filter(e) => e.location(),
+            GetError::Limit(e) => e.location(),
+            GetError::Projection(e) => e.location(),
+            _ => ErrorLocation::default(),
+        }
+    }
 }
 
 impl<E> From<TaskError<E>> for GetError
@@ -68,14 +105,23 @@ where
         match value {
             TaskError::Panic(e) => GetError::Panic(e.unwrap_or_default()),
             TaskError::TaskFailed(e) => e.into(),
+            TaskError::Aborted(e) => GetError::Aborted(e),
         }
     }
 }
 
-type GetOutput = ProjectionOutput;
+#[derive(Debug)]
+pub struct GetOutput {
+    pub records: Vec<ProjectionRecord>,
+    // The offset of the last log that the records cover
+    pub log_offset: i64,
+}
 
 type GetResult = Result<GetOutput, GetError>;
 
+/// The default number of records in a batch of a streamed get query
+pub const DEFAULT_STREAM_BATCH_SIZE: usize = 1000;
+
 /// The `GetOrchestrator` chains a sequence of operators in sequence to evaluate
 /// a `<collection>.get(...)` query from the user
 ///
@@ -137,6 +183,14 @@ type GetResult = Result<GetOutput, GetError>;
 /// from either operators, and if both outputs are present it composes the input
 /// for `FilterOperator` and proceeds with execution. The outputs of other
 /// operators are directly forwarded without being tracked by the orchestrator.
+///
+/// If the offset ids are given upfront with `with_offset_ids`, the orchestrator
+/// proceeds to `ProjectionOperator` directly once both fetches complete.
+///
+/// If neither the offset ids nor the result cache need the logs before the filter,
+/// the orchestrator skips `FetchLogOperator` and `FilterOperator` streams the logs
+/// itself, materializing them batch by batch as they are pulled. The filter then
+/// returns the fetched logs for the operators that follow it.
 #[derive(Debug)]
 pub struct GetOrchestrator {
     // Orchestrator parameters
@@ -152,6 +206,35 @@ pub struct GetOrchestrator {
     fetch_log_output: Option<FetchLogOutput>,
     fetch_segment_output: Option<FetchSegmentOutput>,
 
+    // The logs materialized by the operators, shared so that they are materialized once
+    materialized_logs: SharedMaterializedLogs,
+
+    // The deadline of the query, shared by the operators
+    deadline: Deadline,
+
+    // The pool that admits the query, and the memory of the query shared by the operators
+    memory_pool: MemoryPool,
+    memory: MemoryContext,
+
+    // The priority class of the tasks of the query, and the middleware that their operators
+    // run in
+    priority: TaskPriority,
+    middleware: OperatorMiddleware,
+
+    // The offset ids that the user provided ids resolve to
+    offset_id_cache: OffsetIdCache,
+
+    // The offset ids to project directly instead of evaluating the filter and limit
+    offset_ids: Option<Vec<u32>>,
+
+    // The results of earlier queries, and the key of the result of this query if it misses
+    result_cache: ResultCache,
+    result_cache_key: Option<ResultCacheKey>,
+
+    // The plan of the query, and the tasks whose outputs the next task consumes
+    plan: QueryPlanRecorder,
+    plan_inputs: Vec<Uuid>,
+
     // Pipelined operators
     filter: FilterOperator,
     limit: LimitOperator,
@@ -159,6 +242,9 @@ pub struct GetOrchestrator {
 
     // Result channel
     result_channel: Option<Sender<GetResult>>,
+
+    // The channel for the input of the projection, if the caller streams the projection
+    stream_channel: Option<Sender<Result<ProjectionInput, GetError>>>,
 }
 
 impl GetOrchestrator {
@@ -181,14 +267,86 @@ impl GetOrchestrator {
             fetch_segment,
             fetch_log_output: None,
             fetch_segment_output: None,
+            materialized_logs: SharedMaterializedLogs::new(projection.fields()),
+            deadline: Deadline::default(),
+            memory_pool: MemoryPool::default(),
+            memory: MemoryContext::default(),
+            priority: TaskPriority::Interactive,
+            middleware: OperatorMiddleware::default(),
+            offset_id_cache: OffsetIdCache::default(),
+            offset_ids: None,
+            result_cache: ResultCache::default(),
+            result_cache_key: None,
+            plan: QueryPlanRecorder::default(),
+            plan_inputs: Vec::new(),
             filter,
             limit,
             projection,
             result_channel: None,
+            stream_channel: None,
         }
     }
 
+    /// Stops the limit and projection of the query once the deadline is reached
+    pub fn with_deadline(mut self, deadline: Deadline) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// Admits the query only while the memory held by the running queries is below the limit
+    /// of the pool, and fails the query once it exceeds its budget
+    pub fn with_memory_pool(mut self, memory_pool: MemoryPool) -> Self {
+        self.memory_pool = memory_pool;
+        self
+    }
+
+    /// Runs the tasks of the query in the given priority class, e.g. as batch tasks for a scan
+    /// of the whole collection so that it does not starve point lookups
+    pub fn with_priority(mut self, priority: TaskPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Runs the operators of the query in the given middleware instead of the default one,
+    /// e.g. with a timeout on each operator
+    pub fn with_middleware(mut self, middleware: OperatorMiddleware) -> Self {
+        self.middleware = middleware;
+        self
+    }
+
+    /// Resolves the user provided ids through the shared cache if provided, and fills it with
+    /// the ids that it misses, so that later queries for the same ids skip the record segment
+    pub fn with_offset_id_cache(mut self, shared: Option<SharedOffsetIdCache>) -> Self {
+        self.offset_id_cache = OffsetIdCache::new(shared);
+        self
+    }
+
+    /// Projects the records with the given offset ids, e.g. from the embedding handles of an
+    /// earlier query, and skips the filter and limit of the query
+    pub fn with_offset_ids(mut self, offset_ids: Vec<u32>) -> Self {
+        self.offset_ids = Some(offset_ids);
+        self
+    }
+
+    /// Returns the cached result of the same query on the same version and logs of the
+    /// collection if there is one, and caches the result of the query otherwise. A streamed
+    /// query neither reads nor fills the cache
+    pub(crate) fn with_result_cache(mut self, shared: Option<SharedResultCache>) -> Self {
+        self.result_cache = ResultCache::new(shared);
+        self
+    }
+
+    /// Records the plan of the query as it runs, which the caller reads from the recorder
+    /// once the query completes
+    pub fn with_plan(mut self, plan: QueryPlanRecorder) -> Self {
+        self.plan = plan;
+        self
+    }
+
     pub async fn run(mut self, system: System) -> GetResult {
+        // The operators stop early if the caller stops waiting for the result
+        let _cancel_on_drop = self.deadline.cancel_on_drop();
+        self.memory = self.memory_pool.admit()?;
         let (tx, rx) = oneshot::channel();
         self.result_channel = Some(tx);
         let mut handle = system.start_component(self);
@@ -197,34 +355,242 @@ impl GetOrchestrator {
         result?
     }
 
+    /// Evaluates the query up to its projection, and returns a stream of the projected records
+    /// in batches of at most `batch_size` records. A batch is only read once the stream is
+    /// polled for it, so a slow consumer holds back the reads instead of buffering the result,
+    /// and the memory of a batch is released once the next batch is polled
+    pub async fn run_streaming(
+        mut self,
+        system: System,
+        batch_size: usize,
+    ) -> Result<impl Stream<Item = Result<GetOutput, GetError>> + Send, GetError> {
+        // The reads stop early if the caller drops the stream
+        let cancel_on_drop = self.deadline.cancel_on_drop();
+        self.memory = self.memory_pool.admit()?;
+        let projection = self.projection.clone();
+        let fetch_log = self.fetch_log.clone();
+        let (tx, rx) = oneshot::channel();
+        self.stream_channel = Some(tx);
+        let mut handle = system.start_component(self);
+        let result = rx.await;
+        handle.stop();
+        let input = result??;
+
+        let memory = input.memory.clone();
+        let log_offset = fetch_log.log_offset(&input.logs);
+        let mut sent_bytes = 0;
+        Ok(projection
+            .stream(input, batch_size)
+            .map_err(GetError::from)
+            .map_ok(move |batch| {
+                let _cancel_on_drop = &cancel_on_drop;
+                memory.release(sent_bytes);
+                sent_bytes = batch.records.iter().map(ProjectionRecord::get_size).sum();
+                GetOutput {
+                    records: batch.records,
+                    log_offset,
+                }
+            }))
+    }
+
     fn terminate_with_error<E>(&mut self, ctx: &ComponentContext<Self>, err: E)
     where
         E: Into<GetError>,
     {
         let get_err = err.into();
         tracing::error!("Error running orchestrator: {}", &get_err);
-        terminate_with_error(self.result_channel.take(), get_err, ctx);
+        match self.stream_channel.take() {
+            Some(chan) => terminate_with_error(Some(chan), get_err, ctx),
+            None => terminate_with_error(self.result_channel.take(), get_err, ctx),
+        }
+    }
+
+    /// Whether the filter fetches the logs itself, which holds unless the offset ids or the
+    /// key of the result cache need the logs before the filter
+    fn filter_streams_logs(&self) -> bool {
+        self.offset_ids.is_none()
+            && !(self.result_cache.is_enabled() && self.stream_channel.is_none())
     }
 
     /// Try to start the filter operator once both `FetchLogOperator` and `FetchSegmentOperator` completes
     async fn try_start_filter_operator(&mut self, ctx: &ComponentContext<Self>) {
+        if self.filter_streams_logs() {
+            if let Some(segments) = self.fetch_segment_output.as_ref() {
+                let input = FilterInput {
+                    logs: Chunk::new(Vec::new().into()),
+                    blockfile_provider: self.blockfile_provider.clone(),
+                    metadata_segment: segments.metadata_segment.clone(),
+                    record_segment: segments.record_segment.clone(),
+                    materialized_logs: self.materialized_logs.clone(),
+                    memory: self.memory.clone(),
+                    offset_id_cache: self.offset_id_cache.clone(),
+                    scan_limit: self.scan_limit(),
+                    fetch_log: Some(self.fetch_log.clone()),
+                };
+                self.start_filter(input, ctx).await;
+            }
+            return;
+        }
         if let (Some(logs), Some(segments)) = (
             self.fetch_log_output.as_ref(),
             self.fetch_segment_output.as_ref(),
         ) {
-            let task = wrap(
-                Box::new(self.filter.clone()),
-                FilterInput {
-                    logs: logs.clone(),
-                    blockfile_provider: self.blockfile_provider.clone(),
-                    metadata_segment: segments.metadata_segment.clone(),
-                    record_segment: segments.record_segment.clone(),
-                },
-                ctx.receiver(),
-            );
-            if let Err(err) = self.dispatcher.send(task, Some(Span::current())).await {
-                self.terminate_with_error(ctx, err);
+            if let Some(offset_ids) = self.offset_ids.take() {
+                self.plan.set_strategy("candidates", "offset_ids");
+                self.start_projection(offset_ids, ctx).await;
+                return;
+            }
+            if self.result_cache.is_enabled() && self.stream_channel.is_none() {
+                let key = ResultCacheKey::new(
+                    self.fetch_log.collection_uuid,
+                    self.fetch_segment.collection_version,
+                    self.fetch_log.start_log_offset_id as i64 - 1,
+                    logs,
+                    &self.filter,
+                    &self.limit,
+                    &self.projection,
+                );
+                if let Some(records) = self.result_cache.get(&key).await {
+                    self.plan.set_strategy("result_cache", "hit");
+                    let output = GetOutput {
+                        records,
+                        log_offset: self.fetch_log.log_offset(logs),
+                    };
+                    if let Some(chan) = self.result_channel.take() {
+                        if chan.send(Ok(output)).is_err() {
+                            tracing::error!("Error sending final result");
+                        }
+                    }
+                    return;
+                }
+                self.plan.set_strategy("result_cache", "miss");
+                self.result_cache_key = Some(key);
+            }
+            let input = FilterInput {
+                logs: logs.clone(),
+                blockfile_provider: self.blockfile_provider.clone(),
+                metadata_segment: segments.metadata_segment.clone(),
+                record_segment: segments.record_segment.clone(),
+                materialized_logs: self.materialized_logs.clone(),
+                memory: self.memory.clone(),
+                offset_id_cache: self.offset_id_cache.clone(),
+                scan_limit: self.scan_limit(),
+                fetch_log: None,
+            };
+            self.start_filter(input, ctx).await;
+        }
+    }
+
+    // Only the records up to the end of the page are needed from the blockfile
+    fn scan_limit(&self) -> Option<u32> {
+        self.limit
+            .fetch
+            .map(|fetch| self.limit.skip.saturating_add(fetch))
+    }
+
+    async fn start_filter(&mut self, input: FilterInput, ctx: &ComponentContext<Self>) {
+        self.plan.set_strategy("candidates", "filter");
+        let task = wrap_with_middleware(
+            Box::new(self.filter.clone()),
+            input,
+            ctx.receiver(),
+            self.priority,
+            self.middleware.clone(),
+        );
+        self.plan.start(
+            task.id(),
+            task.get_name(),
+            std::mem::take(&mut self.plan_inputs),
+            self.filter
+                .query_ids
+                .as_ref()
+                .map(|query_ids| query_ids.len() as u64),
+        );
+        if let Err(err) = self.dispatcher.send(task, Some(Span::current())).await {
+            self.terminate_with_error(ctx, err);
+        }
+    }
+
+    /// Prefetches and projects the records with the given offset ids
+    async fn start_projection(&mut self, offset_ids: Vec<u32>, ctx: &ComponentContext<Self>) {
+        // Prefetch records before projection
+        let prefetch_task = wrap_with_middleware(
+            Box::new(PrefetchRecordOperator {}),
+            PrefetchRecordInput {
+                logs: self
+                    .fetch_log_output
+                    .as_ref()
+                    .expect("FetchLogOperator should have finished already")
+                    .clone(),
+                blockfile_provider: self.blockfile_provider.clone(),
+                record_segment: self
+                    .fetch_segment_output
+                    .as_ref()
+                    .expect("FetchSegmentOperator should have finished already")
+                    .record_segment
+                    .clone(),
+                offset_ids: offset_ids.clone(),
+            },
+            ctx.receiver(),
+            self.priority,
+            self.middleware.clone(),
+        );
+        if let Err(err) = self
+            .dispatcher
+            .send(prefetch_task, Some(Span::current()))
+            .await
+        {
+            self.terminate_with_error(ctx, err);
+        }
+
+        let input = ProjectionInput {
+            logs: self
+                .fetch_log_output
+                .as_ref()
+                .expect("FetchLogOperator should have finished already")
+                .clone(),
+            blockfile_provider: self.blockfile_provider.clone(),
+            record_segment: self
+                .fetch_segment_output
+                .as_ref()
+                .expect("FetchSegmentOperator should have finished already")
+                .record_segment
+                .clone(),
+            offset_ids,
+            defaults: ProjectionDefaults::from(
+                &self
+                    .fetch_segment_output
+                    .as_ref()
+                    .expect("FetchSegmentOperator should have finished already")
+                    .collection,
+            ),
+            deadline: self.deadline.clone(),
+            memory: self.memory.clone(),
+            materialized_logs: self.materialized_logs.clone(),
+        };
+        if let Some(chan) = self.stream_channel.take() {
+            if chan.send(Ok(input)).is_err() {
+                tracing::error!("Error sending projection input");
             }
+            return;
+        }
+
+        let estimated_rows = input.offset_ids.len() as u64;
+        let task = wrap_with_middleware(
+            Box::new(self.projection.clone()),
+            input,
+            ctx.receiver(),
+            self.priority,
+            self.middleware.clone(),
+        );
+        self.plan.start(
+            task.id(),
+            task.get_name(),
+            std::mem::take(&mut self.plan_inputs),
+            Some(estimated_rows),
+        );
+        if let Err(err) = self.dispatcher.send(task, Some(Span::current())).await {
+            self.terminate_with_error(ctx, err);
         }
     }
 }
@@ -240,8 +606,45 @@ impl Component for GetOrchestrator {
     }
 
     async fn on_start(&mut self, ctx: &ComponentContext<Self>) {
-        let log_task = wrap(Box::new(self.fetch_log.clone()), (), ctx.receiver());
-        let segment_task = wrap(Box::new(self.fetch_segment.clone()), (), ctx.receiver());
+        if self.filter_streams_logs() {
+            let segment_task = wrap_with_middleware(
+                Box::new(self.fetch_segment.clone()),
+                (),
+                ctx.receiver(),
+                self.priority,
+                self.middleware.clone(),
+            );
+            self.plan
+                .start(segment_task.id(), segment_task.get_name(), [], None);
+            if let Err(err) = self
+                .dispatcher
+                .send(segment_task, Some(Span::current()))
+                .await
+            {
+                self.terminate_with_error(ctx, err);
+            }
+            return;
+        }
+        let log_task = wrap_with_middleware(
+            Box::new(self.fetch_log.clone()),
+            FetchLogInput {
+                memory: self.memory.clone(),
+            },
+            ctx.receiver(),
+            self.priority,
+            self.middleware.clone(),
+        );
+        let segment_task = wrap_with_middleware(
+            Box::new(self.fetch_segment.clone()),
+            (),
+            ctx.receiver(),
+            self.priority,
+            self.middleware.clone(),
+        );
+        self.plan
+            .start(log_task.id(), log_task.get_name(), [], None);
+        self.plan
+            .start(segment_task.id(), segment_task.get_name(), [], None);
         if let Err(err) = self.dispatcher.send(log_task, Some(Span::current())).await {
             self.terminate_with_error(ctx, err);
             return;
@@ -265,6 +668,8 @@ impl Handler<TaskResult<FetchLogOutput, FetchLogError>> for GetOrchestrator {
         message: TaskResult<FetchLogOutput, FetchLogError>,
         ctx: &ComponentContext<Self>,
     ) {
+        let task_id = message.id();
+        let read_stats = message.read_stats();
         let output = match message.into_inner() {
             Ok(output) => output,
             Err(err) => {
@@ -272,6 +677,9 @@ impl Handler<TaskResult<FetchLogOutput, FetchLogError>> for GetOrchestrator {
                 return;
             }
         };
+        self.plan
+            .finish(task_id, read_stats, Some(output.len() as u64));
+        self.plan_inputs.push(task_id);
         self.fetch_log_output = Some(output);
         self.try_start_filter_operator(ctx).await;
     }
@@ -286,6 +694,8 @@ impl Handler<TaskResult<FetchSegmentOutput, FetchSegmentError>> for GetOrchestra
         message: TaskResult<FetchSegmentOutput, FetchSegmentError>,
         ctx: &ComponentContext<Self>,
     ) {
+        let task_id = message.id();
+        let read_stats = message.read_stats();
         let output = match message.into_inner() {
             Ok(output) => output,
             Err(err) => {
@@ -293,6 +703,8 @@ impl Handler<TaskResult<FetchSegmentOutput, FetchSegmentError>> for GetOrchestra
                 return;
             }
         };
+        self.plan.finish(task_id, read_stats, None);
+        self.plan_inputs.push(task_id);
         self.fetch_segment_output = Some(output);
         self.try_start_filter_operator(ctx).await;
     }
@@ -307,6 +719,8 @@ impl Handler<TaskResult<FilterOutput, FilterError>> for GetOrchestrator {
         message: TaskResult<FilterOutput, FilterError>,
         ctx: &ComponentContext<Self>,
     ) {
+        let task_id = message.id();
+        let read_stats = message.read_stats();
         let output = match message.into_inner() {
             Ok(output) => output,
             Err(err) => {
@@ -314,7 +728,43 @@ impl Handler<TaskResult<FilterOutput, FilterError>> for GetOrchestrator {
                 return;
             }
         };
-        let task = wrap(
+        // The size of the candidate set is only known when the filter is not a negation
+        let candidate_rows = if let (
+            SignedRoaringBitmap::Include(log_offset_ids),
+            SignedRoaringBitmap::Include(compact_offset_ids),
+        ) = (&output.log_offset_ids, &output.compact_offset_ids)
+        {
+            let candidate_rows = log_offset_ids.len() + compact_offset_ids.len();
+            QueryMetrics::get().record_candidate_set_size(
+                self.fetch_log.collection_uuid,
+                QueryType::Get,
+                candidate_rows,
+            );
+            Some(candidate_rows)
+        } else {
+            None
+        };
+        self.plan.set_strategy(
+            "bitmap",
+            match output.compact_offset_ids {
+                SignedRoaringBitmap::Include(_) => "include",
+                SignedRoaringBitmap::Exclude(_) => "exclude",
+            },
+        );
+        self.plan.finish(task_id, read_stats, candidate_rows);
+        self.plan_inputs = vec![task_id];
+        if let Some(logs) = output.logs {
+            self.fetch_log_output = Some(logs);
+        }
+        let fetch = self.limit.fetch.map(u64::from);
+        let limit_rows = match candidate_rows {
+            Some(rows) => {
+                let rows = rows.saturating_sub(self.limit.skip as u64);
+                Some(fetch.map_or(rows, |fetch| fetch.min(rows)))
+            }
+            None => fetch,
+        };
+        let task = wrap_with_middleware(
             Box::new(self.limit.clone()),
             LimitInput {
                 logs: self
@@ -331,8 +781,19 @@ impl Handler<TaskResult<FilterOutput, FilterError>> for GetOrchestrator {
                     .clone(),
                 log_offset_ids: output.log_offset_ids,
                 compact_offset_ids: output.compact_offset_ids,
+                materialized_logs: self.materialized_logs.clone(),
+                deadline: self.deadline.clone(),
+                memory: self.memory.clone(),
             },
             ctx.receiver(),
+            self.priority,
+            self.middleware.clone(),
+        );
+        self.plan.start(
+            task.id(),
+            task.get_name(),
+            std::mem::take(&mut self.plan_inputs),
+            limit_rows,
         );
         if let Err(err) = self.dispatcher.send(task, Some(Span::current())).await {
             self.terminate_with_error(ctx, err);
@@ -349,6 +810,8 @@ impl Handler<TaskResult<LimitOutput, LimitError>> for GetOrchestrator {
         message: TaskResult<LimitOutput, LimitError>,
         ctx: &ComponentContext<Self>,
     ) {
+        let task_id = message.id();
+        let read_stats = message.read_stats();
         let output = match message.into_inner() {
             Ok(output) => output,
             Err(err) => {
@@ -356,57 +819,12 @@ impl Handler<TaskResult<LimitOutput, LimitError>> for GetOrchestrator {
                 return;
             }
         };
+        self.plan
+            .finish(task_id, read_stats, Some(output.offset_ids.len()));
+        self.plan_inputs = vec![task_id];
 
-        // Prefetch records before projection
-        let prefetch_task = wrap(
-            Box::new(PrefetchRecordOperator {}),
-            PrefetchRecordInput {
-                logs: self
-                    .fetch_log_output
-                    .as_ref()
-                    .expect("FetchLogOperator should have finished already")
-                    .clone(),
-                blockfile_provider: self.blockfile_provider.clone(),
-                record_segment: self
-                    .fetch_segment_output
-                    .as_ref()
-                    .expect("FetchSegmentOperator should have finished already")
-                    .record_segment
-                    .clone(),
-                offset_ids: output.offset_ids.iter().collect(),
-            },
-            ctx.receiver(),
-        );
-        if let Err(err) = self
-            .dispatcher
-            .send(prefetch_task, Some(Span::current()))
-            .await
-        {
-            self.terminate_with_error(ctx, err);
-        }
-
-        let task = wrap(
-            Box::new(self.projection.clone()),
-            ProjectionInput {
-                logs: self
-                    .fetch_log_output
-                    .as_ref()
-                    .expect("FetchLogOperator should have finished already")
-                    .clone(),
-                blockfile_provider: self.blockfile_provider.clone(),
-                record_segment: self
-                    .fetch_segment_output
-                    .as_ref()
-                    .expect("FetchSegmentOperator should have finished already")
-                    .record_segment
-                    .clone(),
-                offset_ids: output.offset_ids.into_iter().collect(),
-            },
-            ctx.receiver(),
-        );
-        if let Err(err) = self.dispatcher.send(task, Some(Span::current())).await {
-            self.terminate_with_error(ctx, err);
-        }
+        self.start_projection(output.offset_ids.into_iter().collect(), ctx)
+            .await;
     }
 }
 
@@ -432,6 +850,8 @@ impl Handler<TaskResult<ProjectionOutput, ProjectionError>> for GetOrchestrator
         message: TaskResult<ProjectionOutput, ProjectionError>,
         ctx: &ComponentContext<Self>,
     ) {
+        let task_id = message.id();
+        let read_stats = message.read_stats();
         let output = match message.into_inner() {
             Ok(output) => output,
             Err(err) => {
@@ -439,7 +859,21 @@ impl Handler<TaskResult<ProjectionOutput, ProjectionError>> for GetOrchestrator
                 return;
             }
         };
+        self.plan
+            .finish(task_id, read_stats, Some(output.records.len() as u64));
+        if let Some(key) = self.result_cache_key.take() {
+            self.result_cache.insert(key, &output.records).await;
+        }
+        let log_offset = self.fetch_log.log_offset(
+            self.fetch_log_output
+                .as_ref()
+                .expect("FetchLogOperator should have finished already"),
+        );
         if let Some(chan) = self.result_channel.take() {
+            let output = GetOutput {
+                records: output.records,
+                log_offset,
+            };
             if chan.send(Ok(output)).is_err() {
                 tracing::error!("Error sending final result");
             };
@@ -1,11 +1,9 @@
-use super::common::{
-    get_collection_by_id, get_hnsw_segment_by_id, get_record_segment_by_collection_id,
-};
 use crate::{
     execution::{
         dispatcher::Dispatcher,
         operator::{wrap, TaskResult},
         operators::{
+            fetch_segment::{FetchSegmentOperator, FetchSegmentOutput},
             get_vectors_operator::{
                 GetVectorsOperator, GetVectorsOperatorError, GetVectorsOperatorInput,
                 GetVectorsOperatorOutput,
@@ -24,7 +22,9 @@ use crate::{
 use async_trait::async_trait;
 use chroma_blockstore::provider::BlockfileProvider;
 use chroma_error::{ChromaError, ErrorCodes};
-use chroma_types::{Chunk, Collection, CollectionUuid, GetVectorsResult, LogRecord, Segment};
+use chroma_types::{
+    Chunk, Collection, CollectionUuid, GetVectorsResult, LogRecord, Segment, SegmentUuid,
+};
 use std::time::{SystemTime, UNIX_EPOCH};
 use thiserror::Error;
 use tracing::{trace, Span};
@@ -43,8 +43,6 @@ enum GetVectorsError {
     TaskSendError(#[from] ChannelError),
     #[error("System time error")]
     SystemTimeError(#[from] std::time::SystemTimeError),
-    #[error("Collection version mismatch")]
-    CollectionVersionMismatch,
 }
 
 impl ChromaError for GetVectorsError {
@@ -52,7 +50,6 @@ impl ChromaError for GetVectorsError {
         match self {
             GetVectorsError::TaskSendError(e) => e.code(),
             GetVectorsError::SystemTimeError(_) => ErrorCodes::Internal,
-            GetVectorsError::CollectionVersionMismatch => ErrorCodes::VersionMismatch,
         }
     }
 }
@@ -228,50 +225,31 @@ impl Component for GetVectorsOrchestrator {
     }
 
     async fn on_start(&mut self, ctx: &ComponentContext<Self>) {
-        // Populate the orchestrator with the initial state - The HNSW Segment, The Record Segment and the Collection
-        let hnsw_segment = match get_hnsw_segment_by_id(
-            self.sysdb.clone(),
-            &self.hnsw_segment_id,
-            &self.collection_id,
-        )
+        // Pin the collection and its segments at the version of the request, every reader of
+        // the query is created from this snapshot
+        let FetchSegmentOutput {
+            collection,
+            record_segment,
+            ..
+        } = match (FetchSegmentOperator {
+            sysdb: self.sysdb.clone(),
+            collection_uuid: self.collection_id,
+            collection_version: self.collection_version,
+            metadata_uuid: None,
+            record_uuid: None,
+            vector_uuid: Some(SegmentUuid(self.hnsw_segment_id)),
+            cache_invalidator: None,
+        })
+        .fetch()
         .await
         {
-            Ok(segment) => segment,
+            Ok(output) => output,
             Err(e) => {
-                terminate_with_error(self.result_channel.take(), e, ctx);
-                return;
-            }
-        };
-
-        let collection_id = &hnsw_segment.collection;
-
-        let collection = match get_collection_by_id(self.sysdb.clone(), collection_id).await {
-            Ok(collection) => collection,
-            Err(e) => {
-                terminate_with_error(self.result_channel.take(), e, ctx);
+                terminate_with_error(self.result_channel.take(), Box::new(e), ctx);
                 return;
             }
         };
 
-        // If the collection version does not match the request version then we terminate with an error
-        if collection.version as u32 != self.collection_version {
-            terminate_with_error(
-                self.result_channel.take(),
-                Box::new(GetVectorsError::CollectionVersionMismatch),
-                ctx,
-            );
-            return;
-        }
-
-        let record_segment =
-            match get_record_segment_by_collection_id(self.sysdb.clone(), collection_id).await {
-                Ok(segment) => segment,
-                Err(e) => {
-                    terminate_with_error(self.result_channel.take(), e, ctx);
-                    return;
-                }
-            };
-
         self.record_segment = Some(record_segment);
         self.collection = Some(collection);
 
@@ -1,15 +1,15 @@
 use super::super::operator::wrap;
 use super::super::operators::pull_log::{PullLogsInput, PullLogsOperator};
-use super::common::{
-    get_collection_by_id, get_hnsw_segment_by_id, get_record_segment_by_collection_id,
-    terminate_with_error,
-};
+use super::common::terminate_with_error;
 use crate::execution::dispatcher::Dispatcher;
-use crate::execution::operator::TaskResult;
+use crate::execution::explain::QueryPlanRecorder;
+use crate::execution::memory::{MemoryContext, MemoryPool};
+use crate::execution::operator::{Deadline, TaskResult};
 use crate::execution::operators::brute_force_knn::{
     BruteForceKnnOperator, BruteForceKnnOperatorError, BruteForceKnnOperatorInput,
     BruteForceKnnOperatorOutput,
 };
+use crate::execution::operators::fetch_segment::{FetchSegmentOperator, FetchSegmentOutput};
 use crate::execution::operators::hnsw_knn::{
     HnswKnnOperator, HnswKnnOperatorInput, HnswKnnOperatorOutput,
 };
@@ -24,12 +24,14 @@ use crate::execution::operators::record_segment_prefetch::{
     RecordSegmentPrefetchIoOperator, RecordSegmentPrefetchIoOperatorError,
     RecordSegmentPrefetchIoOutput,
 };
+use crate::execution::spill::SpillConfig;
 use crate::log::log::PullLogsError;
 use crate::segment::distributed_hnsw_segment::{
-    distance_function_from_segment, DistributedHNSWSegmentFromSegmentError,
-    DistributedHNSWSegmentReader,
+    distance_function_from_segment, hnsw_index_uuid_from_segment, quantization_from_segment,
+    DistributedHNSWSegmentFromSegmentError, DistributedHNSWSegmentReader,
 };
-use crate::sysdb::sysdb::{GetCollectionsError, GetSegmentsError, SysDb};
+use crate::segment::offset_id_cache::{OffsetIdCache, SharedOffsetIdCache};
+use crate::sysdb::sysdb::SysDb;
 use crate::system::{ComponentContext, ComponentHandle, System};
 use crate::{
     log::log::Log,
@@ -37,15 +39,17 @@ use crate::{
 };
 use async_trait::async_trait;
 use chroma_blockstore::provider::BlockfileProvider;
-use chroma_distance::DistanceFunction;
+use chroma_distance::{DistanceAccumulation, DistanceFunction};
 use chroma_error::{ChromaError, ErrorCodes};
 use chroma_index::hnsw_provider::HnswIndexProvider;
 use chroma_index::IndexConfig;
-use chroma_types::{Chunk, Collection, CollectionUuid, LogRecord, Segment, VectorQueryResult};
+use chroma_types::{
+    Chunk, Collection, CollectionUuid, LogRecord, Segment, SegmentUuid, VectorQueryResult,
+};
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use thiserror::Error;
 use tracing::{trace, Span};
 use uuid::Uuid;
@@ -79,30 +83,35 @@ enum ExecutionState {
 #[derive(Error, Debug)]
 #[allow(dead_code)]
 enum HnswSegmentQueryError {
-    #[error(transparent)]
-    GetByIdError(#[from] super::common::GetHnswSegmentByIdError),
-    #[error("Get segments error: {0}")]
-    GetSegmentsError(#[from] GetSegmentsError),
-    #[error("Get collection error: {0}")]
-    GetCollectionError(#[from] GetCollectionsError),
     #[error("Collection has no dimension set")]
     CollectionHasNoDimension,
-    #[error("Collection version mismatch")]
-    CollectionVersionMismatch,
+    #[error("The log of the collection has more than {0} records that are not compacted")]
+    LogBacklogExceeded(u32),
+    #[error("A minimum score requires a cosine or inner product space, the collection is {0:?}")]
+    MinScoreUnsupported(DistanceFunction),
 }
 
 impl ChromaError for HnswSegmentQueryError {
     fn code(&self) -> ErrorCodes {
         match self {
-            HnswSegmentQueryError::GetByIdError(e) => e.code(),
-            HnswSegmentQueryError::GetSegmentsError(_) => ErrorCodes::Internal,
-            HnswSegmentQueryError::GetCollectionError(_) => ErrorCodes::Internal,
             HnswSegmentQueryError::CollectionHasNoDimension => ErrorCodes::InvalidArgument,
-            HnswSegmentQueryError::CollectionVersionMismatch => ErrorCodes::VersionMismatch,
+            HnswSegmentQueryError::LogBacklogExceeded(_) => ErrorCodes::FailedPrecondition,
+            HnswSegmentQueryError::MinScoreUnsupported(_) => ErrorCodes::InvalidArgument,
         }
     }
 }
 
+#[derive(Debug)]
+pub(crate) struct HnswQueryOrchestratorOutput {
+    pub(crate) results: Vec<Vec<VectorQueryResult>>,
+    /// Whether the HNSW index could not be loaded, so that the record segment was searched
+    /// by brute force instead
+    pub(crate) degraded: bool,
+    /// Whether the time limit cut the search of the logs or of the record segment short, so
+    /// that the results are the nearest among the records searched
+    pub(crate) truncated: bool,
+}
+
 #[derive(Debug)]
 pub(crate) struct HnswQueryOrchestrator {
     state: ExecutionState,
@@ -130,6 +139,8 @@ pub(crate) struct HnswQueryOrchestrator {
     merge_task_id_to_query_index: HashMap<Uuid, usize>,
     // Result state
     results: Option<Vec<Vec<VectorQueryResult>>>,
+    degraded: bool,
+    truncated: bool,
     // State machine management
     merge_dependency_count: u32,
     finish_dependency_count: u32,
@@ -142,11 +153,35 @@ pub(crate) struct HnswQueryOrchestrator {
     // Result channel
     #[allow(clippy::type_complexity)]
     result_channel: Option<
-        tokio::sync::oneshot::Sender<Result<Vec<Vec<VectorQueryResult>>, Box<dyn ChromaError>>>,
+        tokio::sync::oneshot::Sender<Result<HnswQueryOrchestratorOutput, Box<dyn ChromaError>>>,
     >,
     // Request version context
     collection_version: u32,
     log_position: u64,
+    // The maximum number of logs past the log position that the query materializes
+    max_log_backlog: Option<u32>,
+    // Offset ids of the allowed ids, shared by the searches of all query vectors
+    offset_id_cache: OffsetIdCache,
+    // The precision in which the distances are accumulated
+    accumulation: DistanceAccumulation,
+    // The relevance threshold of the results. The minimum score is converted into a maximum
+    // distance once the distance function of the index is known
+    max_distance: Option<f32>,
+    min_score: Option<f32>,
+    // The time after which the brute force searches return the nearest records found so far
+    time_limit: Option<Duration>,
+    // The plan of the query, the pull logs task, and the search tasks that the merge of each
+    // query vector consumes
+    plan: QueryPlanRecorder,
+    pull_logs_task: Option<Uuid>,
+    merge_plan_inputs: HashMap<usize, Vec<Uuid>>,
+    // Where the merges spill their results once they exceed the budget
+    spill: Option<SpillConfig>,
+    // The deadline of the query, shared by the searches
+    deadline: Deadline,
+    // The pool that admits the query, and the memory of the query shared by the searches
+    memory_pool: MemoryPool,
+    memory: MemoryContext,
 }
 
 #[allow(dead_code)]
@@ -205,6 +240,8 @@ impl HnswQueryOrchestrator {
             brute_force_task_id_to_query_index: HashMap::new(),
             merge_task_id_to_query_index: HashMap::new(),
             results,
+            degraded: false,
+            truncated: false,
             log,
             sysdb,
             dispatcher,
@@ -213,9 +250,91 @@ impl HnswQueryOrchestrator {
             result_channel: None,
             collection_version,
             log_position,
+            max_log_backlog: None,
+            offset_id_cache: OffsetIdCache::default(),
+            accumulation: DistanceAccumulation::default(),
+            max_distance: None,
+            min_score: None,
+            time_limit: None,
+            plan: QueryPlanRecorder::default(),
+            pull_logs_task: None,
+            merge_plan_inputs: HashMap::new(),
+            spill: None,
+            deadline: Deadline::default(),
+            memory_pool: MemoryPool::default(),
+            memory: MemoryContext::default(),
         }
     }
 
+    /// Resolves the allowed ids through the shared cache if provided, and fills it with the ids
+    /// that it misses, so that later queries for the same ids skip the record segment
+    pub(crate) fn with_offset_id_cache(mut self, shared: Option<SharedOffsetIdCache>) -> Self {
+        self.offset_id_cache = OffsetIdCache::new(shared);
+        self
+    }
+
+    /// Fails the query with a stale error instead of materializing the logs if the collection
+    /// has more than the given number of logs past the log position
+    pub(crate) fn with_max_log_backlog(mut self, max_log_backlog: Option<u32>) -> Self {
+        self.max_log_backlog = max_log_backlog;
+        self
+    }
+
+    /// Accumulates the distances to the records in the given precision. The distances of the
+    /// HNSW index are rescored unless they are accumulated in single precision
+    pub(crate) fn with_distance_accumulation(mut self, accumulation: DistanceAccumulation) -> Self {
+        self.accumulation = accumulation;
+        self
+    }
+
+    /// Drops the records further than the maximum distance from the query vector, and the
+    /// records whose similarity score `1 - distance` is below the minimum score, from both the
+    /// results of the index and of the logs before they are merged. A minimum score is only
+    /// supported by the cosine and inner product spaces
+    pub(crate) fn with_relevance_threshold(
+        mut self,
+        max_distance: Option<f32>,
+        min_score: Option<f32>,
+    ) -> Self {
+        self.max_distance = max_distance;
+        self.min_score = min_score;
+        self
+    }
+
+    /// Stops the brute force searches of the logs and of the record segment once the time
+    /// limit is reached, and returns the nearest records among those searched instead of
+    /// failing the query
+    pub(crate) fn with_time_limit(mut self, time_limit: Option<Duration>) -> Self {
+        self.time_limit = time_limit;
+        self
+    }
+
+    /// Records the plan of the query as it runs, which the caller reads from the recorder
+    /// once the query completes
+    pub(crate) fn with_plan(mut self, plan: QueryPlanRecorder) -> Self {
+        self.plan = plan;
+        self
+    }
+
+    /// Spills the merged results of each query vector to disk once they exceed the budget
+    pub(crate) fn with_spill(mut self, spill: Option<SpillConfig>) -> Self {
+        self.spill = spill;
+        self
+    }
+
+    /// Stops the searches of the query once the deadline is reached
+    pub(crate) fn with_deadline(mut self, deadline: Deadline) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// Admits the query only while the memory held by the running queries is below the limit
+    /// of the pool, and fails it once it exceeds the budget of a query
+    pub(crate) fn with_memory_pool(mut self, memory_pool: MemoryPool) -> Self {
+        self.memory_pool = memory_pool;
+        self
+    }
+
     async fn pull_logs(
         &mut self,
         self_address: Box<dyn ReceiverForMessage<TaskResult<PullLogsOutput, PullLogsError>>>,
@@ -244,10 +363,13 @@ impl HnswQueryOrchestrator {
             // TODO: We should make all the log service code use u64 instead of i64
             (self.log_position as i64) + 1,
             100,
-            None,
+            // One log past the maximum backlog is enough to tell that it is exceeded
+            self.max_log_backlog
+                .map(|backlog| i32::try_from(backlog.saturating_add(1)).unwrap_or(i32::MAX)),
             Some(end_timestamp),
         );
         let task = wrap(operator, input, self_address);
+        self.plan.start(task.id(), task.get_name(), [], None);
         // Wrap the task with current span as the parent. The worker then executes it
         // inside a child span with this parent.
         match self.dispatcher.send(task, Some(Span::current())).await {
@@ -281,7 +403,10 @@ impl HnswQueryOrchestrator {
                 log: logs.clone(),
                 query: query_vector.clone(),
                 k: self.k as usize,
+                max_distance: self.max_distance,
                 distance_metric: distance_function.clone(),
+                accumulation: self.accumulation,
+                time_limit: self.time_limit,
                 allowed_ids: self.allowed_ids.clone(),
                 record_segment_definition: self
                     .record_segment
@@ -289,10 +414,19 @@ impl HnswQueryOrchestrator {
                     .expect("Invariant violation. Record segment is not set")
                     .clone(),
                 blockfile_provider: self.blockfile_provider.clone(),
+                deadline: self.deadline.clone(),
+                memory: self.memory.clone(),
             };
             let operator = Box::new(BruteForceKnnOperator {});
             let task = wrap(operator, bf_input, self_address.clone());
             self.brute_force_task_id_to_query_index.insert(task.id(), i);
+            self.plan.start(
+                task.id(),
+                task.get_name(),
+                self.pull_logs_task,
+                Some(logs.len().min(self.k as usize) as u64),
+            );
+            self.merge_plan_inputs.entry(i).or_default().push(task.id());
             match self.dispatcher.send(task, Some(Span::current())).await {
                 Ok(_) => (),
                 Err(e) => {
@@ -326,11 +460,23 @@ impl HnswQueryOrchestrator {
         )
         .await
         {
-            Ok(reader) => reader,
+            Ok(reader) => Some(reader),
             Err(e) => {
                 match *e {
+                    // If the index fails to load, the query falls back to searching the record
+                    // segment by brute force, while the local files of the index are repaired
+                    DistributedHNSWSegmentFromSegmentError::HnswIndexProviderOpenError(_) => {
+                        tracing::warn!("[HnswQueryOperation]: Falling back to brute force search, since the HNSW index failed to load {:?}", *e);
+                        if let Some(index_uuid) = hnsw_index_uuid_from_segment(hnsw_segment) {
+                            self.hnsw_index_provider.repair(index_uuid);
+                        }
+                        self.degraded = true;
+                        self.plan.set_strategy("index", "brute_force");
+                        None
+                    }
                     DistributedHNSWSegmentFromSegmentError::Uninitialized => {
                         tracing::info!("[HnswQueryOperation]: Uninitialied reader {:?}", *e);
+                        self.plan.set_strategy("index", "uninitialized");
                         // no task, decrement the merge dependency count and return
                         // with an empty result
                         for (i, _) in self.query_vectors.iter().enumerate() {
@@ -349,6 +495,9 @@ impl HnswQueryOrchestrator {
             }
         };
 
+        if hnsw_segment_reader.is_some() {
+            self.plan.set_strategy("index", "hnsw");
+        }
         let record_segment = self
             .record_segment
             .as_ref()
@@ -359,15 +508,34 @@ impl HnswQueryOrchestrator {
             let operator = Box::new(HnswKnnOperator {});
             let input = HnswKnnOperatorInput {
                 segment: hnsw_segment_reader.clone(),
+                distance_function: self
+                    .index_config
+                    .as_ref()
+                    .expect("Invariant violation. Index config is not set")
+                    .distance_function
+                    .clone(),
+                accumulation: self.accumulation,
                 query: query_vector.clone(),
                 k: self.k as usize,
+                max_distance: self.max_distance,
+                time_limit: self.time_limit,
                 record_segment: record_segment.clone(),
                 blockfile_provider: self.blockfile_provider.clone(),
                 allowed_ids: self.allowed_ids.clone(),
                 logs: logs.clone(),
+                offset_id_cache: self.offset_id_cache.clone(),
+                deadline: self.deadline.clone(),
+                memory: self.memory.clone(),
             };
             let task = wrap(operator, input, ctx.receiver());
             self.hnsw_task_id_to_query_index.insert(task.id(), i);
+            self.plan.start(
+                task.id(),
+                task.get_name(),
+                self.pull_logs_task,
+                Some(self.k as u64),
+            );
+            self.merge_plan_inputs.entry(i).or_default().push(task.id());
             match self.dispatcher.send(task, Some(Span::current())).await {
                 Ok(_) => (),
                 Err(e) => {
@@ -483,7 +651,9 @@ impl HnswQueryOrchestrator {
             &hnsw_result_offset_ids,
         );
 
-        let operator = Box::new(MergeKnnResultsOperator {});
+        let operator = Box::new(MergeKnnResultsOperator {
+            spill: self.spill.clone(),
+        });
         let input = MergeKnnResultsOperatorInput::new(
             hnsw_result_offset_ids,
             hnsw_result_distances,
@@ -501,6 +671,14 @@ impl HnswQueryOrchestrator {
         let task = wrap(operator, input, ctx.receiver());
         self.merge_task_id_to_query_index
             .insert(task.id(), query_vector_index);
+        self.plan.start(
+            task.id(),
+            task.get_name(),
+            self.merge_plan_inputs
+                .remove(&query_vector_index)
+                .unwrap_or_default(),
+            Some(self.k as u64),
+        );
         match self.dispatcher.send(task, Some(Span::current())).await {
             Ok(_) => (),
             Err(e) => {
@@ -519,7 +697,11 @@ impl HnswQueryOrchestrator {
         for _ in 0..self.query_vectors.len() {
             empty_resp.push(vec![]);
         }
-        match result_channel.send(Ok(empty_resp)) {
+        match result_channel.send(Ok(HnswQueryOrchestratorOutput {
+            results: empty_resp,
+            degraded: self.degraded,
+            truncated: self.truncated,
+        })) {
             Ok(_) => (),
             Err(_) => {
                 // Log an error - this implied the listener was dropped
@@ -536,7 +718,13 @@ impl HnswQueryOrchestrator {
     ///  # Note
     ///  Use this over spawning the component directly. This method will start the component and
     ///  wait for it to finish before returning the result.
-    pub(crate) async fn run(mut self) -> Result<Vec<Vec<VectorQueryResult>>, Box<dyn ChromaError>> {
+    pub(crate) async fn run(mut self) -> Result<HnswQueryOrchestratorOutput, Box<dyn ChromaError>> {
+        // The searches stop early if the caller stops waiting for the result
+        let _cancel_on_drop = self.deadline.cancel_on_drop();
+        self.memory = self
+            .memory_pool
+            .admit()
+            .map_err(|e| Box::new(e) as Box<dyn ChromaError>)?;
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.result_channel = Some(tx);
         let mut handle = self.system.clone().start_component(self);
@@ -559,41 +747,32 @@ impl Component for HnswQueryOrchestrator {
     }
 
     async fn on_start(&mut self, ctx: &crate::system::ComponentContext<Self>) -> () {
-        // Populate the orchestrator with the initial state - The HNSW Segment, The Record Segment and the Collection
-        let hnsw_segment = match get_hnsw_segment_by_id(
-            self.sysdb.clone(),
-            &self.hnsw_segment_id,
-            &self.collection_id,
-        )
+        // Pin the collection and its segments at the version of the request, every reader of
+        // the query is created from this snapshot
+        let FetchSegmentOutput {
+            collection,
+            record_segment,
+            vector_segment: hnsw_segment,
+            ..
+        } = match (FetchSegmentOperator {
+            sysdb: self.sysdb.clone(),
+            collection_uuid: self.collection_id,
+            collection_version: self.collection_version,
+            metadata_uuid: None,
+            record_uuid: None,
+            vector_uuid: Some(SegmentUuid(self.hnsw_segment_id)),
+            cache_invalidator: None,
+        })
+        .fetch()
         .await
         {
-            Ok(segment) => segment,
+            Ok(output) => output,
             Err(e) => {
-                terminate_with_error(self.result_channel.take(), e, ctx);
-                return;
-            }
-        };
-
-        let collection_id = &hnsw_segment.collection;
-
-        let collection = match get_collection_by_id(self.sysdb.clone(), collection_id).await {
-            Ok(collection) => collection,
-            Err(e) => {
-                terminate_with_error(self.result_channel.take(), e, ctx);
+                terminate_with_error(self.result_channel.take(), Box::new(e), ctx);
                 return;
             }
         };
 
-        // If the collection version does not match the request version then we terminate with an error
-        if collection.version as u32 != self.collection_version {
-            terminate_with_error(
-                self.result_channel.take(),
-                Box::new(HnswSegmentQueryError::CollectionVersionMismatch),
-                ctx,
-            );
-            return;
-        }
-
         // If segment is uninitialized and dimension is not set then we assume
         // that this is a query before any add so return empty response.
         if hnsw_segment.file_path.is_empty() && collection.dimension.is_none() {
@@ -612,15 +791,6 @@ impl Component for HnswQueryOrchestrator {
             return;
         };
 
-        let record_segment =
-            match get_record_segment_by_collection_id(self.sysdb.clone(), collection_id).await {
-                Ok(segment) => segment,
-                Err(e) => {
-                    terminate_with_error(self.result_channel.take(), e, ctx);
-                    return;
-                }
-            };
-
         let distance_function = match distance_function_from_segment(&hnsw_segment) {
             Ok(distance_function) => distance_function,
             Err(e) => {
@@ -628,9 +798,31 @@ impl Component for HnswQueryOrchestrator {
                 return;
             }
         };
+        if let Some(min_score) = self.min_score {
+            match distance_function {
+                DistanceFunction::Cosine | DistanceFunction::InnerProduct => {
+                    let max_distance = 1.0 - min_score;
+                    self.max_distance = Some(
+                        self.max_distance
+                            .map_or(max_distance, |distance| distance.min(max_distance)),
+                    );
+                }
+                DistanceFunction::Euclidean => {
+                    terminate_with_error(
+                        self.result_channel.take(),
+                        Box::new(HnswSegmentQueryError::MinScoreUnsupported(
+                            distance_function,
+                        )),
+                        ctx,
+                    );
+                    return;
+                }
+            }
+        }
         self.index_config = Some(IndexConfig::new(
             collection.dimension.unwrap(),
             distance_function,
+            quantization_from_segment(&hnsw_segment),
         ));
         // Normalize the query vectors if we are using the cosine similarity
         if self.index_config.as_ref().unwrap().distance_function == DistanceFunction::Cosine {
@@ -643,6 +835,10 @@ impl Component for HnswQueryOrchestrator {
         self.hnsw_segment = Some(hnsw_segment);
         self.collection = Some(collection);
 
+        // The allowed ids restrict the candidates of the search, rather than its results
+        if !self.allowed_ids.is_empty() {
+            self.plan.set_strategy("filter", "filter_first");
+        }
         self.pull_logs(ctx.receiver()).await;
     }
 }
@@ -658,12 +854,27 @@ impl Handler<TaskResult<PullLogsOutput, PullLogsError>> for HnswQueryOrchestrato
         message: TaskResult<PullLogsOutput, PullLogsError>,
         ctx: &crate::system::ComponentContext<HnswQueryOrchestrator>,
     ) {
+        let task_id = message.id();
+        let read_stats = message.read_stats();
         let message = message.into_inner();
         self.state = ExecutionState::Partition;
 
         match message {
             Ok(pull_logs_output) => {
                 let logs = pull_logs_output.logs();
+                self.plan
+                    .finish(task_id, read_stats, Some(logs.len() as u64));
+                if let Some(backlog) = self.max_log_backlog {
+                    if logs.len() > backlog as usize {
+                        terminate_with_error(
+                            self.result_channel.take(),
+                            Box::new(HnswSegmentQueryError::LogBacklogExceeded(backlog)),
+                            ctx,
+                        );
+                        return;
+                    }
+                }
+                self.pull_logs_task = Some(task_id);
                 if !logs.is_empty() {
                     self.brute_force_query(logs.clone(), ctx.receiver()).await;
                 } else {
@@ -692,6 +903,7 @@ impl Handler<TaskResult<BruteForceKnnOperatorOutput, BruteForceKnnOperatorError>
         ctx: &crate::system::ComponentContext<HnswQueryOrchestrator>,
     ) {
         let task_id = message.id();
+        let read_stats = message.read_stats();
         let message = message.into_inner();
         let query_index = self
             .brute_force_task_id_to_query_index
@@ -700,6 +912,9 @@ impl Handler<TaskResult<BruteForceKnnOperatorOutput, BruteForceKnnOperatorError>
 
         match message {
             Ok(output) => {
+                self.plan
+                    .finish(task_id, read_stats, Some(output.user_ids.len() as u64));
+                self.truncated |= output.truncated;
                 self.brute_force_results.insert(query_index, output);
             }
             Err(e) => {
@@ -726,6 +941,7 @@ impl Handler<TaskResult<HnswKnnOperatorOutput, Box<dyn ChromaError>>> for HnswQu
         ctx: &ComponentContext<Self>,
     ) {
         let task_id = message.id();
+        let read_stats = message.read_stats();
         let message = message.into_inner();
         let query_index = self
             .hnsw_task_id_to_query_index
@@ -733,6 +949,9 @@ impl Handler<TaskResult<HnswKnnOperatorOutput, Box<dyn ChromaError>>> for HnswQu
             .expect("Invariant violation. HNSW task id is not set for query vector index");
         match message {
             Ok(output) => {
+                self.plan
+                    .finish(task_id, read_stats, Some(output.offset_ids.len() as u64));
+                self.truncated |= output.truncated;
                 self.hnsw_result_offset_ids
                     .insert(query_index, output.offset_ids);
                 self.hnsw_result_distances
@@ -764,6 +983,7 @@ impl Handler<TaskResult<MergeKnnResultsOperatorOutput, Box<dyn ChromaError>>>
         ctx: &crate::system::ComponentContext<HnswQueryOrchestrator>,
     ) {
         let task_id = message.id();
+        let read_stats = message.read_stats();
         let message = message.into_inner();
         let query_index = self
             .merge_task_id_to_query_index
@@ -773,7 +993,11 @@ impl Handler<TaskResult<MergeKnnResultsOperatorOutput, Box<dyn ChromaError>>>
         self.state = ExecutionState::Finished;
 
         let (mut output_ids, mut output_distances, output_vectors) = match message {
-            Ok(output) => (output.user_ids, output.distances, output.vectors),
+            Ok(output) => {
+                self.plan
+                    .finish(task_id, read_stats, Some(output.user_ids.len() as u64));
+                (output.user_ids, output.distances, output.vectors)
+            }
             Err(e) => {
                 terminate_with_error(self.result_channel.take(), e.boxed(), ctx);
                 return;
@@ -834,11 +1058,14 @@ impl Handler<TaskResult<MergeKnnResultsOperatorOutput, Box<dyn ChromaError>>>
                     .set_len(self.query_vectors.len());
             }
 
-            match result_channel.send(Ok(self
-                .results
-                .take()
-                .expect("Invariant violation. Results are not set")))
-            {
+            match result_channel.send(Ok(HnswQueryOrchestratorOutput {
+                results: self
+                    .results
+                    .take()
+                    .expect("Invariant violation. Results are not set"),
+                degraded: self.degraded,
+                truncated: self.truncated,
+            })) {
                 Ok(_) => (),
                 Err(_) => {
                     // Log an error
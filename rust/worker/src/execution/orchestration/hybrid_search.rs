@@ -0,0 +1,356 @@
+use std::collections::HashMap;
+
+use chroma_blockstore::provider::BlockfileProvider;
+use chroma_index::hnsw_provider::HnswIndexProvider;
+use tokio::sync::oneshot::{self, Sender};
+use tonic::async_trait;
+use tracing::Span;
+
+use crate::{
+    execution::{
+        dispatcher::Dispatcher,
+        operator::{wrap, Deadline, TaskResult},
+        operators::{
+            hybrid_search::{
+                HybridScore, HybridSearchError, HybridSearchInput, HybridSearchOperator,
+                HybridSearchOutput,
+            },
+            knn::RecordDistance,
+            knn_projection::{
+                KnnProjectionError, KnnProjectionInput, KnnProjectionOperator, KnnProjectionOutput,
+            },
+            prefetch_record::{
+                PrefetchRecordError, PrefetchRecordInput, PrefetchRecordOperator,
+                PrefetchRecordOutput,
+            },
+            projection::{ProjectionDefaults, ProjectionRecord},
+        },
+        orchestration::{
+            common::terminate_with_error,
+            knn::{distance_function, KnnError, KnnFilterOutput},
+        },
+    },
+    system::{Component, ComponentContext, ComponentHandle, Handler, System},
+};
+
+#[derive(Clone, Debug)]
+pub struct HybridSearchRecord {
+    pub record: ProjectionRecord,
+    pub score: f32,
+    pub text_score: Option<f32>,
+    pub distance: Option<f32>,
+}
+
+#[derive(Debug)]
+pub struct HybridSearchOrchestratorOutput {
+    pub records: Vec<HybridSearchRecord>,
+    pub truncated: bool,
+}
+
+type HybridSearchResult = Result<HybridSearchOrchestratorOutput, KnnError>;
+
+/// The `HybridSearchOrchestrator` searches for the records that best match both a full-text
+/// query and a target embedding. Like the `KnnOrchestrator`, it takes the output of a
+/// `KnnFilterOrchestrator`, which evaluates the filter of the query
+///
+/// # Pipeline
+/// ```text
+///  ┌────────────┐
+///  │            │
+///  │  on_start  │
+///  │            │
+///  └─────┬──────┘
+///        │
+///        ▼
+///  ┌────────────────────────┐
+///  │                        │
+///  │  HybridSearchOperator  │
+///  │                        │
+///  └─────┬──────────────────┘
+///        │
+///        ▼
+///  ┌─────────────────────────┐
+///  │                         │
+///  │  KnnProjectionOperator  │
+///  │                         │
+///  └─────┬───────────────────┘
+///        │
+///        ▼
+///  ┌──────────────────┐
+///  │                  │
+///  │  result_channel  │
+///  │                  │
+///  └──────────────────┘
+/// ```
+///
+/// The records are prefetched before the projection, like in the `KnnOrchestrator`. The
+/// projected records keep the fused scores, the full-text scores and the distances of the
+/// `HybridSearchOperator`
+#[derive(Debug)]
+pub struct HybridSearchOrchestrator {
+    // Orchestrator parameters
+    blockfile_provider: BlockfileProvider,
+    dispatcher: ComponentHandle<Dispatcher>,
+    hnsw_provider: HnswIndexProvider,
+    queue: usize,
+
+    // Output from KnnFilterOrchestrator
+    knn_filter_output: KnnFilterOutput,
+
+    // Search and project
+    hybrid_search: HybridSearchOperator,
+    knn_projection: KnnProjectionOperator,
+
+    // Hybrid search output
+    hybrid_scores: Vec<HybridScore>,
+    hybrid_search_truncated: bool,
+
+    // The deadline of the query, shared by the operators
+    deadline: Deadline,
+
+    // Result channel
+    result_channel: Option<Sender<HybridSearchResult>>,
+}
+
+impl HybridSearchOrchestrator {
+    pub fn new(
+        blockfile_provider: BlockfileProvider,
+        dispatcher: ComponentHandle<Dispatcher>,
+        hnsw_provider: HnswIndexProvider,
+        queue: usize,
+        knn_filter_output: KnnFilterOutput,
+        hybrid_search: HybridSearchOperator,
+        knn_projection: KnnProjectionOperator,
+    ) -> Self {
+        Self {
+            blockfile_provider,
+            dispatcher,
+            hnsw_provider,
+            queue,
+            knn_filter_output,
+            hybrid_search,
+            knn_projection,
+            hybrid_scores: Vec::new(),
+            hybrid_search_truncated: false,
+            deadline: Deadline::default(),
+            result_channel: None,
+        }
+    }
+
+    /// Stops the vector search and projection once the deadline is reached
+    pub fn with_deadline(mut self, deadline: Deadline) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    pub async fn run(mut self, system: System) -> HybridSearchResult {
+        // The operators stop early if the caller stops waiting for the result
+        let _cancel_on_drop = self.deadline.cancel_on_drop();
+        let (tx, rx) = oneshot::channel();
+        self.result_channel = Some(tx);
+        let mut handle = system.start_component(self);
+        let result = rx.await;
+        handle.stop();
+        result?
+    }
+
+    fn terminate_with_error<E>(&mut self, ctx: &ComponentContext<Self>, err: E)
+    where
+        E: Into<KnnError>,
+    {
+        let knn_err = err.into();
+        tracing::error!("Error running orchestrator: {}", &knn_err);
+        terminate_with_error(self.result_channel.take(), knn_err, ctx);
+    }
+}
+
+#[async_trait]
+impl Component for HybridSearchOrchestrator {
+    fn get_name() -> &'static str {
+        "Hybrid Search Orchestrator"
+    }
+
+    fn queue_size(&self) -> usize {
+        self.queue
+    }
+
+    async fn on_start(&mut self, ctx: &ComponentContext<Self>) {
+        let segments = &self.knn_filter_output.segments;
+        let distance_function = match distance_function(&segments.vector_segment) {
+            Ok(func) => func,
+            Err(err) => {
+                self.terminate_with_error(ctx, err);
+                return;
+            }
+        };
+        let collection_dimension = match segments.collection.dimension {
+            Some(dimension) => dimension as u32,
+            None => {
+                self.terminate_with_error(ctx, KnnError::NoCollectionDimension);
+                return;
+            }
+        };
+
+        let task = wrap(
+            Box::new(self.hybrid_search.clone()),
+            HybridSearchInput {
+                logs: self.knn_filter_output.logs.clone(),
+                blockfile_provider: self.blockfile_provider.clone(),
+                hnsw_provider: self.hnsw_provider.clone(),
+                metadata_segment: segments.metadata_segment.clone(),
+                record_segment: segments.record_segment.clone(),
+                vector_segment: segments.vector_segment.clone(),
+                collection_dimension,
+                log_offset_ids: self.knn_filter_output.filter_output.log_offset_ids.clone(),
+                compact_offset_ids: self
+                    .knn_filter_output
+                    .filter_output
+                    .compact_offset_ids
+                    .clone(),
+                distance_function,
+                deadline: self.deadline.clone(),
+            },
+            ctx.receiver(),
+        );
+        if let Err(err) = self.dispatcher.send(task, Some(Span::current())).await {
+            self.terminate_with_error(ctx, err);
+        }
+    }
+}
+
+#[async_trait]
+impl Handler<TaskResult<HybridSearchOutput, HybridSearchError>> for HybridSearchOrchestrator {
+    type Result = ();
+
+    async fn handle(
+        &mut self,
+        message: TaskResult<HybridSearchOutput, HybridSearchError>,
+        ctx: &ComponentContext<Self>,
+    ) {
+        let output = match message.into_inner() {
+            Ok(output) => output,
+            Err(err) => {
+                self.terminate_with_error(ctx, err);
+                return;
+            }
+        };
+        self.hybrid_search_truncated = output.truncated;
+
+        // Prefetch records before projection
+        let offset_ids = output
+            .records
+            .iter()
+            .map(|record| record.offset_id)
+            .collect();
+        let prefetch_task = wrap(
+            Box::new(PrefetchRecordOperator {}),
+            PrefetchRecordInput {
+                logs: self.knn_filter_output.logs.clone(),
+                blockfile_provider: self.blockfile_provider.clone(),
+                record_segment: self.knn_filter_output.segments.record_segment.clone(),
+                offset_ids,
+            },
+            ctx.receiver(),
+        );
+        if let Err(err) = self
+            .dispatcher
+            .send(prefetch_task, Some(Span::current()))
+            .await
+        {
+            self.terminate_with_error(ctx, err);
+        }
+
+        // The projection keeps the order of the records, which are sorted by the fused score
+        let record_distances = output
+            .records
+            .iter()
+            .map(|record| RecordDistance {
+                offset_id: record.offset_id,
+                measure: record.score,
+            })
+            .collect();
+        self.hybrid_scores = output.records;
+        let projection_task = wrap(
+            Box::new(self.knn_projection.clone()),
+            KnnProjectionInput {
+                logs: self.knn_filter_output.logs.clone(),
+                blockfile_provider: self.blockfile_provider.clone(),
+                record_segment: self.knn_filter_output.segments.record_segment.clone(),
+                record_distances,
+                projection_defaults: ProjectionDefaults::from(
+                    &self.knn_filter_output.segments.collection,
+                ),
+                deadline: self.deadline.clone(),
+                memory: self.knn_filter_output.memory.clone(),
+                materialized_logs: self.knn_filter_output.materialized_logs.clone(),
+            },
+            ctx.receiver(),
+        );
+        if let Err(err) = self
+            .dispatcher
+            .send(projection_task, Some(Span::current()))
+            .await
+        {
+            self.terminate_with_error(ctx, err);
+        }
+    }
+}
+
+#[async_trait]
+impl Handler<TaskResult<PrefetchRecordOutput, PrefetchRecordError>> for HybridSearchOrchestrator {
+    type Result = ();
+
+    async fn handle(
+        &mut self,
+        _message: TaskResult<PrefetchRecordOutput, PrefetchRecordError>,
+        _ctx: &ComponentContext<Self>,
+    ) {
+        // The output and error from `PrefetchRecordOperator` are ignored
+    }
+}
+
+#[async_trait]
+impl Handler<TaskResult<KnnProjectionOutput, KnnProjectionError>> for HybridSearchOrchestrator {
+    type Result = ();
+
+    async fn handle(
+        &mut self,
+        message: TaskResult<KnnProjectionOutput, KnnProjectionError>,
+        ctx: &ComponentContext<Self>,
+    ) {
+        let output = match message.into_inner() {
+            Ok(output) => output,
+            Err(err) => {
+                self.terminate_with_error(ctx, err);
+                return;
+            }
+        };
+        let mut hybrid_scores = self
+            .hybrid_scores
+            .drain(..)
+            .map(|hybrid_score| (hybrid_score.offset_id, hybrid_score))
+            .collect::<HashMap<_, _>>();
+        let records = output
+            .records
+            .into_iter()
+            .filter_map(|projected| {
+                let hybrid_score = hybrid_scores.remove(&projected.record.offset_id)?;
+                Some(HybridSearchRecord {
+                    record: projected.record,
+                    score: hybrid_score.score,
+                    text_score: hybrid_score.text_score,
+                    distance: hybrid_score.distance,
+                })
+            })
+            .collect();
+        let truncated = output.truncated || self.hybrid_search_truncated;
+        if let Some(chan) = self.result_channel.take() {
+            if chan
+                .send(Ok(HybridSearchOrchestratorOutput { records, truncated }))
+                .is_err()
+            {
+                tracing::error!("Error sending final result");
+            };
+        }
+    }
+}
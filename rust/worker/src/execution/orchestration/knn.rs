@@ -1,8 +1,8 @@
 use chroma_blockstore::provider::BlockfileProvider;
 use chroma_distance::{DistanceFunction, DistanceFunctionError};
-use chroma_error::{ChromaError, ErrorCodes};
+use chroma_error::{ChromaError, ErrorCodes, ErrorLocation};
 use chroma_index::hnsw_provider::HnswIndexProvider;
-use chroma_types::MetadataValue;
+use chroma_types::{MetadataValue, Segment};
 use thiserror::Error;
 use tokio::sync::oneshot::{self, error::RecvError, Sender};
 use tonic::async_trait;
@@ -11,11 +11,13 @@ use tracing::Span;
 use crate::{
     execution::{
         dispatcher::Dispatcher,
-        operator::{wrap, TaskError, TaskResult},
+        memory::{MemoryContext, MemoryError, MemoryPool},
+        operator::{wrap, Deadline, TaskError, TaskResult},
         operators::{
-            fetch_log::{FetchLogError, FetchLogOperator, FetchLogOutput},
+            fetch_log::{FetchLogError, FetchLogInput, FetchLogOperator, FetchLogOutput},
             fetch_segment::{FetchSegmentError, FetchSegmentOperator, FetchSegmentOutput},
             filter::{FilterError, FilterInput, FilterOperator, FilterOutput},
+            hybrid_search::HybridSearchError,
             knn::{KnnOperator, RecordDistance},
             knn_hnsw::{KnnHnswError, KnnHnswInput, KnnHnswOutput},
             knn_log::{KnnLogError, KnnLogInput, KnnLogOutput},
@@ -23,13 +25,16 @@ use crate::{
             knn_projection::{
                 KnnProjectionError, KnnProjectionInput, KnnProjectionOperator, KnnProjectionOutput,
             },
+            mmr::{MmrError, MmrInput, MmrOperator, MmrOutput},
             prefetch_record::{
                 PrefetchRecordError, PrefetchRecordInput, PrefetchRecordOperator,
                 PrefetchRecordOutput,
             },
+            projection::ProjectionDefaults,
         },
         orchestration::common::terminate_with_error,
     },
+    segment::{offset_id_cache::OffsetIdCache, SharedMaterializedLogs},
     system::{ChannelError, Component, ComponentContext, ComponentHandle, Handler, System},
 };
 
@@ -155,6 +160,10 @@ use crate::{
 /// from either operators, and if both outputs are present it composes the input for `KnnMergeOperator` and
 /// proceeds with execution. The outputs of other operators are directly forwarded without being tracked
 /// by the orchestrator.
+///
+/// If a `MmrOperator` is provided to the `KnnOrchestrator`, the output of `KnnMergeOperator` is re-ranked by
+/// the `MmrOperator` before it is forwarded to `KnnProjectionOperator`. In this case the `fetch` of the
+/// `KnnOperator` should be the number of candidates to re-rank, instead of the number of final results.
 
 #[derive(Error, Debug)]
 pub enum KnnError {
@@ -170,14 +179,22 @@ pub enum KnnError {
     FetchSegment(#[from] FetchSegmentError),
     #[error("Error running Filter Operator: {0}")]
     Filter(#[from] FilterError),
+    #[error("Error running Hybrid Search Operator: {0}")]
+    HybridSearch(#[from] HybridSearchError),
     #[error("Error running Knn Log Operator: {0}")]
     KnnLog(#[from] KnnLogError),
     #[error("Error running Knn Hnsw Operator: {0}")]
     KnnHnsw(#[from] KnnHnswError),
     #[error("Error running Knn Projection Operator: {0}")]
     KnnProjection(#[from] KnnProjectionError),
+    #[error("Error admitting query: {0}")]
+    Memory(#[from] MemoryError),
+    #[error("Error running Mmr Operator: {0}")]
+    Mmr(#[from] MmrError),
     #[error("Error inspecting collection dimension")]
     NoCollectionDimension,
+    #[error("Task aborted: {0}")]
+    Aborted(String),
     #[error("Panic running task: {0}")]
     Panic(String),
     #[error("Error receiving final result: {0}")]
@@ -193,14 +210,33 @@ impl ChromaError for KnnError {
             KnnError::FetchLog(e) => e.code(),
             KnnError::FetchSegment(e) => e.code(),
             KnnError::Filter(e) => e.code(),
+            KnnError::HybridSearch(e) => e.code(),
             KnnError::KnnLog(e) => e.code(),
             KnnError::KnnHnsw(e) => e.code(),
             KnnError::KnnProjection(e) => e.code(),
+            KnnError::Memory(e) => e.code(),
+            KnnError::Mmr(e) => e.code(),
             KnnError::NoCollectionDimension => ErrorCodes::InvalidArgument,
+            KnnError::Aborted(_) => ErrorCodes::Aborted,
             KnnError::Panic(_) => ErrorCodes::Aborted,
             KnnError::Result(_) => ErrorCodes::Internal,
         }
     }
+
+    fn field(&self) -> Option<&str> {
+        match self {
+            KnnError::Filter(e) => e.field(),
+            _ => None,
+        }
+    }
+
+    fn location(&self) -> ErrorLocation {
+        match self {
+            KnnError::Filter(e) => e.location(),
+            KnnError::KnnHnsw(e) => e.location(),
+            _ => ErrorLocation::default(),
+        }
+    }
 }
 
 impl<E> From<TaskError<E>> for KnnError
@@ -211,6 +247,7 @@ where
         match value {
             TaskError::Panic(e) => KnnError::Panic(e.unwrap_or_default()),
             TaskError::TaskFailed(e) => e.into(),
+            TaskError::Aborted(e) => KnnError::Aborted(e),
         }
     }
 }
@@ -220,6 +257,8 @@ pub struct KnnFilterOutput {
     pub logs: FetchLogOutput,
     pub segments: FetchSegmentOutput,
     pub filter_output: FilterOutput,
+    pub memory: MemoryContext,
+    pub materialized_logs: SharedMaterializedLogs,
 }
 
 type KnnFilterResult = Result<KnnFilterOutput, KnnError>;
@@ -239,9 +278,16 @@ pub struct KnnFilterOrchestrator {
     fetch_log_output: Option<FetchLogOutput>,
     fetch_segment_output: Option<FetchSegmentOutput>,
 
+    // The logs materialized by the filter, shared so that the projection does not materialize them
+    materialized_logs: SharedMaterializedLogs,
+
     // Pipelined operators
     filter: FilterOperator,
 
+    // The pool that admits the query, and the memory of the query shared by the operators
+    memory_pool: MemoryPool,
+    memory: MemoryContext,
+
     // Result channel
     result_channel: Option<Sender<KnnFilterResult>>,
 }
@@ -263,12 +309,23 @@ impl KnnFilterOrchestrator {
             fetch_segment,
             fetch_log_output: None,
             fetch_segment_output: None,
+            materialized_logs: SharedMaterializedLogs::default(),
             filter,
+            memory_pool: MemoryPool::default(),
+            memory: MemoryContext::default(),
             result_channel: None,
         }
     }
 
+    /// Admits the query only while the memory held by the running queries is below the limit
+    /// of the pool, and fails the query once it exceeds its budget
+    pub fn with_memory_pool(mut self, memory_pool: MemoryPool) -> Self {
+        self.memory_pool = memory_pool;
+        self
+    }
+
     pub async fn run(mut self, system: System) -> KnnFilterResult {
+        self.memory = self.memory_pool.admit()?;
         let (tx, rx) = oneshot::channel();
         self.result_channel = Some(tx);
         let mut handle = system.start_component(self);
@@ -295,9 +352,14 @@ impl KnnFilterOrchestrator {
                 Box::new(self.filter.clone()),
                 FilterInput {
                     logs: logs.clone(),
+                    fetch_log: None,
                     blockfile_provider: self.blockfile_provider.clone(),
                     metadata_segment: segments.metadata_segment.clone(),
                     record_segment: segments.record_segment.clone(),
+                    materialized_logs: self.materialized_logs.clone(),
+                    memory: self.memory.clone(),
+                    offset_id_cache: OffsetIdCache::default(),
+                    scan_limit: None,
                 },
                 ctx.receiver(),
             );
@@ -319,7 +381,13 @@ impl Component for KnnFilterOrchestrator {
     }
 
     async fn on_start(&mut self, ctx: &ComponentContext<Self>) {
-        let log_task = wrap(Box::new(self.fetch_log.clone()), (), ctx.receiver());
+        let log_task = wrap(
+            Box::new(self.fetch_log.clone()),
+            FetchLogInput {
+                memory: self.memory.clone(),
+            },
+            ctx.receiver(),
+        );
         let segment_task = wrap(Box::new(self.fetch_segment.clone()), (), ctx.receiver());
         if let Err(err) = self.dispatcher.send(log_task, Some(Span::current())).await {
             self.terminate_with_error(ctx, err);
@@ -411,6 +479,8 @@ impl Handler<TaskResult<FilterOutput, FilterError>> for KnnFilterOrchestrator {
                         .take()
                         .expect("FetchSegmentOperator should have finished already"),
                     filter_output: output,
+                    memory: self.memory.clone(),
+                    materialized_logs: self.materialized_logs.clone(),
                 }))
                 .is_err()
             {
@@ -420,6 +490,21 @@ impl Handler<TaskResult<FilterOutput, FilterError>> for KnnFilterOrchestrator {
     }
 }
 
+/// Returns the distance function configured in the metadata of the vector segment, which
+/// defaults to the squared euclidean distance
+pub(super) fn distance_function(
+    vector_segment: &Segment,
+) -> Result<DistanceFunction, DistanceFunctionError> {
+    let space = match vector_segment.metadata.as_ref() {
+        Some(metadata) => match metadata.get("hnsw:space") {
+            Some(MetadataValue::Str(space)) => space,
+            _ => "l2",
+        },
+        None => "l2",
+    };
+    DistanceFunction::try_from(space)
+}
+
 type KnnOutput = KnnProjectionOutput;
 type KnnResult = Result<KnnOutput, KnnError>;
 
@@ -436,20 +521,27 @@ pub struct KnnOrchestrator {
 
     // Knn operator shared between log and segments
     knn: KnnOperator,
+    distance_function: Option<DistanceFunction>,
 
     // Knn output
     knn_log_distances: Option<Vec<RecordDistance>>,
+    knn_log_truncated: bool,
     knn_segment_distances: Option<Vec<RecordDistance>>,
 
-    // Merge and project
+    // Merge, re-rank and project
     merge: KnnMergeOperator,
+    mmr: Option<MmrOperator>,
     knn_projection: KnnProjectionOperator,
 
+    // The deadline of the query, shared by the operators
+    deadline: Deadline,
+
     // Result channel
     result_channel: Option<Sender<KnnResult>>,
 }
 
 impl KnnOrchestrator {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         blockfile_provider: BlockfileProvider,
         dispatcher: ComponentHandle<Dispatcher>,
@@ -457,6 +549,7 @@ impl KnnOrchestrator {
         queue: usize,
         knn_filter_output: KnnFilterOutput,
         knn: KnnOperator,
+        mmr: Option<MmrOperator>,
         knn_projection: KnnProjectionOperator,
     ) -> Self {
         let fetch = knn.fetch;
@@ -467,15 +560,27 @@ impl KnnOrchestrator {
             queue,
             knn_filter_output,
             knn,
+            distance_function: None,
             knn_log_distances: None,
+            knn_log_truncated: false,
             knn_segment_distances: None,
             merge: KnnMergeOperator { fetch },
+            mmr,
             knn_projection,
+            deadline: Deadline::default(),
             result_channel: None,
         }
     }
 
+    /// Stops the nearest neighbour search and projection once the deadline is reached
+    pub fn with_deadline(mut self, deadline: Deadline) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
     pub async fn run(mut self, system: System) -> KnnResult {
+        // The operators stop early if the caller stops waiting for the result
+        let _cancel_on_drop = self.deadline.cancel_on_drop();
         let (tx, rx) = oneshot::channel();
         self.result_channel = Some(tx);
         let mut handle = system.start_component(self);
@@ -511,6 +616,58 @@ impl KnnOrchestrator {
             }
         }
     }
+
+    async fn start_knn_projection_operator(
+        &mut self,
+        ctx: &ComponentContext<Self>,
+        record_distances: Vec<RecordDistance>,
+    ) {
+        // Prefetch records before projection
+        let prefetch_task = wrap(
+            Box::new(PrefetchRecordOperator {}),
+            PrefetchRecordInput {
+                logs: self.knn_filter_output.logs.clone(),
+                blockfile_provider: self.blockfile_provider.clone(),
+                record_segment: self.knn_filter_output.segments.record_segment.clone(),
+                offset_ids: record_distances
+                    .iter()
+                    .map(|record| record.offset_id)
+                    .collect(),
+            },
+            ctx.receiver(),
+        );
+        if let Err(err) = self
+            .dispatcher
+            .send(prefetch_task, Some(Span::current()))
+            .await
+        {
+            self.terminate_with_error(ctx, err);
+        }
+
+        let projection_task = wrap(
+            Box::new(self.knn_projection.clone()),
+            KnnProjectionInput {
+                logs: self.knn_filter_output.logs.clone(),
+                blockfile_provider: self.blockfile_provider.clone(),
+                record_segment: self.knn_filter_output.segments.record_segment.clone(),
+                record_distances,
+                projection_defaults: ProjectionDefaults::from(
+                    &self.knn_filter_output.segments.collection,
+                ),
+                deadline: self.deadline.clone(),
+                memory: self.knn_filter_output.memory.clone(),
+                materialized_logs: self.knn_filter_output.materialized_logs.clone(),
+            },
+            ctx.receiver(),
+        );
+        if let Err(err) = self
+            .dispatcher
+            .send(projection_task, Some(Span::current()))
+            .await
+        {
+            self.terminate_with_error(ctx, err);
+        }
+    }
 }
 
 #[async_trait]
@@ -524,26 +681,16 @@ impl Component for KnnOrchestrator {
     }
 
     async fn on_start(&mut self, ctx: &ComponentContext<Self>) {
-        let space = match self
-            .knn_filter_output
-            .segments
-            .vector_segment
-            .metadata
-            .as_ref()
-        {
-            Some(metadata) => match metadata.get("hnsw:space") {
-                Some(MetadataValue::Str(space)) => space,
-                _ => "l2",
-            },
-            None => "l2",
-        };
-        let distance_function = match DistanceFunction::try_from(space) {
-            Ok(func) => func,
-            Err(err) => {
-                self.terminate_with_error(ctx, err);
-                return;
-            }
-        };
+        let distance_function =
+            match distance_function(&self.knn_filter_output.segments.vector_segment) {
+                Ok(func) => func,
+                Err(err) => {
+                    self.terminate_with_error(ctx, err);
+                    return;
+                }
+            };
+
+        self.distance_function = Some(distance_function.clone());
 
         let knn_log_task = wrap(
             Box::new(self.knn.clone()),
@@ -553,6 +700,7 @@ impl Component for KnnOrchestrator {
                 record_segment: self.knn_filter_output.segments.record_segment.clone(),
                 log_offset_ids: self.knn_filter_output.filter_output.log_offset_ids.clone(),
                 distance_function: distance_function.clone(),
+                deadline: self.deadline.clone(),
             },
             ctx.receiver(),
         );
@@ -578,6 +726,8 @@ impl Component for KnnOrchestrator {
             KnnHnswInput {
                 hnsw_provider: self.hnsw_provider.clone(),
                 hnsw_segment: self.knn_filter_output.segments.vector_segment.clone(),
+                blockfile_provider: self.blockfile_provider.clone(),
+                record_segment: self.knn_filter_output.segments.record_segment.clone(),
                 collection_dimension,
                 compact_offset_ids: self
                     .knn_filter_output
@@ -585,6 +735,7 @@ impl Component for KnnOrchestrator {
                     .compact_offset_ids
                     .clone(),
                 distance_function,
+                deadline: self.deadline.clone(),
             },
             ctx.receiver(),
         );
@@ -616,6 +767,7 @@ impl Handler<TaskResult<KnnLogOutput, KnnLogError>> for KnnOrchestrator {
             }
         };
         self.knn_log_distances = Some(output.record_distances);
+        self.knn_log_truncated = output.truncated;
         self.try_start_knn_merge_operator(ctx).await;
     }
 }
@@ -654,49 +806,52 @@ impl Handler<TaskResult<KnnMergeOutput, KnnMergeError>> for KnnOrchestrator {
             .into_inner()
             .expect("KnnMergeOperator should not fail");
 
-        // Prefetch records before projection
-        let prefetch_task = wrap(
-            Box::new(PrefetchRecordOperator {}),
-            PrefetchRecordInput {
-                logs: self.knn_filter_output.logs.clone(),
-                blockfile_provider: self.blockfile_provider.clone(),
-                record_segment: self.knn_filter_output.segments.record_segment.clone(),
-                offset_ids: output
-                    .record_distances
-                    .iter()
-                    .map(|record| record.offset_id)
-                    .collect(),
-            },
-            ctx.receiver(),
-        );
-        if let Err(err) = self
-            .dispatcher
-            .send(prefetch_task, Some(Span::current()))
-            .await
-        {
-            self.terminate_with_error(ctx, err);
-        }
+        let (Some(mmr), Some(distance_function)) =
+            (self.mmr.clone(), self.distance_function.clone())
+        else {
+            self.start_knn_projection_operator(ctx, output.record_distances)
+                .await;
+            return;
+        };
 
-        let projection_task = wrap(
-            Box::new(self.knn_projection.clone()),
-            KnnProjectionInput {
+        let mmr_task = wrap(
+            Box::new(mmr),
+            MmrInput {
                 logs: self.knn_filter_output.logs.clone(),
                 blockfile_provider: self.blockfile_provider.clone(),
                 record_segment: self.knn_filter_output.segments.record_segment.clone(),
                 record_distances: output.record_distances,
+                distance_function,
             },
             ctx.receiver(),
         );
-        if let Err(err) = self
-            .dispatcher
-            .send(projection_task, Some(Span::current()))
-            .await
-        {
+        if let Err(err) = self.dispatcher.send(mmr_task, Some(Span::current())).await {
             self.terminate_with_error(ctx, err);
         }
     }
 }
 
+#[async_trait]
+impl Handler<TaskResult<MmrOutput, MmrError>> for KnnOrchestrator {
+    type Result = ();
+
+    async fn handle(
+        &mut self,
+        message: TaskResult<MmrOutput, MmrError>,
+        ctx: &ComponentContext<Self>,
+    ) {
+        let output = match message.into_inner() {
+            Ok(output) => output,
+            Err(err) => {
+                self.terminate_with_error(ctx, err);
+                return;
+            }
+        };
+        self.start_knn_projection_operator(ctx, output.record_distances)
+            .await;
+    }
+}
+
 #[async_trait]
 impl Handler<TaskResult<PrefetchRecordOutput, PrefetchRecordError>> for KnnOrchestrator {
     type Result = ();
@@ -719,13 +874,14 @@ impl Handler<TaskResult<KnnProjectionOutput, KnnProjectionError>> for KnnOrchest
         message: TaskResult<KnnProjectionOutput, KnnProjectionError>,
         ctx: &ComponentContext<Self>,
     ) {
-        let output = match message.into_inner() {
+        let mut output = match message.into_inner() {
             Ok(output) => output,
             Err(err) => {
                 self.terminate_with_error(ctx, err);
                 return;
             }
         };
+        output.truncated |= self.knn_log_truncated;
         if let Some(chan) = self.result_channel.take() {
             if chan.send(Ok(output)).is_err() {
                 tracing::error!("Error sending final result");
@@ -0,0 +1,319 @@
+use std::collections::HashMap;
+
+use chroma_blockstore::provider::BlockfileProvider;
+use chroma_error::{ChromaError, ErrorCodes};
+use thiserror::Error;
+use tokio::sync::oneshot::{self, error::RecvError, Sender};
+use tonic::async_trait;
+use tracing::Span;
+
+use crate::{
+    execution::{
+        dispatcher::Dispatcher,
+        operator::{wrap_with_priority, TaskError, TaskPriority, TaskResult},
+        operators::{
+            fetch_log::{FetchLogError, FetchLogInput, FetchLogOperator, FetchLogOutput},
+            fetch_segment::{FetchSegmentError, FetchSegmentOperator, FetchSegmentOutput},
+            lookup::{LookupError, LookupInput, LookupOperator, LookupOutput},
+            projection::{ProjectionDefaults, ProjectionRecord},
+        },
+        orchestration::common::terminate_with_error,
+    },
+    system::{ChannelError, Component, ComponentContext, ComponentHandle, Handler, System},
+};
+
+#[derive(Error, Debug)]
+pub enum LookupOrchestratorError {
+    #[error("Error sending message through channel: {0}")]
+    Channel(#[from] ChannelError),
+    #[error("Error running Fetch Log Operator: {0}")]
+    FetchLog(#[from] FetchLogError),
+    #[error("Error running Fetch Segment Operator: {0}")]
+    FetchSegment(#[from] FetchSegmentError),
+    #[error("Error running Lookup Operator: {0}")]
+    Lookup(#[from] LookupError),
+    #[error("Task aborted: {0}")]
+    Aborted(String),
+    #[error("Panic running task: {0}")]
+    Panic(String),
+    #[error("Error receiving final result: {0}")]
+    Result(#[from] RecvError),
+}
+
+impl ChromaError for LookupOrchestratorError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            LookupOrchestratorError::Channel(e) => e.code(),
+            LookupOrchestratorError::FetchLog(e) => e.code(),
+            LookupOrchestratorError::FetchSegment(e) => e.code(),
+            LookupOrchestratorError::Lookup(e) => e.code(),
+            LookupOrchestratorError::Aborted(_) => ErrorCodes::Aborted,
+            LookupOrchestratorError::Panic(_) => ErrorCodes::Aborted,
+            LookupOrchestratorError::Result(_) => ErrorCodes::Internal,
+        }
+    }
+}
+
+impl<E> From<TaskError<E>> for LookupOrchestratorError
+where
+    E: Into<LookupOrchestratorError>,
+{
+    fn from(value: TaskError<E>) -> Self {
+        match value {
+            TaskError::Panic(e) => LookupOrchestratorError::Panic(e.unwrap_or_default()),
+            TaskError::TaskFailed(e) => e.into(),
+            TaskError::Aborted(e) => LookupOrchestratorError::Aborted(e),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct LookupOrchestratorOutput {
+    pub records: HashMap<String, ProjectionRecord>,
+    // The offset of the last log that the records cover
+    pub log_offset: i64,
+}
+
+type LookupResult = Result<LookupOrchestratorOutput, LookupOrchestratorError>;
+
+/// The `LookupOrchestrator` fetches the records of a collection by their user ids, so that
+/// a query on another collection can return the records that its results reference
+///
+/// # Pipeline
+/// ```text
+///                       ┌────────────┐
+///                       │            │
+///           ┌───────────┤  on_start  ├────────────────┐
+///           │           │            │                │
+///           │           └────────────┘                │
+///           │                                         │
+///           ▼                                         ▼
+///  ┌────────────────────┐            ┌────────────────────────┐
+///  │                    │            │                        │
+///  │  FetchLogOperator  │            │  FetchSegmentOperator  │
+///  │                    │            │                        │
+///  └────────┬───────────┘            └────────────────┬───────┘
+///           │                                         │
+///           │                                         │
+///           │     ┌─────────────────────────────┐     │
+///           │     │                             │     │
+///           └────►│  try_start_lookup_operator  │◄────┘
+///                 │                             │
+///                 └────────────┬────────────────┘
+///                              │
+///                              ▼
+///                    ┌───────────────────┐
+///                    │                   │
+///                    │  LookupOperator   │
+///                    │                   │
+///                    └─────────┬─────────┘
+///                              │
+///                              ▼
+///                    ┌───────────────────┐
+///                    │                   │
+///                    │  result_channel   │
+///                    │                   │
+///                    └───────────────────┘
+/// ```
+#[derive(Debug)]
+pub struct LookupOrchestrator {
+    // Orchestrator parameters
+    blockfile_provider: BlockfileProvider,
+    dispatcher: ComponentHandle<Dispatcher>,
+    queue: usize,
+
+    // Fetch logs and segments
+    fetch_log: FetchLogOperator,
+    fetch_segment: FetchSegmentOperator,
+
+    // Fetched logs and segments
+    fetch_log_output: Option<FetchLogOutput>,
+    fetch_segment_output: Option<FetchSegmentOutput>,
+    log_offset: i64,
+
+    // The ids of the records to fetch
+    ids: Vec<String>,
+    lookup: LookupOperator,
+
+    // Result channel
+    result_channel: Option<Sender<LookupResult>>,
+}
+
+impl LookupOrchestrator {
+    pub fn new(
+        blockfile_provider: BlockfileProvider,
+        dispatcher: ComponentHandle<Dispatcher>,
+        queue: usize,
+        fetch_log: FetchLogOperator,
+        fetch_segment: FetchSegmentOperator,
+        ids: Vec<String>,
+        lookup: LookupOperator,
+    ) -> Self {
+        Self {
+            blockfile_provider,
+            dispatcher,
+            queue,
+            fetch_log,
+            fetch_segment,
+            fetch_log_output: None,
+            fetch_segment_output: None,
+            log_offset: 0,
+            ids,
+            lookup,
+            result_channel: None,
+        }
+    }
+
+    pub async fn run(mut self, system: System) -> LookupResult {
+        let (tx, rx) = oneshot::channel();
+        self.result_channel = Some(tx);
+        let mut handle = system.start_component(self);
+        let result = rx.await;
+        handle.stop();
+        result?
+    }
+
+    fn terminate_with_error<E>(&mut self, ctx: &ComponentContext<Self>, err: E)
+    where
+        E: Into<LookupOrchestratorError>,
+    {
+        let lookup_err = err.into();
+        tracing::error!("Error running orchestrator: {}", &lookup_err);
+        terminate_with_error(self.result_channel.take(), lookup_err, ctx);
+    }
+
+    async fn try_start_lookup_operator(&mut self, ctx: &ComponentContext<Self>) {
+        let (logs, segments) = match (
+            self.fetch_log_output.take(),
+            self.fetch_segment_output.take(),
+        ) {
+            (Some(logs), Some(segments)) => (logs, segments),
+            (logs, segments) => {
+                self.fetch_log_output = logs;
+                self.fetch_segment_output = segments;
+                return;
+            }
+        };
+        self.log_offset = self.fetch_log.log_offset(&logs);
+        let task = wrap_with_priority(
+            Box::new(self.lookup.clone()),
+            LookupInput {
+                logs,
+                blockfile_provider: self.blockfile_provider.clone(),
+                record_segment: segments.record_segment,
+                ids: std::mem::take(&mut self.ids),
+                defaults: ProjectionDefaults::from(&segments.collection),
+            },
+            ctx.receiver(),
+            TaskPriority::Interactive,
+        );
+        if let Err(err) = self.dispatcher.send(task, Some(Span::current())).await {
+            self.terminate_with_error(ctx, err);
+        }
+    }
+}
+
+#[async_trait]
+impl Component for LookupOrchestrator {
+    fn get_name() -> &'static str {
+        "Lookup Orchestrator"
+    }
+
+    fn queue_size(&self) -> usize {
+        self.queue
+    }
+
+    async fn on_start(&mut self, ctx: &ComponentContext<Self>) {
+        let log_task = wrap_with_priority(
+            Box::new(self.fetch_log.clone()),
+            FetchLogInput::default(),
+            ctx.receiver(),
+            TaskPriority::Interactive,
+        );
+        let segment_task = wrap_with_priority(
+            Box::new(self.fetch_segment.clone()),
+            (),
+            ctx.receiver(),
+            TaskPriority::Interactive,
+        );
+        if let Err(err) = self.dispatcher.send(log_task, Some(Span::current())).await {
+            self.terminate_with_error(ctx, err);
+        } else if let Err(err) = self
+            .dispatcher
+            .send(segment_task, Some(Span::current()))
+            .await
+        {
+            self.terminate_with_error(ctx, err);
+        }
+    }
+}
+
+#[async_trait]
+impl Handler<TaskResult<FetchLogOutput, FetchLogError>> for LookupOrchestrator {
+    type Result = ();
+
+    async fn handle(
+        &mut self,
+        message: TaskResult<FetchLogOutput, FetchLogError>,
+        ctx: &ComponentContext<Self>,
+    ) {
+        let output = match message.into_inner() {
+            Ok(output) => output,
+            Err(err) => {
+                self.terminate_with_error(ctx, err);
+                return;
+            }
+        };
+        self.fetch_log_output = Some(output);
+        self.try_start_lookup_operator(ctx).await;
+    }
+}
+
+#[async_trait]
+impl Handler<TaskResult<FetchSegmentOutput, FetchSegmentError>> for LookupOrchestrator {
+    type Result = ();
+
+    async fn handle(
+        &mut self,
+        message: TaskResult<FetchSegmentOutput, FetchSegmentError>,
+        ctx: &ComponentContext<Self>,
+    ) {
+        let output = match message.into_inner() {
+            Ok(output) => output,
+            Err(err) => {
+                self.terminate_with_error(ctx, err);
+                return;
+            }
+        };
+        self.fetch_segment_output = Some(output);
+        self.try_start_lookup_operator(ctx).await;
+    }
+}
+
+#[async_trait]
+impl Handler<TaskResult<LookupOutput, LookupError>> for LookupOrchestrator {
+    type Result = ();
+
+    async fn handle(
+        &mut self,
+        message: TaskResult<LookupOutput, LookupError>,
+        ctx: &ComponentContext<Self>,
+    ) {
+        let output = match message.into_inner() {
+            Ok(output) => output,
+            Err(err) => {
+                self.terminate_with_error(ctx, err);
+                return;
+            }
+        };
+        let output = LookupOrchestratorOutput {
+            records: output.records,
+            log_offset: self.log_offset,
+        };
+        if let Some(chan) = self.result_channel.take() {
+            if chan.send(Ok(output)).is_err() {
+                tracing::error!("Error sending final result");
+            };
+        }
+    }
+}
@@ -0,0 +1,724 @@
+use super::super::operator::wrap_with_priority;
+use crate::execution::dispatcher::Dispatcher;
+use crate::execution::operator::TaskPriority;
+use crate::execution::operator::TaskResult;
+use crate::execution::operators::flush_s3::FlushS3Input;
+use crate::execution::operators::flush_s3::FlushS3Operator;
+use crate::execution::operators::flush_s3::FlushS3Output;
+use crate::execution::operators::merge_records::MergeDuplicatePolicy;
+use crate::execution::operators::merge_records::MergeRecordsError;
+use crate::execution::operators::merge_records::MergeRecordsInput;
+use crate::execution::operators::merge_records::MergeRecordsOperator;
+use crate::execution::operators::merge_records::MergeRecordsOutput;
+use crate::execution::operators::partition::PartitionError;
+use crate::execution::operators::partition::PartitionInput;
+use crate::execution::operators::partition::PartitionOperator;
+use crate::execution::operators::partition::PartitionOutput;
+use crate::execution::operators::register::RegisterError;
+use crate::execution::operators::register::RegisterInput;
+use crate::execution::operators::register::RegisterOperator;
+use crate::execution::operators::register::RegisterOutput;
+use crate::execution::operators::write_segments::WriteSegmentsInput;
+use crate::execution::operators::write_segments::WriteSegmentsOperator;
+use crate::execution::operators::write_segments::WriteSegmentsOperatorError;
+use crate::execution::operators::write_segments::WriteSegmentsOutput;
+use crate::execution::orchestration::common::terminate_with_error;
+use crate::log::log::Log;
+use crate::segment::distributed_hnsw_segment::DistributedHNSWSegmentWriter;
+use crate::segment::materialized_filter::materialized_filters_from_collection;
+use crate::segment::metadata_segment::MetadataSegmentWriter;
+use crate::segment::record_segment::RecordSegmentWriter;
+use crate::sysdb::sysdb::GetCollectionsError;
+use crate::sysdb::sysdb::GetSegmentsError;
+use crate::sysdb::sysdb::GetTenantError;
+use crate::sysdb::sysdb::SysDb;
+use crate::system::Component;
+use crate::system::ComponentContext;
+use crate::system::ComponentHandle;
+use crate::system::Handler;
+use crate::system::ReceiverForMessage;
+use crate::system::System;
+use async_trait::async_trait;
+use chroma_blockstore::provider::BlockfileProvider;
+use chroma_error::ChromaError;
+use chroma_error::ErrorCodes;
+use chroma_index::hnsw_provider::HnswIndexProvider;
+use chroma_storage::PutOptions;
+use chroma_types::Chunk;
+use chroma_types::{Collection, CollectionUuid, LogRecord, Segment, SegmentFlushInfo, SegmentType};
+use std::sync::atomic::AtomicU32;
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::Span;
+
+/**  The state of the orchestrator.
+The merge orchestrator reads the records of the source collections into a single log, and
+then writes that log to the fresh segments of the target collection the same way that the
+compaction orchestrator writes the log of a collection.
+```plaintext
+
+                                              ┌───► Write─────-------┐
+                                              │                      │
+  Pending ─► MergeRecords ─► Partition ───────┤                      ├─► Flush ─► Register ─► Finished
+                                              │                      │
+                                              └───► Write ───────────┘
+
+```
+*/
+#[derive(Debug)]
+enum ExecutionState {
+    Pending,
+    MergeRecords,
+    Partition,
+    Write,
+    Flush,
+    Register,
+}
+
+/// Merges the records of the source collections into the target collection, which must have
+/// been created with the same dimension and must not have any record yet. The records are
+/// assigned new offset ids in the target collection, and a user id that is present in more
+/// than one source collection is resolved by the `duplicate_policy`.
+///
+/// Only the compacted records of the source collections are merged, so the source collections
+/// should be compacted before they are merged.
+#[derive(Debug)]
+pub struct MergeOrchestrator {
+    state: ExecutionState,
+    // Component Execution
+    system: System,
+    source_collection_ids: Vec<CollectionUuid>,
+    target_collection_id: CollectionUuid,
+    duplicate_policy: MergeDuplicatePolicy,
+    // Dependencies
+    log: Box<Log>,
+    sysdb: Box<SysDb>,
+    blockfile_provider: BlockfileProvider,
+    hnsw_index_provider: HnswIndexProvider,
+    // State we hold across the execution
+    target_collection: Option<Collection>,
+    target_segments: Vec<Segment>,
+    num_records: usize,
+    // Dispatcher
+    dispatcher: ComponentHandle<Dispatcher>,
+    // number of write segments tasks
+    num_write_tasks: i32,
+    max_partition_size: usize,
+    // Result Channel
+    result_channel:
+        Option<tokio::sync::oneshot::Sender<Result<MergeResponse, Box<dyn ChromaError>>>>,
+}
+
+#[derive(Error, Debug)]
+enum MergeError {
+    #[error("Collection {0} has dimension {1}, but the target collection has dimension {2}")]
+    IncompatibleDimension(CollectionUuid, i32, i32),
+    #[error("Collection {0} not found")]
+    CollectionNotFound(CollectionUuid),
+    #[error("Error getting collection")]
+    GetCollectionError(#[from] GetCollectionsError),
+    #[error("Error getting tenant")]
+    GetTenantError(#[from] GetTenantError),
+    #[error("No dimension is known for the merged collections")]
+    UnknownDimension,
+    #[error("Target collection {0} already has records")]
+    TargetNotEmpty(CollectionUuid),
+    #[error("SysDB GetSegments Error")]
+    SysDbGetSegmentsError(#[from] GetSegmentsError),
+    #[error("No {0:?} segment found for collection {1}")]
+    SegmentNotFound(SegmentType, CollectionUuid),
+    #[error("Error creating Record Segment Writer")]
+    RecordSegmentWriterError,
+    #[error("Error creating Metadata Segment Writer")]
+    MetadataSegmentWriterError,
+    #[error("Error creating HNSW Segment Writer")]
+    HnswSegmentWriterError,
+    #[error("Result channel dropped")]
+    ResultChannelDropped,
+}
+
+impl ChromaError for MergeError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            MergeError::IncompatibleDimension(_, _, _) => ErrorCodes::InvalidArgument,
+            MergeError::CollectionNotFound(_) => ErrorCodes::NotFound,
+            MergeError::GetCollectionError(e) => e.code(),
+            MergeError::GetTenantError(e) => e.code(),
+            MergeError::UnknownDimension => ErrorCodes::InvalidArgument,
+            MergeError::TargetNotEmpty(_) => ErrorCodes::FailedPrecondition,
+            MergeError::SysDbGetSegmentsError(e) => e.code(),
+            MergeError::SegmentNotFound(_, _) => ErrorCodes::NotFound,
+            _ => ErrorCodes::Internal,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct MergeResponse {
+    pub(crate) target_collection_id: CollectionUuid,
+    pub(crate) num_records: usize,
+}
+
+impl MergeOrchestrator {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        system: System,
+        source_collection_ids: Vec<CollectionUuid>,
+        target_collection_id: CollectionUuid,
+        duplicate_policy: MergeDuplicatePolicy,
+        log: Box<Log>,
+        sysdb: Box<SysDb>,
+        blockfile_provider: BlockfileProvider,
+        hnsw_index_provider: HnswIndexProvider,
+        dispatcher: ComponentHandle<Dispatcher>,
+        max_partition_size: usize,
+    ) -> Self {
+        MergeOrchestrator {
+            state: ExecutionState::Pending,
+            system,
+            source_collection_ids,
+            target_collection_id,
+            duplicate_policy,
+            log,
+            sysdb,
+            blockfile_provider,
+            hnsw_index_provider,
+            target_collection: None,
+            target_segments: Vec::new(),
+            num_records: 0,
+            dispatcher,
+            num_write_tasks: 0,
+            max_partition_size,
+            result_channel: None,
+        }
+    }
+
+    async fn get_collection(
+        &mut self,
+        collection_id: CollectionUuid,
+    ) -> Result<Collection, Box<dyn ChromaError>> {
+        let mut collections = self
+            .sysdb
+            .get_collections(Some(collection_id), None, None, None)
+            .await
+            .map_err(|e| Box::new(MergeError::GetCollectionError(e)))?;
+        match collections.pop() {
+            Some(collection) => Ok(collection),
+            None => Err(Box::new(MergeError::CollectionNotFound(collection_id))),
+        }
+    }
+
+    async fn get_record_segment(
+        &mut self,
+        collection_id: CollectionUuid,
+    ) -> Result<Segment, Box<dyn ChromaError>> {
+        let mut segments = self
+            .sysdb
+            .get_segments(
+                None,
+                Some(SegmentType::BlockfileRecord.into()),
+                None,
+                collection_id,
+            )
+            .await
+            .map_err(|e| Box::new(MergeError::SysDbGetSegmentsError(e)))?;
+        match segments.pop() {
+            Some(segment) => Ok(segment),
+            None => Err(Box::new(MergeError::SegmentNotFound(
+                SegmentType::BlockfileRecord,
+                collection_id,
+            ))),
+        }
+    }
+
+    // Checks that the collections can be merged into the target collection, and returns the
+    // record segments of the source collections
+    async fn prepare(&mut self) -> Result<Vec<Segment>, Box<dyn ChromaError>> {
+        let mut target_collection = self.get_collection(self.target_collection_id).await?;
+        let mut record_segments = Vec::with_capacity(self.source_collection_ids.len());
+        for collection_id in self.source_collection_ids.clone() {
+            let collection = self.get_collection(collection_id).await?;
+            match (collection.dimension, target_collection.dimension) {
+                (Some(dimension), Some(target_dimension)) if dimension != target_dimension => {
+                    return Err(Box::new(MergeError::IncompatibleDimension(
+                        collection_id,
+                        dimension,
+                        target_dimension,
+                    )));
+                }
+                (Some(dimension), None) => target_collection.dimension = Some(dimension),
+                _ => {}
+            }
+            record_segments.push(self.get_record_segment(collection_id).await?);
+        }
+
+        let target_segments = self
+            .sysdb
+            .get_segments(None, None, None, self.target_collection_id)
+            .await
+            .map_err(|e| Box::new(MergeError::SysDbGetSegmentsError(e)))?;
+        if target_segments
+            .iter()
+            .any(|segment| !segment.file_path.is_empty())
+        {
+            return Err(Box::new(MergeError::TargetNotEmpty(
+                self.target_collection_id,
+            )));
+        }
+
+        // The files of the target collection are encrypted with the KMS key of its tenant, the
+        // same way the compactor writes them
+        let kms_key_id = self
+            .sysdb
+            .get_tenant_kms_key_id(target_collection.tenant.clone())
+            .await
+            .map_err(|e| Box::new(MergeError::GetTenantError(e)))?;
+        if let Some(kms_key_id) = kms_key_id {
+            let put_options = PutOptions {
+                kms_key_id: Some(kms_key_id),
+            };
+            self.blockfile_provider = self
+                .blockfile_provider
+                .with_put_options(put_options.clone());
+            self.hnsw_index_provider = self
+                .hnsw_index_provider
+                .clone()
+                .with_put_options(put_options);
+        }
+
+        self.target_collection = Some(target_collection);
+        self.target_segments = target_segments;
+        Ok(record_segments)
+    }
+
+    async fn merge_records(
+        &mut self,
+        record_segments: Vec<Segment>,
+        self_address: Box<
+            dyn ReceiverForMessage<TaskResult<MergeRecordsOutput, MergeRecordsError>>,
+        >,
+    ) {
+        self.state = ExecutionState::MergeRecords;
+        let operator = Box::new(MergeRecordsOperator {
+            duplicate_policy: self.duplicate_policy,
+        });
+        let input = MergeRecordsInput {
+            blockfile_provider: self.blockfile_provider.clone(),
+            record_segments,
+        };
+        let task = wrap_with_priority(operator, input, self_address, TaskPriority::Batch);
+        match self.dispatcher.send(task, Some(Span::current())).await {
+            Ok(_) => (),
+            Err(e) => {
+                tracing::error!("Error dispatching merge records for merge {:?}", e);
+                panic!(
+                    "Invariant violation. Somehow the dispatcher receiver is dropped. Error: {:?}",
+                    e
+                );
+            }
+        }
+    }
+
+    async fn partition(
+        &mut self,
+        records: Chunk<LogRecord>,
+        self_address: Box<dyn ReceiverForMessage<TaskResult<PartitionOutput, PartitionError>>>,
+    ) {
+        self.state = ExecutionState::Partition;
+        let operator = PartitionOperator::new();
+        let input = PartitionInput::new(records, self.max_partition_size);
+        let task = wrap_with_priority(operator, input, self_address, TaskPriority::Batch);
+        match self.dispatcher.send(task, Some(Span::current())).await {
+            Ok(_) => (),
+            Err(e) => {
+                tracing::error!("Error dispatching partition for merge {:?}", e);
+                panic!(
+                    "Invariant violation. Somehow the dispatcher receiver is dropped. Error: {:?}",
+                    e
+                )
+            }
+        }
+    }
+
+    async fn write(
+        &mut self,
+        partitions: Vec<Chunk<LogRecord>>,
+        self_address: Box<
+            dyn ReceiverForMessage<TaskResult<WriteSegmentsOutput, WriteSegmentsOperatorError>>,
+        >,
+        ctx: &ComponentContext<MergeOrchestrator>,
+    ) {
+        self.state = ExecutionState::Write;
+
+        let (record_segment, record_segment_writer, hnsw_segment_writer, metadata_segment_writer) =
+            match self.get_segment_writers().await {
+                Ok(writers) => writers,
+                Err(e) => {
+                    tracing::error!("Error creating writers for merge {:?}", e);
+                    terminate_with_error(self.result_channel.take(), e, ctx);
+                    return;
+                }
+            };
+
+        // The target collection has no records, so the offset ids start from the beginning
+        let curr_max_offset_id = Arc::new(AtomicU32::new(0));
+        self.num_write_tasks = partitions.len() as i32;
+        for partition in partitions.iter() {
+            let operator = WriteSegmentsOperator::new();
+            let input = WriteSegmentsInput::new(
+                record_segment_writer.clone(),
+                hnsw_segment_writer.clone(),
+                metadata_segment_writer.clone(),
+                partition.clone(),
+                self.blockfile_provider.clone(),
+                record_segment.clone(),
+                curr_max_offset_id.clone(),
+            );
+            let task =
+                wrap_with_priority(operator, input, self_address.clone(), TaskPriority::Batch);
+            match self.dispatcher.send(task, Some(Span::current())).await {
+                Ok(_) => (),
+                Err(e) => {
+                    tracing::error!("Error dispatching writers for merge {:?}", e);
+                    panic!(
+                        "Invariant violation. Somehow the dispatcher receiver is dropped. Error: {:?}",
+                        e)
+                }
+            }
+        }
+    }
+
+    async fn flush_s3(
+        &mut self,
+        record_segment_writer: RecordSegmentWriter,
+        hnsw_segment_writer: Box<DistributedHNSWSegmentWriter>,
+        metadata_segment_writer: MetadataSegmentWriter<'static>,
+        self_address: Box<dyn ReceiverForMessage<TaskResult<FlushS3Output, Box<dyn ChromaError>>>>,
+    ) {
+        self.state = ExecutionState::Flush;
+
+        let operator = FlushS3Operator::new();
+        let input = FlushS3Input::new(
+            self.target_collection_id,
+            record_segment_writer,
+            hnsw_segment_writer,
+            metadata_segment_writer,
+        );
+
+        let task = wrap_with_priority(operator, input, self_address, TaskPriority::Batch);
+        match self.dispatcher.send(task, Some(Span::current())).await {
+            Ok(_) => (),
+            Err(e) => {
+                tracing::error!("Error dispatching flush to S3 for merge {:?}", e);
+                panic!(
+                    "Invariant violation. Somehow the dispatcher receiver is dropped. Error: {:?}",
+                    e
+                );
+            }
+        }
+    }
+
+    async fn register(
+        &mut self,
+        segment_flush_info: Arc<[SegmentFlushInfo]>,
+        self_address: Box<dyn ReceiverForMessage<TaskResult<RegisterOutput, RegisterError>>>,
+    ) {
+        self.state = ExecutionState::Register;
+        let target_collection = self
+            .target_collection
+            .as_ref()
+            .expect("Invariant violation. Target collection is not set.");
+        let operator = RegisterOperator::new();
+        // The log of the target collection is left untouched, so its log position is kept
+        let input = RegisterInput::new(
+            target_collection.tenant.clone(),
+            self.target_collection_id,
+            target_collection.log_position,
+            target_collection.version,
+            segment_flush_info,
+            self.sysdb.clone(),
+            self.log.clone(),
+        );
+
+        let task = wrap_with_priority(operator, input, self_address, TaskPriority::Batch);
+        match self.dispatcher.send(task, Some(Span::current())).await {
+            Ok(_) => (),
+            Err(e) => {
+                tracing::error!("Error dispatching register for merge {:?}", e);
+                panic!(
+                    "Invariant violation. Somehow the dispatcher receiver is dropped. Error: {:?}",
+                    e
+                );
+            }
+        }
+    }
+
+    fn get_target_segment(
+        &self,
+        segment_type: SegmentType,
+    ) -> Result<&Segment, Box<dyn ChromaError>> {
+        match self
+            .target_segments
+            .iter()
+            .find(|segment| segment.r#type == segment_type)
+        {
+            Some(segment) => Ok(segment),
+            None => Err(Box::new(MergeError::SegmentNotFound(
+                segment_type,
+                self.target_collection_id,
+            ))),
+        }
+    }
+
+    async fn get_segment_writers(
+        &self,
+    ) -> Result<
+        (
+            Segment,
+            RecordSegmentWriter,
+            Box<DistributedHNSWSegmentWriter>,
+            MetadataSegmentWriter<'static>,
+        ),
+        Box<dyn ChromaError>,
+    > {
+        let target_collection = self
+            .target_collection
+            .as_ref()
+            .expect("Invariant violation. Target collection is not set.");
+
+        let record_segment = self.get_target_segment(SegmentType::BlockfileRecord)?;
+        let record_segment_writer =
+            match RecordSegmentWriter::from_segment(record_segment, &self.blockfile_provider).await
+            {
+                Ok(writer) => writer,
+                Err(e) => {
+                    tracing::error!("Error creating Record Segment Writer: {:?}", e);
+                    return Err(Box::new(MergeError::RecordSegmentWriterError));
+                }
+            };
+
+        let metadata_segment = self.get_target_segment(SegmentType::BlockfileMetadata)?;
+        let mut metadata_segment_writer =
+            match MetadataSegmentWriter::from_segment(metadata_segment, &self.blockfile_provider)
+                .await
+            {
+                Ok(writer) => writer,
+                Err(e) => {
+                    tracing::error!("Error creating Metadata Segment Writer: {:?}", e);
+                    return Err(Box::new(MergeError::MetadataSegmentWriterError));
+                }
+            };
+        if let Err(e) = metadata_segment_writer
+            .set_materialized_filters(
+                metadata_segment,
+                &self.blockfile_provider,
+                materialized_filters_from_collection(target_collection),
+            )
+            .await
+        {
+            tracing::error!("Error setting materialized filters: {:?}", e);
+            return Err(Box::new(MergeError::MetadataSegmentWriterError));
+        }
+
+        let hnsw_segment = self.get_target_segment(SegmentType::HnswDistributed)?;
+        let dimension = match target_collection.dimension {
+            Some(dimension) => dimension,
+            None => return Err(Box::new(MergeError::UnknownDimension)),
+        };
+        let hnsw_segment_writer = match DistributedHNSWSegmentWriter::from_segment(
+            hnsw_segment,
+            dimension as usize,
+            self.hnsw_index_provider.clone(),
+        )
+        .await
+        {
+            Ok(writer) => writer,
+            Err(e) => {
+                tracing::error!("Error creating HNSW Segment Writer: {:?}", e);
+                return Err(Box::new(MergeError::HnswSegmentWriterError));
+            }
+        };
+
+        Ok((
+            record_segment.clone(),
+            record_segment_writer,
+            hnsw_segment_writer,
+            metadata_segment_writer,
+        ))
+    }
+
+    fn finish(&mut self) {
+        let result_channel = self
+            .result_channel
+            .take()
+            .expect("Invariant violation. Result channel is not set.");
+        let response = MergeResponse {
+            target_collection_id: self.target_collection_id,
+            num_records: self.num_records,
+        };
+        let _ = result_channel.send(Ok(response));
+    }
+
+    pub async fn run(mut self) -> Result<MergeResponse, Box<dyn ChromaError>> {
+        tracing::info!(
+            "Merging collections {:?} into {}",
+            self.source_collection_ids,
+            self.target_collection_id
+        );
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        self.result_channel = Some(tx);
+        let mut handle = self.system.clone().start_component(self);
+        let result = rx.await;
+        handle.stop();
+        result.map_err(|_| Box::new(MergeError::ResultChannelDropped) as Box<dyn ChromaError>)?
+    }
+}
+
+// ============== Component Implementation ==============
+
+#[async_trait]
+impl Component for MergeOrchestrator {
+    fn get_name() -> &'static str {
+        "Merge orchestrator"
+    }
+
+    fn queue_size(&self) -> usize {
+        1000 // TODO: make configurable
+    }
+
+    async fn on_start(&mut self, ctx: &ComponentContext<Self>) -> () {
+        match self.prepare().await {
+            Ok(record_segments) => self.merge_records(record_segments, ctx.receiver()).await,
+            Err(e) => {
+                tracing::error!("Error preparing merge: {:?}", e);
+                terminate_with_error(self.result_channel.take(), e, ctx);
+            }
+        }
+    }
+}
+
+// ============== Handlers ==============
+#[async_trait]
+impl Handler<TaskResult<MergeRecordsOutput, MergeRecordsError>> for MergeOrchestrator {
+    type Result = ();
+
+    async fn handle(
+        &mut self,
+        message: TaskResult<MergeRecordsOutput, MergeRecordsError>,
+        ctx: &ComponentContext<MergeOrchestrator>,
+    ) {
+        let message = message.into_inner();
+        let logs = match message {
+            Ok(output) => output.logs,
+            Err(e) => {
+                tracing::error!("Error merging records: {:?}", e);
+                terminate_with_error(self.result_channel.take(), Box::new(e), ctx);
+                return;
+            }
+        };
+        self.num_records = logs.len();
+        if logs.is_empty() {
+            // There is nothing to write to the target collection
+            self.finish();
+            return;
+        }
+        self.partition(logs, ctx.receiver()).await;
+    }
+}
+
+#[async_trait]
+impl Handler<TaskResult<PartitionOutput, PartitionError>> for MergeOrchestrator {
+    type Result = ();
+
+    async fn handle(
+        &mut self,
+        message: TaskResult<PartitionOutput, PartitionError>,
+        ctx: &ComponentContext<MergeOrchestrator>,
+    ) {
+        let message = message.into_inner();
+        let records = match message {
+            Ok(result) => result.records,
+            Err(e) => {
+                tracing::error!("Error partitioning records: {:?}", e);
+                terminate_with_error(self.result_channel.take(), Box::new(e), ctx);
+                return;
+            }
+        };
+        self.write(records, ctx.receiver(), ctx).await;
+    }
+}
+
+#[async_trait]
+impl Handler<TaskResult<WriteSegmentsOutput, WriteSegmentsOperatorError>> for MergeOrchestrator {
+    type Result = ();
+
+    async fn handle(
+        &mut self,
+        message: TaskResult<WriteSegmentsOutput, WriteSegmentsOperatorError>,
+        ctx: &ComponentContext<MergeOrchestrator>,
+    ) {
+        let message = message.into_inner();
+        let output = match message {
+            Ok(output) => {
+                self.num_write_tasks -= 1;
+                output
+            }
+            Err(e) => {
+                tracing::error!("Error writing segments: {:?}", e);
+                terminate_with_error(self.result_channel.take(), Box::new(e), ctx);
+                return;
+            }
+        };
+        if self.num_write_tasks == 0 {
+            self.flush_s3(
+                output.record_segment_writer,
+                output.hnsw_segment_writer,
+                output.metadata_segment_writer,
+                ctx.receiver(),
+            )
+            .await;
+        }
+    }
+}
+
+#[async_trait]
+impl Handler<TaskResult<FlushS3Output, Box<dyn ChromaError>>> for MergeOrchestrator {
+    type Result = ();
+
+    async fn handle(
+        &mut self,
+        message: TaskResult<FlushS3Output, Box<dyn ChromaError>>,
+        ctx: &ComponentContext<MergeOrchestrator>,
+    ) {
+        let message = message.into_inner();
+        match message {
+            Ok(msg) => {
+                self.register(msg.segment_flush_info, ctx.receiver()).await;
+            }
+            Err(e) => {
+                tracing::error!("Error flushing to S3: {:?}", e);
+                terminate_with_error(self.result_channel.take(), e, ctx);
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Handler<TaskResult<RegisterOutput, RegisterError>> for MergeOrchestrator {
+    type Result = ();
+
+    async fn handle(
+        &mut self,
+        message: TaskResult<RegisterOutput, RegisterError>,
+        ctx: &ComponentContext<MergeOrchestrator>,
+    ) {
+        let message = message.into_inner();
+        match message {
+            Ok(_) => self.finish(),
+            Err(e) => {
+                tracing::error!("Error registering merge: {:?}", e);
+                terminate_with_error(self.result_channel.take(), Box::new(e), ctx);
+            }
+        }
+    }
+}
@@ -1,8 +1,17 @@
+pub(crate) mod analytics;
+pub(crate) mod changes;
+pub(crate) mod check_integrity;
 mod common;
 mod compact;
 mod count;
+pub(crate) mod export_index;
 mod get_vectors;
 pub(crate) mod hnsw;
+pub(crate) mod hybrid_search;
+pub(crate) mod lookup;
+pub(crate) mod merge;
+pub(crate) mod verify_checksum;
+pub(crate) mod warm_up;
 pub(crate) use compact::*;
 pub(crate) use count::*;
 pub(crate) use get_vectors::*;
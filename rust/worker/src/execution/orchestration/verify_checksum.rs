@@ -0,0 +1,225 @@
+use chroma_blockstore::provider::BlockfileProvider;
+use chroma_error::{ChromaError, ErrorCodes};
+use thiserror::Error;
+use tokio::sync::oneshot::{self, error::RecvError, Sender};
+use tonic::async_trait;
+use tracing::Span;
+
+use crate::{
+    execution::{
+        dispatcher::Dispatcher,
+        operator::{wrap_with_priority, TaskError, TaskPriority, TaskResult},
+        operators::{
+            fetch_segment::{FetchSegmentError, FetchSegmentOperator, FetchSegmentOutput},
+            verify_checksum::{
+                VerifyChecksumError, VerifyChecksumInput, VerifyChecksumOperator,
+                VerifyChecksumOutput,
+            },
+        },
+        orchestration::common::terminate_with_error,
+    },
+    system::{ChannelError, Component, ComponentContext, ComponentHandle, Handler, System},
+};
+
+#[derive(Error, Debug)]
+pub enum VerifyChecksumOrchestratorError {
+    #[error("Error sending message through channel: {0}")]
+    Channel(#[from] ChannelError),
+    #[error("Error running Fetch Segment Operator: {0}")]
+    FetchSegment(#[from] FetchSegmentError),
+    #[error("Task aborted: {0}")]
+    Aborted(String),
+    #[error("Panic running task: {0}")]
+    Panic(String),
+    #[error("Error receiving final result: {0}")]
+    Result(#[from] RecvError),
+    #[error("Error running Verify Checksum Operator: {0}")]
+    VerifyChecksum(#[from] VerifyChecksumError),
+}
+
+impl ChromaError for VerifyChecksumOrchestratorError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            VerifyChecksumOrchestratorError::Channel(e) => e.code(),
+            VerifyChecksumOrchestratorError::FetchSegment(e) => e.code(),
+            VerifyChecksumOrchestratorError::Aborted(_) => ErrorCodes::Aborted,
+            VerifyChecksumOrchestratorError::Panic(_) => ErrorCodes::Aborted,
+            VerifyChecksumOrchestratorError::Result(_) => ErrorCodes::Internal,
+            VerifyChecksumOrchestratorError::VerifyChecksum(e) => e.code(),
+        }
+    }
+}
+
+impl<E> From<TaskError<E>> for VerifyChecksumOrchestratorError
+where
+    E: Into<VerifyChecksumOrchestratorError>,
+{
+    fn from(value: TaskError<E>) -> Self {
+        match value {
+            TaskError::Panic(e) => VerifyChecksumOrchestratorError::Panic(e.unwrap_or_default()),
+            TaskError::TaskFailed(e) => e.into(),
+            TaskError::Aborted(e) => VerifyChecksumOrchestratorError::Aborted(e),
+        }
+    }
+}
+
+type VerifyChecksumResult = Result<VerifyChecksumOutput, VerifyChecksumOrchestratorError>;
+
+/// The `VerifyChecksumOrchestrator` recomputes the content checksum of a version of a collection
+/// from its records, to compare it with the checksum stored when the version was flushed
+///
+/// # Pipeline
+/// ```text
+///  ┌────────────┐
+///  │            │
+///  │  on_start  │
+///  │            │
+///  └─────┬──────┘
+///        │
+///        ▼
+///  ┌────────────────────────┐
+///  │                        │
+///  │  FetchSegmentOperator  │
+///  │                        │
+///  └─────┬──────────────────┘
+///        │
+///        ▼
+///  ┌──────────────────────────┐
+///  │                          │
+///  │  VerifyChecksumOperator  │
+///  │                          │
+///  └─────┬────────────────────┘
+///        │
+///        ▼
+///  ┌──────────────────┐
+///  │                  │
+///  │  result_channel  │
+///  │                  │
+///  └──────────────────┘
+/// ```
+#[derive(Debug)]
+pub struct VerifyChecksumOrchestrator {
+    // Orchestrator parameters
+    blockfile_provider: BlockfileProvider,
+    dispatcher: ComponentHandle<Dispatcher>,
+    queue: usize,
+
+    // Fetch segments
+    fetch_segment: FetchSegmentOperator,
+
+    // Result channel
+    result_channel: Option<Sender<VerifyChecksumResult>>,
+}
+
+impl VerifyChecksumOrchestrator {
+    pub fn new(
+        blockfile_provider: BlockfileProvider,
+        dispatcher: ComponentHandle<Dispatcher>,
+        queue: usize,
+        fetch_segment: FetchSegmentOperator,
+    ) -> Self {
+        Self {
+            blockfile_provider,
+            dispatcher,
+            queue,
+            fetch_segment,
+            result_channel: None,
+        }
+    }
+
+    pub async fn run(mut self, system: System) -> VerifyChecksumResult {
+        let (tx, rx) = oneshot::channel();
+        self.result_channel = Some(tx);
+        let mut handle = system.start_component(self);
+        let result = rx.await;
+        handle.stop();
+        result?
+    }
+
+    fn terminate_with_error<E>(&mut self, ctx: &ComponentContext<Self>, err: E)
+    where
+        E: Into<VerifyChecksumOrchestratorError>,
+    {
+        let verify_checksum_err = err.into();
+        tracing::error!("Error running orchestrator: {}", &verify_checksum_err);
+        terminate_with_error(self.result_channel.take(), verify_checksum_err, ctx);
+    }
+}
+
+#[async_trait]
+impl Component for VerifyChecksumOrchestrator {
+    fn get_name() -> &'static str {
+        "Verify Checksum Orchestrator"
+    }
+
+    fn queue_size(&self) -> usize {
+        self.queue
+    }
+
+    async fn on_start(&mut self, ctx: &ComponentContext<Self>) {
+        let task = wrap_with_priority(
+            Box::new(self.fetch_segment.clone()),
+            (),
+            ctx.receiver(),
+            TaskPriority::Batch,
+        );
+        if let Err(err) = self.dispatcher.send(task, Some(Span::current())).await {
+            self.terminate_with_error(ctx, err);
+        }
+    }
+}
+
+#[async_trait]
+impl Handler<TaskResult<FetchSegmentOutput, FetchSegmentError>> for VerifyChecksumOrchestrator {
+    type Result = ();
+
+    async fn handle(
+        &mut self,
+        message: TaskResult<FetchSegmentOutput, FetchSegmentError>,
+        ctx: &ComponentContext<Self>,
+    ) {
+        let output = match message.into_inner() {
+            Ok(output) => output,
+            Err(err) => {
+                self.terminate_with_error(ctx, err);
+                return;
+            }
+        };
+        let task = wrap_with_priority(
+            Box::new(VerifyChecksumOperator {}),
+            VerifyChecksumInput {
+                blockfile_provider: self.blockfile_provider.clone(),
+                record_segment: output.record_segment,
+            },
+            ctx.receiver(),
+            TaskPriority::Batch,
+        );
+        if let Err(err) = self.dispatcher.send(task, Some(Span::current())).await {
+            self.terminate_with_error(ctx, err);
+        }
+    }
+}
+
+#[async_trait]
+impl Handler<TaskResult<VerifyChecksumOutput, VerifyChecksumError>> for VerifyChecksumOrchestrator {
+    type Result = ();
+
+    async fn handle(
+        &mut self,
+        message: TaskResult<VerifyChecksumOutput, VerifyChecksumError>,
+        ctx: &ComponentContext<Self>,
+    ) {
+        let output = match message.into_inner() {
+            Ok(output) => output,
+            Err(err) => {
+                self.terminate_with_error(ctx, err);
+                return;
+            }
+        };
+        if let Some(chan) = self.result_channel.take() {
+            if chan.send(Ok(output)).is_err() {
+                tracing::error!("Error sending final result");
+            };
+        }
+    }
+}
@@ -0,0 +1,232 @@
+use chroma_blockstore::provider::BlockfileProvider;
+use chroma_error::{ChromaError, ErrorCodes};
+use chroma_index::hnsw_provider::HnswIndexProvider;
+use chroma_storage::Storage;
+use thiserror::Error;
+use tokio::sync::oneshot::{self, error::RecvError, Sender};
+use tonic::async_trait;
+use tracing::Span;
+
+use crate::{
+    execution::{
+        dispatcher::Dispatcher,
+        operator::{wrap_with_priority, TaskError, TaskPriority, TaskResult},
+        operators::{
+            fetch_segment::{FetchSegmentError, FetchSegmentOperator, FetchSegmentOutput},
+            warm_up::{WarmUpError, WarmUpInput, WarmUpOperator, WarmUpOutput},
+        },
+        orchestration::common::terminate_with_error,
+    },
+    system::{ChannelError, Component, ComponentContext, ComponentHandle, Handler, System},
+};
+
+#[derive(Error, Debug)]
+pub enum WarmUpOrchestratorError {
+    #[error("Error sending message through channel: {0}")]
+    Channel(#[from] ChannelError),
+    #[error("Error running Fetch Segment Operator: {0}")]
+    FetchSegment(#[from] FetchSegmentError),
+    #[error("Task aborted: {0}")]
+    Aborted(String),
+    #[error("Panic running task: {0}")]
+    Panic(String),
+    #[error("Error receiving final result: {0}")]
+    Result(#[from] RecvError),
+    #[error("Error running Warm Up Operator: {0}")]
+    WarmUp(#[from] WarmUpError),
+}
+
+impl ChromaError for WarmUpOrchestratorError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            WarmUpOrchestratorError::Channel(e) => e.code(),
+            WarmUpOrchestratorError::FetchSegment(e) => e.code(),
+            WarmUpOrchestratorError::Aborted(_) => ErrorCodes::Aborted,
+            WarmUpOrchestratorError::Panic(_) => ErrorCodes::Aborted,
+            WarmUpOrchestratorError::Result(_) => ErrorCodes::Internal,
+            WarmUpOrchestratorError::WarmUp(e) => e.code(),
+        }
+    }
+}
+
+impl<E> From<TaskError<E>> for WarmUpOrchestratorError
+where
+    E: Into<WarmUpOrchestratorError>,
+{
+    fn from(value: TaskError<E>) -> Self {
+        match value {
+            TaskError::Panic(e) => WarmUpOrchestratorError::Panic(e.unwrap_or_default()),
+            TaskError::TaskFailed(e) => e.into(),
+            TaskError::Aborted(e) => WarmUpOrchestratorError::Aborted(e),
+        }
+    }
+}
+
+type WarmUpResult = Result<WarmUpOutput, WarmUpOrchestratorError>;
+
+/// The `WarmUpOrchestrator` runs the part of a query that loads the segments of a collection,
+/// without reading any record, so that the later queries on the collection find its data cached
+///
+/// # Pipeline
+/// ```text
+///  ┌────────────┐
+///  │            │
+///  │  on_start  │
+///  │            │
+///  └─────┬──────┘
+///        │
+///        ▼
+///  ┌────────────────────────┐
+///  │                        │
+///  │  FetchSegmentOperator  │
+///  │                        │
+///  └─────┬──────────────────┘
+///        │
+///        ▼
+///  ┌──────────────────┐
+///  │                  │
+///  │  WarmUpOperator  │
+///  │                  │
+///  └─────┬────────────┘
+///        │
+///        ▼
+///  ┌──────────────────┐
+///  │                  │
+///  │  result_channel  │
+///  │                  │
+///  └──────────────────┘
+/// ```
+#[derive(Debug)]
+pub struct WarmUpOrchestrator {
+    // Orchestrator parameters
+    blockfile_provider: BlockfileProvider,
+    dispatcher: ComponentHandle<Dispatcher>,
+    hnsw_provider: HnswIndexProvider,
+    queue: usize,
+    storage: Storage,
+
+    // Fetch segments
+    fetch_segment: FetchSegmentOperator,
+
+    // Result channel
+    result_channel: Option<Sender<WarmUpResult>>,
+}
+
+impl WarmUpOrchestrator {
+    pub fn new(
+        blockfile_provider: BlockfileProvider,
+        dispatcher: ComponentHandle<Dispatcher>,
+        hnsw_provider: HnswIndexProvider,
+        queue: usize,
+        storage: Storage,
+        fetch_segment: FetchSegmentOperator,
+    ) -> Self {
+        Self {
+            blockfile_provider,
+            dispatcher,
+            hnsw_provider,
+            queue,
+            storage,
+            fetch_segment,
+            result_channel: None,
+        }
+    }
+
+    pub async fn run(mut self, system: System) -> WarmUpResult {
+        let (tx, rx) = oneshot::channel();
+        self.result_channel = Some(tx);
+        let mut handle = system.start_component(self);
+        let result = rx.await;
+        handle.stop();
+        result?
+    }
+
+    fn terminate_with_error<E>(&mut self, ctx: &ComponentContext<Self>, err: E)
+    where
+        E: Into<WarmUpOrchestratorError>,
+    {
+        let warm_up_err = err.into();
+        tracing::error!("Error running orchestrator: {}", &warm_up_err);
+        terminate_with_error(self.result_channel.take(), warm_up_err, ctx);
+    }
+}
+
+#[async_trait]
+impl Component for WarmUpOrchestrator {
+    fn get_name() -> &'static str {
+        "Warm Up Orchestrator"
+    }
+
+    fn queue_size(&self) -> usize {
+        self.queue
+    }
+
+    async fn on_start(&mut self, ctx: &ComponentContext<Self>) {
+        let task = wrap_with_priority(
+            Box::new(self.fetch_segment.clone()),
+            (),
+            ctx.receiver(),
+            TaskPriority::Batch,
+        );
+        if let Err(err) = self.dispatcher.send(task, Some(Span::current())).await {
+            self.terminate_with_error(ctx, err);
+        }
+    }
+}
+
+#[async_trait]
+impl Handler<TaskResult<FetchSegmentOutput, FetchSegmentError>> for WarmUpOrchestrator {
+    type Result = ();
+
+    async fn handle(
+        &mut self,
+        message: TaskResult<FetchSegmentOutput, FetchSegmentError>,
+        ctx: &ComponentContext<Self>,
+    ) {
+        let output = match message.into_inner() {
+            Ok(output) => output,
+            Err(err) => {
+                self.terminate_with_error(ctx, err);
+                return;
+            }
+        };
+        let task = wrap_with_priority(
+            Box::new(WarmUpOperator {}),
+            WarmUpInput {
+                blockfile_provider: self.blockfile_provider.clone(),
+                hnsw_provider: self.hnsw_provider.clone(),
+                segments: output,
+                storage: self.storage.clone(),
+            },
+            ctx.receiver(),
+            TaskPriority::Batch,
+        );
+        if let Err(err) = self.dispatcher.send(task, Some(Span::current())).await {
+            self.terminate_with_error(ctx, err);
+        }
+    }
+}
+
+#[async_trait]
+impl Handler<TaskResult<WarmUpOutput, WarmUpError>> for WarmUpOrchestrator {
+    type Result = ();
+
+    async fn handle(
+        &mut self,
+        message: TaskResult<WarmUpOutput, WarmUpError>,
+        ctx: &ComponentContext<Self>,
+    ) {
+        let output = match message.into_inner() {
+            Ok(output) => output,
+            Err(err) => {
+                self.terminate_with_error(ctx, err);
+                return;
+            }
+        };
+        if let Some(chan) = self.result_channel.take() {
+            if chan.send(Ok(output)).is_err() {
+                tracing::error!("Error sending final result");
+            };
+        }
+    }
+}
@@ -0,0 +1,314 @@
+use std::{
+    fmt::{self, Debug, Formatter},
+    sync::Arc,
+};
+
+use chroma_cache::{Cache, Weighted};
+use chroma_types::{
+    BooleanOperator, Chunk, CollectionUuid, LogRecord, MetadataSetValue, Where, WhereComparison,
+};
+
+use super::operators::{
+    filter::FilterOperator,
+    limit::LimitOperator,
+    projection::{ProjectionOperator, ProjectionRecord},
+};
+
+/// The key of the result of a get query. The log watermark is the offset of the latest log
+/// of the collection that the query read, so a new log or a compaction of the collection
+/// changes the key and the stale results are never read again
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ResultCacheKey {
+    pub collection_id: CollectionUuid,
+    pub collection_version: u32,
+    pub log_watermark: i64,
+    /// The canonical form of the filter, limit and projection of the query
+    pub query: String,
+}
+
+impl ResultCacheKey {
+    /// The key of a query on the given version of the collection, whose logs start after
+    /// `log_position` and are given by `logs`
+    pub(crate) fn new(
+        collection_id: CollectionUuid,
+        collection_version: u32,
+        log_position: i64,
+        logs: &Chunk<LogRecord>,
+        filter: &FilterOperator,
+        limit: &LimitOperator,
+        projection: &ProjectionOperator,
+    ) -> Self {
+        let log_watermark = logs
+            .iter()
+            .map(|(log, _)| log.log_offset)
+            .max()
+            .unwrap_or(log_position)
+            .max(log_position);
+        Self {
+            collection_id,
+            collection_version,
+            log_watermark,
+            query: canonical_query(filter, limit, projection),
+        }
+    }
+}
+
+/// The records of the result of a get query
+#[derive(Clone, Debug)]
+pub struct CachedResult(pub Arc<Vec<ProjectionRecord>>);
+
+impl Weighted for CachedResult {
+    fn weight(&self) -> usize {
+        self.0
+            .iter()
+            .map(ProjectionRecord::get_size)
+            .sum::<usize>()
+            .max(1)
+    }
+}
+
+/// The cache of the results of get queries that is shared across queries
+pub type SharedResultCache = Arc<dyn Cache<ResultCacheKey, CachedResult>>;
+
+/// A cache of the results of get queries that repeat on an unchanged collection, e.g. the
+/// same page of records that many clients poll for
+#[derive(Clone, Default)]
+pub struct ResultCache {
+    shared: Option<SharedResultCache>,
+}
+
+impl Debug for ResultCache {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResultCache")
+            .field("shared", &self.shared.is_some())
+            .finish()
+    }
+}
+
+impl ResultCache {
+    pub fn new(shared: Option<SharedResultCache>) -> Self {
+        Self { shared }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.shared.is_some()
+    }
+
+    pub(crate) async fn get(&self, key: &ResultCacheKey) -> Option<Vec<ProjectionRecord>> {
+        match self.shared.as_ref()?.get(key).await {
+            Ok(Some(CachedResult(records))) => Some(records.as_ref().clone()),
+            Ok(None) => None,
+            Err(e) => {
+                tracing::warn!("Error reading the result cache: {}", e);
+                None
+            }
+        }
+    }
+
+    pub(crate) async fn insert(&self, key: ResultCacheKey, records: &[ProjectionRecord]) {
+        if let Some(shared) = self.shared.as_ref() {
+            shared
+                .insert(key, CachedResult(Arc::new(records.to_vec())))
+                .await;
+        }
+    }
+}
+
+// The form of the query that is equal for queries with the same result, e.g. regardless of
+// the order of the ids or of the operands of a conjunction
+fn canonical_query(
+    filter: &FilterOperator,
+    limit: &LimitOperator,
+    projection: &ProjectionOperator,
+) -> String {
+    let ids = filter.query_ids.as_ref().map(|ids| {
+        let mut ids = ids.clone();
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    });
+    format!(
+        "ids={:?} where={} skip={} fetch={:?} document={} embedding={} metadata={} uri={}",
+        ids,
+        filter
+            .where_clause
+            .as_ref()
+            .map(canonical_where)
+            .unwrap_or_default(),
+        limit.skip,
+        limit.fetch,
+        projection.document,
+        projection.embedding,
+        projection.metadata,
+        projection.uri,
+    )
+}
+
+fn canonical_where(clause: &Where) -> String {
+    match clause {
+        Where::DirectWhereComparison(direct_comparison) => {
+            let mut direct_comparison = direct_comparison.clone();
+            if let WhereComparison::Set(_, values) = &mut direct_comparison.comparison {
+                match values {
+                    MetadataSetValue::Bool(values) => values.sort_unstable(),
+                    MetadataSetValue::Int(values) => values.sort_unstable(),
+                    MetadataSetValue::Float(values) => values.sort_unstable_by(f64::total_cmp),
+                    MetadataSetValue::Str(values) => values.sort_unstable(),
+                }
+            }
+            format!("{:?}", direct_comparison)
+        }
+        Where::DirectWhereDocumentComparison(direct_document_comparison) => {
+            format!("{:?}", direct_document_comparison)
+        }
+        Where::WhereChildren(where_children) => {
+            let mut children = where_children
+                .children
+                .iter()
+                .map(canonical_where)
+                .collect::<Vec<_>>();
+            children.sort_unstable();
+            let operator = match where_children.operator {
+                BooleanOperator::And => "$and",
+                BooleanOperator::Or => "$or",
+            };
+            format!("{}[{}]", operator, children.join(","))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chroma_types::{
+        BooleanOperator, CollectionUuid, DirectWhereComparison, MetadataSetValue, MetadataValue,
+        PrimitiveOperator, SetOperator, Where, WhereChildren, WhereComparison,
+    };
+
+    use super::{canonical_query, ResultCacheKey};
+    use crate::{
+        execution::operators::{
+            filter::FilterOperator,
+            limit::LimitOperator,
+            projection::{ProjectionOperator, DEFAULT_HYDRATION_CONCURRENCY},
+        },
+        log::test::{upsert_generator, LogGenerator},
+    };
+
+    fn filter(query_ids: Vec<&str>, set: Vec<i64>, swap: bool) -> FilterOperator {
+        let mut children = vec![
+            Where::DirectWhereComparison(DirectWhereComparison {
+                key: "a".to_string(),
+                comparison: WhereComparison::Primitive(
+                    PrimitiveOperator::Equal,
+                    MetadataValue::Str("x".to_string()),
+                ),
+            }),
+            Where::DirectWhereComparison(DirectWhereComparison {
+                key: "b".to_string(),
+                comparison: WhereComparison::Set(SetOperator::In, MetadataSetValue::Int(set)),
+            }),
+        ];
+        if swap {
+            children.reverse();
+        }
+        FilterOperator {
+            query_ids: Some(query_ids.into_iter().map(ToString::to_string).collect()),
+            where_clause: Some(Where::WhereChildren(WhereChildren {
+                operator: BooleanOperator::And,
+                children,
+            })),
+        }
+    }
+
+    #[test]
+    fn test_canonical_query() {
+        let limit = LimitOperator {
+            skip: 0,
+            fetch: Some(10),
+        };
+        let projection = ProjectionOperator {
+            document: true,
+            embedding: false,
+            metadata: true,
+            uri: false,
+            concurrency: DEFAULT_HYDRATION_CONCURRENCY,
+            default_include: false,
+        };
+        let query = canonical_query(
+            &filter(vec!["1", "2"], vec![1, 2], false),
+            &limit,
+            &projection,
+        );
+        // The order of the ids, the set values and the operands does not change the query
+        assert_eq!(
+            query,
+            canonical_query(
+                &filter(vec!["2", "1", "2"], vec![2, 1], true),
+                &limit,
+                &projection
+            )
+        );
+        assert_ne!(
+            query,
+            canonical_query(&filter(vec!["1"], vec![1, 2], false), &limit, &projection)
+        );
+        assert_ne!(
+            query,
+            canonical_query(
+                &filter(vec!["1", "2"], vec![1, 2], false),
+                &LimitOperator {
+                    skip: 10,
+                    fetch: Some(10),
+                },
+                &projection
+            )
+        );
+    }
+
+    #[test]
+    fn test_log_watermark() {
+        let generator = LogGenerator {
+            generator: upsert_generator,
+        };
+        let filter = FilterOperator {
+            query_ids: None,
+            where_clause: None,
+        };
+        let limit = LimitOperator {
+            skip: 0,
+            fetch: None,
+        };
+        let projection = ProjectionOperator {
+            document: false,
+            embedding: false,
+            metadata: false,
+            uri: false,
+            concurrency: DEFAULT_HYDRATION_CONCURRENCY,
+            default_include: false,
+        };
+        let collection_id = CollectionUuid::new();
+        let key = |log_position, logs| {
+            ResultCacheKey::new(
+                collection_id,
+                1,
+                log_position,
+                &logs,
+                &filter,
+                &limit,
+                &projection,
+            )
+        };
+
+        assert_eq!(key(10, generator.generate_chunk(11..=20)).log_watermark, 20);
+        // A new log changes the key
+        assert_ne!(
+            key(10, generator.generate_chunk(11..=20)),
+            key(10, generator.generate_chunk(11..=21))
+        );
+        // Without new logs the watermark is the compacted log position
+        assert_eq!(
+            key(20, generator.generate_chunk(std::iter::empty())).log_watermark,
+            20
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use std::{
+    collections::VecDeque,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use chroma_types::{chroma_proto, CollectionUuid};
+use parking_lot::Mutex;
+
+use super::{
+    config::SlowQueryLogConfig,
+    explain::{QueryPlan, QueryPlanRecorder},
+    metrics::QueryType,
+};
+
+/// What a query asks for, without the keys and values of its filter, so that it can be logged
+/// without exposing the data of the collection
+#[derive(Clone, Debug)]
+pub(crate) struct QuerySummary {
+    pub(crate) collection_id: CollectionUuid,
+    pub(crate) query_type: QueryType,
+    /// The operators of the filter in depth first order
+    pub(crate) filter_operators: Vec<&'static str>,
+    /// The number of ids that the query is restricted to, if any
+    pub(crate) num_ids: Option<usize>,
+    pub(crate) limit: Option<u32>,
+    pub(crate) offset: u32,
+}
+
+/// A query that took longer than the threshold of the slow query log
+#[derive(Clone, Debug)]
+pub(crate) struct SlowQuery {
+    pub(crate) summary: QuerySummary,
+    pub(crate) elapsed: Duration,
+    pub(crate) plan: QueryPlan,
+    pub(crate) logged_at: SystemTime,
+}
+
+/// The log of the queries that take longer than a threshold. Every slow query is logged with
+/// its plan, and the most recent ones are kept in a ring buffer for the admin service
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SlowQueryLog {
+    threshold: Option<Duration>,
+    capacity: usize,
+    recent: Arc<Mutex<VecDeque<SlowQuery>>>,
+}
+
+impl SlowQueryLog {
+    pub(crate) fn new(config: &SlowQueryLogConfig) -> Self {
+        Self {
+            threshold: config.threshold_ms.map(Duration::from_millis),
+            capacity: config.capacity,
+            recent: Arc::new(Mutex::new(VecDeque::with_capacity(config.capacity))),
+        }
+    }
+
+    /// The recorder of the plan of a query, which is enabled if the query is explained or if it
+    /// may be logged as slow
+    pub(crate) fn plan_recorder(&self, explain: bool) -> QueryPlanRecorder {
+        if explain || self.threshold.is_some() {
+            QueryPlanRecorder::enabled()
+        } else {
+            QueryPlanRecorder::default()
+        }
+    }
+
+    /// Logs the query with the plan recorded so far if it took longer than the threshold
+    pub(crate) fn record(
+        &self,
+        summary: QuerySummary,
+        elapsed: Duration,
+        plan: &QueryPlanRecorder,
+    ) {
+        match self.threshold {
+            Some(threshold) if elapsed >= threshold => {}
+            _ => return,
+        }
+        let plan = plan.plan().unwrap_or_default();
+        let read_stats = plan.read_stats();
+        tracing::warn!(
+            collection_id = %summary.collection_id,
+            query_type = summary.query_type.as_str(),
+            filter = %summary.filter_operators.join(" "),
+            num_ids = summary.num_ids,
+            limit = summary.limit,
+            offset = summary.offset,
+            elapsed_ms = elapsed.as_millis() as u64,
+            cache_hit_ratio = read_stats.cache_hit_ratio(),
+            bytes_read = read_stats.bytes_read,
+            operators = %operator_timings(&plan),
+            "Slow query"
+        );
+
+        if self.capacity == 0 {
+            return;
+        }
+        let mut recent = self.recent.lock();
+        if recent.len() == self.capacity {
+            recent.pop_front();
+        }
+        recent.push_back(SlowQuery {
+            summary,
+            elapsed,
+            plan,
+            logged_at: SystemTime::now(),
+        });
+    }
+
+    /// The most recent slow queries, from the oldest to the newest
+    pub(crate) fn recent(&self) -> Vec<SlowQuery> {
+        self.recent.lock().iter().cloned().collect()
+    }
+}
+
+// The time of each operator of the plan, e.g. `FilterOperator=1.2ms`
+fn operator_timings(plan: &QueryPlan) -> String {
+    plan.nodes
+        .iter()
+        .map(|node| match node.elapsed {
+            Some(elapsed) => format!("{}={:?}", node.operator, elapsed),
+            None => format!("{}=unfinished", node.operator),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl From<SlowQuery> for chroma_proto::SlowQuery {
+    fn from(query: SlowQuery) -> Self {
+        chroma_proto::SlowQuery {
+            collection_id: query.summary.collection_id.to_string(),
+            query_type: query.summary.query_type.as_str().to_string(),
+            filter_operators: query
+                .summary
+                .filter_operators
+                .into_iter()
+                .map(ToString::to_string)
+                .collect(),
+            num_ids: query.summary.num_ids.map(|num_ids| num_ids as u64),
+            limit: query.summary.limit,
+            offset: query.summary.offset,
+            elapsed_micros: query.elapsed.as_micros() as u64,
+            logged_at_micros: query
+                .logged_at
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_micros() as u64,
+            plan: Some(query.plan.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chroma_types::CollectionUuid;
+    use uuid::Uuid;
+
+    use super::{QuerySummary, SlowQueryLog};
+    use crate::execution::{config::SlowQueryLogConfig, metrics::QueryType};
+
+    #[test]
+    fn test_slow_query_log() {
+        let summary = |limit| QuerySummary {
+            collection_id: CollectionUuid::new(),
+            query_type: QueryType::Get,
+            filter_operators: vec!["$and", "$eq", "$gt"],
+            num_ids: None,
+            limit: Some(limit),
+            offset: 0,
+        };
+
+        let disabled = SlowQueryLog::default();
+        let plan = disabled.plan_recorder(false);
+        assert!(plan.plan().is_none());
+        disabled.record(summary(1), Duration::from_secs(60), &plan);
+        assert!(disabled.recent().is_empty());
+
+        let log = SlowQueryLog::new(&SlowQueryLogConfig {
+            threshold_ms: Some(100),
+            capacity: 2,
+        });
+        let plan = log.plan_recorder(false);
+        plan.start(Uuid::new_v4(), "FilterOperator", [], None);
+        log.record(summary(1), Duration::from_millis(10), &plan);
+        assert!(log.recent().is_empty());
+        for limit in 2..5 {
+            log.record(summary(limit), Duration::from_millis(100), &plan);
+        }
+        // Only the most recent slow queries are kept
+        let recent = log.recent();
+        assert_eq!(
+            recent
+                .iter()
+                .map(|query| query.summary.limit)
+                .collect::<Vec<_>>(),
+            vec![Some(3), Some(4)]
+        );
+        assert_eq!(recent[0].plan.nodes.len(), 1);
+    }
+}
@@ -0,0 +1,263 @@
+use std::{
+    mem::size_of,
+    path::{Path, PathBuf},
+};
+
+use chroma_error::{ChromaError, ErrorCodes};
+use futures::{stream, Stream};
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::config::QuerySpillConfig;
+
+/// Where and when a `SpillableVec` writes its values to disk
+///
+/// # Parameters
+/// - `path`: The local directory under which the spilled values are written
+/// - `budget_bytes`: The number of bytes of values kept in memory before they are spilled
+#[derive(Clone, Debug)]
+pub struct SpillConfig {
+    pub path: PathBuf,
+    pub budget_bytes: usize,
+}
+
+impl From<&QuerySpillConfig> for SpillConfig {
+    fn from(config: &QuerySpillConfig) -> Self {
+        Self {
+            path: PathBuf::from(&config.path),
+            budget_bytes: config.budget_bytes,
+        }
+    }
+}
+
+/// A value with a fixed size encoding, so that it can be spilled to disk
+pub trait Spillable: Sized {
+    /// The number of bytes of the encoding
+    const ENCODED_SIZE: usize;
+
+    fn encode(&self, bytes: &mut Vec<u8>);
+
+    fn decode(bytes: &[u8]) -> Self;
+}
+
+#[derive(Error, Debug)]
+pub enum SpillError {
+    #[error("Error reading spilled values: {0}")]
+    Read(String),
+    #[error("Error writing spilled values: {0}")]
+    Write(String),
+}
+
+impl ChromaError for SpillError {
+    fn code(&self) -> ErrorCodes {
+        ErrorCodes::Internal
+    }
+}
+
+/// A scratch directory with a random name, which is removed once it is dropped so that its
+/// files do not outlive a merge or an export that fails or is cancelled
+#[derive(Debug)]
+pub(crate) struct SpillDir {
+    path: PathBuf,
+}
+
+impl SpillDir {
+    pub(crate) async fn create(parent: &Path) -> std::io::Result<Self> {
+        let path = parent.join(Uuid::new_v4().to_string());
+        tokio::fs::create_dir_all(&path).await?;
+        Ok(Self { path })
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for SpillDir {
+    fn drop(&mut self) {
+        let path = std::mem::take(&mut self.path);
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn(async move {
+                    if let Err(e) = tokio::fs::remove_dir_all(&path).await {
+                        tracing::warn!("Error removing spill directory {:?}: {}", path, e);
+                    }
+                });
+            }
+            Err(_) => {
+                if let Err(e) = std::fs::remove_dir_all(&path) {
+                    tracing::warn!("Error removing spill directory {:?}: {}", path, e);
+                }
+            }
+        }
+    }
+}
+
+/// An append only vector that writes its values to a local directory in chunks once the
+/// values in memory exceed the budget. Without a config, it never spills
+///
+/// The values are read back a chunk at a time by `into_stream`, and the directory is removed
+/// when the vector or its stream is dropped
+#[derive(Debug)]
+pub struct SpillableVec<T> {
+    config: Option<SpillConfig>,
+    dir: Option<SpillDir>,
+    chunks: usize,
+    buffer: Vec<T>,
+}
+
+impl<T: Spillable> SpillableVec<T> {
+    pub fn new(config: Option<SpillConfig>) -> Self {
+        Self {
+            config,
+            dir: None,
+            chunks: 0,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// The number of chunks written to disk
+    #[cfg(test)]
+    pub fn spilled_chunks(&self) -> usize {
+        self.chunks
+    }
+
+    pub async fn push(&mut self, value: T) -> Result<(), SpillError> {
+        self.buffer.push(value);
+        let config = match &self.config {
+            Some(config) if self.buffer.len() * size_of::<T>() >= config.budget_bytes => config,
+            _ => return Ok(()),
+        };
+        if self.dir.is_none() {
+            let dir = SpillDir::create(&config.path)
+                .await
+                .map_err(|e| SpillError::Write(e.to_string()))?;
+            self.dir = Some(dir);
+        }
+        let dir = self
+            .dir
+            .as_ref()
+            .expect("The spill directory should be created");
+        let mut bytes = Vec::with_capacity(self.buffer.len() * T::ENCODED_SIZE);
+        for value in self.buffer.drain(..) {
+            value.encode(&mut bytes);
+        }
+        tokio::fs::write(dir.path.join(self.chunks.to_string()), &bytes)
+            .await
+            .map_err(|e| SpillError::Write(e.to_string()))?;
+        self.chunks += 1;
+        Ok(())
+    }
+
+    /// Returns the values in the order they are pushed, a chunk at a time. Each spilled chunk
+    /// is removed from disk once it is read
+    pub fn into_stream(self) -> impl Stream<Item = Result<Vec<T>, SpillError>> {
+        let SpillableVec {
+            dir,
+            chunks,
+            buffer,
+            ..
+        } = self;
+        stream::try_unfold(
+            (dir, 0, Some(buffer)),
+            move |(dir, chunk, buffer)| async move {
+                match &dir {
+                    Some(spill_dir) if chunk < chunks => {
+                        let path = spill_dir.path.join(chunk.to_string());
+                        let bytes = tokio::fs::read(&path)
+                            .await
+                            .map_err(|e| SpillError::Read(e.to_string()))?;
+                        tokio::fs::remove_file(&path)
+                            .await
+                            .map_err(|e| SpillError::Write(e.to_string()))?;
+                        let values = bytes.chunks_exact(T::ENCODED_SIZE).map(T::decode).collect();
+                        Ok::<_, SpillError>(Some((values, (dir, chunk + 1, buffer))))
+                    }
+                    _ => Ok(buffer
+                        .filter(|buffer| !buffer.is_empty())
+                        .map(|buffer| (buffer, (dir, chunk, None)))),
+                }
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{mem::size_of, path::Path, time::Duration};
+
+    use futures::{StreamExt, TryStreamExt};
+
+    use super::{SpillConfig, Spillable, SpillableVec};
+
+    impl Spillable for u32 {
+        const ENCODED_SIZE: usize = size_of::<u32>();
+
+        fn encode(&self, bytes: &mut Vec<u8>) {
+            bytes.extend_from_slice(&self.to_le_bytes());
+        }
+
+        fn decode(bytes: &[u8]) -> Self {
+            u32::from_le_bytes(bytes.try_into().expect("Should be 4 bytes"))
+        }
+    }
+
+    // The spill directory is removed in the background once it is dropped
+    async fn wait_until_empty(path: &Path) -> bool {
+        for _ in 0..100 {
+            if std::fs::read_dir(path).unwrap().next().is_none() {
+                return true;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        false
+    }
+
+    #[tokio::test]
+    async fn test_spill_and_read_back() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let mut values = SpillableVec::new(Some(SpillConfig {
+            path: tmp_dir.path().to_path_buf(),
+            budget_bytes: 10 * size_of::<u32>(),
+        }));
+        for value in 0..105u32 {
+            values.push(value).await.unwrap();
+        }
+        assert_eq!(values.spilled_chunks(), 10);
+
+        let chunks = values.into_stream().try_collect::<Vec<_>>().await.unwrap();
+        assert_eq!(chunks.len(), 11);
+        assert_eq!(chunks.concat(), (0..105).collect::<Vec<_>>());
+        assert!(wait_until_empty(tmp_dir.path()).await);
+    }
+
+    #[tokio::test]
+    async fn test_spill_removed_when_dropped() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let mut values = SpillableVec::new(Some(SpillConfig {
+            path: tmp_dir.path().to_path_buf(),
+            budget_bytes: 10 * size_of::<u32>(),
+        }));
+        for value in 0..50u32 {
+            values.push(value).await.unwrap();
+        }
+        assert_eq!(std::fs::read_dir(tmp_dir.path()).unwrap().count(), 1);
+
+        // The stream is dropped before all of its chunks are read
+        let mut stream = Box::pin(values.into_stream());
+        assert!(stream.next().await.is_some());
+        drop(stream);
+        assert!(wait_until_empty(tmp_dir.path()).await);
+    }
+
+    #[tokio::test]
+    async fn test_no_spill_without_config() {
+        let mut values = SpillableVec::new(None);
+        for value in 0..100u32 {
+            values.push(value).await.unwrap();
+        }
+        assert_eq!(values.spilled_chunks(), 0);
+        let chunks = values.into_stream().try_collect::<Vec<_>>().await.unwrap();
+        assert_eq!(chunks, vec![(0..100).collect::<Vec<_>>()]);
+    }
+}
@@ -1,25 +1,47 @@
-use super::{dispatcher::TaskRequestMessage, operator::TaskMessage};
+use super::{
+    dispatcher::{RetryTaskMessage, TaskRequestMessage},
+    operator::{TaskMessage, TaskPriority, TaskStatus},
+};
 use crate::system::{Component, ComponentContext, ComponentRuntime, Handler, ReceiverForMessage};
 use async_trait::async_trait;
+use std::cell::Cell;
 use std::fmt::{Debug, Formatter, Result};
 use tracing::{trace_span, Instrument, Span};
 
+thread_local! {
+    static ON_WORKER_THREAD: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Whether the current thread is a worker thread of the dispatcher. Each worker thread runs
+/// its tasks on a dedicated single threaded runtime, so its tasks never move to another thread
+pub(super) fn on_worker_thread() -> bool {
+    ON_WORKER_THREAD.with(Cell::get)
+}
+
 /// A worker thread is responsible for executing tasks
 /// It sends requests to the dispatcher for new tasks.
 /// # Implementation notes
 /// - The actor loop will block until work is available
+/// - The worker prefers the tasks of its priority class, and runs tasks of the other
+///   class only when no task of its own class is queued
 pub(super) struct WorkerThread {
     dispatcher: Box<dyn ReceiverForMessage<TaskRequestMessage>>,
+    retries: Box<dyn ReceiverForMessage<RetryTaskMessage>>,
+    priority: TaskPriority,
     queue_size: usize,
 }
 
 impl WorkerThread {
     pub(super) fn new(
         dispatcher: Box<dyn ReceiverForMessage<TaskRequestMessage>>,
+        retries: Box<dyn ReceiverForMessage<RetryTaskMessage>>,
+        priority: TaskPriority,
         queue_size: usize,
     ) -> WorkerThread {
         WorkerThread {
             dispatcher,
+            retries,
+            priority,
             queue_size,
         }
     }
@@ -27,7 +49,9 @@ impl WorkerThread {
 
 impl Debug for WorkerThread {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        f.debug_struct("WorkerThread").finish()
+        f.debug_struct("WorkerThread")
+            .field("priority", &self.priority)
+            .finish()
     }
 }
 
@@ -46,7 +70,8 @@ impl Component for WorkerThread {
     }
 
     async fn on_start(&mut self, ctx: &ComponentContext<Self>) {
-        let req = TaskRequestMessage::new(ctx.receiver());
+        ON_WORKER_THREAD.with(|on_worker_thread| on_worker_thread.set(true));
+        let req = TaskRequestMessage::new(ctx.receiver(), self.priority);
         let _req = self.dispatcher.send(req, None).await;
         // TODO: what to do with resp?
     }
@@ -59,8 +84,16 @@ impl Handler<TaskMessage> for WorkerThread {
     async fn handle(&mut self, task: TaskMessage, ctx: &ComponentContext<WorkerThread>) {
         let child_span =
             trace_span!(parent: Span::current(), "Task execution", name = task.get_name());
-        task.run().instrument(child_span).await;
-        let req: TaskRequestMessage = TaskRequestMessage::new(ctx.receiver());
+        // A retried task is handed back to the dispatcher, so that its backoff does not hold
+        // the worker thread
+        let status = task.run().instrument(child_span).await;
+        if let TaskStatus::RetryAfter(backoff) = status {
+            let _res = self
+                .retries
+                .send(RetryTaskMessage::new(task, backoff), None)
+                .await;
+        }
+        let req: TaskRequestMessage = TaskRequestMessage::new(ctx.receiver(), self.priority);
         let _res = self.dispatcher.send(req, None).await;
         // TODO: task run should be able to error and we should send it as part of the result
     }
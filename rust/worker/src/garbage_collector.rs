@@ -0,0 +1,620 @@
+use std::{
+    collections::{HashMap, HashSet},
+    time::{Duration, SystemTime},
+};
+
+use chroma_blockstore::provider::{BlockfileObject, BlockfileProvider};
+use chroma_error::{ChromaError, ErrorCodes};
+use chroma_index::{
+    hnsw_provider::{HnswIndexProvider, HnswIndexProviderObjectError, HnswObject},
+    IndexUuid,
+};
+use serde::Deserialize;
+use thiserror::Error;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::sysdb::sysdb::{GetCollectionsError, GetSegmentsError, SysDb};
+
+/// The config of the garbage collection of the blockfiles and HNSW indexes in storage
+/// - `enabled`: Whether the compaction service collects the garbage. Only one compactor of a
+///   deployment needs to
+/// - `interval_secs`: The interval between the collections
+/// - `grace_period_secs`: How long an object must be unreferenced, and since it was written,
+///   before it is deleted. It must be longer than a compaction and a query take, so that the
+///   objects that they are writing or reading are not deleted
+/// - `dry_run`: Whether the objects are only logged instead of deleted
+#[derive(Debug, Deserialize)]
+pub(crate) struct GarbageCollectorConfig {
+    #[serde(default)]
+    pub(crate) enabled: bool,
+    #[serde(default = "GarbageCollectorConfig::default_interval_secs")]
+    pub(crate) interval_secs: u64,
+    #[serde(default = "GarbageCollectorConfig::default_grace_period_secs")]
+    pub(crate) grace_period_secs: u64,
+    #[serde(default)]
+    pub(crate) dry_run: bool,
+}
+
+impl GarbageCollectorConfig {
+    fn default_interval_secs() -> u64 {
+        60 * 60
+    }
+
+    fn default_grace_period_secs() -> u64 {
+        24 * 60 * 60
+    }
+}
+
+impl Default for GarbageCollectorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_secs: Self::default_interval_secs(),
+            grace_period_secs: Self::default_grace_period_secs(),
+            dry_run: false,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub(crate) enum GarbageCollectionError {
+    #[error("Error getting collections: {0}")]
+    GetCollections(#[from] GetCollectionsError),
+    #[error("Error getting segments: {0}")]
+    GetSegments(#[from] GetSegmentsError),
+    #[error("Error reading root {0}: {1}")]
+    ReadRoot(Uuid, Box<dyn ChromaError>),
+    #[error("Error reading manifest of index {0}: {1}")]
+    ReadManifest(Uuid, Box<HnswIndexProviderObjectError>),
+    #[error("Error listing objects: {0}")]
+    ListObjects(Box<dyn ChromaError>),
+}
+
+impl ChromaError for GarbageCollectionError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            GarbageCollectionError::GetCollections(e) => e.code(),
+            GarbageCollectionError::GetSegments(e) => e.code(),
+            GarbageCollectionError::ReadRoot(_, e) => e.code(),
+            GarbageCollectionError::ReadManifest(_, e) => e.code(),
+            GarbageCollectionError::ListObjects(e) => e.code(),
+        }
+    }
+}
+
+/// An object in storage that the garbage collector may delete
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum StorageObject {
+    Blockfile(BlockfileObject),
+    Hnsw(HnswObject),
+}
+
+impl StorageObject {
+    // The roots and the manifests are deleted before the objects that they reference, so that
+    // a collection that fails midway does not leave a root or a manifest whose objects are gone
+    fn references_objects(&self) -> bool {
+        matches!(
+            self,
+            StorageObject::Blockfile(BlockfileObject::Root(_))
+                | StorageObject::Hnsw(HnswObject::Manifest(_))
+        )
+    }
+}
+
+/// The objects that a collection found
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct GarbageCollectionStats {
+    // The roots of the segments and the blocks that they reference
+    pub(crate) live_roots: usize,
+    pub(crate) reachable_blocks: usize,
+    // The HNSW indexes of the segments and the chunks that their manifests reference
+    pub(crate) live_indexes: usize,
+    pub(crate) reachable_chunks: usize,
+    // The objects that nothing references, and those of them that are deleted, or would be
+    // in a dry run
+    pub(crate) unreachable_objects: usize,
+    pub(crate) deleted_objects: usize,
+}
+
+/// Deletes the roots, the blocks, and the HNSW index files, manifests and chunks in storage that
+/// no segment references, which failed flushes and superseded compactions leave behind
+///
+/// The roots and the indexes in the file paths of the segments are live, and the blocks and
+/// the chunks that they reference are reachable. Everything else in storage is garbage once it is older than the grace
+/// period and has been unreferenced for as long, so that the objects of a compaction that is
+/// not registered yet, and those of a version that a query is still reading, are kept
+pub(crate) struct GarbageCollector {
+    sysdb: Box<SysDb>,
+    blockfile_provider: BlockfileProvider,
+    hnsw_provider: HnswIndexProvider,
+    enabled: bool,
+    interval: Duration,
+    grace_period: Duration,
+    dry_run: bool,
+    // When each of the unreachable objects was first found unreachable
+    unreachable_since: HashMap<StorageObject, SystemTime>,
+}
+
+impl GarbageCollector {
+    pub(crate) fn new(
+        config: &GarbageCollectorConfig,
+        sysdb: Box<SysDb>,
+        blockfile_provider: BlockfileProvider,
+        hnsw_provider: HnswIndexProvider,
+    ) -> Self {
+        Self {
+            sysdb,
+            blockfile_provider,
+            hnsw_provider,
+            enabled: config.enabled,
+            interval: Duration::from_secs(config.interval_secs),
+            grace_period: Duration::from_secs(config.grace_period_secs),
+            dry_run: config.dry_run,
+            unreachable_since: HashMap::new(),
+        }
+    }
+
+    // The ids in the file paths of the segments, which are the roots of blockfiles or the ids
+    // of HNSW indexes
+    async fn live_ids(&mut self) -> Result<HashSet<Uuid>, GarbageCollectionError> {
+        let mut ids = HashSet::new();
+        let collections = self.sysdb.get_collections(None, None, None, None).await?;
+        for collection in collections {
+            let segments = self
+                .sysdb
+                .get_segments(None, None, None, collection.collection_id)
+                .await?;
+            ids.extend(
+                segments
+                    .iter()
+                    .flat_map(|segment| segment.file_path.values().flatten())
+                    .filter_map(|path| Uuid::parse_str(path).ok()),
+            );
+        }
+        Ok(ids)
+    }
+
+    // Whether the object is older than the grace period and has been unreachable for as long
+    fn expired(&self, written: SystemTime, unreachable_since: SystemTime, now: SystemTime) -> bool {
+        let older_than_grace_period = |time: SystemTime| {
+            now.duration_since(time)
+                .is_ok_and(|age| age >= self.grace_period)
+        };
+        older_than_grace_period(written) && older_than_grace_period(unreachable_since)
+    }
+
+    pub(crate) async fn collect(
+        &mut self,
+    ) -> Result<GarbageCollectionStats, GarbageCollectionError> {
+        self.collect_at(SystemTime::now()).await
+    }
+
+    async fn collect_at(
+        &mut self,
+        now: SystemTime,
+    ) -> Result<GarbageCollectionStats, GarbageCollectionError> {
+        // The live roots are read before the objects are listed, so that the objects of a
+        // compaction that is registered in between are listed younger than the grace period
+        let mut live_roots = HashSet::new();
+        let mut reachable_blocks = HashSet::new();
+        let mut live_indexes = HashSet::new();
+        let mut reachable_chunks = HashSet::new();
+        for id in self.live_ids().await? {
+            // An id that is not the root of a blockfile is the id of an HNSW index, whose files
+            // or manifest are live. Any other failure aborts the collection, since a block or a
+            // chunk could not be marked
+            match self.blockfile_provider.referenced_block_ids(&id).await {
+                Ok(Some(block_ids)) => {
+                    live_roots.insert(id);
+                    reachable_blocks.extend(block_ids);
+                    continue;
+                }
+                Ok(None) => {}
+                Err(e) => return Err(GarbageCollectionError::ReadRoot(id, e)),
+            }
+            let index_id = IndexUuid(id);
+            live_indexes.insert(index_id);
+            match self.hnsw_provider.referenced_chunks(&index_id).await {
+                Ok(Some(chunks)) => reachable_chunks.extend(chunks),
+                // The index is flushed as whole files, or the id is of neither
+                Ok(None) => {}
+                Err(e) => return Err(GarbageCollectionError::ReadManifest(id, e)),
+            }
+        }
+        let blockfile_objects = self
+            .blockfile_provider
+            .list_objects()
+            .await
+            .map_err(GarbageCollectionError::ListObjects)?;
+        let hnsw_objects = self
+            .hnsw_provider
+            .list_objects()
+            .await
+            .map_err(|e| GarbageCollectionError::ListObjects(e as Box<dyn ChromaError>))?;
+
+        let mut stats = GarbageCollectionStats {
+            live_roots: live_roots.len(),
+            reachable_blocks: reachable_blocks.len(),
+            live_indexes: live_indexes.len(),
+            reachable_chunks: reachable_chunks.len(),
+            ..Default::default()
+        };
+        let mut unreachable = blockfile_objects
+            .into_iter()
+            .filter(|(object, _)| match object {
+                BlockfileObject::Root(id) => !live_roots.contains(id),
+                BlockfileObject::Block(id) => !reachable_blocks.contains(id),
+            })
+            .map(|(object, written)| (StorageObject::Blockfile(object), written))
+            .chain(
+                hnsw_objects
+                    .into_iter()
+                    .filter(|(object, _)| match object {
+                        HnswObject::File(id, _) | HnswObject::Manifest(id) => {
+                            !live_indexes.contains(id)
+                        }
+                        HnswObject::Chunk(..) => !reachable_chunks.contains(object),
+                    })
+                    .map(|(object, written)| (StorageObject::Hnsw(object), written)),
+            )
+            .collect::<Vec<_>>();
+        unreachable.sort_by_key(|(object, _)| !object.references_objects());
+        stats.unreachable_objects = unreachable.len();
+
+        let mut unreachable_since = HashMap::with_capacity(unreachable.len());
+        for (object, written) in unreachable {
+            let since = self.unreachable_since.get(&object).copied().unwrap_or(now);
+            if !self.expired(written, since, now) {
+                unreachable_since.insert(object, since);
+                continue;
+            }
+            if self.dry_run {
+                tracing::info!("Garbage collection dry run would delete {:?}", object);
+                unreachable_since.insert(object, since);
+                stats.deleted_objects += 1;
+                continue;
+            }
+            let res = match &object {
+                StorageObject::Blockfile(blockfile_object) => {
+                    self.blockfile_provider
+                        .delete_object(*blockfile_object)
+                        .await
+                }
+                StorageObject::Hnsw(hnsw_object) => self
+                    .hnsw_provider
+                    .delete_object(hnsw_object)
+                    .await
+                    .map_err(|e| e as Box<dyn ChromaError>),
+            };
+            match res {
+                Ok(()) => stats.deleted_objects += 1,
+                Err(e) => {
+                    tracing::warn!("Error deleting {:?}: {}", object, e);
+                    unreachable_since.insert(object, since);
+                }
+            }
+        }
+        // The objects that are deleted, or that are referenced again, are no longer tracked
+        self.unreachable_since = unreachable_since;
+        Ok(stats)
+    }
+
+    /// Collects the garbage at every interval in the background. If it is not enabled, the
+    /// task returns immediately
+    pub(crate) fn start(mut self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            if !self.enabled {
+                return;
+            }
+            let mut interval = tokio::time::interval(self.interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                match self.collect().await {
+                    Ok(stats) => tracing::info!(
+                        "Garbage collection{} found {:?}",
+                        if self.dry_run { " dry run" } else { "" },
+                        stats
+                    ),
+                    Err(e) => tracing::error!("Garbage collection failed: {}", e),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::{HashMap, HashSet},
+        time::{Duration, SystemTime},
+    };
+
+    use chroma_blockstore::{
+        arrow::{config::TEST_MAX_BLOCK_SIZE_BYTES, provider::ArrowBlockfileProvider},
+        provider::{BlockfileObject, BlockfileProvider},
+        BlockfileWriterOptions,
+    };
+    use chroma_cache::{new_cache_for_test, new_non_persistent_cache_for_test};
+    use chroma_distance::DistanceFunction;
+    use chroma_index::{
+        hnsw_provider::{HnswIndexProvider, HnswObject},
+        Index, IndexUuid, DEFAULT_HNSW_EF_CONSTRUCTION, DEFAULT_HNSW_EF_SEARCH, DEFAULT_HNSW_M,
+    };
+    use chroma_storage::{local::LocalStorage, Storage};
+    use chroma_types::{Collection, CollectionUuid, Segment, SegmentScope, SegmentUuid};
+    use uuid::Uuid;
+
+    use crate::sysdb::{sysdb::SysDb, test_sysdb::TestSysDb};
+
+    use super::{GarbageCollector, GarbageCollectorConfig};
+
+    fn hnsw_provider(storage: &Storage, tmp_dir: &tempfile::TempDir) -> HnswIndexProvider {
+        let (_tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        HnswIndexProvider::new(
+            storage.clone(),
+            tmp_dir.path().to_path_buf(),
+            new_non_persistent_cache_for_test(),
+            rx,
+        )
+    }
+
+    // Flushes an index of the collection with the points, forked from the source if it is set
+    async fn write_index(
+        provider: &HnswIndexProvider,
+        collection_id: &CollectionUuid,
+        source: Option<IndexUuid>,
+        points: u32,
+    ) -> IndexUuid {
+        let index = match source {
+            Some(source) => provider
+                .fork(&source, collection_id, 4, DistanceFunction::Euclidean, None)
+                .await
+                .unwrap(),
+            None => provider
+                .create(
+                    collection_id,
+                    DEFAULT_HNSW_M,
+                    DEFAULT_HNSW_EF_CONSTRUCTION,
+                    DEFAULT_HNSW_EF_SEARCH,
+                    4,
+                    DistanceFunction::Euclidean,
+                    None,
+                )
+                .await
+                .unwrap(),
+        };
+        let id = index.inner.read().id;
+        for point in 1..=points {
+            index
+                .inner
+                .read()
+                .add(point as usize, &[point as f32; 4])
+                .unwrap();
+        }
+        provider.commit(index).unwrap();
+        provider.flush(&id, collection_id).await.unwrap();
+        id
+    }
+
+    fn collection(collection_id: CollectionUuid) -> Collection {
+        Collection {
+            collection_id,
+            name: "collection".to_string(),
+            metadata: None,
+            dimension: None,
+            tenant: "tenant".to_string(),
+            database: "database".to_string(),
+            log_position: 0,
+            version: 0,
+        }
+    }
+
+    async fn write_blockfile(provider: &BlockfileProvider, fork: Option<Uuid>) -> Uuid {
+        let options = match fork {
+            Some(id) => BlockfileWriterOptions::new().fork(id),
+            None => BlockfileWriterOptions::new(),
+        };
+        let writer = provider.write::<u32, String>(options).await.unwrap();
+        let id = writer.id();
+        writer.set("", 0u32, "value".to_string()).await.unwrap();
+        let flusher = writer.commit::<u32, String>().await.unwrap();
+        flusher.flush::<u32, String>().await.unwrap();
+        id
+    }
+
+    #[tokio::test]
+    async fn test_collect_garbage() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let storage = Storage::Local(LocalStorage::new(tmp_dir.path().to_str().unwrap()));
+        let provider =
+            BlockfileProvider::ArrowBlockfileProvider(Box::new(ArrowBlockfileProvider::new(
+                storage.clone(),
+                TEST_MAX_BLOCK_SIZE_BYTES,
+                new_cache_for_test(),
+                new_cache_for_test(),
+            )));
+        let hnsw_tmp_dir = tempfile::tempdir().unwrap();
+        let stale_id = write_blockfile(&provider, None).await;
+        let live_id = write_blockfile(&provider, Some(stale_id)).await;
+        let stale_block_ids = provider
+            .referenced_block_ids(&stale_id)
+            .await
+            .unwrap()
+            .unwrap();
+        let live_block_ids = provider
+            .referenced_block_ids(&live_id)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let collection_id = CollectionUuid::new();
+        let mut sysdb = TestSysDb::new();
+        sysdb.add_collection(collection(collection_id));
+        sysdb.add_segment(Segment {
+            id: SegmentUuid::new(),
+            r#type: chroma_types::SegmentType::BlockfileRecord,
+            scope: SegmentScope::RECORD,
+            collection: collection_id,
+            metadata: None,
+            file_path: HashMap::from([("blockfile".to_string(), vec![live_id.to_string()])]),
+        });
+
+        let config = GarbageCollectorConfig {
+            grace_period_secs: 60,
+            dry_run: true,
+            ..Default::default()
+        };
+        let mut collector = GarbageCollector::new(
+            &config,
+            Box::new(SysDb::Test(sysdb)),
+            provider.clone(),
+            hnsw_provider(&storage, &hnsw_tmp_dir),
+        );
+        let now = SystemTime::now();
+        let stats = collector.collect_at(now).await.unwrap();
+        assert_eq!(stats.live_roots, 1);
+        assert_eq!(stats.reachable_blocks, live_block_ids.len());
+        // The stale root is unreachable, and so are its blocks that the live root replaced
+        let garbage_blocks = stale_block_ids
+            .iter()
+            .filter(|id| !live_block_ids.contains(id))
+            .count();
+        assert_eq!(stats.unreachable_objects, 1 + garbage_blocks);
+        // Nothing has been unreachable for the grace period yet
+        assert_eq!(stats.deleted_objects, 0);
+
+        // The dry run deletes nothing once the grace period passes
+        let later = now + Duration::from_secs(120);
+        let stats = collector.collect_at(later).await.unwrap();
+        assert_eq!(stats.deleted_objects, 1 + garbage_blocks);
+        assert!(provider
+            .referenced_block_ids(&stale_id)
+            .await
+            .unwrap()
+            .is_some());
+
+        collector.dry_run = false;
+        let stats = collector.collect_at(later).await.unwrap();
+        assert_eq!(stats.deleted_objects, 1 + garbage_blocks);
+        assert!(provider
+            .referenced_block_ids(&stale_id)
+            .await
+            .unwrap()
+            .is_none());
+        let objects = provider
+            .list_objects()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(object, _)| object)
+            .collect::<Vec<_>>();
+        assert!(objects.contains(&BlockfileObject::Root(live_id)));
+        for block_id in live_block_ids {
+            assert!(objects.contains(&BlockfileObject::Block(block_id)));
+        }
+        assert_eq!(
+            collector
+                .collect_at(later)
+                .await
+                .unwrap()
+                .unreachable_objects,
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_collect_hnsw_garbage() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let storage = Storage::Local(LocalStorage::new(tmp_dir.path().to_str().unwrap()));
+        let blockfile_provider =
+            BlockfileProvider::ArrowBlockfileProvider(Box::new(ArrowBlockfileProvider::new(
+                storage.clone(),
+                TEST_MAX_BLOCK_SIZE_BYTES,
+                new_cache_for_test(),
+                new_cache_for_test(),
+            )));
+        let hnsw_tmp_dir = tempfile::tempdir().unwrap();
+        let provider = hnsw_provider(&storage, &hnsw_tmp_dir).with_delta_upload(true);
+        let collection_id = CollectionUuid::new();
+
+        // The live index is forked from the stale one, so that they share chunks. The index
+        // that is flushed as whole files is never referenced
+        let stale_id = write_index(&provider, &collection_id, None, 10).await;
+        let live_id = write_index(&provider, &collection_id, Some(stale_id), 100).await;
+        let files_provider = hnsw_provider(&storage, &hnsw_tmp_dir);
+        let files_id = write_index(&files_provider, &collection_id, None, 10).await;
+        let stale_chunks = provider
+            .referenced_chunks(&stale_id)
+            .await
+            .unwrap()
+            .unwrap();
+        let live_chunks = provider.referenced_chunks(&live_id).await.unwrap().unwrap();
+        let garbage_chunks = stale_chunks
+            .iter()
+            .filter(|chunk| !live_chunks.contains(chunk))
+            .collect::<HashSet<_>>();
+        let live_chunks = live_chunks.into_iter().collect::<HashSet<_>>();
+
+        let mut sysdb = TestSysDb::new();
+        sysdb.add_collection(collection(collection_id));
+        sysdb.add_segment(Segment {
+            id: SegmentUuid::new(),
+            r#type: chroma_types::SegmentType::HnswDistributed,
+            scope: SegmentScope::VECTOR,
+            collection: collection_id,
+            metadata: None,
+            file_path: HashMap::from([("hnsw_index".to_string(), vec![live_id.to_string()])]),
+        });
+
+        let config = GarbageCollectorConfig {
+            grace_period_secs: 60,
+            ..Default::default()
+        };
+        let mut collector = GarbageCollector::new(
+            &config,
+            Box::new(SysDb::Test(sysdb)),
+            blockfile_provider,
+            provider.clone(),
+        );
+        let now = SystemTime::now();
+        let stats = collector.collect_at(now).await.unwrap();
+        assert_eq!(stats.live_indexes, 1);
+        assert_eq!(stats.reachable_chunks, live_chunks.len());
+        // The manifest of the stale index, its chunks that the live index does not share, and
+        // the files of the unreferenced index
+        let garbage = 1 + garbage_chunks.len() + 4;
+        assert_eq!(stats.unreachable_objects, garbage);
+        assert_eq!(stats.deleted_objects, 0);
+
+        let later = now + Duration::from_secs(120);
+        let stats = collector.collect_at(later).await.unwrap();
+        assert_eq!(stats.deleted_objects, garbage);
+        let objects = provider
+            .list_objects()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(object, _)| object)
+            .collect::<HashSet<_>>();
+        assert!(objects.contains(&HnswObject::Manifest(live_id)));
+        assert!(!objects.contains(&HnswObject::Manifest(stale_id)));
+        assert!(!objects
+            .iter()
+            .any(|object| matches!(object, HnswObject::File(id, _) if *id == files_id)));
+        let chunks = objects
+            .into_iter()
+            .filter(|object| matches!(object, HnswObject::Chunk(..)))
+            .collect::<HashSet<_>>();
+        assert_eq!(chunks, live_chunks);
+        assert_eq!(
+            collector
+                .collect_at(later)
+                .await
+                .unwrap()
+                .unreachable_objects,
+            0
+        );
+    }
+}
@@ -1,12 +1,17 @@
+#![recursion_limit = "256"]
+
 mod assignment;
 mod compactor;
 mod config;
+mod garbage_collector;
 mod memberlist;
+mod memory_watchdog;
 mod server;
 mod sysdb;
 mod system;
 mod tracing;
 mod utils;
+mod warm_up_scheduler;
 
 use chroma_config::Configurable;
 use memberlist::MemberlistProvider;
@@ -54,6 +59,45 @@ pub async fn query_service_entrypoint() {
     };
     worker_server.set_system(system.clone());
     worker_server.set_dispatcher(dispatcher_handle.clone());
+    let memory_watchdog_handle = worker_server
+        .watch_memory(memory_watchdog::MemoryWatchdog::new(
+            &config.memory_watchdog,
+        ))
+        .start();
+
+    // The collections that the memberlist assigns to the worker are warmed up in the background
+    let mut warm_up_handles = None;
+    if config.warm_up.enabled {
+        let mut memberlist = match memberlist::CustomResourceMemberlistProvider::try_from_config(
+            &config.memberlist_provider,
+        )
+        .await
+        {
+            Ok(memberlist) => memberlist,
+            Err(err) => {
+                println!("Failed to create memberlist component: {:?}", err);
+                return;
+            }
+        };
+        let assignment_policy = match assignment::from_config(&config.assignment_policy).await {
+            Ok(assignment_policy) => assignment_policy,
+            Err(err) => {
+                println!("Failed to create assignment policy: {:?}", err);
+                return;
+            }
+        };
+        let mut warm_up_scheduler = worker_server.warm_up_scheduler(
+            &config.warm_up,
+            config.my_member_id.clone(),
+            assignment_policy,
+        );
+        warm_up_scheduler.set_dispatcher(dispatcher_handle.clone());
+        warm_up_scheduler.set_system(system.clone());
+        let warm_up_scheduler_handle = system.start_component(warm_up_scheduler);
+        memberlist.subscribe(warm_up_scheduler_handle.receiver());
+        let memberlist_handle = system.start_component(memberlist);
+        warm_up_handles = Some((memberlist_handle, warm_up_scheduler_handle));
+    }
 
     let server_join_handle = tokio::spawn(async move {
         let _ = crate::server::WorkerServer::run(worker_server).await;
@@ -72,6 +116,13 @@ pub async fn query_service_entrypoint() {
         // Kubernetes will send SIGTERM to stop the pod gracefully
         // TODO: add more signal handling
         _ = sigterm.recv() => {
+            memory_watchdog_handle.abort();
+            if let Some((mut memberlist_handle, mut warm_up_scheduler_handle)) = warm_up_handles {
+                memberlist_handle.stop();
+                let _ = memberlist_handle.join().await;
+                warm_up_scheduler_handle.stop();
+                let _ = warm_up_scheduler_handle.join().await;
+            }
             dispatcher_handle.stop();
             let _ = dispatcher_handle.join().await;
             system.stop().await;
@@ -129,10 +180,25 @@ pub async fn compaction_service_entrypoint() {
         };
     compaction_manager.set_dispatcher(dispatcher_handle.clone());
     compaction_manager.set_system(system.clone());
+    let compaction_admin =
+        crate::compactor::CompactionAdminService::new(compaction_manager.pauses());
+    let memory_watchdog_handle = compaction_manager
+        .watch_memory(memory_watchdog::MemoryWatchdog::new(
+            &config.memory_watchdog,
+        ))
+        .start();
+    let garbage_collector_handle = compaction_manager
+        .garbage_collector(&config.garbage_collector)
+        .start();
 
     let mut compaction_manager_handle = system.start_component(compaction_manager);
     memberlist.subscribe(compaction_manager_handle.receiver());
 
+    let admin_port = config.my_port;
+    let admin_join_handle = tokio::spawn(async move {
+        let _ = compaction_admin.run(admin_port).await;
+    });
+
     let mut memberlist_handle = system.start_component(memberlist);
 
     let mut sigterm = match signal(SignalKind::terminate()) {
@@ -147,6 +213,8 @@ pub async fn compaction_service_entrypoint() {
         // Kubernetes will send SIGTERM to stop the pod gracefully
         // TODO: add more signal handling
         _ = sigterm.recv() => {
+            memory_watchdog_handle.abort();
+            garbage_collector_handle.abort();
             memberlist_handle.stop();
             let _ = memberlist_handle.join().await;
             dispatcher_handle.stop();
@@ -155,6 +223,115 @@ pub async fn compaction_service_entrypoint() {
             let _ = compaction_manager_handle.join().await;
             system.stop().await;
             system.join().await;
+            let _ = admin_join_handle.await;
+        },
+    };
+    println!("Server stopped");
+}
+
+/// Runs the query and the compaction service in one process, for a single-node deployment.
+/// With a local log configured for both services, the worker accepts the writes itself and
+/// no log service is needed. The compaction service is the only member of its memberlist,
+/// so that it compacts every collection.
+pub async fn single_node_entrypoint() {
+    // Check if the config path is set in the env var
+    let config = match std::env::var(CONFIG_PATH_ENV_VAR) {
+        Ok(config_path) => config::RootConfig::load_from_path(&config_path),
+        Err(_) => config::RootConfig::load(),
+    };
+
+    let query_config = config.query_service;
+    let compaction_config = config.compaction_service;
+
+    crate::tracing::opentelemetry_config::init_otel_tracing(
+        &query_config.service_name,
+        &query_config.otel_endpoint,
+    );
+
+    let system: system::System = system::System::new();
+    let dispatcher =
+        match execution::dispatcher::Dispatcher::try_from_config(&query_config.dispatcher).await {
+            Ok(dispatcher) => dispatcher,
+            Err(err) => {
+                println!("Failed to create dispatcher component: {:?}", err);
+                return;
+            }
+        };
+    let mut dispatcher_handle = system.start_component(dispatcher);
+
+    let mut worker_server = match server::WorkerServer::try_from_config(&query_config).await {
+        Ok(worker_server) => worker_server,
+        Err(err) => {
+            println!("Failed to create worker server component: {:?}", err);
+            return;
+        }
+    };
+    worker_server.set_system(system.clone());
+    worker_server.set_dispatcher(dispatcher_handle.clone());
+
+    let mut compaction_manager =
+        match crate::compactor::CompactionManager::try_from_config(&compaction_config).await {
+            Ok(compaction_manager) => compaction_manager,
+            Err(err) => {
+                println!("Failed to create compaction manager component: {:?}", err);
+                return;
+            }
+        };
+    compaction_manager.set_dispatcher(dispatcher_handle.clone());
+    compaction_manager.set_system(system.clone());
+    // The worker serves the compaction admin service, since both services share its port
+    worker_server.set_compaction_admin(crate::compactor::CompactionAdminService::new(
+        compaction_manager.pauses(),
+    ));
+    // Both services share the memory of the process, so one watchdog coordinates them
+    let memory_watchdog_handle = compaction_manager
+        .watch_memory(
+            worker_server.watch_memory(memory_watchdog::MemoryWatchdog::new(
+                &query_config.memory_watchdog,
+            )),
+        )
+        .start();
+    let garbage_collector_handle = compaction_manager
+        .garbage_collector(&compaction_config.garbage_collector)
+        .start();
+    let mut compaction_manager_handle = system.start_component(compaction_manager);
+    let memberlist: memberlist::Memberlist = vec![compaction_config.my_member_id.clone()];
+    if let Err(err) = compaction_manager_handle
+        .receiver()
+        .send(memberlist, None)
+        .await
+    {
+        println!(
+            "Failed to set the memberlist of the compaction manager: {:?}",
+            err
+        );
+        return;
+    }
+
+    let server_join_handle = tokio::spawn(async move {
+        let _ = crate::server::WorkerServer::run(worker_server).await;
+    });
+
+    let mut sigterm = match signal(SignalKind::terminate()) {
+        Ok(sigterm) => sigterm,
+        Err(e) => {
+            println!("Failed to create signal handler: {:?}", e);
+            return;
+        }
+    };
+
+    println!("Waiting for SIGTERM to stop the server");
+    select! {
+        _ = sigterm.recv() => {
+            memory_watchdog_handle.abort();
+            garbage_collector_handle.abort();
+            compaction_manager_handle.stop();
+            let _ = compaction_manager_handle.join().await;
+            dispatcher_handle.stop();
+            let _ = dispatcher_handle.join().await;
+            system.stop().await;
+            system.join().await;
+            let _ = server_join_handle.await;
         },
     };
     println!("Server stopped");
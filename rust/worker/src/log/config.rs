@@ -8,7 +8,18 @@ pub(crate) struct GrpcLogConfig {
     pub(crate) request_timeout_ms: u64,
 }
 
+#[derive(Deserialize)]
+/// # Description
+/// The configuration of a log that is stored on the local disk of the worker, for a
+/// single-node deployment without the log service.
+/// ## Description of parameters
+/// - path: The directory in which the write-ahead log files are stored.
+pub(crate) struct LocalLogConfig {
+    pub(crate) path: String,
+}
+
 #[derive(Deserialize)]
 pub(crate) enum LogConfig {
     Grpc(GrpcLogConfig),
+    Local(LocalLogConfig),
 }
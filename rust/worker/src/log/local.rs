@@ -0,0 +1,725 @@
+use crate::log::config::LogConfig;
+use crate::log::log::{
+    CollectionInfo, GetCollectionsWithNewDataError, PullLogsError, UpdateCollectionLogOffsetError,
+};
+use crate::log::LogConfigError;
+use async_trait::async_trait;
+use chroma_config::Configurable;
+use chroma_error::{ChromaError, ErrorCodes};
+use chroma_types::chroma_proto::{self, log_service_server::LogService};
+use chroma_types::{
+    error_status, CollectionUuid, LogRecord, OperationRecord, RecordConversionError,
+};
+use parking_lot::Mutex;
+use prost::Message;
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Formatter};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+use tokio::io::AsyncWriteExt;
+use tonic::{Request, Response, Status};
+use uuid::Uuid;
+
+const WAL_EXTENSION: &str = "wal";
+const OFFSET_EXTENSION: &str = "offset";
+// Each entry of a write-ahead log file is the length of the encoded record as a u32, the time
+// at which it was written in nanoseconds as an i64, and the encoded `chroma_proto::LogRecord`,
+// all little endian
+const FRAME_HEADER_SIZE: usize = 12;
+
+#[derive(Error, Debug)]
+pub(crate) enum LocalLogError {
+    #[error("Failed to access the local log: {0}")]
+    Io(#[from] io::Error),
+    #[error("Invalid collection id: {0}")]
+    InvalidCollectionId(String),
+    #[error("Invalid record: {0}")]
+    InvalidRecord(#[from] RecordConversionError),
+}
+
+impl ChromaError for LocalLogError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            LocalLogError::Io(_) => ErrorCodes::Internal,
+            LocalLogError::InvalidCollectionId(_) => ErrorCodes::InvalidArgument,
+            LocalLogError::InvalidRecord(e) => e.code(),
+        }
+    }
+}
+
+/// The uncompacted records of a collection, together with the file they are appended to
+struct CollectionLog {
+    // The records after the compaction offset in log offset order, with their write time
+    records: Vec<(i64, chroma_proto::LogRecord)>,
+    // The log offset up to which the records have been compacted
+    compaction_offset: i64,
+    // The file is locked for the whole of a write, which orders the writes to the file of the
+    // collection without holding the lock on the collections. It is opened on first write.
+    wal: Arc<tokio::sync::Mutex<Option<tokio::fs::File>>>,
+}
+
+impl CollectionLog {
+    fn next_offset(&self) -> i64 {
+        self.records
+            .last()
+            .map_or(self.compaction_offset, |(_, record)| record.log_offset)
+            + 1
+    }
+}
+
+/// A write-ahead log on local disk, which stands in for the log service in a single-node
+/// deployment. Writes are accepted through the `LogService` API and are durable once the
+/// push returns. The uncompacted records are also kept in memory, so that they are read
+/// without touching the disk, and the compacted ones are dropped from the file when the
+/// compaction offset of the collection is updated.
+///
+/// Log offsets start at 1 for every collection, like in the log service.
+#[derive(Clone)]
+pub(crate) struct LocalLog {
+    root: Arc<PathBuf>,
+    collections: Arc<Mutex<HashMap<CollectionUuid, CollectionLog>>>,
+}
+
+impl Debug for LocalLog {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalLog")
+            .field("root", &self.root)
+            .finish()
+    }
+}
+
+impl LocalLog {
+    /// Opens the log in the directory, replaying the records that have not been compacted
+    pub(crate) fn open(root: impl AsRef<Path>) -> Result<Self, LocalLogError> {
+        let root = root.as_ref().to_path_buf();
+        fs::create_dir_all(&root)?;
+        let mut collections = HashMap::new();
+        for entry in fs::read_dir(&root)? {
+            let path = entry?.path();
+            if path.extension().and_then(|extension| extension.to_str()) != Some(WAL_EXTENSION) {
+                continue;
+            }
+            let collection_id = match path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| Uuid::parse_str(stem).ok())
+            {
+                Some(collection_id) => CollectionUuid(collection_id),
+                None => {
+                    tracing::warn!("Skipping unexpected file in local log: {:?}", path);
+                    continue;
+                }
+            };
+            let compaction_offset = read_compaction_offset(&root, collection_id)?;
+            let mut records = replay_wal(&path)?;
+            records.retain(|(_, record)| record.log_offset > compaction_offset);
+            let wal = OpenOptions::new().append(true).open(&path)?;
+            collections.insert(
+                collection_id,
+                CollectionLog {
+                    records,
+                    compaction_offset,
+                    wal: Arc::new(tokio::sync::Mutex::new(Some(tokio::fs::File::from_std(
+                        wal,
+                    )))),
+                },
+            );
+        }
+        Ok(Self {
+            root: Arc::new(root),
+            collections: Arc::new(Mutex::new(collections)),
+        })
+    }
+
+    /// Returns the log opened in the directory by this process, opening it on first use, so
+    /// that the services of a single-node deployment share the same log
+    pub(crate) fn open_shared(root: impl AsRef<Path>) -> Result<Self, LocalLogError> {
+        static LOCAL_LOGS: OnceLock<Mutex<HashMap<PathBuf, LocalLog>>> = OnceLock::new();
+        let mut local_logs = LOCAL_LOGS.get_or_init(Default::default).lock();
+        let root = root.as_ref().to_path_buf();
+        if let Some(log) = local_logs.get(&root) {
+            return Ok(log.clone());
+        }
+        let log = Self::open(&root)?;
+        local_logs.insert(root, log.clone());
+        Ok(log)
+    }
+
+    /// Appends the records to the log of the collection and returns the number of records
+    /// appended. Either all the records are appended or none of them are.
+    pub(crate) async fn push(
+        &self,
+        collection_id: CollectionUuid,
+        records: Vec<chroma_proto::OperationRecord>,
+    ) -> Result<usize, LocalLogError> {
+        for record in &records {
+            OperationRecord::try_from(record.clone())?;
+        }
+        let log_ts = now_nanos();
+        let wal = self
+            .collections
+            .lock()
+            .entry(collection_id)
+            .or_insert_with(|| CollectionLog {
+                records: Vec::new(),
+                compaction_offset: 0,
+                wal: Arc::default(),
+            })
+            .wal
+            .clone();
+        let mut wal = wal.lock().await;
+        // No other write to the collection can happen while its file is locked, so the offsets
+        // stay the next ones until the records are appended
+        let first_offset = match self.collections.lock().get(&collection_id) {
+            Some(collection_log) => collection_log.next_offset(),
+            None => 1,
+        };
+        let entries = records
+            .into_iter()
+            .zip(first_offset..)
+            .map(|(record, log_offset)| {
+                (
+                    log_ts,
+                    chroma_proto::LogRecord {
+                        log_offset,
+                        record: Some(record),
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+        let mut frames = Vec::new();
+        for (log_ts, record) in &entries {
+            encode_frame(*log_ts, record, &mut frames);
+        }
+        let file = match wal.as_mut() {
+            Some(file) => file,
+            None => wal.insert(
+                tokio::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(wal_path(&self.root, collection_id))
+                    .await?,
+            ),
+        };
+        let wal_len = file.metadata().await?.len();
+        let written = match file.write_all(&frames).await {
+            Ok(()) => file.sync_data().await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = written {
+            // Drop the partially written records, so that the next push does not append
+            // after them
+            let _ = file.set_len(wal_len).await;
+            return Err(e.into());
+        }
+        let count = entries.len();
+        if let Some(collection_log) = self.collections.lock().get_mut(&collection_id) {
+            collection_log.records.extend(entries);
+        }
+        Ok(count)
+    }
+
+    /// Returns up to `batch_size` records of the collection from the offset on, that were
+    /// written no later than the end timestamp
+    fn scan(
+        &self,
+        collection_id: CollectionUuid,
+        offset: i64,
+        batch_size: i32,
+        end_timestamp: i64,
+    ) -> Vec<chroma_proto::LogRecord> {
+        let collections = self.collections.lock();
+        let records = match collections.get(&collection_id) {
+            Some(collection_log) => &collection_log.records,
+            None => return Vec::new(),
+        };
+        let start = records.partition_point(|(_, record)| record.log_offset < offset);
+        records[start..]
+            .iter()
+            .take(batch_size.max(0) as usize)
+            .filter(|(log_ts, _)| *log_ts <= end_timestamp)
+            .map(|(_, record)| record.clone())
+            .collect()
+    }
+
+    pub(crate) async fn read(
+        &self,
+        collection_id: CollectionUuid,
+        offset: i64,
+        batch_size: i32,
+        end_timestamp: Option<i64>,
+    ) -> Result<Vec<LogRecord>, PullLogsError> {
+        self.scan(
+            collection_id,
+            offset,
+            batch_size,
+            end_timestamp.unwrap_or(i64::MAX),
+        )
+        .into_iter()
+        .map(LogRecord::try_from)
+        .collect::<Result<_, _>>()
+        .map_err(PullLogsError::ConversionError)
+    }
+
+    pub(crate) async fn get_collections_with_new_data(
+        &self,
+        min_compaction_size: u64,
+    ) -> Result<Vec<CollectionInfo>, GetCollectionsWithNewDataError> {
+        Ok(self
+            .collections
+            .lock()
+            .iter()
+            .filter(|(_, collection_log)| {
+                collection_log.records.len() as u64 >= min_compaction_size
+            })
+            .filter_map(|(collection_id, collection_log)| {
+                collection_log
+                    .records
+                    .first()
+                    .map(|(log_ts, record)| CollectionInfo {
+                        collection_id: *collection_id,
+                        first_log_offset: record.log_offset,
+                        first_log_ts: *log_ts,
+                        num_records: collection_log.records.len() as u64,
+                        num_bytes: collection_log
+                            .records
+                            .iter()
+                            .map(|(_, record)| record.encoded_len() as u64)
+                            .sum(),
+                    })
+            })
+            .collect())
+    }
+
+    pub(crate) async fn update_collection_log_offset(
+        &self,
+        collection_id: CollectionUuid,
+        new_offset: i64,
+    ) -> Result<(), UpdateCollectionLogOffsetError> {
+        Ok(self.compact(collection_id, new_offset).await?)
+    }
+
+    /// Records that the collection is compacted up to the offset, and rewrites its file
+    /// without the compacted records
+    async fn compact(
+        &self,
+        collection_id: CollectionUuid,
+        new_offset: i64,
+    ) -> Result<(), LocalLogError> {
+        let wal = match self.collections.lock().get(&collection_id) {
+            Some(collection_log) => collection_log.wal.clone(),
+            None => return Ok(()),
+        };
+        // The file is rewritten while it is locked, so that no push appends to the file that
+        // is replaced
+        let mut wal = wal.lock().await;
+        let frames = {
+            let collections = self.collections.lock();
+            let collection_log = match collections.get(&collection_id) {
+                Some(collection_log) => collection_log,
+                None => return Ok(()),
+            };
+            if new_offset <= collection_log.compaction_offset {
+                return Ok(());
+            }
+            let mut frames = Vec::new();
+            for (log_ts, record) in &collection_log.records {
+                if record.log_offset > new_offset {
+                    encode_frame(*log_ts, record, &mut frames);
+                }
+            }
+            frames
+        };
+        let offset_path = offset_path(&self.root, collection_id);
+        let path = wal_path(&self.root, collection_id);
+        let file = tokio::task::spawn_blocking(move || {
+            // The offset is written first, so that the compacted records are skipped on
+            // replay if the file is not rewritten
+            write_atomically(&offset_path, new_offset.to_string().as_bytes())?;
+            write_atomically(&path, &frames)?;
+            Ok::<_, LocalLogError>(OpenOptions::new().append(true).open(&path)?)
+        })
+        .await
+        .map_err(|e| LocalLogError::Io(io::Error::other(e)))??;
+        *wal = Some(tokio::fs::File::from_std(file));
+        if let Some(collection_log) = self.collections.lock().get_mut(&collection_id) {
+            collection_log.compaction_offset = new_offset;
+            collection_log
+                .records
+                .retain(|(_, record)| record.log_offset > new_offset);
+        }
+        Ok(())
+    }
+}
+
+fn wal_path(root: &Path, collection_id: CollectionUuid) -> PathBuf {
+    root.join(format!("{}.{}", collection_id.0, WAL_EXTENSION))
+}
+
+fn offset_path(root: &Path, collection_id: CollectionUuid) -> PathBuf {
+    root.join(format!("{}.{}", collection_id.0, OFFSET_EXTENSION))
+}
+
+fn now_nanos() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos() as i64)
+        .unwrap_or_default()
+}
+
+fn encode_frame(log_ts: i64, record: &chroma_proto::LogRecord, frames: &mut Vec<u8>) {
+    frames.extend_from_slice(&(record.encoded_len() as u32).to_le_bytes());
+    frames.extend_from_slice(&log_ts.to_le_bytes());
+    record
+        .encode(frames)
+        .expect("Encoding into a vector should not fail");
+}
+
+/// Reads the records of a write-ahead log file. A frame that is cut short or cannot be
+/// decoded is the trace of a write that did not complete, so the file is truncated to the
+/// frames before it.
+fn replay_wal(path: &Path) -> Result<Vec<(i64, chroma_proto::LogRecord)>, LocalLogError> {
+    let bytes = fs::read(path)?;
+    let mut records = Vec::new();
+    let mut position = 0;
+    while position < bytes.len() {
+        let header = match bytes.get(position..position + FRAME_HEADER_SIZE) {
+            Some(header) => header,
+            None => break,
+        };
+        let length = u32::from_le_bytes(header[..4].try_into().unwrap()) as usize;
+        let log_ts = i64::from_le_bytes(header[4..].try_into().unwrap());
+        let body_start = position + FRAME_HEADER_SIZE;
+        let record = match bytes
+            .get(body_start..body_start + length)
+            .map(chroma_proto::LogRecord::decode)
+        {
+            Some(Ok(record)) => record,
+            _ => break,
+        };
+        records.push((log_ts, record));
+        position = body_start + length;
+    }
+    if position < bytes.len() {
+        tracing::warn!(
+            "Truncating incomplete record at byte {} of local log {:?}",
+            position,
+            path
+        );
+        OpenOptions::new()
+            .write(true)
+            .open(path)?
+            .set_len(position as u64)?;
+    }
+    Ok(records)
+}
+
+fn read_compaction_offset(
+    root: &Path,
+    collection_id: CollectionUuid,
+) -> Result<i64, LocalLogError> {
+    match fs::read_to_string(offset_path(root, collection_id)) {
+        Ok(offset) => offset.trim().parse().map_err(|_| {
+            LocalLogError::Io(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Invalid compaction offset: {}", offset),
+            ))
+        }),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(0),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Replaces the file with the bytes, so that it holds either its old or its new content
+fn write_atomically(path: &Path, bytes: &[u8]) -> Result<(), LocalLogError> {
+    let temporary_path = path.with_extension("tmp");
+    let mut file = File::create(&temporary_path)?;
+    file.write_all(bytes)?;
+    file.sync_data()?;
+    fs::rename(&temporary_path, path)?;
+    Ok(())
+}
+
+fn parse_collection_id(collection_id: &str) -> Result<CollectionUuid, LocalLogError> {
+    Uuid::parse_str(collection_id)
+        .map(CollectionUuid)
+        .map_err(|_| LocalLogError::InvalidCollectionId(collection_id.to_string()))
+}
+
+#[async_trait]
+impl Configurable<LogConfig> for LocalLog {
+    async fn try_from_config(config: &LogConfig) -> Result<Self, Box<dyn ChromaError>> {
+        match &config {
+            LogConfig::Local(local_config) => {
+                tracing::info!("Opening local log at {}", local_config.path);
+                LocalLog::open_shared(&local_config.path)
+                    .map_err(|e| Box::new(e) as Box<dyn ChromaError>)
+            }
+            _ => Err(Box::new(LogConfigError::InvalidLogConfig)),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl LogService for LocalLog {
+    async fn push_logs(
+        &self,
+        request: Request<chroma_proto::PushLogsRequest>,
+    ) -> Result<Response<chroma_proto::PushLogsResponse>, Status> {
+        let request = request.into_inner();
+        let collection_id = parse_collection_id(&request.collection_id)
+            .map_err(|e| error_status(&e, e.to_string()))?;
+        let record_count = self
+            .push(collection_id, request.records)
+            .await
+            .map_err(|e| error_status(&e, format!("Failed to push logs: {}", e)))?;
+        Ok(Response::new(chroma_proto::PushLogsResponse {
+            record_count: record_count as i32,
+        }))
+    }
+
+    async fn pull_logs(
+        &self,
+        request: Request<chroma_proto::PullLogsRequest>,
+    ) -> Result<Response<chroma_proto::PullLogsResponse>, Status> {
+        let request = request.into_inner();
+        let collection_id = parse_collection_id(&request.collection_id)
+            .map_err(|e| error_status(&e, e.to_string()))?;
+        // An unset end timestamp does not limit the records, like in the log service
+        let end_timestamp = if request.end_timestamp > 0 {
+            request.end_timestamp
+        } else {
+            i64::MAX
+        };
+        let records = self.scan(
+            collection_id,
+            request.start_from_offset,
+            request.batch_size,
+            end_timestamp,
+        );
+        Ok(Response::new(chroma_proto::PullLogsResponse { records }))
+    }
+
+    async fn get_all_collection_info_to_compact(
+        &self,
+        request: Request<chroma_proto::GetAllCollectionInfoToCompactRequest>,
+    ) -> Result<Response<chroma_proto::GetAllCollectionInfoToCompactResponse>, Status> {
+        let collections = self
+            .get_collections_with_new_data(request.into_inner().min_compaction_size)
+            .await
+            .map_err(|e| error_status(&e, e.to_string()))?;
+        Ok(Response::new(
+            chroma_proto::GetAllCollectionInfoToCompactResponse {
+                all_collection_info: collections
+                    .into_iter()
+                    .map(|collection| chroma_proto::CollectionInfo {
+                        collection_id: collection.collection_id.0.to_string(),
+                        first_log_offset: collection.first_log_offset,
+                        first_log_ts: collection.first_log_ts,
+                        num_records: collection.num_records,
+                        num_bytes: collection.num_bytes,
+                    })
+                    .collect(),
+            },
+        ))
+    }
+
+    async fn update_collection_log_offset(
+        &self,
+        request: Request<chroma_proto::UpdateCollectionLogOffsetRequest>,
+    ) -> Result<Response<chroma_proto::UpdateCollectionLogOffsetResponse>, Status> {
+        let request = request.into_inner();
+        let collection_id = parse_collection_id(&request.collection_id)
+            .map_err(|e| error_status(&e, e.to_string()))?;
+        self.compact(collection_id, request.log_offset)
+            .await
+            .map_err(|e| error_status(&e, format!("Failed to update log offset: {}", e)))?;
+        Ok(Response::new(
+            chroma_proto::UpdateCollectionLogOffsetResponse {},
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chroma_types::chroma_proto::Operation;
+
+    fn operation_record(id: &str) -> chroma_proto::OperationRecord {
+        chroma_proto::OperationRecord {
+            id: id.to_string(),
+            vector: None,
+            metadata: None,
+            operation: Operation::Delete as i32,
+            vector_reference: None,
+        }
+    }
+
+    fn log_offsets(records: &[LogRecord]) -> Vec<i64> {
+        records.iter().map(|record| record.log_offset).collect()
+    }
+
+    #[tokio::test]
+    async fn test_push_and_read() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let log = LocalLog::open(tmp_dir.path()).unwrap();
+        let collection_id = CollectionUuid::new();
+
+        let count = log
+            .push(
+                collection_id,
+                vec![operation_record("a"), operation_record("b")],
+            )
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+        log.push(collection_id, vec![operation_record("c")])
+            .await
+            .unwrap();
+
+        let records = log.read(collection_id, 2, 10, None).await.unwrap();
+        assert_eq!(log_offsets(&records), vec![2, 3]);
+        assert_eq!(records[0].record.id, "b");
+        let records = log.read(collection_id, 1, 2, None).await.unwrap();
+        assert_eq!(log_offsets(&records), vec![1, 2]);
+        assert!(log
+            .read(collection_id, 1, 10, Some(0))
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(log
+            .read(CollectionUuid::new(), 1, 10, None)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let collections = log.get_collections_with_new_data(3).await.unwrap();
+        assert_eq!(collections.len(), 1);
+        assert_eq!(collections[0].collection_id, collection_id);
+        assert_eq!(collections[0].first_log_offset, 1);
+        assert!(log
+            .get_collections_with_new_data(4)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_replay_after_compaction() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let collection_id = CollectionUuid::new();
+        {
+            let log = LocalLog::open(tmp_dir.path()).unwrap();
+            log.push(
+                collection_id,
+                vec![
+                    operation_record("a"),
+                    operation_record("b"),
+                    operation_record("c"),
+                ],
+            )
+            .await
+            .unwrap();
+            log.update_collection_log_offset(collection_id, 2)
+                .await
+                .unwrap();
+            log.push(collection_id, vec![operation_record("d")])
+                .await
+                .unwrap();
+        }
+
+        let log = LocalLog::open(tmp_dir.path()).unwrap();
+        let records = log.read(collection_id, 1, 10, None).await.unwrap();
+        assert_eq!(log_offsets(&records), vec![3, 4]);
+        // The offsets continue after the replayed records
+        log.push(collection_id, vec![operation_record("e")])
+            .await
+            .unwrap();
+        let records = log.read(collection_id, 1, 10, None).await.unwrap();
+        assert_eq!(log_offsets(&records), vec![3, 4, 5]);
+    }
+
+    #[tokio::test]
+    async fn test_replay_truncates_incomplete_record() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let collection_id = CollectionUuid::new();
+        {
+            let log = LocalLog::open(tmp_dir.path()).unwrap();
+            log.push(
+                collection_id,
+                vec![operation_record("a"), operation_record("b")],
+            )
+            .await
+            .unwrap();
+        }
+        // Cut the last record short, as if the process stopped while writing it
+        let path = wal_path(tmp_dir.path(), collection_id);
+        let length = fs::metadata(&path).unwrap().len();
+        OpenOptions::new()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_len(length - 1)
+            .unwrap();
+
+        let log = LocalLog::open(tmp_dir.path()).unwrap();
+        let records = log.read(collection_id, 1, 10, None).await.unwrap();
+        assert_eq!(log_offsets(&records), vec![1]);
+        log.push(collection_id, vec![operation_record("c")])
+            .await
+            .unwrap();
+        let records = log.read(collection_id, 1, 10, None).await.unwrap();
+        assert_eq!(log_offsets(&records), vec![1, 2]);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_push() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let collection_id = CollectionUuid::new();
+        {
+            let log = LocalLog::open(tmp_dir.path()).unwrap();
+            let pushes = (0..16)
+                .map(|i| {
+                    let log = log.clone();
+                    tokio::spawn(async move {
+                        log.push(collection_id, vec![operation_record(&i.to_string())])
+                            .await
+                    })
+                })
+                .collect::<Vec<_>>();
+            for push in pushes {
+                push.await.unwrap().unwrap();
+            }
+            let records = log.read(collection_id, 1, 100, None).await.unwrap();
+            assert_eq!(log_offsets(&records), (1..=16).collect::<Vec<_>>());
+        }
+
+        // The records are written to the file in the order of their offsets
+        let log = LocalLog::open(tmp_dir.path()).unwrap();
+        let records = log.read(collection_id, 1, 100, None).await.unwrap();
+        assert_eq!(log_offsets(&records), (1..=16).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_push_rejects_invalid_record() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let log = LocalLog::open(tmp_dir.path()).unwrap();
+        let collection_id = CollectionUuid::new();
+        let mut invalid_record = operation_record("b");
+        invalid_record.operation = -1;
+
+        assert!(log
+            .push(collection_id, vec![operation_record("a"), invalid_record])
+            .await
+            .is_err());
+        assert!(log
+            .read(collection_id, 1, 10, None)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
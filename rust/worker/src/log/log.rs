@@ -1,4 +1,6 @@
 use crate::log::config::LogConfig;
+use crate::log::local::{LocalLog, LocalLogError};
+use crate::log::LogConfigError;
 use crate::tracing::util::client_interceptor;
 use async_trait::async_trait;
 use chroma_config::Configurable;
@@ -26,6 +28,8 @@ pub(crate) struct CollectionInfo {
     pub(crate) collection_id: CollectionUuid,
     pub(crate) first_log_offset: i64,
     pub(crate) first_log_ts: i64,
+    pub(crate) num_records: u64,
+    pub(crate) num_bytes: u64,
 }
 
 #[derive(Clone, Debug)]
@@ -44,6 +48,7 @@ pub(crate) enum Log {
     Grpc(GrpcLog),
     #[allow(dead_code)]
     InMemory(InMemoryLog),
+    Local(LocalLog),
 }
 
 impl Log {
@@ -63,6 +68,10 @@ impl Log {
                 log.read(collection_id, offset, batch_size, end_timestamp)
                     .await
             }
+            Log::Local(log) => {
+                log.read(collection_id, offset, batch_size, end_timestamp)
+                    .await
+            }
         }
     }
 
@@ -73,6 +82,7 @@ impl Log {
         match self {
             Log::Grpc(log) => log.get_collections_with_new_data(min_compaction_size).await,
             Log::InMemory(log) => log.get_collections_with_new_data(min_compaction_size).await,
+            Log::Local(log) => log.get_collections_with_new_data(min_compaction_size).await,
         }
     }
 
@@ -90,6 +100,10 @@ impl Log {
                 log.update_collection_log_offset(collection_id, new_offset)
                     .await
             }
+            Log::Local(log) => {
+                log.update_collection_log_offset(collection_id, new_offset)
+                    .await
+            }
         }
     }
 }
@@ -165,6 +179,7 @@ impl Configurable<LogConfig> for GrpcLog {
                     }
                 }
             }
+            _ => Err(Box::new(LogConfigError::InvalidLogConfig)),
         }
     }
 }
@@ -247,6 +262,8 @@ impl GrpcLog {
                         collection_id,
                         first_log_offset: collection.first_log_offset,
                         first_log_ts: collection.first_log_ts,
+                        num_records: collection.num_records,
+                        num_bytes: collection.num_bytes,
                     });
                 }
                 Ok(result)
@@ -289,6 +306,11 @@ pub enum PullLogsError {
 impl ChromaError for PullLogsError {
     fn code(&self) -> ErrorCodes {
         match self {
+            PullLogsError::FailedToPullLogs(status)
+                if status.code() == tonic::Code::Unavailable =>
+            {
+                ErrorCodes::Unavailable
+            }
             PullLogsError::FailedToPullLogs(_) => ErrorCodes::Internal,
             PullLogsError::ConversionError(_) => ErrorCodes::Internal,
         }
@@ -315,6 +337,8 @@ impl ChromaError for GetCollectionsWithNewDataError {
 pub(crate) enum UpdateCollectionLogOffsetError {
     #[error("Failed to update collection log offset")]
     FailedToUpdateCollectionLogOffset(#[from] tonic::Status),
+    #[error("Failed to update local log offset: {0}")]
+    LocalLog(#[from] LocalLogError),
 }
 
 impl ChromaError for UpdateCollectionLogOffsetError {
@@ -323,6 +347,7 @@ impl ChromaError for UpdateCollectionLogOffsetError {
             UpdateCollectionLogOffsetError::FailedToUpdateCollectionLogOffset(_) => {
                 ErrorCodes::Internal
             }
+            UpdateCollectionLogOffsetError::LocalLog(e) => e.code(),
         }
     }
 }
@@ -437,6 +462,11 @@ impl InMemoryLog {
                 collection_id: *collection_id,
                 first_log_offset: logs[0].log_offset,
                 first_log_ts: logs[0].log_ts,
+                num_records: logs.len() as u64,
+                num_bytes: logs
+                    .iter()
+                    .map(|log| log.record.record.get_size() as u64)
+                    .sum(),
             });
         }
         Ok(collections)
@@ -1,4 +1,5 @@
 pub(crate) mod config;
+pub(crate) mod local;
 #[allow(clippy::module_inception)]
 pub(crate) mod log;
 #[allow(dead_code)]
@@ -6,12 +7,30 @@ pub mod test;
 
 use self::config::LogConfig;
 use chroma_config::Configurable;
-use chroma_error::ChromaError;
+use chroma_error::{ChromaError, ErrorCodes};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub(crate) enum LogConfigError {
+    #[error("Invalid log config")]
+    InvalidLogConfig,
+}
+
+impl ChromaError for LogConfigError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            LogConfigError::InvalidLogConfig => ErrorCodes::InvalidArgument,
+        }
+    }
+}
 
 pub(crate) async fn from_config(config: &LogConfig) -> Result<Box<log::Log>, Box<dyn ChromaError>> {
     match &config {
         crate::log::config::LogConfig::Grpc(_) => Ok(Box::new(log::Log::Grpc(
             log::GrpcLog::try_from_config(config).await?,
         ))),
+        crate::log::config::LogConfig::Local(_) => Ok(Box::new(log::Log::Local(
+            local::LocalLog::try_from_config(config).await?,
+        ))),
     }
 }
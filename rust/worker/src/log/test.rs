@@ -63,6 +63,10 @@ pub fn modulo_metadata(value: usize) -> HashMap<String, UpdateMetadataValue> {
     .collect()
 }
 
+pub fn int_as_uri(value: usize) -> String {
+    format!("s3://bucket/{}", int_as_id(value))
+}
+
 pub fn random_document(len: usize) -> String {
     thread_rng()
         .sample_iter(&Alphanumeric)
@@ -84,6 +88,9 @@ pub fn upsert_generator(offset: usize) -> OperationRecord {
         encoding: None,
         metadata: Some(modulo_metadata(offset)),
         document: Some(random_document(6)),
+        uri: Some(int_as_uri(offset)),
+        idempotency_key: None,
+        embedding_reference: None,
         operation: Operation::Upsert,
     }
 }
@@ -100,6 +107,9 @@ pub fn add_delete_generator(offset: usize) -> OperationRecord {
             encoding: None,
             metadata: None,
             document: None,
+            uri: None,
+            idempotency_key: None,
+            embedding_reference: None,
             operation: Operation::Delete,
         }
     } else {
@@ -110,6 +120,9 @@ pub fn add_delete_generator(offset: usize) -> OperationRecord {
             encoding: None,
             metadata: Some(modulo_metadata(int_id)),
             document: Some(modulo_document(int_id)),
+            uri: None,
+            idempotency_key: None,
+            embedding_reference: None,
             operation: Operation::Add,
         }
     }
@@ -0,0 +1,302 @@
+use std::time::Duration;
+
+use chroma_blockstore::provider::BlockfileProvider;
+use chroma_index::hnsw_provider::HnswIndexProvider;
+use serde::Deserialize;
+use tokio::task::JoinHandle;
+
+use crate::{compactor::CompactionPauses, execution::memory::MemoryPool};
+
+// A watermark that is crossed stays crossed until the memory falls below this share of it, so
+// that the worker does not flap between the levels of pressure
+const RECOVERY_RATIO: f64 = 0.9;
+
+/// The memory watermarks of a worker, across its caches and its running queries. Without a
+/// watermark, each of them only manages its own memory
+/// - `high_watermark_bytes`: The bytes above which the caches are shrunk and compaction is
+///   paused
+/// - `critical_watermark_bytes`: The bytes above which new queries are also rejected
+/// - `interval_ms`: The interval at which the memory of the worker is checked
+/// - `cache_shrink_ratio`: The share of their configured capacity that the caches are shrunk
+///   to under pressure
+#[derive(Debug, Deserialize)]
+pub(crate) struct MemoryWatchdogConfig {
+    #[serde(default)]
+    pub(crate) high_watermark_bytes: Option<usize>,
+    #[serde(default)]
+    pub(crate) critical_watermark_bytes: Option<usize>,
+    #[serde(default = "MemoryWatchdogConfig::default_interval_ms")]
+    pub(crate) interval_ms: u64,
+    #[serde(default = "MemoryWatchdogConfig::default_cache_shrink_ratio")]
+    pub(crate) cache_shrink_ratio: f64,
+}
+
+impl MemoryWatchdogConfig {
+    fn default_interval_ms() -> u64 {
+        1000
+    }
+
+    fn default_cache_shrink_ratio() -> f64 {
+        0.5
+    }
+}
+
+impl Default for MemoryWatchdogConfig {
+    fn default() -> Self {
+        Self {
+            high_watermark_bytes: None,
+            critical_watermark_bytes: None,
+            interval_ms: Self::default_interval_ms(),
+            cache_shrink_ratio: Self::default_cache_shrink_ratio(),
+        }
+    }
+}
+
+/// The pressure on the memory of the worker, by the highest watermark that it crossed
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum MemoryPressure {
+    Normal,
+    High,
+    Critical,
+}
+
+// A cache whose memory the watchdog accounts for, and which it shrinks under pressure
+trait WatchedCache: Send + Sync {
+    fn usage_bytes(&self) -> usize;
+    fn capacity_bytes(&self) -> Option<usize>;
+    fn resize(&self, capacity_bytes: usize);
+}
+
+impl WatchedCache for BlockfileProvider {
+    fn usage_bytes(&self) -> usize {
+        self.cache_usage_bytes()
+    }
+
+    fn capacity_bytes(&self) -> Option<usize> {
+        self.cache_capacity_bytes()
+    }
+
+    fn resize(&self, capacity_bytes: usize) {
+        self.resize_cache(capacity_bytes)
+    }
+}
+
+impl WatchedCache for HnswIndexProvider {
+    fn usage_bytes(&self) -> usize {
+        self.cache_usage_bytes()
+    }
+
+    fn capacity_bytes(&self) -> Option<usize> {
+        self.cache_capacity_bytes()
+    }
+
+    fn resize(&self, capacity_bytes: usize) {
+        self.resize_cache(capacity_bytes)
+    }
+}
+
+struct Watched {
+    cache: Box<dyn WatchedCache>,
+    // The capacity that the cache is restored to once the pressure is relieved
+    configured_capacity_bytes: Option<usize>,
+}
+
+/// Tracks the memory that the caches and the running queries of a worker hold in total, and
+/// coordinates them once it crosses a watermark, instead of each of them managing its own
+/// memory until the worker runs out of it
+///
+/// Above the high watermark, the caches are shrunk and compaction is paused. Above the
+/// critical watermark, new queries are also rejected while the running queries continue.
+/// Everything is restored once the memory falls back below the watermarks
+pub(crate) struct MemoryWatchdog {
+    high_watermark_bytes: Option<usize>,
+    critical_watermark_bytes: Option<usize>,
+    interval: Duration,
+    cache_shrink_ratio: f64,
+    caches: Vec<Watched>,
+    memory_pools: Vec<MemoryPool>,
+    compaction_pauses: Vec<CompactionPauses>,
+    pressure: MemoryPressure,
+}
+
+impl MemoryWatchdog {
+    pub(crate) fn new(config: &MemoryWatchdogConfig) -> Self {
+        Self {
+            high_watermark_bytes: config.high_watermark_bytes,
+            critical_watermark_bytes: config.critical_watermark_bytes,
+            interval: Duration::from_millis(config.interval_ms),
+            cache_shrink_ratio: config.cache_shrink_ratio.clamp(0.0, 1.0),
+            caches: Vec::new(),
+            memory_pools: Vec::new(),
+            compaction_pauses: Vec::new(),
+            pressure: MemoryPressure::Normal,
+        }
+    }
+
+    fn with_cache(mut self, cache: impl WatchedCache + 'static) -> Self {
+        self.caches.push(Watched {
+            configured_capacity_bytes: cache.capacity_bytes(),
+            cache: Box::new(cache),
+        });
+        self
+    }
+
+    /// Accounts for the block cache of the provider, and shrinks it under pressure
+    pub(crate) fn with_blockfile_provider(self, provider: BlockfileProvider) -> Self {
+        self.with_cache(provider)
+    }
+
+    /// Accounts for the index cache of the provider, and shrinks it under pressure
+    pub(crate) fn with_hnsw_index_provider(self, provider: HnswIndexProvider) -> Self {
+        self.with_cache(provider)
+    }
+
+    /// Accounts for the memory of the running queries of the pool, and rejects new queries
+    /// under critical pressure
+    pub(crate) fn with_memory_pool(mut self, memory_pool: MemoryPool) -> Self {
+        self.memory_pools.push(memory_pool);
+        self
+    }
+
+    /// Pauses the compactions under pressure
+    pub(crate) fn with_compaction_pauses(mut self, compaction_pauses: CompactionPauses) -> Self {
+        self.compaction_pauses.push(compaction_pauses);
+        self
+    }
+
+    /// The bytes held by the caches and the running queries
+    pub(crate) fn used_bytes(&self) -> usize {
+        self.caches
+            .iter()
+            .map(|watched| watched.cache.usage_bytes())
+            .chain(self.memory_pools.iter().map(MemoryPool::used_bytes))
+            .sum()
+    }
+
+    fn crossed(&self, watermark: Option<usize>, level: MemoryPressure, used: usize) -> bool {
+        match watermark {
+            Some(watermark) if self.pressure >= level => {
+                used as f64 >= watermark as f64 * RECOVERY_RATIO
+            }
+            Some(watermark) => used >= watermark,
+            None => false,
+        }
+    }
+
+    /// Checks the memory of the worker, and coordinates the caches, the queries and the
+    /// compactions if the pressure changed
+    pub(crate) fn check(&mut self) -> MemoryPressure {
+        let used = self.used_bytes();
+        let pressure = if self.crossed(
+            self.critical_watermark_bytes,
+            MemoryPressure::Critical,
+            used,
+        ) {
+            MemoryPressure::Critical
+        } else if self.crossed(self.high_watermark_bytes, MemoryPressure::High, used) {
+            MemoryPressure::High
+        } else {
+            MemoryPressure::Normal
+        };
+        if pressure == self.pressure {
+            return pressure;
+        }
+
+        tracing::warn!(
+            "Memory pressure changed from {:?} to {:?} with {} bytes in use",
+            self.pressure,
+            pressure,
+            used
+        );
+        if (pressure >= MemoryPressure::High) != (self.pressure >= MemoryPressure::High) {
+            let shrink = pressure >= MemoryPressure::High;
+            for watched in &self.caches {
+                if let Some(capacity) = watched.configured_capacity_bytes {
+                    watched.cache.resize(if shrink {
+                        (capacity as f64 * self.cache_shrink_ratio) as usize
+                    } else {
+                        capacity
+                    });
+                }
+            }
+            for pauses in &self.compaction_pauses {
+                pauses.set_memory_pressure(shrink);
+            }
+        }
+        for memory_pool in &self.memory_pools {
+            memory_pool.reject_new_queries(pressure == MemoryPressure::Critical);
+        }
+        self.pressure = pressure;
+        pressure
+    }
+
+    /// Checks the memory of the worker at every interval in the background. Without a
+    /// watermark, there is nothing to check and the task returns immediately
+    pub(crate) fn start(mut self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            if self.high_watermark_bytes.is_none() && self.critical_watermark_bytes.is_none() {
+                return;
+            }
+            let mut interval = tokio::time::interval(self.interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                self.check();
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chroma_types::CollectionUuid;
+
+    use crate::{
+        compactor::CompactionPauses,
+        execution::memory::{MemoryError, MemoryPool},
+    };
+
+    use super::{MemoryPressure, MemoryWatchdog, MemoryWatchdogConfig};
+
+    #[test]
+    fn test_watermarks() {
+        let memory_pool = MemoryPool::default();
+        let pauses = CompactionPauses::default();
+        let collection_id = CollectionUuid::new();
+        let mut watchdog = MemoryWatchdog::new(&MemoryWatchdogConfig {
+            high_watermark_bytes: Some(100),
+            critical_watermark_bytes: Some(200),
+            ..Default::default()
+        })
+        .with_memory_pool(memory_pool.clone())
+        .with_compaction_pauses(pauses.clone());
+
+        let query = memory_pool.admit().expect("Query should be admitted");
+        query.reserve(150).unwrap();
+        assert_eq!(watchdog.check(), MemoryPressure::High);
+        assert!(!pauses.admits(collection_id));
+        memory_pool
+            .admit()
+            .expect("Query should be admitted under high pressure");
+
+        query.reserve(100).unwrap();
+        assert_eq!(watchdog.check(), MemoryPressure::Critical);
+        assert!(matches!(
+            memory_pool.admit(),
+            Err(MemoryError::MemoryPressure)
+        ));
+
+        // The pressure is relieved only once the memory falls clearly below the watermark
+        query.release(60);
+        assert_eq!(watchdog.check(), MemoryPressure::Critical);
+        query.release(40);
+        assert_eq!(watchdog.check(), MemoryPressure::High);
+        memory_pool
+            .admit()
+            .expect("Query should be admitted once the critical pressure is relieved");
+
+        drop(query);
+        assert_eq!(watchdog.check(), MemoryPressure::Normal);
+        assert!(pauses.admits(collection_id));
+    }
+}
@@ -0,0 +1,211 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::{self, Debug, Formatter},
+    sync::Arc,
+};
+
+use chroma_blockstore::provider::BlockfileProvider;
+use chroma_index::{hnsw_provider::HnswIndexProvider, IndexUuid};
+use chroma_types::{Collection, CollectionUuid, Segment, SegmentUuid};
+use parking_lot::Mutex;
+use uuid::Uuid;
+
+use super::distributed_hnsw_segment::HNSW_INDEX;
+
+// The number of collections whose latest version is tracked. Once reached, the collection
+// observed first is forgotten, and its next version is observed without evicting anything
+const MAX_OBSERVED_COLLECTIONS: usize = 1 << 16;
+
+struct ObservedCollection {
+    version: i32,
+    file_paths: HashMap<SegmentUuid, HashMap<String, Vec<String>>>,
+}
+
+struct ObservedCollections {
+    capacity: usize,
+    collections: HashMap<CollectionUuid, ObservedCollection>,
+    // The observed collections in the order they are first observed
+    order: VecDeque<CollectionUuid>,
+}
+
+impl ObservedCollections {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            collections: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    fn insert(&mut self, collection_id: CollectionUuid, collection: ObservedCollection) {
+        while self.collections.len() >= self.capacity {
+            match self.order.pop_front() {
+                Some(oldest) => self.collections.remove(&oldest),
+                None => break,
+            };
+        }
+        self.collections.insert(collection_id, collection);
+        self.order.push_back(collection_id);
+    }
+}
+
+/// Tracks the latest version of each collection observed from the sysdb. When a newer version
+/// is observed, the cached blockfiles and HNSW index of the superseded version are evicted
+/// right away instead of waiting for them to fall out of the LRU caches.
+#[derive(Clone)]
+pub(crate) struct SegmentCacheInvalidator {
+    blockfile_provider: BlockfileProvider,
+    hnsw_provider: HnswIndexProvider,
+    observed: Arc<Mutex<ObservedCollections>>,
+}
+
+impl Debug for SegmentCacheInvalidator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SegmentCacheInvalidator")
+            .finish_non_exhaustive()
+    }
+}
+
+impl SegmentCacheInvalidator {
+    pub(crate) fn new(
+        blockfile_provider: BlockfileProvider,
+        hnsw_provider: HnswIndexProvider,
+    ) -> Self {
+        Self {
+            blockfile_provider,
+            hnsw_provider,
+            observed: Arc::new(Mutex::new(ObservedCollections::new(
+                MAX_OBSERVED_COLLECTIONS,
+            ))),
+        }
+    }
+
+    /// Records the segments of the collection at its current version, and evicts the
+    /// cache entries of the files they replace if the version is newer than the last one observed
+    pub(crate) async fn observe(&self, collection: &Collection, segments: &[&Segment]) {
+        let superseded_file_paths = {
+            let file_paths = segments
+                .iter()
+                .map(|segment| (segment.id, segment.file_path.clone()))
+                .collect();
+            let mut observed = self.observed.lock();
+            match observed.collections.get_mut(&collection.collection_id) {
+                Some(previous) if previous.version >= collection.version => return,
+                Some(previous) => {
+                    previous.version = collection.version;
+                    std::mem::replace(&mut previous.file_paths, file_paths)
+                }
+                None => {
+                    observed.insert(
+                        collection.collection_id,
+                        ObservedCollection {
+                            version: collection.version,
+                            file_paths,
+                        },
+                    );
+                    return;
+                }
+            }
+        };
+
+        for segment in segments {
+            let Some(superseded) = superseded_file_paths.get(&segment.id) else {
+                continue;
+            };
+            for (file, current_ids) in &segment.file_path {
+                let Some(stale_ids) = superseded.get(file) else {
+                    continue;
+                };
+                if file == HNSW_INDEX {
+                    if let Some(current_id) =
+                        current_ids.first().and_then(|id| Uuid::parse_str(id).ok())
+                    {
+                        self.hnsw_provider
+                            .evict_superseded(&collection.collection_id, &IndexUuid(current_id))
+                            .await;
+                    }
+                    continue;
+                }
+                for (stale_id, current_id) in stale_ids.iter().zip(current_ids) {
+                    if stale_id == current_id {
+                        continue;
+                    }
+                    match (Uuid::parse_str(stale_id), Uuid::parse_str(current_id)) {
+                        (Ok(stale_id), Ok(current_id)) => {
+                            self.blockfile_provider
+                                .evict_superseded(&stale_id, &current_id)
+                                .await
+                        }
+                        _ => tracing::warn!(
+                            "Unable to parse blockfile ids {} and {} for eviction",
+                            stale_id,
+                            current_id
+                        ),
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use chroma_blockstore::test_arrow_blockfile_provider;
+    use chroma_index::test_hnsw_index_provider;
+    use chroma_types::{Collection, CollectionUuid};
+    use parking_lot::Mutex;
+
+    use super::{ObservedCollections, SegmentCacheInvalidator};
+
+    fn invalidator(capacity: usize) -> SegmentCacheInvalidator {
+        SegmentCacheInvalidator {
+            blockfile_provider: test_arrow_blockfile_provider(1 << 20),
+            hnsw_provider: test_hnsw_index_provider(),
+            observed: Arc::new(Mutex::new(ObservedCollections::new(capacity))),
+        }
+    }
+
+    fn collection(version: i32) -> Collection {
+        Collection {
+            collection_id: CollectionUuid::new(),
+            name: "Test Collection".to_string(),
+            metadata: None,
+            dimension: None,
+            tenant: "Test Tenant".to_string(),
+            database: String::new(),
+            log_position: 0,
+            version,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_observe_keeps_latest_version() {
+        let invalidator = invalidator(4);
+        let mut collection = collection(2);
+        invalidator.observe(&collection, &[]).await;
+        collection.version = 1;
+        invalidator.observe(&collection, &[]).await;
+        collection.version = 3;
+        invalidator.observe(&collection, &[]).await;
+        let observed = invalidator.observed.lock();
+        assert_eq!(observed.collections.len(), 1);
+        assert_eq!(observed.collections[&collection.collection_id].version, 3);
+    }
+
+    #[tokio::test]
+    async fn test_observe_is_bounded() {
+        let invalidator = invalidator(2);
+        let collections = (0..3).map(collection).collect::<Vec<_>>();
+        for collection in &collections {
+            invalidator.observe(collection, &[]).await;
+        }
+        let observed = invalidator.observed.lock();
+        assert_eq!(observed.collections.len(), 2);
+        assert!(!observed
+            .collections
+            .contains_key(&collections[0].collection_id));
+        assert_eq!(observed.order.len(), 2);
+    }
+}
@@ -0,0 +1,235 @@
+use std::{fmt, str::FromStr};
+
+use chroma_error::{ChromaError, ErrorCodes};
+use chroma_types::{DataRecord, MetadataValue};
+use futures::TryStreamExt;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use super::record_segment::RecordSegmentReader;
+
+// The version of the encoding of the records, which prefixes the checksum so that a change to
+// the encoding is not mistaken for a change to the content
+const CHECKSUM_VERSION: &str = "v1";
+
+/// A checksum of the content of a record segment, which is the same for two segments with the
+/// same records regardless of the order that they are written in, their offset ids, or the
+/// layout of their blocks. It is the count and the wrapping sum of the hashes of the records,
+/// so that it is updated in place as records are added and removed instead of recomputed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ContentChecksum {
+    num_records: u64,
+    sum: u128,
+}
+
+#[derive(Debug, Error)]
+#[error("Invalid content checksum: {0}")]
+pub struct ContentChecksumParseError(String);
+
+impl ChromaError for ContentChecksumParseError {
+    fn code(&self) -> ErrorCodes {
+        ErrorCodes::Internal
+    }
+}
+
+impl ContentChecksum {
+    pub fn num_records(&self) -> u64 {
+        self.num_records
+    }
+
+    pub(crate) fn add(&mut self, record: &DataRecord) {
+        self.num_records = self.num_records.wrapping_add(1);
+        self.sum = self.sum.wrapping_add(record_hash(record));
+    }
+
+    pub(crate) fn remove(&mut self, record: &DataRecord) {
+        self.num_records = self.num_records.wrapping_sub(1);
+        self.sum = self.sum.wrapping_sub(record_hash(record));
+    }
+
+    /// Computes the checksum from every record of the segment
+    pub(crate) async fn compute(
+        reader: &RecordSegmentReader<'_>,
+    ) -> Result<Self, Box<dyn ChromaError>> {
+        reader
+            .scan(None)
+            .try_fold(Self::default(), |mut checksum, (_, record)| async move {
+                checksum.add(&record);
+                Ok(checksum)
+            })
+            .await
+    }
+}
+
+impl fmt::Display for ContentChecksum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{:032x}",
+            CHECKSUM_VERSION, self.num_records, self.sum
+        )
+    }
+}
+
+impl FromStr for ContentChecksum {
+    type Err = ContentChecksumParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ContentChecksumParseError(s.to_string());
+        let mut parts = s.split(':');
+        if parts.next() != Some(CHECKSUM_VERSION) {
+            return Err(invalid());
+        }
+        let num_records = parts
+            .next()
+            .and_then(|part| part.parse().ok())
+            .ok_or_else(invalid)?;
+        let sum = parts
+            .next()
+            .and_then(|part| u128::from_str_radix(part, 16).ok())
+            .ok_or_else(invalid)?;
+        if parts.next().is_some() {
+            return Err(invalid());
+        }
+        Ok(Self { num_records, sum })
+    }
+}
+
+// Hashes an unambiguous encoding of the record, in which every variable length field is length
+// prefixed and the metadata is ordered by key
+fn record_hash(record: &DataRecord) -> u128 {
+    let mut hasher = Sha256::new();
+    hash_str(&mut hasher, record.id);
+    hasher.update((record.embedding.len() as u64).to_le_bytes());
+    for value in record.embedding {
+        hasher.update(value.to_le_bytes());
+    }
+    hash_optional_str(&mut hasher, record.document);
+    hash_optional_str(&mut hasher, record.uri);
+    match &record.metadata {
+        Some(metadata) => {
+            let mut entries = metadata.iter().collect::<Vec<_>>();
+            entries.sort_unstable_by_key(|(key, _)| *key);
+            hasher.update((entries.len() as u64).to_le_bytes());
+            for (key, value) in entries {
+                hash_str(&mut hasher, key);
+                hash_metadata_value(&mut hasher, value);
+            }
+        }
+        None => hasher.update(0u64.to_le_bytes()),
+    }
+    let digest = hasher.finalize();
+    let mut bytes = [0; 16];
+    bytes.copy_from_slice(&digest[..16]);
+    u128::from_le_bytes(bytes)
+}
+
+fn hash_str(hasher: &mut Sha256, value: &str) {
+    hasher.update((value.len() as u64).to_le_bytes());
+    hasher.update(value.as_bytes());
+}
+
+fn hash_optional_str(hasher: &mut Sha256, value: Option<&str>) {
+    match value {
+        Some(value) => {
+            hasher.update([1]);
+            hash_str(hasher, value);
+        }
+        None => hasher.update([0]),
+    }
+}
+
+fn hash_metadata_value(hasher: &mut Sha256, value: &MetadataValue) {
+    match value {
+        MetadataValue::Bool(value) => hasher.update([0, *value as u8]),
+        MetadataValue::Int(value) => {
+            hasher.update([1]);
+            hasher.update(value.to_le_bytes());
+        }
+        MetadataValue::Float(value) => {
+            hasher.update([2]);
+            hasher.update(value.to_le_bytes());
+        }
+        MetadataValue::Str(value) => {
+            hasher.update([3]);
+            hash_str(hasher, value);
+        }
+        MetadataValue::Timestamp(value) => {
+            hasher.update([4]);
+            hasher.update(value.to_le_bytes());
+        }
+        MetadataValue::List(values) => {
+            hasher.update([5]);
+            hasher.update((values.len() as u64).to_le_bytes());
+            for value in values {
+                hash_metadata_value(hasher, value);
+            }
+        }
+        MetadataValue::Object(entries) => {
+            // The entries of an object are ordered by key
+            hasher.update([6]);
+            hasher.update((entries.len() as u64).to_le_bytes());
+            for (key, value) in entries {
+                hash_str(hasher, key);
+                hash_metadata_value(hasher, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chroma_types::{DataRecord, MetadataValue};
+
+    use super::ContentChecksum;
+
+    #[test]
+    fn test_content_checksum() {
+        let embedding = [1.0, 2.0];
+        let record = |id, document| DataRecord {
+            id,
+            embedding: &embedding,
+            metadata: Some(HashMap::from([("key".to_string(), MetadataValue::Int(1))])),
+            document,
+            uri: None,
+        };
+        let (a, b, c) = (
+            record("a", Some("doc")),
+            record("b", None),
+            record("c", Some("")),
+        );
+
+        let mut forward = ContentChecksum::default();
+        forward.add(&a);
+        forward.add(&b);
+        forward.add(&c);
+        let mut backward = ContentChecksum::default();
+        backward.add(&c);
+        backward.add(&b);
+        backward.add(&a);
+        assert_eq!(forward, backward);
+        assert_eq!(forward.num_records(), 3);
+
+        // Removing a record restores the checksum of the other records
+        let mut without_b = ContentChecksum::default();
+        without_b.add(&a);
+        without_b.add(&c);
+        forward.remove(&b);
+        assert_eq!(forward, without_b);
+
+        // A missing document differs from an empty one
+        let mut other = ContentChecksum::default();
+        other.add(&a);
+        other.add(&record("c", None));
+        assert_ne!(other, without_b);
+
+        let parsed = without_b
+            .to_string()
+            .parse::<ContentChecksum>()
+            .expect("Checksum should round trip");
+        assert_eq!(parsed, without_b);
+        assert!("v0:1:ff".parse::<ContentChecksum>().is_err());
+    }
+}
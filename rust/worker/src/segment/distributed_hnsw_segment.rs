@@ -7,16 +7,25 @@ use chroma_index::hnsw_provider::{
     HnswIndexProvider, HnswIndexProviderCreateError, HnswIndexProviderForkError,
     HnswIndexProviderOpenError, HnswIndexRef,
 };
-use chroma_index::{Index, IndexUuid};
+use chroma_index::{Index, IndexUuid, ScalarQuantization};
 use chroma_index::{DEFAULT_HNSW_EF_CONSTRUCTION, DEFAULT_HNSW_EF_SEARCH, DEFAULT_HNSW_M};
 use chroma_types::SegmentUuid;
-use chroma_types::{get_metadata_value_as, MaterializedLogOperation, MetadataValue, Segment};
+use chroma_types::{
+    get_metadata_value_as, CollectionUuid, MaterializedLogOperation, Metadata, MetadataValue,
+    Segment,
+};
 use std::collections::HashMap;
 use std::fmt::Debug;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use thiserror::Error;
 use uuid::Uuid;
 
-const HNSW_INDEX: &str = "hnsw_index";
+pub(crate) const HNSW_INDEX: &str = "hnsw_index";
+
+/// The share of clamped embedding values written by a writer above which the quantization
+/// of the index is considered to have drifted from the embeddings
+const QUANTIZATION_DRIFT_THRESHOLD: f64 = 0.01;
 
 pub struct HnswIndexParamsFromSegment {
     pub m: usize,
@@ -29,6 +38,11 @@ pub(crate) struct DistributedHNSWSegmentWriter {
     index: HnswIndexRef,
     hnsw_index_provider: HnswIndexProvider,
     pub(crate) id: SegmentUuid,
+    collection: CollectionUuid,
+    // The embedding values written by the writer and its clones, and how many of them
+    // were clamped by the quantization of the index
+    clipped_values: Arc<AtomicUsize>,
+    total_values: Arc<AtomicUsize>,
 }
 
 impl Debug for DistributedHNSWSegmentWriter {
@@ -103,6 +117,48 @@ fn hnsw_params_from_segment(segment: &Segment) -> HnswIndexParamsFromSegment {
     }
 }
 
+/// Returns the int8 quantization of the index if it is enabled by `hnsw:quantization`,
+/// with the scale and offset stored in the segment metadata.
+pub fn quantization_from_segment(segment: &Segment) -> Option<ScalarQuantization> {
+    let metadata = segment.metadata.as_ref()?;
+    match metadata.get("hnsw:quantization") {
+        Some(MetadataValue::Str(quantization)) if quantization == "int8" => {}
+        _ => return None,
+    }
+
+    let default_quantization = ScalarQuantization::default();
+    let scale = match get_metadata_value_as::<f64>(metadata, "hnsw:quantization_scale") {
+        Ok(scale) if scale > 0.0 => scale as f32,
+        _ => default_quantization.scale,
+    };
+    let offset = match get_metadata_value_as::<f64>(metadata, "hnsw:quantization_offset") {
+        Ok(offset) => offset as f32,
+        Err(_) => default_quantization.offset,
+    };
+
+    Some(ScalarQuantization { scale, offset })
+}
+
+/// The segment metadata that stores the scale and the offset of the quantization
+pub(crate) fn quantization_metadata(quantization: &ScalarQuantization) -> Metadata {
+    HashMap::from([
+        (
+            "hnsw:quantization_scale".to_string(),
+            MetadataValue::Float(quantization.scale as f64),
+        ),
+        (
+            "hnsw:quantization_offset".to_string(),
+            MetadataValue::Float(quantization.offset as f64),
+        ),
+    ])
+}
+
+/// Returns the id of the index referenced by the files of the segment, if any
+pub(crate) fn hnsw_index_uuid_from_segment(segment: &Segment) -> Option<IndexUuid> {
+    let index_id = segment.file_path.get(HNSW_INDEX)?.first()?;
+    Uuid::parse_str(index_id).ok().map(IndexUuid)
+}
+
 pub fn distance_function_from_segment(
     segment: &Segment,
 ) -> Result<DistanceFunction, Box<DistributedHNSWSegmentFromSegmentError>> {
@@ -126,14 +182,26 @@ impl DistributedHNSWSegmentWriter {
         index: HnswIndexRef,
         hnsw_index_provider: HnswIndexProvider,
         id: SegmentUuid,
+        collection: CollectionUuid,
     ) -> Self {
         DistributedHNSWSegmentWriter {
             index,
             hnsw_index_provider,
             id,
+            collection,
+            clipped_values: Arc::new(AtomicUsize::new(0)),
+            total_values: Arc::new(AtomicUsize::new(0)),
         }
     }
 
+    /// Whether the share of the embedding values clamped by the quantization of the index
+    /// exceeds `QUANTIZATION_DRIFT_THRESHOLD`, so that the quantization should be retrained
+    pub(crate) fn quantization_drifted(&self) -> bool {
+        let clipped_values = self.clipped_values.load(Ordering::Relaxed);
+        let total_values = self.total_values.load(Ordering::Relaxed);
+        clipped_values as f64 > QUANTIZATION_DRIFT_THRESHOLD * total_values as f64
+    }
+
     pub(crate) async fn from_segment(
         segment: &Segment,
         dimensionality: usize,
@@ -186,6 +254,7 @@ impl DistributedHNSWSegmentWriter {
                     &segment.collection,
                     dimensionality as i32,
                     distance_function,
+                    quantization_from_segment(segment),
                 )
                 .await
             {
@@ -201,6 +270,7 @@ impl DistributedHNSWSegmentWriter {
                 index,
                 hnsw_index_provider,
                 segment.id,
+                segment.collection,
             )))
         } else {
             let hnsw_params = hnsw_params_from_segment(segment);
@@ -219,6 +289,7 @@ impl DistributedHNSWSegmentWriter {
                     hnsw_params.ef_search,
                     dimensionality as i32,
                     distance_function,
+                    quantization_from_segment(segment),
                 )
                 .await
             {
@@ -233,6 +304,7 @@ impl DistributedHNSWSegmentWriter {
                 index,
                 hnsw_index_provider,
                 segment.id,
+                segment.collection,
             )))
         }
     }
@@ -243,6 +315,8 @@ impl<'a> SegmentWriter<'a> for DistributedHNSWSegmentWriter {
         &self,
         records: chroma_types::Chunk<super::MaterializedLogRecord<'a>>,
     ) -> Result<(), ApplyMaterializedLogError> {
+        let mut clipped_values = 0;
+        let mut total_values = 0;
         for (record, _) in records.iter() {
             match record.final_operation {
                 // If embedding is not found in case of adds it means that user
@@ -265,6 +339,9 @@ impl<'a> SegmentWriter<'a> for DistributedHNSWSegmentWriter {
                         })?;
                     }
 
+                    clipped_values += index.clipped(embedding);
+                    total_values += embedding.len();
+
                     match index.add(record.offset_id as usize, embedding) {
                         Ok(_) => {}
                         Err(e) => {
@@ -289,6 +366,21 @@ impl<'a> SegmentWriter<'a> for DistributedHNSWSegmentWriter {
                 ),
             }
         }
+
+        // The drift is tracked across the chunks, and the compaction retrains the
+        // quantization once every chunk is written
+        self.clipped_values
+            .fetch_add(clipped_values, Ordering::Relaxed);
+        self.total_values.fetch_add(total_values, Ordering::Relaxed);
+        if clipped_values as f64 > QUANTIZATION_DRIFT_THRESHOLD * total_values as f64 {
+            tracing::warn!(
+                "{} of {} embedding values written to hnsw segment {} are outside of the range of its quantization {:?}",
+                clipped_values,
+                total_values,
+                self.id,
+                self.index.inner.read().quantization(),
+            );
+        }
         Ok(())
     }
 
@@ -305,7 +397,11 @@ impl<'a> SegmentWriter<'a> for DistributedHNSWSegmentWriter {
 impl SegmentFlusher for DistributedHNSWSegmentWriter {
     async fn flush(self) -> Result<HashMap<String, Vec<String>>, Box<dyn ChromaError>> {
         let hnsw_index_id = self.index.inner.read().id;
-        match self.hnsw_index_provider.flush(&hnsw_index_id).await {
+        match self
+            .hnsw_index_provider
+            .flush(&hnsw_index_id, &self.collection)
+            .await
+        {
             Ok(_) => {}
             Err(e) => return Err(e),
         }
@@ -392,6 +488,7 @@ impl DistributedHNSWSegmentReader {
                                 &segment.collection,
                                 dimensionality as i32,
                                 distance_function,
+                                quantization_from_segment(segment),
                             )
                             .await
                         {
@@ -425,21 +522,47 @@ impl DistributedHNSWSegmentReader {
         let index = self.index.inner.read();
         index.query(vector, k, allowed_ids, disallowd_ids)
     }
+
+    /// The number of embeddings in the index
+    pub(crate) fn len(&self) -> usize {
+        self.index.inner.read().len()
+    }
+
+    /// Whether the index holds an embedding for the offset id. The embeddings that are
+    /// marked as deleted are not held
+    pub(crate) fn contains(&self, offset_id: u32) -> bool {
+        matches!(self.index.inner.read().get(offset_id as usize), Ok(Some(_)))
+    }
+
+    /// The quantization of the index. The distances returned by a quantized index are
+    /// approximate and should be rescored with the original embeddings.
+    pub(crate) fn quantization(&self) -> Option<ScalarQuantization> {
+        self.index.inner.read().quantization()
+    }
 }
 
 #[cfg(test)]
 pub mod test {
-    use std::collections::HashMap;
+    use std::{collections::HashMap, sync::atomic::AtomicU32};
 
     use chroma_index::{
-        HnswIndexConfig, DEFAULT_HNSW_EF_CONSTRUCTION, DEFAULT_HNSW_EF_SEARCH, DEFAULT_HNSW_M,
-        DEFAULT_MAX_ELEMENTS,
+        HnswIndexConfig, ScalarQuantization, DEFAULT_HNSW_EF_CONSTRUCTION, DEFAULT_HNSW_EF_SEARCH,
+        DEFAULT_HNSW_M, DEFAULT_MAX_ELEMENTS,
     };
-    use chroma_types::{CollectionUuid, MetadataValue, Segment, SegmentUuid};
+    use chroma_types::{CollectionUuid, MetadataValue, OperationRecord, Segment, SegmentUuid};
     use tempfile::tempdir;
     use uuid::Uuid;
 
-    use crate::segment::distributed_hnsw_segment::hnsw_params_from_segment;
+    use crate::{
+        log::test::{upsert_generator, LogGenerator, TEST_EMBEDDING_DIMENSION},
+        segment::{
+            distributed_hnsw_segment::{
+                hnsw_params_from_segment, quantization_from_segment, DistributedHNSWSegmentWriter,
+            },
+            test::TestSegment,
+            LogMaterializer, SegmentWriter,
+        },
+    };
 
     #[test]
     fn parameter_defaults() {
@@ -499,4 +622,97 @@ pub mod test {
         assert_eq!(config.random_seed, 0);
         assert_eq!(config.persist_path, persist_path.to_str().unwrap());
     }
+
+    #[test]
+    fn quantization_from_metadata() {
+        let mut segment = Segment {
+            id: SegmentUuid(Uuid::new_v4()),
+            r#type: chroma_types::SegmentType::HnswDistributed,
+            scope: chroma_types::SegmentScope::VECTOR,
+            metadata: Some(HashMap::new()),
+            collection: CollectionUuid(Uuid::new_v4()),
+            file_path: HashMap::new(),
+        };
+        assert_eq!(quantization_from_segment(&segment), None);
+
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "hnsw:quantization".to_string(),
+            MetadataValue::Str("int8".to_string()),
+        );
+        segment.metadata = Some(metadata.clone());
+        assert_eq!(
+            quantization_from_segment(&segment),
+            Some(ScalarQuantization::default())
+        );
+
+        metadata.insert(
+            "hnsw:quantization_scale".to_string(),
+            MetadataValue::Float(0.5),
+        );
+        metadata.insert(
+            "hnsw:quantization_offset".to_string(),
+            MetadataValue::Float(-1.0),
+        );
+        segment.metadata = Some(metadata);
+        assert_eq!(
+            quantization_from_segment(&segment),
+            Some(ScalarQuantization {
+                scale: 0.5,
+                offset: -1.0
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn quantization_drift_is_tracked_across_chunks() {
+        let mut test_segment = TestSegment::default();
+        test_segment.vector_segment.metadata = Some(HashMap::from([(
+            "hnsw:quantization".to_string(),
+            MetadataValue::Str("int8".to_string()),
+        )]));
+        let hnsw_writer = DistributedHNSWSegmentWriter::from_segment(
+            &test_segment.vector_segment,
+            TEST_EMBEDDING_DIMENSION,
+            test_segment.hnsw_provider.clone(),
+        )
+        .await
+        .expect("Should be able to initialize hnsw writer.");
+
+        // The embeddings of the upsert generator are within the range of the quantization
+        let logs = LogGenerator {
+            generator: upsert_generator,
+        }
+        .generate_chunk(1..=10);
+        let materializer = LogMaterializer::new(None, logs, Some(AtomicU32::new(0).into()));
+        let materialized_logs = materializer
+            .materialize()
+            .await
+            .expect("Should be able to materialize log.");
+        hnsw_writer
+            .apply_materialized_log_chunk(materialized_logs)
+            .await
+            .expect("Should be able to apply materialized log.");
+        assert!(!hnsw_writer.quantization_drifted());
+
+        // The drift is tracked by the clones of the writer, which write the other partitions
+        let logs = LogGenerator {
+            generator: |offset| OperationRecord {
+                embedding: Some(vec![10.0; TEST_EMBEDDING_DIMENSION]),
+                ..upsert_generator(offset)
+            },
+        }
+        .generate_chunk(11..=20);
+        let materializer = LogMaterializer::new(None, logs, Some(AtomicU32::new(10).into()));
+        let materialized_logs = materializer
+            .materialize()
+            .await
+            .expect("Should be able to materialize log.");
+        hnsw_writer
+            .clone()
+            .apply_materialized_log_chunk(materialized_logs)
+            .await
+            .expect("Should be able to apply materialized log.");
+        assert!(hnsw_writer.quantization_drifted());
+    }
 }
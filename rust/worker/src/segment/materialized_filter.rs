@@ -0,0 +1,135 @@
+use std::collections::BTreeMap;
+
+use chroma_types::{
+    BooleanOperator, Collection, DirectWhereComparison, MetadataValue, PrimitiveOperator, Where,
+    WhereChildren, WhereComparison,
+};
+use serde_json::{Number, Value};
+
+/// The collection metadata key that declares the materialized filters of a collection
+///
+/// The value should be a JSON array of objects, where each object is a conjunction of
+/// equality predicates in the same shape as a `where` clause, e.g.
+/// `[{"tenant_id": "acme"}, {"tenant_id": "globex", "active": true}]`
+pub(crate) const MATERIALIZED_FILTERS_KEY: &str = "materialized_filters";
+
+/// A materialized filter is a where clause whose matching offset ids are precomputed
+/// in the metadata segment at compaction, so that queries with the same where clause
+/// only need a single blockfile read instead of evaluating the metadata indices
+///
+/// Only conjunctions of equality predicates on metadata can be materialized
+#[derive(Clone, Debug)]
+pub(crate) struct MaterializedFilter {
+    pub(crate) key: String,
+    pub(crate) where_clause: Where,
+}
+
+impl MaterializedFilter {
+    pub(crate) fn new(where_clause: Where) -> Option<Self> {
+        materialized_filter_key(&where_clause).map(|key| Self { key, where_clause })
+    }
+}
+
+/// Returns the key of the materialized filter for the where clause, if it could be materialized
+///
+/// The key is the canonical JSON representation of the equality predicates sorted by the
+/// metadata key, so that equivalent where clauses share the same materialized filter
+pub(crate) fn materialized_filter_key(where_clause: &Where) -> Option<String> {
+    let mut predicates = BTreeMap::new();
+    collect_equality_predicates(where_clause, &mut predicates)?;
+    if predicates.is_empty() {
+        return None;
+    }
+    serde_json::to_string(&predicates).ok()
+}
+
+fn collect_equality_predicates<'me>(
+    where_clause: &'me Where,
+    predicates: &mut BTreeMap<&'me str, Value>,
+) -> Option<()> {
+    match where_clause {
+        Where::DirectWhereComparison(DirectWhereComparison {
+            key,
+            comparison: WhereComparison::Primitive(PrimitiveOperator::Equal, value),
+        }) => {
+            let value = match value {
+                MetadataValue::Bool(b) => Value::Bool(*b),
+                MetadataValue::Int(i) => Value::Number((*i).into()),
+                MetadataValue::Float(f) => Value::Number(Number::from_f64(*f)?),
+                MetadataValue::Str(s) => Value::String(s.clone()),
+                MetadataValue::Timestamp(_) | MetadataValue::List(_) | MetadataValue::Object(_) => {
+                    return None
+                }
+            };
+            match predicates.insert(key.as_str(), value.clone()) {
+                // Conflicting predicates on the same key are not worth materializing
+                Some(existing) if existing != value => None,
+                _ => Some(()),
+            }
+        }
+        Where::WhereChildren(WhereChildren {
+            operator: BooleanOperator::And,
+            children,
+        }) => children
+            .iter()
+            .try_for_each(|child| collect_equality_predicates(child, predicates)),
+        _ => None,
+    }
+}
+
+/// Reads the materialized filters declared in the collection metadata
+///
+/// Malformed declarations are ignored, since materialized filters are only an optimization
+pub(crate) fn materialized_filters_from_collection(
+    collection: &Collection,
+) -> Vec<MaterializedFilter> {
+    let declaration = match collection
+        .metadata
+        .as_ref()
+        .and_then(|metadata| metadata.get(MATERIALIZED_FILTERS_KEY))
+    {
+        Some(MetadataValue::Str(declaration)) => declaration,
+        _ => return Vec::new(),
+    };
+
+    let filters = match serde_json::from_str::<Vec<serde_json::Map<String, Value>>>(declaration) {
+        Ok(filters) => filters,
+        Err(e) => {
+            tracing::warn!(
+                "Ignoring malformed materialized filters for collection {}: {}",
+                collection.collection_id,
+                e
+            );
+            return Vec::new();
+        }
+    };
+
+    filters
+        .into_iter()
+        .filter_map(|filter| {
+            let predicates = filter
+                .into_iter()
+                .map(|(key, value)| {
+                    let value = match value {
+                        Value::Bool(b) => MetadataValue::Bool(b),
+                        Value::Number(n) => match n.as_i64() {
+                            Some(i) => MetadataValue::Int(i),
+                            None => MetadataValue::Float(n.as_f64()?),
+                        },
+                        Value::String(s) => MetadataValue::Str(s),
+                        _ => return None,
+                    };
+                    Some(Where::DirectWhereComparison(DirectWhereComparison {
+                        key,
+                        comparison: WhereComparison::Primitive(PrimitiveOperator::Equal, value),
+                    }))
+                })
+                .collect::<Option<Vec<_>>>()?;
+            let where_clause = match predicates.len() {
+                1 => predicates.into_iter().next()?,
+                _ => Where::conjunction(predicates),
+            };
+            MaterializedFilter::new(where_clause)
+        })
+        .collect()
+}
@@ -1,29 +1,42 @@
 use crate::execution::operators::filter::RoaringMetadataFilter;
 
-use super::super::execution::operators::filter::MetadataProvider;
+use super::super::execution::operators::filter::{MetadataLogReader, MetadataProvider};
+use super::materialized_filter::{materialized_filter_key, MaterializedFilter};
 use super::record_segment::ApplyMaterializedLogError;
 use super::types::{MaterializedLogRecord, SegmentWriter};
 use super::SegmentFlusher;
 use async_trait::async_trait;
 use chroma_blockstore::provider::{BlockfileProvider, CreateError, OpenError};
-use chroma_blockstore::BlockfileWriterOptions;
+use chroma_blockstore::{
+    arrow::types::{ArrowReadableKey, ArrowWriteableKey},
+    key::{InvalidKeyConversion, KeyWrapper},
+    BlockfileFlusher, BlockfileReader, BlockfileWriter, BlockfileWriterOptions, Key,
+};
 use chroma_error::{ChromaError, ErrorCodes};
+use chroma_index::fulltext::tokenizer::{TokenizerConfig, TokenizerConfigError};
 use chroma_index::fulltext::types::{
-    DocumentMutation, FullTextIndexError, FullTextIndexFlusher, FullTextIndexReader,
-    FullTextIndexWriter,
+    CorpusStatistics, DocumentMutation, FullTextIndexError, FullTextIndexFlusher,
+    FullTextIndexReader, FullTextIndexWriter, FullTextScores,
 };
-use chroma_index::metadata::types::{
-    MetadataIndexError, MetadataIndexFlusher, MetadataIndexReader, MetadataIndexWriter,
+use chroma_index::metadata::buckets::{
+    BucketedMetadataIndexFlusher, BucketedMetadataIndexReader, BucketedMetadataIndexWriter,
+    MAX_METADATA_KEY_BUCKETS,
 };
+use chroma_index::metadata::types::{MetadataIndexError, MetadataIndexReader, MetadataIndexWriter};
 use chroma_index::utils::merge_sorted_vecs_conjunction;
-use chroma_types::{Chunk, MaterializedLogOperation, MetadataValue, Segment, SegmentUuid, Where};
+use chroma_types::{
+    Chunk, MaterializedLogOperation, Metadata, MetadataValue, Segment, SegmentUuid, Where,
+};
 use chroma_types::{SegmentType, SignedRoaringBitmap};
 use core::panic;
-use futures::future::BoxFuture;
-use futures::FutureExt;
+use futures::future::{self, BoxFuture};
+use futures::{stream, FutureExt, Stream, StreamExt, TryStreamExt};
+use parking_lot::Mutex;
 use roaring::RoaringBitmap;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+use std::time::Instant;
 use tantivy::tokenizer::NgramTokenizer;
 use thiserror::Error;
 use uuid::Uuid;
@@ -33,14 +46,49 @@ const STRING_METADATA: &str = "string_metadata";
 const BOOL_METADATA: &str = "bool_metadata";
 const F32_METADATA: &str = "f32_metadata";
 const U32_METADATA: &str = "u32_metadata";
+const MATERIALIZED_FILTERS: &str = "materialized_filters";
+
+/// Timestamps are stored in the string metadata index under a separate prefix, so that
+/// they never match string values of the same key
+pub(crate) fn timestamp_metadata_prefix(key: &str) -> String {
+    format!("{key}\u{0}timestamp")
+}
+
+/// Strings are also stored lowercased in the string metadata index under a separate prefix,
+/// so that they can be matched case-insensitively
+pub(crate) fn case_insensitive_metadata_prefix(key: &str) -> String {
+    format!("{key}\u{0}lowercase")
+}
+
+// The string metadata index has an empty string for this key, at offset id 0 which no record
+// has, once every string in it is also stored lowercased. The segments flushed before the
+// strings were stored lowercased do not have it until they are backfilled by a compaction
+pub(crate) const CASE_INSENSITIVE_MARKER: &str = "\u{0}lowercase";
+
+/// Encodes a timestamp as a fixed width hex string whose lexicographic order is the
+/// chronological order, by flipping the sign bit of the microseconds
+pub(crate) fn encode_timestamp(micros: i64) -> String {
+    format!("{:016x}", (micros as u64) ^ (1 << 63))
+}
+
+pub(crate) fn decode_timestamp(encoded: &str) -> Option<i64> {
+    u64::from_str_radix(encoded, 16)
+        .ok()
+        .map(|bits| (bits ^ (1 << 63)) as i64)
+}
 
 #[derive(Clone)]
 pub struct MetadataSegmentWriter<'me> {
     pub(crate) full_text_index_writer: Option<FullTextIndexWriter>,
-    pub(crate) string_metadata_index_writer: Option<MetadataIndexWriter<'me>>,
-    pub(crate) bool_metadata_index_writer: Option<MetadataIndexWriter<'me>>,
-    pub(crate) f32_metadata_index_writer: Option<MetadataIndexWriter<'me>>,
-    pub(crate) u32_metadata_index_writer: Option<MetadataIndexWriter<'me>>,
+    pub(crate) string_metadata_index_writer: Option<BucketedMetadataIndexWriter<'me>>,
+    pub(crate) bool_metadata_index_writer: Option<BucketedMetadataIndexWriter<'me>>,
+    pub(crate) f32_metadata_index_writer: Option<BucketedMetadataIndexWriter<'me>>,
+    pub(crate) u32_metadata_index_writer: Option<BucketedMetadataIndexWriter<'me>>,
+    pub(crate) materialized_filter_writer: Option<BlockfileWriter>,
+    pub(crate) materialized_filters: Vec<MaterializedFilter>,
+    // This maps the key of each materialized filter to the offset ids that satisfy it
+    pub(crate) materialized_filter_offset_ids: Arc<Mutex<HashMap<String, RoaringBitmap>>>,
+    pub(crate) tokenizer_config: TokenizerConfig,
     pub(crate) id: SegmentUuid,
 }
 
@@ -79,6 +127,10 @@ pub enum MetadataSegmentError {
     LimitOffsetNotSupported,
     #[error("Could not query metadata index {0}")]
     MetadataIndexQueryError(#[from] MetadataIndexError),
+    #[error("Could not evaluate materialized filter {0}")]
+    MaterializedFilterError(Box<dyn ChromaError>),
+    #[error("Invalid tokenizer configuration: {0}")]
+    TokenizerConfigError(#[from] TokenizerConfigError),
 }
 
 impl ChromaError for MetadataSegmentError {
@@ -97,8 +149,283 @@ impl ChromaError for MetadataSegmentError {
             MetadataSegmentError::BlockfileWriteError => ErrorCodes::Internal,
             MetadataSegmentError::LimitOffsetNotSupported => ErrorCodes::Internal,
             MetadataSegmentError::MetadataIndexQueryError(_) => ErrorCodes::Internal,
+            MetadataSegmentError::MaterializedFilterError(e) => e.code(),
+            MetadataSegmentError::TokenizerConfigError(e) => e.code(),
+        }
+    }
+}
+
+/// Reads the normalization of the documents in the full-text index from the segment metadata
+pub(crate) fn tokenizer_config_from_segment(
+    segment: &Segment,
+) -> Result<TokenizerConfig, MetadataSegmentError> {
+    let metadata = match &segment.metadata {
+        Some(metadata) => metadata,
+        None => return Ok(TokenizerConfig::default()),
+    };
+
+    let stopwords = match metadata.get("fts:stopwords") {
+        Some(MetadataValue::Str(stopwords)) => TokenizerConfig::parse_stopwords(stopwords),
+        _ => HashSet::new(),
+    };
+    let stemmer = match metadata.get("fts:stemmer") {
+        Some(MetadataValue::Str(language)) => Some(TokenizerConfig::parse_stemmer(language)?),
+        _ => None,
+    };
+
+    Ok(TokenizerConfig {
+        lowercase: matches!(
+            metadata.get("fts:lowercase"),
+            Some(MetadataValue::Bool(true))
+        ),
+        stopwords,
+        stemmer,
+        cjk_bigrams: matches!(
+            metadata.get("fts:cjk_bigrams"),
+            Some(MetadataValue::Bool(true))
+        ),
+    })
+}
+
+/// The segment metadata key of the number of buckets that the metadata keys are spread over
+pub(crate) const METADATA_KEY_BUCKETS: &str = "metadata:key_buckets";
+
+/// Reads the number of buckets that the metadata keys of the segment are spread over from the
+/// segment metadata. Spreading the keys of a collection with many metadata keys over more
+/// buckets means that a compaction rewrites fewer posting lists of the keys that it does not
+/// update. A segment without the key keeps the buckets that it is flushed with
+pub(crate) fn metadata_key_buckets(segment: &Segment) -> usize {
+    match segment
+        .metadata
+        .as_ref()
+        .and_then(|metadata| metadata.get(METADATA_KEY_BUCKETS))
+    {
+        Some(MetadataValue::Int(buckets)) if *buckets > 0 => {
+            (*buckets as usize).min(MAX_METADATA_KEY_BUCKETS)
+        }
+        _ => segment
+            .file_path
+            .get(STRING_METADATA)
+            .map(Vec::len)
+            .filter(|buckets| *buckets > 0)
+            .unwrap_or(1),
+    }
+}
+
+/// Whether a metadata index of the segment is flushed with another number of buckets than the
+/// segment is set to, in which case its posting lists are moved over to new buckets
+fn needs_rebucketing(segment: &Segment, file: &str, num_buckets: usize) -> bool {
+    matches!(segment.file_path.get(file), Some(paths) if !paths.is_empty() && paths.len() != num_buckets)
+}
+
+fn blockfile_paths(ids: Vec<Uuid>) -> Vec<String> {
+    ids.iter().map(ToString::to_string).collect()
+}
+
+/// Forks the blockfiles of the buckets of a metadata index of the segment, or creates new
+/// ones if the segment has no such index yet
+async fn open_metadata_index_writer<'me, K>(
+    segment: &Segment,
+    blockfile_provider: &BlockfileProvider,
+    file: &str,
+    num_buckets: usize,
+    new_writer: fn(BlockfileWriter, Option<MetadataIndexReader<'me>>) -> MetadataIndexWriter<'me>,
+    new_reader: fn(BlockfileReader<'me, K, RoaringBitmap>) -> MetadataIndexReader<'me>,
+) -> Result<BucketedMetadataIndexWriter<'me>, MetadataSegmentError>
+where
+    K: Key
+        + Into<KeyWrapper>
+        + TryFrom<&'me KeyWrapper, Error = InvalidKeyConversion>
+        + ArrowReadableKey<'me>
+        + ArrowWriteableKey
+        + Sync
+        + 'me,
+{
+    let mut buckets = Vec::with_capacity(num_buckets);
+    match segment.file_path.get(file) {
+        Some(paths) if paths.is_empty() => return Err(MetadataSegmentError::EmptyPathVector),
+        Some(paths) if paths.len() == num_buckets => {
+            for path in paths {
+                let uuid = Uuid::parse_str(path)
+                    .map_err(|_| MetadataSegmentError::UuidParseError(path.to_string()))?;
+                let writer = blockfile_provider
+                    .write::<K, RoaringBitmap>(BlockfileWriterOptions::new().fork(uuid))
+                    .await
+                    .map_err(|e| MetadataSegmentError::BlockfileError(*e))?;
+                let reader = blockfile_provider
+                    .read::<K, RoaringBitmap>(&uuid)
+                    .await
+                    .map_err(|e| MetadataSegmentError::BlockfileOpenError(*e))?;
+                buckets.push((new_writer(writer, Some(new_reader(reader))), Some(uuid)));
+            }
+        }
+        // A new index, or one that is rebucketed from the blockfiles that it is flushed with
+        _ => {
+            for _ in 0..num_buckets {
+                let writer = blockfile_provider
+                    .write::<K, RoaringBitmap>(BlockfileWriterOptions::default())
+                    .await
+                    .map_err(|e| MetadataSegmentError::BlockfileError(*e))?;
+                buckets.push((new_writer(writer, None), None));
+            }
+        }
+    }
+    Ok(BucketedMetadataIndexWriter::new(buckets))
+}
+
+/// Opens the blockfiles of the buckets of a metadata index of the segment, if it has the index
+async fn open_metadata_index_reader<'me, K>(
+    segment: &Segment,
+    blockfile_provider: &BlockfileProvider,
+    file: &str,
+    new_reader: fn(BlockfileReader<'me, K, RoaringBitmap>) -> MetadataIndexReader<'me>,
+) -> Result<Option<BucketedMetadataIndexReader<'me>>, MetadataSegmentError>
+where
+    K: Key
+        + Into<KeyWrapper>
+        + TryFrom<&'me KeyWrapper, Error = InvalidKeyConversion>
+        + ArrowReadableKey<'me>
+        + Sync
+        + 'me,
+{
+    let paths = match segment.file_path.get(file) {
+        Some(paths) if !paths.is_empty() => paths,
+        _ => return Ok(None),
+    };
+    let mut buckets = Vec::with_capacity(paths.len());
+    for path in paths {
+        let uuid = Uuid::parse_str(path)
+            .map_err(|_| MetadataSegmentError::UuidParseError(path.to_string()))?;
+        let reader = blockfile_provider
+            .read::<K, RoaringBitmap>(&uuid)
+            .await
+            .map_err(|e| MetadataSegmentError::BlockfileOpenError(*e))?;
+        buckets.push(new_reader(reader));
+    }
+    Ok(Some(BucketedMetadataIndexReader::new(buckets)))
+}
+
+/// Moves the posting lists of a metadata index of the segment that is flushed with another
+/// number of buckets over to the buckets of the writer. The index is written in full by this
+/// compaction, and only the buckets of the updated keys by the compactions after it
+async fn rebucket_metadata_index(
+    segment: &Segment,
+    blockfile_provider: &BlockfileProvider,
+    file: &str,
+    writer: &BucketedMetadataIndexWriter<'_>,
+) -> Result<(), MetadataSegmentError> {
+    if !needs_rebucketing(segment, file, writer.num_buckets()) {
+        return Ok(());
+    }
+    let reader = match file {
+        STRING_METADATA => {
+            open_metadata_index_reader(
+                segment,
+                blockfile_provider,
+                file,
+                MetadataIndexReader::new_string,
+            )
+            .await?
+        }
+        BOOL_METADATA => {
+            open_metadata_index_reader(
+                segment,
+                blockfile_provider,
+                file,
+                MetadataIndexReader::new_bool,
+            )
+            .await?
+        }
+        F32_METADATA => {
+            open_metadata_index_reader(
+                segment,
+                blockfile_provider,
+                file,
+                MetadataIndexReader::new_f32,
+            )
+            .await?
+        }
+        U32_METADATA => {
+            open_metadata_index_reader(
+                segment,
+                blockfile_provider,
+                file,
+                MetadataIndexReader::new_u32,
+            )
+            .await?
+        }
+        _ => None,
+    };
+    if let Some(reader) = reader {
+        tracing::info!(
+            "Moving the {} of metadata segment {} from {} to {} buckets",
+            file,
+            segment.id,
+            reader.num_buckets(),
+            writer.num_buckets()
+        );
+        let mut posting_lists = reader.posting_lists_stream();
+        while let Some((key, value, offset_ids)) = posting_lists.try_next().await? {
+            for offset_id in offset_ids {
+                writer.set(key, value.clone(), offset_id).await?;
+            }
         }
     }
+    Ok(())
+}
+
+/// Whether every string in the string metadata index is also stored lowercased
+async fn is_case_insensitive_indexed(
+    reader: &BucketedMetadataIndexReader<'_>,
+) -> Result<bool, MetadataIndexError> {
+    let marker = KeyWrapper::String(String::new());
+    Ok(reader
+        .get(CASE_INSENSITIVE_MARKER, &marker)
+        .await?
+        .contains(0))
+}
+
+/// Stores lowercased the strings of a segment that was flushed before they were, so that the
+/// segment is matched case-insensitively from the index after this compaction
+async fn backfill_case_insensitive_metadata(
+    segment: &Segment,
+    blockfile_provider: &BlockfileProvider,
+    writer: &BucketedMetadataIndexWriter<'_>,
+) -> Result<(), MetadataSegmentError> {
+    if let Some(reader) = open_metadata_index_reader(
+        segment,
+        blockfile_provider,
+        STRING_METADATA,
+        MetadataIndexReader::new_string,
+    )
+    .await?
+    {
+        if is_case_insensitive_indexed(&reader).await? {
+            return Ok(());
+        }
+        tracing::info!(
+            "Backfilling the lowercased strings of metadata segment {}",
+            segment.id
+        );
+        let mut posting_lists = reader.posting_lists_stream();
+        while let Some((key, value, offset_ids)) = posting_lists.try_next().await? {
+            // The timestamps and the lowercased strings are stored under a key with a suffix
+            if key.contains('\u{0}') {
+                continue;
+            }
+            if let KeyWrapper::String(value) = value {
+                let lowercase_key = case_insensitive_metadata_prefix(key);
+                let lowercase = value.to_lowercase();
+                for offset_id in offset_ids {
+                    writer
+                        .set(&lowercase_key, lowercase.as_str(), offset_id)
+                        .await?;
+                }
+            }
+        }
+    }
+    writer.set(CASE_INSENSITIVE_MARKER, "", 0).await?;
+    Ok(())
 }
 
 impl<'me> MetadataSegmentWriter<'me> {
@@ -109,7 +436,7 @@ impl<'me> MetadataSegmentWriter<'me> {
         if segment.r#type != SegmentType::BlockfileMetadata {
             return Err(MetadataSegmentError::InvalidSegmentType);
         }
-        let pls_writer = match segment.file_path.get(FULL_TEXT_PLS) {
+        let (pls_writer, corpus_statistics) = match segment.file_path.get(FULL_TEXT_PLS) {
             Some(pls_path) => match pls_path.first() {
                 Some(pls_uuid) => {
                     let pls_uuid = match Uuid::parse_str(pls_uuid) {
@@ -119,201 +446,132 @@ impl<'me> MetadataSegmentWriter<'me> {
                         }
                     };
 
-                    blockfile_provider
+                    // The corpus statistics of the forked posting lists are updated by the writer
+                    let pls_reader = blockfile_provider
+                        .read::<u32, &[u32]>(&pls_uuid)
+                        .await
+                        .map_err(|e| MetadataSegmentError::BlockfileOpenError(*e))?;
+                    let corpus_statistics = FullTextIndexReader::new(
+                        pls_reader,
+                        NgramTokenizer::new(3, 3, false).unwrap(),
+                    )
+                    .corpus_statistics()
+                    .await?;
+
+                    let pls_writer = blockfile_provider
                         .write::<u32, Vec<u32>>(
                             BlockfileWriterOptions::new()
                                 .fork(pls_uuid)
-                                .ordered_mutations(),
+                                .ordered_mutations()
+                                .stream_blocks(),
                         )
                         .await
-                        .map_err(|e| MetadataSegmentError::BlockfileError(*e))?
+                        .map_err(|e| MetadataSegmentError::BlockfileError(*e))?;
+                    (pls_writer, corpus_statistics)
                 }
                 None => return Err(MetadataSegmentError::EmptyPathVector),
             },
             None => match blockfile_provider
-                .write::<u32, Vec<u32>>(BlockfileWriterOptions::new().ordered_mutations())
+                .write::<u32, Vec<u32>>(
+                    BlockfileWriterOptions::new()
+                        .ordered_mutations()
+                        .stream_blocks(),
+                )
                 .await
             {
-                Ok(writer) => writer,
+                Ok(writer) => (writer, CorpusStatistics::default()),
                 Err(e) => return Err(MetadataSegmentError::BlockfileError(*e)),
             },
         };
 
+        let tokenizer_config = tokenizer_config_from_segment(segment)?;
         let full_text_writer_tokenizer = NgramTokenizer::new(3, 3, false).unwrap();
         let full_text_index_writer =
-            FullTextIndexWriter::new(pls_writer, full_text_writer_tokenizer);
-
-        let (string_metadata_writer, string_metadata_index_reader) =
-            match segment.file_path.get(STRING_METADATA) {
-                Some(string_metadata_path) => match string_metadata_path.first() {
-                    Some(string_metadata_uuid) => {
-                        let string_metadata_uuid = match Uuid::parse_str(string_metadata_uuid) {
-                            Ok(uuid) => uuid,
-                            Err(_) => {
-                                return Err(MetadataSegmentError::UuidParseError(
-                                    string_metadata_uuid.to_string(),
-                                ))
-                            }
-                        };
-                        let string_metadata_writer = match blockfile_provider
-                            .write::<&str, RoaringBitmap>(
-                                BlockfileWriterOptions::new().fork(string_metadata_uuid),
-                            )
-                            .await
-                        {
-                            Ok(writer) => writer,
-                            Err(e) => return Err(MetadataSegmentError::BlockfileError(*e)),
-                        };
-                        let string_metadata_index_reader = match blockfile_provider
-                            .read::<&str, RoaringBitmap>(&string_metadata_uuid)
-                            .await
-                        {
-                            Ok(reader) => MetadataIndexReader::new_string(reader),
-                            Err(e) => return Err(MetadataSegmentError::BlockfileOpenError(*e)),
-                        };
-                        (string_metadata_writer, Some(string_metadata_index_reader))
-                    }
-                    None => return Err(MetadataSegmentError::EmptyPathVector),
-                },
-                None => match blockfile_provider
-                    .write::<&str, RoaringBitmap>(BlockfileWriterOptions::new())
-                    .await
-                {
-                    Ok(writer) => (writer, None),
-                    Err(e) => return Err(MetadataSegmentError::BlockfileError(*e)),
-                },
-            };
-        let string_metadata_index_writer =
-            MetadataIndexWriter::new_string(string_metadata_writer, string_metadata_index_reader);
-
-        let (bool_metadata_writer, bool_metadata_index_reader) =
-            match segment.file_path.get(BOOL_METADATA) {
-                Some(bool_metadata_path) => match bool_metadata_path.first() {
-                    Some(bool_metadata_uuid) => {
-                        let bool_metadata_uuid = match Uuid::parse_str(bool_metadata_uuid) {
-                            Ok(uuid) => uuid,
-                            Err(_) => {
-                                return Err(MetadataSegmentError::UuidParseError(
-                                    bool_metadata_uuid.to_string(),
-                                ))
-                            }
-                        };
-                        let bool_metadata_writer = match blockfile_provider
-                            .write::<bool, RoaringBitmap>(
-                                BlockfileWriterOptions::new().fork(bool_metadata_uuid),
-                            )
-                            .await
-                        {
-                            Ok(writer) => writer,
-                            Err(e) => return Err(MetadataSegmentError::BlockfileError(*e)),
-                        };
-                        let bool_metadata_index_writer = match blockfile_provider
-                            .read::<bool, RoaringBitmap>(&bool_metadata_uuid)
-                            .await
-                        {
-                            Ok(reader) => MetadataIndexReader::new_bool(reader),
-                            Err(e) => return Err(MetadataSegmentError::BlockfileOpenError(*e)),
-                        };
-                        (bool_metadata_writer, Some(bool_metadata_index_writer))
-                    }
-                    None => return Err(MetadataSegmentError::EmptyPathVector),
-                },
-                None => match blockfile_provider
-                    .write::<bool, RoaringBitmap>(BlockfileWriterOptions::default())
-                    .await
-                {
-                    Ok(writer) => (writer, None),
-                    Err(e) => return Err(MetadataSegmentError::BlockfileError(*e)),
-                },
-            };
-        let bool_metadata_index_writer =
-            MetadataIndexWriter::new_bool(bool_metadata_writer, bool_metadata_index_reader);
-
-        let (f32_metadata_writer, f32_metadata_index_reader) =
-            match segment.file_path.get(F32_METADATA) {
-                Some(f32_metadata_path) => match f32_metadata_path.first() {
-                    Some(f32_metadata_uuid) => {
-                        let f32_metadata_uuid = match Uuid::parse_str(f32_metadata_uuid) {
-                            Ok(uuid) => uuid,
-                            Err(_) => {
-                                return Err(MetadataSegmentError::UuidParseError(
-                                    f32_metadata_uuid.to_string(),
-                                ))
-                            }
-                        };
-                        let f32_metadata_writer = match blockfile_provider
-                            .write::<f32, RoaringBitmap>(
-                                BlockfileWriterOptions::new().fork(f32_metadata_uuid),
-                            )
-                            .await
-                        {
-                            Ok(writer) => writer,
-                            Err(e) => return Err(MetadataSegmentError::BlockfileError(*e)),
-                        };
-                        let f32_metadata_index_reader = match blockfile_provider
-                            .read::<f32, RoaringBitmap>(&f32_metadata_uuid)
-                            .await
-                        {
-                            Ok(reader) => MetadataIndexReader::new_f32(reader),
-                            Err(e) => return Err(MetadataSegmentError::BlockfileOpenError(*e)),
-                        };
-                        (f32_metadata_writer, Some(f32_metadata_index_reader))
-                    }
-                    None => return Err(MetadataSegmentError::EmptyPathVector),
-                },
-                None => match blockfile_provider
-                    .write::<f32, RoaringBitmap>(BlockfileWriterOptions::default())
-                    .await
-                {
-                    Ok(writer) => (writer, None),
-                    Err(e) => return Err(MetadataSegmentError::BlockfileError(*e)),
-                },
-            };
-        let f32_metadata_index_writer =
-            MetadataIndexWriter::new_f32(f32_metadata_writer, f32_metadata_index_reader);
-
-        let (u32_metadata_writer, u32_metadata_index_reader) =
-            match segment.file_path.get(U32_METADATA) {
-                Some(u32_metadata_path) => match u32_metadata_path.first() {
-                    Some(u32_metadata_uuid) => {
-                        let u32_metadata_uuid = match Uuid::parse_str(u32_metadata_uuid) {
-                            Ok(uuid) => uuid,
-                            Err(_) => {
-                                return Err(MetadataSegmentError::UuidParseError(
-                                    u32_metadata_uuid.to_string(),
-                                ))
-                            }
-                        };
-                        let u32_metadata_writer = match blockfile_provider
-                            .write::<u32, RoaringBitmap>(
-                                BlockfileWriterOptions::new().fork(u32_metadata_uuid),
-                            )
-                            .await
-                        {
-                            Ok(writer) => writer,
-                            Err(e) => return Err(MetadataSegmentError::BlockfileError(*e)),
-                        };
-                        let u32_metadata_index_reader = match blockfile_provider
-                            .read::<u32, RoaringBitmap>(&u32_metadata_uuid)
-                            .await
-                        {
-                            Ok(reader) => MetadataIndexReader::new_u32(reader),
-                            Err(e) => return Err(MetadataSegmentError::BlockfileOpenError(*e)),
-                        };
-                        (u32_metadata_writer, Some(u32_metadata_index_reader))
-                    }
-                    None => return Err(MetadataSegmentError::EmptyPathVector),
-                },
-                None => match blockfile_provider
-                    .write::<u32, RoaringBitmap>(BlockfileWriterOptions::default())
-                    .await
-                {
-                    Ok(writer) => (writer, None),
-                    Err(e) => return Err(MetadataSegmentError::BlockfileError(*e)),
-                },
-            };
-        let u32_metadata_index_writer =
-            MetadataIndexWriter::new_u32(u32_metadata_writer, u32_metadata_index_reader);
+            FullTextIndexWriter::new(pls_writer, full_text_writer_tokenizer)
+                .with_tokenizer_config(tokenizer_config.clone())
+                .with_corpus_statistics(Some(corpus_statistics));
+
+        let num_buckets = metadata_key_buckets(segment);
+        let string_metadata_index_writer = open_metadata_index_writer(
+            segment,
+            blockfile_provider,
+            STRING_METADATA,
+            num_buckets,
+            MetadataIndexWriter::new_string,
+            MetadataIndexReader::new_string,
+        )
+        .await?;
+        rebucket_metadata_index(
+            segment,
+            blockfile_provider,
+            STRING_METADATA,
+            &string_metadata_index_writer,
+        )
+        .await?;
+        backfill_case_insensitive_metadata(
+            segment,
+            blockfile_provider,
+            &string_metadata_index_writer,
+        )
+        .await?;
+        let bool_metadata_index_writer = open_metadata_index_writer(
+            segment,
+            blockfile_provider,
+            BOOL_METADATA,
+            num_buckets,
+            MetadataIndexWriter::new_bool,
+            MetadataIndexReader::new_bool,
+        )
+        .await?;
+        rebucket_metadata_index(
+            segment,
+            blockfile_provider,
+            BOOL_METADATA,
+            &bool_metadata_index_writer,
+        )
+        .await?;
+        let f32_metadata_index_writer = open_metadata_index_writer(
+            segment,
+            blockfile_provider,
+            F32_METADATA,
+            num_buckets,
+            MetadataIndexWriter::new_f32,
+            MetadataIndexReader::new_f32,
+        )
+        .await?;
+        rebucket_metadata_index(
+            segment,
+            blockfile_provider,
+            F32_METADATA,
+            &f32_metadata_index_writer,
+        )
+        .await?;
+        let u32_metadata_index_writer = open_metadata_index_writer(
+            segment,
+            blockfile_provider,
+            U32_METADATA,
+            num_buckets,
+            MetadataIndexWriter::new_u32,
+            MetadataIndexReader::new_u32,
+        )
+        .await?;
+        rebucket_metadata_index(
+            segment,
+            blockfile_provider,
+            U32_METADATA,
+            &u32_metadata_index_writer,
+        )
+        .await?;
+
+        // The materialized filters are rewritten in full at every compaction,
+        // so that the filters no longer declared for the collection are dropped
+        let materialized_filter_writer = match blockfile_provider
+            .write::<&str, RoaringBitmap>(BlockfileWriterOptions::default())
+            .await
+        {
+            Ok(writer) => writer,
+            Err(e) => return Err(MetadataSegmentError::BlockfileError(*e)),
+        };
 
         Ok(MetadataSegmentWriter {
             full_text_index_writer: Some(full_text_index_writer),
@@ -321,10 +579,100 @@ impl<'me> MetadataSegmentWriter<'me> {
             bool_metadata_index_writer: Some(bool_metadata_index_writer),
             f32_metadata_index_writer: Some(f32_metadata_index_writer),
             u32_metadata_index_writer: Some(u32_metadata_index_writer),
+            materialized_filter_writer: Some(materialized_filter_writer),
+            materialized_filters: Vec::new(),
+            materialized_filter_offset_ids: Arc::new(Mutex::new(HashMap::new())),
+            tokenizer_config,
             id: segment.id,
         })
     }
 
+    /// Sets the materialized filters to maintain in this segment
+    ///
+    /// The offset ids of each filter are loaded from the segment if they are already
+    /// materialized, otherwise they are computed from the metadata indices of the segment.
+    /// This should be called before any materialized log is applied to the writer.
+    pub(crate) async fn set_materialized_filters(
+        &mut self,
+        segment: &Segment,
+        blockfile_provider: &BlockfileProvider,
+        materialized_filters: Vec<MaterializedFilter>,
+    ) -> Result<(), MetadataSegmentError> {
+        let reader = MetadataSegmentReader::from_segment(segment, blockfile_provider).await?;
+        let metadata_provider = MetadataProvider::from_metadata_segment_reader(&reader, None);
+
+        let mut materialized_filter_offset_ids = HashMap::new();
+        for filter in &materialized_filters {
+            let offset_ids = match reader.get_materialized_filter_by_key(&filter.key).await? {
+                Some(offset_ids) => offset_ids,
+                None => match filter
+                    .where_clause
+                    .eval(&metadata_provider)
+                    .await
+                    .map_err(|e| MetadataSegmentError::MaterializedFilterError(Box::new(e)))?
+                {
+                    SignedRoaringBitmap::Include(offset_ids) => offset_ids,
+                    // This should never be the case for equality predicates
+                    SignedRoaringBitmap::Exclude(_) => {
+                        tracing::warn!("Unable to materialize filter {}", filter.key);
+                        continue;
+                    }
+                },
+            };
+            materialized_filter_offset_ids.insert(filter.key.clone(), offset_ids);
+        }
+
+        self.materialized_filters = materialized_filters
+            .into_iter()
+            .filter(|filter| materialized_filter_offset_ids.contains_key(&filter.key))
+            .collect();
+        self.materialized_filter_offset_ids = Arc::new(Mutex::new(materialized_filter_offset_ids));
+        Ok(())
+    }
+
+    // Updates the offset ids of the materialized filters for the records in the chunk
+    async fn apply_materialized_filters(
+        &self,
+        records: &Chunk<MaterializedLogRecord<'_>>,
+    ) -> Result<(), ApplyMaterializedLogError> {
+        if self.materialized_filters.is_empty() {
+            return Ok(());
+        }
+
+        let metadata_log_reader = MetadataLogReader::new(records, None, &self.tokenizer_config);
+        let metadata_provider = MetadataProvider::from_metadata_log_reader(&metadata_log_reader);
+        let mut updated_offset_ids = RoaringBitmap::new();
+        let mut active_offset_ids = RoaringBitmap::new();
+        for (record, _) in records.iter() {
+            updated_offset_ids.insert(record.offset_id);
+            if !matches!(
+                record.final_operation,
+                MaterializedLogOperation::DeleteExisting
+            ) {
+                active_offset_ids.insert(record.offset_id);
+            }
+        }
+
+        for filter in &self.materialized_filters {
+            let matched_offset_ids = match filter
+                .where_clause
+                .eval(&metadata_provider)
+                .await
+                .map_err(|e| ApplyMaterializedLogError::MaterializedFilter(Box::new(e)))?
+            {
+                SignedRoaringBitmap::Include(offset_ids) => offset_ids & &active_offset_ids,
+                SignedRoaringBitmap::Exclude(offset_ids) => &active_offset_ids - offset_ids,
+            };
+            let mut materialized_filter_offset_ids = self.materialized_filter_offset_ids.lock();
+            let offset_ids = materialized_filter_offset_ids
+                .entry(filter.key.clone())
+                .or_default();
+            *offset_ids -= &updated_offset_ids;
+            *offset_ids |= matched_offset_ids;
+        }
+        Ok(())
+    }
+
     pub async fn write_to_blockfiles(&mut self) -> Result<(), MetadataSegmentError> {
         let mut full_text_index_writer = self
             .full_text_index_writer
@@ -381,6 +729,21 @@ impl<'me> MetadataSegmentWriter<'me> {
             Err(_) => return Err(MetadataSegmentError::BlockfileWriteError),
         }
 
+        let materialized_filter_writer = self
+            .materialized_filter_writer
+            .as_ref()
+            .ok_or_else(|| MetadataSegmentError::NoWriter)?;
+        let materialized_filter_offset_ids = self.materialized_filter_offset_ids.lock().clone();
+        for (key, offset_ids) in materialized_filter_offset_ids {
+            match materialized_filter_writer
+                .set("", key.as_str(), offset_ids)
+                .await
+            {
+                Ok(_) => {}
+                Err(_) => return Err(MetadataSegmentError::BlockfileWriteError),
+            }
+        }
+
         Ok(())
     }
 
@@ -389,12 +752,32 @@ impl<'me> MetadataSegmentWriter<'me> {
         prefix: &str,
         key: &MetadataValue,
         offset_id: u32,
+    ) -> Result<(), MetadataIndexError> {
+        // The values of an object are indexed under their paths, and each element of a list
+        // is indexed separately
+        for (path, value) in key.flatten(prefix) {
+            for element in value.elements() {
+                self.set_metadata_element(&path, element, offset_id).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn set_metadata_element(
+        &self,
+        prefix: &str,
+        key: &MetadataValue,
+        offset_id: u32,
     ) -> Result<(), MetadataIndexError> {
         match key {
             MetadataValue::Str(v) => {
                 match &self.string_metadata_index_writer {
                     Some(writer) => {
-                        match writer.set(prefix, v.as_str(), offset_id).await {
+                        let result = match writer.set(prefix, v.as_str(), offset_id).await {
+                            Ok(()) => writer.set(&case_insensitive_metadata_prefix(prefix), v.to_lowercase().as_str(), offset_id).await,
+                            Err(e) => Err(e),
+                        };
+                        match result {
                             Ok(()) => Ok(()),
                             Err(e) => {
                                 tracing::error!("Error inserting into str metadata index writer {:?}", e);
@@ -447,6 +830,22 @@ impl<'me> MetadataSegmentWriter<'me> {
                     None => panic!("Invariant violation. bool metadata index writer should be set for metadata segment"),
                 }
             }
+            MetadataValue::Timestamp(v) => {
+                match &self.string_metadata_index_writer {
+                    Some(writer) => {
+                        match writer.set(&timestamp_metadata_prefix(prefix), encode_timestamp(*v).as_str(), offset_id).await {
+                            Ok(()) => Ok(()),
+                            Err(e) => {
+                                tracing::error!("Error inserting into timestamp metadata index writer {:?}", e);
+                                Err(e)
+                            }
+                        }
+                    }
+                    None => panic!("Invariant violation. String metadata index writer should be set for metadata segment"),
+                }
+            }
+            MetadataValue::List(_) => Err(MetadataIndexError::NestedList),
+            MetadataValue::Object(_) => panic!("Invariant violation. Metadata objects should be flattened"),
         }
     }
 
@@ -455,12 +854,31 @@ impl<'me> MetadataSegmentWriter<'me> {
         prefix: &str,
         key: &MetadataValue,
         offset_id: u32,
+    ) -> Result<(), MetadataIndexError> {
+        for (path, value) in key.flatten(prefix) {
+            for element in value.elements() {
+                self.delete_metadata_element(&path, element, offset_id)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn delete_metadata_element(
+        &self,
+        prefix: &str,
+        key: &MetadataValue,
+        offset_id: u32,
     ) -> Result<(), MetadataIndexError> {
         match key {
             MetadataValue::Str(v) => {
                 match &self.string_metadata_index_writer {
                     Some(writer) => {
-                        match writer.delete(prefix, v.as_str(), offset_id).await {
+                        let result = match writer.delete(prefix, v.as_str(), offset_id).await {
+                            Ok(()) => writer.delete(&case_insensitive_metadata_prefix(prefix), v.to_lowercase().as_str(), offset_id).await,
+                            Err(e) => Err(e),
+                        };
+                        match result {
                             Ok(()) => Ok(()),
                             Err(e) => {
                                 tracing::error!("Error deleting from str metadata index writer {:?}", e);
@@ -513,6 +931,22 @@ impl<'me> MetadataSegmentWriter<'me> {
                     None => panic!("Invariant violation. bool metadata index writer should be set for metadata segment"),
                 }
             }
+            MetadataValue::Timestamp(v) => {
+                match &self.string_metadata_index_writer {
+                    Some(writer) => {
+                        match writer.delete(&timestamp_metadata_prefix(prefix), encode_timestamp(*v).as_str(), offset_id).await {
+                            Ok(()) => Ok(()),
+                            Err(e) => {
+                                tracing::error!("Error deleting from timestamp metadata index writer {:?}", e);
+                                Err(e)
+                            }
+                        }
+                    }
+                    None => panic!("Invariant violation. String metadata index writer should be set for metadata segment"),
+                }
+            }
+            MetadataValue::List(_) => Err(MetadataIndexError::NestedList),
+            MetadataValue::Object(_) => panic!("Invariant violation. Metadata objects should be flattened"),
         }
     }
 
@@ -573,6 +1007,8 @@ impl<'log_records> SegmentWriter<'log_records> for MetadataSegmentWriter<'_> {
             .handle_batch(full_text_writer_batch)
             .map_err(ApplyMaterializedLogError::FullTextIndex)?;
 
+        self.apply_materialized_filters(&records).await?;
+
         for record in records.iter() {
             count += 1;
             let segment_offset_id = record.0.offset_id;
@@ -584,6 +1020,9 @@ impl<'log_records> SegmentWriter<'log_records> for MetadataSegmentWriter<'_> {
                             for (key, value) in metadata.iter() {
                                 match self.set_metadata(key, value, segment_offset_id).await {
                                     Ok(()) => {}
+                                    Err(MetadataIndexError::NestedList) => {
+                                        return Err(ApplyMaterializedLogError::NestedMetadataList);
+                                    }
                                     Err(_) => {
                                         return Err(ApplyMaterializedLogError::BlockfileSet);
                                     }
@@ -599,6 +1038,9 @@ impl<'log_records> SegmentWriter<'log_records> for MetadataSegmentWriter<'_> {
                                     match self.delete_metadata(key, value, segment_offset_id).await
                                     {
                                         Ok(()) => {}
+                                        Err(MetadataIndexError::NestedList) => {
+                                            return Err(ApplyMaterializedLogError::NestedMetadataList);
+                                        }
                                         Err(_) => {
                                             return Err(
                                                 ApplyMaterializedLogError::BlockfileDelete,
@@ -620,6 +1062,11 @@ impl<'log_records> SegmentWriter<'log_records> for MetadataSegmentWriter<'_> {
                             .await
                         {
                             Ok(()) => {}
+                            Err(MetadataSegmentError::MetadataIndexQueryError(
+                                MetadataIndexError::NestedList,
+                            )) => {
+                                return Err(ApplyMaterializedLogError::NestedMetadataList);
+                            }
                             Err(_) => {
                                 return Err(ApplyMaterializedLogError::BlockfileUpdate);
                             }
@@ -632,6 +1079,9 @@ impl<'log_records> SegmentWriter<'log_records> for MetadataSegmentWriter<'_> {
                             .await
                         {
                             Ok(()) => {}
+                            Err(MetadataIndexError::NestedList) => {
+                                return Err(ApplyMaterializedLogError::NestedMetadataList);
+                            }
                             Err(_) => {
                                 return Err(ApplyMaterializedLogError::BlockfileSet);
                             }
@@ -644,6 +1094,9 @@ impl<'log_records> SegmentWriter<'log_records> for MetadataSegmentWriter<'_> {
                             .await
                         {
                             Ok(()) => {}
+                            Err(MetadataIndexError::NestedList) => {
+                                return Err(ApplyMaterializedLogError::NestedMetadataList);
+                            }
                             Err(_) => {
                                 return Err(ApplyMaterializedLogError::BlockfileDelete);
                             }
@@ -660,6 +1113,9 @@ impl<'log_records> SegmentWriter<'log_records> for MetadataSegmentWriter<'_> {
                                         match self.delete_metadata(key, value, segment_offset_id).await
                                         {
                                             Ok(()) => {}
+                                            Err(MetadataIndexError::NestedList) => {
+                                                return Err(ApplyMaterializedLogError::NestedMetadataList);
+                                            }
                                             Err(_) => {
                                                 return Err(
                                                     ApplyMaterializedLogError::BlockfileDelete,
@@ -677,6 +1133,9 @@ impl<'log_records> SegmentWriter<'log_records> for MetadataSegmentWriter<'_> {
                             for (key, value) in metadata.iter() {
                                 match self.set_metadata(key, value, segment_offset_id).await {
                                     Ok(()) => {}
+                                    Err(MetadataIndexError::NestedList) => {
+                                        return Err(ApplyMaterializedLogError::NestedMetadataList);
+                                    }
                                     Err(_) => {
                                         return Err(ApplyMaterializedLogError::BlockfileSet);
                                     }
@@ -733,32 +1192,40 @@ impl<'log_records> SegmentWriter<'log_records> for MetadataSegmentWriter<'_> {
             None => return Err(Box::new(MetadataSegmentError::NoWriter)),
         };
 
+        let materialized_filter_flusher = match self.materialized_filter_writer {
+            Some(flusher) => flusher.commit::<&str, RoaringBitmap>().await?,
+            None => return Err(Box::new(MetadataSegmentError::NoWriter)),
+        };
+
         Ok(MetadataSegmentFlusher {
             full_text_index_flusher: full_text_flusher,
             string_metadata_index_flusher: string_metadata_flusher,
             bool_metadata_index_flusher: bool_metadata_flusher,
             f32_metadata_index_flusher: f32_metadata_flusher,
             u32_metadata_index_flusher: u32_metadata_flusher,
+            materialized_filter_flusher,
         })
     }
 }
 
 pub(crate) struct MetadataSegmentFlusher {
     pub(crate) full_text_index_flusher: FullTextIndexFlusher,
-    pub(crate) string_metadata_index_flusher: MetadataIndexFlusher,
-    pub(crate) bool_metadata_index_flusher: MetadataIndexFlusher,
-    pub(crate) f32_metadata_index_flusher: MetadataIndexFlusher,
-    pub(crate) u32_metadata_index_flusher: MetadataIndexFlusher,
+    pub(crate) string_metadata_index_flusher: BucketedMetadataIndexFlusher,
+    pub(crate) bool_metadata_index_flusher: BucketedMetadataIndexFlusher,
+    pub(crate) f32_metadata_index_flusher: BucketedMetadataIndexFlusher,
+    pub(crate) u32_metadata_index_flusher: BucketedMetadataIndexFlusher,
+    pub(crate) materialized_filter_flusher: BlockfileFlusher,
 }
 
 #[async_trait]
 impl SegmentFlusher for MetadataSegmentFlusher {
     async fn flush(self) -> Result<HashMap<String, Vec<String>>, Box<dyn ChromaError>> {
         let full_text_pls_id = self.full_text_index_flusher.pls_id();
-        let string_metadata_id = self.string_metadata_index_flusher.id();
-        let bool_metadata_id = self.bool_metadata_index_flusher.id();
-        let f32_metadata_id = self.f32_metadata_index_flusher.id();
-        let u32_metadata_id = self.u32_metadata_index_flusher.id();
+        let string_metadata_ids = self.string_metadata_index_flusher.ids();
+        let bool_metadata_ids = self.bool_metadata_index_flusher.ids();
+        let f32_metadata_ids = self.f32_metadata_index_flusher.ids();
+        let u32_metadata_ids = self.u32_metadata_index_flusher.ids();
+        let materialized_filter_id = self.materialized_filter_flusher.id();
 
         let mut flushed = HashMap::new();
 
@@ -777,20 +1244,20 @@ impl SegmentFlusher for MetadataSegmentFlusher {
         }
         flushed.insert(
             BOOL_METADATA.to_string(),
-            vec![bool_metadata_id.to_string()],
+            blockfile_paths(bool_metadata_ids),
         );
 
         match self.f32_metadata_index_flusher.flush().await {
             Ok(_) => {}
             Err(e) => return Err(Box::new(e)),
         }
-        flushed.insert(F32_METADATA.to_string(), vec![f32_metadata_id.to_string()]);
+        flushed.insert(F32_METADATA.to_string(), blockfile_paths(f32_metadata_ids));
 
         match self.u32_metadata_index_flusher.flush().await {
             Ok(_) => {}
             Err(e) => return Err(Box::new(e)),
         }
-        flushed.insert(U32_METADATA.to_string(), vec![u32_metadata_id.to_string()]);
+        flushed.insert(U32_METADATA.to_string(), blockfile_paths(u32_metadata_ids));
 
         match self.string_metadata_index_flusher.flush().await {
             Ok(_) => {}
@@ -798,19 +1265,51 @@ impl SegmentFlusher for MetadataSegmentFlusher {
         }
         flushed.insert(
             STRING_METADATA.to_string(),
-            vec![string_metadata_id.to_string()],
+            blockfile_paths(string_metadata_ids),
+        );
+
+        self.materialized_filter_flusher
+            .flush::<&str, RoaringBitmap>()
+            .await?;
+        flushed.insert(
+            MATERIALIZED_FILTERS.to_string(),
+            vec![materialized_filter_id.to_string()],
         );
 
         Ok(flushed)
     }
+
+    fn metadata(&self) -> Option<Metadata> {
+        Some(Metadata::from([(
+            METADATA_KEY_BUCKETS.to_string(),
+            MetadataValue::Int(self.string_metadata_index_flusher.ids().len() as i64),
+        )]))
+    }
+
+    fn blockfile_flushers(&self) -> Vec<&BlockfileFlusher> {
+        let mut flushers = vec![
+            self.full_text_index_flusher.blockfile_flusher(),
+            &self.materialized_filter_flusher,
+        ];
+        for metadata_index_flusher in [
+            &self.string_metadata_index_flusher,
+            &self.bool_metadata_index_flusher,
+            &self.f32_metadata_index_flusher,
+            &self.u32_metadata_index_flusher,
+        ] {
+            flushers.extend(metadata_index_flusher.blockfile_flushers());
+        }
+        flushers
+    }
 }
 
 pub(crate) struct MetadataSegmentReader<'me> {
     pub(crate) full_text_index_reader: Option<FullTextIndexReader<'me>>,
-    pub(crate) string_metadata_index_reader: Option<MetadataIndexReader<'me>>,
-    pub(crate) bool_metadata_index_reader: Option<MetadataIndexReader<'me>>,
-    pub(crate) f32_metadata_index_reader: Option<MetadataIndexReader<'me>>,
-    pub(crate) u32_metadata_index_reader: Option<MetadataIndexReader<'me>>,
+    pub(crate) string_metadata_index_reader: Option<BucketedMetadataIndexReader<'me>>,
+    pub(crate) bool_metadata_index_reader: Option<BucketedMetadataIndexReader<'me>>,
+    pub(crate) f32_metadata_index_reader: Option<BucketedMetadataIndexReader<'me>>,
+    pub(crate) u32_metadata_index_reader: Option<BucketedMetadataIndexReader<'me>>,
+    pub(crate) materialized_filter_reader: Option<BlockfileReader<'me, &'me str, RoaringBitmap>>,
 }
 
 impl MetadataSegmentReader<'_> {
@@ -842,98 +1341,53 @@ impl MetadataSegmentReader<'_> {
             None => None,
         };
 
+        let tokenizer_config = tokenizer_config_from_segment(segment)?;
         let full_text_index_reader = pls_reader.map(|reader| {
             let tokenizer = NgramTokenizer::new(3, 3, false).unwrap();
-            FullTextIndexReader::new(reader, tokenizer)
+            FullTextIndexReader::new(reader, tokenizer).with_tokenizer_config(tokenizer_config)
         });
 
-        let string_metadata_reader = match segment.file_path.get(STRING_METADATA) {
-            Some(string_metadata_path) => match string_metadata_path.first() {
-                Some(string_metadata_uuid) => {
-                    let string_metadata_uuid = match Uuid::parse_str(string_metadata_uuid) {
-                        Ok(uuid) => uuid,
-                        Err(_) => {
-                            return Err(MetadataSegmentError::UuidParseError(
-                                string_metadata_uuid.to_string(),
-                            ))
-                        }
-                    };
-                    match blockfile_provider
-                        .read::<&str, RoaringBitmap>(&string_metadata_uuid)
-                        .await
-                    {
-                        Ok(reader) => Some(reader),
-                        Err(e) => return Err(MetadataSegmentError::BlockfileOpenError(*e)),
-                    }
-                }
-                None => None,
-            },
-            None => None,
-        };
-        let string_metadata_index_reader =
-            string_metadata_reader.map(MetadataIndexReader::new_string);
-
-        let bool_metadata_reader = match segment.file_path.get(BOOL_METADATA) {
-            Some(bool_metadata_path) => match bool_metadata_path.first() {
-                Some(bool_metadata_uuid) => {
-                    let bool_metadata_uuid = match Uuid::parse_str(bool_metadata_uuid) {
-                        Ok(uuid) => uuid,
-                        Err(_) => {
-                            return Err(MetadataSegmentError::UuidParseError(
-                                bool_metadata_uuid.to_string(),
-                            ))
-                        }
-                    };
-                    match blockfile_provider
-                        .read::<bool, RoaringBitmap>(&bool_metadata_uuid)
-                        .await
-                    {
-                        Ok(reader) => Some(reader),
-                        Err(e) => return Err(MetadataSegmentError::BlockfileOpenError(*e)),
-                    }
-                }
-                None => None,
-            },
-            None => None,
-        };
-        let bool_metadata_index_reader = bool_metadata_reader.map(MetadataIndexReader::new_bool);
-        let u32_metadata_reader = match segment.file_path.get(U32_METADATA) {
-            Some(u32_metadata_path) => match u32_metadata_path.first() {
-                Some(u32_metadata_uuid) => {
-                    let u32_metadata_uuid = match Uuid::parse_str(u32_metadata_uuid) {
-                        Ok(uuid) => uuid,
-                        Err(_) => {
-                            return Err(MetadataSegmentError::UuidParseError(
-                                u32_metadata_uuid.to_string(),
-                            ))
-                        }
-                    };
-                    match blockfile_provider
-                        .read::<u32, RoaringBitmap>(&u32_metadata_uuid)
-                        .await
-                    {
-                        Ok(reader) => Some(reader),
-                        Err(e) => return Err(MetadataSegmentError::BlockfileOpenError(*e)),
-                    }
-                }
-                None => None,
-            },
-            None => None,
-        };
-        let u32_metadata_index_reader = u32_metadata_reader.map(MetadataIndexReader::new_u32);
-        let f32_metadata_reader = match segment.file_path.get(F32_METADATA) {
-            Some(f32_metadata_path) => match f32_metadata_path.first() {
-                Some(f32_metadata_uuid) => {
-                    let f32_metadata_uuid = match Uuid::parse_str(f32_metadata_uuid) {
+        let string_metadata_index_reader = open_metadata_index_reader(
+            segment,
+            blockfile_provider,
+            STRING_METADATA,
+            MetadataIndexReader::new_string,
+        )
+        .await?;
+        let bool_metadata_index_reader = open_metadata_index_reader(
+            segment,
+            blockfile_provider,
+            BOOL_METADATA,
+            MetadataIndexReader::new_bool,
+        )
+        .await?;
+        let u32_metadata_index_reader = open_metadata_index_reader(
+            segment,
+            blockfile_provider,
+            U32_METADATA,
+            MetadataIndexReader::new_u32,
+        )
+        .await?;
+        let f32_metadata_index_reader = open_metadata_index_reader(
+            segment,
+            blockfile_provider,
+            F32_METADATA,
+            MetadataIndexReader::new_f32,
+        )
+        .await?;
+        let materialized_filter_reader = match segment.file_path.get(MATERIALIZED_FILTERS) {
+            Some(materialized_filter_path) => match materialized_filter_path.first() {
+                Some(materialized_filter_uuid) => {
+                    let materialized_filter_uuid = match Uuid::parse_str(materialized_filter_uuid) {
                         Ok(uuid) => uuid,
                         Err(_) => {
                             return Err(MetadataSegmentError::UuidParseError(
-                                f32_metadata_uuid.to_string(),
+                                materialized_filter_uuid.to_string(),
                             ))
                         }
                     };
                     match blockfile_provider
-                        .read::<f32, RoaringBitmap>(&f32_metadata_uuid)
+                        .read::<&str, RoaringBitmap>(&materialized_filter_uuid)
                         .await
                     {
                         Ok(reader) => Some(reader),
@@ -944,7 +1398,6 @@ impl MetadataSegmentReader<'_> {
             },
             None => None,
         };
-        let f32_metadata_index_reader = f32_metadata_reader.map(MetadataIndexReader::new_f32);
 
         Ok(MetadataSegmentReader {
             full_text_index_reader,
@@ -952,7 +1405,135 @@ impl MetadataSegmentReader<'_> {
             bool_metadata_index_reader,
             f32_metadata_index_reader,
             u32_metadata_index_reader,
+            materialized_filter_reader,
+        })
+    }
+
+    /// Returns the precomputed offset ids that satisfy the where clause,
+    /// if the where clause is materialized in this segment
+    pub(crate) async fn get_materialized_filter(
+        &self,
+        where_clause: &Where,
+    ) -> Result<Option<RoaringBitmap>, MetadataSegmentError> {
+        match materialized_filter_key(where_clause) {
+            Some(key) => self.get_materialized_filter_by_key(&key).await,
+            None => Ok(None),
+        }
+    }
+
+    async fn get_materialized_filter_by_key(
+        &self,
+        key: &str,
+    ) -> Result<Option<RoaringBitmap>, MetadataSegmentError> {
+        match &self.materialized_filter_reader {
+            Some(reader) => reader
+                .get("", key)
+                .await
+                .map_err(MetadataSegmentError::MaterializedFilterError),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the offset ids whose string value for the key equals the value when both are
+    /// lowercased. The values of a segment whose strings are not stored lowercased yet are
+    /// lowercased as they are read instead
+    pub(crate) async fn get_case_insensitive(
+        &self,
+        key: &str,
+        value: &str,
+    ) -> Result<RoaringBitmap, MetadataSegmentError> {
+        let reader = match &self.string_metadata_index_reader {
+            Some(reader) => reader,
+            None => return Ok(RoaringBitmap::new()),
+        };
+        let lowercase = value.to_lowercase();
+        if is_case_insensitive_indexed(reader).await? {
+            let lowercase_key = case_insensitive_metadata_prefix(key);
+            let lowercase_kw = KeyWrapper::String(lowercase);
+            return Ok(reader.get(&lowercase_key, &lowercase_kw).await?);
+        }
+        Ok(reader
+            .group_by_stream(key)
+            .try_fold(RoaringBitmap::new(), |offset_ids, (value, matched)| {
+                let offset_ids = match value {
+                    KeyWrapper::String(value) if value.to_lowercase() == lowercase => {
+                        offset_ids | matched
+                    }
+                    _ => offset_ids,
+                };
+                future::ready(Ok(offset_ids))
+            })
+            .await?)
+    }
+
+    /// Returns the offset ids of the documents containing the query together with their
+    /// BM25 relevance scores, ordered by offset id. The scoring stops early at `stop_at`
+    pub(crate) async fn search_document_with_scores(
+        &self,
+        query: &str,
+        stop_at: Option<Instant>,
+    ) -> Result<FullTextScores, MetadataSegmentError> {
+        match &self.full_text_index_reader {
+            Some(reader) => Ok(reader
+                .search_with_scores(query, stop_at)
+                .await
+                .map_err(MetadataIndexError::FullTextError)?),
+            None => Ok(FullTextScores::default()),
+        }
+    }
+
+    /// Streams every value of the metadata key in this segment together with the offset
+    /// ids that have it, grouped by type and in value order within a type. The metadata
+    /// indices are read block by block as the stream is polled, and no record is read
+    pub(crate) fn group_by_stream<'a>(
+        &'a self,
+        key: &'a str,
+    ) -> impl Stream<Item = Result<(MetadataValue, RoaringBitmap), MetadataSegmentError>> + Send + 'a
+    {
+        let values = stream::iter(
+            [
+                &self.string_metadata_index_reader,
+                &self.bool_metadata_index_reader,
+                &self.f32_metadata_index_reader,
+                &self.u32_metadata_index_reader,
+            ]
+            .into_iter()
+            .flatten(),
+        )
+        .flat_map(move |reader| reader.group_by_stream(key))
+        .try_filter_map(|(value, rbm)| {
+            let value = match value {
+                KeyWrapper::String(s) => Some(MetadataValue::Str(s)),
+                KeyWrapper::Bool(b) => Some(MetadataValue::Bool(b)),
+                KeyWrapper::Float32(f) => Some(MetadataValue::Float(f as f64)),
+                // Integers are stored as their truncated bit pattern
+                KeyWrapper::Uint32(i) => Some(MetadataValue::Int(i as i32 as i64)),
+                // The metadata indexes have no other key types
+                KeyWrapper::Uint64(_) | KeyWrapper::Uuid(_) => None,
+            };
+            future::ready(Ok(value.map(|value| (value, rbm))))
+        });
+
+        // The timestamp prefix is built from the key, so the timestamps of the key are read
+        // at once instead of being streamed from the blockfile
+        let timestamps = stream::once(async move {
+            match &self.string_metadata_index_reader {
+                Some(reader) => reader.group_by(&timestamp_metadata_prefix(key)).await,
+                None => Ok(Vec::new()),
+            }
         })
+        .map_ok(|groups| stream::iter(groups.into_iter().map(Ok)))
+        .try_flatten()
+        .try_filter_map(|(value, rbm)| {
+            future::ready(Ok(match value {
+                KeyWrapper::String(s) => {
+                    decode_timestamp(&s).map(|micros| (MetadataValue::Timestamp(micros), rbm))
+                }
+                _ => None,
+            }))
+        });
+
+        values.chain(timestamps).map_err(MetadataSegmentError::from)
     }
 
     // DEPRECATED: This exists only for the legacy testing. Please checkout `MetadataFilteringOperator` for the up to date implementation.
@@ -1052,7 +1633,7 @@ impl MetadataSegmentReader<'_> {
         where_clause: &'me Where,
     ) -> BoxFuture<Result<Vec<usize>, MetadataIndexError>> {
         async move {
-            let provider = MetadataProvider::from_metadata_segment_reader(self);
+            let provider = MetadataProvider::from_metadata_segment_reader(self, None);
             let result = where_clause
                 .eval(&provider)
                 .await
@@ -1074,8 +1655,14 @@ impl MetadataSegmentReader<'_> {
 mod test {
     #![allow(deprecated)]
 
+    use crate::log::test::{upsert_generator, LogGenerator};
+    use crate::segment::test::TestSegment;
     use crate::segment::{
-        metadata_segment::{MetadataSegmentReader, MetadataSegmentWriter},
+        metadata_segment::{
+            tokenizer_config_from_segment, MetadataSegmentError, MetadataSegmentReader,
+            MetadataSegmentWriter, BOOL_METADATA, F32_METADATA, METADATA_KEY_BUCKETS,
+            STRING_METADATA, U32_METADATA,
+        },
         record_segment::{
             RecordSegmentReader, RecordSegmentReaderCreationError, RecordSegmentWriter,
         },
@@ -1086,12 +1673,15 @@ mod test {
         provider::BlockfileProvider,
     };
     use chroma_cache::new_cache_for_test;
+    use chroma_error::{ChromaError, ErrorCodes};
     use chroma_storage::{local::LocalStorage, Storage};
     use chroma_types::{
         Chunk, CollectionUuid, DirectDocumentComparison, DirectWhereComparison, LogRecord,
         MetadataValue, Operation, OperationRecord, PrimitiveOperator, SegmentUuid,
         UpdateMetadataValue, Where, WhereComparison,
     };
+    use futures::TryStreamExt;
+    use roaring::RoaringBitmap;
     use std::{collections::HashMap, str::FromStr};
 
     #[tokio::test]
@@ -1107,7 +1697,7 @@ mod test {
             sparse_index_cache,
         );
         let blockfile_provider =
-            BlockfileProvider::ArrowBlockfileProvider(arrow_blockfile_provider);
+            BlockfileProvider::ArrowBlockfileProvider(Box::new(arrow_blockfile_provider));
         let mut record_segment = chroma_types::Segment {
             id: SegmentUuid::from_str("00000000-0000-0000-0000-000000000000").expect("parse error"),
             r#type: chroma_types::SegmentType::BlockfileRecord,
@@ -1153,6 +1743,9 @@ mod test {
                         encoding: None,
                         metadata: Some(update_metadata.clone()),
                         document: Some(String::from("This is a document about cats.")),
+                        uri: None,
+                        idempotency_key: None,
+                        embedding_reference: None,
                         operation: Operation::Add,
                     },
                 },
@@ -1164,6 +1757,9 @@ mod test {
                         encoding: None,
                         metadata: Some(update_metadata),
                         document: Some(String::from("This is a document about dogs.")),
+                        uri: None,
+                        idempotency_key: None,
+                        embedding_reference: None,
                         operation: Operation::Add,
                     },
                 },
@@ -1178,7 +1774,7 @@ mod test {
                             // Uninitialized segment is fine and means that the record
                             // segment is not yet initialized in storage.
                             RecordSegmentReaderCreationError::UninitializedSegment => None,
-                            RecordSegmentReaderCreationError::BlockfileOpenError(_) => {
+                            RecordSegmentReaderCreationError::BlockfileOpenError(..) => {
                                 panic!("Error creating record segment reader");
                             }
                             RecordSegmentReaderCreationError::InvalidNumberOfFiles => {
@@ -1236,6 +1832,9 @@ mod test {
                     encoding: None,
                     metadata: None,
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Delete,
                 },
             },
@@ -1247,6 +1846,9 @@ mod test {
                     encoding: None,
                     metadata: None,
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Delete,
                 },
             },
@@ -1318,6 +1920,9 @@ mod test {
                     encoding: None,
                     metadata: None,
                     document: Some(String::from("This is a document about cats.")),
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Add,
                 },
             },
@@ -1329,6 +1934,9 @@ mod test {
                     encoding: None,
                     metadata: None,
                     document: Some(String::from("This is a document about dogs.")),
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Add,
                 },
             },
@@ -1405,7 +2013,7 @@ mod test {
             sparse_index_cache,
         );
         let blockfile_provider =
-            BlockfileProvider::ArrowBlockfileProvider(arrow_blockfile_provider);
+            BlockfileProvider::ArrowBlockfileProvider(Box::new(arrow_blockfile_provider));
         let mut record_segment = chroma_types::Segment {
             id: SegmentUuid::from_str("00000000-0000-0000-0000-000000000000").expect("parse error"),
             r#type: chroma_types::SegmentType::BlockfileRecord,
@@ -1447,6 +2055,9 @@ mod test {
                         encoding: None,
                         metadata: Some(update_metadata.clone()),
                         document: Some(String::from("This is a document about cats.")),
+                        uri: None,
+                        idempotency_key: None,
+                        embedding_reference: None,
                         operation: Operation::Add,
                     },
                 },
@@ -1458,6 +2069,9 @@ mod test {
                         encoding: None,
                         metadata: Some(update_metadata),
                         document: Some(String::from("This is a document about dogs.")),
+                        uri: None,
+                        idempotency_key: None,
+                        embedding_reference: None,
                         operation: Operation::Add,
                     },
                 },
@@ -1472,7 +2086,7 @@ mod test {
                             // Uninitialized segment is fine and means that the record
                             // segment is not yet initialized in storage.
                             RecordSegmentReaderCreationError::UninitializedSegment => None,
-                            RecordSegmentReaderCreationError::BlockfileOpenError(_) => {
+                            RecordSegmentReaderCreationError::BlockfileOpenError(..) => {
                                 panic!("Error creating record segment reader");
                             }
                             RecordSegmentReaderCreationError::InvalidNumberOfFiles => {
@@ -1537,6 +2151,9 @@ mod test {
                     encoding: None,
                     metadata: Some(update_metadata_id1.clone()),
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Update,
                 },
             },
@@ -1548,6 +2165,9 @@ mod test {
                     encoding: None,
                     metadata: Some(update_metadata_id2.clone()),
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Update,
                 },
             },
@@ -1667,7 +2287,7 @@ mod test {
             sparse_index_cache,
         );
         let blockfile_provider =
-            BlockfileProvider::ArrowBlockfileProvider(arrow_blockfile_provider);
+            BlockfileProvider::ArrowBlockfileProvider(Box::new(arrow_blockfile_provider));
         let mut record_segment = chroma_types::Segment {
             id: SegmentUuid::from_str("00000000-0000-0000-0000-000000000000").expect("parse error"),
             r#type: chroma_types::SegmentType::BlockfileRecord,
@@ -1712,6 +2332,9 @@ mod test {
                     encoding: None,
                     metadata: Some(update_metadata.clone()),
                     document: Some(String::from("This is a document about cats.")),
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Add,
                 },
             }];
@@ -1725,7 +2348,7 @@ mod test {
                             // Uninitialized segment is fine and means that the record
                             // segment is not yet initialized in storage.
                             RecordSegmentReaderCreationError::UninitializedSegment => None,
-                            RecordSegmentReaderCreationError::BlockfileOpenError(_) => {
+                            RecordSegmentReaderCreationError::BlockfileOpenError(..) => {
                                 panic!("Error creating record segment reader");
                             }
                             RecordSegmentReaderCreationError::InvalidNumberOfFiles => {
@@ -1784,6 +2407,9 @@ mod test {
                 encoding: None,
                 metadata: Some(update_metadata_id1.clone()),
                 document: None,
+                uri: None,
+                idempotency_key: None,
+                embedding_reference: None,
                 operation: Operation::Update,
             },
         }];
@@ -1898,7 +2524,7 @@ mod test {
             sparse_index_cache,
         );
         let blockfile_provider =
-            BlockfileProvider::ArrowBlockfileProvider(arrow_blockfile_provider);
+            BlockfileProvider::ArrowBlockfileProvider(Box::new(arrow_blockfile_provider));
         let mut record_segment = chroma_types::Segment {
             id: SegmentUuid::from_str("00000000-0000-0000-0000-000000000000").expect("parse error"),
             r#type: chroma_types::SegmentType::BlockfileRecord,
@@ -1934,6 +2560,9 @@ mod test {
                     encoding: None,
                     metadata: None,
                     document: Some(String::from("hello")),
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Add,
                 },
             }];
@@ -1947,7 +2576,7 @@ mod test {
                             // Uninitialized segment is fine and means that the record
                             // segment is not yet initialized in storage.
                             RecordSegmentReaderCreationError::UninitializedSegment => None,
-                            RecordSegmentReaderCreationError::BlockfileOpenError(_) => {
+                            RecordSegmentReaderCreationError::BlockfileOpenError(..) => {
                                 panic!("Error creating record segment reader");
                             }
                             RecordSegmentReaderCreationError::InvalidNumberOfFiles => {
@@ -2004,6 +2633,9 @@ mod test {
                 encoding: None,
                 metadata: None,
                 document: Some(String::from("bye")),
+                uri: None,
+                idempotency_key: None,
+                embedding_reference: None,
                 operation: Operation::Update,
             },
         }];
@@ -2099,4 +2731,211 @@ mod test {
             Some(String::from("bye").as_str())
         );
     }
+
+    #[tokio::test]
+    async fn nested_metadata_list() {
+        let test_segment = TestSegment::default();
+        let metadata_writer = MetadataSegmentWriter::from_segment(
+            &test_segment.metadata_segment,
+            &test_segment.blockfile_provider,
+        )
+        .await
+        .expect("Metadata segment writer should be created");
+        let data = vec![LogRecord {
+            log_offset: 1,
+            record: OperationRecord {
+                id: "embedding_id_1".to_string(),
+                embedding: Some(vec![1.0, 2.0, 3.0]),
+                encoding: None,
+                metadata: Some(HashMap::from([(
+                    "tags".to_string(),
+                    UpdateMetadataValue::List(vec![MetadataValue::List(vec![MetadataValue::Int(
+                        1,
+                    )])]),
+                )])),
+                document: None,
+                uri: None,
+                idempotency_key: None,
+                embedding_reference: None,
+                operation: Operation::Add,
+            },
+        }];
+        let materializer = LogMaterializer::new(None, Chunk::new(data.into()), None);
+        let mat_records = materializer
+            .materialize()
+            .await
+            .expect("Log materialization failed");
+        let error = metadata_writer
+            .apply_materialized_log_chunk(mat_records)
+            .await
+            .expect_err("Nested metadata lists should be rejected");
+        assert_eq!(error.code(), ErrorCodes::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn group_by_stream_of_key() {
+        let mut test_segment = TestSegment::default();
+        test_segment
+            .populate_with_generator(
+                30,
+                &LogGenerator {
+                    generator: upsert_generator,
+                },
+            )
+            .await;
+        let metadata_segment_reader = MetadataSegmentReader::from_segment(
+            &test_segment.metadata_segment,
+            &test_segment.blockfile_provider,
+        )
+        .await
+        .expect("Metadata segment reader should be created");
+
+        let counts = |groups: Vec<(MetadataValue, RoaringBitmap)>| {
+            groups
+                .into_iter()
+                .map(|(value, rbm)| (value, rbm.len()))
+                .collect::<Vec<_>>()
+        };
+        let groups = metadata_segment_reader
+            .group_by_stream("modulo_3")
+            .try_collect()
+            .await
+            .expect("Group by should not fail");
+        assert_eq!(
+            counts(groups),
+            vec![
+                (MetadataValue::Int(0), 10),
+                (MetadataValue::Int(1), 10),
+                (MetadataValue::Int(2), 10),
+            ]
+        );
+        let groups = metadata_segment_reader
+            .group_by_stream("is_even")
+            .try_collect()
+            .await
+            .expect("Group by should not fail");
+        assert_eq!(
+            counts(groups),
+            vec![
+                (MetadataValue::Bool(false), 15),
+                (MetadataValue::Bool(true), 15),
+            ]
+        );
+        let groups = metadata_segment_reader
+            .group_by_stream("missing")
+            .try_collect()
+            .await
+            .expect("Group by should not fail");
+        assert!(counts(groups).is_empty());
+    }
+
+    #[tokio::test]
+    async fn metadata_key_buckets() {
+        let mut test_segment = TestSegment::default();
+        let generator = LogGenerator {
+            generator: upsert_generator,
+        };
+        let num_buckets = |test_segment: &TestSegment| {
+            [STRING_METADATA, BOOL_METADATA, U32_METADATA, F32_METADATA]
+                .into_iter()
+                .map(|file| {
+                    test_segment
+                        .metadata_segment
+                        .file_path
+                        .get(file)
+                        .map(Vec::len)
+                })
+                .collect::<Vec<_>>()
+        };
+        // A segment without the key is flushed with a single bucket
+        test_segment.populate_with_generator(30, &generator).await;
+        assert_eq!(num_buckets(&test_segment), vec![Some(1); 4]);
+        assert_eq!(
+            test_segment
+                .metadata_segment
+                .metadata
+                .as_ref()
+                .and_then(|metadata| metadata.get(METADATA_KEY_BUCKETS)),
+            Some(&MetadataValue::Int(1))
+        );
+
+        // The flushed posting lists are moved over to the buckets that the segment is set to
+        test_segment
+            .metadata_segment
+            .metadata
+            .get_or_insert_with(HashMap::new)
+            .insert(METADATA_KEY_BUCKETS.to_string(), MetadataValue::Int(4));
+        test_segment
+            .compact_log(generator.generate_chunk(31..=60), 30)
+            .await;
+        assert_eq!(num_buckets(&test_segment), vec![Some(4); 4]);
+
+        let metadata_segment_reader = MetadataSegmentReader::from_segment(
+            &test_segment.metadata_segment,
+            &test_segment.blockfile_provider,
+        )
+        .await
+        .expect("Metadata segment reader should be created");
+        let groups = metadata_segment_reader
+            .group_by_stream("modulo_3")
+            .try_collect::<Vec<_>>()
+            .await
+            .expect("Group by should not fail");
+        assert_eq!(
+            groups
+                .into_iter()
+                .map(|(value, rbm)| (value, rbm.len()))
+                .collect::<Vec<_>>(),
+            vec![
+                (MetadataValue::Int(0), 20),
+                (MetadataValue::Int(1), 20),
+                (MetadataValue::Int(2), 20),
+            ]
+        );
+    }
+
+    #[test]
+    fn tokenizer_config_from_metadata() {
+        let mut metadata_segment = chroma_types::Segment {
+            id: SegmentUuid::from_str("00000000-0000-0000-0000-000000000001").expect("parse error"),
+            r#type: chroma_types::SegmentType::BlockfileMetadata,
+            scope: chroma_types::SegmentScope::METADATA,
+            collection: CollectionUuid::from_str("00000000-0000-0000-0000-000000000000")
+                .expect("parse error"),
+            metadata: None,
+            file_path: HashMap::new(),
+        };
+        let tokenizer_config =
+            tokenizer_config_from_segment(&metadata_segment).expect("Default config is valid");
+        assert_eq!(tokenizer_config.normalize("Hello World"), "Hello World");
+
+        let mut metadata = HashMap::new();
+        metadata.insert("fts:lowercase".to_string(), MetadataValue::Bool(true));
+        metadata.insert(
+            "fts:stopwords".to_string(),
+            MetadataValue::Str("english".to_string()),
+        );
+        metadata.insert(
+            "fts:stemmer".to_string(),
+            MetadataValue::Str("english".to_string()),
+        );
+        metadata.insert("fts:cjk_bigrams".to_string(), MetadataValue::Bool(true));
+        metadata_segment.metadata = Some(metadata.clone());
+        let tokenizer_config =
+            tokenizer_config_from_segment(&metadata_segment).expect("Config should be valid");
+        assert_eq!(
+            tokenizer_config.normalize("The Running Dogs of 东京都"),
+            "run dog 东京  京都 "
+        );
+
+        metadata.insert(
+            "fts:stemmer".to_string(),
+            MetadataValue::Str("klingon".to_string()),
+        );
+        metadata_segment.metadata = Some(metadata);
+        assert!(matches!(
+            tokenizer_config_from_segment(&metadata_segment),
+            Err(MetadataSegmentError::TokenizerConfigError(_))
+        ));
+    }
 }
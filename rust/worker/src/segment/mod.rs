@@ -1,10 +1,14 @@
+pub(crate) mod cache_invalidation;
+pub(crate) mod checksum;
 pub(crate) mod config;
 pub(crate) mod distributed_hnsw_segment;
+pub(crate) mod materialized_filter;
 pub mod test;
 
 pub(crate) use types::*;
 
 // Required for benchmark
 pub mod metadata_segment;
+pub mod offset_id_cache;
 pub mod record_segment;
 pub mod types;
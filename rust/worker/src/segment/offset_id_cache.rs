@@ -0,0 +1,198 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug, Formatter},
+    sync::Arc,
+};
+
+use chroma_cache::{Cache, Weighted};
+use chroma_error::ChromaError;
+use parking_lot::Mutex;
+use uuid::Uuid;
+
+use super::record_segment::RecordSegmentReader;
+
+/// The key of a user id that is resolved in a user id to offset id blockfile. The blockfile is
+/// replaced whenever the record segment is compacted, so an entry never outlives the version of
+/// the segment that it is resolved in
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OffsetIdCacheKey {
+    pub blockfile_id: Uuid,
+    pub user_id: String,
+}
+
+/// The offset id that a user id resolves to, or `None` if the user id has no record
+#[derive(Clone, Copy, Debug)]
+pub struct CachedOffsetId(pub Option<u32>);
+
+impl Weighted for CachedOffsetId {
+    fn weight(&self) -> usize {
+        1
+    }
+}
+
+/// The cache of resolved user ids that is shared across queries
+pub type SharedOffsetIdCache = Arc<dyn Cache<OffsetIdCacheKey, CachedOffsetId>>;
+
+/// Caches the offset ids that the explicitly requested user ids resolve to, so that the user ids
+/// requested again skip the user id to offset id blockfile. The user ids resolved by a query are
+/// kept for the rest of the query, e.g. for the search of each of its query vectors, and with a
+/// shared cache they are also kept for later queries on the same version of the segment
+#[derive(Clone, Default)]
+pub struct OffsetIdCache {
+    query: Arc<Mutex<HashMap<String, Option<u32>>>>,
+    shared: Option<SharedOffsetIdCache>,
+}
+
+impl Debug for OffsetIdCache {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OffsetIdCache")
+            .field("query_entries", &self.query.lock().len())
+            .field("shared", &self.shared.is_some())
+            .finish()
+    }
+}
+
+impl OffsetIdCache {
+    /// A cache for a single query, which also reads and fills the shared cache if provided
+    pub fn new(shared: Option<SharedOffsetIdCache>) -> Self {
+        Self {
+            query: Arc::default(),
+            shared,
+        }
+    }
+
+    /// Returns the offset ids of the user ids in the record segment, in the same order as the
+    /// user ids. Only the user ids that are not cached are resolved through the blockfile
+    pub(crate) async fn get_offset_ids_for_user_ids(
+        &self,
+        record_segment_reader: &RecordSegmentReader<'_>,
+        user_ids: &[&str],
+    ) -> Result<Vec<Option<u32>>, Box<dyn ChromaError>> {
+        let mut resolved = HashMap::with_capacity(user_ids.len());
+        let mut missing = Vec::new();
+        {
+            let query = self.query.lock();
+            for user_id in user_ids {
+                match query.get(*user_id) {
+                    Some(offset_id) => {
+                        resolved.insert(*user_id, *offset_id);
+                    }
+                    None => missing.push(*user_id),
+                }
+            }
+        }
+
+        let blockfile_id = record_segment_reader.user_id_to_offset_id_blockfile_id();
+        let key = |user_id: &str| OffsetIdCacheKey {
+            blockfile_id,
+            user_id: user_id.to_string(),
+        };
+        let mut newly_resolved = Vec::new();
+        if let Some(shared) = self.shared.as_ref() {
+            let mut shared_missing = Vec::new();
+            for user_id in missing {
+                match shared.get(&key(user_id)).await {
+                    Ok(Some(CachedOffsetId(offset_id))) => {
+                        newly_resolved.push((user_id, offset_id))
+                    }
+                    _ => shared_missing.push(user_id),
+                }
+            }
+            missing = shared_missing;
+        }
+
+        if !missing.is_empty() {
+            let offset_ids = record_segment_reader
+                .get_offset_ids_by_user_id(&missing)
+                .await?;
+            for user_id in missing {
+                let offset_id = offset_ids.get(user_id).copied();
+                if let Some(shared) = self.shared.as_ref() {
+                    shared.insert(key(user_id), CachedOffsetId(offset_id)).await;
+                }
+                newly_resolved.push((user_id, offset_id));
+            }
+        }
+
+        if !newly_resolved.is_empty() {
+            let mut query = self.query.lock();
+            for (user_id, offset_id) in newly_resolved {
+                query.insert(user_id.to_string(), offset_id);
+                resolved.insert(user_id, offset_id);
+            }
+        }
+
+        Ok(user_ids
+            .iter()
+            .map(|user_id| resolved.get(user_id).copied().flatten())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use chroma_cache::new_non_persistent_cache_for_test;
+
+    use crate::{
+        log::test::{int_as_id, upsert_generator, LogGenerator},
+        segment::{record_segment::RecordSegmentReader, test::TestSegment},
+    };
+
+    use super::{CachedOffsetId, OffsetIdCache, OffsetIdCacheKey, SharedOffsetIdCache};
+
+    #[tokio::test]
+    async fn test_offset_id_cache() {
+        let mut test_segment = TestSegment::default();
+        test_segment
+            .populate_with_generator(
+                10,
+                &LogGenerator {
+                    generator: upsert_generator,
+                },
+            )
+            .await;
+        let reader = RecordSegmentReader::from_segment(
+            &test_segment.record_segment,
+            &test_segment.blockfile_provider,
+        )
+        .await
+        .expect("Record segment reader should be created");
+
+        let shared: SharedOffsetIdCache = Arc::from(new_non_persistent_cache_for_test());
+        let ids = [int_as_id(3), int_as_id(7), int_as_id(42)];
+        let user_ids = ids.iter().map(String::as_str).collect::<Vec<_>>();
+        let mut expected = Vec::new();
+        for id in &ids {
+            expected.push(
+                reader
+                    .get_offset_id_for_user_id(id)
+                    .await
+                    .expect("Offset id should be read"),
+            );
+        }
+        assert!(expected[0].is_some() && expected[1].is_some() && expected[2].is_none());
+        let offset_ids = OffsetIdCache::new(Some(shared.clone()))
+            .get_offset_ids_for_user_ids(&reader, &user_ids)
+            .await
+            .expect("Offset ids should be resolved");
+        assert_eq!(offset_ids, expected);
+
+        // A later query reads the resolved ids from the shared cache instead of the blockfile
+        let key = OffsetIdCacheKey {
+            blockfile_id: reader.user_id_to_offset_id_blockfile_id(),
+            user_id: ids[0].clone(),
+        };
+        assert!(matches!(
+            shared.get(&key).await,
+            Ok(Some(CachedOffsetId(offset_id))) if offset_id == expected[0]
+        ));
+        shared.insert(key, CachedOffsetId(Some(1000))).await;
+        let offset_ids = OffsetIdCache::new(Some(shared))
+            .get_offset_ids_for_user_ids(&reader, &user_ids)
+            .await
+            .expect("Offset ids should be resolved");
+        assert_eq!(offset_ids, vec![Some(1000), expected[1], None]);
+    }
+}
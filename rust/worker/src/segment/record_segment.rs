@@ -1,3 +1,4 @@
+use super::checksum::ContentChecksum;
 use super::types::{MaterializedLogRecord, SegmentWriter};
 use super::SegmentFlusher;
 use async_trait::async_trait;
@@ -5,16 +6,22 @@ use chroma_blockstore::provider::{BlockfileProvider, CreateError, OpenError};
 use chroma_blockstore::{
     BlockfileFlusher, BlockfileReader, BlockfileWriter, BlockfileWriterOptions,
 };
-use chroma_error::{ChromaError, ErrorCodes};
+use chroma_error::{ChromaError, ErrorCodes, ErrorLocation};
 use chroma_index::fulltext::types::FullTextIndexError;
 use chroma_types::{
-    Chunk, DataRecord, MaterializedLogOperation, Segment, SegmentType, SegmentUuid,
+    get_metadata_path, Chunk, DataRecord, DataRecordMetadata, DirectWhereComparison, LogRecord,
+    MaterializedLogOperation, Metadata, MetadataValue, PrimitiveOperator, Segment, SegmentType,
+    SegmentUuid, Where, WhereComparison,
 };
+use futures::{future, Stream, StreamExt, TryStreamExt};
+use parking_lot::Mutex;
 use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::fmt::{self, Debug, Formatter};
+use std::mem::discriminant;
 use std::sync::atomic::AtomicU32;
 use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use thiserror::Error;
 use uuid::Uuid;
 
@@ -22,6 +29,54 @@ const USER_ID_TO_OFFSET_ID: &str = "user_id_to_offset_id";
 const OFFSET_ID_TO_USER_ID: &str = "offset_id_to_user_id";
 const OFFSET_ID_TO_DATA: &str = "offset_id_to_data";
 const MAX_OFFSET_ID: &str = "max_offset_id";
+// The content checksum of the segment is stored in its metadata under this key, which is
+// registered together with the blockfiles that it describes
+pub(crate) const CONTENT_CHECKSUM: &str = "record:content_checksum";
+// The idempotency keys of the applied records are stored next to the max offset id,
+// under this prefix, so that the layout of the segment does not change
+const IDEMPOTENCY_KEY_PREFIX: &str = "idempotency_key";
+// The offset of the last log compacted into the segment is stored next to the max offset
+// id as well, so that the logs that the log service delivers again are not applied twice.
+// The values of the blockfile are u32, so the offset is stored as its low and high halves
+const APPLIED_LOG_OFFSET_LOW: &str = "applied_log_offset_low";
+const APPLIED_LOG_OFFSET_HIGH: &str = "applied_log_offset_high";
+// Lists of at most this many user ids are looked up one by one, longer lists are merged
+// against the user id blockfile
+const USER_ID_POINT_LOOKUP_LIMIT: usize = 64;
+
+/// Encodes an idempotency key and the user id of a record written with it into a
+/// blockfile key. The key is length prefixed, so that the encoding is unambiguous.
+fn idempotency_blockfile_key(idempotency_key: &str, user_id: &str) -> String {
+    format!("{}:{}{}", idempotency_key.len(), idempotency_key, user_id)
+}
+
+/// The content checksum stored in the metadata of the segment. It is None for segments
+/// flushed before the checksum was introduced, and for unreadable checksums
+pub(crate) fn stored_content_checksum(segment: &Segment) -> Option<ContentChecksum> {
+    let MetadataValue::Str(checksum) = segment.metadata.as_ref()?.get(CONTENT_CHECKSUM)? else {
+        tracing::warn!(
+            "Ignoring checksum of segment {} that is not a string",
+            segment.id
+        );
+        return None;
+    };
+    match checksum.parse() {
+        Ok(checksum) => Some(checksum),
+        Err(e) => {
+            tracing::warn!("Ignoring checksum of segment {}: {}", segment.id, e);
+            None
+        }
+    }
+}
+
+/// The current time in seconds since the unix epoch, which is when the idempotency
+/// keys in the current compaction are recorded to have been applied
+fn idempotency_timestamp() -> u32 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() as u32)
+        .unwrap_or_default()
+}
 
 #[derive(Clone)]
 pub struct RecordSegmentWriter {
@@ -32,6 +87,9 @@ pub struct RecordSegmentWriter {
     // TODO: for now we store the max offset ID in a separate blockfile, this is not ideal
     // we should store it in metadata of one of the blockfiles
     max_offset_id: Option<BlockfileWriter>,
+    // The checksum of the content after the applied records, which is None until it is
+    // computed for a segment that was flushed without one
+    checksum: Option<Arc<Mutex<ContentChecksum>>>,
     pub(crate) id: SegmentUuid,
 }
 
@@ -80,7 +138,11 @@ impl RecordSegmentWriter {
             embedding: updated_embeddings,
             metadata: final_metadata_opt,
             document: updated_document,
+            uri: mat_record.merged_uri_ref(),
         };
+        if let Some(checksum) = &self.checksum {
+            checksum.lock().add(&data_record);
+        }
         match self
             .id_to_data
             .as_ref()
@@ -289,14 +351,115 @@ impl RecordSegmentWriter {
             _ => return Err(RecordSegmentWriterCreationError::IncorrectNumberOfFiles),
         };
 
+        let checksum = if segment.file_path.is_empty() {
+            Some(ContentChecksum::default())
+        } else {
+            stored_content_checksum(segment)
+        };
+
         Ok(RecordSegmentWriter {
             user_id_to_id: Some(user_id_to_id),
             id_to_user_id: Some(id_to_user_id),
             id_to_data: Some(id_to_data),
             max_offset_id: Some(max_offset_id),
+            checksum: checksum.map(|checksum| Arc::new(Mutex::new(checksum))),
             id: segment.id,
         })
     }
+
+    /// Computes the content checksum of a segment that was flushed without one from its
+    /// records, so that it is kept up to date from this compaction on
+    pub(crate) async fn backfill_checksum(
+        &mut self,
+        reader: &RecordSegmentReader<'_>,
+    ) -> Result<(), Box<dyn ChromaError>> {
+        if self.checksum.is_none() {
+            let checksum = ContentChecksum::compute(reader).await?;
+            self.checksum = Some(Arc::new(Mutex::new(checksum)));
+        }
+        Ok(())
+    }
+
+    /// Records the idempotency keys of the logs as applied, so that the log materializer
+    /// skips the retried writes with the same keys after the logs are compacted
+    pub(crate) async fn apply_idempotency_keys(
+        &self,
+        logs: &Chunk<LogRecord>,
+    ) -> Result<(), ApplyMaterializedLogError> {
+        let applied_at = idempotency_timestamp();
+        for (log_record, _) in logs.iter() {
+            let Some(idempotency_key) = log_record.record.idempotency_key.as_deref() else {
+                continue;
+            };
+            let key = idempotency_blockfile_key(idempotency_key, &log_record.record.id);
+            if self
+                .max_offset_id
+                .as_ref()
+                .unwrap()
+                .set::<&str, u32>(IDEMPOTENCY_KEY_PREFIX, key.as_str(), applied_at)
+                .await
+                .is_err()
+            {
+                return Err(ApplyMaterializedLogError::BlockfileSet);
+            }
+        }
+        Ok(())
+    }
+
+    /// Records that the logs up to the offset are applied, so that the log materializer
+    /// skips them if they are delivered again. Negative offsets are rejected
+    pub(crate) async fn set_applied_log_offset(
+        &self,
+        log_offset: i64,
+    ) -> Result<(), ApplyMaterializedLogError> {
+        let Ok(offset) = u64::try_from(log_offset) else {
+            return Err(ApplyMaterializedLogError::InvalidLogOffset(log_offset));
+        };
+        let max_offset_id = self.max_offset_id.as_ref().unwrap();
+        for (key, half) in [
+            (APPLIED_LOG_OFFSET_LOW, offset as u32),
+            (APPLIED_LOG_OFFSET_HIGH, (offset >> 32) as u32),
+        ] {
+            if let Err(e) = max_offset_id.set::<&str, u32>("", key, half).await {
+                tracing::error!("Error setting applied log offset {:?}", e);
+                return Err(ApplyMaterializedLogError::BlockfileSet);
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes the idempotency keys that were applied more than `ttl` ago, which bounds
+    /// the window in which retried writes are deduplicated
+    pub(crate) async fn expire_idempotency_keys(
+        &self,
+        reader: &RecordSegmentReader<'_>,
+        ttl: Duration,
+    ) -> Result<(), ApplyMaterializedLogError> {
+        let expire_before = idempotency_timestamp().saturating_sub(ttl.as_secs() as u32);
+        let expired_keys = match reader
+            .get_idempotency_keys_applied_before(expire_before)
+            .await
+        {
+            Ok(keys) => keys,
+            Err(e) => {
+                tracing::error!("Error reading idempotency keys {:?}", e);
+                return Err(ApplyMaterializedLogError::BlockfileDelete);
+            }
+        };
+        for key in expired_keys {
+            if let Err(e) = self
+                .max_offset_id
+                .as_ref()
+                .unwrap()
+                .delete::<&str, u32>(IDEMPOTENCY_KEY_PREFIX, key.as_str())
+                .await
+            {
+                tracing::error!("Error deleting idempotency key {:?}", e);
+                return Err(ApplyMaterializedLogError::BlockfileDelete);
+            }
+        }
+        Ok(())
+    }
 }
 
 #[derive(Error, Debug)]
@@ -317,6 +480,12 @@ pub enum ApplyMaterializedLogError {
     FullTextIndex(#[from] FullTextIndexError),
     #[error("Error writing to hnsw index")]
     HnswIndex(#[from] Box<dyn ChromaError>),
+    #[error("Error updating materialized filter: {0}")]
+    MaterializedFilter(Box<dyn ChromaError>),
+    #[error("Invalid applied log offset: {0}")]
+    InvalidLogOffset(i64),
+    #[error("Metadata lists cannot be nested")]
+    NestedMetadataList,
 }
 
 impl ChromaError for ApplyMaterializedLogError {
@@ -328,6 +497,9 @@ impl ChromaError for ApplyMaterializedLogError {
             ApplyMaterializedLogError::Allocation => ErrorCodes::Internal,
             ApplyMaterializedLogError::FullTextIndex(e) => e.code(),
             ApplyMaterializedLogError::HnswIndex(_) => ErrorCodes::Internal,
+            ApplyMaterializedLogError::InvalidLogOffset(_) => ErrorCodes::Internal,
+            ApplyMaterializedLogError::MaterializedFilter(e) => e.code(),
+            ApplyMaterializedLogError::NestedMetadataList => ErrorCodes::InvalidArgument,
         }
     }
 }
@@ -401,6 +573,9 @@ impl<'a> SegmentWriter<'a> for RecordSegmentWriter {
                     // Offset id and user id do not need to change. Only data
                     // needs to change. Blockfile does not have Read then write
                     // semantics so we'll delete and insert.
+                    if let Some(checksum) = &self.checksum {
+                        checksum.lock().remove(log_record.data_record.as_ref().unwrap());
+                    }
                     match self
                         .id_to_data
                         .as_ref()
@@ -429,6 +604,9 @@ impl<'a> SegmentWriter<'a> for RecordSegmentWriter {
                     }
                 }
                 MaterializedLogOperation::DeleteExisting => {
+                    if let Some(checksum) = &self.checksum {
+                        checksum.lock().remove(log_record.data_record.as_ref().unwrap());
+                    }
                     // Delete user id to offset id.
                     match self
                         .user_id_to_id
@@ -540,6 +718,7 @@ impl<'a> SegmentWriter<'a> for RecordSegmentWriter {
             id_to_user_id_flusher: flusher_id_to_user_id,
             id_to_data_flusher: flusher_id_to_data,
             max_offset_id_flusher: flusher_max_offset_id,
+            checksum: self.checksum.map(|checksum| *checksum.lock()),
         })
     }
 }
@@ -549,6 +728,7 @@ pub(crate) struct RecordSegmentFlusher {
     id_to_user_id_flusher: BlockfileFlusher,
     id_to_data_flusher: BlockfileFlusher,
     max_offset_id_flusher: BlockfileFlusher,
+    checksum: Option<ContentChecksum>,
 }
 
 impl Debug for RecordSegmentFlusher {
@@ -621,6 +801,24 @@ impl SegmentFlusher for RecordSegmentFlusher {
 
         Ok(flushed_files)
     }
+
+    fn metadata(&self) -> Option<Metadata> {
+        self.checksum.as_ref().map(|checksum| {
+            Metadata::from([(
+                CONTENT_CHECKSUM.to_string(),
+                MetadataValue::Str(checksum.to_string()),
+            )])
+        })
+    }
+
+    fn blockfile_flushers(&self) -> Vec<&BlockfileFlusher> {
+        vec![
+            &self.user_id_to_id_flusher,
+            &self.id_to_user_id_flusher,
+            &self.id_to_data_flusher,
+            &self.max_offset_id_flusher,
+        ]
+    }
 }
 
 #[derive(Clone)]
@@ -628,6 +826,11 @@ pub struct RecordSegmentReader<'me> {
     user_id_to_id: BlockfileReader<'me, &'me str, u32>,
     id_to_user_id: BlockfileReader<'me, u32, &'me str>,
     id_to_data: BlockfileReader<'me, u32, DataRecord<'me>>,
+    // The same blockfile as `id_to_data`, which only decodes the metadata of the records
+    id_to_metadata: BlockfileReader<'me, u32, DataRecordMetadata>,
+    // Also holds the idempotency keys of the applied records. It is None for segments
+    // written before the max offset id was stored
+    max_offset_id: Option<BlockfileReader<'me, &'me str, u32>>,
     curr_max_offset_id: Arc<AtomicU32>,
 }
 
@@ -635,8 +838,8 @@ pub struct RecordSegmentReader<'me> {
 pub enum RecordSegmentReaderCreationError {
     #[error("Segment uninitialized")]
     UninitializedSegment,
-    #[error("Blockfile Open Error")]
-    BlockfileOpenError(#[from] Box<OpenError>),
+    #[error("Error opening blockfile of segment {0}: {1}")]
+    BlockfileOpenError(SegmentUuid, #[source] Box<OpenError>),
     #[error("Segment has invalid number of files")]
     InvalidNumberOfFiles,
     // This case should never happen, so it's internal, but until our APIs rule it out, we have it.
@@ -650,13 +853,98 @@ pub enum RecordSegmentReaderCreationError {
 impl ChromaError for RecordSegmentReaderCreationError {
     fn code(&self) -> ErrorCodes {
         match self {
-            RecordSegmentReaderCreationError::BlockfileOpenError(e) => e.code(),
+            RecordSegmentReaderCreationError::BlockfileOpenError(_, e) => e.code(),
             RecordSegmentReaderCreationError::InvalidNumberOfFiles => ErrorCodes::InvalidArgument,
             RecordSegmentReaderCreationError::UninitializedSegment => ErrorCodes::InvalidArgument,
             RecordSegmentReaderCreationError::DataRecordNotFound(_) => ErrorCodes::Internal,
             RecordSegmentReaderCreationError::UserRecordNotFound(_) => ErrorCodes::Internal,
         }
     }
+
+    fn location(&self) -> ErrorLocation {
+        match self {
+            RecordSegmentReaderCreationError::BlockfileOpenError(segment_id, e) => {
+                e.location().in_segment(segment_id)
+            }
+            _ => ErrorLocation::default(),
+        }
+    }
+}
+
+/// A simple predicate on a single metadata key of the data records,
+/// which is evaluated against their metadata while scanning the record segment
+#[derive(Clone, Debug)]
+pub(crate) struct RecordScanPredicate {
+    pub(crate) key: String,
+    pub(crate) operator: PrimitiveOperator,
+    pub(crate) value: MetadataValue,
+}
+
+impl RecordScanPredicate {
+    /// The predicate of a where clause that is a single comparison of a metadata key with a
+    /// value, if it is one
+    pub(crate) fn from_where(clause: &Where) -> Option<Self> {
+        match clause {
+            Where::DirectWhereComparison(DirectWhereComparison {
+                key,
+                comparison: WhereComparison::Primitive(operator, value),
+            }) => Some(RecordScanPredicate {
+                key: key.clone(),
+                operator: operator.clone(),
+                value: value.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the metadata of a data record satisfies the predicate
+    /// Values of a different type never compare equal, and records without
+    /// the key only satisfy the `NotEqual` and `NotContains` operators.
+    /// A list satisfies the predicate if any of its elements does, like in the metadata index
+    pub(crate) fn eval(&self, metadata: Option<&Metadata>) -> bool {
+        let elements = match metadata.and_then(|metadata| get_metadata_path(metadata, &self.key)) {
+            Some(value) => value.elements(),
+            None => &[],
+        };
+        // Case-insensitive and prefix matching only apply to strings
+        if let (
+            PrimitiveOperator::EqualCaseInsensitive | PrimitiveOperator::StartsWith,
+            MetadataValue::Str(value),
+        ) = (&self.operator, &self.value)
+        {
+            let lowercase = value.to_lowercase();
+            return elements.iter().any(|element| match element {
+                MetadataValue::Str(s) if self.operator == PrimitiveOperator::StartsWith => {
+                    s.starts_with(value.as_str())
+                }
+                MetadataValue::Str(s) => s.to_lowercase() == lowercase,
+                _ => false,
+            });
+        }
+        let mut orderings = elements
+            .iter()
+            .filter(|element| discriminant(*element) == discriminant(&self.value))
+            .filter_map(|element| element.partial_cmp(&self.value));
+        match self.operator {
+            PrimitiveOperator::Equal | PrimitiveOperator::Contains => {
+                orderings.any(|ordering| ordering == Ordering::Equal)
+            }
+            PrimitiveOperator::NotEqual | PrimitiveOperator::NotContains => {
+                !orderings.any(|ordering| ordering == Ordering::Equal)
+            }
+            PrimitiveOperator::GreaterThan => {
+                orderings.any(|ordering| ordering == Ordering::Greater)
+            }
+            PrimitiveOperator::GreaterThanOrEqual => {
+                orderings.any(|ordering| matches!(ordering, Ordering::Greater | Ordering::Equal))
+            }
+            PrimitiveOperator::LessThan => orderings.any(|ordering| ordering == Ordering::Less),
+            PrimitiveOperator::LessThanOrEqual => {
+                orderings.any(|ordering| matches!(ordering, Ordering::Less | Ordering::Equal))
+            }
+            PrimitiveOperator::EqualCaseInsensitive | PrimitiveOperator::StartsWith => false,
+        }
+    }
 }
 
 impl RecordSegmentReader<'_> {
@@ -664,98 +952,116 @@ impl RecordSegmentReader<'_> {
         segment: &Segment,
         blockfile_provider: &BlockfileProvider,
     ) -> Result<Self, Box<RecordSegmentReaderCreationError>> {
-        let (user_id_to_id, id_to_user_id, id_to_data, existing_max_offset_id) = match segment
-            .file_path
-            .len()
-        {
-            4 => {
-                let user_id_to_id_bf_id = &segment.file_path.get(USER_ID_TO_OFFSET_ID).unwrap()[0];
-                let id_to_user_id_bf_id = &segment.file_path.get(OFFSET_ID_TO_USER_ID).unwrap()[0];
-                let id_to_data_bf_id = &segment.file_path.get(OFFSET_ID_TO_DATA).unwrap()[0];
+        let (user_id_to_id, id_to_user_id, id_to_data, id_to_metadata, max_offset_id) =
+            match segment.file_path.len() {
+                4 => {
+                    let user_id_to_id_bf_id =
+                        &segment.file_path.get(USER_ID_TO_OFFSET_ID).unwrap()[0];
+                    let id_to_user_id_bf_id =
+                        &segment.file_path.get(OFFSET_ID_TO_USER_ID).unwrap()[0];
+                    let id_to_data_bf_id = &segment.file_path.get(OFFSET_ID_TO_DATA).unwrap()[0];
 
-                let max_offset_id_bf_id = match segment.file_path.get(MAX_OFFSET_ID) {
-                    Some(max_offset_id_file_id) => max_offset_id_file_id.first(),
-                    None => None,
-                };
-                let max_offset_id_bf_uuid = match max_offset_id_bf_id {
-                    Some(id) => Uuid::parse_str(id).ok(),
-                    None => None,
-                };
+                    let max_offset_id_bf_id = match segment.file_path.get(MAX_OFFSET_ID) {
+                        Some(max_offset_id_file_id) => max_offset_id_file_id.first(),
+                        None => None,
+                    };
+                    let max_offset_id_bf_uuid = match max_offset_id_bf_id {
+                        Some(id) => Uuid::parse_str(id).ok(),
+                        None => None,
+                    };
 
-                let max_offset_id_bf_reader = match max_offset_id_bf_uuid {
-                    Some(bf_uuid) => match blockfile_provider.read::<&str, u32>(&bf_uuid).await {
-                        Ok(max_offset_id_bf_reader) => Some(max_offset_id_bf_reader),
-                        Err(_) => None,
-                    },
-                    None => None,
-                };
-                let exising_max_offset_id = match max_offset_id_bf_reader {
-                    Some(reader) => match reader.get("", MAX_OFFSET_ID).await {
-                        Ok(Some(max_offset_id)) => Arc::new(AtomicU32::new(max_offset_id)),
-                        Ok(None) | Err(_) => Arc::new(AtomicU32::new(0)),
-                    },
-                    None => Arc::new(AtomicU32::new(0)),
-                };
+                    let max_offset_id_bf_reader = match max_offset_id_bf_uuid {
+                        Some(bf_uuid) => match blockfile_provider.read::<&str, u32>(&bf_uuid).await
+                        {
+                            Ok(max_offset_id_bf_reader) => Some(max_offset_id_bf_reader),
+                            Err(_) => None,
+                        },
+                        None => None,
+                    };
+                    let user_id_to_id = match blockfile_provider
+                        .read::<&str, u32>(&Uuid::parse_str(user_id_to_id_bf_id).unwrap())
+                        .await
+                    {
+                        Ok(user_id_to_id) => user_id_to_id,
+                        Err(e) => {
+                            return Err(Box::new(
+                                RecordSegmentReaderCreationError::BlockfileOpenError(segment.id, e),
+                            ))
+                        }
+                    };
 
-                let user_id_to_id = match blockfile_provider
-                    .read::<&str, u32>(&Uuid::parse_str(user_id_to_id_bf_id).unwrap())
-                    .await
-                {
-                    Ok(user_id_to_id) => user_id_to_id,
-                    Err(e) => {
-                        return Err(Box::new(
-                            RecordSegmentReaderCreationError::BlockfileOpenError(e),
-                        ))
-                    }
-                };
+                    let id_to_user_id = match blockfile_provider
+                        .read::<u32, &str>(&Uuid::parse_str(id_to_user_id_bf_id).unwrap())
+                        .await
+                    {
+                        Ok(id_to_user_id) => id_to_user_id,
+                        Err(e) => {
+                            return Err(Box::new(
+                                RecordSegmentReaderCreationError::BlockfileOpenError(segment.id, e),
+                            ))
+                        }
+                    };
 
-                let id_to_user_id = match blockfile_provider
-                    .read::<u32, &str>(&Uuid::parse_str(id_to_user_id_bf_id).unwrap())
-                    .await
-                {
-                    Ok(id_to_user_id) => id_to_user_id,
-                    Err(e) => {
-                        return Err(Box::new(
-                            RecordSegmentReaderCreationError::BlockfileOpenError(e),
-                        ))
-                    }
-                };
+                    let id_to_data = match blockfile_provider
+                        .read::<u32, DataRecord>(&Uuid::parse_str(id_to_data_bf_id).unwrap())
+                        .await
+                    {
+                        Ok(id_to_data) => id_to_data,
+                        Err(e) => {
+                            return Err(Box::new(
+                                RecordSegmentReaderCreationError::BlockfileOpenError(segment.id, e),
+                            ))
+                        }
+                    };
 
-                let id_to_data = match blockfile_provider
-                    .read::<u32, DataRecord>(&Uuid::parse_str(id_to_data_bf_id).unwrap())
-                    .await
-                {
-                    Ok(id_to_data) => id_to_data,
-                    Err(e) => {
-                        return Err(Box::new(
-                            RecordSegmentReaderCreationError::BlockfileOpenError(e),
-                        ))
-                    }
-                };
+                    let id_to_metadata = match blockfile_provider
+                        .read::<u32, DataRecordMetadata>(
+                            &Uuid::parse_str(id_to_data_bf_id).unwrap(),
+                        )
+                        .await
+                    {
+                        Ok(id_to_metadata) => id_to_metadata,
+                        Err(e) => {
+                            return Err(Box::new(
+                                RecordSegmentReaderCreationError::BlockfileOpenError(segment.id, e),
+                            ))
+                        }
+                    };
 
-                (
-                    user_id_to_id,
-                    id_to_user_id,
-                    id_to_data,
-                    exising_max_offset_id,
-                )
-            }
-            0 => {
-                return Err(Box::new(
-                    RecordSegmentReaderCreationError::UninitializedSegment,
-                ));
-            }
-            _ => {
-                return Err(Box::new(
-                    RecordSegmentReaderCreationError::InvalidNumberOfFiles,
-                ));
-            }
+                    (
+                        user_id_to_id,
+                        id_to_user_id,
+                        id_to_data,
+                        id_to_metadata,
+                        max_offset_id_bf_reader,
+                    )
+                }
+                0 => {
+                    return Err(Box::new(
+                        RecordSegmentReaderCreationError::UninitializedSegment,
+                    ));
+                }
+                _ => {
+                    return Err(Box::new(
+                        RecordSegmentReaderCreationError::InvalidNumberOfFiles,
+                    ));
+                }
+            };
+
+        let existing_max_offset_id = match &max_offset_id {
+            Some(reader) => match reader.get("", MAX_OFFSET_ID).await {
+                Ok(Some(max_offset_id)) => Arc::new(AtomicU32::new(max_offset_id)),
+                Ok(None) | Err(_) => Arc::new(AtomicU32::new(0)),
+            },
+            None => Arc::new(AtomicU32::new(0)),
         };
 
         Ok(RecordSegmentReader {
             user_id_to_id,
             id_to_user_id,
             id_to_data,
+            id_to_metadata,
+            max_offset_id,
             curr_max_offset_id: existing_max_offset_id,
         })
     }
@@ -764,6 +1070,57 @@ impl RecordSegmentReader<'_> {
         self.curr_max_offset_id.clone()
     }
 
+    /// Checks whether a record with the user id was written with the idempotency key
+    /// in the logs that are already compacted into the segment
+    pub(crate) async fn idempotency_key_applied(
+        &self,
+        idempotency_key: &str,
+        user_id: &str,
+    ) -> Result<bool, Box<dyn ChromaError>> {
+        match self.max_offset_id.as_ref() {
+            Some(max_offset_id) => {
+                max_offset_id
+                    .contains(
+                        IDEMPOTENCY_KEY_PREFIX,
+                        idempotency_blockfile_key(idempotency_key, user_id).as_str(),
+                    )
+                    .await
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Returns the offset of the last log that is compacted into the segment, if it is
+    /// recorded. Segments compacted before the offset was recorded have none
+    pub(crate) async fn get_applied_log_offset(&self) -> Result<Option<i64>, Box<dyn ChromaError>> {
+        let Some(max_offset_id) = self.max_offset_id.as_ref() else {
+            return Ok(None);
+        };
+        let low = max_offset_id.get("", APPLIED_LOG_OFFSET_LOW).await?;
+        let high = max_offset_id.get("", APPLIED_LOG_OFFSET_HIGH).await?;
+        let (Some(low), Some(high)) = (low, high) else {
+            return Ok(None);
+        };
+        Ok(Some(((u64::from(high) << 32) | u64::from(low)) as i64))
+    }
+
+    /// Returns the encoded idempotency keys that were applied before the timestamp
+    pub(crate) async fn get_idempotency_keys_applied_before(
+        &self,
+        timestamp: u32,
+    ) -> Result<Vec<String>, Box<dyn ChromaError>> {
+        let Some(max_offset_id) = self.max_offset_id.as_ref() else {
+            return Ok(Vec::new());
+        };
+        max_offset_id
+            .get_range_stream(IDEMPOTENCY_KEY_PREFIX..=IDEMPOTENCY_KEY_PREFIX, ..)
+            .try_filter_map(|(key, applied_at)| {
+                future::ready(Ok((applied_at < timestamp).then(|| key.to_string())))
+            })
+            .try_collect()
+            .await
+    }
+
     pub(crate) async fn get_user_id_for_offset_id(
         &self,
         offset_id: u32,
@@ -784,6 +1141,48 @@ impl RecordSegmentReader<'_> {
         self.user_id_to_id.get("", user_id).await
     }
 
+    /// Returns the offset id of each user id that is in the record segment. The user ids are
+    /// sorted once, so that a long list is merged against the user ids between its smallest and
+    /// largest one in a single pass instead of being looked up one by one
+    pub(crate) async fn get_offset_ids_by_user_id<'ids>(
+        &self,
+        user_ids: &[&'ids str],
+    ) -> Result<HashMap<&'ids str, u32>, Box<dyn ChromaError>> {
+        let mut user_ids = user_ids.to_vec();
+        if !user_ids.windows(2).all(|pair| pair[0] <= pair[1]) {
+            user_ids.sort_unstable();
+        }
+        user_ids.dedup();
+        let (first, last) = match (user_ids.first(), user_ids.last()) {
+            (Some(first), Some(last)) => (*first, *last),
+            _ => return Ok(HashMap::new()),
+        };
+        if user_ids.len() <= USER_ID_POINT_LOOKUP_LIMIT {
+            let mut offset_ids = HashMap::with_capacity(user_ids.len());
+            for user_id in user_ids {
+                if let Some(offset_id) = self.user_id_to_id.get("", user_id).await? {
+                    offset_ids.insert(user_id, offset_id);
+                }
+            }
+            return Ok(offset_ids);
+        }
+        self.user_id_to_id
+            .get_range_stream(""..="", first..=last)
+            .try_fold(HashMap::new(), |mut offset_ids, (user_id, offset_id)| {
+                if let Ok(index) = user_ids.binary_search_by(|probe| (*probe).cmp(user_id)) {
+                    offset_ids.insert(user_ids[index], offset_id);
+                }
+                future::ready(Ok(offset_ids))
+            })
+            .await
+    }
+
+    /// The id of the user id to offset id blockfile, which changes whenever the segment is
+    /// compacted
+    pub(crate) fn user_id_to_offset_id_blockfile_id(&self) -> Uuid {
+        self.user_id_to_id.id()
+    }
+
     pub(crate) async fn get_data_for_offset_id(
         &self,
         offset_id: u32,
@@ -791,6 +1190,66 @@ impl RecordSegmentReader<'_> {
         self.id_to_data.get("", offset_id).await
     }
 
+    /// Returns the data records for the offset ids, in the same order as the offset ids
+    ///
+    /// The blocks holding the offset ids are fetched concurrently, and each block is fetched
+    /// once rather than once per offset id that it holds
+    pub(crate) async fn get_data_for_offset_ids(
+        &self,
+        offset_ids: &[u32],
+    ) -> Result<Vec<DataRecord<'_>>, Box<dyn ChromaError>> {
+        let data = self.get_data_for_offset_ids_if_present(offset_ids).await?;
+        offset_ids
+            .iter()
+            .zip(data)
+            .map(|(offset_id, data_record)| {
+                data_record.ok_or_else(|| {
+                    Box::new(RecordSegmentReaderCreationError::DataRecordNotFound(
+                        *offset_id,
+                    )) as Box<dyn ChromaError>
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the data records for the offset ids, in the same order as the offset ids, or
+    /// `None` for the offset ids that are not in the record segment
+    pub(crate) async fn get_data_for_offset_ids_if_present(
+        &self,
+        offset_ids: &[u32],
+    ) -> Result<Vec<Option<DataRecord<'_>>>, Box<dyn ChromaError>> {
+        self.id_to_data.get_many("", offset_ids.to_vec()).await
+    }
+
+    /// Returns the data records and offset ids for the user ids, in the same order as the
+    /// user ids, or `None` for the user ids that are not in the record segment
+    ///
+    /// Like `get_data_for_offset_ids`, the blocks are fetched concurrently for all the user ids
+    pub(crate) async fn get_data_and_offset_ids_for_user_ids(
+        &self,
+        user_ids: &[&str],
+    ) -> Result<Vec<Option<(DataRecord<'_>, u32)>>, Box<dyn ChromaError>> {
+        let offset_ids = self.user_id_to_id.get_many("", user_ids.to_vec()).await?;
+        let found_offset_ids = offset_ids.iter().flatten().copied().collect::<Vec<_>>();
+        let mut found_data = self
+            .id_to_data
+            .get_many("", found_offset_ids)
+            .await?
+            .into_iter();
+        let mut data = Vec::with_capacity(offset_ids.len());
+        for offset_id in offset_ids {
+            let data_record = match offset_id {
+                Some(offset_id) => found_data
+                    .next()
+                    .flatten()
+                    .map(|data_record| (data_record, offset_id)),
+                None => None,
+            };
+            data.push(data_record);
+        }
+        Ok(data)
+    }
+
     pub(crate) async fn get_data_and_offset_id_for_user_id(
         &self,
         user_id: &str,
@@ -811,6 +1270,14 @@ impl RecordSegmentReader<'_> {
         }
     }
 
+    /// Checks whether the record segment has a record with the offset id
+    pub(crate) async fn offset_id_exists(
+        &self,
+        offset_id: u32,
+    ) -> Result<bool, Box<dyn ChromaError>> {
+        self.id_to_user_id.contains("", offset_id).await
+    }
+
     pub(crate) async fn data_exists_for_user_id(
         &self,
         user_id: &str,
@@ -832,35 +1299,78 @@ impl RecordSegmentReader<'_> {
 
     /// Returns all data in the record segment, sorted by
     /// embedding id
+    ///
+    /// The offset ids are streamed from the user id blockfile, and the data
+    /// records are then read in batch, so that each block is fetched once
     #[allow(dead_code)]
     pub(crate) async fn get_all_data(&self) -> Result<Vec<DataRecord>, Box<dyn ChromaError>> {
-        let mut data = Vec::new();
-        let max_size = self.user_id_to_id.count().await?;
-        for i in 0..max_size {
-            let res = self.user_id_to_id.get_at_index(i).await;
-            match res {
-                Ok((_, _, offset_id)) => {
-                    if let Some(data_record) = self.id_to_data.get("", offset_id).await? {
-                        data.push(data_record);
-                    } else {
-                        return Err(
-                            Box::new(RecordSegmentReaderCreationError::DataRecordNotFound(
-                                offset_id,
-                            )) as _,
-                        );
-                    }
-                }
-                Err(e) => {
-                    tracing::error!(
-                        "[GetAllData] Error getting data record for index {:?}: {:?}",
-                        i,
-                        e
-                    );
-                    return Err(e);
-                }
+        let offset_ids: Vec<u32> = self
+            .user_id_to_id
+            .get_range_stream(""..="", ..)
+            .map_ok(|(_, offset_id)| offset_id)
+            .try_collect()
+            .await?;
+        self.get_data_for_offset_ids(&offset_ids).await
+    }
+
+    /// Scans the data records in the record segment in offset id order,
+    /// skipping the records that do not satisfy the predicate (if any)
+    ///
+    /// The predicate is evaluated against the metadata of the records, so
+    /// only the records that satisfy it are decoded in full
+    ///
+    /// The blocks are fetched as the stream is polled while the next blocks
+    /// are read ahead, so a full scan should use this instead of looking up
+    /// each offset id
+    pub(crate) fn scan<'a>(
+        &'a self,
+        predicate: Option<RecordScanPredicate>,
+    ) -> impl Stream<Item = Result<(u32, DataRecord<'a>), Box<dyn ChromaError>>> + 'a {
+        match predicate {
+            Some(predicate) => {
+                self.scan_offset_ids(predicate)
+                    .try_filter_map(|offset_id| future::ready(Ok(offset_id)))
+                    .and_then(move |offset_id| async move {
+                        match self.id_to_data.get("", offset_id).await? {
+                            Some(record) => Ok((offset_id, record)),
+                            None => Err(Box::new(
+                                RecordSegmentReaderCreationError::DataRecordNotFound(offset_id),
+                            ) as Box<dyn ChromaError>),
+                        }
+                    })
+                    .left_stream()
             }
+            None => self.id_to_data.get_range_stream(""..="", ..).right_stream(),
         }
-        Ok(data)
+    }
+
+    /// Scans the data records in offset id order, and yields the offset id of each record
+    /// that satisfies the predicate, or `None` for each record that does not, so that the
+    /// scan can be bounded by the number of records read. Only the metadata of the records
+    /// is decoded
+    pub(crate) fn scan_offset_ids<'a>(
+        &'a self,
+        predicate: RecordScanPredicate,
+    ) -> impl Stream<Item = Result<Option<u32>, Box<dyn ChromaError>>> + 'a {
+        self.id_to_metadata.get_range_stream(""..="", ..).map_ok(
+            move |(offset_id, DataRecordMetadata(metadata))| {
+                predicate.eval(metadata.as_ref()).then_some(offset_id)
+            },
+        )
+    }
+
+    /// Streams the mapping from user ids to offset ids, in user id order
+    pub(crate) fn user_id_to_offset_id_stream<'a>(
+        &'a self,
+    ) -> impl Stream<Item = Result<(&'a str, u32), Box<dyn ChromaError>>> + 'a {
+        self.user_id_to_id.get_range_stream(""..="", ..)
+    }
+
+    /// Streams the mapping from offset ids to user ids, in offset id order
+    pub(crate) fn offset_id_to_user_id_stream<'a>(
+        &'a self,
+    ) -> impl Stream<Item = Result<(u32, &'a str), Box<dyn ChromaError>>> + 'a {
+        self.id_to_user_id.get_range_stream(""..="", ..)
     }
 
     pub(crate) async fn get_offset_id_at_index(
@@ -880,6 +1390,12 @@ impl RecordSegmentReader<'_> {
         }
     }
 
+    /// Reads ahead the blocks of the offset ids after the offset id in the background, for a
+    /// scan of the offset ids by index
+    pub(crate) fn readahead_offset_ids(&self, offset_id: u32, blocks: usize) {
+        self.id_to_user_id.readahead("", offset_id, blocks)
+    }
+
     // Find the rank of the given offset id in the record segment
     // The implemention is based on std binary search
     pub(crate) async fn get_offset_id_rank(
@@ -917,12 +1433,17 @@ impl RecordSegmentReader<'_> {
         self.id_to_user_id.count().await
     }
 
+    /// Loads all of the blocks mapping between user ids and offset ids, which are looked up by
+    /// most queries and are much smaller than the records. Returns the number of blocks
+    pub(crate) async fn load_id_blocks(&self) -> usize {
+        self.user_id_to_id.load_all_blocks().await + self.id_to_user_id.load_all_blocks().await
+    }
+
     pub(crate) async fn prefetch_id_to_data(&self, keys: &[u32]) {
         let prefixes = vec![""; keys.len()];
         self.id_to_data.load_blocks_for_keys(&prefixes, keys).await
     }
 
-    #[allow(dead_code)]
     pub(crate) async fn prefetch_user_id_to_id(&self, keys: Vec<&str>) {
         let prefixes = vec![""; keys.len()];
         self.user_id_to_id
@@ -937,3 +1458,185 @@ impl RecordSegmentReader<'_> {
             .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chroma_types::{MetadataValue, PrimitiveOperator};
+    use futures::TryStreamExt;
+
+    use crate::{
+        log::test::{upsert_generator, LogGenerator},
+        segment::test::TestSegment,
+    };
+
+    use super::{RecordScanPredicate, RecordSegmentReader, RecordSegmentWriter};
+
+    async fn scan_ids(
+        reader: &RecordSegmentReader<'_>,
+        predicate: Option<RecordScanPredicate>,
+    ) -> Vec<u32> {
+        reader
+            .scan(predicate)
+            .map_ok(|(offset_id, _)| offset_id)
+            .try_collect()
+            .await
+            .expect("Record segment should be scanned")
+    }
+
+    #[tokio::test]
+    async fn test_scan_with_predicate() {
+        let mut test_segment = TestSegment::default();
+        test_segment
+            .populate_with_generator(
+                30,
+                &LogGenerator {
+                    generator: upsert_generator,
+                },
+            )
+            .await;
+        let reader = RecordSegmentReader::from_segment(
+            &test_segment.record_segment,
+            &test_segment.blockfile_provider,
+        )
+        .await
+        .expect("Record segment reader should be created");
+
+        assert_eq!(scan_ids(&reader, None).await, (1..=30).collect::<Vec<_>>());
+
+        let modulo_3 = RecordScanPredicate {
+            key: "modulo_3".to_string(),
+            operator: PrimitiveOperator::Equal,
+            value: MetadataValue::Int(0),
+        };
+        assert_eq!(
+            scan_ids(&reader, Some(modulo_3)).await,
+            (3..=30).step_by(3).collect::<Vec<_>>()
+        );
+
+        let greater = RecordScanPredicate {
+            key: "id".to_string(),
+            operator: PrimitiveOperator::GreaterThan,
+            value: MetadataValue::Int(25),
+        };
+        assert_eq!(
+            scan_ids(&reader, Some(greater)).await,
+            (26..=30).collect::<Vec<_>>()
+        );
+
+        // Values of another type never compare equal, and missing keys only satisfy `NotEqual`
+        let mismatched = RecordScanPredicate {
+            key: "is_even".to_string(),
+            operator: PrimitiveOperator::Equal,
+            value: MetadataValue::Int(1),
+        };
+        assert!(scan_ids(&reader, Some(mismatched)).await.is_empty());
+        let missing = RecordScanPredicate {
+            key: "missing".to_string(),
+            operator: PrimitiveOperator::NotEqual,
+            value: MetadataValue::Int(1),
+        };
+        assert_eq!(scan_ids(&reader, Some(missing)).await.len(), 30);
+    }
+
+    #[tokio::test]
+    async fn test_batched_reads() {
+        let mut test_segment = TestSegment::default();
+        test_segment
+            .populate_with_generator(
+                300,
+                &LogGenerator {
+                    generator: upsert_generator,
+                },
+            )
+            .await;
+        let reader = RecordSegmentReader::from_segment(
+            &test_segment.record_segment,
+            &test_segment.blockfile_provider,
+        )
+        .await
+        .expect("Record segment reader should be created");
+
+        // The records are returned in the order of the offset ids rather than that of the blocks
+        let offset_ids = [250, 1, 120, 1];
+        let data = reader
+            .get_data_for_offset_ids(&offset_ids)
+            .await
+            .expect("Records should be read");
+        assert_eq!(
+            data.iter().map(|record| record.id).collect::<Vec<_>>(),
+            vec!["id_250", "id_1", "id_120", "id_1"]
+        );
+
+        // A missing offset id fails the strict read, but not the lenient one
+        assert!(reader.get_data_for_offset_ids(&[1, 1000]).await.is_err());
+        let data = reader
+            .get_data_for_offset_ids_if_present(&[1000, 42])
+            .await
+            .expect("Records should be read");
+        assert_eq!(
+            data.iter()
+                .map(|record| record.as_ref().map(|record| record.id))
+                .collect::<Vec<_>>(),
+            vec![None, Some("id_42")]
+        );
+
+        let data = reader
+            .get_data_and_offset_ids_for_user_ids(&["id_299", "missing", "id_2"])
+            .await
+            .expect("Records should be read");
+        assert_eq!(
+            data.iter()
+                .map(|record| record
+                    .as_ref()
+                    .map(|(record, offset_id)| (record.id, *offset_id)))
+                .collect::<Vec<_>>(),
+            vec![Some(("id_299", 299)), None, Some(("id_2", 2))]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_applied_log_offset_beyond_u32() {
+        let mut test_segment = TestSegment::default();
+        test_segment
+            .populate_with_generator(
+                10,
+                &LogGenerator {
+                    generator: upsert_generator,
+                },
+            )
+            .await;
+        let writer = RecordSegmentWriter::from_segment(
+            &test_segment.record_segment,
+            &test_segment.blockfile_provider,
+        )
+        .await
+        .expect("Record segment writer should be created");
+        assert!(writer.set_applied_log_offset(-1).await.is_err());
+        let log_offset = i64::from(u32::MAX) + 7;
+        writer
+            .set_applied_log_offset(log_offset)
+            .await
+            .expect("Applied log offset should be set");
+        test_segment.record_segment.file_path = writer
+            .commit()
+            .await
+            .expect("Record segment writer should be committed")
+            .flush()
+            .await
+            .expect("Record segment writer should be flushed");
+
+        let reader = RecordSegmentReader::from_segment(
+            &test_segment.record_segment,
+            &test_segment.blockfile_provider,
+        )
+        .await
+        .expect("Record segment reader should be created");
+        assert_eq!(
+            reader
+                .get_applied_log_offset()
+                .await
+                .expect("Applied log offset should be read"),
+            Some(log_offset)
+        );
+    }
+}
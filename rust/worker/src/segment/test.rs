@@ -10,6 +10,8 @@ use chroma_types::{
 use crate::log::test::{LogGenerator, TEST_EMBEDDING_DIMENSION};
 
 use super::{
+    distributed_hnsw_segment::DistributedHNSWSegmentWriter,
+    materialized_filter::materialized_filters_from_collection,
     metadata_segment::MetadataSegmentWriter, record_segment::RecordSegmentWriter, LogMaterializer,
     SegmentFlusher, SegmentWriter,
 };
@@ -25,9 +27,12 @@ pub struct TestSegment {
 
 impl TestSegment {
     // WARN: The size of the log chunk should not be too large
-    async fn compact_log(&mut self, logs: Chunk<LogRecord>, offset: usize) {
-        let materializer =
-            LogMaterializer::new(None, logs, Some(AtomicU32::new(offset as u32).into()));
+    pub async fn compact_log(&mut self, logs: Chunk<LogRecord>, offset: usize) {
+        let materializer = LogMaterializer::new(
+            None,
+            logs.clone(),
+            Some(AtomicU32::new(offset as u32).into()),
+        );
         let materialized_logs = materializer
             .materialize()
             .await
@@ -37,6 +42,14 @@ impl TestSegment {
             MetadataSegmentWriter::from_segment(&self.metadata_segment, &self.blockfile_provider)
                 .await
                 .expect("Should be able to initialize metadata writer.");
+        metadata_writer
+            .set_materialized_filters(
+                &self.metadata_segment,
+                &self.blockfile_provider,
+                materialized_filters_from_collection(&self.collection),
+            )
+            .await
+            .expect("Should be able to set materialized filters.");
         metadata_writer
             .apply_materialized_log_chunk(materialized_logs.clone())
             .await
@@ -45,10 +58,17 @@ impl TestSegment {
             .write_to_blockfiles()
             .await
             .expect("Should be able to write to blockfile.");
-        self.metadata_segment.file_path = metadata_writer
+        let metadata_flusher = metadata_writer
             .commit()
             .await
-            .expect("Should be able to commit metadata.")
+            .expect("Should be able to commit metadata.");
+        if let Some(metadata) = metadata_flusher.metadata() {
+            self.metadata_segment
+                .metadata
+                .get_or_insert_with(Default::default)
+                .extend(metadata);
+        }
+        self.metadata_segment.file_path = metadata_flusher
             .flush()
             .await
             .expect("Should be able to flush metadata.");
@@ -61,16 +81,75 @@ impl TestSegment {
             .apply_materialized_log_chunk(materialized_logs)
             .await
             .expect("Should be able to apply materialized log.");
+        record_writer
+            .apply_idempotency_keys(&logs)
+            .await
+            .expect("Should be able to apply idempotency keys.");
 
-        self.record_segment.file_path = record_writer
+        let record_flusher = record_writer
             .commit()
             .await
-            .expect("Should be able to commit metadata.")
+            .expect("Should be able to commit metadata.");
+        if let Some(metadata) = record_flusher.metadata() {
+            self.record_segment
+                .metadata
+                .get_or_insert_with(Default::default)
+                .extend(metadata);
+        }
+        self.record_segment.file_path = record_flusher
             .flush()
             .await
             .expect("Should be able to flush metadata.");
     }
 
+    // Indexes the embeddings of the logs in the vector segment, with the same offset ids as
+    // `compact_log` assigns to them
+    pub async fn compact_vector_log(&mut self, logs: Chunk<LogRecord>, offset: usize) {
+        let materializer =
+            LogMaterializer::new(None, logs, Some(AtomicU32::new(offset as u32).into()));
+        let materialized_logs = materializer
+            .materialize()
+            .await
+            .expect("Should be able to materialize log.");
+
+        let hnsw_writer = DistributedHNSWSegmentWriter::from_segment(
+            &self.vector_segment,
+            TEST_EMBEDDING_DIMENSION,
+            self.hnsw_provider.clone(),
+        )
+        .await
+        .expect("Should be able to initialize hnsw writer.");
+        hnsw_writer
+            .apply_materialized_log_chunk(materialized_logs)
+            .await
+            .expect("Should be able to apply materialized log.");
+        self.vector_segment.file_path = hnsw_writer
+            .commit()
+            .await
+            .expect("Should be able to commit hnsw index.")
+            .flush()
+            .await
+            .expect("Should be able to flush hnsw index.");
+    }
+
+    // Like `populate_with_generator`, but also indexes the embeddings in the vector segment
+    pub async fn populate_with_vectors<G>(&mut self, size: usize, generator: &LogGenerator<G>)
+    where
+        G: Fn(usize) -> OperationRecord,
+    {
+        let ids: Vec<_> = (1..=size).collect();
+        for chunk in ids.chunks(100) {
+            let logs = generator.generate_chunk(chunk.iter().copied());
+            let offset = chunk
+                .first()
+                .copied()
+                .expect("The chunk of offset ids to generate should not be empty.")
+                - 1;
+            self.compact_log(logs.clone(), offset).await;
+            self.compact_vector_log(logs, offset).await;
+        }
+    }
+
     pub async fn populate_with_generator<G>(&mut self, size: usize, generator: &LogGenerator<G>)
     where
         G: Fn(usize) -> OperationRecord,
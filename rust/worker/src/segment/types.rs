@@ -1,14 +1,18 @@
 use async_trait::async_trait;
+use chroma_blockstore::BlockfileFlusher;
 use chroma_error::{ChromaError, ErrorCodes};
 use chroma_types::{
     Chunk, DataRecord, DeletedMetadata, LogRecord, MaterializedLogOperation, Metadata,
     MetadataDelta, MetadataValue, MetadataValueConversionError, Operation, OperationRecord,
     UpdateMetadata, UpdateMetadataValue,
 };
+use futures::{Stream, TryStreamExt};
+use roaring::RoaringBitmap;
 use std::collections::{HashMap, HashSet};
 use std::sync::atomic::AtomicU32;
 use std::sync::Arc;
 use thiserror::Error;
+use tokio::sync::OnceCell;
 use tracing::{Instrument, Span};
 
 use super::record_segment::{
@@ -98,6 +102,8 @@ pub enum LogMaterializerError {
     MetadataMaterialization(#[from] MetadataValueConversionError),
     #[error("Error materializing document embedding")]
     EmbeddingMaterialization,
+    #[error("The referenced embedding of record {0} is not resolved until it is compacted")]
+    UnresolvedEmbedding(String),
     #[error("Error reading record segment {0}")]
     RecordSegment(#[from] Box<dyn ChromaError>),
 }
@@ -107,11 +113,66 @@ impl ChromaError for LogMaterializerError {
         match self {
             LogMaterializerError::MetadataMaterialization(e) => e.code(),
             LogMaterializerError::EmbeddingMaterialization => ErrorCodes::Internal,
+            LogMaterializerError::UnresolvedEmbedding(_) => ErrorCodes::FailedPrecondition,
             LogMaterializerError::RecordSegment(e) => e.code(),
         }
     }
 }
 
+/// The fields of the records that a `LogMaterializer` merges from the logs and the record
+/// segment. The offset ids, user ids and final operations are always materialized, while
+/// the fields that are not requested are left empty in the materialized records, so their
+/// accessors should not be used
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MaterializedLogFields {
+    pub metadata: bool,
+    pub document: bool,
+    pub uri: bool,
+    pub embedding: bool,
+}
+
+impl MaterializedLogFields {
+    pub const ALL: Self = Self {
+        metadata: true,
+        document: true,
+        uri: true,
+        embedding: true,
+    };
+
+    pub const OFFSET_IDS: Self = Self {
+        metadata: false,
+        document: false,
+        uri: false,
+        embedding: false,
+    };
+
+    /// Whether the data of the records in the record segment has to be read
+    fn reads_data(&self) -> bool {
+        self.metadata || self.document || self.uri || self.embedding
+    }
+
+    /// The fields that are requested by either of the masks
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            metadata: self.metadata || other.metadata,
+            document: self.document || other.document,
+            uri: self.uri || other.uri,
+            embedding: self.embedding || other.embedding,
+        }
+    }
+
+    /// Whether every field requested by `other` is also requested by this mask
+    pub fn contains(&self, other: &Self) -> bool {
+        self.union(*other) == *self
+    }
+}
+
+impl Default for MaterializedLogFields {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct MaterializedLogRecord<'referred_data> {
     // This is the data record read from the record segment for this id.
@@ -150,6 +211,9 @@ pub struct MaterializedLogRecord<'referred_data> {
     // E.g. if log has [Insert(str0), Update(str1), Update(str2), Update()] then this will contain
     // str2. None if final operation is Delete.
     pub(crate) final_document: Option<&'referred_data str>,
+    // Similar to above, this is the final uri obtained
+    // from the last non null operation. None if final operation is Delete.
+    pub(crate) final_uri: Option<&'referred_data str>,
     // Similar to above, this is the final embedding obtained
     // from the last non null operation.
     // E.g. if log has [Insert(emb0), Update(emb1), Update(emb2), Update()]
@@ -197,6 +261,21 @@ impl<'referred_data> MaterializedLogRecord<'referred_data> {
         };
     }
 
+    pub(crate) fn merged_uri_ref(&self) -> Option<&str> {
+        if self.final_operation == MaterializedLogOperation::OverwriteExisting
+            || self.final_operation == MaterializedLogOperation::AddNew
+        {
+            return self.final_uri;
+        }
+        match self.final_uri {
+            Some(uri) => Some(uri),
+            None => self
+                .data_record
+                .as_ref()
+                .and_then(|data_record| data_record.uri),
+        }
+    }
+
     // Performs a deep copy of the user id so only use it if really
     // needed. If you only need reference then use merged_user_id_ref below.
     pub(crate) fn merged_user_id(&self) -> String {
@@ -317,6 +396,32 @@ impl<'referred_data> MaterializedLogRecord<'referred_data> {
         final_metadata
     }
 
+    // Returns a reference to a single key and value of the merged metadata, without merging the rest of the metadata.
+    pub(crate) fn merged_metadata_entry(&self, key: &str) -> Option<(&str, &MetadataValue)> {
+        if let Some(meta) = &self.metadata_to_be_deleted {
+            if meta.contains(key) {
+                return None;
+            }
+        }
+        if let Some((meta_key, meta_val)) = self
+            .metadata_to_be_merged
+            .as_ref()
+            .and_then(|meta| meta.get_key_value(key))
+        {
+            return Some((meta_key, meta_val));
+        }
+        if self.final_operation == MaterializedLogOperation::OverwriteExisting
+            || self.final_operation == MaterializedLogOperation::AddNew
+        {
+            return None;
+        }
+        self.data_record
+            .as_ref()
+            .and_then(|data_record| data_record.metadata.as_ref())
+            .and_then(|meta| meta.get_key_value(key))
+            .map(|(meta_key, meta_val)| (meta_key.as_str(), meta_val))
+    }
+
     pub(crate) fn merged_embeddings(&self) -> &[f32] {
         if self.final_operation == MaterializedLogOperation::OverwriteExisting
             || self.final_operation == MaterializedLogOperation::AddNew
@@ -350,6 +455,7 @@ impl<'referred_data> From<(DataRecord<'referred_data>, u32)>
             metadata_to_be_merged: None,
             metadata_to_be_deleted: None,
             final_document: None,
+            final_uri: None,
             final_embedding: None,
         }
     }
@@ -366,12 +472,23 @@ impl<'referred_data> TryFrom<(&'referred_data OperationRecord, u32, &'referred_d
     fn try_from(
         log_operation_info: (&'referred_data OperationRecord, u32, &'referred_data str),
     ) -> Result<Self, Self::Error> {
-        let log_record = log_operation_info.0;
-        let offset_id = log_operation_info.1;
-        let user_id = log_operation_info.2;
+        let (log_record, offset_id, user_id) = log_operation_info;
+        Self::from_log_record(log_record, offset_id, user_id, MaterializedLogFields::ALL)
+    }
+}
+
+impl<'referred_data> MaterializedLogRecord<'referred_data> {
+    // Creates a materialized log record like the TryFrom implementation above,
+    // but only materializes the requested fields.
+    fn from_log_record(
+        log_record: &'referred_data OperationRecord,
+        offset_id: u32,
+        user_id: &'referred_data str,
+        fields: MaterializedLogFields,
+    ) -> Result<Self, LogMaterializerError> {
         let merged_metadata;
         let deleted_metadata;
-        match &log_record.metadata {
+        match log_record.metadata.as_ref().filter(|_| fields.metadata) {
             Some(metadata) => match materialize_update_metadata(metadata) {
                 Ok(m) => {
                     merged_metadata = Some(m.0);
@@ -387,10 +504,18 @@ impl<'referred_data> TryFrom<(&'referred_data OperationRecord, u32, &'referred_d
             }
         };
 
-        let document = log_record.document.as_deref();
-        let embedding = match &log_record.embedding {
-            Some(embedding) => Some(embedding.as_slice()),
-            None => {
+        let document = log_record.document.as_deref().filter(|_| fields.document);
+        let uri = log_record.uri.as_deref().filter(|_| fields.uri);
+        let embedding = match (&log_record.embedding, &log_record.embedding_reference) {
+            (Some(embedding), _) => Some(embedding.as_slice()).filter(|_| fields.embedding),
+            // The referenced embedding is only resolved when the logs are compacted
+            (None, Some(_)) if fields.embedding => {
+                return Err(LogMaterializerError::UnresolvedEmbedding(
+                    log_record.id.clone(),
+                ));
+            }
+            (None, Some(_)) => None,
+            (None, None) => {
                 return Err(LogMaterializerError::EmbeddingMaterialization);
             }
         };
@@ -403,9 +528,104 @@ impl<'referred_data> TryFrom<(&'referred_data OperationRecord, u32, &'referred_d
             metadata_to_be_merged: merged_metadata,
             metadata_to_be_deleted: deleted_metadata,
             final_document: document,
+            final_uri: uri,
             final_embedding: embedding,
         })
     }
+
+    // Merges the requested fields of an update in the log into the record.
+    fn apply_update(
+        &mut self,
+        log_record: &'referred_data OperationRecord,
+        fields: MaterializedLogFields,
+    ) -> Result<(), LogMaterializerError> {
+        if fields.metadata {
+            let (metadata_to_be_merged, metadata_to_be_deleted) = merge_update_metadata(
+                (&self.metadata_to_be_merged, &self.metadata_to_be_deleted),
+                &log_record.metadata,
+            )?;
+            self.metadata_to_be_merged = metadata_to_be_merged;
+            self.metadata_to_be_deleted = metadata_to_be_deleted;
+        }
+        if let Some(doc) = log_record.document.as_ref().filter(|_| fields.document) {
+            self.final_document = Some(doc);
+        }
+        if let Some(uri) = log_record.uri.as_ref().filter(|_| fields.uri) {
+            self.final_uri = Some(uri);
+        }
+        if fields.embedding
+            && log_record.embedding.is_none()
+            && log_record.embedding_reference.is_some()
+        {
+            return Err(LogMaterializerError::UnresolvedEmbedding(
+                log_record.id.clone(),
+            ));
+        }
+        if let Some(emb) = log_record.embedding.as_ref().filter(|_| fields.embedding) {
+            self.final_embedding = Some(emb.as_slice());
+        }
+        Ok(())
+    }
+}
+
+// The logs seen so far, which tell the logs that the materializer skips apart
+#[derive(Default)]
+struct SkippedLogs {
+    // The offset of the last log that is compacted into the record segment
+    applied_log_offset: Option<i64>,
+    delivered_log_offsets: HashSet<i64>,
+    // The idempotency keys and user ids of the writes seen so far
+    applied_writes: HashSet<(String, String)>,
+}
+
+impl SkippedLogs {
+    async fn new(
+        record_segment_reader: Option<&RecordSegmentReader<'_>>,
+    ) -> Result<Self, LogMaterializerError> {
+        let applied_log_offset = match record_segment_reader {
+            Some(reader) => reader
+                .get_applied_log_offset()
+                .await
+                .map_err(LogMaterializerError::RecordSegment)?,
+            None => None,
+        };
+        Ok(Self {
+            applied_log_offset,
+            ..Default::default()
+        })
+    }
+
+    // Whether a record with the same user id was written with the same idempotency key before,
+    // either in the logs seen so far or in the logs that are already compacted
+    async fn retried(
+        &mut self,
+        record_segment_reader: Option<&RecordSegmentReader<'_>>,
+        log_record: &LogRecord,
+    ) -> Result<bool, LogMaterializerError> {
+        let Some(idempotency_key) = log_record.record.idempotency_key.as_deref() else {
+            return Ok(false);
+        };
+        let compacted = match record_segment_reader {
+            Some(reader) => reader
+                .idempotency_key_applied(idempotency_key, &log_record.record.id)
+                .await
+                .map_err(LogMaterializerError::RecordSegment)?,
+            None => false,
+        };
+        Ok(compacted
+            || !self
+                .applied_writes
+                .insert((idempotency_key.to_string(), log_record.record.id.clone())))
+    }
+
+    // Whether the offset of the log is already compacted into the segment, or a log with the
+    // same offset was seen before
+    fn redelivered(&mut self, log_record: &LogRecord) -> bool {
+        let compacted = self
+            .applied_log_offset
+            .is_some_and(|applied| log_record.log_offset <= applied);
+        compacted || !self.delivered_log_offsets.insert(log_record.log_offset)
+    }
 }
 
 pub struct LogMaterializer<'me> {
@@ -417,6 +637,8 @@ pub struct LogMaterializer<'me> {
     // for materializing. Writers pass this value to the materializer
     // because they need to share this across all log partitions.
     pub(crate) curr_offset_id: Option<Arc<AtomicU32>>,
+    // The fields of the records to materialize. All fields by default.
+    pub(crate) fields: MaterializedLogFields,
 }
 
 impl<'me> LogMaterializer<'me> {
@@ -429,8 +651,91 @@ impl<'me> LogMaterializer<'me> {
             record_segment_reader,
             logs,
             curr_offset_id,
+            fields: MaterializedLogFields::ALL,
         }
     }
+
+    /// Materializes only the given fields of the records, which saves reading the records
+    /// from the record segment and merging their metadata when they are not needed
+    pub fn with_fields(mut self, fields: MaterializedLogFields) -> Self {
+        self.fields = fields;
+        self
+    }
+
+    /// Takes the logs from a stream of batches, one batch at a time. The logs of a batch that
+    /// are retried or redelivered are dropped, and the blocks of the record segment that map the
+    /// user ids of the other logs to their offset ids are loaded, before the next batch is
+    /// polled. A stream that fetches a batch once it is polled is then held back while the
+    /// logs are processed, and only the logs that are materialized are kept
+    pub async fn from_stream<E>(
+        record_segment_reader: Option<RecordSegmentReader<'me>>,
+        mut batches: impl Stream<Item = Result<Chunk<LogRecord>, E>> + Unpin,
+        curr_offset_id: Option<Arc<AtomicU32>>,
+    ) -> Result<Self, E>
+    where
+        E: From<LogMaterializerError>,
+    {
+        let mut skipped_logs = SkippedLogs::new(record_segment_reader.as_ref()).await?;
+        let mut logs = Vec::new();
+        while let Some(batch) = batches.try_next().await? {
+            let num_kept = logs.len();
+            for (log_record, _) in batch.iter() {
+                let retried = skipped_logs
+                    .retried(record_segment_reader.as_ref(), log_record)
+                    .await?;
+                if !skipped_logs.redelivered(log_record) && !retried {
+                    logs.push(log_record.clone());
+                }
+            }
+            if let Some(reader) = record_segment_reader.as_ref() {
+                reader
+                    .prefetch_user_id_to_id(
+                        logs[num_kept..]
+                            .iter()
+                            .map(|log_record| log_record.record.id.as_str())
+                            .collect(),
+                    )
+                    .await;
+            }
+        }
+        Ok(Self::new(
+            record_segment_reader,
+            Chunk::new(logs.into()),
+            curr_offset_id,
+        ))
+    }
+
+    /// Returns the indices of the logs that are retries of a write with the same idempotency key.
+    /// A log is a retry if a record with the same user id was written with the same key before,
+    /// either earlier in the logs or in the logs that are already compacted.
+    async fn retried_log_indices(&self) -> Result<HashSet<usize>, LogMaterializerError> {
+        let mut skipped_logs = SkippedLogs::default();
+        let mut retried = HashSet::new();
+        for (log_record, index) in self.logs.iter() {
+            if skipped_logs
+                .retried(self.record_segment_reader.as_ref(), log_record)
+                .await?
+            {
+                retried.insert(index);
+            }
+        }
+        Ok(retried)
+    }
+
+    /// Returns the indices of the logs that the log service delivered again. A log is
+    /// redelivered if its offset is already compacted into the segment, or if a log with
+    /// the same offset comes earlier in the logs.
+    async fn redelivered_log_indices(&self) -> Result<HashSet<usize>, LogMaterializerError> {
+        let mut skipped_logs = SkippedLogs::new(self.record_segment_reader.as_ref()).await?;
+        let mut redelivered = HashSet::new();
+        for (log_record, index) in self.logs.iter() {
+            if skipped_logs.redelivered(log_record) {
+                redelivered.insert(index);
+            }
+        }
+        Ok(redelivered)
+    }
+
     pub async fn materialize(
         &'me self,
     ) -> Result<Chunk<MaterializedLogRecord<'me>>, LogMaterializerError> {
@@ -460,12 +765,39 @@ impl<'me> LogMaterializer<'me> {
                 };
             }
         }
+        let mut skipped_log_indices = self.retried_log_indices().await?;
+        skipped_log_indices.extend(self.redelivered_log_indices().await?);
         // Populate entries that are present in the record segment.
         let mut existing_id_to_materialized: HashMap<&str, MaterializedLogRecord> = HashMap::new();
         let mut new_id_to_materialized: HashMap<&str, MaterializedLogRecord> = HashMap::new();
         if let Some(reader) = &self.record_segment_reader {
             async {
                 for (log_record, _) in self.logs.iter() {
+                    // Only the offset ids of the existing records are needed if no field is
+                    // materialized, which avoids reading their data.
+                    if !self.fields.reads_data() {
+                        if let Some(offset_id) = reader
+                            .get_offset_id_for_user_id(log_record.record.id.as_str())
+                            .await
+                            .map_err(LogMaterializerError::RecordSegment)?
+                        {
+                            existing_id_to_materialized.insert(
+                                log_record.record.id.as_str(),
+                                MaterializedLogRecord {
+                                    data_record: None,
+                                    offset_id,
+                                    user_id: Some(log_record.record.id.as_str()),
+                                    final_operation: MaterializedLogOperation::Initial,
+                                    metadata_to_be_merged: None,
+                                    metadata_to_be_deleted: None,
+                                    final_document: None,
+                                    final_uri: None,
+                                    final_embedding: None,
+                                },
+                            );
+                        }
+                        continue;
+                    }
                     let exists = match reader
                         .data_exists_for_user_id(log_record.record.id.as_str())
                         .await
@@ -511,7 +843,11 @@ impl<'me> LogMaterializer<'me> {
         // Populate updates to these and fresh records that are being
         // inserted for the first time.
         async {
-            for (log_record, _) in self.logs.iter() {
+            for (log_record, index) in self.logs.iter() {
+                // Skip the retried and the redelivered writes, which have been applied already.
+                if skipped_log_indices.contains(&index) {
+                    continue;
+                }
                 match log_record.record.operation {
                     Operation::Add => {
                         // If this is an add of a record present in the segment then add
@@ -528,11 +864,12 @@ impl<'me> LogMaterializer<'me> {
                                     let curr_val = existing_id_to_materialized.remove(log_record.record.id.as_str()).unwrap();
                                     // Overwrite.
                                     let mut materialized_record =
-                                        match MaterializedLogRecord::try_from((
+                                        match MaterializedLogRecord::from_log_record(
                                             &log_record.record,
                                             curr_val.offset_id,
                                             log_record.record.id.as_str(),
-                                        )) {
+                                            self.fields,
+                                        ) {
                                             Ok(record) => record,
                                             Err(e) => {
                                                 return Err(e);
@@ -556,11 +893,12 @@ impl<'me> LogMaterializer<'me> {
                         else if !new_id_to_materialized.contains_key(log_record.record.id.as_str()) {
                             let next_offset_id =
                                 next_offset_id.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-                            let materialized_record = match MaterializedLogRecord::try_from((
+                            let materialized_record = match MaterializedLogRecord::from_log_record(
                                 &log_record.record,
                                 next_offset_id,
                                 log_record.record.id.as_str(),
-                            )) {
+                                self.fields,
+                            ) {
                                 Ok(record) => record,
                                 Err(e) => {
                                     return Err(e);
@@ -587,6 +925,7 @@ impl<'me> LogMaterializer<'me> {
                                 .unwrap();
                             record_from_map.final_operation = MaterializedLogOperation::DeleteExisting;
                             record_from_map.final_document = None;
+                            record_from_map.final_uri = None;
                             record_from_map.final_embedding = None;
                             record_from_map.metadata_to_be_merged = None;
                             record_from_map.metadata_to_be_deleted = None;
@@ -618,27 +957,7 @@ impl<'me> LogMaterializer<'me> {
                             },
                         };
 
-                        match merge_update_metadata(
-                            (
-                                &record_from_map.metadata_to_be_merged,
-                                &record_from_map.metadata_to_be_deleted,
-                            ),
-                            &log_record.record.metadata,
-                        ) {
-                            Ok(meta) => {
-                                record_from_map.metadata_to_be_merged = meta.0;
-                                record_from_map.metadata_to_be_deleted = meta.1;
-                            }
-                            Err(e) => {
-                                return Err(LogMaterializerError::MetadataMaterialization(e));
-                            }
-                        };
-                        if let Some(doc) = log_record.record.document.as_ref() {
-                            record_from_map.final_document = Some(doc);
-                        }
-                        if let Some(emb) = log_record.record.embedding.as_ref() {
-                            record_from_map.final_embedding = Some(emb.as_slice());
-                        }
+                        record_from_map.apply_update(&log_record.record, self.fields)?;
                         match record_from_map.final_operation {
                             MaterializedLogOperation::Initial => {
                                 record_from_map.final_operation =
@@ -667,11 +986,12 @@ impl<'me> LogMaterializer<'me> {
                                     let curr_val = existing_id_to_materialized.remove(log_record.record.id.as_str()).unwrap();
                                     // Overwrite.
                                     let mut materialized_record =
-                                        match MaterializedLogRecord::try_from((
+                                        match MaterializedLogRecord::from_log_record(
                                             &log_record.record,
                                             curr_val.offset_id,
                                             log_record.record.id.as_str(),
-                                        )) {
+                                            self.fields,
+                                        ) {
                                             Ok(record) => record,
                                             Err(e) => {
                                                 return Err(e);
@@ -687,21 +1007,7 @@ impl<'me> LogMaterializer<'me> {
                                 MaterializedLogOperation::Initial | MaterializedLogOperation::OverwriteExisting | MaterializedLogOperation::UpdateExisting => {
                                     // Update.
                                     let record_from_map = existing_id_to_materialized.get_mut(log_record.record.id.as_str()).unwrap();
-                                    match merge_update_metadata((&record_from_map.metadata_to_be_merged, &record_from_map.metadata_to_be_deleted,),&log_record.record.metadata,) {
-                                        Ok(meta) => {
-                                            record_from_map.metadata_to_be_merged = meta.0;
-                                            record_from_map.metadata_to_be_deleted = meta.1;
-                                        }
-                                        Err(e) => {
-                                            return Err(LogMaterializerError::MetadataMaterialization(e));
-                                        }
-                                    };
-                                    if let Some(doc) = log_record.record.document.as_ref() {
-                                        record_from_map.final_document = Some(doc);
-                                    }
-                                    if let Some(emb) = log_record.record.embedding.as_ref() {
-                                        record_from_map.final_embedding = Some(emb.as_slice());
-                                    }
+                                    record_from_map.apply_update(&log_record.record, self.fields)?;
                                     match record_from_map.final_operation {
                                         MaterializedLogOperation::Initial => {
                                             record_from_map.final_operation =
@@ -723,27 +1029,7 @@ impl<'me> LogMaterializer<'me> {
                             let record_from_map = new_id_to_materialized
                                 .get_mut(log_record.record.id.as_str())
                                 .unwrap();
-                            match merge_update_metadata(
-                                (
-                                    &record_from_map.metadata_to_be_merged,
-                                    &record_from_map.metadata_to_be_deleted,
-                                ),
-                                &log_record.record.metadata,
-                            ) {
-                                Ok(meta) => {
-                                    record_from_map.metadata_to_be_merged = meta.0;
-                                    record_from_map.metadata_to_be_deleted = meta.1;
-                                }
-                                Err(e) => {
-                                    return Err(LogMaterializerError::MetadataMaterialization(e));
-                                }
-                            };
-                            if let Some(doc) = log_record.record.document.as_ref() {
-                                record_from_map.final_document = Some(doc);
-                            }
-                            if let Some(emb) = log_record.record.embedding.as_ref() {
-                                record_from_map.final_embedding = Some(emb.as_slice());
-                            }
+                            record_from_map.apply_update(&log_record.record, self.fields)?;
                             // This record is not present on storage yet hence final operation is
                             // AddNew and not UpdateExisting.
                             record_from_map.final_operation = MaterializedLogOperation::AddNew;
@@ -751,11 +1037,12 @@ impl<'me> LogMaterializer<'me> {
                             // Insert.
                             let next_offset =
                                 next_offset_id.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-                            let materialized_record = match MaterializedLogRecord::try_from((
+                            let materialized_record = match MaterializedLogRecord::from_log_record(
                                 &log_record.record,
                                 next_offset,
                                 log_record.record.id.as_str(),
-                            )) {
+                                self.fields,
+                            ) {
                                 Ok(record) => record,
                                 Err(e) => {
                                     return Err(e);
@@ -785,6 +1072,158 @@ impl<'me> LogMaterializer<'me> {
     }
 }
 
+/// The offset ids of the records that are present after a chunk of logs is materialized
+fn active_log_offset_ids(materialized_logs: &Chunk<MaterializedLogRecord>) -> RoaringBitmap {
+    materialized_logs
+        .iter()
+        .filter_map(|(log, _)| {
+            (!matches!(
+                log.final_operation,
+                MaterializedLogOperation::DeleteExisting
+            ))
+            .then_some(log.offset_id)
+        })
+        .collect()
+}
+
+/// The content of a record that is present after a chunk of logs is materialized, owned so
+/// that it outlives the record segment reader that the logs are materialized with. The fields
+/// that are not materialized are left empty
+#[derive(Clone, Debug)]
+pub struct MaterializedLogContent {
+    pub user_id: String,
+    pub document: Option<String>,
+    pub embedding: Vec<f32>,
+    pub metadata: Metadata,
+    pub uri: Option<String>,
+}
+
+/// The content of the records that are present after a chunk of logs is materialized
+fn active_log_contents(
+    materialized_logs: &Chunk<MaterializedLogRecord>,
+    fields: MaterializedLogFields,
+) -> HashMap<u32, MaterializedLogContent> {
+    materialized_logs
+        .iter()
+        .filter(|(log, _)| {
+            !matches!(
+                log.final_operation,
+                MaterializedLogOperation::DeleteExisting
+            )
+        })
+        .map(|(log, _)| {
+            (
+                log.offset_id,
+                MaterializedLogContent {
+                    user_id: log.merged_user_id(),
+                    document: fields.document.then(|| log.merged_document()).flatten(),
+                    embedding: match fields.embedding {
+                        true => log.merged_embeddings().to_vec(),
+                        false => Vec::new(),
+                    },
+                    metadata: match fields.metadata {
+                        true => log.merged_metadata(),
+                        false => Metadata::new(),
+                    },
+                    uri: fields
+                        .uri
+                        .then(|| log.merged_uri_ref().map(str::to_string))
+                        .flatten(),
+                },
+            )
+        })
+        .collect()
+}
+
+/// A handle to the result of materializing the logs of a query, which is shared by the
+/// operators of the query so that the logs are materialized at most once for all of them.
+///
+/// The materialized records borrow from the record segment reader of the operator that
+/// materializes them, so the offset ids and the owned content of the records are kept instead.
+/// Only the `fields` that the later operators read are kept in the content, so the operator
+/// that materializes the logs first should materialize at least those
+#[derive(Clone, Debug, Default)]
+pub struct SharedMaterializedLogs {
+    fields: MaterializedLogFields,
+    active_offset_ids: Arc<OnceCell<RoaringBitmap>>,
+    active_contents: Arc<OnceCell<HashMap<u32, MaterializedLogContent>>>,
+}
+
+impl SharedMaterializedLogs {
+    pub fn new(fields: MaterializedLogFields) -> Self {
+        Self {
+            fields,
+            ..Default::default()
+        }
+    }
+
+    /// The fields of the records that the later operators read from the content
+    pub fn fields(&self) -> MaterializedLogFields {
+        self.fields
+    }
+
+    /// Keeps the offset ids of the logs that are materialized by the caller with the given
+    /// fields, and their content if the fields include the ones that the later operators read
+    pub fn record(
+        &self,
+        materialized_logs: &Chunk<MaterializedLogRecord>,
+        fields: MaterializedLogFields,
+    ) {
+        // Another operator may have set the values concurrently, in which case they are identical
+        if !self.active_offset_ids.initialized() {
+            let _ = self
+                .active_offset_ids
+                .set(active_log_offset_ids(materialized_logs));
+        }
+        if fields.contains(&self.fields) && !self.active_contents.initialized() {
+            let _ = self
+                .active_contents
+                .set(active_log_contents(materialized_logs, self.fields));
+        }
+    }
+
+    /// Returns the offset ids of the records that are present after the logs are materialized,
+    /// materializing the logs only if no operator has done so for the query
+    pub async fn active_offset_ids(
+        &self,
+        record_segment_reader: Option<RecordSegmentReader<'_>>,
+        logs: &Chunk<LogRecord>,
+    ) -> Result<&RoaringBitmap, LogMaterializerError> {
+        self.active_offset_ids
+            .get_or_try_init(|| async {
+                let materializer = LogMaterializer::new(record_segment_reader, logs.clone(), None)
+                    .with_fields(MaterializedLogFields::OFFSET_IDS);
+                let materialized_logs = materializer
+                    .materialize()
+                    .instrument(tracing::trace_span!(parent: Span::current(), "Materialize logs"))
+                    .await?;
+                Ok(active_log_offset_ids(&materialized_logs))
+            })
+            .await
+    }
+
+    /// Returns the content of the records that are present after the logs are materialized,
+    /// by offset id, materializing the logs only if no operator has done so with the fields
+    /// that the later operators read
+    pub async fn active_contents(
+        &self,
+        record_segment_reader: Option<RecordSegmentReader<'_>>,
+        logs: &Chunk<LogRecord>,
+    ) -> Result<&HashMap<u32, MaterializedLogContent>, LogMaterializerError> {
+        self.active_contents
+            .get_or_try_init(|| async {
+                let materializer = LogMaterializer::new(record_segment_reader, logs.clone(), None)
+                    .with_fields(self.fields);
+                let materialized_logs = materializer
+                    .materialize()
+                    .instrument(tracing::trace_span!(parent: Span::current(), "Materialize logs"))
+                    .await?;
+                Ok(active_log_contents(&materialized_logs, self.fields))
+            })
+            .await
+    }
+}
+
 // This needs to be public for testing
 #[allow(async_fn_in_trait)]
 pub trait SegmentWriter<'a> {
@@ -799,6 +1238,16 @@ pub trait SegmentWriter<'a> {
 #[async_trait]
 pub trait SegmentFlusher {
     async fn flush(self) -> Result<HashMap<String, Vec<String>>, Box<dyn ChromaError>>;
+
+    /// The blockfiles that will be written by the flush
+    fn blockfile_flushers(&self) -> Vec<&BlockfileFlusher> {
+        Vec::new()
+    }
+
+    /// The segment metadata that is registered together with the flushed files
+    fn metadata(&self) -> Option<Metadata> {
+        None
+    }
 }
 
 #[cfg(test)]
@@ -806,9 +1255,11 @@ mod tests {
     #![allow(deprecated)]
 
     use super::*;
+    use crate::log::test::{add_delete_generator, upsert_generator, LogGenerator};
     use crate::segment::{
         metadata_segment::{MetadataSegmentReader, MetadataSegmentWriter},
         record_segment::{RecordSegmentReaderCreationError, RecordSegmentWriter},
+        test::TestSegment,
     };
     use chroma_blockstore::{
         arrow::{config::TEST_MAX_BLOCK_SIZE_BYTES, provider::ArrowBlockfileProvider},
@@ -835,7 +1286,7 @@ mod tests {
             sparse_index_cache,
         );
         let blockfile_provider =
-            BlockfileProvider::ArrowBlockfileProvider(arrow_blockfile_provider);
+            BlockfileProvider::ArrowBlockfileProvider(Box::new(arrow_blockfile_provider));
         let mut record_segment = chroma_types::Segment {
             id: SegmentUuid::from_str("00000000-0000-0000-0000-000000000000").expect("parse error"),
             r#type: chroma_types::SegmentType::BlockfileRecord,
@@ -880,6 +1331,9 @@ mod tests {
                     encoding: None,
                     metadata: Some(update_metadata.clone()),
                     document: Some(String::from("doc1")),
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Add,
                 },
             }];
@@ -893,7 +1347,7 @@ mod tests {
                             // Uninitialized segment is fine and means that the record
                             // segment is not yet initialized in storage.
                             RecordSegmentReaderCreationError::UninitializedSegment => None,
-                            RecordSegmentReaderCreationError::BlockfileOpenError(_) => {
+                            RecordSegmentReaderCreationError::BlockfileOpenError(..) => {
                                 panic!("Error creating record segment reader");
                             }
                             RecordSegmentReaderCreationError::InvalidNumberOfFiles => {
@@ -953,6 +1407,9 @@ mod tests {
                     encoding: None,
                     metadata: None,
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Delete,
                 },
             },
@@ -964,6 +1421,9 @@ mod tests {
                     encoding: None,
                     metadata: Some(update_metadata),
                     document: Some(String::from("number")),
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Upsert,
                 },
             },
@@ -976,6 +1436,7 @@ mod tests {
             record_segment_reader: Some(reader),
             logs: data,
             curr_offset_id: None,
+            fields: MaterializedLogFields::ALL,
         };
         let res = materializer
             .materialize()
@@ -1134,7 +1595,7 @@ mod tests {
             sparse_index_cache,
         );
         let blockfile_provider =
-            BlockfileProvider::ArrowBlockfileProvider(arrow_blockfile_provider);
+            BlockfileProvider::ArrowBlockfileProvider(Box::new(arrow_blockfile_provider));
         let mut record_segment = chroma_types::Segment {
             id: SegmentUuid::from_str("00000000-0000-0000-0000-000000000000").expect("parse error"),
             r#type: chroma_types::SegmentType::BlockfileRecord,
@@ -1179,6 +1640,9 @@ mod tests {
                     encoding: None,
                     metadata: Some(update_metadata.clone()),
                     document: Some(String::from("doc1")),
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Add,
                 },
             }];
@@ -1192,7 +1656,7 @@ mod tests {
                             // Uninitialized segment is fine and means that the record
                             // segment is not yet initialized in storage.
                             RecordSegmentReaderCreationError::UninitializedSegment => None,
-                            RecordSegmentReaderCreationError::BlockfileOpenError(_) => {
+                            RecordSegmentReaderCreationError::BlockfileOpenError(..) => {
                                 panic!("Error creating record segment reader");
                             }
                             RecordSegmentReaderCreationError::InvalidNumberOfFiles => {
@@ -1251,6 +1715,9 @@ mod tests {
                 encoding: None,
                 metadata: Some(update_metadata),
                 document: None,
+                uri: None,
+                idempotency_key: None,
+                embedding_reference: None,
                 operation: Operation::Upsert,
             },
         }];
@@ -1262,6 +1729,7 @@ mod tests {
             record_segment_reader: Some(reader),
             logs: data,
             curr_offset_id: None,
+            fields: MaterializedLogFields::ALL,
         };
         let res = materializer
             .materialize()
@@ -1425,7 +1893,7 @@ mod tests {
             sparse_index_cache,
         );
         let blockfile_provider =
-            BlockfileProvider::ArrowBlockfileProvider(arrow_blockfile_provider);
+            BlockfileProvider::ArrowBlockfileProvider(Box::new(arrow_blockfile_provider));
         let mut record_segment = chroma_types::Segment {
             id: SegmentUuid::from_str("00000000-0000-0000-0000-000000000000").expect("parse error"),
             r#type: chroma_types::SegmentType::BlockfileRecord,
@@ -1470,6 +1938,9 @@ mod tests {
                     encoding: None,
                     metadata: Some(update_metadata.clone()),
                     document: Some(String::from("doc1")),
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Add,
                 },
             }];
@@ -1483,7 +1954,7 @@ mod tests {
                             // Uninitialized segment is fine and means that the record
                             // segment is not yet initialized in storage.
                             RecordSegmentReaderCreationError::UninitializedSegment => None,
-                            RecordSegmentReaderCreationError::BlockfileOpenError(_) => {
+                            RecordSegmentReaderCreationError::BlockfileOpenError(..) => {
                                 panic!("Error creating record segment reader");
                             }
                             RecordSegmentReaderCreationError::InvalidNumberOfFiles => {
@@ -1543,6 +2014,9 @@ mod tests {
                     encoding: None,
                     metadata: None,
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Delete,
                 },
             },
@@ -1554,6 +2028,9 @@ mod tests {
                     encoding: None,
                     metadata: Some(update_metadata),
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Upsert,
                 },
             },
@@ -1565,6 +2042,9 @@ mod tests {
                     encoding: None,
                     metadata: None,
                     document: Some(String::from("number")),
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Update,
                 },
             },
@@ -1577,6 +2057,7 @@ mod tests {
             record_segment_reader: Some(reader),
             logs: data,
             curr_offset_id: None,
+            fields: MaterializedLogFields::ALL,
         };
         let res = materializer
             .materialize()
@@ -1722,6 +2203,328 @@ mod tests {
         assert_eq!(res.len(), 0);
     }
 
+    fn idempotent_log(
+        log_offset: i64,
+        id: &str,
+        idempotency_key: Option<&str>,
+        operation: Operation,
+    ) -> LogRecord {
+        LogRecord {
+            log_offset,
+            record: OperationRecord {
+                id: id.to_string(),
+                embedding: matches!(operation, Operation::Add).then(|| vec![1.0, 2.0, 3.0]),
+                encoding: None,
+                metadata: None,
+                document: None,
+                uri: None,
+                idempotency_key: idempotency_key.map(str::to_string),
+                embedding_reference: None,
+                operation,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_materializer_idempotency_keys() {
+        let mut test_segment = TestSegment::default();
+        test_segment
+            .compact_log(
+                Chunk::new(
+                    vec![idempotent_log(
+                        1,
+                        "embedding_id_1",
+                        Some("batch_1"),
+                        Operation::Add,
+                    )]
+                    .into(),
+                ),
+                0,
+            )
+            .await;
+
+        let data = vec![
+            idempotent_log(2, "embedding_id_1", None, Operation::Delete),
+            // Retry of the compacted write, with the remaining record of the batch
+            idempotent_log(3, "embedding_id_1", Some("batch_1"), Operation::Add),
+            idempotent_log(4, "embedding_id_2", Some("batch_1"), Operation::Add),
+            // Retry of a write in the logs
+            idempotent_log(5, "embedding_id_3", Some("batch_2"), Operation::Add),
+            idempotent_log(6, "embedding_id_3", None, Operation::Delete),
+            idempotent_log(7, "embedding_id_3", Some("batch_2"), Operation::Add),
+        ];
+        let reader = RecordSegmentReader::from_segment(
+            &test_segment.record_segment,
+            &test_segment.blockfile_provider,
+        )
+        .await
+        .expect("Error creating segment reader");
+        let materializer = LogMaterializer::new(Some(reader), Chunk::new(data.into()), None);
+        let res = materializer
+            .materialize()
+            .await
+            .expect("Error materializing logs");
+        let mut res_vec = res.iter().map(|(record, _)| record).collect::<Vec<_>>();
+        res_vec.sort_by_key(|record| record.offset_id);
+        assert_eq!(2, res_vec.len());
+        assert_eq!(1, res_vec[0].offset_id);
+        assert_eq!(
+            MaterializedLogOperation::DeleteExisting,
+            res_vec[0].final_operation
+        );
+        assert_eq!("embedding_id_2", res_vec[1].merged_user_id_ref());
+        assert_eq!(MaterializedLogOperation::AddNew, res_vec[1].final_operation);
+    }
+
+    #[tokio::test]
+    async fn test_materializer_redelivered_logs() {
+        let mut test_segment = TestSegment::default();
+        test_segment
+            .compact_log(
+                Chunk::new(
+                    vec![
+                        idempotent_log(1, "embedding_id_1", None, Operation::Add),
+                        idempotent_log(2, "embedding_id_2", None, Operation::Add),
+                    ]
+                    .into(),
+                ),
+                0,
+            )
+            .await;
+        let record_writer = RecordSegmentWriter::from_segment(
+            &test_segment.record_segment,
+            &test_segment.blockfile_provider,
+        )
+        .await
+        .expect("Error creating segment writer");
+        record_writer
+            .set_applied_log_offset(2)
+            .await
+            .expect("Error setting applied log offset");
+        test_segment.record_segment.file_path = record_writer
+            .commit()
+            .await
+            .expect("Error committing segment writer")
+            .flush()
+            .await
+            .expect("Error flushing segment writer");
+
+        let data = vec![
+            // Redelivery of the compacted logs
+            idempotent_log(2, "embedding_id_2", None, Operation::Add),
+            idempotent_log(3, "embedding_id_1", None, Operation::Delete),
+            // Redelivery of a log in the logs
+            idempotent_log(4, "embedding_id_3", None, Operation::Add),
+            idempotent_log(5, "embedding_id_3", None, Operation::Delete),
+            idempotent_log(4, "embedding_id_3", None, Operation::Add),
+        ];
+        let reader = RecordSegmentReader::from_segment(
+            &test_segment.record_segment,
+            &test_segment.blockfile_provider,
+        )
+        .await
+        .expect("Error creating segment reader");
+        assert_eq!(
+            Some(2),
+            reader
+                .get_applied_log_offset()
+                .await
+                .expect("Error reading applied log offset")
+        );
+        let materializer = LogMaterializer::new(Some(reader), Chunk::new(data.into()), None);
+        let res = materializer
+            .materialize()
+            .await
+            .expect("Error materializing logs");
+        let res_vec = res.iter().map(|(record, _)| record).collect::<Vec<_>>();
+        assert_eq!(1, res_vec.len());
+        assert_eq!(1, res_vec[0].offset_id);
+        assert_eq!(
+            MaterializedLogOperation::DeleteExisting,
+            res_vec[0].final_operation
+        );
+    }
+
+    #[tokio::test]
+    async fn test_materializer_from_stream() {
+        let mut test_segment = TestSegment::default();
+        test_segment
+            .compact_log(
+                Chunk::new(
+                    vec![
+                        idempotent_log(1, "embedding_id_1", None, Operation::Add),
+                        idempotent_log(2, "embedding_id_2", None, Operation::Add),
+                    ]
+                    .into(),
+                ),
+                0,
+            )
+            .await;
+        let record_writer = RecordSegmentWriter::from_segment(
+            &test_segment.record_segment,
+            &test_segment.blockfile_provider,
+        )
+        .await
+        .expect("Error creating segment writer");
+        record_writer
+            .set_applied_log_offset(2)
+            .await
+            .expect("Error setting applied log offset");
+        test_segment.record_segment.file_path = record_writer
+            .commit()
+            .await
+            .expect("Error committing segment writer")
+            .flush()
+            .await
+            .expect("Error flushing segment writer");
+
+        // The redelivered and retried logs span the batches
+        let batches = vec![
+            vec![
+                idempotent_log(2, "embedding_id_2", None, Operation::Add),
+                idempotent_log(3, "embedding_id_3", Some("key_3"), Operation::Add),
+            ],
+            vec![
+                idempotent_log(3, "embedding_id_3", Some("key_3"), Operation::Add),
+                idempotent_log(4, "embedding_id_3", Some("key_3"), Operation::Add),
+                idempotent_log(5, "embedding_id_1", None, Operation::Delete),
+            ],
+        ];
+        let reader = RecordSegmentReader::from_segment(
+            &test_segment.record_segment,
+            &test_segment.blockfile_provider,
+        )
+        .await
+        .expect("Error creating segment reader");
+        let materializer = LogMaterializer::from_stream(
+            Some(reader),
+            futures::stream::iter(
+                batches
+                    .into_iter()
+                    .map(|batch| Ok::<_, LogMaterializerError>(Chunk::new(batch.into()))),
+            ),
+            None,
+        )
+        .await
+        .expect("Error reading the batches of logs");
+        // Only the logs that are materialized are kept
+        assert_eq!(
+            vec![3, 5],
+            materializer
+                .logs
+                .iter()
+                .map(|(log, _)| log.log_offset)
+                .collect::<Vec<_>>()
+        );
+        let res = materializer
+            .materialize()
+            .await
+            .expect("Error materializing logs");
+        let mut res_vec = res
+            .iter()
+            .map(|(record, _)| (record.offset_id, record.final_operation.clone()))
+            .collect::<Vec<_>>();
+        res_vec.sort_by_key(|(offset_id, _)| *offset_id);
+        assert_eq!(
+            vec![
+                (1, MaterializedLogOperation::DeleteExisting),
+                (3, MaterializedLogOperation::AddNew)
+            ],
+            res_vec
+        );
+    }
+
+    #[tokio::test]
+    async fn test_materializer_offset_ids_only() {
+        let mut test_segment = TestSegment::default();
+        let generator = LogGenerator {
+            generator: add_delete_generator,
+        };
+        test_segment.populate_with_generator(60, &generator).await;
+        let logs = generator.generate_chunk(61..=120);
+        let reader = RecordSegmentReader::from_segment(
+            &test_segment.record_segment,
+            &test_segment.blockfile_provider,
+        )
+        .await
+        .expect("Error creating segment reader");
+
+        let materializer = LogMaterializer::new(Some(reader.clone()), logs.clone(), None);
+        let all_fields = materializer
+            .materialize()
+            .await
+            .expect("Error materializing logs");
+        let materializer = LogMaterializer::new(Some(reader), logs, None)
+            .with_fields(MaterializedLogFields::OFFSET_IDS);
+        let offset_ids_only = materializer
+            .materialize()
+            .await
+            .expect("Error materializing logs");
+
+        let operations = |records: &Chunk<MaterializedLogRecord>| {
+            records
+                .iter()
+                .map(|(record, _)| (record.offset_id, record.final_operation.clone()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(operations(&offset_ids_only), operations(&all_fields));
+        assert!(offset_ids_only.iter().all(|(record, _)| {
+            record.data_record.is_none()
+                && record.metadata_to_be_merged.is_none()
+                && record.final_embedding.is_none()
+        }));
+    }
+
+    #[tokio::test]
+    async fn test_shared_materialized_logs_fields() {
+        let mut test_segment = TestSegment::default();
+        let generator = LogGenerator {
+            generator: upsert_generator,
+        };
+        test_segment.populate_with_generator(60, &generator).await;
+        let logs = generator.generate_chunk(41..=80);
+        let reader = RecordSegmentReader::from_segment(
+            &test_segment.record_segment,
+            &test_segment.blockfile_provider,
+        )
+        .await
+        .expect("Error creating segment reader");
+
+        // The logs are materialized with the offset ids only, while the documents are read later
+        let shared = SharedMaterializedLogs::new(MaterializedLogFields {
+            document: true,
+            ..MaterializedLogFields::OFFSET_IDS
+        });
+        let materializer = LogMaterializer::new(Some(reader.clone()), logs.clone(), None)
+            .with_fields(MaterializedLogFields::OFFSET_IDS);
+        let offset_ids_only = materializer
+            .materialize()
+            .await
+            .expect("Error materializing logs");
+        shared.record(&offset_ids_only, MaterializedLogFields::OFFSET_IDS);
+        assert_eq!(
+            shared
+                .active_offset_ids(Some(reader.clone()), &logs)
+                .await
+                .expect("Error materializing logs")
+                .len(),
+            40
+        );
+
+        // The content is materialized again, with the documents but no other fields
+        let contents = shared
+            .active_contents(Some(reader), &logs)
+            .await
+            .expect("Error materializing logs");
+        assert_eq!(contents.len(), 40);
+        assert!(contents.values().all(|content| {
+            content.document.is_some()
+                && content.embedding.is_empty()
+                && content.metadata.is_empty()
+                && content.uri.is_none()
+        }));
+    }
+
     #[tokio::test]
     async fn test_materializer_basic() {
         let tmp_dir = tempfile::tempdir().unwrap();
@@ -1735,7 +2538,7 @@ mod tests {
             sparse_index_cache,
         );
         let blockfile_provider =
-            BlockfileProvider::ArrowBlockfileProvider(arrow_blockfile_provider);
+            BlockfileProvider::ArrowBlockfileProvider(Box::new(arrow_blockfile_provider));
         let mut record_segment = chroma_types::Segment {
             id: SegmentUuid::from_str("00000000-0000-0000-0000-000000000000").expect("parse error"),
             r#type: chroma_types::SegmentType::BlockfileRecord,
@@ -1768,6 +2571,9 @@ mod tests {
                         encoding: None,
                         metadata: Some(update_metadata.clone()),
                         document: Some(String::from("doc1")),
+                        uri: None,
+                        idempotency_key: None,
+                        embedding_reference: None,
                         operation: Operation::Add,
                     },
                 },
@@ -1779,6 +2585,9 @@ mod tests {
                         encoding: None,
                         metadata: Some(update_metadata),
                         document: Some(String::from("doc2")),
+                        uri: None,
+                        idempotency_key: None,
+                        embedding_reference: None,
                         operation: Operation::Add,
                     },
                 },
@@ -1793,7 +2602,7 @@ mod tests {
                             // Uninitialized segment is fine and means that the record
                             // segment is not yet initialized in storage.
                             RecordSegmentReaderCreationError::UninitializedSegment => None,
-                            RecordSegmentReaderCreationError::BlockfileOpenError(_) => {
+                            RecordSegmentReaderCreationError::BlockfileOpenError(..) => {
                                 panic!("Error creating record segment reader");
                             }
                             RecordSegmentReaderCreationError::InvalidNumberOfFiles => {
@@ -1841,6 +2650,9 @@ mod tests {
                     encoding: None,
                     metadata: Some(update_metadata.clone()),
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Update,
                 },
             },
@@ -1852,6 +2664,9 @@ mod tests {
                     encoding: None,
                     metadata: Some(update_metadata),
                     document: Some(String::from("doc3")),
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Add,
                 },
             },
@@ -1863,6 +2678,9 @@ mod tests {
                     encoding: None,
                     metadata: None,
                     document: None,
+                    uri: None,
+                    idempotency_key: None,
+                    embedding_reference: None,
                     operation: Operation::Delete,
                 },
             },
@@ -1875,6 +2693,7 @@ mod tests {
             record_segment_reader: Some(reader),
             logs: data,
             curr_offset_id: None,
+            fields: MaterializedLogFields::ALL,
         };
         let res = materializer
             .materialize()
@@ -1,32 +1,91 @@
+use crate::assignment::assignment_policy::AssignmentPolicy;
+use crate::compactor::CompactionAdminService;
 use crate::config::QueryServiceConfig;
+use crate::execution::decode_executor::DispatcherDecodeExecutor;
 use crate::execution::dispatcher::Dispatcher;
+use crate::execution::memory::MemoryPool;
+use crate::execution::metrics::{where_operators, QueryMetrics, QueryType};
+use crate::execution::middleware::OperatorMiddleware;
+use crate::execution::operator::{Deadline, TaskPriority};
+use crate::execution::operators::aggregate::AggregateOperator;
+use crate::execution::operators::changes::{ChangesOperator, RecordChange};
+use crate::execution::operators::check_integrity::IntegrityCheck;
+use crate::execution::operators::count::CountOperator;
+use crate::execution::operators::export_index::ExportIndexOperator;
+use crate::execution::operators::facet::{Facet, FacetOperator};
 use crate::execution::operators::fetch_log::FetchLogOperator;
 use crate::execution::operators::fetch_segment::FetchSegmentOperator;
 use crate::execution::operators::filter::FilterOperator;
+use crate::execution::operators::hybrid_search::{
+    FusionFunction, HybridSearchOperator, DEFAULT_RRF_K,
+};
+use crate::execution::operators::knn::KnnOperator;
+use crate::execution::operators::knn_projection::KnnProjectionOperator;
 use crate::execution::operators::limit::LimitOperator;
-use crate::execution::operators::projection::ProjectionOperator;
+use crate::execution::operators::lookup::LookupOperator;
+use crate::execution::operators::merge_records::MergeDuplicatePolicy;
+use crate::execution::operators::projection::{ProjectionOperator, ProjectionRecord};
+use crate::execution::orchestration::analytics::{
+    AnalyticsOrchestrator, AnalyticsOutput, AnalyticsQuery,
+};
+use crate::execution::orchestration::changes::ChangesOrchestrator;
+use crate::execution::orchestration::check_integrity::CheckIntegrityOrchestrator;
+use crate::execution::orchestration::export_index::ExportIndexOrchestrator;
 use crate::execution::orchestration::get::GetOrchestrator;
 use crate::execution::orchestration::hnsw::HnswQueryOrchestrator;
+use crate::execution::orchestration::hybrid_search::HybridSearchOrchestrator;
+use crate::execution::orchestration::knn::{KnnError, KnnFilterOrchestrator};
+use crate::execution::orchestration::lookup::LookupOrchestrator;
+use crate::execution::orchestration::merge::MergeOrchestrator;
+use crate::execution::orchestration::verify_checksum::VerifyChecksumOrchestrator;
+use crate::execution::orchestration::warm_up::WarmUpOrchestrator;
 use crate::execution::orchestration::{CountQueryOrchestrator, GetVectorsOrchestrator};
+use crate::execution::result_cache::SharedResultCache;
+use crate::execution::slow_query::{QuerySummary, SlowQueryLog};
+use crate::execution::spill::SpillConfig;
 use crate::log::log::Log;
+use crate::memory_watchdog::MemoryWatchdog;
+use crate::segment::cache_invalidation::SegmentCacheInvalidator;
+use crate::segment::offset_id_cache::SharedOffsetIdCache;
+use crate::sysdb::alias::{CollectionAliasResolver, ResolveAliasError};
 use crate::sysdb::sysdb::SysDb;
 use crate::system::{ComponentHandle, System};
 use crate::tracing::util::wrap_span_with_parent_context;
+use crate::warm_up_scheduler::{WarmUpScheduler, WarmUpSchedulerConfig};
 use async_trait::async_trait;
 use chroma_blockstore::provider::BlockfileProvider;
 use chroma_config::Configurable;
+use chroma_distance::DistanceAccumulation;
 use chroma_error::ChromaError;
 use chroma_index::hnsw_provider::HnswIndexProvider;
+use chroma_storage::Storage;
 use chroma_types::chroma_proto::{
-    self, CountRecordsRequest, CountRecordsResponse, QueryMetadataRequest, QueryMetadataResponse,
-    RequestVersionContext,
+    self, CountRecordsRequest, CountRecordsResponse, ErrorDetails, QueryMetadataRequest,
+    QueryMetadataResponse, RequestVersionContext,
 };
 use chroma_types::chroma_proto::{
-    GetVectorsRequest, GetVectorsResponse, QueryVectorsRequest, QueryVectorsResponse,
+    AggregateMetadataRequest, AggregateMetadataResponse, CheckCollectionIntegrityRequest,
+    CheckCollectionIntegrityResponse, ExportCollectionIndexesRequest,
+    ExportCollectionIndexesResponse, FacetMetadataRequest, FacetMetadataResponse,
+    GetEmbeddingsRequest, GetEmbeddingsResponse, GetVectorsRequest, GetVectorsResponse,
+    HybridSearchRequest, HybridSearchResponse, ListCachedBlocksRequest, ListCachedBlocksResponse,
+    ListSlowQueriesRequest, ListSlowQueriesResponse, MergeCollectionsRequest,
+    MergeCollectionsResponse, QueryVectorsRequest, QueryVectorsResponse, StreamChangesRequest,
+    StreamChangesResponse, VerifyCollectionChecksumRequest, VerifyCollectionChecksumResponse,
+    WarmUpCollectionRequest, WarmUpCollectionResponse,
 };
-use chroma_types::{CollectionUuid, MetadataValue, ScalarEncoding, SegmentUuid, Where};
+use chroma_types::{
+    error_status, Collection, CollectionUuid, MetadataValue, ScalarEncoding, SegmentScope,
+    SegmentUuid, Where,
+};
+use futures::{Stream, StreamExt};
+use std::collections::{HashMap, HashSet};
+use std::mem::size_of_val;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::signal::unix::{signal, SignalKind};
-use tonic::{transport::Server, Request, Response, Status};
+use tonic::{metadata::MetadataMap, transport::Server, Code, Request, Response, Status};
 use tracing::{trace_span, Instrument};
 use uuid::Uuid;
 
@@ -41,9 +100,38 @@ pub struct WorkerServer {
     sysdb: Box<SysDb>,
     hnsw_index_provider: HnswIndexProvider,
     blockfile_provider: BlockfileProvider,
+    storage: Storage,
+    segment_cache_invalidator: SegmentCacheInvalidator,
+    collection_alias_resolver: CollectionAliasResolver,
+    memory_pool: MemoryPool,
+    hydration_concurrency: usize,
+    offset_id_cache: Option<SharedOffsetIdCache>,
+    result_cache: Option<SharedResultCache>,
+    max_inline_embedding_bytes: Option<u64>,
+    // The maximum number of uncompacted logs that a query materializes, unless the query
+    // sets its own
+    max_log_backlog: Option<u32>,
+    stream_batch_size: usize,
+    // The size of the message queues of the orchestrators of the queries
+    orchestrator_queue_size: usize,
+    slow_query_log: SlowQueryLog,
+    // The layers that the operators of the get queries run in
+    operator_middleware: OperatorMiddleware,
+    // Where the merges of the query results spill once they exceed the budget
+    spill: Option<SpillConfig>,
+    // The compaction admin service of a compactor in the same process
+    compaction_admin: Option<CompactionAdminService>,
     port: u16,
 }
 
+/// The batches of records of a streamed get query
+type MetadataStream =
+    Pin<Box<dyn Stream<Item = Result<QueryMetadataResponse, Status>> + Send + 'static>>;
+
+/// The batches of changes of a collection
+type ChangesStream =
+    Pin<Box<dyn Stream<Item = Result<StreamChangesResponse, Status>> + Send + 'static>>;
+
 #[async_trait]
 impl Configurable<QueryServiceConfig> for WorkerServer {
     async fn try_from_config(config: &QueryServiceConfig) -> Result<Self, Box<dyn ChromaError>> {
@@ -79,6 +167,25 @@ impl Configurable<QueryServiceConfig> for WorkerServer {
         let hnsw_index_provider =
             HnswIndexProvider::try_from_config(&(config.hnsw_provider.clone(), storage.clone()))
                 .await?;
+        let segment_cache_invalidator =
+            SegmentCacheInvalidator::new(blockfile_provider.clone(), hnsw_index_provider.clone());
+        let collection_alias_resolver = CollectionAliasResolver::new(
+            sysdb.clone(),
+            Duration::from_secs(config.collection_alias_cache_ttl_sec),
+            chroma_cache::from_config(&config.collection_alias_cache).await?,
+        );
+        let offset_id_cache = match &config.offset_id_cache {
+            Some(cache_config) => Some(SharedOffsetIdCache::from(
+                chroma_cache::from_config(cache_config).await?,
+            )),
+            None => None,
+        };
+        let result_cache = match &config.result_cache {
+            Some(cache_config) => Some(SharedResultCache::from(
+                chroma_cache::from_config(cache_config).await?,
+            )),
+            None => None,
+        };
         Ok(WorkerServer {
             dispatcher: None,
             system: None,
@@ -86,6 +193,24 @@ impl Configurable<QueryServiceConfig> for WorkerServer {
             log,
             hnsw_index_provider,
             blockfile_provider,
+            storage,
+            segment_cache_invalidator,
+            collection_alias_resolver,
+            memory_pool: MemoryPool::new(
+                config.query_memory.max_total_bytes,
+                config.query_memory.query_budget_bytes,
+            ),
+            hydration_concurrency: config.hydration_concurrency,
+            offset_id_cache,
+            result_cache,
+            max_inline_embedding_bytes: config.max_inline_embedding_bytes,
+            max_log_backlog: config.max_log_backlog,
+            stream_batch_size: config.stream_batch_size,
+            orchestrator_queue_size: config.orchestrator_queue_size,
+            slow_query_log: SlowQueryLog::new(&config.slow_query_log),
+            operator_middleware: OperatorMiddleware::from(&config.operator_middleware),
+            spill: config.query_spill.as_ref().map(SpillConfig::from),
+            compaction_admin: None,
             port: config.my_port,
         })
     }
@@ -95,12 +220,29 @@ impl WorkerServer {
     pub(crate) async fn run(worker: WorkerServer) -> Result<(), Box<dyn std::error::Error>> {
         let addr = format!("[::]:{}", worker.port).parse().unwrap();
         println!("Worker listening on {}", addr);
+        // Without the log service, the worker accepts the writes into its local log
+        let local_log = match worker.log.as_ref() {
+            Log::Local(log) => Some(log.clone()),
+            _ => None,
+        };
         let server = Server::builder()
             .add_service(chroma_proto::vector_reader_server::VectorReaderServer::new(
                 worker.clone(),
             ))
             .add_service(
                 chroma_proto::metadata_reader_server::MetadataReaderServer::new(worker.clone()),
+            )
+            .add_service(chroma_proto::query_admin_server::QueryAdminServer::new(
+                worker.clone(),
+            ))
+            .add_optional_service(
+                local_log.map(chroma_proto::log_service_server::LogServiceServer::new),
+            )
+            .add_optional_service(
+                worker
+                    .compaction_admin
+                    .clone()
+                    .map(CompactionAdminService::into_server),
             );
 
         #[cfg(debug_assertions)]
@@ -125,6 +267,8 @@ impl WorkerServer {
     }
 
     pub(crate) fn set_dispatcher(&mut self, dispatcher: ComponentHandle<Dispatcher>) {
+        self.blockfile_provider
+            .set_decode_executor(Arc::new(DispatcherDecodeExecutor::new(&dispatcher)));
         self.dispatcher = Some(dispatcher);
     }
 
@@ -132,16 +276,73 @@ impl WorkerServer {
         self.system = Some(system);
     }
 
+    /// Adds the caches and the running queries of the worker to the memory watchdog
+    pub(crate) fn watch_memory(&self, watchdog: MemoryWatchdog) -> MemoryWatchdog {
+        watchdog
+            .with_blockfile_provider(self.blockfile_provider.clone())
+            .with_hnsw_index_provider(self.hnsw_index_provider.clone())
+            .with_memory_pool(self.memory_pool.clone())
+    }
+
+    /// Returns a scheduler that warms up the collections assigned to the worker with the
+    /// providers of the worker, so that the queries find their data cached
+    pub(crate) fn warm_up_scheduler(
+        &self,
+        config: &WarmUpSchedulerConfig,
+        my_member_id: String,
+        assignment_policy: Box<dyn AssignmentPolicy>,
+    ) -> WarmUpScheduler {
+        WarmUpScheduler::new(
+            config,
+            my_member_id,
+            self.sysdb.clone(),
+            self.storage.clone(),
+            self.blockfile_provider.clone(),
+            self.hnsw_index_provider.clone(),
+            assignment_policy,
+        )
+    }
+
+    // The blockfile provider of the queries of the collection, whose blocks are accounted to
+    // the collection in the block cache
+    fn collection_blockfile_provider(&self, collection_uuid: CollectionUuid) -> BlockfileProvider {
+        self.blockfile_provider
+            .with_cache_partition(&collection_uuid.to_string())
+    }
+
+    pub(crate) fn set_compaction_admin(&mut self, compaction_admin: CompactionAdminService) {
+        self.compaction_admin = Some(compaction_admin);
+    }
+
+    // The maximum number of uncompacted logs that the query materializes, which the query
+    // may set below the limit of the server but never above it
+    fn max_log_backlog(&self, version_context: &Option<RequestVersionContext>) -> Option<u32> {
+        let requested = version_context.as_ref().and_then(|ctx| ctx.max_log_backlog);
+        match (requested, self.max_log_backlog) {
+            (Some(requested), Some(limit)) => Some(requested.min(limit)),
+            (requested, limit) => requested.or(limit),
+        }
+    }
+
     pub(crate) async fn query_vectors_instrumented(
         &self,
         request: Request<QueryVectorsRequest>,
     ) -> Result<Response<QueryVectorsResponse>, Status> {
+        let started = Instant::now();
+        let deadline = get_deadline(request.metadata());
+        let alias_scope = get_alias_scope(request.metadata());
         let request = request.into_inner();
         let segment_uuid = to_segment_uuid(&request.segment_id)?;
-        let collection_uuid = to_collection_uuid(&request.collection_id)?;
+        let collection_uuid = self
+            .resolve_collection_uuid(&alias_scope, &request.collection_id)
+            .await?;
         let (collection_version, log_position) = get_version_context(&request.version_context)?;
         let system = self.clone_system()?;
         let dispatcher = self.clone_dispatcher()?;
+        let distance_accumulation = match request.accumulation() {
+            chroma_proto::DistanceAccumulation::AccumulateF32 => DistanceAccumulation::F32,
+            chroma_proto::DistanceAccumulation::AccumulateF64 => DistanceAccumulation::F64,
+        };
 
         let mut query_vectors = Vec::with_capacity(request.vectors.len());
         for proto_query_vector in request.vectors {
@@ -154,6 +355,33 @@ impl WorkerServer {
 
         let embedding_dim = query_vectors[0].len();
 
+        let metrics = QueryMetrics::get();
+        metrics.record_query(
+            collection_uuid,
+            QueryType::Knn,
+            u32::try_from(request.k).ok(),
+            false,
+            request.include_embeddings,
+            None,
+        );
+        // An empty list of allowed ids means that every record is a candidate
+        if !request.allowed_ids.is_empty() {
+            metrics.record_candidate_set_size(
+                collection_uuid,
+                QueryType::Knn,
+                request.allowed_ids.len() as u64,
+            );
+        }
+
+        let summary = QuerySummary {
+            collection_id: collection_uuid,
+            query_type: QueryType::Knn,
+            filter_operators: Vec::new(),
+            num_ids: (!request.allowed_ids.is_empty()).then_some(request.allowed_ids.len()),
+            limit: u32::try_from(request.k).ok(),
+            offset: 0,
+        };
+        let plan = self.slow_query_log.plan_recorder(request.explain);
         let hnsw_orchestrator = HnswQueryOrchestrator::new(
             system,
             query_vectors,
@@ -165,22 +393,35 @@ impl WorkerServer {
             self.log.clone(),
             self.sysdb.clone(),
             self.hnsw_index_provider.clone(),
-            self.blockfile_provider.clone(),
+            self.collection_blockfile_provider(collection_uuid),
             dispatcher,
             collection_version,
             log_position,
-        );
-
-        let result = hnsw_orchestrator.run().await.map_err(|e| {
+        )
+        .with_offset_id_cache(self.offset_id_cache.clone())
+        .with_max_log_backlog(self.max_log_backlog(&request.version_context))
+        .with_distance_accumulation(distance_accumulation)
+        .with_relevance_threshold(request.max_distance, request.min_score)
+        .with_time_limit(
+            request
+                .time_limit_ms
+                .map(|time_limit_ms| Duration::from_millis(time_limit_ms.into())),
+        )
+        .with_plan(plan.clone())
+        .with_spill(self.spill.clone())
+        .with_deadline(deadline)
+        .with_memory_pool(self.memory_pool.clone());
+
+        let result = hnsw_orchestrator.run().await;
+        self.slow_query_log
+            .record(summary, started.elapsed(), &plan);
+        let result = result.map_err(|e| {
             tracing::error!("Error running orchestrator: {}", e);
-            Status::new(
-                e.code().into(),
-                format!("Error running orchestrator: {}", e),
-            )
+            error_status(&e, format!("Error running orchestrator: {}", e))
         })?;
 
-        let mut proto_results_for_all = Vec::with_capacity(result.len());
-        for result_set in result {
+        let mut proto_results_for_all = Vec::with_capacity(result.results.len());
+        for result_set in result.results {
             let mut proto_results = Vec::with_capacity(result_set.len());
             for query_result in result_set {
                 let proto_result = chroma_proto::VectorQueryResult {
@@ -210,6 +451,9 @@ impl WorkerServer {
 
         let resp = chroma_proto::QueryVectorsResponse {
             results: proto_results_for_all,
+            degraded: result.degraded,
+            plan: plan.plan().filter(|_| request.explain).map(Into::into),
+            truncated: result.truncated,
         };
 
         Ok(Response::new(resp))
@@ -219,9 +463,12 @@ impl WorkerServer {
         &self,
         request: Request<GetVectorsRequest>,
     ) -> Result<Response<GetVectorsResponse>, Status> {
+        let alias_scope = get_alias_scope(request.metadata());
         let request = request.into_inner();
         let segment_uuid = to_segment_uuid(&request.segment_id)?;
-        let collection_uuid = to_collection_uuid(&request.collection_id)?;
+        let collection_uuid = self
+            .resolve_collection_uuid(&alias_scope, &request.collection_id)
+            .await?;
         let (collection_version, log_position) = get_version_context(&request.version_context)?;
 
         let orchestrator = GetVectorsOrchestrator::new(
@@ -232,16 +479,13 @@ impl WorkerServer {
             self.log.clone(),
             self.sysdb.clone(),
             self.clone_dispatcher()?,
-            self.blockfile_provider.clone(),
+            self.collection_blockfile_provider(collection_uuid),
             collection_version,
             log_position,
         );
         let result = orchestrator.run().await.map_err(|e| {
             tracing::error!("Error running orchestrator: {}", e);
-            Status::new(
-                e.code().into(),
-                format!("Error running orchestrator: {}", e),
-            )
+            error_status(&e, format!("Error running orchestrator: {}", e))
         })?;
 
         let mut output = Vec::with_capacity(result.ids.len());
@@ -269,257 +513,1561 @@ impl WorkerServer {
         &self,
         request: Request<QueryMetadataRequest>,
     ) -> Result<Response<QueryMetadataResponse>, Status> {
+        let started = Instant::now();
+        let deadline = get_deadline(request.metadata());
+        let alias_scope = get_alias_scope(request.metadata());
         let request = request.into_inner();
-        let segment_uuid = to_segment_uuid(&request.segment_id)?;
-        let collection_uuid = to_collection_uuid(&request.collection_id)?;
-        let (collection_version, log_position) = get_version_context(&request.version_context)?;
-
-        // If no ids are provided, pass None to the orchestrator
-        let query_ids = request.ids.map(|uids| uids.ids);
-
-        let where_clause = match request.r#where {
-            Some(where_clause) => match where_clause.try_into() {
-                Ok(where_clause) => Some(where_clause),
-                Err(_) => {
-                    tracing::error!("Error converting where clause");
-                    return Err(Status::internal(
-                        "Error converting where clause".to_string(),
-                    ));
-                }
-            },
+        if let Some(lookup) = &request.lookup {
+            if lookup.id_key.is_some() && !request.include_metadata {
+                return Err(ErrorDetails::invalid_argument("lookup.id_key").into_status(
+                    Code::InvalidArgument,
+                    "A lookup by a metadata key requires the metadata of the records",
+                ));
+            }
+        }
+        let plan = self.slow_query_log.plan_recorder(request.explain);
+        let (orchestrator, summary) = self
+            .get_orchestrator(&request, &alias_scope, deadline)
+            .await?;
+        let orchestrator = orchestrator.with_plan(plan.clone());
+        let lookup_uuid = match &request.lookup {
+            Some(lookup) => Some(
+                self.resolve_lookup_collection(summary.collection_id, lookup, &alias_scope)
+                    .await?,
+            ),
             None => None,
         };
 
-        let where_document_clause = match request.where_document {
-            Some(where_document_clause) => match where_document_clause.try_into() {
-                Ok(where_document_clause) => Some(where_document_clause),
-                Err(_) => {
-                    tracing::error!("Error converting where document clause");
-                    return Err(Status::internal(
-                        "Error converting where document clause".to_string(),
-                    ));
-                }
-            },
-            None => None,
-        };
+        let system = self.clone_system()?;
+        let result = orchestrator.run(system).await;
+        self.slow_query_log
+            .record(summary, started.elapsed(), &plan);
+        let result = result.map_err(|e| {
+            tracing::error!("Error running orchestrator: {}", e);
+            error_status(&e, format!("Error running orchestrator: {}", e))
+        })?;
 
-        let clause = match (where_clause, where_document_clause) {
-            (Some(wc), Some(wdc)) => Some(Where::conjunction(vec![wc, wdc])),
-            (Some(c), None) | (None, Some(c)) => Some(c),
-            _ => None,
+        let max_embedding_bytes = request
+            .max_embedding_bytes
+            .or(self.max_inline_embedding_bytes);
+        let lookup_records = match (&request.lookup, lookup_uuid) {
+            (Some(lookup), Some(lookup_uuid)) => {
+                self.lookup_records(lookup_uuid, lookup, &result.records, max_embedding_bytes)
+                    .await?
+            }
+            _ => HashMap::new(),
         };
+        let records = to_metadata_embedding_records(
+            result.records,
+            request.include_metadata || request.default_include,
+            &request.version_context,
+            result.log_offset,
+            EmbeddingBudget::new(max_embedding_bytes),
+        )?;
+        let response = chroma_proto::QueryMetadataResponse {
+            records,
+            plan: plan.plan().filter(|_| request.explain).map(Into::into),
+            lookup_records,
+        };
+        Ok(Response::new(response))
+    }
 
-        let orchestrator = GetOrchestrator::new(
-            self.blockfile_provider.clone(),
+    async fn stream_metadata_instrumented(
+        &self,
+        request: Request<QueryMetadataRequest>,
+    ) -> Result<Response<MetadataStream>, Status> {
+        let deadline = get_deadline(request.metadata());
+        let alias_scope = get_alias_scope(request.metadata());
+        let request = request.into_inner();
+        if request.lookup.is_some() {
+            return Err(ErrorDetails::invalid_argument("lookup").into_status(
+                Code::InvalidArgument,
+                "Lookups are not supported by streamed queries",
+            ));
+        }
+        // The latency of a streamed query depends on how fast its batches are consumed, so it
+        // is not logged as a slow query
+        let (orchestrator, _) = self
+            .get_orchestrator(&request, &alias_scope, deadline)
+            .await?;
+
+        let system = self.clone_system()?;
+        let batches = orchestrator
+            .run_streaming(system, self.stream_batch_size)
+            .await
+            .map_err(|e| {
+                tracing::error!("Error running orchestrator: {}", e);
+                error_status(&e, format!("Error running orchestrator: {}", e))
+            })?;
+
+        // Each batch is a separate message, so the embedding budget applies to each of them
+        let max_embedding_bytes = request
+            .max_embedding_bytes
+            .or(self.max_inline_embedding_bytes);
+        let responses: MetadataStream = Box::pin(batches.map(move |batch| {
+            let batch = batch.map_err(|e| {
+                tracing::error!("Error streaming records: {}", e);
+                error_status(&e, format!("Error streaming records: {}", e))
+            })?;
+            let records = to_metadata_embedding_records(
+                batch.records,
+                request.include_metadata || request.default_include,
+                &request.version_context,
+                batch.log_offset,
+                EmbeddingBudget::new(max_embedding_bytes),
+            )?;
+            Ok::<_, Status>(chroma_proto::QueryMetadataResponse {
+                records,
+                plan: None,
+                lookup_records: HashMap::new(),
+            })
+        }));
+        Ok(Response::new(responses))
+    }
+
+    async fn stream_changes_instrumented(
+        &self,
+        request: Request<StreamChangesRequest>,
+    ) -> Result<Response<ChangesStream>, Status> {
+        let alias_scope = get_alias_scope(request.metadata());
+        let request = request.into_inner();
+        let collection_uuid = self
+            .resolve_collection_uuid(&alias_scope, &request.collection_id)
+            .await?;
+        let (collection_version, log_position) = get_version_context(&request.version_context)?;
+        if request.start_log_offset <= log_position {
+            return Err(
+                ErrorDetails::invalid_argument("start_log_offset").into_status(
+                    Code::FailedPrecondition,
+                    format!(
+                        "The logs up to offset {} are compacted, read them with a get instead",
+                        log_position
+                    ),
+                ),
+            );
+        }
+
+        // The logs before the start offset are fetched as well, since the changes are merged
+        // into the state of the records that they leave
+        let orchestrator = ChangesOrchestrator::new(
+            self.collection_blockfile_provider(collection_uuid),
             self.clone_dispatcher()?,
-            // TODO: Load the configuration for this
-            1000,
+            self.orchestrator_queue_size,
             FetchLogOperator {
                 log_client: self.log.clone(),
                 batch_size: 100,
                 start_log_offset_id: log_position as u32 + 1,
                 maximum_fetch_count: None,
+                maximum_backlog: self.max_log_backlog(&request.version_context),
                 collection_uuid,
             },
             FetchSegmentOperator {
                 sysdb: self.sysdb.clone(),
                 vector_uuid: None,
-                metadata_uuid: Some(SegmentUuid(segment_uuid)),
+                metadata_uuid: None,
                 record_uuid: None,
                 collection_uuid,
                 collection_version,
+                cache_invalidator: Some(self.segment_cache_invalidator.clone()),
             },
-            FilterOperator {
-                query_ids,
-                where_clause: clause,
-            },
-            LimitOperator {
-                skip: request.offset.unwrap_or_default(),
-                fetch: request.limit,
-            },
-            ProjectionOperator {
-                document: request.include_metadata,
-                embedding: request.include_metadata,
-                metadata: request.include_metadata,
+            ChangesOperator {
+                start_log_offset: request.start_log_offset as i64,
+                projection: ProjectionOperator {
+                    document: true,
+                    embedding: request.include_embeddings,
+                    metadata: true,
+                    uri: true,
+                    concurrency: self.hydration_concurrency,
+                    default_include: request.default_include,
+                },
             },
         );
 
         let system = self.clone_system()?;
         let result = orchestrator.run(system).await.map_err(|e| {
             tracing::error!("Error running orchestrator: {}", e);
-            Status::new(
-                e.code().into(),
-                format!("Error running orchestrator: {}", e),
-            )
+            error_status(&e, format!("Error running orchestrator: {}", e))
         })?;
 
-        let mut output = Vec::with_capacity(result.records.len());
-        for record in result.records {
-            let metadata = if request.include_metadata {
-                let mut meta = record.metadata.unwrap_or_default();
-
-                // The transport layer assumes the document exists in the metadata
-                // with the special key "chroma:document"
-                if let Some(doc) = record.document {
-                    meta.insert("chroma:document".to_string(), MetadataValue::Str(doc));
-                }
-                Some(chroma_proto::UpdateMetadata::from(meta))
-            } else {
-                None
-            };
+        // The changes are converted batch by batch as the stream is polled, so that only the
+        // batch being sent is held as protos. A stream without changes still has a message,
+        // which carries the log offset that the next request starts after
+        let batch_size = self.stream_batch_size.max(1);
+        let version_context = request.version_context;
+        let log_offset = result.log_offset;
+        let mut changes = result.changes.into_iter().peekable();
+        let mut first = true;
+        let responses = std::iter::from_fn(move || {
+            if changes.peek().is_none() && !first {
+                return None;
+            }
+            first = false;
+            let batch = changes.by_ref().take(batch_size).collect();
+            Some(
+                to_record_changes(batch, &version_context, log_offset).map(|changes| {
+                    StreamChangesResponse {
+                        changes,
+                        log_offset: log_offset as u64,
+                    }
+                }),
+            )
+        });
+        let responses: ChangesStream = Box::pin(futures::stream::iter(responses));
+        Ok(Response::new(responses))
+    }
 
-            output.push(chroma_proto::MetadataEmbeddingRecord {
-                id: record.id,
-                metadata,
-            });
+    // Fetches the records of the lookup collection that the records of a get reference, by
+    // their ids. The embeddings of the lookup records have a budget of their own
+    async fn lookup_records(
+        &self,
+        collection_uuid: CollectionUuid,
+        lookup: &chroma_proto::Lookup,
+        records: &[ProjectionRecord],
+        max_embedding_bytes: Option<u64>,
+    ) -> Result<HashMap<String, chroma_proto::MetadataEmbeddingRecord>, Status> {
+        let ids = records
+            .iter()
+            .filter_map(|record| match &lookup.id_key {
+                Some(id_key) => match record.metadata.as_ref()?.get(id_key)? {
+                    MetadataValue::Str(id) => Some(id.clone()),
+                    _ => None,
+                },
+                None => Some(record.id.clone()),
+            })
+            .collect::<HashSet<_>>();
+        if ids.is_empty() {
+            return Ok(HashMap::new());
         }
 
-        // This is an implementation stub
-        let response = chroma_proto::QueryMetadataResponse { records: output };
-        Ok(Response::new(response))
-    }
+        let (collection_version, log_position) = get_version_context(&lookup.version_context)?;
+        let orchestrator = LookupOrchestrator::new(
+            self.collection_blockfile_provider(collection_uuid),
+            self.clone_dispatcher()?,
+            self.orchestrator_queue_size,
+            FetchLogOperator {
+                log_client: self.log.clone(),
+                batch_size: 100,
+                start_log_offset_id: log_position as u32 + 1,
+                maximum_fetch_count: None,
+                maximum_backlog: self.max_log_backlog(&lookup.version_context),
+                collection_uuid,
+            },
+            FetchSegmentOperator {
+                sysdb: self.sysdb.clone(),
+                vector_uuid: None,
+                metadata_uuid: None,
+                record_uuid: None,
+                collection_uuid,
+                collection_version,
+                cache_invalidator: Some(self.segment_cache_invalidator.clone()),
+            },
+            ids.into_iter().collect(),
+            LookupOperator {
+                projection: ProjectionOperator {
+                    document: lookup.include_metadata,
+                    embedding: lookup.include_embeddings,
+                    metadata: lookup.include_metadata,
+                    uri: lookup.include_metadata,
+                    concurrency: self.hydration_concurrency,
+                    default_include: lookup.default_include,
+                },
+            },
+        );
 
-    fn clone_dispatcher(&self) -> Result<ComponentHandle<Dispatcher>, Status> {
-        let dispatcher = self
-            .dispatcher
-            .as_ref()
-            .ok_or_else(|| Status::internal("No dispatcher found"))?;
-        Ok(dispatcher.clone())
-    }
+        let system = self.clone_system()?;
+        let result = orchestrator.run(system).await.map_err(|e| {
+            tracing::error!("Error running orchestrator: {}", e);
+            error_status(&e, format!("Error running orchestrator: {}", e))
+        })?;
 
-    fn clone_system(&self) -> Result<System, Status> {
-        let system = self
-            .system
-            .as_ref()
-            .ok_or_else(|| Status::internal("No system found"))?;
-        Ok(system.clone())
+        let records = to_metadata_embedding_records(
+            result.records.into_values().collect(),
+            lookup.include_metadata || lookup.default_include,
+            &lookup.version_context,
+            result.log_offset,
+            EmbeddingBudget::new(max_embedding_bytes),
+        )?;
+        Ok(records
+            .into_iter()
+            .map(|record| (record.id.clone(), record))
+            .collect())
     }
-}
 
-#[tonic::async_trait]
-impl chroma_proto::vector_reader_server::VectorReader for WorkerServer {
-    async fn get_vectors(
+    // Resolves the lookup collection, which must be in the tenant and database of the queried
+    // collection, so that a lookup never joins the records of another tenant or database
+    async fn resolve_lookup_collection(
         &self,
-        request: Request<GetVectorsRequest>,
-    ) -> Result<Response<GetVectorsResponse>, Status> {
-        // Note: We cannot write a middleware that instruments every service rpc
-        // with a span because of https://github.com/hyperium/tonic/pull/1202.
-        let request_span = trace_span!(
-            "Get vectors",
-            segment_id = request.get_ref().segment_id,
-            ids = ?request.get_ref().ids
-        );
-
-        let instrumented_span = wrap_span_with_parent_context(request_span, request.metadata());
-        self.get_vectors_instrumented(request)
-            .instrument(instrumented_span)
-            .await
+        collection_uuid: CollectionUuid,
+        lookup: &chroma_proto::Lookup,
+        alias_scope: &AliasScope,
+    ) -> Result<CollectionUuid, Status> {
+        let lookup_uuid = self
+            .resolve_collection_uuid(alias_scope, &lookup.collection_id)
+            .await?;
+        let collection = self
+            .get_collection(collection_uuid, "collection_id")
+            .await?;
+        let lookup_collection = self
+            .get_collection(lookup_uuid, "lookup.collection_id")
+            .await?;
+        if collection.tenant != lookup_collection.tenant {
+            return Err(Status::permission_denied(format!(
+                "Lookup collection {} is not in the tenant of collection {}",
+                lookup_uuid, collection_uuid
+            )));
+        }
+        if collection.database != lookup_collection.database {
+            return Err(
+                ErrorDetails::invalid_argument("lookup.collection_id").into_status(
+                    Code::InvalidArgument,
+                    format!(
+                        "Lookup collection {} is not in the database of collection {}",
+                        lookup_uuid, collection_uuid
+                    ),
+                ),
+            );
+        }
+        Ok(lookup_uuid)
     }
 
-    async fn query_vectors(
+    async fn get_collection(
         &self,
-        request: Request<QueryVectorsRequest>,
-    ) -> Result<Response<QueryVectorsResponse>, Status> {
-        // Note: We cannot write a middleware that instruments every service rpc
-        // with a span because of https://github.com/hyperium/tonic/pull/1202.
-        let query_span = trace_span!(
-            "Query vectors",
-            k = request.get_ref().k,
-            segment_id = request.get_ref().segment_id,
-            include_embeddings = request.get_ref().include_embeddings,
-            allowed_ids = ?request.get_ref().allowed_ids
-        );
-        let instrumented_span = wrap_span_with_parent_context(query_span, request.metadata());
-        self.query_vectors_instrumented(request)
-            .instrument(instrumented_span)
+        collection_uuid: CollectionUuid,
+        field: &str,
+    ) -> Result<Collection, Status> {
+        self.sysdb
+            .clone()
+            .get_collections(Some(collection_uuid), None, None, None)
             .await
+            .map_err(|e| error_status(&e, e.to_string()))?
+            .pop()
+            .ok_or_else(|| {
+                ErrorDetails::invalid_argument(field).into_status(
+                    Code::InvalidArgument,
+                    format!("Collection not found: {}", collection_uuid),
+                )
+            })
     }
-}
 
-#[tonic::async_trait]
-impl chroma_proto::metadata_reader_server::MetadataReader for WorkerServer {
-    async fn count_records(
+    // Builds the orchestrator that evaluates a get query up to the projection of its records,
+    // together with the summary of the query for the slow query log
+    async fn get_orchestrator(
         &self,
-        request: Request<CountRecordsRequest>,
-    ) -> Result<Response<CountRecordsResponse>, Status> {
-        let request = request.into_inner();
-        let segment_uuid = match Uuid::parse_str(&request.segment_id) {
-            Ok(uuid) => uuid,
-            Err(_) => {
-                return Err(Status::invalid_argument("Invalid Segment UUID"));
-            }
-        };
-        let collection_uuid = match Uuid::parse_str(&request.collection_id) {
-            Ok(uuid) => uuid,
-            Err(_) => {
-                return Err(Status::invalid_argument("Invalid Collection UUID"));
-            }
-        };
-        let collection_uuid = CollectionUuid(collection_uuid);
-
-        let (collection_version, log_position) = match request.version_context {
-            Some(version_context) => (
-                version_context.collection_version,
-                version_context.log_position,
-            ),
-            None => {
-                return Err(Status::invalid_argument("No version context provided"));
-            }
-        };
+        request: &QueryMetadataRequest,
+        alias_scope: &AliasScope,
+        deadline: Deadline,
+    ) -> Result<(GetOrchestrator, QuerySummary), Status> {
+        let segment_uuid = to_segment_uuid(&request.segment_id)?;
+        let collection_uuid = self
+            .resolve_collection_uuid(alias_scope, &request.collection_id)
+            .await?;
+        let (collection_version, log_position) = get_version_context(&request.version_context)?;
 
-        let dispatcher = match self.dispatcher {
-            Some(ref dispatcher) => dispatcher,
-            None => {
-                return Err(Status::internal("No dispatcher found"));
-            }
-        };
+        // If no ids are provided, pass None to the orchestrator
+        let query_ids = request.ids.clone().map(|uids| uids.ids);
 
-        let system = match self.system {
-            Some(ref system) => system,
-            None => {
-                return Err(Status::internal("No system found"));
-            }
-        };
+        let clause = get_where_clause(request.r#where.clone(), request.where_document.clone())?;
 
-        let orchestrator = CountQueryOrchestrator::new(
-            system.clone(),
-            &segment_uuid,
-            &collection_uuid,
-            self.log.clone(),
-            self.sysdb.clone(),
-            dispatcher.clone(),
-            self.blockfile_provider.clone(),
-            collection_version,
-            log_position,
+        QueryMetrics::get().record_query(
+            collection_uuid,
+            QueryType::Get,
+            request.limit,
+            request.include_metadata,
+            request.include_embeddings,
+            clause.as_ref(),
         );
 
-        let result = orchestrator.run().await;
-        let c = match result {
-            Ok(r) => {
-                println!("Count value {}", r);
-                r
-            }
-            Err(e) => {
-                println!("Error! {:?}", e);
-                // TODO: Return 0 for now but should return an error at some point.
-                0
-            }
+        let summary = QuerySummary {
+            collection_id: collection_uuid,
+            query_type: QueryType::Get,
+            filter_operators: clause.as_ref().map(where_operators).unwrap_or_default(),
+            num_ids: query_ids.as_ref().map(Vec::len),
+            limit: request.limit,
+            offset: request.offset.unwrap_or_default(),
         };
-        let response = CountRecordsResponse { count: c as u32 };
-        Ok(Response::new(response))
-    }
 
-    async fn query_metadata(
-        &self,
-        request: Request<QueryMetadataRequest>,
-    ) -> Result<Response<QueryMetadataResponse>, Status> {
-        let query_span = trace_span!("Query metadata", segment_id = request.get_ref().segment_id);
-        let instrumented_span = wrap_span_with_parent_context(query_span, request.metadata());
-        self.query_metadata_instrumented(request)
-            .instrument(instrumented_span)
-            .await
-    }
-}
+        // A get without ids, filters or limit scans the whole collection, so it runs as batch
+        // work to leave the interactive queue to point lookups
+        let priority = if query_ids.is_none() && clause.is_none() && request.limit.is_none() {
+            TaskPriority::Batch
+        } else {
+            TaskPriority::Interactive
+        };
+
+        let orchestrator = GetOrchestrator::new(
+            self.collection_blockfile_provider(collection_uuid),
+            self.clone_dispatcher()?,
+            self.orchestrator_queue_size,
+            FetchLogOperator {
+                log_client: self.log.clone(),
+                batch_size: 100,
+                start_log_offset_id: log_position as u32 + 1,
+                maximum_fetch_count: None,
+                maximum_backlog: self.max_log_backlog(&request.version_context),
+                collection_uuid,
+            },
+            FetchSegmentOperator {
+                sysdb: self.sysdb.clone(),
+                vector_uuid: None,
+                metadata_uuid: Some(SegmentUuid(segment_uuid)),
+                record_uuid: None,
+                collection_uuid,
+                collection_version,
+                cache_invalidator: Some(self.segment_cache_invalidator.clone()),
+            },
+            FilterOperator {
+                query_ids,
+                where_clause: clause,
+            },
+            LimitOperator {
+                skip: request.offset.unwrap_or_default(),
+                fetch: request.limit,
+            },
+            ProjectionOperator {
+                document: request.include_metadata,
+                embedding: request.include_embeddings,
+                metadata: request.include_metadata,
+                uri: request.include_metadata,
+                concurrency: self.hydration_concurrency,
+                default_include: request.default_include,
+            },
+        )
+        .with_deadline(deadline)
+        .with_memory_pool(self.memory_pool.clone())
+        .with_priority(priority)
+        .with_middleware(self.operator_middleware.clone())
+        .with_offset_id_cache(self.offset_id_cache.clone())
+        .with_result_cache(self.result_cache.clone());
+        Ok((orchestrator, summary))
+    }
+
+    // Builds the orchestrator that evaluates the filter of a query whose records are summarized
+    // or fused instead of projected. The segment of the request is either the metadata or the
+    // vector segment of the collection
+    fn filter_orchestrator(
+        &self,
+        segment_id: &str,
+        scope: SegmentScope,
+        collection_uuid: CollectionUuid,
+        version_context: &Option<RequestVersionContext>,
+        clause: Option<Where>,
+    ) -> Result<KnnFilterOrchestrator, Status> {
+        let segment_uuid = Some(SegmentUuid(to_segment_uuid(segment_id)?));
+        let (metadata_uuid, vector_uuid) = match scope {
+            SegmentScope::VECTOR => (None, segment_uuid),
+            _ => (segment_uuid, None),
+        };
+        let (collection_version, log_position) = get_version_context(version_context)?;
+        let orchestrator = KnnFilterOrchestrator::new(
+            self.collection_blockfile_provider(collection_uuid),
+            self.clone_dispatcher()?,
+            self.orchestrator_queue_size,
+            FetchLogOperator {
+                log_client: self.log.clone(),
+                batch_size: 100,
+                start_log_offset_id: log_position as u32 + 1,
+                maximum_fetch_count: None,
+                maximum_backlog: self.max_log_backlog(version_context),
+                collection_uuid,
+            },
+            FetchSegmentOperator {
+                sysdb: self.sysdb.clone(),
+                vector_uuid,
+                metadata_uuid,
+                record_uuid: None,
+                collection_uuid,
+                collection_version,
+                cache_invalidator: Some(self.segment_cache_invalidator.clone()),
+            },
+            FilterOperator {
+                query_ids: None,
+                where_clause: clause,
+            },
+        )
+        .with_memory_pool(self.memory_pool.clone());
+        Ok(orchestrator)
+    }
+
+    // Summarizes the records of a collection that match the filter without hydrating them.
+    // Returns `None` if the collection is empty
+    async fn run_analytics(
+        &self,
+        filter_orchestrator: KnnFilterOrchestrator,
+        collection_uuid: CollectionUuid,
+        query: AnalyticsQuery,
+    ) -> Result<Option<AnalyticsOutput>, Status> {
+        let knn_filter_output = match filter_orchestrator.run(self.clone_system()?).await {
+            Ok(output) => output,
+            Err(KnnError::EmptyCollection) => return Ok(None),
+            Err(e) => {
+                tracing::error!("Error running orchestrator: {}", e);
+                return Err(error_status(
+                    &e,
+                    format!("Error running orchestrator: {}", e),
+                ));
+            }
+        };
+        let orchestrator = AnalyticsOrchestrator::new(
+            self.collection_blockfile_provider(collection_uuid),
+            self.clone_dispatcher()?,
+            self.orchestrator_queue_size,
+            knn_filter_output,
+            query,
+        );
+        let output = orchestrator.run(self.clone_system()?).await.map_err(|e| {
+            tracing::error!("Error running orchestrator: {}", e);
+            error_status(&e, format!("Error running orchestrator: {}", e))
+        })?;
+        Ok(Some(output))
+    }
+
+    async fn aggregate_metadata_instrumented(
+        &self,
+        request: Request<AggregateMetadataRequest>,
+    ) -> Result<Response<AggregateMetadataResponse>, Status> {
+        let alias_scope = get_alias_scope(request.metadata());
+        let request = request.into_inner();
+        let collection_uuid = self
+            .resolve_collection_uuid(&alias_scope, &request.collection_id)
+            .await?;
+        if request.key.is_empty() {
+            return Err(ErrorDetails::invalid_argument("key")
+                .into_status(Code::InvalidArgument, "No metadata key provided"));
+        }
+        let clause = get_where_clause(request.r#where, request.where_document)?;
+        let filter_orchestrator = self.filter_orchestrator(
+            &request.segment_id,
+            SegmentScope::METADATA,
+            collection_uuid,
+            &request.version_context,
+            clause,
+        )?;
+        let output = match self
+            .run_analytics(
+                filter_orchestrator,
+                collection_uuid,
+                AnalyticsQuery::Aggregate(AggregateOperator { key: request.key }),
+            )
+            .await?
+        {
+            Some(AnalyticsOutput::Aggregate(output)) => output,
+            Some(_) => return Err(Status::internal("Unexpected analytics output")),
+            None => {
+                return Ok(Response::new(AggregateMetadataResponse {
+                    groups: Vec::new(),
+                    numeric: None,
+                }))
+            }
+        };
+        let response = AggregateMetadataResponse {
+            groups: output
+                .groups
+                .into_iter()
+                .map(|group| chroma_proto::MetadataValueCount {
+                    value: Some(group.value.into()),
+                    count: group.count,
+                })
+                .collect(),
+            numeric: output
+                .numeric
+                .map(|numeric| chroma_proto::NumericAggregate {
+                    count: numeric.count,
+                    min: numeric.min,
+                    max: numeric.max,
+                    sum: numeric.sum,
+                    avg: numeric.avg,
+                }),
+        };
+        Ok(Response::new(response))
+    }
+
+    async fn facet_metadata_instrumented(
+        &self,
+        request: Request<FacetMetadataRequest>,
+    ) -> Result<Response<FacetMetadataResponse>, Status> {
+        let alias_scope = get_alias_scope(request.metadata());
+        let request = request.into_inner();
+        let collection_uuid = self
+            .resolve_collection_uuid(&alias_scope, &request.collection_id)
+            .await?;
+        if request.keys.is_empty() {
+            return Err(ErrorDetails::invalid_argument("keys")
+                .into_status(Code::InvalidArgument, "No metadata keys provided"));
+        }
+        let clause = get_where_clause(request.r#where, request.where_document)?;
+        let filter_orchestrator = self.filter_orchestrator(
+            &request.segment_id,
+            SegmentScope::METADATA,
+            collection_uuid,
+            &request.version_context,
+            clause,
+        )?;
+        let facets = match self
+            .run_analytics(
+                filter_orchestrator,
+                collection_uuid,
+                AnalyticsQuery::Facet(FacetOperator {
+                    keys: request.keys.clone(),
+                    limit: request.limit,
+                }),
+            )
+            .await?
+        {
+            Some(AnalyticsOutput::Facet(output)) => output.facets,
+            Some(_) => return Err(Status::internal("Unexpected analytics output")),
+            // The keys of an empty collection have no values
+            None => request
+                .keys
+                .into_iter()
+                .map(|key| Facet {
+                    key,
+                    groups: Vec::new(),
+                })
+                .collect(),
+        };
+        let response = FacetMetadataResponse {
+            facets: facets
+                .into_iter()
+                .map(|facet| chroma_proto::MetadataFacet {
+                    key: facet.key,
+                    values: facet
+                        .groups
+                        .into_iter()
+                        .map(|group| chroma_proto::MetadataValueCount {
+                            value: Some(group.value.into()),
+                            count: group.count,
+                        })
+                        .collect(),
+                })
+                .collect(),
+        };
+        Ok(Response::new(response))
+    }
+
+    async fn hybrid_search_instrumented(
+        &self,
+        request: Request<HybridSearchRequest>,
+    ) -> Result<Response<HybridSearchResponse>, Status> {
+        let deadline = get_deadline(request.metadata());
+        let alias_scope = get_alias_scope(request.metadata());
+        let request = request.into_inner();
+        let collection_uuid = self
+            .resolve_collection_uuid(&alias_scope, &request.collection_id)
+            .await?;
+        if request.query.is_empty() {
+            return Err(ErrorDetails::invalid_argument("query")
+                .into_status(Code::InvalidArgument, "No full-text query provided"));
+        }
+        let fusion = match request.fusion() {
+            chroma_proto::FusionFunction::ReciprocalRank => FusionFunction::ReciprocalRank {
+                k: request.rrf_k.unwrap_or(DEFAULT_RRF_K),
+            },
+            chroma_proto::FusionFunction::WeightedSum => FusionFunction::WeightedSum,
+        };
+        let accumulation = match request.accumulation() {
+            chroma_proto::DistanceAccumulation::AccumulateF32 => DistanceAccumulation::F32,
+            chroma_proto::DistanceAccumulation::AccumulateF64 => DistanceAccumulation::F64,
+        };
+        let (embedding, _encoding): (Vec<f32>, ScalarEncoding) = match request.vector {
+            Some(vector) => vector
+                .try_into()
+                .map_err(|e| Status::internal(format!("Error converting vector: {}", e)))?,
+            None => {
+                return Err(ErrorDetails::invalid_argument("vector")
+                    .into_status(Code::InvalidArgument, "No query vector provided"))
+            }
+        };
+        let (_, log_position) = get_version_context(&request.version_context)?;
+        let clause = get_where_clause(request.r#where, request.where_document)?;
+        let filter_orchestrator = self.filter_orchestrator(
+            &request.segment_id,
+            SegmentScope::VECTOR,
+            collection_uuid,
+            &request.version_context,
+            clause,
+        )?;
+        let knn_filter_output = match filter_orchestrator.run(self.clone_system()?).await {
+            Ok(output) => output,
+            // An empty collection has no records to search
+            Err(KnnError::EmptyCollection) => {
+                return Ok(Response::new(HybridSearchResponse {
+                    results: Vec::new(),
+                    truncated: false,
+                }))
+            }
+            Err(e) => {
+                tracing::error!("Error running orchestrator: {}", e);
+                return Err(error_status(
+                    &e,
+                    format!("Error running orchestrator: {}", e),
+                ));
+            }
+        };
+
+        let hybrid_search = HybridSearchOperator {
+            knn: KnnOperator {
+                embedding,
+                fetch: request.candidates.unwrap_or(request.k),
+                max_distance: None,
+                time_limit: request
+                    .time_limit_ms
+                    .map(|time_limit_ms| Duration::from_millis(time_limit_ms.into())),
+                accumulation,
+            },
+            query: request.query,
+            fetch: request.k,
+            fusion,
+            text_weight: request.text_weight.unwrap_or(1.0),
+            vector_weight: request.vector_weight.unwrap_or(1.0),
+        };
+        let knn_projection = KnnProjectionOperator {
+            projection: ProjectionOperator {
+                document: request.include_metadata,
+                embedding: request.include_embeddings,
+                metadata: request.include_metadata,
+                uri: request.include_metadata,
+                concurrency: self.hydration_concurrency,
+                default_include: false,
+            },
+            // The fused scores are attached to the records by the orchestrator
+            distance: false,
+        };
+        let orchestrator = HybridSearchOrchestrator::new(
+            self.collection_blockfile_provider(collection_uuid),
+            self.clone_dispatcher()?,
+            self.hnsw_index_provider.clone(),
+            self.orchestrator_queue_size,
+            knn_filter_output,
+            hybrid_search,
+            knn_projection,
+        )
+        .with_deadline(deadline);
+        let output = orchestrator.run(self.clone_system()?).await.map_err(|e| {
+            tracing::error!("Error running orchestrator: {}", e);
+            error_status(&e, format!("Error running orchestrator: {}", e))
+        })?;
+
+        let mut scores = Vec::with_capacity(output.records.len());
+        let mut records = Vec::with_capacity(output.records.len());
+        for record in output.records {
+            scores.push((record.score, record.text_score, record.distance));
+            records.push(record.record);
+        }
+        // The results of a hybrid search are few, so their embeddings are always inlined
+        let records = to_metadata_embedding_records(
+            records,
+            request.include_metadata,
+            &request.version_context,
+            log_position as i64,
+            EmbeddingBudget::new(None),
+        )?;
+        let results = records
+            .into_iter()
+            .zip(scores)
+            .map(
+                |(record, (score, text_score, distance))| chroma_proto::HybridSearchResult {
+                    record: Some(record),
+                    score,
+                    text_score,
+                    distance,
+                },
+            )
+            .collect();
+        Ok(Response::new(HybridSearchResponse {
+            results,
+            truncated: output.truncated,
+        }))
+    }
+
+    async fn get_embeddings_instrumented(
+        &self,
+        request: Request<GetEmbeddingsRequest>,
+    ) -> Result<Response<GetEmbeddingsResponse>, Status> {
+        let deadline = get_deadline(request.metadata());
+        let alias_scope = get_alias_scope(request.metadata());
+        let request = request.into_inner();
+        let segment_uuid = to_segment_uuid(&request.segment_id)?;
+        let collection_uuid = self
+            .resolve_collection_uuid(&alias_scope, &request.collection_id)
+            .await?;
+        let (collection_version, log_position) = get_version_context(&request.version_context)?;
+
+        // The handles are resolved at the version of the collection and the log offset that they
+        // were read at, so that their offset ids refer to the same records and the logs written
+        // since do not change them
+        let maximum_fetch_count = request
+            .log_offset
+            .map(|log_offset| log_offset.saturating_sub(log_position) as u32);
+        let orchestrator = GetOrchestrator::new(
+            self.collection_blockfile_provider(collection_uuid),
+            self.clone_dispatcher()?,
+            self.orchestrator_queue_size,
+            FetchLogOperator {
+                log_client: self.log.clone(),
+                batch_size: 100,
+                start_log_offset_id: log_position as u32 + 1,
+                maximum_fetch_count,
+                maximum_backlog: self.max_log_backlog(&request.version_context),
+                collection_uuid,
+            },
+            FetchSegmentOperator {
+                sysdb: self.sysdb.clone(),
+                vector_uuid: None,
+                metadata_uuid: Some(SegmentUuid(segment_uuid)),
+                record_uuid: None,
+                collection_uuid,
+                collection_version,
+                cache_invalidator: Some(self.segment_cache_invalidator.clone()),
+            },
+            FilterOperator {
+                query_ids: None,
+                where_clause: None,
+            },
+            LimitOperator {
+                skip: 0,
+                fetch: None,
+            },
+            ProjectionOperator {
+                document: false,
+                embedding: true,
+                metadata: false,
+                uri: false,
+                concurrency: self.hydration_concurrency,
+                default_include: false,
+            },
+        )
+        .with_deadline(deadline)
+        .with_memory_pool(self.memory_pool.clone())
+        .with_middleware(self.operator_middleware.clone())
+        .with_offset_ids(request.offset_ids);
+
+        let system = self.clone_system()?;
+        let result = orchestrator.run(system).await.map_err(|e| {
+            tracing::error!("Error running orchestrator: {}", e);
+            error_status(&e, format!("Error running orchestrator: {}", e))
+        })?;
+
+        let mut output = Vec::with_capacity(result.records.len());
+        for record in result.records {
+            let vector = record.embedding.unwrap_or_default();
+            let vector_len = vector.len();
+            let proto_vector = match (vector, ScalarEncoding::FLOAT32, vector_len).try_into() {
+                Ok(vector) => vector,
+                Err(_) => {
+                    return Err(Status::internal("Error converting vector"));
+                }
+            };
+            output.push(chroma_proto::VectorEmbeddingRecord {
+                id: record.id,
+                vector: Some(proto_vector),
+            });
+        }
+
+        let response = chroma_proto::GetEmbeddingsResponse { records: output };
+        Ok(Response::new(response))
+    }
+
+    async fn warm_up_collection_instrumented(
+        &self,
+        request: Request<WarmUpCollectionRequest>,
+    ) -> Result<Response<WarmUpCollectionResponse>, Status> {
+        let alias_scope = get_alias_scope(request.metadata());
+        let request = request.into_inner();
+        let collection_uuid = self
+            .resolve_collection_uuid(&alias_scope, &request.collection_id)
+            .await?;
+        let (collection_version, _) = get_version_context(&request.version_context)?;
+
+        let orchestrator = WarmUpOrchestrator::new(
+            self.collection_blockfile_provider(collection_uuid),
+            self.clone_dispatcher()?,
+            self.hnsw_index_provider.clone(),
+            self.orchestrator_queue_size,
+            self.storage.clone(),
+            FetchSegmentOperator {
+                sysdb: self.sysdb.clone(),
+                vector_uuid: None,
+                metadata_uuid: None,
+                record_uuid: None,
+                collection_uuid,
+                collection_version,
+                cache_invalidator: Some(self.segment_cache_invalidator.clone()),
+            },
+        );
+
+        let system = self.clone_system()?;
+        let result = orchestrator.run(system).await.map_err(|e| {
+            tracing::error!("Error running orchestrator: {}", e);
+            error_status(&e, format!("Error running orchestrator: {}", e))
+        })?;
+
+        Ok(Response::new(WarmUpCollectionResponse {
+            num_blocks: result.num_blocks as u32,
+            vector_index_loaded: result.hnsw_loaded,
+            num_hot_blocks: result.num_hot_blocks as u32,
+        }))
+    }
+
+    async fn export_collection_indexes_instrumented(
+        &self,
+        request: Request<ExportCollectionIndexesRequest>,
+    ) -> Result<Response<ExportCollectionIndexesResponse>, Status> {
+        let alias_scope = get_alias_scope(request.metadata());
+        let request = request.into_inner();
+        let collection_uuid = self
+            .resolve_collection_uuid(&alias_scope, &request.collection_id)
+            .await?;
+        let (collection_version, _) = get_version_context(&request.version_context)?;
+        if request.destination_prefix.is_empty() {
+            return Err(Status::invalid_argument("Destination prefix is empty"));
+        }
+        // The export must not overwrite the files of the collections, which are written at
+        // the root of the storage
+        if request.destination_prefix.starts_with('/')
+            || request
+                .destination_prefix
+                .split('/')
+                .any(|component| component.is_empty() || component == "." || component == "..")
+        {
+            return Err(Status::invalid_argument(
+                "Destination prefix must be a relative path without empty, . or .. components",
+            ));
+        }
+
+        let orchestrator = ExportIndexOrchestrator::new(
+            self.collection_blockfile_provider(collection_uuid),
+            self.clone_dispatcher()?,
+            self.orchestrator_queue_size,
+            self.storage.clone(),
+            FetchSegmentOperator {
+                sysdb: self.sysdb.clone(),
+                vector_uuid: None,
+                metadata_uuid: None,
+                record_uuid: None,
+                collection_uuid,
+                collection_version,
+                cache_invalidator: Some(self.segment_cache_invalidator.clone()),
+            },
+            ExportIndexOperator {
+                prefix: request.destination_prefix,
+                temporary_path: self.hnsw_index_provider.temporary_storage_path.clone(),
+            },
+        );
+
+        let system = self.clone_system()?;
+        let result = orchestrator.run(system).await.map_err(|e| {
+            tracing::error!("Error running orchestrator: {}", e);
+            error_status(&e, format!("Error running orchestrator: {}", e))
+        })?;
+
+        Ok(Response::new(ExportCollectionIndexesResponse {
+            num_tokens: result.num_tokens as u32,
+            num_posting_lists: result.num_posting_lists as u32,
+        }))
+    }
+
+    async fn check_collection_integrity_instrumented(
+        &self,
+        request: Request<CheckCollectionIntegrityRequest>,
+    ) -> Result<Response<CheckCollectionIntegrityResponse>, Status> {
+        let alias_scope = get_alias_scope(request.metadata());
+        let request = request.into_inner();
+        let collection_uuid = self
+            .resolve_collection_uuid(&alias_scope, &request.collection_id)
+            .await?;
+        let (collection_version, _) = get_version_context(&request.version_context)?;
+
+        let orchestrator = CheckIntegrityOrchestrator::new(
+            self.collection_blockfile_provider(collection_uuid),
+            self.clone_dispatcher()?,
+            self.hnsw_index_provider.clone(),
+            self.orchestrator_queue_size,
+            self.sysdb.clone(),
+            FetchSegmentOperator {
+                sysdb: self.sysdb.clone(),
+                vector_uuid: None,
+                metadata_uuid: None,
+                record_uuid: None,
+                collection_uuid,
+                collection_version,
+                cache_invalidator: Some(self.segment_cache_invalidator.clone()),
+            },
+            request.repair,
+            self.stream_batch_size,
+        );
+
+        let system = self.clone_system()?;
+        let result = orchestrator.run(system).await.map_err(|e| {
+            tracing::error!("Error running orchestrator: {}", e);
+            error_status(&e, format!("Error running orchestrator: {}", e))
+        })?;
+
+        let issues = result
+            .check
+            .issues
+            .into_iter()
+            .map(|issue| chroma_proto::IntegrityIssue {
+                check: match issue.check {
+                    IntegrityCheck::OffsetIdContinuity => {
+                        chroma_proto::IntegrityCheck::OffsetIdContinuity
+                    }
+                    IntegrityCheck::MetadataAgreement => {
+                        chroma_proto::IntegrityCheck::MetadataAgreement
+                    }
+                    IntegrityCheck::VectorIndexCoverage => {
+                        chroma_proto::IntegrityCheck::VectorIndexCoverage
+                    }
+                } as i32,
+                description: issue.description,
+            })
+            .collect();
+        let (rebuilt_segment_ids, collection_version) = match result.rebuild {
+            Some(rebuild) => (
+                rebuild
+                    .rebuilt_segments
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect(),
+                Some(rebuild.collection_version),
+            ),
+            None => (Vec::new(), None),
+        };
+        Ok(Response::new(CheckCollectionIntegrityResponse {
+            num_records: result.check.num_records as u32,
+            num_issues: result.check.num_issues as u32,
+            issues,
+            rebuilt_segment_ids,
+            collection_version,
+        }))
+    }
+
+    async fn verify_collection_checksum_instrumented(
+        &self,
+        request: Request<VerifyCollectionChecksumRequest>,
+    ) -> Result<Response<VerifyCollectionChecksumResponse>, Status> {
+        let alias_scope = get_alias_scope(request.metadata());
+        let request = request.into_inner();
+        let collection_uuid = self
+            .resolve_collection_uuid(&alias_scope, &request.collection_id)
+            .await?;
+        let (collection_version, _) = get_version_context(&request.version_context)?;
+
+        let orchestrator = VerifyChecksumOrchestrator::new(
+            self.collection_blockfile_provider(collection_uuid),
+            self.clone_dispatcher()?,
+            self.orchestrator_queue_size,
+            FetchSegmentOperator {
+                sysdb: self.sysdb.clone(),
+                vector_uuid: None,
+                metadata_uuid: None,
+                record_uuid: None,
+                collection_uuid,
+                collection_version,
+                cache_invalidator: Some(self.segment_cache_invalidator.clone()),
+            },
+        );
+
+        let system = self.clone_system()?;
+        let result = orchestrator.run(system).await.map_err(|e| {
+            tracing::error!("Error running orchestrator: {}", e);
+            error_status(&e, format!("Error running orchestrator: {}", e))
+        })?;
+
+        Ok(Response::new(VerifyCollectionChecksumResponse {
+            stored_checksum: result.stored.map(|checksum| checksum.to_string()),
+            computed_checksum: result.computed.to_string(),
+            num_records: result.computed.num_records(),
+            matches: result.stored == Some(result.computed),
+        }))
+    }
+
+    async fn list_slow_queries_instrumented(
+        &self,
+        request: Request<ListSlowQueriesRequest>,
+    ) -> Result<Response<ListSlowQueriesResponse>, Status> {
+        let alias_scope = get_alias_scope(request.metadata());
+        let request = request.into_inner();
+        let collection_uuid = match &request.collection_id {
+            Some(collection_id) => Some(
+                self.resolve_collection_uuid(&alias_scope, collection_id)
+                    .await?,
+            ),
+            None => None,
+        };
+        let queries = self
+            .slow_query_log
+            .recent()
+            .into_iter()
+            .filter(|query| {
+                collection_uuid
+                    .is_none_or(|collection_uuid| query.summary.collection_id == collection_uuid)
+            })
+            .map(Into::into)
+            .collect();
+        Ok(Response::new(ListSlowQueriesResponse { queries }))
+    }
+
+    async fn list_cached_blocks_instrumented(
+        &self,
+        request: Request<ListCachedBlocksRequest>,
+    ) -> Result<Response<ListCachedBlocksResponse>, Status> {
+        let limit = request.into_inner().limit as usize;
+        let blocks = self
+            .blockfile_provider
+            .largest_cached_blocks(limit)
+            .into_iter()
+            .map(|entry| chroma_proto::CachedBlock {
+                block_id: entry.key.to_string(),
+                size_bytes: entry.size_bytes as u64,
+                collection_id: entry.partition,
+            })
+            .collect();
+        Ok(Response::new(ListCachedBlocksResponse {
+            blocks,
+            usage_bytes: self.blockfile_provider.cache_usage_bytes() as u64,
+            capacity_bytes: self
+                .blockfile_provider
+                .cache_capacity_bytes()
+                .map(|capacity_bytes| capacity_bytes as u64),
+        }))
+    }
+
+    async fn merge_collections_instrumented(
+        &self,
+        request: Request<MergeCollectionsRequest>,
+    ) -> Result<Response<MergeCollectionsResponse>, Status> {
+        let alias_scope = get_alias_scope(request.metadata());
+        let request = request.into_inner();
+        if request.source_collection_ids.is_empty() {
+            return Err(
+                ErrorDetails::invalid_argument("source_collection_ids").into_status(
+                    Code::InvalidArgument,
+                    "At least one source collection is required",
+                ),
+            );
+        }
+        let target_collection_uuid = self
+            .resolve_collection_uuid(&alias_scope, &request.target_collection_id)
+            .await?;
+        let mut source_collection_uuids = Vec::with_capacity(request.source_collection_ids.len());
+        for collection_id in &request.source_collection_ids {
+            let collection_uuid = self
+                .resolve_collection_uuid(&alias_scope, collection_id)
+                .await?;
+            if collection_uuid == target_collection_uuid {
+                return Err(
+                    ErrorDetails::invalid_argument("source_collection_ids").into_status(
+                        Code::InvalidArgument,
+                        format!(
+                            "The target collection cannot be a source collection: {}",
+                            collection_id
+                        ),
+                    ),
+                );
+            }
+            source_collection_uuids.push(collection_uuid);
+        }
+        let duplicate_policy = match request.duplicate_policy() {
+            chroma_proto::MergeDuplicatePolicy::KeepFirst => MergeDuplicatePolicy::KeepFirst,
+            chroma_proto::MergeDuplicatePolicy::KeepLast => MergeDuplicatePolicy::KeepLast,
+            chroma_proto::MergeDuplicatePolicy::FailOnDuplicate => MergeDuplicatePolicy::Fail,
+        };
+
+        // The merged records are written in partitions of the size of a streamed batch
+        let orchestrator = MergeOrchestrator::new(
+            self.clone_system()?,
+            source_collection_uuids,
+            target_collection_uuid,
+            duplicate_policy,
+            self.log.clone(),
+            self.sysdb.clone(),
+            self.collection_blockfile_provider(target_collection_uuid),
+            self.hnsw_index_provider.clone(),
+            self.clone_dispatcher()?,
+            self.stream_batch_size,
+        );
+
+        let result = orchestrator.run().await.map_err(|e| {
+            tracing::error!("Error running orchestrator: {}", e);
+            error_status(&e, format!("Error running orchestrator: {}", e))
+        })?;
+        tracing::info!(
+            "Merged {} records into collection {}",
+            result.num_records,
+            result.target_collection_id
+        );
+
+        Ok(Response::new(MergeCollectionsResponse {
+            num_records: result.num_records as u64,
+        }))
+    }
+
+    /// Resolves the collection id of a request, which is either a collection uuid or an alias
+    /// in the tenant and database of the request
+    async fn resolve_collection_uuid(
+        &self,
+        alias_scope: &AliasScope,
+        collection_id: &str,
+    ) -> Result<CollectionUuid, Status> {
+        self.collection_alias_resolver
+            .resolve(&alias_scope.tenant, &alias_scope.database, collection_id)
+            .await
+            .map_err(|e| match e {
+                ResolveAliasError::NotFound(_) => ErrorDetails::invalid_argument("collection_id")
+                    .into_status(
+                        Code::InvalidArgument,
+                        format!("Invalid Collection UUID or alias: {}", collection_id),
+                    ),
+                ResolveAliasError::Unscoped(_) => ErrorDetails::invalid_argument("collection_id")
+                    .into_status(
+                        Code::InvalidArgument,
+                        format!(
+                            "An alias requires the {} and {} headers: {}",
+                            TENANT_HEADER, DATABASE_HEADER, collection_id
+                        ),
+                    ),
+                e => error_status(&e, e.to_string()),
+            })
+    }
+
+    fn clone_dispatcher(&self) -> Result<ComponentHandle<Dispatcher>, Status> {
+        let dispatcher = self
+            .dispatcher
+            .as_ref()
+            .ok_or_else(|| Status::internal("No dispatcher found"))?;
+        Ok(dispatcher.clone())
+    }
+
+    fn clone_system(&self) -> Result<System, Status> {
+        let system = self
+            .system
+            .as_ref()
+            .ok_or_else(|| Status::internal("No system found"))?;
+        Ok(system.clone())
+    }
+}
+
+#[tonic::async_trait]
+impl chroma_proto::vector_reader_server::VectorReader for WorkerServer {
+    async fn get_vectors(
+        &self,
+        request: Request<GetVectorsRequest>,
+    ) -> Result<Response<GetVectorsResponse>, Status> {
+        // Note: We cannot write a middleware that instruments every service rpc
+        // with a span because of https://github.com/hyperium/tonic/pull/1202.
+        let request_span = trace_span!(
+            "Get vectors",
+            segment_id = request.get_ref().segment_id,
+            ids = ?request.get_ref().ids
+        );
+
+        let instrumented_span = wrap_span_with_parent_context(request_span, request.metadata());
+        self.get_vectors_instrumented(request)
+            .instrument(instrumented_span)
+            .await
+    }
+
+    async fn query_vectors(
+        &self,
+        request: Request<QueryVectorsRequest>,
+    ) -> Result<Response<QueryVectorsResponse>, Status> {
+        // Note: We cannot write a middleware that instruments every service rpc
+        // with a span because of https://github.com/hyperium/tonic/pull/1202.
+        let query_span = trace_span!(
+            "Query vectors",
+            k = request.get_ref().k,
+            segment_id = request.get_ref().segment_id,
+            include_embeddings = request.get_ref().include_embeddings,
+            allowed_ids = ?request.get_ref().allowed_ids
+        );
+        let instrumented_span = wrap_span_with_parent_context(query_span, request.metadata());
+        self.query_vectors_instrumented(request)
+            .instrument(instrumented_span)
+            .await
+    }
+
+    async fn hybrid_search(
+        &self,
+        request: Request<HybridSearchRequest>,
+    ) -> Result<Response<HybridSearchResponse>, Status> {
+        // Note: We cannot write a middleware that instruments every service rpc
+        // with a span because of https://github.com/hyperium/tonic/pull/1202.
+        let hybrid_search_span = trace_span!(
+            "Hybrid search",
+            k = request.get_ref().k,
+            segment_id = request.get_ref().segment_id,
+            include_embeddings = request.get_ref().include_embeddings
+        );
+        let instrumented_span =
+            wrap_span_with_parent_context(hybrid_search_span, request.metadata());
+        self.hybrid_search_instrumented(request)
+            .instrument(instrumented_span)
+            .await
+    }
+}
+
+#[tonic::async_trait]
+impl chroma_proto::metadata_reader_server::MetadataReader for WorkerServer {
+    async fn count_records(
+        &self,
+        request: Request<CountRecordsRequest>,
+    ) -> Result<Response<CountRecordsResponse>, Status> {
+        let alias_scope = get_alias_scope(request.metadata());
+        let request = request.into_inner();
+        let segment_uuid = to_segment_uuid(&request.segment_id)?;
+        let collection_uuid = self
+            .resolve_collection_uuid(&alias_scope, &request.collection_id)
+            .await?;
+        let (collection_version, log_position) = get_version_context(&request.version_context)?;
+
+        // A filtered count is evaluated on the filtered offset ids, without hydrating the records
+        let clause = get_where_clause(request.r#where, request.where_document)?;
+        if clause.is_some() {
+            let filter_orchestrator = self.filter_orchestrator(
+                &request.segment_id,
+                SegmentScope::METADATA,
+                collection_uuid,
+                &request.version_context,
+                clause,
+            )?;
+            let count = match self
+                .run_analytics(
+                    filter_orchestrator,
+                    collection_uuid,
+                    AnalyticsQuery::Count(CountOperator {}),
+                )
+                .await?
+            {
+                Some(AnalyticsOutput::Count(output)) => output.count,
+                Some(_) => return Err(Status::internal("Unexpected analytics output")),
+                None => 0,
+            };
+            return Ok(Response::new(CountRecordsResponse {
+                count: count as u32,
+            }));
+        }
+
+        let dispatcher = match self.dispatcher {
+            Some(ref dispatcher) => dispatcher,
+            None => {
+                return Err(Status::internal("No dispatcher found"));
+            }
+        };
+
+        let system = match self.system {
+            Some(ref system) => system,
+            None => {
+                return Err(Status::internal("No system found"));
+            }
+        };
+
+        let orchestrator = CountQueryOrchestrator::new(
+            system.clone(),
+            &segment_uuid,
+            &collection_uuid,
+            self.log.clone(),
+            self.sysdb.clone(),
+            dispatcher.clone(),
+            self.collection_blockfile_provider(collection_uuid),
+            collection_version,
+            log_position,
+        );
+
+        let result = orchestrator.run().await;
+        let c = match result {
+            Ok(r) => {
+                println!("Count value {}", r);
+                r
+            }
+            Err(e) => {
+                println!("Error! {:?}", e);
+                // TODO: Return 0 for now but should return an error at some point.
+                0
+            }
+        };
+        let response = CountRecordsResponse { count: c as u32 };
+        Ok(Response::new(response))
+    }
+
+    async fn aggregate_metadata(
+        &self,
+        request: Request<AggregateMetadataRequest>,
+    ) -> Result<Response<AggregateMetadataResponse>, Status> {
+        let aggregate_span = trace_span!(
+            "Aggregate metadata",
+            segment_id = request.get_ref().segment_id,
+            key = request.get_ref().key
+        );
+        let instrumented_span = wrap_span_with_parent_context(aggregate_span, request.metadata());
+        self.aggregate_metadata_instrumented(request)
+            .instrument(instrumented_span)
+            .await
+    }
+
+    async fn facet_metadata(
+        &self,
+        request: Request<FacetMetadataRequest>,
+    ) -> Result<Response<FacetMetadataResponse>, Status> {
+        let facet_span = trace_span!(
+            "Facet metadata",
+            segment_id = request.get_ref().segment_id,
+            keys = ?request.get_ref().keys
+        );
+        let instrumented_span = wrap_span_with_parent_context(facet_span, request.metadata());
+        self.facet_metadata_instrumented(request)
+            .instrument(instrumented_span)
+            .await
+    }
+
+    async fn query_metadata(
+        &self,
+        request: Request<QueryMetadataRequest>,
+    ) -> Result<Response<QueryMetadataResponse>, Status> {
+        let query_span = trace_span!("Query metadata", segment_id = request.get_ref().segment_id);
+        let instrumented_span = wrap_span_with_parent_context(query_span, request.metadata());
+        self.query_metadata_instrumented(request)
+            .instrument(instrumented_span)
+            .await
+    }
+
+    type StreamMetadataStream = MetadataStream;
+
+    async fn stream_metadata(
+        &self,
+        request: Request<QueryMetadataRequest>,
+    ) -> Result<Response<Self::StreamMetadataStream>, Status> {
+        let stream_span = trace_span!("Stream metadata", segment_id = request.get_ref().segment_id);
+        let instrumented_span = wrap_span_with_parent_context(stream_span, request.metadata());
+        self.stream_metadata_instrumented(request)
+            .instrument(instrumented_span)
+            .await
+    }
+
+    type StreamChangesStream = ChangesStream;
+
+    async fn stream_changes(
+        &self,
+        request: Request<StreamChangesRequest>,
+    ) -> Result<Response<Self::StreamChangesStream>, Status> {
+        let stream_span = trace_span!(
+            "Stream changes",
+            collection_id = request.get_ref().collection_id
+        );
+        let instrumented_span = wrap_span_with_parent_context(stream_span, request.metadata());
+        self.stream_changes_instrumented(request)
+            .instrument(instrumented_span)
+            .await
+    }
+
+    async fn get_embeddings(
+        &self,
+        request: Request<GetEmbeddingsRequest>,
+    ) -> Result<Response<GetEmbeddingsResponse>, Status> {
+        let get_span = trace_span!("Get embeddings", segment_id = request.get_ref().segment_id);
+        let instrumented_span = wrap_span_with_parent_context(get_span, request.metadata());
+        self.get_embeddings_instrumented(request)
+            .instrument(instrumented_span)
+            .await
+    }
+}
+
+#[tonic::async_trait]
+impl chroma_proto::query_admin_server::QueryAdmin for WorkerServer {
+    async fn warm_up_collection(
+        &self,
+        request: Request<WarmUpCollectionRequest>,
+    ) -> Result<Response<WarmUpCollectionResponse>, Status> {
+        // Note: We cannot write a middleware that instruments every service rpc
+        // with a span because of https://github.com/hyperium/tonic/pull/1202.
+        let request_span = trace_span!(
+            "Warm up collection",
+            collection_id = request.get_ref().collection_id
+        );
+        let instrumented_span = wrap_span_with_parent_context(request_span, request.metadata());
+        self.warm_up_collection_instrumented(request)
+            .instrument(instrumented_span)
+            .await
+    }
+
+    async fn export_collection_indexes(
+        &self,
+        request: Request<ExportCollectionIndexesRequest>,
+    ) -> Result<Response<ExportCollectionIndexesResponse>, Status> {
+        let request_span = trace_span!(
+            "Export collection indexes",
+            collection_id = request.get_ref().collection_id
+        );
+        let instrumented_span = wrap_span_with_parent_context(request_span, request.metadata());
+        self.export_collection_indexes_instrumented(request)
+            .instrument(instrumented_span)
+            .await
+    }
+
+    async fn check_collection_integrity(
+        &self,
+        request: Request<CheckCollectionIntegrityRequest>,
+    ) -> Result<Response<CheckCollectionIntegrityResponse>, Status> {
+        let request_span = trace_span!(
+            "Check collection integrity",
+            collection_id = request.get_ref().collection_id,
+            repair = request.get_ref().repair
+        );
+        let instrumented_span = wrap_span_with_parent_context(request_span, request.metadata());
+        self.check_collection_integrity_instrumented(request)
+            .instrument(instrumented_span)
+            .await
+    }
+
+    async fn verify_collection_checksum(
+        &self,
+        request: Request<VerifyCollectionChecksumRequest>,
+    ) -> Result<Response<VerifyCollectionChecksumResponse>, Status> {
+        let request_span = trace_span!(
+            "Verify collection checksum",
+            collection_id = request.get_ref().collection_id
+        );
+        let instrumented_span = wrap_span_with_parent_context(request_span, request.metadata());
+        self.verify_collection_checksum_instrumented(request)
+            .instrument(instrumented_span)
+            .await
+    }
+
+    async fn list_slow_queries(
+        &self,
+        request: Request<ListSlowQueriesRequest>,
+    ) -> Result<Response<ListSlowQueriesResponse>, Status> {
+        let request_span = trace_span!("List slow queries");
+        let instrumented_span = wrap_span_with_parent_context(request_span, request.metadata());
+        self.list_slow_queries_instrumented(request)
+            .instrument(instrumented_span)
+            .await
+    }
+
+    async fn list_cached_blocks(
+        &self,
+        request: Request<ListCachedBlocksRequest>,
+    ) -> Result<Response<ListCachedBlocksResponse>, Status> {
+        let request_span = trace_span!("List cached blocks");
+        let instrumented_span = wrap_span_with_parent_context(request_span, request.metadata());
+        self.list_cached_blocks_instrumented(request)
+            .instrument(instrumented_span)
+            .await
+    }
+
+    async fn merge_collections(
+        &self,
+        request: Request<MergeCollectionsRequest>,
+    ) -> Result<Response<MergeCollectionsResponse>, Status> {
+        let request_span = trace_span!(
+            "Merge collections",
+            target_collection_id = request.get_ref().target_collection_id
+        );
+        let instrumented_span = wrap_span_with_parent_context(request_span, request.metadata());
+        self.merge_collections_instrumented(request)
+            .instrument(instrumented_span)
+            .await
+    }
+}
 
 #[cfg(debug_assertions)]
 #[tonic::async_trait]
@@ -553,26 +2101,244 @@ impl chroma_proto::debug_server::Debug for WorkerServer {
     }
 }
 
-fn to_collection_uuid(uuid: &str) -> Result<CollectionUuid, Status> {
-    parse_uuid(uuid, "Invalid Collection UUID").map(CollectionUuid)
-}
+/// Converts the projected records of a get query, and inlines their embeddings within the budget
+fn to_metadata_embedding_records(
+    records: Vec<ProjectionRecord>,
+    include_metadata: bool,
+    version_context: &Option<RequestVersionContext>,
+    log_offset: i64,
+    mut embedding_budget: EmbeddingBudget,
+) -> Result<Vec<chroma_proto::MetadataEmbeddingRecord>, Status> {
+    let mut output = Vec::with_capacity(records.len());
+    for record in records {
+        let metadata = if include_metadata {
+            let mut meta = record.metadata.unwrap_or_default();
+
+            // The transport layer assumes the document exists in the metadata
+            // with the special key "chroma:document"
+            if let Some(doc) = record.document {
+                meta.insert("chroma:document".to_string(), MetadataValue::Str(doc));
+            }
+            // Same for the uri with the special key "chroma:uri"
+            if let Some(uri) = record.uri {
+                meta.insert("chroma:uri".to_string(), MetadataValue::Str(uri));
+            }
+            Some(chroma_proto::UpdateMetadata::from(meta))
+        } else {
+            None
+        };
+
+        let (embedding, embedding_handle) = match record.embedding {
+            Some(vector) if embedding_budget.admit(size_of_val(vector.as_slice()) as u64) => {
+                let vector_len = vector.len();
+                match (vector, ScalarEncoding::FLOAT32, vector_len).try_into() {
+                    Ok(vector) => (Some(vector), None),
+                    Err(_) => {
+                        return Err(Status::internal("Error converting vector"));
+                    }
+                }
+            }
+            // The embedding does not fit in the response, so the client fetches it later
+            Some(_) => (
+                None,
+                Some(chroma_proto::EmbeddingHandle {
+                    offset_id: record.offset_id,
+                    version_context: version_context.clone(),
+                    log_offset: Some(log_offset.max(0) as u64),
+                }),
+            ),
+            None => (None, None),
+        };
+
+        output.push(chroma_proto::MetadataEmbeddingRecord {
+            id: record.id,
+            metadata,
+            embedding,
+            embedding_handle,
+        });
+    }
+    Ok(output)
+}
+
+/// Limits the total size of the embeddings inlined in a response. Once an embedding does not
+/// fit, it and all later embeddings are left out, so that the inlined embeddings are a prefix
+/// of the records in the response
+struct EmbeddingBudget {
+    remaining: Option<u64>,
+    exhausted: bool,
+}
+
+impl EmbeddingBudget {
+    fn new(max_bytes: Option<u64>) -> Self {
+        Self {
+            remaining: max_bytes,
+            exhausted: false,
+        }
+    }
+
+    // Returns whether an embedding of the given size is inlined
+    fn admit(&mut self, bytes: u64) -> bool {
+        if self.exhausted {
+            return false;
+        }
+        match self.remaining.as_mut() {
+            Some(remaining) if *remaining < bytes => {
+                self.exhausted = true;
+                false
+            }
+            Some(remaining) => {
+                *remaining -= bytes;
+                true
+            }
+            None => true,
+        }
+    }
+}
+
+// The embeddings of the changes are always inlined, since the records that are not compacted
+// yet cannot be read by their handles
+fn to_record_changes(
+    changes: Vec<RecordChange>,
+    version_context: &Option<RequestVersionContext>,
+    log_offset: i64,
+) -> Result<Vec<chroma_proto::RecordChange>, Status> {
+    let mut output = Vec::with_capacity(changes.len());
+    for change in changes {
+        let (operation, record) = match change {
+            RecordChange::Upsert(record) => (
+                chroma_proto::Operation::Upsert,
+                to_metadata_embedding_records(
+                    vec![record],
+                    true,
+                    version_context,
+                    log_offset,
+                    EmbeddingBudget::new(None),
+                )?
+                .pop(),
+            ),
+            RecordChange::Delete(id) => (
+                chroma_proto::Operation::Delete,
+                Some(chroma_proto::MetadataEmbeddingRecord {
+                    id,
+                    ..Default::default()
+                }),
+            ),
+        };
+        output.push(chroma_proto::RecordChange {
+            operation: operation as i32,
+            record,
+        });
+    }
+    Ok(output)
+}
+
+fn to_segment_uuid(segment_id: &str) -> Result<Uuid, Status> {
+    parse_uuid(segment_id, "segment_id", "Invalid Segment UUID")
+}
+
+fn parse_uuid(uuid: &str, field: &str, error_msg: &str) -> Result<Uuid, Status> {
+    let uuid = Uuid::parse_str(uuid).map_err(|_| {
+        ErrorDetails::invalid_argument(field)
+            .into_status(Code::InvalidArgument, format!("{}: {}", error_msg, uuid))
+    })?;
+
+    Ok(uuid)
+}
+
+fn get_version_context(ctx: &Option<RequestVersionContext>) -> Result<(u32, u64), Status> {
+    let ctx = ctx.as_ref().ok_or_else(|| {
+        ErrorDetails::invalid_argument("version_context")
+            .into_status(Code::InvalidArgument, "No version context provided")
+    })?;
+    Ok((ctx.collection_version, ctx.log_position))
+}
+
+/// Combines the where and where document clauses of a request into a single clause
+fn get_where_clause(
+    where_clause: Option<chroma_proto::Where>,
+    where_document_clause: Option<chroma_proto::WhereDocument>,
+) -> Result<Option<Where>, Status> {
+    let where_clause = match where_clause {
+        Some(where_clause) => match where_clause.try_into() {
+            Ok(where_clause) => Some(where_clause),
+            Err(_) => {
+                tracing::error!("Error converting where clause");
+                return Err(ErrorDetails::invalid_argument("where")
+                    .into_status(Code::InvalidArgument, "Error converting where clause"));
+            }
+        },
+        None => None,
+    };
+
+    let where_document_clause = match where_document_clause {
+        Some(where_document_clause) => match where_document_clause.try_into() {
+            Ok(where_document_clause) => Some(where_document_clause),
+            Err(_) => {
+                tracing::error!("Error converting where document clause");
+                return Err(
+                    ErrorDetails::invalid_argument("where_document").into_status(
+                        Code::InvalidArgument,
+                        "Error converting where document clause",
+                    ),
+                );
+            }
+        },
+        None => None,
+    };
+
+    Ok(match (where_clause, where_document_clause) {
+        (Some(wc), Some(wdc)) => Some(Where::conjunction(vec![wc, wdc])),
+        (Some(c), None) | (None, Some(c)) => Some(c),
+        _ => None,
+    })
+}
+
+/// The header with the tenant that the collection aliases of a request are resolved in
+const TENANT_HEADER: &str = "x-chroma-tenant";
+/// The header with the database that the collection aliases of a request are resolved in
+const DATABASE_HEADER: &str = "x-chroma-database";
 
-fn to_segment_uuid(segment_id: &str) -> Result<Uuid, Status> {
-    parse_uuid(segment_id, "Invalid Segment UUID")
+/// The tenant and database of a request, which are empty if their headers are missing
+#[derive(Clone, Debug, Default)]
+struct AliasScope {
+    tenant: String,
+    database: String,
 }
 
-fn parse_uuid(uuid: &str, error_msg: &str) -> Result<Uuid, Status> {
-    let uuid = Uuid::parse_str(uuid)
-        .map_err(|_| Status::invalid_argument(format!("{}: {}", error_msg, uuid)))?;
-
-    Ok(uuid)
+fn get_alias_scope(metadata: &MetadataMap) -> AliasScope {
+    let get_header = |key| {
+        metadata
+            .get(key)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string()
+    };
+    AliasScope {
+        tenant: get_header(TENANT_HEADER),
+        database: get_header(DATABASE_HEADER),
+    }
 }
 
-fn get_version_context(ctx: &Option<RequestVersionContext>) -> Result<(u32, u64), Status> {
-    let ctx = ctx
-        .as_ref()
-        .ok_or_else(|| Status::invalid_argument("No version context provided"))?;
-    Ok((ctx.collection_version, ctx.log_position))
+/// Derives the deadline of a query from the `grpc-timeout` header set by the client, which holds
+/// an integer followed by a unit, e.g. `100m` for 100 milliseconds
+fn get_deadline(metadata: &MetadataMap) -> Deadline {
+    let timeout = metadata
+        .get("grpc-timeout")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| {
+            let (amount, unit) = value.split_at(value.len().checked_sub(1)?);
+            let amount = amount.parse::<u64>().ok()?;
+            match unit {
+                "H" => Some(Duration::from_secs(amount.saturating_mul(3600))),
+                "M" => Some(Duration::from_secs(amount.saturating_mul(60))),
+                "S" => Some(Duration::from_secs(amount)),
+                "m" => Some(Duration::from_millis(amount)),
+                "u" => Some(Duration::from_micros(amount)),
+                "n" => Some(Duration::from_nanos(amount)),
+                _ => None,
+            }
+        });
+    timeout.map(Deadline::after).unwrap_or_default()
 }
 
 #[cfg(test)]
@@ -582,6 +2348,10 @@ mod tests {
     #[cfg(debug_assertions)]
     use crate::execution::dispatcher;
     #[cfg(debug_assertions)]
+    use crate::execution::operators::projection::DEFAULT_HYDRATION_CONCURRENCY;
+    #[cfg(debug_assertions)]
+    use crate::execution::orchestration::get::DEFAULT_STREAM_BATCH_SIZE;
+    #[cfg(debug_assertions)]
     use crate::log::log::InMemoryLog;
     #[cfg(debug_assertions)]
     use crate::sysdb::test_sysdb::TestSysDb;
@@ -607,7 +2377,11 @@ mod tests {
 
     #[cfg(debug_assertions)]
     fn run_server() -> String {
-        let sysdb = TestSysDb::new();
+        run_server_with_sysdb(TestSysDb::new())
+    }
+
+    #[cfg(debug_assertions)]
+    fn run_server_with_sysdb(sysdb: TestSysDb) -> String {
         let log = InMemoryLog::new();
         let tmp_dir = tempdir().unwrap();
         let storage = Storage::Local(LocalStorage::new(tmp_dir.path().to_str().unwrap()));
@@ -617,28 +2391,55 @@ mod tests {
         let (_, rx) = tokio::sync::mpsc::unbounded_channel();
         let port = random_port::PortPicker::new().random(true).pick().unwrap();
 
+        let hnsw_index_provider = HnswIndexProvider::new(
+            storage.clone(),
+            tmp_dir.path().to_path_buf(),
+            hnsw_index_cache,
+            rx,
+        );
+        let blockfile_provider = BlockfileProvider::new_arrow(
+            storage.clone(),
+            TEST_MAX_BLOCK_SIZE_BYTES,
+            block_cache,
+            sparse_index_cache,
+        );
+        let sysdb = Box::new(SysDb::Test(sysdb));
         let mut server = WorkerServer {
             dispatcher: None,
             system: None,
-            sysdb: Box::new(SysDb::Test(sysdb)),
-            log: Box::new(Log::InMemory(log)),
-            hnsw_index_provider: HnswIndexProvider::new(
-                storage.clone(),
-                tmp_dir.path().to_path_buf(),
-                hnsw_index_cache,
-                rx,
+            collection_alias_resolver: CollectionAliasResolver::new(
+                sysdb.clone(),
+                Duration::from_secs(60),
+                new_non_persistent_cache_for_test(),
             ),
-            blockfile_provider: BlockfileProvider::new_arrow(
-                storage,
-                TEST_MAX_BLOCK_SIZE_BYTES,
-                block_cache,
-                sparse_index_cache,
+            sysdb,
+            log: Box::new(Log::InMemory(log)),
+            segment_cache_invalidator: SegmentCacheInvalidator::new(
+                blockfile_provider.clone(),
+                hnsw_index_provider.clone(),
             ),
+            hnsw_index_provider,
+            blockfile_provider,
+            storage,
+            memory_pool: MemoryPool::default(),
+            hydration_concurrency: DEFAULT_HYDRATION_CONCURRENCY,
+            offset_id_cache: Some(SharedOffsetIdCache::from(
+                new_non_persistent_cache_for_test(),
+            )),
+            result_cache: Some(SharedResultCache::from(new_non_persistent_cache_for_test())),
+            max_inline_embedding_bytes: None,
+            max_log_backlog: None,
+            stream_batch_size: DEFAULT_STREAM_BATCH_SIZE,
+            orchestrator_queue_size: 1000,
+            slow_query_log: SlowQueryLog::default(),
+            operator_middleware: OperatorMiddleware::default(),
+            spill: None,
+            compaction_admin: None,
             port,
         };
 
         let system: system::System = system::System::new();
-        let dispatcher = dispatcher::Dispatcher::new(4, 10, 10);
+        let dispatcher = dispatcher::Dispatcher::new(4, 0, 10, 10);
         let dispatcher_handle = system.start_component(dispatcher);
 
         server.set_system(system);
@@ -680,6 +2481,7 @@ mod tests {
             version_context: Some(RequestVersionContext {
                 collection_version: 0,
                 log_position: 0,
+                max_log_backlog: None,
             }),
         };
         // segment or collection not found
@@ -741,6 +2543,7 @@ mod tests {
             version_context: Some(RequestVersionContext {
                 collection_version: 0,
                 log_position: 0,
+                max_log_backlog: None,
             }),
             ..Default::default()
         };
@@ -814,6 +2617,7 @@ mod tests {
             version_context: Some(RequestVersionContext {
                 collection_version: 0,
                 log_position: 0,
+                max_log_backlog: None,
             }),
             ..Default::default()
         };
@@ -854,6 +2658,402 @@ mod tests {
         assert!(err.message().contains("context"));
     }
 
+    #[tokio::test]
+    #[cfg(debug_assertions)]
+    async fn validate_count_records_request() {
+        use chroma_proto::metadata_reader_client::MetadataReaderClient as Client;
+        use chroma_types::chroma_proto::CountRecordsRequest as Request;
+
+        let mut reader = Client::connect(run_server()).await.unwrap();
+
+        let request = Request {
+            collection_id: COLLECTION_UUID.to_string(),
+            segment_id: SEGMENT_UUID.into(),
+            version_context: Some(RequestVersionContext {
+                collection_version: 0,
+                log_position: 0,
+                max_log_backlog: None,
+            }),
+            r#where: Some(chroma_proto::Where { r#where: None }),
+            where_document: None,
+        };
+
+        // invalid where clause
+        let response = reader.count_records(request).await;
+        assert!(response.is_err());
+        let err = response.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+        assert!(err.message().contains("where clause"));
+    }
+
+    #[tokio::test]
+    #[cfg(debug_assertions)]
+    async fn validate_aggregate_metadata_request() {
+        use chroma_proto::metadata_reader_client::MetadataReaderClient as Client;
+        use chroma_types::chroma_proto::AggregateMetadataRequest as Request;
+
+        let mut reader = Client::connect(run_server()).await.unwrap();
+
+        let request = Request {
+            collection_id: COLLECTION_UUID.to_string(),
+            segment_id: SEGMENT_UUID.into(),
+            version_context: Some(RequestVersionContext {
+                collection_version: 0,
+                log_position: 0,
+                max_log_backlog: None,
+            }),
+            ..Default::default()
+        };
+
+        // missing key
+        let response = reader.aggregate_metadata(request).await;
+        assert!(response.is_err());
+        let err = response.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+        assert!(err.message().contains("metadata key"));
+    }
+
+    #[tokio::test]
+    #[cfg(debug_assertions)]
+    async fn validate_facet_metadata_request() {
+        use chroma_proto::metadata_reader_client::MetadataReaderClient as Client;
+        use chroma_types::chroma_proto::FacetMetadataRequest as Request;
+
+        let mut reader = Client::connect(run_server()).await.unwrap();
+
+        let request = Request {
+            collection_id: COLLECTION_UUID.to_string(),
+            segment_id: SEGMENT_UUID.into(),
+            version_context: Some(RequestVersionContext {
+                collection_version: 0,
+                log_position: 0,
+                max_log_backlog: None,
+            }),
+            ..Default::default()
+        };
+
+        // missing keys
+        let response = reader.facet_metadata(request).await;
+        assert!(response.is_err());
+        let err = response.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+        assert!(err.message().contains("metadata keys"));
+    }
+
+    #[tokio::test]
+    #[cfg(debug_assertions)]
+    async fn validate_hybrid_search_request() {
+        use chroma_proto::vector_reader_client::VectorReaderClient as Client;
+        use chroma_types::chroma_proto::HybridSearchRequest as Request;
+        use chroma_types::chroma_proto::Vector;
+
+        let mut reader = Client::connect(run_server()).await.unwrap();
+
+        let floats: Vec<f32> = vec![1.0, 2.0];
+
+        let first_request = Request {
+            vector: Some(Vector {
+                vector: to_byte_slice(&floats).into(),
+                encoding: chroma_proto::ScalarEncoding::Float32 as i32,
+                dimension: 2,
+            }),
+            query: "hello".to_string(),
+            k: 1,
+            collection_id: COLLECTION_UUID.to_string(),
+            segment_id: SEGMENT_UUID.into(),
+            version_context: Some(RequestVersionContext {
+                collection_version: 0,
+                log_position: 0,
+                max_log_backlog: None,
+            }),
+            ..Default::default()
+        };
+
+        // missing full-text query
+        let mut request = first_request.clone();
+        request.query = String::new();
+        let response = reader.hybrid_search(request).await;
+        assert!(response.is_err());
+        let err = response.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+        assert!(err.message().contains("full-text query"));
+
+        // missing query vector
+        let mut request = first_request.clone();
+        request.vector = None;
+        let response = reader.hybrid_search(request).await;
+        assert!(response.is_err());
+        let err = response.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+        assert!(err.message().contains("query vector"));
+
+        // invalid segment uuid
+        let mut request = first_request.clone();
+        request.segment_id = INVALID_UUID.into();
+        let response = reader.hybrid_search(request).await;
+        assert!(response.is_err());
+        let err = response.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+        assert!(err.message().contains("Segment UUID"));
+    }
+
+    #[tokio::test]
+    #[cfg(debug_assertions)]
+    async fn validate_lookup_collection_scope() {
+        use chroma_proto::metadata_reader_client::MetadataReaderClient as Client;
+        use chroma_types::chroma_proto::QueryMetadataRequest as Request;
+
+        let mut sysdb = TestSysDb::new();
+        let collection = |id: &str, tenant: &str, database: &str| Collection {
+            collection_id: CollectionUuid(Uuid::parse_str(id).unwrap()),
+            name: id.to_string(),
+            metadata: None,
+            dimension: None,
+            tenant: tenant.to_string(),
+            database: database.to_string(),
+            log_position: 0,
+            version: 0,
+        };
+        let other_tenant = "00000000-0000-0000-0000-000000000004";
+        let other_database = "00000000-0000-0000-0000-000000000005";
+        sysdb.add_collection(collection(COLLECTION_UUID, "tenant", "database"));
+        sysdb.add_collection(collection(other_tenant, "other_tenant", "database"));
+        sysdb.add_collection(collection(other_database, "tenant", "other_database"));
+        let mut reader = Client::connect(run_server_with_sysdb(sysdb)).await.unwrap();
+
+        let version_context = Some(RequestVersionContext {
+            collection_version: 0,
+            log_position: 0,
+            max_log_backlog: None,
+        });
+        let request = |lookup_collection_id: &str| Request {
+            collection_id: COLLECTION_UUID.to_string(),
+            segment_id: SEGMENT_UUID.into(),
+            version_context: version_context.clone(),
+            lookup: Some(chroma_proto::Lookup {
+                collection_id: lookup_collection_id.to_string(),
+                version_context: version_context.clone(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let err = reader
+            .query_metadata(request(other_tenant))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::PermissionDenied);
+
+        let err = reader
+            .query_metadata(request(other_database))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+        assert!(err.message().contains("database"));
+    }
+
+    #[tokio::test]
+    #[cfg(debug_assertions)]
+    async fn validate_stream_metadata_request() {
+        use chroma_proto::metadata_reader_client::MetadataReaderClient as Client;
+        use chroma_types::chroma_proto::QueryMetadataRequest as Request;
+
+        let mut reader = Client::connect(run_server()).await.unwrap();
+
+        let first_request = Request {
+            collection_id: COLLECTION_UUID.to_string(),
+            segment_id: SEGMENT_UUID.into(),
+            version_context: Some(RequestVersionContext {
+                collection_version: 0,
+                log_position: 0,
+                max_log_backlog: None,
+            }),
+            ..Default::default()
+        };
+
+        // The query is validated and planned before the first batch is streamed
+        let response = reader.stream_metadata(first_request.clone()).await;
+        assert!(response.is_err());
+        assert_eq!(response.unwrap_err().code(), tonic::Code::NotFound);
+
+        // invalid segment uuid
+        let mut request = first_request.clone();
+        request.segment_id = INVALID_UUID.into();
+        let response = reader.stream_metadata(request).await;
+
+        assert!(response.is_err());
+        let err = response.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+        assert!(err.message().contains("Segment UUID"));
+    }
+
+    #[tokio::test]
+    #[cfg(debug_assertions)]
+    async fn validate_stream_changes_request() {
+        use chroma_proto::metadata_reader_client::MetadataReaderClient as Client;
+        use chroma_types::chroma_proto::StreamChangesRequest as Request;
+
+        let mut reader = Client::connect(run_server()).await.unwrap();
+
+        let first_request = Request {
+            collection_id: COLLECTION_UUID.to_string(),
+            version_context: Some(RequestVersionContext {
+                collection_version: 0,
+                log_position: 0,
+                max_log_backlog: None,
+            }),
+            start_log_offset: 1,
+            ..Default::default()
+        };
+
+        let response = reader.stream_changes(first_request.clone()).await;
+        assert!(response.is_err());
+        assert_eq!(response.unwrap_err().code(), tonic::Code::NotFound);
+
+        // The changes up to the log position are compacted
+        let mut request = first_request.clone();
+        request.start_log_offset = 0;
+        let response = reader.stream_changes(request).await;
+
+        assert!(response.is_err());
+        let err = response.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+        assert!(err.message().contains("compacted"));
+    }
+
+    #[tokio::test]
+    #[cfg(debug_assertions)]
+    async fn validate_get_embeddings_request() {
+        use chroma_proto::metadata_reader_client::MetadataReaderClient as Client;
+        use chroma_types::chroma_proto::GetEmbeddingsRequest as Request;
+
+        let mut reader = Client::connect(run_server()).await.unwrap();
+
+        let first_request = Request {
+            collection_id: COLLECTION_UUID.to_string(),
+            segment_id: SEGMENT_UUID.into(),
+            version_context: Some(RequestVersionContext {
+                collection_version: 0,
+                log_position: 0,
+                max_log_backlog: None,
+            }),
+            offset_ids: vec![1, 2],
+            log_offset: None,
+        };
+
+        // segment or collection
+        let response = reader.get_embeddings(first_request.clone()).await;
+        assert!(response.is_err());
+        assert_eq!(response.unwrap_err().code(), tonic::Code::NotFound);
+
+        // invalid segment uuid
+        let mut request = first_request.clone();
+        request.segment_id = INVALID_UUID.into();
+        let response = reader.get_embeddings(request).await;
+
+        assert!(response.is_err());
+        let err = response.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+        assert!(err.message().contains("Segment UUID"));
+
+        // invalid version context
+        let mut request = first_request.clone();
+        request.version_context = None;
+        let response = reader.get_embeddings(request).await;
+
+        assert!(response.is_err());
+        let err = response.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+        assert!(err.message().contains("context"));
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn derives_deadline_from_grpc_timeout() {
+        let deadline_for = |timeout: Option<&'static str>| {
+            let mut metadata = MetadataMap::new();
+            if let Some(timeout) = timeout {
+                metadata.insert("grpc-timeout", timeout.parse().unwrap());
+            }
+            get_deadline(&metadata)
+        };
+        assert!(deadline_for(None).check().is_ok());
+        assert!(deadline_for(Some("1H")).check().is_ok());
+        assert!(deadline_for(Some("0n")).check().is_err());
+        // A malformed timeout is ignored
+        assert!(deadline_for(Some("0x")).check().is_ok());
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn inlines_a_prefix_of_embeddings_within_budget() {
+        let mut unlimited = EmbeddingBudget::new(None);
+        assert!(unlimited.admit(u64::MAX));
+        assert!(unlimited.admit(u64::MAX));
+
+        let mut budget = EmbeddingBudget::new(Some(100));
+        assert!(budget.admit(40));
+        assert!(budget.admit(60));
+        assert!(!budget.admit(1));
+        // A smaller embedding after one that does not fit is not inlined either
+        let mut budget = EmbeddingBudget::new(Some(100));
+        assert!(budget.admit(40));
+        assert!(!budget.admit(80));
+        assert!(!budget.admit(10));
+    }
+
+    #[tokio::test]
+    #[cfg(debug_assertions)]
+    async fn validate_export_collection_indexes_request() {
+        use chroma_proto::query_admin_client::QueryAdminClient as Client;
+        use chroma_types::chroma_proto::ExportCollectionIndexesRequest as Request;
+
+        let mut admin = Client::connect(run_server()).await.unwrap();
+
+        for destination_prefix in ["", "/export", "export/../block", "./export", "export//a"] {
+            let response = admin
+                .export_collection_indexes(Request {
+                    collection_id: COLLECTION_UUID.to_string(),
+                    version_context: Some(RequestVersionContext {
+                        collection_version: 0,
+                        log_position: 0,
+                        max_log_backlog: None,
+                    }),
+                    destination_prefix: destination_prefix.to_string(),
+                })
+                .await;
+            assert_eq!(
+                response.unwrap_err().code(),
+                tonic::Code::InvalidArgument,
+                "{destination_prefix}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    #[cfg(debug_assertions)]
+    async fn validate_merge_collections_request() {
+        use chroma_proto::query_admin_client::QueryAdminClient as Client;
+        use chroma_types::chroma_proto::MergeCollectionsRequest as Request;
+
+        let mut admin = Client::connect(run_server()).await.unwrap();
+
+        for source_collection_ids in [vec![], vec![COLLECTION_UUID.to_string()]] {
+            let response = admin
+                .merge_collections(Request {
+                    source_collection_ids: source_collection_ids.clone(),
+                    target_collection_id: COLLECTION_UUID.to_string(),
+                    duplicate_policy: chroma_proto::MergeDuplicatePolicy::KeepFirst as i32,
+                })
+                .await;
+            assert_eq!(
+                response.unwrap_err().code(),
+                tonic::Code::InvalidArgument,
+                "{source_collection_ids:?}"
+            );
+        }
+    }
+
     #[cfg(debug_assertions)]
     fn to_byte_slice(v: &[f32]) -> &[u8] {
         let raw_ptr = v.as_ptr() as *const u8;
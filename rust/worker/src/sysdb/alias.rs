@@ -0,0 +1,326 @@
+use super::sysdb::{GetCollectionAliasError, SysDb};
+use chroma_cache::{Cache, Weighted};
+use chroma_error::{ChromaError, ErrorCodes};
+use chroma_types::CollectionUuid;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use uuid::Uuid;
+
+/// Resolves the collection references accepted by the query service into collection uuids.
+///
+/// A reference is either the uuid of a collection, or an alias. An alias is a name in a database
+/// of a tenant that sysdb maps to a collection, so that an index can be swapped by re-pointing
+/// the alias to another collection without client changes. Resolved aliases are cached by tenant,
+/// database and alias for `ttl`, after which sysdb is consulted again, and the cache evicts
+/// resolved aliases beyond its capacity.
+#[derive(Clone)]
+pub(crate) struct CollectionAliasResolver {
+    sysdb: Box<SysDb>,
+    ttl: Duration,
+    cache: Arc<dyn Cache<CollectionAliasKey, CachedCollectionAlias>>,
+}
+
+impl Debug for CollectionAliasResolver {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CollectionAliasResolver")
+            .field("sysdb", &self.sysdb)
+            .field("ttl", &self.ttl)
+            .finish()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct CollectionAliasKey {
+    tenant: String,
+    database: String,
+    alias: String,
+}
+
+/// The collection that an alias resolved to, and when
+#[derive(Clone, Debug)]
+pub(crate) struct CachedCollectionAlias {
+    collection_id: CollectionUuid,
+    resolved_at: Instant,
+    key_bytes: usize,
+}
+
+impl Weighted for CachedCollectionAlias {
+    fn weight(&self) -> usize {
+        1
+    }
+
+    fn size_bytes(&self) -> usize {
+        std::mem::size_of::<CollectionAliasKey>() + std::mem::size_of::<Self>() + self.key_bytes
+    }
+}
+
+#[derive(Error, Debug)]
+pub(crate) enum ResolveAliasError {
+    #[error("Error when getting collection alias: {0}")]
+    GetCollectionAlias(#[from] GetCollectionAliasError),
+    #[error("No collection found for alias: {0}")]
+    NotFound(String),
+    #[error("Alias requires a tenant and a database: {0}")]
+    Unscoped(String),
+}
+
+impl ChromaError for ResolveAliasError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            ResolveAliasError::GetCollectionAlias(e) => e.code(),
+            ResolveAliasError::NotFound(_) => ErrorCodes::NotFound,
+            ResolveAliasError::Unscoped(_) => ErrorCodes::InvalidArgument,
+        }
+    }
+}
+
+impl CollectionAliasResolver {
+    pub(crate) fn new(
+        sysdb: Box<SysDb>,
+        ttl: Duration,
+        cache: Box<dyn Cache<CollectionAliasKey, CachedCollectionAlias>>,
+    ) -> Self {
+        CollectionAliasResolver {
+            sysdb,
+            ttl,
+            cache: cache.into(),
+        }
+    }
+
+    /// Resolves a collection reference. An alias is only looked up in the given tenant and
+    /// database, which must not be empty.
+    pub(crate) async fn resolve(
+        &self,
+        tenant: &str,
+        database: &str,
+        collection: &str,
+    ) -> Result<CollectionUuid, ResolveAliasError> {
+        if let Ok(uuid) = Uuid::parse_str(collection) {
+            return Ok(CollectionUuid(uuid));
+        }
+
+        if tenant.is_empty() || database.is_empty() {
+            return Err(ResolveAliasError::Unscoped(collection.to_string()));
+        }
+
+        let key = CollectionAliasKey {
+            tenant: tenant.to_string(),
+            database: database.to_string(),
+            alias: collection.to_string(),
+        };
+        if let Ok(Some(cached)) = self.cache.get(&key).await {
+            if cached.resolved_at.elapsed() < self.ttl {
+                return Ok(cached.collection_id);
+            }
+        }
+
+        let collection_id = self
+            .sysdb
+            .clone()
+            .get_collection_alias(
+                tenant.to_string(),
+                database.to_string(),
+                collection.to_string(),
+            )
+            .await?
+            .ok_or_else(|| ResolveAliasError::NotFound(collection.to_string()))?;
+
+        let key_bytes = tenant.len() + database.len() + collection.len();
+        self.cache
+            .insert(
+                key,
+                CachedCollectionAlias {
+                    collection_id,
+                    resolved_at: Instant::now(),
+                    key_bytes,
+                },
+            )
+            .await;
+        Ok(collection_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sysdb::test_sysdb::TestSysDb;
+    use chroma_cache::{new_non_persistent_cache_for_test, CacheConfig, WeightedCacheConfig};
+
+    fn set_alias(
+        sysdb: &mut TestSysDb,
+        tenant: &str,
+        database: &str,
+        alias: &str,
+    ) -> CollectionUuid {
+        let collection_id = CollectionUuid::new();
+        sysdb.set_collection_alias(
+            tenant.to_string(),
+            database.to_string(),
+            alias.to_string(),
+            collection_id,
+        );
+        collection_id
+    }
+
+    fn resolver(sysdb: &TestSysDb, ttl: Duration) -> CollectionAliasResolver {
+        CollectionAliasResolver::new(
+            Box::new(SysDb::Test(sysdb.clone())),
+            ttl,
+            new_non_persistent_cache_for_test(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_resolve_alias() {
+        let mut sysdb = TestSysDb::new();
+        let blue = set_alias(&mut sysdb, "tenant", "database", "blue");
+        let resolver = resolver(&sysdb, Duration::from_secs(60));
+
+        assert_eq!(
+            resolver
+                .resolve("tenant", "database", &blue.to_string())
+                .await
+                .unwrap(),
+            blue
+        );
+        assert_eq!(
+            resolver
+                .resolve("tenant", "database", "blue")
+                .await
+                .unwrap(),
+            blue
+        );
+        assert!(matches!(
+            resolver.resolve("tenant", "database", "green").await,
+            Err(ResolveAliasError::NotFound(_))
+        ));
+        assert!(matches!(
+            resolver.resolve("", "", "blue").await,
+            Err(ResolveAliasError::Unscoped(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_alias_in_database() {
+        let mut sysdb = TestSysDb::new();
+        let blue = set_alias(&mut sysdb, "tenant", "database", "blue");
+        let other_tenant_blue = set_alias(&mut sysdb, "other_tenant", "database", "blue");
+        let other_database_blue = set_alias(&mut sysdb, "tenant", "other_database", "blue");
+        let resolver = resolver(&sysdb, Duration::from_secs(60));
+
+        assert_eq!(
+            resolver
+                .resolve("tenant", "database", "blue")
+                .await
+                .unwrap(),
+            blue
+        );
+        assert_eq!(
+            resolver
+                .resolve("other_tenant", "database", "blue")
+                .await
+                .unwrap(),
+            other_tenant_blue
+        );
+        assert_eq!(
+            resolver
+                .resolve("tenant", "other_database", "blue")
+                .await
+                .unwrap(),
+            other_database_blue
+        );
+        assert!(matches!(
+            resolver
+                .resolve("other_tenant", "other_database", "blue")
+                .await,
+            Err(ResolveAliasError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_alias_after_repoint() {
+        let mut sysdb = TestSysDb::new();
+        let blue = set_alias(&mut sysdb, "tenant", "database", "live");
+        let cached_resolver = resolver(&sysdb, Duration::from_secs(60));
+        let uncached_resolver = resolver(&sysdb, Duration::ZERO);
+        assert_eq!(
+            cached_resolver
+                .resolve("tenant", "database", "live")
+                .await
+                .unwrap(),
+            blue
+        );
+        assert_eq!(
+            uncached_resolver
+                .resolve("tenant", "database", "live")
+                .await
+                .unwrap(),
+            blue
+        );
+
+        // Re-point the alias to a new collection
+        let green = set_alias(&mut sysdb, "tenant", "database", "live");
+
+        // The cached resolution is used until it expires
+        assert_eq!(
+            cached_resolver
+                .resolve("tenant", "database", "live")
+                .await
+                .unwrap(),
+            blue
+        );
+        assert_eq!(
+            uncached_resolver
+                .resolve("tenant", "database", "live")
+                .await
+                .unwrap(),
+            green
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_alias_evicts_beyond_capacity() {
+        let mut sysdb = TestSysDb::new();
+        let blue = set_alias(&mut sysdb, "tenant", "database", "blue");
+        set_alias(&mut sysdb, "tenant", "database", "green");
+        let entry_bytes = CachedCollectionAlias {
+            collection_id: blue,
+            resolved_at: Instant::now(),
+            key_bytes: "tenantdatabaseblue".len(),
+        }
+        .size_bytes();
+        let cache = chroma_cache::from_config(&CacheConfig::Weighted(WeightedCacheConfig {
+            capacity_bytes: entry_bytes,
+            budget: None,
+            max_partition_share: None,
+        }))
+        .await
+        .unwrap();
+        let resolver = CollectionAliasResolver::new(
+            Box::new(SysDb::Test(sysdb.clone())),
+            Duration::from_secs(60),
+            cache,
+        );
+
+        resolver
+            .resolve("tenant", "database", "blue")
+            .await
+            .unwrap();
+        resolver
+            .resolve("tenant", "database", "green")
+            .await
+            .unwrap();
+
+        // Re-point the alias that was evicted, which is resolved again
+        let new_blue = set_alias(&mut sysdb, "tenant", "database", "blue");
+        assert_eq!(
+            resolver
+                .resolve("tenant", "database", "blue")
+                .await
+                .unwrap(),
+            new_blue
+        );
+    }
+}
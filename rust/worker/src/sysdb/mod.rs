@@ -1,3 +1,4 @@
+pub(crate) mod alias;
 pub(crate) mod config;
 #[allow(clippy::module_inception)]
 pub(crate) mod sysdb;
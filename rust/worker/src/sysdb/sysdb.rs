@@ -46,6 +46,20 @@ impl SysDb {
         }
     }
 
+    /// Returns the collection that the alias in the database of the tenant points to, or `None`
+    /// if there is no such alias
+    pub(crate) async fn get_collection_alias(
+        &mut self,
+        tenant: String,
+        database: String,
+        name: String,
+    ) -> Result<Option<CollectionUuid>, GetCollectionAliasError> {
+        match self {
+            SysDb::Grpc(grpc) => grpc.get_collection_alias(tenant, database, name).await,
+            SysDb::Test(test) => test.get_collection_alias(tenant, database, name).await,
+        }
+    }
+
     pub(crate) async fn get_segments(
         &mut self,
         id: Option<SegmentUuid>,
@@ -69,6 +83,18 @@ impl SysDb {
         }
     }
 
+    /// Returns the id of the KMS key that the objects of the tenant are encrypted with,
+    /// or `None` if the tenant uses the default encryption
+    pub(crate) async fn get_tenant_kms_key_id(
+        &mut self,
+        tenant_id: String,
+    ) -> Result<Option<String>, GetTenantError> {
+        match self {
+            SysDb::Grpc(grpc) => grpc.get_tenant_kms_key_id(tenant_id).await,
+            SysDb::Test(test) => test.get_tenant_kms_key_id(tenant_id).await,
+        }
+    }
+
     pub(crate) async fn flush_compaction(
         &mut self,
         tenant_id: String,
@@ -207,6 +233,27 @@ impl GrpcSysDb {
         }
     }
 
+    async fn get_collection_alias(
+        &mut self,
+        tenant: String,
+        database: String,
+        name: String,
+    ) -> Result<Option<CollectionUuid>, GetCollectionAliasError> {
+        let res = self
+            .client
+            .get_collection_alias(chroma_proto::GetCollectionAliasRequest {
+                tenant,
+                database,
+                name,
+            })
+            .await;
+        match res {
+            Ok(res) => Ok(Some(res.into_inner().collection_id.parse()?)),
+            Err(e) if e.code() == tonic::Code::NotFound => Ok(None),
+            Err(e) => Err(GetCollectionAliasError::FailedToGetCollectionAlias(e)),
+        }
+    }
+
     async fn get_segments(
         &mut self,
         id: Option<SegmentUuid>,
@@ -266,6 +313,23 @@ impl GrpcSysDb {
         }
     }
 
+    async fn get_tenant_kms_key_id(
+        &mut self,
+        tenant_id: String,
+    ) -> Result<Option<String>, GetTenantError> {
+        let res = self
+            .client
+            .get_tenant(chroma_proto::GetTenantRequest { name: tenant_id })
+            .await;
+        match res {
+            Ok(res) => match res.into_inner().tenant {
+                Some(tenant) => Ok(tenant.kms_key_id),
+                None => Err(GetTenantError::TenantNotFound),
+            },
+            Err(e) => Err(GetTenantError::FailedToGetTenant(e)),
+        }
+    }
+
     async fn flush_compaction(
         &mut self,
         tenant_id: String,
@@ -336,6 +400,23 @@ impl ChromaError for GetCollectionsError {
     }
 }
 
+#[derive(Error, Debug)]
+pub(crate) enum GetCollectionAliasError {
+    #[error("Failed to fetch")]
+    FailedToGetCollectionAlias(#[from] tonic::Status),
+    #[error("Failed to convert collection id of alias")]
+    ConversionError(#[from] CollectionConversionError),
+}
+
+impl ChromaError for GetCollectionAliasError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            GetCollectionAliasError::FailedToGetCollectionAlias(_) => ErrorCodes::Internal,
+            GetCollectionAliasError::ConversionError(_) => ErrorCodes::Internal,
+        }
+    }
+}
+
 #[derive(Error, Debug)]
 // TODO: This should use our sysdb errors from the proto definition
 // We will have to do an error uniformization pass at some point
@@ -373,6 +454,23 @@ impl ChromaError for GetLastCompactionTimeError {
     }
 }
 
+#[derive(Error, Debug)]
+pub(crate) enum GetTenantError {
+    #[error("Failed to fetch")]
+    FailedToGetTenant(#[from] tonic::Status),
+    #[error("Tenant not found in sysdb")]
+    TenantNotFound,
+}
+
+impl ChromaError for GetTenantError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            GetTenantError::FailedToGetTenant(_) => ErrorCodes::Internal,
+            GetTenantError::TenantNotFound => ErrorCodes::Internal,
+        }
+    }
+}
+
 #[derive(Error, Debug)]
 pub(crate) enum FlushCompactionError {
     #[error("Failed to flush compaction")]
@@ -8,9 +8,11 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use super::sysdb::FlushCompactionError;
+use super::sysdb::GetCollectionAliasError;
 use super::sysdb::GetCollectionsError;
 use super::sysdb::GetLastCompactionTimeError;
 use super::sysdb::GetSegmentsError;
+use super::sysdb::GetTenantError;
 
 #[derive(Clone, Debug)]
 pub(crate) struct TestSysDb {
@@ -20,8 +22,10 @@ pub(crate) struct TestSysDb {
 #[derive(Debug)]
 struct Inner {
     collections: HashMap<CollectionUuid, Collection>,
+    collection_aliases: HashMap<(String, String, String), CollectionUuid>,
     segments: HashMap<SegmentUuid, Segment>,
     tenant_last_compaction_time: HashMap<String, i64>,
+    tenant_kms_key_ids: HashMap<String, String>,
 }
 
 impl TestSysDb {
@@ -30,8 +34,10 @@ impl TestSysDb {
         TestSysDb {
             inner: Arc::new(Mutex::new(Inner {
                 collections: HashMap::new(),
+                collection_aliases: HashMap::new(),
                 segments: HashMap::new(),
                 tenant_last_compaction_time: HashMap::new(),
+                tenant_kms_key_ids: HashMap::new(),
             })),
         }
     }
@@ -44,6 +50,20 @@ impl TestSysDb {
             .insert(collection.collection_id, collection);
     }
 
+    #[cfg(test)]
+    pub(crate) fn set_collection_alias(
+        &mut self,
+        tenant: String,
+        database: String,
+        name: String,
+        collection_id: CollectionUuid,
+    ) {
+        let mut inner = self.inner.lock();
+        inner
+            .collection_aliases
+            .insert((tenant, database, name), collection_id);
+    }
+
     #[cfg(test)]
     pub(crate) fn add_segment(&mut self, segment: Segment) {
         let mut inner = self.inner.lock();
@@ -62,6 +82,12 @@ impl TestSysDb {
             .insert(tenant, last_compaction_time);
     }
 
+    #[cfg(test)]
+    pub(crate) fn set_tenant_kms_key_id(&mut self, tenant: String, kms_key_id: String) {
+        let mut inner = self.inner.lock();
+        inner.tenant_kms_key_ids.insert(tenant, kms_key_id);
+    }
+
     fn filter_collections(
         collection: &Collection,
         collection_id: Option<CollectionUuid>,
@@ -132,6 +158,19 @@ impl TestSysDb {
         Ok(collections)
     }
 
+    pub(crate) async fn get_collection_alias(
+        &mut self,
+        tenant: String,
+        database: String,
+        name: String,
+    ) -> Result<Option<CollectionUuid>, GetCollectionAliasError> {
+        let inner = self.inner.lock();
+        Ok(inner
+            .collection_aliases
+            .get(&(tenant, database, name))
+            .copied())
+    }
+
     pub(crate) async fn get_segments(
         &mut self,
         id: Option<SegmentUuid>,
@@ -171,6 +210,14 @@ impl TestSysDb {
         Ok(tenants)
     }
 
+    pub(crate) async fn get_tenant_kms_key_id(
+        &mut self,
+        tenant_id: String,
+    ) -> Result<Option<String>, GetTenantError> {
+        let inner = self.inner.lock();
+        Ok(inner.tenant_kms_key_ids.get(&tenant_id).cloned())
+    }
+
     pub(crate) async fn flush_compaction(
         &mut self,
         tenant_id: String,
@@ -206,6 +253,12 @@ impl TestSysDb {
             }
             let mut segment = segment.unwrap().clone();
             segment.file_path = segment_flush_info.file_paths.clone();
+            if let Some(metadata) = &segment_flush_info.metadata {
+                segment
+                    .metadata
+                    .get_or_insert_with(Default::default)
+                    .extend(metadata.clone());
+            }
             inner.segments.insert(segment.id, segment);
         }
 
@@ -0,0 +1,365 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{Debug, Formatter},
+};
+
+use async_trait::async_trait;
+use chroma_blockstore::provider::BlockfileProvider;
+use chroma_index::hnsw_provider::HnswIndexProvider;
+use chroma_storage::Storage;
+use chroma_types::{Collection, CollectionUuid, SegmentScope};
+use futures::StreamExt;
+use serde::Deserialize;
+
+use crate::{
+    assignment::assignment_policy::AssignmentPolicy,
+    execution::{
+        dispatcher::Dispatcher,
+        operators::{fetch_segment::FetchSegmentOperator, warm_up::WarmUpManifest},
+        orchestration::warm_up::WarmUpOrchestrator,
+    },
+    memberlist::Memberlist,
+    sysdb::sysdb::SysDb,
+    system::{Component, ComponentContext, ComponentHandle, Handler, System},
+};
+
+/// The config of the warm up of the collections that the memberlist assigns to a query worker
+/// - `enabled`: Whether the worker warms up the collections assigned to it
+/// - `concurrency`: The number of collections that are warmed up at once
+/// - `max_hot_blocks`: The number of the hottest blocks of a collection that the worker
+///   records in its warm up manifest when the collection is assigned away from it
+#[derive(Debug, Deserialize)]
+pub(crate) struct WarmUpSchedulerConfig {
+    #[serde(default)]
+    pub(crate) enabled: bool,
+    #[serde(default = "WarmUpSchedulerConfig::default_concurrency")]
+    pub(crate) concurrency: usize,
+    #[serde(default = "WarmUpSchedulerConfig::default_max_hot_blocks")]
+    pub(crate) max_hot_blocks: usize,
+}
+
+impl WarmUpSchedulerConfig {
+    fn default_concurrency() -> usize {
+        4
+    }
+
+    fn default_max_hot_blocks() -> usize {
+        1024
+    }
+}
+
+impl Default for WarmUpSchedulerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            concurrency: Self::default_concurrency(),
+            max_hot_blocks: Self::default_max_hot_blocks(),
+        }
+    }
+}
+
+/// Warms up the collections that the memberlist assigns to the worker, so that their first
+/// queries are not slowed down by a cold cache
+///
+/// On each memberlist, the collections are assigned to the members like the frontend routes
+/// their queries. The collections newly assigned to the worker are warmed up by a
+/// `WarmUpOrchestrator`, whose tasks the dispatcher runs in the background, which also loads
+/// the hottest blocks of their previous owner. For the collections assigned away from the
+/// worker, it records its hottest blocks in their warm up manifests for their next owner
+pub(crate) struct WarmUpScheduler {
+    my_member_id: String,
+    sysdb: Box<SysDb>,
+    storage: Storage,
+    blockfile_provider: BlockfileProvider,
+    hnsw_provider: HnswIndexProvider,
+    assignment_policy: Box<dyn AssignmentPolicy>,
+    concurrency: usize,
+    max_hot_blocks: usize,
+    dispatcher: Option<ComponentHandle<Dispatcher>>,
+    system: Option<System>,
+    // The collections that are assigned to the worker
+    assigned: HashSet<CollectionUuid>,
+}
+
+impl Debug for WarmUpScheduler {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WarmUpScheduler")
+            .field("my_member_id", &self.my_member_id)
+            .finish()
+    }
+}
+
+impl WarmUpScheduler {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        config: &WarmUpSchedulerConfig,
+        my_member_id: String,
+        sysdb: Box<SysDb>,
+        storage: Storage,
+        blockfile_provider: BlockfileProvider,
+        hnsw_provider: HnswIndexProvider,
+        assignment_policy: Box<dyn AssignmentPolicy>,
+    ) -> Self {
+        Self {
+            my_member_id,
+            sysdb,
+            storage,
+            blockfile_provider,
+            hnsw_provider,
+            assignment_policy,
+            concurrency: config.concurrency.max(1),
+            max_hot_blocks: config.max_hot_blocks,
+            dispatcher: None,
+            system: None,
+            assigned: HashSet::new(),
+        }
+    }
+
+    pub(crate) fn set_dispatcher(&mut self, dispatcher: ComponentHandle<Dispatcher>) {
+        self.dispatcher = Some(dispatcher);
+    }
+
+    pub(crate) fn set_system(&mut self, system: System) {
+        self.system = Some(system);
+    }
+
+    // The collections that the members assign to the worker
+    async fn assigned_collections(
+        &mut self,
+        members: Memberlist,
+    ) -> Option<HashMap<CollectionUuid, Collection>> {
+        self.assignment_policy.set_members(members);
+        let collections = match self.sysdb.get_collections(None, None, None, None).await {
+            Ok(collections) => collections,
+            Err(e) => {
+                tracing::error!("Error getting collections to warm up: {}", e);
+                return None;
+            }
+        };
+        let mut assigned = HashMap::new();
+        for collection in collections {
+            match self
+                .assignment_policy
+                .assign(collection.collection_id.0.to_string().as_str())
+            {
+                Ok(member) if member == self.my_member_id => {
+                    assigned.insert(collection.collection_id, collection);
+                }
+                Ok(_) => {}
+                Err(e) => tracing::error!("Error assigning collection: {:?}", e),
+            }
+        }
+        Some(assigned)
+    }
+
+    // Records the hottest blocks of the collection for its next owner
+    async fn record_manifest(&self, collection_id: CollectionUuid) {
+        let segments = match self
+            .sysdb
+            .clone()
+            .get_segments(None, None, None, collection_id)
+            .await
+        {
+            Ok(segments) => segments,
+            Err(e) => {
+                tracing::error!("Error getting segments of {}: {}", collection_id, e);
+                return;
+            }
+        };
+        let blockfile_segments = segments.iter().filter(|segment| {
+            matches!(segment.scope, SegmentScope::METADATA | SegmentScope::RECORD)
+        });
+        let recorded = match WarmUpManifest::record(
+            &self.blockfile_provider,
+            blockfile_segments,
+            self.max_hot_blocks,
+        )
+        .await
+        {
+            Ok(manifest) if manifest.block_ids.is_empty() => Ok(()),
+            Ok(manifest) => manifest.write(&self.storage, collection_id).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = recorded {
+            tracing::error!(
+                "Error recording warm up manifest of {}: {}",
+                collection_id,
+                e
+            );
+        }
+    }
+
+    async fn warm_up(&self, collection: &Collection) {
+        let (Some(dispatcher), Some(system)) = (&self.dispatcher, &self.system) else {
+            tracing::error!("Warm up scheduler is not started with a dispatcher and a system");
+            return;
+        };
+        let orchestrator = WarmUpOrchestrator::new(
+            self.blockfile_provider
+                .with_cache_partition(&collection.collection_id.to_string()),
+            dispatcher.clone(),
+            self.hnsw_provider.clone(),
+            // TODO: Load the configuration for this
+            1000,
+            self.storage.clone(),
+            FetchSegmentOperator {
+                sysdb: self.sysdb.clone(),
+                vector_uuid: None,
+                metadata_uuid: None,
+                record_uuid: None,
+                collection_uuid: collection.collection_id,
+                collection_version: collection.version as u32,
+                cache_invalidator: None,
+            },
+        );
+        match orchestrator.run(system.clone()).await {
+            Ok(output) => tracing::info!(
+                "Warmed up collection {} with {} id blocks and {} hot blocks",
+                collection.collection_id,
+                output.num_blocks,
+                output.num_hot_blocks
+            ),
+            Err(e) => tracing::error!(
+                "Error warming up collection {}: {}",
+                collection.collection_id,
+                e
+            ),
+        }
+    }
+
+    // Warms up the collections newly assigned to the worker, after recording the manifests of
+    // the collections assigned away from it
+    async fn reassign(&mut self, members: Memberlist) {
+        if members.is_empty() {
+            return;
+        }
+        let Some(assigned) = self.assigned_collections(members).await else {
+            return;
+        };
+
+        let unassigned = self
+            .assigned
+            .iter()
+            .filter(|collection_id| !assigned.contains_key(collection_id))
+            .copied()
+            .collect::<Vec<_>>();
+        futures::stream::iter(&unassigned)
+            .for_each_concurrent(self.concurrency, |collection_id| {
+                self.record_manifest(*collection_id)
+            })
+            .await;
+
+        let newly_assigned = assigned
+            .iter()
+            .filter(|(collection_id, _)| !self.assigned.contains(collection_id))
+            .map(|(_, collection)| collection)
+            .collect::<Vec<_>>();
+        tracing::info!(
+            "Warming up {} collections newly assigned to {}, and {} collections are assigned away",
+            newly_assigned.len(),
+            self.my_member_id,
+            unassigned.len()
+        );
+        futures::stream::iter(newly_assigned)
+            .for_each_concurrent(self.concurrency, |collection| self.warm_up(collection))
+            .await;
+
+        self.assigned = assigned.into_keys().collect();
+    }
+}
+
+#[async_trait]
+impl Component for WarmUpScheduler {
+    fn get_name() -> &'static str {
+        "Warm up scheduler"
+    }
+
+    fn queue_size(&self) -> usize {
+        16
+    }
+}
+
+#[async_trait]
+impl Handler<Memberlist> for WarmUpScheduler {
+    type Result = ();
+
+    async fn handle(&mut self, message: Memberlist, _ctx: &ComponentContext<WarmUpScheduler>) {
+        self.reassign(message).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chroma_blockstore::test_arrow_blockfile_provider;
+    use chroma_index::test_hnsw_index_provider;
+    use chroma_storage::test_storage;
+    use chroma_types::{
+        Collection, CollectionUuid, Segment, SegmentScope, SegmentType, SegmentUuid,
+    };
+
+    use super::{WarmUpScheduler, WarmUpSchedulerConfig};
+    use crate::{
+        assignment::assignment_policy::RendezvousHashingAssignmentPolicy,
+        execution::dispatcher::Dispatcher,
+        sysdb::{sysdb::SysDb, test_sysdb::TestSysDb},
+        system::System,
+    };
+
+    #[tokio::test]
+    async fn test_reassign() {
+        let collection_id = CollectionUuid::new();
+        let mut sysdb = TestSysDb::new();
+        sysdb.add_collection(Collection {
+            collection_id,
+            name: "collection".to_string(),
+            metadata: None,
+            dimension: Some(3),
+            tenant: "tenant".to_string(),
+            database: "database".to_string(),
+            log_position: -1,
+            version: 0,
+        });
+        for (r#type, scope) in [
+            (SegmentType::BlockfileMetadata, SegmentScope::METADATA),
+            (SegmentType::BlockfileRecord, SegmentScope::RECORD),
+            (SegmentType::HnswDistributed, SegmentScope::VECTOR),
+        ] {
+            sysdb.add_segment(Segment {
+                id: SegmentUuid::new(),
+                r#type,
+                scope,
+                collection: collection_id,
+                metadata: None,
+                file_path: HashMap::new(),
+            });
+        }
+
+        let my_member_id = "worker-1".to_string();
+        let mut scheduler = WarmUpScheduler::new(
+            &WarmUpSchedulerConfig::default(),
+            my_member_id.clone(),
+            Box::new(SysDb::Test(sysdb)),
+            test_storage(),
+            test_arrow_blockfile_provider(2 << 22),
+            test_hnsw_index_provider(),
+            Box::new(RendezvousHashingAssignmentPolicy::new()),
+        );
+        let system = System::new();
+        let dispatcher_handle = system.start_component(Dispatcher::new(2, 2, 10, 10));
+        scheduler.set_dispatcher(dispatcher_handle);
+        scheduler.set_system(system);
+
+        // The only member is assigned every collection
+        scheduler.reassign(vec![my_member_id.clone()]).await;
+        assert!(scheduler.assigned.contains(&collection_id));
+
+        // An empty memberlist does not unassign the collections
+        scheduler.reassign(vec![]).await;
+        assert!(scheduler.assigned.contains(&collection_id));
+
+        scheduler.reassign(vec!["worker-2".to_string()]).await;
+        assert!(scheduler.assigned.is_empty());
+    }
+}